@@ -66,11 +66,11 @@ fn schema_to_field(
                 },
                 avro_schema::schema::LongLogical::TimestampMillis => ArrowDataType::Timestamp(
                     TimeUnit::Millisecond,
-                    Some(PlSmallStr::from_static("00:00")),
+                    Some(PlSmallStr::from_static("UTC")),
                 ),
                 avro_schema::schema::LongLogical::TimestampMicros => ArrowDataType::Timestamp(
                     TimeUnit::Microsecond,
-                    Some(PlSmallStr::from_static("00:00")),
+                    Some(PlSmallStr::from_static("UTC")),
                 ),
                 avro_schema::schema::LongLogical::LocalTimestampMillis => {
                     ArrowDataType::Timestamp(TimeUnit::Millisecond, None)
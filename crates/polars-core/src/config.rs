@@ -46,8 +46,68 @@ pub fn get_rg_prefetch_size() -> usize {
         .unwrap_or_else(|_| std::cmp::max(get_file_prefetch_size(), 128))
 }
 
+/// The number of bytes of row groups that may be downloaded ahead of the decoder.
+pub fn get_rg_prefetch_memory_limit() -> usize {
+    std::env::var("POLARS_ROW_GROUP_PREFETCH_MEMORY_LIMIT")
+        .map(|s| s.parse::<usize>().expect("integer"))
+        .unwrap_or(1 << 30)
+}
+
+/// The memory budget of a streaming query in bytes.
+///
+/// It is set with the `POLARS_STREAMING_MEMORY_LIMIT` env var to a number of bytes, optionally
+/// followed by a `KB`, `MB`, `GB` or `TB` unit (powers of 1024), e.g. `8GB`.
+pub fn get_streaming_memory_limit() -> Option<usize> {
+    let limit = std::env::var("POLARS_STREAMING_MEMORY_LIMIT").ok()?;
+    let bytes = parse_memory_size(&limit)
+        .unwrap_or_else(|| panic!("could not parse 'POLARS_STREAMING_MEMORY_LIMIT': {limit}"));
+    Some(bytes)
+}
+
+fn parse_memory_size(size: &str) -> Option<usize> {
+    let size = size.trim().to_ascii_uppercase();
+    let split = size
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(size.len());
+    let (value, unit) = size.split_at(split);
+    let exponent = match unit.trim() {
+        "" | "B" => 0,
+        "K" | "KB" | "KIB" => 1,
+        "M" | "MB" | "MIB" => 2,
+        "G" | "GB" | "GIB" => 3,
+        "T" | "TB" | "TIB" => 4,
+        _ => return None,
+    };
+    let bytes = value.parse::<f64>().ok()? * 1024f64.powi(exponent);
+    // the cast would saturate
+    (bytes < usize::MAX as f64).then_some(bytes as usize)
+}
+
 pub fn force_async() -> bool {
     std::env::var("POLARS_FORCE_ASYNC")
         .map(|value| value == "1")
         .unwrap_or_default()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory_size() {
+        assert_eq!(parse_memory_size("1000"), Some(1000));
+        assert_eq!(parse_memory_size("512B"), Some(512));
+        assert_eq!(parse_memory_size("2k"), Some(2 << 10));
+        assert_eq!(parse_memory_size(" 8 GB "), Some(8 << 30));
+        assert_eq!(parse_memory_size("1.5MiB"), Some(3 << 19));
+        assert_eq!(parse_memory_size("1TB"), Some(1 << 40));
+
+        for size in ["", "GB", "8 PB", "-1", "1.2.3", "eight"] {
+            assert_eq!(parse_memory_size(size), None, "{size}");
+        }
+
+        // larger than the address space
+        assert_eq!(parse_memory_size("100000000000TB"), None);
+        assert_eq!(parse_memory_size(&format!("{}0", usize::MAX)), None);
+    }
+}
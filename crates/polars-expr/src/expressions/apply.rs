@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use polars_core::prelude::*;
 use polars_core::POOL;
-#[cfg(feature = "parquet")]
+#[cfg(any(feature = "parquet", feature = "orc"))]
 use polars_io::predicates::{BatchStats, StatsEvaluator};
 #[cfg(feature = "is_between")]
 use polars_ops::prelude::ClosedInterval;
@@ -407,7 +407,7 @@ impl PhysicalExpr for ApplyExpr {
     fn to_field(&self, input_schema: &Schema) -> PolarsResult<Field> {
         self.expr.to_field(input_schema, Context::Default)
     }
-    #[cfg(feature = "parquet")]
+    #[cfg(any(feature = "parquet", feature = "orc"))]
     fn as_stats_evaluator(&self) -> Option<&dyn polars_io::predicates::StatsEvaluator> {
         let function = match &self.expr {
             Expr::Function { function, .. } => function,
@@ -495,7 +495,7 @@ fn apply_multiple_elementwise<'a>(
     }
 }
 
-#[cfg(feature = "parquet")]
+#[cfg(any(feature = "parquet", feature = "orc"))]
 impl StatsEvaluator for ApplyExpr {
     fn should_read(&self, stats: &BatchStats) -> PolarsResult<bool> {
         let read = self.should_read_impl(stats)?;
@@ -511,7 +511,7 @@ impl StatsEvaluator for ApplyExpr {
     }
 }
 
-#[cfg(feature = "parquet")]
+#[cfg(any(feature = "parquet", feature = "orc"))]
 impl ApplyExpr {
     fn should_read_impl(&self, stats: &BatchStats) -> PolarsResult<bool> {
         let (function, input) = match &self.expr {
@@ -620,7 +620,7 @@ impl PhysicalIoExpr for PhysicalIoHelper {
         Some(expr_to_leaf_column_names(self.expr.as_expression()?))
     }
 
-    #[cfg(feature = "parquet")]
+    #[cfg(any(feature = "parquet", feature = "orc"))]
     fn as_stats_evaluator(&self) -> Option<&dyn polars_io::predicates::StatsEvaluator> {
         self.expr.as_stats_evaluator()
     }
@@ -129,6 +129,19 @@ impl ExecutionState {
         }
     }
 
+    /// Store the start and the end of a node that was timed elsewhere, e.g. by the streaming
+    /// engine, if the nodes are timed.
+    pub fn record_interval(
+        &self,
+        start: std::time::Instant,
+        end: std::time::Instant,
+        name: Cow<'static, str>,
+    ) {
+        if let Some(timer) = &self.node_timer {
+            timer.store(start, end, name.into_owned());
+        }
+    }
+
     /// Partially clones and partially clears state
     /// This should be used when splitting a node, like a join or union
     pub fn split(&self) -> Self {
@@ -0,0 +1,457 @@
+//! Reading of Avro messages that are encoded one by one instead of in an object container file,
+//! as they are sent over message queues. Every message refers to the schema it was written with,
+//! which is resolved from an [`AvroSchemaStore`].
+use arrow::io::avro::avro_schema::file::Block;
+use arrow::io::avro::avro_schema::schema::{Record, Schema as AvroSchema};
+use arrow::io::avro::read;
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+
+#[cfg(feature = "avro_schema_registry")]
+use super::SchemaRegistryClient;
+use crate::prelude::*;
+
+/// How the writer schema of a message is referred to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AvroMessageFormat {
+    /// The [single-object encoding] of Avro: the datum is prefixed with `C3 01` and the 8-byte
+    /// little endian CRC-64-AVRO fingerprint of the writer schema.
+    ///
+    /// [single-object encoding]: https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding
+    SingleObject,
+    /// The wire format of the Confluent schema registry: the datum is prefixed with a zero byte
+    /// and the 4-byte big endian id of the writer schema in the registry.
+    Confluent,
+}
+
+struct WriterSchema {
+    record: Record,
+    arrow_schema: ArrowSchema,
+}
+
+impl WriterSchema {
+    fn try_new(schema: &str) -> PolarsResult<(Self, u64)> {
+        let schema: AvroSchema = serde_json::from_str(schema).map_err(to_compute_err)?;
+        let fingerprint = rabin_fingerprint(parsing_canonical_form(&schema).as_bytes());
+        let AvroSchema::Record(record) = schema else {
+            polars_bail!(ComputeError: "the writer schema of Avro messages must be a record, got {:?}", schema);
+        };
+        let arrow_schema = read::infer_schema(&record)?;
+        Ok((
+            Self {
+                record,
+                arrow_schema,
+            },
+            fingerprint,
+        ))
+    }
+}
+
+/// The writer schemas that messages can refer to.
+///
+/// Schemas of single-object encoded messages are registered up front. Schemas of messages in the
+/// Confluent wire format are either registered up front, or fetched from a schema registry the
+/// first time they are referred to.
+#[derive(Clone, Default)]
+pub struct AvroSchemaStore {
+    by_fingerprint: PlHashMap<u64, Arc<WriterSchema>>,
+    by_id: PlHashMap<u32, Arc<WriterSchema>>,
+    #[cfg(feature = "avro_schema_registry")]
+    registry: Option<SchemaRegistryClient>,
+}
+
+impl AvroSchemaStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve the schemas of messages in the Confluent wire format that aren't registered from
+    /// a schema registry.
+    #[cfg(feature = "avro_schema_registry")]
+    pub fn with_registry(mut self, registry: SchemaRegistryClient) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Register a JSON schema definition for single-object encoded messages. Returns the
+    /// fingerprint of the schema.
+    pub fn register(&mut self, schema: &str) -> PolarsResult<u64> {
+        let (schema, fingerprint) = WriterSchema::try_new(schema)?;
+        self.by_fingerprint.insert(fingerprint, Arc::new(schema));
+        Ok(fingerprint)
+    }
+
+    /// Register a JSON schema definition under its id in the schema registry.
+    pub fn register_with_id(&mut self, id: u32, schema: &str) -> PolarsResult<()> {
+        let (schema, _) = WriterSchema::try_new(schema)?;
+        self.by_id.insert(id, Arc::new(schema));
+        Ok(())
+    }
+
+    fn get_by_fingerprint(&self, fingerprint: u64) -> PolarsResult<Arc<WriterSchema>> {
+        self.by_fingerprint.get(&fingerprint).cloned().ok_or_else(|| {
+            polars_err!(
+                ComputeError: "Avro message refers to an unknown schema with fingerprint {:#018x}",
+                fingerprint
+            )
+        })
+    }
+
+    fn get_by_id(&mut self, id: u32) -> PolarsResult<Arc<WriterSchema>> {
+        if let Some(schema) = self.by_id.get(&id) {
+            return Ok(schema.clone());
+        }
+
+        #[cfg(feature = "avro_schema_registry")]
+        if let Some(registry) = &self.registry {
+            let (schema, _) = WriterSchema::try_new(&registry.fetch_schema(id)?)?;
+            let schema = Arc::new(schema);
+            self.by_id.insert(id, schema.clone());
+            return Ok(schema);
+        }
+
+        polars_bail!(ComputeError: "Avro message refers to an unknown schema with id {}", id)
+    }
+}
+
+/// Read Avro messages that refer to their writer schema into a [`DataFrame`].
+///
+/// Consecutive messages with the same writer schema are decoded together. Messages that were
+/// written with different schemas can only be read together if those schemas map to the same
+/// columns.
+///
+/// # Example
+/// ```
+/// use polars_core::prelude::*;
+/// use polars_io::avro::{AvroMessageFormat, AvroMessageReader, AvroSchemaStore};
+///
+/// fn example(messages: &[Vec<u8>]) -> PolarsResult<DataFrame> {
+///     let mut store = AvroSchemaStore::new();
+///     store.register(r#"{"type": "record", "name": "a", "fields": [{"name": "x", "type": "long"}]}"#)?;
+///
+///     AvroMessageReader::new(&mut store, AvroMessageFormat::SingleObject)
+///         .read(messages.iter().map(|m| m.as_slice()))
+/// }
+/// ```
+#[must_use]
+pub struct AvroMessageReader<'a> {
+    store: &'a mut AvroSchemaStore,
+    format: AvroMessageFormat,
+    columns: Option<Vec<String>>,
+    rechunk: bool,
+}
+
+impl<'a> AvroMessageReader<'a> {
+    pub fn new(store: &'a mut AvroSchemaStore, format: AvroMessageFormat) -> Self {
+        Self {
+            store,
+            format,
+            columns: None,
+            rechunk: true,
+        }
+    }
+
+    /// Columns to select/ project
+    pub fn with_columns(mut self, columns: Option<Vec<String>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Make sure that all columns are contiguous in memory by
+    /// aggregating the chunks into a single array.
+    pub fn set_rechunk(mut self, rechunk: bool) -> Self {
+        self.rechunk = rechunk;
+        self
+    }
+
+    pub fn read<'b, I>(mut self, messages: I) -> PolarsResult<DataFrame>
+    where
+        I: IntoIterator<Item = &'b [u8]>,
+    {
+        let mut dfs = vec![];
+        let mut current: Option<(Arc<WriterSchema>, Block)> = None;
+
+        for message in messages {
+            let (schema, datum) = self.split_message(message)?;
+            if let Some((current_schema, block)) = &mut current {
+                if Arc::ptr_eq(current_schema, &schema) {
+                    block.number_of_rows += 1;
+                    block.data.extend_from_slice(datum);
+                    continue;
+                }
+            }
+            if let Some((schema, block)) = current.take() {
+                dfs.push(self.deserialize(&schema, &block)?);
+            }
+            current = Some((schema, Block::new(1, datum.to_vec())));
+        }
+        if let Some((schema, block)) = current.take() {
+            dfs.push(self.deserialize(&schema, &block)?);
+        }
+        polars_ensure!(!dfs.is_empty(), NoData: "no Avro messages to read");
+
+        let schema = dfs[0].schema();
+        for df in &dfs[1..] {
+            polars_ensure!(
+                df.schema() == schema,
+                SchemaMismatch: "Avro messages were written with incompatible schemas: {:?} and {:?}",
+                schema, df.schema()
+            );
+        }
+        let mut df = accumulate_dataframes_vertical_unchecked(dfs);
+        if self.rechunk {
+            df.as_single_chunk_par();
+        }
+        Ok(df)
+    }
+
+    /// Split a message in its writer schema and its datum.
+    fn split_message<'b>(
+        &mut self,
+        message: &'b [u8],
+    ) -> PolarsResult<(Arc<WriterSchema>, &'b [u8])> {
+        match self.format {
+            AvroMessageFormat::SingleObject => {
+                polars_ensure!(
+                    message.len() >= 10 && message.starts_with(&[0xC3, 0x01]),
+                    ComputeError: "Avro message doesn't start with the header of the single-object encoding"
+                );
+                let fingerprint = u64::from_le_bytes(message[2..10].try_into().unwrap());
+                Ok((self.store.get_by_fingerprint(fingerprint)?, &message[10..]))
+            },
+            AvroMessageFormat::Confluent => {
+                polars_ensure!(
+                    message.len() >= 5 && message[0] == 0,
+                    ComputeError: "Avro message doesn't start with the header of the Confluent wire format"
+                );
+                let id = u32::from_be_bytes(message[1..5].try_into().unwrap());
+                Ok((self.store.get_by_id(id)?, &message[5..]))
+            },
+        }
+    }
+
+    fn deserialize(&self, schema: &WriterSchema, block: &Block) -> PolarsResult<DataFrame> {
+        let arrow_schema = &schema.arrow_schema;
+        let (projection, projected_schema) = match &self.columns {
+            Some(columns) => {
+                let mut indices = columns_to_projection(columns, arrow_schema)?;
+                indices.sort_unstable();
+                let mut projection = vec![false; arrow_schema.len()];
+                for &i in &indices {
+                    projection[i] = true;
+                }
+                (projection, apply_projection(arrow_schema, &indices))
+            },
+            None => (vec![true; arrow_schema.len()], arrow_schema.clone()),
+        };
+
+        let batch = read::deserialize(block, arrow_schema, &schema.record.fields, &projection)?;
+        DataFrame::try_from((batch, &projected_schema))
+    }
+}
+
+/// The [Parsing Canonical Form] of a schema, which is the input of its fingerprint.
+///
+/// [Parsing Canonical Form]: https://avro.apache.org/docs/1.11.1/specification/#parsing-canonical-form-for-schemas
+fn parsing_canonical_form(schema: &AvroSchema) -> String {
+    let mut out = String::new();
+    write_canonical_form(schema, None, &mut out);
+    out
+}
+
+fn write_canonical_form(schema: &AvroSchema, namespace: Option<&str>, out: &mut String) {
+    // Logical types, docs, aliases, defaults and orders aren't part of the canonical form.
+    match schema {
+        AvroSchema::Null => out.push_str("\"null\""),
+        AvroSchema::Boolean => out.push_str("\"boolean\""),
+        AvroSchema::Int(_) => out.push_str("\"int\""),
+        AvroSchema::Long(_) => out.push_str("\"long\""),
+        AvroSchema::Float => out.push_str("\"float\""),
+        AvroSchema::Double => out.push_str("\"double\""),
+        AvroSchema::Bytes(_) => out.push_str("\"bytes\""),
+        AvroSchema::String(_) => out.push_str("\"string\""),
+        AvroSchema::Array(items) => {
+            out.push_str("{\"type\":\"array\",\"items\":");
+            write_canonical_form(items, namespace, out);
+            out.push('}');
+        },
+        AvroSchema::Map(values) => {
+            out.push_str("{\"type\":\"map\",\"values\":");
+            write_canonical_form(values, namespace, out);
+            out.push('}');
+        },
+        AvroSchema::Union(schemas) => {
+            out.push('[');
+            for (i, schema) in schemas.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_form(schema, namespace, out);
+            }
+            out.push(']');
+        },
+        AvroSchema::Record(record) => {
+            let name = full_name(&record.name, record.namespace.as_deref(), namespace);
+            let namespace = name.rsplit_once('.').map(|(namespace, _)| namespace);
+            out.push_str("{\"name\":");
+            write_json_string(&name, out);
+            out.push_str(",\"type\":\"record\",\"fields\":[");
+            for (i, field) in record.fields.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str("{\"name\":");
+                write_json_string(&field.name, out);
+                out.push_str(",\"type\":");
+                write_canonical_form(&field.schema, namespace, out);
+                out.push('}');
+            }
+            out.push_str("]}");
+        },
+        AvroSchema::Enum(e) => {
+            out.push_str("{\"name\":");
+            write_json_string(&full_name(&e.name, e.namespace.as_deref(), namespace), out);
+            out.push_str(",\"type\":\"enum\",\"symbols\":[");
+            for (i, symbol) in e.symbols.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_string(symbol, out);
+            }
+            out.push_str("]}");
+        },
+        AvroSchema::Fixed(fixed) => {
+            out.push_str("{\"name\":");
+            write_json_string(
+                &full_name(&fixed.name, fixed.namespace.as_deref(), namespace),
+                out,
+            );
+            out.push_str(",\"type\":\"fixed\",\"size\":");
+            out.push_str(&fixed.size.to_string());
+            out.push('}');
+        },
+    }
+}
+
+/// Names without a dot are qualified by their own namespace, or else by the namespace of the
+/// enclosing named type.
+fn full_name(name: &str, namespace: Option<&str>, enclosing: Option<&str>) -> String {
+    if name.contains('.') {
+        return name.to_string();
+    }
+    match namespace
+        .or(enclosing)
+        .filter(|namespace| !namespace.is_empty())
+    {
+        Some(namespace) => format!("{namespace}.{name}"),
+        None => name.to_string(),
+    }
+}
+
+fn write_json_string(s: &str, out: &mut String) {
+    out.push_str(&serde_json::to_string(s).unwrap());
+}
+
+const EMPTY_FINGERPRINT: u64 = 0xc15d213aa4d7a795;
+
+const fn fingerprint_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut fp = i as u64;
+        let mut j = 0;
+        while j < 8 {
+            fp = (fp >> 1) ^ (EMPTY_FINGERPRINT & (fp & 1).wrapping_neg());
+            j += 1;
+        }
+        table[i] = fp;
+        i += 1;
+    }
+    table
+}
+
+static FINGERPRINT_TABLE: [u64; 256] = fingerprint_table();
+
+/// The 64-bit Rabin fingerprint (CRC-64-AVRO) that identifies single-object encoded schemas.
+fn rabin_fingerprint(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(EMPTY_FINGERPRINT, |fp, &b| {
+        (fp >> 8) ^ FINGERPRINT_TABLE[((fp ^ b as u64) & 0xFF) as usize]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing_canonical_form() {
+        let schema = r#"{
+            "type": "record",
+            "name": "test",
+            "namespace": "org.pola",
+            "doc": "dropped",
+            "fields": [
+                {"name": "a", "type": {"type": "long", "logicalType": "timestamp-micros"}},
+                {"name": "b", "type": ["null", "string"], "default": null},
+                {"name": "c", "type": {"type": "array", "items": "int"}},
+                {"name": "d", "type": {"type": "record", "name": "inner", "fields": [
+                    {"name": "e", "type": {"type": "fixed", "name": "f", "size": 4}}
+                ]}}
+            ]
+        }"#;
+        let schema: AvroSchema = serde_json::from_str(schema).unwrap();
+        assert_eq!(
+            parsing_canonical_form(&schema),
+            concat!(
+                r#"{"name":"org.pola.test","type":"record","fields":["#,
+                r#"{"name":"a","type":"long"},"#,
+                r#"{"name":"b","type":["null","string"]},"#,
+                r#"{"name":"c","type":{"type":"array","items":"int"}},"#,
+                r#"{"name":"d","type":{"name":"org.pola.inner","type":"record","fields":["#,
+                r#"{"name":"e","type":{"name":"org.pola.f","type":"fixed","size":4}}]}}]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_read_messages() {
+        let schema =
+            r#"{"type": "record", "name": "a", "fields": [{"name": "x", "type": "long"}]}"#;
+        let mut store = AvroSchemaStore::new();
+        let fingerprint = store.register(schema).unwrap();
+        store.register_with_id(7, schema).unwrap();
+
+        // The long 1 is zigzag encoded as 2.
+        let mut single_object = vec![0xC3, 0x01];
+        single_object.extend_from_slice(&fingerprint.to_le_bytes());
+        single_object.push(2);
+        let confluent = [0, 0, 0, 0, 7, 4];
+
+        let df = AvroMessageReader::new(&mut store, AvroMessageFormat::SingleObject)
+            .read([single_object.as_slice(), single_object.as_slice()])
+            .unwrap();
+        assert_eq!(df.column("x").unwrap().i64().unwrap().get(1), Some(1));
+
+        let df = AvroMessageReader::new(&mut store, AvroMessageFormat::Confluent)
+            .read([confluent.as_slice()])
+            .unwrap();
+        assert_eq!(df.column("x").unwrap().i64().unwrap().get(0), Some(2));
+
+        let unknown = [0, 0, 0, 0, 8, 4];
+        assert!(
+            AvroMessageReader::new(&mut store, AvroMessageFormat::Confluent)
+                .read([unknown.as_slice()])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rabin_fingerprint() {
+        assert_eq!(rabin_fingerprint(b""), EMPTY_FINGERPRINT);
+        assert_ne!(
+            rabin_fingerprint(b"\"int\""),
+            rabin_fingerprint(b"\"long\"")
+        );
+    }
+}
@@ -1,5 +1,11 @@
+mod message;
 mod read;
+#[cfg(feature = "avro_schema_registry")]
+mod registry;
 mod write;
 
+pub use message::*;
 pub use read::*;
+#[cfg(feature = "avro_schema_registry")]
+pub use registry::*;
 pub use write::*;
@@ -1,12 +1,22 @@
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
 
+use arrow::io::avro::avro_schema::file::FileMetadata;
 use arrow::io::avro::{self, read};
 use arrow::record_batch::RecordBatch;
 use polars_core::error::to_compute_err;
 use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+use crate::predicates::PhysicalIoExpr;
 use crate::prelude::*;
 use crate::shared::{finish_reader, ArrowReader};
+use crate::RowIndex;
+
+#[derive(Clone, Debug, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AvroScanOptions;
 
 /// Read [Apache Avro] format into a [`DataFrame`]
 ///
@@ -33,6 +43,8 @@ pub struct AvroReader<R> {
     n_rows: Option<usize>,
     columns: Option<Vec<String>>,
     projection: Option<Vec<usize>>,
+    predicate: Option<Arc<dyn PhysicalIoExpr>>,
+    row_index: Option<RowIndex>,
 }
 
 impl<R: Read + Seek> AvroReader<R> {
@@ -68,6 +80,178 @@ impl<R: Read + Seek> AvroReader<R> {
         self.columns = columns;
         self
     }
+
+    /// Add a row index column.
+    pub fn with_row_index(mut self, row_index: Option<RowIndex>) -> Self {
+        self.row_index = row_index;
+        self
+    }
+
+    /// Filter the rows of every block with a predicate.
+    pub fn with_predicate(mut self, predicate: Option<Arc<dyn PhysicalIoExpr>>) -> Self {
+        self.predicate = predicate;
+        self
+    }
+
+    /// Read the file header and determine which fields of the records are read.
+    ///
+    /// Fields are always read in the order of the file schema.
+    fn prepare(
+        &mut self,
+    ) -> PolarsResult<(FileMetadata, ArrowSchema, Option<Vec<bool>>, ArrowSchema)> {
+        let metadata =
+            avro::avro_schema::read::read_metadata(&mut self.reader).map_err(to_compute_err)?;
+        let schema = read::infer_schema(&metadata.record)?;
+
+        if let Some(columns) = &self.columns {
+            self.projection = Some(columns_to_projection(columns, &schema)?);
+        }
+
+        let (projection, projected_schema) = if let Some(mut projection) = self.projection.take() {
+            projection.sort_unstable();
+            projection.dedup();
+            let mut prj = vec![false; schema.len()];
+            for &index in projection.iter() {
+                polars_ensure!(
+                    index < schema.len(),
+                    OutOfBounds: "projection index {} is out of bounds for {} Avro fields",
+                    index, schema.len()
+                );
+                prj[index] = true;
+            }
+            (Some(prj), apply_projection(&schema, &projection))
+        } else {
+            (None, schema.clone())
+        };
+        Ok((metadata, schema, projection, projected_schema))
+    }
+
+    /// Read the file in batches of at least `chunk_size` rows. A batch consists of whole blocks
+    /// of the file, unless it is cut off at the row limit.
+    ///
+    /// A predicate set with [`AvroReader::with_predicate`] filters every batch after the row
+    /// index is added, so the row index and the row limit count the rows of the file and a
+    /// batch may be empty.
+    pub fn batched(mut self, chunk_size: usize) -> PolarsResult<BatchedAvroReader<R>> {
+        let (metadata, schema, projection, projected_schema) = self.prepare()?;
+        let reader = avro::read::Reader::new(self.reader, metadata, schema, projection);
+        Ok(BatchedAvroReader {
+            reader,
+            schema: projected_schema,
+            chunk_size: chunk_size.max(1),
+            n_rows_left: self.n_rows.unwrap_or(usize::MAX),
+            row_index: self.row_index,
+            predicate: self.predicate,
+            rows_read: 0,
+        })
+    }
+}
+
+/// Reads an Avro file in batches of whole blocks. Created by [`AvroReader::batched`].
+pub struct BatchedAvroReader<R: Read> {
+    reader: read::Reader<R>,
+    schema: ArrowSchema,
+    chunk_size: usize,
+    n_rows_left: usize,
+    row_index: Option<RowIndex>,
+    predicate: Option<Arc<dyn PhysicalIoExpr>>,
+    rows_read: IdxSize,
+}
+
+impl<R: Read> BatchedAvroReader<R> {
+    /// Schema of the batches, without the row index.
+    pub fn schema(&self) -> &ArrowSchema {
+        &self.schema
+    }
+
+    pub fn next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        if self.n_rows_left == 0 {
+            return Ok(None);
+        }
+
+        let mut dfs = vec![];
+        let mut height = 0;
+        while height < self.chunk_size && height < self.n_rows_left {
+            let Some(batch) = self.reader.next() else {
+                break;
+            };
+            let df = DataFrame::try_from((batch?, &self.schema))?;
+            height += df.height();
+            dfs.push(df);
+        }
+        if dfs.is_empty() {
+            return Ok(None);
+        }
+
+        let mut df = accumulate_dataframes_vertical_unchecked(dfs);
+        if df.height() > self.n_rows_left {
+            df = df.slice(0, self.n_rows_left);
+        }
+        self.n_rows_left -= df.height();
+
+        if let Some(row_index) = &self.row_index {
+            df.with_row_index_mut(
+                row_index.name.clone(),
+                Some(row_index.offset + self.rows_read),
+            );
+        }
+        self.rows_read += df.height() as IdxSize;
+
+        if let Some(predicate) = &self.predicate {
+            let s = predicate.evaluate_io(&df)?;
+            let mask = s.bool().expect("filter predicates was not of type boolean");
+            df = df.filter(mask)?;
+        }
+        Ok(Some(df))
+    }
+}
+
+/// Count the rows of an Avro file from the headers of its blocks, without decompressing or
+/// decoding them.
+pub fn count_rows<R: Read + Seek>(mut reader: R) -> PolarsResult<usize> {
+    avro::avro_schema::read::read_metadata(&mut reader).map_err(to_compute_err)?;
+
+    let mut count = 0;
+    // Every block starts with its number of rows and its size in bytes, and ends with the
+    // 16-byte sync marker of the file.
+    while let Some(n_rows) = read_long(&mut reader)? {
+        let size = read_long(&mut reader)?.ok_or_else(
+            || polars_err!(ComputeError: "out-of-spec Avro file: truncated block header"),
+        )?;
+        polars_ensure!(
+            n_rows >= 0 && size >= 0,
+            ComputeError: "out-of-spec Avro file: negative block header"
+        );
+        count += n_rows as usize;
+        reader.seek(SeekFrom::Current(size + 16))?;
+    }
+    Ok(count)
+}
+
+/// Read a zigzag encoded long, or `None` at the end of the reader.
+fn read_long<R: Read>(reader: &mut R) -> PolarsResult<Option<i64>> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    let mut byte = [0u8];
+    loop {
+        if reader.read(&mut byte)? == 0 {
+            polars_ensure!(
+                shift == 0,
+                ComputeError: "out-of-spec Avro file: truncated variable-length integer"
+            );
+            return Ok(None);
+        }
+        polars_ensure!(
+            shift < 64,
+            ComputeError: "out-of-spec Avro file: variable-length integer is too long"
+        );
+        value |= ((byte[0] & 0x7F) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(Some((value >> 1) as i64 ^ -((value & 1) as i64)))
 }
 
 impl<R> ArrowReader for read::Reader<R>
@@ -90,6 +274,8 @@ where
             n_rows: None,
             columns: None,
             projection: None,
+            predicate: None,
+            row_index: None,
         }
     }
 
@@ -100,23 +286,7 @@ where
 
     fn finish(mut self) -> PolarsResult<DataFrame> {
         let rechunk = self.rechunk;
-        let metadata =
-            avro::avro_schema::read::read_metadata(&mut self.reader).map_err(to_compute_err)?;
-        let schema = read::infer_schema(&metadata.record)?;
-
-        if let Some(columns) = &self.columns {
-            self.projection = Some(columns_to_projection(columns, &schema)?);
-        }
-
-        let (projection, projected_schema) = if let Some(projection) = self.projection {
-            let mut prj = vec![false; schema.len()];
-            for &index in projection.iter() {
-                prj[index] = true;
-            }
-            (Some(prj), apply_projection(&schema, &projection))
-        } else {
-            (None, schema.clone())
-        };
+        let (metadata, schema, projection, projected_schema) = self.prepare()?;
 
         let avro_reader = avro::read::Reader::new(&mut self.reader, metadata, schema, projection);
 
@@ -124,9 +294,75 @@ where
             avro_reader,
             rechunk,
             self.n_rows,
-            None,
+            self.predicate,
             &projected_schema,
-            None,
+            self.row_index,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_long() {
+        // Zigzag encoding maps 0, -1, 1, -64 and 64 to 0, 1, 2, 127 and 128.
+        let bytes: &[u8] = &[0x00, 0x01, 0x02, 0x7F, 0x80, 0x01];
+        let mut reader = bytes;
+        let mut values = vec![];
+        while let Some(v) = read_long(&mut reader).unwrap() {
+            values.push(v);
+        }
+        assert_eq!(values, [0, -1, 1, -64, 64]);
+
+        let truncated: &[u8] = &[0x80];
+        assert!(read_long(&mut &truncated[..]).is_err());
+    }
+
+    /// Keeps the rows of which `a` is even.
+    struct EvenA;
+
+    impl PhysicalIoExpr for EvenA {
+        fn evaluate_io(&self, df: &DataFrame) -> PolarsResult<Series> {
+            let a = df.column("a")?.i64()?;
+            Ok((a % 2).equal(0).into_series())
+        }
+
+        fn live_variables(&self) -> Option<Vec<PlSmallStr>> {
+            Some(vec!["a".into()])
+        }
+    }
+
+    #[test]
+    fn test_batched_predicate() {
+        let mut df = df!["a" => (0..10i64).collect::<Vec<_>>()].unwrap();
+        let mut buf = vec![];
+        crate::avro::AvroWriter::new(&mut buf)
+            .finish(&mut df)
+            .unwrap();
+
+        let mut reader = AvroReader::new(std::io::Cursor::new(buf))
+            .with_n_rows(Some(7))
+            .with_row_index(Some(RowIndex {
+                name: "idx".into(),
+                offset: 0,
+            }))
+            .with_predicate(Some(Arc::new(EvenA)))
+            .batched(1)
+            .unwrap();
+        let mut dfs = vec![];
+        while let Some(df) = reader.next_batch().unwrap() {
+            dfs.push(df);
+        }
+        let out = accumulate_dataframes_vertical_unchecked(dfs);
+
+        // the row index and the limit count the rows before they are filtered
+        let expected = df![
+            "idx" => [0 as IdxSize, 2, 4, 6],
+            "a" => [0i64, 2, 4, 6],
+        ]
+        .unwrap();
+        assert!(out.equals(&expected), "{out}");
+    }
+}
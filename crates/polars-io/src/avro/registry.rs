@@ -0,0 +1,94 @@
+use polars_error::{polars_bail, polars_ensure, polars_err, to_compute_err, PolarsResult};
+
+use crate::pl_async::{get_runtime, with_concurrency_budget};
+
+/// Client of a [Confluent schema registry] that fetches the writer schemas of Avro messages by
+/// their id.
+///
+/// [Confluent schema registry]: https://docs.confluent.io/platform/current/schema-registry/develop/api.html
+#[derive(Clone, Debug)]
+pub struct SchemaRegistryClient {
+    url: String,
+    basic_auth: Option<(String, String)>,
+}
+
+impl SchemaRegistryClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            basic_auth: None,
+        }
+    }
+
+    /// Authenticate with an API key and secret, or a user name and password.
+    pub fn with_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.basic_auth = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Fetch the JSON definition of the schema with the given id.
+    pub fn fetch_schema(&self, id: u32) -> PolarsResult<String> {
+        let url = format!("{}/schemas/ids/{}", self.url.trim_end_matches('/'), id);
+        let mut request = reqwest::Client::new().get(url);
+        if let Some((username, password)) = &self.basic_auth {
+            request = request.basic_auth(username, Some(password));
+        }
+
+        let fetch = with_concurrency_budget(1, move || async move {
+            let response = request.send().await.map_err(to_compute_err)?;
+            let status = response.status();
+            if !status.is_success() {
+                polars_bail!(
+                    ComputeError: "schema registry responded with status {} to a request for schema id {}",
+                    status, id
+                );
+            }
+            response.bytes().await.map_err(to_compute_err)
+        });
+        let bytes = get_runtime().block_on_potential_spawn(fetch)?;
+        parse_schema_response(&bytes)
+    }
+}
+
+fn parse_schema_response(bytes: &[u8]) -> PolarsResult<String> {
+    let response: serde_json::Value = serde_json::from_slice(bytes).map_err(to_compute_err)?;
+    // The schema type is omitted for Avro schemas.
+    if let Some(schema_type) = response.get("schemaType").and_then(|v| v.as_str()) {
+        polars_ensure!(
+            schema_type == "AVRO",
+            ComputeError: "schema registry returned a {} schema, expected an Avro schema", schema_type
+        );
+    }
+    response
+        .get("schema")
+        .and_then(|v| v.as_str())
+        .map(|schema| schema.to_string())
+        .ok_or_else(|| {
+            polars_err!(
+                ComputeError: "schema registry response has no schema: {}",
+                String::from_utf8_lossy(bytes)
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_schema_response() {
+        let response = br#"{"schema": "{\"type\": \"record\", \"name\": \"a\", \"fields\": []}"}"#;
+        assert_eq!(
+            parse_schema_response(response).unwrap(),
+            r#"{"type": "record", "name": "a", "fields": []}"#
+        );
+
+        let response = br#"{"schemaType": "PROTOBUF", "schema": "message A {}"}"#;
+        assert!(parse_schema_response(response).is_err());
+        assert!(parse_schema_response(br#"{"error_code": 40403}"#).is_err());
+    }
+}
@@ -21,6 +21,8 @@ use crate::pl_async::get_runtime;
 pub struct CloudWriter {
     // Internal writer, constructed at creation
     writer: BufWriter,
+    // Whether the upload is completed by `close`.
+    is_closed: bool,
 }
 
 impl CloudWriter {
@@ -34,7 +36,10 @@ impl CloudWriter {
         path: Path,
     ) -> PolarsResult<Self> {
         let writer = BufWriter::new(object_store, path);
-        Ok(CloudWriter { writer })
+        Ok(CloudWriter {
+            writer,
+            is_closed: false,
+        })
     }
 
     /// Constructs a new CloudWriter from a path and an optional set of CloudOptions.
@@ -50,6 +55,20 @@ impl CloudWriter {
     async fn abort(&mut self) -> PolarsResult<()> {
         self.writer.abort().await.map_err(to_compute_err)
     }
+
+    /// Upload the remaining bytes and complete the multipart upload.
+    ///
+    /// Dropping the writer also completes the upload, but this returns the errors of the upload.
+    pub fn close(&mut self) -> PolarsResult<()> {
+        self.is_closed = true;
+        get_runtime().block_on_potential_spawn(async {
+            let res = self.writer.shutdown().await.map_err(to_compute_err);
+            if res.is_err() {
+                let _ = self.abort().await;
+            }
+            res
+        })
+    }
 }
 
 impl std::io::Write for CloudWriter {
@@ -80,6 +99,9 @@ impl std::io::Write for CloudWriter {
 
 impl Drop for CloudWriter {
     fn drop(&mut self) {
+        if self.is_closed {
+            return;
+        }
         let _ = get_runtime().block_on_potential_spawn(self.writer.shutdown());
     }
 }
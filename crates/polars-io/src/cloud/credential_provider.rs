@@ -0,0 +1,276 @@
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+#[cfg(feature = "aws")]
+use object_store::aws::{AwsCredential, AwsCredentialProvider};
+#[cfg(feature = "azure")]
+use object_store::azure::{AzureCredential, AzureCredentialProvider};
+#[cfg(feature = "gcp")]
+use object_store::gcp::{GcpCredential, GcpCredentialProvider};
+use polars_error::*;
+use tokio::sync::Mutex;
+
+/// Credentials are fetched again if they expire within this many seconds.
+const REFRESH_MARGIN_SECS: u64 = 60;
+
+/// The credentials of a connection to a cloud storage service.
+#[derive(Clone, Debug)]
+pub enum ObjectStoreCredential {
+    #[cfg(feature = "aws")]
+    Aws(Arc<AwsCredential>),
+    #[cfg(feature = "azure")]
+    Azure(Arc<AzureCredential>),
+    #[cfg(feature = "gcp")]
+    Gcp(Arc<GcpCredential>),
+}
+
+/// Credentials and the time at which they expire, in seconds since the Unix epoch. Credentials
+/// without an expiry time are fetched only once.
+pub type CredentialsWithExpiry = (ObjectStoreCredential, Option<u64>);
+
+type CredentialProviderFunction = dyn Fn() -> Pin<Box<dyn Future<Output = PolarsResult<CredentialsWithExpiry>> + Send>>
+    + Send
+    + Sync;
+
+/// Provides the credentials of cloud storage connections, e.g. from an instance profile, a
+/// workload identity or a service that issues expiring SAS or STS tokens.
+///
+/// The credentials are cached by every object store they are set on, and fetched again shortly
+/// before they expire, also in the middle of a query.
+#[derive(Clone)]
+pub struct PlCredentialProvider(Arc<CredentialProviderFunction>);
+
+impl PlCredentialProvider {
+    /// Create a provider that fetches its credentials with `func`.
+    pub fn from_func<F, Fut>(func: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = PolarsResult<CredentialsWithExpiry>> + Send + 'static,
+    {
+        Self(Arc::new(move || Box::pin(func())))
+    }
+
+    /// Create a provider that tries `providers` in order and returns the credentials of the first
+    /// that succeeds.
+    pub fn chain(providers: Vec<PlCredentialProvider>) -> Self {
+        let providers: Arc<[PlCredentialProvider]> = providers.into();
+        Self::from_func(move || {
+            let providers = providers.clone();
+            async move {
+                let mut errors = Vec::with_capacity(providers.len());
+                for provider in providers.iter() {
+                    match provider.fetch().await {
+                        Ok(credentials) => return Ok(credentials),
+                        Err(e) => errors.push(e.to_string()),
+                    }
+                }
+                polars_bail!(
+                    ComputeError: "none of the credential providers returned credentials: [{}]",
+                    errors.join(", ")
+                )
+            }
+        })
+    }
+
+    /// Fetch new credentials.
+    pub async fn fetch(&self) -> PolarsResult<CredentialsWithExpiry> {
+        (self.0)().await
+    }
+
+    #[cfg(feature = "aws")]
+    #[allow(unreachable_patterns)]
+    pub(crate) fn into_aws_provider(self) -> AwsCredentialProvider {
+        Arc::new(CachedCredentialProvider::new(self, "AWS", |c| match c {
+            ObjectStoreCredential::Aws(c) => Some(c),
+            _ => None,
+        }))
+    }
+
+    #[cfg(feature = "azure")]
+    #[allow(unreachable_patterns)]
+    pub(crate) fn into_azure_provider(self) -> AzureCredentialProvider {
+        Arc::new(CachedCredentialProvider::new(self, "Azure", |c| match c {
+            ObjectStoreCredential::Azure(c) => Some(c),
+            _ => None,
+        }))
+    }
+
+    #[cfg(feature = "gcp")]
+    #[allow(unreachable_patterns)]
+    pub(crate) fn into_gcp_provider(self) -> GcpCredentialProvider {
+        Arc::new(CachedCredentialProvider::new(self, "GCP", |c| match c {
+            ObjectStoreCredential::Gcp(c) => Some(c),
+            _ => None,
+        }))
+    }
+
+    /// The address of the provider, which identifies it in the object store cache.
+    pub(crate) fn addr(&self) -> usize {
+        Arc::as_ptr(&self.0) as *const () as usize
+    }
+}
+
+impl Debug for PlCredentialProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PlCredentialProvider({:#x})", self.addr())
+    }
+}
+
+impl PartialEq for PlCredentialProvider {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for PlCredentialProvider {}
+
+impl Hash for PlCredentialProvider {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state)
+    }
+}
+
+/// Caches the credentials of a [`PlCredentialProvider`] for an object store.
+struct CachedCredentialProvider<C> {
+    provider: PlCredentialProvider,
+    cloud: &'static str,
+    extract: fn(ObjectStoreCredential) -> Option<Arc<C>>,
+    cache: Mutex<Option<(Arc<C>, Option<u64>)>>,
+}
+
+impl<C> CachedCredentialProvider<C> {
+    fn new(
+        provider: PlCredentialProvider,
+        cloud: &'static str,
+        extract: fn(ObjectStoreCredential) -> Option<Arc<C>>,
+    ) -> Self {
+        Self {
+            provider,
+            cloud,
+            extract,
+            cache: Mutex::new(None),
+        }
+    }
+}
+
+impl<C> Debug for CachedCredentialProvider<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CachedCredentialProvider({}, {:?})",
+            self.cloud, self.provider
+        )
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn to_object_store_err(e: PolarsError) -> object_store::Error {
+    object_store::Error::Generic {
+        store: "credential provider",
+        source: Box::new(e),
+    }
+}
+
+#[async_trait]
+impl<C> object_store::CredentialProvider for CachedCredentialProvider<C>
+where
+    C: Debug + Send + Sync + 'static,
+{
+    type Credential = C;
+
+    async fn get_credential(&self) -> object_store::Result<Arc<C>> {
+        // Hold the lock while fetching, so that concurrent requests fetch only once.
+        let mut cache = self.cache.lock().await;
+        if let Some((credential, expiry)) = cache.as_ref() {
+            if expiry.map_or(true, |expiry| now_secs() + REFRESH_MARGIN_SECS < expiry) {
+                return Ok(credential.clone());
+            }
+        }
+
+        let (credential, expiry) = self.provider.fetch().await.map_err(to_object_store_err)?;
+        let credential = (self.extract)(credential).ok_or_else(|| {
+            to_object_store_err(polars_err!(
+                ComputeError: "the credential provider returned credentials for another cloud than {}",
+                self.cloud
+            ))
+        })?;
+        *cache = Some((credential.clone(), expiry));
+        Ok(credential)
+    }
+}
+
+#[cfg(feature = "aws")]
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use object_store::CredentialProvider;
+
+    use super::*;
+
+    fn counting_provider(fetches: Arc<AtomicUsize>, lifetime: Option<u64>) -> PlCredentialProvider {
+        PlCredentialProvider::from_func(move || {
+            let n = fetches.fetch_add(1, Ordering::Relaxed);
+            async move {
+                let credential = AwsCredential {
+                    key_id: format!("key_{n}"),
+                    secret_key: "secret".into(),
+                    token: None,
+                };
+                Ok((
+                    ObjectStoreCredential::Aws(Arc::new(credential)),
+                    lifetime.map(|lifetime| now_secs() + lifetime),
+                ))
+            }
+        })
+    }
+
+    #[test]
+    fn test_credential_refresh() {
+        crate::pl_async::get_runtime().block_on(async {
+            // Credentials that expire within the refresh margin are fetched every time.
+            let fetches = Arc::new(AtomicUsize::new(0));
+            let provider = counting_provider(fetches.clone(), Some(1)).into_aws_provider();
+            assert_eq!(provider.get_credential().await.unwrap().key_id, "key_0");
+            assert_eq!(provider.get_credential().await.unwrap().key_id, "key_1");
+
+            let fetches = Arc::new(AtomicUsize::new(0));
+            let provider = counting_provider(fetches.clone(), Some(3600)).into_aws_provider();
+            provider.get_credential().await.unwrap();
+            assert_eq!(provider.get_credential().await.unwrap().key_id, "key_0");
+            assert_eq!(fetches.load(Ordering::Relaxed), 1);
+        })
+    }
+
+    #[test]
+    fn test_credential_chain() {
+        crate::pl_async::get_runtime().block_on(async {
+            let failing = PlCredentialProvider::from_func(|| async {
+                polars_bail!(ComputeError: "no instance profile")
+            });
+            let fetches = Arc::new(AtomicUsize::new(0));
+            let chain = PlCredentialProvider::chain(vec![
+                failing.clone(),
+                counting_provider(fetches.clone(), None),
+            ]);
+            let (ObjectStoreCredential::Aws(credential), None) = chain.fetch().await.unwrap()
+            else {
+                panic!()
+            };
+            assert_eq!(credential.key_id, "key_0");
+
+            let err = PlCredentialProvider::chain(vec![failing]).fetch().await;
+            assert!(err.unwrap_err().to_string().contains("no instance profile"));
+        })
+    }
+}
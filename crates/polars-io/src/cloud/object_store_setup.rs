@@ -31,7 +31,11 @@ fn err_missing_feature(feature: &str, scheme: &str) -> BuildResult {
 /// The credential info will be removed
 fn url_and_creds_to_key(url: &Url, options: Option<&CloudOptions>) -> String {
     // We include credentials as they can expire, so users will send new credentials for the same url.
-    let creds = serde_json::to_string(&options).unwrap_or_else(|_| "".into());
+    let mut creds = serde_json::to_string(&options).unwrap_or_else(|_| "".into());
+    // Credential providers aren't serialized, stores with different providers must not be shared.
+    if let Some(provider) = options.and_then(|o| o.credential_provider.as_ref()) {
+        creds.push_str(&format!("<\\provider\\>{:#x}", provider.addr()));
+    }
     format!(
         "{}://{}<\\creds\\>{}",
         url.scheme(),
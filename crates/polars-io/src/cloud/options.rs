@@ -36,6 +36,8 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "cloud")]
 use url::Url;
 
+#[cfg(feature = "cloud")]
+use super::credential_provider::PlCredentialProvider;
 #[cfg(feature = "file_cache")]
 use crate::file_cache::get_env_file_cache_ttl;
 #[cfg(feature = "aws")]
@@ -75,6 +77,9 @@ pub struct CloudOptions {
     #[cfg(feature = "file_cache")]
     pub file_cache_ttl: u64,
     pub(crate) config: Option<CloudConfig>,
+    #[cfg(feature = "cloud")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) credential_provider: Option<PlCredentialProvider>,
 }
 
 impl Default for CloudOptions {
@@ -84,6 +89,8 @@ impl Default for CloudOptions {
             #[cfg(feature = "file_cache")]
             file_cache_ttl: get_env_file_cache_ttl(),
             config: None,
+            #[cfg(feature = "cloud")]
+            credential_provider: None,
         }
     }
 }
@@ -248,6 +255,18 @@ impl CloudOptions {
         self
     }
 
+    /// Set the provider of the credentials of AWS, Azure and GCP connections. Its credentials
+    /// take precedence over the static keys of the configuration, and are refreshed before they
+    /// expire.
+    #[cfg(feature = "cloud")]
+    pub fn with_credential_provider(
+        mut self,
+        credential_provider: Option<PlCredentialProvider>,
+    ) -> Self {
+        self.credential_provider = credential_provider;
+        self
+    }
+
     /// Set the configuration for AWS connections. This is the preferred API from rust.
     #[cfg(feature = "aws")]
     pub fn with_aws<I: IntoIterator<Item = (AmazonS3ConfigKey, impl Into<String>)>>(
@@ -346,6 +365,10 @@ impl CloudOptions {
             };
         };
 
+        if let Some(provider) = &self.credential_provider {
+            builder = builder.with_credentials(provider.clone().into_aws_provider());
+        }
+
         builder
             .with_client_options(get_client_options())
             .with_retry(get_retry_config(self.max_retries))
@@ -378,6 +401,10 @@ impl CloudOptions {
             }
         }
 
+        if let Some(provider) = &self.credential_provider {
+            builder = builder.with_credentials(provider.clone().into_azure_provider());
+        }
+
         builder
             .with_client_options(get_client_options())
             .with_url(url)
@@ -411,6 +438,10 @@ impl CloudOptions {
             }
         }
 
+        if let Some(provider) = &self.credential_provider {
+            builder = builder.with_credentials(provider.clone().into_gcp_provider());
+        }
+
         builder
             .with_client_options(get_client_options())
             .with_url(url)
@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use polars_core::config::get_file_prefetch_size;
+use polars_error::PolarsResult;
+use polars_utils::mmap::MemSlice;
+use tokio::task::JoinHandle;
+
+use super::block_reader::{get_csv_block_size, LineBoundaryBuffer};
+use crate::cloud::CloudOptions;
+use crate::pl_async::get_runtime;
+use crate::utils::byte_source::{ByteSource, DynByteSource, DynByteSourceBuilder};
+
+/// Fetches a (remote) CSV file with range requests and yields blocks that end on a line
+/// boundary, so that every block can be parsed on its own.
+///
+/// At most `prefetch_size` range requests are in flight at any given moment.
+pub struct CsvBlockFetcher {
+    byte_source: Arc<DynByteSource>,
+    file_size: usize,
+    block_size: usize,
+    prefetch_size: usize,
+    next_offset: usize,
+    in_flight: VecDeque<JoinHandle<PolarsResult<MemSlice>>>,
+    lines: LineBoundaryBuffer,
+    eol_char: u8,
+}
+
+impl CsvBlockFetcher {
+    pub async fn try_new_from_path(
+        path: &str,
+        cloud_options: Option<&CloudOptions>,
+        quote_char: Option<u8>,
+        eol_char: u8,
+    ) -> PolarsResult<Self> {
+        let byte_source = DynByteSourceBuilder::ObjectStore
+            .try_build_from_path(path, cloud_options)
+            .await?;
+        Self::try_new_from_byte_source(byte_source, quote_char, eol_char).await
+    }
+
+    pub async fn try_new_from_byte_source(
+        byte_source: DynByteSource,
+        quote_char: Option<u8>,
+        eol_char: u8,
+    ) -> PolarsResult<Self> {
+        let file_size = byte_source.get_size().await?;
+
+        Ok(Self {
+            byte_source: Arc::new(byte_source),
+            file_size,
+            block_size: get_csv_block_size(),
+            prefetch_size: get_file_prefetch_size(),
+            next_offset: 0,
+            in_flight: VecDeque::new(),
+            lines: LineBoundaryBuffer::new(quote_char, eol_char),
+            eol_char,
+        })
+    }
+
+    /// Set the number of bytes that are requested per range request.
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Set the maximum number of range requests that are in flight.
+    pub fn with_prefetch_size(mut self, prefetch_size: usize) -> Self {
+        self.prefetch_size = prefetch_size.max(1);
+        self
+    }
+
+    /// Total size of the file in bytes.
+    pub fn file_size(&self) -> usize {
+        self.file_size
+    }
+
+    fn fill_prefetch_queue(&mut self) {
+        while self.in_flight.len() < self.prefetch_size && self.next_offset < self.file_size {
+            let end = std::cmp::min(self.next_offset + self.block_size, self.file_size);
+            let range = self.next_offset..end;
+            self.next_offset = end;
+
+            let byte_source = self.byte_source.clone();
+            self.in_flight
+                .push_back(get_runtime().spawn(async move { byte_source.get_range(range).await }));
+        }
+    }
+
+    /// Get the next block of complete lines. Returns `None` if the file is exhausted.
+    ///
+    /// The last block of the file contains the remaining bytes, which may not end on a line
+    /// ending.
+    pub async fn next_block(&mut self) -> PolarsResult<Option<MemSlice>> {
+        loop {
+            self.fill_prefetch_queue();
+
+            let Some(handle) = self.in_flight.pop_front() else {
+                return Ok(self.lines.take_remaining().map(MemSlice::from_vec));
+            };
+            let bytes = handle.await.unwrap()?;
+
+            self.lines.push(&bytes);
+            if let Some(block) = self.lines.take_lines() {
+                return Ok(Some(MemSlice::from_vec(block)));
+            }
+        }
+    }
+
+    /// Fetch blocks until at least `n_lines` line endings are seen, or the file is exhausted,
+    /// and return the concatenated bytes.
+    pub async fn fetch_prefix(&mut self, n_lines: usize) -> PolarsResult<MemSlice> {
+        let mut out = vec![];
+        let mut lines_seen = 0;
+
+        while lines_seen < n_lines {
+            let Some(block) = self.next_block().await? else {
+                break;
+            };
+            lines_seen += memchr::memchr_iter(self.eol_char, &block).count();
+            out.extend_from_slice(&block);
+        }
+        Ok(MemSlice::from_vec(out))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use polars_utils::mmap::MemSlice;
+
+    use super::CsvBlockFetcher;
+    use crate::pl_async::get_runtime;
+
+    #[test]
+    fn test_blocks_end_on_line_boundary() {
+        let input = "a,b\n1,\"x\ny\"\n22,z\n3,w";
+        let byte_source = MemSlice::from_static(input.as_bytes()).into();
+
+        let blocks = get_runtime().block_on(async {
+            let mut fetcher =
+                CsvBlockFetcher::try_new_from_byte_source(byte_source, Some(b'"'), b'\n')
+                    .await?
+                    .with_block_size(3)
+                    .with_prefetch_size(2);
+
+            let mut blocks = vec![];
+            while let Some(block) = fetcher.next_block().await? {
+                blocks.push(String::from_utf8(block.to_vec()).unwrap());
+            }
+            polars_error::PolarsResult::Ok(blocks)
+        });
+
+        assert_eq!(blocks.unwrap(), ["a,b\n", "1,\"x\ny\"\n", "22,z\n", "3,w"]);
+    }
+}
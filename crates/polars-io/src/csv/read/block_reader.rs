@@ -0,0 +1,155 @@
+use std::io::Read;
+
+use polars_error::PolarsResult;
+use polars_utils::mmap::MemSlice;
+
+const DEFAULT_BLOCK_SIZE: usize = 1 << 22;
+
+/// The number of bytes that are requested per range request, or read per block of a
+/// decompressed file.
+pub fn get_csv_block_size() -> usize {
+    std::env::var("POLARS_CSV_BLOCK_SIZE")
+        .map(|s| s.parse::<usize>().expect("integer"))
+        .unwrap_or(DEFAULT_BLOCK_SIZE)
+}
+
+/// Buffers bytes until they can be split on a line ending that is not embedded in a quoted
+/// field.
+pub(super) struct LineBoundaryBuffer {
+    // Bytes that are not yet returned, as they don't end on a line boundary.
+    pending: Vec<u8>,
+    // Number of bytes at the end of `pending` that are not yet scanned for line endings.
+    unscanned: usize,
+    // Whether the end of `pending` lies in a quoted field.
+    in_quote: bool,
+    quote_char: Option<u8>,
+    eol_char: u8,
+}
+
+impl LineBoundaryBuffer {
+    pub(super) fn new(quote_char: Option<u8>, eol_char: u8) -> Self {
+        Self {
+            pending: vec![],
+            unscanned: 0,
+            in_quote: false,
+            quote_char,
+            eol_char,
+        }
+    }
+
+    pub(super) fn push(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        self.unscanned += bytes.len();
+    }
+
+    /// Scan the bytes of `pending` that were not yet scanned and return the position right
+    /// after the last line ending that is not embedded in a quoted field.
+    fn find_last_line_end(&mut self) -> Option<usize> {
+        let start = self.pending.len() - self.unscanned;
+        self.unscanned = 0;
+        let bytes = &self.pending[start..];
+
+        match self.quote_char {
+            None => memchr::memrchr(self.eol_char, bytes).map(|i| start + i + 1),
+            Some(quote_char) => {
+                let mut last_line_end = None;
+                for i in memchr::memchr2_iter(quote_char, self.eol_char, bytes) {
+                    if bytes[i] == quote_char {
+                        self.in_quote = !self.in_quote;
+                    } else if !self.in_quote {
+                        last_line_end = Some(start + i + 1);
+                    }
+                }
+                last_line_end
+            },
+        }
+    }
+
+    /// Take the buffered complete lines, if any.
+    pub(super) fn take_lines(&mut self) -> Option<Vec<u8>> {
+        let end = self.find_last_line_end()?;
+        let remaining = self.pending[end..].to_vec();
+        let mut block = std::mem::replace(&mut self.pending, remaining);
+        block.truncate(end);
+        Some(block)
+    }
+
+    /// Take all buffered bytes, which may not end on a line ending.
+    pub(super) fn take_remaining(&mut self) -> Option<Vec<u8>> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        self.unscanned = 0;
+        self.in_quote = false;
+        Some(std::mem::take(&mut self.pending))
+    }
+}
+
+/// Reads a CSV file from a [`Read`], e.g. a decoder of a compressed file, in blocks.
+///
+/// The blocks end on a line boundary, so that every block can be parsed on its own and the whole
+/// file is never held in memory.
+pub struct CsvBlockReader {
+    reader: Box<dyn Read + Send>,
+    block_size: usize,
+    lines: LineBoundaryBuffer,
+    exhausted: bool,
+}
+
+impl CsvBlockReader {
+    pub fn new(reader: Box<dyn Read + Send>, quote_char: Option<u8>, eol_char: u8) -> Self {
+        Self {
+            reader,
+            block_size: get_csv_block_size(),
+            lines: LineBoundaryBuffer::new(quote_char, eol_char),
+            exhausted: false,
+        }
+    }
+
+    /// Set the number of bytes that are read per block.
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Get the next block of complete lines. Returns `None` if the reader is exhausted.
+    ///
+    /// The last block contains the remaining bytes, which may not end on a line ending.
+    pub fn next_block(&mut self) -> PolarsResult<Option<MemSlice>> {
+        let mut buf = Vec::with_capacity(self.block_size);
+        while !self.exhausted {
+            buf.clear();
+            let n_read = (&mut self.reader)
+                .take(self.block_size as u64)
+                .read_to_end(&mut buf)?;
+            if n_read == 0 {
+                self.exhausted = true;
+                break;
+            }
+
+            self.lines.push(&buf);
+            if let Some(block) = self.lines.take_lines() {
+                return Ok(Some(MemSlice::from_vec(block)));
+            }
+        }
+        Ok(self.lines.take_remaining().map(MemSlice::from_vec))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CsvBlockReader;
+
+    #[test]
+    fn test_blocks_end_on_line_boundary() {
+        let input = "a,b\n1,\"x\ny\"\n22,z\n3,w";
+        let mut reader =
+            CsvBlockReader::new(Box::new(input.as_bytes()), Some(b'"'), b'\n').with_block_size(3);
+
+        let mut blocks = vec![];
+        while let Some(block) = reader.next_block().unwrap() {
+            blocks.push(String::from_utf8(block.to_vec()).unwrap());
+        }
+        assert_eq!(blocks, ["a,b\n", "1,\"x\ny\"\n", "22,z\n", "3,w"]);
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use memchr::memchr_iter;
+use polars_core::prelude::*;
+
+use super::parser::skip_this_line;
+
+/// A line of a CSV file that could not be parsed.
+#[derive(Clone, Debug)]
+struct MalformedRow {
+    /// Byte offset of the start of the line.
+    offset: usize,
+    /// Line number of the start of the line in the file, starting at 1. Line endings in quoted
+    /// fields are counted as well, so that this matches the line numbers of a text editor.
+    line: usize,
+    raw: String,
+    reason: String,
+}
+
+/// Collects the lines of a CSV file that could not be parsed, instead of raising an error or
+/// silently setting the unparsable values to null.
+///
+/// The values of malformed lines are still read as null. Clones share the same collected
+/// lines, so a clone can be passed to the reader and inspected after the query has finished.
+#[derive(Clone, Default)]
+pub struct MalformedRowsSink {
+    rows: Arc<Mutex<Vec<MalformedRow>>>,
+}
+
+impl MalformedRowsSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of collected lines.
+    pub fn len(&self) -> usize {
+        self.rows.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all collected lines.
+    pub fn clear(&self) {
+        self.rows.lock().unwrap().clear()
+    }
+
+    /// Get the collected lines as a [`DataFrame`] with the columns `line` (the line number in
+    /// the file, starting at 1), `raw` (the text of the line) and `reason`.
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let rows = self.rows.lock().unwrap();
+        let line = rows.iter().map(|r| r.line as IdxSize).collect::<Vec<_>>();
+        let raw = rows.iter().map(|r| r.raw.as_str()).collect::<Vec<_>>();
+        let reason = rows.iter().map(|r| r.reason.as_str()).collect::<Vec<_>>();
+
+        DataFrame::new(vec![
+            Series::new(PlSmallStr::from_static("line"), line),
+            Series::new(PlSmallStr::from_static("raw"), raw),
+            Series::new(PlSmallStr::from_static("reason"), reason),
+        ])
+    }
+}
+
+impl fmt::Debug for MalformedRowsSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MalformedRowsSink")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+// Sinks are compared by identity, as their content changes while reading.
+impl PartialEq for MalformedRowsSink {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.rows, &other.rows)
+    }
+}
+
+impl Eq for MalformedRowsSink {}
+
+impl Hash for MalformedRowsSink {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.rows).hash(state)
+    }
+}
+
+/// Collects the malformed lines of a single reader while the chunks are parsed in parallel.
+/// The line numbers are assigned in [`MalformedRowsCollector::flush`], as the chunks don't
+/// know how many lines precede them.
+#[derive(Default)]
+pub(super) struct MalformedRowsCollector {
+    rows: Mutex<Vec<MalformedRow>>,
+    // Byte offset and line number up to which the lines are counted.
+    counted_offset: usize,
+    counted_lines: usize,
+}
+
+impl MalformedRowsCollector {
+    /// Collect the line that starts at `line_start`, at byte `offset` of the file.
+    pub(super) fn push(
+        &self,
+        offset: usize,
+        line_start: &[u8],
+        quote_char: Option<u8>,
+        eol_char: u8,
+        reason: String,
+    ) {
+        let rest = skip_this_line(line_start, quote_char, eol_char);
+        let mut line = &line_start[..line_start.len() - rest.len()];
+        while let [head @ .., b] = line {
+            if *b != eol_char && *b != b'\r' {
+                break;
+            }
+            line = head;
+        }
+
+        self.rows.lock().unwrap().push(MalformedRow {
+            offset,
+            line: 0,
+            raw: String::from_utf8_lossy(line).into_owned(),
+            reason,
+        });
+    }
+
+    /// Assign the line numbers of the collected lines and move them to `sink`. The collected
+    /// lines may not start before the lines of a previous flush.
+    pub(super) fn flush(&mut self, bytes: &[u8], eol_char: u8, sink: &MalformedRowsSink) {
+        let mut rows = std::mem::take(self.rows.get_mut().unwrap());
+        if rows.is_empty() {
+            return;
+        }
+        rows.sort_unstable_by_key(|r| r.offset);
+
+        for row in rows.iter_mut() {
+            self.counted_lines +=
+                memchr_iter(eol_char, &bytes[self.counted_offset..row.offset]).count();
+            self.counted_offset = row.offset;
+            row.line = self.counted_lines + 1;
+        }
+        sink.rows.lock().unwrap().extend(rows);
+    }
+}
@@ -16,17 +16,30 @@
 //! }
 //! ```
 
+#[cfg(feature = "cloud")]
+mod block_fetcher;
+mod block_reader;
 pub mod buffer;
+mod malformed;
 mod options;
 mod parser;
 mod read_impl;
 mod reader;
 pub mod schema_inference;
 mod splitfields;
+mod stream_reader;
 mod utils;
 
-pub use options::{CommentPrefix, CsvEncoding, CsvParseOptions, CsvReadOptions, NullValues};
+#[cfg(feature = "cloud")]
+pub use block_fetcher::CsvBlockFetcher;
+pub use block_reader::{get_csv_block_size, CsvBlockReader};
+pub use malformed::MalformedRowsSink;
+pub use options::{
+    CommentPrefix, CsvEncoding, CsvParseOptions, CsvReadOptions, InferSchemaPolicy,
+    InferSchemaSampling, IntFloatConflict, NullValues, RaggedLines, Separator,
+};
 pub use parser::{count_rows, count_rows_from_slice};
 pub use read_impl::batched::{BatchedCsvReader, OwnedBatchedCsvReader};
 pub use reader::CsvReader;
-pub use schema_inference::infer_file_schema;
+pub use schema_inference::{infer_file_schema, SchemaInferenceReport};
+pub use stream_reader::CsvStreamReader;
@@ -3,11 +3,14 @@ use std::sync::Arc;
 
 use polars_core::datatypes::{DataType, Field};
 use polars_core::schema::{Schema, SchemaRef};
-use polars_error::PolarsResult;
+use polars_error::{polars_ensure, PolarsError, PolarsResult};
 use polars_utils::pl_str::PlSmallStr;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use super::malformed::MalformedRowsSink;
+use super::schema_inference::SchemaInferenceReport;
+use super::utils::{transcode_single_byte, transcode_utf16, windows_1252_to_char};
 use crate::RowIndex;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -35,21 +38,24 @@ pub struct CsvReadOptions {
     pub skip_rows: usize,
     pub skip_rows_after_header: usize,
     pub infer_schema_length: Option<usize>,
+    pub infer_schema_policy: InferSchemaPolicy,
     pub raise_if_empty: bool,
     pub ignore_errors: bool,
     pub fields_to_cast: Vec<Field>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub malformed_rows: Option<MalformedRowsSink>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CsvParseOptions {
-    pub separator: u8,
+    pub separator: Separator,
     pub quote_char: Option<u8>,
     pub eol_char: u8,
     pub encoding: CsvEncoding,
     pub null_values: Option<NullValues>,
     pub missing_is_null: bool,
-    pub truncate_ragged_lines: bool,
+    pub ragged_lines: RaggedLines,
     pub comment_prefix: Option<CommentPrefix>,
     pub try_parse_dates: bool,
     pub decimal_comma: bool,
@@ -80,24 +86,112 @@ impl Default for CsvReadOptions {
             skip_rows: 0,
             skip_rows_after_header: 0,
             infer_schema_length: Some(100),
+            infer_schema_policy: Default::default(),
             raise_if_empty: true,
             ignore_errors: false,
             fields_to_cast: vec![],
+            malformed_rows: None,
         }
     }
 }
 
+/// How lines with fewer or more fields than the schema are handled.
+///
+/// [`RaggedLines::Raise`] and [`RaggedLines::MalformedRows`] do not depend on the projected
+/// columns, so that all readers raise on (or collect) the same lines. [`RaggedLines::Pad`]
+/// only raises on extra fields if all columns are projected, as the fields after the last
+/// projected column are not parsed otherwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RaggedLines {
+    /// Read missing fields as null and raise an error on extra fields.
+    #[default]
+    Pad,
+    /// Read missing fields as null and ignore extra fields.
+    Truncate,
+    /// Raise an error on missing and extra fields.
+    Raise,
+    /// Collect lines with missing or extra fields in the [`MalformedRowsSink`] of the reader,
+    /// which must be set. Missing fields are read as null and extra fields are ignored.
+    MalformedRows,
+}
+
+impl RaggedLines {
+    /// Whether a line with `n_fields` fields is malformed, for a schema of `schema_len` fields.
+    /// Returns the reason if so.
+    pub(super) fn check(self, n_fields: usize, schema_len: usize) -> Option<&'static str> {
+        if n_fields > schema_len && self != Self::Truncate {
+            Some("found more fields than defined in 'Schema'")
+        } else if n_fields < schema_len && matches!(self, Self::Raise | Self::MalformedRows) {
+            Some("found fewer fields than defined in 'Schema'")
+        } else {
+            None
+        }
+    }
+}
+
+/// Controls how the schema of a CSV file is inferred.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InferSchemaPolicy {
+    pub sampling: InferSchemaSampling,
+    pub int_float_conflict: IntFloatConflict,
+    /// Collects the columns of which the data type is widened because of conflicting values.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub report: Option<SchemaInferenceReport>,
+}
+
+impl InferSchemaPolicy {
+    pub fn with_sampling(mut self, sampling: InferSchemaSampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn with_int_float_conflict(mut self, int_float_conflict: IntFloatConflict) -> Self {
+        self.int_float_conflict = int_float_conflict;
+        self
+    }
+
+    pub fn with_report(mut self, report: Option<SchemaInferenceReport>) -> Self {
+        self.report = report;
+        self
+    }
+}
+
+/// Which rows are used to infer the schema.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InferSchemaSampling {
+    /// Use the first `infer_schema_length` rows.
+    #[default]
+    Head,
+    /// Use `infer_schema_length` rows that are spread uniformly over the file. This requires a
+    /// pass over all lines of the file.
+    Uniform,
+}
+
+/// The data type of a column that contains both integer and float values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IntFloatConflict {
+    /// Widen the column to `Float64`.
+    #[default]
+    Float,
+    /// Fall back to `String`, like for other conflicting values.
+    String,
+}
+
 /// Options related to parsing the CSV format.
 impl Default for CsvParseOptions {
     fn default() -> Self {
         Self {
-            separator: b',',
+            separator: Separator::default(),
             quote_char: Some(b'"'),
             eol_char: b'\n',
             encoding: Default::default(),
             null_values: None,
             missing_is_null: true,
-            truncate_ragged_lines: false,
+            ragged_lines: RaggedLines::default(),
             comment_prefix: None,
             try_parse_dates: false,
             decimal_comma: false,
@@ -224,6 +318,12 @@ impl CsvReadOptions {
         self
     }
 
+    /// Set how the schema is inferred from the rows that are read for schema inference.
+    pub fn with_infer_schema_policy(mut self, infer_schema_policy: InferSchemaPolicy) -> Self {
+        self.infer_schema_policy = infer_schema_policy;
+        self
+    }
+
     /// Whether to raise an error if the frame is empty. By default an empty
     /// DataFrame is returned.
     pub fn with_raise_if_empty(mut self, raise_if_empty: bool) -> Self {
@@ -237,6 +337,13 @@ impl CsvReadOptions {
         self
     }
 
+    /// Collect the lines that cannot be parsed into `malformed_rows` instead of raising an
+    /// error. This takes precedence over `ignore_errors`.
+    pub fn with_malformed_rows(mut self, malformed_rows: Option<MalformedRowsSink>) -> Self {
+        self.malformed_rows = malformed_rows;
+        self
+    }
+
     /// Apply a function to the parse options.
     pub fn map_parse_options<F: Fn(CsvParseOptions) -> CsvParseOptions>(
         mut self,
@@ -250,9 +357,10 @@ impl CsvReadOptions {
 
 impl CsvParseOptions {
     /// The character used to separate fields in the CSV file. This
-    /// is most often a comma ','.
-    pub fn with_separator(mut self, separator: u8) -> Self {
-        self.separator = separator;
+    /// is most often a comma ','. Separators of multiple bytes, e.g. `||`,
+    /// can be set with a [`Separator`].
+    pub fn with_separator<T: Into<Separator>>(mut self, separator: T) -> Self {
+        self.separator = separator.into();
         self
     }
 
@@ -292,7 +400,17 @@ impl CsvParseOptions {
 
     /// Truncate lines that are longer than the schema.
     pub fn with_truncate_ragged_lines(mut self, truncate_ragged_lines: bool) -> Self {
-        self.truncate_ragged_lines = truncate_ragged_lines;
+        self.ragged_lines = if truncate_ragged_lines {
+            RaggedLines::Truncate
+        } else {
+            RaggedLines::Pad
+        };
+        self
+    }
+
+    /// Set how lines with fewer or more fields than the schema are handled.
+    pub fn with_ragged_lines(mut self, ragged_lines: RaggedLines) -> Self {
+        self.ragged_lines = ragged_lines;
         self
     }
 
@@ -328,6 +446,127 @@ pub enum CsvEncoding {
     Utf8,
     /// Utf8 encoding and unknown bytes are replaced with �.
     LossyUtf8,
+    /// ISO-8859-1 (latin-1) encoding, of which the lines are transcoded to utf8 after the file
+    /// is split into lines.
+    Latin1,
+    /// Windows-1252 encoding, of which the lines are transcoded to utf8 after the file is split
+    /// into lines.
+    Windows1252,
+    /// Little-endian UTF-16 encoding, of which the whole file is transcoded to utf8 before
+    /// parsing.
+    Utf16Le,
+    /// Big-endian UTF-16 encoding, of which the whole file is transcoded to utf8 before parsing.
+    Utf16Be,
+}
+
+impl CsvEncoding {
+    /// Whether the line endings, quotes and separators of this encoding are the same bytes as
+    /// in utf8. If so, the raw bytes can be split into lines before they are transcoded.
+    pub fn is_ascii_compatible(&self) -> bool {
+        !matches!(self, CsvEncoding::Utf16Le | CsvEncoding::Utf16Be)
+    }
+
+    /// Transcode `bytes` of this encoding to utf8. Returns `None` if the bytes are already
+    /// (lossy) utf8.
+    pub fn transcode_to_utf8(&self, bytes: &[u8]) -> PolarsResult<Option<Vec<u8>>> {
+        Ok(Some(match self {
+            CsvEncoding::Utf8 | CsvEncoding::LossyUtf8 => return Ok(None),
+            CsvEncoding::Latin1 => transcode_single_byte(bytes, |b| b as char),
+            CsvEncoding::Windows1252 => transcode_single_byte(bytes, windows_1252_to_char),
+            CsvEncoding::Utf16Le => transcode_utf16(bytes, u16::from_le_bytes)?,
+            CsvEncoding::Utf16Be => transcode_utf16(bytes, u16::from_be_bytes)?,
+        }))
+    }
+
+    /// Transcode the whole file to utf8 if its lines can't be found in the raw bytes. Returns the
+    /// transcoded bytes, if any, and the encoding of the lines of the returned bytes, which are
+    /// utf8 if the file was transcoded. The lines of ascii compatible encodings are transcoded
+    /// after the file is split, so that the file doesn't have to be copied.
+    pub fn transcode_file_to_utf8(
+        &self,
+        bytes: &[u8],
+    ) -> PolarsResult<(Option<Vec<u8>>, CsvEncoding)> {
+        if self.is_ascii_compatible() {
+            return Ok((None, *self));
+        }
+        Ok((self.transcode_to_utf8(bytes)?, CsvEncoding::Utf8))
+    }
+}
+
+/// The separator of the fields in a line. This is either a single byte or a short sequence
+/// of bytes, e.g. `||` or `~|~`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Separator {
+    bytes: [u8; Separator::MAX_LEN],
+    len: u8,
+}
+
+impl Separator {
+    /// The maximum number of bytes of a separator.
+    pub const MAX_LEN: usize = 8;
+
+    /// Creates a new `Separator` of a single byte.
+    pub const fn new_single(separator: u8) -> Self {
+        let mut bytes = [0; Self::MAX_LEN];
+        bytes[0] = separator;
+        Self { bytes, len: 1 }
+    }
+
+    /// Creates a new `Separator` from a sequence of bytes.
+    pub fn try_new_multi(separator: &[u8]) -> PolarsResult<Self> {
+        polars_ensure!(
+            !separator.is_empty() && separator.len() <= Self::MAX_LEN,
+            InvalidOperation: "separator must be between 1 and {} bytes long, got {} bytes",
+            Self::MAX_LEN, separator.len()
+        );
+        let mut bytes = [0; Self::MAX_LEN];
+        bytes[..separator.len()].copy_from_slice(separator);
+        Ok(Self {
+            bytes,
+            len: separator.len() as u8,
+        })
+    }
+
+    /// The bytes of the separator.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    /// The first byte of the separator.
+    pub fn first_byte(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    /// The number of bytes of the separator.
+    pub fn n_bytes(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Whether the separator consists of a single byte.
+    pub fn is_single_byte(&self) -> bool {
+        self.len == 1
+    }
+}
+
+impl Default for Separator {
+    fn default() -> Self {
+        Self::new_single(b',')
+    }
+}
+
+impl From<u8> for Separator {
+    fn from(value: u8) -> Self {
+        Self::new_single(value)
+    }
+}
+
+impl TryFrom<&str> for Separator {
+    type Error = PolarsError;
+
+    fn try_from(value: &str) -> PolarsResult<Self> {
+        Self::try_new_multi(value.as_bytes())
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -377,6 +616,9 @@ pub enum NullValues {
     AllColumns(Vec<PlSmallStr>),
     /// Tuples that map column names to null value of that column
     Named(Vec<(PlSmallStr, PlSmallStr)>),
+    /// Tuples that map column names to multiple null values of that column. Columns that are
+    /// not named don't have null values.
+    NamedMultiple(Vec<(PlSmallStr, Vec<PlSmallStr>)>),
 }
 
 impl NullValues {
@@ -392,8 +634,28 @@ impl NullValues {
                 }
                 NullValuesCompiled::Columns(null_values)
             },
+            NullValues::NamedMultiple(v) => {
+                let mut null_values = vec![vec![]; schema.len()];
+                for (name, values) in v {
+                    let i = schema.try_index_of(&name)?;
+                    null_values[i].extend(values);
+                }
+                NullValuesCompiled::ColumnsMultiple(null_values)
+            },
         })
     }
+
+    /// Whether `value` is a null value of the column `name`.
+    pub(super) fn is_null_value_of(&self, name: &str, value: &str) -> bool {
+        match self {
+            NullValues::AllColumnsSingle(v) => v == value,
+            NullValues::AllColumns(v) => v.iter().any(|v| v == value),
+            NullValues::Named(v) => v.iter().any(|(n, v)| n == name && v == value),
+            NullValues::NamedMultiple(v) => v
+                .iter()
+                .any(|(n, values)| n == name && values.iter().any(|v| v == value)),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -404,6 +666,8 @@ pub(super) enum NullValuesCompiled {
     AllColumns(Vec<PlSmallStr>),
     /// A different null value per column, computed from `NullValues::Named`
     Columns(Vec<PlSmallStr>),
+    /// Different null values per column, computed from `NullValues::NamedMultiple`
+    ColumnsMultiple(Vec<Vec<PlSmallStr>>),
 }
 
 impl NullValuesCompiled {
@@ -419,6 +683,10 @@ impl NullValuesCompiled {
                 debug_assert!(index < v.len());
                 v.get_unchecked(index).as_bytes() == field
             },
+            ColumnsMultiple(v) => {
+                debug_assert!(index < v.len());
+                v.get_unchecked(index).iter().any(|v| v.as_bytes() == field)
+            },
         }
     }
 }
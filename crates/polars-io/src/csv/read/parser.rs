@@ -10,7 +10,8 @@ use polars_utils::slice::GetSaferUnchecked;
 use rayon::prelude::*;
 
 use super::buffer::Buffer;
-use super::options::{CommentPrefix, NullValuesCompiled};
+use super::malformed::MalformedRowsCollector;
+use super::options::{CommentPrefix, CsvEncoding, NullValuesCompiled, RaggedLines, Separator};
 use super::splitfields::SplitFields;
 use super::utils::get_file_chunks;
 use crate::path_utils::is_cloud_url;
@@ -20,10 +21,11 @@ use crate::utils::maybe_decompress_bytes;
 /// useful for count(*) queries
 pub fn count_rows(
     path: &Path,
-    separator: u8,
+    separator: Separator,
     quote_char: Option<u8>,
     comment_prefix: Option<&CommentPrefix>,
     eol_char: u8,
+    encoding: CsvEncoding,
     has_header: bool,
 ) -> PolarsResult<usize> {
     let file = if is_cloud_url(path) || config::force_async() {
@@ -48,6 +50,7 @@ pub fn count_rows(
         quote_char,
         comment_prefix,
         eol_char,
+        encoding,
         has_header,
     )
 }
@@ -55,13 +58,22 @@ pub fn count_rows(
 /// Read the number of rows without parsing columns
 /// useful for count(*) queries
 pub fn count_rows_from_slice(
-    mut bytes: &[u8],
-    separator: u8,
+    bytes: &[u8],
+    separator: Separator,
     quote_char: Option<u8>,
     comment_prefix: Option<&CommentPrefix>,
     eol_char: u8,
+    encoding: CsvEncoding,
     has_header: bool,
 ) -> PolarsResult<usize> {
+    // Line endings can only be found in the raw bytes of ascii compatible encodings.
+    let transcoded = if encoding.is_ascii_compatible() {
+        None
+    } else {
+        encoding.transcode_to_utf8(bytes)?
+    };
+    let mut bytes = transcoded.as_deref().unwrap_or(bytes);
+
     for _ in 0..bytes.len() {
         if bytes[0] != eol_char {
             break;
@@ -146,20 +158,20 @@ pub(super) fn next_line_position_naive(input: &[u8], eol_char: u8) -> Option<usi
 pub(super) fn next_line_position(
     mut input: &[u8],
     mut expected_fields: Option<usize>,
-    separator: u8,
+    separator: Separator,
     quote_char: Option<u8>,
     eol_char: u8,
 ) -> Option<usize> {
     fn accept_line(
         line: &[u8],
         expected_fields: usize,
-        separator: u8,
+        separator: Separator,
         eol_char: u8,
         quote_char: Option<u8>,
     ) -> bool {
         let mut count = 0usize;
         for (field, _) in SplitFields::new(line, separator, quote_char, eol_char) {
-            if memchr2_iter(separator, eol_char, field).count() >= expected_fields {
+            if memchr2_iter(separator.first_byte(), eol_char, field).count() >= expected_fields {
                 return false;
             }
             count += 1;
@@ -282,7 +294,7 @@ pub(super) fn get_line_stats(
     n_lines: usize,
     eol_char: u8,
     expected_fields: Option<usize>,
-    separator: u8,
+    separator: Separator,
     quote_char: Option<u8>,
 ) -> Option<(f32, f32)> {
     let mut lengths = Vec::with_capacity(n_lines);
@@ -332,6 +344,7 @@ pub(super) fn get_line_stats(
 ///
 /// This will fail when strings fields are have embedded end line characters.
 /// For instance: "This is a valid field\nI have multiples lines" is a valid string field, that contains multiple lines.
+#[derive(Clone)]
 pub(super) struct SplitLines<'a> {
     v: &'a [u8],
     quote_char: u8,
@@ -444,13 +457,13 @@ pub(super) fn skip_this_line(bytes: &[u8], quote: Option<u8>, eol_char: u8) -> &
 pub(super) fn parse_lines(
     mut bytes: &[u8],
     offset: usize,
-    separator: u8,
+    separator: Separator,
     comment_prefix: Option<&CommentPrefix>,
     quote_char: Option<u8>,
     eol_char: u8,
     missing_is_null: bool,
     ignore_errors: bool,
-    mut truncate_ragged_lines: bool,
+    ragged_lines: RaggedLines,
     null_values: Option<&NullValuesCompiled>,
     projection: &[usize],
     buffers: &mut [Buffer],
@@ -458,17 +471,22 @@ pub(super) fn parse_lines(
     // length of original schema
     schema_len: usize,
     schema: &Schema,
+    malformed_rows: Option<&MalformedRowsCollector>,
 ) -> PolarsResult<usize> {
     assert!(
         !projection.is_empty(),
         "at least one column should be projected"
     );
-    // During projection pushdown we are not checking other csv fields.
-    // This would be very expensive and we don't care as we only want
-    // the projected columns.
-    if projection.len() != schema_len {
-        truncate_ragged_lines = true
-    }
+    // Malformed lines are collected instead of being nulled silently.
+    let ignore_errors = ignore_errors && malformed_rows.is_none();
+    // During projection pushdown we are not checking other csv fields. This would be very
+    // expensive and we don't care as we only want the projected columns. Only the policies
+    // that raise on (or collect) every ragged line need them.
+    let ragged_lines = if ragged_lines == RaggedLines::Pad && projection.len() != schema_len {
+        RaggedLines::Truncate
+    } else {
+        ragged_lines
+    };
 
     // we use the pointers to track the no of bytes read.
     let start = bytes.as_ptr() as usize;
@@ -498,6 +516,8 @@ pub(super) fn parse_lines(
         let mut next_projected = unsafe { projection_iter.next().unwrap_unchecked() };
         let mut processed_fields = 0;
 
+        let line_start = bytes;
+        let mut line_is_malformed = false;
         let mut iter = SplitFields::new(bytes, separator, quote_char, eol_char);
         let mut idx = 0u32;
         let mut read_sol = 0;
@@ -513,8 +533,14 @@ pub(super) fn parse_lines(
                 Some((mut field, needs_escaping)) => {
                     let field_len = field.len();
 
-                    // +1 is the split character that is consumed by the iterator.
-                    read_sol += field_len + 1;
+                    // The separator, or the eol character at the end of the line, is consumed by
+                    // the iterator.
+                    read_sol += field_len
+                        + if iter.finished {
+                            1
+                        } else {
+                            separator.n_bytes()
+                        };
 
                     if idx == next_projected as u32 {
                         // the iterator is finished when it encounters a `\n`
@@ -547,32 +573,51 @@ pub(super) fn parse_lines(
                         }
                         if add_null {
                             buf.add_null(!missing_is_null && field.is_empty())
-                        } else {
+                        } else if let Err(e) =
                             buf.add(field, ignore_errors, needs_escaping, missing_is_null)
-                                .map_err(|e| {
-                                    let bytes_offset = offset + field.as_ptr() as usize - start;
-                                    let unparsable = String::from_utf8_lossy(field);
-                                    let column_name = schema.get_at_index(idx as usize).unwrap().0;
-                                    polars_err!(
-                                        ComputeError:
-                                        "could not parse `{}` as dtype `{}` at column '{}' (column number {})\n\n\
-                                        The current offset in the file is {} bytes.\n\
-                                        \n\
-                                        You might want to try:\n\
-                                        - increasing `infer_schema_length` (e.g. `infer_schema_length=10000`),\n\
-                                        - specifying correct dtype with the `dtypes` argument\n\
-                                        - setting `ignore_errors` to `True`,\n\
-                                        - adding `{}` to the `null_values` list.\n\n\
-                                        Original error: ```{}```",
-                                        &unparsable,
-                                        buf.dtype(),
-                                        column_name,
-                                        idx + 1,
-                                        bytes_offset,
-                                        &unparsable,
-                                        e
-                                    )
-                                })?;
+                        {
+                            let unparsable = String::from_utf8_lossy(field);
+                            let column_name = schema.get_at_index(idx as usize).unwrap().0;
+                            if let Some(malformed_rows) = malformed_rows {
+                                if !line_is_malformed {
+                                    line_is_malformed = true;
+                                    malformed_rows.push(
+                                        offset + line_start.as_ptr() as usize - start,
+                                        line_start,
+                                        quote_char,
+                                        eol_char,
+                                        format!(
+                                            "could not parse `{}` as dtype `{}` at column '{}' (column number {})",
+                                            &unparsable,
+                                            buf.dtype(),
+                                            column_name,
+                                            idx + 1,
+                                        ),
+                                    );
+                                }
+                                buf.add_null(false);
+                            } else {
+                                let bytes_offset = offset + field.as_ptr() as usize - start;
+                                return Err(polars_err!(
+                                    ComputeError:
+                                    "could not parse `{}` as dtype `{}` at column '{}' (column number {})\n\n\
+                                    The current offset in the file is {} bytes.\n\
+                                    \n\
+                                    You might want to try:\n\
+                                    - increasing `infer_schema_length` (e.g. `infer_schema_length=10000`),\n\
+                                    - specifying correct dtype with the `dtypes` argument\n\
+                                    - setting `ignore_errors` to `True`,\n\
+                                    - adding `{}` to the `null_values` list.\n\n\
+                                    Original error: ```{}```",
+                                    &unparsable,
+                                    buf.dtype(),
+                                    column_name,
+                                    idx + 1,
+                                    bytes_offset,
+                                    &unparsable,
+                                    e
+                                ));
+                            }
                         }
                         processed_fields += 1;
 
@@ -580,14 +625,39 @@ pub(super) fn parse_lines(
                         match projection_iter.next() {
                             Some(p) => next_projected = p,
                             None => {
+                                // The fields after the last projected column are only counted
+                                // if extra fields are not ignored. Counting them reads the
+                                // rest of the line, so it doesn't have to be skipped after.
+                                let mut n_fields = idx as usize + 1;
+                                if !iter.finished && ragged_lines != RaggedLines::Truncate {
+                                    while let Some((field, _)) = iter.next() {
+                                        n_fields += 1;
+                                        read_sol += field.len()
+                                            + if iter.finished {
+                                                1
+                                            } else {
+                                                separator.n_bytes()
+                                            };
+                                    }
+                                }
+                                if let Some(reason) = ragged_lines.check(n_fields, schema_len) {
+                                    let Some(malformed_rows) = malformed_rows else {
+                                        return Err(ragged_line_err(n_fields, schema_len));
+                                    };
+                                    if !line_is_malformed {
+                                        line_is_malformed = true;
+                                        malformed_rows.push(
+                                            offset + line_start.as_ptr() as usize - start,
+                                            line_start,
+                                            quote_char,
+                                            eol_char,
+                                            reason.to_string(),
+                                        );
+                                    }
+                                }
                                 if bytes.get(read_sol - 1) == Some(&eol_char) {
                                     bytes = &bytes[read_sol..];
                                 } else {
-                                    if !truncate_ragged_lines && read_sol < bytes.len() {
-                                        polars_bail!(ComputeError: r#"found more fields than defined in 'Schema'
-
-Consider setting 'truncate_ragged_lines={}'."#, polars_error::constants::TRUE)
-                                    }
                                     let bytes_rem = skip_this_line(
                                         unsafe { bytes.get_unchecked_release(read_sol - 1..) },
                                         quote_char,
@@ -607,6 +677,22 @@ Consider setting 'truncate_ragged_lines={}'."#, polars_error::constants::TRUE)
         // there can be lines that miss fields (also the comma values)
         // this means the splitter won't process them.
         // We traverse them to read them as null values.
+        if processed_fields < projection.len() {
+            if let Some(reason) = ragged_lines.check(idx as usize, schema_len) {
+                let Some(malformed_rows) = malformed_rows else {
+                    return Err(ragged_line_err(idx as usize, schema_len));
+                };
+                if !line_is_malformed {
+                    malformed_rows.push(
+                        offset + line_start.as_ptr() as usize - start,
+                        line_start,
+                        quote_char,
+                        eol_char,
+                        reason.to_string(),
+                    );
+                }
+            }
+        }
         while processed_fields < projection.len() {
             debug_assert!(processed_fields < buffers.len());
             let buf = unsafe {
@@ -620,6 +706,18 @@ Consider setting 'truncate_ragged_lines={}'."#, polars_error::constants::TRUE)
     }
 }
 
+fn ragged_line_err(n_fields: usize, schema_len: usize) -> PolarsError {
+    if n_fields > schema_len {
+        polars_err!(ComputeError: r#"found more fields than defined in 'Schema'
+
+Consider setting 'truncate_ragged_lines={}'."#, polars_error::constants::TRUE)
+    } else {
+        polars_err!(ComputeError: r#"found fewer fields than defined in 'Schema'
+
+Consider using `RaggedLines::Pad` to read the missing fields as null."#)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::SplitLines;
@@ -1,5 +1,6 @@
 pub(super) mod batched;
 
+use std::borrow::Cow;
 use std::fmt;
 
 use polars_core::config::verbose;
@@ -12,10 +13,14 @@ use polars_utils::flatten;
 use rayon::prelude::*;
 
 use super::buffer::init_buffers;
-use super::options::{CommentPrefix, CsvEncoding, NullValues, NullValuesCompiled};
+use super::malformed::{MalformedRowsCollector, MalformedRowsSink};
+use super::options::{
+    CommentPrefix, CsvEncoding, InferSchemaPolicy, NullValues, NullValuesCompiled, RaggedLines,
+    Separator,
+};
 use super::parser::{
     get_line_stats, is_comment_line, next_line_position, next_line_position_naive, parse_lines,
-    skip_bom, skip_line_ending, skip_this_line,
+    skip_bom, skip_line_ending, skip_this_line, SplitLines,
 };
 use super::schema_inference::{check_decimal_comma, infer_file_schema};
 #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
@@ -112,7 +117,7 @@ pub(crate) struct CoreReader<'a> {
     encoding: CsvEncoding,
     n_threads: Option<usize>,
     has_header: bool,
-    separator: u8,
+    separator: Separator,
     sample_size: usize,
     chunk_size: usize,
     low_memory: bool,
@@ -125,7 +130,8 @@ pub(crate) struct CoreReader<'a> {
     predicate: Option<Arc<dyn PhysicalIoExpr>>,
     to_cast: Vec<Field>,
     row_index: Option<RowIndex>,
-    truncate_ragged_lines: bool,
+    ragged_lines: RaggedLines,
+    malformed_rows: Option<MalformedRowsSink>,
 }
 
 impl<'a> fmt::Debug for CoreReader<'a> {
@@ -146,7 +152,7 @@ impl<'a> CoreReader<'a> {
         skip_rows: usize,
         mut projection: Option<Vec<usize>>,
         max_records: Option<usize>,
-        separator: Option<u8>,
+        separator: Option<Separator>,
         has_header: bool,
         ignore_errors: bool,
         schema: Option<SchemaRef>,
@@ -169,12 +175,18 @@ impl<'a> CoreReader<'a> {
         row_index: Option<RowIndex>,
         try_parse_dates: bool,
         raise_if_empty: bool,
-        truncate_ragged_lines: bool,
+        ragged_lines: RaggedLines,
         decimal_comma: bool,
+        malformed_rows: Option<MalformedRowsSink>,
+        infer_schema_policy: &InferSchemaPolicy,
     ) -> PolarsResult<CoreReader<'a>> {
-        let separator = separator.unwrap_or(b',');
+        let separator = separator.unwrap_or_default();
 
         check_decimal_comma(decimal_comma, separator)?;
+        polars_ensure!(
+            ragged_lines != RaggedLines::MalformedRows || malformed_rows.is_some(),
+            InvalidOperation: "collecting ragged lines requires a 'MalformedRowsSink'"
+        );
         #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
         let mut reader_bytes = reader_bytes;
 
@@ -190,14 +202,32 @@ impl<'a> CoreReader<'a> {
         // again after decompression.
         #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
         {
-            let total_n_rows =
-                n_rows.map(|n| skip_rows + (has_header as usize) + skip_rows_after_header + n);
+            // The lines can only be found in the decompressed bytes if they are not transcoded.
+            let total_n_rows = n_rows
+                .filter(|_| encoding.is_ascii_compatible())
+                .map(|n| skip_rows + (has_header as usize) + skip_rows_after_header + n);
             if let Some(b) =
                 decompress(&reader_bytes, total_n_rows, separator, quote_char, eol_char)
             {
                 reader_bytes = ReaderBytes::Owned(b);
             }
         }
+        // The lines of ascii compatible encodings are transcoded after the file is split, so
+        // that the file can stay memory mapped. The line numbers of malformed rows are counted
+        // in the parsed bytes, so those files are transcoded as a whole.
+        let (transcoded, encoding) = match encoding.transcode_file_to_utf8(&reader_bytes)? {
+            (None, encoding) if malformed_rows.is_some() => {
+                let transcoded = encoding.transcode_to_utf8(&reader_bytes)?;
+                let encoding = if transcoded.is_some() {
+                    CsvEncoding::Utf8
+                } else {
+                    encoding
+                };
+                (transcoded, encoding)
+            },
+            transcoded => transcoded,
+        };
+        let reader_bytes = transcoded.map_or(reader_bytes, ReaderBytes::Owned);
 
         let mut schema = match schema {
             Some(schema) => schema,
@@ -213,11 +243,13 @@ impl<'a> CoreReader<'a> {
                     comment_prefix.as_ref(),
                     quote_char,
                     eol_char,
+                    encoding,
                     null_values.as_ref(),
                     try_parse_dates,
                     raise_if_empty,
                     &mut n_threads,
                     decimal_comma,
+                    infer_schema_policy,
                 )?;
                 Arc::new(inferred_schema)
             },
@@ -265,8 +297,9 @@ impl<'a> CoreReader<'a> {
             predicate,
             to_cast,
             row_index,
-            truncate_ragged_lines,
+            ragged_lines,
             decimal_comma,
+            malformed_rows,
         })
     }
 
@@ -468,12 +501,36 @@ impl<'a> CoreReader<'a> {
             .unwrap_or_else(|| Ok((0..self.schema.len()).collect()))
     }
 
+    /// The first `n_rows` lines of `bytes` in utf8. Only these lines are transcoded, as the
+    /// remaining bytes may be the rest of the file.
+    fn transcode_remaining_lines<'b>(
+        &self,
+        bytes: &'b [u8],
+        n_rows: usize,
+    ) -> PolarsResult<Cow<'b, [u8]>> {
+        if matches!(self.encoding, CsvEncoding::Utf8 | CsvEncoding::LossyUtf8) {
+            return Ok(Cow::Borrowed(bytes));
+        }
+        let lines = SplitLines::new(bytes, self.quote_char.unwrap_or(b'"'), self.eol_char)
+            .filter(|line| !line.is_empty() && !is_comment_line(line, self.comment_prefix.as_ref()))
+            .take(n_rows);
+        let end = lines.last().map_or(0, |line| {
+            let end = line.as_ptr() as usize + line.len() - bytes.as_ptr() as usize;
+            // Include the line ending.
+            std::cmp::min(end + 1, bytes.len())
+        });
+        let (transcoded, ..) = transcode_lines(bytes, 0, end, None, self.encoding)?;
+        Ok(transcoded)
+    }
+
     fn parse_csv(
         &mut self,
         mut n_threads: usize,
         bytes: &[u8],
         predicate: Option<&Arc<dyn PhysicalIoExpr>>,
+        malformed_rows: Option<&MalformedRowsCollector>,
     ) -> PolarsResult<DataFrame> {
+        let file_bytes = bytes;
         let logging = verbose();
         let (file_chunks, chunk_size, total_rows, starting_point_offset, bytes, remaining_bytes) =
             self.determine_file_chunks_and_statistics(&mut n_threads, bytes, logging)?;
@@ -509,6 +566,14 @@ impl<'a> CoreReader<'a> {
                 file_chunks
                     .into_par_iter()
                     .map(|(bytes_offset_thread, stop_at_nbytes)| {
+                        let (bytes, bytes_offset_thread, stop_at_nbytes, starting_point_offset) =
+                            transcode_lines(
+                                bytes,
+                                bytes_offset_thread,
+                                stop_at_nbytes,
+                                starting_point_offset,
+                                self.encoding,
+                            )?;
                         let schema = self.schema.as_ref();
                         let ignore_errors = self.ignore_errors;
                         let projection = &projection;
@@ -543,13 +608,14 @@ impl<'a> CoreReader<'a> {
                                 self.eol_char,
                                 self.missing_is_null,
                                 ignore_errors,
-                                self.truncate_ragged_lines,
+                                self.ragged_lines,
                                 self.null_values.as_ref(),
                                 projection,
                                 &mut buffers,
                                 chunk_size,
                                 self.schema.len(),
                                 &self.schema,
+                                malformed_rows,
                             )?;
 
                             let columns = buffers
@@ -608,11 +674,12 @@ impl<'a> CoreReader<'a> {
                             self.encoding,
                             self.null_values.as_ref(),
                             self.missing_is_null,
-                            self.truncate_ragged_lines,
+                            self.ragged_lines,
                             usize::MAX,
                             stop_at_nbytes,
                             starting_point_offset,
                             self.decimal_comma,
+                            malformed_rows,
                         )?;
 
                         cast_columns(&mut df, &self.to_cast, false, self.ignore_errors)?;
@@ -639,22 +706,29 @@ impl<'a> CoreReader<'a> {
                                 self.decimal_comma,
                             )?;
 
-                            parse_lines(
+                            let offset =
+                                remaining_bytes.as_ptr() as usize - file_bytes.as_ptr() as usize;
+                            let remaining_bytes = self.transcode_remaining_lines(
                                 remaining_bytes,
-                                0,
+                                remaining_rows,
+                            )?;
+                            parse_lines(
+                                &remaining_bytes,
+                                offset,
                                 self.separator,
                                 self.comment_prefix.as_ref(),
                                 self.quote_char,
                                 self.eol_char,
                                 self.missing_is_null,
                                 self.ignore_errors,
-                                self.truncate_ragged_lines,
+                                self.ragged_lines,
                                 self.null_values.as_ref(),
                                 &projection,
                                 &mut buffers,
                                 remaining_rows - 1,
                                 self.schema.len(),
                                 self.schema.as_ref(),
+                                malformed_rows,
                             )?;
 
                             let columns = buffers
@@ -687,7 +761,19 @@ impl<'a> CoreReader<'a> {
 
         let reader_bytes = self.reader_bytes.take().unwrap();
 
-        let mut df = self.parse_csv(n_threads, &reader_bytes, predicate.as_ref())?;
+        let mut malformed_rows = self
+            .malformed_rows
+            .as_ref()
+            .map(|_| MalformedRowsCollector::default());
+        let mut df = self.parse_csv(
+            n_threads,
+            &reader_bytes,
+            predicate.as_ref(),
+            malformed_rows.as_ref(),
+        )?;
+        if let (Some(sink), Some(collector)) = (&self.malformed_rows, &mut malformed_rows) {
+            collector.flush(&reader_bytes, self.eol_char, sink);
+        }
 
         // if multi-threaded the n_rows was probabilistically determined.
         // Let's slice to correct number of rows if possible.
@@ -700,10 +786,33 @@ impl<'a> CoreReader<'a> {
     }
 }
 
+/// The lines of a chunk in utf8, with the offsets of those lines in the bytes and the offset of
+/// the start of the file in the bytes.
+type TranscodedLines<'a> = (Cow<'a, [u8]>, usize, usize, Option<usize>);
+
+/// The lines `start..stop` of `bytes` in utf8. The lines of ascii compatible encodings are
+/// transcoded after the file is split, so that only a chunk of the file is copied at a time.
+fn transcode_lines(
+    bytes: &[u8],
+    start: usize,
+    stop: usize,
+    starting_point_offset: Option<usize>,
+    encoding: CsvEncoding,
+) -> PolarsResult<TranscodedLines<'_>> {
+    Ok(match encoding.transcode_to_utf8(&bytes[start..stop])? {
+        Some(transcoded) => {
+            let stop = transcoded.len();
+            let starting_point_offset = starting_point_offset.map(|offset| offset + start);
+            (Cow::Owned(transcoded), 0, stop, starting_point_offset)
+        },
+        None => (Cow::Borrowed(bytes), start, stop, starting_point_offset),
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 fn read_chunk(
     bytes: &[u8],
-    separator: u8,
+    separator: Separator,
     schema: &Schema,
     ignore_errors: bool,
     projection: &[usize],
@@ -715,12 +824,20 @@ fn read_chunk(
     encoding: CsvEncoding,
     null_values: Option<&NullValuesCompiled>,
     missing_is_null: bool,
-    truncate_ragged_lines: bool,
+    ragged_lines: RaggedLines,
     chunk_size: usize,
     stop_at_nbytes: usize,
     starting_point_offset: Option<usize>,
     decimal_comma: bool,
+    malformed_rows: Option<&MalformedRowsCollector>,
 ) -> PolarsResult<DataFrame> {
+    let (bytes, bytes_offset_thread, stop_at_nbytes, starting_point_offset) = transcode_lines(
+        bytes,
+        bytes_offset_thread,
+        stop_at_nbytes,
+        starting_point_offset,
+        encoding,
+    )?;
     let mut read = bytes_offset_thread;
     // There's an off-by-one error somewhere in the reading code, where it reads
     // one more item than the requested capacity. Given the batch sizes are
@@ -754,13 +871,14 @@ fn read_chunk(
             eol_char,
             missing_is_null,
             ignore_errors,
-            truncate_ragged_lines,
+            ragged_lines,
             null_values,
             projection,
             &mut buffers,
             chunk_size,
             schema.len(),
             schema,
+            malformed_rows,
         )?;
     }
 
@@ -10,7 +10,10 @@ use polars_utils::IdxSize;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use super::{cast_columns, read_chunk, CoreReader};
-use crate::csv::read::options::{CommentPrefix, CsvEncoding, NullValuesCompiled};
+use crate::csv::read::malformed::{MalformedRowsCollector, MalformedRowsSink};
+use crate::csv::read::options::{
+    CommentPrefix, CsvEncoding, NullValuesCompiled, RaggedLines, Separator,
+};
 use crate::csv::read::parser::next_line_position;
 use crate::csv::read::CsvReader;
 use crate::mmap::{MmapBytesReader, ReaderBytes};
@@ -25,7 +28,7 @@ pub(crate) fn get_file_chunks_iterator(
     chunk_size: usize,
     bytes: &[u8],
     expected_fields: usize,
-    separator: u8,
+    separator: Separator,
     quote_char: Option<u8>,
     eol_char: u8,
 ) {
@@ -61,7 +64,7 @@ struct ChunkOffsetIter<'a> {
     // not a promise, but something we want
     rows_per_batch: usize,
     expected_fields: usize,
-    separator: u8,
+    separator: Separator,
     quote_char: Option<u8>,
     eol_char: u8,
 }
@@ -169,7 +172,7 @@ impl<'a> CoreReader<'a> {
             missing_is_null: self.missing_is_null,
             to_cast: self.to_cast,
             ignore_errors: self.ignore_errors,
-            truncate_ragged_lines: self.truncate_ragged_lines,
+            ragged_lines: self.ragged_lines,
             remaining: self.n_rows.unwrap_or(usize::MAX),
             encoding: self.encoding,
             separator: self.separator,
@@ -177,6 +180,8 @@ impl<'a> CoreReader<'a> {
             rows_read: 0,
             _cat_lock,
             decimal_comma: self.decimal_comma,
+            malformed_rows: self.malformed_rows,
+            malformed_rows_collector: Default::default(),
         })
     }
 }
@@ -194,12 +199,12 @@ pub struct BatchedCsvReader<'a> {
     eol_char: u8,
     null_values: Option<NullValuesCompiled>,
     missing_is_null: bool,
-    truncate_ragged_lines: bool,
+    ragged_lines: RaggedLines,
     to_cast: Vec<Field>,
     ignore_errors: bool,
     remaining: usize,
     encoding: CsvEncoding,
-    separator: u8,
+    separator: Separator,
     schema: SchemaRef,
     rows_read: IdxSize,
     #[cfg(feature = "dtype-categorical")]
@@ -207,13 +212,32 @@ pub struct BatchedCsvReader<'a> {
     #[cfg(not(feature = "dtype-categorical"))]
     _cat_lock: Option<u8>,
     decimal_comma: bool,
+    malformed_rows: Option<MalformedRowsSink>,
+    malformed_rows_collector: MalformedRowsCollector,
 }
 
 impl<'a> BatchedCsvReader<'a> {
+    /// Set the number of rows the next batches strive to have.
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size;
+        let iter = &mut self.file_chunks_iter;
+        iter.rows_per_batch = chunk_size;
+        // The offsets that were already found have the old chunk size.
+        if let Some((start, _)) = iter.offsets.front() {
+            iter.last_offset = *start;
+            iter.offsets.clear();
+        }
+    }
+
     pub fn next_batches(&mut self, n: usize) -> PolarsResult<Option<Vec<DataFrame>>> {
         if n == 0 || self.remaining == 0 {
             return Ok(None);
         }
+        // Don't parse more rows than are still requested.
+        if self.remaining < self.chunk_size {
+            self.set_chunk_size(self.remaining);
+        }
+        let n = n.min(self.remaining.div_ceil(self.chunk_size));
 
         // get next `n` offset positions.
         let file_chunks_iter = (&mut self.file_chunks_iter).take(n);
@@ -248,11 +272,14 @@ impl<'a> BatchedCsvReader<'a> {
                         self.encoding,
                         self.null_values.as_ref(),
                         self.missing_is_null,
-                        self.truncate_ragged_lines,
+                        self.ragged_lines,
                         self.chunk_size,
                         stop_at_nbytes,
                         self.starting_point_offset,
                         self.decimal_comma,
+                        self.malformed_rows
+                            .as_ref()
+                            .map(|_| &self.malformed_rows_collector),
                     )?;
 
                     cast_columns(&mut df, &self.to_cast, false, self.ignore_errors)?;
@@ -266,6 +293,11 @@ impl<'a> BatchedCsvReader<'a> {
         })?;
         self.file_chunks.clear();
 
+        if let Some(sink) = &self.malformed_rows {
+            self.malformed_rows_collector
+                .flush(&self.reader_bytes, self.eol_char, sink);
+        }
+
         if self.row_index.is_some() {
             update_row_counts2(&mut chunks, self.rows_read)
         }
@@ -293,6 +325,10 @@ pub struct OwnedBatchedCsvReader {
 }
 
 impl OwnedBatchedCsvReader {
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.batched_reader.set_chunk_size(chunk_size)
+    }
+
     pub fn next_batches(&mut self, n: usize) -> PolarsResult<Option<Vec<DataFrame>>> {
         self.batched_reader.next_batches(n)
     }
@@ -148,8 +148,10 @@ impl<R: MmapBytesReader> CsvReader<R> {
             self.options.row_index.clone(),
             parse_options.try_parse_dates,
             self.options.raise_if_empty,
-            parse_options.truncate_ragged_lines,
+            parse_options.ragged_lines,
             parse_options.decimal_comma,
+            self.options.malformed_rows.clone(),
+            &self.options.infer_schema_policy,
         )
     }
 
@@ -235,11 +237,13 @@ impl CsvReader<Box<dyn MmapBytesReader>> {
                     parse_options.comment_prefix.as_ref(),
                     parse_options.quote_char,
                     parse_options.eol_char,
+                    parse_options.encoding,
                     parse_options.null_values.as_ref(),
                     parse_options.try_parse_dates,
                     self.options.raise_if_empty,
                     &mut self.options.n_threads,
                     parse_options.decimal_comma,
+                    &self.options.infer_schema_policy,
                 )?;
                 let schema = Arc::new(inferred_schema);
                 Ok(to_batched_owned(self.with_schema(schema)))
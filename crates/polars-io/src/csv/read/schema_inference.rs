@@ -1,4 +1,7 @@
 use std::borrow::Cow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
 
 use polars_core::config::verbose;
 use polars_core::prelude::*;
@@ -9,7 +12,10 @@ use polars_time::prelude::string::Pattern;
 use polars_utils::format_pl_smallstr;
 use polars_utils::slice::GetSaferUnchecked;
 
-use super::options::{CommentPrefix, CsvEncoding, NullValues};
+use super::options::{
+    CommentPrefix, CsvEncoding, InferSchemaPolicy, InferSchemaSampling, IntFloatConflict,
+    NullValues, Separator,
+};
 use super::parser::{is_comment_line, skip_bom, skip_line_ending, SplitLines};
 use super::splitfields::SplitFields;
 use super::CsvReadOptions;
@@ -47,6 +53,14 @@ impl SchemaInferenceResult {
         let raise_if_empty = options.raise_if_empty;
         let mut n_threads = options.n_threads;
         let decimal_comma = parse_options.decimal_comma;
+        let infer_schema_policy = &options.infer_schema_policy;
+
+        // The total size must be of the same bytes that the schema is inferred from.
+        let (transcoded, encoding) = parse_options
+            .encoding
+            .transcode_file_to_utf8(reader_bytes)?;
+        let transcoded = transcoded.map(ReaderBytes::Owned);
+        let reader_bytes = transcoded.as_ref().unwrap_or(reader_bytes);
 
         let bytes_total = reader_bytes.len();
 
@@ -61,11 +75,13 @@ impl SchemaInferenceResult {
             comment_prefix,
             quote_char,
             eol_char,
+            encoding,
             null_values.as_ref(),
             try_parse_dates,
             raise_if_empty,
             &mut n_threads,
             decimal_comma,
+            infer_schema_policy,
         )?;
 
         let this = Self {
@@ -84,6 +100,13 @@ impl SchemaInferenceResult {
         self
     }
 
+    /// Set the total size of the file. This is needed to estimate the number of rows if the
+    /// schema was inferred from a prefix of the file.
+    pub fn with_bytes_total(mut self, bytes_total: usize) -> Self {
+        self.bytes_total = bytes_total;
+        self
+    }
+
     pub fn get_inferred_schema(&self) -> SchemaRef {
         self.inferred_schema.clone()
     }
@@ -100,6 +123,95 @@ impl CsvReadOptions {
     }
 }
 
+/// A column of which the data type was widened during schema inference, because it contains
+/// values of different data types.
+#[derive(Clone, Debug)]
+struct WidenedColumn {
+    name: PlSmallStr,
+    observed: Vec<DataType>,
+    dtype: DataType,
+}
+
+/// Reports the columns of which the data type was widened during schema inference, e.g. an
+/// integer column that also contains floats, or a numeric column that contains a string.
+///
+/// Clones share the same report, so a clone can be passed to the reader and inspected after
+/// the schema is inferred. The report holds the result of the last schema inference.
+#[derive(Clone, Default)]
+pub struct SchemaInferenceReport {
+    columns: Arc<Mutex<Vec<WidenedColumn>>>,
+}
+
+impl SchemaInferenceReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of widened columns.
+    pub fn len(&self) -> usize {
+        self.columns.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the widened columns as a [`DataFrame`] with the columns `column`, `observed` (the
+    /// data types of the values in the column) and `dtype` (the inferred data type).
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let columns = self.columns.lock().unwrap();
+        let column = columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+        let observed = columns
+            .iter()
+            .map(|c| {
+                let dtypes = c
+                    .observed
+                    .iter()
+                    .map(|dt| dt.to_string())
+                    .collect::<Vec<_>>();
+                Series::new(PlSmallStr::EMPTY, dtypes)
+            })
+            .collect::<Vec<_>>();
+        let dtype = columns
+            .iter()
+            .map(|c| c.dtype.to_string())
+            .collect::<Vec<_>>();
+
+        DataFrame::new(vec![
+            Series::new(PlSmallStr::from_static("column"), column),
+            Series::new(PlSmallStr::from_static("observed"), observed),
+            Series::new(PlSmallStr::from_static("dtype"), dtype),
+        ])
+    }
+
+    fn set(&self, columns: Vec<WidenedColumn>) {
+        *self.columns.lock().unwrap() = columns;
+    }
+}
+
+impl fmt::Debug for SchemaInferenceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchemaInferenceReport")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+// Reports are compared by identity, as their content changes while reading.
+impl PartialEq for SchemaInferenceReport {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.columns, &other.columns)
+    }
+}
+
+impl Eq for SchemaInferenceReport {}
+
+impl Hash for SchemaInferenceReport {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.columns).hash(state)
+    }
+}
+
 pub fn finish_infer_field_schema(possibilities: &PlHashSet<DataType>) -> DataType {
     // determine data type based on possible types
     // if there are incompatible types, use DataType::String
@@ -116,6 +228,17 @@ pub fn finish_infer_field_schema(possibilities: &PlHashSet<DataType>) -> DataTyp
     }
 }
 
+fn finish_infer_field_schema_with_policy(
+    possibilities: &PlHashSet<DataType>,
+    int_float_conflict: IntFloatConflict,
+) -> DataType {
+    match int_float_conflict {
+        IntFloatConflict::Float => finish_infer_field_schema(possibilities),
+        IntFloatConflict::String if possibilities.len() > 1 => DataType::String,
+        IntFloatConflict::String => finish_infer_field_schema(possibilities),
+    }
+}
+
 /// Infer the data type of a record
 pub fn infer_field_schema(string: &str, try_parse_dates: bool, decimal_comma: bool) -> DataType {
     // when quoting is enabled in the reader, these quotes aren't escaped, we default to
@@ -184,17 +307,23 @@ pub fn infer_field_schema(string: &str, try_parse_dates: bool, decimal_comma: bo
 #[inline]
 fn parse_bytes_with_encoding(bytes: &[u8], encoding: CsvEncoding) -> PolarsResult<Cow<str>> {
     Ok(match encoding {
-        CsvEncoding::Utf8 => simdutf8::basic::from_utf8(bytes)
+        CsvEncoding::LossyUtf8 => String::from_utf8_lossy(bytes),
+        CsvEncoding::Latin1 | CsvEncoding::Windows1252 if !bytes.is_ascii() => {
+            let transcoded = encoding.transcode_to_utf8(bytes)?.unwrap();
+            // SAFETY: the bytes are transcoded to utf8.
+            unsafe { String::from_utf8_unchecked(transcoded) }.into()
+        },
+        // The files of the other encodings are transcoded to utf8 before parsing.
+        _ => simdutf8::basic::from_utf8(bytes)
             .map_err(|_| polars_err!(ComputeError: "invalid utf-8 sequence"))?
             .into(),
-        CsvEncoding::LossyUtf8 => String::from_utf8_lossy(bytes),
     })
 }
 
 #[allow(clippy::too_many_arguments)]
 fn infer_file_schema_inner(
     reader_bytes: &ReaderBytes,
-    separator: u8,
+    separator: Separator,
     max_read_rows: Option<usize>,
     has_header: bool,
     schema_overwrite: Option<&Schema>,
@@ -205,19 +334,25 @@ fn infer_file_schema_inner(
     comment_prefix: Option<&CommentPrefix>,
     quote_char: Option<u8>,
     eol_char: u8,
+    encoding: CsvEncoding,
     null_values: Option<&NullValues>,
     try_parse_dates: bool,
     recursion_count: u8,
     raise_if_empty: bool,
     n_threads: &mut Option<usize>,
     decimal_comma: bool,
+    infer_schema_policy: &InferSchemaPolicy,
 ) -> PolarsResult<(Schema, usize, usize)> {
     // keep track so that we can determine the amount of bytes read
     let start_ptr = reader_bytes.as_ptr() as usize;
 
     // We use lossy utf8 here because we don't want the schema inference to fail on utf8.
-    // It may later.
-    let encoding = CsvEncoding::LossyUtf8;
+    // It may later. The fields of the other encodings are transcoded as they are parsed, so
+    // that only the lines that are read are transcoded.
+    let encoding = match encoding {
+        CsvEncoding::Utf8 => CsvEncoding::LossyUtf8,
+        encoding => encoding,
+    };
 
     let bytes = skip_line_ending(skip_bom(reader_bytes), eol_char);
     if raise_if_empty {
@@ -306,12 +441,14 @@ fn infer_file_schema_inner(
             comment_prefix,
             quote_char,
             eol_char,
+            encoding,
             null_values,
             try_parse_dates,
             recursion_count + 1,
             raise_if_empty,
             n_threads,
             decimal_comma,
+            infer_schema_policy,
         );
     } else if !raise_if_empty {
         return Ok((Schema::default(), 0, 0));
@@ -333,12 +470,24 @@ fn infer_file_schema_inner(
     let mut rows_count = 0;
     let mut fields = Vec::with_capacity(header_length);
 
+    // With uniform sampling only every `stride`-th line is used for inference, such that at
+    // most `max_read_rows` lines are used.
+    let stride = match (infer_schema_policy.sampling, max_read_rows) {
+        (InferSchemaSampling::Uniform, Some(max_read_rows)) if max_read_rows > 0 => {
+            let n_lines = lines.clone().skip(skip_rows_after_header).count();
+            n_lines.div_ceil(max_read_rows).max(1)
+        },
+        _ => 1,
+    };
+
     // needed to prevent ownership going into the iterator loop
     let records_ref = &mut lines;
 
     let mut end_ptr = start_ptr;
-    for mut line in records_ref
+    for (line_idx, mut line) in records_ref
         .take(match max_read_rows {
+            // All lines are visited to sample them uniformly.
+            Some(_) if stride > 1 => usize::MAX,
             Some(max_read_rows) => {
                 if max_read_rows <= (usize::MAX - skip_rows_after_header) {
                     // read skip_rows_after_header more rows for inferring
@@ -352,12 +501,13 @@ fn infer_file_schema_inner(
             None => usize::MAX,
         })
         .skip(skip_rows_after_header)
+        .enumerate()
     {
         rows_count += 1;
         // keep track so that we can determine the amount of bytes read
         end_ptr = line.as_ptr() as usize + line.len();
 
-        if line.is_empty() {
+        if line.is_empty() || line_idx % stride != 0 {
             continue;
         }
 
@@ -388,38 +538,15 @@ fn infer_file_schema_inner(
                         slice
                     };
                     let s = parse_bytes_with_encoding(slice_escaped, encoding)?;
-                    let dtype = match &null_values {
-                        None => Some(infer_field_schema(&s, try_parse_dates, decimal_comma)),
-                        Some(NullValues::AllColumns(names)) => {
-                            if !names.iter().any(|nv| nv == s.as_ref()) {
-                                Some(infer_field_schema(&s, try_parse_dates, decimal_comma))
-                            } else {
-                                None
-                            }
-                        },
-                        Some(NullValues::AllColumnsSingle(name)) => {
-                            if s.as_ref() != name.as_str() {
-                                Some(infer_field_schema(&s, try_parse_dates, decimal_comma))
-                            } else {
-                                None
-                            }
-                        },
-                        Some(NullValues::Named(names)) => {
-                            // SAFETY:
-                            // we iterate over headers length.
-                            let current_name = unsafe { headers.get_unchecked_release(i) };
-                            let null_name = &names.iter().find(|name| name.0 == current_name);
-
-                            if let Some(null_name) = null_name {
-                                if null_name.1.as_str() != s.as_ref() {
-                                    Some(infer_field_schema(&s, try_parse_dates, decimal_comma))
-                                } else {
-                                    None
-                                }
-                            } else {
-                                Some(infer_field_schema(&s, try_parse_dates, decimal_comma))
-                            }
-                        },
+                    // SAFETY:
+                    // we iterate over headers length.
+                    let current_name = unsafe { headers.get_unchecked_release(i) };
+                    let is_null = null_values
+                        .is_some_and(|null_values| null_values.is_null_value_of(current_name, &s));
+                    let dtype = if is_null {
+                        None
+                    } else {
+                        Some(infer_field_schema(&s, try_parse_dates, decimal_comma))
                     };
                     if let Some(dtype) = dtype {
                         if matches!(&dtype, DataType::String)
@@ -446,6 +573,7 @@ fn infer_file_schema_inner(
     }
 
     // build schema from inference results
+    let mut widened_columns = vec![];
     for i in 0..header_length {
         let field_name = &headers[i];
 
@@ -466,7 +594,19 @@ fn infer_file_schema_inner(
         }
 
         let possibilities = &column_types[i];
-        let dtype = finish_infer_field_schema(possibilities);
+        let dtype = finish_infer_field_schema_with_policy(
+            possibilities,
+            infer_schema_policy.int_float_conflict,
+        );
+        if possibilities.len() > 1 {
+            let mut observed = possibilities.iter().cloned().collect::<Vec<_>>();
+            observed.sort_by_cached_key(|dt| dt.to_string());
+            widened_columns.push(WidenedColumn {
+                name: field_name.clone(),
+                observed,
+                dtype: dtype.clone(),
+            });
+        }
         fields.push(Field::new(field_name.clone(), dtype));
     }
     // if there is a single line after the header without an eol
@@ -491,21 +631,26 @@ fn infer_file_schema_inner(
             comment_prefix,
             quote_char,
             eol_char,
+            encoding,
             null_values,
             try_parse_dates,
             recursion_count + 1,
             raise_if_empty,
             n_threads,
             decimal_comma,
+            infer_schema_policy,
         );
     }
 
+    if let Some(report) = &infer_schema_policy.report {
+        report.set(widened_columns);
+    }
     Ok((Schema::from_iter(fields), rows_count, end_ptr - start_ptr))
 }
 
-pub(super) fn check_decimal_comma(decimal_comma: bool, separator: u8) -> PolarsResult<()> {
+pub(super) fn check_decimal_comma(decimal_comma: bool, separator: Separator) -> PolarsResult<()> {
     if decimal_comma {
-        polars_ensure!(b',' != separator, InvalidOperation: "'decimal_comma' argument cannot be combined with ',' separator")
+        polars_ensure!(!separator.as_bytes().contains(&b','), InvalidOperation: "'decimal_comma' argument cannot be combined with ',' separator")
     }
     Ok(())
 }
@@ -513,7 +658,9 @@ pub(super) fn check_decimal_comma(decimal_comma: bool, separator: u8) -> PolarsR
 /// Infer the schema of a CSV file by reading through the first n rows of the file,
 /// with `max_read_rows` controlling the maximum number of rows to read.
 ///
-/// If `max_read_rows` is not set, the whole file is read to infer its schema.
+/// If `max_read_rows` is not set, the whole file is read to infer its schema. The files of
+/// encodings that aren't ascii compatible are transcoded to utf8 as a whole, the lines of the
+/// other encodings as they are read.
 ///
 /// Returns
 ///     - inferred schema
@@ -522,7 +669,7 @@ pub(super) fn check_decimal_comma(decimal_comma: bool, separator: u8) -> PolarsR
 #[allow(clippy::too_many_arguments)]
 pub fn infer_file_schema(
     reader_bytes: &ReaderBytes,
-    separator: u8,
+    separator: Separator,
     max_read_rows: Option<usize>,
     has_header: bool,
     schema_overwrite: Option<&Schema>,
@@ -533,15 +680,19 @@ pub fn infer_file_schema(
     comment_prefix: Option<&CommentPrefix>,
     quote_char: Option<u8>,
     eol_char: u8,
+    encoding: CsvEncoding,
     null_values: Option<&NullValues>,
     try_parse_dates: bool,
     raise_if_empty: bool,
     n_threads: &mut Option<usize>,
     decimal_comma: bool,
+    infer_schema_policy: &InferSchemaPolicy,
 ) -> PolarsResult<(Schema, usize, usize)> {
     check_decimal_comma(decimal_comma, separator)?;
+    let (transcoded, encoding) = encoding.transcode_file_to_utf8(reader_bytes)?;
+    let transcoded = transcoded.map(ReaderBytes::Owned);
     infer_file_schema_inner(
-        reader_bytes,
+        transcoded.as_ref().unwrap_or(reader_bytes),
         separator,
         max_read_rows,
         has_header,
@@ -551,11 +702,13 @@ pub fn infer_file_schema(
         comment_prefix,
         quote_char,
         eol_char,
+        encoding,
         null_values,
         try_parse_dates,
         0,
         raise_if_empty,
         n_threads,
         decimal_comma,
+        infer_schema_policy,
     )
 }
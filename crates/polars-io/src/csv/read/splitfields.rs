@@ -1,11 +1,13 @@
 #[cfg(not(feature = "simd"))]
 mod inner {
+    use crate::csv::read::Separator;
+
     /// An adapted version of std::iter::Split.
     /// This exists solely because we cannot split the lines naively as
     pub(crate) struct SplitFields<'a> {
         v: &'a [u8],
-        separator: u8,
-        finished: bool,
+        separator: Separator,
+        pub finished: bool,
         quote_char: u8,
         quoting: bool,
         eol_char: u8,
@@ -14,7 +16,7 @@ mod inner {
     impl<'a> SplitFields<'a> {
         pub(crate) fn new(
             slice: &'a [u8],
-            separator: u8,
+            separator: Separator,
             quote_char: Option<u8>,
             eol_char: u8,
         ) -> Self {
@@ -44,7 +46,12 @@ mod inner {
         }
 
         fn eof_oel(&self, current_ch: u8) -> bool {
-            current_ch == self.separator || current_ch == self.eol_char
+            current_ch == self.separator.first_byte() || current_ch == self.eol_char
+        }
+
+        /// Whether the full separator starts at `idx`, given that its first byte is at `idx`.
+        fn is_separator_at(&self, idx: usize) -> bool {
+            self.separator.is_single_byte() || self.v[idx..].starts_with(self.separator.as_bytes())
         }
     }
 
@@ -96,8 +103,10 @@ mod inner {
                                 self.finish_eol(needs_escaping, current_idx as usize)
                             };
                         }
-                        idx = current_idx;
-                        break;
+                        if self.is_separator_at(current_idx as usize) {
+                            idx = current_idx;
+                            break;
+                        }
                     }
                     current_idx += 1;
                 }
@@ -108,17 +117,23 @@ mod inner {
 
                 idx as usize
             } else {
-                match self.v.iter().position(|&c| self.eof_oel(c)) {
-                    None => return self.finish(needs_escaping),
-                    Some(idx) => unsafe {
-                        // SAFETY:
-                        // idx was just found
-                        if *self.v.get_unchecked(idx) == self.eol_char {
-                            return self.finish_eol(needs_escaping, idx);
-                        } else {
-                            idx
-                        }
-                    },
+                let mut offset = 0;
+                loop {
+                    match self.v[offset..].iter().position(|&c| self.eof_oel(c)) {
+                        None => return self.finish(needs_escaping),
+                        Some(idx) => unsafe {
+                            let idx = offset + idx;
+                            // SAFETY:
+                            // idx was just found
+                            if *self.v.get_unchecked(idx) == self.eol_char {
+                                return self.finish_eol(needs_escaping, idx);
+                            } else if self.is_separator_at(idx) {
+                                break idx;
+                            }
+                            // Only the first byte of a multi-byte separator matched.
+                            offset = idx + 1;
+                        },
+                    }
                 }
             };
 
@@ -127,7 +142,7 @@ mod inner {
                 // SAFETY:
                 // we are in bounds
                 let ret = Some((self.v.get_unchecked(..pos), needs_escaping));
-                self.v = self.v.get_unchecked(pos + 1..);
+                self.v = self.v.get_unchecked(pos + self.separator.n_bytes()..);
                 ret
             }
         }
@@ -142,6 +157,8 @@ mod inner {
     use polars_utils::slice::GetSaferUnchecked;
     use polars_utils::unwrap::UnwrapUncheckedRelease;
 
+    use crate::csv::read::Separator;
+
     const SIMD_SIZE: usize = 16;
     type SimdVec = u8x16;
 
@@ -149,7 +166,7 @@ mod inner {
     /// This exists solely because we cannot split the lines naively as
     pub(crate) struct SplitFields<'a> {
         pub v: &'a [u8],
-        separator: u8,
+        separator: Separator,
         pub finished: bool,
         quote_char: u8,
         quoting: bool,
@@ -161,11 +178,11 @@ mod inner {
     impl<'a> SplitFields<'a> {
         pub(crate) fn new(
             slice: &'a [u8],
-            separator: u8,
+            separator: Separator,
             quote_char: Option<u8>,
             eol_char: u8,
         ) -> Self {
-            let simd_separator = SimdVec::splat(separator);
+            let simd_separator = SimdVec::splat(separator.first_byte());
             let simd_eol_char = SimdVec::splat(eol_char);
 
             Self {
@@ -196,7 +213,12 @@ mod inner {
         }
 
         fn eof_oel(&self, current_ch: u8) -> bool {
-            current_ch == self.separator || current_ch == self.eol_char
+            current_ch == self.separator.first_byte() || current_ch == self.eol_char
+        }
+
+        /// Whether the full separator starts at `idx`, given that its first byte is at `idx`.
+        fn is_separator_at(&self, idx: usize) -> bool {
+            self.separator.is_single_byte() || self.v[idx..].starts_with(self.separator.as_bytes())
         }
     }
 
@@ -248,8 +270,10 @@ mod inner {
                                 self.finish_eol(needs_escaping, current_idx as usize)
                             };
                         }
-                        idx = current_idx;
-                        break;
+                        if self.is_separator_at(current_idx as usize) {
+                            idx = current_idx;
+                            break;
+                        }
                     }
                     current_idx += 1;
                 }
@@ -278,26 +302,27 @@ mod inner {
                         let has_any = has_separator.bitor(has_eol_char);
                         if let Some(idx) = has_any.first_set() {
                             total_idx += idx;
-                            break;
                         } else {
                             total_idx += SIMD_SIZE;
+                            continue;
                         }
                     } else {
                         match bytes.iter().position(|&c| self.eof_oel(c)) {
                             None => return self.finish(needs_escaping),
-                            Some(idx) => {
-                                total_idx += idx;
-                                break;
-                            },
+                            Some(idx) => total_idx += idx,
                         }
                     }
-                }
-                unsafe {
-                    if *self.v.get_unchecked_release(total_idx) == self.eol_char {
-                        return self.finish_eol(needs_escaping, total_idx);
-                    } else {
-                        total_idx
+
+                    unsafe {
+                        if *self.v.get_unchecked_release(total_idx) == self.eol_char {
+                            return self.finish_eol(needs_escaping, total_idx);
+                        }
+                    }
+                    if self.is_separator_at(total_idx) {
+                        break total_idx;
                     }
+                    // Only the first byte of a multi-byte separator matched.
+                    total_idx += 1;
                 }
             };
 
@@ -306,7 +331,7 @@ mod inner {
                 // SAFETY:
                 // we are in bounds
                 let ret = Some((self.v.get_unchecked(..pos), needs_escaping));
-                self.v = self.v.get_unchecked(pos + 1..);
+                self.v = self.v.get_unchecked(pos + self.separator.n_bytes()..);
                 ret
             }
         }
@@ -318,22 +343,36 @@ pub(crate) use inner::SplitFields;
 #[cfg(test)]
 mod test {
     use super::SplitFields;
+    use crate::csv::read::Separator;
 
     #[test]
     fn test_splitfields() {
         let input = "\"foo\",\"bar\"";
-        let mut fields = SplitFields::new(input.as_bytes(), b',', Some(b'"'), b'\n');
+        let mut fields = SplitFields::new(input.as_bytes(), b','.into(), Some(b'"'), b'\n');
 
         assert_eq!(fields.next(), Some(("\"foo\"".as_bytes(), true)));
         assert_eq!(fields.next(), Some(("\"bar\"".as_bytes(), true)));
         assert_eq!(fields.next(), None);
 
         let input2 = "\"foo\n bar\";\"baz\";12345";
-        let mut fields2 = SplitFields::new(input2.as_bytes(), b';', Some(b'"'), b'\n');
+        let mut fields2 = SplitFields::new(input2.as_bytes(), b';'.into(), Some(b'"'), b'\n');
 
         assert_eq!(fields2.next(), Some(("\"foo\n bar\"".as_bytes(), true)));
         assert_eq!(fields2.next(), Some(("\"baz\"".as_bytes(), true)));
         assert_eq!(fields2.next(), Some(("12345".as_bytes(), false)));
         assert_eq!(fields2.next(), None);
     }
+
+    #[test]
+    fn test_splitfields_multi_byte_separator() {
+        let separator = Separator::try_from("~|~").unwrap();
+        let input = "a~b~|~\"c~|~d\"~|~~|~e|f\nnext";
+        let mut fields = SplitFields::new(input.as_bytes(), separator, Some(b'"'), b'\n');
+
+        assert_eq!(fields.next(), Some(("a~b".as_bytes(), false)));
+        assert_eq!(fields.next(), Some(("\"c~|~d\"".as_bytes(), true)));
+        assert_eq!(fields.next(), Some(("".as_bytes(), false)));
+        assert_eq!(fields.next(), Some(("e|f".as_bytes(), false)));
+        assert_eq!(fields.next(), None);
+    }
 }
@@ -0,0 +1,186 @@
+use std::io::{Cursor, Read};
+
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_utils::mmap::MemSlice;
+
+use super::schema_inference::SchemaInferenceResult;
+use super::{CsvBlockReader, CsvReadOptions};
+use crate::mmap::ReaderBytes;
+use crate::SerReader;
+
+/// Reads a CSV file from a [`Read`] that can't be memory mapped or seeked, such as stdin or a
+/// pipe, in batches.
+///
+/// The input is read in blocks of complete lines, of which every block is parsed into a batch, so
+/// that the input is never fully held in memory. If the options don't contain a schema, it is
+/// inferred from the first block.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::csv::read::{CsvReadOptions, CsvStreamReader};
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     let stdin = Box::new(std::io::stdin());
+///     CsvStreamReader::new(stdin, CsvReadOptions::default()).finish()
+/// }
+/// ```
+pub struct CsvStreamReader {
+    blocks: CsvBlockReader,
+    options: CsvReadOptions,
+    /// The batch of the first block, which is parsed when the schema is resolved.
+    first_batch: Option<DataFrame>,
+    schema: Option<SchemaRef>,
+    n_rows_read: usize,
+    finished: bool,
+}
+
+impl CsvStreamReader {
+    pub fn new(reader: Box<dyn Read + Send>, options: CsvReadOptions) -> Self {
+        let parse_options = options.get_parse_options();
+        let blocks = CsvBlockReader::new(reader, parse_options.quote_char, parse_options.eol_char);
+        Self {
+            blocks,
+            options,
+            first_batch: None,
+            schema: None,
+            n_rows_read: 0,
+            finished: false,
+        }
+    }
+
+    /// Set the number of bytes that are read per block.
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        self.blocks = self.blocks.with_block_size(block_size);
+        self
+    }
+
+    /// The schema of the batches, which is resolved by reading the first block.
+    pub fn schema(&mut self) -> PolarsResult<SchemaRef> {
+        if self.schema.is_none() {
+            let parse_options = self.options.get_parse_options();
+            // The line endings of other encodings cannot be found in the raw bytes.
+            polars_ensure!(
+                parse_options.encoding.is_ascii_compatible(),
+                InvalidOperation: "CSV of encoding {:?} cannot be read from a stream",
+                parse_options.encoding
+            );
+            let block = self.blocks.next_block()?.unwrap_or_default();
+
+            if self.options.schema.is_none() {
+                if block.len() < 2 && self.options.raise_if_empty {
+                    polars_bail!(NoData: "empty CSV")
+                }
+                let reader_bytes = ReaderBytes::Borrowed(&block);
+                let si_result = SchemaInferenceResult::try_from_reader_bytes_and_options(
+                    &reader_bytes,
+                    &self.options,
+                )?;
+                self.options.update_with_inference_result(&si_result);
+                self.options.schema = Some(si_result.get_inferred_schema());
+            }
+
+            // Only the start of the input can contain a header and rows to skip.
+            let df = self.parse_block(block, self.options.clone())?;
+            self.schema = Some(Arc::new(df.schema()));
+            self.first_batch = Some(df);
+        }
+        Ok(self.schema.clone().unwrap())
+    }
+
+    fn parse_block(
+        &mut self,
+        block: MemSlice,
+        mut options: CsvReadOptions,
+    ) -> PolarsResult<DataFrame> {
+        let remaining = options.n_rows.map(|n| n.saturating_sub(self.n_rows_read));
+        if let Some(row_index) = &mut options.row_index {
+            row_index.offset += self.n_rows_read as IdxSize;
+        }
+        let df = options
+            .with_n_rows(remaining)
+            .with_rechunk(false)
+            .into_reader_with_file_handle(Cursor::new(block))
+            .finish()?;
+        self.n_rows_read += df.height();
+        Ok(df)
+    }
+
+    /// Read and parse the next block, or `None` if the input is exhausted or the number of rows
+    /// of the options has been read.
+    pub fn next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        self.schema()?;
+        if let Some(df) = self.first_batch.take() {
+            if !df.is_empty() {
+                return Ok(Some(df));
+            }
+        }
+        while !self.finished {
+            if self.options.n_rows.is_some_and(|n| n <= self.n_rows_read) {
+                break;
+            }
+            let Some(block) = self.blocks.next_block()? else {
+                break;
+            };
+            let options = self
+                .options
+                .clone()
+                .with_has_header(false)
+                .with_skip_rows(0)
+                .with_skip_rows_after_header(0)
+                .with_raise_if_empty(false);
+            let df = self.parse_block(block, options)?;
+            if !df.is_empty() {
+                return Ok(Some(df));
+            }
+        }
+        self.finished = true;
+        Ok(None)
+    }
+
+    /// Read the rest of the input into a DataFrame.
+    pub fn finish(mut self) -> PolarsResult<DataFrame> {
+        let schema = self.schema()?;
+        let mut dfs = vec![];
+        while let Some(df) = self.next_batch()? {
+            dfs.push(df);
+        }
+        if dfs.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&schema));
+        }
+        Ok(accumulate_dataframes_vertical_unchecked(dfs))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stream_reader_batches() {
+        let input = "a,b\n1,x\n2,\"y\nz\"\n3,w\n";
+        let mut reader = CsvStreamReader::new(
+            Box::new(input.as_bytes()),
+            CsvReadOptions::default().with_n_rows(Some(2)),
+        )
+        .with_block_size(10);
+        assert_eq!(
+            reader.schema().unwrap().as_ref(),
+            &Schema::from_iter([
+                Field::new("a".into(), DataType::Int64),
+                Field::new("b".into(), DataType::String),
+            ])
+        );
+
+        let df = reader.finish().unwrap();
+        assert_eq!(
+            df.column("a").unwrap().i64().unwrap().to_vec(),
+            [Some(1), Some(2)]
+        );
+        assert_eq!(
+            df.column("b").unwrap().str().unwrap().to_vec(),
+            [Some("x"), Some("y\nz")]
+        );
+    }
+}
@@ -2,16 +2,21 @@
 use std::io::Read;
 use std::mem::MaybeUninit;
 
+use polars_error::{polars_ensure, polars_err, PolarsResult};
+
+use super::options::Separator;
 use super::parser::next_line_position;
 #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
 use super::parser::next_line_position_naive;
 use super::splitfields::SplitFields;
+#[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+use crate::utils::compression::{decompressing_reader, SupportedCompression};
 
 pub(crate) fn get_file_chunks(
     bytes: &[u8],
     n_chunks: usize,
     expected_fields: Option<usize>,
-    separator: u8,
+    separator: Separator,
     quote_char: Option<u8>,
     eol_char: u8,
 ) -> Vec<(usize, usize)> {
@@ -49,7 +54,7 @@ pub(crate) fn get_file_chunks(
 fn decompress_impl<R: Read>(
     decoder: &mut R,
     n_rows: Option<usize>,
-    separator: u8,
+    separator: Separator,
     quote_char: Option<u8>,
     eol_char: u8,
 ) -> Option<Vec<u8>> {
@@ -125,23 +130,74 @@ fn decompress_impl<R: Read>(
 pub(crate) fn decompress(
     bytes: &[u8],
     n_rows: Option<usize>,
-    separator: u8,
+    separator: Separator,
     quote_char: Option<u8>,
     eol_char: u8,
 ) -> Option<Vec<u8>> {
-    use crate::utils::compression::magic::*;
-    if bytes.starts_with(&GZIP) {
-        let mut decoder = flate2::read::MultiGzDecoder::new(bytes);
-        decompress_impl(&mut decoder, n_rows, separator, quote_char, eol_char)
-    } else if bytes.starts_with(&ZLIB0) || bytes.starts_with(&ZLIB1) || bytes.starts_with(&ZLIB2) {
-        let mut decoder = flate2::read::ZlibDecoder::new(bytes);
-        decompress_impl(&mut decoder, n_rows, separator, quote_char, eol_char)
-    } else if bytes.starts_with(&ZSTD) {
-        let mut decoder = zstd::Decoder::new(bytes).ok()?;
-        decompress_impl(&mut decoder, n_rows, separator, quote_char, eol_char)
-    } else {
-        None
+    let compression = SupportedCompression::check(bytes)?;
+    let mut decoder = decompressing_reader(bytes, compression).ok()?;
+    decompress_impl(&mut decoder, n_rows, separator, quote_char, eol_char)
+}
+
+/// Transcode a single byte encoding to utf8. ASCII bytes are the same in utf8, the other
+/// bytes are mapped to a `char` by `to_char`.
+pub(super) fn transcode_single_byte(bytes: &[u8], to_char: impl Fn(u8) -> char) -> Vec<u8> {
+    let n_non_ascii = bytes.iter().filter(|b| !b.is_ascii()).count();
+    // A non-ASCII byte takes at most 3 bytes in utf8.
+    let mut out = Vec::with_capacity(bytes.len() + 2 * n_non_ascii);
+    let mut buf = [0; 4];
+    for &b in bytes {
+        if b.is_ascii() {
+            out.push(b);
+        } else {
+            out.extend_from_slice(to_char(b).encode_utf8(&mut buf).as_bytes());
+        }
     }
+    out
+}
+
+/// Map a windows-1252 byte to a `char`. Bytes 0x80..=0x9F differ from latin-1; the five
+/// undefined bytes in that range are mapped to the C1 control characters, as browsers do.
+pub(super) fn windows_1252_to_char(b: u8) -> char {
+    const C1: [char; 32] = [
+        '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}',
+        '\u{017D}', '\u{008F}', '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}',
+        '\u{2022}', '\u{2013}', '\u{2014}', '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}',
+        '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+    ];
+    match b {
+        0x80..=0x9F => C1[(b - 0x80) as usize],
+        _ => b as char,
+    }
+}
+
+/// Transcode UTF-16 to utf8. A leading byte order mark is removed.
+pub(super) fn transcode_utf16(
+    bytes: &[u8],
+    from_bytes: fn([u8; 2]) -> u16,
+) -> PolarsResult<Vec<u8>> {
+    polars_ensure!(
+        bytes.len() % 2 == 0,
+        ComputeError: "invalid UTF-16 data: odd number of bytes ({})", bytes.len()
+    );
+    let code_units = bytes.chunks_exact(2).map(|c| from_bytes([c[0], c[1]]));
+
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut buf = [0; 4];
+    for (i, c) in char::decode_utf16(code_units).enumerate() {
+        let c = c.map_err(|e| {
+            polars_err!(
+                ComputeError: "invalid UTF-16 data: unpaired surrogate {:#06x}",
+                e.unpaired_surrogate()
+            )
+        })?;
+        if i == 0 && c == '\u{FEFF}' {
+            continue;
+        }
+        out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+    Ok(out)
 }
 
 /// replace double quotes by single ones
@@ -180,6 +236,7 @@ pub(super) unsafe fn escape_field(bytes: &[u8], quote: u8, buf: &mut [MaybeUnini
 #[cfg(test)]
 mod test {
     use super::get_file_chunks;
+    use crate::csv::read::CsvEncoding;
 
     #[test]
     fn test_get_file_chunks() {
@@ -188,10 +245,39 @@ mod test {
         let bytes = s.as_bytes();
         // can be within -1 / +1 bounds.
         assert!(
-            (get_file_chunks(bytes, 10, Some(4), b',', None, b'\n').len() as i32 - 10).abs() <= 1
+            (get_file_chunks(bytes, 10, Some(4), b','.into(), None, b'\n').len() as i32 - 10).abs()
+                <= 1
         );
         assert!(
-            (get_file_chunks(bytes, 8, Some(4), b',', None, b'\n').len() as i32 - 8).abs() <= 1
+            (get_file_chunks(bytes, 8, Some(4), b','.into(), None, b'\n').len() as i32 - 8).abs()
+                <= 1
         );
     }
+
+    #[test]
+    fn test_transcode_to_utf8() {
+        let latin1 = b"caf\xe9,\xa3\n";
+        let out = CsvEncoding::Latin1
+            .transcode_to_utf8(latin1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(out, "café,£\n".as_bytes());
+
+        let windows_1252 = b"\x80,\x93a\x94\n";
+        let out = CsvEncoding::Windows1252
+            .transcode_to_utf8(windows_1252)
+            .unwrap()
+            .unwrap();
+        assert_eq!(out, "€,\u{201C}a\u{201D}\n".as_bytes());
+
+        let utf16: Vec<u16> = "\u{FEFF}a,é\n".encode_utf16().collect();
+        let le: Vec<u8> = utf16.iter().flat_map(|c| c.to_le_bytes()).collect();
+        let be: Vec<u8> = utf16.iter().flat_map(|c| c.to_be_bytes()).collect();
+        for (encoding, bytes) in [(CsvEncoding::Utf16Le, le), (CsvEncoding::Utf16Be, be)] {
+            let out = encoding.transcode_to_utf8(&bytes).unwrap().unwrap();
+            assert_eq!(out, "a,é\n".as_bytes());
+        }
+        assert!(CsvEncoding::Utf16Le.transcode_to_utf8(b"a").is_err());
+        assert!(CsvEncoding::Utf8.transcode_to_utf8(b"a").unwrap().is_none());
+    }
 }
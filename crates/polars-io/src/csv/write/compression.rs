@@ -0,0 +1,72 @@
+use std::io::Write;
+
+use polars_error::PolarsResult;
+
+use super::CsvCompression;
+
+/// A writer that compresses everything that is written to it.
+pub(super) enum CompressedWriter<W: Write> {
+    Uncompressed(W),
+    #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+    Zstd(zstd::Encoder<'static, W>),
+}
+
+impl<W: Write> CompressedWriter<W> {
+    pub(super) fn try_new(writer: W, compression: CsvCompression) -> PolarsResult<Self> {
+        Ok(match compression {
+            CsvCompression::Uncompressed => Self::Uncompressed(writer),
+            #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+            CsvCompression::Gzip(level) => Self::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                level.map_or_else(flate2::Compression::default, flate2::Compression::new),
+            )),
+            #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+            CsvCompression::Zstd(level) => Self::Zstd(zstd::Encoder::new(
+                writer,
+                level.unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL),
+            )?),
+            #[cfg(not(any(feature = "decompress", feature = "decompress-fast")))]
+            _ => polars_error::polars_bail!(
+                ComputeError: "cannot write compressed CSV file; \
+                compile with feature 'decompress' or 'decompress-fast'"
+            ),
+        })
+    }
+
+    /// Write the remaining compressed data and the trailer of the compression format. Nothing
+    /// should be written after this.
+    pub(super) fn finish(&mut self) -> PolarsResult<()> {
+        match self {
+            Self::Uncompressed(w) => w.flush()?,
+            #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+            Self::Gzip(w) => w.try_finish()?,
+            #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+            Self::Zstd(w) => w.do_finish()?,
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Uncompressed(w) => w.write(buf),
+            #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+            Self::Gzip(w) => w.write(buf),
+            #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+            Self::Zstd(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Uncompressed(w) => w.flush(),
+            #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+            Self::Gzip(w) => w.flush(),
+            #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+            Self::Zstd(w) => w.flush(),
+        }
+    }
+}
@@ -17,9 +17,10 @@
 //! }
 //! ```
 
+mod compression;
 mod options;
 mod write_impl;
 mod writer;
 
-pub use options::{CsvWriterOptions, QuoteStyle, SerializeOptions};
+pub use options::{CsvCompression, CsvWriterOptions, QuoteStyle, SerializeOptions};
 pub use writer::{BatchedWriter, CsvWriter};
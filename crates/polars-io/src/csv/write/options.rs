@@ -12,6 +12,7 @@ pub struct CsvWriterOptions {
     pub batch_size: NonZeroUsize,
     pub maintain_order: bool,
     pub serialize_options: SerializeOptions,
+    pub compression: CsvCompression,
 }
 
 impl Default for CsvWriterOptions {
@@ -22,10 +23,23 @@ impl Default for CsvWriterOptions {
             batch_size: NonZeroUsize::new(1024).unwrap(),
             maintain_order: false,
             serialize_options: SerializeOptions::default(),
+            compression: CsvCompression::default(),
         }
     }
 }
 
+/// Compression of the written CSV file.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CsvCompression {
+    #[default]
+    Uncompressed,
+    /// Gzip compression with an optional level between 0 and 9.
+    Gzip(Option<u32>),
+    /// Zstandard compression with an optional level between 1 and 22.
+    Zstd(Option<i32>),
+}
+
 /// Options to serialize logical types to CSV.
 ///
 /// The default is to format times and dates as `chrono` crate formats them.
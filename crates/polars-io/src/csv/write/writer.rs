@@ -6,8 +6,9 @@ use polars_core::schema::Schema;
 use polars_core::POOL;
 use polars_error::PolarsResult;
 
+use super::compression::CompressedWriter;
 use super::write_impl::{write, write_bom, write_header};
-use super::{QuoteStyle, SerializeOptions};
+use super::{CsvCompression, QuoteStyle, SerializeOptions};
 use crate::shared::SerWriter;
 
 /// Write a DataFrame to csv.
@@ -22,6 +23,7 @@ pub struct CsvWriter<W: Write> {
     bom: bool,
     batch_size: NonZeroUsize,
     n_threads: usize,
+    compression: CsvCompression,
 }
 
 impl<W> SerWriter<W> for CsvWriter<W>
@@ -42,12 +44,14 @@ where
             bom: false,
             batch_size: NonZeroUsize::new(1024).unwrap(),
             n_threads: POOL.current_num_threads(),
+            compression: CsvCompression::default(),
         }
     }
 
     fn finish(&mut self, df: &mut DataFrame) -> PolarsResult<()> {
+        let mut buffer = CompressedWriter::try_new(&mut self.buffer, self.compression)?;
         if self.bom {
-            write_bom(&mut self.buffer)?;
+            write_bom(&mut buffer)?;
         }
         let names = df
             .get_column_names()
@@ -55,15 +59,16 @@ where
             .map(|x| x.as_str())
             .collect::<Vec<_>>();
         if self.header {
-            write_header(&mut self.buffer, names.as_slice(), &self.options)?;
+            write_header(&mut buffer, names.as_slice(), &self.options)?;
         }
         write(
-            &mut self.buffer,
+            &mut buffer,
             df,
             self.batch_size.into(),
             &self.options,
             self.n_threads,
-        )
+        )?;
+        buffer.finish()
     }
 }
 
@@ -165,11 +170,27 @@ where
         self
     }
 
+    /// Set the compression of the written file.
+    pub fn with_compression(mut self, compression: CsvCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     pub fn batched(self, schema: &Schema) -> PolarsResult<BatchedWriter<W>> {
         let expects_bom = self.bom;
         let expects_header = self.header;
+        // The batches are written to the same compressed stream.
+        let writer = CsvWriter {
+            buffer: CompressedWriter::try_new(self.buffer, self.compression)?,
+            options: self.options,
+            header: self.header,
+            bom: self.bom,
+            batch_size: self.batch_size,
+            n_threads: self.n_threads,
+            compression: self.compression,
+        };
         Ok(BatchedWriter {
-            writer: self,
+            writer,
             has_written_bom: !expects_bom,
             has_written_header: !expects_header,
             schema: schema.clone(),
@@ -178,7 +199,7 @@ where
 }
 
 pub struct BatchedWriter<W: Write> {
-    writer: CsvWriter<W>,
+    writer: CsvWriter<CompressedWriter<W>>,
     has_written_bom: bool,
     has_written_header: bool,
     schema: Schema,
@@ -219,7 +240,8 @@ impl<W: Write> BatchedWriter<W> {
         Ok(())
     }
 
-    /// Writes the header of the csv file if not done already. Returns the total size of the file.
+    /// Writes the header of the csv file if not done already and finishes the compression.
+    /// Nothing should be written after this.
     pub fn finish(&mut self) -> PolarsResult<()> {
         if !self.has_written_bom {
             self.has_written_bom = true;
@@ -236,6 +258,6 @@ impl<W: Write> BatchedWriter<W> {
             write_header(&mut self.writer.buffer, &names, &self.writer.options)?;
         };
 
-        Ok(())
+        self.writer.buffer.finish()
     }
 }
@@ -0,0 +1,298 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+
+/// The magic number at the start of a serialized deletion vector.
+const MAGIC_NUMBER: u32 = 1681511377;
+const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+const SERIAL_COOKIE: u32 = 12347;
+/// Containers of serialized roaring bitmaps with run containers only have offsets if there are
+/// at least this many.
+const NO_OFFSET_THRESHOLD: usize = 4;
+/// Array containers have at most this many values; containers with more are bitmaps.
+const MAX_ARRAY_CARDINALITY: usize = 4096;
+
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Where the deleted rows of a data file are stored.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DeletionVector {
+    /// `u` for a file next to the data files, `p` for a file at an absolute path or `i` for an
+    /// inline vector.
+    pub storage_type: String,
+    pub path_or_inline_dv: String,
+    /// The position of the vector in its file.
+    pub offset: Option<i64>,
+    pub size_in_bytes: i64,
+    /// The number of deleted rows.
+    pub cardinality: i64,
+}
+
+impl DeletionVector {
+    /// The identifier of the vector in the log, which is unique per data file.
+    pub(super) fn unique_id(&self) -> String {
+        match self.offset {
+            Some(offset) => format!("{}{}@{}", self.storage_type, self.path_or_inline_dv, offset),
+            None => format!("{}{}", self.storage_type, self.path_or_inline_dv),
+        }
+    }
+
+    /// The positions of the deleted rows in the data file, in ascending order.
+    pub fn deleted_rows(&self, table_root: &Path) -> PolarsResult<Vec<u64>> {
+        let size = self.size_in_bytes as usize;
+        let rows = match self.storage_type.as_str() {
+            "i" => {
+                let bytes = z85_decode(&self.path_or_inline_dv)?;
+                polars_ensure!(
+                    bytes.len() >= size,
+                    ComputeError: "out-of-spec Delta table: inline deletion vector is too short"
+                );
+                read_bitmap_array(&bytes[..size])?
+            },
+            "u" | "p" => {
+                let path = self.path(table_root)?;
+                let bytes = std::fs::read(&path).map_err(|e| {
+                    polars_err!(ComputeError: "could not read deletion vector {}: {}", path.display(), e)
+                })?;
+                read_stored_bitmap(&bytes, self.offset.unwrap_or(1) as usize, size)?
+            },
+            storage_type => polars_bail!(
+                ComputeError: "out-of-spec Delta table: unknown deletion vector storage type '{}'",
+                storage_type
+            ),
+        };
+        polars_ensure!(
+            rows.len() as i64 == self.cardinality,
+            ComputeError: "out-of-spec Delta table: deletion vector has {} rows, expected {}",
+            rows.len(), self.cardinality
+        );
+        Ok(rows)
+    }
+
+    fn path(&self, table_root: &Path) -> PolarsResult<PathBuf> {
+        if self.storage_type == "p" {
+            return super::log::resolve_path(table_root, &self.path_or_inline_dv);
+        }
+        // A random prefix of the directory, followed by the encoded UUID of the file.
+        let dv = self.path_or_inline_dv.as_str();
+        polars_ensure!(
+            dv.len() >= 20 && dv.is_char_boundary(dv.len() - 20),
+            ComputeError: "out-of-spec Delta table: invalid deletion vector path '{}'", dv
+        );
+        let (prefix, uuid) = dv.split_at(dv.len() - 20);
+        let uuid = z85_decode(uuid)?;
+        let uuid = format!(
+            "{}-{}-{}-{}-{}",
+            hex(&uuid[..4]),
+            hex(&uuid[4..6]),
+            hex(&uuid[6..8]),
+            hex(&uuid[8..10]),
+            hex(&uuid[10..])
+        );
+        Ok(table_root
+            .join(prefix)
+            .join(format!("deletion_vector_{uuid}.bin")))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, b| {
+            write!(hex, "{b:02x}").unwrap();
+            hex
+        })
+}
+
+/// Decode the Z85 encoding of ZeroMQ, in which 5 characters encode 4 bytes.
+fn z85_decode(text: &str) -> PolarsResult<Vec<u8>> {
+    polars_ensure!(
+        text.len() % 5 == 0,
+        ComputeError: "out-of-spec Delta table: invalid Z85 encoding of length {}", text.len()
+    );
+    let mut out = Vec::with_capacity(text.len() / 5 * 4);
+    for chunk in text.as_bytes().chunks(5) {
+        let mut value = 0u64;
+        for c in chunk {
+            let digit = Z85_ALPHABET.iter().position(|a| a == c).ok_or_else(|| {
+                polars_err!(ComputeError: "out-of-spec Delta table: invalid Z85 character '{}'", *c as char)
+            })?;
+            value = value * 85 + digit as u64;
+        }
+        polars_ensure!(
+            value <= u32::MAX as u64,
+            ComputeError: "out-of-spec Delta table: invalid Z85 encoding"
+        );
+        out.extend((value as u32).to_be_bytes());
+    }
+    Ok(out)
+}
+
+/// Read a vector from a file of deletion vectors. The vector at `offset` consists of its size
+/// and its checksum, both big endian, around the serialized bitmap.
+fn read_stored_bitmap(bytes: &[u8], offset: usize, size: usize) -> PolarsResult<Vec<u64>> {
+    let truncated =
+        || polars_err!(ComputeError: "out-of-spec Delta table: truncated deletion vector");
+    let header = bytes.get(offset..offset + 4).ok_or_else(truncated)?;
+    polars_ensure!(
+        u32::from_be_bytes(header.try_into().unwrap()) as usize == size,
+        ComputeError: "out-of-spec Delta table: deletion vector size mismatch"
+    );
+    let data = bytes
+        .get(offset + 4..offset + 4 + size)
+        .ok_or_else(truncated)?;
+    let checksum = bytes
+        .get(offset + 4 + size..offset + 8 + size)
+        .ok_or_else(truncated)?;
+
+    let mut crc = flate2::Crc::new();
+    crc.update(data);
+    polars_ensure!(
+        crc.sum() == u32::from_be_bytes(checksum.try_into().unwrap()),
+        ComputeError: "out-of-spec Delta table: deletion vector checksum mismatch"
+    );
+    read_bitmap_array(data)
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Bytes<'a> {
+    fn take(&mut self, n: usize) -> PolarsResult<&'a [u8]> {
+        polars_ensure!(
+            n <= self.0.len(),
+            ComputeError: "out-of-spec Delta table: truncated deletion vector"
+        );
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u16(&mut self) -> PolarsResult<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> PolarsResult<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> PolarsResult<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// Read a serialized 64-bit roaring bitmap: the magic number, followed by the number of 32-bit
+/// bitmaps and every bitmap with the 32 high bits of its values, all little endian.
+fn read_bitmap_array(bytes: &[u8]) -> PolarsResult<Vec<u64>> {
+    let mut bytes = Bytes(bytes);
+    polars_ensure!(
+        bytes.u32()? == MAGIC_NUMBER,
+        ComputeError: "out-of-spec Delta table: invalid deletion vector magic number"
+    );
+    let n_bitmaps = bytes.u64()?;
+    let mut out = vec![];
+    for _ in 0..n_bitmaps {
+        let high = bytes.u32()? as u64;
+        read_bitmap(&mut bytes, high << 32, &mut out)?;
+    }
+    Ok(out)
+}
+
+/// Read a 32-bit roaring bitmap in its portable serialization format, and push its values
+/// plus `base` to `out`.
+fn read_bitmap(bytes: &mut Bytes, base: u64, out: &mut Vec<u64>) -> PolarsResult<()> {
+    let cookie = bytes.u32()?;
+    let (n_containers, run_flags) = if cookie & 0xFFFF == SERIAL_COOKIE {
+        let n_containers = (cookie >> 16) as usize + 1;
+        (n_containers, Some(bytes.take(n_containers.div_ceil(8))?))
+    } else if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+        (bytes.u32()? as usize, None)
+    } else {
+        polars_bail!(ComputeError: "out-of-spec Delta table: invalid roaring bitmap cookie {}", cookie)
+    };
+
+    // The 16 high bits of the values and the cardinality minus 1 of every container.
+    let header = bytes.take(4 * n_containers)?;
+    if run_flags.is_none() || n_containers >= NO_OFFSET_THRESHOLD {
+        bytes.take(4 * n_containers)?;
+    }
+
+    for (i, header) in header.chunks_exact(4).enumerate() {
+        let key = u16::from_le_bytes([header[0], header[1]]) as u64;
+        let cardinality = u16::from_le_bytes([header[2], header[3]]) as usize + 1;
+        let base = base | (key << 16);
+        let is_run = run_flags.is_some_and(|flags| flags[i / 8] & (1 << (i % 8)) != 0);
+
+        if is_run {
+            let n_runs = bytes.u16()?;
+            for _ in 0..n_runs {
+                let start = bytes.u16()? as u64;
+                let length = bytes.u16()? as u64;
+                out.extend((start..=start + length).map(|v| base | v));
+            }
+        } else if cardinality > MAX_ARRAY_CARDINALITY {
+            for (i, word) in bytes.take(8192)?.chunks_exact(8).enumerate() {
+                let mut word = u64::from_le_bytes(word.try_into().unwrap());
+                while word != 0 {
+                    out.push(base | (i as u64 * 64 + word.trailing_zeros() as u64));
+                    word &= word - 1;
+                }
+            }
+        } else {
+            for _ in 0..cardinality {
+                out.push(base | bytes.u16()? as u64);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_z85_decode() {
+        // The test vector of the Z85 specification.
+        assert_eq!(
+            z85_decode("HelloWorld").unwrap(),
+            [0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B]
+        );
+        assert!(z85_decode("Hello").is_ok());
+        assert!(z85_decode("Hell").is_err());
+        assert!(z85_decode("Hell~").is_err());
+    }
+
+    #[test]
+    fn test_read_bitmap_array() {
+        let mut bytes = MAGIC_NUMBER.to_le_bytes().to_vec();
+        bytes.extend(2u64.to_le_bytes());
+
+        // An array container of 3, 5 and 65536 + 1 without run containers.
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(SERIAL_COOKIE_NO_RUNCONTAINER.to_le_bytes());
+        bytes.extend(2u32.to_le_bytes());
+        for (key, cardinality) in [(0u16, 2u16), (1, 1)] {
+            bytes.extend(key.to_le_bytes());
+            bytes.extend((cardinality - 1).to_le_bytes());
+        }
+        bytes.extend([0; 8]);
+        for v in [3u16, 5, 1] {
+            bytes.extend(v.to_le_bytes());
+        }
+
+        // A run container of 10 to 12 in the bitmap of the values with 32 high bits of 1.
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend(SERIAL_COOKIE.to_le_bytes());
+        bytes.push(1);
+        bytes.extend([0, 0, 2, 0]);
+        bytes.extend(1u16.to_le_bytes());
+        bytes.extend(10u16.to_le_bytes());
+        bytes.extend(2u16.to_le_bytes());
+
+        let rows = read_bitmap_array(&bytes).unwrap();
+        let high = 1u64 << 32;
+        assert_eq!(rows, [3, 5, 65537, high + 10, high + 11, high + 12]);
+    }
+}
@@ -0,0 +1,756 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use percent_encoding::percent_decode_str;
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use serde_json::Value;
+
+use super::deletion_vector::DeletionVector;
+use crate::prelude::*;
+
+/// The reader features of the Delta protocol that are supported.
+const SUPPORTED_READER_FEATURES: &[&str] = &[
+    "columnMapping",
+    "deletionVectors",
+    "timestampNtz",
+    "vacuumProtocolCheck",
+];
+
+/// A data file of a version of a Delta table.
+#[derive(Clone, Debug)]
+pub struct DeltaFile {
+    /// The path of the Parquet file.
+    pub path: PathBuf,
+    /// The values of the partition columns, in the order of
+    /// [`DeltaSnapshot::partition_columns`]. `None` is a null value.
+    pub partition_values: Vec<Option<String>>,
+    pub deletion_vector: Option<DeletionVector>,
+    /// The path of the file as it is in the log.
+    pub(super) log_path: String,
+}
+
+/// The files and the schema of a version of a Delta table, resolved from its transaction log.
+#[derive(Clone, Debug)]
+pub struct DeltaSnapshot {
+    pub version: i64,
+    pub schema: Schema,
+    pub partition_columns: Vec<PlSmallStr>,
+    /// The data files, ordered by their path.
+    pub files: Vec<DeltaFile>,
+    pub(super) table_id: String,
+    pub(super) configuration: PlHashMap<String, Option<String>>,
+    pub(super) protocol: Option<Protocol>,
+}
+
+impl DeltaSnapshot {
+    /// Read the transaction log of the table at `table_root`, up to `version` or to the latest
+    /// version of the table.
+    pub fn try_new(table_root: &Path, version: Option<i64>) -> PolarsResult<Self> {
+        let log_dir = table_root.join("_delta_log");
+        let entries = std::fs::read_dir(&log_dir).map_err(|e| {
+            polars_err!(
+                ComputeError: "could not read the transaction log of the Delta table at {}: {}",
+                table_root.display(), e
+            )
+        })?;
+
+        let mut commits = BTreeMap::new();
+        let mut checkpoints = BTreeMap::<i64, Vec<(Option<(u32, u32)>, PathBuf)>>::new();
+        for entry in entries {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            match parse_log_name(name) {
+                Some((version, LogFile::Commit)) => {
+                    commits.insert(version, path);
+                },
+                Some((version, LogFile::Checkpoint(part))) => {
+                    checkpoints.entry(version).or_default().push((part, path));
+                },
+                None => {},
+            }
+        }
+
+        let latest = commits
+            .keys()
+            .chain(checkpoints.keys())
+            .max()
+            .copied()
+            .ok_or_else(|| {
+                polars_err!(
+                    ComputeError: "no Delta table at {}: the transaction log is empty",
+                    table_root.display()
+                )
+            })?;
+        let version = version.unwrap_or(latest);
+        polars_ensure!(
+            (0..=latest).contains(&version),
+            ComputeError: "version {} of the Delta table doesn't exist, the latest version is {}",
+            version, latest
+        );
+
+        let mut replay = Replay::default();
+        // Start from the latest checkpoint of which all parts are written.
+        let checkpoint = checkpoints
+            .range(..=version)
+            .rev()
+            .find_map(|(&version, parts)| complete_checkpoint(parts).map(|parts| (version, parts)));
+        let first_commit = match checkpoint {
+            Some((checkpoint_version, parts)) => {
+                for path in parts {
+                    replay.apply_checkpoint(&path)?;
+                }
+                checkpoint_version + 1
+            },
+            None => 0,
+        };
+        for v in first_commit..=version {
+            let path = commits.get(&v).ok_or_else(|| {
+                polars_err!(
+                    ComputeError: "the transaction log of the Delta table is missing the commit of version {}",
+                    v
+                )
+            })?;
+            replay.apply_commit(path)?;
+        }
+
+        replay.finish(table_root, version)
+    }
+
+    /// The values of the partition columns of every file, parsed to the dtypes of the columns.
+    pub fn partition_values(&self) -> PolarsResult<Vec<Series>> {
+        self.partition_columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let dtype = self.schema.try_get(name)?;
+                let values = self
+                    .files
+                    .iter()
+                    .map(|file| file.partition_values[i].as_deref())
+                    .collect::<Vec<_>>();
+                parse_partition_values(name, dtype, values)
+            })
+            .collect()
+    }
+}
+
+/// Whether the transaction log of a Delta table exists at `table_root`.
+pub(super) fn has_log(table_root: &Path) -> PolarsResult<bool> {
+    let entries = match std::fs::read_dir(table_root.join("_delta_log")) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+        if entry?
+            .file_name()
+            .to_str()
+            .and_then(parse_log_name)
+            .is_some()
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+enum LogFile {
+    Commit,
+    /// A checkpoint, or one of the parts of a checkpoint with its number and the number of parts.
+    Checkpoint(Option<(u32, u32)>),
+}
+
+/// Parse the name of a file in the transaction log, which starts with a version of 20 digits.
+fn parse_log_name(name: &str) -> Option<(i64, LogFile)> {
+    let version = name.get(..20)?;
+    if !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let version = version.parse().ok()?;
+    let kind = match name.get(20..)? {
+        ".json" => LogFile::Commit,
+        ".checkpoint.parquet" => LogFile::Checkpoint(None),
+        rest => {
+            let parts = rest
+                .strip_prefix(".checkpoint.")?
+                .strip_suffix(".parquet")?;
+            let (part, n_parts) = parts.split_once('.')?;
+            LogFile::Checkpoint(Some((part.parse().ok()?, n_parts.parse().ok()?)))
+        },
+    };
+    Some((version, kind))
+}
+
+/// The files of a checkpoint if it is complete.
+fn complete_checkpoint(parts: &[(Option<(u32, u32)>, PathBuf)]) -> Option<Vec<PathBuf>> {
+    if let Some((_, path)) = parts.iter().find(|(part, _)| part.is_none()) {
+        return Some(vec![path.clone()]);
+    }
+    // Multi-part checkpoints may have been written more than once with a different number of
+    // parts.
+    let mut by_n_parts = BTreeMap::<u32, BTreeMap<u32, PathBuf>>::new();
+    for (part, path) in parts {
+        let (part, n_parts) = part.unwrap();
+        by_n_parts
+            .entry(n_parts)
+            .or_default()
+            .insert(part, path.clone());
+    }
+    by_n_parts
+        .into_iter()
+        .find(|(n_parts, parts)| parts.keys().copied().eq(1..=*n_parts))
+        .map(|(_, parts)| parts.into_values().collect())
+}
+
+/// Resolve the path of a file in the log, which is a URI or relative to the root of the table.
+pub(super) fn resolve_path(table_root: &Path, path: &str) -> PolarsResult<PathBuf> {
+    let decode = |path: &str| {
+        percent_decode_str(path)
+            .decode_utf8()
+            .map(|path| path.into_owned())
+            .map_err(to_compute_err)
+    };
+    if let Some(path) = path.strip_prefix("file:") {
+        let path = path.strip_prefix("//").unwrap_or(path);
+        return Ok(PathBuf::from(decode(path)?));
+    }
+    if path.contains("://") {
+        polars_bail!(nyi = "reading Delta tables with files in cloud storage");
+    }
+    Ok(table_root.join(decode(path)?))
+}
+
+struct AddFile {
+    path: String,
+    partition_values: PlHashMap<String, Option<String>>,
+    deletion_vector: Option<DeletionVector>,
+}
+
+struct Metadata {
+    id: String,
+    schema_string: String,
+    partition_columns: Vec<String>,
+    configuration: PlHashMap<String, Option<String>>,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Protocol {
+    pub(super) min_reader_version: i64,
+    pub(super) min_writer_version: i64,
+    pub(super) reader_features: Vec<String>,
+    pub(super) writer_features: Vec<String>,
+}
+
+/// The state of the table while its log is replayed.
+#[derive(Default)]
+struct Replay {
+    /// The files that are added, by their path and the identifier of their deletion vector.
+    files: PlHashMap<(String, Option<String>), AddFile>,
+    metadata: Option<Metadata>,
+    protocol: Option<Protocol>,
+}
+
+impl Replay {
+    fn apply_commit(&mut self, path: &Path) -> PolarsResult<()> {
+        let text = std::fs::read_to_string(path)?;
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let action: Value = serde_json::from_str(line).map_err(to_compute_err)?;
+            if let Some(add) = action.get("add") {
+                let deletion_vector = match add.get("deletionVector") {
+                    Some(dv) if !dv.is_null() => Some(json_deletion_vector(dv)?),
+                    _ => None,
+                };
+                let add = AddFile {
+                    path: json_str(add, "path")?.to_string(),
+                    partition_values: json_map(add.get("partitionValues")),
+                    deletion_vector,
+                };
+                let key = (
+                    add.path.clone(),
+                    add.deletion_vector.as_ref().map(|dv| dv.unique_id()),
+                );
+                self.files.insert(key, add);
+            } else if let Some(remove) = action.get("remove") {
+                let deletion_vector = match remove.get("deletionVector") {
+                    Some(dv) if !dv.is_null() => Some(json_deletion_vector(dv)?.unique_id()),
+                    _ => None,
+                };
+                self.files
+                    .remove(&(json_str(remove, "path")?.to_string(), deletion_vector));
+            } else if let Some(metadata) = action.get("metaData") {
+                self.metadata = Some(Metadata {
+                    id: json_str(metadata, "id")?.to_string(),
+                    schema_string: json_str(metadata, "schemaString")?.to_string(),
+                    partition_columns: json_strings(metadata.get("partitionColumns")),
+                    configuration: json_map(metadata.get("configuration")),
+                });
+            } else if let Some(protocol) = action.get("protocol") {
+                self.protocol = Some(Protocol {
+                    min_reader_version: protocol
+                        .get("minReaderVersion")
+                        .and_then(Value::as_i64)
+                        .unwrap_or(1),
+                    min_writer_version: protocol
+                        .get("minWriterVersion")
+                        .and_then(Value::as_i64)
+                        .unwrap_or(2),
+                    reader_features: json_strings(protocol.get("readerFeatures")),
+                    writer_features: json_strings(protocol.get("writerFeatures")),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the actions of a checkpoint, of which only the files that are added, the metadata
+    /// and the protocol are needed.
+    fn apply_checkpoint(&mut self, path: &Path) -> PolarsResult<()> {
+        let file = polars_utils::open_file(path)?;
+        let df = ParquetReader::new(file).finish()?;
+
+        if let Ok(protocol) = df.column("protocol") {
+            let protocol = protocol.struct_()?;
+            let version = protocol
+                .field_by_name("minReaderVersion")?
+                .cast(&DataType::Int64)?;
+            let writer_version = protocol
+                .field_by_name("minWriterVersion")?
+                .cast(&DataType::Int64)?;
+            let features = protocol.field_by_name("readerFeatures").ok();
+            let writer_features = protocol.field_by_name("writerFeatures").ok();
+            let writer_version = writer_version.i64()?;
+            for (i, version) in version.i64()?.iter().enumerate() {
+                if let Some(version) = version {
+                    self.protocol = Some(Protocol {
+                        min_reader_version: version,
+                        min_writer_version: writer_version.get(i).unwrap_or(2),
+                        reader_features: match &features {
+                            Some(features) => list_strings(features.list()?, i)?,
+                            None => vec![],
+                        },
+                        writer_features: match &writer_features {
+                            Some(features) => list_strings(features.list()?, i)?,
+                            None => vec![],
+                        },
+                    });
+                }
+            }
+        }
+
+        if let Ok(metadata) = df.column("metaData") {
+            let metadata = metadata.struct_()?;
+            let ids = metadata.field_by_name("id")?;
+            let schema = metadata.field_by_name("schemaString")?;
+            let partition_columns = metadata.field_by_name("partitionColumns")?;
+            let configuration = metadata.field_by_name("configuration").ok();
+            for (i, schema) in schema.str()?.iter().enumerate() {
+                if let Some(schema) = schema {
+                    self.metadata = Some(Metadata {
+                        id: ids.str()?.get(i).unwrap_or_default().to_string(),
+                        schema_string: schema.to_string(),
+                        partition_columns: list_strings(partition_columns.list()?, i)?,
+                        configuration: match &configuration {
+                            Some(configuration) => list_map(configuration.list()?, i)?,
+                            None => PlHashMap::new(),
+                        },
+                    });
+                }
+            }
+        }
+
+        if let Ok(add) = df.column("add") {
+            let add = add.struct_()?;
+            let paths = add.field_by_name("path")?;
+            let partition_values = add.field_by_name("partitionValues")?;
+            let deletion_vectors = add.field_by_name("deletionVector").ok();
+            for (i, path) in paths.str()?.iter().enumerate() {
+                let Some(path) = path else {
+                    continue;
+                };
+                let deletion_vector = match &deletion_vectors {
+                    Some(dvs) => struct_deletion_vector(dvs.struct_()?, i)?,
+                    None => None,
+                };
+                let add = AddFile {
+                    path: path.to_string(),
+                    partition_values: list_map(partition_values.list()?, i)?,
+                    deletion_vector,
+                };
+                let key = (
+                    add.path.clone(),
+                    add.deletion_vector.as_ref().map(|dv| dv.unique_id()),
+                );
+                self.files.insert(key, add);
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self, table_root: &Path, version: i64) -> PolarsResult<DeltaSnapshot> {
+        let metadata = self.metadata.ok_or_else(
+            || polars_err!(ComputeError: "out-of-spec Delta table: the log has no metadata"),
+        )?;
+        if let Some(protocol) = &self.protocol {
+            if protocol.min_reader_version > 3 {
+                polars_bail!(
+                    nyi = "reading Delta tables of reader version {}",
+                    protocol.min_reader_version
+                );
+            }
+            if let Some(feature) = protocol
+                .reader_features
+                .iter()
+                .find(|feature| !SUPPORTED_READER_FEATURES.contains(&feature.as_str()))
+            {
+                polars_bail!(
+                    nyi = "reading Delta tables with the reader feature '{}'",
+                    feature
+                );
+            }
+        }
+        if let Some(Some(mode)) = metadata.configuration.get("delta.columnMapping.mode") {
+            if mode != "none" {
+                polars_bail!(nyi = "reading Delta tables with column mapping");
+            }
+        }
+
+        let schema = parse_schema(&metadata.schema_string)?;
+        let partition_columns = metadata
+            .partition_columns
+            .iter()
+            .map(|name| PlSmallStr::from_str(name))
+            .collect::<Vec<_>>();
+        let mut files = self
+            .files
+            .into_values()
+            .map(|add| {
+                Ok(DeltaFile {
+                    path: resolve_path(table_root, &add.path)?,
+                    partition_values: metadata
+                        .partition_columns
+                        .iter()
+                        .map(|name| add.partition_values.get(name).cloned().flatten())
+                        .collect(),
+                    deletion_vector: add.deletion_vector,
+                    log_path: add.path,
+                })
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(DeltaSnapshot {
+            version,
+            schema,
+            partition_columns,
+            files,
+            table_id: metadata.id,
+            configuration: metadata.configuration,
+            protocol: self.protocol,
+        })
+    }
+}
+
+fn json_str<'a>(value: &'a Value, key: &str) -> PolarsResult<&'a str> {
+    value.get(key).and_then(Value::as_str).ok_or_else(
+        || polars_err!(ComputeError: "out-of-spec Delta table: action without '{}'", key),
+    )
+}
+
+fn json_strings(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn json_map(value: Option<&Value>) -> PlHashMap<String, Option<String>> {
+    value
+        .and_then(Value::as_object)
+        .map(|map| {
+            map.iter()
+                .map(|(k, v)| (k.clone(), v.as_str().map(str::to_string)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn json_deletion_vector(value: &Value) -> PolarsResult<DeletionVector> {
+    let int = |key| {
+        value.get(key).and_then(Value::as_i64).ok_or_else(|| {
+            polars_err!(ComputeError: "out-of-spec Delta table: deletion vector without '{}'", key)
+        })
+    };
+    Ok(DeletionVector {
+        storage_type: json_str(value, "storageType")?.to_string(),
+        path_or_inline_dv: json_str(value, "pathOrInlineDv")?.to_string(),
+        offset: value.get("offset").and_then(Value::as_i64),
+        size_in_bytes: int("sizeInBytes")?,
+        cardinality: int("cardinality")?,
+    })
+}
+
+fn list_strings(list: &ListChunked, i: usize) -> PolarsResult<Vec<String>> {
+    let Some(values) = list.get_as_series(i) else {
+        return Ok(vec![]);
+    };
+    Ok(values.str()?.iter().flatten().map(str::to_string).collect())
+}
+
+/// Read a map of strings, which is a list of structs with a key and a value.
+fn list_map(list: &ListChunked, i: usize) -> PolarsResult<PlHashMap<String, Option<String>>> {
+    let Some(entries) = list.get_as_series(i) else {
+        return Ok(PlHashMap::new());
+    };
+    let entries = entries.struct_()?;
+    let keys = entries.field_by_name("key")?;
+    let values = entries.field_by_name("value")?;
+    Ok(keys
+        .str()?
+        .iter()
+        .zip(values.str()?.iter())
+        .filter_map(|(k, v)| Some((k?.to_string(), v.map(str::to_string))))
+        .collect())
+}
+
+fn struct_deletion_vector(dvs: &StructChunked, i: usize) -> PolarsResult<Option<DeletionVector>> {
+    let string = |name| -> PolarsResult<Option<String>> {
+        Ok(dvs.field_by_name(name)?.str()?.get(i).map(str::to_string))
+    };
+    let int = |name| -> PolarsResult<Option<i64>> {
+        Ok(dvs
+            .field_by_name(name)?
+            .cast(&DataType::Int64)?
+            .i64()?
+            .get(i))
+    };
+    let Some(storage_type) = string("storageType")? else {
+        return Ok(None);
+    };
+    let missing = || polars_err!(ComputeError: "out-of-spec Delta table: incomplete deletion vector in checkpoint");
+    Ok(Some(DeletionVector {
+        storage_type,
+        path_or_inline_dv: string("pathOrInlineDv")?.ok_or_else(missing)?,
+        offset: int("offset")?,
+        size_in_bytes: int("sizeInBytes")?.ok_or_else(missing)?,
+        cardinality: int("cardinality")?.ok_or_else(missing)?,
+    }))
+}
+
+/// Parse the schema of a table, which is a Delta struct type in JSON.
+pub(super) fn parse_schema(schema_string: &str) -> PolarsResult<Schema> {
+    let value: Value = serde_json::from_str(schema_string).map_err(to_compute_err)?;
+    match parse_type(&value)? {
+        DataType::Struct(fields) => Ok(Schema::from_iter(fields)),
+        _ => polars_bail!(ComputeError: "out-of-spec Delta table: the schema is not a struct"),
+    }
+}
+
+fn parse_type(value: &Value) -> PolarsResult<DataType> {
+    let invalid = || polars_err!(ComputeError: "out-of-spec Delta table: invalid type {}", value);
+    if let Some(name) = value.as_str() {
+        return Ok(match name {
+            "string" => DataType::String,
+            "long" => DataType::Int64,
+            "integer" => DataType::Int32,
+            "short" => DataType::Int16,
+            "byte" => DataType::Int8,
+            "float" => DataType::Float32,
+            "double" => DataType::Float64,
+            "boolean" => DataType::Boolean,
+            "binary" => DataType::Binary,
+            "date" => DataType::Date,
+            "timestamp" => {
+                DataType::Datetime(TimeUnit::Microseconds, Some(PlSmallStr::from_static("UTC")))
+            },
+            "timestamp_ntz" => DataType::Datetime(TimeUnit::Microseconds, None),
+            name if name.starts_with("decimal(") => {
+                #[cfg(feature = "dtype-decimal")]
+                {
+                    let (precision, scale) = name
+                        .strip_prefix("decimal(")
+                        .and_then(|name| name.strip_suffix(')'))
+                        .and_then(|name| name.split_once(','))
+                        .ok_or_else(invalid)?;
+                    DataType::Decimal(
+                        Some(precision.trim().parse().map_err(|_| invalid())?),
+                        Some(scale.trim().parse().map_err(|_| invalid())?),
+                    )
+                }
+                #[cfg(not(feature = "dtype-decimal"))]
+                {
+                    polars_bail!(nyi = "reading Delta tables with decimal columns without the 'dtype-decimal' feature")
+                }
+            },
+            _ => polars_bail!(nyi = "reading Delta tables with columns of type '{}'", name),
+        });
+    }
+
+    match value.get("type").and_then(Value::as_str) {
+        Some("struct") => {
+            let fields = value
+                .get("fields")
+                .and_then(Value::as_array)
+                .ok_or_else(invalid)?
+                .iter()
+                .map(|field| {
+                    let name = json_str(field, "name")?;
+                    let dtype = parse_type(field.get("type").ok_or_else(invalid)?)?;
+                    Ok(Field::new(PlSmallStr::from_str(name), dtype))
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            Ok(DataType::Struct(fields))
+        },
+        Some("array") => Ok(DataType::List(Box::new(parse_type(
+            value.get("elementType").ok_or_else(invalid)?,
+        )?))),
+        Some("map") => {
+            let key = parse_type(value.get("keyType").ok_or_else(invalid)?)?;
+            let value = parse_type(value.get("valueType").ok_or_else(invalid)?)?;
+            Ok(DataType::List(Box::new(DataType::Struct(vec![
+                Field::new(PlSmallStr::from_static("key"), key),
+                Field::new(PlSmallStr::from_static("value"), value),
+            ]))))
+        },
+        _ => Err(invalid()),
+    }
+}
+
+/// Parse the values of a partition column, which are serialized as strings in the log.
+fn parse_partition_values(
+    name: &PlSmallStr,
+    dtype: &DataType,
+    values: Vec<Option<&str>>,
+) -> PolarsResult<Series> {
+    let invalid = |value: &str| {
+        polars_err!(
+            ComputeError: "invalid value `{}` of the partition column '{}' of dtype {}",
+            value, name, dtype
+        )
+    };
+    match dtype {
+        DataType::Boolean => {
+            let values = values
+                .into_iter()
+                .map(|value| {
+                    value
+                        .map(|value| match value {
+                            "true" => Ok(true),
+                            "false" => Ok(false),
+                            _ => Err(invalid(value)),
+                        })
+                        .transpose()
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            Ok(Series::new(name.clone(), values))
+        },
+        DataType::Date => {
+            let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+            let values = values
+                .into_iter()
+                .map(|value| {
+                    value
+                        .map(|value| {
+                            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                                .map(|date| (date - epoch).num_days() as i32)
+                                .map_err(|_| invalid(value))
+                        })
+                        .transpose()
+                })
+                .collect::<PolarsResult<Int32Chunked>>()?;
+            Ok(values.with_name(name.clone()).into_date().into_series())
+        },
+        DataType::Datetime(tu, tz) => {
+            let values = values
+                .into_iter()
+                .map(|value| {
+                    value
+                        .map(|value| {
+                            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+                                .or_else(|_| {
+                                    DateTime::parse_from_rfc3339(value).map(|dt| dt.naive_utc())
+                                })
+                                .map(|dt| dt.and_utc().timestamp_micros())
+                                .map_err(|_| invalid(value))
+                        })
+                        .transpose()
+                })
+                .collect::<PolarsResult<Int64Chunked>>()?;
+            values
+                .with_name(name.clone())
+                .into_datetime(TimeUnit::Microseconds, tz.clone())
+                .into_series()
+                .cast(&DataType::Datetime(*tu, tz.clone()))
+        },
+        DataType::String => Ok(Series::new(name.clone(), values)),
+        dtype => Series::new(name.clone(), values).strict_cast(dtype),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_name() {
+        assert!(matches!(
+            parse_log_name("00000000000000000010.json"),
+            Some((10, LogFile::Commit))
+        ));
+        assert!(matches!(
+            parse_log_name("00000000000000000010.checkpoint.parquet"),
+            Some((10, LogFile::Checkpoint(None)))
+        ));
+        assert!(matches!(
+            parse_log_name("00000000000000000010.checkpoint.0000000002.0000000003.parquet"),
+            Some((10, LogFile::Checkpoint(Some((2, 3)))))
+        ));
+        assert!(parse_log_name("_last_checkpoint").is_none());
+        assert!(parse_log_name("00000000000000000010.crc").is_none());
+    }
+
+    #[test]
+    fn test_parse_schema() {
+        let schema = parse_schema(
+            r#"{"type":"struct","fields":[
+                {"name":"id","type":"long","nullable":true,"metadata":{}},
+                {"name":"tags","type":{"type":"array","elementType":"string","containsNull":true},"nullable":true,"metadata":{}},
+                {"name":"ts","type":"timestamp_ntz","nullable":true,"metadata":{}}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            schema,
+            Schema::from_iter([
+                Field::new("id".into(), DataType::Int64),
+                Field::new("tags".into(), DataType::List(Box::new(DataType::String))),
+                Field::new(
+                    "ts".into(),
+                    DataType::Datetime(TimeUnit::Microseconds, None)
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let root = Path::new("/data/table");
+        assert_eq!(
+            resolve_path(root, "p=a%20b/part-0.parquet").unwrap(),
+            Path::new("/data/table/p=a b/part-0.parquet")
+        );
+        assert_eq!(
+            resolve_path(root, "file:///other/part-0.parquet").unwrap(),
+            Path::new("/other/part-0.parquet")
+        );
+        assert!(resolve_path(root, "s3://bucket/part-0.parquet").is_err());
+    }
+}
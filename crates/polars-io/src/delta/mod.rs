@@ -0,0 +1,12 @@
+//! Read and write [Delta Lake] tables: the transaction log of a table is read to resolve the
+//! Parquet files and the deleted rows of a version of the table, and new versions are
+//! committed to it.
+//!
+//! [Delta Lake]: https://delta.io
+mod deletion_vector;
+mod log;
+mod write;
+
+pub use deletion_vector::DeletionVector;
+pub use log::{DeltaFile, DeltaSnapshot};
+pub use write::{write_delta, DeltaTransaction, DeltaWriteMode, DeltaWriteOptions};
@@ -0,0 +1,649 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use arrow::temporal_conversions::{date32_to_date, timestamp_us_to_datetime};
+use polars_core::prelude::*;
+use serde_json::{json, Map, Value};
+
+use super::log::{has_log, parse_schema, Protocol};
+use super::DeltaSnapshot;
+use crate::parquet::write::ParquetWriteOptions;
+use crate::utils::URL_ENCODE_CHAR_SET;
+
+/// The writer features of the Delta protocol that are supported.
+const SUPPORTED_WRITER_FEATURES: &[&str] = &[
+    "appendOnly",
+    "deletionVectors",
+    "invariants",
+    "timestampNtz",
+    "vacuumProtocolCheck",
+];
+
+/// How the data is written to a Delta table that already exists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DeltaWriteMode {
+    /// Add the data to the table.
+    #[default]
+    Append,
+    /// Replace the data of the table.
+    Overwrite,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DeltaWriteOptions {
+    pub mode: DeltaWriteMode,
+    /// The partition columns of a new table. The partition columns of an existing table must
+    /// be the same, unless its schema is overwritten.
+    pub partition_by: Option<Vec<PlSmallStr>>,
+    /// In overwrite mode, replace the schema and the partition columns of the table with those
+    /// of the data, instead of raising an error if they differ.
+    pub overwrite_schema: bool,
+    pub parquet_options: ParquetWriteOptions,
+}
+
+impl DeltaWriteOptions {
+    pub fn with_mode(mut self, mode: DeltaWriteMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Partition a new table by these columns.
+    pub fn with_partition_by(mut self, partition_by: Option<Vec<PlSmallStr>>) -> Self {
+        self.partition_by = partition_by;
+        self
+    }
+
+    /// Replace the schema of the table in overwrite mode.
+    pub fn with_overwrite_schema(mut self, overwrite_schema: bool) -> Self {
+        self.overwrite_schema = overwrite_schema;
+        self
+    }
+
+    /// Set the options with which the Parquet data files are written.
+    pub fn with_parquet_options(mut self, parquet_options: ParquetWriteOptions) -> Self {
+        self.parquet_options = parquet_options;
+        self
+    }
+}
+
+/// Write `df` to the Delta table at `table_root`, which is created if it doesn't exist, and
+/// return the version of the table that is committed.
+pub fn write_delta(
+    df: &DataFrame,
+    table_root: impl AsRef<Path>,
+    options: &DeltaWriteOptions,
+) -> PolarsResult<i64> {
+    let mut transaction = DeltaTransaction::try_new(table_root.as_ref(), &df.schema(), options)?;
+    transaction.write(df)?;
+    transaction.commit()
+}
+
+/// A write to a Delta table. The data files are written first, and are then committed at once
+/// as the next version of the table, so that readers never see a part of the data.
+pub struct DeltaTransaction {
+    table_root: PathBuf,
+    mode: DeltaWriteMode,
+    /// The version that is committed.
+    version: i64,
+    /// The schema of the table after the commit.
+    schema: Schema,
+    partition_columns: Vec<PlSmallStr>,
+    /// The actions other than adding the new files.
+    actions: Vec<Value>,
+    /// The data files that are written, with the actions that add them.
+    files: Vec<(PathBuf, Value)>,
+    parquet_options: ParquetWriteOptions,
+}
+
+impl DeltaTransaction {
+    /// Start a write of data with `schema` to the table at `table_root`. The schema of the data
+    /// is checked against the schema of the table if it exists.
+    pub fn try_new(
+        table_root: &Path,
+        schema: &Schema,
+        options: &DeltaWriteOptions,
+    ) -> PolarsResult<Self> {
+        let schema_string = delta_schema(schema)?.to_string();
+        let data_schema = parse_schema(&schema_string)?;
+        let snapshot = if has_log(table_root)? {
+            Some(DeltaSnapshot::try_new(table_root, None)?)
+        } else {
+            None
+        };
+
+        let mut actions = vec![];
+        let (version, schema, partition_columns) = match snapshot {
+            Some(snapshot) => {
+                check_protocol(&snapshot, options.mode)?;
+                let same_partitioning = options
+                    .partition_by
+                    .as_ref()
+                    .map_or(true, |columns| *columns == snapshot.partition_columns);
+                let replace_schema = options.mode == DeltaWriteMode::Overwrite
+                    && options.overwrite_schema
+                    && (data_schema != snapshot.schema || !same_partitioning);
+
+                if options.mode == DeltaWriteMode::Overwrite {
+                    let now = now();
+                    actions.extend(snapshot.files.iter().map(|file| {
+                        let mut remove = json!({
+                            "path": file.log_path,
+                            "deletionTimestamp": now,
+                            "dataChange": true,
+                        });
+                        if let Some(dv) = &file.deletion_vector {
+                            remove["deletionVector"] = json!({
+                                "storageType": dv.storage_type,
+                                "pathOrInlineDv": dv.path_or_inline_dv,
+                                "offset": dv.offset,
+                                "sizeInBytes": dv.size_in_bytes,
+                                "cardinality": dv.cardinality,
+                            });
+                        }
+                        json!({ "remove": remove })
+                    }));
+                }
+
+                if replace_schema {
+                    let partition_columns = options.partition_by.clone().unwrap_or_default();
+                    check_partition_columns(&data_schema, &partition_columns)?;
+                    if let Some(protocol) = upgraded_protocol(snapshot.protocol, &data_schema) {
+                        actions.push(protocol);
+                    }
+                    actions.push(metadata(
+                        &snapshot.table_id,
+                        &schema_string,
+                        &partition_columns,
+                        &snapshot.configuration,
+                    ));
+                    (snapshot.version + 1, data_schema, partition_columns)
+                } else {
+                    polars_ensure!(
+                        same_partitioning,
+                        SchemaMismatch: "the Delta table is partitioned by {:?}, not by {:?}",
+                        snapshot.partition_columns, options.partition_by.as_deref().unwrap_or_default()
+                    );
+                    check_schema(&snapshot.schema, &data_schema)?;
+                    (
+                        snapshot.version + 1,
+                        snapshot.schema,
+                        snapshot.partition_columns,
+                    )
+                }
+            },
+            None => {
+                let partition_columns = options.partition_by.clone().unwrap_or_default();
+                check_partition_columns(&data_schema, &partition_columns)?;
+                actions.push(upgraded_protocol(None, &data_schema).unwrap());
+                actions.push(metadata(
+                    &uuid::Uuid::new_v4().to_string(),
+                    &schema_string,
+                    &partition_columns,
+                    &PlHashMap::new(),
+                ));
+                (0, data_schema, partition_columns)
+            },
+        };
+
+        Ok(Self {
+            table_root: table_root.to_path_buf(),
+            mode: options.mode,
+            version,
+            schema,
+            partition_columns,
+            actions,
+            files: vec![],
+            parquet_options: options.parquet_options.clone(),
+        })
+    }
+
+    /// The schema of the table after the commit, to which the data is cast.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn partition_columns(&self) -> &[PlSmallStr] {
+        &self.partition_columns
+    }
+
+    /// A new path for a data file of the partition with these values, of which the directory is
+    /// created.
+    pub fn data_file(&self, partition_values: &[Option<String>]) -> PolarsResult<PathBuf> {
+        polars_ensure!(
+            partition_values.len() == self.partition_columns.len(),
+            ComputeError: "expected {} partition values, got {}",
+            self.partition_columns.len(), partition_values.len()
+        );
+        let mut dir = self.table_root.clone();
+        for (name, value) in self.partition_columns.iter().zip(partition_values) {
+            let value = value.as_deref().unwrap_or("__HIVE_DEFAULT_PARTITION__");
+            dir.push(format!(
+                "{}={}",
+                percent_encoding::utf8_percent_encode(name, URL_ENCODE_CHAR_SET),
+                percent_encoding::utf8_percent_encode(value, URL_ENCODE_CHAR_SET)
+            ));
+        }
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir.join(format!(
+            "part-{:05}-{}.parquet",
+            self.files.len(),
+            uuid::Uuid::new_v4()
+        )))
+    }
+
+    /// Add a data file of `num_records` rows that has been written to `path`, which must be in
+    /// the directory of the table.
+    pub fn add_file(
+        &mut self,
+        path: PathBuf,
+        partition_values: Vec<Option<String>>,
+        num_records: usize,
+    ) -> PolarsResult<()> {
+        let relative = path.strip_prefix(&self.table_root).map_err(|_| {
+            polars_err!(
+                ComputeError: "data file {} is not in the Delta table at {}",
+                path.display(), self.table_root.display()
+            )
+        })?;
+        // The directories of the partitions are already percent-encoded, so only their percent
+        // signs are encoded again.
+        let log_path = relative
+            .iter()
+            .map(|part| part.to_string_lossy().replace('%', "%25"))
+            .collect::<Vec<_>>()
+            .join("/");
+        let partition_values = self
+            .partition_columns
+            .iter()
+            .map(|name| name.to_string())
+            .zip(partition_values.into_iter().map(Value::from))
+            .collect::<Map<_, _>>();
+        let add = json!({
+            "add": {
+                "path": log_path,
+                "partitionValues": partition_values,
+                "size": std::fs::metadata(&path)?.len(),
+                "modificationTime": now(),
+                "dataChange": true,
+                "stats": json!({ "numRecords": num_records }).to_string(),
+            }
+        });
+        self.files.push((path, add));
+        Ok(())
+    }
+
+    /// Write `df` to data files, a file per partition.
+    pub fn write(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        let data_schema = parse_schema(&delta_schema(&df.schema())?.to_string())?;
+        check_schema(&self.schema, &data_schema)?;
+        let columns = self
+            .schema
+            .iter()
+            .map(|(name, dtype)| df.column(name)?.cast(dtype))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let df = DataFrame::new(columns)?;
+        if df.height() == 0 {
+            return Ok(());
+        }
+        if self.partition_columns.is_empty() {
+            return self.write_file(df, vec![]);
+        }
+
+        for partition in df.partition_by_stable(self.partition_columns.clone(), true)? {
+            let values = self
+                .partition_columns
+                .iter()
+                .map(|name| partition_value(partition.column(name)?))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            let data = partition.drop_many(self.partition_columns.iter().cloned());
+            self.write_file(data, values)?;
+        }
+        Ok(())
+    }
+
+    fn write_file(
+        &mut self,
+        mut df: DataFrame,
+        partition_values: Vec<Option<String>>,
+    ) -> PolarsResult<()> {
+        let path = self.data_file(&partition_values)?;
+        let file = std::fs::File::create(&path)?;
+        self.parquet_options.to_writer(file).finish(&mut df)?;
+        self.add_file(path, partition_values, df.height())
+    }
+
+    /// Commit the data files as the next version of the table, and return that version. This
+    /// fails if another writer has committed that version in the meantime, after which the data
+    /// files are removed.
+    pub fn commit(self) -> PolarsResult<i64> {
+        let operation = match (self.version, self.mode) {
+            (0, _) => "CREATE TABLE",
+            (_, DeltaWriteMode::Append) => "WRITE",
+            (_, DeltaWriteMode::Overwrite) => "OVERWRITE",
+        };
+        let commit_info = json!({
+            "commitInfo": {
+                "timestamp": now(),
+                "operation": operation,
+                "operationParameters": { "mode": format!("{:?}", self.mode) },
+                "engineInfo": concat!("polars/", env!("CARGO_PKG_VERSION")),
+            }
+        });
+        let mut commit = String::new();
+        for action in std::iter::once(&commit_info)
+            .chain(&self.actions)
+            .chain(self.files.iter().map(|(_, add)| add))
+        {
+            commit.push_str(&action.to_string());
+            commit.push('\n');
+        }
+
+        let log_dir = self.table_root.join("_delta_log");
+        std::fs::create_dir_all(&log_dir)?;
+        let path = log_dir.join(format!("{:020}.json", self.version));
+        // The complete commit is linked to its path, which fails if the path exists, so that a
+        // commit is never partially visible or overwritten.
+        let tmp_path = log_dir.join(format!(".{}.json.tmp", uuid::Uuid::new_v4()));
+        std::fs::write(&tmp_path, commit)?;
+        let result = std::fs::hard_link(&tmp_path, &path);
+        let _ = std::fs::remove_file(&tmp_path);
+
+        if let Err(e) = result {
+            for (path, _) in &self.files {
+                let _ = std::fs::remove_file(path);
+            }
+            if e.kind() == std::io::ErrorKind::AlreadyExists {
+                polars_bail!(
+                    ComputeError: "version {} of the Delta table was committed by a concurrent write",
+                    self.version
+                );
+            }
+            return Err(e.into());
+        }
+        Ok(self.version)
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64)
+}
+
+fn check_protocol(snapshot: &DeltaSnapshot, mode: DeltaWriteMode) -> PolarsResult<()> {
+    if let Some(protocol) = &snapshot.protocol {
+        let version = protocol.min_writer_version;
+        if version > 7 || (version > 2 && version < 7) {
+            polars_bail!(nyi = "writing Delta tables of writer version {}", version);
+        }
+        if let Some(feature) = protocol
+            .writer_features
+            .iter()
+            .find(|feature| !SUPPORTED_WRITER_FEATURES.contains(&feature.as_str()))
+        {
+            polars_bail!(
+                nyi = "writing Delta tables with the writer feature '{}'",
+                feature
+            );
+        }
+    }
+    let append_only = snapshot.configuration.get("delta.appendOnly");
+    polars_ensure!(
+        mode == DeltaWriteMode::Append || !matches!(append_only, Some(Some(v)) if v == "true"),
+        InvalidOperation: "the Delta table is append-only, it can't be overwritten"
+    );
+    Ok(())
+}
+
+/// The protocol action that supports the columns of `schema`, or `None` if `protocol` already
+/// supports them.
+fn upgraded_protocol(protocol: Option<Protocol>, schema: &Schema) -> Option<Value> {
+    let timestamp_ntz = schema.iter_values().any(has_timestamp_ntz);
+    match protocol {
+        Some(protocol)
+            if !timestamp_ntz
+                || protocol
+                    .reader_features
+                    .iter()
+                    .any(|feature| feature == "timestampNtz") =>
+        {
+            None
+        },
+        Some(mut protocol) => {
+            // The features of the legacy writer versions are listed once the table has table
+            // features.
+            if protocol.min_writer_version < 7 {
+                protocol.writer_features = vec!["appendOnly".into(), "invariants".into()];
+            }
+            protocol.reader_features.push("timestampNtz".into());
+            protocol.writer_features.push("timestampNtz".into());
+            Some(json!({
+                "protocol": {
+                    "minReaderVersion": 3,
+                    "minWriterVersion": 7,
+                    "readerFeatures": protocol.reader_features,
+                    "writerFeatures": protocol.writer_features,
+                }
+            }))
+        },
+        None if timestamp_ntz => Some(json!({
+            "protocol": {
+                "minReaderVersion": 3,
+                "minWriterVersion": 7,
+                "readerFeatures": ["timestampNtz"],
+                "writerFeatures": ["timestampNtz"],
+            }
+        })),
+        None => Some(json!({
+            "protocol": { "minReaderVersion": 1, "minWriterVersion": 2 }
+        })),
+    }
+}
+
+fn has_timestamp_ntz(dtype: &DataType) -> bool {
+    match dtype {
+        DataType::Datetime(_, None) => true,
+        DataType::List(inner) => has_timestamp_ntz(inner),
+        DataType::Struct(fields) => fields.iter().any(|f| has_timestamp_ntz(f.dtype())),
+        _ => false,
+    }
+}
+
+fn metadata(
+    id: &str,
+    schema_string: &str,
+    partition_columns: &[PlSmallStr],
+    configuration: &PlHashMap<String, Option<String>>,
+) -> Value {
+    json!({
+        "metaData": {
+            "id": id,
+            "format": { "provider": "parquet", "options": {} },
+            "schemaString": schema_string,
+            "partitionColumns": partition_columns.iter().map(|name| name.as_str()).collect::<Vec<_>>(),
+            "configuration": configuration
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::from(v.clone())))
+                .collect::<Map<_, _>>(),
+            "createdTime": now(),
+        }
+    })
+}
+
+fn check_partition_columns(schema: &Schema, partition_columns: &[PlSmallStr]) -> PolarsResult<()> {
+    for name in partition_columns {
+        let dtype = schema.try_get(name)?;
+        polars_ensure!(
+            !dtype.is_nested() && !matches!(dtype, DataType::Binary),
+            InvalidOperation: "can't partition a Delta table by column '{}' of dtype {}", name, dtype
+        );
+    }
+    polars_ensure!(
+        partition_columns.len() < schema.len(),
+        InvalidOperation: "a Delta table can't be partitioned by all of its columns"
+    );
+    Ok(())
+}
+
+/// Check that the data has the columns of the table, with the same dtypes once they are
+/// converted to Delta types.
+fn check_schema(table: &Schema, data: &Schema) -> PolarsResult<()> {
+    for (name, dtype) in table.iter() {
+        let data_dtype = data.get(name).ok_or_else(|| {
+            polars_err!(
+                SchemaMismatch: "column '{}' of the Delta table is missing in the data", name
+            )
+        })?;
+        polars_ensure!(
+            data_dtype == dtype,
+            SchemaMismatch: "column '{}' is written to the Delta table as dtype {}, but its dtype in the table is {}",
+            name, data_dtype, dtype
+        );
+    }
+    if let Some(name) = data.iter_names().find(|name| !table.contains(name)) {
+        polars_bail!(SchemaMismatch: "column '{}' is not in the schema of the Delta table", name);
+    }
+    Ok(())
+}
+
+/// The schema of a table with the columns of `schema`, as a Delta struct type in JSON.
+fn delta_schema(schema: &Schema) -> PolarsResult<Value> {
+    let fields = schema
+        .iter()
+        .map(|(name, dtype)| delta_field(name, dtype))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    Ok(json!({ "type": "struct", "fields": fields }))
+}
+
+fn delta_field(name: &str, dtype: &DataType) -> PolarsResult<Value> {
+    Ok(json!({
+        "name": name,
+        "type": delta_type(dtype)?,
+        "nullable": true,
+        "metadata": {},
+    }))
+}
+
+fn delta_type(dtype: &DataType) -> PolarsResult<Value> {
+    Ok(match dtype {
+        DataType::String => json!("string"),
+        #[cfg(feature = "dtype-categorical")]
+        DataType::Categorical(_, _) | DataType::Enum(_, _) => json!("string"),
+        DataType::Int64 => json!("long"),
+        DataType::Int32 => json!("integer"),
+        DataType::Int16 => json!("short"),
+        DataType::Int8 => json!("byte"),
+        DataType::Float32 => json!("float"),
+        DataType::Float64 => json!("double"),
+        DataType::Boolean => json!("boolean"),
+        DataType::Binary => json!("binary"),
+        DataType::Date => json!("date"),
+        DataType::Datetime(_, Some(_)) => json!("timestamp"),
+        DataType::Datetime(_, None) => json!("timestamp_ntz"),
+        #[cfg(feature = "dtype-decimal")]
+        DataType::Decimal(precision, scale) => {
+            json!(format!(
+                "decimal({},{})",
+                precision.unwrap_or(38),
+                scale.unwrap_or(0)
+            ))
+        },
+        DataType::List(inner) => json!({
+            "type": "array",
+            "elementType": delta_type(inner)?,
+            "containsNull": true,
+        }),
+        DataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|f| delta_field(f.name(), f.dtype()))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            json!({ "type": "struct", "fields": fields })
+        },
+        dtype => polars_bail!(nyi = "writing columns of dtype {} to Delta tables", dtype),
+    })
+}
+
+/// The value of a partition column of a partition, serialized as in the log.
+fn partition_value(s: &Series) -> PolarsResult<Option<String>> {
+    Ok(match s.get(0)? {
+        AnyValue::Null => None,
+        AnyValue::String(v) => Some(v.to_string()),
+        AnyValue::Date(days) => Some(date32_to_date(days).to_string()),
+        AnyValue::Datetime(v, TimeUnit::Microseconds, _) => Some(
+            timestamp_us_to_datetime(v)
+                .format("%Y-%m-%d %H:%M:%S%.6f")
+                .to_string(),
+        ),
+        v @ (AnyValue::Boolean(_)
+        | AnyValue::Int8(_)
+        | AnyValue::Int16(_)
+        | AnyValue::Int32(_)
+        | AnyValue::Int64(_)
+        | AnyValue::Float32(_)
+        | AnyValue::Float64(_)) => Some(v.to_string()),
+        v => polars_bail!(
+            nyi = "writing Delta tables partitioned by columns of dtype {}",
+            v.dtype()
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta_schema() {
+        let schema = Schema::from_iter([
+            (PlSmallStr::from_static("a"), DataType::Int64),
+            (
+                PlSmallStr::from_static("b"),
+                DataType::Datetime(TimeUnit::Nanoseconds, Some("Europe/Amsterdam".into())),
+            ),
+            (
+                PlSmallStr::from_static("c"),
+                DataType::List(Box::new(DataType::Datetime(TimeUnit::Milliseconds, None))),
+            ),
+        ]);
+        let table_schema = parse_schema(&delta_schema(&schema).unwrap().to_string()).unwrap();
+        let expected = Schema::from_iter([
+            (PlSmallStr::from_static("a"), DataType::Int64),
+            (
+                PlSmallStr::from_static("b"),
+                DataType::Datetime(TimeUnit::Microseconds, Some("UTC".into())),
+            ),
+            (
+                PlSmallStr::from_static("c"),
+                DataType::List(Box::new(DataType::Datetime(TimeUnit::Microseconds, None))),
+            ),
+        ]);
+        assert_eq!(table_schema, expected);
+        let protocol = upgraded_protocol(None, &table_schema).unwrap();
+        assert_eq!(protocol["protocol"]["minReaderVersion"], 3);
+
+        let schema = Schema::from_iter([(PlSmallStr::from_static("a"), DataType::UInt32)]);
+        assert!(delta_schema(&schema).is_err());
+    }
+
+    #[test]
+    fn test_check_schema() {
+        let table = Schema::from_iter([
+            (PlSmallStr::from_static("a"), DataType::Int64),
+            (PlSmallStr::from_static("b"), DataType::String),
+        ]);
+        let data = Schema::from_iter([
+            (PlSmallStr::from_static("b"), DataType::String),
+            (PlSmallStr::from_static("a"), DataType::Int64),
+        ]);
+        assert!(check_schema(&table, &data).is_ok());
+
+        let data = Schema::from_iter([(PlSmallStr::from_static("a"), DataType::Int64)]);
+        assert!(check_schema(&table, &data).is_err());
+        let data = Schema::from_iter([
+            (PlSmallStr::from_static("a"), DataType::Int32),
+            (PlSmallStr::from_static("b"), DataType::String),
+        ]);
+        assert!(check_schema(&table, &data).is_err());
+    }
+}
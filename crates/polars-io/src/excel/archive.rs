@@ -0,0 +1,238 @@
+//! A minimal reader and writer of the ZIP archives that XLSX workbooks are stored in.
+//!
+//! Only what the workbook parts need is supported: entries that are stored or deflated, in an
+//! archive without ZIP64 extensions.
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use polars_core::prelude::*;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+/// The version of the ZIP specification needed to extract deflated entries.
+const VERSION: u16 = 20;
+/// The DOS date of 1980-01-01, the earliest date of a ZIP entry.
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+struct Entry {
+    method: u16,
+    crc32: u32,
+    compressed_size: usize,
+    uncompressed_size: usize,
+    local_header_offset: usize,
+}
+
+pub(super) struct ZipArchive<'a> {
+    bytes: &'a [u8],
+    /// Entries by their lowercase name, as the names of the parts of a workbook are case
+    /// insensitive.
+    entries: PlHashMap<String, Entry>,
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> PolarsResult<u16> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| polars_err!(ComputeError: "out-of-spec XLSX file: truncated ZIP archive"))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> PolarsResult<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| polars_err!(ComputeError: "out-of-spec XLSX file: truncated ZIP archive"))
+}
+
+impl<'a> ZipArchive<'a> {
+    pub(super) fn new(bytes: &'a [u8]) -> PolarsResult<Self> {
+        polars_ensure!(
+            bytes.len() >= END_OF_CENTRAL_DIRECTORY_SIZE
+                && u32_at(bytes, 0)? == LOCAL_HEADER_SIGNATURE,
+            ComputeError: "not an XLSX file: the file is not a ZIP archive"
+        );
+
+        // The end of central directory record is followed by a comment of at most 65535 bytes.
+        let last = bytes.len() - END_OF_CENTRAL_DIRECTORY_SIZE;
+        let eocd = (last.saturating_sub(u16::MAX as usize)..=last)
+            .rev()
+            .find(|&offset| u32_at(bytes, offset).unwrap() == END_OF_CENTRAL_DIRECTORY_SIGNATURE)
+            .ok_or_else(|| {
+                polars_err!(ComputeError: "out-of-spec XLSX file: no ZIP end of central directory")
+            })?;
+        let n_entries = u16_at(bytes, eocd + 10)?;
+        let directory_offset = u32_at(bytes, eocd + 16)?;
+        polars_ensure!(
+            n_entries != u16::MAX && directory_offset != u32::MAX,
+            ComputeError: "reading ZIP64 archives is not supported"
+        );
+
+        let mut entries = PlHashMap::with_capacity(n_entries as usize);
+        let mut offset = directory_offset as usize;
+        for _ in 0..n_entries {
+            polars_ensure!(
+                u32_at(bytes, offset)? == CENTRAL_HEADER_SIGNATURE,
+                ComputeError: "out-of-spec XLSX file: invalid ZIP central directory"
+            );
+            let name_length = u16_at(bytes, offset + 28)? as usize;
+            let extra_length = u16_at(bytes, offset + 30)? as usize;
+            let comment_length = u16_at(bytes, offset + 32)? as usize;
+            let name = bytes
+                .get(offset + 46..offset + 46 + name_length)
+                .ok_or_else(
+                    || polars_err!(ComputeError: "out-of-spec XLSX file: truncated ZIP archive"),
+                )?;
+            let entry = Entry {
+                method: u16_at(bytes, offset + 10)?,
+                crc32: u32_at(bytes, offset + 16)?,
+                compressed_size: u32_at(bytes, offset + 20)? as usize,
+                uncompressed_size: u32_at(bytes, offset + 24)? as usize,
+                local_header_offset: u32_at(bytes, offset + 42)? as usize,
+            };
+            entries.insert(String::from_utf8_lossy(name).to_lowercase(), entry);
+            offset += 46 + name_length + extra_length + comment_length;
+        }
+
+        Ok(Self { bytes, entries })
+    }
+
+    /// Read and decompress the entry `name`, or `None` if the archive doesn't contain it.
+    pub(super) fn read(&self, name: &str) -> PolarsResult<Option<Vec<u8>>> {
+        let Some(entry) = self.entries.get(&name.to_lowercase()) else {
+            return Ok(None);
+        };
+
+        let offset = entry.local_header_offset;
+        polars_ensure!(
+            u32_at(self.bytes, offset)? == LOCAL_HEADER_SIGNATURE,
+            ComputeError: "out-of-spec XLSX file: invalid ZIP header of '{}'", name
+        );
+        // The local header repeats the name, but its extra field may differ from the one in the
+        // central directory.
+        let start = offset
+            + 30
+            + u16_at(self.bytes, offset + 26)? as usize
+            + u16_at(self.bytes, offset + 28)? as usize;
+        let data = self
+            .bytes
+            .get(start..start + entry.compressed_size)
+            .ok_or_else(
+                || polars_err!(ComputeError: "out-of-spec XLSX file: truncated ZIP archive"),
+            )?;
+
+        let out = match entry.method {
+            METHOD_STORED => data.to_vec(),
+            METHOD_DEFLATED => {
+                let mut out = Vec::with_capacity(entry.uncompressed_size);
+                DeflateDecoder::new(data).read_to_end(&mut out).map_err(
+                    |e| polars_err!(ComputeError: "could not inflate '{}': {}", name, e),
+                )?;
+                out
+            },
+            method => {
+                polars_bail!(ComputeError: "ZIP compression method {} of '{}' is not supported", method, name)
+            },
+        };
+
+        let mut crc = flate2::Crc::new();
+        crc.update(&out);
+        polars_ensure!(
+            crc.sum() == entry.crc32,
+            ComputeError: "out-of-spec XLSX file: checksum mismatch of '{}'", name
+        );
+        Ok(Some(out))
+    }
+}
+
+/// Writes the entries of a ZIP archive, deflated.
+pub(super) struct ZipWriter<W: Write> {
+    writer: W,
+    offset: usize,
+    directory: Vec<u8>,
+    n_entries: u16,
+}
+
+impl<W: Write> ZipWriter<W> {
+    pub(super) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            offset: 0,
+            directory: vec![],
+            n_entries: 0,
+        }
+    }
+
+    pub(super) fn add(&mut self, name: &str, content: &[u8]) -> PolarsResult<()> {
+        let mut encoder = DeflateEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(content)?;
+        let data = encoder.finish()?;
+        let mut crc = flate2::Crc::new();
+        crc.update(content);
+        polars_ensure!(
+            self.n_entries < u16::MAX
+                && data.len() < u32::MAX as usize
+                && content.len() < u32::MAX as usize
+                && self.offset < u32::MAX as usize,
+            ComputeError: "writing ZIP64 archives is not supported"
+        );
+
+        // The fields that the local header and the central directory header have in common:
+        // the version, flags, method, time, date, checksum, sizes and the length of the name.
+        let mut common = Vec::with_capacity(26);
+        common.extend(VERSION.to_le_bytes());
+        common.extend(0u16.to_le_bytes());
+        common.extend(METHOD_DEFLATED.to_le_bytes());
+        common.extend(0u16.to_le_bytes());
+        common.extend(DOS_DATE.to_le_bytes());
+        common.extend(crc.sum().to_le_bytes());
+        common.extend((data.len() as u32).to_le_bytes());
+        common.extend((content.len() as u32).to_le_bytes());
+        common.extend((name.len() as u16).to_le_bytes());
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend(LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        header.extend(&common);
+        header.extend(0u16.to_le_bytes());
+        header.extend(name.as_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&data)?;
+
+        self.directory
+            .extend(CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        self.directory.extend(VERSION.to_le_bytes());
+        self.directory.extend(&common);
+        // The lengths of the extra field and the comment, the disk and the attributes.
+        self.directory.extend([0; 12]);
+        self.directory.extend((self.offset as u32).to_le_bytes());
+        self.directory.extend(name.as_bytes());
+
+        self.offset += header.len() + data.len();
+        self.n_entries += 1;
+        Ok(())
+    }
+
+    /// Write the central directory, which ends the archive.
+    pub(super) fn finish(mut self) -> PolarsResult<W> {
+        polars_ensure!(
+            self.offset + self.directory.len() < u32::MAX as usize,
+            ComputeError: "writing ZIP64 archives is not supported"
+        );
+        let mut end = Vec::with_capacity(END_OF_CENTRAL_DIRECTORY_SIZE);
+        end.extend(END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        end.extend([0; 4]);
+        end.extend(self.n_entries.to_le_bytes());
+        end.extend(self.n_entries.to_le_bytes());
+        end.extend((self.directory.len() as u32).to_le_bytes());
+        end.extend((self.offset as u32).to_le_bytes());
+        end.extend([0; 2]);
+
+        self.writer.write_all(&self.directory)?;
+        self.writer.write_all(&end)?;
+        Ok(self.writer)
+    }
+}
@@ -0,0 +1,12 @@
+//! # Reading and writing Excel workbooks.
+//!
+//! Workbooks in the XLSX format are ZIP archives of XML documents. The cells of a sheet are read
+//! into a DataFrame, of which the dtypes are inferred from the values and number formats of the
+//! cells. The binary XLSB and legacy XLS formats are not supported.
+mod archive;
+mod read;
+mod write;
+mod xlsx;
+
+pub use read::{read_excel, ExcelReadOptions, ExcelReader, ExcelSheet};
+pub use write::{ExcelWriteOptions, ExcelWriter};
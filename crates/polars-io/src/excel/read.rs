@@ -0,0 +1,537 @@
+use std::path::Path;
+
+use polars_core::prelude::*;
+use polars_utils::format_pl_smallstr;
+
+use super::xlsx::{Cell, Sheet, Workbook, MS_PER_DAY};
+use crate::mmap::MmapBytesReader;
+use crate::prelude::*;
+use crate::utils::get_reader_bytes;
+
+/// The sheet of a workbook to read.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExcelSheet {
+    /// The sheet at this position in the workbook, counting from 0.
+    Index(usize),
+    Name(String),
+}
+
+impl Default for ExcelSheet {
+    fn default() -> Self {
+        Self::Index(0)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExcelReadOptions {
+    pub sheet: ExcelSheet,
+    pub has_header: bool,
+    /// The row with the column names, or the first row of data if there is no header. The rows
+    /// above it are skipped.
+    pub header_row: usize,
+    pub infer_schema_length: Option<usize>,
+    /// Overwrite the inferred dtypes of these columns.
+    pub schema_overwrite: Option<SchemaRef>,
+    pub columns: Option<Arc<[PlSmallStr]>>,
+    pub n_rows: Option<usize>,
+}
+
+impl Default for ExcelReadOptions {
+    fn default() -> Self {
+        Self {
+            sheet: ExcelSheet::default(),
+            has_header: true,
+            header_row: 0,
+            infer_schema_length: Some(100),
+            schema_overwrite: None,
+            columns: None,
+            n_rows: None,
+        }
+    }
+}
+
+impl ExcelReadOptions {
+    /// Read the sheet at this position in the workbook.
+    pub fn with_sheet_index(mut self, index: usize) -> Self {
+        self.sheet = ExcelSheet::Index(index);
+        self
+    }
+
+    /// Read the sheet with this name.
+    pub fn with_sheet_name(mut self, name: impl Into<String>) -> Self {
+        self.sheet = ExcelSheet::Name(name.into());
+        self
+    }
+
+    /// Whether the first row that is read has the column names.
+    pub fn with_has_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// Skip the rows above this row, counting from 0.
+    pub fn with_header_row(mut self, header_row: usize) -> Self {
+        self.header_row = header_row;
+        self
+    }
+
+    /// Number of rows to infer the dtypes from. `None` reads all rows.
+    pub fn with_infer_schema_length(mut self, infer_schema_length: Option<usize>) -> Self {
+        self.infer_schema_length = infer_schema_length;
+        self
+    }
+
+    /// Overwrite the inferred dtypes of the columns in this schema.
+    pub fn with_schema_overwrite(mut self, schema_overwrite: Option<SchemaRef>) -> Self {
+        self.schema_overwrite = schema_overwrite;
+        self
+    }
+
+    /// Columns to select/ project.
+    pub fn with_columns(mut self, columns: Option<Arc<[PlSmallStr]>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Stop reading after `n_rows` rows of data.
+    pub fn with_n_rows(mut self, n_rows: Option<usize>) -> Self {
+        self.n_rows = n_rows;
+        self
+    }
+}
+
+/// Read a sheet of an Excel workbook in the XLSX format into a DataFrame.
+///
+/// The dtypes of the columns are inferred from the values of their cells. Numbers that are
+/// formatted as a date are read as [`DataType::Date`], or as [`DataType::Datetime`] if the
+/// format shows the time. Cells with an error value are read as null.
+///
+/// # Example
+/// ```
+/// use polars_core::prelude::*;
+/// use std::fs::File;
+/// use polars_io::excel::{ExcelReader, ExcelReadOptions};
+/// use polars_io::SerReader;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     let file = File::open("file.xlsx").expect("file not found");
+///
+///     ExcelReader::new(file)
+///         .with_options(ExcelReadOptions::default().with_sheet_name("data"))
+///         .finish()
+/// }
+/// ```
+#[must_use]
+pub struct ExcelReader<R: MmapBytesReader> {
+    reader: R,
+    options: ExcelReadOptions,
+}
+
+impl<R: MmapBytesReader> ExcelReader<R> {
+    pub fn with_options(mut self, options: ExcelReadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The names of the sheets in the workbook, in order.
+    pub fn sheet_names(&mut self) -> PolarsResult<Vec<String>> {
+        let bytes = get_reader_bytes(&mut self.reader)?;
+        let workbook = Workbook::new(&bytes)?;
+        Ok(workbook
+            .sheets
+            .into_iter()
+            .map(|sheet| sheet.name)
+            .collect())
+    }
+}
+
+impl<R: MmapBytesReader> SerReader<R> for ExcelReader<R> {
+    fn new(reader: R) -> Self {
+        ExcelReader {
+            reader,
+            options: ExcelReadOptions::default(),
+        }
+    }
+
+    fn finish(mut self) -> PolarsResult<DataFrame> {
+        let options = self.options;
+        let bytes = get_reader_bytes(&mut self.reader)?;
+        let workbook = Workbook::new(&bytes)?;
+        let sheet = select_sheet(&workbook, &options.sheet)?;
+
+        let mut rows = workbook
+            .read_sheet(sheet)?
+            .into_iter()
+            .skip(options.header_row);
+        let header = if options.has_header {
+            rows.next().unwrap_or_default()
+        } else {
+            vec![]
+        };
+        let mut rows = rows.collect::<Vec<_>>();
+        // Cells below the data that are only formatted leave rows without values.
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        if let Some(n_rows) = options.n_rows {
+            rows.truncate(n_rows);
+        }
+
+        let width = rows
+            .iter()
+            .map(|row| row.len())
+            .fold(header.len(), usize::max);
+        let names = column_names(&header, width);
+        let projection = match &options.columns {
+            Some(columns) => columns
+                .iter()
+                .map(|name| {
+                    names
+                        .iter()
+                        .position(|n| n == name)
+                        .ok_or_else(|| polars_err!(ColumnNotFound: "{}", name))
+                })
+                .collect::<PolarsResult<Vec<_>>>()?,
+            None => (0..width).collect(),
+        };
+
+        // The row in the sheet of the first row of data, for error messages.
+        let first_row = options.header_row + options.has_header as usize;
+        let infer_schema_length = options.infer_schema_length.unwrap_or(usize::MAX);
+        let columns = projection
+            .into_iter()
+            .map(|i| {
+                let name = names[i].clone();
+                let cells = rows
+                    .iter()
+                    .map(|row| row.get(i).unwrap_or(&Cell::Empty))
+                    .collect::<Vec<_>>();
+                let kind = cells
+                    .iter()
+                    .take(infer_schema_length)
+                    .fold(Kind::Null, |kind, cell| kind.merge(Kind::of(cell)));
+                let dtype = options
+                    .schema_overwrite
+                    .as_ref()
+                    .and_then(|schema| schema.get(&name));
+                match dtype {
+                    // Read the text of the cells, rather than casting the inferred dtype.
+                    Some(DataType::String) => read_column(name, Kind::String, &cells, first_row),
+                    Some(dtype) => read_column(name, kind, &cells, first_row)?.strict_cast(dtype),
+                    None => read_column(name, kind, &cells, first_row),
+                }
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        DataFrame::new(columns)
+    }
+}
+
+/// Read a sheet of the Excel workbook at `path`.
+pub fn read_excel<P: AsRef<Path>>(path: P, options: ExcelReadOptions) -> PolarsResult<DataFrame> {
+    let file = polars_utils::open_file(path.as_ref())?;
+    ExcelReader::new(file).with_options(options).finish()
+}
+
+fn select_sheet<'a>(workbook: &'a Workbook, sheet: &ExcelSheet) -> PolarsResult<&'a Sheet> {
+    match sheet {
+        ExcelSheet::Index(index) => workbook.sheets.get(*index).ok_or_else(|| {
+            polars_err!(
+                OutOfBounds: "sheet index {} is out of bounds for a workbook with {} sheets",
+                index, workbook.sheets.len()
+            )
+        }),
+        ExcelSheet::Name(name) => workbook
+            .sheets
+            .iter()
+            .find(|sheet| &sheet.name == name)
+            .ok_or_else(|| {
+                let names = workbook.sheets.iter().map(|s| &s.name).collect::<Vec<_>>();
+                polars_err!(ComputeError: "workbook has no sheet '{}', its sheets are {:?}", name, names)
+            }),
+    }
+}
+
+/// The names of the columns, from the header or `column_{i}` if the header cell is empty.
+fn column_names(header: &[Cell], width: usize) -> Vec<PlSmallStr> {
+    let mut counts = PlHashMap::with_capacity(width);
+    (0..width)
+        .map(|i| {
+            let name = header
+                .get(i)
+                .and_then(Cell::to_text)
+                .unwrap_or_else(|| format!("column_{}", i + 1));
+            let count = counts.entry(name.clone()).or_insert(0usize);
+            let name = if *count != 0 {
+                format_pl_smallstr!("{}_duplicated_{}", name, *count - 1)
+            } else {
+                PlSmallStr::from_string(name)
+            };
+            *count += 1;
+            name
+        })
+        .collect()
+}
+
+/// The kinds of values that determine the dtype of a column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Null,
+    Boolean,
+    Int,
+    Float,
+    Date,
+    Datetime,
+    String,
+}
+
+impl Kind {
+    fn of(cell: &Cell) -> Self {
+        match cell {
+            Cell::Empty => Kind::Null,
+            Cell::Boolean(_) => Kind::Boolean,
+            Cell::Number(v) if is_integer(*v) => Kind::Int,
+            Cell::Number(_) => Kind::Float,
+            Cell::String(_) => Kind::String,
+            Cell::Date(_) => Kind::Date,
+            Cell::Datetime(_) => Kind::Datetime,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Kind::Null, kind) | (kind, Kind::Null) => kind,
+            (a, b) if a == b => a,
+            (Kind::Int, Kind::Float) | (Kind::Float, Kind::Int) => Kind::Float,
+            (Kind::Date, Kind::Datetime) | (Kind::Datetime, Kind::Date) => Kind::Datetime,
+            _ => Kind::String,
+        }
+    }
+
+    fn dtype(self) -> DataType {
+        match self {
+            Kind::Boolean => DataType::Boolean,
+            Kind::Int => DataType::Int64,
+            Kind::Float => DataType::Float64,
+            Kind::Date => DataType::Date,
+            Kind::Datetime => DataType::Datetime(TimeUnit::Milliseconds, None),
+            Kind::Null | Kind::String => DataType::String,
+        }
+    }
+}
+
+/// Whether a number is an integer that a float represents exactly.
+fn is_integer(v: f64) -> bool {
+    v.fract() == 0.0 && v.abs() <= (1u64 << f64::MANTISSA_DIGITS) as f64
+}
+
+fn read_column(
+    name: PlSmallStr,
+    kind: Kind,
+    cells: &[&Cell],
+    first_row: usize,
+) -> PolarsResult<Series> {
+    let mismatch = |i: usize, cell: &Cell| {
+        polars_err!(
+            ComputeError: "could not read '{}' in row {} of column '{}' as {}\n\n\
+            You might want to increase `infer_schema_length` or set the dtype of the column in \
+            `schema_overwrite`.",
+            cell.to_text().unwrap_or_default(), first_row + i + 1, name, kind.dtype()
+        )
+    };
+    macro_rules! read {
+        ($($pattern:pat $(if $guard:expr)? => $value:expr),+) => {
+            cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| match cell {
+                    Cell::Empty => Ok(None),
+                    $($pattern $(if $guard)? => Ok(Some($value)),)+
+                    cell => Err(mismatch(i, cell)),
+                })
+                .collect::<PolarsResult<Vec<_>>>()?
+        };
+    }
+
+    let series = match kind {
+        Kind::Boolean => Series::new(name.clone(), read!(Cell::Boolean(v) => *v)),
+        Kind::Int => Series::new(
+            name.clone(),
+            read!(Cell::Number(v) if is_integer(*v) => *v as i64),
+        ),
+        Kind::Float => Series::new(name.clone(), read!(Cell::Number(v) => *v)),
+        Kind::Date => {
+            Series::new(name.clone(), read!(Cell::Date(v) => *v)).cast(&DataType::Date)?
+        },
+        Kind::Datetime => Series::new(
+            name.clone(),
+            read!(Cell::Date(v) => *v as i64 * MS_PER_DAY, Cell::Datetime(v) => *v),
+        )
+        .cast(&kind.dtype())?,
+        Kind::Null | Kind::String => Series::new(
+            name.clone(),
+            cells.iter().map(|cell| cell.to_text()).collect::<Vec<_>>(),
+        ),
+    };
+    Ok(series)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use polars_core::df;
+
+    use super::*;
+    use crate::excel::archive::ZipWriter;
+
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(vec![]);
+        for (name, content) in files {
+            writer.add(name, content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    fn xlsx_file() -> Vec<u8> {
+        let root_rels = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>
+</Relationships>"#;
+        let workbook = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<workbookPr/>
+<sheets><sheet name="notes" sheetId="1" r:id="rId1"/><sheet name="data &amp; more" sheetId="2" r:id="rId2"/></sheets>
+</workbook>"#;
+        let workbook_rels = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet2.xml"/>
+<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings" Target="sharedStrings.xml"/>
+<Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="/xl/styles.xml"/>
+</Relationships>"#;
+        let shared_strings = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+<si><t>id</t></si><si><t>name</t></si>
+<si><r><t>pol</t></r><r><rPr><b/></rPr><t>ars</t></r></si>
+<si><t>x</t><rPh sb="0" eb="1"><t>ekusu</t></rPh></si>
+</sst>"#;
+        let styles = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+<numFmts count="1"><numFmt numFmtId="164" formatCode="yyyy\-mm\-dd\ hh:mm"/></numFmts>
+<cellStyleXfs count="1"><xf numFmtId="14"/></cellStyleXfs>
+<cellXfs count="3"><xf numFmtId="0"/><xf numFmtId="14" applyNumberFormat="1"/><xf numFmtId="164" applyNumberFormat="1"/></cellXfs>
+</styleSheet>"#;
+        let notes = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+<sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>a</t></is></c></row><row r="2"><c r="A2"><v>1</v></c></row></sheetData>
+</worksheet>"#;
+        // A title above the header, and formatted cells below the data.
+        let data = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+<sheetData>
+<row r="1"><c r="A1" t="inlineStr"><is><t>Report</t></is></c></row>
+<row r="2">
+<c r="A2" t="s"><v>0</v></c><c r="B2" t="s"><v>1</v></c><c r="C2" t="inlineStr"><is><t>score</t></is></c>
+<c r="D2" t="inlineStr"><is><t>ok</t></is></c><c r="E2" t="inlineStr"><is><t>day</t></is></c>
+<c r="F2" t="inlineStr"><is><t>at</t></is></c><c r="G2" t="inlineStr"><is><t>mixed</t></is></c>
+</row>
+<row r="3">
+<c r="A3"><v>1</v></c><c r="B3" t="s"><v>2</v></c><c r="C3"><v>0.5</v></c><c r="D3" t="b"><v>1</v></c>
+<c r="E3" s="1"><v>45292</v></c><c r="F3" s="2"><v>45292.5</v></c><c r="G3"><v>1</v></c>
+</row>
+<row r="4">
+<c r="A4"><v>2</v></c><c r="B4" t="s"><v>3</v></c><c r="C4"><v>2</v></c><c r="D4" t="b"><v>0</v></c>
+<c r="E4" s="1"/><c r="F4" t="d"><v>2024-01-02T06:00:00</v></c>
+<c r="G4" t="str"><f>CONCAT("t", "wo")</f><v>two</v></c>
+</row>
+<row r="5">
+<c r="A5"><v>3</v></c><c r="C5" t="e"><v>#DIV/0!</v></c>
+<c r="E5" s="1"><v>45294</v></c><c r="F5" s="2"><v>45294.25</v></c><c r="G5" t="inlineStr"><is><t>3</t></is></c>
+</row>
+<row r="8" spans="1:7"/>
+<row r="9"><c r="B9" s="1"/></row>
+</sheetData>
+</worksheet>"#;
+
+        zip(&[
+            ("_rels/.rels", root_rels),
+            ("xl/workbook.xml", workbook),
+            ("xl/_rels/workbook.xml.rels", workbook_rels),
+            ("xl/sharedStrings.xml", shared_strings),
+            ("xl/styles.xml", styles),
+            ("xl/worksheets/sheet1.xml", notes),
+            ("xl/worksheets/sheet2.xml", data),
+        ])
+    }
+
+    #[test]
+    fn test_read_excel() -> PolarsResult<()> {
+        let mut reader = ExcelReader::new(Cursor::new(xlsx_file()));
+        assert_eq!(reader.sheet_names()?, ["notes", "data & more"]);
+
+        let options = ExcelReadOptions::default()
+            .with_sheet_name("data & more")
+            .with_header_row(1);
+        let df = reader.with_options(options.clone()).finish()?;
+
+        let day = 19723;
+        let ms = |days: i64, hours: i64| (day + days) * MS_PER_DAY + hours * 3_600_000;
+        let expected = DataFrame::new(vec![
+            Series::new("id".into(), [1i64, 2, 3]),
+            Series::new("name".into(), [Some("polars"), Some("x"), None]),
+            Series::new("score".into(), [Some(0.5f64), Some(2.0), None]),
+            Series::new("ok".into(), [Some(true), Some(false), None]),
+            Series::new("day".into(), [Some(day as i32), None, Some(day as i32 + 2)])
+                .cast(&DataType::Date)?,
+            Series::new("at".into(), [ms(0, 12), ms(1, 6), ms(2, 6)])
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?,
+            Series::new("mixed".into(), ["1", "two", "3"]),
+        ])?;
+        assert!(df.equals_missing(&expected));
+
+        // Projection, limits and overwritten dtypes.
+        let df = ExcelReader::new(Cursor::new(xlsx_file()))
+            .with_options(
+                options
+                    .with_columns(Some(["mixed".into(), "id".into()].into()))
+                    .with_n_rows(Some(2))
+                    .with_schema_overwrite(Some(Arc::new(Schema::from_iter([
+                        Field::new("id".into(), DataType::String),
+                        Field::new("mixed".into(), DataType::String),
+                    ])))),
+            )
+            .finish()?;
+        let expected = df![
+            "mixed" => ["1", "two"],
+            "id" => ["1", "2"],
+        ]?;
+        assert!(df.equals(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_excel_sheets() -> PolarsResult<()> {
+        let read = |options: ExcelReadOptions| {
+            ExcelReader::new(Cursor::new(xlsx_file()))
+                .with_options(options)
+                .finish()
+        };
+
+        let df = read(ExcelReadOptions::default())?;
+        assert!(df.equals(&df!["a" => [1i64]]?));
+
+        let df = read(ExcelReadOptions::default().with_has_header(false))?;
+        assert!(df.equals(&df!["column_1" => ["a", "1"]]?));
+
+        // The types of the first rows don't fit the last one.
+        let options = ExcelReadOptions::default()
+            .with_sheet_index(1)
+            .with_header_row(1)
+            .with_infer_schema_length(Some(1));
+        assert!(read(options).is_err());
+
+        assert!(read(ExcelReadOptions::default().with_sheet_index(2)).is_err());
+        assert!(read(ExcelReadOptions::default().with_sheet_name("missing")).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,713 @@
+use std::io::Write;
+
+use polars_core::prelude::*;
+
+use super::archive::ZipWriter;
+use crate::prelude::*;
+
+/// The largest number of rows and columns of a sheet.
+const MAX_ROWS: usize = 1_048_576;
+const MAX_COLUMNS: usize = 16_384;
+/// The serial number of 1970-01-01 in the 1900 date system of Excel.
+const UNIX_EPOCH_SERIAL: f64 = 25569.0;
+/// The ids of custom number formats start after those of the built-in formats.
+const FIRST_CUSTOM_FORMAT_ID: usize = 164;
+
+const SPREADSHEETML: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const PACKAGE_RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+
+/// How a DataFrame is written to a sheet.
+#[derive(Clone, Debug)]
+pub struct ExcelWriteOptions {
+    pub include_header: bool,
+    /// Keep the header in view when scrolling down.
+    pub freeze_header: bool,
+    /// The width of columns, in characters.
+    pub column_widths: PlHashMap<PlSmallStr, f64>,
+    /// The number formats of columns, like `0.00%` or `dd/mm/yyyy`.
+    pub column_formats: PlHashMap<PlSmallStr, String>,
+    /// The number format of [`DataType::Date`] columns without a column format.
+    pub date_format: String,
+    /// The number format of [`DataType::Datetime`] columns without a column format.
+    pub datetime_format: String,
+    /// The number format of [`DataType::Time`] columns without a column format.
+    pub time_format: String,
+}
+
+impl Default for ExcelWriteOptions {
+    fn default() -> Self {
+        Self {
+            include_header: true,
+            freeze_header: false,
+            column_widths: PlHashMap::new(),
+            column_formats: PlHashMap::new(),
+            date_format: "yyyy-mm-dd".to_string(),
+            datetime_format: "yyyy-mm-dd hh:mm:ss".to_string(),
+            time_format: "hh:mm:ss".to_string(),
+        }
+    }
+}
+
+impl ExcelWriteOptions {
+    /// Write the column names in the first row.
+    pub fn with_include_header(mut self, include_header: bool) -> Self {
+        self.include_header = include_header;
+        self
+    }
+
+    /// Freeze the header, so that it stays in view when scrolling down.
+    pub fn with_freeze_header(mut self, freeze_header: bool) -> Self {
+        self.freeze_header = freeze_header;
+        self
+    }
+
+    /// Set the width of a column, in characters.
+    pub fn with_column_width(mut self, column: impl Into<PlSmallStr>, width: f64) -> Self {
+        self.column_widths.insert(column.into(), width);
+        self
+    }
+
+    /// Set the number format of a column, like `0.00%` or `dd/mm/yyyy`.
+    pub fn with_column_format(
+        mut self,
+        column: impl Into<PlSmallStr>,
+        format: impl Into<String>,
+    ) -> Self {
+        self.column_formats.insert(column.into(), format.into());
+        self
+    }
+
+    /// Set the number format of date columns.
+    pub fn with_date_format(mut self, format: impl Into<String>) -> Self {
+        self.date_format = format.into();
+        self
+    }
+
+    /// Set the number format of datetime columns.
+    pub fn with_datetime_format(mut self, format: impl Into<String>) -> Self {
+        self.datetime_format = format.into();
+        self
+    }
+
+    /// Set the number format of time columns.
+    pub fn with_time_format(mut self, format: impl Into<String>) -> Self {
+        self.time_format = format.into();
+        self
+    }
+}
+
+/// Write DataFrames to the sheets of an Excel workbook in the XLSX format.
+///
+/// Dates, datetimes, times and durations are written as numbers with a date or time format, as
+/// Excel stores them. Datetimes with a time zone are written in UTC.
+///
+/// # Example
+///
+/// ```
+/// use polars_core::prelude::*;
+/// use polars_io::excel::{ExcelWriteOptions, ExcelWriter};
+/// use polars_io::SerWriter;
+/// use std::fs::File;
+///
+/// fn example(sales: &DataFrame, customers: &DataFrame) -> PolarsResult<()> {
+///     let file = File::create("report.xlsx").expect("could not create file");
+///     let options = ExcelWriteOptions::default()
+///         .with_freeze_header(true)
+///         .with_column_format("revenue", "#,##0.00");
+///
+///     ExcelWriter::new(file).finish_sheets(&[
+///         ("sales", sales, &options),
+///         ("customers", customers, &ExcelWriteOptions::default()),
+///     ])
+/// }
+/// ```
+#[must_use]
+pub struct ExcelWriter<W: Write> {
+    writer: W,
+    sheet_name: String,
+    options: ExcelWriteOptions,
+}
+
+impl<W: Write> ExcelWriter<W> {
+    /// Set the name of the sheet that [`SerWriter::finish`] writes to.
+    pub fn with_sheet_name(mut self, sheet_name: impl Into<String>) -> Self {
+        self.sheet_name = sheet_name.into();
+        self
+    }
+
+    /// Set how [`SerWriter::finish`] writes the DataFrame.
+    pub fn with_options(mut self, options: ExcelWriteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Write a workbook with a sheet for every DataFrame, in order.
+    pub fn finish_sheets(
+        &mut self,
+        sheets: &[(&str, &DataFrame, &ExcelWriteOptions)],
+    ) -> PolarsResult<()> {
+        polars_ensure!(
+            !sheets.is_empty(),
+            InvalidOperation: "a workbook must have at least one sheet"
+        );
+        let mut names = PlHashSet::with_capacity(sheets.len());
+        for (name, _, _) in sheets {
+            check_sheet_name(name)?;
+            polars_ensure!(
+                names.insert(name.to_lowercase()),
+                Duplicate: "sheet names must be unique, got '{}' twice", name
+            );
+        }
+
+        let mut strings = SharedStrings::default();
+        let mut styles = Styles::default();
+        let worksheets = sheets
+            .iter()
+            .enumerate()
+            .map(|(i, (_, df, options))| {
+                write_worksheet(df, options, i == 0, &mut strings, &mut styles)
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let mut zip = ZipWriter::new(&mut self.writer);
+        zip.add(
+            "[Content_Types].xml",
+            content_types(sheets.len()).as_bytes(),
+        )?;
+        zip.add("_rels/.rels", root_relationships().as_bytes())?;
+        zip.add("xl/workbook.xml", workbook(sheets).as_bytes())?;
+        zip.add(
+            "xl/_rels/workbook.xml.rels",
+            workbook_relationships(sheets.len()).as_bytes(),
+        )?;
+        zip.add("xl/styles.xml", styles.to_xml().as_bytes())?;
+        zip.add("xl/sharedStrings.xml", strings.to_xml().as_bytes())?;
+        for (i, worksheet) in worksheets.iter().enumerate() {
+            zip.add(&format!("xl/worksheets/sheet{}.xml", i + 1), worksheet)?;
+        }
+        zip.finish()?.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: Write> SerWriter<W> for ExcelWriter<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            sheet_name: "Sheet1".to_string(),
+            options: ExcelWriteOptions::default(),
+        }
+    }
+
+    fn finish(&mut self, df: &mut DataFrame) -> PolarsResult<()> {
+        let sheet_name = self.sheet_name.clone();
+        let options = self.options.clone();
+        self.finish_sheets(&[(sheet_name.as_str(), &*df, &options)])
+    }
+}
+
+fn check_sheet_name(name: &str) -> PolarsResult<()> {
+    polars_ensure!(
+        !name.is_empty() && name.chars().count() <= 31,
+        InvalidOperation: "sheet names must have 1 to 31 characters, got '{}'", name
+    );
+    polars_ensure!(
+        !name.contains(['[', ']', ':', '*', '?', '/', '\\']) && !name.starts_with('\'')
+            && !name.ends_with('\''),
+        InvalidOperation: "sheet name '{}' contains characters that Excel doesn't allow", name
+    );
+    Ok(())
+}
+
+/// The strings of all sheets, which cells refer to by their index.
+#[derive(Default)]
+struct SharedStrings {
+    strings: PlIndexSet<String>,
+    n_references: usize,
+}
+
+impl SharedStrings {
+    fn index_of(&mut self, s: &str) -> usize {
+        self.n_references += 1;
+        match self.strings.get_index_of(s) {
+            Some(index) => index,
+            None => self.strings.insert_full(s.to_string()).0,
+        }
+    }
+
+    fn to_xml(&self) -> String {
+        let mut xml = format!(
+            r#"{XML_DECLARATION}<sst xmlns="{SPREADSHEETML}" count="{}" uniqueCount="{}">"#,
+            self.n_references,
+            self.strings.len()
+        );
+        for s in &self.strings {
+            // Leading and trailing whitespace is dropped without `xml:space`.
+            if s.starts_with(char::is_whitespace) || s.ends_with(char::is_whitespace) {
+                xml.push_str(r#"<si><t xml:space="preserve">"#);
+            } else {
+                xml.push_str("<si><t>");
+            }
+            xml_escape(&mut xml, s);
+            xml.push_str("</t></si>");
+        }
+        xml.push_str("</sst>");
+        xml
+    }
+}
+
+/// The cell formats of all sheets. The first format is the default, the second one is that of
+/// the header.
+struct Styles {
+    number_formats: PlIndexSet<String>,
+    /// The number format and whether the font is bold.
+    cell_formats: PlIndexSet<(usize, bool)>,
+}
+
+impl Default for Styles {
+    fn default() -> Self {
+        Self {
+            number_formats: PlIndexSet::default(),
+            cell_formats: PlIndexSet::from_iter([(0, false), (0, true)]),
+        }
+    }
+}
+
+const HEADER_STYLE: usize = 1;
+
+impl Styles {
+    /// The index of the cell format with this number format.
+    fn index_of(&mut self, number_format: &str) -> usize {
+        let id = match self.number_formats.get_index_of(number_format) {
+            Some(index) => index,
+            None => self.number_formats.insert_full(number_format.to_string()).0,
+        } + FIRST_CUSTOM_FORMAT_ID;
+        self.cell_formats.insert_full((id, false)).0
+    }
+
+    fn to_xml(&self) -> String {
+        let mut xml = format!(r#"{XML_DECLARATION}<styleSheet xmlns="{SPREADSHEETML}">"#);
+        if !self.number_formats.is_empty() {
+            xml.push_str(&format!(
+                r#"<numFmts count="{}">"#,
+                self.number_formats.len()
+            ));
+            for (i, code) in self.number_formats.iter().enumerate() {
+                xml.push_str(&format!(
+                    r#"<numFmt numFmtId="{}" formatCode=""#,
+                    FIRST_CUSTOM_FORMAT_ID + i
+                ));
+                xml_escape(&mut xml, code);
+                xml.push_str(r#""/>"#);
+            }
+            xml.push_str("</numFmts>");
+        }
+        xml.push_str(concat!(
+            r#"<fonts count="2">"#,
+            r#"<font><sz val="11"/><name val="Calibri"/><family val="2"/></font>"#,
+            r#"<font><b/><sz val="11"/><name val="Calibri"/><family val="2"/></font>"#,
+            r#"</fonts>"#,
+            r#"<fills count="2">"#,
+            r#"<fill><patternFill patternType="none"/></fill>"#,
+            r#"<fill><patternFill patternType="gray125"/></fill>"#,
+            r#"</fills>"#,
+            r#"<borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders>"#,
+            r#"<cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs>"#,
+        ));
+        xml.push_str(&format!(r#"<cellXfs count="{}">"#, self.cell_formats.len()));
+        for (number_format, bold) in &self.cell_formats {
+            xml.push_str(&format!(
+                r#"<xf numFmtId="{}" fontId="{}" fillId="0" borderId="0" xfId="0"{}{}/>"#,
+                number_format,
+                *bold as u8,
+                if *number_format != 0 {
+                    r#" applyNumberFormat="1""#
+                } else {
+                    ""
+                },
+                if *bold { r#" applyFont="1""# } else { "" },
+            ));
+        }
+        xml.push_str("</cellXfs>");
+        xml.push_str(r#"<cellStyles count="1"><cellStyle name="Normal" xfId="0" builtinId="0"/></cellStyles>"#);
+        xml.push_str("</styleSheet>");
+        xml
+    }
+}
+
+/// The values of a column as they are written to cells.
+enum Values<'a> {
+    Boolean(&'a BooleanChunked),
+    Integer(&'a Int64Chunked),
+    /// Floats, and the serial numbers of temporal values.
+    Float(&'a Float64Chunked),
+    String(&'a StringChunked),
+}
+
+/// Convert a column to the dtype that its cells are written from.
+fn to_cell_dtype(s: &Series) -> PolarsResult<Series> {
+    // The number of units of the physical representation in a day.
+    let units_per_day = |tu: &TimeUnit| match tu {
+        TimeUnit::Nanoseconds => 86_400_000_000_000.0,
+        TimeUnit::Microseconds => 86_400_000_000.0,
+        TimeUnit::Milliseconds => 86_400_000.0,
+    };
+    let serial = |per_day: f64, offset: f64| -> PolarsResult<Series> {
+        let physical = s.to_physical_repr().cast(&DataType::Float64)?;
+        Ok(physical
+            .f64()?
+            .apply_values(|v| v / per_day + offset)
+            .into_series())
+    };
+
+    match s.dtype() {
+        DataType::Boolean | DataType::String => Ok(s.clone()),
+        dt if dt.is_integer() => s.cast(&DataType::Int64),
+        dt if dt.is_float() || dt.is_decimal() => s.cast(&DataType::Float64),
+        dt if dt.is_categorical() || dt.is_enum() => s.cast(&DataType::String),
+        DataType::Date => serial(1.0, UNIX_EPOCH_SERIAL),
+        DataType::Datetime(tu, _) => serial(units_per_day(tu), UNIX_EPOCH_SERIAL),
+        DataType::Duration(tu) => serial(units_per_day(tu), 0.0),
+        DataType::Time => serial(units_per_day(&TimeUnit::Nanoseconds), 0.0),
+        DataType::Null => s.cast(&DataType::String),
+        dt => polars_bail!(
+            InvalidOperation: "cannot write column '{}' of dtype {} to Excel", s.name(), dt
+        ),
+    }
+}
+
+/// Serialize a DataFrame to the XML of a worksheet.
+fn write_worksheet(
+    df: &DataFrame,
+    options: &ExcelWriteOptions,
+    selected: bool,
+    strings: &mut SharedStrings,
+    styles: &mut Styles,
+) -> PolarsResult<Vec<u8>> {
+    for name in options
+        .column_widths
+        .keys()
+        .chain(options.column_formats.keys())
+    {
+        df.try_get_column_index(name)?;
+    }
+    let n_header_rows = options.include_header as usize;
+    polars_ensure!(
+        df.height() + n_header_rows <= MAX_ROWS && df.width() <= MAX_COLUMNS,
+        InvalidOperation: "a sheet has at most {} rows and {} columns, got a DataFrame of shape {:?}",
+        MAX_ROWS, MAX_COLUMNS, df.shape()
+    );
+
+    let columns = df
+        .get_columns()
+        .iter()
+        .map(|s| Ok(to_cell_dtype(s)?.rechunk()))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let values = columns
+        .iter()
+        .map(|s| {
+            Ok(match s.dtype() {
+                DataType::Boolean => Values::Boolean(s.bool()?),
+                DataType::Int64 => Values::Integer(s.i64()?),
+                DataType::Float64 => Values::Float(s.f64()?),
+                _ => Values::String(s.str()?),
+            })
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let cell_styles = df
+        .get_columns()
+        .iter()
+        .map(|s| {
+            let format = match (options.column_formats.get(s.name()), s.dtype()) {
+                (Some(format), _) => format.as_str(),
+                (None, DataType::Date) => options.date_format.as_str(),
+                (None, DataType::Datetime(_, _)) => options.datetime_format.as_str(),
+                (None, DataType::Time) => options.time_format.as_str(),
+                // Durations may be longer than a day.
+                (None, DataType::Duration(_)) => "[h]:mm:ss",
+                (None, _) => return 0,
+            };
+            styles.index_of(format)
+        })
+        .collect::<Vec<_>>();
+    let column_names = (0..df.width()).map(column_name).collect::<Vec<_>>();
+
+    let mut xml = Vec::with_capacity(df.height() * df.width() * 16);
+    write!(
+        xml,
+        r#"{XML_DECLARATION}<worksheet xmlns="{SPREADSHEETML}" xmlns:r="{RELATIONSHIPS}">"#
+    )?;
+    let n_rows = df.height() + n_header_rows;
+    if n_rows > 0 && df.width() > 0 {
+        write!(
+            xml,
+            r#"<dimension ref="A1:{}{}"/>"#,
+            column_names[df.width() - 1],
+            n_rows
+        )?;
+    } else {
+        write!(xml, r#"<dimension ref="A1"/>"#)?;
+    }
+
+    write!(
+        xml,
+        r#"<sheetViews><sheetView workbookViewId="0"{}>"#,
+        if selected { r#" tabSelected="1""# } else { "" }
+    )?;
+    if options.freeze_header && options.include_header {
+        write!(
+            xml,
+            r#"<pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/><selection pane="bottomLeft"/>"#
+        )?;
+    }
+    write!(
+        xml,
+        r#"</sheetView></sheetViews><sheetFormatPr defaultRowHeight="15"/>"#
+    )?;
+
+    // The widths must be ordered by column.
+    let mut widths = options
+        .column_widths
+        .iter()
+        .map(|(name, width)| (df.try_get_column_index(name).unwrap(), *width))
+        .collect::<Vec<_>>();
+    widths.sort_unstable_by_key(|(i, _)| *i);
+    if !widths.is_empty() {
+        write!(xml, "<cols>")?;
+        for (i, width) in widths {
+            polars_ensure!(
+                (0.0..=255.0).contains(&width),
+                InvalidOperation: "the width of a column must be between 0 and 255, got {}", width
+            );
+            write!(
+                xml,
+                r#"<col min="{0}" max="{0}" width="{1}" customWidth="1"/>"#,
+                i + 1,
+                width
+            )?;
+        }
+        write!(xml, "</cols>")?;
+    }
+
+    write!(xml, "<sheetData>")?;
+    if options.include_header {
+        write!(xml, r#"<row r="1">"#)?;
+        for (s, column) in df.get_columns().iter().zip(&column_names) {
+            write!(
+                xml,
+                r#"<c r="{}1" s="{}" t="s"><v>{}</v></c>"#,
+                column,
+                HEADER_STYLE,
+                strings.index_of(s.name())
+            )?;
+        }
+        write!(xml, "</row>")?;
+    }
+    for i in 0..df.height() {
+        let row = i + n_header_rows + 1;
+        write!(xml, r#"<row r="{}">"#, row)?;
+        for ((values, column), style) in values.iter().zip(&column_names).zip(&cell_styles) {
+            // Nulls, NaNs and infinities are written as empty cells.
+            let (cell_type, value) = match values {
+                Values::Boolean(ca) => match ca.get(i) {
+                    Some(v) => (r#" t="b""#, (v as u8).to_string()),
+                    None => continue,
+                },
+                Values::Integer(ca) => match ca.get(i) {
+                    Some(v) => ("", v.to_string()),
+                    None => continue,
+                },
+                Values::Float(ca) => match ca.get(i) {
+                    Some(v) if v.is_finite() => ("", v.to_string()),
+                    _ => continue,
+                },
+                Values::String(ca) => match ca.get(i) {
+                    Some(v) => (r#" t="s""#, strings.index_of(v).to_string()),
+                    None => continue,
+                },
+            };
+            write!(xml, r#"<c r="{}{}""#, column, row)?;
+            if *style != 0 {
+                write!(xml, r#" s="{}""#, style)?;
+            }
+            write!(xml, r#"{}><v>{}</v></c>"#, cell_type, value)?;
+        }
+        write!(xml, "</row>")?;
+    }
+    write!(xml, "</sheetData></worksheet>")?;
+    Ok(xml)
+}
+
+/// The letters of the column at index `i`, like `A` or `AB`.
+fn column_name(i: usize) -> String {
+    let mut letters = vec![];
+    let mut n = i + 1;
+    while n > 0 {
+        letters.push(b'A' + ((n - 1) % 26) as u8);
+        n = (n - 1) / 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).unwrap()
+}
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#;
+
+/// Escape text for an XML element or attribute. Control characters aren't allowed in XML and are
+/// left out.
+fn xml_escape(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() && (c as u32) < 0x20 => {},
+            c => out.push(c),
+        }
+    }
+}
+
+fn content_types(n_sheets: usize) -> String {
+    let mut xml = format!(
+        concat!(
+            r#"{}<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+            r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+            r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+            r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+            r#"<Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/>"#,
+            r#"<Override PartName="/xl/sharedStrings.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sharedStrings+xml"/>"#,
+        ),
+        XML_DECLARATION
+    );
+    for i in 1..=n_sheets {
+        xml.push_str(&format!(
+            r#"<Override PartName="/xl/worksheets/sheet{i}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#
+        ));
+    }
+    xml.push_str("</Types>");
+    xml
+}
+
+fn root_relationships() -> String {
+    format!(
+        r#"{XML_DECLARATION}<Relationships xmlns="{PACKAGE_RELATIONSHIPS}"><Relationship Id="rId1" Type="{RELATIONSHIPS}/officeDocument" Target="xl/workbook.xml"/></Relationships>"#
+    )
+}
+
+fn workbook(sheets: &[(&str, &DataFrame, &ExcelWriteOptions)]) -> String {
+    let mut xml = format!(
+        r#"{XML_DECLARATION}<workbook xmlns="{SPREADSHEETML}" xmlns:r="{RELATIONSHIPS}"><bookViews><workbookView/></bookViews><sheets>"#
+    );
+    for (i, (name, _, _)) in sheets.iter().enumerate() {
+        xml.push_str(r#"<sheet name=""#);
+        xml_escape(&mut xml, name);
+        xml.push_str(&format!(r#"" sheetId="{0}" r:id="rId{0}"/>"#, i + 1));
+    }
+    xml.push_str("</sheets></workbook>");
+    xml
+}
+
+/// The relationships of the workbook: its sheets, followed by the styles and shared strings.
+fn workbook_relationships(n_sheets: usize) -> String {
+    let mut xml = format!(r#"{XML_DECLARATION}<Relationships xmlns="{PACKAGE_RELATIONSHIPS}">"#);
+    for i in 1..=n_sheets {
+        xml.push_str(&format!(
+            r#"<Relationship Id="rId{i}" Type="{RELATIONSHIPS}/worksheet" Target="worksheets/sheet{i}.xml"/>"#
+        ));
+    }
+    xml.push_str(&format!(
+        r#"<Relationship Id="rId{}" Type="{RELATIONSHIPS}/styles" Target="styles.xml"/>"#,
+        n_sheets + 1
+    ));
+    xml.push_str(&format!(
+        r#"<Relationship Id="rId{}" Type="{RELATIONSHIPS}/sharedStrings" Target="sharedStrings.xml"/>"#,
+        n_sheets + 2
+    ));
+    xml.push_str("</Relationships>");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use polars_core::df;
+
+    use super::*;
+    use crate::excel::archive::ZipArchive;
+    use crate::excel::{ExcelReadOptions, ExcelReader};
+
+    #[test]
+    fn test_column_name() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn test_write_excel() -> PolarsResult<()> {
+        let day = 19723;
+        let ms = 86_400_000i64;
+        let df = DataFrame::new(vec![
+            Series::new("id".into(), [1i32, 2, 3]),
+            Series::new("name".into(), [Some(" a & <b> "), None, Some("a & <b>")]),
+            Series::new("score".into(), [Some(0.25f64), Some(f64::NAN), None]),
+            Series::new("ok".into(), [Some(true), None, Some(false)]),
+            Series::new("day".into(), [day, day + 1, day + 2]).cast(&DataType::Date)?,
+            Series::new("at".into(), [day as i64 * ms, day as i64 * ms + 1, 0])
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?,
+        ])?;
+        let other = df!["x" => ["only"]]?;
+
+        let options = ExcelWriteOptions::default()
+            .with_freeze_header(true)
+            .with_column_width("name", 20.0)
+            .with_column_format("score", "0.00%");
+        let mut buf = vec![];
+        ExcelWriter::new(&mut buf).finish_sheets(&[
+            ("data", &df, &options),
+            ("other", &other, &ExcelWriteOptions::default()),
+        ])?;
+
+        let mut reader = ExcelReader::new(Cursor::new(buf.clone()));
+        assert_eq!(reader.sheet_names()?, ["data", "other"]);
+        let read = reader.finish()?;
+        let mut expected = df.clone();
+        expected.with_column(df.column("id")?.cast(&DataType::Int64)?)?;
+        expected.with_column(Series::new("score".into(), [Some(0.25f64), None, None]))?;
+        assert!(read.equals_missing(&expected));
+
+        let read = ExcelReader::new(Cursor::new(buf.clone()))
+            .with_options(ExcelReadOptions::default().with_sheet_name("other"))
+            .finish()?;
+        assert!(read.equals(&other));
+
+        let archive = ZipArchive::new(&buf)?;
+        let sheet = String::from_utf8(archive.read("xl/worksheets/sheet1.xml")?.unwrap()).unwrap();
+        assert!(sheet.contains(r#"state="frozen""#));
+        assert!(sheet.contains(r#"<col min="2" max="2" width="20" customWidth="1"/>"#));
+        let styles = String::from_utf8(archive.read("xl/styles.xml")?.unwrap()).unwrap();
+        assert!(styles.contains(r#"formatCode="0.00%""#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_excel_invalid() {
+        let df = df!["a" => [1]].unwrap();
+        let write = |name: &str, options: &ExcelWriteOptions| {
+            ExcelWriter::new(vec![]).finish_sheets(&[(name, &df, options)])
+        };
+        let options = ExcelWriteOptions::default();
+        assert!(write("a/b", &options).is_err());
+        assert!(write(&"x".repeat(32), &options).is_err());
+        assert!(write("a", &options.clone().with_column_width("b", 10.0)).is_err());
+        assert!(write("a", &options.clone().with_column_width("a", 300.0)).is_err());
+        assert!(ExcelWriter::new(vec![])
+            .finish_sheets(&[("a", &df, &options), ("A", &df, &options)])
+            .is_err());
+    }
+}
@@ -0,0 +1,566 @@
+//! The parts of an XLSX workbook: the list of sheets, the shared strings, the cell formats and
+//! the cells of the worksheets.
+use chrono::{NaiveDate, NaiveDateTime};
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::archive::ZipArchive;
+
+pub(super) const MS_PER_DAY: i64 = 86_400_000;
+
+/// A sheet of the workbook.
+pub(super) struct Sheet {
+    pub(super) name: String,
+    /// The path of the worksheet in the archive.
+    path: String,
+}
+
+/// Whether the numbers formatted with a cell format are dates or dates with a time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DateKind {
+    Date,
+    Datetime,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Cell {
+    /// A cell without a value. Cells with an error value like `#N/A` are read as empty.
+    Empty,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    /// Days since the UNIX epoch.
+    Date(i32),
+    /// Milliseconds since the UNIX epoch.
+    Datetime(i64),
+}
+
+impl Cell {
+    /// The text shown for the value, without the number formatting of the cell.
+    pub(super) fn to_text(&self) -> Option<String> {
+        Some(match self {
+            Cell::Empty => return None,
+            Cell::Boolean(v) => v.to_string(),
+            Cell::Number(v) if v.fract() == 0.0 && v.abs() < 1e15 => (*v as i64).to_string(),
+            Cell::Number(v) => v.to_string(),
+            Cell::String(v) => v.clone(),
+            Cell::Date(days) => {
+                (NaiveDate::default() + chrono::Duration::days(*days as i64)).to_string()
+            },
+            Cell::Datetime(ms) => chrono::DateTime::from_timestamp_millis(*ms)
+                .map(|dt| dt.naive_utc().to_string())
+                .unwrap_or_else(|| ms.to_string()),
+        })
+    }
+}
+
+pub(super) struct Workbook<'a> {
+    archive: ZipArchive<'a>,
+    pub(super) sheets: Vec<Sheet>,
+    /// Whether the serial numbers of dates count from 1904-01-01 instead of 1900-01-01.
+    date1904: bool,
+    shared_strings: Vec<String>,
+    /// The kind of date of the numbers of every cell format, if they are formatted as a date.
+    cell_formats: Vec<Option<DateKind>>,
+}
+
+impl<'a> Workbook<'a> {
+    pub(super) fn new(bytes: &'a [u8]) -> PolarsResult<Self> {
+        let archive = ZipArchive::new(bytes)?;
+
+        let root_relationships = read_relationships(&archive, "")?;
+        let workbook_path = root_relationships
+            .iter()
+            .find(|r| r.kind.ends_with("/officeDocument"))
+            .map_or_else(|| "xl/workbook.xml".to_string(), |r| resolve("", &r.target));
+        let workbook_dir = workbook_path
+            .rsplit_once('/')
+            .map_or("", |(dir, _)| dir)
+            .to_string();
+        let relationships = read_relationships(&archive, &workbook_path)?;
+        let part = |kind: &str| {
+            relationships
+                .iter()
+                .find(|r| r.kind.ends_with(kind))
+                .map(|r| resolve(&workbook_dir, &r.target))
+        };
+
+        let xml = archive.read(&workbook_path)?.ok_or_else(
+            || polars_err!(ComputeError: "not an XLSX file: the archive has no workbook"),
+        )?;
+        let mut date1904 = false;
+        let mut sheets = vec![];
+        let mut reader = Reader::from_reader(xml.as_slice());
+        loop {
+            match reader.read_event().map_err(to_compute_err)? {
+                Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                    b"workbookPr" => {
+                        date1904 =
+                            matches!(attribute(&e, b"date1904")?.as_deref(), Some("1" | "true"))
+                    },
+                    b"sheet" => {
+                        let (Some(name), Some(id)) =
+                            (attribute(&e, b"name")?, attribute(&e, b"id")?)
+                        else {
+                            polars_bail!(ComputeError: "out-of-spec XLSX file: invalid sheet in the workbook")
+                        };
+                        // Chart sheets have no cells.
+                        if let Some(r) = relationships
+                            .iter()
+                            .find(|r| r.id == id && r.kind.ends_with("/worksheet"))
+                        {
+                            sheets.push(Sheet {
+                                name,
+                                path: resolve(&workbook_dir, &r.target),
+                            })
+                        }
+                    },
+                    _ => {},
+                },
+                Event::Eof => break,
+                _ => {},
+            }
+        }
+
+        let shared_strings = match part("/sharedStrings") {
+            Some(path) => match archive.read(&path)? {
+                Some(xml) => read_shared_strings(&xml)?,
+                None => vec![],
+            },
+            None => vec![],
+        };
+        let cell_formats = match part("/styles") {
+            Some(path) => match archive.read(&path)? {
+                Some(xml) => read_cell_formats(&xml)?,
+                None => vec![],
+            },
+            None => vec![],
+        };
+
+        Ok(Self {
+            archive,
+            sheets,
+            date1904,
+            shared_strings,
+            cell_formats,
+        })
+    }
+
+    /// Read the cells of a sheet into rows, such that `rows[i][j]` is the cell in the `i`-th row
+    /// and `j`-th column of the sheet. Rows are not padded to the same length.
+    pub(super) fn read_sheet(&self, sheet: &Sheet) -> PolarsResult<Vec<Vec<Cell>>> {
+        let xml = self.archive.read(&sheet.path)?.ok_or_else(|| {
+            polars_err!(ComputeError: "out-of-spec XLSX file: sheet '{}' is missing", sheet.name)
+        })?;
+
+        let mut rows: Vec<Vec<Cell>> = vec![];
+        let mut current_row = None;
+        let mut next_column = 0;
+        // The column, type and format of the current cell.
+        let mut cell: Option<(usize, Option<String>, usize)> = None;
+        let mut value = String::new();
+        let mut in_value = false;
+        let mut in_inline_string = false;
+        let mut in_phonetic = false;
+
+        let mut reader = Reader::from_reader(xml.as_slice());
+        loop {
+            match reader.read_event().map_err(to_compute_err)? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"row" => {
+                        let index = match attribute(&e, b"r")? {
+                            Some(r) => r.parse::<usize>().map_err(to_compute_err)?.max(1) - 1,
+                            None => rows.len(),
+                        };
+                        rows.resize_with(rows.len().max(index + 1), Vec::new);
+                        current_row = Some(index);
+                        next_column = 0;
+                    },
+                    b"c" => {
+                        let column = cell_column(&e, next_column)?;
+                        let style = match attribute(&e, b"s")? {
+                            Some(s) => s.parse::<usize>().map_err(to_compute_err)?,
+                            None => 0,
+                        };
+                        cell = Some((column, attribute(&e, b"t")?, style));
+                        next_column = column + 1;
+                        value.clear();
+                    },
+                    b"v" => in_value = true,
+                    b"is" => in_inline_string = true,
+                    b"t" if in_inline_string => in_value = true,
+                    b"rPh" => in_phonetic = true,
+                    _ => {},
+                },
+                Event::Empty(e) => match e.local_name().as_ref() {
+                    b"row" => {
+                        if let Some(r) = attribute(&e, b"r")? {
+                            let index = r.parse::<usize>().map_err(to_compute_err)?.max(1);
+                            rows.resize_with(rows.len().max(index), Vec::new);
+                        }
+                    },
+                    // A cell without a value, that is only formatted.
+                    b"c" => next_column = cell_column(&e, next_column)? + 1,
+                    _ => {},
+                },
+                Event::Text(t) if in_value && !in_phonetic => {
+                    value.push_str(&t.unescape().map_err(to_compute_err)?)
+                },
+                Event::CData(t) if in_value && !in_phonetic => {
+                    value.push_str(&String::from_utf8_lossy(&t))
+                },
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"v" | b"t" => in_value = false,
+                    b"is" => in_inline_string = false,
+                    b"rPh" => in_phonetic = false,
+                    b"row" => current_row = None,
+                    b"c" => {
+                        if let (Some((column, cell_type, style)), Some(row)) =
+                            (cell.take(), current_row)
+                        {
+                            let row = &mut rows[row];
+                            let cell = self.to_cell(cell_type.as_deref(), style, &value)?;
+                            if cell != Cell::Empty {
+                                if row.len() <= column {
+                                    row.resize(column + 1, Cell::Empty);
+                                }
+                                row[column] = cell;
+                            }
+                        }
+                    },
+                    _ => {},
+                },
+                Event::Eof => break,
+                _ => {},
+            }
+        }
+        Ok(rows)
+    }
+
+    fn to_cell(&self, cell_type: Option<&str>, style: usize, value: &str) -> PolarsResult<Cell> {
+        if value.is_empty() {
+            return Ok(Cell::Empty);
+        }
+        Ok(match cell_type {
+            Some("s") => {
+                let index = value.trim().parse::<usize>().map_err(to_compute_err)?;
+                let s = self.shared_strings.get(index).ok_or_else(|| {
+                    polars_err!(ComputeError: "out-of-spec XLSX file: unknown shared string {}", index)
+                })?;
+                Cell::String(s.clone())
+            },
+            Some("str" | "inlineStr") => Cell::String(value.to_string()),
+            Some("b") => Cell::Boolean(matches!(value.trim(), "1" | "true")),
+            Some("e") => Cell::Empty,
+            Some("d") => parse_iso_date(value.trim()),
+            _ => {
+                let number = value.trim().parse::<f64>().map_err(
+                    |_| polars_err!(ComputeError: "out-of-spec XLSX file: invalid number '{}'", value),
+                )?;
+                match self.cell_formats.get(style).copied().flatten() {
+                    Some(kind) if (0.0..=MAX_SERIAL).contains(&number) => {
+                        let ms = serial_to_ms(number, self.date1904);
+                        match kind {
+                            DateKind::Date => Cell::Date(ms.div_euclid(MS_PER_DAY) as i32),
+                            DateKind::Datetime => Cell::Datetime(ms),
+                        }
+                    },
+                    _ => Cell::Number(number),
+                }
+            },
+        })
+    }
+}
+
+/// The serial number of 9999-12-31 23:59:59, the last date that Excel can show.
+const MAX_SERIAL: f64 = 2_958_465.999_988_4;
+
+/// Convert the serial number of a date to milliseconds since the UNIX epoch.
+fn serial_to_ms(serial: f64, date1904: bool) -> i64 {
+    // In the 1900 date system serial 1 is 1900-01-01 and serial 60 is 1900-02-29, a day that
+    // doesn't exist but is kept for compatibility with Lotus 1-2-3.
+    let epoch = if date1904 {
+        24107.0
+    } else if serial < 61.0 {
+        25568.0
+    } else {
+        25569.0
+    };
+    ((serial - epoch) * MS_PER_DAY as f64).round() as i64
+}
+
+/// Parse the value of a cell of type `d`, which is an ISO 8601 date.
+fn parse_iso_date(value: &str) -> Cell {
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f") {
+        Cell::Datetime(dt.and_utc().timestamp_millis())
+    } else if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Cell::Date((date - NaiveDate::default()).num_days() as i32)
+    } else {
+        Cell::String(value.to_string())
+    }
+}
+
+/// The column of a cell, from its reference like `B3`, or the column after the previous cell.
+fn cell_column(e: &BytesStart, next_column: usize) -> PolarsResult<usize> {
+    let Some(reference) = attribute(e, b"r")? else {
+        return Ok(next_column);
+    };
+    let letters = reference
+        .bytes()
+        .take_while(|b| b.is_ascii_alphabetic())
+        .collect::<Vec<_>>();
+    polars_ensure!(
+        (1..=3).contains(&letters.len()),
+        ComputeError: "out-of-spec XLSX file: invalid cell reference '{}'", reference
+    );
+    let column = letters.iter().fold(0, |acc, b| {
+        acc * 26 + (b.to_ascii_uppercase() - b'A') as usize + 1
+    });
+    Ok(column - 1)
+}
+
+fn attribute(e: &BytesStart, key: &[u8]) -> PolarsResult<Option<String>> {
+    for attr in e.attributes() {
+        let attr = attr.map_err(to_compute_err)?;
+        if attr.key.local_name().as_ref() == key {
+            return Ok(Some(
+                attr.unescape_value().map_err(to_compute_err)?.into_owned(),
+            ));
+        }
+    }
+    Ok(None)
+}
+
+struct Relationship {
+    id: String,
+    kind: String,
+    target: String,
+}
+
+/// Read the relationships of the part at `path`, or of the package if `path` is empty.
+fn read_relationships(archive: &ZipArchive, path: &str) -> PolarsResult<Vec<Relationship>> {
+    let rels_path = match path.rsplit_once('/') {
+        Some((dir, name)) => format!("{dir}/_rels/{name}.rels"),
+        None => format!("_rels/{path}.rels"),
+    };
+    let Some(xml) = archive.read(&rels_path)? else {
+        return Ok(vec![]);
+    };
+
+    let mut relationships = vec![];
+    let mut reader = Reader::from_reader(xml.as_slice());
+    loop {
+        match reader.read_event().map_err(to_compute_err)? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Relationship" => {
+                if let (Some(id), Some(kind), Some(target)) = (
+                    attribute(&e, b"Id")?,
+                    attribute(&e, b"Type")?,
+                    attribute(&e, b"Target")?,
+                ) {
+                    relationships.push(Relationship { id, kind, target })
+                }
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+    Ok(relationships)
+}
+
+/// Resolve the target of a relationship of a part in the directory `dir`.
+fn resolve(dir: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+    let mut parts = dir.split('/').filter(|p| !p.is_empty()).collect::<Vec<_>>();
+    for part in target.split('/') {
+        match part {
+            ".." => {
+                parts.pop();
+            },
+            "." | "" => {},
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Read the table of strings that cells of type `s` refer to by their index.
+fn read_shared_strings(xml: &[u8]) -> PolarsResult<Vec<String>> {
+    let mut strings = vec![];
+    let mut current = String::new();
+    let mut in_text = false;
+    // The phonetic reading of east asian text is not part of the value.
+    let mut in_phonetic = false;
+
+    let mut reader = Reader::from_reader(xml);
+    loop {
+        match reader.read_event().map_err(to_compute_err)? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"si" => current.clear(),
+                b"t" => in_text = true,
+                b"rPh" => in_phonetic = true,
+                _ => {},
+            },
+            Event::Empty(e) if e.local_name().as_ref() == b"si" => strings.push(String::new()),
+            Event::Text(t) if in_text && !in_phonetic => {
+                current.push_str(&t.unescape().map_err(to_compute_err)?)
+            },
+            Event::CData(t) if in_text && !in_phonetic => {
+                current.push_str(&String::from_utf8_lossy(&t))
+            },
+            Event::End(e) => match e.local_name().as_ref() {
+                b"si" => strings.push(std::mem::take(&mut current)),
+                b"t" => in_text = false,
+                b"rPh" => in_phonetic = false,
+                _ => {},
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+    Ok(strings)
+}
+
+/// Read which cell formats show numbers as dates.
+fn read_cell_formats(xml: &[u8]) -> PolarsResult<Vec<Option<DateKind>>> {
+    let mut custom_formats = PlHashMap::new();
+    let mut format_ids = vec![];
+    let mut in_cell_formats = false;
+
+    let mut reader = Reader::from_reader(xml);
+    loop {
+        match reader.read_event().map_err(to_compute_err)? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"numFmt" => {
+                    if let (Some(id), Some(code)) =
+                        (attribute(&e, b"numFmtId")?, attribute(&e, b"formatCode")?)
+                    {
+                        custom_formats.insert(id.parse::<u32>().map_err(to_compute_err)?, code);
+                    }
+                },
+                b"cellXfs" => in_cell_formats = true,
+                b"xf" if in_cell_formats => {
+                    let id = match attribute(&e, b"numFmtId")? {
+                        Some(id) => id.parse::<u32>().map_err(to_compute_err)?,
+                        None => 0,
+                    };
+                    format_ids.push(id);
+                },
+                _ => {},
+            },
+            Event::End(e) if e.local_name().as_ref() == b"cellXfs" => in_cell_formats = false,
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok(format_ids
+        .into_iter()
+        .map(|id| match custom_formats.get(&id) {
+            Some(code) => date_kind_of_format(code),
+            None => builtin_date_kind(id),
+        })
+        .collect())
+}
+
+/// The kind of date of the built-in number formats.
+fn builtin_date_kind(id: u32) -> Option<DateKind> {
+    match id {
+        14..=17 | 27..=36 | 50..=58 => Some(DateKind::Date),
+        // 46 is `[h]:mm:ss`, an elapsed time.
+        18..=22 | 45 | 47 => Some(DateKind::Datetime),
+        _ => None,
+    }
+}
+
+/// Whether a number format shows a date, and whether it includes the time.
+fn date_kind_of_format(code: &str) -> Option<DateKind> {
+    // Only the format of positive numbers matters.
+    let code = code.split(';').next().unwrap_or_default();
+
+    let (mut has_date, mut has_time, mut has_m) = (false, false, false);
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        match c.to_ascii_lowercase() {
+            // Literal text.
+            '"' => {
+                chars.by_ref().find(|&c| c == '"');
+            },
+            '\\' | '_' | '*' => {
+                chars.next();
+            },
+            // Colors, conditions, locales and elapsed times like `[h]`.
+            '[' => {
+                let section = chars.by_ref().take_while(|&c| c != ']').collect::<String>();
+                if matches!(
+                    section.to_ascii_lowercase().as_str(),
+                    "h" | "hh" | "m" | "mm" | "s" | "ss"
+                ) {
+                    return None;
+                }
+            },
+            'y' | 'd' => has_date = true,
+            'h' | 's' => has_time = true,
+            'm' => has_m = true,
+            _ => {},
+        }
+    }
+
+    // `m` is the month unless the format has hours or seconds, in which case it may be minutes.
+    match (has_date || (has_m && !has_time), has_time) {
+        (true, false) => Some(DateKind::Date),
+        (_, true) => Some(DateKind::Datetime),
+        (false, false) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_kind_of_format() {
+        assert_eq!(date_kind_of_format("yyyy-mm-dd"), Some(DateKind::Date));
+        assert_eq!(date_kind_of_format("mmm yy"), Some(DateKind::Date));
+        assert_eq!(
+            date_kind_of_format("dd/mm/yyyy hh:mm:ss"),
+            Some(DateKind::Datetime)
+        );
+        assert_eq!(date_kind_of_format("h:mm AM/PM"), Some(DateKind::Datetime));
+        assert_eq!(
+            date_kind_of_format("[$-409]d-mmm-yy;@"),
+            Some(DateKind::Date)
+        );
+        assert_eq!(date_kind_of_format("[h]:mm:ss"), None);
+        assert_eq!(date_kind_of_format("#,##0.00 \"days\""), None);
+        assert_eq!(date_kind_of_format("[Red]0.00;[Blue]-0.00"), None);
+        assert_eq!(date_kind_of_format("General"), None);
+    }
+
+    #[test]
+    fn test_serial_to_ms() {
+        assert_eq!(serial_to_ms(25569.0, false), 0);
+        assert_eq!(serial_to_ms(25569.5, false), MS_PER_DAY / 2);
+        // 1900-01-01 and 1900-03-01, around the day that doesn't exist.
+        assert_eq!(serial_to_ms(1.0, false), -25567 * MS_PER_DAY);
+        assert_eq!(serial_to_ms(61.0, false), -25508 * MS_PER_DAY);
+        assert_eq!(serial_to_ms(24107.0, true), 0);
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            resolve("xl", "worksheets/sheet1.xml"),
+            "xl/worksheets/sheet1.xml"
+        );
+        assert_eq!(
+            resolve("xl", "/xl/sharedStrings.xml"),
+            "xl/sharedStrings.xml"
+        );
+        assert_eq!(resolve("xl/worksheets", "../styles.xml"), "xl/styles.xml");
+        assert_eq!(resolve("", "xl/workbook.xml"), "xl/workbook.xml");
+    }
+}
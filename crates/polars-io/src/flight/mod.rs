@@ -0,0 +1,61 @@
+//! # Arrow Flight.
+//!
+//! A client of the Arrow Flight RPC protocol, which transfers Arrow IPC messages over gRPC. The
+//! stream of a `DoGet` call is read in batches by a [`FlightReader`], and the batches of a
+//! [`FlightWriter`] are uploaded with a `DoPut` call. The result of a query on a Flight SQL
+//! service is read from its endpoints with a [`FlightSqlStatement`].
+mod read;
+mod sql;
+mod write;
+
+use arrow_format::flight::data;
+pub use read::FlightReader;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+pub use sql::FlightSqlStatement;
+pub use write::FlightWriter;
+
+/// Identifies the data that is uploaded to a Flight service.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FlightDescriptor {
+    /// An opaque command that is interpreted by the service, such as a query.
+    Cmd(Vec<u8>),
+    /// The path of the data, such as the name of a table.
+    Path(Vec<String>),
+}
+
+impl FlightDescriptor {
+    fn to_proto(&self) -> data::FlightDescriptor {
+        use data::flight_descriptor::DescriptorType;
+        match self {
+            Self::Cmd(cmd) => data::FlightDescriptor {
+                r#type: DescriptorType::Cmd as i32,
+                cmd: cmd.clone(),
+                path: vec![],
+            },
+            Self::Path(path) => data::FlightDescriptor {
+                r#type: DescriptorType::Path as i32,
+                cmd: vec![],
+                path: path.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descriptor_to_proto() {
+        let descriptor = FlightDescriptor::Path(vec!["db".into(), "table".into()]).to_proto();
+        assert_eq!(descriptor.r#type, 1);
+        assert_eq!(descriptor.path, ["db", "table"]);
+        assert!(descriptor.cmd.is_empty());
+
+        let descriptor = FlightDescriptor::Cmd(b"SELECT 1".to_vec()).to_proto();
+        assert_eq!(descriptor.r#type, 2);
+        assert_eq!(descriptor.cmd, b"SELECT 1");
+    }
+}
@@ -0,0 +1,106 @@
+use arrow::datatypes::ArrowSchemaRef;
+use arrow::io::flight::{deserialize_message, deserialize_schemas};
+use arrow::io::ipc::read::Dictionaries;
+use arrow::io::ipc::IpcSchema;
+use arrow_format::flight::data::{FlightData, Ticket};
+use arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+
+use crate::pl_async::get_runtime;
+
+/// Reads the stream of a `DoGet` call to a Flight service in batches.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::flight::FlightReader;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     FlightReader::try_new("http://localhost:8815", "SELECT * FROM t")?.finish()
+/// }
+/// ```
+pub struct FlightReader {
+    stream: BoxStream<'static, PolarsResult<FlightData>>,
+    arrow_schema: ArrowSchemaRef,
+    ipc_schema: IpcSchema,
+    schema: SchemaRef,
+    dictionaries: Dictionaries,
+}
+
+impl FlightReader {
+    /// Call `DoGet` with `ticket` on the Flight service at `endpoint`, such as
+    /// `http://localhost:8815`, and read the schema of the stream.
+    pub fn try_new(endpoint: &str, ticket: impl Into<Vec<u8>>) -> PolarsResult<Self> {
+        let endpoint = endpoint.to_string();
+        let ticket = Ticket {
+            ticket: ticket.into(),
+        };
+        get_runtime().block_on_potential_spawn(async move {
+            let mut client = FlightServiceClient::connect(endpoint)
+                .await
+                .map_err(to_compute_err)?;
+            let stream = client
+                .do_get(ticket)
+                .await
+                .map_err(to_compute_err)?
+                .into_inner();
+            // The client owns the connection, so it is kept for as long as the stream is read.
+            let mut stream = stream
+                .map(move |data| {
+                    let _ = &client;
+                    data.map_err(to_compute_err)
+                })
+                .boxed();
+
+            let data = stream.try_next().await?.ok_or_else(
+                || polars_err!(ComputeError: "the Flight stream ended before its schema"),
+            )?;
+            let (arrow_schema, ipc_schema) = deserialize_schemas(&data.data_header)?;
+            Ok(Self {
+                stream,
+                schema: Arc::new(Schema::from_arrow_schema(&arrow_schema)),
+                arrow_schema: Arc::new(arrow_schema),
+                ipc_schema,
+                dictionaries: Default::default(),
+            })
+        })
+    }
+
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Read the next record batch of the stream, or `None` if the stream has ended.
+    pub fn next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        loop {
+            let Some(data) = get_runtime().block_on_potential_spawn(self.stream.try_next())? else {
+                return Ok(None);
+            };
+            // Dictionary batches are collected until the record batches that refer to them.
+            if let Some(batch) = deserialize_message(
+                &data,
+                &self.arrow_schema,
+                &self.ipc_schema,
+                &mut self.dictionaries,
+            )? {
+                return DataFrame::try_from((batch, self.arrow_schema.as_ref())).map(Some);
+            }
+        }
+    }
+
+    /// Read the rest of the stream into a DataFrame.
+    pub fn finish(mut self) -> PolarsResult<DataFrame> {
+        let mut dfs = vec![];
+        while let Some(df) = self.next_batch()? {
+            dfs.push(df);
+        }
+        if dfs.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&self.schema));
+        }
+        Ok(accumulate_dataframes_vertical_unchecked(dfs))
+    }
+}
@@ -0,0 +1,257 @@
+use arrow::io::flight::deserialize_schemas;
+use arrow_format::flight::data::FlightEndpoint;
+use arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+
+use super::{FlightDescriptor, FlightReader};
+use crate::pl_async::get_runtime;
+
+const COMMAND_STATEMENT_QUERY: &str =
+    "type.googleapis.com/arrow.flight.protocol.sql.CommandStatementQuery";
+
+/// A query that is executed by a Flight SQL service, of which the result is read from its
+/// endpoints.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::flight::FlightSqlStatement;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     FlightSqlStatement::try_new("grpc://localhost:32010", "SELECT * FROM t")?.finish()
+/// }
+/// ```
+pub struct FlightSqlStatement {
+    schema: Option<SchemaRef>,
+    /// The location and the ticket of the endpoints of the result.
+    endpoints: Vec<(String, Vec<u8>)>,
+    /// The reader of the first endpoint, if it was opened to read the schema.
+    first_reader: Option<FlightReader>,
+}
+
+impl FlightSqlStatement {
+    /// Execute `query` on the Flight SQL service at `dsn`, such as `grpc://localhost:32010`,
+    /// with a `GetFlightInfo` call.
+    pub fn try_new(dsn: &str, query: &str) -> PolarsResult<Self> {
+        let dsn = normalize_uri(dsn)?;
+        let descriptor = FlightDescriptor::Cmd(statement_query_command(query)).to_proto();
+        let endpoint = dsn.clone();
+        let info = get_runtime().block_on_potential_spawn(async move {
+            let mut client = FlightServiceClient::connect(endpoint)
+                .await
+                .map_err(to_compute_err)?;
+            client
+                .get_flight_info(descriptor)
+                .await
+                .map(|response| response.into_inner())
+                .map_err(to_compute_err)
+        })?;
+
+        let schema = match schema_message(&info.schema)? {
+            Some(message) => {
+                let (arrow_schema, _) = deserialize_schemas(message)?;
+                Some(Arc::new(Schema::from_arrow_schema(&arrow_schema)))
+            },
+            None => None,
+        };
+        let endpoints = info
+            .endpoint
+            .into_iter()
+            .map(|endpoint| endpoint_location(&dsn, endpoint))
+            .collect::<PolarsResult<_>>()?;
+        Ok(Self {
+            schema,
+            endpoints,
+            first_reader: None,
+        })
+    }
+
+    /// The schema of the result, which is read from the first endpoint if the service doesn't
+    /// return it with the `FlightInfo`.
+    pub fn schema(&mut self) -> PolarsResult<SchemaRef> {
+        if self.schema.is_none() {
+            let schema = match self.endpoints.first() {
+                Some((location, ticket)) => {
+                    let reader = FlightReader::try_new(location, ticket.clone())?;
+                    let schema = reader.schema().clone();
+                    self.first_reader = Some(reader);
+                    schema
+                },
+                None => Default::default(),
+            };
+            self.schema = Some(schema);
+        }
+        Ok(self.schema.clone().unwrap())
+    }
+
+    /// Read the record batches of the endpoints one at a time, in the order of the endpoints.
+    /// The columns of the batches are cast to the dtypes of [`FlightSqlStatement::schema`].
+    pub fn batches(mut self) -> PolarsResult<impl Iterator<Item = PolarsResult<DataFrame>> + Send> {
+        let schema = self.schema()?;
+        let mut endpoints = self.endpoints.into_iter();
+        let mut reader = self.first_reader.take();
+        if reader.is_some() {
+            endpoints.next();
+        }
+        Ok(std::iter::from_fn(move || loop {
+            if reader.is_none() {
+                let (location, ticket) = endpoints.next()?;
+                match FlightReader::try_new(&location, ticket) {
+                    Ok(r) => reader = Some(r),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+            match reader.as_mut().unwrap().next_batch() {
+                Ok(Some(df)) => return Some(cast_to_schema(df, &schema)),
+                Ok(None) => reader = None,
+                Err(e) => return Some(Err(e)),
+            }
+        }))
+    }
+
+    /// Read the result into a DataFrame.
+    pub fn finish(mut self) -> PolarsResult<DataFrame> {
+        let schema = self.schema()?;
+        let mut df = DataFrame::empty_with_schema(&schema);
+        for batch in self.batches()? {
+            df.vstack_mut(&batch?)?;
+        }
+        Ok(df)
+    }
+}
+
+fn encode_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Encode a length-delimited field of a protobuf message.
+fn encode_bytes_field(buf: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    encode_varint(buf, (field << 3) | 2);
+    encode_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// The command of the descriptor of a query, which is a `CommandStatementQuery` message packed
+/// in a `google.protobuf.Any` message.
+fn statement_query_command(query: &str) -> Vec<u8> {
+    let mut command = vec![];
+    encode_bytes_field(&mut command, 1, query.as_bytes());
+    let mut any = vec![];
+    encode_bytes_field(&mut any, 1, COMMAND_STATEMENT_QUERY.as_bytes());
+    encode_bytes_field(&mut any, 2, &command);
+    any
+}
+
+/// The flatbuffer of the schema message of a `FlightInfo`, which is encapsulated as an IPC
+/// message, or `None` if the service didn't return a schema.
+fn schema_message(schema: &[u8]) -> PolarsResult<Option<&[u8]>> {
+    if schema.is_empty() {
+        return Ok(None);
+    }
+    // The length is preceded by a continuation marker, except in the legacy format.
+    let offset = if schema.starts_with(&[0xff; 4]) { 4 } else { 0 };
+    polars_ensure!(
+        schema.len() >= offset + 4,
+        ComputeError: "invalid schema in the FlightInfo of a Flight SQL query"
+    );
+    let length = i32::from_le_bytes(schema[offset..offset + 4].try_into().unwrap()) as usize;
+    let message = schema.get(offset + 4..offset + 4 + length).ok_or_else(
+        || polars_err!(ComputeError: "invalid schema in the FlightInfo of a Flight SQL query"),
+    )?;
+    Ok(Some(message))
+}
+
+/// The URI of the Flight location, which gRPC clients connect to over HTTP/2.
+fn normalize_uri(uri: &str) -> PolarsResult<String> {
+    let (scheme, rest) = uri.split_once("://").ok_or_else(
+        || polars_err!(ComputeError: "invalid Flight SQL location '{}'; expected a URI", uri),
+    )?;
+    let scheme = match scheme {
+        "grpc" | "grpc+tcp" | "http" => "http",
+        "grpc+tls" | "https" => "https",
+        scheme => polars_bail!(
+            ComputeError: "unsupported scheme '{}' of Flight SQL location '{}'", scheme, uri
+        ),
+    };
+    Ok(format!("{scheme}://{rest}"))
+}
+
+/// The location and the ticket of an endpoint. Endpoints without a location are read from the
+/// service that executed the query.
+fn endpoint_location(dsn: &str, endpoint: FlightEndpoint) -> PolarsResult<(String, Vec<u8>)> {
+    let ticket = endpoint
+        .ticket
+        .ok_or_else(|| polars_err!(ComputeError: "Flight SQL endpoint without a ticket"))?
+        .ticket;
+    let location = match endpoint.location.first() {
+        Some(location) if !location.uri.starts_with("arrow-flight-reuse-connection:") => {
+            normalize_uri(&location.uri)?
+        },
+        _ => dsn.to_string(),
+    };
+    Ok((location, ticket))
+}
+
+fn cast_to_schema(df: DataFrame, schema: &Schema) -> PolarsResult<DataFrame> {
+    polars_ensure!(
+        df.width() == schema.len(),
+        SchemaMismatch: "a Flight SQL endpoint returned {} columns, expected {}",
+        df.width(), schema.len()
+    );
+    let columns = df
+        .get_columns()
+        .iter()
+        .zip(schema.iter())
+        .map(|(s, (name, dtype))| {
+            let s = if s.dtype() == dtype {
+                s.clone()
+            } else {
+                s.cast(dtype)?
+            };
+            Ok(s.with_name(name.clone()))
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    DataFrame::new(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statement_query_command() {
+        let command = statement_query_command("SELECT 1");
+        let type_url = COMMAND_STATEMENT_QUERY.as_bytes();
+        assert_eq!(command[..2], [0x0a, type_url.len() as u8]);
+        assert_eq!(&command[2..2 + type_url.len()], type_url);
+        assert_eq!(command[2 + type_url.len()..], *b"\x12\x0a\x0a\x08SELECT 1");
+    }
+
+    #[test]
+    fn test_schema_message() {
+        assert_eq!(schema_message(&[]).unwrap(), None);
+        let encapsulated = [0xff, 0xff, 0xff, 0xff, 2, 0, 0, 0, 7, 8];
+        assert_eq!(schema_message(&encapsulated).unwrap(), Some(&[7, 8][..]));
+        assert_eq!(schema_message(&[1, 0, 0, 0, 7]).unwrap(), Some(&[7][..]));
+        assert!(schema_message(&[0xff, 0xff, 0xff, 0xff, 9, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_uri() {
+        assert_eq!(
+            normalize_uri("grpc://localhost:32010").unwrap(),
+            "http://localhost:32010"
+        );
+        assert_eq!(
+            normalize_uri("grpc+tls://example.com:443").unwrap(),
+            "https://example.com:443"
+        );
+        assert!(normalize_uri("localhost:32010").is_err());
+        assert!(normalize_uri("grpc+unix:///tmp/flight.sock").is_err());
+    }
+}
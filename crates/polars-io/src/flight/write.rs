@@ -0,0 +1,113 @@
+use arrow::io::flight::{default_ipc_fields, serialize_batch, serialize_schema, WriteOptions};
+use arrow::io::ipc::IpcField;
+use arrow_format::flight::data::FlightData;
+use arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::FlightDescriptor;
+use crate::pl_async::get_runtime;
+
+/// Uploads batches to a Flight service with a `DoPut` call.
+///
+/// The call is made when the writer is created, and the batches are streamed to the service as
+/// they are written. The upload is complete once [`FlightWriter::finish`] returns.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::flight::{FlightDescriptor, FlightWriter};
+///
+/// fn example(df: &DataFrame) -> PolarsResult<()> {
+///     let descriptor = FlightDescriptor::Path(vec!["events".into()]);
+///     let mut writer = FlightWriter::try_new("http://localhost:8815", &descriptor, &df.schema())?;
+///     writer.write_batch(df)?;
+///     writer.finish()
+/// }
+/// ```
+pub struct FlightWriter {
+    sender: Option<mpsc::Sender<FlightData>>,
+    handle: Option<JoinHandle<PolarsResult<()>>>,
+    ipc_fields: Vec<IpcField>,
+}
+
+impl FlightWriter {
+    /// Call `DoPut` for `descriptor` on the Flight service at `endpoint`, such as
+    /// `http://localhost:8815`, and send the schema of the batches.
+    pub fn try_new(
+        endpoint: &str,
+        descriptor: &FlightDescriptor,
+        schema: &Schema,
+    ) -> PolarsResult<Self> {
+        let arrow_schema = schema.to_arrow(CompatLevel::newest());
+        let ipc_fields = default_ipc_fields(arrow_schema.iter_values());
+        let mut schema_data = serialize_schema(&arrow_schema, Some(&ipc_fields));
+        schema_data.flight_descriptor = Some(descriptor.to_proto());
+
+        let (sender, receiver) = mpsc::channel(16);
+        let endpoint = endpoint.to_string();
+        let handle = get_runtime().spawn(async move {
+            let mut client = FlightServiceClient::connect(endpoint)
+                .await
+                .map_err(to_compute_err)?;
+            let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+                receiver.recv().await.map(|data| (data, receiver))
+            });
+            let mut results = client
+                .do_put(stream)
+                .await
+                .map_err(to_compute_err)?
+                .into_inner();
+            // The service may acknowledge the batches, which are not used.
+            while results.message().await.map_err(to_compute_err)?.is_some() {}
+            Ok(())
+        });
+
+        let mut writer = Self {
+            sender: Some(sender),
+            handle: Some(handle),
+            ipc_fields,
+        };
+        writer.send(schema_data)?;
+        Ok(writer)
+    }
+
+    fn send(&mut self, data: FlightData) -> PolarsResult<()> {
+        let sender = self.sender.as_ref().unwrap();
+        if sender.blocking_send(data).is_err() {
+            // The call has failed, of which the error is returned.
+            self.join()?;
+            polars_bail!(ComputeError: "the Flight DoPut call ended before all batches were sent");
+        }
+        Ok(())
+    }
+
+    fn join(&mut self) -> PolarsResult<()> {
+        self.sender = None;
+        match self.handle.take() {
+            Some(handle) => get_runtime()
+                .block_on_potential_spawn(handle)
+                .map_err(to_compute_err)?,
+            None => Ok(()),
+        }
+    }
+
+    pub fn write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        let options = WriteOptions { compression: None };
+        for batch in df.iter_chunks(CompatLevel::newest(), true) {
+            let (dictionaries, batch) = serialize_batch(&batch, &self.ipc_fields, &options)?;
+            for data in dictionaries {
+                self.send(data)?;
+            }
+            self.send(batch)?;
+        }
+        Ok(())
+    }
+
+    /// End the stream of batches, and wait for the service to complete the call.
+    pub fn finish(&mut self) -> PolarsResult<()> {
+        self.join()
+    }
+}
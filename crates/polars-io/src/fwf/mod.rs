@@ -0,0 +1,22 @@
+//! Read fixed-width files, in which every column is at the same byte range of every line.
+//!
+//! # Examples
+//!
+//! ```
+//! use polars_core::prelude::*;
+//! use polars_io::fwf::{read_fwf, FwfLayout, FwfReadOptions};
+//!
+//! fn example() -> PolarsResult<DataFrame> {
+//!     // An account number of 10 bytes, followed by a name of 30 bytes and a balance of 12.
+//!     let options = FwfReadOptions::default()
+//!         .with_layout(FwfLayout::from_widths(&[10, 30, 12]))
+//!         .with_has_header(false)
+//!         .with_column_names(Some(["account".into(), "name".into(), "balance".into()].into()));
+//!     read_fwf("extract.txt", options)
+//! }
+//! ```
+mod options;
+mod read;
+
+pub use options::*;
+pub use read::*;
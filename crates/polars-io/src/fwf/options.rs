@@ -0,0 +1,114 @@
+use polars_core::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How the lines of a fixed-width file are split in columns.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FwfLayout {
+    /// The `[start, end)` byte range of every column in a line. Lines that are shorter than a
+    /// range are padded with spaces.
+    Offsets(Vec<(usize, usize)>),
+    /// Infer the columns from the lines that are sampled for schema inference. A column is a run
+    /// of byte positions at which any of these lines has a character other than whitespace, so
+    /// columns must be separated by at least one space in every line. The last column extends to
+    /// the end of the line.
+    Infer,
+}
+
+impl FwfLayout {
+    /// Columns of these widths in bytes, that follow each other without a gap.
+    pub fn from_widths(widths: &[usize]) -> Self {
+        let mut start = 0;
+        Self::Offsets(
+            widths
+                .iter()
+                .map(|width| {
+                    let range = (start, start + width);
+                    start += width;
+                    range
+                })
+                .collect(),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FwfReadOptions {
+    pub layout: FwfLayout,
+    pub has_header: bool,
+    /// Names of the columns, instead of the names in the header.
+    pub column_names: Option<Arc<[PlSmallStr]>>,
+    /// Number of lines to skip before the header, or before the first line of data if there is
+    /// no header. Empty lines are not counted.
+    pub skip_rows: usize,
+    pub infer_schema_length: Option<usize>,
+    /// The names and dtypes of all columns, which are then not inferred.
+    pub schema: Option<SchemaRef>,
+    /// Overwrite the inferred dtypes of these columns.
+    pub schema_overwrite: Option<SchemaRef>,
+}
+
+impl Default for FwfReadOptions {
+    fn default() -> Self {
+        Self {
+            layout: FwfLayout::Infer,
+            has_header: true,
+            column_names: None,
+            skip_rows: 0,
+            infer_schema_length: Some(100),
+            schema: None,
+            schema_overwrite: None,
+        }
+    }
+}
+
+impl FwfReadOptions {
+    pub fn with_layout(mut self, layout: FwfLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Read the columns at these `[start, end)` byte ranges of every line.
+    pub fn with_offsets(mut self, offsets: Vec<(usize, usize)>) -> Self {
+        self.layout = FwfLayout::Offsets(offsets);
+        self
+    }
+
+    /// Whether the first line that is read has the column names.
+    pub fn with_has_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// Set the names of the columns, instead of reading them from the header.
+    pub fn with_column_names(mut self, column_names: Option<Arc<[PlSmallStr]>>) -> Self {
+        self.column_names = column_names;
+        self
+    }
+
+    /// Skip this number of lines before the header.
+    pub fn with_skip_rows(mut self, skip_rows: usize) -> Self {
+        self.skip_rows = skip_rows;
+        self
+    }
+
+    /// Number of lines to infer the layout and the dtypes from. `None` reads all lines.
+    pub fn with_infer_schema_length(mut self, infer_schema_length: Option<usize>) -> Self {
+        self.infer_schema_length = infer_schema_length;
+        self
+    }
+
+    /// Set the names and dtypes of all columns.
+    pub fn with_schema(mut self, schema: Option<SchemaRef>) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// Overwrite the inferred dtypes of these columns.
+    pub fn with_schema_overwrite(mut self, schema_overwrite: Option<SchemaRef>) -> Self {
+        self.schema_overwrite = schema_overwrite;
+        self
+    }
+}
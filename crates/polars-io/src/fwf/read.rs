@@ -0,0 +1,582 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use polars_core::prelude::*;
+use polars_core::POOL;
+use polars_utils::format_pl_smallstr;
+use polars_utils::mmap::MemSlice;
+use rayon::prelude::*;
+
+use super::options::{FwfLayout, FwfReadOptions};
+use crate::mmap::MmapBytesReader;
+use crate::predicates::PhysicalIoExpr;
+use crate::prelude::*;
+use crate::utils::{get_reader_bytes, BOOLEAN_RE, FLOAT_RE, INTEGER_RE};
+use crate::RowIndex;
+
+/// Read a fixed-width file into a [`DataFrame`].
+///
+/// # Example
+///
+/// ```
+/// use std::fs::File;
+/// use polars_core::prelude::*;
+/// use polars_io::fwf::{FwfReadOptions, FwfReader};
+/// use polars_io::SerReader;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     let file = File::open("extract.txt").expect("file not found");
+///
+///     FwfReader::new(file)
+///         .with_options(FwfReadOptions::default().with_offsets(vec![(0, 8), (8, 20)]))
+///         .finish()
+/// }
+/// ```
+#[must_use]
+pub struct FwfReader<R> {
+    reader: R,
+    options: FwfReadOptions,
+    rechunk: bool,
+    n_rows: Option<usize>,
+    columns: Option<Arc<[PlSmallStr]>>,
+    row_index: Option<RowIndex>,
+    predicate: Option<Arc<dyn PhysicalIoExpr>>,
+}
+
+impl<R: MmapBytesReader> FwfReader<R> {
+    pub fn with_options(mut self, options: FwfReadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Stop reading when `n` rows are read.
+    pub fn with_n_rows(mut self, num_rows: Option<usize>) -> Self {
+        self.n_rows = num_rows;
+        self
+    }
+
+    /// Columns to select/ project. They are read in the order of the file.
+    pub fn with_columns(mut self, columns: Option<Arc<[PlSmallStr]>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Add a row index column.
+    pub fn with_row_index(mut self, row_index: Option<RowIndex>) -> Self {
+        self.row_index = row_index;
+        self
+    }
+
+    pub fn with_predicate(mut self, predicate: Option<Arc<dyn PhysicalIoExpr>>) -> Self {
+        self.predicate = predicate;
+        self
+    }
+
+    /// Infer the layout and the schema from the first lines of the file. Reading a file with
+    /// the returned options doesn't infer anything.
+    pub fn resolved_options(&mut self) -> PolarsResult<FwfReadOptions> {
+        let bytes = get_reader_bytes(&mut self.reader)?;
+        let resolved = resolve(&bytes, &self.options)?;
+        Ok(self
+            .options
+            .clone()
+            .with_offsets(resolved.offsets)
+            .with_schema(Some(resolved.schema))
+            .with_column_names(None)
+            .with_schema_overwrite(None))
+    }
+
+    /// Get the schema of the file, without the row index.
+    pub fn schema(&mut self) -> PolarsResult<SchemaRef> {
+        let bytes = get_reader_bytes(&mut self.reader)?;
+        Ok(resolve(&bytes, &self.options)?.schema)
+    }
+}
+
+impl FwfReader<Cursor<MemSlice>> {
+    /// Read the file in batches of `chunk_size` lines.
+    ///
+    /// A predicate set with [`FwfReader::with_predicate`] is not applied to the batches.
+    pub fn batched(self, chunk_size: usize) -> PolarsResult<BatchedFwfReader> {
+        let bytes = self.reader.into_inner();
+        let resolved = resolve(&bytes, &self.options)?;
+        let projection = projection(self.columns.as_deref(), &resolved.schema)?;
+        Ok(BatchedFwfReader {
+            bytes,
+            position: resolved.data_offset,
+            offsets: resolved.offsets,
+            schema: resolved.schema,
+            projection,
+            chunk_size: chunk_size.max(1),
+            n_rows_left: self.n_rows.unwrap_or(usize::MAX),
+            row_index: self.row_index,
+            rows_read: 0,
+        })
+    }
+}
+
+impl<R: MmapBytesReader> SerReader<R> for FwfReader<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            options: FwfReadOptions::default(),
+            rechunk: true,
+            n_rows: None,
+            columns: None,
+            row_index: None,
+            predicate: None,
+        }
+    }
+
+    fn set_rechunk(mut self, rechunk: bool) -> Self {
+        self.rechunk = rechunk;
+        self
+    }
+
+    fn finish(mut self) -> PolarsResult<DataFrame> {
+        let bytes = get_reader_bytes(&mut self.reader)?;
+        let resolved = resolve(&bytes, &self.options)?;
+        let projection = projection(self.columns.as_deref(), &resolved.schema)?;
+        let lines = lines(&bytes[resolved.data_offset..])
+            .take(self.n_rows.unwrap_or(usize::MAX))
+            .collect::<Vec<_>>();
+
+        let mut df = parse_lines(&lines, &resolved.offsets, &resolved.schema, &projection)?;
+        if let Some(row_index) = &self.row_index {
+            df.with_row_index_mut(row_index.name.clone(), Some(row_index.offset));
+        }
+        if let Some(predicate) = &self.predicate {
+            let mask = predicate.evaluate_io(&df)?;
+            df = df.filter(mask.bool()?)?;
+        }
+        if self.rechunk {
+            df.as_single_chunk_par();
+        }
+        Ok(df)
+    }
+}
+
+/// Reads a fixed-width file in batches of lines. Created by [`FwfReader::batched`].
+pub struct BatchedFwfReader {
+    bytes: MemSlice,
+    position: usize,
+    offsets: Vec<(usize, usize)>,
+    schema: SchemaRef,
+    projection: Vec<usize>,
+    chunk_size: usize,
+    n_rows_left: usize,
+    row_index: Option<RowIndex>,
+    rows_read: IdxSize,
+}
+
+impl BatchedFwfReader {
+    pub fn next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        let mut lines = vec![];
+        while lines.len() < self.chunk_size && lines.len() < self.n_rows_left {
+            let Some((line, next)) = next_line(&self.bytes, self.position) else {
+                break;
+            };
+            lines.push(line);
+            self.position = next;
+        }
+        if lines.is_empty() {
+            return Ok(None);
+        }
+
+        let mut df = parse_lines(&lines, &self.offsets, &self.schema, &self.projection)?;
+        self.n_rows_left -= lines.len();
+        if let Some(row_index) = &self.row_index {
+            df.with_row_index_mut(
+                row_index.name.clone(),
+                Some(row_index.offset + self.rows_read),
+            );
+        }
+        self.rows_read += lines.len() as IdxSize;
+        Ok(Some(df))
+    }
+}
+
+/// Read the fixed-width file at `path`.
+pub fn read_fwf<P: AsRef<Path>>(path: P, options: FwfReadOptions) -> PolarsResult<DataFrame> {
+    let file = polars_utils::open_file(path.as_ref())?;
+    FwfReader::new(file).with_options(options).finish()
+}
+
+/// Count the lines of data of a fixed-width file, without parsing them.
+pub fn count_rows<R: MmapBytesReader>(
+    mut reader: R,
+    options: &FwfReadOptions,
+) -> PolarsResult<usize> {
+    let bytes = get_reader_bytes(&mut reader)?;
+    let skipped = options.skip_rows + options.has_header as usize;
+    Ok(lines(&bytes).count().saturating_sub(skipped))
+}
+
+/// The columns of a file, resolved from its options and its first lines.
+struct Resolved {
+    offsets: Vec<(usize, usize)>,
+    schema: SchemaRef,
+    /// The position of the first line of data.
+    data_offset: usize,
+}
+
+fn resolve(bytes: &[u8], options: &FwfReadOptions) -> PolarsResult<Resolved> {
+    let mut position = 0;
+    for _ in 0..options.skip_rows {
+        let Some((_, next)) = next_line(bytes, position) else {
+            break;
+        };
+        position = next;
+    }
+    let mut header = None;
+    if options.has_header {
+        if let Some((line, next)) = next_line(bytes, position) {
+            header = Some(line);
+            position = next;
+        }
+    }
+    let sample = lines(&bytes[position..])
+        .take(options.infer_schema_length.unwrap_or(usize::MAX))
+        .collect::<Vec<_>>();
+
+    let offsets = match &options.layout {
+        FwfLayout::Offsets(offsets) => {
+            for &(start, end) in offsets {
+                polars_ensure!(
+                    start < end,
+                    ComputeError: "invalid fixed-width column: the byte range {}..{} is empty",
+                    start, end
+                );
+            }
+            offsets.clone()
+        },
+        FwfLayout::Infer => infer_offsets(header.into_iter().chain(sample.iter().copied())),
+    };
+    polars_ensure!(!offsets.is_empty(), NoData: "empty fixed-width file");
+
+    let schema = match &options.schema {
+        Some(schema) => {
+            polars_ensure!(
+                schema.len() == offsets.len(),
+                ShapeMismatch: "the schema has {} columns, but the layout of the file has {}",
+                schema.len(), offsets.len()
+            );
+            schema.clone()
+        },
+        None => {
+            let names = column_names(header, &offsets, options.column_names.as_deref())?;
+            let mut schema = names
+                .into_iter()
+                .zip(&offsets)
+                .map(|(name, &range)| Ok((name, infer_dtype(&sample, range)?)))
+                .collect::<PolarsResult<Schema>>()?;
+            if let Some(overwrite) = &options.schema_overwrite {
+                for (name, dtype) in overwrite.iter() {
+                    schema.set_dtype(name, dtype.clone());
+                }
+            }
+            Arc::new(schema)
+        },
+    };
+
+    Ok(Resolved {
+        offsets,
+        schema,
+        data_offset: position,
+    })
+}
+
+/// The columns of the lines, separated by whitespace in all of them.
+fn infer_offsets<'a>(lines: impl Iterator<Item = &'a [u8]>) -> Vec<(usize, usize)> {
+    let mut used = vec![];
+    for line in lines {
+        if line.len() > used.len() {
+            used.resize(line.len(), false);
+        }
+        for (used, byte) in used.iter_mut().zip(line) {
+            *used |= !byte.is_ascii_whitespace();
+        }
+    }
+
+    let mut offsets = vec![];
+    let mut start = None;
+    for (i, &used) in used.iter().enumerate() {
+        match start {
+            None if used => start = Some(i),
+            Some(s) if !used => {
+                offsets.push((s, i));
+                start = None;
+            },
+            _ => {},
+        }
+    }
+    if let Some(s) = start {
+        offsets.push((s, usize::MAX));
+    } else if let Some(last) = offsets.last_mut() {
+        last.1 = usize::MAX;
+    }
+    offsets
+}
+
+fn column_names(
+    header: Option<&[u8]>,
+    offsets: &[(usize, usize)],
+    names: Option<&[PlSmallStr]>,
+) -> PolarsResult<Vec<PlSmallStr>> {
+    if let Some(names) = names {
+        polars_ensure!(
+            names.len() == offsets.len(),
+            ShapeMismatch: "{} column names were given, but the layout of the file has {} columns",
+            names.len(), offsets.len()
+        );
+        return Ok(names.to_vec());
+    }
+
+    let mut counts = PlHashMap::new();
+    offsets
+        .iter()
+        .enumerate()
+        .map(|(i, &range)| {
+            let name = match header {
+                Some(line) => field(line, range)?,
+                None => None,
+            };
+            let name = name.map_or_else(|| format_pl_smallstr!("column_{}", i + 1), Into::into);
+            let count = counts.entry(name.clone()).or_insert(0usize);
+            *count += 1;
+            if *count > 1 {
+                Ok(format_pl_smallstr!("{}_duplicated_{}", name, *count - 1))
+            } else {
+                Ok(name)
+            }
+        })
+        .collect()
+}
+
+/// Infer the dtype of a column from the text of its fields.
+fn infer_dtype(lines: &[&[u8]], range: (usize, usize)) -> PolarsResult<DataType> {
+    let (mut any, mut boolean, mut integer, mut float) = (false, true, true, true);
+    for line in lines {
+        if let Some(text) = field(line, range)? {
+            any = true;
+            boolean &= BOOLEAN_RE.is_match(text);
+            integer &= INTEGER_RE.is_match(text);
+            float &= INTEGER_RE.is_match(text) || FLOAT_RE.is_match(text);
+        }
+    }
+    Ok(match () {
+        _ if !any => DataType::String,
+        _ if boolean => DataType::Boolean,
+        _ if integer => DataType::Int64,
+        _ if float => DataType::Float64,
+        _ => DataType::String,
+    })
+}
+
+/// The next line at or after `position` that isn't empty, without its line ending, and the
+/// position after it.
+fn next_line(bytes: &[u8], mut position: usize) -> Option<(&[u8], usize)> {
+    while position < bytes.len() {
+        let end = memchr::memchr(b'\n', &bytes[position..]).map_or(bytes.len(), |i| position + i);
+        let mut line = &bytes[position..end];
+        position = (end + 1).min(bytes.len());
+        if let [rest @ .., b'\r'] = line {
+            line = rest;
+        }
+        if !line.is_empty() {
+            return Some((line, position));
+        }
+    }
+    None
+}
+
+/// The lines that aren't empty.
+fn lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        let (line, next) = next_line(bytes, position)?;
+        position = next;
+        Some(line)
+    })
+}
+
+/// The text of the field at `range` without surrounding whitespace, or `None` if it is empty.
+fn field(line: &[u8], (start, end): (usize, usize)) -> PolarsResult<Option<&str>> {
+    let bytes = &line[start.min(line.len())..end.min(line.len())];
+    let text = std::str::from_utf8(bytes).map_err(|_| {
+        polars_err!(
+            ComputeError: "invalid utf-8 in the fixed-width field at byte {} of the line `{}`",
+            start, String::from_utf8_lossy(line)
+        )
+    })?;
+    let text = text.trim();
+    Ok((!text.is_empty()).then_some(text))
+}
+
+fn projection(columns: Option<&[PlSmallStr]>, schema: &Schema) -> PolarsResult<Vec<usize>> {
+    match columns {
+        Some(columns) => {
+            let mut projection = columns
+                .iter()
+                .map(|name| schema.try_index_of(name))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            projection.sort_unstable();
+            projection.dedup();
+            Ok(projection)
+        },
+        None => Ok((0..schema.len()).collect()),
+    }
+}
+
+fn parse_lines(
+    lines: &[&[u8]],
+    offsets: &[(usize, usize)],
+    schema: &Schema,
+    projection: &[usize],
+) -> PolarsResult<DataFrame> {
+    let columns = POOL.install(|| {
+        projection
+            .par_iter()
+            .map(|&i| {
+                let (name, dtype) = schema.get_at_index(i).unwrap();
+                parse_column(lines, offsets[i], name, dtype)
+            })
+            .collect::<PolarsResult<Vec<_>>>()
+    })?;
+    DataFrame::new(columns)
+}
+
+fn parse_column(
+    lines: &[&[u8]],
+    range: (usize, usize),
+    name: &PlSmallStr,
+    dtype: &DataType,
+) -> PolarsResult<Series> {
+    let values = lines
+        .iter()
+        .map(|line| field(line, range))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    match dtype {
+        DataType::String => Ok(Series::new(name.clone(), values)),
+        DataType::Boolean => {
+            let values = values
+                .into_iter()
+                .map(|value| {
+                    value
+                        .map(|text| match text {
+                            _ if text.eq_ignore_ascii_case("true") => Ok(true),
+                            _ if text.eq_ignore_ascii_case("false") => Ok(false),
+                            _ => Err(polars_err!(
+                                ComputeError: "could not parse `{}` as dtype `bool` at column '{}'",
+                                text, name
+                            )),
+                        })
+                        .transpose()
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            Ok(Series::new(name.clone(), values))
+        },
+        dtype => Series::new(name.clone(), values).strict_cast(dtype),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &[u8] = b"\
+id   name      amount active
+1    alice      10.50 true
+2    bob       100.00 false
+
+3              -1.25
+";
+
+    fn read(options: FwfReadOptions) -> PolarsResult<DataFrame> {
+        FwfReader::new(Cursor::new(FILE))
+            .with_options(options)
+            .finish()
+    }
+
+    #[test]
+    fn test_infer_offsets() {
+        let offsets = infer_offsets(lines(FILE));
+        assert_eq!(offsets, [(0, 2), (5, 10), (15, 21), (22, usize::MAX)]);
+    }
+
+    #[test]
+    fn test_read_fwf() -> PolarsResult<()> {
+        let df = read(FwfReadOptions::default())?;
+        let expected = DataFrame::new(vec![
+            Series::new("id".into(), [1i64, 2, 3]),
+            Series::new("name".into(), [Some("alice"), Some("bob"), None]),
+            Series::new("amount".into(), [10.5, 100.0, -1.25]),
+            Series::new("active".into(), [Some(true), Some(false), None]),
+        ])?;
+        assert!(df.equals_missing(&expected));
+
+        let df = read(
+            FwfReadOptions::default()
+                .with_offsets(vec![(0, 5), (5, 15)])
+                .with_has_header(false)
+                .with_skip_rows(1)
+                .with_column_names(Some(["key".into(), "value".into()].into())),
+        )?;
+        let expected = DataFrame::new(vec![
+            Series::new("key".into(), [1i64, 2, 3]),
+            Series::new("value".into(), [Some("alice"), Some("bob"), None]),
+        ])?;
+        assert!(df.equals_missing(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_batched_fwf() -> PolarsResult<()> {
+        let options = FwfReadOptions::default().with_schema_overwrite(Some(Arc::new(
+            Schema::from_iter([(PlSmallStr::from_static("id"), DataType::String)]),
+        )));
+        let mut reader = FwfReader::new(Cursor::new(MemSlice::from_static(FILE)))
+            .with_options(options)
+            .with_columns(Some(["amount".into(), "id".into()].into()))
+            .with_row_index(Some(RowIndex {
+                name: "index".into(),
+                offset: 0,
+            }))
+            .batched(2)?;
+
+        let mut heights = vec![];
+        let mut dfs = vec![];
+        while let Some(df) = reader.next_batch()? {
+            heights.push(df.height());
+            dfs.push(df);
+        }
+        assert_eq!(heights, [2, 1]);
+        let df = polars_core::utils::accumulate_dataframes_vertical(dfs)?;
+        assert_eq!(df.get_column_names(), ["index", "id", "amount"]);
+        assert_eq!(df.column("id")?.dtype(), &DataType::String);
+        assert_eq!(df.column("index")?.idx()?.cont_slice()?, [0, 1, 2]);
+
+        assert_eq!(
+            count_rows(Cursor::new(FILE), &FwfReadOptions::default())?,
+            3
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_fwf_invalid() {
+        assert!(read(FwfReadOptions::default().with_offsets(vec![(5, 5)])).is_err());
+        assert!(
+            read(FwfReadOptions::default().with_column_names(Some(["id".into()].into())))
+                .is_err_and(|e| matches!(e, PolarsError::ShapeMismatch(_)))
+        );
+        // The names can't be parsed as integers.
+        assert!(read(
+            FwfReadOptions::default().with_schema_overwrite(Some(Arc::new(Schema::from_iter([(
+                PlSmallStr::from_static("name"),
+                DataType::Int64
+            )]))))
+        )
+        .is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use polars_core::frame::DataFrame;
+use polars_core::prelude::DataType;
 use polars_core::series::Series;
 
+use crate::utils::URL_ENCODE_CHAR_SET;
+
 /// Materializes hive partitions.
 /// We have a special num_rows arg, as df can be empty when a projection contains
 /// only hive partition columns.
@@ -72,3 +75,30 @@ pub(crate) fn materialize_hive_partitions<D>(
         *unsafe { df.get_columns_mut() } = out_columns;
     }
 }
+
+/// Get the hive path of the partition of `df`, e.g. `year=2024/month=1`. The partition is
+/// determined by the values of the first row of the columns at `partition_by_col_idx`.
+pub fn get_hive_path_part(df: &DataFrame, partition_by_col_idx: &[usize]) -> String {
+    let cols = df.get_columns();
+
+    partition_by_col_idx
+        .iter()
+        .map(|&i| {
+            let s = &cols[i].slice(0, 1).cast(&DataType::String).unwrap();
+
+            format!(
+                "{}={}",
+                s.name(),
+                percent_encoding::percent_encode(
+                    s.str()
+                        .unwrap()
+                        .get(0)
+                        .unwrap_or("__HIVE_DEFAULT_PARTITION__")
+                        .as_bytes(),
+                    URL_ENCODE_CHAR_SET
+                )
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
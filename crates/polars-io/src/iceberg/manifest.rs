@@ -0,0 +1,236 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+
+use super::metadata::{resolve_path, PartitionField};
+use crate::avro::AvroReader;
+use crate::SerReader;
+
+/// A data file of a snapshot of an Iceberg table.
+#[derive(Clone, Debug)]
+pub struct IcebergDataFile {
+    /// The path of the Parquet file.
+    pub path: PathBuf,
+    pub record_count: i64,
+    /// The lower bounds of the values of the columns of the schema of the snapshot, in the
+    /// physical representation of their dtypes. A bound is null if it is unknown.
+    pub lower_bounds: Vec<AnyValue<'static>>,
+    pub upper_bounds: Vec<AnyValue<'static>>,
+}
+
+/// An entry of the manifest list of a snapshot.
+pub(super) struct Manifest {
+    pub(super) path: PathBuf,
+    pub(super) partition_spec_id: i32,
+    /// 0 for a manifest of data files, 1 for a manifest of delete files.
+    pub(super) content: i32,
+}
+
+fn read_avro(path: &Path) -> PolarsResult<DataFrame> {
+    let file = polars_utils::open_file(path)?;
+    AvroReader::new(file).finish()
+}
+
+pub(super) fn read_manifest_list(path: &Path) -> PolarsResult<Vec<Manifest>> {
+    let df = read_avro(path)?;
+    let paths = df.column("manifest_path")?.str()?;
+    let spec_ids = df.column("partition_spec_id")?.cast(&DataType::Int32)?;
+    // Manifest lists of format version 1 only list manifests of data files.
+    let content = match df.column("content") {
+        Ok(content) => Some(content.cast(&DataType::Int32)?),
+        Err(_) => None,
+    };
+    let content = content.as_ref().map(|c| c.i32()).transpose()?;
+
+    paths
+        .iter()
+        .zip(spec_ids.i32()?.iter())
+        .enumerate()
+        .map(|(i, (path, spec_id))| {
+            let path = path.ok_or_else(
+                || polars_err!(ComputeError: "out-of-spec Iceberg table: manifest without path"),
+            )?;
+            Ok(Manifest {
+                path: resolve_path(path)?,
+                partition_spec_id: spec_id.unwrap_or(0),
+                content: content.and_then(|c| c.get(i)).unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// Read the live data files of a manifest, i.e. those that are not deleted, into `files`.
+pub(super) fn read_manifest(
+    manifest: &Manifest,
+    spec: &[PartitionField],
+    fields: &[(i32, PlSmallStr, DataType)],
+    files: &mut Vec<IcebergDataFile>,
+) -> PolarsResult<()> {
+    let df = read_avro(&manifest.path)?;
+    let status = df.column("status")?.cast(&DataType::Int32)?;
+    let status = status.i32()?;
+    let data_file = df.column("data_file")?.struct_()?;
+    let content = match data_file.field_by_name("content") {
+        Ok(content) => Some(content.cast(&DataType::Int32)?),
+        Err(_) => None,
+    };
+    let content = content.as_ref().map(|c| c.i32()).transpose()?;
+
+    let is_live = |i: usize| status.get(i) != Some(2);
+    let has_deletes = (0..df.height()).any(|i| {
+        is_live(i) && (manifest.content != 0 || content.and_then(|c| c.get(i)).unwrap_or(0) != 0)
+    });
+    if has_deletes {
+        polars_bail!(nyi = "reading Iceberg tables with delete files");
+    }
+
+    let paths = data_file.field_by_name("file_path")?;
+    let paths = paths.str()?;
+    let formats = data_file.field_by_name("file_format")?;
+    let formats = formats.str()?;
+    let counts = data_file
+        .field_by_name("record_count")?
+        .cast(&DataType::Int64)?;
+    let counts = counts.i64()?;
+    let lower_bounds = data_file.field_by_name("lower_bounds").ok();
+    let upper_bounds = data_file.field_by_name("upper_bounds").ok();
+
+    // The values of the partitions of the identity transform are the values of their column.
+    let mut identity = vec![];
+    if let Ok(partition) = data_file.field_by_name("partition") {
+        let partition = partition.struct_()?;
+        for field in spec.iter().filter(|field| field.transform == "identity") {
+            let Some(i) = fields.iter().position(|(id, _, _)| *id == field.source_id) else {
+                continue;
+            };
+            let values = partition
+                .field_by_name(&field.name)?
+                .cast(&fields[i].2)?
+                .to_physical_repr()
+                .into_owned();
+            identity.push((i, values));
+        }
+    }
+
+    for i in (0..df.height()).filter(|&i| is_live(i)) {
+        let format = formats.get(i).unwrap_or("PARQUET");
+        if !format.eq_ignore_ascii_case("parquet") {
+            polars_bail!(nyi = "reading Iceberg data files in the {} format", format);
+        }
+        let path = paths.get(i).ok_or_else(
+            || polars_err!(ComputeError: "out-of-spec Iceberg table: data file without path"),
+        )?;
+
+        let mut lower = bounds(lower_bounds.as_ref(), i, fields, false)?;
+        let mut upper = bounds(upper_bounds.as_ref(), i, fields, true)?;
+        for (column, values) in &identity {
+            let value = values.get(i)?.into_static()?;
+            if !value.is_null() {
+                lower[*column] = value.clone();
+                upper[*column] = value;
+            }
+        }
+
+        files.push(IcebergDataFile {
+            path: resolve_path(path)?,
+            record_count: counts.get(i).unwrap_or(0),
+            lower_bounds: lower,
+            upper_bounds: upper,
+        });
+    }
+    Ok(())
+}
+
+/// Read the bounds of the values of the columns in the data file `i`, which are a map from the
+/// field ids of the columns to their serialized values.
+fn bounds(
+    bounds: Option<&Series>,
+    i: usize,
+    fields: &[(i32, PlSmallStr, DataType)],
+    upper: bool,
+) -> PolarsResult<Vec<AnyValue<'static>>> {
+    let mut out = vec![AnyValue::Null; fields.len()];
+    let Some(entries) = bounds
+        .map(|b| b.list())
+        .transpose()?
+        .and_then(|b| b.get_as_series(i))
+    else {
+        return Ok(out);
+    };
+    let entries = entries.struct_()?;
+    let keys = entries.field_by_name("key")?.cast(&DataType::Int32)?;
+    let values = entries.field_by_name("value")?;
+    for (key, value) in keys.i32()?.iter().zip(values.binary()?.iter()) {
+        let (Some(key), Some(value)) = (key, value) else {
+            continue;
+        };
+        if let Some(column) = fields.iter().position(|(id, _, _)| *id == key) {
+            out[column] = decode_bound(value, &fields[column].2, upper);
+        }
+    }
+    Ok(out)
+}
+
+/// Decode a bound in the binary single-value serialization of Iceberg, to the physical
+/// representation of `dtype`. Columns of which the type has been promoted may have bounds of
+/// their previous type.
+fn decode_bound(bytes: &[u8], dtype: &DataType, upper: bool) -> AnyValue<'static> {
+    let int = || match bytes.len() {
+        4 => Some(i32::from_le_bytes(bytes.try_into().unwrap()) as i64),
+        8 => Some(i64::from_le_bytes(bytes.try_into().unwrap())),
+        _ => None,
+    };
+    let float = || match bytes.len() {
+        4 => Some(f32::from_le_bytes(bytes.try_into().unwrap()) as f64),
+        8 => Some(f64::from_le_bytes(bytes.try_into().unwrap())),
+        _ => None,
+    };
+    let value = match dtype {
+        DataType::Boolean => bytes.first().map(|b| AnyValue::Boolean(*b != 0)),
+        DataType::Int32 | DataType::Date => int().map(|v| AnyValue::Int32(v as i32)),
+        DataType::Int64 | DataType::Datetime(_, _) => int().map(AnyValue::Int64),
+        // NaN values are not included in the bounds, but are larger than all other values in
+        // Polars.
+        DataType::Float32 if !upper => float().map(|v| AnyValue::Float32(v as f32)),
+        DataType::Float64 if !upper => float().map(AnyValue::Float64),
+        DataType::String => std::str::from_utf8(bytes)
+            .ok()
+            .map(|s| AnyValue::StringOwned(s.into())),
+        _ => None,
+    };
+    value.unwrap_or(AnyValue::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_bound() {
+        assert_eq!(
+            decode_bound(&7i32.to_le_bytes(), &DataType::Int32, false),
+            AnyValue::Int32(7)
+        );
+        // A column that is promoted from int to long.
+        assert_eq!(
+            decode_bound(&7i32.to_le_bytes(), &DataType::Int64, false),
+            AnyValue::Int64(7)
+        );
+        assert_eq!(
+            decode_bound(&1.5f64.to_le_bytes(), &DataType::Float64, false),
+            AnyValue::Float64(1.5)
+        );
+        assert_eq!(
+            decode_bound(&1.5f64.to_le_bytes(), &DataType::Float64, true),
+            AnyValue::Null
+        );
+        assert_eq!(
+            decode_bound(b"abc", &DataType::String, true),
+            AnyValue::StringOwned("abc".into())
+        );
+        assert_eq!(
+            decode_bound(&[1, 2], &DataType::Int64, false),
+            AnyValue::Null
+        );
+    }
+}
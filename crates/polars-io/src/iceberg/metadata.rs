@@ -0,0 +1,415 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use serde_json::Value;
+
+use super::manifest::{read_manifest, read_manifest_list, IcebergDataFile, Manifest};
+
+/// A field of a partition spec, of which the values are computed from a column of the table.
+pub(super) struct PartitionField {
+    pub(super) name: String,
+    pub(super) source_id: i32,
+    pub(super) transform: String,
+}
+
+/// The data files and the schema of a snapshot of an Iceberg table.
+#[derive(Clone, Debug)]
+pub struct IcebergSnapshot {
+    /// `None` if the table has no snapshots, in which case it has no data files.
+    pub snapshot_id: Option<i64>,
+    /// The schema with which the snapshot was written.
+    pub schema: Schema,
+    /// The field ids of the columns of the schema, which identify the columns in the data files
+    /// when they have been renamed or reordered since.
+    pub field_ids: Vec<i32>,
+    /// The data files, ordered by their path.
+    pub files: Vec<IcebergDataFile>,
+}
+
+impl IcebergSnapshot {
+    /// Read the metadata of the table at `path`, which is the directory of the table or one of
+    /// its metadata files, and the manifests of the snapshot `snapshot_id` or of the current
+    /// snapshot.
+    pub fn try_new(path: &Path, snapshot_id: Option<i64>) -> PolarsResult<Self> {
+        let metadata_path = metadata_file(path)?;
+        let text = std::fs::read_to_string(&metadata_path).map_err(|e| {
+            polars_err!(
+                ComputeError: "could not read the Iceberg table metadata {}: {}",
+                metadata_path.display(), e
+            )
+        })?;
+        let metadata: Value = serde_json::from_str(&text).map_err(to_compute_err)?;
+        let format_version = metadata
+            .get("format-version")
+            .and_then(Value::as_i64)
+            .unwrap_or(1);
+        if format_version > 2 {
+            polars_bail!(
+                nyi = "reading Iceberg tables of format version {}",
+                format_version
+            );
+        }
+
+        let snapshot_id = snapshot_id.or_else(|| {
+            metadata
+                .get("current-snapshot-id")
+                .and_then(Value::as_i64)
+                .filter(|&id| id != -1)
+        });
+        let snapshot = snapshot_id
+            .map(|id| {
+                metadata
+                    .get("snapshots")
+                    .and_then(Value::as_array)
+                    .and_then(|snapshots| {
+                        snapshots
+                            .iter()
+                            .find(|s| s.get("snapshot-id").and_then(Value::as_i64) == Some(id))
+                    })
+                    .ok_or_else(|| {
+                        polars_err!(ComputeError: "snapshot {} of the Iceberg table doesn't exist", id)
+                    })
+            })
+            .transpose()?;
+
+        // A snapshot is read with the schema with which it was written.
+        let schema_id = snapshot
+            .and_then(|snapshot| snapshot.get("schema-id"))
+            .or_else(|| metadata.get("current-schema-id"))
+            .and_then(Value::as_i64);
+        let fields = parse_fields(find_schema(&metadata, schema_id)?)?;
+        let specs = partition_specs(&metadata)?;
+
+        let mut files = vec![];
+        if let Some(snapshot) = snapshot {
+            let manifests = match snapshot.get("manifest-list").and_then(Value::as_str) {
+                Some(list) => read_manifest_list(&resolve_path(list)?)?,
+                // Tables of format version 1 may list the manifests in the snapshot.
+                None => {
+                    let spec_id = metadata
+                        .get("default-spec-id")
+                        .and_then(Value::as_i64)
+                        .unwrap_or(0) as i32;
+                    snapshot
+                        .get("manifests")
+                        .and_then(Value::as_array)
+                        .map(|manifests| manifests.iter().filter_map(Value::as_str))
+                        .into_iter()
+                        .flatten()
+                        .map(|path| {
+                            Ok(Manifest {
+                                path: resolve_path(path)?,
+                                partition_spec_id: spec_id,
+                                content: 0,
+                            })
+                        })
+                        .collect::<PolarsResult<Vec<_>>>()?
+                },
+            };
+            for manifest in manifests {
+                let spec = specs.get(&manifest.partition_spec_id).ok_or_else(|| {
+                    polars_err!(
+                        ComputeError: "out-of-spec Iceberg table: unknown partition spec {}",
+                        manifest.partition_spec_id
+                    )
+                })?;
+                read_manifest(&manifest, spec, &fields, &mut files)?;
+            }
+        }
+        files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Self {
+            snapshot_id,
+            field_ids: fields.iter().map(|(id, _, _)| *id).collect(),
+            schema: fields
+                .into_iter()
+                .map(|(_, name, dtype)| (name, dtype))
+                .collect(),
+            files,
+        })
+    }
+
+    /// The lower and upper bounds of the values of the columns in every file, for the columns of
+    /// which any file has bounds. A bound is null if it is unknown.
+    pub fn column_bounds(&self) -> PolarsResult<Vec<(Series, Series)>> {
+        let mut out = vec![];
+        for (i, (name, dtype)) in self.schema.iter().enumerate() {
+            if self
+                .files
+                .iter()
+                .all(|file| file.lower_bounds[i].is_null() && file.upper_bounds[i].is_null())
+            {
+                continue;
+            }
+            let physical = dtype.to_physical();
+            let series = |values: Vec<AnyValue>| {
+                Series::from_any_values_and_dtype(name.clone(), &values, &physical, false)?
+                    .cast(dtype)
+            };
+            let lower = series(
+                self.files
+                    .iter()
+                    .map(|f| f.lower_bounds[i].clone())
+                    .collect(),
+            )?;
+            let upper = series(
+                self.files
+                    .iter()
+                    .map(|f| f.upper_bounds[i].clone())
+                    .collect(),
+            )?;
+            out.push((lower, upper));
+        }
+        Ok(out)
+    }
+}
+
+/// The path of the current metadata file of the table at `path`.
+fn metadata_file(path: &Path) -> PolarsResult<PathBuf> {
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    let dir = path.join("metadata");
+    if let Ok(hint) = std::fs::read_to_string(dir.join("version-hint.text")) {
+        let file = dir.join(format!("v{}.metadata.json", hint.trim()));
+        if file.is_file() {
+            return Ok(file);
+        }
+    }
+
+    // Otherwise the metadata file with the highest version, of which the name is
+    // `v<version>.metadata.json` or `<version>-<uuid>.metadata.json`.
+    let no_table = |e: &dyn std::fmt::Display| {
+        polars_err!(
+            ComputeError: "no Iceberg table at {}: {}", path.display(), e
+        )
+    };
+    let entries = std::fs::read_dir(&dir).map_err(|e| no_table(&e))?;
+    let mut latest = None;
+    for entry in entries {
+        let file = entry?.path();
+        let Some(stem) = file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".metadata.json"))
+        else {
+            continue;
+        };
+        let stem = stem.strip_prefix('v').unwrap_or(stem);
+        let Some(version) = stem.split('-').next().and_then(|v| v.parse::<u64>().ok()) else {
+            continue;
+        };
+        if latest
+            .as_ref()
+            .map_or(true, |(latest, _)| version > *latest)
+        {
+            latest = Some((version, file));
+        }
+    }
+    latest
+        .map(|(_, file)| file)
+        .ok_or_else(|| no_table(&"the metadata directory has no metadata files"))
+}
+
+/// Resolve the location of a file of the table, which is an absolute path or URI.
+pub(super) fn resolve_path(location: &str) -> PolarsResult<PathBuf> {
+    if let Some(path) = location.strip_prefix("file:") {
+        return Ok(PathBuf::from(path.strip_prefix("//").unwrap_or(path)));
+    }
+    if location.contains("://") {
+        polars_bail!(nyi = "reading Iceberg tables with files in cloud storage");
+    }
+    Ok(PathBuf::from(location))
+}
+
+fn find_schema(metadata: &Value, schema_id: Option<i64>) -> PolarsResult<&Value> {
+    let schemas = metadata.get("schemas").and_then(Value::as_array);
+    let schema = match (schemas, schema_id) {
+        (Some(schemas), Some(id)) => schemas
+            .iter()
+            .find(|schema| schema.get("schema-id").and_then(Value::as_i64) == Some(id)),
+        _ => metadata.get("schema"),
+    };
+    schema.ok_or_else(
+        || polars_err!(ComputeError: "out-of-spec Iceberg table: the metadata has no schema"),
+    )
+}
+
+fn partition_specs(metadata: &Value) -> PolarsResult<PlHashMap<i32, Vec<PartitionField>>> {
+    let mut specs = PlHashMap::new();
+    if let Some(list) = metadata.get("partition-specs").and_then(Value::as_array) {
+        for spec in list {
+            let id = spec.get("spec-id").and_then(Value::as_i64).unwrap_or(0) as i32;
+            specs.insert(id, parse_partition_fields(spec.get("fields"))?);
+        }
+    } else if let Some(fields) = metadata.get("partition-spec") {
+        specs.insert(0, parse_partition_fields(Some(fields))?);
+    }
+    Ok(specs)
+}
+
+fn parse_partition_fields(value: Option<&Value>) -> PolarsResult<Vec<PartitionField>> {
+    let invalid = || polars_err!(ComputeError: "out-of-spec Iceberg table: invalid partition spec");
+    value
+        .and_then(Value::as_array)
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|field| {
+            Ok(PartitionField {
+                name: json_str(field, "name")?.to_string(),
+                source_id: field
+                    .get("source-id")
+                    .and_then(Value::as_i64)
+                    .ok_or_else(invalid)? as i32,
+                transform: json_str(field, "transform")?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn json_str<'a>(value: &'a Value, key: &str) -> PolarsResult<&'a str> {
+    value.get(key).and_then(Value::as_str).ok_or_else(
+        || polars_err!(ComputeError: "out-of-spec Iceberg table: metadata without '{}'", key),
+    )
+}
+
+/// Parse the fields of a struct type, with their ids.
+fn parse_fields(value: &Value) -> PolarsResult<Vec<(i32, PlSmallStr, DataType)>> {
+    let invalid = || polars_err!(ComputeError: "out-of-spec Iceberg table: invalid type {}", value);
+    value
+        .get("fields")
+        .and_then(Value::as_array)
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|field| {
+            let id = field
+                .get("id")
+                .and_then(Value::as_i64)
+                .ok_or_else(invalid)? as i32;
+            let name = PlSmallStr::from_str(json_str(field, "name")?);
+            let dtype = parse_type(field.get("type").ok_or_else(invalid)?)?;
+            Ok((id, name, dtype))
+        })
+        .collect()
+}
+
+fn parse_type(value: &Value) -> PolarsResult<DataType> {
+    let invalid = || polars_err!(ComputeError: "out-of-spec Iceberg table: invalid type {}", value);
+    if let Some(name) = value.as_str() {
+        let utc = || Some(PlSmallStr::from_static("UTC"));
+        return Ok(match name {
+            "boolean" => DataType::Boolean,
+            "int" => DataType::Int32,
+            "long" => DataType::Int64,
+            "float" => DataType::Float32,
+            "double" => DataType::Float64,
+            "date" => DataType::Date,
+            "time" => DataType::Time,
+            "timestamp" => DataType::Datetime(TimeUnit::Microseconds, None),
+            "timestamptz" => DataType::Datetime(TimeUnit::Microseconds, utc()),
+            "timestamp_ns" => DataType::Datetime(TimeUnit::Nanoseconds, None),
+            "timestamptz_ns" => DataType::Datetime(TimeUnit::Nanoseconds, utc()),
+            "string" => DataType::String,
+            "uuid" | "binary" => DataType::Binary,
+            name if name.starts_with("fixed[") => DataType::Binary,
+            name if name.starts_with("decimal(") => {
+                #[cfg(feature = "dtype-decimal")]
+                {
+                    let (precision, scale) = name
+                        .strip_prefix("decimal(")
+                        .and_then(|name| name.strip_suffix(')'))
+                        .and_then(|name| name.split_once(','))
+                        .ok_or_else(invalid)?;
+                    DataType::Decimal(
+                        Some(precision.trim().parse().map_err(|_| invalid())?),
+                        Some(scale.trim().parse().map_err(|_| invalid())?),
+                    )
+                }
+                #[cfg(not(feature = "dtype-decimal"))]
+                {
+                    polars_bail!(nyi = "reading Iceberg tables with decimal columns without the 'dtype-decimal' feature")
+                }
+            },
+            _ => polars_bail!(
+                nyi = "reading Iceberg tables with columns of type '{}'",
+                name
+            ),
+        });
+    }
+
+    match value.get("type").and_then(Value::as_str) {
+        Some("struct") => Ok(DataType::Struct(
+            parse_fields(value)?
+                .into_iter()
+                .map(|(_, name, dtype)| Field::new(name, dtype))
+                .collect(),
+        )),
+        Some("list") => Ok(DataType::List(Box::new(parse_type(
+            value.get("element").ok_or_else(invalid)?,
+        )?))),
+        Some("map") => {
+            let key = parse_type(value.get("key").ok_or_else(invalid)?)?;
+            let value = parse_type(value.get("value").ok_or_else(invalid)?)?;
+            Ok(DataType::List(Box::new(DataType::Struct(vec![
+                Field::new(PlSmallStr::from_static("key"), key),
+                Field::new(PlSmallStr::from_static("value"), value),
+            ]))))
+        },
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fields() {
+        let schema: Value = serde_json::from_str(
+            r#"{
+                "type": "struct",
+                "schema-id": 1,
+                "fields": [
+                    {"id": 1, "name": "id", "required": true, "type": "long"},
+                    {"id": 3, "name": "ts", "required": false, "type": "timestamptz"},
+                    {"id": 2, "name": "tags", "required": false, "type": {
+                        "type": "map", "key-id": 4, "key": "string",
+                        "value-id": 5, "value": "int", "value-required": false
+                    }}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let fields = parse_fields(&schema).unwrap();
+        assert_eq!(
+            fields.iter().map(|(id, _, _)| *id).collect::<Vec<_>>(),
+            [1, 3, 2]
+        );
+        assert_eq!(
+            fields[1].2,
+            DataType::Datetime(TimeUnit::Microseconds, Some("UTC".into()))
+        );
+        assert_eq!(
+            fields[2].2,
+            DataType::List(Box::new(DataType::Struct(vec![
+                Field::new("key".into(), DataType::String),
+                Field::new("value".into(), DataType::Int32),
+            ])))
+        );
+        assert!(parse_type(&Value::from("variant")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        assert_eq!(
+            resolve_path("file:///tmp/table/data/0.parquet").unwrap(),
+            PathBuf::from("/tmp/table/data/0.parquet")
+        );
+        assert_eq!(
+            resolve_path("/tmp/table/data/0.parquet").unwrap(),
+            PathBuf::from("/tmp/table/data/0.parquet")
+        );
+        assert!(resolve_path("s3://bucket/table/data/0.parquet").is_err());
+    }
+}
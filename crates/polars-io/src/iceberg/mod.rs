@@ -0,0 +1,9 @@
+//! Read the metadata of [Apache Iceberg] tables, to resolve the Parquet data files of a
+//! snapshot of a table and the bounds of the values in these files.
+//!
+//! [Apache Iceberg]: https://iceberg.apache.org
+mod manifest;
+mod metadata;
+
+pub use manifest::IcebergDataFile;
+pub use metadata::IcebergSnapshot;
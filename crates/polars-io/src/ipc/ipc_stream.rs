@@ -42,7 +42,7 @@ use arrow::io::ipc::{read, write};
 use polars_core::prelude::*;
 
 use crate::prelude::*;
-use crate::shared::{finish_reader, ArrowReader};
+use crate::shared::{finish_reader, schema_to_arrow_checked, ArrowReader};
 
 /// Read Arrows Stream IPC format into a DataFrame
 ///
@@ -120,6 +120,68 @@ impl<R: Read> IpcStreamReader<R> {
     }
 }
 
+impl<R: Read> IpcStreamReader<R> {
+    /// Read the stream one record batch at a time, e.g. to process the batches that are sent
+    /// over a socket as they arrive.
+    pub fn batched(mut self) -> PolarsResult<BatchedIpcStreamReader<R>> {
+        let metadata = self.metadata()?;
+        if let Some(columns) = &self.columns {
+            self.projection = Some(columns_to_projection(columns, &metadata.schema)?);
+        }
+        let schema = if let Some(projection) = &self.projection {
+            apply_projection(&metadata.schema, projection)
+        } else {
+            metadata.schema.clone()
+        };
+
+        Ok(BatchedIpcStreamReader {
+            reader: read::StreamReader::new(self.reader, metadata, self.projection),
+            schema,
+            n_rows: self.n_rows,
+            row_index: self.row_index,
+            n_rows_read: 0,
+        })
+    }
+}
+
+/// Reads an IPC stream one record batch at a time, see [`IpcStreamReader::batched`].
+pub struct BatchedIpcStreamReader<R: Read> {
+    reader: read::StreamReader<R>,
+    schema: ArrowSchema,
+    n_rows: Option<usize>,
+    row_index: Option<RowIndex>,
+    n_rows_read: usize,
+}
+
+impl<R: Read> BatchedIpcStreamReader<R> {
+    /// Read the next record batch. Returns `None` at the end of the stream or once the row
+    /// limit is reached.
+    pub fn next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        let remaining = self
+            .n_rows
+            .map_or(usize::MAX, |n_rows| n_rows - self.n_rows_read);
+        if remaining == 0 {
+            return Ok(None);
+        }
+        let Some(batch) = self.reader.next_record_batch()? else {
+            return Ok(None);
+        };
+
+        let mut df = DataFrame::try_from((batch, &self.schema))?;
+        if df.height() > remaining {
+            df = df.head(Some(remaining));
+        }
+        if let Some(rc) = &self.row_index {
+            df.with_row_index_mut(
+                rc.name.clone(),
+                Some(rc.offset + self.n_rows_read as IdxSize),
+            );
+        }
+        self.n_rows_read += df.height();
+        Ok(Some(df))
+    }
+}
+
 impl<R> ArrowReader for read::StreamReader<R>
 where
     R: Read,
@@ -227,6 +289,50 @@ impl<W> IpcStreamWriter<W> {
     }
 }
 
+impl<W: Write> IpcStreamWriter<W> {
+    /// Write the stream one [`DataFrame`] at a time, e.g. as the batches of a streaming query
+    /// are produced.
+    pub fn batched(self, schema: &Schema) -> PolarsResult<BatchedIpcStreamWriter<W>> {
+        let schema = schema_to_arrow_checked(schema, self.compat_level, "ipc")?;
+        let mut writer = write::StreamWriter::new(
+            self.writer,
+            WriteOptions {
+                compression: self.compression.map(|c| c.into()),
+            },
+        );
+        writer.start(&schema, None)?;
+
+        Ok(BatchedIpcStreamWriter {
+            writer,
+            compat_level: self.compat_level,
+        })
+    }
+}
+
+/// Writes an IPC stream one [`DataFrame`] at a time, see [`IpcStreamWriter::batched`].
+pub struct BatchedIpcStreamWriter<W: Write> {
+    writer: write::StreamWriter<W>,
+    compat_level: CompatLevel,
+}
+
+impl<W: Write> BatchedIpcStreamWriter<W> {
+    /// Write a batch to the stream.
+    ///
+    /// # Panics
+    /// The caller must ensure the chunks in the given [`DataFrame`] are aligned.
+    pub fn write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        for batch in df.iter_chunks(self.compat_level, true) {
+            self.writer.write(&batch, None)?
+        }
+        Ok(())
+    }
+
+    /// Write the end-of-stream marker.
+    pub fn finish(&mut self) -> PolarsResult<()> {
+        self.writer.finish()
+    }
+}
+
 impl<W> SerWriter<W> for IpcStreamWriter<W>
 where
     W: Write,
@@ -64,6 +64,7 @@
 //!
 pub(crate) mod infer;
 
+use std::borrow::Cow;
 use std::io::Write;
 use std::num::NonZeroUsize;
 use std::ops::Deref;
@@ -80,11 +81,19 @@ use simd_json::BorrowedValue;
 use crate::mmap::{MmapBytesReader, ReaderBytes};
 use crate::prelude::*;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct JsonWriterOptions {
     /// maintain the order the data was processed
     pub maintain_order: bool,
+    /// Used for [`DataType::Date`](polars_core::datatypes::DataType::Date).
+    pub date_format: Option<String>,
+    /// Used for [`DataType::Time`](polars_core::datatypes::DataType::Time).
+    pub time_format: Option<String>,
+    /// Used for [`DataType::Datetime`](polars_core::datatypes::DataType::Datetime).
+    pub datetime_format: Option<String>,
+    /// Null value representation, written as a JSON string instead of `null`.
+    pub null_value: Option<String>,
 }
 
 /// The format to use to write the DataFrame to JSON: `Json` (a JSON array)
@@ -175,6 +184,10 @@ where
 
 pub struct BatchedWriter<W: Write> {
     writer: W,
+    date_format: Option<String>,
+    time_format: Option<String>,
+    datetime_format: Option<String>,
+    null_value: Option<String>,
 }
 
 impl<W> BatchedWriter<W>
@@ -182,13 +195,88 @@ where
     W: Write,
 {
     pub fn new(writer: W) -> Self {
-        BatchedWriter { writer }
+        BatchedWriter {
+            writer,
+            date_format: None,
+            time_format: None,
+            datetime_format: None,
+            null_value: None,
+        }
+    }
+
+    /// Set the format of the date columns, as a
+    /// [chrono strftime](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+    /// format string. Dates are written as `yyyy-mm-dd` by default.
+    pub fn with_date_format(mut self, format: Option<String>) -> Self {
+        self.date_format = format;
+        self
+    }
+
+    /// Set the format of the time columns. Times are written as `HH:MM:SS.f` by default.
+    pub fn with_time_format(mut self, format: Option<String>) -> Self {
+        self.time_format = format;
+        self
+    }
+
+    /// Set the format of the datetime columns. Datetimes are written as
+    /// `yyyy-mm-dd HH:MM:SS.f` by default.
+    pub fn with_datetime_format(mut self, format: Option<String>) -> Self {
+        self.datetime_format = format;
+        self
+    }
+
+    /// Write the null values of the columns as this string instead of `null`. The null values
+    /// of the fields of nested columns are written as `null`.
+    pub fn with_null_value(mut self, null_value: Option<String>) -> Self {
+        self.null_value = null_value;
+        self
+    }
+
+    /// Format the temporal columns with the formats that are set.
+    fn format_temporal_columns<'a>(&self, df: &'a DataFrame) -> PolarsResult<Cow<'a, DataFrame>> {
+        let format = |dtype: &DataType| match dtype {
+            #[cfg(feature = "dtype-date")]
+            DataType::Date => self.date_format.as_deref(),
+            // Times can only be written formatted.
+            #[cfg(feature = "dtype-time")]
+            DataType::Time => Some(self.time_format.as_deref().unwrap_or("%H:%M:%S%.f")),
+            #[cfg(feature = "dtype-datetime")]
+            DataType::Datetime(_, _) => self.datetime_format.as_deref(),
+            _ => None,
+        };
+        if df.get_columns().iter().all(|s| format(s.dtype()).is_none()) {
+            return Ok(Cow::Borrowed(df));
+        }
+
+        let columns = df
+            .get_columns()
+            .iter()
+            .map(|s| {
+                let Some(format) = format(s.dtype()) else {
+                    return Ok(s.clone());
+                };
+                let formatted = match s.dtype() {
+                    #[cfg(feature = "dtype-date")]
+                    DataType::Date => s.date()?.to_string(format)?,
+                    #[cfg(feature = "dtype-time")]
+                    DataType::Time => s.time()?.to_string(format),
+                    #[cfg(feature = "dtype-datetime")]
+                    DataType::Datetime(_, _) => s.datetime()?.to_string(format)?,
+                    _ => unreachable!(),
+                };
+                Ok(formatted.with_name(s.name().clone()).into_series())
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        // SAFETY: the columns keep their names and lengths.
+        Ok(Cow::Owned(unsafe { DataFrame::new_no_checks(columns) }))
     }
+
     /// Write a batch to the json writer.
     ///
     /// # Panics
     /// The caller must ensure the chunks in the given [`DataFrame`] are aligned.
     pub fn write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        let df = self.format_temporal_columns(df)?;
         let fields = df
             .iter()
             .map(|s| {
@@ -200,7 +288,8 @@ where
         let chunks = df.iter_chunks(CompatLevel::newest(), false);
         let batches =
             chunks.map(|chunk| Ok(Box::new(chunk_to_struct(chunk, fields.clone())) as ArrayRef));
-        let mut serializer = polars_json::ndjson::write::Serializer::new(batches, vec![]);
+        let mut serializer = polars_json::ndjson::write::Serializer::new(batches, vec![])
+            .with_null_value(self.null_value.as_deref());
         while let Some(block) = serializer.next()? {
             self.writer.write_all(block)?;
         }
@@ -0,0 +1,125 @@
+//! # Reading Kafka topics.
+//!
+//! A bounded range of the messages of a topic is consumed in batches, of which the payloads are
+//! decoded into DataFrames. The range starts at an offset in every partition and ends at an
+//! offset, or after a number of messages. Offsets are not committed to a consumer group, so
+//! reading a range again reads the same messages.
+mod read;
+
+use polars_core::prelude::*;
+pub use read::KafkaReader;
+
+#[cfg(feature = "avro")]
+use crate::avro::{AvroMessageFormat, AvroSchemaStore};
+
+/// The offset in every partition from which the messages are read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KafkaStartOffset {
+    /// The first message that is retained in the partition.
+    #[default]
+    Beginning,
+    /// The message at this offset, or the first message that is retained after it.
+    Offset(i64),
+}
+
+/// Where the messages that are read end. The messages that are produced after the reading
+/// started are never read, so that the range is bounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KafkaEnd {
+    /// The end of every partition when the reading started.
+    #[default]
+    Latest,
+    /// The message before this offset in every partition.
+    Offset(i64),
+    /// After this number of messages in total.
+    Count(usize),
+}
+
+/// How the payloads of the messages are decoded.
+#[derive(Clone)]
+pub enum KafkaPayloadFormat {
+    /// Every payload is a JSON object.
+    Json,
+    /// Every payload is an Avro datum that refers to its writer schema in `store`.
+    #[cfg(feature = "avro")]
+    Avro {
+        store: AvroSchemaStore,
+        format: AvroMessageFormat,
+    },
+}
+
+/// Options of a [`KafkaReader`].
+#[derive(Clone)]
+pub struct KafkaOptions {
+    /// The comma-separated `host:port` addresses of the brokers.
+    pub brokers: String,
+    pub topic: String,
+    /// The schema of the decoded payloads.
+    pub schema: SchemaRef,
+    pub payload_format: KafkaPayloadFormat,
+    /// The partitions that are read, or every partition of the topic if `None`.
+    pub partitions: Option<Vec<i32>>,
+    pub start: KafkaStartOffset,
+    pub end: KafkaEnd,
+    /// The maximum number of messages per batch.
+    pub batch_size: usize,
+    /// How long to wait for a message before the reading fails.
+    pub timeout: std::time::Duration,
+    /// Configuration properties of the consumer, such as `security.protocol`.
+    pub properties: Vec<(String, String)>,
+}
+
+impl KafkaOptions {
+    pub fn new(
+        brokers: impl Into<String>,
+        topic: impl Into<String>,
+        schema: SchemaRef,
+        payload_format: KafkaPayloadFormat,
+    ) -> Self {
+        Self {
+            brokers: brokers.into(),
+            topic: topic.into(),
+            schema,
+            payload_format,
+            partitions: None,
+            start: KafkaStartOffset::default(),
+            end: KafkaEnd::default(),
+            batch_size: 10_000,
+            timeout: std::time::Duration::from_secs(30),
+            properties: vec![],
+        }
+    }
+
+    /// Read only these partitions of the topic.
+    pub fn with_partitions(mut self, partitions: Option<Vec<i32>>) -> Self {
+        self.partitions = partitions;
+        self
+    }
+
+    pub fn with_start(mut self, start: KafkaStartOffset) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn with_end(mut self, end: KafkaEnd) -> Self {
+        self.end = end;
+        self
+    }
+
+    /// Decode at most this number of messages per batch.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set a configuration property of the consumer.
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.push((key.into(), value.into()));
+        self
+    }
+}
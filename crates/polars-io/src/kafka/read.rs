@@ -0,0 +1,260 @@
+use std::io::Cursor;
+
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::error::KafkaError;
+use rdkafka::{ClientConfig, Message, Offset, TopicPartitionList};
+
+use super::{KafkaEnd, KafkaOptions, KafkaPayloadFormat, KafkaStartOffset};
+#[cfg(feature = "avro")]
+use crate::avro::AvroMessageReader;
+use crate::json::{JsonFormat, JsonReader};
+use crate::SerReader;
+
+/// Reads a bounded range of the messages of a Kafka topic in batches.
+///
+/// The payloads of the messages in a batch are decoded together into a DataFrame with the
+/// schema of the [`KafkaOptions`]. Messages without a payload, such as tombstones, are skipped.
+/// The messages of different partitions are interleaved in the order in which they are
+/// received.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::kafka::{KafkaEnd, KafkaOptions, KafkaPayloadFormat, KafkaReader};
+///
+/// fn example(schema: SchemaRef) -> PolarsResult<DataFrame> {
+///     let options = KafkaOptions::new("localhost:9092", "events", schema, KafkaPayloadFormat::Json)
+///         .with_end(KafkaEnd::Count(1000));
+///     KafkaReader::try_new(options)?.finish()
+/// }
+/// ```
+pub struct KafkaReader {
+    consumer: BaseConsumer,
+    options: KafkaOptions,
+    /// The offset at which every partition that hasn't been read to its end ends.
+    end_offsets: PlHashMap<i32, i64>,
+    /// The number of messages that are left to read, if the range ends after a number of
+    /// messages.
+    remaining: Option<usize>,
+}
+
+impl KafkaReader {
+    /// Connect to the brokers and assign the partitions of the range to a consumer.
+    pub fn try_new(options: KafkaOptions) -> PolarsResult<Self> {
+        polars_ensure!(
+            options.batch_size > 0,
+            InvalidOperation: "the batch size must be greater than 0"
+        );
+        let mut config = ClientConfig::new();
+        config
+            .set("bootstrap.servers", &options.brokers)
+            .set("group.id", "polars")
+            .set("enable.auto.commit", "false")
+            .set("enable.partition.eof", "true");
+        for (key, value) in &options.properties {
+            config.set(key, value);
+        }
+        let consumer: BaseConsumer = config.create().map_err(to_compute_err)?;
+
+        let topic = options.topic.as_str();
+        let partitions = match &options.partitions {
+            Some(partitions) => partitions.clone(),
+            None => {
+                let metadata = consumer
+                    .fetch_metadata(Some(topic), options.timeout)
+                    .map_err(to_compute_err)?;
+                let metadata = metadata
+                    .topics()
+                    .iter()
+                    .find(|metadata| metadata.name() == topic)
+                    .filter(|metadata| metadata.error().is_none())
+                    .ok_or_else(
+                        || polars_err!(ComputeError: "Kafka topic '{}' doesn't exist", topic),
+                    )?;
+                metadata.partitions().iter().map(|p| p.id()).collect()
+            },
+        };
+
+        let mut assignment = TopicPartitionList::new();
+        let mut end_offsets = PlHashMap::new();
+        for partition in partitions {
+            let (low, high) = consumer
+                .fetch_watermarks(topic, partition, options.timeout)
+                .map_err(to_compute_err)?;
+            let start = match options.start {
+                KafkaStartOffset::Beginning => low,
+                KafkaStartOffset::Offset(offset) => offset.max(low),
+            };
+            let end = match options.end {
+                KafkaEnd::Offset(offset) => offset.min(high),
+                KafkaEnd::Latest | KafkaEnd::Count(_) => high,
+            };
+            if start < end {
+                assignment
+                    .add_partition_offset(topic, partition, Offset::Offset(start))
+                    .map_err(to_compute_err)?;
+                end_offsets.insert(partition, end);
+            }
+        }
+        if !end_offsets.is_empty() {
+            consumer.assign(&assignment).map_err(to_compute_err)?;
+        }
+
+        let remaining = match options.end {
+            KafkaEnd::Count(count) => Some(count),
+            _ => None,
+        };
+        Ok(Self {
+            consumer,
+            options,
+            end_offsets,
+            remaining,
+        })
+    }
+
+    pub fn schema(&self) -> &SchemaRef {
+        &self.options.schema
+    }
+
+    fn is_finished(&self) -> bool {
+        self.end_offsets.is_empty() || self.remaining == Some(0)
+    }
+
+    /// Read and decode the next batch of messages, or `None` if the range has been read.
+    pub fn next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        let mut payloads = vec![];
+        while payloads.len() < self.options.batch_size && !self.is_finished() {
+            let message = match self.consumer.poll(self.options.timeout) {
+                Some(Ok(message)) => message,
+                // The rest of the partition isn't readable, e.g. because it ends with the
+                // control records of a transaction.
+                Some(Err(KafkaError::PartitionEOF(partition))) => {
+                    self.end_offsets.remove(&partition);
+                    continue;
+                },
+                Some(Err(e)) => return Err(to_compute_err(e)),
+                None => polars_bail!(
+                    ComputeError: "timed out waiting for messages of Kafka topic '{}'",
+                    self.options.topic
+                ),
+            };
+            let partition = message.partition();
+            let Some(&end) = self.end_offsets.get(&partition) else {
+                continue;
+            };
+            if message.offset() >= end {
+                self.end_offsets.remove(&partition);
+                continue;
+            }
+            if message.offset() + 1 == end {
+                self.end_offsets.remove(&partition);
+            }
+            if let Some(payload) = message.payload() {
+                payloads.push(payload.to_vec());
+                if let Some(remaining) = &mut self.remaining {
+                    *remaining -= 1;
+                }
+            }
+        }
+        if payloads.is_empty() {
+            return Ok(None);
+        }
+        decode_payloads(
+            &mut self.options.payload_format,
+            &self.options.schema,
+            &payloads,
+        )
+        .map(Some)
+    }
+
+    /// Read the rest of the range into a DataFrame.
+    pub fn finish(mut self) -> PolarsResult<DataFrame> {
+        let mut dfs = vec![];
+        while let Some(df) = self.next_batch()? {
+            dfs.push(df);
+        }
+        if dfs.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&self.options.schema));
+        }
+        Ok(accumulate_dataframes_vertical_unchecked(dfs))
+    }
+}
+
+fn decode_payloads(
+    format: &mut KafkaPayloadFormat,
+    schema: &SchemaRef,
+    payloads: &[Vec<u8>],
+) -> PolarsResult<DataFrame> {
+    match format {
+        // The payloads are decoded together as the elements of a JSON array.
+        KafkaPayloadFormat::Json => {
+            let mut buf =
+                Vec::with_capacity(payloads.iter().map(|p| p.len() + 1).sum::<usize>() + 1);
+            buf.push(b'[');
+            for (i, payload) in payloads.iter().enumerate() {
+                if i > 0 {
+                    buf.push(b',');
+                }
+                buf.extend_from_slice(payload);
+            }
+            buf.push(b']');
+            JsonReader::new(Cursor::new(buf))
+                .with_json_format(JsonFormat::Json)
+                .with_schema(schema.clone())
+                .finish()
+        },
+        #[cfg(feature = "avro")]
+        KafkaPayloadFormat::Avro { store, format } => {
+            let df = AvroMessageReader::new(store, *format)
+                .read(payloads.iter().map(|p| p.as_slice()))?;
+            conform_to_schema(df, schema)
+        },
+    }
+}
+
+/// Select the columns of the schema from a decoded batch, in the order of the schema.
+#[cfg(feature = "avro")]
+fn conform_to_schema(df: DataFrame, schema: &Schema) -> PolarsResult<DataFrame> {
+    let columns = schema
+        .iter()
+        .map(|(name, dtype)| {
+            let s = df.column(name)?;
+            if s.dtype() == dtype {
+                Ok(s.clone())
+            } else {
+                s.cast(dtype)
+            }
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    DataFrame::new(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_json_payloads() {
+        let schema = Arc::new(Schema::from_iter([
+            Field::new("a".into(), DataType::Int64),
+            Field::new("b".into(), DataType::String),
+        ]));
+        let payloads = [
+            br#"{"a": 1, "b": "x"}"#.to_vec(),
+            b"{\n  \"b\": \"y\"\n}".to_vec(),
+        ];
+        let df = decode_payloads(&mut KafkaPayloadFormat::Json, &schema, &payloads).unwrap();
+        assert_eq!(df.schema(), *schema);
+        assert_eq!(
+            df.column("a").unwrap().i64().unwrap().to_vec(),
+            [Some(1), None]
+        );
+        assert_eq!(
+            df.column("b").unwrap().str().unwrap().to_vec(),
+            [Some("x"), Some("y")]
+        );
+    }
+}
@@ -8,26 +8,44 @@ pub mod avro;
 pub mod cloud;
 #[cfg(any(feature = "csv", feature = "json"))]
 pub mod csv;
+#[cfg(feature = "delta")]
+pub mod delta;
+#[cfg(feature = "excel")]
+pub mod excel;
 #[cfg(feature = "file_cache")]
 pub mod file_cache;
+#[cfg(feature = "flight")]
+pub mod flight;
+#[cfg(feature = "fwf")]
+pub mod fwf;
+#[cfg(feature = "iceberg")]
+pub mod iceberg;
 #[cfg(any(feature = "ipc", feature = "ipc_streaming"))]
 pub mod ipc;
 #[cfg(feature = "json")]
 pub mod json;
+#[cfg(feature = "kafka")]
+pub mod kafka;
 pub mod mmap;
 #[cfg(feature = "json")]
 pub mod ndjson;
 mod options;
+#[cfg(feature = "orc")]
+pub mod orc;
 #[cfg(feature = "parquet")]
 pub mod parquet;
 #[cfg(feature = "parquet")]
 pub mod partition;
 pub mod path_utils;
+#[cfg(feature = "postgres")]
+pub mod postgres;
 #[cfg(feature = "async")]
 pub mod pl_async;
 pub mod predicates;
 pub mod prelude;
 mod shared;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod utils;
 
 #[cfg(feature = "cloud")]
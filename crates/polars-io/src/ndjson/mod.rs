@@ -5,6 +5,9 @@ use polars_core::prelude::*;
 
 pub(crate) mod buffer;
 pub mod core;
+mod stream;
+
+pub use stream::JsonLineStreamReader;
 
 pub fn infer_schema<R: std::io::BufRead>(
     reader: &mut R,
@@ -0,0 +1,205 @@
+use std::io::{Cursor, Read};
+use std::num::NonZeroUsize;
+
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+
+use super::core::JsonLineReader;
+use crate::utils::overwrite_schema;
+use crate::SerReader;
+
+const DEFAULT_BLOCK_SIZE: usize = 1 << 22;
+
+/// Reads newline-delimited JSON from a [`Read`] that can't be memory mapped or seeked, such as
+/// stdin or a pipe, in batches.
+///
+/// The input is read in blocks of complete lines, of which every block is parsed into a batch, so
+/// that the input is never fully held in memory. If no schema is given, it is inferred from the
+/// first block.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::ndjson::JsonLineStreamReader;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     JsonLineStreamReader::new(Box::new(std::io::stdin())).finish()
+/// }
+/// ```
+#[must_use]
+pub struct JsonLineStreamReader {
+    reader: Box<dyn Read + Send>,
+    block_size: usize,
+    /// The bytes after the last line ending that was read.
+    pending: Vec<u8>,
+    exhausted: bool,
+    schema: Option<SchemaRef>,
+    schema_overwrite: Option<SchemaRef>,
+    infer_schema_len: Option<NonZeroUsize>,
+    ignore_errors: bool,
+    /// The first block, which is read when the schema is inferred.
+    first_block: Option<Vec<u8>>,
+}
+
+impl JsonLineStreamReader {
+    pub fn new(reader: Box<dyn Read + Send>) -> Self {
+        Self {
+            reader,
+            block_size: DEFAULT_BLOCK_SIZE,
+            pending: vec![],
+            exhausted: false,
+            schema: None,
+            schema_overwrite: None,
+            infer_schema_len: NonZeroUsize::new(100),
+            ignore_errors: false,
+            first_block: None,
+        }
+    }
+
+    /// Set the schema of the input, so that it isn't inferred.
+    pub fn with_schema(mut self, schema: Option<SchemaRef>) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// Overwrite the dtypes of these columns in the inferred schema.
+    pub fn with_schema_overwrite(mut self, schema_overwrite: Option<SchemaRef>) -> Self {
+        self.schema_overwrite = schema_overwrite;
+        self
+    }
+
+    /// Set the number of rows of the first block that are used to infer the schema. `None`
+    /// uses all rows of the first block.
+    pub fn infer_schema_len(mut self, infer_schema_len: Option<NonZeroUsize>) -> Self {
+        self.infer_schema_len = infer_schema_len;
+        self
+    }
+
+    /// Set values as `Null` if parsing fails because of schema mismatches.
+    pub fn with_ignore_errors(mut self, ignore_errors: bool) -> Self {
+        self.ignore_errors = ignore_errors;
+        self
+    }
+
+    /// Set the number of bytes that are read per block.
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Get the next block of complete lines, or `None` if the reader is exhausted.
+    fn next_block(&mut self) -> PolarsResult<Option<Vec<u8>>> {
+        let mut buf = Vec::with_capacity(self.block_size);
+        while !self.exhausted {
+            buf.clear();
+            let n_read = (&mut self.reader)
+                .take(self.block_size as u64)
+                .read_to_end(&mut buf)?;
+            if n_read == 0 {
+                self.exhausted = true;
+                break;
+            }
+
+            // A line ending can't be part of a JSON value, so every line ends on a line ending.
+            if let Some(pos) = memchr::memrchr(b'\n', &buf) {
+                let mut block = std::mem::take(&mut self.pending);
+                block.extend_from_slice(&buf[..pos + 1]);
+                self.pending.extend_from_slice(&buf[pos + 1..]);
+                return Ok(Some(block));
+            }
+            self.pending.extend_from_slice(&buf);
+        }
+        if self.pending.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(std::mem::take(&mut self.pending)))
+        }
+    }
+
+    /// The schema of the batches, which is inferred from the first block if it isn't given.
+    pub fn schema(&mut self) -> PolarsResult<SchemaRef> {
+        if self.schema.is_none() {
+            let block = self.next_block()?.unwrap_or_default();
+            let mut schema =
+                super::infer_schema(&mut Cursor::new(block.as_slice()), self.infer_schema_len)?;
+            if let Some(overwriting_schema) = &self.schema_overwrite {
+                overwrite_schema(&mut schema, overwriting_schema)?;
+            }
+            self.schema = Some(Arc::new(schema));
+            self.first_block = Some(block);
+        }
+        Ok(self.schema.clone().unwrap())
+    }
+
+    /// Read and parse the next block, or `None` if the input is exhausted.
+    pub fn next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        let schema = self.schema()?;
+        loop {
+            let block = match self.first_block.take() {
+                Some(block) => block,
+                None => match self.next_block()? {
+                    Some(block) => block,
+                    None => return Ok(None),
+                },
+            };
+            let df = JsonLineReader::new(Cursor::new(block))
+                .with_schema(schema.clone())
+                .with_ignore_errors(self.ignore_errors)
+                .with_rechunk(false)
+                .finish()?;
+            if !df.is_empty() {
+                return Ok(Some(df));
+            }
+        }
+    }
+
+    /// Read the rest of the input into a DataFrame.
+    pub fn finish(mut self) -> PolarsResult<DataFrame> {
+        let schema = self.schema()?;
+        let mut dfs = vec![];
+        while let Some(df) = self.next_batch()? {
+            dfs.push(df);
+        }
+        if dfs.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&schema));
+        }
+        Ok(accumulate_dataframes_vertical_unchecked(dfs))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stream_reader_blocks() {
+        let input = "{\"a\": 1, \"b\": \"x\"}\n{\"a\": 2}\n{\"a\": 3, \"b\": \"z\"}";
+        let mut reader = JsonLineStreamReader::new(Box::new(input.as_bytes())).with_block_size(7);
+
+        let mut blocks = vec![];
+        while let Some(block) = reader.next_block().unwrap() {
+            blocks.push(String::from_utf8(block).unwrap());
+        }
+        assert_eq!(
+            blocks,
+            [
+                "{\"a\": 1, \"b\": \"x\"}\n",
+                "{\"a\": 2}\n",
+                "{\"a\": 3, \"b\": \"z\"}"
+            ]
+        );
+
+        let df = JsonLineStreamReader::new(Box::new(input.as_bytes()))
+            .with_block_size(7)
+            .finish()
+            .unwrap();
+        assert_eq!(
+            df.column("a").unwrap().i64().unwrap().to_vec(),
+            [Some(1), Some(2), Some(3)]
+        );
+        assert_eq!(
+            df.column("b").unwrap().str().unwrap().to_vec(),
+            [Some("x"), None, Some("z")]
+        );
+    }
+}
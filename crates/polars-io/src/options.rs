@@ -34,3 +34,30 @@ impl Default for HiveOptions {
         }
     }
 }
+
+/// How the schemas of the files of a multi-file scan may differ.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SchemaEvolution {
+    /// All files must have the same schema.
+    #[default]
+    Strict,
+    /// Columns that are missing from a file are filled with nulls. Columns that are in multiple
+    /// files must have the same dtype.
+    AllowMissingColumns,
+    /// Columns that are missing from a file are filled with nulls, and columns that have
+    /// different dtypes in different files are cast to their supertype.
+    Relaxed,
+}
+
+/// Options for writing a hive-partitioned dataset.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartitionedWriteOptions {
+    /// The maximum number of rows per file. The rows of a partition are split over multiple
+    /// files if it has more rows.
+    pub max_rows_per_file: Option<usize>,
+    /// Write a `_metadata` file with the footers of all files to the root of the dataset, so
+    /// that readers don't need to read the footer of every file. Only supported for Parquet.
+    pub write_metadata: bool,
+}
@@ -0,0 +1,85 @@
+use std::io::Read;
+
+use polars_error::{polars_bail, polars_ensure, polars_err, to_compute_err, PolarsResult};
+
+use super::proto::CompressionKind;
+
+/// Decompress a stream or a message of the file tail.
+///
+/// A compressed stream is a sequence of chunks that each start with a 3 byte little endian
+/// header that holds the length of the chunk and whether it is stored uncompressed.
+pub(super) fn decompress(
+    bytes: &[u8],
+    compression: CompressionKind,
+    block_size: usize,
+) -> PolarsResult<Vec<u8>> {
+    if compression == CompressionKind::None {
+        return Ok(bytes.to_vec());
+    }
+
+    let mut out = Vec::with_capacity(bytes.len() * 2);
+    let mut bytes = bytes;
+    while !bytes.is_empty() {
+        polars_ensure!(
+            bytes.len() >= 3,
+            ComputeError: "out-of-spec ORC file: truncated compression chunk header"
+        );
+        let header = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+        let is_original = header & 1 == 1;
+        let length = (header >> 1) as usize;
+        let chunk = bytes.get(3..3 + length).ok_or_else(
+            || polars_err!(ComputeError: "out-of-spec ORC file: truncated compression chunk"),
+        )?;
+        bytes = &bytes[3 + length..];
+
+        if is_original {
+            out.extend_from_slice(chunk);
+            continue;
+        }
+        match compression {
+            CompressionKind::None => unreachable!(),
+            CompressionKind::Zlib => {
+                flate2::read::DeflateDecoder::new(chunk)
+                    .read_to_end(&mut out)
+                    .map_err(to_compute_err)?;
+            },
+            CompressionKind::Snappy => {
+                let decompressed = snap::raw::Decoder::new()
+                    .decompress_vec(chunk)
+                    .map_err(to_compute_err)?;
+                out.extend_from_slice(&decompressed);
+            },
+            CompressionKind::Zstd => {
+                zstd::Decoder::new(chunk)
+                    .and_then(|mut decoder| decoder.read_to_end(&mut out))
+                    .map_err(to_compute_err)?;
+            },
+            CompressionKind::Lz4 => {
+                let start = out.len();
+                out.resize(start + block_size, 0);
+                let n = lz4_flex::block::decompress_into(chunk, &mut out[start..])
+                    .map_err(to_compute_err)?;
+                out.truncate(start + n);
+            },
+            CompressionKind::Lzo => {
+                polars_bail!(ComputeError: "LZO compressed ORC files are not supported")
+            },
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompress_original_chunks() {
+        // Two chunks that are stored uncompressed, of 2 and 1 bytes.
+        let bytes = [0x05, 0x00, 0x00, 1, 2, 0x03, 0x00, 0x00, 3];
+        let out = decompress(&bytes, CompressionKind::Zlib, 256 * 1024).unwrap();
+        assert_eq!(out, [1, 2, 3]);
+
+        assert!(decompress(&bytes[..4], CompressionKind::Zlib, 256 * 1024).is_err());
+    }
+}
@@ -0,0 +1,391 @@
+//! Decoding of the run length encodings of the streams of a stripe.
+//!
+//! See <https://orc.apache.org/specification/ORCv1/> for the encodings.
+use polars_error::{polars_bail, polars_ensure, polars_err, PolarsResult};
+
+use super::proto::{read_varint, unzigzag};
+
+fn out_of_spec(msg: &str) -> polars_error::PolarsError {
+    polars_err!(ComputeError: "out-of-spec ORC file: {}", msg)
+}
+
+fn next_byte(bytes: &mut &[u8]) -> PolarsResult<u8> {
+    let (&byte, rest) = bytes
+        .split_first()
+        .ok_or_else(|| out_of_spec("truncated run length encoding"))?;
+    *bytes = rest;
+    Ok(byte)
+}
+
+/// Decode `n` bytes that are encoded with the byte run length encoding.
+pub(super) fn decode_bytes(mut bytes: &[u8], n: usize) -> PolarsResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(n);
+    while out.len() < n {
+        let header = next_byte(&mut bytes)? as i8;
+        if header >= 0 {
+            let value = next_byte(&mut bytes)?;
+            out.extend(std::iter::repeat(value).take(header as usize + 3));
+        } else {
+            let length = -(header as isize) as usize;
+            let literals = bytes
+                .get(..length)
+                .ok_or_else(|| out_of_spec("truncated byte literals"))?;
+            out.extend_from_slice(literals);
+            bytes = &bytes[length..];
+        }
+    }
+    out.truncate(n);
+    Ok(out)
+}
+
+/// Decode `n` booleans, which are bit packed with the most significant bit first and then
+/// byte run length encoded.
+pub(super) fn decode_booleans(bytes: &[u8], n: usize) -> PolarsResult<Vec<bool>> {
+    let packed = decode_bytes(bytes, n.div_ceil(8))?;
+    Ok((0..n)
+        .map(|i| packed[i / 8] & (0x80 >> (i % 8)) != 0)
+        .collect())
+}
+
+/// Decode `n` integers, which are encoded with version 1 or 2 of the integer run length
+/// encoding depending on the column encoding.
+pub(super) fn decode_ints(
+    bytes: &[u8],
+    n: usize,
+    signed: bool,
+    v2: bool,
+) -> PolarsResult<Vec<i64>> {
+    if v2 {
+        decode_ints_v2(bytes, n, signed)
+    } else {
+        decode_ints_v1(bytes, n, signed)
+    }
+}
+
+fn read_int_varint(bytes: &mut &[u8], signed: bool) -> PolarsResult<i64> {
+    let value = read_varint(bytes)?;
+    Ok(if signed {
+        unzigzag(value)
+    } else {
+        value as i64
+    })
+}
+
+fn decode_ints_v1(mut bytes: &[u8], n: usize, signed: bool) -> PolarsResult<Vec<i64>> {
+    let mut out = Vec::with_capacity(n);
+    while out.len() < n {
+        let header = next_byte(&mut bytes)? as i8;
+        if header >= 0 {
+            let delta = next_byte(&mut bytes)? as i8 as i64;
+            let base = read_int_varint(&mut bytes, signed)?;
+            out.extend((0..header as i64 + 3).map(|i| base.wrapping_add(i * delta)));
+        } else {
+            for _ in 0..-(header as i64) {
+                out.push(read_int_varint(&mut bytes, signed)?);
+            }
+        }
+    }
+    out.truncate(n);
+    Ok(out)
+}
+
+/// The 5 bit encoding of the bit widths of RLE v2.
+fn decode_bit_width(encoded: u8) -> usize {
+    match encoded {
+        0..=23 => encoded as usize + 1,
+        24 => 26,
+        25 => 28,
+        26 => 30,
+        27 => 32,
+        28 => 40,
+        29 => 48,
+        30 => 56,
+        _ => 64,
+    }
+}
+
+/// The smallest bit width that is supported by the encodings and holds `n` bits.
+fn closest_fixed_bits(n: usize) -> usize {
+    match n {
+        0 => 1,
+        1..=24 => n,
+        25..=26 => 26,
+        27..=28 => 28,
+        29..=30 => 30,
+        31..=32 => 32,
+        33..=40 => 40,
+        41..=48 => 48,
+        49..=56 => 56,
+        _ => 64,
+    }
+}
+
+/// Unpack `count` big endian bit packed values of `width` bits. The values are padded to a
+/// whole byte at the end of a run.
+fn unpack(bytes: &mut &[u8], width: usize, count: usize, out: &mut Vec<u64>) -> PolarsResult<()> {
+    let mut acc = 0u128;
+    let mut n_bits = 0;
+    for _ in 0..count {
+        while n_bits < width {
+            acc = (acc << 8) | next_byte(bytes)? as u128;
+            n_bits += 8;
+        }
+        n_bits -= width;
+        let value = (acc >> n_bits) as u64;
+        out.push(if width == 64 {
+            value
+        } else {
+            value & ((1u64 << width) - 1)
+        });
+        acc &= (1u128 << n_bits) - 1;
+    }
+    Ok(())
+}
+
+fn read_big_endian(bytes: &mut &[u8], width: usize) -> PolarsResult<u64> {
+    let mut value = 0u64;
+    for _ in 0..width {
+        value = (value << 8) | next_byte(bytes)? as u64;
+    }
+    Ok(value)
+}
+
+fn decode_ints_v2(mut bytes: &[u8], n: usize, signed: bool) -> PolarsResult<Vec<i64>> {
+    let to_i64 = |v: u64| if signed { unzigzag(v) } else { v as i64 };
+    let mut out = Vec::with_capacity(n);
+    let mut unpacked = vec![];
+    while out.len() < n {
+        let header = next_byte(&mut bytes)?;
+        match header >> 6 {
+            // Short repeat.
+            0 => {
+                let width = ((header >> 3) & 7) as usize + 1;
+                let count = (header & 7) as usize + 3;
+                let value = to_i64(read_big_endian(&mut bytes, width)?);
+                out.extend(std::iter::repeat(value).take(count));
+            },
+            // Direct.
+            1 => {
+                let width = decode_bit_width((header >> 1) & 0x1f);
+                let length = (((header & 1) as usize) << 8 | next_byte(&mut bytes)? as usize) + 1;
+                unpacked.clear();
+                unpack(&mut bytes, width, length, &mut unpacked)?;
+                out.extend(unpacked.iter().map(|v| to_i64(*v)));
+            },
+            // Patched base.
+            2 => {
+                let width = decode_bit_width((header >> 1) & 0x1f);
+                let length = (((header & 1) as usize) << 8 | next_byte(&mut bytes)? as usize) + 1;
+                let byte = next_byte(&mut bytes)?;
+                let base_width = ((byte >> 5) & 7) as usize + 1;
+                let patch_width = decode_bit_width(byte & 0x1f);
+                let byte = next_byte(&mut bytes)?;
+                let gap_width = ((byte >> 5) & 7) as usize + 1;
+                let patch_list_length = (byte & 0x1f) as usize;
+
+                // The base is stored in sign magnitude representation.
+                let base = read_big_endian(&mut bytes, base_width)?;
+                let sign_mask = 1u64 << (base_width * 8 - 1);
+                let base = if base & sign_mask != 0 {
+                    -((base & !sign_mask) as i64)
+                } else {
+                    base as i64
+                };
+
+                unpacked.clear();
+                unpack(&mut bytes, width, length, &mut unpacked)?;
+                let mut patches = vec![];
+                let patch_entry_width = closest_fixed_bits(gap_width + patch_width);
+                polars_ensure!(
+                    width + patch_width <= 64 && patch_entry_width <= 64,
+                    ComputeError: "out-of-spec ORC file: patch is too wide"
+                );
+                unpack(
+                    &mut bytes,
+                    patch_entry_width,
+                    patch_list_length,
+                    &mut patches,
+                )?;
+
+                // The gaps are relative to the previous patch. A gap of 255 with an empty
+                // patch only moves the position.
+                let patch_mask = if patch_width == 64 {
+                    u64::MAX
+                } else {
+                    (1u64 << patch_width) - 1
+                };
+                let mut position = 0;
+                for entry in patches {
+                    position += (entry >> patch_width) as usize;
+                    let patch = entry & patch_mask;
+                    if patch != 0 {
+                        let Some(value) = unpacked.get_mut(position) else {
+                            polars_bail!(ComputeError: "out-of-spec ORC file: patch out of bounds");
+                        };
+                        *value |= patch << width;
+                    }
+                }
+                out.extend(unpacked.iter().map(|v| base.wrapping_add(*v as i64)));
+            },
+            // Delta.
+            _ => {
+                let encoded_width = (header >> 1) & 0x1f;
+                let width = if encoded_width == 0 {
+                    0
+                } else {
+                    decode_bit_width(encoded_width)
+                };
+                let length = (((header & 1) as usize) << 8 | next_byte(&mut bytes)? as usize) + 1;
+                let base = read_int_varint(&mut bytes, signed)?;
+                let delta_base = unzigzag(read_varint(&mut bytes)?);
+
+                out.push(base);
+                if length == 1 {
+                    continue;
+                }
+                if width == 0 {
+                    // A fixed delta.
+                    let mut value = base;
+                    for _ in 1..length {
+                        value = value.wrapping_add(delta_base);
+                        out.push(value);
+                    }
+                } else {
+                    let mut value = base.wrapping_add(delta_base);
+                    out.push(value);
+                    unpacked.clear();
+                    unpack(&mut bytes, width, length - 2, &mut unpacked)?;
+                    // The deltas are stored as absolute values with the sign of the delta base.
+                    for delta in &unpacked {
+                        value = if delta_base < 0 {
+                            value.wrapping_sub(*delta as i64)
+                        } else {
+                            value.wrapping_add(*delta as i64)
+                        };
+                        out.push(value);
+                    }
+                }
+            },
+        }
+    }
+    out.truncate(n);
+    Ok(out)
+}
+
+#[cfg(feature = "dtype-decimal")]
+/// Decode `n` zigzag encoded varints of up to 128 bits, which hold the unscaled values of
+/// decimals.
+pub(super) fn decode_i128_varints(mut bytes: &[u8], n: usize) -> PolarsResult<Vec<i128>> {
+    (0..n)
+        .map(|_| {
+            let mut value = 0u128;
+            let mut shift = 0;
+            loop {
+                let byte = next_byte(&mut bytes)?;
+                polars_ensure!(
+                    shift < 128,
+                    ComputeError: "out-of-spec ORC file: decimal varint is too long"
+                );
+                value |= ((byte & 0x7f) as u128) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                shift += 7;
+            }
+            Ok(((value >> 1) as i128) ^ -((value & 1) as i128))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_bytes() {
+        assert_eq!(decode_bytes(&[0x61, 0x00], 100).unwrap(), vec![0; 100]);
+        assert_eq!(decode_bytes(&[0xfe, 0x44, 0x45], 2).unwrap(), [0x44, 0x45]);
+        assert!(decode_bytes(&[0xfe, 0x44], 2).is_err());
+    }
+
+    #[test]
+    fn test_decode_booleans() {
+        let mut expected = vec![true; 8];
+        expected.extend([false; 7]);
+        assert_eq!(decode_booleans(&[0xff, 0x80], 15).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_decode_ints_v1() {
+        assert_eq!(
+            decode_ints(&[0x61, 0x00, 0x07], 100, false, false).unwrap(),
+            vec![7; 100]
+        );
+        assert_eq!(
+            decode_ints(&[0x61, 0xff, 0x64], 100, false, false).unwrap(),
+            (1..=100).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            decode_ints(&[0xfb, 0x02, 0x03, 0x06, 0x07, 0xb], 5, false, false).unwrap(),
+            [2, 3, 6, 7, 11]
+        );
+    }
+
+    #[test]
+    fn test_decode_ints_v2() {
+        // Short repeat.
+        assert_eq!(
+            decode_ints(&[0x0a, 0x27, 0x10], 5, false, true).unwrap(),
+            vec![10000; 5]
+        );
+        // Direct.
+        assert_eq!(
+            decode_ints(
+                &[0x5e, 0x03, 0x5c, 0xa1, 0xab, 0x1e, 0xde, 0xad, 0xbe, 0xef],
+                4,
+                false,
+                true
+            )
+            .unwrap(),
+            [23713, 43806, 57005, 48879]
+        );
+        // Patched base.
+        assert_eq!(
+            decode_ints(
+                &[
+                    0x8e, 0x13, 0x2b, 0x21, 0x07, 0xd0, 0x1e, 0x00, 0x14, 0x70, 0x28, 0x32, 0x3c,
+                    0x46, 0x50, 0x5a, 0x64, 0x6e, 0x78, 0x82, 0x8c, 0x96, 0xa0, 0xaa, 0xb4, 0xbe,
+                    0xfc, 0xe8
+                ],
+                20,
+                false,
+                true
+            )
+            .unwrap(),
+            [
+                2030, 2000, 2020, 1000000, 2040, 2050, 2060, 2070, 2080, 2090, 2100, 2110, 2120,
+                2130, 2140, 2150, 2160, 2170, 2180, 2190
+            ]
+        );
+        // Delta.
+        assert_eq!(
+            decode_ints(
+                &[0xc6, 0x09, 0x02, 0x02, 0x22, 0x42, 0x42, 0x46],
+                10,
+                false,
+                true
+            )
+            .unwrap(),
+            [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]
+        );
+    }
+
+    #[cfg(feature = "dtype-decimal")]
+    #[test]
+    fn test_decode_i128_varints() {
+        assert_eq!(
+            decode_i128_varints(&[0x01, 0x02, 0x80, 0x01], 3).unwrap(),
+            [-1, 1, 64]
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! # Reading Apache ORC files.
+//!
+//! [ORC](https://orc.apache.org/) is a columnar file format. A file is split in stripes, of which
+//! the statistics in the file footer are used to skip stripes that cannot match a predicate.
+//!
+//! The reader supports the primitive, temporal and nested types of ORC except for unions, and
+//! files that are uncompressed or compressed with zlib, snappy, lz4 or zstd.
+mod compression;
+mod decode;
+mod proto;
+mod read;
+
+pub use read::OrcReader;
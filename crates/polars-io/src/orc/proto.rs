@@ -0,0 +1,609 @@
+//! Decoding of the protobuf messages in the tail of an ORC file and in the footers of its
+//! stripes. Only the fields that are used by the reader are decoded, the other fields are
+//! skipped.
+use polars_error::{polars_bail, polars_err, PolarsResult};
+
+/// A field value in the protobuf wire format.
+enum Value<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32,
+}
+
+impl<'a> Value<'a> {
+    fn as_u64(&self) -> PolarsResult<u64> {
+        match self {
+            Value::Varint(v) => Ok(*v),
+            _ => Err(out_of_spec("expected a varint")),
+        }
+    }
+
+    fn as_i64(&self) -> PolarsResult<i64> {
+        self.as_u64().map(|v| v as i64)
+    }
+
+    /// A `sint32` or `sint64`.
+    fn as_zigzag(&self) -> PolarsResult<i64> {
+        self.as_u64().map(unzigzag)
+    }
+
+    fn as_f64(&self) -> PolarsResult<f64> {
+        match self {
+            Value::Fixed64(v) => Ok(f64::from_bits(*v)),
+            _ => Err(out_of_spec("expected a double")),
+        }
+    }
+
+    fn as_bytes(&self) -> PolarsResult<&'a [u8]> {
+        match self {
+            Value::Bytes(v) => Ok(v),
+            _ => Err(out_of_spec("expected a length-delimited field")),
+        }
+    }
+
+    fn as_string(&self) -> PolarsResult<String> {
+        let bytes = self.as_bytes()?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| out_of_spec("invalid utf-8 in a string"))
+    }
+
+    /// Repeated scalar fields can be packed or not.
+    fn extend_u64(&self, out: &mut Vec<u64>) -> PolarsResult<()> {
+        match self {
+            Value::Varint(v) => out.push(*v),
+            Value::Bytes(bytes) => {
+                let mut bytes = *bytes;
+                while !bytes.is_empty() {
+                    out.push(read_varint(&mut bytes)?);
+                }
+            },
+            _ => return Err(out_of_spec("expected a repeated varint")),
+        }
+        Ok(())
+    }
+}
+
+fn out_of_spec(msg: &str) -> polars_error::PolarsError {
+    polars_err!(ComputeError: "out-of-spec ORC metadata: {}", msg)
+}
+
+pub(super) fn unzigzag(v: u64) -> i64 {
+    ((v >> 1) as i64) ^ -((v & 1) as i64)
+}
+
+/// Read a base 128 varint.
+pub(super) fn read_varint(bytes: &mut &[u8]) -> PolarsResult<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let Some((&byte, rest)) = bytes.split_first() else {
+            polars_bail!(ComputeError: "out-of-spec ORC file: truncated varint");
+        };
+        *bytes = rest;
+        if shift >= 64 {
+            polars_bail!(ComputeError: "out-of-spec ORC file: varint is too long");
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> PolarsResult<&'a [u8]> {
+    if bytes.len() < n {
+        return Err(out_of_spec("truncated message"));
+    }
+    let (head, tail) = bytes.split_at(n);
+    *bytes = tail;
+    Ok(head)
+}
+
+/// Iterate over the fields of a message as `(field_number, value)`.
+fn for_each_field<'a>(
+    mut bytes: &'a [u8],
+    mut f: impl FnMut(u32, Value<'a>) -> PolarsResult<()>,
+) -> PolarsResult<()> {
+    while !bytes.is_empty() {
+        let key = read_varint(&mut bytes)?;
+        let value = match key & 7 {
+            0 => Value::Varint(read_varint(&mut bytes)?),
+            1 => Value::Fixed64(u64::from_le_bytes(take(&mut bytes, 8)?.try_into().unwrap())),
+            2 => {
+                let length = read_varint(&mut bytes)? as usize;
+                Value::Bytes(take(&mut bytes, length)?)
+            },
+            5 => {
+                take(&mut bytes, 4)?;
+                Value::Fixed32
+            },
+            wire_type => polars_bail!(
+                ComputeError: "out-of-spec ORC metadata: unsupported wire type {}", wire_type
+            ),
+        };
+        f((key >> 3) as u32, value)?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum CompressionKind {
+    None,
+    Zlib,
+    Snappy,
+    Lzo,
+    Lz4,
+    Zstd,
+}
+
+#[derive(Debug)]
+pub(super) struct PostScript {
+    pub footer_length: u64,
+    pub compression: CompressionKind,
+    pub compression_block_size: u64,
+    pub metadata_length: u64,
+}
+
+impl PostScript {
+    pub fn decode(bytes: &[u8]) -> PolarsResult<Self> {
+        let mut out = PostScript {
+            footer_length: 0,
+            compression: CompressionKind::None,
+            // The default of the ORC writers.
+            compression_block_size: 256 * 1024,
+            metadata_length: 0,
+        };
+        let mut magic = None;
+        for_each_field(bytes, |field, value| {
+            match field {
+                1 => out.footer_length = value.as_u64()?,
+                2 => {
+                    out.compression = match value.as_u64()? {
+                        0 => CompressionKind::None,
+                        1 => CompressionKind::Zlib,
+                        2 => CompressionKind::Snappy,
+                        3 => CompressionKind::Lzo,
+                        4 => CompressionKind::Lz4,
+                        5 => CompressionKind::Zstd,
+                        v => polars_bail!(ComputeError: "unknown ORC compression kind {}", v),
+                    }
+                },
+                3 => out.compression_block_size = value.as_u64()?,
+                5 => out.metadata_length = value.as_u64()?,
+                8000 => magic = Some(value.as_bytes()?),
+                _ => {},
+            }
+            Ok(())
+        })?;
+        if magic != Some(b"ORC") {
+            return Err(out_of_spec(
+                "the postscript does not end with the magic bytes",
+            ));
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct StripeInformation {
+    pub offset: u64,
+    pub index_length: u64,
+    pub data_length: u64,
+    pub footer_length: u64,
+    pub number_of_rows: u64,
+}
+
+impl StripeInformation {
+    fn decode(bytes: &[u8]) -> PolarsResult<Self> {
+        let mut out = Self::default();
+        for_each_field(bytes, |field, value| {
+            match field {
+                1 => out.offset = value.as_u64()?,
+                2 => out.index_length = value.as_u64()?,
+                3 => out.data_length = value.as_u64()?,
+                4 => out.footer_length = value.as_u64()?,
+                5 => out.number_of_rows = value.as_u64()?,
+                _ => {},
+            }
+            Ok(())
+        })?;
+        Ok(out)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TypeKind {
+    Boolean,
+    Byte,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+    Binary,
+    Timestamp,
+    List,
+    Map,
+    Struct,
+    Union,
+    Decimal,
+    Date,
+    Varchar,
+    Char,
+    TimestampInstant,
+}
+
+impl TypeKind {
+    fn from_u64(v: u64) -> PolarsResult<Self> {
+        use TypeKind::*;
+        Ok(match v {
+            0 => Boolean,
+            1 => Byte,
+            2 => Short,
+            3 => Int,
+            4 => Long,
+            5 => Float,
+            6 => Double,
+            7 => String,
+            8 => Binary,
+            9 => Timestamp,
+            10 => List,
+            11 => Map,
+            12 => Struct,
+            13 => Union,
+            14 => Decimal,
+            15 => Date,
+            16 => Varchar,
+            17 => Char,
+            18 => TimestampInstant,
+            v => polars_bail!(ComputeError: "unknown ORC type kind {}", v),
+        })
+    }
+}
+
+/// A node in the type tree of the file. The column ids are the indexes in the types of the
+/// footer, the root struct is column 0.
+#[derive(Debug, Clone)]
+pub(super) struct Type {
+    pub kind: TypeKind,
+    pub subtypes: Vec<u32>,
+    pub field_names: Vec<String>,
+    #[cfg(feature = "dtype-decimal")]
+    pub precision: Option<u64>,
+    #[cfg(feature = "dtype-decimal")]
+    pub scale: Option<u64>,
+}
+
+impl Type {
+    fn decode(bytes: &[u8]) -> PolarsResult<Self> {
+        let mut kind = TypeKind::Boolean;
+        let mut subtypes = vec![];
+        let mut field_names = vec![];
+        #[cfg(feature = "dtype-decimal")]
+        let mut precision = None;
+        #[cfg(feature = "dtype-decimal")]
+        let mut scale = None;
+        for_each_field(bytes, |field, value| {
+            match field {
+                1 => kind = TypeKind::from_u64(value.as_u64()?)?,
+                2 => value.extend_u64(&mut subtypes)?,
+                3 => field_names.push(value.as_string()?),
+                #[cfg(feature = "dtype-decimal")]
+                5 => precision = Some(value.as_u64()?),
+                #[cfg(feature = "dtype-decimal")]
+                6 => scale = Some(value.as_u64()?),
+                _ => {},
+            }
+            Ok(())
+        })?;
+        Ok(Self {
+            kind,
+            subtypes: subtypes.into_iter().map(|v| v as u32).collect(),
+            field_names,
+            #[cfg(feature = "dtype-decimal")]
+            precision,
+            #[cfg(feature = "dtype-decimal")]
+            scale,
+        })
+    }
+}
+
+/// The minimum and maximum value of a column.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum MinMax {
+    Int(i64, i64),
+    Double(f64, f64),
+    String(String, String),
+    Date(i32, i32),
+    /// Milliseconds since the unix epoch in UTC.
+    Timestamp(i64, i64),
+    /// The number of true values.
+    Boolean(u64),
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct ColumnStatistics {
+    pub number_of_values: Option<u64>,
+    pub min_max: Option<MinMax>,
+}
+
+impl ColumnStatistics {
+    fn decode(bytes: &[u8]) -> PolarsResult<Self> {
+        let mut out = Self::default();
+        for_each_field(bytes, |field, value| {
+            match field {
+                1 => out.number_of_values = Some(value.as_u64()?),
+                2 => {
+                    out.min_max = decode_min_max(value.as_bytes()?, |v| v.as_zigzag())?
+                        .map(|(min, max)| MinMax::Int(min, max))
+                },
+                3 => {
+                    out.min_max = decode_min_max(value.as_bytes()?, |v| v.as_f64())?
+                        .map(|(min, max)| MinMax::Double(min, max))
+                },
+                4 => {
+                    out.min_max = decode_min_max(value.as_bytes()?, |v| v.as_string())?
+                        .map(|(min, max)| MinMax::String(min, max))
+                },
+                5 => {
+                    let mut count = vec![];
+                    for_each_field(value.as_bytes()?, |field, value| {
+                        if field == 1 {
+                            value.extend_u64(&mut count)?;
+                        }
+                        Ok(())
+                    })?;
+                    out.min_max = count.first().map(|n_true| MinMax::Boolean(*n_true));
+                },
+                7 => {
+                    out.min_max = decode_min_max(value.as_bytes()?, |v| v.as_zigzag())?
+                        .map(|(min, max)| MinMax::Date(min as i32, max as i32))
+                },
+                9 => {
+                    // The minimum and maximum in UTC are fields 3 and 4.
+                    let mut min = None;
+                    let mut max = None;
+                    for_each_field(value.as_bytes()?, |field, value| {
+                        match field {
+                            3 => min = Some(value.as_zigzag()?),
+                            4 => max = Some(value.as_zigzag()?),
+                            _ => {},
+                        }
+                        Ok(())
+                    })?;
+                    out.min_max = min.zip(max).map(|(min, max)| MinMax::Timestamp(min, max));
+                },
+                _ => {},
+            }
+            Ok(())
+        })?;
+        Ok(out)
+    }
+}
+
+/// Decode the `minimum` and `maximum` fields, which are fields 1 and 2 of the statistics of
+/// every type.
+fn decode_min_max<T>(
+    bytes: &[u8],
+    decode: impl Fn(&Value) -> PolarsResult<T>,
+) -> PolarsResult<Option<(T, T)>> {
+    let mut min = None;
+    let mut max = None;
+    for_each_field(bytes, |field, value| {
+        match field {
+            1 => min = Some(decode(&value)?),
+            2 => max = Some(decode(&value)?),
+            _ => {},
+        }
+        Ok(())
+    })?;
+    Ok(min.zip(max))
+}
+
+#[derive(Debug)]
+pub(super) struct Footer {
+    pub stripes: Vec<StripeInformation>,
+    pub types: Vec<Type>,
+    pub number_of_rows: u64,
+}
+
+impl Footer {
+    pub fn decode(bytes: &[u8]) -> PolarsResult<Self> {
+        let mut stripes = vec![];
+        let mut types = vec![];
+        let mut number_of_rows = 0;
+        for_each_field(bytes, |field, value| {
+            match field {
+                3 => stripes.push(StripeInformation::decode(value.as_bytes()?)?),
+                4 => types.push(Type::decode(value.as_bytes()?)?),
+                6 => number_of_rows = value.as_u64()?,
+                _ => {},
+            }
+            Ok(())
+        })?;
+        Ok(Self {
+            stripes,
+            types,
+            number_of_rows,
+        })
+    }
+}
+
+/// The statistics of the columns of every stripe.
+#[derive(Debug, Default)]
+pub(super) struct Metadata {
+    pub stripe_statistics: Vec<Vec<ColumnStatistics>>,
+}
+
+impl Metadata {
+    pub fn decode(bytes: &[u8]) -> PolarsResult<Self> {
+        let mut stripe_statistics = vec![];
+        for_each_field(bytes, |field, value| {
+            if field == 1 {
+                let mut column_statistics = vec![];
+                for_each_field(value.as_bytes()?, |field, value| {
+                    if field == 1 {
+                        column_statistics.push(ColumnStatistics::decode(value.as_bytes()?)?);
+                    }
+                    Ok(())
+                })?;
+                stripe_statistics.push(column_statistics);
+            }
+            Ok(())
+        })?;
+        Ok(Self { stripe_statistics })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(super) enum StreamKind {
+    Present,
+    Data,
+    Length,
+    DictionaryData,
+    Secondary,
+    /// The indexes, bloom filters and encrypted streams aren't read.
+    Other,
+}
+
+#[derive(Debug)]
+pub(super) struct Stream {
+    pub kind: StreamKind,
+    pub column: u32,
+    pub length: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ColumnEncodingKind {
+    Direct,
+    Dictionary,
+    DirectV2,
+    DictionaryV2,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ColumnEncoding {
+    pub kind: ColumnEncodingKind,
+    pub dictionary_size: u64,
+}
+
+impl ColumnEncoding {
+    /// Whether the integers are run-length encoded with version 2 of the RLE.
+    pub fn is_v2(&self) -> bool {
+        matches!(
+            self.kind,
+            ColumnEncodingKind::DirectV2 | ColumnEncodingKind::DictionaryV2
+        )
+    }
+
+    pub fn is_dictionary(&self) -> bool {
+        matches!(
+            self.kind,
+            ColumnEncodingKind::Dictionary | ColumnEncodingKind::DictionaryV2
+        )
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct StripeFooter {
+    pub streams: Vec<Stream>,
+    pub columns: Vec<ColumnEncoding>,
+}
+
+impl StripeFooter {
+    pub fn decode(bytes: &[u8]) -> PolarsResult<Self> {
+        let mut streams = vec![];
+        let mut columns = vec![];
+        for_each_field(bytes, |field, value| {
+            match field {
+                1 => {
+                    let mut kind = StreamKind::Other;
+                    let mut column = 0;
+                    let mut length = 0;
+                    for_each_field(value.as_bytes()?, |field, value| {
+                        match field {
+                            1 => {
+                                kind = match value.as_u64()? {
+                                    0 => StreamKind::Present,
+                                    1 => StreamKind::Data,
+                                    2 => StreamKind::Length,
+                                    3 => StreamKind::DictionaryData,
+                                    5 => StreamKind::Secondary,
+                                    _ => StreamKind::Other,
+                                }
+                            },
+                            2 => column = value.as_u64()? as u32,
+                            3 => length = value.as_u64()?,
+                            _ => {},
+                        }
+                        Ok(())
+                    })?;
+                    streams.push(Stream {
+                        kind,
+                        column,
+                        length,
+                    });
+                },
+                2 => {
+                    let mut kind = ColumnEncodingKind::Direct;
+                    let mut dictionary_size = 0;
+                    for_each_field(value.as_bytes()?, |field, value| {
+                        match field {
+                            1 => {
+                                kind = match value.as_i64()? {
+                                    0 => ColumnEncodingKind::Direct,
+                                    1 => ColumnEncodingKind::Dictionary,
+                                    2 => ColumnEncodingKind::DirectV2,
+                                    3 => ColumnEncodingKind::DictionaryV2,
+                                    v => polars_bail!(
+                                        ComputeError: "unknown ORC column encoding {}", v
+                                    ),
+                                }
+                            },
+                            2 => dictionary_size = value.as_u64()?,
+                            _ => {},
+                        }
+                        Ok(())
+                    })?;
+                    columns.push(ColumnEncoding {
+                        kind,
+                        dictionary_size,
+                    });
+                },
+                _ => {},
+            }
+            Ok(())
+        })?;
+        Ok(Self { streams, columns })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_postscript() {
+        // footerLength: 300, compression: ZLIB, compressionBlockSize: 65536, magic: "ORC"
+        let bytes = [
+            0x08, 0xac, 0x02, 0x10, 0x01, 0x18, 0x80, 0x80, 0x04, 0x82, 0xf4, 0x03, 0x03, b'O',
+            b'R', b'C',
+        ];
+        let postscript = PostScript::decode(&bytes).unwrap();
+        assert_eq!(postscript.footer_length, 300);
+        assert_eq!(postscript.compression, CompressionKind::Zlib);
+        assert_eq!(postscript.compression_block_size, 65536);
+
+        assert!(PostScript::decode(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_unzigzag() {
+        assert_eq!(unzigzag(0), 0);
+        assert_eq!(unzigzag(1), -1);
+        assert_eq!(unzigzag(2), 1);
+        assert_eq!(unzigzag(u64::MAX), i64::MIN);
+    }
+}
@@ -0,0 +1,888 @@
+use std::io::SeekFrom;
+
+use arrow::array::ListArray;
+use arrow::offset::Offsets;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_core::POOL;
+use rayon::prelude::*;
+
+use super::compression::decompress;
+#[cfg(feature = "dtype-decimal")]
+use super::decode::decode_i128_varints;
+use super::decode::{decode_booleans, decode_bytes, decode_ints};
+use super::proto::{
+    ColumnEncoding, ColumnStatistics, CompressionKind, Footer, Metadata, MinMax, PostScript,
+    StreamKind, StripeFooter, StripeInformation, Type, TypeKind,
+};
+use crate::mmap::MmapBytesReader;
+use crate::predicates::{apply_predicate, BatchStats, ColumnStats, PhysicalIoExpr};
+use crate::prelude::*;
+use crate::utils::get_reader_bytes;
+
+/// The timestamps of ORC are stored as seconds since 2015-01-01 00:00:00.
+const ORC_TIMESTAMP_EPOCH: i64 = 1_420_070_400;
+
+/// Read an ORC file into a DataFrame.
+///
+/// Stripes whose statistics show that no row matches the predicate are skipped.
+///
+/// # Example
+/// ```
+/// use polars_core::prelude::*;
+/// use std::fs::File;
+/// use polars_io::orc::OrcReader;
+/// use polars_io::SerReader;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     let file = File::open("file.orc").expect("file not found");
+///
+///     OrcReader::new(file)
+///         .with_columns(Some(vec!["a".into()]))
+///         .finish()
+/// }
+/// ```
+#[must_use]
+pub struct OrcReader<R: MmapBytesReader> {
+    reader: R,
+    /// Aggregates chunks afterwards to a single chunk.
+    rechunk: bool,
+    n_rows: Option<usize>,
+    projection: Option<Vec<usize>>,
+    columns: Option<Vec<String>>,
+    predicate: Option<Arc<dyn PhysicalIoExpr>>,
+    tail: Option<FileTail>,
+}
+
+/// The metadata at the end of an ORC file.
+struct FileTail {
+    postscript: PostScript,
+    footer: Footer,
+    metadata: Metadata,
+    schema: SchemaRef,
+}
+
+impl FileTail {
+    fn decode(bytes: &[u8]) -> PolarsResult<Self> {
+        polars_ensure!(
+            bytes.len() > 3 && bytes.starts_with(b"ORC"),
+            ComputeError: "not an ORC file: the file does not start with the magic bytes"
+        );
+        let postscript_length = *bytes.last().unwrap() as usize;
+        let postscript_start = (bytes.len() - 1)
+            .checked_sub(postscript_length)
+            .ok_or_else(
+                || polars_err!(ComputeError: "out-of-spec ORC file: invalid postscript length"),
+            )?;
+        let postscript = PostScript::decode(&bytes[postscript_start..bytes.len() - 1])?;
+
+        let footer_start = postscript_start
+            .checked_sub(postscript.footer_length as usize)
+            .ok_or_else(
+                || polars_err!(ComputeError: "out-of-spec ORC file: invalid footer length"),
+            )?;
+        let metadata_start = footer_start
+            .checked_sub(postscript.metadata_length as usize)
+            .ok_or_else(
+                || polars_err!(ComputeError: "out-of-spec ORC file: invalid metadata length"),
+            )?;
+        let block_size = postscript.compression_block_size as usize;
+        let footer = Footer::decode(&decompress(
+            &bytes[footer_start..postscript_start],
+            postscript.compression,
+            block_size,
+        )?)?;
+        let metadata = Metadata::decode(&decompress(
+            &bytes[metadata_start..footer_start],
+            postscript.compression,
+            block_size,
+        )?)?;
+
+        let root = footer
+            .types
+            .first()
+            .ok_or_else(|| polars_err!(ComputeError: "out-of-spec ORC file: no types"))?;
+        polars_ensure!(
+            root.kind == TypeKind::Struct,
+            ComputeError: "the root type of an ORC file must be a struct, got {:?}", root.kind
+        );
+        let schema = root
+            .field_names
+            .iter()
+            .zip(&root.subtypes)
+            .map(|(name, id)| Ok(Field::new(name.into(), to_dtype(&footer.types, *id)?)))
+            .collect::<PolarsResult<Schema>>()?;
+
+        Ok(Self {
+            postscript,
+            footer,
+            metadata,
+            schema: Arc::new(schema),
+        })
+    }
+
+    /// The ids of the top-level columns.
+    fn column_ids(&self) -> &[u32] {
+        &self.footer.types[0].subtypes
+    }
+}
+
+fn get_type(types: &[Type], id: u32) -> PolarsResult<&Type> {
+    types
+        .get(id as usize)
+        .ok_or_else(|| polars_err!(ComputeError: "out-of-spec ORC file: unknown type id {}", id))
+}
+
+/// The Polars data type of the ORC column `id`.
+fn to_dtype(types: &[Type], id: u32) -> PolarsResult<DataType> {
+    let ty = get_type(types, id)?;
+    let child = |i: usize| {
+        let id = ty.subtypes.get(i).ok_or_else(
+            || polars_err!(ComputeError: "out-of-spec ORC file: missing subtype of {:?}", ty.kind),
+        )?;
+        to_dtype(types, *id)
+    };
+    Ok(match ty.kind {
+        TypeKind::Boolean => DataType::Boolean,
+        TypeKind::Byte => DataType::Int8,
+        TypeKind::Short => DataType::Int16,
+        TypeKind::Int => DataType::Int32,
+        TypeKind::Long => DataType::Int64,
+        TypeKind::Float => DataType::Float32,
+        TypeKind::Double => DataType::Float64,
+        TypeKind::String | TypeKind::Varchar | TypeKind::Char => DataType::String,
+        TypeKind::Binary => DataType::Binary,
+        TypeKind::Timestamp | TypeKind::TimestampInstant => {
+            DataType::Datetime(TimeUnit::Nanoseconds, None)
+        },
+        TypeKind::Date => DataType::Date,
+        #[cfg(feature = "dtype-decimal")]
+        TypeKind::Decimal => DataType::Decimal(
+            ty.precision.map(|p| p as usize),
+            Some(ty.scale.unwrap_or(0) as usize),
+        ),
+        TypeKind::List => DataType::List(Box::new(child(0)?)),
+        TypeKind::Map => DataType::List(Box::new(DataType::Struct(vec![
+            Field::new("key".into(), child(0)?),
+            Field::new("value".into(), child(1)?),
+        ]))),
+        TypeKind::Struct => DataType::Struct(
+            ty.field_names
+                .iter()
+                .zip(&ty.subtypes)
+                .map(|(name, id)| Ok(Field::new(name.into(), to_dtype(types, *id)?)))
+                .collect::<PolarsResult<_>>()?,
+        ),
+        kind => {
+            polars_bail!(ComputeError: "reading ORC columns of type {:?} is not supported", kind)
+        },
+    })
+}
+
+/// The streams of a stripe.
+struct Stripe<'a> {
+    streams: PlHashMap<(u32, StreamKind), &'a [u8]>,
+    encodings: Vec<ColumnEncoding>,
+    compression: CompressionKind,
+    block_size: usize,
+}
+
+impl<'a> Stripe<'a> {
+    fn new(
+        bytes: &'a [u8],
+        info: &StripeInformation,
+        postscript: &PostScript,
+    ) -> PolarsResult<Self> {
+        let block_size = postscript.compression_block_size as usize;
+        let start = info.offset as usize;
+        let footer_start = start + (info.index_length + info.data_length) as usize;
+        let footer_end = footer_start + info.footer_length as usize;
+        polars_ensure!(
+            footer_end <= bytes.len(),
+            ComputeError: "out-of-spec ORC file: stripe is out of bounds"
+        );
+        let footer = StripeFooter::decode(&decompress(
+            &bytes[footer_start..footer_end],
+            postscript.compression,
+            block_size,
+        )?)?;
+
+        // The streams are stored back to back in the order of the footer.
+        let mut streams = PlHashMap::with_capacity(footer.streams.len());
+        let mut offset = start;
+        for stream in &footer.streams {
+            let end = offset + stream.length as usize;
+            polars_ensure!(
+                end <= footer_start,
+                ComputeError: "out-of-spec ORC file: stream is out of bounds"
+            );
+            if stream.kind != StreamKind::Other {
+                streams.insert((stream.column, stream.kind), &bytes[offset..end]);
+            }
+            offset = end;
+        }
+
+        Ok(Self {
+            streams,
+            encodings: footer.columns,
+            compression: postscript.compression,
+            block_size,
+        })
+    }
+
+    fn stream(&self, column: u32, kind: StreamKind) -> PolarsResult<Option<Vec<u8>>> {
+        self.streams
+            .get(&(column, kind))
+            .map(|bytes| decompress(bytes, self.compression, self.block_size))
+            .transpose()
+    }
+
+    fn required_stream(&self, column: u32, kind: StreamKind) -> PolarsResult<Vec<u8>> {
+        self.stream(column, kind)?.ok_or_else(
+            || polars_err!(ComputeError: "out-of-spec ORC file: missing {:?} stream of column {}", kind, column),
+        )
+    }
+
+    fn encoding(&self, column: u32) -> PolarsResult<ColumnEncoding> {
+        self.encodings.get(column as usize).copied().ok_or_else(
+            || polars_err!(ComputeError: "out-of-spec ORC file: missing encoding of column {}", column),
+        )
+    }
+
+    fn ints(
+        &self,
+        column: u32,
+        kind: StreamKind,
+        n: usize,
+        signed: bool,
+    ) -> PolarsResult<Vec<i64>> {
+        let bytes = self.required_stream(column, kind)?;
+        decode_ints(&bytes, n, signed, self.encoding(column)?.is_v2())
+    }
+}
+
+/// Read the `n` values of the column `id` of a stripe. The values of a nested column are only
+/// stored for the rows in which its parent is valid, so `n` is the number of valid values of the
+/// parent.
+fn read_column(
+    stripe: &Stripe,
+    types: &[Type],
+    id: u32,
+    name: PlSmallStr,
+    n: usize,
+) -> PolarsResult<Series> {
+    let ty = get_type(types, id)?;
+
+    // Only the valid values are stored, so they are decoded densely and then spread over the
+    // rows with a gather.
+    let validity = stripe
+        .stream(id, StreamKind::Present)?
+        .map(|bytes| decode_booleans(&bytes, n))
+        .transpose()?;
+    let n_valid = validity
+        .as_ref()
+        .map_or(n, |validity| validity.iter().filter(|v| **v).count());
+
+    let values = read_values(stripe, types, ty, id, name, n_valid)?;
+    polars_ensure!(
+        values.len() == n_valid,
+        ComputeError: "out-of-spec ORC file: expected {} values in column {}, got {}", n_valid, id, values.len()
+    );
+
+    match validity {
+        Some(validity) if n_valid < n => {
+            let mut idx = 0;
+            let indices = IdxCa::from_iter_options(
+                PlSmallStr::EMPTY,
+                validity.iter().map(|is_valid| {
+                    is_valid.then(|| {
+                        idx += 1;
+                        (idx - 1) as IdxSize
+                    })
+                }),
+            );
+            values.take(&indices)
+        },
+        _ => Ok(values),
+    }
+}
+
+fn read_values(
+    stripe: &Stripe,
+    types: &[Type],
+    ty: &Type,
+    id: u32,
+    name: PlSmallStr,
+    n: usize,
+) -> PolarsResult<Series> {
+    let encoding = stripe.encoding(id)?;
+    Ok(match ty.kind {
+        TypeKind::Boolean => {
+            let values = decode_booleans(&stripe.required_stream(id, StreamKind::Data)?, n)?;
+            BooleanChunked::from_slice(name, &values).into_series()
+        },
+        TypeKind::Byte => {
+            let values = decode_bytes(&stripe.required_stream(id, StreamKind::Data)?, n)?;
+            Int8Chunked::from_iter_values(name, values.into_iter().map(|v| v as i8)).into_series()
+        },
+        TypeKind::Short => {
+            let values = stripe.ints(id, StreamKind::Data, n, true)?;
+            Int16Chunked::from_iter_values(name, values.into_iter().map(|v| v as i16)).into_series()
+        },
+        TypeKind::Int => {
+            let values = stripe.ints(id, StreamKind::Data, n, true)?;
+            Int32Chunked::from_iter_values(name, values.into_iter().map(|v| v as i32)).into_series()
+        },
+        TypeKind::Long => {
+            Int64Chunked::from_vec(name, stripe.ints(id, StreamKind::Data, n, true)?).into_series()
+        },
+        TypeKind::Float => {
+            let bytes = stripe.required_stream(id, StreamKind::Data)?;
+            polars_ensure!(bytes.len() >= n * 4, ComputeError: "out-of-spec ORC file: truncated floats");
+            Float32Chunked::from_iter_values(
+                name,
+                bytes
+                    .chunks_exact(4)
+                    .take(n)
+                    .map(|v| f32::from_le_bytes(v.try_into().unwrap())),
+            )
+            .into_series()
+        },
+        TypeKind::Double => {
+            let bytes = stripe.required_stream(id, StreamKind::Data)?;
+            polars_ensure!(bytes.len() >= n * 8, ComputeError: "out-of-spec ORC file: truncated doubles");
+            Float64Chunked::from_iter_values(
+                name,
+                bytes
+                    .chunks_exact(8)
+                    .take(n)
+                    .map(|v| f64::from_le_bytes(v.try_into().unwrap())),
+            )
+            .into_series()
+        },
+        TypeKind::String | TypeKind::Varchar | TypeKind::Char | TypeKind::Binary => {
+            let data = stripe.required_stream(id, StreamKind::Data)?;
+            // The values borrow from the dictionary.
+            let dictionary_data;
+            let values: Vec<&[u8]> = if encoding.is_dictionary() {
+                dictionary_data = stripe.required_stream(id, StreamKind::DictionaryData)?;
+                let lengths = stripe.ints(
+                    id,
+                    StreamKind::Length,
+                    encoding.dictionary_size as usize,
+                    false,
+                )?;
+                let dictionary = split_by_lengths(&dictionary_data, &lengths)?;
+                decode_ints(&data, n, false, encoding.is_v2())?
+                    .into_iter()
+                    .map(|idx| {
+                        dictionary.get(idx as usize).copied().ok_or_else(
+                            || polars_err!(ComputeError: "out-of-spec ORC file: dictionary index out of bounds"),
+                        )
+                    })
+                    .collect::<PolarsResult<_>>()?
+            } else {
+                let lengths = stripe.ints(id, StreamKind::Length, n, false)?;
+                split_by_lengths(&data, &lengths)?
+            };
+            if ty.kind == TypeKind::Binary {
+                BinaryChunked::from_iter_values(name, values.into_iter()).into_series()
+            } else {
+                let values = values
+                    .into_iter()
+                    .map(|v| {
+                        std::str::from_utf8(v).map_err(
+                            |_| polars_err!(ComputeError: "invalid utf-8 in ORC string column {}", id),
+                        )
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                StringChunked::from_iter_values(name, values.into_iter()).into_series()
+            }
+        },
+        TypeKind::Timestamp | TypeKind::TimestampInstant => {
+            let seconds = stripe.ints(id, StreamKind::Data, n, true)?;
+            let nanos = stripe.ints(id, StreamKind::Secondary, n, false)?;
+            let values = seconds.into_iter().zip(nanos).map(|(seconds, nanos)| {
+                // The trailing decimal zeros of the nanoseconds are stored in the lowest 3 bits.
+                let zeros = nanos & 7;
+                let mut nanos = nanos >> 3;
+                if zeros != 0 {
+                    nanos *= 10i64.pow(zeros as u32 + 1);
+                }
+                (seconds + ORC_TIMESTAMP_EPOCH) * 1_000_000_000 + nanos
+            });
+            Int64Chunked::from_iter_values(name, values)
+                .into_datetime(TimeUnit::Nanoseconds, None)
+                .into_series()
+        },
+        TypeKind::Date => {
+            let values = stripe.ints(id, StreamKind::Data, n, true)?;
+            Int32Chunked::from_iter_values(name, values.into_iter().map(|v| v as i32))
+                .into_date()
+                .into_series()
+        },
+        #[cfg(feature = "dtype-decimal")]
+        TypeKind::Decimal => {
+            let scale = ty.scale.unwrap_or(0) as i64;
+            let values = decode_i128_varints(&stripe.required_stream(id, StreamKind::Data)?, n)?;
+            let scales = stripe.ints(id, StreamKind::Secondary, n, true)?;
+            // Every value has its own scale, which is rescaled to the scale of the column.
+            let values = values.into_iter().zip(scales).map(|(value, value_scale)| {
+                match scale - value_scale {
+                    0 => value,
+                    diff if diff > 0 => value * 10i128.pow(diff as u32),
+                    diff => value / 10i128.pow(-diff as u32),
+                }
+            });
+            Int128Chunked::from_iter_values(name, values)
+                .into_decimal_unchecked(ty.precision.map(|p| p as usize), scale as usize)
+                .into_series()
+        },
+        TypeKind::List | TypeKind::Map => {
+            let lengths = stripe.ints(id, StreamKind::Length, n, false)?;
+            let n_values = lengths.iter().map(|v| *v as usize).sum();
+            let values = if ty.kind == TypeKind::List {
+                read_column(stripe, types, ty.subtypes[0], PlSmallStr::EMPTY, n_values)?
+            } else {
+                let key = read_column(stripe, types, ty.subtypes[0], "key".into(), n_values)?;
+                let value = read_column(stripe, types, ty.subtypes[1], "value".into(), n_values)?;
+                StructChunked::from_series(PlSmallStr::EMPTY, &[key, value])?.into_series()
+            };
+            let values = values.rechunk().to_arrow(0, CompatLevel::newest());
+            let offsets = Offsets::<i64>::try_from_lengths(lengths.iter().map(|v| *v as usize))?;
+            let array = ListArray::<i64>::new(
+                ListArray::<i64>::default_datatype(values.dtype().clone()),
+                offsets.into(),
+                values,
+                None,
+            );
+            Series::try_from((name, Box::new(array) as ArrayRef))?
+        },
+        TypeKind::Struct => {
+            let fields = ty
+                .field_names
+                .iter()
+                .zip(&ty.subtypes)
+                .map(|(field_name, id)| read_column(stripe, types, *id, field_name.into(), n))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            StructChunked::from_series(name, &fields)?.into_series()
+        },
+        kind => {
+            polars_bail!(ComputeError: "reading ORC columns of type {:?} is not supported", kind)
+        },
+    })
+}
+
+fn split_by_lengths<'a>(mut bytes: &'a [u8], lengths: &[i64]) -> PolarsResult<Vec<&'a [u8]>> {
+    lengths
+        .iter()
+        .map(|length| {
+            let length = *length as usize;
+            polars_ensure!(
+                length <= bytes.len(),
+                ComputeError: "out-of-spec ORC file: truncated string data"
+            );
+            let (value, rest) = bytes.split_at(length);
+            bytes = rest;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// The statistics of a stripe, to be evaluated by the predicate.
+fn collect_statistics(
+    column_statistics: &[ColumnStatistics],
+    tail: &FileTail,
+    n_rows: u64,
+) -> PolarsResult<BatchStats> {
+    let stats = tail
+        .schema
+        .iter_fields()
+        .zip(tail.column_ids())
+        .map(|(field, id)| {
+            let Some(statistics) = column_statistics.get(*id as usize) else {
+                return Ok(ColumnStats::from_field(field));
+            };
+            let name = field.name().clone();
+            let null_count = statistics
+                .number_of_values
+                .map(|n| Series::new(name.clone(), [n_rows.saturating_sub(n) as IdxSize]));
+            let min_max = match &statistics.min_max {
+                Some(MinMax::Int(min, max)) => Some((
+                    Series::new(name.clone(), [*min]),
+                    Series::new(name.clone(), [*max]),
+                )),
+                Some(MinMax::Double(min, max)) => Some((
+                    Series::new(name.clone(), [*min]),
+                    Series::new(name.clone(), [*max]),
+                )),
+                Some(MinMax::String(min, max)) => Some((
+                    Series::new(name.clone(), [min.as_str()]),
+                    Series::new(name.clone(), [max.as_str()]),
+                )),
+                Some(MinMax::Date(min, max)) => Some((
+                    Series::new(name.clone(), [*min]),
+                    Series::new(name.clone(), [*max]),
+                )),
+                // The statistics have a precision of milliseconds, so the maximum is rounded up.
+                Some(MinMax::Timestamp(min, max)) => Some((
+                    Series::new(name.clone(), [*min * 1_000_000]),
+                    Series::new(name.clone(), [*max * 1_000_000 + 999_999]),
+                )),
+                Some(MinMax::Boolean(n_true)) => statistics.number_of_values.map(|n| {
+                    (
+                        Series::new(name.clone(), [*n_true == n]),
+                        Series::new(name.clone(), [*n_true > 0]),
+                    )
+                }),
+                None => None,
+            };
+            // Statistics whose type doesn't match the column are ignored.
+            let min_max = min_max.and_then(|(min, max)| {
+                Some((
+                    min.strict_cast(field.dtype()).ok()?,
+                    max.strict_cast(field.dtype()).ok()?,
+                ))
+            });
+            let (min, max) = min_max.unzip();
+            Ok(ColumnStats::new(field, null_count, min, max))
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    Ok(BatchStats::new(
+        tail.schema.clone(),
+        stats,
+        Some(n_rows as usize),
+    ))
+}
+
+fn read_this_stripe(
+    predicate: Option<&dyn PhysicalIoExpr>,
+    tail: &FileTail,
+    stripe_idx: usize,
+) -> PolarsResult<bool> {
+    let Some(predicate) = predicate.and_then(|p| p.as_stats_evaluator()) else {
+        return Ok(true);
+    };
+    let Some(column_statistics) = tail.metadata.stripe_statistics.get(stripe_idx) else {
+        return Ok(true);
+    };
+    let n_rows = tail.footer.stripes[stripe_idx].number_of_rows;
+    let stats = collect_statistics(column_statistics, tail, n_rows)?;
+    let should_read = predicate.should_read(&stats);
+    // an ORC file may not have statistics of all columns
+    if matches!(should_read, Ok(false)) {
+        return Ok(false);
+    } else if !matches!(should_read, Err(PolarsError::ColumnNotFound(_))) {
+        let _ = should_read?;
+    }
+    Ok(true)
+}
+
+impl<R: MmapBytesReader> OrcReader<R> {
+    fn get_tail(&mut self) -> PolarsResult<&FileTail> {
+        if self.tail.is_none() {
+            let position = self.reader.stream_position()?;
+            let tail = FileTail::decode(&get_reader_bytes(&mut self.reader)?)?;
+            self.reader.seek(SeekFrom::Start(position))?;
+            self.tail = Some(tail);
+        }
+        Ok(self.tail.as_ref().unwrap())
+    }
+
+    /// Get the schema of the ORC file.
+    pub fn schema(&mut self) -> PolarsResult<SchemaRef> {
+        Ok(self.get_tail()?.schema.clone())
+    }
+
+    /// Number of rows in the ORC file.
+    pub fn num_rows(&mut self) -> PolarsResult<usize> {
+        Ok(self.get_tail()?.footer.number_of_rows as usize)
+    }
+
+    /// Stop reading when `n` rows are read.
+    pub fn with_n_rows(mut self, num_rows: Option<usize>) -> Self {
+        self.n_rows = num_rows;
+        self
+    }
+
+    /// Columns to select/ project
+    pub fn with_columns(mut self, columns: Option<Vec<String>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Set the reader's column projection. This counts from 0, meaning that
+    /// `vec![0, 4]` would select the 1st and 5th column.
+    pub fn with_projection(mut self, projection: Option<Vec<usize>>) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// Only keep the rows for which the predicate is true. Stripes for which the statistics
+    /// show that the predicate is false for every row are not read.
+    pub fn with_predicate(mut self, predicate: Option<Arc<dyn PhysicalIoExpr>>) -> Self {
+        self.predicate = predicate;
+        self
+    }
+}
+
+impl<R: MmapBytesReader> SerReader<R> for OrcReader<R> {
+    fn new(reader: R) -> Self {
+        OrcReader {
+            reader,
+            rechunk: true,
+            n_rows: None,
+            projection: None,
+            columns: None,
+            predicate: None,
+            tail: None,
+        }
+    }
+
+    fn set_rechunk(mut self, rechunk: bool) -> Self {
+        self.rechunk = rechunk;
+        self
+    }
+
+    fn finish(mut self) -> PolarsResult<DataFrame> {
+        self.get_tail()?;
+        let tail = self.tail.take().unwrap();
+
+        let projection = match (&self.columns, self.projection.take()) {
+            (Some(columns), _) => columns
+                .iter()
+                .map(|name| tail.schema.try_index_of(name))
+                .collect::<PolarsResult<Vec<_>>>()?,
+            (None, Some(projection)) => projection,
+            (None, None) => (0..tail.schema.len()).collect(),
+        };
+        let projected_schema = projection
+            .iter()
+            .map(|i| {
+                let (name, dtype) = tail.schema.get_at_index(*i).ok_or_else(
+                    || polars_err!(ComputeError: "projection index {} is out of bounds", i),
+                )?;
+                Ok(Field::new(name.clone(), dtype.clone()))
+            })
+            .collect::<PolarsResult<Schema>>()?;
+
+        let bytes = get_reader_bytes(&mut self.reader)?;
+        let predicate = self.predicate.as_deref();
+        let n_rows_limit = self.n_rows.unwrap_or(usize::MAX);
+
+        let mut dfs = vec![];
+        let mut n_rows_read = 0;
+        for (stripe_idx, info) in tail.footer.stripes.iter().enumerate() {
+            if n_rows_read >= n_rows_limit {
+                break;
+            }
+            if !read_this_stripe(predicate, &tail, stripe_idx)? {
+                continue;
+            }
+
+            let stripe = Stripe::new(&bytes, info, &tail.postscript)?;
+            let n = info.number_of_rows as usize;
+            let columns = POOL.install(|| {
+                projection
+                    .par_iter()
+                    .map(|i| {
+                        let id = tail.column_ids()[*i];
+                        let name = tail.schema.get_at_index(*i).unwrap().0.clone();
+                        read_column(&stripe, &tail.footer.types, id, name, n)
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()
+            })?;
+            let mut df = unsafe { DataFrame::new_no_checks(columns) };
+
+            apply_predicate(&mut df, predicate, true)?;
+            if n_rows_read + df.height() > n_rows_limit {
+                df = df.slice(0, n_rows_limit - n_rows_read);
+            }
+            n_rows_read += df.height();
+            dfs.push(df);
+        }
+
+        if dfs.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&projected_schema));
+        }
+        let mut df = accumulate_dataframes_vertical_unchecked(dfs);
+        if self.rechunk {
+            df.as_single_chunk_par();
+        }
+        Ok(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn varint(out: &mut Vec<u8>, mut v: u64) {
+        while v >= 0x80 {
+            out.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        out.push(v as u8);
+    }
+
+    fn field_varint(out: &mut Vec<u8>, field: u64, v: u64) {
+        varint(out, field << 3);
+        varint(out, v);
+    }
+
+    fn field_bytes(out: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+        varint(out, (field << 3) | 2);
+        varint(out, bytes.len() as u64);
+        out.extend_from_slice(bytes);
+    }
+
+    fn message(fields: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+        let mut out = vec![];
+        fields(&mut out);
+        out
+    }
+
+    /// An uncompressed file with an int column `a = [1, 2, 3]` and a string column
+    /// `b = ["x", "yy", null]` in a single stripe.
+    fn orc_file() -> Vec<u8> {
+        // (column, kind, bytes) of the streams, which are RLE v1 encoded.
+        let streams: [(u64, u64, &[u8]); 4] = [
+            (1, 1, &[0xfd, 0x02, 0x04, 0x06]),
+            (2, 0, &[0xff, 0xc0]),
+            (2, 2, &[0xfe, 0x01, 0x02]),
+            (2, 1, b"xyy"),
+        ];
+        let mut file = b"ORC".to_vec();
+        let data_length = streams.iter().map(|s| s.2.len()).sum::<usize>() as u64;
+        for (_, _, bytes) in &streams {
+            file.extend_from_slice(bytes);
+        }
+        let stripe_footer = message(|out| {
+            for (column, kind, bytes) in &streams {
+                let stream = message(|out| {
+                    field_varint(out, 1, *kind);
+                    field_varint(out, 2, *column);
+                    field_varint(out, 3, bytes.len() as u64);
+                });
+                field_bytes(out, 1, &stream);
+            }
+            for _ in 0..3 {
+                field_bytes(out, 2, &message(|out| field_varint(out, 1, 0)));
+            }
+        });
+        file.extend_from_slice(&stripe_footer);
+
+        let metadata = message(|out| {
+            let stripe_statistics = message(|out| {
+                field_bytes(out, 1, &message(|out| field_varint(out, 1, 3)));
+                field_bytes(
+                    out,
+                    1,
+                    &message(|out| {
+                        field_varint(out, 1, 3);
+                        // sint64 minimum 1 and maximum 3.
+                        field_bytes(
+                            out,
+                            2,
+                            &message(|out| {
+                                field_varint(out, 1, 2);
+                                field_varint(out, 2, 6);
+                            }),
+                        );
+                    }),
+                );
+                field_bytes(
+                    out,
+                    1,
+                    &message(|out| {
+                        field_varint(out, 1, 2);
+                        field_bytes(
+                            out,
+                            4,
+                            &message(|out| {
+                                field_bytes(out, 1, b"x");
+                                field_bytes(out, 2, b"yy");
+                            }),
+                        );
+                    }),
+                );
+            });
+            field_bytes(out, 1, &stripe_statistics);
+        });
+        file.extend_from_slice(&metadata);
+
+        let footer = message(|out| {
+            let stripe = message(|out| {
+                field_varint(out, 1, 3);
+                field_varint(out, 2, 0);
+                field_varint(out, 3, data_length);
+                field_varint(out, 4, stripe_footer.len() as u64);
+                field_varint(out, 5, 3);
+            });
+            field_bytes(out, 3, &stripe);
+            let root = message(|out| {
+                field_varint(out, 1, 12);
+                field_bytes(out, 2, &[1, 2]);
+                field_bytes(out, 3, b"a");
+                field_bytes(out, 3, b"b");
+            });
+            field_bytes(out, 4, &root);
+            field_bytes(out, 4, &message(|out| field_varint(out, 1, 3)));
+            field_bytes(out, 4, &message(|out| field_varint(out, 1, 7)));
+            field_varint(out, 6, 3);
+        });
+        file.extend_from_slice(&footer);
+
+        let postscript = message(|out| {
+            field_varint(out, 1, footer.len() as u64);
+            field_varint(out, 2, 0);
+            field_varint(out, 5, metadata.len() as u64);
+            field_bytes(out, 8000, b"ORC");
+        });
+        file.extend_from_slice(&postscript);
+        file.push(postscript.len() as u8);
+        file
+    }
+
+    #[test]
+    fn test_read_orc() -> PolarsResult<()> {
+        let mut reader = OrcReader::new(Cursor::new(orc_file()));
+        let schema = reader.schema()?;
+        assert_eq!(schema.get("a"), Some(&DataType::Int32));
+        assert_eq!(schema.get("b"), Some(&DataType::String));
+        assert_eq!(reader.num_rows()?, 3);
+
+        let df = reader.finish()?;
+        let expected = df![
+            "a" => [1i32, 2, 3],
+            "b" => [Some("x"), Some("yy"), None],
+        ]?;
+        assert!(df.equals_missing(&expected));
+
+        let df = OrcReader::new(Cursor::new(orc_file()))
+            .with_columns(Some(vec!["b".into()]))
+            .with_n_rows(Some(2))
+            .finish()?;
+        assert!(df.equals(&expected.select(["b"])?.head(Some(2))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_orc_stripe_statistics() -> PolarsResult<()> {
+        let tail = FileTail::decode(&orc_file())?;
+        let stats = collect_statistics(&tail.metadata.stripe_statistics[0], &tail, 3)?;
+
+        let a = stats.get_stats("a")?;
+        assert_eq!(a.null_count(), Some(0));
+        assert!(a.to_min().unwrap().equals(&Series::new("a".into(), [1i32])));
+        assert!(a.to_max().unwrap().equals(&Series::new("a".into(), [3i32])));
+
+        let b = stats.get_stats("b")?;
+        assert_eq!(b.null_count(), Some(1));
+        assert!(b.to_max().unwrap().equals(&Series::new("b".into(), ["yy"])));
+        Ok(())
+    }
+}
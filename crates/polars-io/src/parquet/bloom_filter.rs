@@ -0,0 +1,113 @@
+//! Split block bloom filters of Parquet columns.
+//!
+//! The values are hashed as their Parquet physical type, e.g. an `Int8` column is hashed as
+//! `INT32`, so that the bloom filters can also be used by other readers.
+use arrow::array::{Array, BinaryArray, BinaryViewArray, PrimitiveArray, Utf8Array, Utf8ViewArray};
+use arrow::datatypes::{ArrowDataType, PhysicalType};
+use arrow::types::PrimitiveType;
+use polars_core::prelude::*;
+use polars_parquet::parquet::bloom_filter::{
+    hash_byte, hash_native, insert, is_in_set, optimal_num_bytes,
+};
+
+use crate::predicates::BloomFilter;
+
+/// The false positive probability of the written bloom filters.
+const FPP: f64 = 0.01;
+
+/// Whether bloom filters can be written for columns of `dtype`.
+pub(super) fn is_supported(dtype: &ArrowDataType) -> bool {
+    use PrimitiveType::*;
+    match dtype.to_physical_type() {
+        PhysicalType::Primitive(primitive) => matches!(
+            primitive,
+            Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64 | Float32 | Float64
+        ),
+        PhysicalType::Utf8View
+        | PhysicalType::BinaryView
+        | PhysicalType::LargeUtf8
+        | PhysicalType::LargeBinary => true,
+        _ => false,
+    }
+}
+
+/// Calls `f` with the hash of every non-null value of `array`, which must be of a supported
+/// type.
+fn for_each_hash(array: &dyn Array, mut f: impl FnMut(u64)) {
+    macro_rules! primitive {
+        ($T:ty, $P:ty) => {{
+            let array = array.as_any().downcast_ref::<PrimitiveArray<$T>>().unwrap();
+            array
+                .non_null_values_iter()
+                .for_each(|v| f(hash_native(v as $P)))
+        }};
+    }
+    macro_rules! bytes {
+        ($A:ty) => {{
+            let array = array.as_any().downcast_ref::<$A>().unwrap();
+            array.non_null_values_iter().for_each(|v| f(hash_byte(v)))
+        }};
+    }
+
+    use PrimitiveType::*;
+    match array.dtype().to_physical_type() {
+        PhysicalType::Primitive(Int8) => primitive!(i8, i32),
+        PhysicalType::Primitive(Int16) => primitive!(i16, i32),
+        PhysicalType::Primitive(Int32) => primitive!(i32, i32),
+        PhysicalType::Primitive(Int64) => primitive!(i64, i64),
+        PhysicalType::Primitive(UInt8) => primitive!(u8, i32),
+        PhysicalType::Primitive(UInt16) => primitive!(u16, i32),
+        PhysicalType::Primitive(UInt32) => primitive!(u32, i32),
+        PhysicalType::Primitive(UInt64) => primitive!(u64, i64),
+        PhysicalType::Primitive(Float32) => primitive!(f32, f32),
+        PhysicalType::Primitive(Float64) => primitive!(f64, f64),
+        PhysicalType::Utf8View => bytes!(Utf8ViewArray),
+        PhysicalType::BinaryView => bytes!(BinaryViewArray),
+        PhysicalType::LargeUtf8 => bytes!(Utf8Array<i64>),
+        PhysicalType::LargeBinary => bytes!(BinaryArray<i64>),
+        dt => unreachable!("bloom filters are not supported for {dt:?}"),
+    }
+}
+
+/// Build the bitset of the bloom filter of the values of `array`.
+pub(super) fn build_bloom_filter(array: &dyn Array) -> Vec<u8> {
+    // The number of values is an upper bound of the number of distinct values.
+    let ndv = array.len() - array.null_count();
+    let mut bitset = vec![0; optimal_num_bytes(ndv, FPP)];
+    for_each_hash(array, |hash| insert(&mut bitset, hash));
+    bitset
+}
+
+/// The bloom filter of a column chunk that is read from a Parquet file.
+#[derive(Debug)]
+pub(super) struct ParquetBloomFilter {
+    bitset: Vec<u8>,
+    dtype: DataType,
+}
+
+impl ParquetBloomFilter {
+    pub(super) fn new(bitset: Vec<u8>, dtype: DataType) -> Self {
+        Self { bitset, dtype }
+    }
+}
+
+impl BloomFilter for ParquetBloomFilter {
+    fn may_contain(&self, value: &Series) -> bool {
+        let Ok(value) = value.strict_cast(&self.dtype) else {
+            return true;
+        };
+        if value.len() != 1 || value.null_count() == 1 {
+            return true;
+        }
+        let array = value.to_arrow(0, CompatLevel::newest());
+        if !is_supported(array.dtype()) {
+            return true;
+        }
+
+        let mut may_contain = true;
+        for_each_hash(array.as_ref(), |hash| {
+            may_contain = is_in_set(&self.bitset, hash)
+        });
+        may_contain
+    }
+}
@@ -0,0 +1,51 @@
+//! [Parquet modular encryption](https://github.com/apache/parquet-format/blob/master/Encryption.md).
+//!
+//! The keys are retrieved by a [`KeyRetriever`], e.g. a closure that looks up the key metadata
+//! that is stored in the file in a key management service.
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+pub use polars_parquet::parquet::encryption::{
+    EncryptionAlgorithm, FileDecryptionProperties, FileEncryptionProperties, KeyRetriever,
+};
+
+macro_rules! impl_by_identity {
+    ($T:ident, $Properties:ident) => {
+        impl $T {
+            pub fn new(properties: $Properties) -> Self {
+                Self(Arc::new(properties))
+            }
+
+            pub fn properties(&self) -> &$Properties {
+                &self.0
+            }
+        }
+
+        // The key retriever can't be compared, so the properties are compared by identity.
+        impl PartialEq for $T {
+            fn eq(&self, other: &Self) -> bool {
+                Arc::ptr_eq(&self.0, &other.0)
+            }
+        }
+
+        impl Eq for $T {}
+
+        impl Hash for $T {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                Arc::as_ptr(&self.0).hash(state)
+            }
+        }
+    };
+}
+
+/// The properties to read encrypted Parquet files.
+#[derive(Clone, Debug)]
+pub struct ParquetDecryption(Arc<FileDecryptionProperties>);
+
+impl_by_identity!(ParquetDecryption, FileDecryptionProperties);
+
+/// The properties to write encrypted Parquet files.
+#[derive(Clone, Debug)]
+pub struct ParquetEncryption(Arc<FileEncryptionProperties>);
+
+impl_by_identity!(ParquetEncryption, FileEncryptionProperties);
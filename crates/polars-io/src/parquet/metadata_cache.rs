@@ -0,0 +1,106 @@
+//! A process-level cache of the metadata of Parquet files.
+//!
+//! Re-running a query over a large dataset otherwise fetches and parses the footer of every file
+//! on every collect. The cache is opt-in with `POLARS_PARQUET_METADATA_CACHE=1` and holds up to
+//! `POLARS_PARQUET_METADATA_CACHE_SIZE` (default 1024) footers. Entries are keyed by the path and
+//! the version of a file, i.e. its etag or its modification time and size, so a file that is
+//! overwritten is read again.
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use once_cell::sync::Lazy;
+use polars_utils::cache::FastFixedCache;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::metadata::FileMetadataRef;
+
+static METADATA_CACHE: Lazy<Mutex<FastFixedCache<MetadataCacheKey, FileMetadataRef>>> =
+    Lazy::new(|| Mutex::new(FastFixedCache::new(get_env_metadata_cache_size())));
+
+pub fn get_env_metadata_cache() -> bool {
+    std::env::var("POLARS_PARQUET_METADATA_CACHE").as_deref() == Ok("1")
+}
+
+fn get_env_metadata_cache_size() -> usize {
+    std::env::var("POLARS_PARQUET_METADATA_CACHE_SIZE")
+        .map(|x| x.parse::<usize>().expect("integer"))
+        .unwrap_or(1024)
+}
+
+/// Identifies a version of a file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MetadataCacheKey {
+    path: PlSmallStr,
+    version: PlSmallStr,
+}
+
+impl MetadataCacheKey {
+    /// The key of the current version of the local file at `path`, if its modification time is
+    /// known.
+    pub fn from_local_file(path: &Path) -> Option<Self> {
+        let path = std::fs::canonicalize(path).ok()?;
+        let metadata = std::fs::metadata(&path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            path: path.to_str()?.into(),
+            version: format!("{}-{}", modified.as_nanos(), metadata.len()).into(),
+        })
+    }
+
+    /// The key of the version of the object at `uri` that is described by `object_meta`.
+    #[cfg(feature = "cloud")]
+    pub fn from_object_meta(uri: &str, object_meta: &object_store::ObjectMeta) -> Self {
+        let version = match &object_meta.e_tag {
+            Some(e_tag) => e_tag.as_str().into(),
+            None => format!(
+                "{}-{}",
+                object_meta
+                    .last_modified
+                    .timestamp_nanos_opt()
+                    .unwrap_or_default(),
+                object_meta.size
+            )
+            .into(),
+        };
+        Self {
+            path: uri.into(),
+            version,
+        }
+    }
+}
+
+pub fn get(key: &MetadataCacheKey) -> Option<FileMetadataRef> {
+    METADATA_CACHE.lock().unwrap().get(key).cloned()
+}
+
+pub fn insert(key: MetadataCacheKey, metadata: FileMetadataRef) {
+    METADATA_CACHE.lock().unwrap().insert(key, metadata);
+}
+
+/// Remove all the metadata from the cache.
+pub fn clear() {
+    *METADATA_CACHE.lock().unwrap() = FastFixedCache::new(get_env_metadata_cache_size());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_file_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.parquet");
+
+        std::fs::write(&path, b"a").unwrap();
+        let key = MetadataCacheKey::from_local_file(&path).unwrap();
+        assert_eq!(MetadataCacheKey::from_local_file(&path), Some(key.clone()));
+
+        std::fs::write(&path, b"ab").unwrap();
+        assert_ne!(MetadataCacheKey::from_local_file(&path), Some(key));
+        assert_eq!(
+            MetadataCacheKey::from_local_file(&dir.path().join("missing.parquet")),
+            None
+        );
+    }
+}
@@ -1,5 +1,8 @@
 //! Functionality for reading and writing Apache Parquet files.
 
+mod bloom_filter;
+pub mod encryption;
 pub mod metadata;
+pub mod metadata_cache;
 pub mod read;
 pub mod write;
@@ -4,32 +4,36 @@ use std::ops::Range;
 use arrow::datatypes::ArrowSchemaRef;
 use bytes::Bytes;
 use object_store::path::Path as ObjectPath;
-use polars_core::config::{get_rg_prefetch_size, verbose};
+use polars_core::config::{get_rg_prefetch_memory_limit, get_rg_prefetch_size, verbose};
 use polars_core::prelude::*;
 use polars_parquet::read::RowGroupMetadata;
 use polars_parquet::write::FileMetadata;
 use polars_utils::pl_str::PlSmallStr;
-use tokio::sync::mpsc::{channel, Receiver, Sender};
-use tokio::sync::Mutex;
+use tokio::sync::mpsc::{channel, Receiver};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
 
 use super::mmap::ColumnStore;
-use super::predicates::read_this_row_group;
+use super::predicates::{read_this_row_group, sorted_row_group_range};
 use crate::cloud::{
     build_object_store, object_path_from_str, CloudLocation, CloudOptions, PolarsObjectStore,
 };
+use crate::parquet::encryption::ParquetDecryption;
 use crate::parquet::metadata::FileMetadataRef;
+use crate::parquet::metadata_cache::{self, MetadataCacheKey};
 use crate::pl_async::get_runtime;
 use crate::predicates::PhysicalIoExpr;
 
 type DownloadedRowGroup = Vec<(u64, Bytes)>;
-type QueuePayload = (usize, DownloadedRowGroup);
-type QueueSend = Arc<Sender<PolarsResult<QueuePayload>>>;
+type QueuePayload = (usize, DownloadedRowGroup, OwnedSemaphorePermit);
 
 pub struct ParquetObjectStore {
     store: PolarsObjectStore,
+    uri: PlSmallStr,
     path: ObjectPath,
     length: Option<usize>,
     metadata: Option<FileMetadataRef>,
+    metadata_cache_key: Option<MetadataCacheKey>,
+    decryption: Option<ParquetDecryption>,
 }
 
 impl ParquetObjectStore {
@@ -43,12 +47,21 @@ impl ParquetObjectStore {
 
         Ok(ParquetObjectStore {
             store: PolarsObjectStore::new(store),
+            uri: uri.into(),
             path,
             length: None,
             metadata,
+            metadata_cache_key: None,
+            decryption: None,
         })
     }
 
+    /// Decrypt the metadata of encrypted files, unless it is already given.
+    pub fn with_decryption(mut self, decryption: Option<ParquetDecryption>) -> Self {
+        self.decryption = decryption;
+        self
+    }
+
     async fn get_range(&self, start: usize, length: usize) -> PolarsResult<Bytes> {
         self.store
             .get_range(&self.path, start..start + length)
@@ -62,7 +75,10 @@ impl ParquetObjectStore {
     /// Initialize the length property of the object, unless it has already been fetched.
     async fn length(&mut self) -> PolarsResult<usize> {
         if self.length.is_none() {
-            self.length = Some(self.store.head(&self.path).await?.size);
+            let object_meta = self.store.head(&self.path).await?;
+            self.length = Some(object_meta.size);
+            self.metadata_cache_key =
+                Some(MetadataCacheKey::from_object_meta(&self.uri, &object_meta));
         }
         Ok(self.length.unwrap())
     }
@@ -76,13 +92,27 @@ impl ParquetObjectStore {
     /// Fetch the metadata of the parquet file, do not memoize it.
     async fn fetch_metadata(&mut self) -> PolarsResult<FileMetadata> {
         let length = self.length().await?;
-        fetch_metadata(&self.store, &self.path, length).await
+        fetch_metadata(&self.store, &self.path, length, self.decryption.as_ref()).await
     }
 
-    /// Fetch and memoize the metadata of the parquet file.
+    /// Fetch and memoize the metadata of the parquet file. The metadata is looked up in the
+    /// [metadata cache](metadata_cache) first if it is enabled.
     pub async fn get_metadata(&mut self) -> PolarsResult<&FileMetadataRef> {
         if self.metadata.is_none() {
-            self.metadata = Some(Arc::new(self.fetch_metadata().await?));
+            // The version of the object is fetched with its length.
+            self.length().await?;
+            let cache_key = self
+                .metadata_cache_key
+                .clone()
+                .filter(|_| metadata_cache::get_env_metadata_cache() && self.decryption.is_none());
+            self.metadata = cache_key.as_ref().and_then(metadata_cache::get);
+            if self.metadata.is_none() {
+                let metadata = Arc::new(self.fetch_metadata().await?);
+                if let Some(cache_key) = cache_key {
+                    metadata_cache::insert(cache_key, metadata.clone());
+                }
+                self.metadata = Some(metadata);
+            }
         }
         Ok(self.metadata.as_ref().unwrap())
     }
@@ -107,6 +137,7 @@ pub async fn fetch_metadata(
     store: &PolarsObjectStore,
     path: &ObjectPath,
     file_byte_length: usize,
+    decryption: Option<&ParquetDecryption>,
 ) -> PolarsResult<FileMetadata> {
     let footer_header_bytes = store
         .get_range(
@@ -121,22 +152,18 @@ pub async fn fetch_metadata(
         )
         .await?;
 
-    let footer_byte_length: usize = {
+    let (footer_byte_length, encrypted_footer): (usize, bool) = {
         let reader = &mut footer_header_bytes.as_ref();
         let footer_byte_size = read_i32le(reader).unwrap();
-        let magic = read_n(reader).unwrap();
+        let magic = read_n::<4>(reader).unwrap();
         debug_assert!(reader.is_empty());
-        if magic != polars_parquet::parquet::PARQUET_MAGIC {
-            return Err(polars_parquet::parquet::error::ParquetError::OutOfSpec(
-                "incorrect magic in parquet footer".to_string(),
-            )
-            .into());
-        }
-        footer_byte_size.try_into().map_err(|_| {
+        let encrypted_footer = polars_parquet::parquet::read::footer_is_encrypted(&magic)?;
+        let footer_byte_length = footer_byte_size.try_into().map_err(|_| {
             polars_parquet::parquet::error::ParquetError::OutOfSpec(
                 "negative footer byte length".to_string(),
             )
-        })?
+        })?;
+        (footer_byte_length, encrypted_footer)
     };
 
     let footer_bytes = store
@@ -152,13 +179,13 @@ pub async fn fetch_metadata(
         )
         .await?;
 
-    Ok(polars_parquet::parquet::read::deserialize_metadata(
-        std::io::Cursor::new(footer_bytes.as_ref()),
-        // TODO: Describe why this makes sense. Taken from the previous
-        // implementation which said "a highly nested but sparse struct could
-        // result in many allocations".
-        footer_bytes.as_ref().len() * 2 + 1024,
-    )?)
+    Ok(
+        polars_parquet::parquet::read::deserialize_metadata_with_decryption(
+            &footer_bytes[..footer_byte_length],
+            encrypted_footer,
+            decryption.map(ParquetDecryption::properties),
+        )?,
+    )
 }
 
 /// Download rowgroups for the column whose indexes are given in `projection`.
@@ -167,9 +194,7 @@ async fn download_projection(
     fields: Arc<[PlSmallStr]>,
     row_group: RowGroupMetadata,
     async_reader: Arc<ParquetObjectStore>,
-    sender: QueueSend,
-    rg_index: usize,
-) -> bool {
+) -> PolarsResult<DownloadedRowGroup> {
     let async_reader = &async_reader;
     let row_group = &row_group;
     let fields = fields.as_ref();
@@ -191,61 +216,100 @@ async fn download_projection(
         }
     });
 
-    let result = async_reader.get_ranges(&ranges).await.map(|bytes| {
-        (
-            rg_index,
-            bytes
-                .into_iter()
-                .zip(offsets)
-                .map(|(bytes, offset)| (offset, bytes))
-                .collect::<Vec<_>>(),
-        )
-    });
-    sender.send(result).await.is_ok()
+    let bytes = async_reader.get_ranges(&ranges).await?;
+    Ok(bytes
+        .into_iter()
+        .zip(offsets)
+        .map(|(bytes, offset)| (offset, bytes))
+        .collect())
 }
 
 async fn download_row_group(
     rg: RowGroupMetadata,
     async_reader: Arc<ParquetObjectStore>,
-    sender: QueueSend,
-    rg_index: usize,
-) -> bool {
+) -> PolarsResult<DownloadedRowGroup> {
     if rg.n_columns() == 0 {
-        return true;
+        return Ok(vec![]);
     }
 
     let full_byte_range = rg.full_byte_range();
     let full_byte_range = full_byte_range.start as usize..full_byte_range.end as usize;
 
-    let result = async_reader
+    let bytes = async_reader
         .get_range(
             full_byte_range.start,
             full_byte_range.end - full_byte_range.start,
         )
-        .await
-        .map(|bytes| {
+        .await?;
+
+    Ok(rg
+        .byte_ranges_iter()
+        .map(|range| {
             (
-                rg_index,
-                rg.byte_ranges_iter()
-                    .map(|range| {
-                        (
-                            range.start,
-                            bytes.slice(
-                                range.start as usize - full_byte_range.start
-                                    ..range.end as usize - full_byte_range.start,
-                            ),
-                        )
-                    })
-                    .collect::<DownloadedRowGroup>(),
+                range.start,
+                bytes.slice(
+                    range.start as usize - full_byte_range.start
+                        ..range.end as usize - full_byte_range.start,
+                ),
             )
-        });
+        })
+        .collect())
+}
 
-    sender.send(result).await.is_ok()
+/// The number of bytes that are downloaded for a row group.
+fn download_size(rg: &RowGroupMetadata, fields: Option<&[PlSmallStr]>) -> usize {
+    match fields {
+        Some(fields) => fields
+            .iter()
+            .flat_map(|name| rg.columns_under_root_iter(name))
+            .map(|meta| {
+                let byte_range = meta.byte_range();
+                (byte_range.end - byte_range.start) as usize
+            })
+            .sum(),
+        None if rg.n_columns() == 0 => 0,
+        None => {
+            let full_byte_range = rg.full_byte_range();
+            (full_byte_range.end - full_byte_range.start) as usize
+        },
+    }
+}
+
+/// Bounds the number of bytes of the row groups that are downloaded, but not yet decoded. Permits
+/// are counted in KiB.
+struct MemoryBudget {
+    semaphore: Arc<Semaphore>,
+    n_permits: usize,
+}
+
+impl MemoryBudget {
+    fn new(n_bytes: usize) -> Self {
+        let n_permits = (n_bytes / 1024).clamp(1, Semaphore::MAX_PERMITS.min(u32::MAX as usize));
+        Self {
+            semaphore: Arc::new(Semaphore::new(n_permits)),
+            n_permits,
+        }
+    }
+
+    /// Wait until `n_bytes` fit in the budget. A row group that is larger than the whole budget
+    /// waits until all other downloads are received.
+    async fn acquire(&self, n_bytes: usize) -> OwnedSemaphorePermit {
+        let n_permits = n_bytes.div_ceil(1024).min(self.n_permits);
+        self.semaphore
+            .clone()
+            .acquire_many_owned(n_permits as u32)
+            .await
+            // The semaphore is never closed.
+            .unwrap()
+    }
 }
 
 pub struct FetchRowGroupsFromObjectStore {
     rg_q: Arc<Mutex<Receiver<PolarsResult<QueuePayload>>>>,
-    prefetched_rg: PlHashMap<usize, DownloadedRowGroup>,
+    prefetched_rg: PlHashMap<usize, (DownloadedRowGroup, Option<OwnedSemaphorePermit>)>,
+    // The budget of the row groups that are handed to the decoder, which is returned once they
+    // are decoded.
+    decoding: Vec<OwnedSemaphorePermit>,
 }
 
 impl FetchRowGroupsFromObjectStore {
@@ -256,6 +320,39 @@ impl FetchRowGroupsFromObjectStore {
         predicate: Option<Arc<dyn PhysicalIoExpr>>,
         row_group_range: Range<usize>,
         row_groups: &[RowGroupMetadata],
+    ) -> PolarsResult<Self> {
+        let max_downloads = get_rg_prefetch_size().max(1);
+        let memory_limit = get_rg_prefetch_memory_limit();
+
+        if verbose() {
+            eprintln!("POLARS ROW_GROUP PREFETCH_SIZE: {}", max_downloads);
+            eprintln!("POLARS ROW_GROUP PREFETCH_MEMORY_LIMIT: {}", memory_limit);
+        }
+
+        Self::with_prefetch_limits(
+            reader,
+            schema,
+            projection,
+            predicate,
+            row_group_range,
+            row_groups,
+            max_downloads,
+            memory_limit,
+        )
+    }
+
+    /// Like [`Self::new`], with the number of downloads in flight and the bytes that may be
+    /// downloaded ahead of the decoder given, instead of taken from the environment.
+    #[allow(clippy::too_many_arguments)]
+    fn with_prefetch_limits(
+        reader: ParquetObjectStore,
+        schema: ArrowSchemaRef,
+        projection: Option<&[usize]>,
+        predicate: Option<Arc<dyn PhysicalIoExpr>>,
+        row_group_range: Range<usize>,
+        row_groups: &[RowGroupMetadata],
+        max_downloads: usize,
+        memory_limit: usize,
     ) -> PolarsResult<Self> {
         let projected_fields: Option<Arc<[PlSmallStr]>> = projection.map(|projection| {
             projection
@@ -264,115 +361,304 @@ impl FetchRowGroupsFromObjectStore {
                 .collect()
         });
 
-        let mut prefetched: PlHashMap<usize, DownloadedRowGroup> = PlHashMap::new();
+        let mut prefetched: PlHashMap<usize, (DownloadedRowGroup, Option<OwnedSemaphorePermit>)> =
+            PlHashMap::new();
 
-        let mut row_groups = if let Some(pred) = predicate.as_deref() {
+        let row_groups = if let Some(pred) = predicate.as_deref() {
+            let candidates =
+                sorted_row_group_range(Some(pred), row_groups, &schema, row_group_range.clone())?;
             row_group_range
                 .filter_map(|i| {
                     let rg = &row_groups[i];
 
-                    let should_be_read =
-                        matches!(read_this_row_group(Some(pred), rg, &schema), Ok(true));
+                    let should_be_read = candidates.contains(&i)
+                        && matches!(read_this_row_group(Some(pred), rg, &schema), Ok(true));
 
                     // Already add the row groups that will be skipped to the prefetched data.
                     if !should_be_read {
-                        prefetched.insert(i, Default::default());
+                        prefetched.insert(i, (Default::default(), None));
                     }
 
                     should_be_read.then(|| (i, rg.clone()))
                 })
                 .collect::<Vec<_>>()
         } else {
-            row_groups.iter().cloned().enumerate().collect()
+            row_group_range
+                .map(|i| (i, row_groups[i].clone()))
+                .collect()
         };
         let reader = Arc::new(reader);
-        let msg_limit = get_rg_prefetch_size();
-
-        if verbose() {
-            eprintln!("POLARS ROW_GROUP PREFETCH_SIZE: {}", msg_limit)
-        }
-
-        let (snd, rcv) = channel(msg_limit);
-        let snd = Arc::new(snd);
+        let (snd, rcv) = channel(max_downloads);
+        let downloads = Arc::new(Semaphore::new(max_downloads));
+        let memory_budget = MemoryBudget::new(memory_limit);
 
+        // The downloads run ahead of the decoder until either `max_downloads` requests are in
+        // flight or `memory_limit` bytes are downloaded but not yet decoded, so the network is
+        // kept busy while the previous row groups are decoded.
         get_runtime().spawn(async move {
-            let chunk_len = msg_limit;
-            let mut handles = Vec::with_capacity(chunk_len.clamp(0, row_groups.len()));
-            for chunk in row_groups.chunks_mut(chunk_len) {
-                // Start downloads concurrently
-                for (i, rg) in chunk {
-                    let rg = std::mem::take(rg);
-
-                    match &projected_fields {
-                        Some(projected_fields) => {
-                            let handle = tokio::spawn(download_projection(
-                                projected_fields.clone(),
-                                rg,
-                                reader.clone(),
-                                snd.clone(),
-                                *i,
-                            ));
-                            handles.push(handle)
-                        },
-                        None => {
-                            let handle = tokio::spawn(download_row_group(
-                                rg,
-                                reader.clone(),
-                                snd.clone(),
-                                *i,
-                            ));
-                            handles.push(handle)
-                        },
-                    }
+            for (i, rg) in row_groups {
+                // The budget is acquired in row group order, so the row groups that the decoder
+                // waits on are never starved by the ones after them.
+                let memory = memory_budget
+                    .acquire(download_size(&rg, projected_fields.as_deref()))
+                    .await;
+                let download = downloads.clone().acquire_owned().await.unwrap();
+
+                // The reader is dropped or it has received an error.
+                if snd.is_closed() {
+                    return;
                 }
 
-                // Wait n - 3 tasks, so we already start the next downloads earlier.
-                for task in handles.drain(..handles.len().saturating_sub(3)) {
-                    let succeeded = task.await.unwrap();
-                    if !succeeded {
-                        return;
-                    }
-                }
-            }
+                let projected_fields = projected_fields.clone();
+                let reader = reader.clone();
+                let snd = snd.clone();
+                tokio::spawn(async move {
+                    let result = match projected_fields {
+                        Some(projected_fields) => {
+                            download_projection(projected_fields, rg, reader).await
+                        },
+                        None => download_row_group(rg, reader).await,
+                    };
+                    drop(download);
 
-            // Drain remaining tasks.
-            for task in handles.drain(..) {
-                let succeeded = task.await.unwrap();
-                if !succeeded {
-                    return;
-                }
+                    // Don't unwrap send attempt - the reader could be dropped.
+                    let _ = snd.send(result.map(|rg| (i, rg, memory))).await;
+                });
             }
         });
 
         Ok(FetchRowGroupsFromObjectStore {
             rg_q: Arc::new(Mutex::new(rcv)),
-            prefetched_rg: Default::default(),
+            prefetched_rg: prefetched,
+            decoding: vec![],
         })
     }
 
+    /// Fetch the row groups of `row_groups` that are downloaded in order, which are at least the
+    /// first one. Those are decoded while the next ones are downloaded, instead of waiting for
+    /// all of them. Returns the range of the fetched row groups.
     pub(crate) async fn fetch_row_groups(
         &mut self,
         row_groups: Range<usize>,
-    ) -> PolarsResult<ColumnStore> {
+    ) -> PolarsResult<(ColumnStore, Range<usize>)> {
         let mut guard = self.rg_q.lock().await;
 
-        while !row_groups
-            .clone()
-            .all(|i| self.prefetched_rg.contains_key(&i))
-        {
+        while !row_groups.is_empty() && !self.prefetched_rg.contains_key(&row_groups.start) {
             let Some(fetched) = guard.recv().await else {
                 break;
             };
-            let (rg_i, payload) = fetched?;
+            let (rg_i, payload, memory) = fetched?;
+            self.prefetched_rg.insert(rg_i, (payload, Some(memory)));
+        }
+        // Also take the row groups that were received in the meantime, without waiting.
+        while let Ok(fetched) = guard.try_recv() {
+            let (rg_i, payload, memory) = fetched?;
+            self.prefetched_rg.insert(rg_i, (payload, Some(memory)));
+        }
+
+        // If the first row group is missing, all downloads are received and the decoder reports
+        // the missing columns.
+        let end = if self.prefetched_rg.contains_key(&row_groups.start) {
+            row_groups
+                .clone()
+                .find(|i| !self.prefetched_rg.contains_key(i))
+                .unwrap_or(row_groups.end)
+        } else {
+            row_groups.end
+        };
+        let mut received = PlHashMap::new();
+        for i in row_groups.start..end {
+            if let Some((rg, memory)) = self.prefetched_rg.remove(&i) {
+                received.extend(rg);
+                self.decoding.extend(memory);
+            }
+        }
+
+        Ok((ColumnStore::Fetched(received), row_groups.start..end))
+    }
+
+    /// Return the budget of the fetched row groups, once they are decoded.
+    pub(crate) fn finish_decoding(&mut self) {
+        self.decoding.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+
+    use super::*;
+    use crate::parquet::read::ParquetAsyncReader;
+    use crate::parquet::write::ParquetWriter;
+    use crate::predicates::{BatchStats, StatsEvaluator};
+
+    const N_ROW_GROUPS: usize = 4;
+    const ROW_GROUP_SIZE: usize = 1000;
+
+    /// Write a file of which the row groups are larger than a KiB, the smallest budget.
+    fn write_file(dir: &tempfile::TempDir) -> String {
+        let path = dir.path().join("file.parquet");
+        let mut df = df![
+            "a" => (0..(N_ROW_GROUPS * ROW_GROUP_SIZE) as i64).collect::<Vec<_>>(),
+        ]
+        .unwrap();
+        let file = std::fs::File::create(&path).unwrap();
+        ParquetWriter::new(file)
+            .with_statistics(Default::default())
+            .with_row_group_size(Some(ROW_GROUP_SIZE))
+            .finish(&mut df)
+            .unwrap();
+        format!("file://{}", path.display())
+    }
+
+    /// Skips the second row group.
+    struct SkipSecondRowGroup;
+
+    impl PhysicalIoExpr for SkipSecondRowGroup {
+        fn evaluate_io(&self, df: &DataFrame) -> PolarsResult<Series> {
+            let a = df.column("a")?.i64()?;
+            Ok(a.into_iter()
+                .map(|v| {
+                    v.map(|v| !(ROW_GROUP_SIZE as i64..2 * ROW_GROUP_SIZE as i64).contains(&v))
+                })
+                .collect::<BooleanChunked>()
+                .into_series())
+        }
+
+        fn live_variables(&self) -> Option<Vec<PlSmallStr>> {
+            None
+        }
+
+        fn as_stats_evaluator(&self) -> Option<&dyn StatsEvaluator> {
+            Some(self)
+        }
+    }
 
-            self.prefetched_rg.insert(rg_i, payload);
+    impl StatsEvaluator for SkipSecondRowGroup {
+        fn should_read(&self, stats: &BatchStats) -> PolarsResult<bool> {
+            let min = stats.get_stats("a")?.to_min().unwrap().i64()?.get(0);
+            Ok(min != Some(ROW_GROUP_SIZE as i64))
         }
+    }
+
+    async fn fetcher(
+        uri: &str,
+        predicate: Option<Arc<dyn PhysicalIoExpr>>,
+        memory_limit: usize,
+    ) -> FetchRowGroupsFromObjectStore {
+        let mut reader = ParquetObjectStore::from_uri(uri, None, None).await.unwrap();
+        let metadata = reader.get_metadata().await.unwrap().clone();
+        let schema = Arc::new(polars_parquet::arrow::read::infer_schema(&metadata).unwrap());
+        assert_eq!(metadata.row_groups.len(), N_ROW_GROUPS);
+        FetchRowGroupsFromObjectStore::with_prefetch_limits(
+            reader,
+            schema,
+            None,
+            predicate,
+            0..N_ROW_GROUPS,
+            &metadata.row_groups,
+            N_ROW_GROUPS,
+            memory_limit,
+        )
+        .unwrap()
+    }
+
+    fn n_columns(store: &ColumnStore) -> usize {
+        match store {
+            ColumnStore::Fetched(columns) => columns.len(),
+            ColumnStore::Local(_) => unreachable!(),
+        }
+    }
 
-        let received = row_groups
-            .flat_map(|i| self.prefetched_rg.remove(&i))
-            .flat_map(|rg| rg.into_iter())
-            .collect::<PlHashMap<_, _>>();
+    #[test]
+    fn test_budget_smaller_than_row_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = write_file(&dir);
+        get_runtime().block_on(async {
+            let budget = MemoryBudget::new(1);
+            assert_eq!(budget.n_permits, 1);
+            // a row group that is larger than the budget takes all of it
+            let permit = budget.acquire(1 << 20).await;
+            assert_eq!(permit.num_permits(), 1);
+            drop(permit);
+
+            // only one row group fits at a time, but every row group is fetched
+            let mut fetcher = fetcher(&uri, None, 1).await;
+            for i in 0..N_ROW_GROUPS {
+                let (store, range) = fetcher.fetch_row_groups(i..N_ROW_GROUPS).await.unwrap();
+                assert_eq!(range, i..i + 1);
+                assert_eq!(n_columns(&store), 1);
+                fetcher.finish_decoding();
+            }
+        })
+    }
+
+    #[test]
+    fn test_budget_released_after_decoding() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = write_file(&dir);
+        get_runtime().block_on(async {
+            let mut fetcher = fetcher(&uri, None, 1).await;
+            let (_, range) = fetcher.fetch_row_groups(0..N_ROW_GROUPS).await.unwrap();
+            assert_eq!(range, 0..1);
+
+            // the next row group is not downloaded while the first one is decoded
+            let fetch = fetcher.fetch_row_groups(1..N_ROW_GROUPS);
+            assert!(tokio::time::timeout(Duration::from_millis(100), fetch)
+                .await
+                .is_err());
+
+            fetcher.finish_decoding();
+            let (_, range) = fetcher.fetch_row_groups(1..N_ROW_GROUPS).await.unwrap();
+            assert_eq!(range, 1..2);
+        })
+    }
+
+    #[test]
+    fn test_skipped_row_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = write_file(&dir);
+        get_runtime().block_on(async {
+            let mut fetcher = fetcher(&uri, Some(Arc::new(SkipSecondRowGroup)), 1).await;
+            // the skipped row group follows the downloaded one without being downloaded
+            let (store, range) = fetcher.fetch_row_groups(0..N_ROW_GROUPS).await.unwrap();
+            assert_eq!(range, 0..2);
+            assert_eq!(n_columns(&store), 1);
+            fetcher.finish_decoding();
+
+            let (store, range) = fetcher.fetch_row_groups(2..N_ROW_GROUPS).await.unwrap();
+            assert_eq!(range, 2..3);
+            assert_eq!(n_columns(&store), 1);
+        })
+    }
+
+    #[test]
+    fn test_batches_after_partial_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = write_file(&dir);
+        // Only one row group is downloaded at a time, so every batch fetches a prefix of the
+        // requested row groups. This only slows down the other tests that read from an object
+        // store.
+        std::env::set_var("POLARS_ROW_GROUP_PREFETCH_MEMORY_LIMIT", "1");
+        let out = get_runtime().block_on(async {
+            let mut reader = ParquetAsyncReader::from_uri(&uri, None, None)
+                .await?
+                .batched(ROW_GROUP_SIZE)
+                .await?;
+            let mut out = vec![];
+            while let Some(dfs) = reader.next_batches(N_ROW_GROUPS).await? {
+                out.extend(dfs);
+            }
+            PolarsResult::Ok(out)
+        });
+        std::env::remove_var("POLARS_ROW_GROUP_PREFETCH_MEMORY_LIMIT");
 
-        Ok(ColumnStore::Fetched(received))
+        let out = accumulate_dataframes_vertical_unchecked(out.unwrap());
+        let a = out.column("a").unwrap().i64().unwrap();
+        assert!(a
+            .into_no_null_iter()
+            .eq(0..(N_ROW_GROUPS * ROW_GROUP_SIZE) as i64));
     }
 }
@@ -0,0 +1,535 @@
+//! Read Parquet files from plain HTTP(S) servers with range requests, without an object store.
+use std::future::Future;
+use std::ops::Range;
+use std::time::Duration;
+
+use arrow::datatypes::ArrowSchemaRef;
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use polars_core::config::{get_rg_prefetch_size, verbose};
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_parquet::read::RowGroupMetadata;
+use polars_utils::pl_str::PlSmallStr;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
+
+use super::mmap::ColumnStore;
+use super::predicates::{read_this_row_group, sorted_row_group_range};
+use super::read_impl::{compute_row_group_range, BatchedParquetReader};
+use super::utils::materialize_empty_df;
+use super::ParallelStrategy;
+use crate::parquet::metadata::FileMetadataRef;
+use crate::pl_async::get_runtime;
+use crate::predicates::PhysicalIoExpr;
+use crate::RowIndex;
+
+/// The number of bytes at the end of the file that are requested for the footer, which saves a
+/// request for the metadata of most files.
+const FOOTER_PREFETCH_SIZE: usize = 64 * 1024;
+/// Byte ranges that are at most this far apart are fetched with a single request.
+const COALESCE_GAP: usize = 1024 * 1024;
+const MAX_RETRIES: usize = 5;
+
+struct RequestError {
+    error: PolarsError,
+    retry: bool,
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(error: reqwest::Error) -> Self {
+        let retry = error.is_timeout() || error.is_connect() || error.is_request();
+        Self {
+            error: to_compute_err(error),
+            retry,
+        }
+    }
+}
+
+/// Run a request until it succeeds or fails with an error that is not transient, waiting
+/// exponentially longer between the attempts.
+async fn with_retries<T, F, Fut>(mut request: F) -> PolarsResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, RequestError>>,
+{
+    let mut backoff = Duration::from_millis(100);
+    let mut attempt = 0;
+    loop {
+        match request().await {
+            Ok(value) => return Ok(value),
+            Err(RequestError { error, retry }) if !retry || attempt == MAX_RETRIES => {
+                return Err(error)
+            },
+            Err(RequestError { error, .. }) => {
+                if verbose() {
+                    eprintln!("retrying HTTP request in {backoff:?} after: {error}");
+                }
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(Duration::from_secs(10));
+                attempt += 1;
+            },
+        }
+    }
+}
+
+fn status_error(url: &str, status: StatusCode) -> RequestError {
+    RequestError {
+        error: polars_err!(ComputeError: "HTTP request to '{}' failed with status {}", url, status),
+        retry: status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+    }
+}
+
+/// The total length of the file in the `Content-Range` header of a partial response.
+fn total_length(content_range: &str) -> Option<usize> {
+    content_range
+        .strip_prefix("bytes ")?
+        .rsplit_once('/')?
+        .1
+        .parse()
+        .ok()
+}
+
+struct HttpRangeClient {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl HttpRangeClient {
+    /// Fetch the last `n` bytes of the file, and the length of the file.
+    async fn get_suffix(&self, n: usize) -> PolarsResult<(Bytes, usize)> {
+        with_retries(|| async move {
+            let response = self
+                .client
+                .get(&self.url)
+                .header(RANGE, format!("bytes=-{n}"))
+                .send()
+                .await?;
+            match response.status() {
+                StatusCode::PARTIAL_CONTENT => {
+                    let length = response
+                        .headers()
+                        .get(CONTENT_RANGE)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(total_length)
+                        .ok_or_else(|| RequestError {
+                            error: polars_err!(
+                                ComputeError: "HTTP response of '{}' has no valid Content-Range header", self.url
+                            ),
+                            retry: false,
+                        })?;
+                    Ok((response.bytes().await?, length))
+                },
+                // The file is smaller than the range, or the server ignores ranges.
+                StatusCode::OK => {
+                    let bytes = response.bytes().await?;
+                    let length = bytes.len();
+                    Ok((bytes.slice(length.saturating_sub(n)..), length))
+                },
+                status => Err(status_error(&self.url, status)),
+            }
+        })
+        .await
+    }
+
+    async fn get_range(&self, range: Range<usize>) -> PolarsResult<Bytes> {
+        if range.is_empty() {
+            return Ok(Bytes::new());
+        }
+        let range = &range;
+        with_retries(|| async move {
+            let response = self
+                .client
+                .get(&self.url)
+                .header(RANGE, format!("bytes={}-{}", range.start, range.end - 1))
+                .send()
+                .await?;
+            match response.status() {
+                StatusCode::PARTIAL_CONTENT => {
+                    let bytes = response.bytes().await?;
+                    if bytes.len() != range.len() {
+                        return Err(RequestError {
+                            error: polars_err!(
+                                ComputeError: "HTTP server sent {} bytes of '{}' for a range of {} bytes",
+                                bytes.len(), self.url, range.len()
+                            ),
+                            retry: true,
+                        });
+                    }
+                    Ok(bytes)
+                },
+                StatusCode::OK => Err(RequestError {
+                    error: polars_err!(
+                        ComputeError: "HTTP server of '{}' doesn't support range requests", self.url
+                    ),
+                    retry: false,
+                }),
+                status => Err(status_error(&self.url, status)),
+            }
+        })
+        .await
+    }
+
+    async fn fetch_metadata(&self) -> PolarsResult<FileMetadataRef> {
+        const FOOTER_SIZE: usize = polars_parquet::parquet::FOOTER_SIZE as usize;
+
+        let (mut bytes, file_length) = self.get_suffix(FOOTER_PREFETCH_SIZE).await?;
+        polars_ensure!(
+            bytes.len() >= FOOTER_SIZE,
+            ComputeError: "'{}' is too small to be a Parquet file", self.url
+        );
+        let footer = &bytes[bytes.len() - FOOTER_SIZE..];
+        let metadata_length = i32::from_le_bytes(footer[..4].try_into().unwrap());
+        let encrypted_footer = polars_parquet::parquet::read::footer_is_encrypted(&footer[4..])?;
+        let metadata_length = usize::try_from(metadata_length)
+            .ok()
+            .filter(|length| length + FOOTER_SIZE <= file_length)
+            .ok_or_else(
+                || polars_err!(ComputeError: "invalid footer length of Parquet file '{}'", self.url),
+            )?;
+
+        if metadata_length + FOOTER_SIZE > bytes.len() {
+            let start = file_length - metadata_length - FOOTER_SIZE;
+            let head = self.get_range(start..file_length - bytes.len()).await?;
+            bytes = [head, bytes].concat().into();
+        }
+        let metadata =
+            &bytes[bytes.len() - FOOTER_SIZE - metadata_length..bytes.len() - FOOTER_SIZE];
+        let metadata = polars_parquet::parquet::read::deserialize_metadata_with_decryption(
+            metadata,
+            encrypted_footer,
+            None,
+        )?;
+        Ok(Arc::new(metadata))
+    }
+}
+
+/// Merge the sorted byte ranges that are at most `gap` bytes apart. Returns the merged ranges and,
+/// for every merged range, the ranges that it contains.
+fn coalesce_ranges(ranges: &[Range<usize>], gap: usize) -> Vec<(Range<usize>, Range<usize>)> {
+    let mut out: Vec<(Range<usize>, Range<usize>)> = vec![];
+    for (i, range) in ranges.iter().enumerate() {
+        match out.last_mut() {
+            Some((merged, members)) if range.start <= merged.end.saturating_add(gap) => {
+                merged.end = merged.end.max(range.end);
+                members.end = i + 1;
+            },
+            _ => out.push((range.clone(), i..i + 1)),
+        }
+    }
+    out
+}
+
+/// Fetches the column chunks of the row groups that are read with range requests.
+pub struct FetchRowGroupsFromHttp {
+    client: Arc<HttpRangeClient>,
+    schema: ArrowSchemaRef,
+    projected_fields: Option<Arc<[PlSmallStr]>>,
+    predicate: Option<Arc<dyn PhysicalIoExpr>>,
+    metadata: FileMetadataRef,
+}
+
+impl FetchRowGroupsFromHttp {
+    /// The row groups of `range` that can't be skipped by their statistics.
+    fn row_groups_to_read(&self, range: Range<usize>) -> PolarsResult<Vec<&RowGroupMetadata>> {
+        let row_groups = &self.metadata.row_groups;
+        let Some(predicate) = self.predicate.as_deref() else {
+            return Ok(row_groups[range].iter().collect());
+        };
+        let candidates =
+            sorted_row_group_range(Some(predicate), row_groups, &self.schema, range.clone())?;
+        Ok(range
+            .filter(|i| {
+                candidates.contains(i)
+                    && matches!(
+                        read_this_row_group(Some(predicate), &row_groups[*i], &self.schema),
+                        Ok(true)
+                    )
+            })
+            .map(|i| &row_groups[i])
+            .collect())
+    }
+
+    pub(crate) async fn fetch_row_groups(
+        &mut self,
+        row_groups: Range<usize>,
+    ) -> PolarsResult<ColumnStore> {
+        let mut ranges = vec![];
+        for rg in self.row_groups_to_read(row_groups)? {
+            match &self.projected_fields {
+                Some(fields) => {
+                    for name in fields.iter() {
+                        ranges.extend(
+                            rg.columns_under_root_iter(name)
+                                .map(|meta| meta.byte_range()),
+                        );
+                    }
+                },
+                None => ranges.extend(rg.byte_ranges_iter()),
+            }
+        }
+        let mut ranges = ranges
+            .into_iter()
+            .map(|range| range.start as usize..range.end as usize)
+            .collect::<Vec<_>>();
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let client = &self.client;
+        let ranges = &ranges;
+        let fetched = futures::stream::iter(coalesce_ranges(ranges, COALESCE_GAP))
+            .map(|(merged, members)| async move {
+                let bytes = client.get_range(merged.clone()).await?;
+                PolarsResult::Ok(
+                    ranges[members]
+                        .iter()
+                        .map(|range| {
+                            (
+                                range.start as u64,
+                                bytes.slice(range.start - merged.start..range.end - merged.start),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .buffer_unordered(get_rg_prefetch_size().max(1))
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(ColumnStore::Fetched(
+            fetched.into_iter().flatten().collect(),
+        ))
+    }
+}
+
+/// Read a Parquet file from a plain HTTP(S) server into a DataFrame.
+///
+/// The metadata in the footer of the file is fetched first, and then only the column chunks of
+/// the projected columns of the row groups that are needed for the slice and that can't be
+/// skipped by their statistics. The server must support HTTP range requests. Requests that fail
+/// with a transient error are retried with exponential backoff.
+#[must_use]
+pub struct ParquetHttpReader {
+    client: Arc<HttpRangeClient>,
+    rechunk: bool,
+    slice: (usize, usize),
+    projection: Option<Vec<usize>>,
+    parallel: ParallelStrategy,
+    schema: Option<ArrowSchemaRef>,
+    row_index: Option<RowIndex>,
+    metadata: Option<FileMetadataRef>,
+    predicate: Option<Arc<dyn PhysicalIoExpr>>,
+    hive_partition_columns: Option<Vec<Series>>,
+    include_file_path: Option<(PlSmallStr, Arc<str>)>,
+    use_statistics: bool,
+}
+
+impl ParquetHttpReader {
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: Arc::new(HttpRangeClient {
+                client: reqwest::Client::new(),
+                url: url.to_string(),
+            }),
+            rechunk: false,
+            slice: (0, usize::MAX),
+            projection: None,
+            parallel: Default::default(),
+            schema: None,
+            row_index: None,
+            metadata: None,
+            predicate: None,
+            hive_partition_columns: None,
+            include_file_path: None,
+            use_statistics: true,
+        }
+    }
+
+    pub fn with_slice(mut self, slice: Option<(usize, usize)>) -> Self {
+        self.slice = slice.unwrap_or((0, usize::MAX));
+        self
+    }
+
+    pub fn with_projection(mut self, projection: Option<Vec<usize>>) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    pub fn with_row_index(mut self, row_index: Option<RowIndex>) -> Self {
+        self.row_index = row_index;
+        self
+    }
+
+    pub fn with_predicate(mut self, predicate: Option<Arc<dyn PhysicalIoExpr>>) -> Self {
+        self.predicate = predicate;
+        self
+    }
+
+    /// Use statistics in the parquet to determine if pages
+    /// can be skipped from reading.
+    pub fn use_statistics(mut self, toggle: bool) -> Self {
+        self.use_statistics = toggle;
+        self
+    }
+
+    pub fn with_hive_partition_columns(mut self, columns: Option<Vec<Series>>) -> Self {
+        self.hive_partition_columns = columns;
+        self
+    }
+
+    pub fn with_include_file_path(
+        mut self,
+        include_file_path: Option<(PlSmallStr, Arc<str>)>,
+    ) -> Self {
+        self.include_file_path = include_file_path;
+        self
+    }
+
+    pub fn read_parallel(mut self, parallel: ParallelStrategy) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn set_rechunk(mut self, rechunk: bool) -> Self {
+        self.rechunk = rechunk;
+        self
+    }
+
+    /// Use the metadata of the file, e.g. because it was already fetched when the schema of a
+    /// scan was resolved.
+    pub fn with_metadata(mut self, metadata: Option<FileMetadataRef>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn get_metadata(&mut self) -> PolarsResult<&FileMetadataRef> {
+        if self.metadata.is_none() {
+            let client = self.client.clone();
+            self.metadata = Some(
+                get_runtime()
+                    .block_on_potential_spawn(async move { client.fetch_metadata().await })?,
+            );
+        }
+        Ok(self.metadata.as_ref().unwrap())
+    }
+
+    pub fn schema(&mut self) -> PolarsResult<ArrowSchemaRef> {
+        if self.schema.is_none() {
+            let metadata = self.get_metadata()?;
+            self.schema = Some(Arc::new(polars_parquet::arrow::read::infer_schema(
+                metadata,
+            )?));
+        }
+        Ok(self.schema.clone().unwrap())
+    }
+
+    /// Ensure the schema of the file matches the given schema. Calling this
+    /// after setting the projection will ensure only the projected indices
+    /// are checked.
+    pub fn check_schema(mut self, schema: &ArrowSchema) -> PolarsResult<Self> {
+        let self_schema = self.schema()?;
+        let self_schema = self_schema.as_ref();
+
+        if let Some(projection) = self.projection.as_deref() {
+            ensure_matching_schema(
+                &schema.try_project_indices(projection)?,
+                &self_schema.try_project_indices(projection)?,
+            )?;
+        } else {
+            ensure_matching_schema(schema, self_schema)?;
+        }
+
+        Ok(self)
+    }
+
+    pub fn num_rows(&mut self) -> PolarsResult<usize> {
+        Ok(self.get_metadata()?.num_rows)
+    }
+
+    pub fn finish(mut self) -> PolarsResult<DataFrame> {
+        let metadata = self.get_metadata()?.clone();
+        let schema = self.schema()?;
+        let projected_fields = self.projection.as_ref().map(|projection| {
+            projection
+                .iter()
+                .map(|i| schema.get_at_index(*i).unwrap().0.clone())
+                .collect()
+        });
+        let fetcher = FetchRowGroupsFromHttp {
+            client: self.client.clone(),
+            schema: schema.clone(),
+            projected_fields,
+            predicate: self.predicate.clone(),
+            metadata: metadata.clone(),
+        };
+        let n_row_groups = compute_row_group_range(
+            0,
+            metadata.row_groups.len(),
+            self.slice,
+            &metadata.row_groups,
+        )
+        .len();
+
+        let projection = self.projection.clone();
+        let row_index = self.row_index.clone();
+        let hive_partition_columns = self.hive_partition_columns.clone();
+        let reader = BatchedParquetReader::new(
+            fetcher.into(),
+            metadata,
+            schema.clone(),
+            self.slice,
+            self.projection,
+            self.predicate,
+            self.row_index,
+            usize::MAX,
+            self.use_statistics,
+            self.hive_partition_columns,
+            self.include_file_path,
+            self.parallel,
+        )?;
+        let chunks = get_runtime().block_on_potential_spawn(async move {
+            let mut iter = reader.iter(n_row_groups.max(1));
+            let mut chunks = vec![];
+            while let Some(result) = iter.next_().await {
+                chunks.push(result?)
+            }
+            PolarsResult::Ok(chunks)
+        })?;
+
+        if chunks.is_empty() {
+            return Ok(materialize_empty_df(
+                projection.as_deref(),
+                schema.as_ref(),
+                hive_partition_columns.as_deref(),
+                row_index.as_ref(),
+            ));
+        }
+        let mut df = accumulate_dataframes_vertical_unchecked(chunks);
+        if self.rechunk {
+            df.as_single_chunk_par();
+        }
+        Ok(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_length() {
+        assert_eq!(total_length("bytes 900-999/1000"), Some(1000));
+        assert_eq!(total_length("bytes 0-9/*"), None);
+        assert_eq!(total_length("900-999/1000"), None);
+    }
+
+    #[test]
+    fn test_coalesce_ranges() {
+        let ranges = [0..10, 15..20, 100..110, 105..120, 300..301];
+        assert_eq!(
+            coalesce_ranges(&ranges, 10),
+            [(0..20, 0..2), (100..120, 2..4), (300..301, 4..5)]
+        );
+        assert_eq!(coalesce_ranges(&ranges, 1000), [(0..301, 0..5)]);
+        assert!(coalesce_ranges(&[], 10).is_empty());
+    }
+}
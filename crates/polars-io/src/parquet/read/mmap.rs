@@ -27,6 +27,17 @@ pub enum ColumnStore {
     Fetched(PlHashMap<u64, Bytes>),
 }
 
+impl ColumnStore {
+    /// The bytes of the whole file, which are only available for local files.
+    pub(super) fn file_bytes(&self) -> Option<&[u8]> {
+        match self {
+            ColumnStore::Local(mem_slice) => Some(&mem_slice[..]),
+            #[cfg(feature = "async")]
+            ColumnStore::Fetched(_) => None,
+        }
+    }
+}
+
 /// For local files memory maps all columns that are part of the parquet field `field_name`.
 /// For cloud files the relevant memory regions should have been prefetched.
 pub(super) fn mmap_columns<'a>(
@@ -16,6 +16,8 @@
 
 #[cfg(feature = "cloud")]
 mod async_impl;
+#[cfg(feature = "parquet_http")]
+mod http;
 mod mmap;
 mod options;
 mod predicates;
@@ -31,6 +33,8 @@ consider compiling with polars-bigidx feature (polars-u64-idx package on python)
 or set 'streaming'",
 ));
 
+#[cfg(feature = "parquet_http")]
+pub use http::ParquetHttpReader;
 pub use options::{ParallelStrategy, ParquetOptions};
 use polars_error::{ErrString, PolarsError};
 #[cfg(feature = "cloud")]
@@ -40,5 +44,5 @@ pub use utils::materialize_empty_df;
 
 pub mod _internal {
     pub use super::mmap::to_deserializer;
-    pub use super::predicates::read_this_row_group;
+    pub use super::predicates::{collect_statistics, read_this_row_group};
 }
@@ -1,12 +1,17 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, Copy, Hash)]
+use crate::parquet::encryption::ParquetDecryption;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParquetOptions {
     pub parallel: ParallelStrategy,
     pub low_memory: bool,
     pub use_statistics: bool,
+    /// The keys to read encrypted files. They are not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub decryption: Option<ParquetDecryption>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Default, Hash)]
@@ -1,7 +1,14 @@
+use std::io::Cursor;
+use std::ops::Range;
+
+use arrow::bitmap::{Bitmap, MutableBitmap};
 use polars_core::prelude::*;
-use polars_parquet::read::statistics::{deserialize, Statistics};
-use polars_parquet::read::RowGroupMetadata;
+use polars_parquet::parquet::bloom_filter;
+use polars_parquet::parquet::read::{read_column_index, read_offset_index};
+use polars_parquet::read::statistics::{deserialize, deserialize_column_index, Statistics};
+use polars_parquet::read::{ColumnChunkMetadata, RowGroupMetadata};
 
+use crate::parquet::bloom_filter::ParquetBloomFilter;
 use crate::predicates::{BatchStats, ColumnStats, PhysicalIoExpr};
 
 impl ColumnStats {
@@ -15,10 +22,12 @@ impl ColumnStats {
     }
 }
 
-/// Collect the statistics in a row-group
-pub(crate) fn collect_statistics(
+/// Collect the statistics in a row-group. If the bytes of the file are given, the bloom filters
+/// of the columns are read as well.
+pub fn collect_statistics(
     md: &RowGroupMetadata,
     schema: &ArrowSchema,
+    file_bytes: Option<&[u8]>,
 ) -> PolarsResult<Option<BatchStats>> {
     // TODO! fix this performance. This is a full sequential scan.
     let stats = schema
@@ -29,7 +38,18 @@ pub(crate) fn collect_statistics(
             Ok(if iter.len() == 0 {
                 ColumnStats::new(field.into(), None, None, None)
             } else {
-                ColumnStats::from_arrow_stats(deserialize(field, iter)?, field)
+                let bloom_filter = match file_bytes {
+                    Some(file_bytes) if iter.len() == 1 => {
+                        let column = md.columns_under_root_iter(&field.name).next().unwrap();
+                        read_bloom_filter(column, field, file_bytes)?
+                    },
+                    _ => None,
+                };
+                let stats = ColumnStats::from_arrow_stats(deserialize(field, iter)?, field);
+                match bloom_filter {
+                    Some(bloom_filter) => stats.with_bloom_filter(Arc::new(bloom_filter)),
+                    None => stats,
+                }
             })
         })
         .collect::<PolarsResult<Vec<_>>>()?;
@@ -45,14 +65,40 @@ pub(crate) fn collect_statistics(
     )))
 }
 
+fn read_bloom_filter(
+    column: &ColumnChunkMetadata,
+    field: &ArrowField,
+    file_bytes: &[u8],
+) -> PolarsResult<Option<ParquetBloomFilter>> {
+    if column.metadata().bloom_filter_offset.is_none() {
+        return Ok(None);
+    }
+    let mut bitset = vec![];
+    bloom_filter::read(column, &mut Cursor::new(file_bytes), &mut bitset)?;
+    // The bitset is empty if the algorithm of the bloom filter is not supported.
+    Ok((!bitset.is_empty())
+        .then(|| ParquetBloomFilter::new(bitset, Field::from(field).dtype().clone())))
+}
+
 pub fn read_this_row_group(
     predicate: Option<&dyn PhysicalIoExpr>,
     md: &RowGroupMetadata,
     schema: &ArrowSchema,
+) -> PolarsResult<bool> {
+    read_this_row_group_with_bloom_filters(predicate, md, schema, None)
+}
+
+/// Like [`read_this_row_group`], but equality predicates are also decided with the bloom filters
+/// of the row group if the bytes of the file are given.
+pub(super) fn read_this_row_group_with_bloom_filters(
+    predicate: Option<&dyn PhysicalIoExpr>,
+    md: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    file_bytes: Option<&[u8]>,
 ) -> PolarsResult<bool> {
     if let Some(pred) = predicate {
         if let Some(pred) = pred.as_stats_evaluator() {
-            if let Some(stats) = collect_statistics(md, schema)? {
+            if let Some(stats) = collect_statistics(md, schema, file_bytes)? {
                 let should_read = pred.should_read(&stats);
                 // a parquet file may not have statistics of all columns
                 if matches!(should_read, Ok(false)) {
@@ -65,3 +111,290 @@ pub fn read_this_row_group(
     }
     Ok(true)
 }
+
+/// The statistics of a column whose values are sorted over consecutive row groups, i.e. every
+/// row group starts at or after the end of the row group before it.
+struct SortedColumn {
+    field: Field,
+    null_count: Series,
+    min_value: Series,
+    max_value: Series,
+    descending: bool,
+}
+
+impl SortedColumn {
+    /// Returns `None` if the statistics of the column don't show that it is sorted.
+    fn try_new(field: &ArrowField, row_groups: &[RowGroupMetadata]) -> PolarsResult<Option<Self>> {
+        if field.dtype().is_nested() {
+            return Ok(None);
+        }
+        let mut stats = Vec::with_capacity(row_groups.len());
+        for md in row_groups {
+            let columns = md.columns_under_root_iter(&field.name);
+            if columns.len() != 1 {
+                return Ok(None);
+            }
+            stats.push(ColumnStats::from_arrow_stats(
+                deserialize(field, columns)?,
+                field,
+            ));
+        }
+
+        let concat = |state: fn(&ColumnStats) -> Option<&Series>| -> PolarsResult<Option<Series>> {
+            let mut out: Option<Series> = None;
+            for stats in &stats {
+                let Some(s) = state(stats) else {
+                    return Ok(None);
+                };
+                match &mut out {
+                    Some(out) => {
+                        out.append(s)?;
+                    },
+                    None => out = Some(s.clone()),
+                }
+            }
+            Ok(out)
+        };
+        let (Some(null_count), Some(min_value), Some(max_value)) = (
+            concat(ColumnStats::get_null_count_state)?,
+            concat(ColumnStats::get_min_state)?,
+            concat(ColumnStats::get_max_state)?,
+        ) else {
+            return Ok(None);
+        };
+        if min_value.null_count() > 0 || max_value.null_count() > 0 {
+            return Ok(None);
+        }
+
+        let n = min_value.len() - 1;
+        let (Ok(ascending), Ok(descending)) = (
+            max_value.slice(0, n).lt_eq(&min_value.slice(1, n)),
+            min_value.slice(0, n).gt_eq(&max_value.slice(1, n)),
+        ) else {
+            return Ok(None);
+        };
+        let descending = if ascending.all() {
+            false
+        } else if descending.all() {
+            true
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            field: field.into(),
+            null_count,
+            min_value,
+            max_value,
+            descending,
+        }))
+    }
+
+    /// The statistics of the row groups `first..=last` together.
+    fn stats(&self, first: usize, last: usize) -> ColumnStats {
+        let (min_idx, max_idx) = if self.descending {
+            (last, first)
+        } else {
+            (first, last)
+        };
+        ColumnStats::new(
+            self.field.clone(),
+            Some(self.null_count.slice(first as i64, last - first + 1)),
+            Some(self.min_value.slice(min_idx as i64, 1)),
+            Some(self.max_value.slice(max_idx as i64, 1)),
+        )
+    }
+}
+
+/// Narrow the `range` of row groups to the ones that may satisfy the predicate if a column in the
+/// predicate is sorted over the row groups. The row groups that come before and after the values
+/// that satisfy the predicate are found with a binary search on the statistics of the combined
+/// row groups, so the predicate doesn't have to be evaluated on the statistics of every row group.
+pub(super) fn sorted_row_group_range(
+    predicate: Option<&dyn PhysicalIoExpr>,
+    row_groups: &[RowGroupMetadata],
+    schema: &ArrowSchema,
+    mut range: Range<usize>,
+) -> PolarsResult<Range<usize>> {
+    let Some(predicate) = predicate else {
+        return Ok(range);
+    };
+    let (Some(evaluator), Some(live_variables)) =
+        (predicate.as_stats_evaluator(), predicate.live_variables())
+    else {
+        return Ok(range);
+    };
+
+    let live_fields = schema
+        .iter_values()
+        .filter(|field| live_variables.contains(&field.name))
+        .collect::<Vec<_>>();
+    let stats_schema = Arc::new(Schema::from_iter(
+        live_fields.iter().map(|f| Field::from(*f)),
+    ));
+
+    for (i, field) in live_fields.iter().enumerate() {
+        if range.len() < 2 {
+            break;
+        }
+        let offset = range.start;
+        let Some(column) = SortedColumn::try_new(field, &row_groups[range.clone()])? else {
+            continue;
+        };
+
+        // Whether the row groups `first..=last` may satisfy the predicate. The statistics of the
+        // other columns are unknown.
+        let may_satisfy = |first: usize, last: usize| {
+            let stats = live_fields
+                .iter()
+                .enumerate()
+                .map(|(j, f)| {
+                    if i == j {
+                        column.stats(first, last)
+                    } else {
+                        ColumnStats::from_field(Field::from(*f))
+                    }
+                })
+                .collect();
+            let num_rows = row_groups[offset + first..=offset + last]
+                .iter()
+                .map(|md| md.num_rows())
+                .sum();
+            let stats = BatchStats::new(stats_schema.clone(), stats, Some(num_rows));
+            !matches!(evaluator.should_read(&stats), Ok(false))
+        };
+
+        // The number of leading row groups that cannot satisfy the predicate. Adding row groups
+        // only widens the combined statistics, so this is found with a binary search.
+        let n = range.len();
+        let (mut lo, mut hi) = (0, n);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            if may_satisfy(0, mid - 1) {
+                hi = mid - 1;
+            } else {
+                lo = mid;
+            }
+        }
+        let start = lo;
+
+        // The number of trailing row groups that cannot satisfy the predicate.
+        let (mut lo, mut hi) = (0, n - start);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            if may_satisfy(n - mid, n - 1) {
+                hi = mid - 1;
+            } else {
+                lo = mid;
+            }
+        }
+        let end = n - lo;
+
+        range = offset + start..offset + end;
+    }
+
+    Ok(range)
+}
+
+/// Compute the rows of a row group that may satisfy the predicate from the page indexes of the
+/// columns in the predicate. The rows of the pages whose statistics show that none of their
+/// values satisfy the predicate are unset.
+///
+/// Returns `None` if the page indexes cannot exclude any rows.
+pub(super) fn read_these_rows_of_row_group(
+    predicate: Option<&dyn PhysicalIoExpr>,
+    md: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    file_bytes: Option<&[u8]>,
+) -> PolarsResult<Option<Bitmap>> {
+    let (Some(predicate), Some(file_bytes)) = (predicate, file_bytes) else {
+        return Ok(None);
+    };
+    let (Some(evaluator), Some(live_variables)) =
+        (predicate.as_stats_evaluator(), predicate.live_variables())
+    else {
+        return Ok(None);
+    };
+
+    // Only the statistics of the columns in the predicate are needed, columns that are not in the
+    // file (e.g. hive partitions) are unknown to the evaluator.
+    let live_fields = schema
+        .iter_values()
+        .filter(|field| live_variables.contains(&field.name))
+        .collect::<Vec<_>>();
+    let stats_schema = Arc::new(Schema::from_iter(
+        live_fields.iter().map(|f| Field::from(*f)),
+    ));
+
+    let num_rows = md.num_rows();
+    let mut mask: Option<Bitmap> = None;
+
+    for (i, field) in live_fields.iter().enumerate() {
+        let mut columns = md.columns_under_root_iter(&field.name);
+        // Pages of nested columns don't map to rows.
+        if columns.len() != 1 {
+            continue;
+        }
+        let column = columns.next().unwrap();
+        let (Some(column_index), Some(offset_index)) = (
+            read_column_index(column, file_bytes)?,
+            read_offset_index(column, file_bytes)?,
+        ) else {
+            continue;
+        };
+        let pages = &offset_index.page_locations;
+        if pages.len() != column_index.null_pages.len() {
+            continue;
+        }
+
+        let page_stats = deserialize_column_index(
+            field,
+            &column.descriptor().descriptor.primitive_type,
+            &column_index,
+        )?;
+        let null_count = Series::try_from((PlSmallStr::EMPTY, page_stats.null_count)).unwrap();
+        let min_value = Series::try_from((PlSmallStr::EMPTY, page_stats.min_value)).unwrap();
+        let max_value = Series::try_from((PlSmallStr::EMPTY, page_stats.max_value)).unwrap();
+
+        let mut column_mask = MutableBitmap::with_capacity(num_rows);
+        for (page_idx, page) in pages.iter().enumerate() {
+            let start = page.first_row_index as usize;
+            let end = pages
+                .get(page_idx + 1)
+                .map_or(num_rows, |next| next.first_row_index as usize)
+                .max(start);
+
+            let stats = live_fields
+                .iter()
+                .enumerate()
+                .map(|(j, f)| {
+                    if i == j {
+                        ColumnStats::new(
+                            Field::from(*f),
+                            Some(null_count.slice(page_idx as i64, 1)),
+                            Some(min_value.slice(page_idx as i64, 1)),
+                            Some(max_value.slice(page_idx as i64, 1)),
+                        )
+                    } else {
+                        ColumnStats::from_field(Field::from(*f))
+                    }
+                })
+                .collect();
+            let stats = BatchStats::new(stats_schema.clone(), stats, Some(end - start));
+
+            let skip_page = matches!(evaluator.should_read(&stats), Ok(false));
+            column_mask.extend_constant(end - start, !skip_page);
+        }
+        if column_mask.len() != num_rows || column_mask.unset_bits() == 0 {
+            continue;
+        }
+
+        let column_mask = column_mask.freeze();
+        mask = Some(match mask {
+            Some(mask) => &mask & &column_mask,
+            None => column_mask,
+        });
+    }
+
+    Ok(mask)
+}
@@ -7,6 +7,7 @@ use arrow::bitmap::MutableBitmap;
 use arrow::datatypes::ArrowSchemaRef;
 use polars_core::chunked_array::builder::NullChunkedBuilder;
 use polars_core::prelude::*;
+use polars_core::series::IsSorted;
 use polars_core::utils::{accumulate_dataframes_vertical, split_df};
 use polars_core::POOL;
 use polars_parquet::parquet::error::ParquetResult;
@@ -19,8 +20,12 @@ use rayon::prelude::*;
 
 #[cfg(feature = "cloud")]
 use super::async_impl::FetchRowGroupsFromObjectStore;
+#[cfg(feature = "parquet_http")]
+use super::http::FetchRowGroupsFromHttp;
 use super::mmap::{mmap_columns, ColumnStore};
-use super::predicates::read_this_row_group;
+use super::predicates::{
+    read_these_rows_of_row_group, read_this_row_group_with_bloom_filters, sorted_row_group_range,
+};
 use super::to_metadata::ToMetadata;
 use super::utils::materialize_empty_df;
 use super::{mmap, ParallelStrategy};
@@ -135,6 +140,67 @@ fn column_idx_to_series(
     Ok(series)
 }
 
+/// The rows that are read of the `slice` of a row group. If statistics are used, the pages whose
+/// page index shows that none of their rows satisfy the predicate are filtered out as well.
+fn row_group_filter(
+    slice: (usize, usize),
+    md: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    predicate: Option<&dyn PhysicalIoExpr>,
+    use_statistics: bool,
+    store: &mmap::ColumnStore,
+) -> PolarsResult<Filter> {
+    let range = Filter::new_ranged(slice.0, slice.0 + slice.1);
+    if !use_statistics {
+        return Ok(range);
+    }
+    let Some(page_mask) = read_these_rows_of_row_group(predicate, md, schema, store.file_bytes())?
+    else {
+        return Ok(range);
+    };
+
+    let num_rows = md.num_rows();
+    let mut mask = MutableBitmap::with_capacity(num_rows);
+    mask.extend_constant(slice.0, false);
+    mask.extend_from_bitmap(&page_mask.sliced(slice.0, slice.1));
+    mask.extend_constant(num_rows - slice.0 - slice.1, false);
+    Ok(Filter::new_masked(mask.freeze()))
+}
+
+/// Add a row index to a `df` that is read from a row group with `filter`. `offset` is the index
+/// of the first row of the row group slice starting at `slice_start`.
+fn with_filtered_row_index(
+    df: &mut DataFrame,
+    row_index: &RowIndex,
+    offset: IdxSize,
+    slice_start: usize,
+    filter: &Filter,
+) {
+    match filter {
+        Filter::Range(_) => {
+            df.with_row_index_mut(row_index.name.clone(), Some(offset + row_index.offset));
+        },
+        Filter::Mask(mask) => {
+            let first = offset + row_index.offset;
+            let values = mask
+                .true_idx_iter()
+                .map(|i| first + (i - slice_start) as IdxSize)
+                .collect();
+            let mut ca = IdxCa::from_vec(row_index.name.clone(), values);
+            ca.set_sorted_flag(IsSorted::Ascending);
+            unsafe { df.get_columns_mut() }.insert(0, ca.into_series());
+        },
+    }
+}
+
+/// The number of rows that are selected by `filter`.
+fn filter_num_rows(filter: &Filter) -> usize {
+    match filter {
+        Filter::Range(range) => range.len(),
+        Filter::Mask(mask) => mask.set_bits(),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn rg_to_dfs(
     store: &mmap::ColumnStore,
@@ -165,6 +231,19 @@ fn rg_to_dfs(
         }
     }
 
+    // The row groups outside of this range cannot satisfy the predicate, as shown by the statistics
+    // of a sorted column.
+    let candidate_row_groups = if use_statistics {
+        sorted_row_group_range(
+            predicate,
+            &file_metadata.row_groups,
+            schema,
+            row_group_start..row_group_end,
+        )?
+    } else {
+        row_group_start..row_group_end
+    };
+
     use ParallelStrategy as S;
 
     if parallel == S::Prefiltered {
@@ -175,6 +254,7 @@ fn rg_to_dfs(
                     previous_row_count,
                     row_group_start,
                     row_group_end,
+                    candidate_row_groups,
                     file_metadata,
                     schema,
                     live_variables,
@@ -194,6 +274,7 @@ fn rg_to_dfs(
             previous_row_count,
             row_group_start,
             row_group_end,
+            candidate_row_groups,
             slice,
             file_metadata,
             schema,
@@ -208,6 +289,7 @@ fn rg_to_dfs(
             store,
             row_group_start,
             row_group_end,
+            candidate_row_groups,
             previous_row_count,
             slice,
             file_metadata,
@@ -227,6 +309,7 @@ fn rg_to_dfs_prefiltered(
     previous_row_count: &mut IdxSize,
     row_group_start: usize,
     row_group_end: usize,
+    candidate_row_groups: Range<usize>,
     file_metadata: &FileMetadata,
     schema: &ArrowSchemaRef,
     live_variables: Vec<PlSmallStr>,
@@ -304,14 +387,33 @@ fn rg_to_dfs_prefiltered(
             .map(|rg_idx| {
                 let md = &file_metadata.row_groups[rg_idx];
 
+                if !candidate_row_groups.contains(&rg_idx) {
+                    return Ok(None);
+                }
                 if use_statistics {
-                    match read_this_row_group(Some(predicate), md, schema) {
+                    match read_this_row_group_with_bloom_filters(
+                        Some(predicate),
+                        md,
+                        schema,
+                        store.file_bytes(),
+                    ) {
                         Ok(false) => return Ok(None),
                         Ok(true) => {},
                         Err(e) => return Err(e),
                     }
                 }
 
+                // The rows of the pages that cannot satisfy the predicate are not read.
+                let page_mask = if use_statistics {
+                    read_these_rows_of_row_group(Some(predicate), md, schema, store.file_bytes())?
+                } else {
+                    None
+                };
+                if page_mask.as_ref().is_some_and(|mask| mask.set_bits() == 0) {
+                    return Ok(None);
+                }
+                let live_filter = page_mask.clone().map(Filter::new_masked);
+
                 // Collect the data for the live columns
                 let live_columns = (0..num_live_columns)
                     .into_par_iter()
@@ -323,7 +425,13 @@ fn rg_to_dfs_prefiltered(
                             .columns_under_root_iter(name)
                             .collect::<Vec<_>>();
 
-                        column_idx_to_series(col_idx, field_md.as_slice(), None, schema, store)
+                        column_idx_to_series(
+                            col_idx,
+                            field_md.as_slice(),
+                            live_filter.clone(),
+                            schema,
+                            store,
+                        )
                     })
                     .collect::<PolarsResult<Vec<_>>>()?;
 
@@ -335,13 +443,25 @@ fn rg_to_dfs_prefiltered(
                     &mut df,
                     schema.as_ref(),
                     hive_partition_columns,
-                    md.num_rows(),
+                    page_mask
+                        .as_ref()
+                        .map_or(md.num_rows(), |mask| mask.set_bits()),
                 );
                 let s = predicate.evaluate_io(&df)?;
                 let mask = s.bool().expect("filter predicates was not of type boolean");
 
                 if let Some(rc) = &row_index {
-                    df.with_row_index_mut(rc.name.clone(), Some(rg_offsets[rg_idx] + rc.offset));
+                    match &live_filter {
+                        Some(filter) => {
+                            with_filtered_row_index(&mut df, rc, rg_offsets[rg_idx], 0, filter)
+                        },
+                        None => {
+                            df.with_row_index_mut(
+                                rc.name.clone(),
+                                Some(rg_offsets[rg_idx] + rc.offset),
+                            );
+                        },
+                    }
                 }
                 df = df.filter(mask)?;
 
@@ -357,7 +477,16 @@ fn rg_to_dfs_prefiltered(
                     }
                 }
 
-                let filter_mask = filter_mask.freeze();
+                let mut filter_mask = filter_mask.freeze();
+
+                // The mask only covers the rows of the pages that were read.
+                if let Some(page_mask) = &page_mask {
+                    let mut values = filter_mask.iter();
+                    filter_mask = page_mask
+                        .iter()
+                        .map(|is_read| is_read && values.next().unwrap())
+                        .collect();
+                }
 
                 debug_assert_eq!(md.num_rows(), filter_mask.len());
                 debug_assert_eq!(df.height(), filter_mask.set_bits());
@@ -405,7 +534,7 @@ fn rg_to_dfs_prefiltered(
                         #[cfg(debug_assertions)]
                         {
                             let md = &file_metadata.row_groups[rg_idx];
-                            debug_assert_eq!(md.num_rows(), mask.len());
+                            debug_assert_eq!(md.num_rows(), filter_mask.len());
                         }
                         let field_md = file_metadata.row_groups[rg_idx]
                             .columns_under_root_iter(name)
@@ -429,7 +558,7 @@ fn rg_to_dfs_prefiltered(
                                 store,
                             )?;
 
-                            debug_assert_eq!(array.len(), mask.len());
+                            debug_assert_eq!(array.len(), filter_mask.len());
 
                             let mask_arr = BooleanArray::new(
                                 ArrowDataType::Boolean,
@@ -500,6 +629,7 @@ fn rg_to_dfs_optionally_par_over_columns(
     previous_row_count: &mut IdxSize,
     row_group_start: usize,
     row_group_end: usize,
+    candidate_row_groups: Range<usize>,
     slice: (usize, usize),
     file_metadata: &FileMetadata,
     schema: &ArrowSchemaRef,
@@ -524,12 +654,23 @@ fn rg_to_dfs_optionally_par_over_columns(
             split_slice_at_file(&mut n_rows_processed, md.num_rows(), slice.0, slice_end);
         let current_row_count = md.num_rows() as IdxSize;
 
-        if use_statistics
-            && !read_this_row_group(predicate, &file_metadata.row_groups[rg_idx], schema)?
+        if !candidate_row_groups.contains(&rg_idx)
+            || use_statistics
+                && !read_this_row_group_with_bloom_filters(
+                    predicate,
+                    &file_metadata.row_groups[rg_idx],
+                    schema,
+                    store.file_bytes(),
+                )?
         {
             *previous_row_count += rg_slice.1 as IdxSize;
             continue;
         }
+        let filter = row_group_filter(rg_slice, md, schema, predicate, use_statistics, store)?;
+        if filter_num_rows(&filter) == 0 {
+            *previous_row_count += current_row_count;
+            continue;
+        }
         // test we don't read the parquet file if this env var is set
         #[cfg(debug_assertions)]
         {
@@ -547,7 +688,7 @@ fn rg_to_dfs_optionally_par_over_columns(
                         column_idx_to_series(
                             *column_i,
                             part.as_slice(),
-                            Some(Filter::new_ranged(rg_slice.0, rg_slice.0 + rg_slice.1)),
+                            Some(filter.clone()),
                             schema,
                             store,
                         )
@@ -564,7 +705,7 @@ fn rg_to_dfs_optionally_par_over_columns(
                     column_idx_to_series(
                         *column_i,
                         part.as_slice(),
-                        Some(Filter::new_ranged(rg_slice.0, rg_slice.0 + rg_slice.1)),
+                        Some(filter.clone()),
                         schema,
                         store,
                     )
@@ -574,10 +715,15 @@ fn rg_to_dfs_optionally_par_over_columns(
 
         let mut df = unsafe { DataFrame::new_no_checks(columns) };
         if let Some(rc) = &row_index {
-            df.with_row_index_mut(rc.name.clone(), Some(*previous_row_count + rc.offset));
+            with_filtered_row_index(&mut df, rc, *previous_row_count, rg_slice.0, &filter);
         }
 
-        materialize_hive_partitions(&mut df, schema.as_ref(), hive_partition_columns, rg_slice.1);
+        materialize_hive_partitions(
+            &mut df,
+            schema.as_ref(),
+            hive_partition_columns,
+            filter_num_rows(&filter),
+        );
         apply_predicate(&mut df, predicate, true)?;
 
         *previous_row_count = previous_row_count.checked_add(current_row_count).ok_or_else(||
@@ -603,6 +749,7 @@ fn rg_to_dfs_par_over_rg(
     store: &mmap::ColumnStore,
     row_group_start: usize,
     row_group_end: usize,
+    candidate_row_groups: Range<usize>,
     previous_row_count: &mut IdxSize,
     slice: (usize, usize),
     file_metadata: &FileMetadata,
@@ -642,11 +789,21 @@ fn rg_to_dfs_par_over_rg(
         // Ensure all row groups are partitioned.
         row_groups
             .into_par_iter()
-            .enumerate()
-            .map(|(iter_idx, (_rg_idx, _md, slice, row_count_start))| {
-                let md = &file_metadata.row_groups[iter_idx];
-
-                if slice.1 == 0 || use_statistics && !read_this_row_group(predicate, md, schema)? {
+            .map(|(rg_idx, md, slice, row_count_start)| {
+                if slice.1 == 0
+                    || !candidate_row_groups.contains(&rg_idx)
+                    || use_statistics
+                        && !read_this_row_group_with_bloom_filters(
+                            predicate,
+                            md,
+                            schema,
+                            store.file_bytes(),
+                        )?
+                {
+                    return Ok(None);
+                }
+                let filter = row_group_filter(slice, md, schema, predicate, use_statistics, store)?;
+                if filter_num_rows(&filter) == 0 {
                     return Ok(None);
                 }
                 // test we don't read the parquet file if this env var is set
@@ -664,7 +821,7 @@ fn rg_to_dfs_par_over_rg(
                         column_idx_to_series(
                             *column_i,
                             field_md.as_slice(),
-                            Some(Filter::new_ranged(slice.0, slice.0 + slice.1)),
+                            Some(filter.clone()),
                             schema,
                             store,
                         )
@@ -674,17 +831,14 @@ fn rg_to_dfs_par_over_rg(
                 let mut df = unsafe { DataFrame::new_no_checks(columns) };
 
                 if let Some(rc) = &row_index {
-                    df.with_row_index_mut(
-                        rc.name.clone(),
-                        Some(row_count_start as IdxSize + rc.offset),
-                    );
+                    with_filtered_row_index(&mut df, rc, row_count_start, slice.0, &filter);
                 }
 
                 materialize_hive_partitions(
                     &mut df,
                     schema.as_ref(),
                     hive_partition_columns,
-                    slice.1,
+                    filter_num_rows(&filter),
                 );
                 apply_predicate(&mut df, predicate, false)?;
 
@@ -825,6 +979,8 @@ impl FetchRowGroupsFromMmapReader {
 pub enum RowGroupFetcher {
     #[cfg(feature = "cloud")]
     ObjectStore(FetchRowGroupsFromObjectStore),
+    #[cfg(feature = "parquet_http")]
+    Http(FetchRowGroupsFromHttp),
     Local(FetchRowGroupsFromMmapReader),
 }
 
@@ -835,6 +991,13 @@ impl From<FetchRowGroupsFromObjectStore> for RowGroupFetcher {
     }
 }
 
+#[cfg(feature = "parquet_http")]
+impl From<FetchRowGroupsFromHttp> for RowGroupFetcher {
+    fn from(value: FetchRowGroupsFromHttp) -> Self {
+        RowGroupFetcher::Http(value)
+    }
+}
+
 impl From<FetchRowGroupsFromMmapReader> for RowGroupFetcher {
     fn from(value: FetchRowGroupsFromMmapReader) -> Self {
         RowGroupFetcher::Local(value)
@@ -842,11 +1005,30 @@ impl From<FetchRowGroupsFromMmapReader> for RowGroupFetcher {
 }
 
 impl RowGroupFetcher {
-    async fn fetch_row_groups(&mut self, _row_groups: Range<usize>) -> PolarsResult<ColumnStore> {
+    /// Fetch the row groups of `_row_groups`, or the first of them that are downloaded. Returns
+    /// the range of the fetched row groups.
+    async fn fetch_row_groups(
+        &mut self,
+        _row_groups: Range<usize>,
+    ) -> PolarsResult<(ColumnStore, Range<usize>)> {
         match self {
-            RowGroupFetcher::Local(f) => f.fetch_row_groups(_row_groups),
+            RowGroupFetcher::Local(f) => {
+                Ok((f.fetch_row_groups(_row_groups.clone())?, _row_groups))
+            },
             #[cfg(feature = "cloud")]
             RowGroupFetcher::ObjectStore(f) => f.fetch_row_groups(_row_groups).await,
+            #[cfg(feature = "parquet_http")]
+            RowGroupFetcher::Http(f) => {
+                Ok((f.fetch_row_groups(_row_groups.clone()).await?, _row_groups))
+            },
+        }
+    }
+
+    /// Release the memory of the fetched row groups, once they are decoded.
+    fn finish_decoding(&mut self) {
+        #[cfg(feature = "cloud")]
+        if let RowGroupFetcher::ObjectStore(f) = self {
+            f.finish_decoding()
         }
     }
 }
@@ -1011,13 +1193,14 @@ impl BatchedParquetReader {
                 self.slice,
                 &self.metadata.row_groups,
             );
+            let requested_end = row_group_range.end;
 
-            let store = self
+            let (store, row_group_range) = self
                 .row_group_fetcher
-                .fetch_row_groups(row_group_range.clone())
+                .fetch_row_groups(row_group_range)
                 .await?;
 
-            let mut dfs = match store {
+            let dfs = match store {
                 ColumnStore::Local(_) => rg_to_dfs(
                     &store,
                     &mut self.rows_read,
@@ -1089,7 +1272,9 @@ impl BatchedParquetReader {
                     self.rows_read = rows_read;
                     dfs
                 },
-            }?;
+            };
+            self.row_group_fetcher.finish_decoding();
+            let mut dfs = dfs?;
 
             if let Some(ca) = self.include_file_path.as_mut() {
                 let mut max_len = 0;
@@ -1124,7 +1309,13 @@ impl BatchedParquetReader {
                 }
             }
 
-            self.row_group_offset += n;
+            // The rest of the row groups are fetched in the next batch, if only the first of them
+            // were downloaded.
+            if row_group_range.end == requested_end {
+                self.row_group_offset += n;
+            } else {
+                self.row_group_offset = row_group_range.end;
+            }
 
             // case where there is no data in the file
             // the streaming engine needs at least a single chunk
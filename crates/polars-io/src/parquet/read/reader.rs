@@ -1,4 +1,5 @@
 use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
 use std::sync::Arc;
 
 use arrow::datatypes::ArrowSchemaRef;
@@ -18,7 +19,9 @@ use super::utils::materialize_empty_df;
 #[cfg(feature = "cloud")]
 use crate::cloud::CloudOptions;
 use crate::mmap::MmapBytesReader;
+use crate::parquet::encryption::ParquetDecryption;
 use crate::parquet::metadata::FileMetadataRef;
+use crate::parquet::metadata_cache::{self, MetadataCacheKey};
 use crate::predicates::PhysicalIoExpr;
 use crate::prelude::*;
 use crate::RowIndex;
@@ -40,6 +43,8 @@ pub struct ParquetReader<R: Read + Seek> {
     hive_partition_columns: Option<Vec<Series>>,
     include_file_path: Option<(PlSmallStr, Arc<str>)>,
     use_statistics: bool,
+    decryption: Option<ParquetDecryption>,
+    metadata_cache_key: Option<MetadataCacheKey>,
 }
 
 impl<R: MmapBytesReader> ParquetReader<R> {
@@ -138,9 +143,38 @@ impl<R: MmapBytesReader> ParquetReader<R> {
         self
     }
 
+    /// Keys to decrypt encrypted files. This must be set before the metadata is read.
+    pub fn with_decryption(mut self, decryption: Option<ParquetDecryption>) -> Self {
+        self.decryption = decryption;
+        self
+    }
+
+    /// Look up the metadata in the [metadata cache](metadata_cache) if the reader reads the
+    /// local file at `path` and the cache is enabled. Encrypted files are never cached.
+    pub fn with_metadata_cache(mut self, path: Option<&Path>) -> Self {
+        self.metadata_cache_key = path
+            .filter(|_| metadata_cache::get_env_metadata_cache())
+            .and_then(MetadataCacheKey::from_local_file);
+        self
+    }
+
     pub fn get_metadata(&mut self) -> PolarsResult<&FileMetadataRef> {
         if self.metadata.is_none() {
-            self.metadata = Some(Arc::new(read::read_metadata(&mut self.reader)?));
+            let cache_key = self
+                .metadata_cache_key
+                .take()
+                .filter(|_| self.decryption.is_none());
+            self.metadata = cache_key.as_ref().and_then(metadata_cache::get);
+            if self.metadata.is_none() {
+                let metadata = Arc::new(read::read_metadata_with_decryption(
+                    &mut self.reader,
+                    self.decryption.as_ref().map(ParquetDecryption::properties),
+                )?);
+                if let Some(cache_key) = cache_key {
+                    metadata_cache::insert(cache_key, metadata.clone());
+                }
+                self.metadata = Some(metadata);
+            }
         }
         Ok(self.metadata.as_ref().unwrap())
     }
@@ -194,6 +228,8 @@ impl<R: MmapBytesReader> SerReader<R> for ParquetReader<R> {
             use_statistics: true,
             hive_partition_columns: None,
             include_file_path: None,
+            decryption: None,
+            metadata_cache_key: None,
         }
     }
 
@@ -300,6 +336,12 @@ impl ParquetAsyncReader {
         Ok(self)
     }
 
+    /// Keys to decrypt encrypted files. This must be set before the metadata is read.
+    pub fn with_decryption(mut self, decryption: Option<ParquetDecryption>) -> Self {
+        self.reader = self.reader.with_decryption(decryption);
+        self
+    }
+
     pub async fn schema(&mut self) -> PolarsResult<ArrowSchemaRef> {
         self.schema = Some(match self.schema.as_ref() {
             Some(schema) => Arc::clone(schema),
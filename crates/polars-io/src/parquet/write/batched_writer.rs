@@ -8,25 +8,37 @@ use polars_parquet::read::ParquetError;
 use polars_parquet::write::{
     array_to_columns, CompressedPage, Compressor, DynIter, DynStreamingIterator, Encoding,
     FallibleStreamingIterator, FileWriter, Page, ParquetType, RowGroupIterColumns,
-    SchemaDescriptor, WriteOptions,
+    SchemaDescriptor, ThriftFileMetadata, WriteOptions,
 };
 use rayon::prelude::*;
 
+use crate::parquet::bloom_filter::build_bloom_filter;
+
 pub struct BatchedWriter<W: Write> {
     // A mutex so that streaming engine can get concurrent read access to
     // compress pages.
     pub(super) writer: Mutex<FileWriter<W>>,
     pub(super) parquet_schema: SchemaDescriptor,
     pub(super) encodings: Vec<Vec<Encoding>>,
-    pub(super) options: WriteOptions,
+    /// The options of every column, which can differ in compression.
+    pub(super) column_options: Vec<WriteOptions>,
+    /// The index of the field of every parquet column that has a bloom filter. Empty if no
+    /// bloom filters are written.
+    pub(super) bloom_filter_columns: Vec<Option<usize>>,
     pub(super) parallel: bool,
 }
 
+/// A row group that is encoded and compressed, but not yet written.
+pub struct EncodedRowGroup {
+    columns: RowGroupIterColumns<'static, PolarsError>,
+    bloom_filters: Vec<Option<Vec<u8>>>,
+}
+
 impl<W: Write> BatchedWriter<W> {
     pub fn encode_and_compress<'a>(
         &'a self,
         df: &'a DataFrame,
-    ) -> impl Iterator<Item = PolarsResult<RowGroupIterColumns<'static, PolarsError>>> + 'a {
+    ) -> impl Iterator<Item = PolarsResult<EncodedRowGroup>> + 'a {
         let rb_iter = df.iter_chunks(CompatLevel::newest(), false);
         rb_iter.filter_map(move |batch| match batch.len() {
             0 => None,
@@ -35,7 +47,8 @@ impl<W: Write> BatchedWriter<W> {
                     batch,
                     self.parquet_schema.fields(),
                     self.encodings.as_ref(),
-                    self.options,
+                    &self.column_options,
+                    &self.bloom_filter_columns,
                 );
 
                 Some(row_group)
@@ -52,13 +65,15 @@ impl<W: Write> BatchedWriter<W> {
             df,
             &self.parquet_schema,
             &self.encodings,
-            self.options,
+            &self.column_options,
+            &self.bloom_filter_columns,
             self.parallel,
         );
         // Lock before looping so that order is maintained under contention.
         let mut writer = self.writer.lock().unwrap();
         for group in row_group_iter {
-            writer.write(group?)?;
+            let group = group?;
+            writer.write_with_bloom_filters(group.columns, group.bloom_filters)?;
         }
         Ok(())
     }
@@ -67,14 +82,11 @@ impl<W: Write> BatchedWriter<W> {
         &self.writer
     }
 
-    pub fn write_row_groups(
-        &self,
-        rgs: Vec<RowGroupIterColumns<'static, PolarsError>>,
-    ) -> PolarsResult<()> {
+    pub fn write_row_groups(&self, rgs: Vec<EncodedRowGroup>) -> PolarsResult<()> {
         // Lock before looping so that order is maintained.
         let mut writer = self.writer.lock().unwrap();
         for group in rgs {
-            writer.write(group)?;
+            writer.write_with_bloom_filters(group.columns, group.bloom_filters)?;
         }
         Ok(())
     }
@@ -85,6 +97,11 @@ impl<W: Write> BatchedWriter<W> {
         let size = writer.end(None)?;
         Ok(size)
     }
+
+    /// The footer of the parquet file. This is `Some` iff [`Self::finish`] has been called.
+    pub fn metadata(&self) -> Option<ThriftFileMetadata> {
+        self.writer.lock().unwrap().metadata().cloned()
+    }
 }
 
 // Note that the df should be rechunked
@@ -92,15 +109,22 @@ fn prepare_rg_iter<'a>(
     df: &'a DataFrame,
     parquet_schema: &'a SchemaDescriptor,
     encodings: &'a [Vec<Encoding>],
-    options: WriteOptions,
+    column_options: &'a [WriteOptions],
+    bloom_filter_columns: &'a [Option<usize>],
     parallel: bool,
-) -> impl Iterator<Item = PolarsResult<RowGroupIterColumns<'static, PolarsError>>> + 'a {
+) -> impl Iterator<Item = PolarsResult<EncodedRowGroup>> + 'a {
     let rb_iter = df.iter_chunks(CompatLevel::newest(), false);
     rb_iter.filter_map(move |batch| match batch.len() {
         0 => None,
         _ => {
-            let row_group =
-                create_serializer(batch, parquet_schema.fields(), encodings, options, parallel);
+            let row_group = create_serializer(
+                batch,
+                parquet_schema.fields(),
+                encodings,
+                column_options,
+                bloom_filter_columns,
+                parallel,
+            );
 
             Some(row_group)
         },
@@ -143,16 +167,30 @@ fn array_to_pages_iter(
     pages_iter_to_compressor(encoded_columns, options)
 }
 
+fn create_bloom_filters(
+    batch: &RecordBatch,
+    bloom_filter_columns: &[Option<usize>],
+) -> Vec<Option<Vec<u8>>> {
+    bloom_filter_columns
+        .iter()
+        .map(|field_idx| field_idx.map(|i| build_bloom_filter(batch.columns()[i].as_ref())))
+        .collect()
+}
+
 fn create_serializer(
     batch: RecordBatch,
     fields: &[ParquetType],
     encodings: &[Vec<Encoding>],
-    options: WriteOptions,
+    column_options: &[WriteOptions],
+    bloom_filter_columns: &[Option<usize>],
     parallel: bool,
-) -> PolarsResult<RowGroupIterColumns<'static, PolarsError>> {
-    let func = move |((array, type_), encoding): ((&ArrayRef, &ParquetType), &Vec<Encoding>)| {
-        array_to_pages_iter(array, type_, encoding, options)
-    };
+) -> PolarsResult<EncodedRowGroup> {
+    let bloom_filters = create_bloom_filters(&batch, bloom_filter_columns);
+
+    let func = move |(((array, type_), encoding), options): (
+        ((&ArrayRef, &ParquetType), &Vec<Encoding>),
+        &WriteOptions,
+    )| { array_to_pages_iter(array, type_, encoding, *options) };
 
     let columns = if parallel {
         POOL.install(|| {
@@ -161,6 +199,7 @@ fn create_serializer(
                 .par_iter()
                 .zip(fields)
                 .zip(encodings)
+                .zip(column_options)
                 .flat_map(func)
                 .collect::<Vec<_>>()
         })
@@ -170,13 +209,17 @@ fn create_serializer(
             .iter()
             .zip(fields)
             .zip(encodings)
+            .zip(column_options)
             .flat_map(func)
             .collect::<Vec<_>>()
     };
 
     let row_group = DynIter::new(columns.into_iter());
 
-    Ok(row_group)
+    Ok(EncodedRowGroup {
+        columns: row_group,
+        bloom_filters,
+    })
 }
 
 /// This serializer encodes and compresses all eagerly in memory.
@@ -185,21 +228,28 @@ fn create_eager_serializer(
     batch: RecordBatch,
     fields: &[ParquetType],
     encodings: &[Vec<Encoding>],
-    options: WriteOptions,
-) -> PolarsResult<RowGroupIterColumns<'static, PolarsError>> {
-    let func = move |((array, type_), encoding): ((&ArrayRef, &ParquetType), &Vec<Encoding>)| {
-        array_to_pages_iter(array, type_, encoding, options)
-    };
+    column_options: &[WriteOptions],
+    bloom_filter_columns: &[Option<usize>],
+) -> PolarsResult<EncodedRowGroup> {
+    let bloom_filters = create_bloom_filters(&batch, bloom_filter_columns);
+    let func = move |(((array, type_), encoding), options): (
+        ((&ArrayRef, &ParquetType), &Vec<Encoding>),
+        &WriteOptions,
+    )| { array_to_pages_iter(array, type_, encoding, *options) };
 
     let columns = batch
         .columns()
         .iter()
         .zip(fields)
         .zip(encodings)
+        .zip(column_options)
         .flat_map(func)
         .collect::<Vec<_>>();
 
     let row_group = DynIter::new(columns.into_iter());
 
-    Ok(row_group)
+    Ok(EncodedRowGroup {
+        columns: row_group,
+        bloom_filters,
+    })
 }
@@ -1,24 +1,40 @@
 use polars_error::PolarsResult;
 use polars_parquet::write::{
-    BrotliLevel as BrotliLevelParquet, CompressionOptions, GzipLevel as GzipLevelParquet,
+    BrotliLevel as BrotliLevelParquet, CompressionOptions, Encoding, GzipLevel as GzipLevelParquet,
     StatisticsOptions, ZstdLevel as ZstdLevelParquet,
 };
+use polars_utils::pl_str::PlSmallStr;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Hash)]
+use crate::parquet::encryption::ParquetEncryption;
+
+#[derive(Clone, Debug, PartialEq, Eq, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParquetWriteOptions {
     /// Data page compression
     pub compression: ParquetCompression,
+    /// Data page compression of specific columns, overriding `compression`.
+    pub column_compression: Vec<(PlSmallStr, ParquetCompression)>,
+    /// Encoding of specific columns, overriding the default encoding of their type.
+    pub column_encoding: Vec<(PlSmallStr, ParquetEncoding)>,
     /// Compute and write column statistics.
     pub statistics: StatisticsOptions,
-    /// If `None` will be all written to a single row group.
+    /// Columns for which bloom filters are written, so that readers can skip row groups that
+    /// don't contain a value.
+    pub bloom_filter_columns: Vec<PlSmallStr>,
+    /// If `None` will be all written to a single row group. The streaming sink then writes a
+    /// row group per morsel.
     pub row_group_size: Option<usize>,
     /// if `None` will be 1024^2 bytes
     pub data_page_size: Option<usize>,
+    /// Dictionaries larger than this are plain encoded. If `None` they are not limited.
+    pub dictionary_page_size: Option<usize>,
     /// maintain the order the data was processed
     pub maintain_order: bool,
+    /// The keys to encrypt the file with. They are not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub encryption: Option<ParquetEncryption>,
 }
 
 /// The compression strategy to use for writing Parquet files.
@@ -40,6 +56,35 @@ impl Default for ParquetCompression {
     }
 }
 
+/// The encoding of the values of a column.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParquetEncoding {
+    Plain,
+    /// Encode the values as indices into a dictionary page. Falls back to `Plain` if the column
+    /// has too many distinct values.
+    Dictionary,
+    /// Delta encoding of integer columns.
+    DeltaBinaryPacked,
+    /// Delta encoding of the lengths of string and binary columns.
+    DeltaLengthByteArray,
+    /// Splits the bytes of the values of integer and float columns into streams, which often
+    /// compresses float columns better.
+    ByteStreamSplit,
+}
+
+impl From<ParquetEncoding> for Encoding {
+    fn from(value: ParquetEncoding) -> Self {
+        match value {
+            ParquetEncoding::Plain => Encoding::Plain,
+            ParquetEncoding::Dictionary => Encoding::RleDictionary,
+            ParquetEncoding::DeltaBinaryPacked => Encoding::DeltaBinaryPacked,
+            ParquetEncoding::DeltaLengthByteArray => Encoding::DeltaLengthByteArray,
+            ParquetEncoding::ByteStreamSplit => Encoding::ByteStreamSplit,
+        }
+    }
+}
+
 /// A valid Gzip compression level.
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
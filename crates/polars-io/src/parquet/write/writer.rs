@@ -4,13 +4,15 @@ use std::sync::Mutex;
 use arrow::datatypes::PhysicalType;
 use polars_core::prelude::*;
 use polars_parquet::write::{
-    to_parquet_schema, transverse, CompressionOptions, Encoding, FileWriter, StatisticsOptions,
-    Version, WriteOptions,
+    to_parquet_schema, transverse, CompressionOptions, Encoding, FileWriter, SchemaDescriptor,
+    StatisticsOptions, Version, WriteOptions,
 };
 
 use super::batched_writer::BatchedWriter;
-use super::options::ParquetCompression;
+use super::options::{ParquetCompression, ParquetEncoding};
 use super::ParquetWriteOptions;
+use crate::parquet::bloom_filter::is_supported;
+use crate::parquet::encryption::ParquetEncryption;
 use crate::prelude::chunk_df_for_writing;
 use crate::shared::schema_to_arrow_checked;
 
@@ -21,9 +23,14 @@ impl ParquetWriteOptions {
     {
         ParquetWriter::new(f)
             .with_compression(self.compression)
+            .with_column_compression(self.column_compression.clone())
+            .with_column_encoding(self.column_encoding.clone())
             .with_statistics(self.statistics)
+            .with_bloom_filter_columns(self.bloom_filter_columns.clone())
             .with_row_group_size(self.row_group_size)
             .with_data_page_size(self.data_page_size)
+            .with_dictionary_page_size(self.dictionary_page_size)
+            .with_encryption(self.encryption.clone())
     }
 }
 
@@ -33,14 +40,24 @@ pub struct ParquetWriter<W> {
     writer: W,
     /// Data page compression
     compression: CompressionOptions,
+    /// Data page compression of specific columns
+    column_compression: Vec<(PlSmallStr, ParquetCompression)>,
+    /// Encoding of specific columns
+    column_encoding: Vec<(PlSmallStr, ParquetEncoding)>,
     /// Compute and write column statistics.
     statistics: StatisticsOptions,
+    /// Write bloom filters of these columns
+    bloom_filter_columns: Vec<PlSmallStr>,
     /// if `None` will be 512^2 rows
     row_group_size: Option<usize>,
     /// if `None` will be 1024^2 bytes
     data_page_size: Option<usize>,
+    /// if `None` dictionaries are not limited
+    dictionary_page_size: Option<usize>,
     /// Serialize columns in parallel
     parallel: bool,
+    /// Encrypt the file with these properties
+    encryption: Option<ParquetEncryption>,
 }
 
 impl<W> ParquetWriter<W>
@@ -55,10 +72,15 @@ where
         ParquetWriter {
             writer,
             compression: ParquetCompression::default().into(),
+            column_compression: vec![],
+            column_encoding: vec![],
             statistics: StatisticsOptions::default(),
+            bloom_filter_columns: vec![],
             row_group_size: None,
             data_page_size: None,
+            dictionary_page_size: None,
             parallel: true,
+            encryption: None,
         }
     }
 
@@ -71,12 +93,47 @@ where
         self
     }
 
+    /// Set the compression used for specific columns, overriding the compression set with
+    /// [`ParquetWriter::with_compression`].
+    pub fn with_column_compression(
+        mut self,
+        column_compression: Vec<(PlSmallStr, ParquetCompression)>,
+    ) -> Self {
+        self.column_compression = column_compression;
+        self
+    }
+
+    /// Set the encoding of specific columns. By default, integer, string and binary columns are
+    /// dictionary encoded if they have few distinct values, and the other columns are plain
+    /// encoded.
+    ///
+    /// Only top-level columns that are not nested can be given an encoding.
+    pub fn with_column_encoding(
+        mut self,
+        column_encoding: Vec<(PlSmallStr, ParquetEncoding)>,
+    ) -> Self {
+        self.column_encoding = column_encoding;
+        self
+    }
+
     /// Compute and write statistic
     pub fn with_statistics(mut self, statistics: StatisticsOptions) -> Self {
         self.statistics = statistics;
         self
     }
 
+    /// Write a bloom filter of every row group of the given columns, which lets readers skip
+    /// the row groups that don't contain the value of an equality predicate, e.g.
+    /// `col("id") == lit(42)`. This is most useful for columns with many distinct values that
+    /// are not sorted, as the minimum and maximum statistics can't be used to skip row groups
+    /// for those.
+    ///
+    /// Bloom filters are supported for integer, float, string and binary columns.
+    pub fn with_bloom_filter_columns(mut self, columns: Vec<PlSmallStr>) -> Self {
+        self.bloom_filter_columns = columns;
+        self
+    }
+
     /// Set the row group size (in number of rows) during writing. This can reduce memory pressure and improve
     /// writing performance.
     pub fn with_row_group_size(mut self, size: Option<usize>) -> Self {
@@ -90,6 +147,22 @@ where
         self
     }
 
+    /// Sets the maximum bytes size of a dictionary page. Columns whose dictionary is larger are
+    /// plain encoded. If `None` dictionaries are not limited.
+    pub fn with_dictionary_page_size(mut self, limit: Option<usize>) -> Self {
+        self.dictionary_page_size = limit;
+        self
+    }
+
+    /// Encrypt the file with Parquet modular encryption. The footer is encrypted with the footer
+    /// key, and the columns with the footer key or their own column key.
+    ///
+    /// The bloom filters and page indexes of encrypted columns are not written.
+    pub fn with_encryption(mut self, encryption: Option<ParquetEncryption>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
     /// Serialize columns in parallel
     pub fn set_parallel(mut self, parallel: bool) -> Self {
         self.parallel = parallel;
@@ -97,17 +170,25 @@ where
     }
 
     pub fn batched(self, schema: &Schema) -> PolarsResult<BatchedWriter<W>> {
+        let column_options = self.materialize_column_options(schema)?;
         let schema = schema_to_arrow_checked(schema, CompatLevel::newest(), "parquet")?;
         let parquet_schema = to_parquet_schema(&schema)?;
-        let encodings = get_encodings(&schema);
+        let bloom_filter_columns =
+            self.materialize_bloom_filter_columns(&schema, &parquet_schema)?;
+        let encodings = self.materialize_encodings(&schema)?;
         let options = self.materialize_options();
-        let writer = Mutex::new(FileWriter::try_new(self.writer, schema, options)?);
+        let mut writer = FileWriter::try_new(self.writer, schema, options)?;
+        if let Some(encryption) = &self.encryption {
+            writer = writer.with_encryption(encryption.properties())?;
+        }
+        let writer = Mutex::new(writer);
 
         Ok(BatchedWriter {
             writer,
             parquet_schema,
             encodings,
-            options,
+            column_options,
+            bloom_filter_columns,
             parallel: self.parallel,
         })
     }
@@ -118,7 +199,61 @@ where
             compression: self.compression,
             version: Version::V1,
             data_page_size: self.data_page_size,
+            dictionary_page_size: self.dictionary_page_size,
+        }
+    }
+
+    /// The options of every column of `schema`.
+    fn materialize_column_options(&self, schema: &Schema) -> PolarsResult<Vec<WriteOptions>> {
+        let mut column_options = vec![self.materialize_options(); schema.len()];
+        for (name, compression) in &self.column_compression {
+            let idx = schema.try_index_of(name)?;
+            column_options[idx].compression = (*compression).into();
         }
+        Ok(column_options)
+    }
+
+    /// The encodings of the parquet columns of every field of `schema`.
+    fn materialize_encodings(&self, schema: &ArrowSchema) -> PolarsResult<Vec<Vec<Encoding>>> {
+        let mut encodings = get_encodings(schema);
+        for (name, encoding) in &self.column_encoding {
+            let (field_idx, _, field) = schema.try_get_full(name)?;
+            polars_ensure!(
+                is_encoding_supported(&field.dtype, *encoding),
+                InvalidOperation: "{:?} encoding is not supported for column {:?} of type {:?}",
+                encoding, name, field.dtype
+            );
+            encodings[field_idx] = vec![(*encoding).into()];
+        }
+        Ok(encodings)
+    }
+
+    /// The index of the field of every parquet column that has a bloom filter.
+    fn materialize_bloom_filter_columns(
+        &self,
+        schema: &ArrowSchema,
+        parquet_schema: &SchemaDescriptor,
+    ) -> PolarsResult<Vec<Option<usize>>> {
+        if self.bloom_filter_columns.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut bloom_filter_columns = vec![None; parquet_schema.columns().len()];
+        for name in &self.bloom_filter_columns {
+            let (field_idx, _, field) = schema.try_get_full(name)?;
+            polars_ensure!(
+                is_supported(&field.dtype),
+                InvalidOperation: "bloom filters are not supported for column {:?} of type {:?}",
+                name, field.dtype
+            );
+            // Non-nested fields are a single parquet column.
+            let column_idx = parquet_schema
+                .columns()
+                .iter()
+                .position(|column| column.path_in_schema.first() == Some(name))
+                .unwrap();
+            bloom_filter_columns[column_idx] = Some(field_idx);
+        }
+        Ok(bloom_filter_columns)
     }
 
     /// Write the given DataFrame in the writer `W`. Returns the total size of the file.
@@ -137,6 +272,43 @@ fn get_encodings(schema: &ArrowSchema) -> Vec<Vec<Encoding>> {
         .collect()
 }
 
+/// Whether the non-nested columns of type `dtype` can be written with `encoding`.
+fn is_encoding_supported(dtype: &ArrowDataType, encoding: ParquetEncoding) -> bool {
+    use arrow::types::PrimitiveType::*;
+
+    let physical_type = match dtype.to_logical_type() {
+        ArrowDataType::Dictionary(_, values, _) => values.to_physical_type(),
+        _ => dtype.to_physical_type(),
+    };
+    let is_integer = matches!(
+        physical_type,
+        PhysicalType::Primitive(Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64)
+    );
+    let is_float = matches!(physical_type, PhysicalType::Primitive(Float32 | Float64));
+    let is_binary = matches!(
+        physical_type,
+        PhysicalType::LargeBinary
+            | PhysicalType::LargeUtf8
+            | PhysicalType::BinaryView
+            | PhysicalType::Utf8View
+    );
+    let is_nested = matches!(
+        physical_type,
+        PhysicalType::List
+            | PhysicalType::LargeList
+            | PhysicalType::FixedSizeList
+            | PhysicalType::Struct
+            | PhysicalType::Map
+    );
+
+    match encoding {
+        ParquetEncoding::Plain | ParquetEncoding::Dictionary => !is_nested,
+        ParquetEncoding::DeltaBinaryPacked => is_integer,
+        ParquetEncoding::DeltaLengthByteArray => is_binary,
+        ParquetEncoding::ByteStreamSplit => is_integer || is_float,
+    }
+}
+
 /// Declare encodings
 fn encoding_map(dtype: &ArrowDataType) -> Encoding {
     match dtype.to_physical_type() {
@@ -1,17 +1,19 @@
 //! Functionality for writing a DataFrame partitioned into multiple files.
 
 use std::path::Path;
+use std::sync::Mutex;
 
 use polars_core::prelude::*;
 use polars_core::series::IsSorted;
 use polars_core::POOL;
+use polars_parquet::write::{write_metadata_sidecar, ThriftFileMetadata};
 use rayon::prelude::*;
 
 use crate::parquet::write::ParquetWriteOptions;
 #[cfg(feature = "ipc")]
 use crate::prelude::IpcWriterOptions;
-use crate::prelude::URL_ENCODE_CHAR_SET;
-use crate::{SerWriter, WriteDataFrameToFile};
+use crate::utils::chunk_df_for_writing;
+use crate::{PartitionedWriteOptions, SerWriter, WriteDataFrameToFile};
 
 impl WriteDataFrameToFile for ParquetWriteOptions {
     fn write_df_to_file<W: std::io::Write>(&self, mut df: DataFrame, file: W) -> PolarsResult<()> {
@@ -28,16 +30,22 @@ impl WriteDataFrameToFile for IpcWriterOptions {
     }
 }
 
-fn write_partitioned_dataset_impl<W>(
+/// Writes a file. The arguments are the data, the path of the file and the path of the file
+/// relative to the root of the dataset.
+type WritePart<'a> = dyn Fn(DataFrame, &Path, &str) -> PolarsResult<()> + Send + Sync + 'a;
+
+fn write_partitioned_dataset_impl(
     df: &mut DataFrame,
     path: &Path,
     partition_by: Vec<PlSmallStr>,
-    file_write_options: &W,
     chunk_size: usize,
-) -> PolarsResult<()>
-where
-    W: WriteDataFrameToFile + Send + Sync,
-{
+    max_rows_per_file: Option<usize>,
+    write_part: &WritePart,
+) -> PolarsResult<()> {
+    polars_ensure!(
+        max_rows_per_file != Some(0),
+        InvalidOperation: "'max_rows_per_file' must be greater than 0"
+    );
     let partition_by = partition_by
         .into_iter()
         .map(Into::into)
@@ -61,30 +69,7 @@ where
             })
             .collect::<PolarsResult<Vec<_>>>()?;
 
-        move |df: &DataFrame| {
-            let cols = df.get_columns();
-
-            partition_by_col_idx
-                .iter()
-                .map(|&i| {
-                    let s = &cols[i].slice(0, 1).cast(&DataType::String).unwrap();
-
-                    format!(
-                        "{}={}",
-                        s.name(),
-                        percent_encoding::percent_encode(
-                            s.str()
-                                .unwrap()
-                                .get(0)
-                                .unwrap_or("__HIVE_DEFAULT_PARTITION__")
-                                .as_bytes(),
-                            URL_ENCODE_CHAR_SET
-                        )
-                    )
-                })
-                .collect::<Vec<_>>()
-                .join("/")
-        }
+        move |df: &DataFrame| crate::hive::get_hive_path_part(df, &partition_by_col_idx)
     };
 
     let base_path = path;
@@ -92,10 +77,10 @@ where
 
     let init_part_base_dir = |part_df: &DataFrame| {
         let path_part = get_hive_path_part(part_df);
-        let dir = base_path.join(path_part);
+        let dir = base_path.join(&path_part);
         std::fs::create_dir_all(&dir)?;
 
-        PolarsResult::Ok(dir)
+        PolarsResult::Ok((dir, path_part))
     };
 
     fn get_path_for_index(i: usize) -> String {
@@ -104,15 +89,24 @@ where
     }
 
     let get_n_files_and_rows_per_file = |part_df: &DataFrame| {
-        let n_files = (part_df.estimated_size() / chunk_size).clamp(1, 0xffff_ffff);
-        let rows_per_file = (df.height() / n_files).saturating_add(1);
+        let mut n_files = (part_df.estimated_size() / chunk_size).clamp(1, 0xffff_ffff);
+        let mut rows_per_file = (part_df.height() / n_files).saturating_add(1);
+        if let Some(max_rows_per_file) = max_rows_per_file {
+            if rows_per_file > max_rows_per_file {
+                rows_per_file = max_rows_per_file;
+                n_files = part_df.height().div_ceil(max_rows_per_file);
+            }
+        }
         (n_files, rows_per_file)
     };
 
-    let write_part = |df: DataFrame, path: &Path| {
-        let f = std::fs::File::create(path)?;
-        file_write_options.write_df_to_file(df, f)?;
-        PolarsResult::Ok(())
+    let write_part = |df: DataFrame, dir_path: &Path, path_part: &str, idx: usize| {
+        let file_name = get_path_for_index(idx);
+        write_part(
+            df,
+            &dir_path.join(&file_name),
+            &format!("{path_part}/{file_name}"),
+        )
     };
 
     // This is sqrt(N) of the actual limit - we chunk the input both at the groups
@@ -120,11 +114,11 @@ where
     const MAX_OPEN_FILES: usize = 8;
 
     let finish_part_df = |df: DataFrame| {
-        let dir_path = init_part_base_dir(&df)?;
+        let (dir_path, path_part) = init_part_base_dir(&df)?;
         let (n_files, rows_per_file) = get_n_files_and_rows_per_file(&df);
 
         if n_files == 1 {
-            write_part(df.clone(), &dir_path.join(get_path_for_index(0)))
+            write_part(df.clone(), &dir_path, &path_part, 0)
         } else {
             (0..df.height())
                 .step_by(rows_per_file)
@@ -136,7 +130,7 @@ where
                         .into_par_iter()
                         .map(|&(idx, slice_start)| {
                             let df = df.slice(slice_start as i64, rows_per_file);
-                            write_part(df.clone(), &dir_path.join(get_path_for_index(idx)))
+                            write_part(df.clone(), &dir_path, &path_part, idx)
                         })
                         .reduce(
                             || PolarsResult::Ok(()),
@@ -204,5 +198,114 @@ where
         .into_iter()
         .map(Into::into)
         .collect::<Vec<PlSmallStr>>();
-    write_partitioned_dataset_impl(df, path, partition_by, file_write_options, chunk_size)
+    let write_part = |df: DataFrame, path: &Path, _: &str| {
+        let f = std::fs::File::create(path)?;
+        file_write_options.write_df_to_file(df, f)
+    };
+    write_partitioned_dataset_impl(df, path, partition_by, chunk_size, None, &write_part)
+}
+
+/// Write a hive-partitioned parquet dataset, e.g. `path/year=2024/month=1/00000000.parquet`.
+///
+/// The dataset can be read by other engines such as Spark and Trino. The partition columns are
+/// also written to the files.
+///
+/// `chunk_size` is the estimated size in bytes of the files, which is further limited by
+/// `max_rows_per_file` of the `partition_options`.
+pub fn write_parquet_partitioned<I, S>(
+    df: &mut DataFrame,
+    path: &Path,
+    partition_by: I,
+    options: &ParquetWriteOptions,
+    partition_options: &PartitionedWriteOptions,
+    chunk_size: usize,
+) -> PolarsResult<()>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<PlSmallStr>,
+{
+    let partition_by = partition_by
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<PlSmallStr>>();
+    let partition_options = *partition_options;
+
+    if !partition_options.write_metadata {
+        let write_part = |df: DataFrame, path: &Path, _: &str| {
+            let f = std::fs::File::create(path)?;
+            options.write_df_to_file(df, f)
+        };
+        return write_partitioned_dataset_impl(
+            df,
+            path,
+            partition_by,
+            chunk_size,
+            partition_options.max_rows_per_file,
+            &write_part,
+        );
+    }
+
+    let metadata = DatasetMetadata::default();
+    let write_part = |mut df: DataFrame, path: &Path, relative_path: &str| {
+        let f = std::fs::File::create(path)?;
+        let df = chunk_df_for_writing(&mut df, options.row_group_size.unwrap_or(512 * 512))?;
+        let mut writer = options.to_writer(f).batched(&df.schema())?;
+        writer.write_batch(&df)?;
+        writer.finish()?;
+        metadata.push(relative_path.to_string(), writer.metadata().unwrap());
+        Ok(())
+    };
+    write_partitioned_dataset_impl(
+        df,
+        path,
+        partition_by,
+        chunk_size,
+        partition_options.max_rows_per_file,
+        &write_part,
+    )?;
+    metadata.write(path)
+}
+
+/// Collects the footers of the files of a partitioned parquet dataset, to write them to a
+/// `_metadata` file in the root of the dataset.
+#[derive(Default)]
+pub struct DatasetMetadata {
+    files: Mutex<Vec<(String, ThriftFileMetadata)>>,
+}
+
+impl DatasetMetadata {
+    /// Add the footer of the file at `relative_path`, relative to the root of the dataset.
+    pub fn push(&self, relative_path: String, metadata: ThriftFileMetadata) {
+        self.files.lock().unwrap().push((relative_path, metadata))
+    }
+
+    /// Write the collected footers to `root/_metadata`. Nothing is written if no footers are
+    /// collected.
+    pub fn write(self, root: &Path) -> PolarsResult<()> {
+        let mut files = self.files.into_inner().unwrap();
+        // Order the row groups by the paths of the files, as files are written in parallel.
+        files.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        let mut summary: Option<ThriftFileMetadata> = None;
+        for (relative_path, mut metadata) in files {
+            for row_group in metadata.row_groups.iter_mut() {
+                for column in row_group.columns.iter_mut() {
+                    column.file_path = Some(relative_path.clone());
+                }
+            }
+            match &mut summary {
+                Some(summary) => {
+                    summary.num_rows += metadata.num_rows;
+                    summary.row_groups.extend(metadata.row_groups);
+                },
+                None => summary = Some(metadata),
+            }
+        }
+
+        if let Some(summary) = summary {
+            let mut file = std::fs::File::create(root.join("_metadata"))?;
+            write_metadata_sidecar(&mut file, &summary)?;
+        }
+        Ok(())
+    }
 }
@@ -58,6 +58,15 @@ pub fn is_cloud_url<P: AsRef<Path>>(p: P) -> bool {
     }
 }
 
+/// Check if the path is an HTTP(S) url that is read with plain range requests, which is the case
+/// if the `parquet_http` feature is enabled and the `cloud` feature is not.
+pub fn is_http_range_url<P: AsRef<Path>>(p: P) -> bool {
+    cfg!(all(feature = "parquet_http", not(feature = "cloud")))
+        && p.as_ref()
+            .to_str()
+            .is_some_and(|s| s.starts_with("http://") || s.starts_with("https://"))
+}
+
 /// Get the index of the first occurrence of a glob symbol.
 pub fn get_glob_start_idx(path: &[u8]) -> Option<usize> {
     memchr::memchr3(b'*', b'?', b'[', path)
@@ -137,6 +146,11 @@ pub fn expand_paths_hive(
 
     let is_cloud = is_cloud_url(first_path);
 
+    // URLs that are read with range requests refer to single files.
+    if is_http_range_url(first_path) {
+        return Ok((Arc::from(paths), 0));
+    }
+
     /// Wrapper around `Vec<PathBuf>` that also tracks file extensions, so that
     /// we don't have to traverse the entire list again to validate extensions.
     struct OutPaths {
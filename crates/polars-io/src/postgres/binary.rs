@@ -0,0 +1,331 @@
+use std::io::{BufReader, Read};
+
+use polars_core::prelude::*;
+
+/// The signature at the start of the output of `COPY ... (FORMAT binary)`.
+const SIGNATURE: &[u8; 11] = b"PGCOPY\n\xff\r\n\0";
+/// The bit of the header flags that is set if the tuples have an OID field.
+const HAS_OIDS: i32 = 1 << 16;
+/// Days between the Unix epoch and the Postgres epoch (2000-01-01).
+const PG_EPOCH_DAYS: i32 = 10_957;
+/// Microseconds between the Unix epoch and the Postgres epoch (2000-01-01).
+const PG_EPOCH_US: i64 = 946_684_800_000_000;
+
+/// The Postgres types that are decoded from their binary representation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum PgType {
+    Bool,
+    Int2,
+    Int4,
+    Int8,
+    Float4,
+    Float8,
+    Text,
+    Bytea,
+    Date,
+    Time,
+    Timestamp,
+    Timestamptz,
+}
+
+impl PgType {
+    /// The type with this name in `pg_catalog`, or `None` if its binary representation is not
+    /// decoded.
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        use PgType::*;
+        let pg_type = match name {
+            "bool" => Bool,
+            "int2" => Int2,
+            "int4" => Int4,
+            "int8" => Int8,
+            "float4" => Float4,
+            "float8" => Float8,
+            "text" | "varchar" | "bpchar" | "name" => Text,
+            "bytea" => Bytea,
+            "date" => Date,
+            "time" => Time,
+            "timestamp" => Timestamp,
+            "timestamptz" => Timestamptz,
+            _ => return None,
+        };
+        Some(pg_type)
+    }
+
+    pub(super) fn dtype(self) -> DataType {
+        use PgType::*;
+        match self {
+            Bool => DataType::Boolean,
+            Int2 => DataType::Int16,
+            Int4 => DataType::Int32,
+            Int8 => DataType::Int64,
+            Float4 => DataType::Float32,
+            Float8 => DataType::Float64,
+            Text => DataType::String,
+            Bytea => DataType::Binary,
+            Date => DataType::Date,
+            Time => DataType::Time,
+            Timestamp => DataType::Datetime(TimeUnit::Microseconds, None),
+            Timestamptz => {
+                DataType::Datetime(TimeUnit::Microseconds, Some(PlSmallStr::from_static("UTC")))
+            },
+        }
+    }
+}
+
+enum ColumnBuilder {
+    Boolean(BooleanChunkedBuilder),
+    Int16(PrimitiveChunkedBuilder<Int16Type>),
+    Int32(PrimitiveChunkedBuilder<Int32Type>),
+    Int64(PrimitiveChunkedBuilder<Int64Type>),
+    Float32(PrimitiveChunkedBuilder<Float32Type>),
+    Float64(PrimitiveChunkedBuilder<Float64Type>),
+    String(StringChunkedBuilder),
+    Binary(BinaryChunkedBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(name: PlSmallStr, pg_type: PgType) -> Self {
+        use PgType::*;
+        let capacity = 1024;
+        match pg_type {
+            Bool => Self::Boolean(BooleanChunkedBuilder::new(name, capacity)),
+            Int2 => Self::Int16(PrimitiveChunkedBuilder::new(name, capacity)),
+            Int4 | Date => Self::Int32(PrimitiveChunkedBuilder::new(name, capacity)),
+            Int8 | Time | Timestamp | Timestamptz => {
+                Self::Int64(PrimitiveChunkedBuilder::new(name, capacity))
+            },
+            Float4 => Self::Float32(PrimitiveChunkedBuilder::new(name, capacity)),
+            Float8 => Self::Float64(PrimitiveChunkedBuilder::new(name, capacity)),
+            Text => Self::String(StringChunkedBuilder::new(name, capacity)),
+            Bytea => Self::Binary(BinaryChunkedBuilder::new(name, capacity)),
+        }
+    }
+
+    fn append_null(&mut self) {
+        match self {
+            Self::Boolean(b) => b.append_null(),
+            Self::Int16(b) => b.append_null(),
+            Self::Int32(b) => b.append_null(),
+            Self::Int64(b) => b.append_null(),
+            Self::Float32(b) => b.append_null(),
+            Self::Float64(b) => b.append_null(),
+            Self::String(b) => b.append_null(),
+            Self::Binary(b) => b.append_null(),
+        }
+    }
+
+    fn append_value(&mut self, pg_type: PgType, value: &[u8]) -> PolarsResult<()> {
+        match (self, pg_type) {
+            (Self::Boolean(b), _) => b.append_value(fixed::<1>(value)?[0] != 0),
+            (Self::Int16(b), _) => b.append_value(i16::from_be_bytes(fixed(value)?)),
+            (Self::Int32(b), PgType::Date) => {
+                // `infinity` and `-infinity` have no equivalent and are read as null.
+                match i32::from_be_bytes(fixed(value)?) {
+                    i32::MAX | i32::MIN => b.append_null(),
+                    days => b.append_option(days.checked_add(PG_EPOCH_DAYS)),
+                }
+            },
+            (Self::Int32(b), _) => b.append_value(i32::from_be_bytes(fixed(value)?)),
+            (Self::Int64(b), PgType::Time) => {
+                b.append_value(i64::from_be_bytes(fixed(value)?) * 1000)
+            },
+            (Self::Int64(b), PgType::Timestamp | PgType::Timestamptz) => {
+                match i64::from_be_bytes(fixed(value)?) {
+                    i64::MAX | i64::MIN => b.append_null(),
+                    us => b.append_option(us.checked_add(PG_EPOCH_US)),
+                }
+            },
+            (Self::Int64(b), _) => b.append_value(i64::from_be_bytes(fixed(value)?)),
+            (Self::Float32(b), _) => b.append_value(f32::from_be_bytes(fixed(value)?)),
+            (Self::Float64(b), _) => b.append_value(f64::from_be_bytes(fixed(value)?)),
+            (Self::String(b), _) => {
+                let value = std::str::from_utf8(value).map_err(
+                    |_| polars_err!(ComputeError: "invalid utf-8 in a text value of Postgres"),
+                )?;
+                b.append_value(value)
+            },
+            (Self::Binary(b), _) => b.append_value(value),
+        }
+        Ok(())
+    }
+
+    fn finish(self, pg_type: PgType) -> Series {
+        match (self, pg_type) {
+            (Self::Boolean(b), _) => b.finish().into_series(),
+            (Self::Int16(b), _) => b.finish().into_series(),
+            (Self::Int32(b), PgType::Date) => b.finish().into_date().into_series(),
+            (Self::Int32(b), _) => b.finish().into_series(),
+            (Self::Int64(b), PgType::Time) => b.finish().into_time().into_series(),
+            (Self::Int64(b), PgType::Timestamp | PgType::Timestamptz) => {
+                let DataType::Datetime(time_unit, time_zone) = pg_type.dtype() else {
+                    unreachable!()
+                };
+                b.finish().into_datetime(time_unit, time_zone).into_series()
+            },
+            (Self::Int64(b), _) => b.finish().into_series(),
+            (Self::Float32(b), _) => b.finish().into_series(),
+            (Self::Float64(b), _) => b.finish().into_series(),
+            (Self::String(b), _) => b.finish().into_series(),
+            (Self::Binary(b), _) => b.finish().into_series(),
+        }
+    }
+}
+
+fn fixed<const N: usize>(value: &[u8]) -> PolarsResult<[u8; N]> {
+    value.try_into().map_err(|_| {
+        polars_err!(
+            ComputeError: "invalid binary value of Postgres with length {}, expected length {}",
+            value.len(), N
+        )
+    })
+}
+
+fn read_i16<R: Read>(reader: &mut R) -> PolarsResult<i16> {
+    let mut buf = [0; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(i16::from_be_bytes(buf))
+}
+
+fn read_i32<R: Read>(reader: &mut R) -> PolarsResult<i32> {
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(i32::from_be_bytes(buf))
+}
+
+/// Decode the output of `COPY ... TO STDOUT (FORMAT binary)` into a DataFrame with a column of
+/// every field in `schema`.
+pub(super) fn read_copy_binary<R: Read>(
+    reader: R,
+    schema: &[(PlSmallStr, PgType)],
+) -> PolarsResult<DataFrame> {
+    let mut reader = BufReader::new(reader);
+
+    let mut signature = [0; 11];
+    reader.read_exact(&mut signature)?;
+    polars_ensure!(
+        &signature == SIGNATURE,
+        ComputeError: "invalid signature of the binary COPY output of Postgres"
+    );
+    let flags = read_i32(&mut reader)?;
+    polars_ensure!(
+        flags & HAS_OIDS == 0,
+        ComputeError: "reading the OIDs of Postgres rows is not supported"
+    );
+    let extension_len = read_i32(&mut reader)?;
+    polars_ensure!(
+        extension_len >= 0,
+        ComputeError: "invalid header of the binary COPY output of Postgres"
+    );
+    std::io::copy(
+        &mut reader.by_ref().take(extension_len as u64),
+        &mut std::io::sink(),
+    )?;
+
+    let mut builders = schema
+        .iter()
+        .map(|(name, pg_type)| ColumnBuilder::new(name.clone(), *pg_type))
+        .collect::<Vec<_>>();
+    let mut value = Vec::new();
+    loop {
+        let n_fields = read_i16(&mut reader)?;
+        // The trailer.
+        if n_fields == -1 {
+            break;
+        }
+        polars_ensure!(
+            n_fields as usize == schema.len(),
+            ComputeError: "expected {} fields in a row of Postgres, got {}", schema.len(), n_fields
+        );
+        for (builder, (_, pg_type)) in builders.iter_mut().zip(schema) {
+            let len = read_i32(&mut reader)?;
+            if len < 0 {
+                builder.append_null();
+                continue;
+            }
+            value.resize(len as usize, 0);
+            reader.read_exact(&mut value)?;
+            builder.append_value(*pg_type, &value)?;
+        }
+    }
+
+    let columns = builders
+        .into_iter()
+        .zip(schema)
+        .map(|(builder, (_, pg_type))| builder.finish(*pg_type))
+        .collect();
+    DataFrame::new(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_field(buf: &mut Vec<u8>, value: Option<&[u8]>) {
+        match value {
+            None => buf.extend((-1i32).to_be_bytes()),
+            Some(value) => {
+                buf.extend((value.len() as i32).to_be_bytes());
+                buf.extend(value);
+            },
+        }
+    }
+
+    #[test]
+    fn test_read_copy_binary() {
+        let mut buf = SIGNATURE.to_vec();
+        buf.extend(0i32.to_be_bytes());
+        // A header extension, that is skipped.
+        buf.extend(2i32.to_be_bytes());
+        buf.extend([0xab, 0xcd]);
+
+        let rows: [(i32, Option<&str>, i32, i64); 2] =
+            [(1, Some("a"), 0, 0), (-2, None, i32::MAX, -1_000_000)];
+        for (int, text, date, timestamp) in rows {
+            buf.extend(4i16.to_be_bytes());
+            push_field(&mut buf, Some(&int.to_be_bytes()));
+            push_field(&mut buf, text.map(|s| s.as_bytes()));
+            push_field(&mut buf, Some(&date.to_be_bytes()));
+            push_field(&mut buf, Some(&timestamp.to_be_bytes()));
+        }
+        buf.extend((-1i16).to_be_bytes());
+
+        let schema: [(PlSmallStr, PgType); 4] = [
+            ("int".into(), PgType::Int4),
+            ("text".into(), PgType::Text),
+            ("date".into(), PgType::Date),
+            ("timestamp".into(), PgType::Timestamp),
+        ];
+        let df = read_copy_binary(buf.as_slice(), &schema).unwrap();
+
+        let dtypes = schema.iter().map(|(_, t)| t.dtype()).collect::<Vec<_>>();
+        assert_eq!(df.dtypes(), dtypes);
+        assert_eq!(
+            df.column("int").unwrap().i32().unwrap().to_vec(),
+            [Some(1), Some(-2)]
+        );
+        assert_eq!(df.column("text").unwrap().str().unwrap().get(0), Some("a"));
+        assert_eq!(df.column("text").unwrap().null_count(), 1);
+        assert_eq!(
+            df.column("date").unwrap().date().unwrap().to_vec(),
+            [Some(PG_EPOCH_DAYS), None]
+        );
+        assert_eq!(
+            df.column("timestamp").unwrap().datetime().unwrap().to_vec(),
+            [Some(PG_EPOCH_US), Some(PG_EPOCH_US - 1_000_000)]
+        );
+    }
+
+    #[test]
+    fn test_read_copy_binary_invalid() {
+        let schema: [(PlSmallStr, PgType); 1] = [("int".into(), PgType::Int4)];
+        assert!(read_copy_binary(b"PGCOPY\n".as_slice(), &schema).is_err());
+
+        let mut buf = SIGNATURE.to_vec();
+        buf.extend([0; 8]);
+        buf.extend(1i16.to_be_bytes());
+        // An int4 value of 2 bytes.
+        push_field(&mut buf, Some(&[0, 1]));
+        buf.extend((-1i16).to_be_bytes());
+        assert!(read_copy_binary(buf.as_slice(), &schema).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! # Reading from Postgres.
+//!
+//! The result of a query is streamed with `COPY (query) TO STDOUT (FORMAT binary)` and decoded
+//! from the binary representation of its values, which is cheaper than the text protocol used by
+//! most clients. The query can be split into partitions on the values of an integer column, that
+//! are read over separate connections in parallel.
+mod binary;
+mod read;
+
+pub use read::PostgresReader;
@@ -22,7 +22,13 @@ pub trait StatsEvaluator {
     fn should_read(&self, stats: &BatchStats) -> PolarsResult<bool>;
 }
 
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+/// A probabilistic set of the values in a column, e.g. a Parquet bloom filter.
+pub trait BloomFilter: std::fmt::Debug + Send + Sync {
+    /// Returns `false` if the value of the single-value `value` is certainly not in the column.
+    fn may_contain(&self, value: &Series) -> bool;
+}
+
+#[cfg(any(feature = "parquet", feature = "ipc", feature = "orc", feature = "csv"))]
 pub fn apply_predicate(
     df: &mut DataFrame,
     predicate: Option<&dyn PhysicalIoExpr>,
@@ -47,6 +53,7 @@ pub fn apply_predicate(
 /// - Null count
 /// - Minimum value
 /// - Maximum value
+/// - A bloom filter, if the file has one
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ColumnStats {
@@ -55,6 +62,8 @@ pub struct ColumnStats {
     null_count: Option<Series>,
     min_value: Option<Series>,
     max_value: Option<Series>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bloom_filter: Option<Arc<dyn BloomFilter>>,
 }
 
 impl ColumnStats {
@@ -70,9 +79,16 @@ impl ColumnStats {
             null_count,
             min_value,
             max_value,
+            bloom_filter: None,
         }
     }
 
+    /// Set the bloom filter of the values of the column.
+    pub fn with_bloom_filter(mut self, bloom_filter: Arc<dyn BloomFilter>) -> Self {
+        self.bloom_filter = Some(bloom_filter);
+        self
+    }
+
     /// Constructs a new [`ColumnStats`] with only the [`Field`] information and no statistics.
     pub fn from_field(field: Field) -> Self {
         Self {
@@ -80,6 +96,7 @@ impl ColumnStats {
             null_count: None,
             min_value: None,
             max_value: None,
+            bloom_filter: None,
         }
     }
 
@@ -91,6 +108,7 @@ impl ColumnStats {
             null_count: None,
             min_value: Some(s.clone()),
             max_value: Some(s),
+            bloom_filter: None,
         }
     }
 
@@ -118,6 +136,11 @@ impl ColumnStats {
         self.max_value.as_ref()
     }
 
+    /// Returns the bloom filter of the column.
+    pub fn bloom_filter(&self) -> Option<&dyn BloomFilter> {
+        self.bloom_filter.as_deref()
+    }
+
     /// Returns the null count of the column.
     pub fn null_count(&self) -> Option<usize> {
         match self.dtype() {
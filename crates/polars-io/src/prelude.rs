@@ -1,16 +1,24 @@
-pub use crate::cloud;
 #[cfg(feature = "csv")]
 pub use crate::csv::{read::*, write::*};
+#[cfg(feature = "excel")]
+pub use crate::excel::*;
 #[cfg(any(feature = "ipc", feature = "ipc_streaming"))]
 pub use crate::ipc::*;
 #[cfg(feature = "json")]
 pub use crate::json::*;
 #[cfg(feature = "json")]
 pub use crate::ndjson::core::*;
+#[cfg(feature = "orc")]
+pub use crate::orc::*;
 #[cfg(feature = "parquet")]
 pub use crate::parquet::{metadata::*, read::*, write::*};
 #[cfg(feature = "parquet")]
-pub use crate::partition::write_partitioned_dataset;
+pub use crate::partition::{write_parquet_partitioned, write_partitioned_dataset};
 pub use crate::path_utils::*;
+#[cfg(feature = "postgres")]
+pub use crate::postgres::*;
 pub use crate::shared::{SerReader, SerWriter};
+#[cfg(feature = "sqlite")]
+pub use crate::sqlite::*;
 pub use crate::utils::*;
+pub use crate::{cloud, PartitionedWriteOptions};
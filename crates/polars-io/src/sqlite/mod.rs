@@ -0,0 +1,10 @@
+//! # Reading and writing SQLite databases.
+//!
+//! The columns of SQLite are dynamically typed, so the dtypes of the result of a query are
+//! determined by the declared types of the columns, following the rules for the type affinity
+//! of SQLite. Dates and times are stored as text in the ISO 8601 format.
+mod read;
+mod write;
+
+pub use read::read_sqlite;
+pub use write::{write_sqlite, SqliteIfTableExists, SqliteWriteOptions};
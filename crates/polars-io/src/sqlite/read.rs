@@ -0,0 +1,223 @@
+use std::path::Path;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+
+/// How the values of a column are read, by its declared type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColumnKind {
+    Integer,
+    Real,
+    Text,
+    Blob,
+    Boolean,
+    Date,
+    Datetime,
+    Time,
+    /// The column has no declared type, or `NUMERIC` affinity. Its dtype is the supertype of
+    /// its values.
+    Dynamic,
+}
+
+impl ColumnKind {
+    /// See <https://www.sqlite.org/datatype3.html#determination_of_column_affinity>.
+    fn from_decl_type(decl_type: Option<&str>) -> Self {
+        let Some(decl_type) = decl_type else {
+            return Self::Dynamic;
+        };
+        let decl_type = decl_type.to_ascii_uppercase();
+        let contains_any = |names: &[&str]| names.iter().any(|name| decl_type.contains(name));
+        if decl_type.starts_with("BOOL") {
+            Self::Boolean
+        } else if decl_type == "DATE" {
+            Self::Date
+        } else if decl_type.starts_with("DATETIME") || decl_type.starts_with("TIMESTAMP") {
+            Self::Datetime
+        } else if decl_type == "TIME" {
+            Self::Time
+        } else if decl_type.contains("INT") {
+            Self::Integer
+        } else if contains_any(&["CHAR", "CLOB", "TEXT"]) {
+            Self::Text
+        } else if decl_type.contains("BLOB") {
+            Self::Blob
+        } else if contains_any(&["REAL", "FLOA", "DOUB"]) {
+            Self::Real
+        } else {
+            Self::Dynamic
+        }
+    }
+
+    fn to_any_value(self, value: ValueRef) -> PolarsResult<AnyValue<'static>> {
+        let value = match (self, value) {
+            (_, ValueRef::Null) => AnyValue::Null,
+            (Self::Boolean, ValueRef::Integer(v)) => AnyValue::Boolean(v != 0),
+            (Self::Date, ValueRef::Text(v)) => AnyValue::Int32(parse_date(text(v)?)?),
+            (Self::Datetime, ValueRef::Text(v)) => AnyValue::Int64(parse_datetime(text(v)?)?),
+            (Self::Time, ValueRef::Text(v)) => AnyValue::Int64(parse_time(text(v)?)?),
+            (_, ValueRef::Integer(v)) => AnyValue::Int64(v),
+            (_, ValueRef::Real(v)) => AnyValue::Float64(v),
+            (_, ValueRef::Text(v)) => AnyValue::StringOwned(text(v)?.into()),
+            (_, ValueRef::Blob(v)) => AnyValue::BinaryOwned(v.to_vec()),
+        };
+        Ok(value)
+    }
+
+    fn finish(self, name: PlSmallStr, values: &[AnyValue]) -> PolarsResult<Series> {
+        let physical =
+            |dtype| Series::from_any_values_and_dtype(name.clone(), values, &dtype, false);
+        let s = match self {
+            Self::Integer => physical(DataType::Int64)?,
+            Self::Real => physical(DataType::Float64)?,
+            Self::Text => physical(DataType::String)?,
+            Self::Blob => physical(DataType::Binary)?,
+            Self::Boolean => physical(DataType::Boolean)?,
+            Self::Date => physical(DataType::Int32)?
+                .i32()?
+                .clone()
+                .into_date()
+                .into_series(),
+            Self::Datetime => physical(DataType::Int64)?
+                .i64()?
+                .clone()
+                .into_datetime(TimeUnit::Microseconds, None)
+                .into_series(),
+            Self::Time => physical(DataType::Int64)?
+                .i64()?
+                .clone()
+                .into_time()
+                .into_series(),
+            Self::Dynamic => Series::from_any_values(name.clone(), values, false)?,
+        };
+        Ok(s)
+    }
+}
+
+fn text(value: &[u8]) -> PolarsResult<&str> {
+    std::str::from_utf8(value)
+        .map_err(|_| polars_err!(ComputeError: "invalid utf-8 in a text value of SQLite"))
+}
+
+fn parse_date(value: &str) -> PolarsResult<i32> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| polars_err!(ComputeError: "could not parse '{}' as a date", value))?;
+    Ok((date - NaiveDate::default()).num_days() as i32)
+}
+
+fn parse_datetime(value: &str) -> PolarsResult<i64> {
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .map(|date| date.and_time(NaiveTime::MIN))
+    })
+    .map(|datetime| datetime.and_utc().timestamp_micros())
+    .ok_or_else(|| polars_err!(ComputeError: "could not parse '{}' as a datetime", value))
+}
+
+fn parse_time(value: &str) -> PolarsResult<i64> {
+    let time = NaiveTime::parse_from_str(value, "%H:%M:%S%.f")
+        .map_err(|_| polars_err!(ComputeError: "could not parse '{}' as a time", value))?;
+    Ok(time.num_seconds_from_midnight() as i64 * 1_000_000_000 + time.nanosecond() as i64)
+}
+
+/// Read the result of `query` on the SQLite database at `path` into a DataFrame.
+///
+/// The dtypes of the columns are determined by their declared types:
+/// - `INTEGER` (and other types that contain `INT`) as [`DataType::Int64`]
+/// - `REAL`, `FLOAT` and `DOUBLE` as [`DataType::Float64`]
+/// - `TEXT`, `VARCHAR` and `CLOB` as [`DataType::String`]
+/// - `BLOB` as [`DataType::Binary`]
+/// - `BOOLEAN` as [`DataType::Boolean`]
+/// - `DATE`, `DATETIME`/`TIMESTAMP` and `TIME` from their ISO 8601 text representation as
+///   [`DataType::Date`], [`DataType::Datetime`] and [`DataType::Time`]
+///
+/// The dtype of other columns, such as computed columns, is the supertype of their values.
+/// The database is opened read-only.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::sqlite::read_sqlite;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     read_sqlite("app.db", "SELECT id, name FROM users WHERE active")
+/// }
+/// ```
+pub fn read_sqlite(path: impl AsRef<Path>, query: &str) -> PolarsResult<DataFrame> {
+    let flags = OpenFlags::SQLITE_OPEN_READ_ONLY
+        | OpenFlags::SQLITE_OPEN_URI
+        | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    let connection = Connection::open_with_flags(path, flags).map_err(to_compute_err)?;
+    let mut statement = connection.prepare(query).map_err(to_compute_err)?;
+    let columns = statement
+        .columns()
+        .iter()
+        .map(|column| {
+            (
+                PlSmallStr::from_str(column.name()),
+                ColumnKind::from_decl_type(column.decl_type()),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut values = vec![Vec::new(); columns.len()];
+    let mut rows = statement.query([]).map_err(to_compute_err)?;
+    while let Some(row) = rows.next().map_err(to_compute_err)? {
+        for (i, ((_, kind), values)) in columns.iter().zip(values.iter_mut()).enumerate() {
+            let value = row.get_ref(i).map_err(to_compute_err)?;
+            values.push(kind.to_any_value(value)?);
+        }
+    }
+
+    let columns = columns
+        .into_iter()
+        .zip(values)
+        .map(|((name, kind), values)| kind.finish(name, &values))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    DataFrame::new(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_kind() {
+        use ColumnKind::*;
+        for (decl_type, kind) in [
+            (None, Dynamic),
+            (Some("INTEGER"), Integer),
+            (Some("bigint"), Integer),
+            (Some("VARCHAR(255)"), Text),
+            (Some("DOUBLE PRECISION"), Real),
+            (Some("BLOB"), Blob),
+            (Some("BOOLEAN"), Boolean),
+            (Some("DATE"), Date),
+            (Some("DATETIME"), Datetime),
+            (Some("TIME"), Time),
+            (Some("NUMERIC(10, 2)"), Dynamic),
+        ] {
+            assert_eq!(ColumnKind::from_decl_type(decl_type), kind, "{decl_type:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_temporal() {
+        assert_eq!(parse_date("1970-01-02").unwrap(), 1);
+        assert_eq!(parse_datetime("1970-01-01 00:00:01.5").unwrap(), 1_500_000);
+        assert_eq!(parse_datetime("1970-01-01T00:01:00").unwrap(), 60_000_000);
+        assert_eq!(parse_datetime("1970-01-02").unwrap(), 86_400_000_000);
+        assert_eq!(parse_time("00:00:01.000001").unwrap(), 1_000_001_000);
+        assert!(parse_date("yesterday").is_err());
+    }
+}
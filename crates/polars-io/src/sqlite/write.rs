@@ -0,0 +1,249 @@
+use std::path::Path;
+
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+
+/// The maximum number of parameters of a statement in SQLite.
+const MAX_VARIABLE_NUMBER: usize = 32766;
+
+/// What happens if the table that is written to already exists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SqliteIfTableExists {
+    /// Raise an error.
+    #[default]
+    Fail,
+    /// Drop the table and create it again.
+    Replace,
+    /// Insert the rows into the table.
+    Append,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SqliteWriteOptions {
+    pub if_table_exists: SqliteIfTableExists,
+    /// Number of rows that are inserted per `INSERT` statement.
+    pub batch_size: usize,
+}
+
+impl Default for SqliteWriteOptions {
+    fn default() -> Self {
+        Self {
+            if_table_exists: SqliteIfTableExists::default(),
+            batch_size: 1000,
+        }
+    }
+}
+
+impl SqliteWriteOptions {
+    pub fn with_if_table_exists(mut self, if_table_exists: SqliteIfTableExists) -> Self {
+        self.if_table_exists = if_table_exists;
+        self
+    }
+
+    /// Insert this number of rows per `INSERT` statement.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// The declared type of a column of this dtype, which is read back as the same dtype.
+fn sqlite_type(name: &str, dtype: &DataType) -> PolarsResult<&'static str> {
+    let decl_type = match dtype {
+        DataType::Boolean => "BOOLEAN",
+        dt if dt.is_integer() => "INTEGER",
+        dt if dt.is_float() => "REAL",
+        DataType::String => "TEXT",
+        DataType::Binary => "BLOB",
+        DataType::Date => "DATE",
+        DataType::Datetime(_, _) => "DATETIME",
+        DataType::Time => "TIME",
+        dt => polars_bail!(
+            InvalidOperation: "cannot write column '{}' of dtype {} to SQLite", name, dt
+        ),
+    };
+    Ok(decl_type)
+}
+
+fn text_values(ca: &StringChunked) -> Vec<Option<Value>> {
+    ca.iter()
+        .map(|v| v.map(|v| Value::Text(v.to_string())))
+        .collect()
+}
+
+fn sql_values(s: &Series) -> PolarsResult<Vec<Value>> {
+    let values = match s.dtype() {
+        DataType::Boolean => s
+            .bool()?
+            .iter()
+            .map(|v| v.map(|v| Value::Integer(v as i64)))
+            .collect(),
+        DataType::UInt64 => s
+            .u64()?
+            .iter()
+            .map(|v| {
+                v.map(|v| {
+                    i64::try_from(v).map(Value::Integer).map_err(|_| {
+                        polars_err!(
+                            ComputeError: "value {} of column '{}' does not fit in an SQLite integer",
+                            v, s.name()
+                        )
+                    })
+                })
+                .transpose()
+            })
+            .collect::<PolarsResult<_>>()?,
+        dt if dt.is_integer() => s
+            .cast(&DataType::Int64)?
+            .i64()?
+            .iter()
+            .map(|v| v.map(Value::Integer))
+            .collect(),
+        dt if dt.is_float() => s
+            .cast(&DataType::Float64)?
+            .f64()?
+            .iter()
+            .map(|v| v.map(Value::Real))
+            .collect(),
+        DataType::String => text_values(s.str()?),
+        DataType::Binary => s
+            .binary()?
+            .iter()
+            .map(|v| v.map(|v| Value::Blob(v.to_vec())))
+            .collect(),
+        DataType::Date => text_values(&s.date()?.to_string("%Y-%m-%d")?),
+        DataType::Datetime(time_unit, _) => {
+            // Datetimes with a time zone are written in UTC.
+            let ca = s.datetime()?.physical().clone();
+            text_values(
+                &ca.into_datetime(*time_unit, None)
+                    .to_string("%Y-%m-%d %H:%M:%S%.f")?,
+            )
+        },
+        DataType::Time => text_values(&s.time()?.to_string("%H:%M:%S%.f")),
+        dt => polars_bail!(
+            InvalidOperation: "cannot write column '{}' of dtype {} to SQLite", s.name(), dt
+        ),
+    };
+    Ok(values
+        .into_iter()
+        .map(|v| v.unwrap_or(Value::Null))
+        .collect())
+}
+
+/// Write `df` to `table` in the SQLite database at `path`, which is created if it doesn't
+/// exist.
+///
+/// The table is created with the declared types that [`read_sqlite`](super::read_sqlite)
+/// reads back as the dtypes of the columns. Datetimes with a time zone are written in UTC.
+/// The rows are inserted in batches of multiple rows in a single transaction, so that either
+/// all rows or no rows are written.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_io::sqlite::{write_sqlite, SqliteIfTableExists, SqliteWriteOptions};
+///
+/// fn example(df: &DataFrame) -> PolarsResult<()> {
+///     let options =
+///         SqliteWriteOptions::default().with_if_table_exists(SqliteIfTableExists::Append);
+///     write_sqlite(df, "app.db", "events", &options)
+/// }
+/// ```
+pub fn write_sqlite(
+    df: &DataFrame,
+    path: impl AsRef<Path>,
+    table: &str,
+    options: &SqliteWriteOptions,
+) -> PolarsResult<()> {
+    polars_ensure!(
+        df.width() > 0,
+        InvalidOperation: "cannot write a DataFrame without columns to SQLite"
+    );
+    polars_ensure!(
+        options.batch_size > 0,
+        InvalidOperation: "the batch size must be greater than 0"
+    );
+    let column_defs = df
+        .get_columns()
+        .iter()
+        .map(|s| {
+            sqlite_type(s.name(), s.dtype())
+                .map(|decl_type| format!("{} {decl_type}", quote_identifier(s.name())))
+        })
+        .collect::<PolarsResult<Vec<_>>>()?
+        .join(", ");
+
+    let mut connection = Connection::open(path).map_err(to_compute_err)?;
+    let transaction = connection.transaction().map_err(to_compute_err)?;
+    let table_name = quote_identifier(table);
+
+    let exists = transaction
+        .query_row(
+            "SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table],
+            |row| row.get::<_, i64>(0),
+        )
+        .map_err(to_compute_err)?
+        > 0;
+    let create = match (exists, options.if_table_exists) {
+        (false, _) => true,
+        (true, SqliteIfTableExists::Fail) => {
+            polars_bail!(InvalidOperation: "table '{}' already exists", table)
+        },
+        (true, SqliteIfTableExists::Replace) => {
+            transaction
+                .execute(&format!("DROP TABLE {table_name}"), [])
+                .map_err(to_compute_err)?;
+            true
+        },
+        (true, SqliteIfTableExists::Append) => false,
+    };
+    if create {
+        transaction
+            .execute(&format!("CREATE TABLE {table_name} ({column_defs})"), [])
+            .map_err(to_compute_err)?;
+    }
+
+    let column_names = df
+        .get_column_names()
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let row_placeholders = format!("({})", vec!["?"; df.width()].join(", "));
+    let insert = |n_rows: usize| {
+        format!(
+            "INSERT INTO {table_name} ({column_names}) VALUES {}",
+            vec![row_placeholders.as_str(); n_rows].join(", ")
+        )
+    };
+
+    let batch_size = options
+        .batch_size
+        .min(MAX_VARIABLE_NUMBER / df.width())
+        .max(1);
+    let mut offset = 0;
+    while offset < df.height() {
+        let batch = df.slice(offset as i64, batch_size);
+        let columns = batch
+            .get_columns()
+            .iter()
+            .map(sql_values)
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let params = (0..batch.height()).flat_map(|row| columns.iter().map(move |c| &c[row]));
+        transaction
+            .prepare_cached(&insert(batch.height()))
+            .and_then(|mut statement| statement.execute(params_from_iter(params)))
+            .map_err(to_compute_err)?;
+        offset += batch.height();
+    }
+
+    transaction.commit().map_err(to_compute_err)
+}
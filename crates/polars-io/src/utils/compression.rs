@@ -1,3 +1,7 @@
+use std::io::Read;
+
+use polars_error::PolarsResult;
+
 // magic numbers
 pub mod magic {
     pub const GZIP: [u8; 2] = [31, 139];
@@ -5,15 +9,85 @@ pub mod magic {
     pub const ZLIB1: [u8; 2] = [0x78, 0x9C];
     pub const ZLIB2: [u8; 2] = [0x78, 0xDA];
     pub const ZSTD: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+    /// Followed by the block size `'1'..='9'`.
+    pub const BZIP2: [u8; 3] = [b'B', b'Z', b'h'];
+    /// Start of the first block of a bzip2 stream.
+    pub const BZIP2_BLOCK: [u8; 6] = [0x31, 0x41, 0x59, 0x26, 0x53, 0x59];
+    /// End of an empty bzip2 stream.
+    pub const BZIP2_EOS: [u8; 6] = [0x17, 0x72, 0x45, 0x38, 0x50, 0x90];
+}
+
+/// Compression formats that are detected by their magic bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SupportedCompression {
+    Gzip,
+    Zlib,
+    Zstd,
+    Bzip2,
+}
+
+impl SupportedCompression {
+    /// Detect the compression of `bytes`, which should contain at least the first 10 bytes of
+    /// the file.
+    pub fn check(bytes: &[u8]) -> Option<Self> {
+        use magic::*;
+
+        if bytes.starts_with(&GZIP) {
+            Some(Self::Gzip)
+        } else if bytes.starts_with(&ZLIB0)
+            || bytes.starts_with(&ZLIB1)
+            || bytes.starts_with(&ZLIB2)
+        {
+            Some(Self::Zlib)
+        } else if bytes.starts_with(&ZSTD) {
+            Some(Self::Zstd)
+        } else if is_bzip2(bytes) {
+            Some(Self::Bzip2)
+        } else {
+            None
+        }
+    }
+}
+
+// "BZh" is valid text, so the block size and the magic of the first block are checked as well.
+fn is_bzip2(bytes: &[u8]) -> bool {
+    use magic::*;
+
+    bytes.len() >= 10
+        && bytes.starts_with(&BZIP2)
+        && matches!(bytes[3], b'1'..=b'9')
+        && (bytes[4..10] == BZIP2_BLOCK || bytes[4..10] == BZIP2_EOS)
 }
 
 /// check if csv file is compressed
 pub fn is_compressed(bytes: &[u8]) -> bool {
-    use magic::*;
+    SupportedCompression::check(bytes).is_some()
+}
 
-    bytes.starts_with(&ZLIB0)
-        || bytes.starts_with(&ZLIB1)
-        || bytes.starts_with(&ZLIB2)
-        || bytes.starts_with(&GZIP)
-        || bytes.starts_with(&ZSTD)
+/// Wrap `reader` in a decoder that decompresses it while it is read.
+pub fn decompressing_reader<'a, R: Read + Send + 'a>(
+    reader: R,
+    compression: SupportedCompression,
+) -> PolarsResult<Box<dyn Read + Send + 'a>> {
+    #[cfg(not(any(feature = "decompress", feature = "decompress-fast")))]
+    {
+        let _ = reader;
+        polars_error::polars_bail!(
+            ComputeError: "cannot decompress {compression:?} compressed file; \
+            compile with feature 'decompress' or 'decompress-fast'"
+        )
+    }
+    #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+    Ok(match compression {
+        SupportedCompression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+        SupportedCompression::Zlib => Box::new(flate2::read::ZlibDecoder::new(reader)),
+        SupportedCompression::Zstd => Box::new(zstd::Decoder::new(reader)?),
+        #[cfg(feature = "decompress-bzip2")]
+        SupportedCompression::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+        #[cfg(not(feature = "decompress-bzip2"))]
+        SupportedCompression::Bzip2 => polars_error::polars_bail!(
+            ComputeError: "cannot decompress bzip2 compressed file; \
+            compile with feature 'decompress-bzip2'"
+        ),
+    })
 }
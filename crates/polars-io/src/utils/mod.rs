@@ -1,7 +1,7 @@
 pub mod compression;
 mod other;
 
-pub use compression::is_compressed;
+pub use compression::{decompressing_reader, is_compressed, SupportedCompression};
 pub use other::*;
 #[cfg(feature = "cloud")]
 pub mod byte_source;
@@ -10,6 +10,9 @@ use polars_error::to_compute_err;
 use polars_utils::mmap::MMapSemaphore;
 use regex::{Regex, RegexBuilder};
 
+#[cfg(any(feature = "decompress", feature = "decompress-fast"))]
+use super::compression::decompressing_reader;
+use super::compression::SupportedCompression;
 use crate::mmap::{MmapBytesReader, ReaderBytes};
 
 pub fn get_reader_bytes<'a, R: Read + MmapBytesReader + ?Sized>(
@@ -50,35 +53,19 @@ pub fn get_reader_bytes<'a, R: Read + MmapBytesReader + ?Sized>(
 /// An `out` vec must be given for ownership of the decompressed data.
 pub fn maybe_decompress_bytes<'a>(bytes: &'a [u8], out: &'a mut Vec<u8>) -> PolarsResult<&'a [u8]> {
     assert!(out.is_empty());
-    use crate::prelude::is_compressed;
-    let is_compressed = bytes.len() >= 4 && is_compressed(bytes);
+    let compression = SupportedCompression::check(bytes);
 
-    if is_compressed {
+    if let Some(compression) = compression {
         #[cfg(any(feature = "decompress", feature = "decompress-fast"))]
         {
-            use crate::utils::compression::magic::*;
-
-            if bytes.starts_with(&GZIP) {
-                flate2::read::MultiGzDecoder::new(bytes)
-                    .read_to_end(out)
-                    .map_err(to_compute_err)?;
-            } else if bytes.starts_with(&ZLIB0)
-                || bytes.starts_with(&ZLIB1)
-                || bytes.starts_with(&ZLIB2)
-            {
-                flate2::read::ZlibDecoder::new(bytes)
-                    .read_to_end(out)
-                    .map_err(to_compute_err)?;
-            } else if bytes.starts_with(&ZSTD) {
-                zstd::Decoder::new(bytes)?.read_to_end(out)?;
-            } else {
-                polars_bail!(ComputeError: "unimplemented compression format")
-            }
-
+            decompressing_reader(bytes, compression)?
+                .read_to_end(out)
+                .map_err(to_compute_err)?;
             Ok(out)
         }
         #[cfg(not(any(feature = "decompress", feature = "decompress-fast")))]
         {
+            let _ = compression;
             panic!("cannot decompress without 'decompress' or 'decompress-fast' feature")
         }
     } else {
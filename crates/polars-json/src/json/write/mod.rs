@@ -10,9 +10,10 @@ use arrow::io::iterator::StreamingIterator;
 use arrow::record_batch::RecordBatchT;
 pub use fallible_streaming_iterator::*;
 use polars_error::{PolarsError, PolarsResult};
-pub(crate) use serialize::new_serializer;
 use serialize::serialize;
+pub(crate) use serialize::{new_serializer, serialize_rows};
 pub use utf8::serialize_to_utf8;
+pub(crate) use utf8::write_str;
 
 /// [`FallibleStreamingIterator`] that serializes an [`Array`] to bytes of valid JSON
 /// # Implementation
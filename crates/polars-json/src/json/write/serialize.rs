@@ -540,6 +540,39 @@ fn serialize_item<'a>(
     buffer.push(b'}');
 }
 
+/// Serializes the rows of `array` to `buffer` as lines of NDJSON, where the null values of the
+/// fields of the rows are written as `null_value` instead of `null`.
+/// # Implementation
+/// This operation is CPU-bounded
+pub(crate) fn serialize_rows(array: &StructArray, null_value: &[u8], buffer: &mut Vec<u8>) {
+    let mut serializers = array
+        .values()
+        .iter()
+        .map(|x| new_serializer(x.as_ref(), 0, usize::MAX))
+        .collect::<Vec<_>>();
+
+    for row in ZipValidity::new_with_validity(0..array.len(), array.validity()) {
+        if row.is_some() {
+            let names = array.fields().iter().map(|f| f.name.as_str());
+            let values = serializers.iter_mut().map(|serializer| {
+                let value = serializer.next().unwrap();
+                if value == b"null" {
+                    null_value
+                } else {
+                    value
+                }
+            });
+            serialize_item(buffer, names.zip(values), true);
+        } else {
+            serializers.iter_mut().for_each(|iter| {
+                let _ = iter.next();
+            });
+            buffer.extend(b"null");
+        }
+        buffer.push(b'\n');
+    }
+}
+
 /// Serializes `array` to a valid JSON to `buffer`
 /// # Implementation
 /// This operation is CPU-bounded
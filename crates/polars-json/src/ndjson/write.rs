@@ -1,13 +1,20 @@
 //! APIs to serialize and write to [NDJSON](http://ndjson.org/).
 use std::io::Write;
 
-use arrow::array::Array;
+use arrow::array::{Array, StructArray};
 pub use fallible_streaming_iterator::FallibleStreamingIterator;
 use polars_error::{PolarsError, PolarsResult};
 
-use super::super::json::write::new_serializer;
+use super::super::json::write::{new_serializer, serialize_rows};
+
+fn serialize(array: &dyn Array, null_value: Option<&[u8]>, buffer: &mut Vec<u8>) {
+    if let (Some(null_value), Some(array)) =
+        (null_value, array.as_any().downcast_ref::<StructArray>())
+    {
+        serialize_rows(array, null_value, buffer);
+        return;
+    }
 
-fn serialize(array: &dyn Array, buffer: &mut Vec<u8>) {
     let mut serializer = new_serializer(array, 0, usize::MAX);
     (0..array.len()).for_each(|_| {
         buffer.extend_from_slice(serializer.next().unwrap());
@@ -27,6 +34,7 @@ where
 {
     arrays: I,
     buffer: Vec<u8>,
+    null_value: Option<Vec<u8>>,
 }
 
 impl<A, I> Serializer<A, I>
@@ -36,7 +44,22 @@ where
 {
     /// Creates a new [`Serializer`].
     pub fn new(arrays: I, buffer: Vec<u8>) -> Self {
-        Self { arrays, buffer }
+        Self {
+            arrays,
+            buffer,
+            null_value: None,
+        }
+    }
+
+    /// Write the null values of the fields of rows, i.e. of arrays of structs, as this string
+    /// instead of `null`. The null values that are nested deeper are written as `null`.
+    pub fn with_null_value(mut self, null_value: Option<&str>) -> Self {
+        self.null_value = null_value.map(|value| {
+            let mut buf = vec![];
+            super::super::json::write::write_str(&mut buf, value).unwrap();
+            buf
+        });
+        self
     }
 }
 
@@ -53,7 +76,11 @@ where
         self.buffer.clear();
         self.arrays
             .next()
-            .map(|maybe_array| maybe_array.map(|array| serialize(array.as_ref(), &mut self.buffer)))
+            .map(|maybe_array| {
+                maybe_array.map(|array| {
+                    serialize(array.as_ref(), self.null_value.as_deref(), &mut self.buffer)
+                })
+            })
             .transpose()?;
         Ok(())
     }
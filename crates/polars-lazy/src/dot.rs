@@ -1,16 +1,31 @@
 use polars_core::prelude::*;
+use polars_plan::plans::DiagramFormat;
 
 use crate::prelude::*;
 
 impl LazyFrame {
-    /// Get a dot language representation of the LogicalPlan.
-    pub fn to_dot(&self, optimized: bool) -> PolarsResult<String> {
+    fn to_diagram(&self, optimized: bool, format: DiagramFormat) -> PolarsResult<String> {
         let lp = if optimized {
-            self.clone().to_alp_optimized()
+            self.clone()._describe_to_alp_optimized()
         } else {
             self.clone().to_alp()
         }?;
 
-        Ok(lp.display_dot().to_string())
+        Ok(lp.display_dot().with_format(format).to_string())
+    }
+
+    /// Get a dot language representation of the LogicalPlan.
+    ///
+    /// The nodes have the ids of [`explain_with_node_ids`](LazyFrame::explain_with_node_ids).
+    /// The nodes of the pipelines of the streaming engine are labeled with their pipelines and
+    /// their roles in them.
+    pub fn to_dot(&self, optimized: bool) -> PolarsResult<String> {
+        self.to_diagram(optimized, DiagramFormat::Dot)
+    }
+
+    /// Get a [Mermaid](https://mermaid.js.org) representation of the LogicalPlan, with the same
+    /// nodes as [`to_dot`](LazyFrame::to_dot).
+    pub fn to_mermaid(&self, optimized: bool) -> PolarsResult<String> {
+        self.to_diagram(optimized, DiagramFormat::Mermaid)
     }
 }
@@ -87,14 +87,41 @@ pub fn concat<L: AsRef<[LazyFrame]>>(inputs: L, args: UnionArgs) -> PolarsResult
     concat_impl(inputs, args)
 }
 
+/// Merge multiple [`LazyFrame`]s that are sorted on `key` in ascending order into a single
+/// sorted [`LazyFrame`], see [`LazyFrame::merge_sorted`].
+///
+/// On the streaming engine, the scans of files are merged while they are read, and the `key`
+/// column of the result is flagged as sorted, so that e.g. an asof join or a `group_by_dynamic`
+/// on the `key` that follows is streamed as well.
+#[cfg(feature = "merge_sorted")]
+pub fn merge_sorted<L: AsRef<[LazyFrame]>>(
+    inputs: L,
+    key: impl Into<PlSmallStr>,
+) -> PolarsResult<LazyFrame> {
+    let key = key.into();
+    let mut inputs = inputs.as_ref().iter().cloned();
+    let first = inputs
+        .next()
+        .ok_or_else(|| polars_err!(NoData: "empty container given"))?;
+    inputs.try_fold(first, |merged, lf| merged.merge_sorted(lf, key.clone()))
+}
+
 /// Collect all [`LazyFrame`] computations.
+///
+/// If they all run on the streaming engine and compute the same subplan, e.g. the same scans and
+/// joins followed by different filters and aggregations, the subplan is computed once and its
+/// output is streamed into all of them, instead of reading the sources for every query.
 pub fn collect_all<I>(lfs: I) -> PolarsResult<Vec<DataFrame>>
 where
     I: IntoParallelIterator<Item = LazyFrame>,
 {
-    let iter = lfs.into_par_iter();
+    let lfs = lfs.into_par_iter().collect::<Vec<_>>();
 
-    polars_core::POOL.install(|| iter.map(|lf| lf.collect()).collect())
+    #[cfg(all(feature = "streaming", feature = "cse"))]
+    if let Some(dfs) = crate::physical_plan::streaming::collect_all_shared_subplan(&lfs)? {
+        return Ok(dfs);
+    }
+    polars_core::POOL.install(|| lfs.into_par_iter().map(|lf| lf.collect()).collect())
 }
 
 #[cfg(test)]
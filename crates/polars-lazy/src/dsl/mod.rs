@@ -38,6 +38,8 @@ pub mod functions;
 mod into;
 #[cfg(feature = "list_eval")]
 mod list;
+#[cfg(feature = "string_replace_eval")]
+mod string_eval;
 
 #[cfg(any(feature = "cumulative_eval", feature = "list_eval"))]
 pub use eval::*;
@@ -48,3 +50,5 @@ use into::IntoExpr;
 pub use list::*;
 pub use polars_plan::dsl::*;
 pub use polars_plan::plans::UdfSchema;
+#[cfg(feature = "string_replace_eval")]
+pub use string_eval::*;
@@ -0,0 +1,102 @@
+use polars_core::export::regex::Regex;
+use polars_core::prelude::*;
+use polars_plan::dsl::string::StringNameSpace;
+use polars_plan::dsl::*;
+use polars_utils::format_pl_smallstr;
+
+use crate::prelude::*;
+
+/// Replace the matches of `reg` in the strings of `s` by the values of `replacement`, which is
+/// evaluated once on a frame of all matches.
+fn replace_all_eval(s: &Series, reg: &Regex, replacement: &Expr) -> PolarsResult<Series> {
+    let ca = s.str()?;
+    let names = reg
+        .capture_names()
+        .enumerate()
+        .map(|(idx, opt_name)| {
+            opt_name
+                .map(PlSmallStr::from_str)
+                .unwrap_or_else(|| format_pl_smallstr!("{idx}"))
+        })
+        .collect::<Vec<_>>();
+
+    // The groups of the matches, the spans of the matches and the number of matches per string.
+    let mut groups = vec![vec![]; names.len()];
+    let mut spans = vec![];
+    let mut counts = Vec::with_capacity(ca.len());
+    for opt_s in ca {
+        let mut count = 0;
+        for captures in opt_s.into_iter().flat_map(|s| reg.captures_iter(s)) {
+            let m = captures.get(0).unwrap();
+            spans.push((m.start(), m.end()));
+            for (i, group) in groups.iter_mut().enumerate() {
+                group.push(captures.get(i).map(|m| m.as_str()));
+            }
+            count += 1;
+        }
+        counts.push(count);
+    }
+    if spans.is_empty() {
+        return Ok(s.clone());
+    }
+
+    let matches = DataFrame::new(
+        groups
+            .iter()
+            .zip(names)
+            .map(|(group, name)| Series::new(name, group))
+            .collect(),
+    )?;
+    let out = matches.lazy().select([replacement.clone()]).collect()?;
+    let replacements = out.get_columns()[0].cast(&DataType::String)?;
+    let replacements = match replacements.len() {
+        1 => replacements.new_from_index(0, spans.len()),
+        len => {
+            polars_ensure!(
+                len == spans.len(),
+                ShapeMismatch: "the replacement of {} matches has length {}", spans.len(), len
+            );
+            replacements
+        },
+    };
+
+    let mut replacements = replacements.str()?.into_iter();
+    let mut spans = spans.into_iter();
+    let out: StringChunked = ca
+        .into_iter()
+        .zip(counts)
+        .map(|(opt_s, count)| {
+            let s = opt_s?;
+            let mut out = String::with_capacity(s.len());
+            let mut last = 0;
+            for ((start, end), replacement) in (&mut spans).zip(&mut replacements).take(count) {
+                out.push_str(&s[last..start]);
+                out.push_str(replacement.unwrap_or(&s[start..end]));
+                last = end;
+            }
+            out.push_str(&s[last..]);
+            Some(out)
+        })
+        .collect();
+    Ok(out.with_name(s.name().clone()).into_series())
+}
+
+pub trait StringNameSpaceExtension: Sized + Into<StringNameSpace> {
+    /// Replace all matches of a regex `pat` by the result of the `replacement` expression. The
+    /// expression is evaluated on a frame with a row per match, of which the column `"0"` is the
+    /// match and the other columns are its capture groups, named by their name or position. A
+    /// match of which the replacement is null is kept.
+    ///
+    /// E.g. `col("0").str().to_uppercase()` uppercases the matches.
+    fn replace_all_with(self, pat: &str, replacement: Expr) -> PolarsResult<Expr> {
+        let reg = Regex::new(pat)?;
+        let this = self.into();
+        let func = move |s: Series| replace_all_eval(&s, &reg, &replacement).map(Some);
+        Ok(this
+            .0
+            .map(func, GetOutput::from_type(DataType::String))
+            .with_fmt("str.replace_all_with"))
+    }
+}
+
+impl StringNameSpaceExtension for StringNameSpace {}
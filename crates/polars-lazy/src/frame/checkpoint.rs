@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_io::parquet::write::ParquetWriteOptions;
+use polars_plan::plans::expr_ir::ExprIR;
+use polars_plan::plans::{is_streamable, node_to_lp_cloned, Context, FileScan, ScanSources, IR};
+
+use crate::prelude::*;
+
+/// The file in the output directory that lists the finished input files.
+const CHECKPOINT_FILE: &str = "_checkpoint";
+
+fn part_name(i: usize) -> String {
+    format!("part-{i:08}.parquet")
+}
+
+/// Walk from the root to the scan and check that every node works on a chunk at a time, so that
+/// the query can be run for every input file on its own. Returns the node of the scan.
+fn find_row_wise_scan(plan: &IRPlan) -> PolarsResult<Node> {
+    let streamable = |e: &ExprIR| is_streamable(e.node(), &plan.expr_arena, Context::Default);
+    let mut node = plan.lp_top;
+    loop {
+        node = match plan.lp_arena.get(node) {
+            IR::Filter { input, predicate } if streamable(predicate) => *input,
+            IR::Select { input, expr, .. } if expr.iter().all(streamable) => *input,
+            IR::HStack { input, exprs, .. } if exprs.iter().all(streamable) => *input,
+            IR::SimpleProjection { input, .. } => *input,
+            IR::MapFunction { input, function } if function.is_streamable() => *input,
+            IR::Scan {
+                sources: ScanSources::Paths(_),
+                file_options,
+                ..
+            } => {
+                polars_ensure!(
+                    file_options.row_index.is_none()
+                        && file_options.slice.is_none()
+                        && file_options.n_rows_per_file.is_none(),
+                    InvalidOperation: "cannot checkpoint a scan with a row index or a row limit"
+                );
+                return Ok(node);
+            },
+            lp => polars_bail!(
+                InvalidOperation: "cannot checkpoint the query: '{}' depends on more than one \
+                input file; only scans of files followed by row-wise operations can be \
+                checkpointed", lp.name()
+            ),
+        }
+    }
+}
+
+/// Read the finished input files of the checkpoint, keyed by the name of their output file.
+fn read_checkpoint(path: &Path) -> PolarsResult<BTreeMap<String, PathBuf>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    std::fs::read_to_string(path)?
+        .lines()
+        .map(|line| {
+            let (part, source) = line.split_once('\t').ok_or_else(
+                || polars_err!(ComputeError: "invalid checkpoint line '{}' in {:?}", line, path),
+            )?;
+            Ok((part.to_string(), PathBuf::from(source)))
+        })
+        .collect()
+}
+
+impl LazyFrame {
+    /// Stream a query result into a directory of parquet files, with a file per input file of
+    /// the scan, e.g. `path/part-00000003.parquet` for the fourth file. Every finished input
+    /// file is recorded in a `path/_checkpoint` file, so that a run that was interrupted resumes
+    /// from the first input file that was not finished.
+    ///
+    /// Only queries that scan files and are followed by row-wise operations, e.g. filters and
+    /// projections, can be checkpointed, as these compute the result of every input file on its
+    /// own. The input files may not change between runs.
+    pub fn sink_parquet_checkpointed(
+        self,
+        path: impl AsRef<Path>,
+        options: ParquetWriteOptions,
+    ) -> PolarsResult<()> {
+        let dir = path.as_ref();
+        let opt_state = self.opt_state;
+        let mut plan = self.logical_plan.to_alp()?;
+        let scan_node = find_row_wise_scan(&plan)?;
+        let IR::Scan {
+            sources,
+            hive_parts,
+            scan_type,
+            ..
+        } = plan.lp_arena.get(scan_node).clone()
+        else {
+            unreachable!()
+        };
+        let paths = sources.as_paths().unwrap();
+
+        std::fs::create_dir_all(dir)?;
+        let checkpoint_path = dir.join(CHECKPOINT_FILE);
+        let done = read_checkpoint(&checkpoint_path)?;
+        let mut checkpoint = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&checkpoint_path)?;
+
+        for (i, source) in paths.iter().enumerate() {
+            let part = part_name(i);
+            if let Some(finished) = done.get(&part) {
+                polars_ensure!(
+                    finished == source,
+                    ComputeError: "the input files changed since the checkpoint in {:?}: {} was \
+                    written for {:?}, but the input file is {:?}", dir, part, finished, source
+                );
+                continue;
+            }
+
+            let mut scan_type = scan_type.clone();
+            // The metadata belongs to the first file.
+            if let FileScan::Parquet { metadata, .. } = &mut scan_type {
+                *metadata = metadata.take().filter(|_| i == 0);
+            }
+            #[cfg(feature = "ipc")]
+            if let FileScan::Ipc { metadata, .. } = &mut scan_type {
+                *metadata = metadata.take().filter(|_| i == 0);
+            }
+            let IR::Scan {
+                sources,
+                hive_parts: file_hive_parts,
+                scan_type: file_scan_type,
+                ..
+            } = plan.lp_arena.get_mut(scan_node)
+            else {
+                unreachable!()
+            };
+            *sources = ScanSources::Paths(Arc::from([source.clone()]));
+            *file_hive_parts = hive_parts
+                .as_ref()
+                .map(|hive_parts| Arc::new(vec![hive_parts[i].clone()]));
+            *file_scan_type = scan_type;
+
+            let lp = node_to_lp_cloned(plan.lp_top, &plan.expr_arena, &plan.lp_arena);
+            // Write to a temporary file, so that an interrupted write doesn't leave a part.
+            let tmp_path = dir.join(format!("{part}.tmp"));
+            LazyFrame::from_logical_plan(lp, opt_state).sink_parquet(&tmp_path, options.clone())?;
+            std::fs::rename(&tmp_path, dir.join(&part))?;
+
+            writeln!(checkpoint, "{part}\t{}", source.display())?;
+            checkpoint.sync_all()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use polars_core::prelude::*;
+use polars_mem_engine::{create_physical_plan_with_stats, PlanStats};
+
+use crate::prelude::*;
+
+fn fmt_duration(d: Duration) -> String {
+    let micros = d.as_micros();
+    if micros < 1_000 {
+        format!("{micros}us")
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1e3)
+    } else {
+        format!("{:.2}s", micros as f64 / 1e6)
+    }
+}
+
+fn fmt_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Annotate the nodes that ran on the in-memory engine. The time of a node is its own, without
+/// the time of its inputs.
+fn in_memory_annotations(
+    plan: &IRPlan,
+    stats: &PlanStats,
+    annotations: &mut PlHashMap<Node, String>,
+) {
+    let stats = stats.to_map();
+    let mut inputs = vec![];
+    for (&node, node_stats) in &stats {
+        inputs.clear();
+        plan.lp_arena.get(node).copy_inputs(&mut inputs);
+        let input_time = inputs
+            .iter()
+            .filter_map(|input| stats.get(input))
+            .map(|input| input.time)
+            .sum::<Duration>();
+
+        let mut annotation = format!(
+            "rows: {}, time: {}, memory: {}",
+            node_stats.rows,
+            fmt_duration(node_stats.time.saturating_sub(input_time)),
+            fmt_bytes(node_stats.bytes as u64),
+        );
+        if node_stats.runs > 1 {
+            annotation.push_str(&format!(", runs: {}", node_stats.runs));
+        }
+        annotations.insert(node, annotation);
+    }
+}
+
+/// Annotate the nodes that ran on the streaming engine, with the metrics of the pipelines that
+/// they ran in.
+#[cfg(feature = "streaming")]
+fn streaming_annotations(
+    plan: &IRPlan,
+    annotations: &mut PlHashMap<Node, String>,
+) -> PolarsResult<()> {
+    for (_, lp) in (&plan.lp_arena).iter(plan.lp_top) {
+        let IR::MapFunction { function, .. } = lp else {
+            continue;
+        };
+        let Some(metrics) = function.streaming_metrics() else {
+            continue;
+        };
+
+        let kind = metrics.column("kind")?.str()?;
+        let plan_node = metrics.column("plan_node")?.u64()?;
+        let counter = |name: &str| -> PolarsResult<Vec<u64>> {
+            Ok(metrics.column(name)?.u64()?.into_no_null_iter().collect())
+        };
+        let rows_in = counter("rows_in")?;
+        let rows_out = counter("rows_out")?;
+        let bytes_out = counter("bytes_out")?;
+        let time = counter("time")?;
+        let spill_bytes = counter("spill_bytes")?;
+        let peak_memory = counter("peak_memory")?;
+
+        for i in 0..metrics.height() {
+            let Some(node) = plan_node.get(i) else {
+                continue;
+            };
+            let time = fmt_duration(Duration::from_micros(time[i]));
+            let kind = kind.get(i).unwrap();
+            let mut annotation = match kind {
+                "source" => format!(
+                    "source: {} rows out, time: {time}, memory: {}",
+                    rows_out[i],
+                    fmt_bytes(bytes_out[i])
+                ),
+                "sink" => format!(
+                    "sink: {} rows in, time: {time}, peak memory: {}",
+                    rows_in[i],
+                    fmt_bytes(peak_memory[i])
+                ),
+                _ => format!(
+                    "{kind}: {} rows in, {} rows out, time: {time}, memory: {}",
+                    rows_in[i],
+                    rows_out[i],
+                    fmt_bytes(bytes_out[i])
+                ),
+            };
+            if spill_bytes[i] > 0 {
+                annotation.push_str(&format!(", spilled: {}", fmt_bytes(spill_bytes[i])));
+            }
+
+            // a node can run in several pipelines, e.g. a join is the sink of one and an
+            // operator of another, and the root also runs on the in-memory engine
+            annotations
+                .entry(Node(node as usize))
+                .and_modify(|existing| {
+                    existing.push_str(" | ");
+                    existing.push_str(&annotation)
+                })
+                .or_insert(annotation);
+        }
+    }
+    Ok(())
+}
+
+impl LazyFrame {
+    /// Execute the query and return its result, together with the optimized plan in which every
+    /// node that ran is annotated with its runtime statistics.
+    ///
+    /// Unlike the flat table of [`LazyFrame::profile`], the statistics are shown where they belong
+    /// in the plan, with the ids of [`LazyFrame::explain_with_node_ids`]. The nodes that ran on
+    /// the in-memory engine show the rows that they produced, the time that they took without
+    /// their inputs and the estimated size of their output. The nodes that ran on the streaming
+    /// engine show the rows that went in and came out, their time summed over all threads and,
+    /// for sinks, the peak of the memory that they used and the bytes they spilled to disk.
+    pub fn explain_analyze(mut self) -> PolarsResult<(DataFrame, String)> {
+        let (mut lp_arena, mut expr_arena) = self.get_arenas();
+        let lp_top =
+            self.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut vec![], true)?;
+        // the physical plan takes the nodes out of the arena, keep them to format the plan
+        let plan = IRPlan::new(lp_top, lp_arena.clone(), expr_arena.clone());
+
+        let stats = PlanStats::new();
+        let mut physical_plan =
+            create_physical_plan_with_stats(lp_top, &mut lp_arena, &expr_arena, &stats)?;
+        let out = physical_plan.execute(&mut ExecutionState::new())?;
+
+        let mut annotations = PlHashMap::new();
+        in_memory_annotations(&plan, &stats, &mut annotations);
+        // The root of a streaming plan is also the node that runs its pipelines, so it shows
+        // the statistics of both engines.
+        #[cfg(feature = "streaming")]
+        streaming_annotations(&plan, &mut annotations)?;
+
+        let explain = plan.display().with_annotations(&annotations).to_string();
+        Ok((out, explain))
+    }
+}
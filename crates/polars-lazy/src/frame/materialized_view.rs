@@ -0,0 +1,525 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::SystemTime;
+
+use polars_core::prelude::*;
+use polars_plan::plans::expr_ir::ExprIR;
+
+use crate::prelude::*;
+
+/// A file of a scan when a materialized view processed it.
+#[derive(Clone, Copy)]
+struct ProcessedFile {
+    /// The size and the modification time of the file, if they are known, so that the file isn't
+    /// scanned again if they didn't change.
+    size: Option<u64>,
+    modified: Option<SystemTime>,
+    /// The number of rows of the file that were processed.
+    rows: usize,
+}
+
+impl ProcessedFile {
+    /// The file at `path` as it is now, of which no rows were processed.
+    fn unprocessed(path: &Path) -> Self {
+        let metadata = std::fs::metadata(path).ok();
+        Self {
+            size: metadata.as_ref().map(|m| m.len()),
+            modified: metadata.and_then(|m| m.modified().ok()),
+            rows: 0,
+        }
+    }
+}
+
+/// A source of a materialized view and the part of it that was processed.
+#[derive(Clone)]
+enum Source {
+    /// The sources of a scan as they were given, e.g. a glob that matches the files that are
+    /// added later, and the files that were processed.
+    Scan {
+        sources: DslScanSources,
+        files: PlHashMap<PathBuf, ProcessedFile>,
+    },
+    /// In-memory data doesn't change, it is processed once.
+    Frame { processed: bool },
+}
+
+/// How the aggregation of the new rows is merged into the result of the view.
+struct Merge {
+    /// The output names of the keys, if the aggregation has groups.
+    keys: Option<Vec<PlSmallStr>>,
+    /// The aggregations that combine the aggregations of the old and the new rows.
+    aggs: Vec<Expr>,
+    maintain_order: bool,
+}
+
+/// The result of a query over append-only sources, see [`LazyFrame::materialize`].
+///
+/// [`MaterializedView::refresh`] brings it up to date by only processing the files and the rows
+/// that were added to the sources.
+pub struct MaterializedView {
+    plan: DslPlan,
+    opt_state: OptFlags,
+    merge: Option<Merge>,
+    sources: Vec<Source>,
+    result: DataFrame,
+}
+
+/// Check that every row of the input at `node` is computed from a single row of the sources, so
+/// that the new rows of the sources only give new rows of the input.
+fn check_row_wise(node: Node, lp_arena: &Arena<IR>, expr_arena: &Arena<AExpr>) -> PolarsResult<()> {
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        let lp = lp_arena.get(node);
+        let row_wise = match lp {
+            IR::Scan { .. } | IR::DataFrameScan { .. } | IR::SimpleProjection { .. } => true,
+            IR::Filter { predicate, .. } => {
+                is_streamable(predicate.node(), expr_arena, Context::Default)
+            },
+            IR::Select { expr, .. } => {
+                all_streamable(expr, expr_arena, Context::Default)
+                    && !expr.iter().all(|e| e.is_scalar(expr_arena))
+            },
+            IR::HStack { exprs, .. } => all_streamable(exprs, expr_arena, Context::Default),
+            IR::Union { options, .. } => options.slice.is_none(),
+            IR::MapFunction { function, .. } => matches!(
+                function,
+                FunctionIR::Rechunk
+                    | FunctionIR::Hints(_)
+                    | FunctionIR::Rename { .. }
+                    | FunctionIR::Explode { .. }
+                    | FunctionIR::Unnest { .. }
+            ),
+            _ => false,
+        };
+        polars_ensure!(
+            row_wise,
+            InvalidOperation: "a materialized view can't compute '{}' incrementally", lp.name()
+        );
+        lp.copy_inputs(&mut stack);
+    }
+    Ok(())
+}
+
+/// The aggregation that merges the aggregation `e` of the old rows and of the new rows.
+fn merge_agg(e: &ExprIR, expr_arena: &Arena<AExpr>, grouped: bool) -> Option<Expr> {
+    let column = col(e.output_name().clone());
+    let agg = match expr_arena.get(e.node()) {
+        AExpr::Len => return Some(column.sum()),
+        AExpr::Agg(agg) => agg,
+        _ => return None,
+    };
+    if !is_streamable(agg.get_input().first(), expr_arena, Context::Default) {
+        return None;
+    }
+    let merged = match agg {
+        IRAggExpr::Sum(_) | IRAggExpr::Count(_, _) => column.sum(),
+        IRAggExpr::Min { propagate_nans, .. } => Expr::Agg(AggExpr::Min {
+            input: Arc::new(column),
+            propagate_nans: *propagate_nans,
+        }),
+        IRAggExpr::Max { propagate_nans, .. } => Expr::Agg(AggExpr::Max {
+            input: Arc::new(column),
+            propagate_nans: *propagate_nans,
+        }),
+        // Without groups, the first and the last of no new rows are null.
+        IRAggExpr::First(_) if grouped => column.first(),
+        IRAggExpr::Last(_) if grouped => column.last(),
+        _ => return None,
+    };
+    Some(merged)
+}
+
+/// Check that the query at `root` can be computed incrementally, and return how the result of
+/// the new rows is merged into the result of the view if the query aggregates.
+fn get_merge(
+    root: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> PolarsResult<Option<Merge>> {
+    let merge_aggs = |aggs: &[ExprIR], grouped| {
+        aggs.iter()
+            .map(|e| merge_agg(e, expr_arena, grouped))
+            .collect::<Option<Vec<_>>>()
+    };
+    match lp_arena.get(root) {
+        IR::GroupBy {
+            input,
+            keys,
+            aggs,
+            apply,
+            maintain_order,
+            options,
+            ..
+        } => {
+            let aggs = merge_aggs(aggs, true);
+            #[cfg(feature = "dynamic_group_by")]
+            let temporal = options.rolling.is_some() || options.dynamic.is_some();
+            #[cfg(not(feature = "dynamic_group_by"))]
+            let temporal = false;
+            polars_ensure!(
+                aggs.is_some()
+                    && apply.is_none()
+                    && !temporal
+                    && options.slice.is_none()
+                    && all_streamable(keys, expr_arena, Context::Default),
+                InvalidOperation: "a materialized view can only compute sums, counts, lengths, \
+                minimums, maximums, firsts and lasts of groups incrementally"
+            );
+            check_row_wise(*input, lp_arena, expr_arena)?;
+            Ok(Some(Merge {
+                keys: Some(keys.iter().map(|e| e.output_name().clone()).collect()),
+                aggs: aggs.unwrap(),
+                maintain_order: *maintain_order,
+            }))
+        },
+        IR::Select { input, expr, .. } => match merge_aggs(expr, false) {
+            Some(aggs) => {
+                check_row_wise(*input, lp_arena, expr_arena)?;
+                Ok(Some(Merge {
+                    keys: None,
+                    aggs,
+                    maintain_order: false,
+                }))
+            },
+            None => check_row_wise(root, lp_arena, expr_arena).map(|_| None),
+        },
+        _ => check_row_wise(root, lp_arena, expr_arena).map(|_| None),
+    }
+}
+
+/// Rebuild `plan` with its sources replaced by `f`, which is called on the sources in the order
+/// of the plan.
+fn map_sources(
+    plan: &DslPlan,
+    f: &mut impl FnMut(&DslPlan) -> PolarsResult<DslPlan>,
+) -> PolarsResult<DslPlan> {
+    let mut map = |input: &Arc<DslPlan>| map_sources(input, f).map(Arc::new);
+    let plan = match plan {
+        DslPlan::Scan { .. } | DslPlan::DataFrameScan { .. } => return f(plan),
+        DslPlan::Filter { input, predicate } => DslPlan::Filter {
+            input: map(input)?,
+            predicate: predicate.clone(),
+        },
+        DslPlan::Cache {
+            input,
+            id,
+            cache_hits,
+        } => DslPlan::Cache {
+            input: map(input)?,
+            id: *id,
+            cache_hits: *cache_hits,
+        },
+        DslPlan::Select {
+            expr,
+            input,
+            options,
+        } => DslPlan::Select {
+            expr: expr.clone(),
+            input: map(input)?,
+            options: *options,
+        },
+        DslPlan::GroupBy {
+            input,
+            keys,
+            aggs,
+            apply,
+            maintain_order,
+            options,
+        } => DslPlan::GroupBy {
+            input: map(input)?,
+            keys: keys.clone(),
+            aggs: aggs.clone(),
+            apply: apply.clone(),
+            maintain_order: *maintain_order,
+            options: options.clone(),
+        },
+        DslPlan::HStack {
+            input,
+            exprs,
+            options,
+        } => DslPlan::HStack {
+            input: map(input)?,
+            exprs: exprs.clone(),
+            options: *options,
+        },
+        DslPlan::MapFunction { input, function } => DslPlan::MapFunction {
+            input: map(input)?,
+            function: function.clone(),
+        },
+        DslPlan::Union { inputs, args } => DslPlan::Union {
+            inputs: inputs
+                .iter()
+                .map(|input| map_sources(input, f))
+                .collect::<PolarsResult<_>>()?,
+            args: *args,
+        },
+        DslPlan::IR { dsl, .. } => return map_sources(dsl, f),
+        _ => polars_bail!(
+            InvalidOperation: "a materialized view can't compute this plan incrementally"
+        ),
+    };
+    Ok(plan)
+}
+
+/// A scan of the file at `path` with the options of an expanded scan.
+fn scan_file(path: &Path, scan: &DslPlan) -> DslPlan {
+    let DslPlan::Scan {
+        predicate,
+        file_options,
+        scan_type,
+        ..
+    } = scan
+    else {
+        unreachable!()
+    };
+    #[allow(unused_mut)]
+    let mut scan_type = scan_type.clone();
+    // The metadata belongs to another file.
+    #[cfg(feature = "parquet")]
+    if let FileScan::Parquet { metadata, .. } = &mut scan_type {
+        *metadata = None;
+    }
+    #[cfg(feature = "ipc")]
+    if let FileScan::Ipc { metadata, .. } = &mut scan_type {
+        *metadata = None;
+    }
+    DslPlan::Scan {
+        sources: Arc::new(Mutex::new(DslScanSources {
+            sources: ScanSources::Paths([path.to_path_buf()].into()),
+            is_expanded: true,
+        })),
+        file_info: Arc::new(RwLock::new(None)),
+        hive_parts: None,
+        predicate: predicate.clone(),
+        file_options: file_options.clone(),
+        scan_type,
+    }
+}
+
+fn empty(plan: DslPlan) -> DslPlan {
+    DslPlan::Slice {
+        input: Arc::new(plan),
+        offset: 0,
+        len: 0,
+    }
+}
+
+impl Source {
+    /// The plan of the rows of the source at `plan` that weren't processed, and the source once
+    /// they are.
+    fn new_rows(&self, plan: &DslPlan) -> PolarsResult<(DslPlan, Source)> {
+        match self {
+            Source::Frame { processed } => {
+                let new_rows = plan.clone();
+                let new_rows = if *processed {
+                    empty(new_rows)
+                } else {
+                    new_rows
+                };
+                Ok((new_rows, Source::Frame { processed: true }))
+            },
+            Source::Scan { sources, files } => {
+                let DslPlan::Scan {
+                    predicate,
+                    file_options,
+                    scan_type,
+                    ..
+                } = plan
+                else {
+                    unreachable!()
+                };
+                let (mut file_options, mut scan_type) = (file_options.clone(), scan_type.clone());
+                polars_ensure!(
+                    file_options.slice.is_none() && file_options.row_index.is_none(),
+                    InvalidOperation: "a materialized view can't scan files with a row limit or \
+                    a row index, as they depend on the rows of the other files"
+                );
+                let mut expanded = sources.clone();
+                expanded.expand_paths(&mut scan_type, &mut file_options)?;
+                let ScanSources::Paths(paths) = &expanded.sources else {
+                    polars_bail!(
+                        InvalidOperation: "a materialized view can only scan files at paths"
+                    )
+                };
+                let scan = DslPlan::Scan {
+                    sources: Arc::new(Mutex::new(expanded.clone())),
+                    file_info: Arc::new(RwLock::new(None)),
+                    hive_parts: None,
+                    predicate: predicate.clone(),
+                    file_options,
+                    scan_type,
+                };
+
+                let mut new_rows = vec![];
+                let mut new_files = files.clone();
+                for path in paths.iter() {
+                    let current = ProcessedFile::unprocessed(path);
+                    let processed = files.get(path);
+                    if let Some(processed) = processed {
+                        let shrunk = matches!(
+                            (current.size, processed.size),
+                            (Some(size), Some(processed_size)) if size < processed_size
+                        );
+                        polars_ensure!(
+                            !shrunk,
+                            ComputeError: "the file {:?} is smaller than when the materialized \
+                            view processed it, the sources of a view must be append-only", path
+                        );
+                        // The rows of a file of which the size and the modification time didn't
+                        // change aren't counted again.
+                        if current.size.is_some()
+                            && current.size == processed.size
+                            && current.modified == processed.modified
+                        {
+                            continue;
+                        }
+                    }
+
+                    let file = scan_file(path, &scan);
+                    let height = LazyFrame::from(file.clone()).select([len()]).collect()?;
+                    let height = height.get_columns()[0].idx()?.get(0).unwrap_or(0) as usize;
+                    let processed_rows = processed.map_or(0, |processed| processed.rows);
+                    polars_ensure!(
+                        height >= processed_rows,
+                        ComputeError: "the file {:?} has fewer rows than when the materialized \
+                        view processed it, the sources of a view must be append-only", path
+                    );
+                    if height > processed_rows {
+                        new_rows.push(DslPlan::Slice {
+                            input: Arc::new(file),
+                            offset: processed_rows as i64,
+                            len: (height - processed_rows) as IdxSize,
+                        });
+                    }
+                    new_files.insert(
+                        path.clone(),
+                        ProcessedFile {
+                            rows: height,
+                            ..current
+                        },
+                    );
+                }
+                let new_rows = match new_rows.len() {
+                    0 => empty(scan),
+                    1 => new_rows.pop().unwrap(),
+                    _ => DslPlan::Union {
+                        inputs: new_rows,
+                        args: Default::default(),
+                    },
+                };
+                let source = Source::Scan {
+                    sources: sources.clone(),
+                    files: new_files,
+                };
+                Ok((new_rows, source))
+            },
+        }
+    }
+}
+
+impl MaterializedView {
+    /// The result of the query over the rows that the view processed.
+    pub fn result(&self) -> &DataFrame {
+        &self.result
+    }
+
+    /// A [`LazyFrame`] of the result, to query it further.
+    pub fn lazy(&self) -> LazyFrame {
+        self.result.clone().lazy()
+    }
+
+    /// Compute the result of the query over the rows that were added to the sources since the
+    /// last refresh, and the sources once these rows are processed.
+    fn compute_new_rows(&self) -> PolarsResult<(DataFrame, Vec<Source>)> {
+        let mut sources = self.sources.iter();
+        let mut new_sources = Vec::with_capacity(self.sources.len());
+        let plan = map_sources(&self.plan, &mut |source| {
+            let (new_rows, new_source) = sources.next().unwrap().new_rows(source)?;
+            new_sources.push(new_source);
+            Ok(new_rows)
+        })?;
+        let new_rows = LazyFrame::from(plan)
+            .with_optimizations(self.opt_state)
+            .collect()?;
+        Ok((new_rows, new_sources))
+    }
+
+    /// Bring the result up to date with the sources, by processing the files that were added to
+    /// the scans and the rows that were appended to their files since the last refresh. The files
+    /// of which the size and the modification time didn't change aren't scanned.
+    ///
+    /// If the refresh fails, the view is unchanged and the next refresh processes the same rows.
+    pub fn refresh(&mut self) -> PolarsResult<()> {
+        let (new_rows, sources) = self.compute_new_rows()?;
+        match &self.merge {
+            None => {
+                self.result.vstack_mut(&new_rows)?;
+            },
+            Some(merge) => {
+                let both = concat(
+                    [self.result.clone().lazy(), new_rows.lazy()],
+                    Default::default(),
+                )?;
+                let merged = match &merge.keys {
+                    Some(keys) => {
+                        let keys = keys
+                            .iter()
+                            .map(|name| col(name.clone()))
+                            .collect::<Vec<_>>();
+                        if merge.maintain_order {
+                            both.group_by_stable(keys)
+                        } else {
+                            both.group_by(keys)
+                        }
+                        .agg(&merge.aggs)
+                    },
+                    None => both.select(&merge.aggs),
+                };
+                self.result = merged.collect()?;
+            },
+        }
+        // The rows are only marked as processed once they are merged into the result.
+        self.sources = sources;
+        Ok(())
+    }
+}
+
+impl LazyFrame {
+    /// Materialize the result of this query into a view that is refreshed incrementally, for
+    /// queries over append-only sources, e.g. a glob that matches files that are added later.
+    ///
+    /// [`MaterializedView::refresh`] only processes the new files of the scans and the rows that
+    /// were appended to their files, and merges their result into the result of the view. This
+    /// requires that every row of the result is computed from a single row of the sources, by
+    /// filters, projections and unions, or that the query ends in an aggregation of which the
+    /// results of the old and the new rows can be combined, i.e. sums, counts, lengths, minimums
+    /// and maximums, and the firsts and the lasts of groups. In-memory data in the query is
+    /// processed once.
+    pub fn materialize(self) -> PolarsResult<MaterializedView> {
+        let mut sources = vec![];
+        let plan = map_sources(&self.logical_plan, &mut |source| {
+            sources.push(match source {
+                DslPlan::Scan { sources, .. } => Source::Scan {
+                    sources: sources.lock().unwrap().clone(),
+                    files: Default::default(),
+                },
+                _ => Source::Frame { processed: false },
+            });
+            Ok(source.clone())
+        })?;
+
+        let opt_state = self.opt_state;
+        let alp = self.to_alp()?;
+        let merge = get_merge(alp.lp_top, &alp.lp_arena, &alp.expr_arena)?;
+
+        let mut view = MaterializedView {
+            plan,
+            opt_state,
+            merge,
+            sources,
+            result: Default::default(),
+        };
+        (view.result, view.sources) = view.compute_new_rows()?;
+        Ok(view)
+    }
+}
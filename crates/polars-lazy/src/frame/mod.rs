@@ -3,12 +3,26 @@
 mod python;
 
 mod cached_arenas;
+#[cfg(feature = "parquet")]
+mod checkpoint;
 mod err;
 #[cfg(not(target_arch = "wasm32"))]
 mod exitable;
+mod explain_analyze;
+#[cfg(feature = "materialized_view")]
+mod materialized_view;
+#[cfg(feature = "persist")]
+mod persist;
 #[cfg(feature = "pivot")]
 pub mod pivot;
-
+mod prepared;
+#[cfg(feature = "result_cache")]
+mod result_cache;
+#[cfg(feature = "substrait")]
+mod substrait;
+
+#[cfg(any(feature = "ipc", feature = "csv"))]
+use std::io::Write;
 #[cfg(any(
     feature = "parquet",
     feature = "ipc",
@@ -19,25 +33,50 @@ use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 pub use anonymous_scan::*;
+#[cfg(feature = "avro")]
+pub use avro::*;
 #[cfg(feature = "csv")]
 pub use csv::*;
+#[cfg(feature = "dataset")]
+pub use dataset::*;
+#[cfg(feature = "delta")]
+pub use delta::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use exitable::*;
 pub use file_list_reader::*;
+#[cfg(feature = "fwf")]
+pub use fwf::*;
+#[cfg(feature = "iceberg")]
+pub use iceberg::*;
 #[cfg(feature = "ipc")]
 pub use ipc::*;
+#[cfg(feature = "kafka")]
+pub use kafka::*;
+#[cfg(feature = "materialized_view")]
+pub use materialized_view::MaterializedView;
 #[cfg(feature = "json")]
 pub use ndjson::*;
+#[cfg(feature = "orc")]
+pub use orc::*;
 #[cfg(feature = "parquet")]
 pub use parquet::*;
+#[cfg(feature = "persist")]
+pub use persist::{PersistFormat, PersistOptions};
 use polars_core::prelude::*;
 use polars_expr::{create_physical_expr, ExpressionConversionState};
-use polars_io::RowIndex;
+use polars_io::{PartitionedWriteOptions, RowIndex};
 use polars_mem_engine::{create_physical_plan, Executor};
 use polars_ops::frame::JoinCoalesce;
+#[cfg(feature = "streaming")]
+pub use polars_pipe::operators::{FinalizedSink, Sink, SinkResult};
 pub use polars_plan::frame::{AllowedOptimizations, OptFlags};
 use polars_plan::global::FETCH_ROWS;
 use polars_utils::pl_str::PlSmallStr;
+pub use prepared::PreparedQuery;
+#[cfg(feature = "result_cache")]
+pub use result_cache::ResultCache;
+#[cfg(feature = "streaming")]
+pub use source::*;
 
 use crate::frame::cached_arenas::CachedArena;
 #[cfg(feature = "streaming")]
@@ -145,6 +184,12 @@ impl LazyFrame {
         self
     }
 
+    /// Toggle reordering chains of inner joins by the estimated size of their inputs.
+    pub fn with_join_reorder(mut self, toggle: bool) -> Self {
+        self.opt_state.set(OptFlags::JOIN_REORDER, toggle);
+        self
+    }
+
     /// Toggle predicate pushdown optimization.
     pub fn with_predicate_pushdown(mut self, toggle: bool) -> Self {
         self.opt_state.set(OptFlags::PREDICATE_PUSHDOWN, toggle);
@@ -208,6 +253,40 @@ impl LazyFrame {
         self
     }
 
+    /// Force or forbid behaviors of the engines for this query, where the environment
+    /// variables and the global settings apply to all queries of the process.
+    ///
+    /// The join hint applies to the joins of this query that have no hint of their own, and
+    /// turning streaming off keeps the nodes of this query in memory, also when a query that is
+    /// built on it streams. The maximum number of threads only bounds the pipelines of the
+    /// streaming engine; the default engine always runs on the global thread pool.
+    pub fn with_hints(self, hints: QueryHints) -> Self {
+        let mut opt_state = self.opt_state;
+        if let Some(cse) = hints.cse {
+            opt_state.set(
+                OptFlags::COMM_SUBPLAN_ELIM | OptFlags::COMM_SUBEXPR_ELIM,
+                cse,
+            );
+        }
+        if hints.streaming == Some(true) {
+            opt_state |= OptFlags::STREAMING;
+        }
+        self.map_private(DslFunction::FunctionIR(FunctionIR::Hints(hints)))
+            .with_optimizations(opt_state)
+    }
+
+    /// Let the streaming engine buffer at most `bytes` for this query, where the
+    /// `POLARS_STREAMING_MEMORY_LIMIT` env var applies to all queries of the process.
+    ///
+    /// Once the query buffers this much, its operators spill to disk and its sources wait for
+    /// the spills. The default engine ignores the limit.
+    pub fn with_memory_limit(self, bytes: usize) -> Self {
+        self.with_hints(QueryHints {
+            memory_limit: Some(bytes),
+            ..Default::default()
+        })
+    }
+
     /// Return a String describing the naive (un-optimized) logical plan.
     pub fn describe_plan(&self) -> PolarsResult<String> {
         Ok(self.clone().to_alp()?.describe())
@@ -220,7 +299,7 @@ impl LazyFrame {
 
     // @NOTE: this is used because we want to set the `enable_fmt` flag of `optimize_with_scratch`
     // to `true` for describe.
-    fn _describe_to_alp_optimized(mut self) -> PolarsResult<IRPlan> {
+    pub(crate) fn _describe_to_alp_optimized(mut self) -> PolarsResult<IRPlan> {
         let (mut lp_arena, mut expr_arena) = self.get_arenas();
         let node = self.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut vec![], true)?;
 
@@ -244,6 +323,17 @@ impl LazyFrame {
             .describe_tree_format())
     }
 
+    /// Return the optimized logical plan as a [Substrait](https://substrait.io) plan, in the JSON
+    /// encoding of its protobuf messages.
+    ///
+    /// Substrait is a vendor-neutral format of relational plans, so the plan can be executed by
+    /// other engines or archived. Returns `Err` if the plan has operations that have no
+    /// equivalent in Substrait, e.g. user defined functions.
+    #[cfg(feature = "substrait")]
+    pub fn to_substrait(&self) -> PolarsResult<String> {
+        self.clone().to_alp_optimized()?.to_substrait()
+    }
+
     /// Return a String describing the logical plan.
     ///
     /// If `optimized` is `true`, explains the optimized plan. If `optimized` is `false,
@@ -256,6 +346,39 @@ impl LazyFrame {
         }
     }
 
+    /// Return a String describing the logical plan, with the id of every node above it.
+    ///
+    /// The ids are the ids of the nodes of the diagrams of `to_dot`.
+    pub fn explain_with_node_ids(&self, optimized: bool) -> PolarsResult<String> {
+        let lp = if optimized {
+            self.clone()._describe_to_alp_optimized()
+        } else {
+            self.clone().to_alp()
+        }?;
+        Ok(lp.display().with_node_ids().to_string())
+    }
+
+    /// Return the optimized logical plan as JSON, so that tools can check the shape of the plan
+    /// without parsing the output of `explain`.
+    ///
+    /// Every node has the same id as in `explain_with_node_ids`, its kind, the columns of its
+    /// schema, its expressions, what was pushed down into it and its inputs.
+    #[cfg(feature = "explain_json")]
+    pub fn explain_json(&self) -> PolarsResult<String> {
+        Ok(self.clone()._describe_to_alp_optimized()?.to_json())
+    }
+
+    /// Estimate the number of rows and the size in bytes of the result of the query without
+    /// running it, from the metadata of the files that it scans and the guessed selectivity
+    /// of its filters, group-bys and joins. This lets e.g. orchestration code choose between
+    /// the streaming and the in-memory engine before collecting.
+    pub fn estimate(&self) -> PolarsResult<PlanEstimate> {
+        let mut lf = self.clone();
+        // The pipelines of the streaming engine hide the nodes that they run.
+        lf.opt_state &= !(OptFlags::STREAMING | OptFlags::NEW_STREAMING);
+        Ok(lf.to_alp_optimized()?.estimate())
+    }
+
     /// Add a sort operation to the logical plan.
     ///
     /// Sorts the LazyFrame by the column name specified using the provided options.
@@ -775,15 +898,62 @@ impl LazyFrame {
         Ok((out, timer_df))
     }
 
+    /// Execute the query and return its result, together with the metrics of the nodes that ran
+    /// on the streaming engine.
+    ///
+    /// Every row of the metrics is a source, operator or sink of a pipeline, in the order in which
+    /// they finished. The columns are the name of the node, its kind, the index of the node of
+    /// the plan that it executes if there is one, the rows and estimated bytes that went in and
+    /// came out, the wall time in microseconds summed over all threads, the bytes that a sink
+    /// spilled to disk and the peak of the memory that the query buffered while a sink ran,
+    /// and the first start and the last end of the work of the node in microseconds since
+    /// its pipelines started, which are null if it did no work. The metrics are empty if no part
+    /// of the query ran on the streaming engine.
+    #[cfg(feature = "streaming")]
+    pub fn collect_with_streaming_metrics(self) -> PolarsResult<(DataFrame, DataFrame)> {
+        // the physical plan takes the nodes out of the arena, keep the functions that run the
+        // pipelines, as they hold the metrics of their last run
+        let pipelines = std::cell::RefCell::new(vec![]);
+        let out = self._collect_post_opt(|lp_top, lp_arena, _| {
+            let functions = (&*lp_arena).iter(lp_top).filter_map(|(_, lp)| match lp {
+                IR::MapFunction { function, .. } => Some(function.clone()),
+                _ => None,
+            });
+            pipelines.borrow_mut().extend(functions);
+            Ok(())
+        })?;
+
+        let pipelines = pipelines.into_inner();
+        let mut metrics = pipelines
+            .iter()
+            .filter_map(|function| function.streaming_metrics());
+        let Some(mut df) = metrics.next() else {
+            return Ok((out, DataFrame::empty()));
+        };
+        for other in metrics {
+            df.vstack_mut(&other)?;
+        }
+        Ok((out, df))
+    }
+
     /// Stream a query result into a parquet file. This is useful if the final result doesn't fit
     /// into memory. This methods will return an error if the query cannot be completely done in a
     /// streaming fashion.
+    ///
+    /// If the `path` is a cloud URL, e.g. `s3://bucket/file.parquet`, the file is uploaded in parts
+    /// while it is written, with the cloud options from the environment. Use
+    /// [`LazyFrame::sink_parquet_cloud`] to set the cloud options.
     #[cfg(feature = "parquet")]
     pub fn sink_parquet(
         self,
         path: impl AsRef<Path>,
         options: ParquetWriteOptions,
     ) -> PolarsResult<()> {
+        #[cfg(feature = "cloud_write")]
+        if polars_io::is_cloud_url(path.as_ref()) {
+            let uri = path.as_ref().to_string_lossy().into_owned();
+            return self.sink_parquet_cloud(uri, None, options);
+        }
         self.sink(
             SinkType::File {
                 path: Arc::new(path.as_ref().to_path_buf()),
@@ -793,6 +963,39 @@ impl LazyFrame {
         )
     }
 
+    /// Stream a query result into a hive-partitioned directory of parquet files, with a file per
+    /// unique combination of the values of the `partition_by` columns, e.g.
+    /// `path/date=2024-01-01/customer=a/00000000.parquet`. The partition columns are also
+    /// written to the files. This methods will return an error if the query cannot be completely
+    /// done in a streaming fashion.
+    ///
+    /// A partition is split over multiple files if it has more rows than `max_rows_per_file` of
+    /// the `partition_options`, or if it is interleaved with many other partitions in the input.
+    /// If `write_metadata` is set, the footers of all files are collected in a `_metadata` file
+    /// in the root of the dataset.
+    #[cfg(feature = "parquet")]
+    pub fn sink_parquet_partitioned<I, S>(
+        self,
+        path: impl AsRef<Path>,
+        partition_by: I,
+        options: ParquetWriteOptions,
+        partition_options: PartitionedWriteOptions,
+    ) -> PolarsResult<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<PlSmallStr>,
+    {
+        self.sink(
+            SinkType::Partitioned {
+                path: Arc::new(path.as_ref().to_path_buf()),
+                file_type: FileType::Parquet(options),
+                partition_by: partition_by.into_iter().map(Into::into).collect(),
+                options: partition_options,
+            },
+            "collect().write_parquet()",
+        )
+    }
+
     /// Stream a query result into a parquet file on an ObjectStore-compatible cloud service. This is useful if the final result doesn't fit
     /// into memory, and where you do not want to write to a local file but to a location in the cloud.
     /// This method will return an error if the query cannot be completely done in a
@@ -814,6 +1017,48 @@ impl LazyFrame {
         )
     }
 
+    /// Stream a query result into a Parquet file that is committed as a new version of the Delta
+    /// table at `path`, which is created if it doesn't exist. Returns the committed version.
+    ///
+    /// Partitioned tables are not supported; use `collect().write_delta()` for those.
+    #[cfg(feature = "delta")]
+    pub fn sink_delta(
+        mut self,
+        path: impl AsRef<Path>,
+        options: polars_io::delta::DeltaWriteOptions,
+    ) -> PolarsResult<i64> {
+        use polars_io::parquet::read::ParquetReader;
+        use polars_io::SerReader;
+
+        let schema = self.collect_schema()?;
+        let mut transaction =
+            polars_io::delta::DeltaTransaction::try_new(path.as_ref(), &schema, &options)?;
+        polars_ensure!(
+            transaction.partition_columns().is_empty(),
+            InvalidOperation: "cannot sink into a partitioned Delta table; use `collect().write_delta()` instead"
+        );
+        let columns = transaction
+            .schema()
+            .iter()
+            .map(|(name, dtype)| col(name.clone()).strict_cast(dtype.clone()))
+            .collect::<Vec<_>>();
+
+        let file = transaction.data_file(&[])?;
+        let num_rows = self
+            .select(columns)
+            .sink_parquet(&file, options.parquet_options)
+            .and_then(|_| ParquetReader::new(polars_utils::open_file(&file)?).num_rows());
+        let num_rows = match num_rows {
+            Ok(num_rows) => num_rows,
+            Err(e) => {
+                let _ = std::fs::remove_file(&file);
+                return Err(e);
+            },
+        };
+        transaction.add_file(file, vec![], num_rows)?;
+        transaction.commit()
+    }
+
     /// Stream a query result into an ipc/arrow file. This is useful if the final result doesn't fit
     /// into memory. This methods will return an error if the query cannot be completely done in a
     /// streaming fashion.
@@ -828,6 +1073,44 @@ impl LazyFrame {
         )
     }
 
+    /// Stream a query result as an ipc/arrow file into `writer`, such as a socket or an in-memory
+    /// buffer. This is useful if the final result doesn't fit into memory. This methods will
+    /// return an error if the query cannot be completely done in a streaming fashion.
+    #[cfg(feature = "ipc")]
+    pub fn sink_ipc_to_writer(
+        self,
+        writer: impl Write + Send + 'static,
+        options: IpcWriterOptions,
+    ) -> PolarsResult<()> {
+        self.sink(
+            SinkType::Writer {
+                writer: SharedWriter::new(writer),
+                file_type: FileType::Ipc(options),
+            },
+            "collect()` and an `IpcWriter",
+        )
+    }
+
+    /// Stream a query result into `writer` in the Arrow IPC stream format, e.g. to pipe the
+    /// result to another process. The batches are written as they are produced, so that the
+    /// reader, e.g. [`LazyFrame::scan_ipc_stream`], can process them before the query is done.
+    /// This methods will return an error if the query cannot be completely done in a streaming
+    /// fashion.
+    #[cfg(feature = "ipc_streaming")]
+    pub fn sink_ipc_stream(
+        self,
+        writer: impl Write + Send + 'static,
+        options: IpcWriterOptions,
+    ) -> PolarsResult<()> {
+        self.sink(
+            SinkType::Writer {
+                writer: SharedWriter::new(writer),
+                file_type: FileType::IpcStream(options),
+            },
+            "collect()` and an `IpcStreamWriter",
+        )
+    }
+
     /// Stream a query result into an ipc/arrow file on an ObjectStore-compatible cloud service.
     /// This is useful if the final result doesn't fit
     /// into memory, and where you do not want to write to a local file but to a location in the cloud.
@@ -873,11 +1156,75 @@ impl LazyFrame {
         )
     }
 
+    /// Stream a query result as a csv file into `writer`, such as a socket or an in-memory
+    /// buffer. This is useful if the final result doesn't fit into memory. This methods will
+    /// return an error if the query cannot be completely done in a streaming fashion.
+    #[cfg(feature = "csv")]
+    pub fn sink_csv_to_writer(
+        self,
+        writer: impl Write + Send + 'static,
+        options: CsvWriterOptions,
+    ) -> PolarsResult<()> {
+        self.sink(
+            SinkType::Writer {
+                writer: SharedWriter::new(writer),
+                file_type: FileType::Csv(options),
+            },
+            "collect()` and a `CsvWriter",
+        )
+    }
+
+    /// Stream a query result into a hive-partitioned directory of csv files, with a file per
+    /// unique combination of the values of the `partition_by` columns, e.g.
+    /// `path/date=2024-01-01/customer=a/00000000.csv`. The partition columns are not written to
+    /// the files. This methods will return an error if the query cannot be completely done in a
+    /// streaming fashion.
+    ///
+    /// A limited number of files is kept open. If the input is (roughly) sorted by the partition
+    /// columns, every partition is written to a single file, unless it has more rows than
+    /// `max_rows_per_file` of the `partition_options`.
+    #[cfg(feature = "csv")]
+    pub fn sink_csv_partitioned<I, S>(
+        self,
+        path: impl AsRef<Path>,
+        partition_by: I,
+        options: CsvWriterOptions,
+        partition_options: PartitionedWriteOptions,
+    ) -> PolarsResult<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<PlSmallStr>,
+    {
+        self.sink(
+            SinkType::Partitioned {
+                path: Arc::new(path.as_ref().to_path_buf()),
+                file_type: FileType::Csv(options),
+                partition_by: partition_by.into_iter().map(Into::into).collect(),
+                options: partition_options,
+            },
+            "collect().write_csv()",
+        )
+    }
+
     /// Stream a query result into a json file. This is useful if the final result doesn't fit
     /// into memory. This methods will return an error if the query cannot be completely done in a
     /// streaming fashion.
+    ///
+    /// The file is written as newline-delimited JSON, see [`LazyFrame::sink_ndjson`].
     #[cfg(feature = "json")]
     pub fn sink_json(self, path: impl AsRef<Path>, options: JsonWriterOptions) -> PolarsResult<()> {
+        self.sink_ndjson(path, options)
+    }
+
+    /// Stream a query result into a newline-delimited JSON file, with a JSON object per row.
+    /// This is useful if the final result doesn't fit into memory. This methods will return an
+    /// error if the query cannot be completely done in a streaming fashion.
+    #[cfg(feature = "json")]
+    pub fn sink_ndjson(
+        self,
+        path: impl AsRef<Path>,
+        options: JsonWriterOptions,
+    ) -> PolarsResult<()> {
         self.sink(
             SinkType::File {
                 path: Arc::new(path.as_ref().to_path_buf()),
@@ -887,12 +1234,65 @@ impl LazyFrame {
         )
     }
 
+    /// Stream a query result to the Flight service at `endpoint`, such as
+    /// `http://localhost:8815`, with a `DoPut` call for `descriptor`. This is useful if the final
+    /// result doesn't fit into memory. This methods will return an error if the query cannot be
+    /// completely done in a streaming fashion.
+    #[cfg(feature = "flight")]
+    pub fn sink_flight(
+        self,
+        endpoint: impl Into<String>,
+        descriptor: polars_io::flight::FlightDescriptor,
+    ) -> PolarsResult<()> {
+        self.sink(
+            SinkType::Flight {
+                endpoint: Arc::new(endpoint.into()),
+                descriptor,
+            },
+            "collect()` and a `FlightWriter",
+        )
+    }
+
+    /// Stream a query result into a custom streaming [`Sink`], such as a message queue. The
+    /// sink can apply backpressure by blocking in [`Sink::sink`]. This methods will return an
+    /// error if the query cannot be completely done in a streaming fashion.
+    #[cfg(feature = "streaming")]
+    pub fn sink_custom(self, sink: impl Sink + 'static) -> PolarsResult<()> {
+        let sink: Box<dyn Sink> = Box::new(sink);
+        self.sink(
+            SinkType::Custom {
+                sink: SharedSink::new(sink),
+            },
+            "collect()",
+        )
+    }
+
+    /// Stream a query result into multiple sinks in a single pass over the input, e.g. a
+    /// parquet file for the archive and an IPC file for the next stage, instead of running
+    /// the query once per sink. This methods will return an error if the query cannot be
+    /// completely done in a streaming fashion.
+    #[cfg(feature = "streaming")]
+    pub fn sink_multiple(self, sinks: impl IntoIterator<Item = SinkType>) -> PolarsResult<()> {
+        let sinks = sinks.into_iter().collect::<Arc<[_]>>();
+        polars_ensure!(
+            !sinks.is_empty(),
+            InvalidOperation: "`sink_multiple` needs at least one sink"
+        );
+        polars_ensure!(
+            !sinks.iter().any(|sink| matches!(sink, SinkType::Memory)),
+            InvalidOperation: "`sink_multiple` cannot sink into memory; use `collect()` instead"
+        );
+        self.sink(SinkType::Tee { sinks }, "collect()")
+    }
+
     #[cfg(any(
         feature = "ipc",
         feature = "parquet",
         feature = "cloud_write",
         feature = "csv",
         feature = "json",
+        feature = "flight",
+        feature = "streaming",
     ))]
     fn sink(mut self, payload: SinkType, msg_alternative: &str) -> Result<(), PolarsError> {
         self.opt_state |= OptFlags::STREAMING;
@@ -1719,6 +2119,41 @@ impl LazyFrame {
             .map(
                 function,
                 optimizations,
+                UdfProperties::empty(),
+                schema,
+                PlSmallStr::from_static(name.unwrap_or("ANONYMOUS UDF")),
+            )
+            .build();
+        Self::from_logical_plan(lp, opt_state)
+    }
+
+    /// Apply a function/closure once the logical plan get executed, like [`LazyFrame::map`],
+    /// but let the optimizer decide from the `properties` of the function what it can move
+    /// below it.
+    ///
+    /// Filters on the columns of the input are applied before an elementwise and pure
+    /// function, so they still reach the scans, and so are slices if the function also
+    /// maintains the order. Such a function also runs on the streaming engine. The columns
+    /// that the function reads are unknown, so projections are never pushed below it.
+    pub fn map_with_properties<F>(
+        self,
+        function: F,
+        properties: UdfProperties,
+        schema: Option<Arc<dyn UdfSchema>>,
+        name: Option<&'static str>,
+    ) -> LazyFrame
+    where
+        F: 'static + Fn(DataFrame) -> PolarsResult<DataFrame> + Send + Sync,
+    {
+        let mut optimizations = AllowedOptimizations::empty();
+        optimizations.set(OptFlags::STREAMING, properties.allows_predicate_pushdown());
+        let opt_state = self.get_opt_state();
+        let lp = self
+            .get_plan_builder()
+            .map(
+                function,
+                optimizations,
+                properties,
                 schema,
                 PlSmallStr::from_static(name.unwrap_or("ANONYMOUS UDF")),
             )
@@ -1975,6 +2410,7 @@ pub struct JoinBuilder {
     validation: JoinValidation,
     coalesce: JoinCoalesce,
     join_nulls: bool,
+    broadcast: Option<JoinSide>,
 }
 impl JoinBuilder {
     /// Create the `JoinBuilder` with the provided `LazyFrame` as the left table.
@@ -1988,6 +2424,7 @@ impl JoinBuilder {
             allow_parallel: true,
             force_parallel: false,
             join_nulls: false,
+            broadcast: None,
             suffix: None,
             validation: Default::default(),
             coalesce: Default::default(),
@@ -2071,6 +2508,16 @@ impl JoinBuilder {
         self
     }
 
+    /// Hint that the `side` input of the join is small. The streaming engine then builds the
+    /// hash table of the join from that input and shares it with all threads that stream the
+    /// other input, without partitioning it. Left joins can only broadcast their right input.
+    ///
+    /// Without a hint, inputs of which the scans know that they are small are broadcast.
+    pub fn broadcast(mut self, side: JoinSide) -> Self {
+        self.broadcast = Some(side);
+        self
+    }
+
     /// Finish builder
     pub fn finish(self) -> LazyFrame {
         let mut opt_state = self.lf.opt_state;
@@ -2101,6 +2548,7 @@ impl JoinBuilder {
                     allow_parallel: self.allow_parallel,
                     force_parallel: self.force_parallel,
                     args,
+                    broadcast: self.broadcast,
                     ..Default::default()
                 }
                 .into(),
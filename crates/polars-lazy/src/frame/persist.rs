@@ -0,0 +1,298 @@
+use std::any::Any;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use polars_core::config::verbose;
+use polars_core::prelude::*;
+use polars_io::ipc::IpcWriterOptions;
+use polars_io::parquet::write::ParquetWriteOptions;
+use polars_io::SerWriter;
+use polars_mem_engine::create_physical_plan;
+
+use crate::prelude::*;
+
+/// The number of rows that the streaming engine reads from a persisted file at a time.
+const BATCH_ROWS: usize = 1 << 17;
+
+/// The number of temporary files of this process, which makes their names unique.
+static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// The format of the file that [`LazyFrame::persist`] writes.
+#[derive(Clone, Debug)]
+pub enum PersistFormat {
+    Parquet(ParquetWriteOptions),
+    Ipc(IpcWriterOptions),
+}
+
+impl Default for PersistFormat {
+    fn default() -> Self {
+        Self::Parquet(Default::default())
+    }
+}
+
+impl PersistFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Parquet(_) => "parquet",
+            Self::Ipc(_) => "arrow",
+        }
+    }
+
+    fn file_type(&self) -> FileType {
+        match self {
+            Self::Parquet(options) => FileType::Parquet(options.clone()),
+            Self::Ipc(options) => FileType::Ipc(*options),
+        }
+    }
+
+    fn write(&self, df: &mut DataFrame, file: File) -> PolarsResult<()> {
+        match self {
+            Self::Parquet(options) => options.to_writer(file).finish(df).map(|_| ()),
+            Self::Ipc(options) => options.to_writer(file).finish(df),
+        }
+    }
+
+    fn scan(&self, path: &Path) -> PolarsResult<LazyFrame> {
+        match self {
+            Self::Parquet(_) => LazyFrame::scan_parquet(path, Default::default()),
+            Self::Ipc(_) => LazyFrame::scan_ipc(path, Default::default()),
+        }
+    }
+}
+
+/// Options of [`LazyFrame::persist`].
+#[derive(Clone, Debug, Default)]
+pub struct PersistOptions {
+    /// The file to write the result to. If `None`, the result is written to a file in the
+    /// temporary directory of the system, which is removed when the persisted LazyFrame and
+    /// all LazyFrames that are built on it are dropped.
+    pub path: Option<PathBuf>,
+    pub format: PersistFormat,
+}
+
+struct PersistScan {
+    input: LazyFrame,
+    schema: SchemaRef,
+    path: PathBuf,
+    format: PersistFormat,
+    temporary: bool,
+    written: Mutex<bool>,
+}
+
+impl PersistScan {
+    /// Write the result of the input to the file if this is the first use, and scan the file.
+    fn scan_file(&self) -> PolarsResult<LazyFrame> {
+        let mut written = self.written.lock().unwrap();
+        if !*written {
+            self.write()?;
+            *written = true;
+        }
+        drop(written);
+        self.format.scan(&self.path)
+    }
+
+    fn write(&self) -> PolarsResult<()> {
+        // Write to a temporary file first, so that an interrupted write is never read.
+        let tmp = self
+            .path
+            .with_extension(format!("{}.tmp", std::process::id()));
+
+        // Stream the result into the file, so that it doesn't have to fit in memory.
+        let mut sink = self.input.clone();
+        sink.opt_state |= OptFlags::STREAMING;
+        sink.logical_plan = DslPlan::Sink {
+            input: Arc::new(sink.logical_plan),
+            payload: SinkType::File {
+                path: Arc::new(tmp.clone()),
+                file_type: self.format.file_type(),
+            },
+        };
+        let (mut lp_arena, mut expr_arena) = sink.get_arenas();
+        let lp_top =
+            sink.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut vec![], false)?;
+
+        // The sink is replaced if the query runs on the streaming engine.
+        if matches!(lp_arena.get(lp_top), IR::Sink { .. }) {
+            if verbose() {
+                eprintln!("persist: cannot run the query in a streaming order, collecting it");
+            }
+            let mut df = self.input.clone().collect()?;
+            self.format.write(&mut df, File::create(&tmp)?)?;
+        } else {
+            let mut physical_plan = create_physical_plan(lp_top, &mut lp_arena, &expr_arena)?;
+            physical_plan.execute(&mut ExecutionState::new())?;
+        }
+        std::fs::rename(tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+impl Drop for PersistScan {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Apply the pushdowns of `scan_opts` but the slice.
+fn apply_pushdowns(mut lf: LazyFrame, scan_opts: &AnonymousScanArgs) -> LazyFrame {
+    if let Some(predicate) = &scan_opts.predicate {
+        lf = lf.filter(predicate.clone());
+    }
+    if let Some(columns) = &scan_opts.with_columns {
+        lf = lf.select(
+            columns
+                .iter()
+                .map(|name| col(name.clone()))
+                .collect::<Vec<_>>(),
+        );
+    }
+    lf
+}
+
+impl AnonymousScan for PersistScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let mut lf = apply_pushdowns(self.scan_file()?, &scan_opts);
+        if let Some(n_rows) = scan_opts.n_rows {
+            lf = lf.limit(n_rows as IdxSize);
+        }
+        lf.collect()
+    }
+
+    fn scan_batches(
+        &self,
+        scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>> {
+        let file = self.scan_file()?;
+        let height = file.clone().select([len()]).collect()?;
+        let height = height.get_columns()[0].idx()?.get(0).unwrap_or(0) as usize;
+
+        // Read slices of the file, of which the scans only read the row groups or the record
+        // batches that they need.
+        let mut offset = 0;
+        let mut remaining = scan_opts.n_rows.unwrap_or(usize::MAX);
+        Ok(Box::new(std::iter::from_fn(move || {
+            if offset >= height || remaining == 0 {
+                return None;
+            }
+            let batch = file.clone().slice(offset as i64, BATCH_ROWS as IdxSize);
+            offset += BATCH_ROWS;
+            let df = match apply_pushdowns(batch, &scan_opts).collect() {
+                Ok(df) => df.head(Some(remaining)),
+                Err(e) => return Some(Err(e)),
+            };
+            remaining -= df.height();
+            Some(Ok(df))
+        })))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_predicate_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+}
+
+impl LazyFrame {
+    fn persisted_scan(
+        mut self,
+        path: PathBuf,
+        format: PersistFormat,
+        temporary: bool,
+        written: bool,
+        name: &'static str,
+    ) -> PolarsResult<LazyFrame> {
+        let schema = self.collect_schema()?;
+        let opt_state = self.opt_state;
+        if written {
+            let file_schema = format.scan(&path)?.collect_schema()?;
+            polars_ensure!(
+                file_schema == schema,
+                SchemaMismatch: "the checkpoint at {:?} has a different schema than the query; \
+                remove the file to compute the query again", path
+            );
+        }
+
+        let lf = LazyFrame::anonymous_scan(
+            Arc::new(PersistScan {
+                input: self,
+                schema: schema.clone(),
+                path,
+                format,
+                temporary,
+                written: Mutex::new(written),
+            }),
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name,
+                ..Default::default()
+            },
+        )?;
+        Ok(lf.with_optimizations(opt_state))
+    }
+
+    /// Persist the result of this query to disk, so that the queries that are built on the
+    /// returned LazyFrame reuse it instead of computing it again.
+    ///
+    /// The result is computed and written to a Parquet or IPC file the first time that one of
+    /// these queries runs, and read from the file afterwards, with their projections, predicates
+    /// and slices pushed into the scan of the file. If the query can run on the streaming
+    /// engine, the result is streamed into the file, so it doesn't have to fit in memory. A file
+    /// at the path of the options is overwritten.
+    pub fn persist(self, options: PersistOptions) -> PolarsResult<LazyFrame> {
+        let PersistOptions { path, format } = options;
+        let (path, temporary) = match path {
+            Some(path) => (path, false),
+            None => {
+                let i = TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed);
+                let name = format!(
+                    "polars-persist-{}-{i}.{}",
+                    std::process::id(),
+                    format.extension()
+                );
+                (std::env::temp_dir().join(name), true)
+            },
+        };
+        self.persisted_scan(path, format, temporary, false, "PERSISTED SCAN")
+    }
+
+    /// Checkpoint the result of this query at `path`, so that a multi-stage pipeline restarts
+    /// from it instead of computing the expensive or flaky stages before it again.
+    ///
+    /// Like [`LazyFrame::persist`], the result is written the first time that a query that is
+    /// built on the returned LazyFrame runs, but a checkpoint that was written before, e.g. by
+    /// a run that failed afterwards or by an earlier process, is read instead of computed. The
+    /// file is only renamed to `path` once it is complete, so an interrupted run never leaves a
+    /// partial checkpoint. The schema of an existing checkpoint must match the query; remove the
+    /// file to compute the query again.
+    pub fn checkpoint(
+        self,
+        path: impl AsRef<Path>,
+        format: PersistFormat,
+    ) -> PolarsResult<LazyFrame> {
+        let path = path.as_ref().to_path_buf();
+        let written = path.exists();
+        self.persisted_scan(path, format, false, written, "CHECKPOINT SCAN")
+    }
+}
@@ -0,0 +1,108 @@
+use polars_core::chunked_array::cast::CastOptions;
+use polars_core::prelude::*;
+use polars_expr::state::ExecutionState;
+use polars_mem_engine::create_physical_plan;
+use polars_plan::plans::{AExpr, LiteralValue};
+
+use crate::prelude::*;
+
+/// A query that is optimized once and executed many times, with the [`param`] placeholders of
+/// its expressions bound to different values every time.
+pub struct PreparedQuery {
+    plan: IRPlan,
+    /// The nodes of the placeholders in the expression arena of the plan.
+    params: Vec<(Node, PlSmallStr, DataType)>,
+}
+
+impl PreparedQuery {
+    fn new(plan: IRPlan) -> PolarsResult<Self> {
+        let mut params: Vec<(Node, PlSmallStr, DataType)> = vec![];
+        for (_, lp) in (&plan.lp_arena).iter(plan.lp_top) {
+            for e in lp.get_exprs() {
+                for (node, ae) in (&plan.expr_arena).iter(e.node()) {
+                    let AExpr::Function {
+                        function: FunctionExpr::Param { name, dtype },
+                        ..
+                    } = ae
+                    else {
+                        continue;
+                    };
+                    if params.iter().any(|(n, _, _)| *n == node) {
+                        continue;
+                    }
+                    if let Some((_, _, other)) = params.iter().find(|(_, n, _)| n == name) {
+                        polars_ensure!(
+                            other == dtype,
+                            InvalidOperation: "parameter '{}' is used with dtypes '{}' and '{}'",
+                            name, other, dtype
+                        );
+                    }
+                    params.push((node, name.clone(), dtype.clone()));
+                }
+            }
+        }
+        Ok(Self { plan, params })
+    }
+
+    /// The names and the dtypes of the parameters of the query.
+    pub fn params(&self) -> Schema {
+        self.params
+            .iter()
+            .map(|(_, name, dtype)| Field::new(name.clone(), dtype.clone()))
+            .collect()
+    }
+
+    /// Bind the parameters to `values` and execute the query. Every parameter must be bound,
+    /// and the values are cast to the dtypes of the parameters.
+    pub fn collect(&self, values: &[(&str, AnyValue)]) -> PolarsResult<DataFrame> {
+        for (name, _) in values {
+            polars_ensure!(
+                self.params.iter().any(|(_, param, _)| param == name),
+                ColumnNotFound: "the query has no parameter '{}'", name
+            );
+        }
+
+        let mut lp_arena = self.plan.lp_arena.clone();
+        let mut expr_arena = self.plan.expr_arena.clone();
+        for (node, name, dtype) in &self.params {
+            let value = values
+                .iter()
+                .find(|(param, _)| param == name)
+                .map(|(_, value)| value.clone())
+                .ok_or_else(|| polars_err!(ComputeError: "parameter '{}' is not bound", name))?;
+            let value =
+                Series::from_any_values(name.clone(), &[value], true)?.strict_cast(dtype)?;
+            let value = match LiteralValue::try_from(value.get(0)?)? {
+                // A null literal has no dtype of its own.
+                LiteralValue::Null => AExpr::Cast {
+                    expr: expr_arena.add(AExpr::Literal(LiteralValue::Null)),
+                    dtype: dtype.clone(),
+                    options: CastOptions::Strict,
+                },
+                value => AExpr::Literal(value),
+            };
+            expr_arena.replace(*node, value);
+        }
+
+        let mut physical_plan = create_physical_plan(self.plan.lp_top, &mut lp_arena, &expr_arena)?;
+        let mut state = ExecutionState::new();
+        physical_plan.execute(&mut state)
+    }
+}
+
+impl LazyFrame {
+    /// Optimize the query once into a [`PreparedQuery`], of which the [`param`] placeholders are
+    /// bound to literals when it is collected. Executing a prepared query doesn't rebuild or
+    /// optimize the plan again, but the optimizations can't use the values of the parameters,
+    /// e.g. to prune files with a hive partition filter.
+    ///
+    /// Prepared queries run on the in-memory engine.
+    pub fn prepare(self) -> PolarsResult<PreparedQuery> {
+        polars_ensure!(
+            !self.opt_state.contains(OptFlags::STREAMING)
+                && !self.opt_state.contains(OptFlags::NEW_STREAMING),
+            InvalidOperation: "prepared queries run on the in-memory engine, disable streaming"
+        );
+        PreparedQuery::new(self.to_alp_optimized()?)
+    }
+}
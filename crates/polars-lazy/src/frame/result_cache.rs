@@ -0,0 +1,216 @@
+use std::fmt::Write;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use polars_core::config::verbose;
+use polars_core::prelude::*;
+use polars_io::ipc::{IpcReader, IpcWriter};
+use polars_io::{SerReader, SerWriter};
+use polars_mem_engine::create_physical_plan;
+use polars_plan::plans::{plan_fingerprint, PlanFingerprint};
+
+use crate::prelude::*;
+
+const EXTENSION: &str = "arrow";
+
+fn to_hex(fingerprint: &PlanFingerprint) -> String {
+    fingerprint
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, b| {
+            write!(hex, "{b:02x}").unwrap();
+            hex
+        })
+}
+
+enum Storage {
+    Memory(Mutex<PlHashMap<PlanFingerprint, DataFrame>>),
+    Disk {
+        dir: PathBuf,
+        // the bytes of the files of the results that are kept, if bounded
+        max_bytes: Option<u64>,
+    },
+}
+
+/// A cache of the results of queries, for [`LazyFrame::collect_cached`].
+///
+/// The results are keyed by the fingerprints of the optimized plans, 256-bit digests of the plans
+/// and of the sizes and the modification times of the files that they scan. A query is only executed again if
+/// the query or its files changed.
+pub struct ResultCache {
+    storage: Storage,
+}
+
+impl ResultCache {
+    /// A cache that keeps the results in memory.
+    pub fn in_memory() -> Self {
+        Self {
+            storage: Storage::Memory(Default::default()),
+        }
+    }
+
+    /// A cache that writes the results to IPC files in `dir`, so that they outlive the process
+    /// and are shared by the processes that use the same build of polars.
+    pub fn on_disk(dir: impl Into<PathBuf>) -> PolarsResult<Self> {
+        Self::new_on_disk(dir.into(), None)
+    }
+
+    /// Like [`ResultCache::on_disk`], but the files of the results take at most `max_bytes`.
+    /// Inserting a result removes the least recently used results until the rest fits, so a
+    /// result that is larger than `max_bytes` is not kept.
+    pub fn on_disk_with_max_bytes(dir: impl Into<PathBuf>, max_bytes: u64) -> PolarsResult<Self> {
+        Self::new_on_disk(dir.into(), Some(max_bytes))
+    }
+
+    fn new_on_disk(dir: PathBuf, max_bytes: Option<u64>) -> PolarsResult<Self> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            storage: Storage::Disk { dir, max_bytes },
+        })
+    }
+
+    fn get(&self, fingerprint: &PlanFingerprint) -> PolarsResult<Option<DataFrame>> {
+        match &self.storage {
+            Storage::Memory(entries) => Ok(entries.lock().unwrap().get(fingerprint).cloned()),
+            Storage::Disk { dir, max_bytes } => {
+                let path = dir.join(format!("{}.{EXTENSION}", to_hex(fingerprint)));
+                match File::open(path) {
+                    Ok(file) => {
+                        // The modification time orders the results by their last use, for the
+                        // eviction.
+                        if max_bytes.is_some() {
+                            file.set_modified(SystemTime::now())?;
+                        }
+                        IpcReader::new(file).finish().map(Some)
+                    },
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(e) => Err(e.into()),
+                }
+            },
+        }
+    }
+
+    fn insert(&self, fingerprint: &PlanFingerprint, df: &DataFrame) -> PolarsResult<()> {
+        match &self.storage {
+            Storage::Memory(entries) => {
+                entries.lock().unwrap().insert(*fingerprint, df.clone());
+            },
+            Storage::Disk { dir, max_bytes } => {
+                // Write to a temporary file first, so that other processes never read a partial
+                // result.
+                let path = dir.join(format!("{}.{EXTENSION}", to_hex(fingerprint)));
+                let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+                IpcWriter::new(File::create(&tmp)?).finish(&mut df.clone())?;
+                std::fs::rename(tmp, path)?;
+                if let Some(max_bytes) = max_bytes {
+                    Self::evict(dir, *max_bytes)?;
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Remove the least recently used results from `dir` until the rest takes at most
+    /// `max_bytes`.
+    fn evict(dir: &Path, max_bytes: u64) -> PolarsResult<()> {
+        let mut entries = Self::disk_entries(dir)?
+            .filter_map(|path| {
+                // Another process may have removed the file in the meantime.
+                let metadata = std::fs::metadata(&path).ok()?;
+                Some((metadata.modified().ok()?, metadata.len(), path))
+            })
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        let mut total = entries.iter().map(|(_, len, _)| len).sum::<u64>();
+        for (_, len, path) in entries {
+            if total <= max_bytes {
+                break;
+            }
+            match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => total -= len,
+            }
+        }
+        Ok(())
+    }
+
+    fn disk_entries(dir: &Path) -> PolarsResult<impl Iterator<Item = PathBuf>> {
+        Ok(std::fs::read_dir(dir)?.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == EXTENSION).then_some(path)
+        }))
+    }
+
+    /// The number of cached results.
+    pub fn len(&self) -> PolarsResult<usize> {
+        match &self.storage {
+            Storage::Memory(entries) => Ok(entries.lock().unwrap().len()),
+            Storage::Disk { dir, .. } => Ok(Self::disk_entries(dir)?.count()),
+        }
+    }
+
+    pub fn is_empty(&self) -> PolarsResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Remove all cached results.
+    pub fn clear(&self) -> PolarsResult<()> {
+        match &self.storage {
+            Storage::Memory(entries) => entries.lock().unwrap().clear(),
+            Storage::Disk { dir, .. } => {
+                for path in Self::disk_entries(dir)? {
+                    std::fs::remove_file(path)?;
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+impl LazyFrame {
+    /// Execute all the lazy operations and collect them into a [`DataFrame`], unless `cache` has
+    /// the result of the same optimized plan over the same files already.
+    ///
+    /// Queries that read in-memory data, files in the cloud or anonymous scans, and queries that
+    /// call user defined functions may give other results for the same plan, so they are always
+    /// executed. The same holds for queries that run on the streaming engine. Failures to read or
+    /// write the cache are not errors of the query, the query is executed instead.
+    pub fn collect_cached(mut self, cache: &ResultCache) -> PolarsResult<DataFrame> {
+        let (mut lp_arena, mut expr_arena) = self.get_arenas();
+        let lp_top =
+            self.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut vec![], false)?;
+
+        let fingerprint = plan_fingerprint(lp_top, &lp_arena, &expr_arena);
+        if let Some(fingerprint) = &fingerprint {
+            match cache.get(fingerprint) {
+                Ok(Some(df)) => {
+                    if verbose() {
+                        eprintln!("result cache hit for plan {}", to_hex(fingerprint));
+                    }
+                    return Ok(df);
+                },
+                Ok(None) => {},
+                Err(e) => {
+                    if verbose() {
+                        eprintln!("reading the result cache failed: {e}");
+                    }
+                },
+            }
+        }
+
+        let mut physical_plan = create_physical_plan(lp_top, &mut lp_arena, &expr_arena)?;
+        let mut state = ExecutionState::new();
+        let df = physical_plan.execute(&mut state)?;
+
+        if let Some(fingerprint) = &fingerprint {
+            if let Err(e) = cache.insert(fingerprint, &df) {
+                if verbose() {
+                    eprintln!("writing the result cache failed: {e}");
+                }
+            }
+        }
+        Ok(df)
+    }
+}
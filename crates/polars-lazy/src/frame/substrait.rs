@@ -0,0 +1,1007 @@
+//! Reading of [Substrait](https://substrait.io) plans, in the JSON encoding of the Substrait
+//! protobuf messages, into lazy queries.
+//!
+//! The fields of Substrait relations are referred to by their position, so every relation is
+//! converted together with the names of its columns. Columns that are computed by a relation get
+//! generated names, which are replaced by the names of the root relation at the end.
+use std::path::PathBuf;
+
+use polars_core::prelude::*;
+use polars_core::error::to_compute_err;
+use polars_ops::frame::JoinCoalesce;
+use polars_utils::format_pl_smallstr;
+use serde_json::Value;
+
+use crate::prelude::*;
+
+/// A relation as a lazy query and the names of its columns, by their position.
+struct Relation {
+    lf: LazyFrame,
+    names: Vec<PlSmallStr>,
+}
+
+impl Relation {
+    fn select(self, fields: &[usize]) -> PolarsResult<Self> {
+        let names = fields
+            .iter()
+            .map(|i| field_name(&self.names, *i).cloned())
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let lf = self.lf.select(
+            names
+                .iter()
+                .map(|name| col(name.clone()))
+                .collect::<Vec<_>>(),
+        );
+        Ok(Self { lf, names })
+    }
+}
+
+fn unsupported<T>(what: &str) -> PolarsResult<T> {
+    polars_bail!(InvalidOperation: "{} in a Substrait plan is not supported", what)
+}
+
+fn field_name(names: &[PlSmallStr], i: usize) -> PolarsResult<&PlSmallStr> {
+    names.get(i).ok_or_else(
+        || polars_err!(ComputeError: "field {} of a Substrait relation out of bounds", i),
+    )
+}
+
+/// The single entry of the object `v`, which is a `oneof` of the protobuf messages.
+fn one_of(v: &Value) -> PolarsResult<(&str, &Value)> {
+    match v.as_object().map(|o| o.iter().next()) {
+        Some(Some((kind, v))) => Ok((kind.as_str(), v)),
+        _ => polars_bail!(ComputeError: "invalid Substrait plan: expected an object, got {}", v),
+    }
+}
+
+/// The integer `v`, which the JSON encoding of protobuf writes as a number or as a string.
+/// Missing fields have the default value of zero.
+fn int(v: &Value) -> PolarsResult<i64> {
+    match v {
+        Value::Null => Ok(0),
+        Value::Number(n) => n
+            .as_i64()
+            .ok_or_else(|| polars_err!(ComputeError: "invalid Substrait integer {}", n)),
+        Value::String(s) => s
+            .parse()
+            .map_err(|_| polars_err!(ComputeError: "invalid Substrait integer {}", s)),
+        v => polars_bail!(ComputeError: "invalid Substrait integer {}", v),
+    }
+}
+
+fn float(v: &Value) -> PolarsResult<f64> {
+    match v {
+        Value::Null => Ok(0.0),
+        Value::Number(n) => Ok(n.as_f64().unwrap()),
+        Value::String(s) => match s.as_str() {
+            "NaN" => Ok(f64::NAN),
+            "Infinity" => Ok(f64::INFINITY),
+            "-Infinity" => Ok(f64::NEG_INFINITY),
+            s => s
+                .parse()
+                .map_err(|_| polars_err!(ComputeError: "invalid Substrait float {}", s)),
+        },
+        v => polars_bail!(ComputeError: "invalid Substrait float {}", v),
+    }
+}
+
+fn array(v: &Value) -> &[Value] {
+    v.as_array().map(|a| a.as_slice()).unwrap_or_default()
+}
+
+#[cfg(feature = "dtype-datetime")]
+fn time_unit(precision: i64) -> PolarsResult<TimeUnit> {
+    match precision {
+        3 => Ok(TimeUnit::Milliseconds),
+        6 => Ok(TimeUnit::Microseconds),
+        9 => Ok(TimeUnit::Nanoseconds),
+        p => unsupported(&format!("a timestamp precision of {p}")),
+    }
+}
+
+/// The data type of the Substrait type `ty`, which takes the names of its nested fields from
+/// `names`.
+fn data_type<'a>(
+    ty: &Value,
+    names: &mut impl Iterator<Item = &'a Value>,
+) -> PolarsResult<DataType> {
+    let (kind, ty) = one_of(ty)?;
+    let dtype = match kind {
+        "bool" => DataType::Boolean,
+        "i8" => DataType::Int8,
+        "i16" => DataType::Int16,
+        "i32" => DataType::Int32,
+        "i64" => DataType::Int64,
+        "fp32" => DataType::Float32,
+        "fp64" => DataType::Float64,
+        "string" | "varchar" | "fixedChar" => DataType::String,
+        "binary" | "fixedBinary" => DataType::Binary,
+        #[cfg(feature = "dtype-date")]
+        "date" => DataType::Date,
+        #[cfg(feature = "dtype-datetime")]
+        "timestamp" => DataType::Datetime(TimeUnit::Microseconds, None),
+        #[cfg(feature = "dtype-datetime")]
+        "timestampTz" => DataType::Datetime(TimeUnit::Microseconds, Some("UTC".into())),
+        #[cfg(feature = "dtype-datetime")]
+        "precisionTimestamp" => DataType::Datetime(time_unit(int(&ty["precision"])?)?, None),
+        #[cfg(feature = "dtype-datetime")]
+        "precisionTimestampTz" => {
+            DataType::Datetime(time_unit(int(&ty["precision"])?)?, Some("UTC".into()))
+        },
+        #[cfg(feature = "dtype-decimal")]
+        "decimal" => DataType::Decimal(
+            Some(int(&ty["precision"])? as usize),
+            Some(int(&ty["scale"])? as usize),
+        ),
+        "list" => DataType::List(Box::new(data_type(&ty["type"], names)?)),
+        #[cfg(feature = "dtype-struct")]
+        "struct" => {
+            let fields = array(&ty["types"])
+                .iter()
+                .map(|ty| {
+                    let name = names.next().and_then(|n| n.as_str()).unwrap_or_default();
+                    Ok(Field::new(name.into(), data_type(ty, names)?))
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            DataType::Struct(fields)
+        },
+        kind => return unsupported(&format!("the type {kind}")),
+    };
+    Ok(dtype)
+}
+
+/// The fields of a `NamedStruct`, of which the names of the nested fields follow the names of
+/// their parents.
+fn named_struct(schema: &Value) -> PolarsResult<Vec<Field>> {
+    let mut names = array(&schema["names"]).iter();
+    array(&schema["struct"]["types"])
+        .iter()
+        .map(|ty| {
+            let Some(name) = names.next().and_then(|n| n.as_str()) else {
+                polars_bail!(ComputeError: "invalid Substrait schema: missing field names");
+            };
+            Ok(Field::new(name.into(), data_type(ty, &mut names)?))
+        })
+        .collect()
+}
+
+/// The number of names of the fields nested in `dtype`.
+fn n_nested_names(dtype: &DataType) -> usize {
+    match dtype {
+        #[cfg(feature = "dtype-struct")]
+        DataType::Struct(fields) => fields.iter().map(|f| 1 + n_nested_names(f.dtype())).sum(),
+        DataType::List(inner) => n_nested_names(inner),
+        _ => 0,
+    }
+}
+
+/// The literal `v` as a value of its physical type and its logical type.
+fn literal(v: &Value) -> PolarsResult<(AnyValue<'static>, DataType)> {
+    let (kind, v) = one_of(v)?;
+    let literal = match kind {
+        "boolean" => (
+            AnyValue::Boolean(v.as_bool().unwrap_or_default()),
+            DataType::Boolean,
+        ),
+        "i8" => (AnyValue::Int32(int(v)? as i32), DataType::Int8),
+        "i16" => (AnyValue::Int32(int(v)? as i32), DataType::Int16),
+        "i32" => (AnyValue::Int32(int(v)? as i32), DataType::Int32),
+        "i64" => (AnyValue::Int64(int(v)?), DataType::Int64),
+        "fp32" => (AnyValue::Float32(float(v)? as f32), DataType::Float32),
+        "fp64" => (AnyValue::Float64(float(v)?), DataType::Float64),
+        "string" | "fixedChar" => {
+            let s = v.as_str().unwrap_or_default();
+            (AnyValue::StringOwned(s.into()), DataType::String)
+        },
+        "varChar" => {
+            let s = v["value"].as_str().unwrap_or_default();
+            (AnyValue::StringOwned(s.into()), DataType::String)
+        },
+        #[cfg(feature = "dtype-date")]
+        "date" => (AnyValue::Int32(int(v)? as i32), DataType::Date),
+        #[cfg(feature = "dtype-datetime")]
+        "timestamp" | "timestampTz" | "precisionTimestamp" | "precisionTimestampTz" => {
+            let (tu, value) = match kind {
+                "timestamp" | "timestampTz" => (TimeUnit::Microseconds, int(v)?),
+                _ => (time_unit(int(&v["precision"])?)?, int(&v["value"])?),
+            };
+            let tz = kind.ends_with("Tz").then(|| "UTC".into());
+            (AnyValue::Int64(value), DataType::Datetime(tu, tz))
+        },
+        "null" => (AnyValue::Null, data_type(v, &mut std::iter::empty())?),
+        "list" => {
+            let values = array(&v["values"])
+                .iter()
+                .map(literal)
+                .collect::<PolarsResult<Vec<_>>>()?;
+            let Some((_, dtype)) = values.first() else {
+                polars_bail!(ComputeError: "invalid Substrait plan: empty list literal");
+            };
+            let dtype = dtype.clone();
+            let s = series(PlSmallStr::EMPTY, values, &dtype)?;
+            (AnyValue::List(s), DataType::List(Box::new(dtype)))
+        },
+        "emptyList" => {
+            let dtype = data_type(&v["type"], &mut std::iter::empty())?;
+            let s = Series::new_empty(PlSmallStr::EMPTY, &dtype);
+            (AnyValue::List(s), DataType::List(Box::new(dtype)))
+        },
+        kind => return unsupported(&format!("the literal {kind}")),
+    };
+    Ok(literal)
+}
+
+/// The series of the `values` of their physical types, cast to the logical type `dtype`.
+fn series(
+    name: PlSmallStr,
+    values: Vec<(AnyValue<'static>, DataType)>,
+    dtype: &DataType,
+) -> PolarsResult<Series> {
+    let values = values.into_iter().map(|(av, _)| av).collect::<Vec<_>>();
+    let strict = false;
+    Series::from_any_values(name, &values, strict)?.cast(dtype)
+}
+
+/// The join type of polars, and whether the inputs are swapped, of the Substrait join type.
+fn join_type(kind: &str) -> PolarsResult<(JoinType, bool)> {
+    let join_type = match kind {
+        "JOIN_TYPE_INNER" => (JoinType::Inner, false),
+        "JOIN_TYPE_LEFT" => (JoinType::Left, false),
+        // right joins are left joins of the swapped inputs
+        "JOIN_TYPE_RIGHT" => (JoinType::Left, true),
+        "JOIN_TYPE_OUTER" => (JoinType::Full, false),
+        "JOIN_TYPE_LEFT_SEMI" | "JOIN_TYPE_SEMI" => (JoinType::Semi, false),
+        "JOIN_TYPE_LEFT_ANTI" | "JOIN_TYPE_ANTI" => (JoinType::Anti, false),
+        "JOIN_TYPE_RIGHT_SEMI" => (JoinType::Semi, true),
+        "JOIN_TYPE_RIGHT_ANTI" => (JoinType::Anti, true),
+        kind => return unsupported(&format!("the join type {kind}")),
+    };
+    Ok(join_type)
+}
+
+/// The expression of the Substrait scalar function `name` of the `args`, of which the type of
+/// the output is `output_type`.
+fn scalar_function(
+    name: &str,
+    args: Vec<Expr>,
+    output_type: Option<DataType>,
+) -> PolarsResult<Expr> {
+    let mut args = args.into_iter();
+    let expr = match (name, args.len()) {
+        ("and", _) => args.reduce(|l, r| l.and(r)).unwrap_or(lit(true)),
+        ("or", _) => args.reduce(|l, r| l.or(r)).unwrap_or(lit(false)),
+        ("coalesce", n) if n > 0 => {
+            let mut args = args.rev();
+            let last = args.next().unwrap();
+            args.fold(last, |expr, arg| {
+                when(arg.clone().is_not_null()).then(arg).otherwise(expr)
+            })
+        },
+        (_, 1) => {
+            let arg = args.next().unwrap();
+            match name {
+                "not" => arg.not(),
+                "is_null" => arg.is_null(),
+                "is_not_null" => arg.is_not_null(),
+                "is_nan" => arg.is_nan(),
+                "is_finite" => arg.is_finite(),
+                "is_infinite" => arg.is_infinite(),
+                "negate" => -arg,
+                #[cfg(feature = "abs")]
+                "abs" => arg.abs(),
+                name => return unsupported(&format!("the function {name}")),
+            }
+        },
+        (_, 2) => {
+            let (l, r) = (args.next().unwrap(), args.next().unwrap());
+            match name {
+                "equal" => l.eq(r),
+                "not_equal" => l.neq(r),
+                "lt" => l.lt(r),
+                "gt" => l.gt(r),
+                "lte" => l.lt_eq(r),
+                "gte" => l.gt_eq(r),
+                "is_not_distinct_from" => l.eq_missing(r),
+                "is_distinct_from" => l.neq_missing(r),
+                "xor" => l.xor(r),
+                "add" => l + r,
+                "subtract" => l - r,
+                "multiply" => l * r,
+                "divide" => match output_type {
+                    // the division of integers truncates
+                    Some(dtype) if dtype.is_integer() => {
+                        (l.cast(DataType::Float64) / r.cast(DataType::Float64)).strict_cast(dtype)
+                    },
+                    _ => l / r,
+                },
+                name => return unsupported(&format!("the function {name}")),
+            }
+        },
+        (name, _) => return unsupported(&format!("the function {name}")),
+    };
+    Ok(expr)
+}
+
+struct Reader<'a> {
+    tables: &'a PlHashMap<String, LazyFrame>,
+    // the names of the extension functions by their anchors, without their signatures
+    functions: PlHashMap<i64, &'a str>,
+    n_generated_names: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn generate_name(&mut self) -> PlSmallStr {
+        self.n_generated_names += 1;
+        format_pl_smallstr!("__substrait_{}", self.n_generated_names)
+    }
+
+    fn function(&self, reference: &Value) -> PolarsResult<&'a str> {
+        let anchor = int(reference)?;
+        self.functions.get(&anchor).copied().ok_or_else(
+            || polars_err!(ComputeError: "invalid Substrait plan: undeclared function {}", anchor),
+        )
+    }
+
+    fn args(&mut self, f: &Value, names: &[PlSmallStr]) -> PolarsResult<Vec<Expr>> {
+        array(&f["arguments"])
+            .iter()
+            .map(|arg| match arg.get("value") {
+                Some(value) => self.expr(value, names),
+                None => unsupported("a function argument that is not a value"),
+            })
+            .collect()
+    }
+
+    /// The expression `e` over the fields `names`.
+    fn expr(&mut self, e: &Value, names: &[PlSmallStr]) -> PolarsResult<Expr> {
+        let (kind, e) = one_of(e)?;
+        let expr = match kind {
+            "selection" => {
+                let reference = &e["directReference"]["structField"];
+                if reference.is_null() || !reference["child"].is_null() {
+                    return unsupported("a reference to a nested field");
+                }
+                col(field_name(names, int(&reference["field"])? as usize)?.clone())
+            },
+            "literal" => {
+                let (av, dtype) = literal(e)?;
+                let physical = av.dtype();
+                let expr = match av {
+                    AnyValue::Null => return Ok(Expr::Literal(LiteralValue::Null).cast(dtype)),
+                    AnyValue::Boolean(v) => lit(v),
+                    AnyValue::Int32(v) => lit(v),
+                    AnyValue::Int64(v) => lit(v),
+                    AnyValue::Float32(v) => lit(v),
+                    AnyValue::Float64(v) => lit(v),
+                    AnyValue::StringOwned(v) => lit(v.as_str()),
+                    _ => return unsupported(&format!("a literal of {dtype}")),
+                };
+                // the physical values are cast to their logical types
+                if physical == dtype {
+                    expr
+                } else {
+                    expr.strict_cast(dtype)
+                }
+            },
+            "scalarFunction" => {
+                let name = self.function(&e["functionReference"])?;
+                let args = self.args(e, names)?;
+                let output_type = match e.get("outputType") {
+                    Some(ty) => Some(data_type(ty, &mut std::iter::empty())?),
+                    None => None,
+                };
+                scalar_function(name, args, output_type)?
+            },
+            "cast" => {
+                let dtype = data_type(&e["type"], &mut std::iter::empty())?;
+                let input = self.expr(&e["input"], names)?;
+                match e["failureBehavior"].as_str() {
+                    Some("FAILURE_BEHAVIOR_RETURN_NULL") => input.cast(dtype),
+                    _ => input.strict_cast(dtype),
+                }
+            },
+            "ifThen" => {
+                let mut expr = match e.get("else") {
+                    Some(e) => self.expr(e, names)?,
+                    None => Expr::Literal(LiteralValue::Null),
+                };
+                for clause in array(&e["ifs"]).iter().rev() {
+                    let predicate = self.expr(&clause["if"], names)?;
+                    let then = self.expr(&clause["then"], names)?;
+                    expr = when(predicate).then(then).otherwise(expr);
+                }
+                expr
+            },
+            kind => return unsupported(&format!("the expression {kind}")),
+        };
+        Ok(expr)
+    }
+
+    /// The aggregation of the `measure` of an aggregate relation over the fields `names`.
+    fn measure(&mut self, measure: &Value, names: &[PlSmallStr]) -> PolarsResult<Expr> {
+        let filter = match measure.get("filter") {
+            Some(filter) => Some(self.expr(filter, names)?),
+            None => None,
+        };
+        let measure = &measure["measure"];
+        let name = self.function(&measure["functionReference"])?;
+        let mut args = self.args(measure, names)?;
+        if let Some(filter) = &filter {
+            args = args
+                .into_iter()
+                .map(|arg| arg.filter(filter.clone()))
+                .collect();
+        }
+        let distinct = measure["invocation"].as_str() == Some("AGGREGATION_INVOCATION_DISTINCT");
+        // the sample distribution is the default of the statistical functions
+        let ddof = array(&measure["options"])
+            .iter()
+            .find(|option| option["name"].as_str() == Some("distribution"))
+            .map_or(1, |option| {
+                match array(&option["preference"])
+                    .first()
+                    .and_then(|p| p.as_str())
+                {
+                    Some("POPULATION") => 0,
+                    _ => 1,
+                }
+            });
+
+        let expr = match (name, args.as_slice()) {
+            ("count", []) => match filter {
+                Some(filter) => filter.sum(),
+                None => len(),
+            },
+            ("count", [arg]) if distinct => arg.clone().drop_nulls().n_unique(),
+            ("count", [arg]) => arg.clone().count(),
+            (_, _) if distinct => return unsupported(&format!("a distinct {name}")),
+            ("sum", [arg]) => arg.clone().sum(),
+            ("min", [arg]) => arg.clone().min(),
+            ("max", [arg]) => arg.clone().max(),
+            ("avg", [arg]) => arg.clone().mean(),
+            ("median", [arg]) => arg.clone().median(),
+            ("any_value", [arg]) => arg.clone().first(),
+            ("std_dev", [arg]) => arg.clone().std(ddof),
+            ("variance", [arg]) => arg.clone().var(ddof),
+            (name, _) => return unsupported(&format!("the aggregate function {name}")),
+        };
+        Ok(expr)
+    }
+
+    /// The equalities of the fields of the left and the right input of a join in its
+    /// `condition`, and the remaining conditions.
+    fn join_keys(
+        &mut self,
+        condition: &Value,
+        n_left: usize,
+        keys: &mut Vec<(usize, usize, bool)>,
+        residual: &mut Vec<Expr>,
+        names: &[PlSmallStr],
+    ) -> PolarsResult<()> {
+        let field = |arg: &Value| -> Option<usize> {
+            let reference = &arg["value"]["selection"]["directReference"]["structField"];
+            if reference.is_null() || !reference["child"].is_null() {
+                return None;
+            }
+            int(&reference["field"]).ok().map(|i| i as usize)
+        };
+        if let Some(f) = condition.get("scalarFunction") {
+            let name = self.function(&f["functionReference"])?;
+            let args = array(&f["arguments"]);
+            match (name, args) {
+                ("and", args) => {
+                    for arg in args {
+                        self.join_keys(&arg["value"], n_left, keys, residual, names)?;
+                    }
+                    return Ok(());
+                },
+                ("equal" | "is_not_distinct_from", [l, r]) => {
+                    let nulls_equal = name == "is_not_distinct_from";
+                    match (field(l), field(r)) {
+                        (Some(l), Some(r)) if l < n_left && r >= n_left => {
+                            keys.push((l, r - n_left, nulls_equal));
+                            return Ok(());
+                        },
+                        (Some(l), Some(r)) if r < n_left && l >= n_left => {
+                            keys.push((r, l - n_left, nulls_equal));
+                            return Ok(());
+                        },
+                        _ => {},
+                    }
+                },
+                _ => {},
+            }
+        }
+        residual.push(self.expr(condition, names)?);
+        Ok(())
+    }
+
+    fn read(&mut self, read: &Value) -> PolarsResult<Relation> {
+        let schema = named_struct(&read["baseSchema"])?;
+        let names = schema.iter().map(|f| f.name().clone()).collect::<Vec<_>>();
+
+        let lf = if let Some(table) = read.get("namedTable") {
+            let name = array(&table["names"])
+                .iter()
+                .filter_map(|n| n.as_str())
+                .collect::<Vec<_>>()
+                .join(".");
+            match self.tables.get(&name) {
+                Some(lf) => lf.clone(),
+                None => {
+                    polars_bail!(ComputeError: "the table {} of the Substrait plan is not given", name)
+                },
+            }
+        } else if let Some(table) = read.get("virtualTable") {
+            let rows = if let Some(values) = table.get("values") {
+                array(values)
+                    .iter()
+                    .map(|row| {
+                        array(&row["fields"])
+                            .iter()
+                            .map(literal)
+                            .collect::<PolarsResult<_>>()
+                    })
+                    .collect::<PolarsResult<Vec<Vec<_>>>>()?
+            } else {
+                array(&table["expressions"])
+                    .iter()
+                    .map(|row| {
+                        array(&row["fields"])
+                            .iter()
+                            .map(|e| match e.get("literal") {
+                                Some(e) => literal(e),
+                                None => unsupported("a virtual table of expressions"),
+                            })
+                            .collect::<PolarsResult<_>>()
+                    })
+                    .collect::<PolarsResult<Vec<Vec<_>>>>()?
+            };
+            let mut columns = vec![vec![]; schema.len()];
+            for row in rows {
+                polars_ensure!(
+                    row.len() == schema.len(),
+                    ComputeError: "invalid Substrait plan: a row of a virtual table has {} fields, expected {}",
+                    row.len(), schema.len()
+                );
+                for (column, value) in columns.iter_mut().zip(row) {
+                    column.push(value);
+                }
+            }
+            let columns = columns
+                .into_iter()
+                .zip(&schema)
+                .map(|(values, field)| series(field.name().clone(), values, field.dtype()))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            DataFrame::new(columns)?.lazy()
+        } else if let Some(files) = read.get("localFiles") {
+            self.local_files(files, &schema)?
+        } else {
+            return unsupported("a read of an extension table");
+        };
+        let mut rel = Relation {
+            lf: lf.select(
+                names
+                    .iter()
+                    .map(|name| col(name.clone()))
+                    .collect::<Vec<_>>(),
+            ),
+            names,
+        };
+
+        if let Some(filter) = read.get("filter") {
+            let predicate = self.expr(filter, &rel.names)?;
+            rel.lf = rel.lf.filter(predicate);
+        }
+        if let Some(projection) = read.get("projection") {
+            let fields = array(&projection["select"]["structItems"])
+                .iter()
+                .map(|item| {
+                    if !item["child"].is_null() {
+                        return unsupported("a projection of a nested field");
+                    }
+                    Ok(int(&item["field"])? as usize)
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            rel = rel.select(&fields)?;
+        }
+        Ok(rel)
+    }
+
+    fn local_files(&mut self, files: &Value, schema: &[Field]) -> PolarsResult<LazyFrame> {
+        let mut format = None;
+        let mut paths = vec![];
+        for item in array(&files["items"]) {
+            if int(&item["start"])? != 0 || item.get("length").is_some() {
+                return unsupported("a read of a part of a file");
+            }
+            let Some(uri) = ["uriPath", "uriPathGlob", "uriFile", "uriFolder"]
+                .iter()
+                .find_map(|key| item[*key].as_str())
+            else {
+                polars_bail!(ComputeError: "invalid Substrait plan: a file without a uri");
+            };
+            let path = uri.strip_prefix("file://").unwrap_or(uri);
+            paths.push(PathBuf::from(path));
+
+            let item_format = ["parquet", "arrow", "text"]
+                .into_iter()
+                .find(|key| item.get(*key).is_some());
+            let Some(item_format) = item_format else {
+                return unsupported(&format!("the file format of {uri}"));
+            };
+            if format.is_some_and(|(f, _)| f != item_format) {
+                return unsupported("a read of files of different formats");
+            }
+            format = Some((item_format, &item[item_format]));
+        }
+        let Some((format, options)) = format else {
+            polars_bail!(ComputeError: "invalid Substrait plan: a read of no files");
+        };
+        let paths: Arc<[PathBuf]> = paths.into();
+
+        match format {
+            #[cfg(feature = "parquet")]
+            "parquet" => LazyFrame::scan_parquet_files(paths, ScanArgsParquet::default()),
+            #[cfg(feature = "ipc")]
+            "arrow" => LazyFrame::scan_ipc_files(paths, ScanArgsIpc::default()),
+            #[cfg(feature = "csv")]
+            "text" => {
+                let separator = options["fieldDelimiter"].as_str().unwrap_or(",");
+                let header_lines = int(&options["headerLinesToSkip"])? as usize;
+                let quote_char = options["quote"].as_str().and_then(|q| q.bytes().next());
+                let schema = Schema::from_iter(schema.iter().cloned());
+                LazyCsvReader::new_paths(paths)
+                    .with_separator(polars_io::csv::read::Separator::try_new_multi(
+                        separator.as_bytes(),
+                    )?)
+                    .with_has_header(header_lines > 0)
+                    .with_skip_rows(header_lines.saturating_sub(1))
+                    .with_quote_char(quote_char)
+                    .with_schema(Some(Arc::new(schema)))
+                    .finish()
+            },
+            format => {
+                let _ = (paths, options, schema);
+                unsupported(&format!("a read of {format} files"))
+            },
+        }
+    }
+
+    fn project(&mut self, project: &Value, input: Relation) -> PolarsResult<Relation> {
+        let mut names = input.names;
+        let mut exprs = vec![];
+        for e in array(&project["expressions"]) {
+            let name = self.generate_name();
+            exprs.push(self.expr(e, &names[..])?.alias(name.clone()));
+            names.push(name);
+        }
+        Ok(Relation {
+            lf: input.lf.with_columns(exprs),
+            names,
+        })
+    }
+
+    fn aggregate(&mut self, aggregate: &Value, input: Relation) -> PolarsResult<Relation> {
+        let groupings = array(&aggregate["groupings"]);
+        if groupings.len() > 1 {
+            return unsupported("an aggregate of multiple grouping sets");
+        }
+        let mut names = vec![];
+        let mut keys = vec![];
+        let grouping_expressions = groupings
+            .first()
+            .map(|g| array(&g["groupingExpressions"]))
+            .unwrap_or_default();
+        for e in grouping_expressions {
+            let key = self.expr(e, &input.names)?;
+            let key = match key {
+                Expr::Column(name) if !names.contains(&name) => {
+                    names.push(name.clone());
+                    Expr::Column(name)
+                },
+                key => {
+                    let name = self.generate_name();
+                    names.push(name.clone());
+                    key.alias(name)
+                },
+            };
+            keys.push(key);
+        }
+        let mut aggs = vec![];
+        for measure in array(&aggregate["measures"]) {
+            let name = self.generate_name();
+            aggs.push(self.measure(measure, &input.names)?.alias(name.clone()));
+            names.push(name);
+        }
+        let lf = if keys.is_empty() {
+            input.lf.select(aggs)
+        } else {
+            input.lf.group_by(keys).agg(aggs)
+        };
+        Ok(Relation { lf, names })
+    }
+
+    /// Rename the columns of the `right` input of a join that clash with the `left` names, as the
+    /// columns of polars joins are selected by name.
+    fn rename_clashing(&mut self, left: &[PlSmallStr], right: &mut Relation) {
+        if !right.names.iter().any(|name| left.contains(name)) {
+            return;
+        }
+        let mut exprs = vec![];
+        for name in right.names.iter_mut() {
+            if left.contains(name) {
+                let new = self.generate_name();
+                exprs.push(col(name.clone()).alias(new.clone()));
+                *name = new;
+            } else {
+                exprs.push(col(name.clone()));
+            }
+        }
+        right.lf = std::mem::take(&mut right.lf).select(exprs);
+    }
+
+    fn join(&mut self, join: &Value) -> PolarsResult<Relation> {
+        let left = self.rel(&join["left"])?;
+        let mut right = self.rel(&join["right"])?;
+        let (how, swapped) = join_type(join["type"].as_str().unwrap_or_default())?;
+
+        self.rename_clashing(&left.names, &mut right);
+        let names = left
+            .names
+            .iter()
+            .chain(&right.names)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut keys = vec![];
+        let mut residual = vec![];
+        if let Some(condition) = join.get("expression") {
+            self.join_keys(
+                condition,
+                left.names.len(),
+                &mut keys,
+                &mut residual,
+                &names,
+            )?;
+        }
+        if !residual.is_empty() && !matches!((&how, swapped), (JoinType::Inner, false)) {
+            return unsupported("a join condition that is not an equality of fields");
+        }
+        let join_nulls = keys.first().is_some_and(|(_, _, nulls_equal)| *nulls_equal);
+        if keys
+            .iter()
+            .any(|(_, _, nulls_equal)| *nulls_equal != join_nulls)
+        {
+            return unsupported("a join on both equal and not distinct fields");
+        }
+        let left_on = keys
+            .iter()
+            .map(|(l, _, _)| col(left.names[*l].clone()))
+            .collect::<Vec<_>>();
+        let right_on = keys
+            .iter()
+            .map(|(_, r, _)| Ok(col(field_name(&right.names, *r)?.clone())))
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let mut lf = if keys.is_empty() && matches!(how, JoinType::Inner) {
+            left.lf.cross_join(right.lf, None)
+        } else {
+            let mut args = JoinArgs::new(how.clone()).with_coalesce(JoinCoalesce::KeepColumns);
+            args.join_nulls = join_nulls;
+            if swapped {
+                right.lf.join(left.lf, right_on, left_on, args)
+            } else {
+                left.lf.join(right.lf, left_on, right_on, args)
+            }
+        };
+        for predicate in residual {
+            lf = lf.filter(predicate);
+        }
+
+        let names = match (how, swapped) {
+            (JoinType::Semi | JoinType::Anti, false) => left.names,
+            (JoinType::Semi | JoinType::Anti, true) => right.names,
+            (_, true) => {
+                // the columns of the left input come first
+                let exprs = names
+                    .iter()
+                    .map(|name| col(name.clone()))
+                    .collect::<Vec<_>>();
+                lf = lf.select(exprs);
+                names
+            },
+            _ => names,
+        };
+        let mut rel = Relation { lf, names };
+        if let Some(filter) = join.get("postJoinFilter") {
+            let predicate = self.expr(filter, &rel.names)?;
+            rel.lf = rel.lf.filter(predicate);
+        }
+        Ok(rel)
+    }
+
+    fn set(&mut self, set: &Value) -> PolarsResult<Relation> {
+        let mut inputs = array(&set["inputs"])
+            .iter()
+            .map(|input| self.rel(input))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let Some(first) = inputs.first() else {
+            polars_bail!(ComputeError: "invalid Substrait plan: a set of no inputs");
+        };
+        // the inputs are concatenated by name
+        let names = first.names.clone();
+        for input in inputs.iter_mut().skip(1) {
+            if input.names != names {
+                let exprs = input
+                    .names
+                    .iter()
+                    .zip(&names)
+                    .map(|(name, new)| col(name.clone()).alias(new.clone()))
+                    .collect::<Vec<_>>();
+                input.lf = std::mem::take(&mut input.lf).select(exprs);
+            }
+        }
+        let lfs = inputs.into_iter().map(|input| input.lf).collect::<Vec<_>>();
+        let lf = concat(lfs, UnionArgs::default())?;
+        let lf = match set["op"].as_str() {
+            Some("SET_OP_UNION_ALL") => lf,
+            Some("SET_OP_UNION_DISTINCT") => lf.unique(None, UniqueKeepStrategy::Any),
+            op => return unsupported(&format!("the set operation {}", op.unwrap_or_default())),
+        };
+        Ok(Relation { lf, names })
+    }
+
+    fn rel(&mut self, rel: &Value) -> PolarsResult<Relation> {
+        let (kind, r) = one_of(rel)?;
+        let mut rel = match kind {
+            "read" => self.read(r)?,
+            "filter" => {
+                let mut input = self.rel(&r["input"])?;
+                let predicate = self.expr(&r["condition"], &input.names)?;
+                input.lf = input.lf.filter(predicate);
+                input
+            },
+            "fetch" => {
+                let mut input = self.rel(&r["input"])?;
+                let offset = int(&r["offset"])?;
+                // a missing or negative count fetches all rows
+                let len = match r.get("count").map(int).transpose()? {
+                    Some(count) if count >= 0 => count as IdxSize,
+                    _ => IdxSize::MAX,
+                };
+                input.lf = input.lf.slice(offset, len);
+                input
+            },
+            "project" => {
+                let input = self.rel(&r["input"])?;
+                self.project(r, input)?
+            },
+            "sort" => {
+                let mut input = self.rel(&r["input"])?;
+                let mut by = vec![];
+                let mut descending = vec![];
+                let mut nulls_last = vec![];
+                for sort in array(&r["sorts"]) {
+                    by.push(self.expr(&sort["expr"], &input.names)?);
+                    let (d, n) = match sort["direction"].as_str() {
+                        Some("SORT_DIRECTION_ASC_NULLS_FIRST") => (false, false),
+                        Some("SORT_DIRECTION_ASC_NULLS_LAST") => (false, true),
+                        Some("SORT_DIRECTION_DESC_NULLS_FIRST") => (true, false),
+                        Some("SORT_DIRECTION_DESC_NULLS_LAST") => (true, true),
+                        direction => {
+                            return unsupported(&format!(
+                                "the sort direction {}",
+                                direction.unwrap_or_default()
+                            ))
+                        },
+                    };
+                    descending.push(d);
+                    nulls_last.push(n);
+                }
+                let options = SortMultipleOptions::default()
+                    .with_order_descending_multi(descending)
+                    .with_nulls_last_multi(nulls_last)
+                    .with_maintain_order(true);
+                input.lf = input.lf.sort_by_exprs(by, options);
+                input
+            },
+            "aggregate" => {
+                let input = self.rel(&r["input"])?;
+                self.aggregate(r, input)?
+            },
+            "join" => self.join(r)?,
+            "cross" => {
+                let left = self.rel(&r["left"])?;
+                let mut right = self.rel(&r["right"])?;
+                self.rename_clashing(&left.names, &mut right);
+                let names = left.names.into_iter().chain(right.names).collect();
+                Relation {
+                    lf: left.lf.cross_join(right.lf, None),
+                    names,
+                }
+            },
+            "set" => self.set(r)?,
+            kind => return unsupported(&format!("the relation {kind}")),
+        };
+
+        if let Some(emit) = r["common"].get("emit") {
+            let fields = array(&emit["outputMapping"])
+                .iter()
+                .map(|i| Ok(int(i)? as usize))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            rel = rel.select(&fields)?;
+        }
+        Ok(rel)
+    }
+}
+
+impl LazyFrame {
+    /// Read a [Substrait](https://substrait.io) plan, in the JSON encoding of its protobuf
+    /// messages, into a lazy query, so that plans of other tools can be executed by polars.
+    ///
+    /// The named tables that the plan reads are looked up in `tables` by their names joined
+    /// with `.`. Returns `Err` if the plan has relations or functions that polars doesn't
+    /// support.
+    pub fn from_substrait(
+        plan: &str,
+        tables: &PlHashMap<String, LazyFrame>,
+    ) -> PolarsResult<LazyFrame> {
+        let plan: Value = serde_json::from_str(plan).map_err(to_compute_err)?;
+        let functions = array(&plan["extensions"])
+            .iter()
+            .filter_map(|e| {
+                let f = e.get("extensionFunction")?;
+                let name = f["name"].as_str()?;
+                // the compound name has the signature of the arguments
+                let name = name.split(':').next().unwrap();
+                Some((int(&f["functionAnchor"]).ok()?, name))
+            })
+            .collect();
+        let mut reader = Reader {
+            tables,
+            functions,
+            n_generated_names: 0,
+        };
+
+        let relations = array(&plan["relations"]);
+        let (rel, root_names) = match relations.iter().find_map(|r| r.get("root")) {
+            Some(root) => (&root["input"], array(&root["names"])),
+            None => match relations.first().and_then(|r| r.get("rel")) {
+                Some(rel) => (rel, &[][..]),
+                None => polars_bail!(ComputeError: "invalid Substrait plan: no relations"),
+            },
+        };
+        let mut rel = reader.rel(rel)?;
+        if root_names.is_empty() {
+            return Ok(rel.lf);
+        }
+
+        // the names of the root also name the nested fields
+        let mut names = vec![];
+        if root_names.len() == rel.names.len() {
+            names.extend(root_names.iter().map(|n| n.as_str().unwrap_or_default()));
+        } else {
+            let schema = rel.lf.collect_schema()?;
+            let mut root_names = root_names.iter();
+            for (_, dtype) in schema.iter() {
+                names.push(
+                    root_names
+                        .next()
+                        .and_then(|n| n.as_str())
+                        .unwrap_or_default(),
+                );
+                for _ in 0..n_nested_names(dtype) {
+                    root_names.next();
+                }
+            }
+        }
+        let exprs = rel
+            .names
+            .iter()
+            .zip(names)
+            .map(|(name, new)| col(name.clone()).alias(new))
+            .collect::<Vec<_>>();
+        Ok(rel.lf.select(exprs))
+    }
+}
@@ -1,4 +1,5 @@
 use polars_core::chunked_array::ops::SortMultipleOptions;
+use polars_core::prelude::DataType;
 use polars_ops::prelude::*;
 use polars_plan::plans::expr_ir::ExprIR;
 use polars_plan::prelude::*;
@@ -17,6 +18,29 @@ pub(super) fn is_streamable_sort(
     }
 }
 
+/// Check if the windows of a projection can be computed by the streaming engine, see
+/// [`polars_pipe::pipeline::window_aggregations`].
+pub(super) fn streamable_windows(
+    exprs: &[ExprIR],
+    input: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &mut Arena<AExpr>,
+) -> bool {
+    let input_schema = lp_arena.get(input).schema(lp_arena);
+    polars_pipe::pipeline::window_aggregations(exprs, expr_arena, &input_schema).is_some_and(
+        |windows| {
+            // the partition columns are row encoded and the aggregates may be spilled
+            windows.schema().iter_values().all(|dt| match dt {
+                #[cfg(feature = "dtype-categorical")]
+                DataType::Categorical(_, _) => polars_core::using_string_cache(),
+                #[cfg(feature = "dtype-decimal")]
+                DataType::Decimal(_, _) => false,
+                dt => !dt.is_nested() && !dt.is_object() && dt.is_known(),
+            })
+        },
+    )
+}
+
 /// check if all expressions are a simple column projection
 pub(super) fn all_column(exprs: &[ExprIR], expr_arena: &Arena<AExpr>) -> bool {
     exprs
@@ -37,7 +61,108 @@ pub(super) fn streamable_join(args: &JoinArgs) -> bool {
             )
         },
         JoinType::Full { .. } => true,
+        #[cfg(feature = "semi_anti_join")]
+        JoinType::Semi | JoinType::Anti => true,
         _ => false,
     };
     supported && !args.validation.needs_checks()
 }
+
+/// The streaming IPC source memory maps the files, so IPC files in the cloud are only read by
+/// the in-memory engine.
+#[allow(unused_variables)]
+pub(super) fn is_cloud_ipc_scan(scan_type: &FileScan, sources: &ScanSources) -> bool {
+    #[cfg(feature = "ipc")]
+    if matches!(scan_type, FileScan::Ipc { .. }) {
+        return sources.is_cloud_url();
+    }
+    false
+}
+
+/// Check if the group_by at `node` can be computed by the hash aggregations of the streaming
+/// engine.
+pub(super) fn streamable_group_by(
+    node: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    #[allow(unused_variables)]
+    let IR::GroupBy {
+        input,
+        keys,
+        aggs,
+        maintain_order,
+        apply: None,
+        schema: output_schema,
+        options,
+        ..
+    } = lp_arena.get(node)
+    else {
+        return false;
+    };
+
+    #[cfg(feature = "dtype-categorical")]
+    let string_cache = polars_core::using_string_cache();
+    #[cfg(not(feature = "dtype-categorical"))]
+    let string_cache = true;
+
+    #[allow(unused_variables)]
+    fn allowed_dtype(dt: &DataType, string_cache: bool) -> bool {
+        match dt {
+            #[cfg(feature = "object")]
+            DataType::Object(_, _) => false,
+            #[cfg(feature = "dtype-categorical")]
+            DataType::Categorical(_, _) => string_cache,
+            DataType::List(inner) => allowed_dtype(inner, string_cache),
+            #[cfg(feature = "dtype-struct")]
+            DataType::Struct(fields) => fields
+                .iter()
+                .all(|fld| allowed_dtype(fld.dtype(), string_cache)),
+            // We need to be able to sink to disk or produce the aggregate return dtype.
+            DataType::Unknown(_) => false,
+            #[cfg(feature = "dtype-decimal")]
+            DataType::Decimal(_, _) => false,
+            _ => true,
+        }
+    }
+    let input_schema = lp_arena.get(*input).schema(lp_arena);
+
+    // The temporal group_bys maintain the order of the windows.
+    #[cfg(feature = "dynamic_group_by")]
+    {
+        if options.rolling.is_some() || options.dynamic.is_some() {
+            if !polars_pipe::pipeline::can_stream_temporal_group_by(keys, options) {
+                return false;
+            }
+        } else if *maintain_order {
+            return false;
+        }
+    }
+    #[cfg(not(feature = "dynamic_group_by"))]
+    if *maintain_order {
+        return false;
+    }
+
+    let valid_agg = || {
+        aggs.iter().all(|e| {
+            polars_pipe::pipeline::can_convert_to_hash_agg(e.node(), expr_arena, &input_schema)
+        })
+    };
+
+    let valid_key = || {
+        keys.iter().all(|e| {
+            output_schema
+                .get(e.output_name())
+                .map(|dt| !matches!(dt, DataType::List(_)))
+                .unwrap_or(false)
+        })
+    };
+
+    let valid_types = || {
+        output_schema
+            .iter_values()
+            .all(|dt| allowed_dtype(dt, string_cache))
+    };
+
+    valid_agg() && valid_key() && valid_types()
+}
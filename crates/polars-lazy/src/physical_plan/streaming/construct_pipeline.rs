@@ -1,15 +1,17 @@
 use std::cell::RefCell;
+use std::num::NonZeroUsize;
 use std::rc::Rc;
 use std::sync::Mutex;
 
-use polars_core::config::verbose;
+use polars_core::config::{get_streaming_memory_limit, verbose};
 use polars_core::prelude::*;
 use polars_expr::{create_physical_expr, ExpressionConversionState};
 use polars_io::predicates::{PhysicalIoExpr, StatsEvaluator};
 use polars_pipe::expressions::PhysicalPipedExpr;
 use polars_pipe::operators::chunks::DataChunk;
 use polars_pipe::pipeline::{
-    create_pipeline, execute_pipeline, get_dummy_operator, get_operator, CallBacks, PipeLine,
+    create_pipeline, execute_pipeline_with_metrics, get_dummy_operator, get_filter_projection,
+    get_operator, CallBacks, MemoryAccountant, PipeLine,
 };
 use polars_plan::prelude::expr_ir::ExprIR;
 
@@ -47,7 +49,7 @@ impl PhysicalPipedExpr for Wrap {
     }
 }
 
-fn to_physical_piped_expr(
+pub(super) fn to_physical_piped_expr(
     expr: &ExprIR,
     expr_arena: &Arena<AExpr>,
     schema: Option<&SchemaRef>,
@@ -98,12 +100,19 @@ pub(super) fn construct(
     tree: Tree,
     lp_arena: &mut Arena<IR>,
     expr_arena: &mut Arena<AExpr>,
+    max_threads: Option<NonZeroUsize>,
+    memory_limit: Option<usize>,
     fmt: bool,
 ) -> PolarsResult<Option<Node>> {
     use IR::*;
 
     let mut pipelines = Vec::with_capacity(tree.len());
+    let mut layout = vec![];
     let mut callbacks = CallBacks::new();
+    // The pipelines run once, so this accounts for the memory of a single execution.
+    let accountant = Arc::new(MemoryAccountant::new(
+        memory_limit.or_else(get_streaming_memory_limit),
+    ));
 
     let is_verbose = verbose();
 
@@ -150,11 +159,13 @@ pub(super) fn construct(
 
         let mut operators = Vec::with_capacity(branch.operators_sinks.len());
         let mut operator_nodes = Vec::with_capacity(branch.operators_sinks.len());
+        // the node of the plan of every operator, for the metrics
+        let mut operator_plan_nodes = Vec::with_capacity(branch.operators_sinks.len());
 
         // iterate from leaves upwards
-        let mut iter = branch.operators_sinks.into_iter().rev();
+        let mut iter = branch.operators_sinks.into_iter().rev().peekable();
 
-        for pipeline_node in &mut iter {
+        while let Some(pipeline_node) = iter.next() {
             let operator_offset = operators.len();
             match pipeline_node {
                 PipelineNode::Sink(node) => {
@@ -168,16 +179,35 @@ pub(super) fn construct(
                 },
                 PipelineNode::Operator(node) => {
                     operator_nodes.push(node);
+                    // A filter and the projection of its output are fused.
+                    if let Some(&PipelineNode::Operator(projection)) = iter.peek() {
+                        if let Some(op) = get_filter_projection(
+                            node,
+                            projection,
+                            lp_arena,
+                            expr_arena,
+                            &to_physical_piped_expr,
+                        )? {
+                            iter.next();
+                            operator_nodes.push(projection);
+                            operator_plan_nodes.push(projection);
+                            operators.push(op);
+                            continue;
+                        }
+                    }
                     let op = get_operator(node, lp_arena, expr_arena, &to_physical_piped_expr)?;
+                    operator_plan_nodes.push(node);
                     operators.push(op);
                 },
                 PipelineNode::Union(node) => {
                     operator_nodes.push(node);
+                    operator_plan_nodes.push(node);
                     let op = get_operator(node, lp_arena, expr_arena, &to_physical_piped_expr)?;
                     operators.push(op);
                 },
                 PipelineNode::RhsJoin(node) => {
                     operator_nodes.push(node);
+                    operator_plan_nodes.push(node);
                     jit_insert_slice(node, lp_arena, &mut sink_nodes, operator_offset);
                     let op = callbacks.get(&node).unwrap().clone();
                     operators.push(Box::new(op))
@@ -185,9 +215,17 @@ pub(super) fn construct(
             }
         }
 
+        if fmt {
+            layout.push(PipelineLayout {
+                sources: branch.sources.clone(),
+                operators: operator_nodes,
+                sinks: sink_nodes.iter().map(|(_, node, _)| *node).collect(),
+            });
+        }
         let pipeline = create_pipeline(
             &branch.sources,
             operators,
+            operator_plan_nodes,
             sink_nodes,
             lp_arena,
             expr_arena,
@@ -195,7 +233,9 @@ pub(super) fn construct(
             is_verbose,
             &mut sink_cache,
             &mut callbacks,
-        )?;
+            &accountant,
+        )?
+        .with_max_threads(max_threads);
         pipelines.push(pipeline);
     }
 
@@ -228,7 +268,7 @@ pub(super) fn construct(
         .get(insertion_location)
         .schema(lp_arena)
         .into_owned();
-    let pipeline_node = get_pipeline_node(lp_arena, pipelines, schema, original_lp);
+    let pipeline_node = get_pipeline_node(lp_arena, pipelines, schema, original_lp, layout);
     lp_arena.replace(insertion_location, pipeline_node);
 
     Ok(Some(final_sink))
@@ -239,6 +279,7 @@ fn get_pipeline_node(
     mut pipelines: Vec<PipeLine>,
     schema: SchemaRef,
     original_lp: Option<IRPlan>,
+    layout: Vec<PipelineLayout>,
 ) -> IR {
     // create a dummy input as the map function will call the input
     // so we just create a scan that returns an empty df
@@ -249,6 +290,9 @@ fn get_pipeline_node(
         filter: None,
     });
 
+    let metrics = Arc::new(Mutex::new(None));
+    let last_metrics = metrics.clone();
+
     IR::MapFunction {
         function: FunctionIR::Pipeline {
             function: Arc::new(Mutex::new(move |_df: DataFrame| {
@@ -257,10 +301,15 @@ fn get_pipeline_node(
                     eprintln!("RUN STREAMING PIPELINE");
                     eprintln!("{:?}", &pipelines)
                 }
-                execute_pipeline(state, std::mem::take(&mut pipelines))
+                let (df, metrics) =
+                    execute_pipeline_with_metrics(state, std::mem::take(&mut pipelines))?;
+                *last_metrics.lock().unwrap() = Some(metrics);
+                Ok(df)
             })),
             schema,
             original: original_lp.map(Arc::new),
+            layout: layout.into(),
+            metrics,
         },
         input: dummy,
     }
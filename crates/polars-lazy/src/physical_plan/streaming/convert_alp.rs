@@ -81,6 +81,43 @@ fn insert_file_sink(mut root: Node, lp_arena: &mut Arena<IR>) -> Node {
     root
 }
 
+/// Whether `node` is a scan, or a merge of sorted scans, that is read by a single source.
+#[cfg(any(feature = "merge_sorted", feature = "asof_join"))]
+fn is_streamable_source(node: Node, lp_arena: &Arena<IR>) -> bool {
+    match lp_arena.get(node) {
+        lp @ IR::Scan { .. } => is_streamable_scan(lp),
+        IR::DataFrameScan { .. } => true,
+        #[cfg(feature = "merge_sorted")]
+        IR::MapFunction {
+            function: FunctionIR::MergeSorted { .. },
+            ..
+        } => polars_pipe::pipeline::merge_sorted_scans(node, lp_arena).is_some_and(|scans| {
+            scans
+                .iter()
+                .all(|scan| is_streamable_scan(lp_arena.get(*scan)))
+        }),
+        _ => false,
+    }
+}
+
+// Files on plain HTTP(S) servers are only read by the in-memory engine.
+fn is_streamable_scan(lp: &IR) -> bool {
+    match lp {
+        IR::Scan {
+            sources,
+            scan_type,
+            file_options: FileScanOptions { slice, .. },
+            ..
+        } => {
+            scan_type.streamable()
+                && !sources.is_http_range_url()
+                && !is_cloud_ipc_scan(scan_type, sources)
+                && slice.map(|slice| slice.0 >= 0).unwrap_or(true)
+        },
+        _ => false,
+    }
+}
+
 pub(crate) fn insert_streaming_nodes(
     root: Node,
     lp_arena: &mut Arena<IR>,
@@ -103,6 +140,20 @@ pub(crate) fn insert_streaming_nodes(
 
     scratch.clear();
 
+    // The smallest number of threads and memory limit that the hints of the plan allow.
+    let hints = (&*lp_arena)
+        .iter(root)
+        .filter_map(|(_, lp)| match lp {
+            IR::MapFunction {
+                function: FunctionIR::Hints(hints),
+                ..
+            } => Some(hints.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let max_threads = hints.iter().filter_map(|hints| hints.max_threads).min();
+    let memory_limit = hints.iter().filter_map(|hints| hints.memory_limit).min();
+
     // The pipelines always need to end in a SINK, we insert that here.
     // this allows us to split at joins/unions and share a sink
     let root = insert_file_sink(root, lp_arena);
@@ -169,6 +220,14 @@ pub(crate) fn insert_streaming_nodes(
                 state.operators_sinks.push(PipelineNode::Operator(root));
                 stack.push(StackFrame::new(*input, state, current_idx))
             },
+            // Projections that explode every column are exploded chunk by chunk.
+            Select { input, expr, .. }
+                if polars_pipe::pipeline::explode_projection(expr, expr_arena).is_some() =>
+            {
+                state.streamable = true;
+                state.operators_sinks.push(PipelineNode::Operator(root));
+                stack.push(StackFrame::new(*input, state, current_idx))
+            },
             HStack { input, exprs, .. } if all_streamable(exprs, expr_arena, Context::Default) => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Operator(root));
@@ -199,6 +258,20 @@ pub(crate) fn insert_streaming_nodes(
                 state.operators_sinks.push(PipelineNode::Operator(root));
                 stack.push(StackFrame::new(*input, state, current_idx))
             },
+            HStack { input, exprs, .. }
+                if streamable_windows(exprs, *input, lp_arena, expr_arena) =>
+            {
+                state.streamable = true;
+                state.operators_sinks.push(PipelineNode::Sink(root));
+                stack.push(StackFrame::new(*input, state, current_idx))
+            },
+            Select { input, expr, .. }
+                if streamable_windows(expr, *input, lp_arena, expr_arena) =>
+            {
+                state.streamable = true;
+                state.operators_sinks.push(PipelineNode::Sink(root));
+                stack.push(StackFrame::new(*input, state, current_idx))
+            },
             SimpleProjection { input, .. } => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Operator(root));
@@ -209,10 +282,30 @@ pub(crate) fn insert_streaming_nodes(
                 state.operators_sinks.push(PipelineNode::Sink(root));
                 stack.push(StackFrame::new(*input, state, current_idx))
             },
-            // Rechunks are ignored
+            // Merges of sorted scans are a single source.
+            #[cfg(feature = "merge_sorted")]
+            MapFunction {
+                function: FunctionIR::MergeSorted { .. },
+                ..
+            } if is_streamable_source(root, lp_arena) => {
+                state.sources.push(root);
+                pipeline_trees[current_idx].push(state);
+            },
+            // A subtree of which the hints disable streaming runs on the default engine.
+            MapFunction {
+                function: FunctionIR::Hints(hints),
+                ..
+            } if hints.streaming == Some(false) => {
+                if allow_partial {
+                    state.streamable = false;
+                } else {
+                    return Ok(false);
+                }
+            },
+            // Rechunks and hints are ignored
             MapFunction {
                 input,
-                function: FunctionIR::Rechunk,
+                function: FunctionIR::Rechunk | FunctionIR::Hints(_),
             } => {
                 state.streamable = true;
                 stack.push(StackFrame::new(*input, state, current_idx))
@@ -234,11 +327,7 @@ pub(crate) fn insert_streaming_nodes(
                     )
                 }
             },
-            Scan {
-                scan_type,
-                file_options: FileScanOptions { slice, .. },
-                ..
-            } if scan_type.streamable() && slice.map(|slice| slice.0 >= 0).unwrap_or(true) => {
+            lp @ Scan { .. } if is_streamable_scan(lp) => {
                 if state.streamable {
                     state.sources.push(root);
                     pipeline_trees[current_idx].push(state)
@@ -250,6 +339,30 @@ pub(crate) fn insert_streaming_nodes(
                     pipeline_trees[current_idx].push(state)
                 }
             },
+            // The batches of IO plugins are pulled from their generator.
+            #[cfg(feature = "python")]
+            PythonScan { options }
+                if matches!(options.python_source, PythonScanSource::IOPlugin) =>
+            {
+                if state.streamable {
+                    state.sources.push(root);
+                    pipeline_trees[current_idx].push(state)
+                }
+            },
+            // Asof joins of sorted scans are merged by a single source.
+            #[cfg(feature = "asof_join")]
+            Join { .. }
+                if polars_pipe::pipeline::asof_join_inputs(root, lp_arena).is_some_and(
+                    |inputs| {
+                        inputs
+                            .iter()
+                            .all(|input| is_streamable_source(*input, lp_arena))
+                    },
+                ) =>
+            {
+                state.sources.push(root);
+                pipeline_trees[current_idx].push(state);
+            },
             Join {
                 input_left,
                 input_right,
@@ -321,101 +434,15 @@ pub(crate) fn insert_streaming_nodes(
                     }
                 }
             },
-            Distinct { input, options }
-                if !options.maintain_order
-                    && !matches!(options.keep_strategy, UniqueKeepStrategy::None) =>
-            {
+            Distinct { input, options } if !options.maintain_order => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Sink(root));
                 stack.push(StackFrame::new(*input, state, current_idx))
             },
-            #[allow(unused_variables)]
-            lp @ GroupBy {
-                input,
-                keys,
-                aggs,
-                maintain_order: false,
-                apply: None,
-                schema: output_schema,
-                options,
-                ..
-            } => {
-                #[cfg(feature = "dtype-categorical")]
-                let string_cache = polars_core::using_string_cache();
-                #[cfg(not(feature = "dtype-categorical"))]
-                let string_cache = true;
-
-                #[allow(unused_variables)]
-                fn allowed_dtype(dt: &DataType, string_cache: bool) -> bool {
-                    match dt {
-                        #[cfg(feature = "object")]
-                        DataType::Object(_, _) => false,
-                        #[cfg(feature = "dtype-categorical")]
-                        DataType::Categorical(_, _) => string_cache,
-                        DataType::List(inner) => allowed_dtype(inner, string_cache),
-                        #[cfg(feature = "dtype-struct")]
-                        DataType::Struct(fields) => fields
-                            .iter()
-                            .all(|fld| allowed_dtype(fld.dtype(), string_cache)),
-                        // We need to be able to sink to disk or produce the aggregate return dtype.
-                        DataType::Unknown(_) => false,
-                        #[cfg(feature = "dtype-decimal")]
-                        DataType::Decimal(_, _) => false,
-                        _ => true,
-                    }
-                }
-                let input_schema = lp_arena.get(*input).schema(lp_arena);
-                #[allow(unused_mut)]
-                let mut can_stream = true;
-
-                #[cfg(feature = "dynamic_group_by")]
-                {
-                    if options.rolling.is_some() || options.dynamic.is_some() {
-                        can_stream = false
-                    }
-                }
-
-                let valid_agg = || {
-                    aggs.iter().all(|e| {
-                        polars_pipe::pipeline::can_convert_to_hash_agg(
-                            e.node(),
-                            expr_arena,
-                            &input_schema,
-                        )
-                    })
-                };
-
-                let valid_key = || {
-                    keys.iter().all(|e| {
-                        output_schema
-                            .get(e.output_name())
-                            .map(|dt| !matches!(dt, DataType::List(_)))
-                            .unwrap_or(false)
-                    })
-                };
-
-                let valid_types = || {
-                    output_schema
-                        .iter_values()
-                        .all(|dt| allowed_dtype(dt, string_cache))
-                };
-
-                if can_stream && valid_agg() && valid_key() && valid_types() {
-                    state.streamable = true;
-                    state.operators_sinks.push(PipelineNode::Sink(root));
-                    stack.push(StackFrame::new(*input, state, current_idx))
-                } else if allow_partial {
-                    process_non_streamable_node(
-                        &mut current_idx,
-                        &mut state,
-                        &mut stack,
-                        scratch,
-                        &mut pipeline_trees,
-                        lp,
-                    )
-                } else {
-                    return Ok(false);
-                }
+            GroupBy { input, .. } if streamable_group_by(root, lp_arena, expr_arena) => {
+                state.streamable = true;
+                state.operators_sinks.push(PipelineNode::Sink(root));
+                stack.push(StackFrame::new(*input, state, current_idx))
             },
             lp => {
                 if allow_partial {
@@ -437,7 +464,15 @@ pub(crate) fn insert_streaming_nodes(
     let mut inserted = false;
     for tree in pipeline_trees {
         if is_valid_tree(&tree)
-            && super::construct_pipeline::construct(tree, lp_arena, expr_arena, fmt)?.is_some()
+            && super::construct_pipeline::construct(
+                tree,
+                lp_arena,
+                expr_arena,
+                max_threads,
+                memory_limit,
+                fmt,
+            )?
+            .is_some()
         {
             inserted = true;
         }
@@ -0,0 +1,315 @@
+use std::sync::{Arc, Mutex};
+
+use polars_core::config::verbose;
+use polars_core::prelude::*;
+use polars_core::POOL;
+use polars_mem_engine::create_physical_plan;
+use polars_pipe::pipeline::{explode_projection, get_fan_out_sink};
+use rayon::prelude::*;
+
+use super::checks::*;
+use super::construct_pipeline::to_physical_piped_expr;
+use crate::prelude::*;
+
+/// The part of a query that runs in the pipeline of the shared subplan: the row-wise operators
+/// that follow the subplan and the sink that they feed.
+struct Branch {
+    operators: Vec<Node>,
+    sink: Node,
+    /// The node of which the branch computes the output, which is replaced by that output to
+    /// compute the rest of the query, or `None` if the branch computes the whole query.
+    output: Option<Node>,
+}
+
+fn is_row_wise(lp: &IR, expr_arena: &Arena<AExpr>) -> bool {
+    match lp {
+        IR::Filter { predicate, .. } => {
+            is_streamable(predicate.node(), expr_arena, Context::Default)
+        },
+        IR::Select { expr, .. } => {
+            all_streamable(expr, expr_arena, Context::Default)
+                || explode_projection(expr, expr_arena).is_some()
+        },
+        IR::HStack { exprs, .. } => all_streamable(exprs, expr_arena, Context::Default),
+        IR::SimpleProjection { .. } => true,
+        IR::MapFunction { function, .. } => function.is_streamable(),
+        _ => false,
+    }
+}
+
+fn is_sink(node: Node, lp_arena: &Arena<IR>, expr_arena: &Arena<AExpr>) -> bool {
+    match lp_arena.get(node) {
+        IR::Slice { offset, .. } => *offset >= 0,
+        IR::Sort {
+            by_column,
+            slice,
+            sort_options,
+            ..
+        } => is_streamable_sort(slice, sort_options) && all_column(by_column, expr_arena),
+        IR::Distinct { options, .. } => !options.maintain_order,
+        IR::GroupBy { .. } => streamable_group_by(node, lp_arena, expr_arena),
+        _ => false,
+    }
+}
+
+/// Find the branch of the query at `root` that streams the output of the `placeholder` of the
+/// shared subplan, which arrives with the schema of the `input` node.
+fn get_branch(
+    root: Node,
+    placeholder: Node,
+    input: Node,
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> Branch {
+    let mut parents = PlHashMap::new();
+    let mut inputs = vec![];
+    for (node, lp) in (&*lp_arena).iter(root) {
+        inputs.clear();
+        lp.copy_inputs(&mut inputs);
+        for input in &inputs {
+            parents.insert(*input, node);
+        }
+    }
+
+    // The projection and the predicate that were pushed down into the placeholder.
+    let mut operators = vec![];
+    let IR::DataFrameScan {
+        output_schema,
+        filter,
+        ..
+    } = lp_arena.get(placeholder).clone()
+    else {
+        unreachable!()
+    };
+    let mut last = input;
+    if let Some(columns) = output_schema {
+        last = lp_arena.add(IR::SimpleProjection {
+            input: last,
+            columns,
+        });
+        operators.push(last);
+    }
+    if let Some(predicate) = filter {
+        last = lp_arena.add(IR::Filter {
+            input: last,
+            predicate,
+        });
+        operators.push(last);
+    }
+
+    let mut node = placeholder;
+    while let Some(&parent) = parents.get(&node) {
+        if is_row_wise(lp_arena.get(parent), expr_arena) {
+            operators.push(parent);
+            node = parent;
+        } else if is_sink(parent, lp_arena, expr_arena) {
+            return Branch {
+                operators,
+                sink: parent,
+                output: (parent != root).then_some(parent),
+            };
+        } else {
+            break;
+        }
+    }
+    let sink = lp_arena.add(IR::Sink {
+        input: node,
+        payload: SinkType::Memory,
+    });
+    Branch {
+        operators,
+        sink,
+        output: (node != root).then_some(node),
+    }
+}
+
+/// Collect queries that run on the streaming engine and have a subplan in common, e.g. the same
+/// scans and joins followed by different filters and aggregations, by streaming the subplan
+/// once into all of them.
+///
+/// The row-wise operations of every query that follow the shared subplan run in its pipeline,
+/// together with the aggregation, sort, slice or unique that they feed, if any. The rest of the
+/// query runs on their output afterwards. Returns `None` if the queries have no subplan in
+/// common or if it can't be streamed.
+pub(crate) fn collect_all_shared_subplan(
+    lfs: &[LazyFrame],
+) -> PolarsResult<Option<Vec<DataFrame>>> {
+    let streaming = |lf: &LazyFrame| {
+        lf.opt_state.contains(OptFlags::STREAMING)
+            && !lf.opt_state.contains(OptFlags::NEW_STREAMING)
+    };
+    if lfs.len() < 2 || !lfs.iter().all(streaming) {
+        return Ok(None);
+    }
+
+    // The subplan is found before the queries are optimized, as the optimizations push other
+    // projections and predicates into it for every query.
+    let mut lp_arena = Arena::with_capacity(16);
+    let mut expr_arena = Arena::with_capacity(16);
+    let roots = lfs
+        .iter()
+        .map(|lf| {
+            to_alp(
+                lf.logical_plan.clone(),
+                &mut expr_arena,
+                &mut lp_arena,
+                &mut lf.opt_state.clone(),
+            )
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let Some(shared) = shared_subplan(&roots, &lp_arena, &expr_arena) else {
+        return Ok(None);
+    };
+    let schema = lp_arena.get(shared[0]).schema(&lp_arena).into_owned();
+    let shared_plan = node_to_lp_cloned(shared[0], &expr_arena, &lp_arena);
+
+    // Replace the subplan with a placeholder, of which the queries are optimized.
+    let placeholder = Arc::new(DataFrame::empty_with_schema(&schema));
+    for node in &shared {
+        lp_arena.replace(
+            *node,
+            IR::DataFrameScan {
+                df: placeholder.clone(),
+                schema: schema.clone(),
+                output_schema: None,
+                filter: None,
+            },
+        );
+    }
+    let mut queries = Vec::with_capacity(lfs.len());
+    let (mut branch_lp_arena, mut branch_expr_arena) =
+        (Arena::with_capacity(16), Arena::with_capacity(16));
+    for (lf, root) in lfs.iter().zip(&roots) {
+        // A slice that is pushed down would replace the placeholder with a slice of it, so the
+        // slices are kept for the branches to run them.
+        let query = LazyFrame::from(node_to_lp_cloned(*root, &expr_arena, &lp_arena))
+            .with_optimizations(lf.opt_state & !(OptFlags::STREAMING | OptFlags::SLICE_PUSHDOWN));
+        let root = query.optimize_with_scratch(
+            &mut branch_lp_arena,
+            &mut branch_expr_arena,
+            &mut vec![],
+            false,
+        )?;
+        let Some(node) = (&branch_lp_arena).iter(root).find_map(|(node, lp)| {
+            matches!(lp, IR::DataFrameScan { df, .. } if Arc::ptr_eq(df, &placeholder))
+                .then_some(node)
+        }) else {
+            return Ok(None);
+        };
+        queries.push((root, node));
+    }
+
+    // Only stream the columns that the queries use.
+    let streamed_schema = queries
+        .iter()
+        .map(|(_, node)| match branch_lp_arena.get(*node) {
+            IR::DataFrameScan { output_schema, .. } => output_schema.clone(),
+            _ => unreachable!(),
+        })
+        .collect::<Option<Vec<_>>>()
+        .map_or(schema.clone(), |projections| {
+            let schema = schema
+                .iter()
+                .filter(|(name, _)| projections.iter().any(|p| p.contains(name)))
+                .map(|(name, dtype)| (name.clone(), dtype.clone()))
+                .collect::<Schema>();
+            Arc::new(schema)
+        });
+    let input = branch_lp_arena.add(IR::DataFrameScan {
+        df: placeholder.clone(),
+        schema: streamed_schema.clone(),
+        output_schema: None,
+        filter: None,
+    });
+    let branches = queries
+        .iter()
+        .map(|(root, node)| {
+            get_branch(
+                *root,
+                *node,
+                input,
+                &mut branch_lp_arena,
+                &branch_expr_arena,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let outputs = Arc::new(Mutex::new(vec![]));
+    let sink = get_fan_out_sink(
+        &branches
+            .iter()
+            .map(|branch| (branch.operators.clone(), branch.sink))
+            .collect::<Vec<_>>(),
+        &branch_lp_arena,
+        &mut branch_expr_arena,
+        &to_physical_piped_expr,
+        outputs.clone(),
+        // the branches are built before the shared subplan, so they account for their own memory
+        &Default::default(),
+    )?;
+
+    let mut shared_lf = LazyFrame::from(shared_plan).with_optimizations(lfs[0].opt_state);
+    if streamed_schema.len() < schema.len() {
+        shared_lf = shared_lf.select(
+            streamed_schema
+                .iter_names()
+                .map(|name| col(name.clone()))
+                .collect::<Vec<_>>(),
+        );
+    }
+    shared_lf.logical_plan = DslPlan::Sink {
+        input: Arc::new(shared_lf.logical_plan),
+        payload: SinkType::Custom {
+            sink: SharedSink::new(sink),
+        },
+    };
+    let (mut lp_arena, mut expr_arena) = (Arena::with_capacity(16), Arena::with_capacity(16));
+    let lp_top =
+        shared_lf.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut vec![], false)?;
+    // The sink is replaced if the subplan runs on the streaming engine.
+    if matches!(lp_arena.get(lp_top), IR::Sink { .. }) {
+        if verbose() {
+            eprintln!(
+                "collect_all: cannot stream the shared subplan, collecting the queries apart"
+            );
+        }
+        return Ok(None);
+    }
+    let mut physical_plan = create_physical_plan(lp_top, &mut lp_arena, &expr_arena)?;
+    physical_plan.execute(&mut ExecutionState::new())?;
+    let outputs = std::mem::take(&mut *outputs.lock().unwrap());
+
+    // Compute the rest of the queries from the outputs of their branches.
+    let rest = branches
+        .iter()
+        .zip(&queries)
+        .zip(lfs)
+        .zip(outputs)
+        .map(|(((branch, (root, _)), lf), out)| {
+            let Some(node) = branch.output else {
+                return out.lazy();
+            };
+            let schema = branch_lp_arena
+                .get(node)
+                .schema(&branch_lp_arena)
+                .into_owned();
+            branch_lp_arena.replace(
+                node,
+                IR::DataFrameScan {
+                    df: Arc::new(out),
+                    schema,
+                    output_schema: None,
+                    filter: None,
+                },
+            );
+            let plan = node_to_lp_cloned(*root, &branch_expr_arena, &branch_lp_arena);
+            LazyFrame::from(plan).with_optimizations(lf.opt_state)
+        })
+        .collect::<Vec<_>>();
+    POOL.install(|| {
+        rest.into_par_iter()
+            .map(|lf| lf.collect())
+            .collect::<PolarsResult<Vec<_>>>()
+            .map(Some)
+    })
+}
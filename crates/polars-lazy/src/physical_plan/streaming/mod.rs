@@ -1,6 +1,10 @@
 mod checks;
 mod construct_pipeline;
 mod convert_alp;
+#[cfg(feature = "cse")]
+mod fan_out;
 mod tree;
 
 pub(crate) use convert_alp::insert_streaming_nodes;
+#[cfg(feature = "cse")]
+pub(crate) use fan_out::collect_all_shared_subplan;
@@ -7,16 +7,22 @@ pub use polars_io::ipc::IpcWriterOptions;
 pub use polars_io::json::JsonWriterOptions;
 #[cfg(feature = "parquet")]
 pub use polars_io::parquet::write::ParquetWriteOptions;
+#[cfg(feature = "string_collation")]
+pub use polars_ops::prelude::CollationOptions;
+#[cfg(feature = "string_normalize")]
+pub use polars_ops::prelude::UnicodeForm;
 pub use polars_ops::prelude::{JoinArgs, JoinType, JoinValidation};
 #[cfg(feature = "rank")]
 pub use polars_ops::prelude::{RankMethod, RankOptions};
+#[cfg(feature = "string_tokenize")]
+pub use polars_ops::prelude::{TokenizeMode, TokenizeOptions};
 #[cfg(feature = "polars_cloud")]
 pub use polars_plan::client::prepare_cloud_plan;
 pub use polars_plan::plans::{
-    AnonymousScan, AnonymousScanArgs, AnonymousScanOptions, DslPlan, Literal, LiteralValue, Null,
-    NULL,
+    AnonymousScan, AnonymousScanArgs, AnonymousScanOptions, AppliedPushdowns, DslPlan, Literal,
+    LiteralValue, Null, PlanEstimate, UdfProperties, NULL,
 };
-pub use polars_plan::prelude::UnionArgs;
+pub use polars_plan::prelude::{FileType, SinkType, UnionArgs};
 pub(crate) use polars_plan::prelude::*;
 #[cfg(feature = "rolling_window_by")]
 pub use polars_time::Duration;
@@ -1,14 +1,16 @@
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 
 use polars_core::prelude::*;
 use polars_io::cloud::CloudOptions;
 use polars_io::csv::read::{
-    infer_file_schema, CommentPrefix, CsvEncoding, CsvParseOptions, CsvReadOptions, NullValues,
+    infer_file_schema, CommentPrefix, CsvEncoding, CsvParseOptions, CsvReadOptions,
+    InferSchemaPolicy, MalformedRowsSink, NullValues, RaggedLines, Separator,
 };
 use polars_io::mmap::ReaderBytes;
 use polars_io::path_utils::expand_paths;
-use polars_io::utils::get_reader_bytes;
-use polars_io::RowIndex;
+use polars_io::utils::{get_reader_bytes, maybe_decompress_bytes};
+use polars_io::{HiveOptions, RowIndex, SchemaEvolution};
 
 use crate::prelude::*;
 
@@ -20,7 +22,11 @@ pub struct LazyCsvReader {
     cache: bool,
     read_options: CsvReadOptions,
     cloud_options: Option<CloudOptions>,
+    hive_options: HiveOptions,
     include_file_paths: Option<PlSmallStr>,
+    schema_evolution: SchemaEvolution,
+    n_rows_per_file: Option<usize>,
+    max_concurrent_files: Option<NonZeroUsize>,
 }
 
 #[cfg(feature = "csv")]
@@ -42,7 +48,14 @@ impl LazyCsvReader {
             cache: true,
             read_options: Default::default(),
             cloud_options: Default::default(),
+            hive_options: HiveOptions {
+                enabled: Some(false),
+                ..Default::default()
+            },
             include_file_paths: None,
+            schema_evolution: SchemaEvolution::Strict,
+            n_rows_per_file: None,
+            max_concurrent_files: None,
         }
     }
 
@@ -81,6 +94,13 @@ impl LazyCsvReader {
         self
     }
 
+    /// Set how the schema is inferred from the rows that are read for schema inference.
+    #[must_use]
+    pub fn with_infer_schema_policy(mut self, infer_schema_policy: InferSchemaPolicy) -> Self {
+        self.read_options.infer_schema_policy = infer_schema_policy;
+        self
+    }
+
     /// Continue with next batch when a ParserError is encountered.
     #[must_use]
     pub fn with_ignore_errors(mut self, ignore: bool) -> Self {
@@ -88,6 +108,14 @@ impl LazyCsvReader {
         self
     }
 
+    /// Collect the lines that cannot be parsed into `malformed_rows` instead of raising an
+    /// error. The sink can be inspected after the query has finished.
+    #[must_use]
+    pub fn with_malformed_rows(mut self, malformed_rows: Option<MalformedRowsSink>) -> Self {
+        self.read_options.malformed_rows = malformed_rows;
+        self
+    }
+
     /// Set the CSV file's schema
     #[must_use]
     pub fn with_schema(mut self, schema: Option<SchemaRef>) -> Self {
@@ -117,9 +145,11 @@ impl LazyCsvReader {
         self
     }
 
-    /// Set the CSV file's column separator as a byte character
+    /// Set the CSV file's column separator. This is either a byte character or a
+    /// [`Separator`] of multiple bytes.
     #[must_use]
-    pub fn with_separator(self, separator: u8) -> Self {
+    pub fn with_separator<T: Into<Separator>>(self, separator: T) -> Self {
+        let separator = separator.into();
         self.map_parse_options(|opts| opts.with_separator(separator))
     }
 
@@ -200,6 +230,12 @@ impl LazyCsvReader {
         self.map_parse_options(|opts| opts.with_truncate_ragged_lines(truncate_ragged_lines))
     }
 
+    /// Set how lines with fewer or more fields than the schema are handled.
+    #[must_use]
+    pub fn with_ragged_lines(self, ragged_lines: RaggedLines) -> Self {
+        self.map_parse_options(|opts| opts.with_ragged_lines(ragged_lines))
+    }
+
     #[must_use]
     pub fn with_decimal_comma(self, decimal_comma: bool) -> Self {
         self.map_parse_options(|opts| opts.with_decimal_comma(decimal_comma))
@@ -229,6 +265,9 @@ impl LazyCsvReader {
         let mut infer_schema = |reader_bytes: ReaderBytes| {
             let skip_rows = self.read_options.skip_rows;
             let parse_options = self.read_options.get_parse_options();
+            let mut owned = vec![];
+            let bytes = maybe_decompress_bytes(&reader_bytes, &mut owned)?;
+            let reader_bytes = ReaderBytes::Borrowed(bytes);
 
             PolarsResult::Ok(
                 infer_file_schema(
@@ -243,11 +282,13 @@ impl LazyCsvReader {
                     parse_options.comment_prefix.as_ref(),
                     parse_options.quote_char,
                     parse_options.eol_char,
+                    parse_options.encoding,
                     None,
                     parse_options.try_parse_dates,
                     self.read_options.raise_if_empty,
                     &mut n_threads,
                     parse_options.decimal_comma,
+                    &self.read_options.infer_schema_policy,
                 )?
                 .0,
             )
@@ -305,18 +346,57 @@ impl LazyCsvReader {
         self.include_file_paths = include_file_paths;
         self
     }
+
+    /// Add the partition columns of `key=value` directories in the paths of the files. Hive
+    /// partitioning is disabled by default for CSV files.
+    pub fn with_hive_options(mut self, hive_options: HiveOptions) -> Self {
+        self.hive_options = hive_options;
+        self
+    }
+
+    /// Set how the schemas of the files may differ. By default all files must have the same
+    /// schema.
+    pub fn with_schema_evolution(mut self, schema_evolution: SchemaEvolution) -> Self {
+        self.schema_evolution = schema_evolution;
+        self
+    }
+
+    /// Read at most this number of rows of every file, e.g. to sample every file of a glob.
+    #[must_use]
+    pub fn with_n_rows_per_file(mut self, n_rows_per_file: Option<usize>) -> Self {
+        self.n_rows_per_file = n_rows_per_file;
+        self
+    }
+
+    /// Open and parse at most this number of files at the same time.
+    #[must_use]
+    pub fn with_max_concurrent_files(mut self, max_concurrent_files: Option<NonZeroUsize>) -> Self {
+        self.max_concurrent_files = max_concurrent_files;
+        self
+    }
 }
 
 impl LazyFileListReader for LazyCsvReader {
     /// Get the final [LazyFrame].
     fn finish(self) -> PolarsResult<LazyFrame> {
+        let schema_evolution = self.schema_evolution;
+        if schema_evolution != SchemaEvolution::Strict {
+            return self.finish_with_schema_evolution(schema_evolution, |mut reader| {
+                reader.schema_evolution = SchemaEvolution::Strict;
+                reader.finish()
+            });
+        }
+
         let mut lf: LazyFrame = DslBuilder::scan_csv(
             self.sources.to_dsl(false),
             self.read_options,
             self.cache,
             self.cloud_options,
             self.glob,
+            self.hive_options,
             self.include_file_paths,
+            self.n_rows_per_file,
+            self.max_concurrent_files,
         )?
         .build()
         .into();
@@ -0,0 +1,486 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_core::utils::try_get_supertype;
+use polars_expr::{create_physical_expr, ExpressionConversionState};
+use polars_io::parquet::read::ParquetReader;
+use polars_io::parquet::read::_internal::collect_statistics;
+use polars_io::predicates::{BatchStats, ColumnStats, PhysicalIoExpr};
+use polars_io::{expand_paths_hive, HiveOptions, SerReader};
+use polars_plan::plans::hive::hive_partitions_from_paths;
+
+use crate::prelude::*;
+
+/// Options for discovering the fragments of a [`Dataset`].
+#[derive(Clone, Debug)]
+pub struct DatasetOptions {
+    /// Parse the partition values of the fragments from the `key=value` directories in their
+    /// paths.
+    pub hive_partitioning: bool,
+    /// The dtypes of the partition columns, which are inferred from the paths if not given.
+    pub partition_schema: Option<SchemaRef>,
+    /// Try to parse inferred partition values as dates and datetimes.
+    pub try_parse_dates: bool,
+    /// The schema of the columns of the files, which is unified from the schemas of the
+    /// fragments if not given.
+    pub schema: Option<SchemaRef>,
+}
+
+impl Default for DatasetOptions {
+    fn default() -> Self {
+        Self {
+            hive_partitioning: true,
+            partition_schema: None,
+            try_parse_dates: true,
+            schema: None,
+        }
+    }
+}
+
+/// The file format of a [`Fragment`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FragmentFormat {
+    Parquet,
+    Csv,
+    Ipc,
+}
+
+impl FragmentFormat {
+    /// The format of a file by the extension of its path, or `None` if the extension is unknown.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "parquet" | "pq" => Some(Self::Parquet),
+            "csv" => Some(Self::Csv),
+            "ipc" | "arrow" | "feather" => Some(Self::Ipc),
+            _ => None,
+        }
+    }
+
+    fn scan(self, path: &Path) -> PolarsResult<LazyFrame> {
+        // The partition columns of a fragment are added by the dataset.
+        let hive_options = HiveOptions {
+            enabled: Some(false),
+            ..Default::default()
+        };
+        match self {
+            Self::Parquet => LazyFrame::scan_parquet(
+                path,
+                ScanArgsParquet {
+                    hive_options,
+                    ..Default::default()
+                },
+            ),
+            Self::Csv => LazyCsvReader::new(path).finish(),
+            Self::Ipc => LazyFrame::scan_ipc(
+                path,
+                ScanArgsIpc {
+                    hive_options,
+                    ..Default::default()
+                },
+            ),
+        }
+    }
+}
+
+/// A single file of a [`Dataset`].
+#[derive(Clone, Debug)]
+pub struct Fragment {
+    path: PathBuf,
+    format: FragmentFormat,
+    schema: SchemaRef,
+    /// A single-value Series for every column of the partition schema of the dataset.
+    partition_values: Vec<Series>,
+    /// The statistics of the columns of the file, merged over its row groups. These are only
+    /// available for Parquet files.
+    statistics: Option<BatchStats>,
+}
+
+impl Fragment {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn format(&self) -> FragmentFormat {
+        self.format
+    }
+
+    /// The schema of the file itself, before it is unified with the other fragments.
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// The values of the partition columns of the fragment.
+    pub fn partition_values(&self) -> &[Series] {
+        &self.partition_values
+    }
+
+    /// The statistics of the columns of the file, if its format stores them.
+    pub fn statistics(&self) -> Option<&BatchStats> {
+        self.statistics.as_ref()
+    }
+
+    /// The number of rows of the file, if it is known without reading the file.
+    pub fn num_rows(&self) -> Option<usize> {
+        self.statistics.as_ref().and_then(|stats| stats.num_rows())
+    }
+}
+
+/// A collection of Parquet, CSV and IPC files that are read as a single table.
+///
+/// The schemas of the files are unified into a single schema, in which columns that are
+/// missing from a file are null and columns of different dtypes are cast to their supertype.
+/// The columns of the `key=value` directories that contain the files are added as partition
+/// columns. A predicate that is added with [`Dataset::filter`] is evaluated against the
+/// partition values and the statistics of every fragment, so that fragments which can't contain
+/// matching rows are never scanned. The predicate and projections of the query are pushed down
+/// into the scans of the remaining fragments.
+///
+/// # Example
+/// ```no_run
+/// use polars_core::prelude::*;
+/// use polars_lazy::prelude::*;
+///
+/// fn example() -> PolarsResult<DataFrame> {
+///     Dataset::discover("data/sales", DatasetOptions::default())?
+///         .filter(col("year").eq(lit(2024)))
+///         .to_lazy()?
+///         .select([col("amount").sum()])
+///         .collect()
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Dataset {
+    fragments: Vec<Fragment>,
+    schema: SchemaRef,
+    partition_schema: SchemaRef,
+    predicate: Option<Expr>,
+}
+
+impl Dataset {
+    /// Discover the fragments of a directory, which are all files below it, or of a glob
+    /// pattern. Files of which the name starts with `_` or `.`, such as `_SUCCESS` markers, are
+    /// skipped.
+    pub fn discover(path: impl AsRef<Path>, options: DatasetOptions) -> PolarsResult<Self> {
+        let path = path.as_ref();
+        polars_ensure!(
+            !polars_io::is_cloud_url(path),
+            ComputeError: "a dataset can only be discovered on the local file system"
+        );
+        let pattern = if path.is_dir() {
+            path.join("**/*")
+        } else {
+            path.to_path_buf()
+        };
+        let (paths, hive_start_idx) =
+            expand_paths_hive(&[pattern], true, None, options.hive_partitioning)?;
+        let paths = paths
+            .iter()
+            .filter(|path| path.is_file() && !is_hidden(path))
+            .cloned()
+            .collect::<Vec<_>>();
+        polars_ensure!(
+            !paths.is_empty(),
+            ComputeError: "dataset '{}' contains no files", path.display()
+        );
+
+        let mut fragments = paths
+            .iter()
+            .map(|path| {
+                let format = FragmentFormat::from_path(path).ok_or_else(|| {
+                    polars_err!(
+                        ComputeError: "file '{}' of the dataset has an unknown format",
+                        path.display()
+                    )
+                })?;
+                let schema = format.scan(path)?.collect_schema()?;
+                let statistics = match format {
+                    FragmentFormat::Parquet => parquet_statistics(path)?,
+                    _ => None,
+                };
+                Ok(Fragment {
+                    path: path.clone(),
+                    format,
+                    schema,
+                    partition_values: vec![],
+                    statistics,
+                })
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let mut file_schema = Schema::default();
+        for fragment in &fragments {
+            for (name, dtype) in fragment.schema.iter() {
+                match file_schema.get(name) {
+                    Some(existing) => {
+                        let supertype = try_get_supertype(existing, dtype)?;
+                        file_schema.set_dtype(name, supertype);
+                    },
+                    None => {
+                        file_schema.with_column(name.clone(), dtype.clone());
+                    },
+                }
+            }
+        }
+
+        let mut partition_schema = Schema::default();
+        if options.hive_partitioning {
+            let partitions = hive_partitions_from_paths(
+                &paths,
+                hive_start_idx,
+                options.partition_schema.clone(),
+                &file_schema,
+                options.try_parse_dates,
+            )?;
+            if let Some(partitions) = partitions {
+                if let Some(first) = partitions.first() {
+                    partition_schema = first.get_statistics().schema().as_ref().clone();
+                }
+                for (fragment, partition) in fragments.iter_mut().zip(partitions.iter()) {
+                    fragment.partition_values = partition.materialize_partition_columns();
+                }
+            }
+        }
+
+        // The value of a partition column is given by the path, even if the files contain it.
+        let mut schema = match options.schema {
+            Some(schema) => schema.as_ref().clone(),
+            None => file_schema,
+        };
+        for name in partition_schema.iter_names() {
+            schema.shift_remove(name);
+        }
+        schema.merge_from_ref(&partition_schema);
+
+        Ok(Self {
+            fragments,
+            schema: Arc::new(schema),
+            partition_schema: Arc::new(partition_schema),
+            predicate: None,
+        })
+    }
+
+    /// The unified schema of the dataset, which ends with the partition columns.
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// The schema of the partition columns.
+    pub fn partition_schema(&self) -> &SchemaRef {
+        &self.partition_schema
+    }
+
+    /// All fragments of the dataset, including those that are pruned by the predicate.
+    pub fn fragments(&self) -> &[Fragment] {
+        &self.fragments
+    }
+
+    /// Only read the rows that match this predicate. A predicate that is added to a dataset that
+    /// already has one is combined with it.
+    pub fn filter(mut self, predicate: Expr) -> Self {
+        self.predicate = Some(match self.predicate.take() {
+            Some(existing) => existing.and(predicate),
+            None => predicate,
+        });
+        self
+    }
+
+    /// The fragments that may contain rows that match the predicate.
+    pub fn pruned_fragments(&self) -> PolarsResult<Vec<&Fragment>> {
+        let Some(predicate) = self.stats_predicate()? else {
+            return Ok(self.fragments.iter().collect());
+        };
+        let Some(evaluator) = predicate.as_stats_evaluator() else {
+            return Ok(self.fragments.iter().collect());
+        };
+        Ok(self
+            .fragments
+            .iter()
+            // The predicate itself is still applied to the rows of the fragments that are read,
+            // so a fragment of which the statistics can't be evaluated is read.
+            .filter(|fragment| {
+                evaluator
+                    .should_read(&self.fragment_statistics(fragment))
+                    .unwrap_or(true)
+            })
+            .collect())
+    }
+
+    /// Create a LazyFrame of the fragments that aren't pruned by the predicate.
+    pub fn to_lazy(&self) -> PolarsResult<LazyFrame> {
+        let fragments = self.pruned_fragments()?;
+        if fragments.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&self.schema).lazy());
+        }
+        let lfs = fragments
+            .into_iter()
+            .map(|fragment| self.fragment_lazy(fragment))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let lf = concat(
+            lfs,
+            UnionArgs {
+                rechunk: false,
+                ..Default::default()
+            },
+        )?;
+        Ok(match &self.predicate {
+            Some(predicate) => lf.filter(predicate.clone()),
+            None => lf,
+        })
+    }
+
+    /// Scan a fragment and conform it to the schema of the dataset.
+    fn fragment_lazy(&self, fragment: &Fragment) -> PolarsResult<LazyFrame> {
+        let exprs = self
+            .schema
+            .iter()
+            .map(|(name, dtype)| {
+                if let Some(idx) = self.partition_schema.index_of(name) {
+                    lit(fragment.partition_values[idx].clone())
+                        .first()
+                        .alias(name.clone())
+                } else if fragment.schema.contains(name) {
+                    col(name.clone()).cast(dtype.clone())
+                } else {
+                    lit(NULL).cast(dtype.clone()).alias(name.clone())
+                }
+            })
+            .collect::<Vec<_>>();
+        Ok(fragment.format.scan(&fragment.path)?.select(exprs))
+    }
+
+    /// Convert the predicate into an expression that can be evaluated against statistics.
+    fn stats_predicate(&self) -> PolarsResult<Option<Arc<dyn PhysicalIoExpr>>> {
+        let Some(predicate) = &self.predicate else {
+            return Ok(None);
+        };
+        // The conversion of the filter coerces the literals of the predicate to the dtypes of
+        // the columns.
+        let plan = DataFrame::empty_with_schema(&self.schema)
+            .lazy()
+            .filter(predicate.clone())
+            .to_alp()?;
+        let IR::Filter { predicate, .. } = plan.lp_arena.get(plan.lp_top) else {
+            return Ok(None);
+        };
+        // A predicate that can't be converted is only applied to the rows.
+        let Ok(phys_expr) = create_physical_expr(
+            predicate,
+            Context::Default,
+            &plan.expr_arena,
+            Some(&self.schema),
+            &mut ExpressionConversionState::new(true, 0),
+        ) else {
+            return Ok(None);
+        };
+        Ok(Some(phys_expr_to_io_expr(phys_expr)))
+    }
+
+    /// The statistics of a fragment in the schema of the dataset.
+    fn fragment_statistics(&self, fragment: &Fragment) -> BatchStats {
+        let stats = self
+            .schema
+            .iter()
+            .map(|(name, dtype)| {
+                if let Some(idx) = self.partition_schema.index_of(name) {
+                    return ColumnStats::from_column_literal(
+                        fragment.partition_values[idx].clone(),
+                    );
+                }
+                fragment
+                    .statistics
+                    .as_ref()
+                    .and_then(|stats| stats.get_stats(name).ok())
+                    .and_then(|stats| conform_column_stats(stats, name, dtype))
+                    .unwrap_or_else(|| {
+                        ColumnStats::from_field(Field::new(name.clone(), dtype.clone()))
+                    })
+            })
+            .collect();
+        BatchStats::new(self.schema.clone(), stats, fragment.num_rows())
+    }
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('_') || name.starts_with('.'))
+}
+
+/// Cast the statistics of a column of a file to the dtype of the dataset, if the order of the
+/// values is kept by the cast.
+fn conform_column_stats(stats: &ColumnStats, name: &str, dtype: &DataType) -> Option<ColumnStats> {
+    if stats.dtype() != dtype && !(stats.dtype().is_numeric() && dtype.is_numeric()) {
+        return None;
+    }
+    let min = stats.to_min()?.cast(dtype).ok()?;
+    let max = stats.to_max()?.cast(dtype).ok()?;
+    Some(ColumnStats::new(
+        Field::new(name.into(), dtype.clone()),
+        stats.get_null_count_state().cloned(),
+        Some(min),
+        Some(max),
+    ))
+}
+
+/// Read the statistics of the row groups of a Parquet file and merge them into the statistics of
+/// the whole file.
+fn parquet_statistics(path: &Path) -> PolarsResult<Option<BatchStats>> {
+    let mut reader = ParquetReader::new(polars_utils::open_file(path)?);
+    let arrow_schema = reader.schema()?;
+    let metadata = reader.get_metadata()?.clone();
+    let row_groups = metadata
+        .row_groups
+        .iter()
+        .map(|md| collect_statistics(md, &arrow_schema, None))
+        .collect::<PolarsResult<Option<Vec<_>>>>()?;
+    let Some(row_groups) = row_groups.filter(|row_groups| !row_groups.is_empty()) else {
+        return Ok(None);
+    };
+
+    let schema = row_groups[0].schema().clone();
+    let stats = (0..schema.len())
+        .map(|i| {
+            let columns = row_groups
+                .iter()
+                .map(|stats| &stats.column_stats()[i])
+                .collect::<Vec<_>>();
+            merge_column_stats(&columns)
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let num_rows = row_groups.iter().map(|stats| stats.num_rows()).sum();
+    Ok(Some(BatchStats::new(schema, stats, num_rows)))
+}
+
+/// Merge the statistics of a column over row groups. The minimum and maximum are only known if
+/// they are known for every row group.
+fn merge_column_stats(columns: &[&ColumnStats]) -> PolarsResult<ColumnStats> {
+    let field = Field::new(columns[0].field_name().clone(), columns[0].dtype().clone());
+
+    let concat_states = |state: fn(&ColumnStats) -> Option<&Series>| -> PolarsResult<_> {
+        let mut acc: Option<Series> = None;
+        for column in columns {
+            let Some(s) = state(column) else {
+                return Ok(None);
+            };
+            match &mut acc {
+                Some(acc) => {
+                    acc.append(s)?;
+                },
+                None => acc = Some(s.clone()),
+            }
+        }
+        Ok(acc)
+    };
+    let reduce = |s: Option<Series>, max: bool| -> Option<Series> {
+        let s = s.filter(|s| s.null_count() == 0)?;
+        let scalar = if max { s.max_reduce() } else { s.min_reduce() };
+        let scalar = scalar.ok()?;
+        Some(scalar.into_series(s.name().clone()))
+    };
+
+    let null_count = concat_states(ColumnStats::get_null_count_state)?;
+    let min = reduce(concat_states(ColumnStats::get_min_state)?, false);
+    let max = reduce(concat_states(ColumnStats::get_max_state)?, true);
+    Ok(ColumnStats::new(field, null_count, min, max))
+}
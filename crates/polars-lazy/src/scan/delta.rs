@@ -0,0 +1,174 @@
+use std::path::Path;
+
+use polars_core::prelude::*;
+use polars_io::delta::DeltaSnapshot;
+use polars_io::parquet::read::ParallelStrategy;
+use polars_io::{HiveOptions, RowIndex};
+use polars_utils::aliases::PlIndexMap;
+
+use crate::prelude::*;
+
+/// The row index of the files with deleted rows, by which the deleted rows are filtered out.
+const ROW_INDEX: &str = "__POLARS_DELTA_ROW_INDEX";
+const FILE_INDEX: &str = "__POLARS_DELTA_FILE_INDEX";
+
+#[derive(Clone)]
+pub struct ScanArgsDelta {
+    /// Read this version of the table, instead of its latest version.
+    pub version: Option<i64>,
+    /// Only read the files of the partitions for which this predicate on the partition columns
+    /// is true.
+    pub partition_filter: Option<Expr>,
+    pub parallel: ParallelStrategy,
+    pub low_memory: bool,
+    pub rechunk: bool,
+    pub cache: bool,
+}
+
+impl Default for ScanArgsDelta {
+    fn default() -> Self {
+        Self {
+            version: None,
+            partition_filter: None,
+            parallel: Default::default(),
+            low_memory: false,
+            rechunk: false,
+            cache: true,
+        }
+    }
+}
+
+impl LazyFrame {
+    /// Create a LazyFrame directly from a scan of the Parquet files of a Delta Lake table.
+    ///
+    /// The transaction log of the table is read when the LazyFrame is created. The files are
+    /// scanned with the values of their partitions, and without the rows that their deletion
+    /// vectors delete. Only tables in local storage can be read.
+    pub fn scan_delta(path: impl AsRef<Path>, args: ScanArgsDelta) -> PolarsResult<Self> {
+        let table_root = path.as_ref();
+        let snapshot = DeltaSnapshot::try_new(table_root, args.version)?;
+        let partition_values = snapshot.partition_values()?;
+        let files = match args.partition_filter {
+            Some(predicate) => prune_files(&partition_values, snapshot.files.len(), predicate)?,
+            None => (0..snapshot.files.len()).collect(),
+        };
+        if files.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&snapshot.schema).lazy());
+        }
+
+        let columns = snapshot
+            .schema
+            .iter_names()
+            .map(|name| col(name.clone()))
+            .collect::<Vec<_>>();
+        let parquet_args = ScanArgsParquet {
+            parallel: args.parallel,
+            low_memory: args.low_memory,
+            cache: args.cache,
+            hive_options: HiveOptions {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            glob: false,
+            ..Default::default()
+        };
+
+        // The files of a partition without deleted rows are read in a single scan.
+        let mut partitions = PlIndexMap::<&[Option<String>], Vec<usize>>::new();
+        let mut lfs = vec![];
+        for i in files {
+            let file = &snapshot.files[i];
+            let Some(deletion_vector) = &file.deletion_vector else {
+                partitions
+                    .entry(file.partition_values.as_slice())
+                    .or_default()
+                    .push(i);
+                continue;
+            };
+
+            let deleted = Series::new(PlSmallStr::EMPTY, deletion_vector.deleted_rows(table_root)?)
+                .strict_cast(&IDX_DTYPE)?;
+            let args = ScanArgsParquet {
+                row_index: Some(RowIndex {
+                    name: ROW_INDEX.into(),
+                    offset: 0,
+                }),
+                ..parquet_args.clone()
+            };
+            let lf = LazyFrame::scan_parquet(&file.path, args)?
+                .filter(col(ROW_INDEX).is_in(lit(deleted)).not());
+            lfs.push(with_partition_values(lf, &partition_values, i, &columns)?);
+        }
+        for files in partitions.into_values() {
+            let paths = files
+                .iter()
+                .map(|&i| snapshot.files[i].path.clone())
+                .collect();
+            let lf = LazyFrame::scan_parquet_files(paths, parquet_args.clone())?;
+            lfs.push(with_partition_values(
+                lf,
+                &partition_values,
+                files[0],
+                &columns,
+            )?);
+        }
+
+        concat(
+            lfs,
+            UnionArgs {
+                rechunk: args.rechunk,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+/// The files of which the partition values satisfy the predicate.
+fn prune_files(
+    partition_values: &[Series],
+    n_files: usize,
+    predicate: Expr,
+) -> PolarsResult<Vec<usize>> {
+    let index = Series::new(
+        PlSmallStr::from_static(FILE_INDEX),
+        (0..n_files as IdxSize).collect::<Vec<_>>(),
+    );
+    let mut columns = vec![index];
+    columns.extend(partition_values.iter().cloned());
+    let df = DataFrame::new(columns)?
+        .lazy()
+        .filter(predicate)
+        .select([col(FILE_INDEX)])
+        .collect()?;
+    Ok(df
+        .column(FILE_INDEX)?
+        .idx()?
+        .into_no_null_iter()
+        .map(|i| i as usize)
+        .collect())
+}
+
+/// Add the partition columns, which aren't in the Parquet files, with the values of file `i`,
+/// and order the columns as in the schema of the table.
+fn with_partition_values(
+    lf: LazyFrame,
+    partition_values: &[Series],
+    i: usize,
+    columns: &[Expr],
+) -> PolarsResult<LazyFrame> {
+    let values = partition_values
+        .iter()
+        .map(|s| {
+            let value = LiteralValue::try_from(s.get(i)?)?;
+            Ok(Expr::Literal(value)
+                .cast(s.dtype().clone())
+                .alias(s.name().clone()))
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let lf = if values.is_empty() {
+        lf
+    } else {
+        lf.with_columns(values)
+    };
+    Ok(lf.select(columns))
+}
@@ -3,7 +3,8 @@ use std::sync::Arc;
 
 use polars_core::prelude::*;
 use polars_io::cloud::CloudOptions;
-use polars_io::RowIndex;
+use polars_io::path_utils::expand_paths;
+use polars_io::{RowIndex, SchemaEvolution};
 use polars_plan::prelude::UnionArgs;
 
 use crate::prelude::*;
@@ -73,6 +74,83 @@ pub trait LazyFileListReader: Clone {
         concat_impl(&lfs, args)
     }
 
+    /// Get the final [LazyFrame] of files whose schemas may differ according to
+    /// `schema_evolution`.
+    ///
+    /// Every file is scanned separately with `finish_file`, and the scans are concatenated
+    /// diagonally, so that columns that are missing from a file are filled with nulls.
+    fn finish_with_schema_evolution<F>(
+        self,
+        schema_evolution: SchemaEvolution,
+        finish_file: F,
+    ) -> PolarsResult<LazyFrame>
+    where
+        F: Fn(Self) -> PolarsResult<LazyFrame>,
+    {
+        let Some(paths) = self.sources().as_paths() else {
+            polars_ensure!(
+                self.sources().len() <= 1,
+                InvalidOperation: "schema evolution is only supported for scans of paths"
+            );
+            return finish_file(self);
+        };
+        let paths = expand_paths(paths, self.glob(), self.cloud_options())?;
+
+        let lfs = paths
+            .iter()
+            .map(|path| {
+                finish_file(
+                    self.clone()
+                        .with_n_rows(None)
+                        .with_row_index(None)
+                        .with_paths([path.clone()].into())
+                        .with_rechunk(false),
+                )
+                .map_err(|e| {
+                    polars_err!(
+                        ComputeError: "error while reading {}: {}", path.display(), e
+                    )
+                })
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        polars_ensure!(
+            !lfs.is_empty(),
+            ComputeError: "no matching files found in {:?}", paths.iter().map(|x| x.to_str().unwrap()).collect::<Vec<_>>()
+        );
+
+        if schema_evolution == SchemaEvolution::AllowMissingColumns {
+            let mut dtypes = PlHashMap::new();
+            for (lf, path) in lfs.iter().zip(paths.iter()) {
+                for (name, dtype) in lf.clone().collect_schema()?.iter() {
+                    let expected = dtypes.entry(name.clone()).or_insert_with(|| dtype.clone());
+                    polars_ensure!(
+                        expected == dtype,
+                        SchemaMismatch: "column {} of {} has dtype {}, expected {}",
+                        name, path.display(), dtype, expected
+                    );
+                }
+            }
+        }
+
+        let args = UnionArgs {
+            rechunk: self.rechunk(),
+            parallel: true,
+            to_supertypes: schema_evolution == SchemaEvolution::Relaxed,
+            diagonal: true,
+            from_partitioned_ds: true,
+        };
+        let mut lf = concat_impl(&lfs, args)?;
+        if let Some(n_rows) = self.n_rows() {
+            lf = lf.slice(0, n_rows as IdxSize)
+        };
+        if let Some(rc) = self.row_index() {
+            lf = lf.with_row_index(rc.name.clone(), Some(rc.offset))
+        };
+
+        Ok(lf)
+    }
+
     /// Get the final [LazyFrame].
     /// This method assumes, that path is *not* a glob.
     ///
@@ -0,0 +1,167 @@
+use std::any::Any;
+use std::sync::Mutex;
+
+use polars_core::prelude::*;
+use polars_io::flight::{FlightReader, FlightSqlStatement};
+
+use crate::prelude::*;
+
+impl LazyFrame {
+    /// Create a LazyFrame directly from the stream of a `DoGet` call with `ticket` to the Flight
+    /// service at `endpoint`, such as `http://localhost:8815`.
+    ///
+    /// The call is made when the LazyFrame is created, to read the schema of the stream, and its
+    /// record batches are read when the query runs. On the streaming engine the batches are
+    /// processed as they arrive, so that the stream doesn't have to fit into memory. Running the
+    /// query again calls `DoGet` again with the same ticket, which not every service allows.
+    pub fn scan_flight(endpoint: &str, ticket: impl Into<Vec<u8>>) -> PolarsResult<Self> {
+        let ticket = ticket.into();
+        let reader = FlightReader::try_new(endpoint, ticket.clone())?;
+        let schema = reader.schema().clone();
+        let scan = FlightScan {
+            endpoint: endpoint.to_string(),
+            ticket,
+            schema: schema.clone(),
+            reader: Mutex::new(Some(reader)),
+        };
+        LazyFrame::anonymous_scan(
+            Arc::new(scan),
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name: "FLIGHT SCAN",
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a LazyFrame directly from the result of `query` on the Flight SQL service at
+    /// `dsn`, such as `grpc://localhost:32010`.
+    ///
+    /// The query is executed when the LazyFrame is created, and the Arrow schema of its result
+    /// determines the dtypes of the columns. If the service doesn't return the schema with the
+    /// result, it is read from the stream of the first endpoint. The record batches of the
+    /// endpoints of the result are read when the query runs, in the order of the endpoints, and
+    /// processed as they arrive on the streaming engine. Running the query again executes
+    /// `query` again.
+    pub fn scan_flight_sql(dsn: &str, query: &str) -> PolarsResult<Self> {
+        let mut statement = FlightSqlStatement::try_new(dsn, query)?;
+        let schema = statement.schema()?;
+        let scan = FlightSqlScan {
+            dsn: dsn.to_string(),
+            query: query.to_string(),
+            schema: schema.clone(),
+            statement: Mutex::new(Some(statement)),
+        };
+        LazyFrame::anonymous_scan(
+            Arc::new(scan),
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name: "FLIGHT SQL SCAN",
+                ..Default::default()
+            },
+        )
+    }
+}
+
+struct FlightScan {
+    endpoint: String,
+    ticket: Vec<u8>,
+    schema: SchemaRef,
+    /// The reader of which the schema was read, which is used by the first run of the query.
+    reader: Mutex<Option<FlightReader>>,
+}
+
+impl FlightScan {
+    fn reader(&self) -> PolarsResult<FlightReader> {
+        match self.reader.lock().unwrap().take() {
+            Some(reader) => Ok(reader),
+            None => FlightReader::try_new(&self.endpoint, self.ticket.clone()),
+        }
+    }
+}
+
+impl AnonymousScan for FlightScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let df = self.reader()?.finish()?;
+        Ok(match scan_opts.n_rows {
+            Some(n_rows) => df.head(Some(n_rows)),
+            None => df,
+        })
+    }
+
+    fn scan_batches(
+        &self,
+        _scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>> {
+        let mut reader = self.reader()?;
+        Ok(Box::new(std::iter::from_fn(move || {
+            reader.next_batch().transpose()
+        })))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+}
+
+struct FlightSqlScan {
+    dsn: String,
+    query: String,
+    schema: SchemaRef,
+    /// The statement of which the schema was read, which is used by the first run of the query.
+    statement: Mutex<Option<FlightSqlStatement>>,
+}
+
+impl FlightSqlScan {
+    fn statement(&self) -> PolarsResult<FlightSqlStatement> {
+        match self.statement.lock().unwrap().take() {
+            Some(statement) => Ok(statement),
+            None => FlightSqlStatement::try_new(&self.dsn, &self.query),
+        }
+    }
+}
+
+impl AnonymousScan for FlightSqlScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let df = self.statement()?.finish()?;
+        Ok(match scan_opts.n_rows {
+            Some(n_rows) => df.head(Some(n_rows)),
+            None => df,
+        })
+    }
+
+    fn scan_batches(
+        &self,
+        _scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>> {
+        Ok(Box::new(self.statement()?.batches()?))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,129 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_io::cloud::CloudOptions;
+use polars_io::fwf::FwfReadOptions;
+use polars_io::RowIndex;
+
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct ScanArgsFwf {
+    pub options: FwfReadOptions,
+    pub n_rows: Option<usize>,
+    pub cache: bool,
+    pub rechunk: bool,
+    pub row_index: Option<RowIndex>,
+    pub include_file_paths: Option<PlSmallStr>,
+}
+
+impl Default for ScanArgsFwf {
+    fn default() -> Self {
+        Self {
+            options: FwfReadOptions::default(),
+            n_rows: None,
+            cache: true,
+            rechunk: false,
+            row_index: None,
+            include_file_paths: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct LazyFwfReader {
+    args: ScanArgsFwf,
+    sources: ScanSources,
+}
+
+impl LazyFwfReader {
+    fn new(args: ScanArgsFwf) -> Self {
+        Self {
+            args,
+            sources: ScanSources::default(),
+        }
+    }
+}
+
+impl LazyFileListReader for LazyFwfReader {
+    fn finish(self) -> PolarsResult<LazyFrame> {
+        let args = self.args;
+
+        let lf: LazyFrame = DslBuilder::scan_fwf(
+            self.sources.to_dsl(false),
+            args.options,
+            args.n_rows,
+            args.cache,
+            args.row_index,
+            args.rechunk,
+            args.include_file_paths,
+        )?
+        .build()
+        .into();
+
+        Ok(lf)
+    }
+
+    fn finish_no_glob(self) -> PolarsResult<LazyFrame> {
+        unreachable!()
+    }
+
+    fn sources(&self) -> &ScanSources {
+        &self.sources
+    }
+
+    fn with_sources(mut self, sources: ScanSources) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    fn with_n_rows(mut self, n_rows: impl Into<Option<usize>>) -> Self {
+        self.args.n_rows = n_rows.into();
+        self
+    }
+
+    fn with_row_index(mut self, row_index: impl Into<Option<RowIndex>>) -> Self {
+        self.args.row_index = row_index.into();
+        self
+    }
+
+    fn rechunk(&self) -> bool {
+        self.args.rechunk
+    }
+
+    fn with_rechunk(mut self, toggle: bool) -> Self {
+        self.args.rechunk = toggle;
+        self
+    }
+
+    fn n_rows(&self) -> Option<usize> {
+        self.args.n_rows
+    }
+
+    fn row_index(&self) -> Option<&RowIndex> {
+        self.args.row_index.as_ref()
+    }
+
+    /// Fixed-width files are only read from local storage.
+    fn cloud_options(&self) -> Option<&CloudOptions> {
+        None
+    }
+}
+
+impl LazyFrame {
+    /// Create a LazyFrame directly from a scan of a fixed-width file.
+    pub fn scan_fwf(path: impl AsRef<Path>, args: ScanArgsFwf) -> PolarsResult<Self> {
+        Self::scan_fwf_sources(
+            ScanSources::Paths([path.as_ref().to_path_buf()].into()),
+            args,
+        )
+    }
+
+    pub fn scan_fwf_files(paths: Arc<[PathBuf]>, args: ScanArgsFwf) -> PolarsResult<Self> {
+        Self::scan_fwf_sources(ScanSources::Paths(paths), args)
+    }
+
+    pub fn scan_fwf_sources(sources: ScanSources, args: ScanArgsFwf) -> PolarsResult<Self> {
+        LazyFwfReader::new(args).with_sources(sources).finish()
+    }
+}
@@ -0,0 +1,328 @@
+use std::any::Any;
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_io::iceberg::IcebergSnapshot;
+use polars_io::parquet::read::ParquetReader;
+use polars_io::{HiveOptions, SerReader};
+
+use crate::prelude::*;
+
+const FILE_INDEX: &str = "__POLARS_ICEBERG_FILE_INDEX";
+
+/// The names and the field ids of the columns of data files, their schema and the paths of the
+/// files.
+type Layout = (Vec<(PlSmallStr, Option<i32>)>, Schema, Vec<PathBuf>);
+
+#[derive(Clone, Default)]
+pub struct ScanArgsIceberg {
+    /// Read this snapshot of the table, instead of its current snapshot.
+    pub snapshot_id: Option<i64>,
+    pub low_memory: bool,
+    pub rechunk: bool,
+}
+
+impl LazyFrame {
+    /// Create a LazyFrame directly from a scan of the Parquet data files of a snapshot of an
+    /// Iceberg table at `path`, which is the directory of the table or one of its metadata
+    /// files.
+    ///
+    /// The metadata and the manifests of the table are read when the LazyFrame is created. When
+    /// the query runs, the data files are pruned with the predicate that is pushed down to the
+    /// scan, by the bounds of the values of their columns and by the values of their identity
+    /// partitions. The columns of the data files are matched to the schema of the snapshot by
+    /// their field ids, so that columns that have been renamed or reordered since the files were
+    /// written are read correctly, and columns that have been added are null. Tables with
+    /// delete files are not supported.
+    pub fn scan_iceberg(path: impl AsRef<Path>, args: ScanArgsIceberg) -> PolarsResult<Self> {
+        let snapshot = IcebergSnapshot::try_new(path.as_ref(), args.snapshot_id)?;
+        let schema = Arc::new(snapshot.schema.clone());
+        let scan = IcebergScan {
+            snapshot,
+            schema: schema.clone(),
+            args,
+        };
+        LazyFrame::anonymous_scan(
+            Arc::new(scan),
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name: "ICEBERG SCAN",
+                ..Default::default()
+            },
+        )
+    }
+}
+
+struct IcebergScan {
+    snapshot: IcebergSnapshot,
+    schema: SchemaRef,
+    args: ScanArgsIceberg,
+}
+
+impl IcebergScan {
+    /// The data files of which the bounds of the values don't rule out that they have rows that
+    /// satisfy the predicate.
+    fn prune_files(&self, predicate: &Expr) -> PolarsResult<Vec<usize>> {
+        let n_files = self.snapshot.files.len();
+        let bounds = self.snapshot.column_bounds()?;
+        let columns = bounds
+            .iter()
+            .map(|(lower, _)| lower.name().clone())
+            .collect::<PlHashSet<_>>();
+
+        let mut conjunctions = vec![];
+        split_conjunction(predicate, &mut conjunctions);
+        let Some(predicate) = conjunctions
+            .into_iter()
+            .filter_map(|expr| bounds_predicate(expr, &columns))
+            .reduce(|a, b| a.and(b))
+        else {
+            return Ok((0..n_files).collect());
+        };
+
+        let mut series = vec![Series::new(
+            PlSmallStr::from_static(FILE_INDEX),
+            (0..n_files as IdxSize).collect::<Vec<_>>(),
+        )];
+        for (lower, upper) in bounds {
+            let name = lower.name().clone();
+            series.push(lower.with_name(bound_name(&name, false)));
+            series.push(upper.with_name(bound_name(&name, true)));
+        }
+        let files = DataFrame::new(series)?
+            .lazy()
+            .filter(predicate)
+            .select([col(FILE_INDEX)])
+            .collect();
+        // The predicate can fail on the bounds if it fails on the data, in which case the query
+        // raises the error.
+        match files {
+            Ok(files) => Ok(files
+                .column(FILE_INDEX)?
+                .idx()?
+                .into_no_null_iter()
+                .map(|i| i as usize)
+                .collect()),
+            Err(_) => Ok((0..n_files).collect()),
+        }
+    }
+
+    /// Scan the data files, mapping their columns to the schema of the snapshot.
+    fn scan_files(&self, files: Vec<usize>) -> PolarsResult<LazyFrame> {
+        // The files with the same columns are read in a single scan.
+        let mut layouts: Vec<Layout> = vec![];
+        for i in files {
+            let path = &self.snapshot.files[i].path;
+            let mut reader = ParquetReader::new(polars_utils::open_file(path)?);
+            let ids = reader
+                .get_metadata()?
+                .schema()
+                .fields()
+                .iter()
+                .map(|field| {
+                    let info = field.get_field_info();
+                    (info.name.clone(), info.id)
+                })
+                .collect::<Vec<_>>();
+            let arrow_schema = reader.schema()?;
+            let schema = Schema::from_arrow_schema(&arrow_schema);
+            match layouts
+                .iter_mut()
+                .find(|(other_ids, other_schema, _)| *other_ids == ids && *other_schema == schema)
+            {
+                Some((_, _, paths)) => paths.push(path.clone()),
+                None => layouts.push((ids, schema, vec![path.clone()])),
+            }
+        }
+
+        let args = ScanArgsParquet {
+            low_memory: self.args.low_memory,
+            hive_options: HiveOptions {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            glob: false,
+            ..Default::default()
+        };
+        let lfs = layouts
+            .into_iter()
+            .map(|(ids, _, paths)| {
+                let lf = LazyFrame::scan_parquet_files(paths.into(), args.clone())?;
+                Ok(lf.select(self.column_mapping(&ids)))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        concat(
+            lfs,
+            UnionArgs {
+                rechunk: self.args.rechunk,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The columns of the snapshot, selected from a data file with these columns and field ids.
+    /// Files that are written without field ids are matched by the names of the columns.
+    fn column_mapping(&self, ids: &[(PlSmallStr, Option<i32>)]) -> Vec<Expr> {
+        let has_ids = ids.iter().any(|(_, id)| id.is_some());
+        self.snapshot
+            .schema
+            .iter()
+            .zip(&self.snapshot.field_ids)
+            .map(|((name, dtype), field_id)| {
+                let source = ids.iter().find_map(|(source, id)| {
+                    let matches = if has_ids {
+                        *id == Some(*field_id)
+                    } else {
+                        source == name
+                    };
+                    matches.then_some(source)
+                });
+                let expr = match source {
+                    Some(source) => col(source.clone()),
+                    None => Expr::Literal(LiteralValue::Null),
+                };
+                expr.cast(dtype.clone()).alias(name.clone())
+            })
+            .collect()
+    }
+}
+
+impl AnonymousScan for IcebergScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let files = match &scan_opts.predicate {
+            Some(predicate) => self.prune_files(predicate)?,
+            None => (0..self.snapshot.files.len()).collect(),
+        };
+        let mut lf = if files.is_empty() {
+            DataFrame::empty_with_schema(&self.schema).lazy()
+        } else {
+            self.scan_files(files)?
+        };
+
+        if let Some(predicate) = scan_opts.predicate {
+            lf = lf.filter(predicate);
+        }
+        if let Some(columns) = scan_opts.with_columns {
+            lf = lf.select(
+                columns
+                    .iter()
+                    .map(|name| col(name.clone()))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        if let Some(n_rows) = scan_opts.n_rows {
+            lf = lf.limit(n_rows as IdxSize);
+        }
+        lf.collect()
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_predicate_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+}
+
+fn bound_name(name: &str, upper: bool) -> PlSmallStr {
+    let bound = if upper { "UPPER" } else { "LOWER" };
+    format!("__POLARS_ICEBERG_{bound}_{name}").into()
+}
+
+fn split_conjunction<'a>(expr: &'a Expr, out: &mut Vec<&'a Expr>) {
+    match expr {
+        Expr::BinaryExpr {
+            left,
+            op: Operator::And | Operator::LogicalAnd,
+            right,
+        } => {
+            split_conjunction(left, out);
+            split_conjunction(right, out);
+        },
+        _ => out.push(expr),
+    }
+}
+
+/// Rewrite a comparison of a column with a literal to a predicate on the bounds of the column
+/// in the files, which is true if a file may have values for which the comparison is true.
+fn bounds_predicate(expr: &Expr, columns: &PlHashSet<PlSmallStr>) -> Option<Expr> {
+    let Expr::BinaryExpr { left, op, right } = expr else {
+        return None;
+    };
+    let (name, op, value) = match (&**left, &**right) {
+        (Expr::Column(name), Expr::Literal(value)) => (name, *op, value),
+        (Expr::Literal(value), Expr::Column(name)) if op.is_comparison() => {
+            (name, op.swap_operands(), value)
+        },
+        _ => return None,
+    };
+    if !columns.contains(name) || matches!(value, LiteralValue::Null) {
+        return None;
+    }
+
+    let lower = col(bound_name(name, false));
+    let upper = col(bound_name(name, true));
+    let value = Expr::Literal(value.clone());
+    let predicate = match op {
+        Operator::Eq => lower.lt_eq(value.clone()).and(upper.gt_eq(value)),
+        Operator::Lt => lower.lt(value),
+        Operator::LtEq => lower.lt_eq(value),
+        Operator::Gt => upper.gt(value),
+        Operator::GtEq => upper.gt_eq(value),
+        _ => return None,
+    };
+    // Files of which the bounds are unknown are kept.
+    Some(predicate.fill_null(lit(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_predicate() {
+        let columns = PlHashSet::from_iter([PlSmallStr::from_static("a")]);
+        let df = df![
+            bound_name("a", false).as_str() => [Some(0), Some(10), None],
+            bound_name("a", true).as_str() => [Some(5), Some(20), None],
+        ]
+        .unwrap();
+        let prune = |predicate: Expr| -> Vec<bool> {
+            let mut conjunctions = vec![];
+            split_conjunction(&predicate, &mut conjunctions);
+            let predicate = conjunctions
+                .into_iter()
+                .filter_map(|expr| bounds_predicate(expr, &columns))
+                .reduce(|a, b| a.and(b))
+                .unwrap();
+            let out = df.clone().lazy().select([predicate]).collect().unwrap();
+            out.get_columns()[0]
+                .bool()
+                .unwrap()
+                .into_no_null_iter()
+                .collect()
+        };
+
+        assert_eq!(prune(col("a").eq(lit(7))), [false, false, true]);
+        assert_eq!(prune(col("a").gt(lit(5))), [false, true, true]);
+        assert_eq!(prune(lit(5).gt(col("a"))), [true, false, true]);
+        assert_eq!(
+            prune(col("a").gt_eq(lit(5)).and(col("b").eq(lit(1)))),
+            [true, true, true]
+        );
+        assert!(bounds_predicate(&col("b").eq(lit(1)), &columns).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 
 use polars_core::prelude::*;
@@ -16,6 +17,10 @@ pub struct ScanArgsIpc {
     pub cloud_options: Option<CloudOptions>,
     pub hive_options: HiveOptions,
     pub include_file_paths: Option<PlSmallStr>,
+    /// Read at most this number of rows of every file.
+    pub n_rows_per_file: Option<usize>,
+    /// Open and parse at most this number of files at the same time.
+    pub max_concurrent_files: Option<NonZeroUsize>,
 }
 
 impl Default for ScanArgsIpc {
@@ -28,6 +33,8 @@ impl Default for ScanArgsIpc {
             cloud_options: Default::default(),
             hive_options: Default::default(),
             include_file_paths: None,
+            n_rows_per_file: None,
+            max_concurrent_files: None,
         }
     }
 }
@@ -63,6 +70,8 @@ impl LazyFileListReader for LazyIpcReader {
             args.cloud_options,
             args.hive_options,
             args.include_file_paths,
+            args.n_rows_per_file,
+            args.max_concurrent_files,
         )?
         .build()
         .into();
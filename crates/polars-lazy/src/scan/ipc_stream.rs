@@ -0,0 +1,93 @@
+use std::any::Any;
+use std::io::Read;
+use std::sync::Mutex;
+
+use polars_core::prelude::*;
+use polars_io::ipc::IpcStreamReader;
+use polars_io::SerReader;
+
+use crate::prelude::*;
+
+type StreamReader = IpcStreamReader<Box<dyn Read + Send>>;
+
+impl LazyFrame {
+    /// Create a LazyFrame from `reader` in the Arrow IPC stream format, such as a socket or the
+    /// output of [`LazyFrame::sink_ipc_stream`] in another process.
+    ///
+    /// The schema of the stream is read when the LazyFrame is created, the record batches when
+    /// the query runs. On the streaming engine, the batches are processed as they arrive. As
+    /// the stream can't be read again, the query can run only once.
+    pub fn scan_ipc_stream(reader: impl Read + Send + 'static) -> PolarsResult<Self> {
+        let mut reader = IpcStreamReader::new(Box::new(reader) as Box<dyn Read + Send>);
+        let schema = Arc::new(reader.schema()?);
+        LazyFrame::anonymous_scan(
+            Arc::new(IpcStreamScan {
+                schema: schema.clone(),
+                reader: Mutex::new(Some(reader)),
+            }),
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name: "IPC STREAM SCAN",
+                ..Default::default()
+            },
+        )
+    }
+}
+
+struct IpcStreamScan {
+    schema: SchemaRef,
+    reader: Mutex<Option<StreamReader>>,
+}
+
+impl IpcStreamScan {
+    fn take_reader(&self, scan_opts: &AnonymousScanArgs) -> PolarsResult<StreamReader> {
+        let reader = self.reader.lock().unwrap().take().ok_or_else(
+            || polars_err!(ComputeError: "the reader of an IPC stream scan can only be read once"),
+        )?;
+        // The scan must produce the projected columns in the order of the schema.
+        let columns = scan_opts.with_columns.as_deref().map(|columns| {
+            self.schema
+                .iter_names()
+                .filter(|name| columns.contains(name))
+                .map(|name| name.to_string())
+                .collect()
+        });
+        Ok(reader.with_columns(columns).with_n_rows(scan_opts.n_rows))
+    }
+}
+
+impl AnonymousScan for IpcStreamScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        self.take_reader(&scan_opts)?.finish()
+    }
+
+    fn scan_batches(
+        &self,
+        scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>> {
+        let mut reader = self.take_reader(&scan_opts)?.batched()?;
+        Ok(Box::new(std::iter::from_fn(move || {
+            reader.next_batch().transpose()
+        })))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+}
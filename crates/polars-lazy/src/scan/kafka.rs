@@ -0,0 +1,70 @@
+use std::any::Any;
+
+use polars_core::prelude::*;
+use polars_io::kafka::{KafkaOptions, KafkaReader};
+
+use crate::prelude::*;
+
+impl LazyFrame {
+    /// Create a LazyFrame directly from a bounded range of the messages of a Kafka topic, of
+    /// which the payloads are decoded with the schema of the options.
+    ///
+    /// The topic is consumed when the query runs. On the streaming engine, the batches of
+    /// messages are processed as they are consumed, so that micro-batch jobs don't have to
+    /// consume the whole range into memory. No offsets are committed, so running the query
+    /// again consumes the same range, unless its end is [`KafkaEnd::Latest`] and messages were
+    /// produced in the meantime.
+    ///
+    /// [`KafkaEnd::Latest`]: polars_io::kafka::KafkaEnd::Latest
+    pub fn scan_kafka(options: KafkaOptions) -> PolarsResult<Self> {
+        let schema = options.schema.clone();
+        LazyFrame::anonymous_scan(
+            Arc::new(KafkaScan { options }),
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name: "KAFKA SCAN",
+                ..Default::default()
+            },
+        )
+    }
+}
+
+struct KafkaScan {
+    options: KafkaOptions,
+}
+
+impl AnonymousScan for KafkaScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let df = KafkaReader::try_new(self.options.clone())?.finish()?;
+        Ok(match scan_opts.n_rows {
+            Some(n_rows) => df.head(Some(n_rows)),
+            None => df,
+        })
+    }
+
+    fn scan_batches(
+        &self,
+        _scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>> {
+        let mut reader = KafkaReader::try_new(self.options.clone())?;
+        Ok(Box::new(std::iter::from_fn(move || {
+            reader.next_batch().transpose()
+        })))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.options.schema.clone())
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+}
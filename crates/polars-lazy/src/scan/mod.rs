@@ -1,10 +1,32 @@
 pub(super) mod anonymous_scan;
+#[cfg(feature = "avro")]
+pub(super) mod avro;
 #[cfg(feature = "csv")]
 pub(super) mod csv;
+#[cfg(feature = "dataset")]
+pub(super) mod dataset;
+#[cfg(feature = "delta")]
+pub(super) mod delta;
 pub(super) mod file_list_reader;
+#[cfg(feature = "flight")]
+pub(super) mod flight;
+#[cfg(feature = "fwf")]
+pub(super) mod fwf;
+#[cfg(feature = "iceberg")]
+pub(super) mod iceberg;
 #[cfg(feature = "ipc")]
 pub(super) mod ipc;
+#[cfg(feature = "ipc_streaming")]
+pub(super) mod ipc_stream;
+#[cfg(feature = "kafka")]
+pub(super) mod kafka;
 #[cfg(feature = "json")]
 pub(super) mod ndjson;
+#[cfg(feature = "orc")]
+pub(super) mod orc;
 #[cfg(feature = "parquet")]
 pub(super) mod parquet;
+#[cfg(any(feature = "csv", feature = "json"))]
+pub(super) mod reader;
+#[cfg(feature = "streaming")]
+pub(super) mod source;
@@ -25,6 +25,8 @@ pub struct LazyJsonLineReader {
     pub(crate) ignore_errors: bool,
     pub(crate) include_file_paths: Option<PlSmallStr>,
     pub(crate) cloud_options: Option<CloudOptions>,
+    pub(crate) n_rows_per_file: Option<usize>,
+    pub(crate) max_concurrent_files: Option<NonZeroUsize>,
 }
 
 impl LazyJsonLineReader {
@@ -46,6 +48,8 @@ impl LazyJsonLineReader {
             n_rows: None,
             include_file_paths: None,
             cloud_options: None,
+            n_rows_per_file: None,
+            max_concurrent_files: None,
         }
     }
 
@@ -118,6 +122,18 @@ impl LazyJsonLineReader {
         self.include_file_paths = include_file_paths;
         self
     }
+
+    /// Read at most this number of rows of every file, e.g. to sample every file of a glob.
+    pub fn with_n_rows_per_file(mut self, n_rows_per_file: Option<usize>) -> Self {
+        self.n_rows_per_file = n_rows_per_file;
+        self
+    }
+
+    /// Open and parse at most this number of files at the same time.
+    pub fn with_max_concurrent_files(mut self, max_concurrent_files: Option<NonZeroUsize>) -> Self {
+        self.max_concurrent_files = max_concurrent_files;
+        self
+    }
 }
 
 impl LazyFileListReader for LazyJsonLineReader {
@@ -137,6 +153,8 @@ impl LazyFileListReader for LazyJsonLineReader {
             },
             glob: true,
             include_file_paths: self.include_file_paths,
+            n_rows_per_file: self.n_rows_per_file,
+            max_concurrent_files: self.max_concurrent_files,
         };
 
         let options = NDJsonReadOptions {
@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_expr::{create_physical_expr, ExpressionConversionState};
+use polars_io::orc::OrcReader;
+use polars_io::{RowIndex, SerReader};
+
+use crate::prelude::*;
+
+#[derive(Clone, Default)]
+pub struct ScanArgsOrc {
+    pub n_rows: Option<usize>,
+    pub rechunk: bool,
+    pub row_index: Option<RowIndex>,
+}
+
+/// Scans an ORC file with the projections, predicates and slices of the query pushed down to
+/// the reader.
+struct OrcScan {
+    path: PathBuf,
+    rechunk: bool,
+}
+
+impl AnonymousScan for OrcScan {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let mut columns = scan_opts
+            .with_columns
+            .as_deref()
+            .map(|columns| columns.iter().map(|c| c.to_string()).collect::<Vec<_>>());
+
+        let predicate = match scan_opts.predicate {
+            Some(predicate) => {
+                // The columns of the predicate are read even if they aren't projected.
+                if let Some(columns) = &mut columns {
+                    for name in expr_to_leaf_column_names(&predicate) {
+                        if !columns.iter().any(|c| c == name.as_str()) {
+                            columns.push(name.to_string());
+                        }
+                    }
+                }
+                let mut arena = Arena::with_capacity(16);
+                let expr = to_expr_ir(predicate, &mut arena)?;
+                let phys_expr = create_physical_expr(
+                    &expr,
+                    Context::Default,
+                    &arena,
+                    Some(&scan_opts.schema),
+                    &mut ExpressionConversionState::new(true, 0),
+                )?;
+                Some(phys_expr_to_io_expr(phys_expr))
+            },
+            None => None,
+        };
+
+        let file = polars_utils::open_file(&self.path)?;
+        let df = OrcReader::new(file)
+            .with_columns(columns)
+            .with_predicate(predicate)
+            .with_n_rows(scan_opts.n_rows)
+            .set_rechunk(self.rechunk)
+            .finish()?;
+
+        match scan_opts.with_columns {
+            Some(with_columns) if with_columns.len() < df.width() => {
+                df.select(with_columns.iter().cloned())
+            },
+            _ => Ok(df),
+        }
+    }
+
+    fn allows_predicate_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+}
+
+impl LazyFrame {
+    /// Create a LazyFrame directly from an ORC scan.
+    pub fn scan_orc(path: impl AsRef<Path>, args: ScanArgsOrc) -> PolarsResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let schema = OrcReader::new(polars_utils::open_file(&path)?).schema()?;
+
+        let function = Arc::new(OrcScan {
+            path,
+            rechunk: args.rechunk,
+        });
+        let args = ScanArgsAnonymous {
+            schema: Some(schema),
+            n_rows: args.n_rows,
+            row_index: args.row_index,
+            name: "ORC SCAN",
+            ..ScanArgsAnonymous::default()
+        };
+        Self::anonymous_scan(function, args)
+    }
+}
@@ -1,9 +1,11 @@
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 
 use polars_core::prelude::*;
 use polars_io::cloud::CloudOptions;
+use polars_io::parquet::encryption::ParquetDecryption;
 use polars_io::parquet::read::ParallelStrategy;
-use polars_io::{HiveOptions, RowIndex};
+use polars_io::{HiveOptions, RowIndex, SchemaEvolution};
 
 use crate::prelude::*;
 
@@ -21,6 +23,14 @@ pub struct ScanArgsParquet {
     /// Expand path given via globbing rules.
     pub glob: bool,
     pub include_file_paths: Option<PlSmallStr>,
+    /// Keys to decrypt encrypted files.
+    pub decryption: Option<ParquetDecryption>,
+    /// How the schemas of the files may differ.
+    pub schema_evolution: SchemaEvolution,
+    /// Read at most this number of rows of every file.
+    pub n_rows_per_file: Option<usize>,
+    /// Open and parse at most this number of files at the same time.
+    pub max_concurrent_files: Option<NonZeroUsize>,
 }
 
 impl Default for ScanArgsParquet {
@@ -37,6 +47,10 @@ impl Default for ScanArgsParquet {
             cache: true,
             glob: true,
             include_file_paths: None,
+            decryption: None,
+            schema_evolution: SchemaEvolution::Strict,
+            n_rows_per_file: None,
+            max_concurrent_files: None,
         }
     }
 }
@@ -59,6 +73,14 @@ impl LazyParquetReader {
 impl LazyFileListReader for LazyParquetReader {
     /// Get the final [LazyFrame].
     fn finish(self) -> PolarsResult<LazyFrame> {
+        let schema_evolution = self.args.schema_evolution;
+        if schema_evolution != SchemaEvolution::Strict {
+            return self.finish_with_schema_evolution(schema_evolution, |mut reader| {
+                reader.args.schema_evolution = SchemaEvolution::Strict;
+                reader.finish()
+            });
+        }
+
         let row_index = self.args.row_index;
 
         let mut lf: LazyFrame = DslBuilder::scan_parquet(
@@ -74,6 +96,9 @@ impl LazyFileListReader for LazyParquetReader {
             self.args.hive_options,
             self.args.glob,
             self.args.include_file_paths,
+            self.args.decryption,
+            self.args.n_rows_per_file,
+            self.args.max_concurrent_files,
         )?
         .build()
         .into();
@@ -0,0 +1,131 @@
+use std::any::Any;
+use std::sync::Mutex;
+
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+#[cfg(feature = "csv")]
+use polars_io::csv::read::CsvStreamReader;
+#[cfg(feature = "json")]
+use polars_io::ndjson::JsonLineStreamReader;
+
+use crate::prelude::*;
+
+type Batches = Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>;
+
+impl LazyFrame {
+    /// Create a LazyFrame from a CSV file that is read from an arbitrary reader, such as stdin.
+    ///
+    /// The first block of the reader is read when the LazyFrame is created, to resolve the
+    /// schema. The rest is read when the query runs, in blocks of complete lines, and on the
+    /// streaming engine the blocks are processed as they are read, so that the input doesn't have
+    /// to fit into memory. A reader can only be read once, so the query can only run once.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use polars_core::prelude::*;
+    /// use polars_io::csv::read::{CsvReadOptions, CsvStreamReader};
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example() -> PolarsResult<DataFrame> {
+    ///     let reader = CsvStreamReader::new(Box::new(std::io::stdin()), CsvReadOptions::default());
+    ///     LazyFrame::scan_csv_from_reader(reader)?
+    ///         .filter(col("a").gt(lit(1)))
+    ///         .collect()
+    /// }
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn scan_csv_from_reader(mut reader: CsvStreamReader) -> PolarsResult<Self> {
+        let schema = reader.schema()?;
+        let batches = std::iter::from_fn(move || reader.next_batch().transpose());
+        ReaderScan::new(schema, Box::new(batches)).into_lazy_frame("CSV READER SCAN")
+    }
+
+    /// Create a LazyFrame from newline-delimited JSON that is read from an arbitrary reader,
+    /// such as stdin.
+    ///
+    /// The first block of the reader is read when the LazyFrame is created, to resolve the
+    /// schema. The rest is read when the query runs, in blocks of complete lines, and on the
+    /// streaming engine the blocks are processed as they are read, so that the input doesn't have
+    /// to fit into memory. A reader can only be read once, so the query can only run once.
+    #[cfg(feature = "json")]
+    pub fn scan_ndjson_from_reader(mut reader: JsonLineStreamReader) -> PolarsResult<Self> {
+        let schema = reader.schema()?;
+        let batches = std::iter::from_fn(move || reader.next_batch().transpose());
+        ReaderScan::new(schema, Box::new(batches)).into_lazy_frame("NDJSON READER SCAN")
+    }
+}
+
+struct ReaderScan {
+    schema: SchemaRef,
+    /// The batches of the reader, which are taken by the run of the query.
+    batches: Mutex<Option<Batches>>,
+}
+
+impl ReaderScan {
+    fn new(schema: SchemaRef, batches: Batches) -> Self {
+        Self {
+            schema,
+            batches: Mutex::new(Some(batches)),
+        }
+    }
+
+    fn into_lazy_frame(self, name: &'static str) -> PolarsResult<LazyFrame> {
+        let schema = self.schema.clone();
+        LazyFrame::anonymous_scan(
+            Arc::new(self),
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn batches(&self) -> PolarsResult<Batches> {
+        self.batches.lock().unwrap().take().ok_or_else(
+            || polars_err!(ComputeError: "the reader of this scan can only be read once"),
+        )
+    }
+}
+
+impl AnonymousScan for ReaderScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let n_rows = scan_opts.n_rows.unwrap_or(usize::MAX);
+        let mut batches = self.batches()?;
+        let mut dfs = vec![];
+        let mut height = 0;
+        // Stop reading once the slice is read, as the rest of the input may never end.
+        while height < n_rows {
+            let Some(df) = batches.next().transpose()? else {
+                break;
+            };
+            let df = df.head(Some(n_rows - height));
+            height += df.height();
+            dfs.push(df);
+        }
+        if dfs.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&self.schema));
+        }
+        Ok(accumulate_dataframes_vertical_unchecked(dfs))
+    }
+
+    fn scan_batches(&self, _scan_opts: AnonymousScanArgs) -> PolarsResult<Batches> {
+        self.batches()
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+}
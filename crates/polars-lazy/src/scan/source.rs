@@ -0,0 +1,119 @@
+use std::any::Any;
+use std::collections::VecDeque;
+
+use polars_core::config::verbose;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_expr::state::ExecutionState;
+pub use polars_pipe::operators::{
+    next_chunk_index, DataChunk, PExecutionContext, Source, SourceResult,
+};
+
+use crate::prelude::*;
+
+type CreateSource = dyn Fn() -> PolarsResult<Box<dyn Source>> + Send + Sync;
+
+impl LazyFrame {
+    /// Create a LazyFrame from a custom streaming [`Source`], of which `schema` is the schema of
+    /// the produced chunks.
+    ///
+    /// The source is created with `create_source` every time the query runs. On the streaming
+    /// engine its chunks are processed as they are produced, the in-memory engine collects them
+    /// first.
+    pub fn scan_source<F>(schema: SchemaRef, create_source: F) -> PolarsResult<Self>
+    where
+        F: Fn() -> PolarsResult<Box<dyn Source>> + Send + Sync + 'static,
+    {
+        LazyFrame::anonymous_scan(
+            Arc::new(SourceScan {
+                schema: schema.clone(),
+                create_source: Box::new(create_source),
+            }),
+            ScanArgsAnonymous {
+                schema: Some(schema),
+                name: "SOURCE SCAN",
+                ..Default::default()
+            },
+        )
+    }
+}
+
+struct SourceScan {
+    schema: SchemaRef,
+    create_source: Box<CreateSource>,
+}
+
+/// Pulls the chunks of a [`Source`] in the order of their chunk indices.
+struct SourceBatches {
+    source: Box<dyn Source>,
+    context: PExecutionContext,
+    pending: VecDeque<DataFrame>,
+    finished: bool,
+}
+
+impl Iterator for SourceBatches {
+    type Item = PolarsResult<DataFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(df) = self.pending.pop_front() {
+                return Some(Ok(df));
+            }
+            if self.finished {
+                return None;
+            }
+            match self.source.get_batches(&self.context) {
+                Ok(SourceResult::Finished) => self.finished = true,
+                Ok(SourceResult::GotMoreData(mut chunks)) => {
+                    chunks.sort_unstable_by_key(|chunk| chunk.chunk_index);
+                    self.pending
+                        .extend(chunks.into_iter().map(|chunk| chunk.data));
+                },
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                },
+            }
+        }
+    }
+}
+
+impl SourceScan {
+    fn batches(&self) -> PolarsResult<SourceBatches> {
+        Ok(SourceBatches {
+            source: (self.create_source)()?,
+            context: PExecutionContext::new(ExecutionState::new(), verbose()),
+            pending: VecDeque::new(),
+            finished: false,
+        })
+    }
+}
+
+impl AnonymousScan for SourceScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, _scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let dfs = self.batches()?.collect::<PolarsResult<Vec<_>>>()?;
+        if dfs.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&self.schema));
+        }
+        Ok(accumulate_dataframes_vertical_unchecked(dfs))
+    }
+
+    fn scan_batches(
+        &self,
+        _scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>> {
+        Ok(Box::new(self.batches()?))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+}
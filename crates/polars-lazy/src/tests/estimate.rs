@@ -0,0 +1,69 @@
+use super::*;
+
+#[test]
+fn test_estimate_dataframe() -> PolarsResult<()> {
+    let df = df![
+        "a" => (0..100i64).collect::<Vec<_>>(),
+        "b" => (0..100i32).collect::<Vec<_>>(),
+    ]?;
+    let estimate = df.clone().lazy().estimate()?;
+    assert_eq!(estimate.rows, Some(100));
+    assert!(estimate.exact);
+    assert_eq!(estimate.bytes, Some(df.estimated_size()));
+
+    let estimate = df
+        .clone()
+        .lazy()
+        .slice(90, 20)
+        .select([col("a")])
+        .estimate()?;
+    assert_eq!(estimate.rows, Some(10));
+    assert_eq!(estimate.bytes, Some(80));
+    assert!(estimate.exact);
+
+    let estimate = df.lazy().select([col("a").sum()]).estimate()?;
+    assert_eq!(estimate.rows, Some(1));
+    assert!(estimate.exact);
+    Ok(())
+}
+
+#[test]
+fn test_estimate_selectivity() -> PolarsResult<()> {
+    let df = df!["a" => (0..1000i64).collect::<Vec<_>>()]?;
+    let estimate = df
+        .clone()
+        .lazy()
+        .filter(col("a").eq(lit(1i64)))
+        .estimate()?;
+    assert_eq!(estimate.rows, Some(100));
+    assert!(!estimate.exact);
+
+    // the selectivities of a conjunction multiply
+    let estimate = df
+        .clone()
+        .lazy()
+        .filter(col("a").gt(lit(1i64)).and(col("a").eq(lit(1i64))))
+        .estimate()?;
+    assert_eq!(estimate.rows, Some(33));
+
+    let estimate = df
+        .lazy()
+        .group_by([col("a")])
+        .agg([col("a").count().alias("n")])
+        .estimate()?;
+    assert_eq!(estimate.rows, Some(100));
+    assert!(!estimate.exact);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn test_estimate_parquet_metadata() -> PolarsResult<()> {
+    let _guard = SINGLE_LOCK.lock().unwrap();
+    let expected = scan_foods_parquet(false).collect()?;
+    let estimate = scan_foods_parquet(false).estimate()?;
+    assert_eq!(estimate.rows, Some(expected.height()));
+    assert!(estimate.exact);
+    assert!(estimate.bytes.unwrap() > 0);
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use super::*;
+
+/// The line with the id of the node that is annotated with `annotation`.
+fn annotated_line<'a>(explain: &'a str, annotation: &str) -> Option<&'a str> {
+    explain
+        .lines()
+        .find(|line| line.trim_start().starts_with("[p") && line.contains(annotation))
+}
+
+#[test]
+fn test_explain_analyze() -> PolarsResult<()> {
+    let df = df![
+        "g" => (0..100i64).map(|v| v % 3).collect::<Vec<_>>(),
+        "a" => (0..100i64).collect::<Vec<_>>(),
+    ]?;
+    let q = df
+        .lazy()
+        .filter(col("a").lt(lit(10)))
+        .group_by([col("g")])
+        .agg([col("a").sum()]);
+
+    let (out, explain) = q.explain_analyze()?;
+    assert_eq!(out.shape(), (3, 2));
+
+    // The filter is pushed into the scan, which produces 10 rows, the group by 3.
+    let root = explain.lines().next().unwrap();
+    assert!(
+        root.starts_with("[p") && root.contains("rows: 3,"),
+        "{explain}"
+    );
+    let scan = annotated_line(&explain, "rows: 10,").expect(&explain);
+    assert!(scan.contains("time: ") && scan.contains("memory: "));
+    Ok(())
+}
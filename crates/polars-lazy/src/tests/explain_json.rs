@@ -0,0 +1,60 @@
+use serde_json::Value;
+
+use super::*;
+
+/// The nodes of the plan, from the root to the leaves.
+fn nodes<'a>(node: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(node);
+    for input in node["inputs"].as_array().unwrap() {
+        nodes(input, out);
+    }
+}
+
+#[test]
+fn test_explain_json_pushdown() -> PolarsResult<()> {
+    let q = LazyCsvReader::new("../../examples/datasets/foods1.csv")
+        .finish()?
+        .filter(col("calories").gt(lit(100)))
+        .select([col("category"), col("fats_g")]);
+    let plan: Value = serde_json::from_str(&q.explain_json()?).unwrap();
+    assert_eq!(plan["schema"], serde_json::json!(["category", "fats_g"]));
+
+    let mut all = vec![];
+    nodes(&plan, &mut all);
+    let scan = all.last().unwrap();
+    assert!(scan["sources"][0].as_str().unwrap().ends_with("foods1.csv"));
+    assert!(scan["predicate"].as_str().unwrap().contains("calories"));
+    let projection = scan["projection"].as_array().unwrap();
+    assert_eq!(projection.len(), 3);
+    assert!(projection.contains(&"calories".into()));
+
+    // The filter is pushed into the scan.
+    assert!(!all.iter().any(|node| node["kind"] == "selection"));
+
+    // The ids are the ones of the text explain.
+    let explain = q.explain_with_node_ids(true)?;
+    for node in all {
+        let id = node["id"].as_str().unwrap();
+        assert!(explain.contains(&format!("[{id}]")));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_explain_json_join() -> PolarsResult<()> {
+    let right = df!["b" => ["a", "b"], "d" => [10, 20]]?.lazy();
+    let q = load_df()
+        .lazy()
+        .join(right, [col("b")], [col("b")], JoinType::Left.into())
+        .group_by([col("b")])
+        .agg([col("d").sum()]);
+    let plan: Value = serde_json::from_str(&q.explain_json()?).unwrap();
+
+    assert_eq!(plan["kind"], "aggregate");
+    assert_eq!(plan["keys"], serde_json::json!(["col(\"b\")"]));
+    let join = &plan["inputs"][0];
+    assert_eq!(join["kind"], "join");
+    assert_eq!(join["how"], "LEFT");
+    assert_eq!(join["inputs"].as_array().unwrap().len(), 2);
+    Ok(())
+}
@@ -420,6 +420,8 @@ fn test_ipc_globbing() -> PolarsResult<()> {
             cloud_options: None,
             hive_options: Default::default(),
             include_file_paths: None,
+            n_rows_per_file: None,
+            max_concurrent_files: None,
         },
     )?
     .collect()?;
@@ -490,6 +492,237 @@ fn test_ndjson_globbing() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_scan_csv_from_reader() -> PolarsResult<()> {
+    let file = std::fs::File::open(FOODS_CSV)?;
+    let reader = CsvStreamReader::new(Box::new(file), CsvReadOptions::default());
+    let lf = LazyFrame::scan_csv_from_reader(reader)?;
+
+    let expected = scan_foods_csv()
+        .filter(col("calories").gt(lit(100)))
+        .collect()?;
+    let df = lf.clone().filter(col("calories").gt(lit(100))).collect()?;
+    assert_eq!(df, expected);
+
+    // The reader was read by the first run.
+    assert!(lf.collect().is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "dataset")]
+fn test_dataset_mixed_fragments() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_dataset_mixed_fragments");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("year=2023"))?;
+    std::fs::create_dir_all(dir.join("year=2024"))?;
+
+    let mut df = df![
+        "x" => [1i32, 2],
+        "y" => ["a", "b"],
+    ]?;
+    ParquetWriter::new(std::fs::File::create(dir.join("year=2023/0.parquet"))?).finish(&mut df)?;
+    std::fs::write(dir.join("year=2024/0.csv"), "x,z\n10,1.5\n20,2.5\n")?;
+    std::fs::write(dir.join("year=2024/_SUCCESS"), "done")?;
+
+    let dataset = Dataset::discover(&dir, DatasetOptions::default())?;
+    assert_eq!(dataset.fragments().len(), 2);
+    assert_eq!(
+        dataset.schema().as_ref(),
+        &Schema::from_iter([
+            Field::new("x".into(), DataType::Int64),
+            Field::new("y".into(), DataType::String),
+            Field::new("z".into(), DataType::Float64),
+            Field::new("year".into(), DataType::Int64),
+        ])
+    );
+
+    // Pruned by the partition values.
+    let pruned = dataset.clone().filter(col("year").eq(lit(2024)));
+    assert_eq!(pruned.pruned_fragments()?.len(), 1);
+    // Pruned by the statistics of the Parquet fragment.
+    let pruned = dataset.clone().filter(col("x").gt(lit(5)));
+    assert_eq!(pruned.pruned_fragments()?.len(), 1);
+    let out = pruned
+        .to_lazy()?
+        .sort(["x"], Default::default())
+        .collect()?;
+    assert!(out.equals_missing(&df![
+        "x" => [10i64, 20],
+        "y" => [None::<&str>, None],
+        "z" => [1.5, 2.5],
+        "year" => [2024i64, 2024],
+    ]?));
+
+    let out = dataset.to_lazy()?.select([col("x").sum()]).collect()?;
+    assert_eq!(out.column("x")?.i64()?.get(0), Some(33));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_scan_csv_hive_partitioned() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_csv_hive_partitioned");
+    let _ = std::fs::remove_dir_all(&dir);
+    for (part, file) in [("1", "a,b\n1,x\n2,y\n"), ("2", "a,b\n3,z\n")] {
+        std::fs::create_dir_all(dir.join(format!("part={part}")))?;
+        std::fs::write(dir.join(format!("part={part}/0.csv")), file)?;
+    }
+
+    let lf = LazyCsvReader::new(&dir)
+        .with_hive_options(polars_io::HiveOptions {
+            enabled: Some(true),
+            ..Default::default()
+        })
+        .finish()?;
+
+    let q = lf.clone().filter(col("part").eq(lit(2)));
+    let expected = df![
+        "a" => [3i64],
+        "b" => ["z"],
+        "part" => [2i64],
+    ]?;
+    assert!(q.clone().collect()?.equals(&expected));
+    #[cfg(feature = "streaming")]
+    assert!(q.with_streaming(true).collect()?.equals(&expected));
+
+    // Only the partition column is projected.
+    let q = lf.select([col("part")]);
+    let expected = df!["part" => [1i64, 1, 2]]?;
+    assert!(q.clone().collect()?.equals(&expected));
+    #[cfg(feature = "streaming")]
+    assert!(q.with_streaming(true).collect()?.equals(&expected));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_scan_csv_schema_evolution() -> PolarsResult<()> {
+    use polars_io::SchemaEvolution;
+
+    let dir = std::env::temp_dir().join("polars_test_scan_csv_schema_evolution");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("0.csv"), "a,b\n1,x\n2,y\n")?;
+    std::fs::write(dir.join("1.csv"), "a,b,c\n3.5,z,true\n")?;
+
+    // The dtype of `a` differs between the files.
+    let out = LazyCsvReader::new(&dir)
+        .with_schema_evolution(SchemaEvolution::AllowMissingColumns)
+        .finish();
+    assert!(matches!(out, Err(PolarsError::SchemaMismatch(_))));
+
+    let out = LazyCsvReader::new(&dir)
+        .with_schema_evolution(SchemaEvolution::Relaxed)
+        .with_row_index(Some(RowIndex {
+            name: "index".into(),
+            offset: 0,
+        }))
+        .finish()?
+        .collect()?;
+    let expected = df![
+        "index" => [0 as IdxSize, 1, 2],
+        "a" => [1.0, 2.0, 3.5],
+        "b" => ["x", "y", "z"],
+        "c" => [None, None, Some(true)],
+    ]?;
+    assert!(out.equals_missing(&expected));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_scan_csv_n_rows_per_file() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_csv_n_rows_per_file");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    for (i, file) in ["a\n1\n2\n3\n", "a\n4\n", "a\n5\n6\n"].iter().enumerate() {
+        std::fs::write(dir.join(format!("{i}.csv")), file)?;
+    }
+
+    let q = LazyCsvReader::new(&dir)
+        .with_n_rows_per_file(Some(2))
+        .with_max_concurrent_files(std::num::NonZeroUsize::new(1))
+        .with_row_index(Some(RowIndex {
+            name: "index".into(),
+            offset: 0,
+        }))
+        .finish()?;
+    let expected = df![
+        "index" => [0 as IdxSize, 1, 2, 3, 4],
+        "a" => [1i64, 2, 4, 5, 6],
+    ]?;
+    assert!(q.clone().collect()?.equals(&expected));
+    #[cfg(feature = "streaming")]
+    assert!(q.clone().with_streaming(true).collect()?.equals(&expected));
+
+    // The limit of the query is applied after the limit per file.
+    let out = q.filter(col("a").gt(lit(1))).limit(2).collect()?;
+    assert_eq!(out.column("a")?.i64()?.to_vec(), [Some(2), Some(4)]);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "csv", feature = "parquet", feature = "streaming"))]
+fn test_sink_parquet_checkpointed() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_sink_parquet_checkpointed");
+    let _ = std::fs::remove_dir_all(&dir);
+    let input = dir.join("input");
+    let output = dir.join("output");
+    std::fs::create_dir_all(&input)?;
+    for (i, file) in ["a\n1\n2\n3\n", "a\n4\n", "a\n5\n6\n"].iter().enumerate() {
+        std::fs::write(input.join(format!("{i}.csv")), file)?;
+    }
+    let q = LazyCsvReader::new(&input)
+        .finish()?
+        .filter(col("a").neq(lit(2)))
+        .with_column((col("a") * lit(10)).alias("b"));
+    let read_output = || -> PolarsResult<DataFrame> {
+        LazyFrame::scan_parquet(output.join("*.parquet"), Default::default())?
+            .sort(["a"], Default::default())
+            .collect()
+    };
+    let expected = df![
+        "a" => [1i64, 3, 4, 5, 6],
+        "b" => [10i64, 30, 40, 50, 60],
+    ]?;
+
+    q.clone()
+        .sink_parquet_checkpointed(&output, Default::default())?;
+    assert!(read_output()?.equals(&expected));
+
+    // Interrupt after the first file: the other files are written when the query is resumed.
+    let checkpoint = std::fs::read_to_string(output.join("_checkpoint"))?;
+    assert_eq!(checkpoint.lines().count(), 3);
+    std::fs::write(
+        output.join("_checkpoint"),
+        format!("{}\n", checkpoint.lines().next().unwrap()),
+    )?;
+    std::fs::remove_file(output.join("part-00000002.parquet"))?;
+    std::fs::write(output.join("part-00000002.parquet.tmp"), "partial")?;
+    q.clone()
+        .sink_parquet_checkpointed(&output, Default::default())?;
+    assert!(read_output()?.equals(&expected));
+    assert!(!output.join("part-00000002.parquet.tmp").exists());
+
+    // Aggregations depend on all input files.
+    let q = q.select([col("a").sum()]);
+    assert!(q
+        .sink_parquet_checkpointed(&output, Default::default())
+        .is_err());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
 #[test]
 pub fn test_simple_slice() -> PolarsResult<()> {
     let _guard = SINGLE_LOCK.lock().unwrap();
@@ -687,6 +920,62 @@ fn scan_anonymous_fn_with_options() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn scan_anonymous_fn_with_partial_pushdowns() -> PolarsResult<()> {
+    struct MyScan {}
+
+    impl AnonymousScan for MyScan {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn allows_predicate_pushdown(&self) -> bool {
+            true
+        }
+
+        fn allows_projection_pushdown(&self) -> bool {
+            true
+        }
+
+        fn scan(&self, _scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+            unreachable!()
+        }
+
+        // Only the projection is applied, the predicate and the slice are left to Polars.
+        fn scan_with_pushdowns(
+            &self,
+            scan_opts: AnonymousScanArgs,
+        ) -> PolarsResult<(DataFrame, AppliedPushdowns)> {
+            assert!(scan_opts.predicate.is_some());
+            let columns = scan_opts.with_columns.unwrap();
+            assert_eq!(columns.len(), 2);
+            let df = fruits_cars().select(columns.iter().cloned())?;
+            let applied = AppliedPushdowns {
+                projection: true,
+                ..Default::default()
+            };
+            Ok((df, applied))
+        }
+    }
+
+    let args = ScanArgsAnonymous {
+        schema: Some(Arc::new(fruits_cars().schema())),
+        ..ScanArgsAnonymous::default()
+    };
+    let df = LazyFrame::anonymous_scan(Arc::new(MyScan {}), args)?
+        .filter(col("A").gt(lit(1)))
+        .select([col("A"), col("fruits")])
+        .collect()?;
+
+    let expected = fruits_cars()
+        .lazy()
+        .filter(col("A").gt(lit(1)))
+        .select([col("A"), col("fruits")])
+        .collect()?;
+    assert!(df.equals(&expected));
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "dtype-full")]
 fn scan_small_dtypes() -> PolarsResult<()> {
@@ -711,3 +1000,42 @@ fn scan_small_dtypes() -> PolarsResult<()> {
     }
     Ok(())
 }
+
+#[test]
+#[cfg(all(feature = "parquet", feature = "ipc", feature = "streaming"))]
+fn test_sink_multiple() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_sink_multiple");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let parquet = dir.join("out.parquet");
+    let ipc = dir.join("out.ipc");
+
+    let df = df![
+        "a" => (0..1000i64).collect::<Vec<_>>(),
+    ]?;
+    let q = df
+        .clone()
+        .lazy()
+        .with_column((col("a") * lit(2)).alias("b"));
+    q.clone().sink_multiple([
+        SinkType::File {
+            path: Arc::new(parquet.clone()),
+            file_type: FileType::Parquet(Default::default()),
+        },
+        SinkType::File {
+            path: Arc::new(ipc.clone()),
+            file_type: FileType::Ipc(Default::default()),
+        },
+    ])?;
+
+    let expected = q.clone().collect()?;
+    let out = LazyFrame::scan_parquet(&parquet, Default::default())?.collect()?;
+    assert!(out.equals(&expected));
+    let out = LazyFrame::scan_ipc(&ipc, Default::default())?.collect()?;
+    assert!(out.equals(&expected));
+
+    assert!(q.sink_multiple([SinkType::Memory]).is_err());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
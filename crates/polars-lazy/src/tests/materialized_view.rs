@@ -0,0 +1,78 @@
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+use super::*;
+
+fn write_csv(path: &Path, range: std::ops::Range<i64>, append: bool) -> PolarsResult<()> {
+    let mut df = df![
+        "a" => range.clone().collect::<Vec<_>>(),
+        "g" => range.map(|i| i % 3).collect::<Vec<_>>(),
+    ]?;
+    if append {
+        let file = OpenOptions::new().append(true).open(path)?;
+        CsvWriter::new(file).include_header(false).finish(&mut df)
+    } else {
+        CsvWriter::new(File::create(path)?).finish(&mut df)
+    }
+}
+
+#[test]
+fn test_materialized_view_refresh() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_materialized_view_refresh");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    write_csv(&dir.join("part-1.csv"), 0..10, false)?;
+    let scan = || LazyCsvReader::new(dir.join("*.csv")).finish().unwrap();
+
+    // The queries are created again to compare the views with, as a scan only expands its glob
+    // once.
+    let queries = || {
+        let rows = scan()
+            .filter((col("a") % lit(2i64)).eq(lit(0i64)))
+            .with_column((col("a") * lit(10i64)).alias("b"));
+        let groups = scan().group_by_stable([col("g")]).agg([
+            col("a").sum(),
+            col("a").max().alias("max"),
+            col("a").first().alias("first"),
+            len(),
+        ]);
+        let total = scan().select([col("a").sum(), col("a").min().alias("min")]);
+        [rows, groups, total]
+    };
+    let mut views = queries().map(|q| q.materialize().unwrap());
+    for (view, q) in views.iter().zip(queries()) {
+        assert_eq!(view.result(), &q.collect()?);
+    }
+
+    // a file is added and rows are appended to the other
+    write_csv(&dir.join("part-2.csv"), 10..25, false)?;
+    write_csv(&dir.join("part-1.csv"), 25..30, true)?;
+    for (view, q) in views.iter_mut().zip(queries()) {
+        view.refresh()?;
+        let expected = q.collect()?;
+        assert_eq!(
+            view.lazy().sort(["a"], Default::default()).collect()?,
+            expected.lazy().sort(["a"], Default::default()).collect()?
+        );
+    }
+    assert_eq!(views[1].result().column("len")?.idx()?.sum(), Some(30));
+
+    // without new rows, the result doesn't change
+    let before = views[1].result().clone();
+    views[1].refresh()?;
+    assert_eq!(views[1].result(), &before);
+    Ok(())
+}
+
+#[test]
+fn test_materialized_view_not_incremental() -> PolarsResult<()> {
+    let q = scan_foods_csv().sort(["calories"], Default::default());
+    assert!(q.materialize().is_err());
+    let q = scan_foods_csv()
+        .group_by([col("category")])
+        .agg([col("calories").mean()]);
+    assert!(q.materialize().is_err());
+    let q = scan_foods_csv().with_column((col("calories") - col("calories").mean()).alias("d"));
+    assert!(q.materialize().is_err());
+    Ok(())
+}
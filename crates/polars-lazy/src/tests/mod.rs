@@ -2,18 +2,31 @@ mod aggregations;
 mod arity;
 #[cfg(all(feature = "strings", feature = "cse"))]
 mod cse;
+mod estimate;
+mod explain_analyze;
+#[cfg(all(feature = "explain_json", feature = "csv"))]
+mod explain_json;
 #[cfg(feature = "parquet")]
 mod io;
 mod logical;
+#[cfg(all(feature = "materialized_view", feature = "csv"))]
+mod materialized_view;
 mod optimization_checks;
 #[cfg(all(feature = "strings", feature = "cse"))]
 mod pdsh;
+#[cfg(feature = "persist")]
+mod persist;
 mod predicate_queries;
+mod prepared;
 mod projection_queries;
 mod queries;
+#[cfg(all(feature = "result_cache", feature = "csv"))]
+mod result_cache;
 mod schema;
 #[cfg(feature = "streaming")]
 mod streaming;
+#[cfg(feature = "substrait")]
+mod substrait;
 
 fn get_arenas() -> (Arena<AExpr>, Arena<IR>) {
     let expr_arena = Arena::with_capacity(16);
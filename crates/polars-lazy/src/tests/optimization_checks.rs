@@ -521,6 +521,43 @@ fn test_slice_at_scan_group_by() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "csv")]
+fn test_pushdown_through_udf_with_properties() -> PolarsResult<()> {
+    fn add_one(mut df: DataFrame) -> PolarsResult<DataFrame> {
+        let one = Series::new("one".into(), vec![1i32; df.height()]);
+        df.with_column(one)?;
+        Ok(df)
+    }
+    let map = |properties: UdfProperties| {
+        let schema = Arc::new(|input: &Schema| {
+            let mut schema = input.clone();
+            schema.with_column("one".into(), DataType::Int32);
+            Ok(Arc::new(schema))
+        });
+        scan_foods_csv().map_with_properties(add_one, properties, Some(schema), None)
+    };
+    let all = UdfProperties::ELEMENTWISE | UdfProperties::MAINTAINS_ORDER | UdfProperties::PURE;
+
+    let q = map(all).filter(col("calories").lt(lit(50)));
+    assert!(predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.shape(), (9, 5));
+    // the columns that the function adds don't exist below it
+    let q = map(all).filter(col("one").eq(lit(1)));
+    assert!(!predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.height(), 27);
+    assert!(!predicate_at_scan(
+        map(UdfProperties::ELEMENTWISE).filter(col("calories").lt(lit(50)))
+    ));
+
+    let q = map(all).slice(0, 5);
+    assert!(slice_at_scan(q.clone()));
+    assert_eq!(q.collect()?.shape(), (5, 5));
+    let q = map(UdfProperties::ELEMENTWISE | UdfProperties::PURE).slice(0, 5);
+    assert!(!slice_at_scan(q));
+    Ok(())
+}
+
 #[test]
 fn test_flatten_unions() -> PolarsResult<()> {
     let (mut expr_arena, mut lp_arena) = get_arenas();
@@ -694,3 +731,128 @@ fn test_cluster_with_columns_chain() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_join_reorder() -> PolarsResult<()> {
+    let fact = df![
+        "id" => (0..100i32).collect::<Vec<_>>(),
+        "big_id" => (0..100i32).map(|v| v % 50).collect::<Vec<_>>(),
+        "small_id" => (0..100i32).map(|v| v % 5).collect::<Vec<_>>(),
+    ]?
+    .lazy();
+    let big = df![
+        "big_id" => (0..50i32).collect::<Vec<_>>(),
+        "big" => (0..50i32).map(|v| v * 2).collect::<Vec<_>>(),
+    ]?
+    .lazy();
+    let small = df![
+        "small_id" => (0..5i32).collect::<Vec<_>>(),
+        "small" => (0..5i32).map(|v| v * 3).collect::<Vec<_>>(),
+    ]?
+    .lazy();
+
+    let q = fact
+        .inner_join(big, col("big_id"), col("big_id"))
+        .inner_join(small, col("small_id"), col("small_id"));
+
+    // the small table is joined first, so it is the right table of the bottom join
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let right_heights = (&lp_arena)
+        .iter(lp)
+        .filter_map(|(_, lp)| match lp {
+            IR::Join { input_right, .. } => match lp_arena.get(*input_right) {
+                IR::DataFrameScan { df, .. } => Some(df.height()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(right_heights, [50, 5]);
+
+    let out = q.clone().sort(["id"], Default::default()).collect()?;
+    let expected = q
+        .with_join_reorder(false)
+        .sort(["id"], Default::default())
+        .collect()?;
+    assert_eq!(out, expected);
+    Ok(())
+}
+
+/// Rewrites `x * 2` into `x + x` for the column `user_rule_x`.
+struct DoubleToSum {
+    rewrites: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl OptimizationRule for DoubleToSum {
+    fn optimize_expr(
+        &mut self,
+        expr_arena: &mut Arena<AExpr>,
+        expr_node: Node,
+        _lp_arena: &Arena<IR>,
+        _lp_node: Node,
+    ) -> PolarsResult<Option<AExpr>> {
+        let Some((left, right)) = pattern::binary_op(expr_node, Operator::Multiply, expr_arena)
+        else {
+            return Ok(None);
+        };
+        let is_x =
+            pattern::column(left, expr_arena).is_some_and(|name| name.as_str() == "user_rule_x");
+        let is_two = pattern::literal(right, expr_arena)
+            .and_then(|lv| lv.to_any_value())
+            .and_then(|av| av.extract::<i64>())
+            == Some(2);
+        if !(is_x && is_two) {
+            return Ok(None);
+        }
+        self.rewrites
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Ok(Some(AExpr::BinaryExpr {
+            left,
+            op: Operator::Plus,
+            right: left,
+        }))
+    }
+}
+
+#[test]
+fn test_user_optimization_rule() -> PolarsResult<()> {
+    let rewrites = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = rewrites.clone();
+    register_optimization_rule("double_to_sum", move || {
+        Box::new(DoubleToSum {
+            rewrites: counter.clone(),
+        })
+    })?;
+    assert!(
+        register_optimization_rule("double_to_sum", || Box::new(SimplifyBooleanRule {})).is_err()
+    );
+    assert!(registered_optimization_rules().contains(&"double_to_sum".into()));
+
+    let q = df!["user_rule_x" => [1, 2, 3]]?
+        .lazy()
+        .select([(col("user_rule_x") * lit(2)).alias("y")]);
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let multiplies = (&lp_arena).iter(lp).any(|(_, lp)| {
+        lp.get_exprs().iter().any(|e| {
+            (&expr_arena).iter(e.node()).any(|(_, e)| {
+                matches!(
+                    e,
+                    AExpr::BinaryExpr {
+                        op: Operator::Multiply,
+                        ..
+                    }
+                )
+            })
+        })
+    });
+    let out = q.collect()?;
+
+    assert!(unregister_optimization_rule("double_to_sum"));
+    assert!(!unregister_optimization_rule("double_to_sum"));
+    assert!(rewrites.load(std::sync::atomic::Ordering::Relaxed) > 0);
+    assert!(!multiplies);
+    assert_eq!(out, df!["y" => [2, 4, 6]]?);
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::*;
+
+fn numbers(runs: &'static AtomicUsize) -> LazyFrame {
+    df!["a" => (0..1000i64).collect::<Vec<_>>()]
+        .unwrap()
+        .lazy()
+        .map(
+            move |df| {
+                runs.fetch_add(1, Ordering::Relaxed);
+                Ok(df)
+            },
+            Default::default(),
+            None,
+            None,
+        )
+        .with_column((col("a") * lit(2i64)).alias("b"))
+}
+
+#[test]
+fn test_persist_temporary_file() -> PolarsResult<()> {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let persisted = numbers(&RUNS).persist(Default::default())?;
+    assert_eq!(RUNS.load(Ordering::Relaxed), 0);
+
+    let out = persisted
+        .clone()
+        .filter(col("a").lt(lit(10i64)))
+        .select([col("b")])
+        .collect()?;
+    assert_eq!(out.shape(), (10, 1));
+    assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+
+    // later queries read the file
+    let out = persisted.clone().slice(990, 20).collect()?;
+    assert_eq!(out.shape(), (10, 2));
+    let out = persisted.select([col("b").sum()]).collect()?;
+    assert_eq!(out.column("b")?.i64()?.get(0), Some(999_000));
+    assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+    Ok(())
+}
+
+#[test]
+fn test_persist_to_path() -> PolarsResult<()> {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join("polars_test_persist_to_path");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("numbers.arrow");
+
+    let persisted = numbers(&RUNS).persist(PersistOptions {
+        path: Some(path.clone()),
+        format: PersistFormat::Ipc(Default::default()),
+    })?;
+    assert!(!path.exists());
+    let expected = persisted.clone().collect()?;
+    assert!(path.exists());
+
+    #[cfg(feature = "streaming")]
+    {
+        let out = persisted
+            .clone()
+            .filter(col("a").gt_eq(lit(500i64)))
+            .with_streaming(true)
+            .collect()?;
+        assert!(out.equals(&expected.slice(500, 500)));
+    }
+    let out = persisted.collect()?;
+    assert!(out.equals(&expected));
+    assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+
+    // the file outlives the frame
+    assert!(path.exists());
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_checkpoint_restarts_from_file() -> PolarsResult<()> {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join("polars_test_checkpoint_restarts_from_file");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("numbers.parquet");
+
+    // a later stage fails after the checkpoint was written
+    let checkpointed = numbers(&RUNS).checkpoint(&path, Default::default())?;
+    let failing = checkpointed
+        .clone()
+        .map(
+            |_| polars_bail!(ComputeError: "flaky stage"),
+            Default::default(),
+            None,
+            None,
+        )
+        .collect();
+    assert!(failing.is_err());
+    assert!(path.exists());
+    assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+
+    // a new pipeline restarts from the checkpoint
+    let checkpointed = numbers(&RUNS).checkpoint(&path, Default::default())?;
+    let out = checkpointed.select([col("b").sum()]).collect()?;
+    assert_eq!(out.column("b")?.i64()?.get(0), Some(999_000));
+    assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+
+    // a checkpoint of another query is rejected
+    let other = numbers(&RUNS).select([col("a")]);
+    assert!(other.checkpoint(&path, Default::default()).is_err());
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+#[test]
+fn test_prepared_query() -> PolarsResult<()> {
+    let df = df![
+        "a" => [1, 2, 3, 4, 5],
+        "b" => ["x", "y", "x", "y", "x"],
+    ]?;
+    let q = df
+        .clone()
+        .lazy()
+        .filter(col("a").gt_eq(param("low", DataType::Int32)))
+        .filter(col("b").eq(param("b", DataType::String)))
+        .select([
+            col("a"),
+            (col("a") * param("factor", DataType::Int32)).alias("c"),
+        ]);
+    assert!(q.clone().collect().is_err());
+
+    let prepared = q.prepare()?;
+    assert_eq!(prepared.params().len(), 3);
+    for low in [1, 3] {
+        let out = prepared.collect(&[
+            ("low", AnyValue::Int32(low)),
+            ("b", AnyValue::String("x")),
+            // cast to the dtype of the parameter
+            ("factor", AnyValue::Int64(10)),
+        ])?;
+        let expected = df
+            .clone()
+            .lazy()
+            .filter(col("a").gt_eq(lit(low)))
+            .filter(col("b").eq(lit("x")))
+            .select([col("a"), (col("a") * lit(10)).alias("c")])
+            .collect()?;
+        assert_eq!(out, expected);
+    }
+
+    // unbound and unknown parameters
+    assert!(prepared.collect(&[("low", AnyValue::Int32(1))]).is_err());
+    assert!(prepared
+        .collect(&[
+            ("low", AnyValue::Int32(1)),
+            ("b", AnyValue::String("x")),
+            ("factor", AnyValue::Int32(1)),
+            ("high", AnyValue::Int32(1)),
+        ])
+        .is_err());
+    Ok(())
+}
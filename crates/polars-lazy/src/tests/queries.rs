@@ -1933,3 +1933,351 @@ fn test_sort_maintain_order_true() -> PolarsResult<()> {
     ]?));
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "string_similarity")]
+fn test_string_similarity() -> PolarsResult<()> {
+    let df = df![
+        "a" => [Some("kitten"), Some("karolin"), Some(""), None],
+        "b" => [Some("sitting"), Some("kathrin"), Some(""), Some("x")],
+    ]?;
+    let out = df
+        .lazy()
+        .select([
+            col("a").str().levenshtein(col("b"), false).alias("lev"),
+            col("a").str().levenshtein(col("b"), true).alias("lev_sim"),
+            col("a").str().hamming(col("b"), false).alias("ham"),
+            col("a").str().jaro_winkler(lit("kitten")).alias("jw"),
+        ])
+        .collect()?;
+
+    let lev = out.column("lev")?.u32()?;
+    assert_eq!(Vec::from(lev), &[Some(3), Some(3), Some(0), None]);
+    let lev_sim = out.column("lev_sim")?.f64()?;
+    assert!((lev_sim.get(0).unwrap() - (1.0 - 3.0 / 7.0)).abs() < 1e-12);
+    assert_eq!(lev_sim.get(2), Some(1.0));
+    let ham = out.column("ham")?.u32()?;
+    assert_eq!(Vec::from(ham), &[None, Some(3), Some(0), None]);
+    let jw = out.column("jw")?.f64()?;
+    assert_eq!(jw.get(0), Some(1.0));
+    assert_eq!(jw.get(2), Some(0.0));
+    assert_eq!(jw.get(3), None);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "string_phonetic")]
+fn test_string_phonetic() -> PolarsResult<()> {
+    let df = df![
+        "name" => [Some("Robert"), Some("Rupert"), Some("Catherine"), Some("Kathryn"), Some("42"), None],
+    ]?;
+    let out = df
+        .lazy()
+        .group_by_stable([col("name").str().soundex().alias("soundex")])
+        .agg([
+            col("name")
+                .str()
+                .metaphone()
+                .n_unique()
+                .alias("n_metaphone"),
+            len(),
+        ])
+        .collect()?;
+
+    let soundex = out.column("soundex")?.str()?;
+    assert_eq!(
+        Vec::from(soundex),
+        &[Some("R163"), Some("C365"), Some("K365"), None]
+    );
+    let n_metaphone = out.column("n_metaphone")?.idx()?;
+    assert_eq!(
+        Vec::from(n_metaphone),
+        &[Some(2), Some(1), Some(1), Some(1)]
+    );
+    assert_eq!(out.column("len")?.idx()?.sum(), Some(6));
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "extract_groups", feature = "dtype-struct"))]
+fn test_extract_all_groups() -> PolarsResult<()> {
+    let df = df![
+        "log" => [Some("a=1 b=22 c"), Some("none"), None],
+    ]?;
+    let out = df
+        .lazy()
+        .select([col("log").str().extract_all_groups(r"(?<key>\w+)=(\d+)")?])
+        .explode(["log"])
+        .unnest(["log"])
+        .collect()?;
+
+    let key = out.column("key")?.str()?;
+    assert_eq!(Vec::from(key), &[Some("a"), Some("b"), None, None]);
+    let value = out.column("2")?.str()?;
+    assert_eq!(Vec::from(value), &[Some("1"), Some("22"), None, None]);
+
+    assert!(col("log").str().extract_all_groups(r"\w+").is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "find_many")]
+fn test_replace_many() -> PolarsResult<()> {
+    let df = df![
+        "text" => [Some("cat and dog"), Some("CAT"), None],
+    ]?;
+    let patterns = || lit(Series::new("patterns".into(), ["cat", "dog"]));
+    let replace_with = lit(Series::new("replace_with".into(), ["dog", "cat"]));
+    let out = df
+        .lazy()
+        .select([
+            col("text")
+                .str()
+                .replace_many(patterns(), replace_with, false)
+                .alias("mapped"),
+            // a single replacement is used for every pattern
+            col("text")
+                .str()
+                .replace_many(patterns(), lit("pet"), true)
+                .alias("single"),
+        ])
+        .collect()?;
+
+    // the patterns are replaced in one pass, so replacements are not matched again
+    let mapped = out.column("mapped")?.str()?;
+    assert_eq!(Vec::from(mapped), &[Some("dog and cat"), Some("CAT"), None]);
+    let single = out.column("single")?.str()?;
+    assert_eq!(Vec::from(single), &[Some("pet and pet"), Some("pet"), None]);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "string_normalize")]
+fn test_string_normalize() -> PolarsResult<()> {
+    // "é" as a single code point and as "e" followed by a combining acute accent
+    let df = df![
+        "a" => [Some("caf\u{e9}"), Some("cafe\u{301}"), Some("\u{fb01}"), None],
+    ]?;
+    let out = df
+        .lazy()
+        .select([
+            col("a").str().normalize(UnicodeForm::NFC).alias("nfc"),
+            col("a").str().normalize(UnicodeForm::NFD).alias("nfd"),
+            col("a").str().normalize(UnicodeForm::NFKC).alias("nfkc"),
+        ])
+        .collect()?;
+
+    let nfc = out.column("nfc")?.str()?;
+    assert_eq!(
+        Vec::from(nfc),
+        &[Some("caf\u{e9}"), Some("caf\u{e9}"), Some("\u{fb01}"), None]
+    );
+    let nfd = out.column("nfd")?.str()?;
+    assert_eq!(nfd.get(0), Some("cafe\u{301}"));
+    assert_eq!(nfd.get(0), nfd.get(1));
+    // the compatibility forms decompose the "fi" ligature
+    let nfkc = out.column("nfkc")?.str()?;
+    assert_eq!(nfkc.get(2), Some("fi"));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "string_collation")]
+fn test_string_collation_key() -> PolarsResult<()> {
+    let df = df![
+        "name" => ["Öberg", "Zetterlund", "Åström", "eriksson", "Andersson", "Eriksson"],
+    ]?;
+    let swedish = CollationOptions {
+        locale: "sv".into(),
+        ..Default::default()
+    };
+    let out = df
+        .clone()
+        .lazy()
+        .sort_by_exprs(
+            [col("name").str().collation_key(swedish)],
+            Default::default(),
+        )
+        .collect()?;
+    let name = out.column("name")?.str()?;
+    assert_eq!(
+        Vec::from(name),
+        &[
+            Some("Andersson"),
+            Some("eriksson"),
+            Some("Eriksson"),
+            Some("Zetterlund"),
+            Some("Åström"),
+            Some("Öberg")
+        ]
+    );
+
+    let insensitive = CollationOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+    let out = df
+        .clone()
+        .lazy()
+        .group_by([col("name").str().collation_key(insensitive)])
+        .agg([len()])
+        .collect()?;
+    assert_eq!(out.height(), 5);
+
+    // the locales of which the collation isn't supported are an error, not the root order
+    let turkish = CollationOptions {
+        locale: "tr".into(),
+        ..Default::default()
+    };
+    let out = df
+        .lazy()
+        .select([col("name").str().collation_key(turkish)])
+        .collect();
+    assert!(out.is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "string_tokenize")]
+fn test_string_tokenize_ngrams() -> PolarsResult<()> {
+    let df = df![
+        "text" => [Some("It's  a Test!"), Some(""), None],
+    ]?;
+    let words = TokenizeOptions {
+        lowercase: true,
+        ..Default::default()
+    };
+    let chars = TokenizeOptions {
+        mode: TokenizeMode::Chars,
+        lowercase: false,
+    };
+    let out = df
+        .lazy()
+        .select([
+            col("text").str().tokenize(words).alias("words"),
+            col("text")
+                .str()
+                .tokenize(TokenizeOptions {
+                    mode: TokenizeMode::Whitespace,
+                    lowercase: false,
+                })
+                .alias("whitespace"),
+            col("text").str().ngrams(2, words).alias("bigrams"),
+            col("text").str().ngrams(3, chars).alias("trigrams"),
+        ])
+        .collect()?;
+
+    let list = |name: &str, idx: usize| -> PolarsResult<Vec<Option<String>>> {
+        let s = out.column(name)?.list()?.get_as_series(idx).unwrap();
+        Ok(s.str()?
+            .into_iter()
+            .map(|v| v.map(|v| v.to_string()))
+            .collect())
+    };
+    let strings = |v: &[&str]| v.iter().map(|v| Some(v.to_string())).collect::<Vec<_>>();
+    assert_eq!(list("words", 0)?, strings(&["it", "s", "a", "test"]));
+    assert_eq!(list("whitespace", 0)?, strings(&["It's", "a", "Test!"]));
+    assert_eq!(list("bigrams", 0)?, strings(&["it s", "s a", "a test"]));
+    assert_eq!(list("trigrams", 0)?[..2], strings(&["It'", "t's"]));
+    assert!(list("bigrams", 1)?.is_empty());
+    assert_eq!(out.column("words")?.null_count(), 1);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "dtype-struct")]
+fn test_split_to_struct() -> PolarsResult<()> {
+    let df = df![
+        "id" => [Some("eu-west-1-prod"), Some("us-east"), None],
+    ]?;
+    let out = df
+        .lazy()
+        .select([
+            col("id")
+                .str()
+                .split_to_struct(lit("-"), ["region", "zone", "n"], None)
+                .alias("exact"),
+            col("id")
+                .str()
+                .split_to_struct(lit("-"), ["head", "rest", "extra"], Some(1))
+                .alias("once"),
+        ])
+        .unnest(["exact"])
+        .unnest(["once"])
+        .collect()?;
+
+    let column = |name: &str| -> PolarsResult<Vec<Option<String>>> {
+        Ok(out
+            .column(name)?
+            .str()?
+            .into_iter()
+            .map(|v| v.map(|v| v.to_string()))
+            .collect())
+    };
+    let strings = |v: &[Option<&str>]| {
+        v.iter()
+            .map(|v| v.map(|v| v.to_string()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        column("zone")?,
+        strings(&[Some("west"), Some("east"), None])
+    );
+    assert_eq!(column("n")?, strings(&[Some("1"), None, None]));
+    assert_eq!(
+        column("rest")?,
+        strings(&[Some("west-1-prod"), Some("east"), None])
+    );
+    assert_eq!(column("extra")?.iter().flatten().count(), 0);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "string_replace_eval")]
+fn test_replace_all_with() -> PolarsResult<()> {
+    let df = df![
+        "text" => [Some("user=alice id=7"), Some("no match"), None],
+        "g" => [1, 1, 2],
+    ]?;
+    let pat = r"(?<key>\w+)=(\w+)";
+    let out = df
+        .clone()
+        .lazy()
+        .select([
+            col("text")
+                .str()
+                .replace_all_with(pat, col("0").str().to_uppercase())?
+                .alias("upper"),
+            col("text")
+                .str()
+                .replace_all_with(
+                    pat,
+                    when(col("key").eq(lit("id")))
+                        .then(lit("#"))
+                        .otherwise(lit(NULL)),
+                )?
+                .alias("keys"),
+        ])
+        .collect()?;
+
+    let upper = out.column("upper")?.str()?;
+    assert_eq!(
+        Vec::from(upper),
+        &[Some("USER=ALICE ID=7"), Some("no match"), None]
+    );
+    // a null replacement keeps the match
+    let keys = out.column("keys")?.str()?;
+    assert_eq!(
+        Vec::from(keys),
+        &[Some("user=alice #"), Some("no match"), None]
+    );
+
+    let out = df
+        .lazy()
+        .group_by_stable([col("g")])
+        .agg([col("text").str().replace_all_with(pat, col("2"))?.first()])
+        .collect()?;
+    let text = out.column("text")?.str()?;
+    assert_eq!(Vec::from(text), &[Some("alice 7"), None]);
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+fn query(path: &std::path::Path) -> PolarsResult<LazyFrame> {
+    Ok(LazyCsvReader::new(path)
+        .finish()?
+        .group_by([col("a")])
+        .agg([col("b").sum()])
+        .sort(["a"], Default::default()))
+}
+
+fn check_result_cache(cache: ResultCache, dir: &std::path::Path) -> PolarsResult<()> {
+    let path = dir.join("data.csv");
+    std::fs::write(&path, "a,b\n1,10\n1,20\n2,30\n")?;
+
+    let expected = df!["a" => [1i64, 2], "b" => [30i64, 30]]?;
+    let out = query(&path)?.collect_cached(&cache)?;
+    assert!(out.equals(&expected));
+    assert_eq!(cache.len()?, 1);
+
+    // an identical query hits the cache
+    let out = query(&path)?.collect_cached(&cache)?;
+    assert!(out.equals(&expected));
+    assert_eq!(cache.len()?, 1);
+
+    // another query doesn't
+    let out = query(&path)?.limit(1).collect_cached(&cache)?;
+    assert!(out.equals(&expected.head(Some(1))));
+    assert_eq!(cache.len()?, 2);
+
+    // neither does the same query after the file changed
+    std::fs::write(&path, "a,b\n1,10\n2,30\n2,400\n")?;
+    let out = query(&path)?.collect_cached(&cache)?;
+    assert!(out.equals(&df!["a" => [1i64, 2], "b" => [10i64, 430]]?));
+    assert_eq!(cache.len()?, 3);
+
+    cache.clear()?;
+    assert!(cache.is_empty()?);
+    Ok(())
+}
+
+#[test]
+fn test_result_cache_in_memory() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_result_cache_in_memory");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+
+    check_result_cache(ResultCache::in_memory(), &dir)?;
+
+    // queries over in-memory data are not cached
+    let cache = ResultCache::in_memory();
+    let out = load_df().lazy().select([col("a")]).collect_cached(&cache)?;
+    assert_eq!(out.height(), 5);
+    assert!(cache.is_empty()?);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_result_cache_on_disk() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_result_cache_on_disk");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+
+    check_result_cache(ResultCache::on_disk(dir.join("cache"))?, &dir)?;
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_result_cache_on_disk_max_bytes() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_result_cache_on_disk_max_bytes");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("data.csv");
+    std::fs::write(&path, "a,b\n1,10\n1,20\n2,30\n")?;
+
+    let queries = [
+        query(&path)?,
+        query(&path)?.limit(1),
+        query(&path)?.select([col("b")]),
+    ];
+    let files = |cache_dir: &std::path::Path| -> PolarsResult<Vec<(String, u64)>> {
+        let mut files = std::fs::read_dir(cache_dir)?
+            .map(|entry| {
+                let entry = entry?;
+                Ok((
+                    entry.file_name().into_string().unwrap(),
+                    entry.metadata()?.len(),
+                ))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        files.sort();
+        Ok(files)
+    };
+    // the result files of the queries, in their order
+    let unbounded = ResultCache::on_disk(dir.join("unbounded"))?;
+    let mut names = vec![];
+    let mut total = 0;
+    for q in &queries {
+        let before = files(&dir.join("unbounded"))?;
+        q.clone().collect_cached(&unbounded)?;
+        let (name, len) = files(&dir.join("unbounded"))?
+            .into_iter()
+            .find(|file| !before.contains(file))
+            .unwrap();
+        names.push(name);
+        total += len;
+    }
+
+    // The modification times order the uses of the results.
+    let tick = || std::thread::sleep(std::time::Duration::from_millis(20));
+    let cache_dir = dir.join("bounded");
+    let cache = ResultCache::on_disk_with_max_bytes(&cache_dir, total - 1)?;
+    queries[0].clone().collect_cached(&cache)?;
+    tick();
+    queries[1].clone().collect_cached(&cache)?;
+    tick();
+    // a hit makes the first result the most recently used one
+    queries[0].clone().collect_cached(&cache)?;
+    tick();
+    queries[2].clone().collect_cached(&cache)?;
+    let kept = files(&cache_dir)?
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    let mut expected = vec![names[0].clone(), names[2].clone()];
+    expected.sort();
+    assert_eq!(kept, expected);
+
+    // a result that is larger than the whole budget is not kept
+    let cache = ResultCache::on_disk_with_max_bytes(dir.join("tiny"), 1)?;
+    let out = queries[0].clone().collect_cached(&cache)?;
+    assert_eq!(out.height(), 2);
+    assert!(cache.is_empty()?);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
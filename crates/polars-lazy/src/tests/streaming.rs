@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use polars_ops::frame::JoinCoalesce;
 
 use super::*;
@@ -61,6 +63,53 @@ fn test_streaming_csv() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "ipc")]
+fn test_streaming_ipc() -> PolarsResult<()> {
+    let q = scan_foods_ipc()
+        .select([col("sugars_g"), col("calories")])
+        .group_by([col("sugars_g")])
+        .agg([col("calories").sum()])
+        .sort(["sugars_g"], Default::default());
+    assert_streaming_with_default(q, true, false);
+
+    let q = scan_foods_ipc()
+        .with_row_index("idx", None)
+        .select([col("fats_g"), col("idx")])
+        .limit(5);
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
+#[test]
+fn test_streaming_filter_projection() -> PolarsResult<()> {
+    let df = df![
+        "a" => (0..10_000i32).collect::<Vec<_>>(),
+        "b" => (0..10_000i32).map(|v| v.to_string()).collect::<Vec<_>>(),
+        "c" => (0..10_000i32).map(|v| v % 7).collect::<Vec<_>>(),
+    ]?;
+
+    let q = df
+        .clone()
+        .lazy()
+        .filter(col("c").eq(lit(3)))
+        .select([
+            (col("a") * lit(2)).alias("a2"),
+            col("b"),
+            lit(1).alias("one"),
+        ])
+        .with_predicate_pushdown(false);
+    assert_streaming_with_default(q, true, false);
+
+    let q = df
+        .lazy()
+        .filter(col("c").eq(lit(3)))
+        .select([col("b"), col("a")])
+        .with_predicate_pushdown(false);
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
 #[test]
 fn test_streaming_glob() -> PolarsResult<()> {
     let q = get_csv_glob();
@@ -144,6 +193,104 @@ fn test_streaming_unique() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_streaming_unique_keep_strategies() -> PolarsResult<()> {
+    for keep in [
+        UniqueKeepStrategy::First,
+        UniqueKeepStrategy::Last,
+        UniqueKeepStrategy::None,
+    ] {
+        let q = get_csv_file()
+            .unique(Some(vec!["sugars_g".into()]), keep)
+            .sort_by_exprs([cols(["sugars_g"])], SortMultipleOptions::default());
+
+        assert_streaming_with_default(q, true, false);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_streaming_window_aggregations() -> PolarsResult<()> {
+    let q = get_csv_file().with_columns([
+        col("calories")
+            .sum()
+            .over([col("category")])
+            .alias("calories_sum"),
+        (col("calories") - col("calories").mean().over([col("category")])).alias("calories_diff"),
+    ]);
+    assert_streaming_with_default(q, true, false);
+
+    let q = get_csv_file().select([
+        col("category"),
+        col("sugars_g").max().over([col("category")]),
+        len().over([col("category")]).alias("len"),
+    ]);
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "dynamic_group_by")]
+fn test_streaming_temporal_group_by() -> PolarsResult<()> {
+    use polars_time::{ClosedWindow, Duration};
+
+    let t = (0..10_000i64).map(|i| i / 3).collect::<Vec<_>>();
+    let value = (0..10_000i64).map(|i| i % 7).collect::<Vec<_>>();
+    let df = df![
+        "t" => t,
+        "value" => value,
+    ]?;
+    // The windows continue in the next chunks of the union.
+    let chunks = |len: usize| {
+        let inputs = (0..df.height())
+            .step_by(len)
+            .map(|i| df.slice(i as i64, len).lazy())
+            .collect::<Vec<_>>();
+        concat(inputs, Default::default()).unwrap()
+    };
+
+    for (period, len) in [("3i", 1_000), ("7i", 10), ("20i", 1_000), ("100i", 50)] {
+        for include_boundaries in [true, false] {
+            let q = chunks(len)
+                .group_by_dynamic(
+                    col("t"),
+                    [],
+                    DynamicGroupOptions {
+                        every: Duration::parse("7i"),
+                        period: Duration::parse(period),
+                        offset: Duration::parse("0i"),
+                        include_boundaries,
+                        ..Default::default()
+                    },
+                )
+                .agg([col("value").sum(), col("value").first().alias("first")]);
+            // The windows that overlap aren't streamed.
+            let overlapping = Duration::parse(period) > Duration::parse("7i");
+            assert_streaming_with_default(q, !overlapping, false);
+        }
+
+        for (offset, closed_window) in [
+            (-Duration::parse(period), ClosedWindow::Right),
+            (Duration::parse("0i"), ClosedWindow::Left),
+        ] {
+            let q = chunks(len)
+                .rolling(
+                    col("t"),
+                    [],
+                    RollingGroupOptions {
+                        period: Duration::parse(period),
+                        offset,
+                        closed_window,
+                        ..Default::default()
+                    },
+                )
+                .agg([col("value").sum(), col("value").max().alias("max")]);
+            assert_streaming_with_default(q, true, false);
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn test_streaming_aggregate_slice() -> PolarsResult<()> {
     let q = get_parquet_file();
@@ -260,6 +407,381 @@ fn test_streaming_left_join() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_streaming_broadcast_join() -> PolarsResult<()> {
+    let lf_left = df![
+        "a" => (0..10_000i32).map(|i| i % 100).collect::<Vec<_>>(),
+        "b" => (0..10_000i32).collect::<Vec<_>>(),
+    ]?
+    .lazy();
+    let lf_right = df![
+        "a" => [10, 18, 13, 9, 1, 13, 14, 12, 15, 11],
+        "c" => [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    ]?
+    .lazy();
+
+    for (how, side) in [
+        (JoinType::Inner, JoinSide::Left),
+        (JoinType::Inner, JoinSide::Right),
+        (JoinType::Left, JoinSide::Right),
+        (JoinType::Full, JoinSide::Left),
+    ] {
+        let q = lf_left
+            .clone()
+            .join_builder()
+            .with(lf_right.clone())
+            .on([col("a")])
+            .how(how)
+            .broadcast(side)
+            .finish()
+            .sort(["b", "c"], Default::default());
+        assert_streaming_with_default(q, true, false);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_streaming_join_broadcast_at_runtime() -> PolarsResult<()> {
+    // The filters hide the sizes of the inputs from the plan, the build side is only known to
+    // be small once its hash table is built.
+    let lf_left = df![
+        "a" => (0..10_000i32).map(|i| i % 100).collect::<Vec<_>>(),
+        "b" => (0..10_000i32).collect::<Vec<_>>(),
+    ]?
+    .lazy()
+    .filter(col("b").gt(lit(10)));
+    let lf_right = df![
+        "a" => (0..1_000i32).map(|i| i % 200).collect::<Vec<_>>(),
+        "c" => (0..1_000i32).collect::<Vec<_>>(),
+    ]?
+    .lazy()
+    .filter(col("c").lt(lit(150)));
+
+    for how in [JoinType::Inner, JoinType::Left, JoinType::Full] {
+        let q = lf_left
+            .clone()
+            .join(lf_right.clone(), [col("a")], [col("a")], JoinArgs::new(how))
+            .sort(["b", "c"], Default::default());
+        assert_streaming_with_default(q, true, false);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_streaming_group_by_sorted_keys() -> PolarsResult<()> {
+    use polars_core::series::IsSorted;
+
+    let mut g = Series::new(
+        "g".into(),
+        (0..10_000i32).map(|i| i / 7).collect::<Vec<_>>(),
+    );
+    g.set_sorted_flag(IsSorted::Ascending);
+    let v = Series::new(
+        "v".into(),
+        (0..10_000i32).map(|i| i % 13).collect::<Vec<_>>(),
+    );
+    let df = DataFrame::new(vec![g, v])?;
+    let aggs = [
+        col("v").sum().alias("sum"),
+        col("v").min().alias("min"),
+        col("v").first().alias("first"),
+        col("v").last().alias("last"),
+        col("v").mean().alias("mean"),
+        len(),
+    ];
+
+    // The groups continue in other chunks.
+    let q = df
+        .clone()
+        .lazy()
+        .group_by([col("g")])
+        .agg(aggs.clone())
+        .sort(["g"], Default::default());
+    assert_streaming_with_default(q, true, false);
+
+    // Every chunk of the union is a single group, which continues in the next chunks.
+    let chunks = || {
+        let inputs = (0..10)
+            .map(|i| df.slice(i * 1_000, 1_000).lazy())
+            .collect::<Vec<_>>();
+        concat(inputs, Default::default())
+            .unwrap()
+            .with_column((col("g") / lit(500)).alias("g"))
+            .with_column(col("g").set_sorted_flag(IsSorted::Ascending))
+            .group_by([col("g")])
+            .agg(aggs.clone())
+    };
+    assert_streaming_with_default(chunks().sort(["g"], Default::default()), true, false);
+    let q = chunks().slice(1, 2).with_streaming(true);
+    assert!(optimization_checks::is_pipeline(q.clone()));
+    // the groups are in the order of the input
+    let out = q.collect()?;
+    assert_eq!(out.column("g")?, &Series::new("g".into(), [1i32, 2]));
+
+    // The sorted flag is set on a key that would otherwise be hashed.
+    let q = df
+        .lazy()
+        .with_column((col("g") * lit(2)).alias("g2"))
+        .with_column(col("g2").set_sorted_flag(IsSorted::Ascending))
+        .group_by([col("g2")])
+        .agg(aggs)
+        .sort(["g2"], Default::default());
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
+#[test]
+fn test_streaming_query_hints() -> PolarsResult<()> {
+    let lf_left = df![
+        "a" => (0..10_000i32).map(|i| i % 100).collect::<Vec<_>>(),
+        "b" => (0..10_000i32).collect::<Vec<_>>(),
+    ]?
+    .lazy();
+    let lf_right = df![
+        "a" => [10, 18, 13, 9, 1, 13, 14, 12, 15, 11],
+        "c" => [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    ]?
+    .lazy();
+    let join = lf_left.join(
+        lf_right,
+        [col("a")],
+        [col("a")],
+        JoinArgs::new(JoinType::Inner),
+    );
+
+    for join_hint in [JoinHint::Broadcast(JoinSide::Right), JoinHint::Partitioned] {
+        let q = join
+            .clone()
+            .with_hints(QueryHints {
+                join: Some(join_hint),
+                max_threads: NonZeroUsize::new(1),
+                ..Default::default()
+            })
+            .sort(["b", "c"], Default::default());
+        assert_streaming_with_default(q, true, false);
+    }
+
+    // The hinted part of the query stays in memory.
+    let q = join
+        .with_hints(QueryHints {
+            streaming: Some(false),
+            ..Default::default()
+        })
+        .group_by([col("a")])
+        .agg([col("c").sum()])
+        .sort(["a"], Default::default());
+    assert!(!optimization_checks::has_pipeline(
+        q.clone().with_streaming(true)
+    ));
+    let explain = q.clone().with_streaming(true).explain(true)?;
+    assert!(explain.contains("HINTS: streaming: false"), "{explain}");
+    assert_eq!(
+        q.clone().with_streaming(true).collect()?,
+        q.with_streaming(false).collect()?
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "asof_join")]
+fn test_streaming_asof_join() -> PolarsResult<()> {
+    use polars_ops::prelude::{AsOfOptions, AsofStrategy};
+
+    // The frames are pulled a chunk at a time, so the right chunks that the left chunks match
+    // are merged in.
+    let chunked = |df: DataFrame, len: usize| -> PolarsResult<LazyFrame> {
+        let mut out = df.slice(0, len);
+        for offset in (len..df.height()).step_by(len) {
+            out.vstack_mut(&df.slice(offset as i64, len))?;
+        }
+        Ok(out.lazy())
+    };
+    let n = 1000;
+    let lf_left = chunked(
+        df![
+            "time" => (0..n).map(|i| i as i64 * 3).collect::<Vec<i64>>(),
+            "group" => (0..n).map(|i| i % 3).collect::<Vec<i32>>()
+        ]?,
+        70,
+    )?;
+    let lf_right = chunked(
+        df![
+            "time" => (0..n).map(|i| i as i64 * 5 + 1).collect::<Vec<i64>>(),
+            "group" => (0..n).map(|i| i % 2).collect::<Vec<i32>>(),
+            "value" => (0..n).collect::<Vec<i32>>()
+        ]?,
+        30,
+    )?;
+
+    for strategy in [
+        AsofStrategy::Backward,
+        AsofStrategy::Forward,
+        AsofStrategy::Nearest,
+    ] {
+        for by in [false, true] {
+            for tolerance in [None, Some(AnyValue::Int64(2))] {
+                // without a tolerance the forward and nearest matches of a group aren't bounded
+                let streams = strategy == AsofStrategy::Backward || !by || tolerance.is_some();
+                let by = by.then(|| vec!["group".into()]);
+                let options = AsOfOptions {
+                    strategy,
+                    tolerance,
+                    left_by: by.clone(),
+                    right_by: by,
+                    ..Default::default()
+                };
+                let q = lf_left
+                    .clone()
+                    .join_builder()
+                    .with(lf_right.clone())
+                    .left_on([col("time")])
+                    .right_on([col("time")])
+                    .how(JoinType::AsOf(options))
+                    .finish();
+
+                if streams {
+                    assert_streaming_with_default(q, true, false);
+                } else {
+                    assert!(!optimization_checks::is_pipeline(
+                        q.clone().with_streaming(true)
+                    ));
+                    assert_eq!(q.clone().with_streaming(true).collect()?, q.collect()?);
+                }
+            }
+        }
+    }
+
+    // the chunks are sorted, but the keys go backwards from the first to the second chunk
+    let mut unsorted = df!["time" => [10i64, 20], "group" => [0i32, 1]]?;
+    unsorted.vstack_mut(&df!["time" => [15i64, 30], "group" => [0i32, 1]]?)?;
+    let out = unsorted
+        .lazy()
+        .join_builder()
+        .with(lf_right)
+        .left_on([col("time")])
+        .right_on([col("time")])
+        .how(JoinType::AsOf(Default::default()))
+        .finish()
+        .with_streaming(true)
+        .collect();
+    assert!(out.is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "asof_join")]
+fn test_streaming_asof_join_left_only_group() -> PolarsResult<()> {
+    use polars_ops::prelude::{AsOfOptions, AsofStrategy};
+
+    // Group 1 is only in the left input, so its rows never find a right row of their group and
+    // the window is bounded by the tolerance instead.
+    let mut left = df!["time" => [0i64, 10, 20], "group" => [0i32, 1, 0]]?;
+    left.vstack_mut(&df!["time" => [30i64, 40], "group" => [1i32, 0]]?)?;
+    let n = 500;
+    let mut right = df![
+        "time" => [0i64, 5],
+        "group" => [0i32, 0],
+        "value" => [0i32, 1],
+    ]?;
+    for i in 1..n {
+        right.vstack_mut(&df![
+            "time" => [i as i64 * 10, i as i64 * 10 + 5],
+            "group" => [0i32, 0],
+            "value" => [2 * i, 2 * i + 1],
+        ]?)?;
+    }
+
+    for strategy in [AsofStrategy::Forward, AsofStrategy::Nearest] {
+        let join = |tolerance| {
+            let options = AsOfOptions {
+                strategy,
+                tolerance,
+                left_by: Some(vec!["group".into()]),
+                right_by: Some(vec!["group".into()]),
+                ..Default::default()
+            };
+            left.clone()
+                .lazy()
+                .join_builder()
+                .with(right.clone().lazy())
+                .left_on([col("time")])
+                .right_on([col("time")])
+                .how(JoinType::AsOf(options))
+                .finish()
+        };
+
+        let q = join(Some(AnyValue::Int64(3)));
+        assert_streaming_with_default(q.clone(), true, false);
+        let out = q.with_streaming(true).collect()?;
+        assert_eq!(
+            out.column("value")?.i32()?.to_vec(),
+            [Some(0), None, Some(4), None, Some(8)]
+        );
+
+        // without a tolerance the join stays in memory
+        let q = join(None);
+        assert!(!optimization_checks::is_pipeline(
+            q.clone().with_streaming(true)
+        ));
+        assert_eq!(q.clone().with_streaming(true).collect()?, q.collect()?);
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "merge_sorted", feature = "asof_join"))]
+fn test_streaming_merge_sorted() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_streaming_merge_sorted");
+    std::fs::create_dir_all(&dir)?;
+    // every file is sorted on "time", and the files interleave
+    let inputs = (0..3i64)
+        .map(|i| {
+            let mut df = df![
+                "time" => (0..1000i64).map(|t| t * 3 + i).collect::<Vec<_>>(),
+                "file" => vec![i; 1000],
+            ]?;
+            let path = dir.join(format!("{i}.csv"));
+            CsvWriter::new(std::fs::File::create(&path)?).finish(&mut df)?;
+            LazyCsvReader::new(path).finish()
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let right = df![
+        "time" => (0..600i64).map(|t| t * 5 + 1).collect::<Vec<_>>(),
+        "value" => (0..600i32).collect::<Vec<_>>(),
+    ]?
+    .lazy();
+
+    let merged = merge_sorted(&inputs, "time")?;
+    let q = merged
+        .clone()
+        .join_builder()
+        .with(right)
+        .left_on([col("time")])
+        .right_on([col("time")])
+        .how(JoinType::AsOf(Default::default()))
+        .finish();
+    assert_streaming_with_default(q, true, false);
+
+    let out = merged.with_streaming(true).collect()?;
+    let time = out.column("time")?;
+    assert_eq!(time.len(), 3000);
+    assert!(time.i64()?.into_no_null_iter().eq(0..3000));
+
+    // an unsorted input is an error
+    let mut unsorted_inputs = inputs.clone();
+    let mut df = df!["time" => [3i64, 1, 2], "file" => [3i64, 3, 3]]?;
+    let path = dir.join("unsorted.csv");
+    CsvWriter::new(std::fs::File::create(&path)?).finish(&mut df)?;
+    unsorted_inputs.push(LazyCsvReader::new(path).finish()?);
+    let out = merge_sorted(&unsorted_inputs, "time")?
+        .with_streaming(true)
+        .collect();
+    assert!(out.is_err());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "cross_join")]
 fn test_streaming_slice() -> PolarsResult<()> {
@@ -277,6 +799,25 @@ fn test_streaming_slice() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_streaming_slice_scan() -> PolarsResult<()> {
+    let q = get_csv_file().limit(5);
+    assert_streaming_with_default(q, true, false);
+
+    // The slice can't be pushed into the scan, the sink stops the source.
+    let q = get_csv_file()
+        .filter(col("calories").gt(lit(100)))
+        .slice(2, 4);
+    assert_streaming_with_default(q, true, false);
+
+    // More rows than the source has, so the source reads ahead.
+    let q = get_csv_file()
+        .filter(col("calories").gt(lit(100)))
+        .slice(1, 1000);
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
 #[test]
 fn test_streaming_partial() -> PolarsResult<()> {
     let lf_left = df![
@@ -428,3 +969,534 @@ fn test_streaming_full_outer_join() -> PolarsResult<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "semi_anti_join")]
+fn test_streaming_semi_anti_join() -> PolarsResult<()> {
+    let lf_left = df![
+        "a" => [Some(0), Some(1), None, Some(3), Some(0), Some(4), Some(9), None, Some(2)],
+        "b" => [0, 1, 2, 3, 4, 5, 6, 7, 8]
+    ]?
+    .lazy();
+    let lf_right = df![
+        "a" => [Some(0), None, Some(2), Some(2), Some(7)],
+        "c" => [0, 1, 2, 3, 4]
+    ]?
+    .lazy();
+
+    for how in [JoinType::Semi, JoinType::Anti] {
+        for join_nulls in [false, true] {
+            let q = lf_left
+                .clone()
+                .join_builder()
+                .with(lf_right.clone())
+                .on([col("a")])
+                .how(how.clone())
+                .join_nulls(join_nulls)
+                .finish();
+
+            assert_streaming_with_default(q, true, false);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_streaming_full_outer_join_coalesce() -> PolarsResult<()> {
+    let lf_small = df![
+        "key" => [10, 18, 13, 9, 1, 13, 14, 12, 15, 11],
+        "b" => [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+    ]?
+    .lazy();
+    let lf_large = df![
+        "a" => [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19],
+        "b" => [0, 0, 0, 3, 0, 1, 3, 3, 3, 1, 4, 4, 2, 1, 1, 3, 1, 4, 2, 2]
+    ]?
+    .lazy();
+
+    // Both join orders, with differently named keys.
+    for (lf_left, lf_right, left_on, right_on) in [
+        (lf_large.clone(), lf_small.clone(), "a", "key"),
+        (lf_small, lf_large, "key", "a"),
+    ] {
+        let q = lf_left
+            .join_builder()
+            .with(lf_right)
+            .left_on([col(left_on)])
+            .right_on([col(right_on)])
+            .how(JoinType::Full)
+            .coalesce(JoinCoalesce::CoalesceColumns)
+            .finish()
+            .sort_by_exprs([all()], SortMultipleOptions::default());
+
+        assert_streaming_with_default(q, true, false);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_streaming_sink_csv_partitioned() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_sink_csv_partitioned");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let df = df![
+        "date" => ["2024-01-01", "2024-01-02", "2024-01-01", "2024-01-03"],
+        "customer" => ["a", "b", "a", "c"],
+        "value" => [1, 2, 3, 4],
+    ]?;
+    df.lazy().sink_csv_partitioned(
+        &dir,
+        ["date", "customer"],
+        Default::default(),
+        Default::default(),
+    )?;
+
+    let read = |date: &str, customer: &str| {
+        std::fs::read_to_string(
+            dir.join(format!("date={date}"))
+                .join(format!("customer={customer}"))
+                .join("00000000.csv"),
+        )
+    };
+    assert_eq!(read("2024-01-01", "a")?, "value\n1\n3\n");
+    assert_eq!(read("2024-01-02", "b")?, "value\n2\n");
+    assert_eq!(read("2024-01-03", "c")?, "value\n4\n");
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn test_streaming_sink_parquet_partitioned() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_sink_parquet_partitioned");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let df = df![
+        "part" => ["a", "a", "b", "a"],
+        "value" => [1, 2, 3, 4],
+    ]?;
+    df.lazy().sink_parquet_partitioned(
+        &dir,
+        ["part"],
+        Default::default(),
+        PartitionedWriteOptions {
+            max_rows_per_file: Some(2),
+            write_metadata: true,
+        },
+    )?;
+
+    let read = |part: &str, file_name: &str| {
+        let file = std::fs::File::open(dir.join(format!("part={part}")).join(file_name))?;
+        ParquetReader::new(file).finish()
+    };
+    let a = read("a", "00000000.parquet")?.vstack(&read("a", "00000001.parquet")?)?;
+    assert!(a.equals(&df![
+        "part" => ["a", "a", "a"],
+        "value" => [1, 2, 4],
+    ]?));
+    assert!(read("b", "00000000.parquet")?.equals(&df![
+        "part" => ["b"],
+        "value" => [3],
+    ]?));
+    assert!(dir.join("_metadata").exists());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_streaming_sink_csv_to_writer() -> PolarsResult<()> {
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let df = df![
+        "a" => [1, 2, 3],
+        "b" => ["x", "y", "z"],
+    ]?;
+    let buf = SharedBuffer::default();
+    df.lazy()
+        .filter(col("a").gt(lit(1)))
+        .sink_csv_to_writer(buf.clone(), Default::default())?;
+
+    let written = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(written, "a,b\n2,y\n3,z\n");
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "ipc_streaming")]
+fn test_streaming_ipc_stream_roundtrip() -> PolarsResult<()> {
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let df = df![
+        "a" => [1, 2, 3],
+        "b" => ["x", "y", "z"],
+    ]?;
+    let buf = SharedBuffer::default();
+    df.lazy().sink_ipc_stream(buf.clone(), Default::default())?;
+
+    let bytes = buf.0.lock().unwrap().clone();
+    let q = LazyFrame::scan_ipc_stream(std::io::Cursor::new(bytes))?
+        .filter(col("a").gt(lit(1)))
+        .select([col("b")]);
+    assert_eq!(
+        q.clone().with_streaming(true).collect()?,
+        df!["b" => ["y", "z"]]?
+    );
+    // The stream is consumed by the first run.
+    assert!(q.collect().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_streaming_scan_source() -> PolarsResult<()> {
+    /// Produces the numbers up to `n` in two chunks per batch.
+    struct RangeSource {
+        n: i64,
+        next: i64,
+    }
+
+    impl Source for RangeSource {
+        fn get_batches(&mut self, _context: &PExecutionContext) -> PolarsResult<SourceResult> {
+            if self.next >= self.n {
+                return Ok(SourceResult::Finished);
+            }
+            let chunk_index = next_chunk_index(2);
+            let chunks = (0..2)
+                .map(|i| {
+                    let start = self.next + i * 5;
+                    let values = (start..self.n.min(start + 5)).collect::<Vec<_>>();
+                    Ok(DataChunk::new(
+                        chunk_index + i as IdxSize,
+                        df!["a" => values]?,
+                    ))
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            self.next += 10;
+            Ok(SourceResult::GotMoreData(chunks))
+        }
+
+        fn fmt(&self) -> &str {
+            "range"
+        }
+    }
+
+    let schema = Arc::new(Schema::from_iter([Field::new("a".into(), DataType::Int64)]));
+    let q = LazyFrame::scan_source(schema, || {
+        Ok(Box::new(RangeSource { n: 23, next: 0 }) as Box<dyn Source>)
+    })?
+    .filter(col("a").gt(lit(10)));
+
+    let expected = df!["a" => (11..23i64).collect::<Vec<_>>()]?;
+    assert_eq!(q.clone().with_streaming(true).collect()?, expected);
+    assert_eq!(q.collect()?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_streaming_sink_custom() -> PolarsResult<()> {
+    /// Sums the values of column `a` over all threads.
+    struct SumSink {
+        total: Arc<std::sync::Mutex<i64>>,
+        sum: i64,
+    }
+
+    impl Sink for SumSink {
+        fn sink(
+            &mut self,
+            _context: &PExecutionContext,
+            chunk: DataChunk,
+        ) -> PolarsResult<SinkResult> {
+            self.sum += chunk.data.column("a")?.i64()?.sum().unwrap_or(0);
+            Ok(SinkResult::CanHaveMoreInput)
+        }
+
+        fn combine(&mut self, other: &mut dyn Sink) {
+            self.sum += other.as_any().downcast_ref::<SumSink>().unwrap().sum;
+        }
+
+        fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+            Box::new(SumSink {
+                total: self.total.clone(),
+                sum: 0,
+            })
+        }
+
+        fn finalize(&mut self, _context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+            *self.total.lock().unwrap() = self.sum;
+            Ok(FinalizedSink::Finished(DataFrame::empty()))
+        }
+
+        fn as_any(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+
+        fn fmt(&self) -> &str {
+            "sum"
+        }
+    }
+
+    let total = Arc::new(std::sync::Mutex::new(0));
+    df!["a" => (0..100i64).collect::<Vec<_>>()]?
+        .lazy()
+        .filter(col("a").gt_eq(lit(50)))
+        .sink_custom(SumSink {
+            total: total.clone(),
+            sum: 0,
+        })?;
+
+    assert_eq!(*total.lock().unwrap(), (50..100i64).sum::<i64>());
+    Ok(())
+}
+
+#[test]
+fn test_streaming_metrics() -> PolarsResult<()> {
+    let df = df![
+        "g" => (0..12_345i64).map(|v| v % 3).collect::<Vec<_>>(),
+        "a" => (0..12_345i64).collect::<Vec<_>>(),
+    ]?;
+    let q = df
+        .lazy()
+        .filter(col("a").gt_eq(lit(12_000)))
+        .group_by([col("g")])
+        .agg([col("a").sum()])
+        .with_predicate_pushdown(false);
+    let (_, metrics) = q
+        .clone()
+        .with_streaming(true)
+        .collect_with_streaming_metrics()?;
+
+    // only the nodes of this query are returned, the scan, the filter and the group_by come
+    // first
+    let kinds = metrics.column("kind")?.str()?;
+    assert_eq!(
+        kinds.into_iter().take(3).collect::<Vec<_>>(),
+        [Some("source"), Some("operator"), Some("sink")]
+    );
+    let counter = |name: &str| -> PolarsResult<Vec<Option<u64>>> {
+        Ok(metrics.column(name)?.u64()?.to_vec())
+    };
+    assert_eq!(counter("rows_in")?[..3], [Some(0), Some(12_345), Some(345)]);
+    assert_eq!(counter("rows_out")?[..2], [Some(12_345), Some(345)]);
+    assert!(counter("spill_bytes")?
+        .iter()
+        .all(|bytes| *bytes == Some(0)));
+
+    let (_, metrics) = q.collect_with_streaming_metrics()?;
+    assert_eq!(metrics.height(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_streaming_memory_limit() -> PolarsResult<()> {
+    let df = df![
+        "a" => (0..100_000i64).rev().collect::<Vec<_>>(),
+        "b" => (0..100_000i64).map(|v| v % 7).collect::<Vec<_>>(),
+    ]?;
+    // The parts are pushed in the sort one after the other.
+    let parts = (0..4)
+        .map(|i| df.slice(i * 25_000, 25_000).lazy())
+        .collect::<Vec<_>>();
+    let q = concat(parts, Default::default())?.sort(["a"], Default::default());
+    let expected = df.sort(["a"], Default::default())?;
+
+    // The sort spills under a limit that is smaller than its input.
+    let limited = q.clone().with_memory_limit(1).with_streaming(true);
+    assert!(limited.explain(true)?.contains("memory limit: 1"));
+    let (out, metrics) = limited.collect_with_streaming_metrics()?;
+    assert_eq!(out, expected);
+    let spilled = metrics.column("spill_bytes")?.u64()?.sum().unwrap_or(0);
+    assert!(spilled > 0, "{metrics}");
+
+    // Without a limit the sort stays in memory.
+    let (out, metrics) = q.with_streaming(true).collect_with_streaming_metrics()?;
+    assert_eq!(out, expected);
+    assert_eq!(metrics.column("spill_bytes")?.u64()?.sum(), Some(0));
+    Ok(())
+}
+
+#[test]
+fn test_streaming_profile() -> PolarsResult<()> {
+    let q = get_csv_file()
+        .filter(col("sugars_g").gt(lit(1)))
+        .group_by([col("category")])
+        .agg([col("calories").sum()])
+        .sort(["category"], Default::default());
+    let (out, profile) = q.clone().with_streaming(true).profile()?;
+    assert_eq!(out, q.with_streaming(false).collect()?);
+
+    // The source, the operator and the sink of the pipeline are timed.
+    let nodes = profile.column("node")?.str()?;
+    for node in ["csv", "filter"] {
+        assert!(nodes.into_iter().any(|n| n == Some(node)), "{profile}");
+    }
+    let start = profile.column("start")?.u64()?;
+    let end = profile.column("end")?.u64()?;
+    assert!(start
+        .into_no_null_iter()
+        .zip(end.into_no_null_iter())
+        .all(|(start, end)| start <= end));
+    Ok(())
+}
+
+#[test]
+fn test_streaming_explain_analyze() -> PolarsResult<()> {
+    let df = df![
+        "g" => (0..12_345i64).map(|v| v % 3).collect::<Vec<_>>(),
+        "a" => (0..12_345i64).collect::<Vec<_>>(),
+    ]?;
+    let (out, explain) = df
+        .lazy()
+        .filter(col("a").gt_eq(lit(12_000)))
+        .group_by([col("g")])
+        .agg([col("a").sum()])
+        .with_predicate_pushdown(false)
+        .with_streaming(true)
+        .explain_analyze()?;
+    assert_eq!(out.height(), 3);
+
+    // The nodes of the pipeline are annotated with its metrics, and the root, which runs the
+    // pipeline, with the statistics of the in-memory engine as well.
+    assert!(explain.contains("STREAMING"), "{explain}");
+    let annotated = |annotation: &str| {
+        explain
+            .lines()
+            .any(|line| line.trim_start().starts_with("[p") && line.contains(annotation))
+    };
+    assert!(annotated("source: 12345 rows out"), "{explain}");
+    assert!(
+        annotated("operator: 12345 rows in, 345 rows out"),
+        "{explain}"
+    );
+    assert!(annotated("rows: 3, "), "{explain}");
+    assert!(annotated("sink: 345 rows in"), "{explain}");
+    Ok(())
+}
+
+#[test]
+fn test_streaming_explode() -> PolarsResult<()> {
+    // the lists explode into more rows than fit in a chunk
+    let values = (0..1_000i32)
+        .map(|i| {
+            Series::new(
+                PlSmallStr::EMPTY,
+                (i * 100..(i + 1) * 100).collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let df = df![
+        "id" => (0..1_000i32).collect::<Vec<_>>(),
+        "values" => values,
+    ]?;
+
+    let q = df
+        .clone()
+        .lazy()
+        .explode([col("values")])
+        .filter(col("values").gt(lit(10)));
+    assert_streaming_with_default(q, true, false);
+
+    // not the same expression twice, which the common subexpression elimination would explode
+    // in a `with_columns` that isn't streamed
+    let q = df.lazy().select([
+        col("values").explode(),
+        col("values").list().reverse().explode().alias("reversed"),
+    ]);
+    assert_streaming_with_default(q.clone(), true, false);
+    let out = q.with_streaming(true).collect()?;
+    assert_eq!(out.height(), 100_000);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "dot_diagram")]
+fn test_streaming_dot_pipelines() -> PolarsResult<()> {
+    let q = get_csv_file()
+        .filter(col("sugars_g").gt(lit(10)))
+        .group_by([col("category")])
+        .agg([col("calories").sum()])
+        .with_streaming(true);
+
+    let dot = q.to_dot(true)?;
+    assert!(dot.starts_with("graph  polars_query {"));
+    assert!(dot.contains("STREAMING"));
+    assert!(dot.contains("pipeline 0: source"));
+    assert!(dot.contains("pipeline 0: sink"));
+
+    // the nodes of the diagram have the ids of the explained plan
+    let explained = q.explain_with_node_ids(true)?;
+    for line in explained.lines() {
+        let id = line
+            .trim()
+            .strip_prefix("[p")
+            .and_then(|l| l.strip_suffix(']'));
+        if let Some(id) = id.filter(|id| id.bytes().all(|b| b.is_ascii_digit())) {
+            assert!(dot.contains(&format!("  p{id}[label=")));
+        }
+    }
+
+    let mermaid = q.to_mermaid(true)?;
+    assert!(mermaid.starts_with("graph TD"));
+    assert!(mermaid.contains("<br>pipeline 0: source"));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "cse")]
+fn test_streaming_collect_all_shared_subplan() -> PolarsResult<()> {
+    let shared = get_csv_file()
+        .filter(col("sugars_g").gt(lit(1)))
+        .with_column((col("calories") * lit(2)).alias("double_calories"));
+    let queries = [
+        shared
+            .clone()
+            .filter(col("fats_g").gt(lit(1)))
+            .select([col("category"), col("double_calories")]),
+        shared
+            .clone()
+            .group_by([col("category")])
+            .agg([col("double_calories").sum()])
+            .sort(["category"], Default::default()),
+        shared.clone().slice(2, 5),
+    ]
+    .map(|q| q.with_streaming(true));
+
+    // the queries compute the subplan of `shared` once
+    let out = crate::physical_plan::streaming::collect_all_shared_subplan(&queries)?.unwrap();
+    assert_eq!(out.len(), queries.len());
+    for (q, out) in queries.iter().zip(&out) {
+        let expected = q.clone().with_streaming(false).collect()?;
+        assert_eq!(out, &expected);
+    }
+
+    let out = collect_all(queries.clone())?;
+    for (q, out) in queries.iter().zip(&out) {
+        assert_eq!(out, &q.clone().with_streaming(false).collect()?);
+    }
+    Ok(())
+}
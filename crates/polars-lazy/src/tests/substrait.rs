@@ -0,0 +1,160 @@
+use serde_json::Value;
+
+use super::*;
+
+fn to_substrait(q: LazyFrame) -> Value {
+    serde_json::from_str(&q.to_substrait().unwrap()).unwrap()
+}
+
+/// The kinds of the relations of the plan, from the root to the leaves.
+fn relation_kinds(rel: &Value, kinds: &mut Vec<String>) {
+    let (kind, rel) = rel.as_object().unwrap().iter().next().unwrap();
+    kinds.push(kind.clone());
+    for key in ["input", "left", "right"] {
+        if let Some(input) = rel.get(key) {
+            relation_kinds(input, kinds);
+        }
+    }
+    if let Some(Value::Array(inputs)) = rel.get("inputs") {
+        for input in inputs {
+            relation_kinds(input, kinds);
+        }
+    }
+}
+
+fn function_names(plan: &Value) -> Vec<&str> {
+    plan["extensions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["extensionFunction"]["name"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_substrait_select() {
+    let q = load_df()
+        .lazy()
+        .filter(col("a").gt(lit(1)))
+        .select([col("b"), (col("a") + col("c")).alias("sum")])
+        .sort(
+            ["sum"],
+            SortMultipleOptions::default().with_order_descending(true),
+        )
+        .slice(1, 2);
+    let plan = to_substrait(q);
+
+    let root = &plan["relations"][0]["root"];
+    assert_eq!(root["names"], serde_json::json!(["b", "sum"]));
+    let mut kinds = vec![];
+    relation_kinds(&root["input"], &mut kinds);
+    assert_eq!(kinds.first().map(|k| k.as_str()), Some("fetch"));
+    assert_eq!(kinds.last().map(|k| k.as_str()), Some("read"));
+    assert!(kinds.iter().any(|k| k == "sort"));
+    assert!(kinds.iter().any(|k| k == "project"));
+
+    let functions = function_names(&plan);
+    assert!(functions.contains(&"gt:any_any"));
+    assert!(functions.contains(&"add:i32_i32"));
+}
+
+#[test]
+fn test_substrait_group_by_join() {
+    let right = df!["b" => ["a", "b"], "d" => [10, 20]].unwrap().lazy();
+    let q = load_df()
+        .lazy()
+        .join(right, [col("b")], [col("b")], JoinType::Inner.into())
+        .group_by([col("b")])
+        .agg([col("a").sum(), col("d").count()]);
+    let plan = to_substrait(q);
+
+    let root = &plan["relations"][0]["root"];
+    assert_eq!(root["names"], serde_json::json!(["b", "a", "d"]));
+    let mut kinds = vec![];
+    relation_kinds(&root["input"], &mut kinds);
+    assert_eq!(kinds[0], "aggregate");
+    assert!(kinds.iter().any(|k| k == "join"));
+
+    let functions = function_names(&plan);
+    assert!(functions.contains(&"equal:any_any"));
+    assert!(functions.contains(&"sum:i32"));
+    assert!(functions.contains(&"count:any"));
+}
+
+#[test]
+fn test_substrait_unsupported() {
+    let q = load_df()
+        .lazy()
+        .select([col("a").map(|s| Ok(Some(s)), GetOutput::same_type())]);
+    let err = q.to_substrait().unwrap_err();
+    assert!(err.to_string().contains("cannot be exported to Substrait"));
+}
+
+#[test]
+fn test_substrait_round_trip() -> PolarsResult<()> {
+    let right = df!["b" => ["a", "c"], "d" => [10, 20]]?.lazy();
+    let queries = [
+        load_df()
+            .lazy()
+            .filter(col("a").gt(lit(1)))
+            .with_columns([(col("a") * col("c")).alias("p")])
+            .sort(
+                ["p"],
+                SortMultipleOptions::default().with_order_descending(true),
+            )
+            .slice(0, 3),
+        load_df()
+            .lazy()
+            .join(right, [col("b")], [col("b")], JoinType::Left.into())
+            .group_by([col("b")])
+            .agg([col("a").sum(), col("d").max()])
+            .sort(["b"], Default::default()),
+    ];
+    for q in queries {
+        let plan = q.to_substrait()?;
+        let out = LazyFrame::from_substrait(&plan, &PlHashMap::new())?.collect()?;
+        assert!(out.equals_missing(&q.collect()?));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_substrait_named_table() -> PolarsResult<()> {
+    let plan = r#"{
+        "extensionUris": [{"extensionUriAnchor": 1, "uri": "functions_comparison.yaml"}],
+        "extensions": [
+            {"extensionFunction": {"extensionUriReference": 1, "functionAnchor": 1, "name": "gt:any_any"}}
+        ],
+        "relations": [{"root": {
+            "input": {"project": {
+                "common": {"emit": {"outputMapping": [1, 3]}},
+                "input": {"filter": {
+                    "input": {"read": {
+                        "baseSchema": {
+                            "names": ["a", "b", "c"],
+                            "struct": {"types": [{"i32": {}}, {"string": {}}, {"i32": {}}]}
+                        },
+                        "namedTable": {"names": ["t"]}
+                    }},
+                    "condition": {"scalarFunction": {
+                        "functionReference": 1,
+                        "arguments": [
+                            {"value": {"selection": {"directReference": {"structField": {}}}}},
+                            {"value": {"literal": {"i32": 2}}}
+                        ]
+                    }}
+                }},
+                "expressions": [{"selection": {"directReference": {"structField": {"field": 2}}}}]
+            }},
+            "names": ["name", "value"]
+        }}]
+    }"#;
+    let tables = PlHashMap::from_iter([("t".to_string(), load_df().lazy())]);
+    let out = LazyFrame::from_substrait(plan, &tables)?.collect()?;
+    let expected = df!["name" => ["b", "c", "c"], "value" => [3, 4, 5]]?;
+    assert!(out.equals_missing(&expected));
+
+    let err = LazyFrame::from_substrait(plan, &PlHashMap::new()).err().unwrap();
+    assert!(err.to_string().contains("the table t"));
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::*;
+
+/// The runtime statistics of a node of a physical plan.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NodeStats {
+    /// The number of times that the node ran.
+    pub runs: usize,
+    /// The rows that the node produced, summed over its runs.
+    pub rows: usize,
+    /// The estimated size in bytes of what the node produced, summed over its runs.
+    pub bytes: usize,
+    /// The wall time of the node and its inputs, summed over its runs.
+    pub time: Duration,
+}
+
+/// The runtime statistics of the nodes of a physical plan that was created with
+/// [`create_physical_plan_with_stats`](crate::create_physical_plan_with_stats), keyed by the
+/// nodes of its logical plan.
+#[derive(Clone, Default)]
+pub struct PlanStats(Arc<Mutex<PlHashMap<Node, NodeStats>>>);
+
+impl PlanStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The statistics of `node`, if it ran.
+    pub fn get(&self, node: Node) -> Option<NodeStats> {
+        self.0.lock().unwrap().get(&node).copied()
+    }
+
+    /// The statistics of all nodes that ran.
+    pub fn to_map(&self) -> PlHashMap<Node, NodeStats> {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn record(&self, node: Node, df: &DataFrame, time: Duration) {
+        let mut stats = self.0.lock().unwrap();
+        let stats = stats.entry(node).or_default();
+        stats.runs += 1;
+        stats.rows += df.height();
+        stats.bytes += df.estimated_size();
+        stats.time += time;
+    }
+}
+
+/// Records the statistics of the executor of a node.
+pub(crate) struct AnalyzeExec {
+    pub(crate) input: Box<dyn Executor>,
+    pub(crate) node: Node,
+    pub(crate) stats: PlanStats,
+}
+
+impl Executor for AnalyzeExec {
+    fn execute(&mut self, state: &mut ExecutionState) -> PolarsResult<DataFrame> {
+        let start = Instant::now();
+        let df = self.input.execute(state)?;
+        self.stats.record(self.node, &df, start.elapsed());
+        Ok(df)
+    }
+}
@@ -1,3 +1,4 @@
+mod analyze;
 mod cache;
 mod executor;
 mod ext_context;
@@ -21,6 +22,7 @@ mod unique;
 
 use std::borrow::Cow;
 
+pub use analyze::{NodeStats, PlanStats};
 pub use executor::*;
 use polars_core::POOL;
 use polars_plan::global::FETCH_ROWS;
@@ -28,6 +30,7 @@ use polars_plan::utils::*;
 use projection_utils::*;
 use rayon::prelude::*;
 
+pub(super) use self::analyze::AnalyzeExec;
 pub(super) use self::cache::*;
 pub(super) use self::ext_context::*;
 pub(super) use self::filter::*;
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use hive::HivePartitions;
 use polars_core::config;
 use polars_core::utils::{
     accumulate_dataframes_vertical, accumulate_dataframes_vertical_unchecked,
 };
+use polars_io::predicates::apply_predicate;
 
 use super::*;
 
@@ -13,16 +15,30 @@ pub struct CsvExec {
     pub options: CsvReadOptions,
     pub file_options: FileScanOptions,
     pub predicate: Option<Arc<dyn PhysicalExpr>>,
+    pub hive_parts: Option<Arc<Vec<HivePartitions>>>,
 }
 
 impl CsvExec {
     fn read(&self) -> PolarsResult<DataFrame> {
-        let with_columns = self
-            .file_options
-            .with_columns
-            .clone()
-            // Interpret selecting no columns as selecting all columns.
-            .filter(|columns| !columns.is_empty());
+        let reader_schema = self.file_info.reader_schema.clone().unwrap().unwrap_right();
+        // If only hive partition columns are projected, the first column of the files is read to
+        // know the number of rows.
+        let hive_height_column = self
+            .hive_parts
+            .as_ref()
+            .zip(self.file_options.with_columns.as_ref())
+            .filter(|(_, columns)| columns.is_empty())
+            .and_then(|_| reader_schema.get_at_index(0))
+            .map(|(name, _)| name.clone());
+        let with_columns = match &hive_height_column {
+            Some(name) => Some(Arc::from([name.clone()])),
+            None => self
+                .file_options
+                .with_columns
+                .clone()
+                // Interpret selecting no columns as selecting all columns.
+                .filter(|columns| !columns.is_empty()),
+        };
 
         let n_rows = _set_n_rows_for_scan(self.file_options.slice.map(|x| {
             assert_eq!(x.0, 0);
@@ -32,9 +48,7 @@ impl CsvExec {
         let options_base = self
             .options
             .clone()
-            .with_schema(Some(
-                self.file_info.reader_schema.clone().unwrap().unwrap_right(),
-            ))
+            .with_schema(Some(reader_schema.clone()))
             .with_columns(with_columns)
             .with_rechunk(
                 // We rechunk at the end to avoid rechunking multiple times in the
@@ -69,11 +83,24 @@ impl CsvExec {
                 let memslice = source.to_memslice_async_latest(run_async)?;
 
                 let reader = std::io::Cursor::new(maybe_decompress_bytes(&memslice, owned)?);
+                // The predicate may refer to the hive partition columns, so it is applied after
+                // they are materialized.
                 let mut df = options
                     .into_reader_with_file_handle(reader)
-                    ._with_predicate(predicate.clone())
+                    ._with_predicate(predicate.clone().filter(|_| self.hive_parts.is_none()))
                     .finish()?;
 
+                if let Some(hive_parts) = &self.hive_parts {
+                    let num_rows = df.height();
+                    if let Some(name) = &hive_height_column {
+                        df = df.drop(name)?;
+                    }
+                    for s in hive_parts[i].materialize_partition_columns() {
+                        df.with_column(s.new_from_index(0, num_rows))?;
+                    }
+                    apply_predicate(&mut df, predicate.as_deref(), true)?;
+                }
+
                 if let Some(col) = &self.file_options.include_file_paths {
                     let name = source.to_include_path_name();
 
@@ -175,7 +202,10 @@ impl CsvExec {
             }
 
             let dfs = POOL.install(|| {
-                let step = std::cmp::min(POOL.current_num_threads(), 128);
+                let step = self
+                    .file_options
+                    .max_concurrent_files
+                    .map_or(std::cmp::min(POOL.current_num_threads(), 128), |n| n.get());
 
                 (0..self.sources.len())
                     .step_by(step)
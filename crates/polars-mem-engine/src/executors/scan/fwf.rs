@@ -0,0 +1,135 @@
+use polars_core::config;
+use polars_core::utils::accumulate_dataframes_vertical;
+use polars_io::fwf::{FwfReadOptions, FwfReader};
+use polars_io::RowIndex;
+
+use super::*;
+
+pub struct FwfExec {
+    sources: ScanSources,
+    options: FwfReadOptions,
+    file_options: FileScanOptions,
+    file_info: FileInfo,
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+}
+
+impl FwfExec {
+    pub fn new(
+        sources: ScanSources,
+        options: FwfReadOptions,
+        file_options: FileScanOptions,
+        file_info: FileInfo,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+    ) -> Self {
+        Self {
+            sources,
+            options,
+            file_options,
+            file_info,
+            predicate,
+        }
+    }
+
+    fn read(&mut self) -> PolarsResult<DataFrame> {
+        if config::verbose() {
+            eprintln!(
+                "executing fixed-width read with row_index = {:?}, n_rows = {:?}, predicate = {:?} for sources {:?}",
+                self.file_options.row_index.as_ref(),
+                self.file_options.slice.map(|x| x.1),
+                self.predicate.is_some(),
+                self.sources,
+            );
+        }
+
+        let mut n_rows = self.file_options.slice.map(|x| {
+            assert_eq!(x.0, 0);
+            x.1
+        });
+        let columns = self.file_options.with_columns.clone();
+        let predicate = self.predicate.clone().map(phys_expr_to_io_expr);
+
+        let mut rows_read = 0;
+        let mut dfs = Vec::with_capacity(self.sources.len());
+        for source in self.sources.iter() {
+            if n_rows == Some(0) {
+                break;
+            }
+
+            let row_index = self.file_options.row_index.as_ref().map(|ri| RowIndex {
+                name: ri.name.clone(),
+                offset: ri.offset + rows_read as IdxSize,
+            });
+            let memslice = source.to_memslice()?;
+            let mut df = FwfReader::new(std::io::Cursor::new(memslice))
+                .with_options(self.options.clone())
+                .with_columns(columns.clone())
+                .with_n_rows(n_rows)
+                .with_row_index(row_index)
+                .set_rechunk(false)
+                .finish()?;
+
+            // The predicate is applied after the rows of the file are counted, so that the row
+            // index and the row limit of the next file are correct.
+            rows_read += df.height();
+            if let Some(n_rows) = &mut n_rows {
+                *n_rows -= df.height();
+            }
+            if let Some(predicate) = &predicate {
+                let mask = predicate.evaluate_io(&df)?;
+                df = df.filter(mask.bool()?)?;
+            }
+            if let Some(col) = &self.file_options.include_file_paths {
+                let name = source.to_include_path_name();
+                unsafe {
+                    df.with_column_unchecked(
+                        StringChunked::full(col.clone(), name, df.height()).into_series(),
+                    )
+                };
+            }
+            dfs.push(df);
+        }
+
+        if dfs.is_empty() {
+            let schema = self
+                .file_info
+                .reader_schema
+                .as_ref()
+                .unwrap()
+                .as_ref()
+                .unwrap_right();
+            let mut df = DataFrame::empty_with_schema(schema);
+            if let Some(columns) = &columns {
+                df = df.select(columns.iter().cloned())?;
+            }
+            if let Some(row_index) = &self.file_options.row_index {
+                df.with_row_index_mut(row_index.name.clone(), Some(row_index.offset));
+            }
+            if let Some(col) = &self.file_options.include_file_paths {
+                unsafe {
+                    df.with_column_unchecked(Series::new_empty(col.clone(), &DataType::String))
+                };
+            }
+            return Ok(df);
+        }
+
+        let mut df = accumulate_dataframes_vertical(dfs)?;
+        if self.file_options.rechunk {
+            df.as_single_chunk_par();
+        }
+        Ok(df)
+    }
+}
+
+impl Executor for FwfExec {
+    fn execute(&mut self, state: &mut ExecutionState) -> PolarsResult<DataFrame> {
+        let profile_name = if state.has_node_timer() {
+            let ids = vec![self.sources.id()];
+            let name = comma_delimited("fwf".to_string(), &ids);
+            Cow::Owned(name)
+        } else {
+            Cow::Borrowed("")
+        };
+
+        state.record(|| self.read(), profile_name)
+    }
+}
@@ -128,11 +128,22 @@ impl IpcExec {
 
             out
         } else {
+            let step = self
+                .file_options
+                .max_concurrent_files
+                .map_or(self.sources.len().max(1), |n| n.get());
             POOL.install(|| {
-                (0..self.sources.len())
-                    .into_par_iter()
-                    .map(|i| read_path(i, None))
-                    .collect::<PolarsResult<Vec<_>>>()
+                let mut out = Vec::with_capacity(self.sources.len());
+                for start in (0..self.sources.len()).step_by(step) {
+                    let end = std::cmp::min(start.saturating_add(step), self.sources.len());
+                    out.extend(
+                        (start..end)
+                            .into_par_iter()
+                            .map(|i| read_path(i, None))
+                            .collect::<PolarsResult<Vec<_>>>()?,
+                    );
+                }
+                PolarsResult::Ok(out)
             })?
         };
 
@@ -1,5 +1,9 @@
+#[cfg(feature = "avro")]
+mod avro;
 #[cfg(feature = "csv")]
 mod csv;
+#[cfg(feature = "fwf")]
+mod fwf;
 #[cfg(feature = "ipc")]
 mod ipc;
 #[cfg(feature = "json")]
@@ -11,8 +15,12 @@ mod python_scan;
 
 use std::mem;
 
+#[cfg(feature = "avro")]
+pub(crate) use avro::AvroExec;
 #[cfg(feature = "csv")]
 pub(crate) use csv::CsvExec;
+#[cfg(feature = "fwf")]
+pub(crate) use fwf::FwfExec;
 #[cfg(feature = "ipc")]
 pub(crate) use ipc::IpcExec;
 #[cfg(feature = "json")]
@@ -109,12 +117,14 @@ pub(crate) struct AnonymousScanExec {
 
 impl Executor for AnonymousScanExec {
     fn execute(&mut self, state: &mut ExecutionState) -> PolarsResult<DataFrame> {
+        let n_rows = self.file_options.slice.map(|x| {
+            assert_eq!(x.0, 0);
+            x.1
+        });
+        let with_columns = self.file_options.with_columns.clone();
         let mut args = AnonymousScanArgs {
-            n_rows: self.file_options.slice.map(|x| {
-                assert_eq!(x.0, 0);
-                x.1
-            }),
-            with_columns: self.file_options.with_columns.clone(),
+            n_rows,
+            with_columns: with_columns.clone(),
             schema: self.file_info.schema.clone(),
             output_schema: self.output_schema.clone(),
             predicate: None,
@@ -122,18 +132,19 @@ impl Executor for AnonymousScanExec {
         if self.predicate.is_some() {
             state.insert_has_window_function_flag()
         }
+        if self.function.allows_predicate_pushdown() {
+            args.predicate = self
+                .predicate
+                .as_ref()
+                .and_then(|predicate| predicate.as_expression().cloned());
+        }
+
+        state.record(
+            || {
+                let (mut df, applied) = self.function.scan_with_pushdowns(args)?;
 
-        match (self.function.allows_predicate_pushdown(), &self.predicate) {
-            (true, Some(predicate)) => state.record(
-                || {
-                    args.predicate = predicate.as_expression().cloned();
-                    self.function.scan(args)
-                },
-                "anonymous_scan".into(),
-            ),
-            (false, Some(predicate)) => state.record(
-                || {
-                    let mut df = self.function.scan(args)?;
+                // Apply the pushdowns that the scan didn't apply.
+                if let Some(predicate) = self.predicate.as_ref().filter(|_| !applied.predicate) {
                     let s = predicate.evaluate(&df, state)?;
                     if self.predicate_has_windows {
                         state.clear_window_expr_cache()
@@ -142,12 +153,20 @@ impl Executor for AnonymousScanExec {
                         |_| polars_err!(ComputeError: "filter predicate was not of type boolean"),
                     )?;
                     df = df.filter(mask)?;
-
-                    Ok(df)
-                },
-                "anonymous_scan".into(),
-            ),
-            _ => state.record(|| self.function.scan(args), "anonymous_scan".into()),
-        }
+                }
+                if let Some(columns) = with_columns
+                    .as_ref()
+                    .filter(|columns| !applied.projection && !columns.is_empty())
+                {
+                    df = df.select(columns.iter().cloned())?;
+                }
+                if let Some(n_rows) = n_rows.filter(|_| !applied.slice) {
+                    df = df.head(Some(n_rows));
+                }
+
+                Ok(df)
+            },
+            "anonymous_scan".into(),
+        )
     }
 }
@@ -58,7 +58,10 @@ impl ParquetExec {
 
         let mut result = vec![];
 
-        let step = std::cmp::min(POOL.current_num_threads(), 128);
+        let step = self
+            .file_options
+            .max_concurrent_files
+            .map_or(std::cmp::min(POOL.current_num_threads(), 128), |n| n.get());
         // Modified if we have a negative slice
         let mut first_source = 0;
 
@@ -81,8 +84,12 @@ impl ParquetExec {
                         let row_counts = path_indexes
                             .into_par_iter()
                             .map(|&i| {
-                                let memslice = self.sources.at(i).to_memslice()?;
-                                ParquetReader::new(std::io::Cursor::new(memslice)).num_rows()
+                                let source = self.sources.at(i);
+                                let memslice = source.to_memslice()?;
+                                ParquetReader::new(std::io::Cursor::new(memslice))
+                                    .with_decryption(self.options.decryption.clone())
+                                    .with_metadata_cache(source.as_path())
+                                    .num_rows()
                             })
                             .collect::<PolarsResult<Vec<_>>>()?;
 
@@ -152,6 +159,8 @@ impl ParquetExec {
                 let memslice = source.to_memslice()?;
 
                 let mut reader = ParquetReader::new(std::io::Cursor::new(memslice))
+                    .with_decryption(self.options.decryption.clone())
+                    .with_metadata_cache(source.as_path())
                     .read_parallel(parallel)
                     .set_low_memory(self.options.low_memory)
                     .use_statistics(self.options.use_statistics)
@@ -236,9 +245,13 @@ impl ParquetExec {
         let paths = self.sources.into_paths().unwrap();
         let first_metadata = &self.metadata;
         let cloud_options = self.cloud_options.as_ref();
+        let decryption = &self.options.decryption;
 
         let mut result = vec![];
-        let batch_size = get_file_prefetch_size();
+        let batch_size = self
+            .file_options
+            .max_concurrent_files
+            .map_or(get_file_prefetch_size(), |n| n.get());
 
         if verbose {
             eprintln!("POLARS PREFETCH_SIZE: {}", batch_size)
@@ -259,6 +272,7 @@ impl ParquetExec {
 
                 let paths = &paths;
                 let cloud_options = Arc::new(self.cloud_options.clone());
+                let decryption = decryption.clone();
 
                 let paths = paths.clone();
                 let cloud_options = cloud_options.clone();
@@ -266,6 +280,7 @@ impl ParquetExec {
                 let mut iter = stream::iter((0..paths.len()).rev().map(|i| {
                     let paths = paths.clone();
                     let cloud_options = cloud_options.clone();
+                    let decryption = decryption.clone();
 
                     pl_async::get_runtime().spawn(async move {
                         PolarsResult::Ok((
@@ -276,6 +291,7 @@ impl ParquetExec {
                                 None,
                             )
                             .await?
+                            .with_decryption(decryption)
                             .num_rows()
                             .await?,
                         ))
@@ -343,7 +359,8 @@ impl ParquetExec {
                 };
                 let mut reader =
                     ParquetAsyncReader::from_uri(&path.to_string_lossy(), cloud_options, metadata)
-                        .await?;
+                        .await?
+                        .with_decryption(decryption.clone());
 
                 let num_rows = reader.num_rows().await?;
                 PolarsResult::Ok((num_rows, reader))
@@ -438,6 +455,92 @@ impl ParquetExec {
         Ok(result)
     }
 
+    /// Read the files from plain HTTP(S) servers one at a time, as every file is already
+    /// fetched with concurrent range requests.
+    #[cfg(feature = "parquet_http")]
+    fn read_http(&mut self) -> PolarsResult<Vec<DataFrame>> {
+        let paths = self.sources.into_paths().unwrap();
+        let readers = paths.iter().enumerate().map(|(i, path)| {
+            // Use the cached metadata of the first file as this saves a request.
+            let metadata = if i == 0 { self.metadata.clone() } else { None };
+            ParquetHttpReader::new(&path.to_string_lossy()).with_metadata(metadata)
+        });
+        let mut readers = readers.collect::<Vec<_>>();
+
+        // (offset, end)
+        let (slice_offset, slice_end) = match self.file_options.slice {
+            Some((offset, len)) if offset >= 0 => {
+                (offset as usize, len.saturating_add(offset as usize))
+            },
+            Some((offset, len)) => {
+                let mut total_rows = 0usize;
+                for reader in readers.iter_mut() {
+                    total_rows += reader.num_rows()?;
+                }
+                let n_from_end = -offset as usize;
+                // E.g. SLICE[offset: -100, len: 75] on 50 rows should only give the first 25 rows.
+                let (start, len) = if n_from_end > total_rows {
+                    (0, len.saturating_sub(n_from_end - total_rows))
+                } else {
+                    (total_rows - n_from_end, len)
+                };
+                (start, start.saturating_add(len))
+            },
+            None => (0, usize::MAX),
+        };
+
+        let mut result = vec![];
+        let mut current_offset = 0;
+        let base_row_index = self.file_options.row_index.take();
+        let reader_schema = self.file_info.reader_schema.clone().unwrap().unwrap_left();
+
+        for (i, mut reader) in readers.into_iter().enumerate() {
+            if current_offset >= slice_end && !result.is_empty() {
+                break;
+            }
+            let cumulative_read = current_offset;
+            let num_rows = reader.num_rows()?;
+            let slice = split_slice_at_file(&mut current_offset, num_rows, slice_offset, slice_end);
+
+            let hive_partitions = self
+                .hive_parts
+                .as_ref()
+                .map(|x| x[i].materialize_partition_columns());
+            let (projection, predicate) = prepare_scan_args(
+                self.predicate.clone(),
+                &mut self.file_options.with_columns.clone(),
+                &mut self.file_info.schema.clone(),
+                base_row_index.is_some(),
+                hive_partitions.as_deref(),
+            );
+            let row_index = base_row_index.as_ref().map(|rc| RowIndex {
+                name: rc.name.clone(),
+                offset: rc.offset + cumulative_read as IdxSize,
+            });
+
+            let df = reader
+                .with_slice(Some(slice))
+                .with_row_index(row_index)
+                .with_projection(projection)
+                .check_schema(reader_schema.as_ref())?
+                .with_predicate(predicate)
+                .use_statistics(self.options.use_statistics)
+                .read_parallel(self.options.parallel)
+                .set_rechunk(false)
+                .with_hive_partition_columns(hive_partitions)
+                .with_include_file_path(
+                    self.file_options
+                        .include_file_paths
+                        .as_ref()
+                        .map(|x| (x.clone(), Arc::from(paths[i].to_str().unwrap()))),
+                )
+                .finish()?;
+            result.push(df);
+        }
+
+        Ok(result)
+    }
+
     fn read(&mut self) -> PolarsResult<DataFrame> {
         // FIXME: The row index implementation is incorrect when a predicate is
         // applied. This code mitigates that by applying the predicate after the
@@ -453,7 +556,9 @@ impl ParquetExec {
         let is_cloud = self.sources.is_cloud_url();
         let force_async = config::force_async();
 
-        let out = if is_cloud || (self.sources.is_paths() && force_async) {
+        let out = if self.sources.is_http_range_url() {
+            feature_gated!("parquet_http", self.read_http()?)
+        } else if is_cloud || (self.sources.is_paths() && force_async) {
             feature_gated!("cloud", {
                 if force_async && config::verbose() {
                     eprintln!("ASYNC READING FORCED");
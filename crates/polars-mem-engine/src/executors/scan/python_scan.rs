@@ -1,5 +1,6 @@
 use polars_core::error::to_compute_err;
 use polars_core::utils::accumulate_dataframes_vertical;
+use polars_plan::plans::python::python_df_to_rust;
 use pyo3::exceptions::PyStopIteration;
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
@@ -13,24 +14,6 @@ pub(crate) struct PythonScanExec {
     pub(crate) predicate_serialized: Option<Vec<u8>>,
 }
 
-fn python_df_to_rust(py: Python, df: Bound<PyAny>) -> PolarsResult<DataFrame> {
-    let err = |_| polars_err!(ComputeError: "expected a polars.DataFrame; got {}", df);
-    let pydf = df.getattr(intern!(py, "_df")).map_err(err)?;
-    let raw_parts = pydf
-        .call_method0(intern!(py, "into_raw_parts"))
-        .map_err(err)?;
-    let raw_parts = raw_parts.extract::<(usize, usize, usize)>().unwrap();
-
-    let (ptr, len, cap) = raw_parts;
-    unsafe {
-        Ok(DataFrame::new_no_checks(Vec::from_raw_parts(
-            ptr as *mut Series,
-            len,
-            cap,
-        )))
-    }
-}
-
 impl Executor for PythonScanExec {
     fn execute(&mut self, state: &mut ExecutionState) -> PolarsResult<DataFrame> {
         state.should_stop()?;
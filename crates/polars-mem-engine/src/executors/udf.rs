@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use super::*;
 
 pub(crate) struct UdfExec {
@@ -5,6 +7,25 @@ pub(crate) struct UdfExec {
     pub(crate) function: FunctionIR,
 }
 
+/// Store the nodes of the streaming pipeline that started at `start` in the node timer, from
+/// their intervals in the `metrics` of the pipeline.
+fn record_pipeline_nodes(
+    state: &ExecutionState,
+    start: Instant,
+    metrics: &DataFrame,
+) -> PolarsResult<()> {
+    let nodes = metrics.column("node")?.str()?;
+    let starts = metrics.column("start")?.u64()?;
+    let ends = metrics.column("end")?.u64()?;
+    let at = |micros| start + Duration::from_micros(micros);
+    for ((node, node_start), node_end) in nodes.into_iter().zip(starts).zip(ends) {
+        if let (Some(node), Some(node_start), Some(node_end)) = (node, node_start, node_end) {
+            state.record_interval(at(node_start), at(node_end), node.to_string().into());
+        }
+    }
+    Ok(())
+}
+
 impl Executor for UdfExec {
     fn execute(&mut self, state: &mut ExecutionState) -> PolarsResult<DataFrame> {
         state.should_stop()?;
@@ -21,6 +42,14 @@ impl Executor for UdfExec {
         } else {
             Cow::Borrowed("")
         };
-        state.record(|| self.function.evaluate(df), profile_name)
+        let start = Instant::now();
+        let out = state.record(|| self.function.evaluate(df), profile_name)?;
+        // A streaming pipeline times its own nodes.
+        if state.has_node_timer() {
+            if let Some(metrics) = self.function.streaming_metrics() {
+                record_pipeline_nodes(state, start, &metrics)?;
+            }
+        }
+        Ok(out)
     }
 }
@@ -86,9 +86,13 @@ impl Executor for UnionExec {
             // we don't use par_iter directly because the LP may also start threads for every LP (for instance scan_csv)
             // this might then lead to a rayon SO. So we take a multitude of the threads to keep work stealing
             // within bounds
+            let chunk_size = self
+                .options
+                .max_parallel_inputs
+                .map_or(POOL.current_num_threads() * 3, |n| n.get());
             let out = POOL.install(|| {
                 inputs
-                    .chunks_mut(POOL.current_num_threads() * 3)
+                    .chunks_mut(chunk_size)
                     .map(|chunk| {
                         chunk
                             .into_par_iter()
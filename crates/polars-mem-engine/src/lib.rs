@@ -3,5 +3,5 @@ mod planner;
 mod prelude;
 mod utils;
 
-pub use executors::Executor;
-pub use planner::create_physical_plan;
+pub use executors::{Executor, NodeStats, PlanStats};
+pub use planner::{create_physical_plan, create_physical_plan_with_stats};
@@ -3,7 +3,7 @@ use polars_core::POOL;
 use polars_plan::global::_set_n_rows_for_scan;
 use polars_plan::plans::expr_ir::ExprIR;
 
-use super::super::executors::{self, Executor};
+use super::super::executors::{self, Executor, PlanStats};
 use super::*;
 use crate::utils::*;
 
@@ -128,12 +128,15 @@ fn partitionable_gb(
 
 struct ConversionState {
     expr_depth: u16,
+    // if set, the executor of every node records its statistics here
+    stats: Option<PlanStats>,
 }
 
 impl ConversionState {
     fn new() -> PolarsResult<Self> {
         Ok(ConversionState {
             expr_depth: get_expr_depth_limit()?,
+            stats: None,
         })
     }
 }
@@ -147,11 +150,41 @@ pub fn create_physical_plan(
     create_physical_plan_impl(root, lp_arena, expr_arena, &state)
 }
 
+/// Like [`create_physical_plan`], but the executors record the rows, the bytes and the time of
+/// every node of the plan in `stats`, keyed by the node.
+pub fn create_physical_plan_with_stats(
+    root: Node,
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+    stats: &PlanStats,
+) -> PolarsResult<Box<dyn Executor>> {
+    let mut state = ConversionState::new()?;
+    state.stats = Some(stats.clone());
+    create_physical_plan_impl(root, lp_arena, expr_arena, &state)
+}
+
 fn create_physical_plan_impl(
     root: Node,
     lp_arena: &mut Arena<IR>,
     expr_arena: &Arena<AExpr>,
     state: &ConversionState,
+) -> PolarsResult<Box<dyn Executor>> {
+    let executor = create_node_executor(root, lp_arena, expr_arena, state)?;
+    Ok(match &state.stats {
+        Some(stats) => Box::new(executors::AnalyzeExec {
+            input: executor,
+            node: root,
+            stats: stats.clone(),
+        }),
+        None => executor,
+    })
+}
+
+fn create_node_executor(
+    root: Node,
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+    state: &ConversionState,
 ) -> PolarsResult<Box<dyn Executor>> {
     use IR::*;
 
@@ -213,10 +246,26 @@ fn create_physical_plan_impl(
                     "sink_{file_type:?} not yet supported in standard engine. Use 'collect().write_parquet()'"
                 )
             },
+            SinkType::Partitioned { .. } => {
+                polars_bail!(InvalidOperation: "partitioned sink not supported in standard engine.")
+            },
             #[cfg(feature = "cloud")]
             SinkType::Cloud { .. } => {
                 polars_bail!(InvalidOperation: "cloud sink not supported in standard engine.")
             },
+            #[cfg(feature = "flight")]
+            SinkType::Flight { .. } => {
+                polars_bail!(InvalidOperation: "flight sink not supported in standard engine.")
+            },
+            SinkType::Writer { .. } => {
+                polars_bail!(InvalidOperation: "writer sink not supported in standard engine.")
+            },
+            SinkType::Custom { .. } => {
+                polars_bail!(InvalidOperation: "custom sink not supported in standard engine.")
+            },
+            SinkType::Tee { .. } => {
+                polars_bail!(InvalidOperation: "tee sink not supported in standard engine.")
+            },
         },
         Union { inputs, options } => {
             let inputs = inputs
@@ -311,6 +360,7 @@ fn create_physical_plan_impl(
                     options,
                     predicate,
                     file_options,
+                    hive_parts,
                 })),
                 #[cfg(feature = "ipc")]
                 FileScan::Ipc {
@@ -349,6 +399,22 @@ fn create_physical_plan_impl(
                     file_info,
                     predicate,
                 ))),
+                #[cfg(feature = "avro")]
+                FileScan::Avro { options } => Ok(Box::new(executors::AvroExec::new(
+                    sources,
+                    options,
+                    file_options,
+                    file_info,
+                    predicate,
+                ))),
+                #[cfg(feature = "fwf")]
+                FileScan::Fwf { options } => Ok(Box::new(executors::FwfExec::new(
+                    sources,
+                    options,
+                    file_options,
+                    file_info,
+                    predicate,
+                ))),
                 FileScan::Anonymous { function, .. } => {
                     Ok(Box::new(executors::AnonymousScanExec {
                         function,
@@ -417,13 +483,14 @@ fn create_physical_plan_impl(
             ..
         } => {
             let mut state = ExpressionConversionState::new(true, state.expr_depth);
+            // The predicate is evaluated on the projected columns.
             let selection = predicate
                 .map(|pred| {
                     create_physical_expr(
                         &pred,
                         Context::Default,
                         expr_arena,
-                        Some(&schema),
+                        Some(output_schema.as_ref().unwrap_or(&schema)),
                         &mut state,
                     )
                 })
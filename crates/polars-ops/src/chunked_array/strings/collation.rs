@@ -0,0 +1,258 @@
+use polars_core::prelude::arity::unary_elementwise;
+use polars_core::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+use unicode_normalization::UnicodeNormalization;
+
+/// How strings are ordered by their [`collation_key`].
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CollationOptions {
+    /// The locale, e.g. `"sv"` or `"es-ES"`, of which the alphabet orders the letters. The empty
+    /// locale is the root order. Only the languages of [`SUPPORTED_LANGUAGES`] are supported,
+    /// other locales are an error.
+    pub locale: PlSmallStr,
+    /// Order strings that only differ in case, with lowercase first.
+    pub case_sensitive: bool,
+    /// Order strings that only differ in accents.
+    pub accent_sensitive: bool,
+}
+
+impl Default for CollationOptions {
+    fn default() -> Self {
+        Self {
+            locale: PlSmallStr::EMPTY,
+            case_sensitive: true,
+            accent_sensitive: true,
+        }
+    }
+}
+
+/// Letters that a language orders as separate letters after another letter, instead of as that
+/// letter with an accent: e.g. Swedish orders `å`, `ä` and `ö` after `z`.
+type Tailoring = &'static [(char, char, u32)];
+
+/// The languages of which the collation is supported: the root order, the languages that use the
+/// root order, and the languages of which the alphabet is tailored.
+pub const SUPPORTED_LANGUAGES: &[&str] = &[
+    "", "root", "und", "en", "de", "it", "nl", "pt", "da", "nb", "nn", "no", "sv", "fi", "es",
+];
+
+fn tailoring(locale: &str) -> PolarsResult<Tailoring> {
+    let language = locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    Ok(match language.as_str() {
+        "da" | "nb" | "nn" | "no" => &[('æ', 'z', 1), ('ø', 'z', 2), ('å', 'z', 3)],
+        "sv" | "fi" => &[
+            ('å', 'z', 1),
+            ('ä', 'z', 2),
+            ('æ', 'z', 2),
+            ('ö', 'z', 3),
+            ('ø', 'z', 3),
+        ],
+        "es" => &[('ñ', 'n', 1)],
+        language if SUPPORTED_LANGUAGES.contains(&language) => &[],
+        _ => polars_bail!(
+            InvalidOperation: "the collation of locale '{}' is not supported, the supported \
+            languages are {:?}", locale, SUPPORTED_LANGUAGES
+        ),
+    })
+}
+
+/// Letters that don't decompose into a base letter and an accent, but are ordered as if they did.
+fn root_decomposition(c: char) -> Option<(char, char)> {
+    Some(match c {
+        'ø' => ('o', '\u{338}'),
+        'ł' => ('l', '\u{337}'),
+        'đ' => ('d', '\u{335}'),
+        'ħ' => ('h', '\u{335}'),
+        'ŧ' => ('t', '\u{335}'),
+        _ => return None,
+    })
+}
+
+/// Letters that are ordered as a sequence of letters.
+fn root_expansion(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        _ => return None,
+    })
+}
+
+const NO_ACCENT: u32 = 1;
+const LOWERCASE: u8 = 1;
+const UPPERCASE: u8 = 2;
+const EXPANSION: u8 = 3;
+
+/// The weights of the characters of a string at the three levels of comparison: the letters, then
+/// their accents and then their case.
+#[derive(Default)]
+struct Weights {
+    primary: Vec<u32>,
+    secondary: Vec<u32>,
+    tertiary: Vec<u8>,
+}
+
+impl Weights {
+    fn clear(&mut self) {
+        self.primary.clear();
+        self.secondary.clear();
+        self.tertiary.clear();
+    }
+
+    fn push(&mut self, letter: char, rank: u32, case: u8) {
+        // Leave room for the tailored letters after every letter.
+        self.primary.push(letter as u32 * 4 + 1 + rank);
+        self.secondary.push(NO_ACCENT);
+        self.tertiary.push(case);
+    }
+
+    fn push_accent(&mut self, accent: char) {
+        match self.secondary.last_mut() {
+            Some(last) if *last == NO_ACCENT => *last = accent as u32 + 2,
+            _ => self.secondary.push(accent as u32 + 2),
+        }
+    }
+
+    fn extend(&mut self, s: &str, tailoring: Tailoring) {
+        for c in s.nfc() {
+            let case = if c.is_uppercase() {
+                UPPERCASE
+            } else {
+                LOWERCASE
+            };
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            if let Some(&(_, after, rank)) = tailoring.iter().find(|(t, ..)| *t == lower) {
+                self.push(after, rank, case);
+            } else if let Some(expansion) = root_expansion(lower) {
+                expansion
+                    .chars()
+                    .for_each(|letter| self.push(letter, 0, EXPANSION));
+            } else if let Some((letter, accent)) = root_decomposition(lower) {
+                self.push(letter, 0, case);
+                self.push_accent(accent);
+            } else {
+                decompose_canonical(lower, |d| {
+                    if is_combining_mark(d) && !self.primary.is_empty() {
+                        self.push_accent(d)
+                    } else {
+                        self.push(d, 0, case)
+                    }
+                });
+            }
+        }
+    }
+
+    /// Concatenate the levels of weights, each of which starts with a separator that is smaller
+    /// than any weight, so that the bytes of the keys are ordered like the strings.
+    fn key(&self, options: &CollationOptions) -> Vec<u8> {
+        let mut key = Vec::with_capacity(self.primary.len() * 7 + 6);
+        let mut push = |w: u32| key.extend_from_slice(&w.to_be_bytes()[1..]);
+        self.primary.iter().for_each(|&w| push(w));
+        if options.accent_sensitive {
+            push(0);
+            self.secondary.iter().for_each(|&w| push(w));
+        }
+        if options.case_sensitive {
+            push(0);
+            key.extend_from_slice(&self.tertiary);
+        }
+        key
+    }
+}
+
+/// A binary key of each string, of which the byte order is the collation of `options`.
+///
+/// Sorting by the keys sorts the strings alphabetically in the locale, and strings with equal
+/// keys are equal in the collation, e.g. when case and accents are ignored. The collation is not
+/// an option of the sorts, but sorting by the key, e.g. in `sort_by`, sorts in the collation.
+///
+/// The collation is a simplification of the Unicode Collation Algorithm: letters are ordered by
+/// their base letter, then their accents and then their case, and the alphabets of the
+/// [`SUPPORTED_LANGUAGES`] are tailored. Other characters are ordered by their code point.
+pub fn collation_key(
+    ca: &StringChunked,
+    options: &CollationOptions,
+) -> PolarsResult<BinaryChunked> {
+    let tailoring = tailoring(&options.locale)?;
+    let mut weights = Weights::default();
+    Ok(unary_elementwise(ca, |opt_s: Option<&str>| {
+        weights.clear();
+        weights.extend(opt_s?, tailoring);
+        Some(weights.key(options))
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sorted(words: &[&'static str], options: &CollationOptions) -> Vec<&'static str> {
+        let tailoring = tailoring(&options.locale).unwrap();
+        let mut weights = Weights::default();
+        let mut keyed = words
+            .iter()
+            .map(|w| {
+                weights.clear();
+                weights.extend(w, tailoring);
+                (weights.key(options), *w)
+            })
+            .collect::<Vec<_>>();
+        keyed.sort();
+        keyed.into_iter().map(|(_, w)| w).collect()
+    }
+
+    #[test]
+    fn test_collation_order() {
+        let root = CollationOptions::default();
+        assert_eq!(
+            sorted(&["b", "A", "a", "ab", "á", "Ä", "z"], &root),
+            &["a", "A", "á", "Ä", "ab", "b", "z"]
+        );
+        assert_eq!(
+            sorted(&["côté", "côte", "coté", "cote"], &root),
+            &["cote", "coté", "côte", "côté"]
+        );
+        assert_eq!(sorted(&["sz", "ß", "ss"], &root), &["ss", "ß", "sz"]);
+        assert_eq!(sorted(&["m", "ł", "l"], &root), &["l", "ł", "m"]);
+
+        let swedish = CollationOptions {
+            locale: "sv-SE".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            sorted(&["ö", "ä", "z", "å", "a"], &swedish),
+            &["a", "z", "å", "ä", "ö"]
+        );
+        let spanish = CollationOptions {
+            locale: "es".into(),
+            ..Default::default()
+        };
+        assert_eq!(sorted(&["o", "ñ", "nz"], &spanish), &["nz", "ñ", "o"]);
+        let german = CollationOptions {
+            locale: "de-DE".into(),
+            ..Default::default()
+        };
+        assert_eq!(sorted(&["b", "ä", "a"], &german), &["a", "ä", "b"]);
+        assert!(tailoring("tr").is_err());
+        assert!(tailoring("cs-CZ").is_err());
+
+        let insensitive = CollationOptions {
+            case_sensitive: false,
+            accent_sensitive: false,
+            ..Default::default()
+        };
+        let mut weights = Weights::default();
+        weights.extend("Éclair", &[]);
+        let key = weights.key(&insensitive);
+        weights.clear();
+        weights.extend("eclair", &[]);
+        assert_eq!(key, weights.key(&insensitive));
+    }
+}
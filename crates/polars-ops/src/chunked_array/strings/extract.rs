@@ -1,8 +1,10 @@
 use std::iter::zip;
 
 #[cfg(feature = "extract_groups")]
-use arrow::array::{Array, StructArray};
+use arrow::array::{Array, ListArray, StructArray};
 use arrow::array::{MutablePlString, Utf8ViewArray};
+#[cfg(feature = "extract_groups")]
+use arrow::offset::Offsets;
 use polars_core::export::regex::Regex;
 use polars_core::prelude::arity::{try_binary_mut_with_options, try_unary_mut_with_options};
 
@@ -72,6 +74,62 @@ pub(super) fn extract_groups(
     Series::try_from((ca.name().clone(), chunks))
 }
 
+#[cfg(feature = "extract_groups")]
+fn extract_all_groups_array(
+    arr: &Utf8ViewArray,
+    reg: &Regex,
+    n_fields: usize,
+    dtype: ArrowDataType,
+) -> PolarsResult<ArrayRef> {
+    let mut builders = (0..n_fields)
+        .map(|_| MutablePlString::with_capacity(arr.len()))
+        .collect::<Vec<_>>();
+    let mut offsets = Offsets::<i64>::with_capacity(arr.len());
+
+    for opt_v in arr {
+        let mut n_matches = 0;
+        // A null string gets a null list, which comes from arr's validity mask.
+        if let Some(s) = opt_v {
+            for captures in reg.captures_iter(s) {
+                for (i, builder) in builders.iter_mut().enumerate() {
+                    builder.push(captures.get(i + 1).map(|m| m.as_str()));
+                }
+                n_matches += 1;
+            }
+        }
+        offsets.try_push(n_matches)?;
+    }
+
+    let ArrowDataType::LargeList(field) = &dtype else {
+        unreachable!() // Implementation error if it isn't a list.
+    };
+    let values = builders.into_iter().map(|a| a.freeze().boxed()).collect();
+    let structs = StructArray::new(field.dtype().clone(), values, None).boxed();
+    Ok(ListArray::<i64>::new(dtype, offsets.into(), structs, arr.validity().cloned()).boxed())
+}
+
+#[cfg(feature = "extract_groups")]
+pub(super) fn extract_all_groups(
+    ca: &StringChunked,
+    pat: &str,
+    dtype: &DataType,
+) -> PolarsResult<Series> {
+    let reg = Regex::new(pat)?;
+    let n_fields = reg.captures_len() - 1;
+    polars_ensure!(
+        n_fields > 0,
+        ComputeError: "the pattern of `extract_all_groups` has no capture groups"
+    );
+
+    let arrow_dtype = dtype.try_to_arrow(CompatLevel::newest())?;
+    let chunks = ca
+        .downcast_iter()
+        .map(|array| extract_all_groups_array(array, &reg, n_fields, arrow_dtype.clone()))
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    Series::try_from((ca.name().clone(), chunks))
+}
+
 fn extract_group_reg_lit(
     arr: &Utf8ViewArray,
     reg: &Regex,
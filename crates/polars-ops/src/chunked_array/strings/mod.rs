@@ -1,5 +1,7 @@
 #[cfg(feature = "strings")]
 mod case;
+#[cfg(feature = "string_collation")]
+mod collation;
 #[cfg(feature = "strings")]
 mod concat;
 #[cfg(feature = "strings")]
@@ -10,20 +12,30 @@ mod find_many;
 mod json_path;
 #[cfg(feature = "strings")]
 mod namespace;
+#[cfg(feature = "string_normalize")]
+mod normalize;
 #[cfg(feature = "string_pad")]
 mod pad;
+#[cfg(feature = "string_phonetic")]
+mod phonetic;
 #[cfg(feature = "string_reverse")]
 mod reverse;
+#[cfg(feature = "string_similarity")]
+mod similarity;
 #[cfg(feature = "strings")]
 mod split;
 #[cfg(feature = "strings")]
 mod strip;
 #[cfg(feature = "strings")]
 mod substring;
+#[cfg(feature = "string_tokenize")]
+mod tokenize;
 
 #[cfg(all(not(feature = "nightly"), feature = "strings"))]
 mod unicode_internals;
 
+#[cfg(feature = "string_collation")]
+pub use collation::*;
 #[cfg(feature = "strings")]
 pub use concat::*;
 #[cfg(feature = "find_many")]
@@ -32,11 +44,19 @@ pub use find_many::*;
 pub use json_path::*;
 #[cfg(feature = "strings")]
 pub use namespace::*;
+#[cfg(feature = "string_normalize")]
+pub use normalize::*;
+#[cfg(feature = "string_phonetic")]
+pub use phonetic::*;
 use polars_core::prelude::*;
+#[cfg(feature = "string_similarity")]
+pub use similarity::*;
 #[cfg(feature = "strings")]
 pub use split::*;
 #[cfg(feature = "strings")]
 pub use strip::*;
+#[cfg(feature = "string_tokenize")]
+pub use tokenize::*;
 
 pub trait AsString {
     fn as_string(&self) -> &StringChunked;
@@ -476,6 +476,45 @@ pub trait StringNameSpaceImpl: AsString {
         split_to_struct(ca, by, n, |s, by| s.splitn(n, by), true)
     }
 
+    #[cfg(feature = "dtype-struct")]
+    /// Split by a substring into the fields `names`, at most `max_splits` times if given, in which
+    /// case the remainder of the string is kept intact in the field after the last split. The
+    /// fields without a part are null.
+    fn split_to_named_struct(
+        &self,
+        by: &StringChunked,
+        names: &[PlSmallStr],
+        max_splits: Option<usize>,
+    ) -> PolarsResult<StructChunked> {
+        let ca = self.as_string();
+        let n = names.len();
+        polars_ensure!(n > 0, InvalidOperation: "`split_to_struct` needs at least one field name");
+
+        let (out, n_parts) = match max_splits {
+            Some(max_splits) => {
+                let n_parts = (max_splits + 1).min(n);
+                let keep_remainder = max_splits < n;
+                let out = split_to_struct(
+                    ca,
+                    by,
+                    n_parts,
+                    |s, by| s.splitn(max_splits + 1, by),
+                    keep_remainder,
+                )?;
+                (out, n_parts)
+            },
+            None => (split_to_struct(ca, by, n, str::split, false)?, n),
+        };
+        let mut fields = out.fields_as_series();
+        fields.extend(
+            (n_parts..n).map(|_| Series::full_null(PlSmallStr::EMPTY, ca.len(), &DataType::String)),
+        );
+        for (field, name) in fields.iter_mut().zip(names) {
+            field.rename(name.clone());
+        }
+        StructChunked::from_series(ca.name().clone(), &fields)
+    }
+
     fn split(&self, by: &StringChunked) -> ListChunked {
         let ca = self.as_string();
 
@@ -518,6 +557,13 @@ pub trait StringNameSpaceImpl: AsString {
         super::extract::extract_groups(ca, pat, dtype)
     }
 
+    #[cfg(feature = "extract_groups")]
+    /// Extract the capture groups of every match of the pattern as a list of structs.
+    fn extract_all_groups(&self, pat: &str, dtype: &DataType) -> PolarsResult<Series> {
+        let ca = self.as_string();
+        super::extract::extract_all_groups(ca, pat, dtype)
+    }
+
     /// Count all successive non-overlapping regex matches.
     fn count_matches(&self, pat: &str, literal: bool) -> PolarsResult<UInt32Chunked> {
         let ca = self.as_string();
@@ -0,0 +1,33 @@
+use polars_core::prelude::arity::unary_elementwise;
+use polars_core::prelude::StringChunked;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+/// The Unicode normalization forms.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UnicodeForm {
+    /// Canonical decomposition followed by canonical composition.
+    NFC,
+    /// Canonical decomposition.
+    NFD,
+    /// Compatibility decomposition followed by canonical composition.
+    NFKC,
+    /// Compatibility decomposition.
+    NFKD,
+}
+
+/// Normalize the strings to the Unicode normalization `form`, so that canonically equivalent
+/// strings are equal.
+pub fn normalize(ca: &StringChunked, form: UnicodeForm) -> StringChunked {
+    unary_elementwise(ca, |opt_s: Option<&str>| {
+        let s = opt_s?;
+        Some(match form {
+            UnicodeForm::NFC => s.nfc().collect::<String>(),
+            UnicodeForm::NFD => s.nfd().collect(),
+            UnicodeForm::NFKC => s.nfkc().collect(),
+            UnicodeForm::NFKD => s.nfkd().collect(),
+        })
+    })
+}
@@ -0,0 +1,202 @@
+use polars_core::prelude::arity::unary_elementwise;
+use polars_core::prelude::StringChunked;
+
+/// The uppercase ASCII letters of `s`; other characters are ignored by the encodings.
+fn letters(s: &str) -> Vec<u8> {
+    s.bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase())
+        .collect()
+}
+
+fn is_vowel(b: u8) -> bool {
+    matches!(b, b'A' | b'E' | b'I' | b'O' | b'U')
+}
+
+/// The digit of a consonant in the Soundex code. Vowels and `Y` separate consonants with the
+/// same digit, `H` and `W` are ignored.
+fn soundex_digit(b: u8) -> Option<u8> {
+    Some(match b {
+        b'B' | b'F' | b'P' | b'V' => b'1',
+        b'C' | b'G' | b'J' | b'K' | b'Q' | b'S' | b'X' | b'Z' => b'2',
+        b'D' | b'T' => b'3',
+        b'L' => b'4',
+        b'M' | b'N' => b'5',
+        b'R' => b'6',
+        _ => return None,
+    })
+}
+
+fn soundex_helper(s: Option<&str>) -> Option<String> {
+    let letters = letters(s?);
+    let (&first, rest) = letters.split_first()?;
+    let mut code = vec![first];
+    let mut last = soundex_digit(first);
+    for &b in rest {
+        if code.len() == 4 {
+            break;
+        }
+        if matches!(b, b'H' | b'W') {
+            continue;
+        }
+        let digit = soundex_digit(b);
+        if let Some(d) = digit {
+            if digit != last {
+                code.push(d);
+            }
+        }
+        last = digit;
+    }
+    code.resize(4, b'0');
+    Some(String::from_utf8(code).unwrap())
+}
+
+fn metaphone_helper(s: Option<&str>) -> Option<String> {
+    let mut w = letters(s?);
+    if w.is_empty() {
+        return None;
+    }
+    // Adjacent duplicate letters are encoded once, except for `C`.
+    w.dedup_by(|b, a| a == b && *b != b'C');
+    // Initial letter exceptions.
+    match (w[0], w.get(1).copied()) {
+        (b'K' | b'G' | b'P', Some(b'N')) | (b'A', Some(b'E')) | (b'W', Some(b'R')) => {
+            w.remove(0);
+        },
+        (b'W', Some(b'H')) => {
+            w.remove(1);
+        },
+        (b'X', _) => w[0] = b'S',
+        _ => {},
+    }
+
+    let n = w.len();
+    let at = |i: usize| w.get(i).copied().unwrap_or(0);
+    let followed_by = |i: usize, s: &[u8]| w.get(i + 1..).is_some_and(|w| w.starts_with(s));
+    let front_vowel = |b: u8| matches!(b, b'E' | b'I' | b'Y');
+
+    let mut code = String::with_capacity(n);
+    for i in 0..n {
+        let (prev, b, next) = (if i > 0 { at(i - 1) } else { 0 }, at(i), at(i + 1));
+        match b {
+            b'A' | b'E' | b'I' | b'O' | b'U' => {
+                if i == 0 {
+                    code.push(b as char)
+                }
+            },
+            b'B' => {
+                // Silent in a final MB.
+                if prev != b'M' || i + 1 < n {
+                    code.push('B')
+                }
+            },
+            b'C' => {
+                if followed_by(i, b"IA") || (next == b'H' && prev != b'S') {
+                    code.push('X')
+                } else if front_vowel(next) {
+                    code.push('S')
+                } else {
+                    code.push('K')
+                }
+            },
+            b'D' => {
+                if next == b'G' && front_vowel(at(i + 2)) {
+                    code.push('J')
+                } else {
+                    code.push('T')
+                }
+            },
+            b'G' => {
+                let silent_gh = next == b'H' && i + 2 < n && !is_vowel(at(i + 2));
+                let silent_gn =
+                    (i + 2 == n && next == b'N') || (i + 4 == n && followed_by(i, b"NED"));
+                if silent_gh || silent_gn {
+                    continue;
+                }
+                if front_vowel(next) {
+                    code.push('J')
+                } else {
+                    code.push('K')
+                }
+            },
+            b'H' => {
+                let silent = matches!(prev, b'C' | b'G' | b'P' | b'S' | b'T')
+                    || (is_vowel(prev) && !is_vowel(next));
+                if !silent {
+                    code.push('H')
+                }
+            },
+            b'K' => {
+                if prev != b'C' {
+                    code.push('K')
+                }
+            },
+            b'P' => code.push(if next == b'H' { 'F' } else { 'P' }),
+            b'Q' => code.push('K'),
+            b'S' => {
+                if next == b'H' || followed_by(i, b"IO") || followed_by(i, b"IA") {
+                    code.push('X')
+                } else {
+                    code.push('S')
+                }
+            },
+            b'T' => {
+                if followed_by(i, b"IA") || followed_by(i, b"IO") {
+                    code.push('X')
+                } else if next == b'H' {
+                    code.push('0')
+                } else if !followed_by(i, b"CH") {
+                    code.push('T')
+                }
+            },
+            b'V' => code.push('F'),
+            b'W' | b'Y' => {
+                if is_vowel(next) {
+                    code.push(b as char)
+                }
+            },
+            b'X' => code.push_str("KS"),
+            b'Z' => code.push('S'),
+            // F, J, L, M, N and R
+            _ => code.push(b as char),
+        }
+    }
+    Some(code)
+}
+
+/// The American Soundex code of the strings: their first letter followed by three digits that
+/// encode the consonants after it. It is null for strings without ASCII letters.
+pub fn soundex(ca: &StringChunked) -> StringChunked {
+    unary_elementwise(ca, soundex_helper)
+}
+
+/// The Metaphone code of the strings, which encodes the way they sound in English. It is null for
+/// strings without ASCII letters.
+pub fn metaphone(ca: &StringChunked) -> StringChunked {
+    unary_elementwise(ca, metaphone_helper)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_phonetic_encodings() {
+        let soundex = |s| soundex_helper(Some(s));
+        assert_eq!(soundex("Robert").as_deref(), Some("R163"));
+        assert_eq!(soundex("Rupert").as_deref(), Some("R163"));
+        assert_eq!(soundex("Ashcraft").as_deref(), Some("A261"));
+        assert_eq!(soundex("Tymczak").as_deref(), Some("T522"));
+        assert_eq!(soundex("Pfister").as_deref(), Some("P236"));
+        assert_eq!(soundex("Lee").as_deref(), Some("L000"));
+        assert_eq!(soundex("123"), None);
+
+        let metaphone = |s| metaphone_helper(Some(s));
+        assert_eq!(metaphone("Thumb").as_deref(), Some("0M"));
+        assert_eq!(metaphone("knight").as_deref(), Some("NT"));
+        assert_eq!(metaphone("Schmidt").as_deref(), Some("SKMTT"));
+        assert_eq!(metaphone("Xavier").as_deref(), Some("SFR"));
+        assert_eq!(metaphone("Philip").as_deref(), Some("FLP"));
+        assert_eq!(metaphone("Catherine"), metaphone("Kathryn"));
+    }
+}
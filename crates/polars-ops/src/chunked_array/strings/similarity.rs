@@ -0,0 +1,185 @@
+use polars_core::prelude::arity::broadcast_binary_elementwise;
+use polars_core::prelude::*;
+
+/// The number of single character insertions, deletions and substitutions that turn `a` into
+/// `b`. `row` is a buffer that is reused between calls.
+fn levenshtein_distance(a: &str, b: &str, row: &mut Vec<usize>) -> usize {
+    let b_len = b.chars().count();
+    row.clear();
+    row.extend(0..=b_len);
+    for (i, ca) in a.chars().enumerate() {
+        // The distance of the previous prefix of `a` to the previous prefix of `b`.
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.chars().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b_len]
+}
+
+/// The number of positions at which the characters of `a` and `b` differ, or `None` if they
+/// don't have the same number of characters.
+fn hamming_distance(a: &str, b: &str) -> Option<usize> {
+    let mut a = a.chars();
+    let mut b = b.chars();
+    let mut distance = 0;
+    loop {
+        match (a.next(), b.next()) {
+            (Some(ca), Some(cb)) => distance += (ca != cb) as usize,
+            (None, None) => return Some(distance),
+            _ => return None,
+        }
+    }
+}
+
+/// Turn a distance into a similarity between 0 and 1, where 1 means that the strings are equal.
+fn normalize(distance: usize, len: usize) -> f64 {
+    if len == 0 {
+        1.0
+    } else {
+        1.0 - distance as f64 / len as f64
+    }
+}
+
+#[derive(Default)]
+struct JaroBuffers {
+    a: Vec<char>,
+    b: Vec<char>,
+    b_matched: Vec<bool>,
+    a_matches: Vec<char>,
+}
+
+/// The Jaro similarity of `a` and `b`, with the bonus of Winkler for a common prefix of up to
+/// four characters.
+fn jaro_winkler_similarity(a: &str, b: &str, buffers: &mut JaroBuffers) -> f64 {
+    let JaroBuffers {
+        a: a_chars,
+        b: b_chars,
+        b_matched,
+        a_matches,
+    } = buffers;
+    a_chars.clear();
+    a_chars.extend(a.chars());
+    b_chars.clear();
+    b_chars.extend(b.chars());
+    if a_chars.is_empty() && b_chars.is_empty() {
+        return 1.0;
+    }
+    if a_chars.is_empty() || b_chars.is_empty() {
+        return 0.0;
+    }
+
+    // Characters match if they are equal and not farther apart than the window.
+    let window = (a_chars.len().max(b_chars.len()) / 2).saturating_sub(1);
+    b_matched.clear();
+    b_matched.resize(b_chars.len(), false);
+    a_matches.clear();
+    for (i, ca) in a_chars.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b_chars.len());
+        for j in start..end {
+            if !b_matched[j] && b_chars[j] == *ca {
+                b_matched[j] = true;
+                a_matches.push(*ca);
+                break;
+            }
+        }
+    }
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+
+    // Half the number of matching characters that are in a different order.
+    let transpositions = b_chars
+        .iter()
+        .zip(b_matched.iter())
+        .filter_map(|(cb, matched)| matched.then_some(cb))
+        .zip(a_matches.iter())
+        .filter(|(cb, ca)| cb != ca)
+        .count() as f64
+        / 2.0;
+    let m = a_matches.len() as f64;
+    let jaro =
+        (m / a_chars.len() as f64 + m / b_chars.len() as f64 + (m - transpositions) / m) / 3.0;
+
+    let prefix = a_chars
+        .iter()
+        .zip(b_chars.iter())
+        .take(4)
+        .take_while(|(ca, cb)| ca == cb)
+        .count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// The Levenshtein distance, in characters, between the strings of `ca` and `other`.
+pub fn levenshtein(ca: &StringChunked, other: &StringChunked) -> UInt32Chunked {
+    let mut row = vec![];
+    broadcast_binary_elementwise(ca, other, |a: Option<&str>, b: Option<&str>| {
+        Some(levenshtein_distance(a?, b?, &mut row) as u32)
+    })
+}
+
+/// The Levenshtein distance between the strings of `ca` and `other`, normalized by the number of
+/// characters of the longer string into a similarity between 0 and 1.
+pub fn levenshtein_similarity(ca: &StringChunked, other: &StringChunked) -> Float64Chunked {
+    let mut row = vec![];
+    broadcast_binary_elementwise(ca, other, |a: Option<&str>, b: Option<&str>| {
+        let (a, b) = (a?, b?);
+        let len = a.chars().count().max(b.chars().count());
+        Some(normalize(levenshtein_distance(a, b, &mut row), len))
+    })
+}
+
+/// The Hamming distance between the strings of `ca` and `other`. It is null for strings with a
+/// different number of characters.
+pub fn hamming(ca: &StringChunked, other: &StringChunked) -> UInt32Chunked {
+    broadcast_binary_elementwise(ca, other, |a: Option<&str>, b: Option<&str>| {
+        hamming_distance(a?, b?).map(|distance| distance as u32)
+    })
+}
+
+/// The Hamming distance between the strings of `ca` and `other`, normalized by their number of
+/// characters into a similarity between 0 and 1.
+pub fn hamming_similarity(ca: &StringChunked, other: &StringChunked) -> Float64Chunked {
+    broadcast_binary_elementwise(ca, other, |a: Option<&str>, b: Option<&str>| {
+        let (a, b) = (a?, b?);
+        hamming_distance(a, b).map(|distance| normalize(distance, a.chars().count()))
+    })
+}
+
+/// The Jaro-Winkler similarity, between 0 and 1, of the strings of `ca` and `other`.
+pub fn jaro_winkler(ca: &StringChunked, other: &StringChunked) -> Float64Chunked {
+    let mut buffers = JaroBuffers::default();
+    broadcast_binary_elementwise(ca, other, |a: Option<&str>, b: Option<&str>| {
+        Some(jaro_winkler_similarity(a?, b?, &mut buffers))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_string_distances() {
+        let mut row = vec![];
+        assert_eq!(levenshtein_distance("kitten", "sitting", &mut row), 3);
+        assert_eq!(levenshtein_distance("", "abc", &mut row), 3);
+        assert_eq!(levenshtein_distance("äbc", "abc", &mut row), 1);
+        assert_eq!(hamming_distance("karolin", "kathrin"), Some(3));
+        assert_eq!(hamming_distance("a", "ab"), None);
+
+        let mut buffers = JaroBuffers::default();
+        let sim = jaro_winkler_similarity("MARTHA", "MARHTA", &mut buffers);
+        assert!((sim - 0.9611).abs() < 1e-4);
+        let sim = jaro_winkler_similarity("DIXON", "DICKSONX", &mut buffers);
+        assert!((sim - 0.8133).abs() < 1e-4);
+        assert_eq!(jaro_winkler_similarity("abc", "xyz", &mut buffers), 0.0);
+    }
+}
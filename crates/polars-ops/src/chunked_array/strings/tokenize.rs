@@ -0,0 +1,122 @@
+use polars_core::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How strings are split into tokens.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TokenizeMode {
+    /// The runs of alphanumeric characters, e.g. `["it", "s", "42"]` for `"it's 42!"`.
+    #[default]
+    Words,
+    /// The runs of characters that aren't whitespace, e.g. `["it's", "42!"]` for `"it's 42!"`.
+    Whitespace,
+    /// Every character.
+    Chars,
+}
+
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TokenizeOptions {
+    pub mode: TokenizeMode,
+    /// Lowercase the strings before they are split.
+    pub lowercase: bool,
+}
+
+/// Push the byte ranges of the runs of characters of `s` that are part of a token.
+fn push_runs(s: &str, spans: &mut Vec<(usize, usize)>, in_token: impl Fn(char) -> bool) {
+    let mut start = None;
+    for (i, c) in s.char_indices() {
+        match (in_token(c), start) {
+            (true, None) => start = Some(i),
+            (false, Some(token_start)) => {
+                spans.push((token_start, i));
+                start = None;
+            },
+            _ => {},
+        }
+    }
+    if let Some(token_start) = start {
+        spans.push((token_start, s.len()));
+    }
+}
+
+fn token_spans(s: &str, mode: TokenizeMode, spans: &mut Vec<(usize, usize)>) {
+    spans.clear();
+    match mode {
+        TokenizeMode::Words => push_runs(s, spans, char::is_alphanumeric),
+        TokenizeMode::Whitespace => push_runs(s, spans, |c| !c.is_whitespace()),
+        TokenizeMode::Chars => {
+            spans.extend(s.char_indices().map(|(i, c)| (i, i + c.len_utf8())));
+        },
+    }
+}
+
+fn lowercased<'a>(s: &'a str, lowercase: bool, buf: &'a mut String) -> &'a str {
+    if lowercase {
+        buf.clear();
+        buf.extend(s.chars().flat_map(char::to_lowercase));
+        buf
+    } else {
+        s
+    }
+}
+
+/// Split the strings into lists of tokens.
+pub fn tokenize(ca: &StringChunked, options: TokenizeOptions) -> ListChunked {
+    let mut builder = ListStringChunkedBuilder::new(ca.name().clone(), ca.len(), ca.len() * 4);
+    let mut spans = vec![];
+    let mut lower = String::new();
+    for opt_s in ca {
+        let Some(s) = opt_s else {
+            builder.append_null();
+            continue;
+        };
+        let s = lowercased(s, options.lowercase, &mut lower);
+        token_spans(s, options.mode, &mut spans);
+        builder.append_values_iter(spans.iter().map(|&(start, end)| &s[start..end]));
+    }
+    builder.finish()
+}
+
+/// The lists of the sequences of `n` consecutive tokens of the strings.
+///
+/// The tokens of an n-gram are joined by a space, or without a separator if the tokens are
+/// characters. A string with fewer than `n` tokens has no n-grams.
+pub fn ngrams(ca: &StringChunked, n: usize, options: TokenizeOptions) -> PolarsResult<ListChunked> {
+    polars_ensure!(n > 0, InvalidOperation: "the n-grams must have at least one token");
+    let mut builder = ListStringChunkedBuilder::new(ca.name().clone(), ca.len(), ca.len() * 4);
+    let mut spans = vec![];
+    let mut lower = String::new();
+    // The n-grams of words are joined into a buffer, as the words aren't adjacent in the string.
+    let mut joined = String::new();
+    let mut grams = vec![];
+    for opt_s in ca {
+        let Some(s) = opt_s else {
+            builder.append_null();
+            continue;
+        };
+        let s = lowercased(s, options.lowercase, &mut lower);
+        token_spans(s, options.mode, &mut spans);
+        let windows = spans.windows(n);
+        if options.mode == TokenizeMode::Chars {
+            builder.append_values_iter(windows.map(|w| &s[w[0].0..w[n - 1].1]));
+            continue;
+        }
+
+        joined.clear();
+        grams.clear();
+        for w in windows {
+            let start = joined.len();
+            for (i, &(token_start, token_end)) in w.iter().enumerate() {
+                if i > 0 {
+                    joined.push(' ');
+                }
+                joined.push_str(&s[token_start..token_end]);
+            }
+            grams.push((start, joined.len()));
+        }
+        builder.append_values_iter(grams.iter().map(|&(start, end)| &joined[start..end]));
+    }
+    Ok(builder.finish())
+}
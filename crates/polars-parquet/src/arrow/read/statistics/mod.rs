@@ -8,10 +8,13 @@ use arrow::with_match_primitive_type_full;
 use ethnum::I256;
 use polars_error::{polars_bail, PolarsResult};
 
+use crate::parquet::read::ColumnIndex;
 use crate::parquet::schema::types::{
     PhysicalType as ParquetPhysicalType, PrimitiveType as ParquetPrimitiveType,
 };
-use crate::parquet::statistics::{PrimitiveStatistics, Statistics as ParquetStatistics};
+use crate::parquet::statistics::{
+    ParquetStatistics as ThriftStatistics, PrimitiveStatistics, Statistics as ParquetStatistics,
+};
 use crate::parquet::types::int96_to_i64_ns;
 use crate::read::ColumnChunkMetadata;
 
@@ -574,3 +577,51 @@ pub fn deserialize<'a>(
 
     Ok(statistics.into())
 }
+
+/// Deserializes the statistics of the pages of a non-nested column chunk from its
+/// [`ColumnIndex`] into [`Statistics`] with one value per page.
+///
+/// # Errors
+/// This function errors if the deserialization of the statistics fails (e.g. invalid utf8)
+pub fn deserialize_column_index(
+    field: &Field,
+    primitive_type: &ParquetPrimitiveType,
+    column_index: &ColumnIndex,
+) -> PolarsResult<Statistics> {
+    let mut statistics = MutableStatistics::try_new(field)?;
+
+    for (i, is_null_page) in column_index.null_pages.iter().enumerate() {
+        // The min and max values of pages with only nulls are not meaningful.
+        let (min_value, max_value) = if *is_null_page {
+            (None, None)
+        } else {
+            (
+                column_index.min_values.get(i).cloned(),
+                column_index.max_values.get(i).cloned(),
+            )
+        };
+        let page = ThriftStatistics {
+            null_count: column_index
+                .null_counts
+                .as_ref()
+                .and_then(|null_counts| null_counts.get(i).copied()),
+            distinct_count: None,
+            max_value,
+            min_value,
+            min: None,
+            max: None,
+        };
+        let page = ParquetStatistics::deserialize(&page, primitive_type.clone())?;
+
+        let mut stats = VecDeque::from([(Some(page), primitive_type.clone())]);
+        push(
+            &mut stats,
+            statistics.min_value.as_mut(),
+            statistics.max_value.as_mut(),
+            statistics.distinct_count.as_mut(),
+            statistics.null_count.as_mut(),
+        )?;
+    }
+
+    Ok(statistics.into())
+}
@@ -3,7 +3,9 @@ use arrow::array::{
 };
 use arrow::bitmap::{Bitmap, MutableBitmap};
 use arrow::buffer::Buffer;
+use arrow::compute::aggregate::estimated_bytes_size;
 use arrow::datatypes::{ArrowDataType, IntegerType};
+use arrow::match_integer_type;
 use arrow::types::NativeType;
 use polars_compute::min_max::MinMaxKernel;
 use polars_error::{polars_bail, PolarsResult};
@@ -144,6 +146,9 @@ pub(crate) fn encode_as_dictionary_optional(
     };
 
     if let Some(fast_dictionary) = fast_dictionary {
+        if exceeds_page_size(&fast_dictionary, options) {
+            return None;
+        }
         return Some(array_to_pages(
             &fast_dictionary,
             type_,
@@ -169,7 +174,9 @@ pub(crate) fn encode_as_dictionary_optional(
         .downcast_ref::<DictionaryArray<u32>>()
         .unwrap();
 
-    if (array.values().len() as f64) / (len_before as f64) > 0.75 {
+    if (array.values().len() as f64) / (len_before as f64) > 0.75
+        || exceeds_page_size(array, options)
+    {
         return None;
     }
 
@@ -182,6 +189,25 @@ pub(crate) fn encode_as_dictionary_optional(
     ))
 }
 
+/// Whether the dictionary page of `array`, which must be a dictionary array, would be larger than
+/// the maximum dictionary page size.
+pub(crate) fn exceeds_page_size(array: &dyn Array, options: WriteOptions) -> bool {
+    let Some(max_page_size) = options.dictionary_page_size else {
+        return false;
+    };
+    let ArrowDataType::Dictionary(key_type, _, _) = array.dtype().to_logical_type() else {
+        unreachable!()
+    };
+    let values = match_integer_type!(key_type, |$T| {
+        array
+            .as_any()
+            .downcast_ref::<DictionaryArray<$T>>()
+            .unwrap()
+            .values()
+    });
+    estimated_bytes_size(values.as_ref()) > max_page_size
+}
+
 fn serialize_def_levels_simple(
     validity: Option<&Bitmap>,
     length: usize,
@@ -5,6 +5,7 @@ use polars_error::{PolarsError, PolarsResult};
 
 use super::schema::schema_to_metadata_key;
 use super::{to_parquet_schema, ThriftFileMetadata, WriteOptions};
+use crate::parquet::encryption::FileEncryptionProperties;
 use crate::parquet::metadata::{KeyValue, SchemaDescriptor};
 use crate::parquet::write::{RowGroupIterColumns, WriteOptions as FileWriteOptions};
 
@@ -44,6 +45,11 @@ impl<W: Write> FileWriter<W> {
     pub fn schema(&self) -> &ArrowSchema {
         &self.schema
     }
+
+    /// Returns the [`ThriftFileMetadata`]. This is Some iff the [`Self::end`] has been called.
+    pub fn metadata(&self) -> Option<&ThriftFileMetadata> {
+        self.writer.metadata()
+    }
 }
 
 impl<W: Write> FileWriter<W> {
@@ -70,11 +76,29 @@ impl<W: Write> FileWriter<W> {
         })
     }
 
+    /// Encrypt the file with [Parquet modular encryption](crate::parquet::encryption).
+    pub fn with_encryption(mut self, properties: &FileEncryptionProperties) -> PolarsResult<Self> {
+        self.writer = self.writer.with_encryption(properties)?;
+        Ok(self)
+    }
+
     /// Writes a row group to the file.
     pub fn write(&mut self, row_group: RowGroupIterColumns<'_, PolarsError>) -> PolarsResult<()> {
         Ok(self.writer.write(row_group)?)
     }
 
+    /// Writes a row group to the file, with the bitsets of the split block bloom filters of its
+    /// columns.
+    pub fn write_with_bloom_filters(
+        &mut self,
+        row_group: RowGroupIterColumns<'_, PolarsError>,
+        bloom_filters: Vec<Option<Vec<u8>>>,
+    ) -> PolarsResult<()> {
+        Ok(self
+            .writer
+            .write_with_bloom_filters(row_group, bloom_filters)?)
+    }
+
     /// Writes the footer of the parquet file. Returns the total size of the file.
     pub fn end(&mut self, key_value_metadata: Option<Vec<KeyValue>>) -> PolarsResult<u64> {
         let key_value_metadata = add_arrow_schema(&self.schema, key_value_metadata);
@@ -86,6 +110,11 @@ impl<W: Write> FileWriter<W> {
         self.writer.into_inner()
     }
 
+    /// Returns a mutable reference to the inner writer
+    pub fn get_mut(&mut self) -> &mut W {
+        self.writer.get_mut()
+    }
+
     /// Returns the underlying writer and [`ThriftFileMetadata`]
     /// # Panics
     /// This function panics if [`Self::end`] has not yet been called
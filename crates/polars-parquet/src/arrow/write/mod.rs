@@ -37,6 +37,7 @@ pub use utils::write_def_levels;
 
 pub use crate::parquet::compression::{BrotliLevel, CompressionOptions, GzipLevel, ZstdLevel};
 pub use crate::parquet::encoding::Encoding;
+pub use crate::parquet::encryption::FileEncryptionProperties;
 pub use crate::parquet::metadata::{
     Descriptor, FileMetadata, KeyValue, SchemaDescriptor, ThriftFileMetadata,
 };
@@ -90,6 +91,9 @@ pub struct WriteOptions {
     pub compression: CompressionOptions,
     /// The size to flush a page, defaults to 1024 * 1024 if None
     pub data_page_size: Option<usize>,
+    /// The maximum size of a dictionary page. Columns with larger dictionaries are plain
+    /// encoded. Dictionaries are not limited if None
+    pub dictionary_page_size: Option<usize>,
 }
 
 use arrow::compute::aggregate::estimated_bytes_size;
@@ -287,16 +291,34 @@ pub fn array_to_pages(
     options: WriteOptions,
     mut encoding: Encoding,
 ) -> PolarsResult<DynIter<'static, PolarsResult<Page>>> {
-    if let ArrowDataType::Dictionary(key_type, _, _) = primitive_array.dtype().to_logical_type() {
-        return match_integer_type!(key_type, |$T| {
-            dictionary::array_to_pages::<$T>(
-                primitive_array.as_any().downcast_ref().unwrap(),
-                type_,
-                &nested,
-                options,
-                encoding,
-            )
-        });
+    if let ArrowDataType::Dictionary(key_type, values_dtype, _) =
+        primitive_array.dtype().to_logical_type()
+    {
+        let is_dictionary_encoded = matches!(
+            encoding,
+            Encoding::RleDictionary | Encoding::PlainDictionary
+        );
+        if is_dictionary_encoded && !dictionary::exceeds_page_size(primitive_array, options) {
+            return match_integer_type!(key_type, |$T| {
+                dictionary::array_to_pages::<$T>(
+                    primitive_array.as_any().downcast_ref().unwrap(),
+                    type_,
+                    &nested,
+                    options,
+                    encoding,
+                )
+            });
+        }
+
+        // Write the values of the dictionary instead.
+        let values =
+            arrow::compute::cast::cast(primitive_array, values_dtype.as_ref(), Default::default())?;
+        let encoding = if is_dictionary_encoded {
+            Encoding::Plain
+        } else {
+            encoding
+        };
+        return array_to_pages(values.as_ref(), type_, nested, options, encoding);
     };
     if let Encoding::RleDictionary = encoding {
         // Only take this path for primitive columns
@@ -459,16 +481,22 @@ pub fn array_to_page_simple(
                 encoding,
             )
         },
-        ArrowDataType::Float32 => primitive::array_to_page_plain::<f32, f32>(
-            array.as_any().downcast_ref().unwrap(),
-            options,
-            type_,
-        ),
-        ArrowDataType::Float64 => primitive::array_to_page_plain::<f64, f64>(
-            array.as_any().downcast_ref().unwrap(),
-            options,
-            type_,
-        ),
+        ArrowDataType::Float32 => {
+            return primitive::array_to_page_float::<f32, f32>(
+                array.as_any().downcast_ref().unwrap(),
+                options,
+                type_,
+                encoding,
+            )
+        },
+        ArrowDataType::Float64 => {
+            return primitive::array_to_page_float::<f64, f64>(
+                array.as_any().downcast_ref().unwrap(),
+                options,
+                type_,
+                encoding,
+            )
+        },
         ArrowDataType::LargeUtf8 => {
             let array =
                 arrow::compute::cast::cast(array, &ArrowDataType::LargeBinary, Default::default())
@@ -5,7 +5,7 @@ mod split_block;
 
 pub use hash::{hash_byte, hash_native};
 pub use read::read;
-pub use split_block::{insert, is_in_set};
+pub use split_block::{insert, is_in_set, optimal_num_bytes};
 
 #[cfg(test)]
 mod tests {
@@ -68,4 +68,10 @@ mod tests {
         ];
         assert_eq!(bitset, expected);
     }
+
+    #[test]
+    fn num_bytes() {
+        assert_eq!(optimal_num_bytes(0, 0.01), 32);
+        assert_eq!(optimal_num_bytes(1_000_000, 0.01), 2 * 1024 * 1024);
+    }
 }
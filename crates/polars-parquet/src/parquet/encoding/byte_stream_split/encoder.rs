@@ -0,0 +1,17 @@
+use crate::parquet::types::NativeType;
+
+/// Encodes `values` using the [Byte Stream Split](https://github.com/apache/parquet-format/blob/master/Encodings.md#byte-stream-split-byte_stream_split--9)
+/// encoding and appends them to `buffer`.
+pub fn encode<T: NativeType>(values: &[T], buffer: &mut Vec<u8>) {
+    let num_elements = values.len();
+    let offset = buffer.len();
+    buffer.resize(offset + std::mem::size_of_val(values), 0);
+    let stream = &mut buffer[offset..];
+
+    for (i, value) in values.iter().enumerate() {
+        let value_bytes = value.to_le_bytes();
+        for (n, byte) in value_bytes.as_ref().iter().enumerate() {
+            stream[(num_elements * n) + i] = *byte;
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! The AES ciphers of Parquet modular encryption.
+//!
+//! An encrypted module is stored as `length | nonce | ciphertext [| tag]`, where the 4 byte
+//! little-endian `length` is the number of bytes that follow it. The functions in this module
+//! produce and consume modules without the `length`, which is handled by the callers.
+use crate::parquet::error::{ParquetError, ParquetResult};
+
+pub(super) const NONCE_LEN: usize = 12;
+pub(super) const TAG_LEN: usize = 16;
+
+#[cfg(feature = "encryption")]
+mod aes {
+    use aes_gcm::aead::consts::U12;
+    use aes_gcm::aead::rand_core::RngCore;
+    use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload};
+    use aes_gcm::aes::{Aes128, Aes192, Aes256};
+    use aes_gcm::{AesGcm, Nonce};
+    use ctr::cipher::{KeyIvInit, StreamCipher};
+
+    use super::*;
+
+    /// Runs `$body` with `$Aes` defined as the AES block cipher of the size of `$key`.
+    macro_rules! with_aes {
+        ($key:expr, $Aes:ident => $body:expr) => {
+            match $key.len() {
+                16 => {
+                    type $Aes = Aes128;
+                    $body
+                },
+                24 => {
+                    type $Aes = Aes192;
+                    $body
+                },
+                32 => {
+                    type $Aes = Aes256;
+                    $body
+                },
+                n => Err(ParquetError::InvalidParameter(format!(
+                    "an AES key must have 16, 24 or 32 bytes, got {n}"
+                ))),
+            }
+        };
+    }
+
+    pub(crate) fn random_bytes<const N: usize>() -> ParquetResult<[u8; N]> {
+        let mut bytes = [0; N];
+        OsRng.fill_bytes(&mut bytes);
+        Ok(bytes)
+    }
+
+    pub(crate) fn gcm_encrypt_with_nonce(
+        key: &[u8],
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> ParquetResult<Vec<u8>> {
+        with_aes!(key, Aes => {
+            let cipher = AesGcm::<Aes, U12>::new_from_slice(key).unwrap();
+            let ciphertext = cipher
+                .encrypt(Nonce::from_slice(nonce), Payload { msg: plaintext, aad })
+                .map_err(|_| ParquetError::oos("AES-GCM encryption failed"))?;
+
+            let mut module = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+            module.extend_from_slice(nonce);
+            module.extend_from_slice(&ciphertext);
+            Ok(module)
+        })
+    }
+
+    pub(crate) fn gcm_decrypt(key: &[u8], aad: &[u8], module: &[u8]) -> ParquetResult<Vec<u8>> {
+        if module.len() < NONCE_LEN + TAG_LEN {
+            return Err(ParquetError::oos("An AES-GCM module is too short"));
+        }
+        let (nonce, ciphertext) = module.split_at(NONCE_LEN);
+        with_aes!(key, Aes => {
+            let cipher = AesGcm::<Aes, U12>::new_from_slice(key).unwrap();
+            cipher
+                .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad })
+                .map_err(|_| {
+                    ParquetError::InvalidParameter(
+                        "failed to decrypt a module of the Parquet file; the key is wrong or \
+                        the file is corrupted"
+                            .to_string(),
+                    )
+                })
+        })
+    }
+
+    /// The initialization vector of AES-CTR is the nonce followed by a 4 byte big-endian counter
+    /// that starts at 1.
+    fn ctr_iv(nonce: &[u8]) -> [u8; 16] {
+        let mut iv = [0; 16];
+        iv[..NONCE_LEN].copy_from_slice(nonce);
+        iv[15] = 1;
+        iv
+    }
+
+    pub(crate) fn ctr_encrypt(key: &[u8], plaintext: &[u8]) -> ParquetResult<Vec<u8>> {
+        let nonce = random_bytes::<NONCE_LEN>()?;
+        let mut module = Vec::with_capacity(NONCE_LEN + plaintext.len());
+        module.extend_from_slice(&nonce);
+        module.extend_from_slice(plaintext);
+        with_aes!(key, Aes => {
+            let mut cipher =
+                ctr::Ctr32BE::<Aes>::new_from_slices(key, &ctr_iv(&nonce)).unwrap();
+            cipher.apply_keystream(&mut module[NONCE_LEN..]);
+            Ok(())
+        })?;
+        Ok(module)
+    }
+
+    pub(crate) fn ctr_decrypt(key: &[u8], module: &[u8]) -> ParquetResult<Vec<u8>> {
+        if module.len() < NONCE_LEN {
+            return Err(ParquetError::oos("An AES-CTR module is too short"));
+        }
+        let (nonce, ciphertext) = module.split_at(NONCE_LEN);
+        let mut plaintext = ciphertext.to_vec();
+        with_aes!(key, Aes => {
+            let mut cipher = ctr::Ctr32BE::<Aes>::new_from_slices(key, &ctr_iv(nonce)).unwrap();
+            cipher.apply_keystream(&mut plaintext);
+            Ok(())
+        })?;
+        Ok(plaintext)
+    }
+}
+
+#[cfg(not(feature = "encryption"))]
+mod aes {
+    use super::*;
+    use crate::parquet::error::Feature;
+
+    fn inactive() -> ParquetError {
+        ParquetError::FeatureNotActive(
+            Feature::Encryption,
+            "read or write encrypted files".to_string(),
+        )
+    }
+
+    pub(crate) fn random_bytes<const N: usize>() -> ParquetResult<[u8; N]> {
+        Err(inactive())
+    }
+
+    pub(crate) fn gcm_encrypt_with_nonce(
+        _key: &[u8],
+        _nonce: &[u8; NONCE_LEN],
+        _aad: &[u8],
+        _plaintext: &[u8],
+    ) -> ParquetResult<Vec<u8>> {
+        Err(inactive())
+    }
+
+    pub(crate) fn gcm_decrypt(_key: &[u8], _aad: &[u8], _module: &[u8]) -> ParquetResult<Vec<u8>> {
+        Err(inactive())
+    }
+
+    pub(crate) fn ctr_encrypt(_key: &[u8], _plaintext: &[u8]) -> ParquetResult<Vec<u8>> {
+        Err(inactive())
+    }
+
+    pub(crate) fn ctr_decrypt(_key: &[u8], _module: &[u8]) -> ParquetResult<Vec<u8>> {
+        Err(inactive())
+    }
+}
+
+pub(super) use aes::*;
+
+/// Encrypts `plaintext` with AES-GCM and a random nonce.
+pub(super) fn gcm_encrypt(key: &[u8], aad: &[u8], plaintext: &[u8]) -> ParquetResult<Vec<u8>> {
+    gcm_encrypt_with_nonce(key, &random_bytes::<NONCE_LEN>()?, aad, plaintext)
+}
@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use parquet_format_safe::thrift::protocol::TCompactInputProtocol;
+use parquet_format_safe::{
+    ColumnCryptoMetaData, ColumnMetaData, EncryptionAlgorithm as ThriftEncryptionAlgorithm,
+    FileMetaData,
+};
+use polars_utils::aliases::{InitHashMaps, PlHashMap};
+
+use super::cipher::{self, NONCE_LEN, TAG_LEN};
+use super::{
+    module_aad, module_body, ColumnCrypto, EncryptionAlgorithm, FileDecryptionProperties,
+    ModuleType,
+};
+use crate::parquet::error::{ParquetError, ParquetResult};
+
+/// Decrypts the footer of a file and the metadata of its encrypted columns.
+pub(crate) struct FileDecryptor {
+    algorithm: EncryptionAlgorithm,
+    footer_key: Arc<[u8]>,
+    file_aad: Arc<[u8]>,
+    properties: FileDecryptionProperties,
+}
+
+impl FileDecryptor {
+    pub(crate) fn try_new(
+        algorithm: &ThriftEncryptionAlgorithm,
+        footer_key_metadata: Option<&[u8]>,
+        properties: &FileDecryptionProperties,
+    ) -> ParquetResult<Self> {
+        let (algorithm, stored_aad_prefix, aad_file_unique, supply_aad_prefix) = match algorithm {
+            ThriftEncryptionAlgorithm::AESGCMV1(a) => (
+                EncryptionAlgorithm::AesGcmV1,
+                &a.aad_prefix,
+                &a.aad_file_unique,
+                a.supply_aad_prefix,
+            ),
+            ThriftEncryptionAlgorithm::AESGCMCTRV1(a) => (
+                EncryptionAlgorithm::AesGcmCtrV1,
+                &a.aad_prefix,
+                &a.aad_file_unique,
+                a.supply_aad_prefix,
+            ),
+        };
+
+        let aad_prefix = match (&properties.aad_prefix, stored_aad_prefix) {
+            (Some(aad_prefix), Some(stored)) if aad_prefix != stored => {
+                return Err(ParquetError::InvalidParameter(
+                    "the AAD prefix does not match the AAD prefix that is stored in the file"
+                        .to_string(),
+                ))
+            },
+            (Some(aad_prefix), _) | (None, Some(aad_prefix)) => aad_prefix.as_slice(),
+            (None, None) if supply_aad_prefix == Some(true) => {
+                return Err(ParquetError::InvalidParameter(
+                    "the file was written without storing its AAD prefix, which must be supplied"
+                        .to_string(),
+                ))
+            },
+            (None, None) => &[],
+        };
+        let file_aad = [aad_prefix, aad_file_unique.as_deref().unwrap_or_default()].concat();
+
+        let footer_key = properties
+            .key_retriever
+            .retrieve_key(footer_key_metadata.unwrap_or_default())?;
+
+        Ok(Self {
+            algorithm,
+            footer_key: footer_key.into(),
+            file_aad: file_aad.into(),
+            properties: properties.clone(),
+        })
+    }
+
+    fn footer_aad(&self) -> Vec<u8> {
+        module_aad(&self.file_aad, ModuleType::Footer, None, None)
+    }
+
+    /// Decrypts the encrypted footer `module`, which starts with its length.
+    pub(crate) fn decrypt_footer(&self, module: &[u8]) -> ParquetResult<Vec<u8>> {
+        cipher::gcm_decrypt(&self.footer_key, &self.footer_aad(), module_body(module)?)
+    }
+
+    /// Verifies the signature of a plaintext `footer`, which is the nonce and the tag of the
+    /// footer encrypted with AES-GCM.
+    pub(crate) fn verify_footer_signature(
+        &self,
+        footer: &[u8],
+        signature: &[u8],
+    ) -> ParquetResult<()> {
+        if signature.len() != NONCE_LEN + TAG_LEN {
+            return Err(ParquetError::oos(
+                "The signature of a plaintext footer must have 28 bytes",
+            ));
+        }
+        let (nonce, tag) = signature.split_at(NONCE_LEN);
+        let encrypted = cipher::gcm_encrypt_with_nonce(
+            &self.footer_key,
+            nonce.try_into().unwrap(),
+            &self.footer_aad(),
+            footer,
+        )?;
+        if &encrypted[encrypted.len() - TAG_LEN..] != tag {
+            return Err(ParquetError::InvalidParameter(
+                "the signature of the footer does not match; the footer key is wrong or the file \
+                is corrupted"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Decrypts the metadata of the encrypted columns in `metadata` and returns the keys to
+    /// decrypt the column chunks, per row group.
+    pub(crate) fn decrypt_row_groups(
+        &self,
+        metadata: &mut FileMetaData,
+    ) -> ParquetResult<Vec<Vec<Option<ColumnCrypto>>>> {
+        // A key is retrieved once per file.
+        let mut column_keys = PlHashMap::<Vec<u8>, Arc<[u8]>>::new();
+
+        metadata
+            .row_groups
+            .iter_mut()
+            .enumerate()
+            .map(|(row_group_ordinal, row_group)| {
+                row_group
+                    .columns
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(column_ordinal, column)| {
+                        let key = match &column.crypto_metadata {
+                            None => return Ok(None),
+                            Some(ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(_)) => {
+                                self.footer_key.clone()
+                            },
+                            Some(ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(column_key)) => {
+                                let key_metadata =
+                                    column_key.key_metadata.clone().unwrap_or_default();
+                                match column_keys.get(&key_metadata) {
+                                    Some(key) => key.clone(),
+                                    None => {
+                                        let key: Arc<[u8]> = self
+                                            .properties
+                                            .key_retriever
+                                            .retrieve_key(&key_metadata)?
+                                            .into();
+                                        column_keys.insert(key_metadata, key.clone());
+                                        key
+                                    },
+                                }
+                            },
+                        };
+                        let crypto = ColumnCrypto::new(
+                            self.algorithm,
+                            key,
+                            self.file_aad.clone(),
+                            row_group_ordinal,
+                            column_ordinal,
+                        )?;
+
+                        if let Some(encrypted) = column.encrypted_column_metadata.take() {
+                            let decrypted = crypto.decrypt_column_metadata(&encrypted)?;
+                            let max_size = decrypted.len() * 2 + 1024;
+                            let mut prot = TCompactInputProtocol::new(&decrypted[..], max_size);
+                            column.meta_data =
+                                Some(ColumnMetaData::read_from_in_protocol(&mut prot)?);
+                        }
+                        Ok(Some(crypto))
+                    })
+                    .collect::<ParquetResult<Vec<_>>>()
+            })
+            .collect()
+    }
+}
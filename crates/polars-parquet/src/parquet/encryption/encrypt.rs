@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use parquet_format_safe::thrift::protocol::TCompactOutputProtocol;
+use parquet_format_safe::{
+    AesGcmCtrV1, AesGcmV1, ColumnChunk, ColumnCryptoMetaData,
+    EncryptionAlgorithm as ThriftEncryptionAlgorithm, EncryptionWithColumnKey,
+    EncryptionWithFooterKey, FileCryptoMetaData, FileMetaData,
+};
+
+use super::{
+    cipher, module_aad, with_length, ColumnCrypto, EncryptionAlgorithm, FileEncryptionProperties,
+    ModuleType,
+};
+use crate::parquet::error::{ParquetError, ParquetResult};
+use crate::parquet::metadata::SchemaDescriptor;
+
+/// The length of the random part of the additional authenticated data, which makes it unique per
+/// file.
+const AAD_FILE_UNIQUE_LEN: usize = 8;
+
+/// A key and its key metadata, which is `None` for the footer key.
+type KeyWithMetadata<'a> = (Arc<[u8]>, Option<&'a [u8]>);
+
+struct ColumnKey {
+    column: String,
+    key_metadata: Vec<u8>,
+    key: Arc<[u8]>,
+}
+
+/// Encrypts the column chunks and the footer of a file.
+pub(crate) struct FileEncryptor {
+    algorithm: EncryptionAlgorithm,
+    footer_key: Arc<[u8]>,
+    footer_key_metadata: Vec<u8>,
+    column_keys: Vec<ColumnKey>,
+    aad_prefix: Option<Vec<u8>>,
+    store_aad_prefix: bool,
+    aad_file_unique: [u8; AAD_FILE_UNIQUE_LEN],
+    file_aad: Arc<[u8]>,
+}
+
+impl FileEncryptor {
+    pub(crate) fn try_new(
+        properties: &FileEncryptionProperties,
+        schema: &SchemaDescriptor,
+    ) -> ParquetResult<Self> {
+        let retrieve = |key_metadata: &[u8]| -> ParquetResult<Arc<[u8]>> {
+            let key = properties.key_retriever.retrieve_key(key_metadata)?;
+            if !matches!(key.len(), 16 | 24 | 32) {
+                return Err(ParquetError::InvalidParameter(format!(
+                    "an AES key must have 16, 24 or 32 bytes, got {}",
+                    key.len()
+                )));
+            }
+            Ok(key.into())
+        };
+
+        let column_keys = properties
+            .column_key_metadata
+            .iter()
+            .map(|(column, key_metadata)| {
+                // A misspelled column would silently be written in plaintext.
+                if !schema
+                    .columns()
+                    .iter()
+                    .any(|c| c.path_in_schema[0].as_str() == column)
+                {
+                    return Err(ParquetError::InvalidParameter(format!(
+                        "cannot encrypt column '{column}' as it is not in the schema"
+                    )));
+                }
+                Ok(ColumnKey {
+                    column: column.clone(),
+                    key_metadata: key_metadata.clone(),
+                    key: retrieve(key_metadata)?,
+                })
+            })
+            .collect::<ParquetResult<Vec<_>>>()?;
+
+        let aad_file_unique = cipher::random_bytes::<AAD_FILE_UNIQUE_LEN>()?;
+        let file_aad = [
+            properties.aad_prefix.as_deref().unwrap_or_default(),
+            &aad_file_unique[..],
+        ]
+        .concat();
+
+        Ok(Self {
+            algorithm: properties.algorithm,
+            footer_key: retrieve(&properties.footer_key_metadata)?,
+            footer_key_metadata: properties.footer_key_metadata.clone(),
+            column_keys,
+            aad_prefix: properties.aad_prefix.clone(),
+            store_aad_prefix: properties.store_aad_prefix,
+            aad_file_unique,
+            file_aad: file_aad.into(),
+        })
+    }
+
+    /// The key of the top-level column `column` and its key metadata. Returns `None` if the
+    /// column is not encrypted.
+    fn column_key(&self, column: &str) -> Option<KeyWithMetadata<'_>> {
+        if self.column_keys.is_empty() {
+            return Some((self.footer_key.clone(), None));
+        }
+        self.column_keys
+            .iter()
+            .find(|column_key| column_key.column == column)
+            .map(|column_key| {
+                (
+                    column_key.key.clone(),
+                    Some(column_key.key_metadata.as_slice()),
+                )
+            })
+    }
+
+    /// The keys to encrypt the column chunk of a leaf of the top-level column `column`, or `None`
+    /// if it is not encrypted.
+    pub(crate) fn column_crypto(
+        &self,
+        column: &str,
+        row_group_ordinal: usize,
+        column_ordinal: usize,
+    ) -> ParquetResult<Option<ColumnCrypto>> {
+        let Some((key, _)) = self.column_key(column) else {
+            return Ok(None);
+        };
+        ColumnCrypto::new(
+            self.algorithm,
+            key,
+            self.file_aad.clone(),
+            row_group_ordinal,
+            column_ordinal,
+        )
+        .map(Some)
+    }
+
+    /// Sets the crypto metadata of an encrypted `column`. The metadata of columns that are
+    /// encrypted with their own key is encrypted as well and removed from the footer.
+    pub(crate) fn encrypt_column_chunk(
+        &self,
+        column: &mut ColumnChunk,
+        crypto: &ColumnCrypto,
+    ) -> ParquetResult<()> {
+        let metadata = column.meta_data.as_ref().unwrap();
+        let Some((_, key_metadata)) = self.column_key(&metadata.path_in_schema[0]) else {
+            return Ok(());
+        };
+
+        match key_metadata {
+            None => {
+                column.crypto_metadata = Some(ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(
+                    EncryptionWithFooterKey {},
+                ));
+            },
+            Some(key_metadata) => {
+                column.crypto_metadata = Some(ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(
+                    EncryptionWithColumnKey {
+                        path_in_schema: metadata.path_in_schema.clone(),
+                        key_metadata: Some(key_metadata.to_vec()),
+                    },
+                ));
+
+                let mut bytes = vec![];
+                let mut protocol = TCompactOutputProtocol::new(&mut bytes);
+                metadata.write_to_out_protocol(&mut protocol)?;
+                column.encrypted_column_metadata = Some(crypto.encrypt_column_metadata(&bytes)?);
+                column.meta_data = None;
+            },
+        }
+        Ok(())
+    }
+
+    fn thrift_algorithm(&self) -> ThriftEncryptionAlgorithm {
+        let aad_prefix = self.aad_prefix.clone().filter(|_| self.store_aad_prefix);
+        let aad_file_unique = Some(self.aad_file_unique.to_vec());
+        let supply_aad_prefix = self.aad_prefix.as_ref().map(|_| !self.store_aad_prefix);
+        match self.algorithm {
+            EncryptionAlgorithm::AesGcmV1 => ThriftEncryptionAlgorithm::AESGCMV1(AesGcmV1 {
+                aad_prefix,
+                aad_file_unique,
+                supply_aad_prefix,
+            }),
+            EncryptionAlgorithm::AesGcmCtrV1 => {
+                ThriftEncryptionAlgorithm::AESGCMCTRV1(AesGcmCtrV1 {
+                    aad_prefix,
+                    aad_file_unique,
+                    supply_aad_prefix,
+                })
+            },
+        }
+    }
+
+    /// Serializes the footer of an encrypted file, which is the plaintext crypto metadata of the
+    /// file followed by the encrypted `metadata`.
+    pub(crate) fn encrypt_footer(&self, metadata: &FileMetaData) -> ParquetResult<Vec<u8>> {
+        let crypto_metadata = FileCryptoMetaData {
+            encryption_algorithm: self.thrift_algorithm(),
+            key_metadata: Some(self.footer_key_metadata.clone()),
+        };
+
+        let mut bytes = vec![];
+        let mut protocol = TCompactOutputProtocol::new(&mut bytes);
+        crypto_metadata.write_to_out_protocol(&mut protocol)?;
+
+        let mut footer = vec![];
+        let mut protocol = TCompactOutputProtocol::new(&mut footer);
+        metadata.write_to_out_protocol(&mut protocol)?;
+        let aad = module_aad(&self.file_aad, ModuleType::Footer, None, None);
+        bytes.extend(with_length(cipher::gcm_encrypt(
+            &self.footer_key,
+            &aad,
+            &footer,
+        )?)?);
+        Ok(bytes)
+    }
+}
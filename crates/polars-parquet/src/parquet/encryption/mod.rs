@@ -0,0 +1,366 @@
+//! [Parquet modular encryption](https://github.com/apache/parquet-format/blob/master/Encryption.md).
+//!
+//! Files are written with an encrypted footer. The columns are encrypted with the footer key, or
+//! with their own key. Files with an encrypted footer and files with a plaintext footer can be
+//! read.
+//!
+//! The keys are not stored in the file. Instead, every key is identified by its key metadata,
+//! e.g. the id of the key in a key management service, which is passed to a [`KeyRetriever`] to
+//! get the key.
+mod cipher;
+mod decrypt;
+mod encrypt;
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+pub(crate) use decrypt::FileDecryptor;
+pub(crate) use encrypt::FileEncryptor;
+use parquet_format_safe::thrift::protocol::TCompactInputProtocol;
+use polars_utils::mmap::MemReader;
+
+use crate::parquet::error::{ParquetError, ParquetResult};
+use crate::parquet::page::ParquetPageHeader;
+
+/// Retrieves the key that is identified by the key metadata in a file.
+pub trait KeyRetriever: Send + Sync {
+    /// Returns the AES key with 16, 24 or 32 bytes that is identified by `key_metadata`.
+    fn retrieve_key(&self, key_metadata: &[u8]) -> ParquetResult<Vec<u8>>;
+}
+
+impl<F> KeyRetriever for F
+where
+    F: Fn(&[u8]) -> ParquetResult<Vec<u8>> + Send + Sync,
+{
+    fn retrieve_key(&self, key_metadata: &[u8]) -> ParquetResult<Vec<u8>> {
+        self(key_metadata)
+    }
+}
+
+/// The algorithm that is used to encrypt the modules of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EncryptionAlgorithm {
+    /// All modules are encrypted with AES-GCM.
+    #[default]
+    AesGcmV1,
+    /// The pages are encrypted with AES-CTR, which is faster but doesn't authenticate them. All
+    /// other modules are encrypted with AES-GCM.
+    AesGcmCtrV1,
+}
+
+/// The properties to decrypt a file.
+#[derive(Clone)]
+pub struct FileDecryptionProperties {
+    key_retriever: Arc<dyn KeyRetriever>,
+    aad_prefix: Option<Vec<u8>>,
+}
+
+impl FileDecryptionProperties {
+    pub fn new(key_retriever: Arc<dyn KeyRetriever>) -> Self {
+        Self {
+            key_retriever,
+            aad_prefix: None,
+        }
+    }
+
+    /// Set the prefix of the additional authenticated data, which must be given if the file was
+    /// written without storing it.
+    pub fn with_aad_prefix(mut self, aad_prefix: Option<Vec<u8>>) -> Self {
+        self.aad_prefix = aad_prefix;
+        self
+    }
+}
+
+impl Debug for FileDecryptionProperties {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileDecryptionProperties")
+            .field("aad_prefix", &self.aad_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The properties to encrypt a file.
+#[derive(Clone)]
+pub struct FileEncryptionProperties {
+    key_retriever: Arc<dyn KeyRetriever>,
+    algorithm: EncryptionAlgorithm,
+    footer_key_metadata: Vec<u8>,
+    column_key_metadata: Vec<(String, Vec<u8>)>,
+    aad_prefix: Option<Vec<u8>>,
+    store_aad_prefix: bool,
+}
+
+impl FileEncryptionProperties {
+    /// Encrypt the footer with the key of `footer_key_metadata`. If no column keys are set, all
+    /// columns are encrypted with the footer key.
+    pub fn new(key_retriever: Arc<dyn KeyRetriever>, footer_key_metadata: Vec<u8>) -> Self {
+        Self {
+            key_retriever,
+            algorithm: EncryptionAlgorithm::default(),
+            footer_key_metadata,
+            column_key_metadata: vec![],
+            aad_prefix: None,
+            store_aad_prefix: true,
+        }
+    }
+
+    pub fn with_algorithm(mut self, algorithm: EncryptionAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Encrypt the columns with the keys of their key metadata, by the name of the top-level
+    /// column. The columns without a key are not encrypted.
+    pub fn with_column_key_metadata(mut self, column_key_metadata: Vec<(String, Vec<u8>)>) -> Self {
+        self.column_key_metadata = column_key_metadata;
+        self
+    }
+
+    /// Set the prefix of the additional authenticated data, e.g. the name of the file to
+    /// protect against swapping files. If `store` is `false`, readers have to supply it.
+    pub fn with_aad_prefix(mut self, aad_prefix: Option<Vec<u8>>, store: bool) -> Self {
+        self.aad_prefix = aad_prefix;
+        self.store_aad_prefix = store;
+        self
+    }
+}
+
+impl Debug for FileEncryptionProperties {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileEncryptionProperties")
+            .field("algorithm", &self.algorithm)
+            .field("footer_key_metadata", &self.footer_key_metadata)
+            .field("column_key_metadata", &self.column_key_metadata)
+            .field("aad_prefix", &self.aad_prefix)
+            .field("store_aad_prefix", &self.store_aad_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The types of modules that are encrypted, which are part of their additional authenticated
+/// data.
+#[derive(Debug, Clone, Copy)]
+enum ModuleType {
+    Footer = 0,
+    ColumnMetaData = 1,
+    DataPage = 2,
+    DictionaryPage = 3,
+    DataPageHeader = 4,
+    DictionaryPageHeader = 5,
+}
+
+/// The keys and the position in the file that are needed to encrypt and decrypt the modules of
+/// a column chunk.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ColumnCrypto {
+    algorithm: EncryptionAlgorithm,
+    key: Arc<[u8]>,
+    file_aad: Arc<[u8]>,
+    row_group_ordinal: i16,
+    column_ordinal: i16,
+}
+
+impl Debug for ColumnCrypto {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColumnCrypto")
+            .field("algorithm", &self.algorithm)
+            .field("row_group_ordinal", &self.row_group_ordinal)
+            .field("column_ordinal", &self.column_ordinal)
+            .finish_non_exhaustive()
+    }
+}
+
+fn ordinal(ordinal: usize, what: &str) -> ParquetResult<i16> {
+    ordinal.try_into().map_err(|_| {
+        ParquetError::not_supported(format!(
+            "encrypted files can contain at most {} {what}",
+            i16::MAX as usize + 1
+        ))
+    })
+}
+
+/// Split off the 4 byte length of an encrypted module at the start of `bytes`.
+fn module_body(bytes: &[u8]) -> ParquetResult<&[u8]> {
+    let length = bytes
+        .get(..4)
+        .map(|length| u32::from_le_bytes(length.try_into().unwrap()) as usize)
+        .ok_or_else(|| ParquetError::oos("An encrypted module must start with its length"))?;
+    bytes
+        .get(4..4 + length)
+        .ok_or_else(|| ParquetError::oos("The length of an encrypted module is out of bounds"))
+}
+
+/// Prefix an encrypted module with its length.
+fn with_length(module: Vec<u8>) -> ParquetResult<Vec<u8>> {
+    let length: u32 = module
+        .len()
+        .try_into()
+        .map_err(|_| ParquetError::oos("An encrypted module can have at most u32::MAX bytes"))?;
+    let mut bytes = Vec::with_capacity(4 + module.len());
+    bytes.extend_from_slice(&length.to_le_bytes());
+    bytes.extend(module);
+    Ok(bytes)
+}
+
+/// The additional authenticated data of a module.
+fn module_aad(
+    file_aad: &[u8],
+    module_type: ModuleType,
+    ordinals: Option<(i16, i16)>,
+    page_ordinal: Option<i16>,
+) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(file_aad.len() + 7);
+    aad.extend_from_slice(file_aad);
+    aad.push(module_type as u8);
+    if let Some((row_group_ordinal, column_ordinal)) = ordinals {
+        aad.extend_from_slice(&row_group_ordinal.to_le_bytes());
+        aad.extend_from_slice(&column_ordinal.to_le_bytes());
+    }
+    if let Some(page_ordinal) = page_ordinal {
+        aad.extend_from_slice(&page_ordinal.to_le_bytes());
+    }
+    aad
+}
+
+impl ColumnCrypto {
+    pub(crate) fn new(
+        algorithm: EncryptionAlgorithm,
+        key: Arc<[u8]>,
+        file_aad: Arc<[u8]>,
+        row_group_ordinal: usize,
+        column_ordinal: usize,
+    ) -> ParquetResult<Self> {
+        Ok(Self {
+            algorithm,
+            key,
+            file_aad,
+            row_group_ordinal: ordinal(row_group_ordinal, "row groups")?,
+            column_ordinal: ordinal(column_ordinal, "columns")?,
+        })
+    }
+
+    fn aad(&self, module_type: ModuleType, page_ordinal: Option<i16>) -> Vec<u8> {
+        module_aad(
+            &self.file_aad,
+            module_type,
+            Some((self.row_group_ordinal, self.column_ordinal)),
+            page_ordinal,
+        )
+    }
+
+    /// The module type and page ordinal of the header and the data of a page. Only data pages
+    /// have an ordinal.
+    fn page_modules(
+        page_ordinal: Option<usize>,
+    ) -> ParquetResult<(ModuleType, ModuleType, Option<i16>)> {
+        Ok(match page_ordinal {
+            Some(page_ordinal) => (
+                ModuleType::DataPageHeader,
+                ModuleType::DataPage,
+                Some(ordinal(page_ordinal, "pages per column chunk")?),
+            ),
+            None => (
+                ModuleType::DictionaryPageHeader,
+                ModuleType::DictionaryPage,
+                None,
+            ),
+        })
+    }
+
+    /// Reads and decrypts the header of the page with `page_ordinal`, or of the dictionary page
+    /// if it is `None`.
+    pub(crate) fn read_page_header(
+        &self,
+        reader: &mut MemReader,
+        page_ordinal: Option<usize>,
+        max_size: usize,
+    ) -> ParquetResult<ParquetPageHeader> {
+        let (header_module, _, page_ordinal) = Self::page_modules(page_ordinal)?;
+
+        let length = reader.read_slice(4);
+        if length.len() != 4 {
+            return Err(ParquetError::oos(
+                "An encrypted page header is out of bounds",
+            ));
+        }
+        let length = u32::from_le_bytes(length[..].try_into().unwrap()) as usize;
+        if length > max_size {
+            return Err(ParquetError::WouldOverAllocate);
+        }
+        let module = reader.read_slice(length);
+        if module.len() != length {
+            return Err(ParquetError::oos(
+                "An encrypted page header is out of bounds",
+            ));
+        }
+
+        let header =
+            cipher::gcm_decrypt(&self.key, &self.aad(header_module, page_ordinal), &module)?;
+        let mut prot = TCompactInputProtocol::new(&header[..], max_size);
+        Ok(ParquetPageHeader::read_from_in_protocol(&mut prot)?)
+    }
+
+    /// Decrypts the data of the page with `page_ordinal`, or of the dictionary page if it is
+    /// `None`.
+    pub(crate) fn decrypt_page(
+        &self,
+        page: &[u8],
+        page_ordinal: Option<usize>,
+    ) -> ParquetResult<Vec<u8>> {
+        let (_, data_module, page_ordinal) = Self::page_modules(page_ordinal)?;
+        let module = module_body(page)?;
+        match self.algorithm {
+            EncryptionAlgorithm::AesGcmV1 => {
+                cipher::gcm_decrypt(&self.key, &self.aad(data_module, page_ordinal), module)
+            },
+            EncryptionAlgorithm::AesGcmCtrV1 => cipher::ctr_decrypt(&self.key, module),
+        }
+    }
+
+    /// Encrypts the serialized header of the page with `page_ordinal`, or of the dictionary page
+    /// if it is `None`.
+    pub(crate) fn encrypt_page_header(
+        &self,
+        header: &[u8],
+        page_ordinal: Option<usize>,
+    ) -> ParquetResult<Vec<u8>> {
+        let (header_module, _, page_ordinal) = Self::page_modules(page_ordinal)?;
+        with_length(cipher::gcm_encrypt(
+            &self.key,
+            &self.aad(header_module, page_ordinal),
+            header,
+        )?)
+    }
+
+    /// Encrypts the data of the page with `page_ordinal`, or of the dictionary page if it is
+    /// `None`.
+    pub(crate) fn encrypt_page(
+        &self,
+        page: &[u8],
+        page_ordinal: Option<usize>,
+    ) -> ParquetResult<Vec<u8>> {
+        let (_, data_module, page_ordinal) = Self::page_modules(page_ordinal)?;
+        with_length(match self.algorithm {
+            EncryptionAlgorithm::AesGcmV1 => {
+                cipher::gcm_encrypt(&self.key, &self.aad(data_module, page_ordinal), page)?
+            },
+            EncryptionAlgorithm::AesGcmCtrV1 => cipher::ctr_encrypt(&self.key, page)?,
+        })
+    }
+
+    fn decrypt_column_metadata(&self, module: &[u8]) -> ParquetResult<Vec<u8>> {
+        cipher::gcm_decrypt(
+            &self.key,
+            &self.aad(ModuleType::ColumnMetaData, None),
+            module_body(module)?,
+        )
+    }
+
+    fn encrypt_column_metadata(&self, metadata: &[u8]) -> ParquetResult<Vec<u8>> {
+        with_length(cipher::gcm_encrypt(
+            &self.key,
+            &self.aad(ModuleType::ColumnMetaData, None),
+            metadata,
+        )?)
+    }
+}
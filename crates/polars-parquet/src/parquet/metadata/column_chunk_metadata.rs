@@ -2,6 +2,7 @@ use parquet_format_safe::{ColumnChunk, ColumnMetaData, Encoding};
 
 use super::column_descriptor::ColumnDescriptor;
 use crate::parquet::compression::Compression;
+use crate::parquet::encryption::ColumnCrypto;
 use crate::parquet::error::{ParquetError, ParquetResult};
 use crate::parquet::schema::types::PhysicalType;
 use crate::parquet::statistics::Statistics;
@@ -39,6 +40,8 @@ pub struct ColumnChunkMetadata {
     )]
     column_chunk: ColumnChunk,
     column_descr: ColumnDescriptor,
+    #[cfg_attr(feature = "serde_types", serde(skip))]
+    crypto: Option<ColumnCrypto>,
 }
 
 #[cfg(feature = "serde_types")]
@@ -76,6 +79,7 @@ impl ColumnChunkMetadata {
         Self {
             column_chunk,
             column_descr,
+            crypto: None,
         }
     }
 
@@ -102,6 +106,17 @@ impl ColumnChunkMetadata {
         self.column_chunk.meta_data.as_ref().unwrap()
     }
 
+    /// Whether the pages of this column chunk are encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.column_chunk.crypto_metadata.is_some()
+    }
+
+    /// The keys to decrypt the pages of this column chunk. This is `None` if the column chunk is
+    /// not encrypted, or if the file was read without the keys.
+    pub fn crypto(&self) -> Option<&ColumnCrypto> {
+        self.crypto.as_ref()
+    }
+
     /// The [`ColumnDescriptor`] for this column. This descriptor contains the physical and logical type
     /// of the pages.
     pub fn descriptor(&self) -> &ColumnDescriptor {
@@ -176,6 +191,7 @@ impl ColumnChunkMetadata {
     pub(crate) fn try_from_thrift(
         column_descr: ColumnDescriptor,
         column_chunk: ColumnChunk,
+        crypto: Option<ColumnCrypto>,
     ) -> ParquetResult<Self> {
         // validate metadata
         if let Some(meta) = &column_chunk.meta_data {
@@ -187,6 +203,11 @@ impl ColumnChunkMetadata {
             let _: u64 = meta.data_page_offset.try_into()?;
 
             let _: Compression = meta.codec.try_into()?;
+        } else if column_chunk.encrypted_column_metadata.is_some() {
+            return Err(ParquetError::InvalidParameter(format!(
+                "the metadata of column '{}' is encrypted; the keys to decrypt it must be given",
+                column_descr.path_in_schema.join(".")
+            )));
         } else {
             return Err(ParquetError::oos("Column chunk requires metadata"));
         }
@@ -194,6 +215,7 @@ impl ColumnChunkMetadata {
         Ok(Self {
             column_chunk,
             column_descr,
+            crypto,
         })
     }
 
@@ -3,6 +3,7 @@ use parquet_format_safe::ColumnOrder as TColumnOrder;
 use super::column_order::ColumnOrder;
 use super::schema_descriptor::SchemaDescriptor;
 use super::RowGroupMetadata;
+use crate::parquet::encryption::FileDecryptor;
 use crate::parquet::error::ParquetError;
 use crate::parquet::metadata::get_sort_order;
 pub use crate::parquet::thrift_format::KeyValue;
@@ -66,16 +67,31 @@ impl FileMetadata {
     /// Deserializes [`crate::parquet::thrift_format::FileMetadata`] into this struct
     pub fn try_from_thrift(
         metadata: parquet_format_safe::FileMetaData,
+    ) -> Result<Self, ParquetError> {
+        Self::try_from_thrift_decrypted(metadata, None)
+    }
+
+    /// Deserializes [`crate::parquet::thrift_format::FileMetadata`] into this struct, decrypting
+    /// the metadata of the encrypted columns with `decryptor`.
+    pub(crate) fn try_from_thrift_decrypted(
+        mut metadata: parquet_format_safe::FileMetaData,
+        decryptor: Option<&FileDecryptor>,
     ) -> Result<Self, ParquetError> {
         let schema_descr = SchemaDescriptor::try_from_thrift(&metadata.schema)?;
 
+        let mut crypto = decryptor
+            .map(|decryptor| decryptor.decrypt_row_groups(&mut metadata))
+            .transpose()?
+            .map(Vec::into_iter);
+
         let mut max_row_group_height = 0;
 
         let row_groups = metadata
             .row_groups
             .into_iter()
             .map(|rg| {
-                let md = RowGroupMetadata::try_from_thrift(&schema_descr, rg)?;
+                let crypto = crypto.as_mut().and_then(Iterator::next);
+                let md = RowGroupMetadata::try_from_thrift(&schema_descr, rg, crypto)?;
                 max_row_group_height = max_row_group_height.max(md.num_rows());
                 Ok(md)
             })
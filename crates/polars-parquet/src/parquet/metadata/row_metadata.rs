@@ -9,6 +9,7 @@ use polars_utils::unitvec;
 
 use super::column_chunk_metadata::{column_metadata_byte_range, ColumnChunkMetadata};
 use super::schema_descriptor::SchemaDescriptor;
+use crate::parquet::encryption::ColumnCrypto;
 use crate::parquet::error::{ParquetError, ParquetResult};
 
 type ColumnLookup = PlHashMap<PlSmallStr, UnitVec<usize>>;
@@ -91,6 +92,7 @@ impl RowGroupMetadata {
     pub(crate) fn try_from_thrift(
         schema_descr: &SchemaDescriptor,
         rg: RowGroup,
+        crypto: Option<Vec<Option<ColumnCrypto>>>,
     ) -> ParquetResult<RowGroupMetadata> {
         if schema_descr.columns().len() != rg.columns.len() {
             return Err(ParquetError::oos(format!("The number of columns in the row group ({}) must be equal to the number of columns in the schema ({})", rg.columns.len(), schema_descr.columns().len())));
@@ -99,23 +101,22 @@ impl RowGroupMetadata {
         let num_rows = rg.num_rows.try_into()?;
 
         let mut column_lookup = ColumnLookup::with_capacity(rg.columns.len());
-        let mut full_byte_range = if let Some(first_column_chunk) = rg.columns.first() {
-            let Some(metadata) = &first_column_chunk.meta_data else {
-                return Err(ParquetError::oos("Column chunk requires metadata"));
-            };
-            column_metadata_byte_range(metadata)
-        } else {
-            0..0
+        // A column chunk without metadata is rejected below.
+        let mut full_byte_range = match rg.columns.first().and_then(|c| c.meta_data.as_ref()) {
+            Some(metadata) => column_metadata_byte_range(metadata),
+            None => 0..0,
         };
 
+        let mut crypto = crypto.map(Vec::into_iter);
         let columns = rg
             .columns
             .into_iter()
             .zip(schema_descr.columns())
             .enumerate()
             .map(|(i, (column_chunk, descriptor))| {
+                let crypto = crypto.as_mut().and_then(Iterator::next).flatten();
                 let column =
-                    ColumnChunkMetadata::try_from_thrift(descriptor.clone(), column_chunk)?;
+                    ColumnChunkMetadata::try_from_thrift(descriptor.clone(), column_chunk, crypto)?;
 
                 column_lookup.add_column(i, &column);
 
@@ -0,0 +1,62 @@
+use parquet_format_safe::thrift::protocol::TCompactInputProtocol;
+
+use crate::parquet::error::{ParquetError, ParquetResult};
+use crate::parquet::metadata::ColumnChunkMetadata;
+pub use crate::parquet::thrift_format::{ColumnIndex, OffsetIndex, PageLocation};
+
+/// Returns the bytes of the index at `offset` with `length` bytes.
+fn index_bytes(
+    file_bytes: &[u8],
+    offset: Option<i64>,
+    length: Option<i32>,
+) -> ParquetResult<Option<&[u8]>> {
+    let (Some(offset), Some(length)) = (offset, length) else {
+        return Ok(None);
+    };
+    let offset: usize = offset.try_into()?;
+    let length: usize = length.try_into()?;
+    file_bytes
+        .get(offset..offset + length)
+        .map(Some)
+        .ok_or_else(|| ParquetError::oos("The page index is out of bounds of the file"))
+}
+
+/// Reads the [`ColumnIndex`] of `column_chunk` from the bytes of the file.
+/// Returns `None` if the column chunk has no column index.
+pub fn read_column_index(
+    column_chunk: &ColumnChunkMetadata,
+    file_bytes: &[u8],
+) -> ParquetResult<Option<ColumnIndex>> {
+    let chunk = column_chunk.column_chunk();
+    let Some(mut bytes) = index_bytes(
+        file_bytes,
+        chunk.column_index_offset,
+        chunk.column_index_length,
+    )?
+    else {
+        return Ok(None);
+    };
+    let max_size = bytes.len() * 2 + 1024;
+    let mut prot = TCompactInputProtocol::new(&mut bytes, max_size);
+    Ok(Some(ColumnIndex::read_from_in_protocol(&mut prot)?))
+}
+
+/// Reads the [`OffsetIndex`] of `column_chunk` from the bytes of the file.
+/// Returns `None` if the column chunk has no offset index.
+pub fn read_offset_index(
+    column_chunk: &ColumnChunkMetadata,
+    file_bytes: &[u8],
+) -> ParquetResult<Option<OffsetIndex>> {
+    let chunk = column_chunk.column_chunk();
+    let Some(mut bytes) = index_bytes(
+        file_bytes,
+        chunk.offset_index_offset,
+        chunk.offset_index_length,
+    )?
+    else {
+        return Ok(None);
+    };
+    let max_size = bytes.len() * 2 + 1024;
+    let mut prot = TCompactInputProtocol::new(&mut bytes, max_size);
+    Ok(Some(OffsetIndex::read_from_in_protocol(&mut prot)?))
+}
@@ -2,10 +2,14 @@ use std::cmp::min;
 use std::io::{Read, Seek, SeekFrom};
 
 use parquet_format_safe::thrift::protocol::TCompactInputProtocol;
-use parquet_format_safe::FileMetaData as TFileMetadata;
+use parquet_format_safe::{FileCryptoMetaData, FileMetaData as TFileMetadata};
 
 use super::super::metadata::FileMetadata;
-use super::super::{DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE, HEADER_SIZE, PARQUET_MAGIC};
+use super::super::{
+    DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE, HEADER_SIZE, PARQUET_MAGIC,
+    PARQUET_MAGIC_ENCRYPTED_FOOTER,
+};
+use crate::parquet::encryption::{FileDecryptionProperties, FileDecryptor};
 use crate::parquet::error::{ParquetError, ParquetResult};
 
 pub(super) fn metadata_len(buffer: &[u8], len: usize) -> i32 {
@@ -28,15 +32,34 @@ fn stream_len(seek: &mut impl Seek) -> std::result::Result<u64, std::io::Error>
 
 /// Reads a [`FileMetadata`] from the reader, located at the end of the file.
 pub fn read_metadata<R: Read + Seek>(reader: &mut R) -> ParquetResult<FileMetadata> {
+    read_metadata_with_decryption(reader, None)
+}
+
+/// Reads a [`FileMetadata`] from the reader, located at the end of the file.
+///
+/// The metadata of encrypted files is decrypted with `decryption`. Without it, the columns that
+/// are encrypted can not be read.
+pub fn read_metadata_with_decryption<R: Read + Seek>(
+    reader: &mut R,
+    decryption: Option<&FileDecryptionProperties>,
+) -> ParquetResult<FileMetadata> {
     // check file is large enough to hold footer
     let file_size = stream_len(reader)?;
-    read_metadata_with_size(reader, file_size)
+    read_footer(reader, file_size, decryption)
 }
 
 /// Reads a [`FileMetadata`] from the reader, located at the end of the file, with known file size.
 pub fn read_metadata_with_size<R: Read + Seek>(
     reader: &mut R,
     file_size: u64,
+) -> ParquetResult<FileMetadata> {
+    read_footer(reader, file_size, None)
+}
+
+fn read_footer<R: Read + Seek>(
+    reader: &mut R,
+    file_size: u64,
+    decryption: Option<&FileDecryptionProperties>,
 ) -> ParquetResult<FileMetadata> {
     if file_size < HEADER_SIZE + FOOTER_SIZE {
         return Err(ParquetError::oos(
@@ -55,9 +78,7 @@ pub fn read_metadata_with_size<R: Read + Seek>(
         .read_to_end(&mut buffer)?;
 
     // check this is indeed a parquet file
-    if buffer[default_end_len - 4..] != PARQUET_MAGIC {
-        return Err(ParquetError::oos("The file must end with PAR1"));
-    }
+    let encrypted_footer = footer_is_encrypted(&buffer[default_end_len - 4..])?;
 
     let metadata_len = metadata_len(&buffer, default_end_len);
 
@@ -85,10 +106,22 @@ pub fn read_metadata_with_size<R: Read + Seek>(
         &buffer
     };
 
-    // a highly nested but sparse struct could result in many allocations
-    let max_size = reader.len() * 2 + 1024;
+    deserialize_metadata_with_decryption(
+        &reader[..metadata_len as usize],
+        encrypted_footer,
+        decryption,
+    )
+}
 
-    deserialize_metadata(reader, max_size)
+/// Checks the `magic` at the end of a file and returns whether its footer is encrypted.
+pub fn footer_is_encrypted(magic: &[u8]) -> ParquetResult<bool> {
+    if magic == PARQUET_MAGIC {
+        Ok(false)
+    } else if magic == PARQUET_MAGIC_ENCRYPTED_FOOTER {
+        Ok(true)
+    } else {
+        Err(ParquetError::oos("The file must end with PAR1 or PARE"))
+    }
 }
 
 /// Parse loaded metadata bytes
@@ -98,3 +131,61 @@ pub fn deserialize_metadata<R: Read>(reader: R, max_size: usize) -> ParquetResul
 
     FileMetadata::try_from_thrift(metadata)
 }
+
+/// Parse loaded metadata bytes, which are the footer of the file without its length and magic.
+///
+/// The footer is decrypted with `decryption` if it is encrypted, and its signature is verified
+/// if it is a signed plaintext footer.
+pub fn deserialize_metadata_with_decryption(
+    metadata: &[u8],
+    encrypted_footer: bool,
+    decryption: Option<&FileDecryptionProperties>,
+) -> ParquetResult<FileMetadata> {
+    // a highly nested but sparse struct could result in many allocations
+    let max_size = metadata.len() * 2 + 1024;
+    let mut reader = metadata;
+
+    if encrypted_footer {
+        let Some(decryption) = decryption else {
+            return Err(ParquetError::InvalidParameter(
+                "the footer of the file is encrypted; the keys to decrypt it must be given"
+                    .to_string(),
+            ));
+        };
+
+        // The footer is the plaintext crypto metadata, followed by the encrypted file metadata.
+        let mut prot = TCompactInputProtocol::new(&mut reader, max_size);
+        let crypto_metadata = FileCryptoMetaData::read_from_in_protocol(&mut prot)?;
+        let decryptor = FileDecryptor::try_new(
+            &crypto_metadata.encryption_algorithm,
+            crypto_metadata.key_metadata.as_deref(),
+            decryption,
+        )?;
+
+        let footer = decryptor.decrypt_footer(reader)?;
+        let max_size = footer.len() * 2 + 1024;
+        let mut prot = TCompactInputProtocol::new(&footer[..], max_size);
+        let metadata = TFileMetadata::read_from_in_protocol(&mut prot)?;
+        return FileMetadata::try_from_thrift_decrypted(metadata, Some(&decryptor));
+    }
+
+    let mut prot = TCompactInputProtocol::new(&mut reader, max_size);
+    let file_metadata = TFileMetadata::read_from_in_protocol(&mut prot)?;
+
+    let decryptor = match (decryption, &file_metadata.encryption_algorithm) {
+        (Some(decryption), Some(algorithm)) => {
+            let decryptor = FileDecryptor::try_new(
+                algorithm,
+                file_metadata.footer_signing_key_metadata.as_deref(),
+                decryption,
+            )?;
+            // The signature immediately follows the plaintext footer.
+            let footer_len = metadata.len() - reader.len();
+            decryptor.verify_footer_signature(&metadata[..footer_len], reader)?;
+            Some(decryptor)
+        },
+        _ => None,
+    };
+
+    FileMetadata::try_from_thrift_decrypted(file_metadata, decryptor.as_ref())
+}
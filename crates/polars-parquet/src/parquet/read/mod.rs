@@ -1,5 +1,6 @@
 mod column;
 mod compression;
+mod indexes;
 pub mod levels;
 mod metadata;
 mod page;
@@ -10,7 +11,11 @@ use std::io::{Seek, SeekFrom};
 
 pub use column::*;
 pub use compression::{decompress, BasicDecompressor};
-pub use metadata::{deserialize_metadata, read_metadata, read_metadata_with_size};
+pub use indexes::{read_column_index, read_offset_index, ColumnIndex, OffsetIndex, PageLocation};
+pub use metadata::{
+    deserialize_metadata, deserialize_metadata_with_decryption, footer_is_encrypted, read_metadata,
+    read_metadata_with_decryption, read_metadata_with_size,
+};
 #[cfg(feature = "async")]
 pub use page::{get_page_stream, get_page_stream_from_column_start};
 pub use page::{PageIterator, PageMetaData, PageReader};
@@ -6,6 +6,7 @@ use polars_utils::mmap::{MemReader, MemSlice};
 
 use super::PageIterator;
 use crate::parquet::compression::Compression;
+use crate::parquet::encryption::ColumnCrypto;
 use crate::parquet::error::{ParquetError, ParquetResult};
 use crate::parquet::metadata::{ColumnChunkMetadata, Descriptor};
 use crate::parquet::page::{
@@ -25,6 +26,8 @@ pub struct PageMetaData {
     pub compression: Compression,
     /// The descriptor of this parquet column
     pub descriptor: Descriptor,
+    /// The keys to decrypt the pages, if the column is encrypted
+    pub crypto: Option<ColumnCrypto>,
 }
 
 impl PageMetaData {
@@ -40,6 +43,7 @@ impl PageMetaData {
             num_values,
             compression,
             descriptor,
+            crypto: None,
         }
     }
 }
@@ -51,6 +55,7 @@ impl From<&ColumnChunkMetadata> for PageMetaData {
             num_values: column.num_values(),
             compression: column.compression(),
             descriptor: column.descriptor().descriptor.clone(),
+            crypto: column.crypto().cloned(),
         }
     }
 }
@@ -80,6 +85,12 @@ pub struct PageReader {
 
     // Maximum page size (compressed or uncompressed) to limit allocations
     max_page_size: usize,
+
+    crypto: Option<ColumnCrypto>,
+    // Whether the column is encrypted, but the keys to decrypt it were not given.
+    missing_keys: bool,
+    // The ordinal of the next data page, which is part of the AAD of encrypted pages.
+    data_page_ordinal: usize,
 }
 
 impl PageReader {
@@ -93,7 +104,10 @@ impl PageReader {
         scratch: Vec<u8>,
         max_page_size: usize,
     ) -> Self {
-        Self::new_with_page_meta(reader, column.into(), scratch, max_page_size)
+        let mut page_reader =
+            Self::new_with_page_meta(reader, column.into(), scratch, max_page_size);
+        page_reader.missing_keys = column.is_encrypted() && column.crypto().is_none();
+        page_reader
     }
 
     /// Create a a new [`PageReader`] with [`PageMetaData`].
@@ -113,6 +127,9 @@ impl PageReader {
             descriptor: reader_meta.descriptor,
             scratch,
             max_page_size,
+            crypto: reader_meta.crypto,
+            missing_keys: false,
+            data_page_ordinal: 0,
         }
     }
 
@@ -136,14 +153,23 @@ impl PageReader {
         // a dictionary page exists iff the first data page is not at the start of
         // the column
         let seek_offset = self.reader.position();
-        let page_header = read_page_header(&mut self.reader, self.max_page_size)?;
-        let page_type = page_header.type_.try_into()?;
-
-        if !matches!(page_type, PageType::DictionaryPage) {
+        let page_header = match self.read_page_header(true) {
+            // The header of a dictionary page is encrypted with a different AAD than the header
+            // of a data page, so if it cannot be decrypted, the first page is a data page.
+            Err(_) if self.crypto.is_some() => None,
+            page_header => Some(page_header?),
+        };
+        let page_type = page_header
+            .as_ref()
+            .map(|page_header| page_header.type_.try_into())
+            .transpose()?;
+
+        let Some(page_header) = page_header.filter(|_| page_type == Some(PageType::DictionaryPage))
+        else {
             self.reader
                 .seek(std::io::SeekFrom::Start(seek_offset as u64))?;
             return Ok(None);
-        }
+        };
 
         let read_size: usize = page_header.compressed_page_size.try_into()?;
 
@@ -158,6 +184,7 @@ impl PageReader {
                 "The page header reported the wrong page size",
             ));
         }
+        let buffer = self.decrypt_page(buffer, true)?;
 
         finish_page(page_header, buffer, self.compression, &self.descriptor).map(|p| {
             if let CompressedPage::Dict(d) = p {
@@ -167,6 +194,35 @@ impl PageReader {
             }
         })
     }
+
+    /// Reads the header of the next page, which is decrypted as the header of a dictionary page
+    /// if `is_dict_page`.
+    fn read_page_header(&mut self, is_dict_page: bool) -> ParquetResult<ParquetPageHeader> {
+        match &self.crypto {
+            None if self.missing_keys => Err(ParquetError::InvalidParameter(
+                "the column is encrypted; the keys to decrypt it must be given".to_string(),
+            )),
+            None => read_page_header(&mut self.reader, self.max_page_size),
+            Some(crypto) => crypto.read_page_header(
+                &mut self.reader,
+                (!is_dict_page).then_some(self.data_page_ordinal),
+                self.max_page_size,
+            ),
+        }
+    }
+
+    fn decrypt_page(&mut self, buffer: MemSlice, is_dict_page: bool) -> ParquetResult<MemSlice> {
+        let Some(crypto) = &self.crypto else {
+            return Ok(buffer);
+        };
+        if is_dict_page {
+            crypto.decrypt_page(&buffer, None).map(MemSlice::from_vec)
+        } else {
+            let page = crypto.decrypt_page(&buffer, Some(self.data_page_ordinal))?;
+            self.data_page_ordinal += 1;
+            Ok(MemSlice::from_vec(page))
+        }
+    }
 }
 
 impl PageIterator for PageReader {
@@ -209,7 +265,7 @@ fn next_page(reader: &mut PageReader) -> ParquetResult<Option<CompressedPage>> {
 }
 
 pub(super) fn build_page(reader: &mut PageReader) -> ParquetResult<Option<CompressedPage>> {
-    let page_header = read_page_header(&mut reader.reader, reader.max_page_size)?;
+    let page_header = reader.read_page_header(false)?;
 
     reader.seen_num_values += get_page_num_values(&page_header)? as i64;
 
@@ -226,6 +282,7 @@ pub(super) fn build_page(reader: &mut PageReader) -> ParquetResult<Option<Compre
             "The page header reported the wrong page size",
         ));
     }
+    let buffer = reader.decrypt_page(buffer, false)?;
 
     finish_page(page_header, buffer, reader.compression, &reader.descriptor).map(Some)
 }
@@ -3,8 +3,8 @@ use std::io::SeekFrom;
 use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 
 use super::super::metadata::FileMetadata;
-use super::super::{DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE, PARQUET_MAGIC};
-use super::metadata::{deserialize_metadata, metadata_len};
+use super::super::{DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE};
+use super::metadata::{deserialize_metadata_with_decryption, footer_is_encrypted, metadata_len};
 use crate::parquet::error::{ParquetError, ParquetResult};
 use crate::parquet::HEADER_SIZE;
 
@@ -49,9 +49,7 @@ pub async fn read_metadata<R: AsyncRead + AsyncSeek + Send + std::marker::Unpin>
         .await?;
 
     // check this is indeed a parquet file
-    if buffer[default_end_len - 4..] != PARQUET_MAGIC {
-        return Err(ParquetError::oos("Invalid Parquet file. Corrupt footer"));
-    }
+    let encrypted_footer = footer_is_encrypted(&buffer[default_end_len - 4..])?;
 
     let metadata_len = metadata_len(&buffer, default_end_len);
     let metadata_len: u64 = metadata_len.try_into()?;
@@ -81,8 +79,5 @@ pub async fn read_metadata<R: AsyncRead + AsyncSeek + Send + std::marker::Unpin>
         &buffer
     };
 
-    // a highly nested but sparse struct could result in many allocations
-    let max_size = reader.len() * 2 + 1024;
-
-    deserialize_metadata(reader, max_size)
+    deserialize_metadata_with_decryption(&reader[..metadata_len as usize], encrypted_footer, None)
 }
@@ -10,21 +10,24 @@ use polars_utils::aliases::PlHashSet;
 
 #[cfg(feature = "async")]
 use super::page::write_page_async;
-use super::page::{write_page, PageWriteSpec};
+use super::page::{is_data_page, write_page, PageWriteSpec};
 use super::statistics::reduce;
 use super::DynStreamingIterator;
 use crate::parquet::compression::Compression;
 use crate::parquet::encoding::Encoding;
+use crate::parquet::encryption::ColumnCrypto;
 use crate::parquet::error::{ParquetError, ParquetResult};
 use crate::parquet::metadata::ColumnDescriptor;
 use crate::parquet::page::{CompressedPage, PageType};
 use crate::parquet::FallibleStreamingIterator;
 
+/// Writes the pages of a column chunk, which are encrypted with `crypto` if it is given.
 pub fn write_column_chunk<W, E>(
     writer: &mut W,
     mut offset: u64,
     descriptor: &ColumnDescriptor,
     mut compressed_pages: DynStreamingIterator<'_, CompressedPage, E>,
+    crypto: Option<&ColumnCrypto>,
 ) -> ParquetResult<(ColumnChunk, Vec<PageWriteSpec>, u64)>
 where
     W: Write,
@@ -36,8 +39,15 @@ where
     let initial = offset;
 
     let mut specs = vec![];
+    let mut data_page_ordinal = 0;
     while let Some(compressed_page) = compressed_pages.next()? {
-        let spec = write_page(writer, offset, compressed_page)?;
+        let spec = write_page(
+            writer,
+            offset,
+            compressed_page,
+            crypto.map(|crypto| (crypto, data_page_ordinal)),
+        )?;
+        data_page_ordinal += is_data_page(&spec) as usize;
         offset += spec.bytes_written;
         specs.push(spec);
     }
@@ -45,6 +55,11 @@ where
 
     let column_chunk = build_column_chunk(&specs, descriptor)?;
 
+    // The metadata of encrypted column chunks is only written in the footer.
+    if crypto.is_some() {
+        return Ok((column_chunk, specs, bytes_written));
+    }
+
     // write metadata
     let mut protocol = TCompactOutputProtocol::new(writer);
     bytes_written += column_chunk
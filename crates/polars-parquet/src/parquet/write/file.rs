@@ -3,28 +3,57 @@ use std::io::Write;
 use parquet_format_safe::thrift::protocol::TCompactOutputProtocol;
 use parquet_format_safe::RowGroup;
 
+use super::bloom_filter::write_bloom_filter;
 use super::indexes::{write_column_index, write_offset_index};
 use super::page::PageWriteSpec;
 use super::row_group::write_row_group;
 use super::{RowGroupIterColumns, WriteOptions};
+use crate::parquet::encryption::{ColumnCrypto, FileEncryptionProperties, FileEncryptor};
 use crate::parquet::error::{ParquetError, ParquetResult};
 pub use crate::parquet::metadata::KeyValue;
 use crate::parquet::metadata::{SchemaDescriptor, ThriftFileMetadata};
 use crate::parquet::write::State;
-use crate::parquet::{FOOTER_SIZE, PARQUET_MAGIC};
+use crate::parquet::{FOOTER_SIZE, PARQUET_MAGIC, PARQUET_MAGIC_ENCRYPTED_FOOTER};
 
 pub(super) fn start_file<W: Write>(writer: &mut W) -> ParquetResult<u64> {
-    writer.write_all(&PARQUET_MAGIC)?;
-    Ok(PARQUET_MAGIC.len() as u64)
+    start_file_with_magic(writer, &PARQUET_MAGIC)
+}
+
+fn start_file_with_magic<W: Write>(writer: &mut W, magic: &[u8; 4]) -> ParquetResult<u64> {
+    writer.write_all(magic)?;
+    Ok(magic.len() as u64)
 }
 
 pub(super) fn end_file<W: Write>(
+    writer: &mut W,
+    metadata: &ThriftFileMetadata,
+) -> ParquetResult<u64> {
+    end_file_with_encryption(writer, metadata, None)
+}
+
+/// Writes the footer, which is encrypted with `encryptor` if it is given.
+fn end_file_with_encryption<W: Write>(
     mut writer: &mut W,
     metadata: &ThriftFileMetadata,
+    encryptor: Option<&FileEncryptor>,
 ) -> ParquetResult<u64> {
     // Write metadata
-    let mut protocol = TCompactOutputProtocol::new(&mut writer);
-    let metadata_len = metadata.write_to_out_protocol(&mut protocol)? as i32;
+    let (metadata_len, magic) = match encryptor {
+        None => {
+            let mut protocol = TCompactOutputProtocol::new(&mut writer);
+            let metadata_len = metadata.write_to_out_protocol(&mut protocol)? as i32;
+            (metadata_len, PARQUET_MAGIC)
+        },
+        Some(encryptor) => {
+            let footer = encryptor.encrypt_footer(metadata)?;
+            writer.write_all(&footer)?;
+            let metadata_len = footer
+                .len()
+                .try_into()
+                .map_err(|_| ParquetError::oos("The footer can only contain i32::MAX bytes"))?;
+            (metadata_len, PARQUET_MAGIC_ENCRYPTED_FOOTER)
+        },
+    };
 
     // Write footer
     let metadata_bytes = metadata_len.to_le_bytes();
@@ -33,7 +62,7 @@ pub(super) fn end_file<W: Write>(
         footer_buffer[i] = metadata_bytes[i];
     });
 
-    (&mut footer_buffer[4..]).write_all(&PARQUET_MAGIC)?;
+    (&mut footer_buffer[4..]).write_all(&magic)?;
     writer.write_all(&footer_buffer)?;
     writer.flush()?;
     Ok(metadata_len as u64 + FOOTER_SIZE)
@@ -64,6 +93,11 @@ pub struct FileWriter<W: Write> {
     offset: u64,
     row_groups: Vec<RowGroup>,
     page_specs: Vec<Vec<Vec<PageWriteSpec>>>,
+    /// The bitsets of the bloom filters of the columns, per row group
+    bloom_filters: Vec<Vec<Option<Vec<u8>>>>,
+    encryptor: Option<FileEncryptor>,
+    /// The keys of the encrypted columns, per row group
+    column_crypto: Vec<Vec<Option<ColumnCrypto>>>,
     /// Used to store the current state for writing the file
     state: State,
     // when the file is written, metadata becomes available
@@ -123,11 +157,23 @@ impl<W: Write> FileWriter<W> {
             offset: 0,
             row_groups: vec![],
             page_specs: vec![],
+            bloom_filters: vec![],
+            encryptor: None,
+            column_crypto: vec![],
             state: State::Initialised,
             metadata: None,
         }
     }
 
+    /// Encrypt the file with [Parquet modular encryption](crate::parquet::encryption). The
+    /// footer is encrypted.
+    ///
+    /// The page indexes and bloom filters of encrypted columns are not written.
+    pub fn with_encryption(mut self, properties: &FileEncryptionProperties) -> ParquetResult<Self> {
+        self.encryptor = Some(FileEncryptor::try_new(properties, &self.schema)?);
+        Ok(self)
+    }
+
     /// Writes the header of the file.
     ///
     /// This is automatically called by [`Self::write`] if not called following [`Self::new`].
@@ -136,7 +182,11 @@ impl<W: Write> FileWriter<W> {
     /// Returns an error if data has been written to the file.
     fn start(&mut self) -> ParquetResult<()> {
         if self.offset == 0 {
-            self.offset = start_file(&mut self.writer)?;
+            let magic = match self.encryptor {
+                None => PARQUET_MAGIC,
+                Some(_) => PARQUET_MAGIC_ENCRYPTED_FOOTER,
+            };
+            self.offset = start_file_with_magic(&mut self.writer, &magic)?;
             self.state = State::Started;
             Ok(())
         } else {
@@ -150,6 +200,22 @@ impl<W: Write> FileWriter<W> {
     ///
     /// This call is IO-bounded
     pub fn write<E>(&mut self, row_group: RowGroupIterColumns<'_, E>) -> ParquetResult<()>
+    where
+        ParquetError: From<E>,
+        E: std::error::Error,
+    {
+        self.write_with_bloom_filters(row_group, vec![])
+    }
+
+    /// Writes a row group to the file, with the bitsets of the split block bloom filters of its
+    /// columns. The bloom filters are written before the footer.
+    ///
+    /// This call is IO-bounded
+    pub fn write_with_bloom_filters<E>(
+        &mut self,
+        row_group: RowGroupIterColumns<'_, E>,
+        mut bloom_filters: Vec<Option<Vec<u8>>>,
+    ) -> ParquetResult<()>
     where
         ParquetError: From<E>,
         E: std::error::Error,
@@ -158,19 +224,52 @@ impl<W: Write> FileWriter<W> {
             self.start()?;
         }
         let ordinal = self.row_groups.len();
+
+        let column_crypto = match &self.encryptor {
+            None => vec![],
+            Some(encryptor) => self
+                .schema
+                .columns()
+                .iter()
+                .enumerate()
+                .map(|(i, column)| encryptor.column_crypto(&column.path_in_schema[0], ordinal, i))
+                .collect::<ParquetResult<Vec<_>>>()?,
+        };
+        // The bloom filters of encrypted columns would leak their values.
+        bloom_filters
+            .iter_mut()
+            .zip(&column_crypto)
+            .filter(|(_, crypto)| crypto.is_some())
+            .for_each(|(bitset, _)| *bitset = None);
+
         let (group, specs, size) = write_row_group(
             &mut self.writer,
             self.offset,
             self.schema.columns(),
             row_group,
             ordinal,
+            &column_crypto,
         )?;
         self.offset += size;
         self.row_groups.push(group);
         self.page_specs.push(specs);
+        self.bloom_filters.push(bloom_filters);
+        self.column_crypto.push(column_crypto);
         Ok(())
     }
 
+    /// Whether the `column` of the row group with `ordinal` is encrypted.
+    fn is_encrypted(
+        column_crypto: &[Vec<Option<ColumnCrypto>>],
+        ordinal: usize,
+        column: usize,
+    ) -> bool {
+        column_crypto
+            .get(ordinal)
+            .and_then(|columns| columns.get(column))
+            .is_some_and(Option::is_some)
+    }
+
     /// Writes the footer of the parquet file. Returns the total size of the file and the
     /// underlying writer.
     pub fn end(&mut self, key_value_metadata: Option<Vec<KeyValue>>) -> ParquetResult<u64> {
@@ -186,22 +285,51 @@ impl<W: Write> FileWriter<W> {
         // compute file stats
         let num_rows = self.row_groups.iter().map(|group| group.num_rows).sum();
 
+        // write bloom filters
+        self.row_groups
+            .iter_mut()
+            .zip(std::mem::take(&mut self.bloom_filters))
+            .try_for_each(|(group, bloom_filters)| {
+                group
+                    .columns
+                    .iter_mut()
+                    .zip(bloom_filters)
+                    .try_for_each(|(column, bitset)| {
+                        if let (Some(bitset), Some(metadata)) = (bitset, &mut column.meta_data) {
+                            metadata.bloom_filter_offset = Some(self.offset as i64);
+                            self.offset += write_bloom_filter(&mut self.writer, &bitset)?;
+                        }
+                        ParquetResult::Ok(())
+                    })
+            })?;
+
+        // The page indexes of encrypted columns would leak their statistics, so they are not
+        // written.
+        let column_crypto = &self.column_crypto;
+
         if self.options.write_statistics {
             // write column indexes (require page statistics)
             self.row_groups
                 .iter_mut()
                 .zip(self.page_specs.iter())
-                .try_for_each(|(group, pages)| {
-                    group.columns.iter_mut().zip(pages.iter()).try_for_each(
-                        |(column, pages)| {
+                .enumerate()
+                .try_for_each(|(ordinal, (group, pages))| {
+                    group
+                        .columns
+                        .iter_mut()
+                        .zip(pages.iter())
+                        .enumerate()
+                        .try_for_each(|(i, (column, pages))| {
+                            if Self::is_encrypted(column_crypto, ordinal, i) {
+                                return Ok(());
+                            }
                             let offset = self.offset;
                             column.column_index_offset = Some(offset as i64);
                             self.offset += write_column_index(&mut self.writer, pages)?;
                             let length = self.offset - offset;
                             column.column_index_length = Some(length as i32);
                             ParquetResult::Ok(())
-                        },
-                    )?;
+                        })?;
                     ParquetResult::Ok(())
                 })?;
         };
@@ -210,12 +338,17 @@ impl<W: Write> FileWriter<W> {
         self.row_groups
             .iter_mut()
             .zip(self.page_specs.iter())
-            .try_for_each(|(group, pages)| {
+            .enumerate()
+            .try_for_each(|(ordinal, (group, pages))| {
                 group
                     .columns
                     .iter_mut()
                     .zip(pages.iter())
-                    .try_for_each(|(column, pages)| {
+                    .enumerate()
+                    .try_for_each(|(i, (column, pages))| {
+                        if Self::is_encrypted(column_crypto, ordinal, i) {
+                            return Ok(());
+                        }
                         let offset = self.offset;
                         column.offset_index_offset = Some(offset as i64);
                         self.offset += write_offset_index(&mut self.writer, pages)?;
@@ -225,6 +358,22 @@ impl<W: Write> FileWriter<W> {
                 ParquetResult::Ok(())
             })?;
 
+        if let Some(encryptor) = &self.encryptor {
+            self.row_groups
+                .iter_mut()
+                .zip(&self.column_crypto)
+                .try_for_each(|(group, column_crypto)| {
+                    group
+                        .columns
+                        .iter_mut()
+                        .zip(column_crypto)
+                        .filter_map(|(column, crypto)| Some((column, crypto.as_ref()?)))
+                        .try_for_each(|(column, crypto)| {
+                            encryptor.encrypt_column_chunk(column, crypto)
+                        })
+                })?;
+        }
+
         let metadata = ThriftFileMetadata::new(
             self.options.version.into(),
             self.schema.clone().into_thrift(),
@@ -237,7 +386,7 @@ impl<W: Write> FileWriter<W> {
             None,
         );
 
-        let len = end_file(&mut self.writer, &metadata)?;
+        let len = end_file_with_encryption(&mut self.writer, &metadata, self.encryptor.as_ref())?;
         self.state = State::Finished;
         self.metadata = Some(metadata);
         Ok(self.offset + len)
@@ -248,6 +397,11 @@ impl<W: Write> FileWriter<W> {
         self.writer
     }
 
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
     /// Returns the underlying writer and [`ThriftFileMetadata`]
     /// # Panics
     /// This function panics if [`Self::end`] has not yet been called
@@ -8,6 +8,7 @@ use parquet_format_safe::thrift::protocol::TCompactOutputStreamProtocol;
 use parquet_format_safe::{DictionaryPageHeader, Encoding, PageType};
 
 use crate::parquet::compression::Compression;
+use crate::parquet::encryption::ColumnCrypto;
 use crate::parquet::error::{ParquetError, ParquetResult};
 use crate::parquet::page::{
     CompressedDataPage, CompressedDictPage, CompressedPage, DataPageHeader, ParquetPageHeader,
@@ -50,32 +51,57 @@ pub struct PageWriteSpec {
     pub statistics: Option<Statistics>,
 }
 
+/// Writes a page, which is encrypted with `crypto` if it is given. The data pages of an encrypted
+/// column chunk are numbered by their ordinal, which is part of their AAD.
 pub fn write_page<W: Write>(
     writer: &mut W,
     offset: u64,
     compressed_page: &CompressedPage,
+    crypto: Option<(&ColumnCrypto, usize)>,
 ) -> ParquetResult<PageWriteSpec> {
     let num_values = compressed_page.num_values();
     let num_rows = compressed_page
         .num_rows()
         .expect("We should have num_rows when we are writing");
 
-    let header = match &compressed_page {
+    let mut header = match &compressed_page {
         CompressedPage::Data(compressed_page) => assemble_data_page_header(compressed_page),
         CompressedPage::Dict(compressed_page) => assemble_dict_page_header(compressed_page),
     }?;
 
-    let header_size = write_page_header(writer, &header)?;
-    let mut bytes_written = header_size;
+    let buffer: &[u8] = match &compressed_page {
+        CompressedPage::Data(compressed_page) => &compressed_page.buffer,
+        CompressedPage::Dict(compressed_page) => &compressed_page.buffer,
+    };
 
-    bytes_written += match &compressed_page {
-        CompressedPage::Data(compressed_page) => {
-            writer.write_all(&compressed_page.buffer)?;
-            compressed_page.buffer.len() as u64
+    let (header_size, bytes_written) = match crypto {
+        None => {
+            let header_size = write_page_header(writer, &header)?;
+            writer.write_all(buffer)?;
+            (header_size, header_size + buffer.len() as u64)
         },
-        CompressedPage::Dict(compressed_page) => {
-            writer.write_all(&compressed_page.buffer)?;
-            compressed_page.buffer.len() as u64
+        Some((crypto, data_page_ordinal)) => {
+            let page_ordinal = match &compressed_page {
+                CompressedPage::Data(_) => Some(data_page_ordinal),
+                CompressedPage::Dict(_) => None,
+            };
+            // SPEC: the size of an encrypted page is the size of the encrypted module.
+            let page = crypto.encrypt_page(buffer, page_ordinal)?;
+            header.compressed_page_size = page.len().try_into().map_err(|_| {
+                ParquetError::oos(format!(
+                    "A page can only contain i32::MAX encrypted bytes. This one contains {}",
+                    page.len()
+                ))
+            })?;
+
+            let mut header_bytes = vec![];
+            write_page_header(&mut header_bytes, &header)?;
+            let header_bytes = crypto.encrypt_page_header(&header_bytes, page_ordinal)?;
+
+            writer.write_all(&header_bytes)?;
+            writer.write_all(&page)?;
+            let header_size = header_bytes.len() as u64;
+            (header_size, header_size + page.len() as u64)
         },
     };
 
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use polars_core::prelude::*;
+use polars_core::POOL;
+use polars_plan::prelude::expr_ir::ExprIR;
+use polars_plan::prelude::*;
+
+use super::ProjectionOperator;
+use crate::operators::{DataChunk, Operator, OperatorResult, PExecutionContext};
+use crate::pipeline::determine_chunk_size;
+
+/// The expressions of a projection in which every expression is exploded, with the explode
+/// removed. Such a projection can be streamed as an [`ExplodeOperator`].
+pub fn explode_projection(exprs: &[ExprIR], expr_arena: &Arena<AExpr>) -> Option<Vec<ExprIR>> {
+    exprs
+        .iter()
+        .map(|e| match expr_arena.get(e.node()) {
+            AExpr::Explode(input) if is_streamable(*input, expr_arena, Context::Default) => {
+                Some(ExprIR::new(*input, e.output_name_inner().clone()))
+            },
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .filter(|exprs| !exprs.is_empty())
+}
+
+/// The number of rows every row of `s` explodes into.
+fn exploded_lengths(s: &Series) -> Vec<usize> {
+    match s.dtype() {
+        // empty and null lists explode into a single null
+        DataType::List(_) => s
+            .list()
+            .unwrap()
+            .downcast_iter()
+            .flat_map(|arr| arr.offsets().lengths())
+            .map(|len| std::cmp::max(len, 1))
+            .collect(),
+        #[cfg(feature = "dtype-array")]
+        DataType::Array(_, width) => vec![std::cmp::max(*width, 1); s.len()],
+        _ => vec![1; s.len()],
+    }
+}
+
+/// The chunk that is being exploded.
+#[derive(Clone)]
+struct Pending {
+    df: DataFrame,
+    lengths: Vec<usize>,
+    // the first row that isn't exploded yet
+    offset: usize,
+}
+
+/// Explodes the list columns of the chunks.
+///
+/// A row may explode into many rows, so the rows of a chunk are exploded in slices that each
+/// explode into about the chunk size of the pipeline. Those are emitted one by one, so only a
+/// single slice of a chunk is exploded in memory at a time. The slices keep the index of their
+/// chunk and are emitted in order, the sinks that maintain the order sort the chunks stably.
+#[derive(Clone)]
+pub(crate) struct ExplodeOperator {
+    columns: Arc<[PlSmallStr]>,
+    // the projection that computes the exploded columns, if every column of a `select` is exploded
+    projection: Option<ProjectionOperator>,
+    n_threads: usize,
+    pending: Option<Pending>,
+}
+
+impl ExplodeOperator {
+    pub(crate) fn new(columns: Arc<[PlSmallStr]>, projection: Option<ProjectionOperator>) -> Self {
+        Self {
+            columns,
+            projection,
+            n_threads: POOL.current_num_threads(),
+            pending: None,
+        }
+    }
+
+    fn explode(&self, df: &DataFrame) -> PolarsResult<DataFrame> {
+        if self.projection.is_some() {
+            // every column is exploded on its own, as in `select`
+            let columns = df
+                .get_columns()
+                .iter()
+                .map(|s| s.explode())
+                .collect::<PolarsResult<Vec<_>>>()?;
+            DataFrame::new(columns)
+        } else {
+            df.explode(self.columns.iter().cloned())
+        }
+    }
+
+    fn start(&mut self, context: &PExecutionContext, chunk: &DataChunk) -> PolarsResult<()> {
+        let df = match &mut self.projection {
+            Some(projection) => match projection.execute(context, chunk)? {
+                OperatorResult::Finished(chunk) => chunk.data,
+                _ => unreachable!(),
+            },
+            None => chunk.data.clone(),
+        };
+        let lengths = match self.columns.first() {
+            Some(name) => exploded_lengths(df.column(name)?),
+            None => vec![1; df.height()],
+        };
+        self.pending = Some(Pending {
+            df,
+            lengths,
+            offset: 0,
+        });
+        Ok(())
+    }
+}
+
+impl Operator for ExplodeOperator {
+    fn execute(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<OperatorResult> {
+        if self.pending.is_none() {
+            self.start(context, chunk)?;
+        }
+        let pending = self.pending.as_mut().unwrap();
+        let chunk_size = determine_chunk_size(pending.df.width(), self.n_threads)?;
+
+        // take rows until they explode into a chunk, but at least one row
+        let height = pending.lengths.len();
+        let offset = pending.offset;
+        let mut end = offset;
+        let mut rows = 0;
+        while end < height && (rows < chunk_size || end == offset) {
+            rows += pending.lengths[end];
+            end += 1;
+        }
+        pending.offset = end;
+
+        let df = if offset == 0 && end == height {
+            std::mem::take(&mut pending.df)
+        } else {
+            pending.df.slice(offset as i64, end - offset)
+        };
+        let finished = end >= height;
+        if finished {
+            self.pending = None;
+        }
+
+        let output = chunk.with_data(self.explode(&df)?);
+        if finished {
+            Ok(OperatorResult::Finished(output))
+        } else {
+            Ok(OperatorResult::HaveMoreOutPut(output))
+        }
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Operator> {
+        Box::new(self.clone())
+    }
+
+    fn fmt(&self) -> &str {
+        "explode"
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use polars_core::error::PolarsResult;
-use polars_core::prelude::polars_err;
+use polars_core::prelude::{polars_err, BooleanChunked};
 
 use crate::expressions::PhysicalPipedExpr;
 use crate::operators::{DataChunk, Operator, OperatorResult, PExecutionContext};
@@ -11,21 +11,32 @@ pub(crate) struct FilterOperator {
     pub(crate) predicate: Arc<dyn PhysicalPipedExpr>,
 }
 
-impl Operator for FilterOperator {
-    fn execute(
-        &mut self,
+impl FilterOperator {
+    pub(crate) fn mask(
+        &self,
         context: &PExecutionContext,
         chunk: &DataChunk,
-    ) -> PolarsResult<OperatorResult> {
+    ) -> PolarsResult<BooleanChunked> {
         let s = self.predicate.evaluate(chunk, &context.execution_state)?;
         let mask = s.bool().map_err(|_| {
             polars_err!(
                 ComputeError: "filter predicate must be of type `Boolean`, got `{}`", s.dtype()
             )
         })?;
+        Ok(mask.clone())
+    }
+}
+
+impl Operator for FilterOperator {
+    fn execute(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<OperatorResult> {
+        let mask = self.mask(context, chunk)?;
         // the filter is sequential as they are already executed on different threads
         // we don't want to increase contention and data copies
-        let df = chunk.data._filter_seq(mask)?;
+        let df = chunk.data._filter_seq(&mask)?;
 
         Ok(OperatorResult::Finished(chunk.with_data(df)))
     }
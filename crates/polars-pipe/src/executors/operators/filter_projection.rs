@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use polars_core::error::PolarsResult;
+use polars_core::schema::SchemaRef;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::{FilterOperator, ProjectionOperator};
+use crate::operators::{DataChunk, Operator, OperatorResult, PExecutionContext};
+
+/// A filter of which the output is projected, in a single operator.
+///
+/// Only the columns that the projection reads are filtered, so the filtered chunk of the
+/// columns that are projected away is never materialized.
+#[derive(Clone)]
+pub(crate) struct FilterProjectionOperator {
+    filter: FilterOperator,
+    // the columns of the input that the projection reads
+    columns: Arc<[PlSmallStr]>,
+    input_schema: SchemaRef,
+    // `None` if the projection only selects `columns`
+    projection: Option<ProjectionOperator>,
+}
+
+impl FilterProjectionOperator {
+    pub(crate) fn new(
+        filter: FilterOperator,
+        columns: Arc<[PlSmallStr]>,
+        input_schema: SchemaRef,
+        projection: Option<ProjectionOperator>,
+    ) -> Self {
+        Self {
+            filter,
+            columns,
+            input_schema,
+            projection,
+        }
+    }
+}
+
+impl Operator for FilterProjectionOperator {
+    fn execute(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<OperatorResult> {
+        let mask = self.filter.mask(context, chunk)?;
+        let check_duplicates = false;
+        let df = chunk.data._select_with_schema_impl(
+            self.columns.as_ref(),
+            &self.input_schema,
+            check_duplicates,
+        )?;
+        // sequential, see `FilterOperator`
+        let chunk = chunk.with_data(df._filter_seq(&mask)?);
+
+        match &mut self.projection {
+            Some(projection) => projection.execute(context, &chunk),
+            None => Ok(OperatorResult::Finished(chunk)),
+        }
+    }
+    fn split(&self, _thread_no: usize) -> Box<dyn Operator> {
+        Box::new(self.clone())
+    }
+    fn fmt(&self) -> &str {
+        "filter_projection"
+    }
+}
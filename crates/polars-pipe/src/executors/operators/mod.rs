@@ -1,13 +1,15 @@
+pub(crate) mod explode;
 mod filter;
+mod filter_projection;
 mod function;
 mod pass;
 mod placeholder;
 mod projection;
-mod reproject;
 
+pub(crate) use explode::ExplodeOperator;
 pub(crate) use filter::*;
+pub(crate) use filter_projection::*;
 pub(crate) use function::*;
 pub(crate) use pass::Pass;
 pub(crate) use placeholder::PlaceHolder;
 pub(crate) use projection::*;
-pub(crate) use reproject::*;
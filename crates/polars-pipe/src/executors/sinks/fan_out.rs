@@ -0,0 +1,134 @@
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+use polars_core::prelude::*;
+
+use crate::operators::{
+    DataChunk, FinalizedSink, Operator, OperatorResult, PExecutionContext, Sink, SinkResult,
+};
+use crate::pipeline::consume_source;
+
+/// The operators and the sink of a query that is computed from the output of another query.
+pub(crate) struct FanOutBranch {
+    pub(crate) operators: Vec<Box<dyn Operator>>,
+    pub(crate) sink: Box<dyn Sink>,
+}
+
+impl FanOutBranch {
+    fn split(&self, thread_no: usize) -> Self {
+        Self {
+            operators: self
+                .operators
+                .iter()
+                .map(|op| op.split(thread_no))
+                .collect(),
+            sink: self.sink.split(thread_no),
+        }
+    }
+
+    /// Push a chunk through the operators into the sink, like the pipelines do on a single
+    /// thread.
+    fn push(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        let mut in_process = vec![(0, chunk)];
+        while let Some((op_i, chunk)) = in_process.pop() {
+            match self.operators.get_mut(op_i) {
+                None => {
+                    if let SinkResult::Finished = self.sink.sink(context, chunk)? {
+                        return Ok(SinkResult::Finished);
+                    }
+                },
+                Some(op) => match op.execute(context, &chunk)? {
+                    OperatorResult::Finished(out) => in_process.push((op_i + 1, out)),
+                    OperatorResult::HaveMoreOutPut(out) => {
+                        in_process.push((op_i, chunk));
+                        in_process.push((op_i + 1, out));
+                    },
+                    OperatorResult::NeedsNewData => {},
+                },
+            }
+        }
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+}
+
+/// Pushes every chunk through the branches of the queries that are computed from the same input,
+/// so that the input is computed only once. The results of the branches are stored in
+/// `outputs`, in the order of the branches.
+pub(crate) struct FanOutSink {
+    branches: Vec<FanOutBranch>,
+    // the branches that don't want more input
+    finished: Vec<bool>,
+    outputs: Arc<Mutex<Vec<DataFrame>>>,
+}
+
+impl FanOutSink {
+    pub(crate) fn new(branches: Vec<FanOutBranch>, outputs: Arc<Mutex<Vec<DataFrame>>>) -> Self {
+        let finished = vec![false; branches.len()];
+        Self {
+            branches,
+            finished,
+            outputs,
+        }
+    }
+}
+
+impl Sink for FanOutSink {
+    fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        for (branch, finished) in self.branches.iter_mut().zip(self.finished.iter_mut()) {
+            if !*finished {
+                // The columns are reference counted, so this doesn't copy the data.
+                *finished = matches!(branch.push(context, chunk.clone())?, SinkResult::Finished);
+            }
+        }
+        if self.finished.iter().all(|finished| *finished) {
+            Ok(SinkResult::Finished)
+        } else {
+            Ok(SinkResult::CanHaveMoreInput)
+        }
+    }
+
+    fn combine(&mut self, other: &mut dyn Sink) {
+        let other = other.as_any().downcast_mut::<Self>().unwrap();
+        for (branch, other) in self.branches.iter_mut().zip(other.branches.iter_mut()) {
+            branch.sink.combine(other.sink.as_mut())
+        }
+    }
+
+    fn split(&self, thread_no: usize) -> Box<dyn Sink> {
+        Box::new(Self {
+            branches: self
+                .branches
+                .iter()
+                .map(|branch| branch.split(thread_no))
+                .collect(),
+            finished: self.finished.clone(),
+            outputs: self.outputs.clone(),
+        })
+    }
+
+    fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        let mut outputs = Vec::with_capacity(self.branches.len());
+        for branch in &mut self.branches {
+            let out = match branch.sink.finalize(context)? {
+                FinalizedSink::Finished(df) => df,
+                FinalizedSink::Source(mut src) => consume_source(src.as_mut(), context)?,
+                FinalizedSink::Operator => polars_bail!(
+                    ComputeError: "the '{}' sink of a fan-out sink must finish its branch",
+                    branch.sink.fmt()
+                ),
+            };
+            outputs.push(out);
+        }
+        *self.outputs.lock().unwrap() = outputs;
+        // return a dummy dataframe;
+        Ok(FinalizedSink::Finished(Default::default()))
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        "fan_out_sink"
+    }
+}
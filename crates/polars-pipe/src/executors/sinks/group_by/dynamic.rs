@@ -0,0 +1,576 @@
+use std::any::Any;
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_plan::prelude::expr_ir::ExprIR;
+use polars_plan::prelude::GroupbyOptions;
+use polars_time::prelude::*;
+
+use super::aggregates::AggregateFn;
+use crate::executors::sinks::group_by::aggregates::AggregateFunction;
+use crate::executors::sinks::group_by::utils::{finalize_ordered_groups, finish_groups};
+use crate::expressions::PhysicalPipedExpr;
+use crate::operators::{
+    DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult, Source, SourceResult,
+};
+use crate::pipeline::morsels_per_sink;
+
+/// Checks if a `group_by_dynamic` or `rolling` group_by can be executed by the
+/// [`DynamicGroupbySink`].
+///
+/// The windows found in a chunk must be the windows of the whole input, restricted to that
+/// chunk. For `group_by_dynamic` this holds if the windows start at the window boundaries with
+/// an offset that is not positive and don't overlap, as a chunk doesn't find the windows that
+/// start before its first row. For `rolling` it holds if every row is part of its own window.
+pub fn can_stream_temporal_group_by(keys: &[ExprIR], options: &GroupbyOptions) -> bool {
+    if !keys.is_empty() {
+        return false;
+    }
+    match (&options.dynamic, &options.rolling) {
+        (Some(options), None) => {
+            options.start_by == StartBy::WindowBound
+                && (options.offset.negative() || options.offset.is_zero())
+                && options.period <= options.every
+        },
+        (None, Some(options)) => {
+            let closed = options.closed_window;
+            (options.offset == -options.period
+                && matches!(closed, ClosedWindow::Right | ClosedWindow::Both))
+                || (options.offset.is_zero()
+                    && matches!(closed, ClosedWindow::Left | ClosedWindow::Both))
+        },
+        _ => false,
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum TemporalWindows {
+    Dynamic(DynamicGroupOptions),
+    Rolling(RollingGroupOptions),
+}
+
+impl TemporalWindows {
+    pub(crate) fn new(options: &GroupbyOptions) -> Option<Self> {
+        match (&options.dynamic, &options.rolling) {
+            (Some(options), None) => {
+                // The lower boundaries identify the windows.
+                let mut options = options.clone();
+                options.include_boundaries = true;
+                Some(Self::Dynamic(options))
+            },
+            (None, Some(options)) => Some(Self::Rolling(options.clone())),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Dynamic(_) => "group_by_dynamic",
+            Self::Rolling(_) => "rolling",
+        }
+    }
+
+    fn index_column(&self) -> &PlSmallStr {
+        match self {
+            Self::Dynamic(options) => &options.index_column,
+            Self::Rolling(options) => &options.index_column,
+        }
+    }
+
+    /// Returns the time key, the boundaries of the windows and the windows.
+    fn groups(&self, df: &DataFrame) -> PolarsResult<(Series, Vec<Series>, GroupsSlice)> {
+        let (time_key, keys, groups) = match self {
+            Self::Dynamic(options) => df.group_by_dynamic(vec![], options)?,
+            Self::Rolling(options) => df.rolling(vec![], options)?,
+        };
+        let groups = match groups {
+            GroupsProxy::Slice { groups, .. } => groups,
+            // empty input
+            GroupsProxy::Idx(_) => vec![],
+        };
+        Ok((time_key, keys, groups))
+    }
+
+    /// Whether the `rolling` windows start at their row, instead of ending at it.
+    fn forward(&self) -> bool {
+        matches!(self, Self::Rolling(options) if options.offset.is_zero())
+    }
+}
+
+/// The aggregations of the windows.
+#[derive(Clone)]
+struct Aggregations {
+    aggregation_columns: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+    agg_fns: Arc<[AggregateFunction]>,
+    // the output fields of the aggregations
+    agg_schema: SchemaRef,
+}
+
+impl Aggregations {
+    fn evaluate(&self, chunk: &DataChunk, context: &PExecutionContext) -> PolarsResult<Vec<Series>> {
+        self.aggregation_columns
+            .iter()
+            .map(|e| {
+                let s = e.evaluate(chunk, &context.execution_state)?;
+                Ok(s.to_physical_repr().rechunk())
+            })
+            .collect()
+    }
+
+    /// Start the aggregation of the rows of the window `[first, len]`.
+    fn pre_aggregate(
+        &self,
+        chunk_index: IdxSize,
+        values: &[Series],
+        [first, len]: [IdxSize; 2],
+    ) -> Vec<AggregateFunction> {
+        self.agg_fns
+            .iter()
+            .zip(values)
+            .map(|(agg_fn, values)| {
+                let mut agg_fn = agg_fn.split();
+                agg_fn.pre_agg_ordered(chunk_index, first, len, values);
+                agg_fn
+            })
+            .collect()
+    }
+
+    /// The windows at `idx` as output rows, with the boundaries and the time key in
+    /// `key_columns`.
+    fn aggregate(
+        &self,
+        chunk_index: IdxSize,
+        values: &[Series],
+        key_columns: &[Series],
+        groups: &GroupsSlice,
+        idx: &[IdxSize],
+    ) -> PolarsResult<DataFrame> {
+        let keys = key_columns
+            .iter()
+            .map(|s| s.take_slice(idx))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let aggs = idx
+            .iter()
+            .map(|i| self.pre_aggregate(chunk_index, values, groups[*i as usize]))
+            .collect();
+        Ok(finish_groups(keys, aggs, &self.agg_fns, &self.agg_schema))
+    }
+}
+
+/// The partial aggregates of a `group_by_dynamic` window that may continue in other chunks.
+struct OpenWindow {
+    // the boundaries and the time key of the window, as single rows
+    keys: Vec<Series>,
+    aggs: Vec<AggregateFunction>,
+}
+
+impl OpenWindow {
+    fn combine(&mut self, other: &Self) {
+        for (agg, other) in self.aggs.iter_mut().zip(&other.aggs) {
+            agg.combine(other.as_any())
+        }
+    }
+}
+
+// The windows of a chunk that may continue in other chunks.
+enum IncompleteWindows {
+    // `group_by_dynamic`: the windows that contain the first or the last row by their lower
+    // boundary, and whether they contain the last row
+    Dynamic(Vec<(i64, bool, OpenWindow)>),
+    // `rolling`: the rows that the windows of other chunks need, of which the leading `n_head`
+    // and the trailing `n_tail` rows have an incomplete window
+    Rolling {
+        df: DataFrame,
+        n_head: usize,
+        n_tail: usize,
+    },
+}
+
+struct ChunkWindows {
+    chunk_index: IdxSize,
+    // the first and the last value of the index column of the chunk
+    bounds: (i64, i64),
+    incomplete: IncompleteWindows,
+    // the aggregated windows that are complete within the chunk
+    complete: Option<DataFrame>,
+}
+
+/// Executes `group_by_dynamic` and `rolling` on an input that is sorted by the index column.
+///
+/// As the index column is sorted, a window can only continue in another chunk if it contains
+/// the first or the last row of a chunk. All other windows are aggregated as soon as their
+/// chunk arrives. The `group_by_dynamic` windows that may continue are partially aggregated,
+/// the `rolling` windows only keep the rows within a window of the chunk boundaries. The
+/// [`DynamicGroupbySource`] merges the chunks in order and emits the windows as soon as the
+/// chunks behind them are merged, so only the open windows are held.
+pub(crate) struct DynamicGroupbySink {
+    windows: TemporalWindows,
+    aggregations: Aggregations,
+    output_schema: SchemaRef,
+    slice: Option<(i64, usize)>,
+    chunks: Vec<ChunkWindows>,
+}
+
+impl DynamicGroupbySink {
+    pub(crate) fn new(
+        windows: TemporalWindows,
+        aggregation_columns: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        agg_fns: Arc<[AggregateFunction]>,
+        output_schema: SchemaRef,
+        slice: Option<(i64, usize)>,
+    ) -> Self {
+        let agg_schema = output_schema
+            .iter_fields()
+            .skip(output_schema.len() - agg_fns.len())
+            .collect::<Schema>();
+        Self {
+            windows,
+            aggregations: Aggregations {
+                aggregation_columns,
+                agg_fns,
+                agg_schema: Arc::new(agg_schema),
+            },
+            output_schema,
+            slice,
+            chunks: vec![],
+        }
+    }
+}
+
+fn lower_boundaries(keys: &[Series]) -> PolarsResult<Int64Chunked> {
+    let lower = keys[0].to_physical_repr().cast(&DataType::Int64)?;
+    Ok(lower.i64()?.clone())
+}
+
+/// The rows `[0, head_end)` and `[tail_start, n)` of `df`.
+fn head_and_tail(df: &DataFrame, head_end: usize, tail_start: usize) -> PolarsResult<DataFrame> {
+    if head_end >= tail_start {
+        return Ok(df.clone());
+    }
+    let mut out = df.slice(0, head_end);
+    out.vstack_mut(&df.slice(tail_start as i64, df.height() - tail_start))?;
+    Ok(out)
+}
+
+impl Sink for DynamicGroupbySink {
+    fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        if chunk.is_empty() {
+            return Ok(SinkResult::CanHaveMoreInput);
+        }
+        let (time_key, mut key_columns, groups) = self.windows.groups(&chunk.data)?;
+        key_columns.push(time_key);
+        let values = self.aggregations.evaluate(&chunk, context)?;
+        let n = chunk.data.height() as IdxSize;
+
+        let time = chunk
+            .data
+            .column(self.windows.index_column())?
+            .to_physical_repr()
+            .cast(&DataType::Int64)?;
+        let time = time.i64()?;
+        let bounds = (time.get(0).unwrap(), time.get(n as usize - 1).unwrap());
+
+        // As the index column is sorted, a window that contains rows of other chunks contains
+        // the first or the last row of this chunk.
+        let mut complete_idx = vec![];
+        let incomplete = match &self.windows {
+            TemporalWindows::Dynamic(_) => {
+                let lower = lower_boundaries(&key_columns)?;
+                let mut open = vec![];
+                for (i, &[first, len]) in groups.iter().enumerate() {
+                    if len == 0 {
+                        continue;
+                    }
+                    let tail = first + len == n;
+                    if first == 0 || tail {
+                        let window = OpenWindow {
+                            keys: key_columns.iter().map(|s| s.slice(i as i64, 1)).collect(),
+                            aggs: self.aggregations.pre_aggregate(
+                                chunk.chunk_index,
+                                &values,
+                                [first, len],
+                            ),
+                        };
+                        open.push((lower.get(i).unwrap(), tail, window));
+                    } else {
+                        complete_idx.push(i as IdxSize);
+                    }
+                }
+                IncompleteWindows::Dynamic(open)
+            },
+            TemporalWindows::Rolling(_) => {
+                // Every row has a window, which contains the rows with the same time. The
+                // windows of the other chunks don't reach beyond the window of the first row
+                // (forward) or of the last row (backward).
+                let n_head = groups.iter().filter(|[first, _]| *first == 0).count();
+                let n_tail = groups.iter().filter(|[first, len]| first + len == n).count();
+                let n_tail = n_tail.min(n as usize - n_head);
+                let (head_end, tail_start) = if self.windows.forward() {
+                    (n_head.max(groups[0][1] as usize), n as usize - n_tail)
+                } else {
+                    (n_head, groups[n as usize - 1][0] as usize)
+                };
+                complete_idx.extend(n_head as IdxSize..n - n_tail as IdxSize);
+                let df = head_and_tail(&chunk.data, head_end, tail_start)?;
+                IncompleteWindows::Rolling { df, n_head, n_tail }
+            },
+        };
+
+        let complete = if complete_idx.is_empty() {
+            None
+        } else {
+            Some(self.aggregations.aggregate(
+                chunk.chunk_index,
+                &values,
+                &key_columns,
+                &groups,
+                &complete_idx,
+            )?)
+        };
+        self.chunks.push(ChunkWindows {
+            chunk_index: chunk.chunk_index,
+            bounds,
+            incomplete,
+            complete,
+        });
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, other: &mut dyn Sink) {
+        let other = other.as_any().downcast_mut::<Self>().unwrap();
+        self.chunks.append(&mut other.chunks);
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+        Box::new(Self {
+            windows: self.windows.clone(),
+            aggregations: self.aggregations.clone(),
+            output_schema: self.output_schema.clone(),
+            slice: self.slice,
+            chunks: vec![],
+        })
+    }
+
+    fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        let mut chunks = std::mem::take(&mut self.chunks);
+        chunks.sort_unstable_by_key(|windows| windows.chunk_index);
+        for windows in chunks.windows(2) {
+            polars_ensure!(
+                windows[0].bounds.1 <= windows[1].bounds.0,
+                InvalidOperation: "argument in operation '{}' is not sorted, please sort the 'expr/series/column' first",
+                self.windows.name()
+            );
+        }
+        if chunks.is_empty() {
+            return Ok(FinalizedSink::Finished(DataFrame::empty_with_schema(
+                &self.output_schema,
+            )));
+        }
+
+        let source = DynamicGroupbySource {
+            windows: self.windows.clone(),
+            aggregations: self.aggregations.clone(),
+            output_names: self.output_schema.iter_names_cloned().collect(),
+            chunks: chunks.into_iter().peekable(),
+            open: BTreeMap::new(),
+            carry: None,
+        };
+        finalize_ordered_groups(Box::new(source), &self.output_schema, self.slice, context)
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        "dynamic_group_by"
+    }
+}
+
+/// Merges the windows of the chunks in the order of the chunks, and emits the windows that are
+/// closed once a chunk is merged.
+///
+/// A `group_by_dynamic` window is closed if it doesn't contain the last row of the merged
+/// chunks. The backward `rolling` windows of a chunk are complete once the rows of the previous
+/// chunks they reach back to are merged, the forward windows once a chunk is merged whose first
+/// row is beyond them.
+struct DynamicGroupbySource {
+    windows: TemporalWindows,
+    aggregations: Aggregations,
+    output_names: Vec<PlSmallStr>,
+    chunks: std::iter::Peekable<std::vec::IntoIter<ChunkWindows>>,
+    // `group_by_dynamic`: the open windows by their lower boundary
+    open: BTreeMap<i64, OpenWindow>,
+    // `rolling`: the rows of the previous chunks that the windows of the next chunks need, of
+    // which the trailing rows have an incomplete window
+    carry: Option<(DataFrame, usize)>,
+}
+
+impl DynamicGroupbySource {
+    /// The output rows of `group_by_dynamic` windows.
+    fn finish_windows(&self, windows: BTreeMap<i64, OpenWindow>) -> PolarsResult<DataFrame> {
+        let mut keys: Option<Vec<Series>> = None;
+        let mut aggs = Vec::with_capacity(windows.len());
+        for window in windows.into_values() {
+            match &mut keys {
+                Some(keys) => {
+                    for (s, key) in keys.iter_mut().zip(&window.keys) {
+                        s.append(key)?;
+                    }
+                },
+                None => keys = Some(window.keys),
+            }
+            aggs.push(window.aggs);
+        }
+        let keys = keys.unwrap_or_default();
+        let agg = &self.aggregations;
+        Ok(finish_groups(keys, aggs, &agg.agg_fns, &agg.agg_schema))
+    }
+
+    fn merge_dynamic(
+        &mut self,
+        open: Vec<(i64, bool, OpenWindow)>,
+        complete: Option<DataFrame>,
+        last: bool,
+    ) -> PolarsResult<Vec<DataFrame>> {
+        let mut first_tail = None;
+        for (lower, tail, window) in open {
+            if tail {
+                first_tail = Some(first_tail.map_or(lower, |first: i64| first.min(lower)));
+            }
+            match self.open.entry(lower) {
+                Entry::Occupied(mut entry) => entry.get_mut().combine(&window),
+                Entry::Vacant(entry) => {
+                    entry.insert(window);
+                },
+            }
+        }
+        // The windows that contain the last row start after the closed windows.
+        let mut closed = match first_tail {
+            Some(lower) => self.open.split_off(&lower),
+            None => BTreeMap::new(),
+        };
+        std::mem::swap(&mut closed, &mut self.open);
+
+        let mut dfs = vec![];
+        if !closed.is_empty() {
+            dfs.push(self.finish_windows(closed)?);
+        }
+        dfs.extend(complete);
+        if last && !self.open.is_empty() {
+            let open = std::mem::take(&mut self.open);
+            dfs.push(self.finish_windows(open)?);
+        }
+        Ok(dfs)
+    }
+
+    fn merge_rolling(
+        &mut self,
+        context: &PExecutionContext,
+        df: DataFrame,
+        [n_head, n_tail]: [usize; 2],
+        complete: Option<DataFrame>,
+        last: bool,
+    ) -> PolarsResult<Vec<DataFrame>> {
+        let (n_carry, n_pending, mut rows) = match self.carry.take() {
+            Some((mut carry, n_pending)) => {
+                let n_carry = carry.height();
+                carry.vstack_mut(&df)?;
+                (n_carry, n_pending, carry)
+            },
+            None => (0, 0, df),
+        };
+        rows.as_single_chunk_par();
+        let chunk = DataChunk::new(0, rows);
+        let (time_key, mut key_columns, groups) = self.windows.groups(&chunk.data)?;
+        key_columns.push(time_key);
+        let values = self.aggregations.evaluate(&chunk, context)?;
+        let n = chunk.data.height();
+
+        // The windows end in the order of the rows, so the incomplete windows are complete up to
+        // the first one that reaches the last row. If that is a carried or a leading row, the
+        // chunk has no complete windows, as these would end after it.
+        let is_complete = |i: &usize| {
+            let [first, len] = groups[*i];
+            last || ((first + len) as usize) < n
+        };
+        let aggregate = |rows: Range<usize>| {
+            let idx = rows.map(|i| i as IdxSize).collect::<Vec<_>>();
+            self.aggregations
+                .aggregate(0, &values, &key_columns, &groups, &idx)
+        };
+        let leading = n_carry - n_pending..n_carry + n_head;
+        let mut pending_start = leading.clone().find(|i| !is_complete(i));
+        let mut dfs = vec![];
+        match pending_start {
+            Some(start) => {
+                debug_assert!(complete.is_none());
+                dfs.push(aggregate(leading.start..start)?);
+            },
+            None => {
+                dfs.push(aggregate(leading)?);
+                dfs.extend(complete);
+                let trailing = n - n_tail..n;
+                pending_start = trailing.clone().find(|i| !is_complete(i));
+                dfs.push(aggregate(trailing.start..pending_start.unwrap_or(n))?);
+            },
+        }
+
+        if !last {
+            // The incomplete windows are the trailing rows, and the backward windows of the
+            // next chunks reach back as far as the window of the last row.
+            let pending_start = pending_start.unwrap_or(n);
+            let start = if self.windows.forward() {
+                pending_start
+            } else {
+                pending_start.min(groups[n - 1][0] as usize)
+            };
+            let carry = chunk.data.slice(start as i64, n - start);
+            self.carry = Some((carry, n - pending_start));
+        }
+        Ok(dfs)
+    }
+}
+
+impl Source for DynamicGroupbySource {
+    fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        let mut chunks = vec![];
+        while chunks.len() < morsels_per_sink() {
+            let Some(windows) = self.chunks.next() else {
+                break;
+            };
+            let last = self.chunks.peek().is_none();
+            let dfs = match windows.incomplete {
+                IncompleteWindows::Dynamic(open) => {
+                    self.merge_dynamic(open, windows.complete, last)?
+                },
+                IncompleteWindows::Rolling { df, n_head, n_tail } => {
+                    let n = [n_head, n_tail];
+                    self.merge_rolling(context, df, n, windows.complete, last)?
+                },
+            };
+            let dfs = dfs.into_iter().filter(|df| !df.is_empty()).collect::<Vec<_>>();
+            if dfs.is_empty() {
+                continue;
+            }
+            // drops the boundaries if they were not requested
+            let mut df =
+                accumulate_dataframes_vertical_unchecked(dfs).select(self.output_names.clone())?;
+            df.as_single_chunk_par();
+            chunks.push(DataChunk::new(windows.chunk_index, df));
+        }
+        if chunks.is_empty() {
+            Ok(SourceResult::Finished)
+        } else {
+            Ok(SourceResult::GotMoreData(chunks))
+        }
+    }
+
+    fn fmt(&self) -> &str {
+        "dynamic_group_by_source"
+    }
+}
@@ -1,7 +1,7 @@
 use polars_core::config::verbose;
 
 use super::*;
-use crate::executors::sinks::memory::MemTracker;
+use crate::executors::sinks::memory::{MemTracker, MemoryAccountant};
 use crate::pipeline::{morsels_per_sink, FORCE_OOC};
 
 #[derive(Clone)]
@@ -18,24 +18,6 @@ pub(super) struct OocState {
     to_disk_threshold: f64,
 }
 
-impl Default for OocState {
-    fn default() -> Self {
-        let to_disk_threshold = if std::env::var(FORCE_OOC).is_ok() {
-            1.0
-        } else {
-            TO_DISK_THRESHOLD
-        };
-
-        Self {
-            mem_track: MemTracker::new(morsels_per_sink()),
-            ooc: false,
-            io_thread: Default::default(),
-            count: 0,
-            to_disk_threshold,
-        }
-    }
-}
-
 // If this is reached we early merge the overflow buckets
 // to free up memory
 const EARLY_MERGE_THRESHOLD: f64 = 0.5;
@@ -50,6 +32,22 @@ pub(super) enum SpillAction {
 }
 
 impl OocState {
+    pub(super) fn new(accountant: Arc<MemoryAccountant>) -> Self {
+        let to_disk_threshold = if std::env::var(FORCE_OOC).is_ok() {
+            1.0
+        } else {
+            TO_DISK_THRESHOLD
+        };
+
+        Self {
+            mem_track: MemTracker::new(morsels_per_sink(), accountant),
+            ooc: false,
+            io_thread: Default::default(),
+            count: 0,
+            to_disk_threshold,
+        }
+    }
+
     fn init_ooc(&mut self, spill_schema: Schema) -> PolarsResult<()> {
         if verbose() {
             eprintln!("OOC group_by started");
@@ -59,7 +57,14 @@ impl OocState {
         // start IO thread
         let mut iot = self.io_thread.lock().unwrap();
         if iot.is_none() {
-            *iot = Some(IOThread::try_new(Arc::new(spill_schema), "group_by").unwrap());
+            *iot = Some(
+                IOThread::try_new(
+                    Arc::new(spill_schema),
+                    "group_by",
+                    self.mem_track.accountant().clone(),
+                )
+                .unwrap(),
+            );
         }
         Ok(())
     }
@@ -6,6 +6,7 @@ use super::*;
 use crate::executors::sinks::group_by::generic::global::GlobalTable;
 use crate::executors::sinks::group_by::generic::ooc_state::{OocState, SpillAction};
 use crate::executors::sinks::group_by::generic::source::GroupBySource;
+use crate::executors::sinks::memory::MemoryAccountant;
 use crate::executors::sources::DataFrameSource;
 use crate::expressions::PhysicalPipedExpr;
 
@@ -25,6 +26,7 @@ impl GenericGroupby2 {
         output_schema: SchemaRef,
         agg_input_dtypes: Vec<DataType>,
         slice: Option<(i64, usize)>,
+        accountant: Arc<MemoryAccountant>,
     ) -> Self {
         let key_dtypes: Arc<[DataType]> = Arc::from(
             output_schema
@@ -52,7 +54,7 @@ impl GenericGroupby2 {
             global_table: Arc::new(global_map),
             eval: Eval::new(key_columns, aggregation_columns),
             slice,
-            ooc_state: Default::default(),
+            ooc_state: OocState::new(accountant),
         }
     }
 }
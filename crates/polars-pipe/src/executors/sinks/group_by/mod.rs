@@ -1,16 +1,24 @@
 pub(crate) mod aggregates;
+#[cfg(feature = "dynamic_group_by")]
+mod dynamic;
 mod generic;
 mod ooc;
 mod ooc_state;
 mod primitive;
+mod sorted;
 mod string;
 mod utils;
 
+#[cfg(feature = "dynamic_group_by")]
+pub use dynamic::can_stream_temporal_group_by;
+#[cfg(feature = "dynamic_group_by")]
+pub(crate) use dynamic::{DynamicGroupbySink, TemporalWindows};
 pub(crate) use generic::GenericGroupby2;
 use polars_core::prelude::*;
 #[cfg(feature = "dtype-categorical")]
 use polars_core::using_string_cache;
 pub(crate) use primitive::*;
+pub(crate) use sorted::{contiguous_group_keys, SortedGroupbySink};
 pub(crate) use string::*;
 
 pub(super) fn physical_agg_to_logical(cols: &mut [Series], output_schema: &Schema) {
@@ -1,7 +1,10 @@
+use std::path::PathBuf;
+
 use polars_core::config::verbose;
 use polars_core::prelude::*;
-use polars_core::utils::split_df;
+use polars_core::utils::{accumulate_dataframes_vertical_unchecked, split_df};
 
+use crate::executors::sinks::group_by::utils::SpilledPartitions;
 use crate::executors::sinks::io::IOThread;
 use crate::executors::sources::IpcSourceOneShot;
 use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, Source, SourceResult};
@@ -11,22 +14,23 @@ pub(super) struct GroupBySource {
     // Holding this keeps the lockfile in place
     io_thread: IOThread,
     already_finished: Option<DataFrame>,
-    partitions: std::fs::ReadDir,
-    group_by_sink: Box<dyn Sink>,
+    // the directories of the spilled partitions and the sinks with the in-memory groups of those
+    // partitions, which are combined with the aggregated spilled rows
+    partitions: std::vec::IntoIter<(PathBuf, Box<dyn Sink>)>,
     chunk_idx: IdxSize,
     morsels_per_sink: usize,
     slice: Option<(usize, usize)>,
+    // a partition that didn't fit in memory itself and went out-of-core again
+    nested: Option<Box<dyn Source>>,
 }
 
 impl GroupBySource {
     pub(super) fn new(
         io_thread: IOThread,
         already_finished: DataFrame,
-        group_by_sink: Box<dyn Sink>,
+        partitions: SpilledPartitions,
         slice: Option<(i64, usize)>,
     ) -> PolarsResult<Self> {
-        let partitions = std::fs::read_dir(&io_thread.dir)?;
-
         if let Some(slice) = slice {
             if slice.0 < 0 {
                 polars_bail!(ComputeError: "negative slice not supported with out-of-core group_by")
@@ -36,13 +40,48 @@ impl GroupBySource {
         Ok(Self {
             io_thread,
             already_finished: Some(already_finished),
-            partitions,
-            group_by_sink,
+            partitions: partitions.into_iter(),
             chunk_idx: 0,
             morsels_per_sink: morsels_per_sink(),
             slice: slice.map(|slice| (slice.0 as usize, slice.1)),
+            nested: None,
         })
     }
+
+    /// Apply the remaining slice to a finished part of the output.
+    /// Returns `None` if the whole part is before the slice offset.
+    fn apply_slice(&mut self, df: DataFrame) -> Option<DataFrame> {
+        match &mut self.slice {
+            None => Some(df),
+            Some(slice) => {
+                let height = df.height();
+                if slice.0 >= height {
+                    slice.0 -= height;
+                    None
+                } else {
+                    let df = df.slice(slice.0 as i64, slice.1);
+                    slice.0 = 0;
+                    slice.1 = slice.1.saturating_sub(df.height());
+                    Some(df)
+                }
+            },
+        }
+    }
+
+    fn split_chunks(&mut self, mut df: DataFrame) -> Vec<DataChunk> {
+        let dfs = split_df(&mut df, self.morsels_per_sink, false);
+        dfs.into_iter()
+            .map(|data| {
+                let chunk = DataChunk {
+                    chunk_index: self.chunk_idx,
+                    data,
+                };
+                self.chunk_idx += 1;
+
+                chunk
+            })
+            .collect()
+    }
 }
 
 impl Source for GroupBySource {
@@ -52,24 +91,33 @@ impl Source for GroupBySource {
         }
 
         if let Some(df) = self.already_finished.take() {
-            let chunk_idx = self.chunk_idx;
-            self.chunk_idx += 1;
-            return Ok(SourceResult::GotMoreData(vec![DataChunk::new(
-                chunk_idx, df,
-            )]));
+            return match self.apply_slice(df) {
+                Some(df) => Ok(SourceResult::GotMoreData(self.split_chunks(df))),
+                None => self.get_batches(context),
+            };
+        }
+
+        if let Some(nested) = &mut self.nested {
+            match nested.get_batches(context)? {
+                SourceResult::Finished => self.nested = None,
+                SourceResult::GotMoreData(chunks) => {
+                    let df = accumulate_dataframes_vertical_unchecked(
+                        chunks.into_iter().map(|chunk| chunk.data),
+                    );
+                    return match self.apply_slice(df) {
+                        Some(df) => Ok(SourceResult::GotMoreData(self.split_chunks(df))),
+                        None => self.get_batches(context),
+                    };
+                },
+            }
         }
 
         match self.partitions.next() {
             None => Ok(SourceResult::Finished),
-            Some(dir) => {
-                let partition_dir = dir?;
-                if partition_dir.path().ends_with(".lock") {
-                    return self.get_batches(context);
-                }
-
+            Some((partition_dir, mut sink)) => {
                 // read the files in the partition into sources
                 // ensure we read in the right order
-                let mut files = std::fs::read_dir(partition_dir.path())?
+                let mut files = std::fs::read_dir(&partition_dir)?
                     .map(|e| e.map(|e| e.path()))
                     .collect::<Result<Vec<_>, _>>()?;
                 files.sort_unstable();
@@ -82,48 +130,32 @@ impl Source for GroupBySource {
                     .collect::<PolarsResult<Vec<_>>>()?;
 
                 // create a pipeline with a the files as sources and the group_by as sink
-                let mut pipe =
-                    PipeLine::new_simple(sources, vec![], self.group_by_sink.split(0), verbose());
-
-                let out = match pipe.run_pipeline(context, &mut vec![])?.unwrap() {
-                    FinalizedSink::Finished(mut df) => {
-                        if let Some(slice) = &mut self.slice {
-                            let height = df.height();
-                            if slice.0 >= height {
-                                slice.0 -= height;
-                                return self.get_batches(context);
-                            } else {
-                                df = df.slice(slice.0 as i64, slice.1);
-                                slice.0 = 0;
-                                slice.1 = slice.1.saturating_sub(height);
-                            }
-                        }
-
-                        let dfs = split_df(&mut df, self.morsels_per_sink, false);
-                        let chunks = dfs
-                            .into_iter()
-                            .map(|data| {
-                                let chunk = DataChunk {
-                                    chunk_index: self.chunk_idx,
-                                    data,
-                                };
-                                self.chunk_idx += 1;
-
-                                chunk
-                            })
-                            .collect::<Vec<_>>();
-
-                        Ok(SourceResult::GotMoreData(chunks))
-                    },
-                    // recursively out of core path
-                    FinalizedSink::Source(mut src) => src.get_batches(context),
-                    _ => unreachable!(),
-                };
+                // the in-memory groups of the partition are combined before it is finalized
+                let mut pipe = PipeLine::new_simple(
+                    sources,
+                    vec![],
+                    sink.split(0),
+                    verbose(),
+                    context.mem_track.accountant().clone(),
+                );
+
+                let finalized = pipe.run_pipeline_combined(context, sink.as_mut())?;
                 for path in files {
                     self.io_thread.clean(path)
                 }
 
-                out
+                match finalized {
+                    FinalizedSink::Finished(df) => match self.apply_slice(df) {
+                        Some(df) => Ok(SourceResult::GotMoreData(self.split_chunks(df))),
+                        None => self.get_batches(context),
+                    },
+                    // recursively out of core path
+                    FinalizedSink::Source(src) => {
+                        self.nested = Some(src);
+                        self.get_batches(context)
+                    },
+                    _ => unreachable!(),
+                }
             },
         }
     }
@@ -2,32 +2,55 @@ use std::sync::Mutex;
 
 use polars_core::config::verbose;
 use polars_core::prelude::*;
+use polars_utils::hashing::hash_to_partition;
 
 use crate::executors::sinks::io::IOThread;
-use crate::executors::sinks::memory::MemTracker;
-use crate::pipeline::morsels_per_sink;
+use crate::executors::sinks::memory::{MemTracker, MemoryAccountant};
+use crate::pipeline::{morsels_per_sink, FORCE_OOC, PARTITION_SIZE};
 
-/// THIS CODE DOESN'T MAKE SENSE
-/// it is a remnant of OOC, but will be rewritten to use the generic OOC
-/// Table
+// If this is reached we stop growing the hash tables and
+// spill the rows of new keys to disk to aggregate them in a second run
+const TO_DISK_THRESHOLD: f64 = 0.3;
 
+/// Out-of-core state of the single key (primitive and string) group_by sinks.
+///
+/// Once the free memory drops below the threshold, the hash tables no longer grow.
+/// Rows of keys that are already in the table are still aggregated, the other rows
+/// are partitioned by hash and spilled to disk. Every partition is aggregated in a
+/// second pass by the `GroupBySource`, together with the in-memory groups of the
+/// partition, as the other thread local sinks may hold their keys.
 pub(super) struct OocState {
     // OOC
     // Stores available memory in the system at the start of this sink.
     // and stores the memory used by this this sink.
-    _mem_track: MemTracker,
-    // sort in-memory or out-of-core
+    mem_track: MemTracker,
+    // aggregate in-memory or out-of-core
     pub(super) ooc: bool,
     // when ooc, we write to disk using an IO thread
     pub(super) io_thread: Arc<Mutex<Option<IOThread>>>,
+    // the row indexes of the current chunk that must be spilled, per partition
+    ooc_rows: Vec<Vec<IdxSize>>,
+    to_disk_threshold: f64,
 }
 
 impl OocState {
-    pub(super) fn new(io_thread: Option<Arc<Mutex<Option<IOThread>>>>, ooc: bool) -> Self {
+    pub(super) fn new(
+        io_thread: Option<Arc<Mutex<Option<IOThread>>>>,
+        ooc: bool,
+        accountant: Arc<MemoryAccountant>,
+    ) -> Self {
+        let to_disk_threshold = if std::env::var(FORCE_OOC).is_ok() {
+            1.0
+        } else {
+            TO_DISK_THRESHOLD
+        };
+
         Self {
-            _mem_track: MemTracker::new(morsels_per_sink()),
+            mem_track: MemTracker::new(morsels_per_sink(), accountant),
             ooc,
             io_thread: io_thread.unwrap_or_default(),
+            ooc_rows: vec![vec![]; PARTITION_SIZE],
+            to_disk_threshold,
         }
     }
 
@@ -40,26 +63,61 @@ impl OocState {
         // start IO thread
         let mut iot = self.io_thread.lock().unwrap();
         if iot.is_none() {
-            *iot = Some(IOThread::try_new(input_schema, "group_by")?)
+            *iot = Some(IOThread::try_new(
+                input_schema,
+                "group_by",
+                self.mem_track.accountant().clone(),
+            )?)
         }
         Ok(())
     }
 
-    pub(super) fn reset_ooc_filter_rows(&mut self, _len: usize) {
-        // no-op
+    pub(super) fn accountant(&self) -> &Arc<MemoryAccountant> {
+        self.mem_track.accountant()
     }
 
-    pub(super) fn check_memory_usage(&mut self, _schema: &SchemaRef) -> PolarsResult<()> {
-        // ooc is broken we will rewrite to generic table
-        // n-op
+    pub(super) fn check_memory_usage(&mut self, schema: &SchemaRef) -> PolarsResult<()> {
+        if self.ooc {
+            return Ok(());
+        }
+        if self.mem_track.free_memory_fraction_since_start() < self.to_disk_threshold {
+            self.init_ooc(schema.clone())?
+        }
         Ok(())
     }
 
+    /// Mark the row at `idx` of the current chunk as not processed, it will be
+    /// spilled to the partition of its `hash`.
     #[inline]
-    pub(super) unsafe fn set_row_as_ooc(&mut self, _idx: usize) {}
+    pub(super) fn set_row_as_ooc(&mut self, idx: usize, hash: u64) {
+        let partition = hash_to_partition(hash, PARTITION_SIZE);
+        // SAFETY: `hash_to_partition` returns a value in bounds of `PARTITION_SIZE`.
+        unsafe { self.ooc_rows.get_unchecked_mut(partition) }.push(idx as IdxSize);
+    }
+
+    /// Spill the rows of `data` that were marked with `set_row_as_ooc`.
+    pub(super) fn dump(&mut self, data: DataFrame) {
+        let (partitions, dfs): (Vec<IdxSize>, Vec<DataFrame>) = self
+            .ooc_rows
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, rows)| !rows.is_empty())
+            .map(|(partition, rows)| {
+                // SAFETY: the row indexes are collected from this chunk.
+                let df = unsafe { data._take_unchecked_slice(rows, false) };
+                rows.clear();
+                (partition as IdxSize, df)
+            })
+            .unzip();
 
-    pub(super) fn dump(&self, _data: DataFrame, _hashes: &mut [u64]) {
-        // ooc is broken we will rewrite to generic table
-        todo!()
+        if dfs.is_empty() {
+            return;
+        }
+        let iot = self.io_thread.lock().unwrap();
+        let iot = iot.as_ref().unwrap();
+        iot.dump_iter(
+            Some(IdxCa::from_vec(PlSmallStr::EMPTY, partitions)),
+            Box::new(dfs.into_iter()),
+        )
     }
 }
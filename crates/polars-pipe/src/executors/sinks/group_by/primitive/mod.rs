@@ -22,12 +22,16 @@ use crate::executors::sinks::group_by::aggregates::AggregateFunction;
 use crate::executors::sinks::group_by::ooc_state::OocState;
 use crate::executors::sinks::group_by::physical_agg_to_logical;
 use crate::executors::sinks::group_by::string::{apply_aggregate, write_agg_idx};
-use crate::executors::sinks::group_by::utils::{compute_slices, finalize_group_by, prepare_key};
+use crate::executors::sinks::group_by::utils::{
+    compute_slices, finalize_group_by, ooc_payload, prepare_key, spilled_partitions,
+};
 use crate::executors::sinks::io::IOThread;
+use crate::executors::sinks::memory::MemoryAccountant;
 use crate::executors::sinks::utils::load_vec;
 use crate::executors::sinks::HASHMAP_INIT_SIZE;
 use crate::expressions::PhysicalPipedExpr;
 use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult};
+use crate::pipeline::PARTITION_SIZE;
 
 // hash + value
 #[derive(Eq, Copy, Clone)]
@@ -90,6 +94,7 @@ where
         input_schema: SchemaRef,
         output_schema: SchemaRef,
         slice: Option<(i64, usize)>,
+        accountant: Arc<MemoryAccountant>,
     ) -> Self {
         // this ooc is broken fix later
         Self::new_inner(
@@ -101,6 +106,7 @@ where
             slice,
             None,
             false,
+            accountant,
         )
     }
 
@@ -114,6 +120,7 @@ where
         slice: Option<(i64, usize)>,
         io_thread: Option<Arc<Mutex<Option<IOThread>>>>,
         ooc: bool,
+        accountant: Arc<MemoryAccountant>,
     ) -> Self {
         let hb = PlRandomState::default();
         let partitions = _set_partition_size();
@@ -136,7 +143,7 @@ where
             hashes: vec![],
             slice,
             sort_partitions: vec![],
-            ooc_state: OocState::new(io_thread, ooc),
+            ooc_state: OocState::new(io_thread, ooc, accountant),
         };
         if ooc {
             out.ooc_state.init_ooc(out.input_schema.clone()).unwrap();
@@ -215,6 +222,57 @@ where
         })
     }
 
+    /// Take the groups of which the hash is in the spilled `partition` out of the tables, into a
+    /// new sink.
+    fn take_partition(&mut self, partition: usize) -> Self {
+        let mut sink = self.split_inner(0);
+        let n_aggs = self.number_of_aggs();
+
+        self.pre_agg_partitions
+            .iter_mut()
+            .zip(sink.pre_agg_partitions.iter_mut())
+            .for_each(|(map_self, map_new)| {
+                map_self.retain(|key, &mut agg_idx| {
+                    if hash_to_partition(key.hash, PARTITION_SIZE) != partition {
+                        return true;
+                    }
+                    let offset = NumCast::from(sink.aggregators.len()).unwrap();
+                    let agg_idx = agg_idx as usize;
+                    // leave fresh aggregation functions behind, the table no longer points to them
+                    let aggregators = self.aggregators[agg_idx..agg_idx + n_aggs]
+                        .iter_mut()
+                        .zip(&self.agg_fns)
+                        .map(|(agg, agg_fn)| std::mem::replace(agg, agg_fn.split()));
+                    sink.aggregators.extend(aggregators);
+                    // the keys of a table are unique
+                    if let RawEntryMut::Vacant(entry) =
+                        map_new.raw_entry_mut().from_hash(key.hash, |_| false)
+                    {
+                        entry.insert(*key, offset);
+                    }
+                    false
+                })
+            });
+        sink
+    }
+
+    fn split_inner(&self, thread_no: usize) -> Self {
+        let mut new = Self::new_inner(
+            self.key.clone(),
+            self.aggregation_columns.clone(),
+            self.agg_fns.iter().map(|func| func.split()).collect(),
+            self.input_schema.clone(),
+            self.output_schema.clone(),
+            self.slice,
+            Some(self.ooc_state.io_thread.clone()),
+            self.ooc_state.ooc,
+            self.ooc_state.accountant().clone(),
+        );
+        new.hb = self.hb.clone();
+        new.thread_no = thread_no;
+        new
+    }
+
     fn sink_sorted(&mut self, ca: &ChunkedArray<K>, chunk: DataChunk) -> PolarsResult<SinkResult> {
         if chunk.is_empty() {
             return Ok(SinkResult::CanHaveMoreInput);
@@ -297,9 +355,6 @@ where
         let arr = ca.downcast_iter().next().unwrap();
         let pre_agg_len = self.pre_agg_partitions.len();
 
-        // set all bits to false
-        self.ooc_state.reset_ooc_filter_rows(ca.len());
-
         // this reuses the hashes buffer as [u64] as idx buffer as [idxsize]
         // write the hashes to self.hashes buffer
         // s.vec_hash(self.hb.clone(), &mut self.hashes).unwrap();
@@ -325,11 +380,8 @@ where
                 unsafe { write_agg_idx(agg_idx_ptr, processed, agg_idx) };
                 processed += 1;
             } else {
-                // set this row to true: e.g. processed ooc
-                // SAFETY: we correctly set the length with `reset_ooc_filter_rows`
-                unsafe {
-                    self.ooc_state.set_row_as_ooc(iteration_idx);
-                }
+                // the key is not in the table, this row is processed ooc
+                self.ooc_state.set_row_as_ooc(iteration_idx, h);
             }
         }
 
@@ -343,8 +395,9 @@ where
             &mut self.aggregators,
         );
 
-        self.ooc_state.dump(chunk.data, &mut self.hashes);
+        self.ooc_state.dump(chunk.data);
 
+        self.aggregation_series.clear();
         Ok(SinkResult::CanHaveMoreInput)
     }
 }
@@ -452,36 +505,20 @@ where
     }
 
     fn finalize(&mut self, _context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
-        let dfs = self.pre_finalize()?;
-        let payload = if self.ooc_state.ooc {
-            let mut iot = self.ooc_state.io_thread.lock().unwrap();
-            // make sure that we reset the shared states
-            // the OOC group_by will call split as well and it should
-            // not send continue spilling to disk
-            let iot = iot.take().unwrap();
-            self.ooc_state.ooc = false;
-
-            Some((iot, self.split(0)))
-        } else {
-            None
+        let (slice, payload) = ooc_payload(&mut self.ooc_state, &mut self.slice);
+        let payload = match payload {
+            Some(iot) => {
+                let partitions = spilled_partitions(&iot, |p| Box::new(self.take_partition(p)))?;
+                Some((iot, partitions))
+            },
+            None => None,
         };
-        finalize_group_by(dfs, &self.output_schema, self.slice, payload)
+        let dfs = self.pre_finalize()?;
+        finalize_group_by(dfs, &self.output_schema, slice, payload)
     }
 
     fn split(&self, thread_no: usize) -> Box<dyn Sink> {
-        let mut new = Self::new_inner(
-            self.key.clone(),
-            self.aggregation_columns.clone(),
-            self.agg_fns.iter().map(|func| func.split()).collect(),
-            self.input_schema.clone(),
-            self.output_schema.clone(),
-            self.slice,
-            Some(self.ooc_state.io_thread.clone()),
-            self.ooc_state.ooc,
-        );
-        new.hb = self.hb.clone();
-        new.thread_no = thread_no;
-        Box::new(new)
+        Box::new(self.split_inner(thread_no))
     }
 
     fn as_any(&mut self) -> &mut dyn Any {
@@ -0,0 +1,408 @@
+use std::any::Any;
+
+use polars_core::chunked_array::metadata::MetadataFlags;
+use polars_core::prelude::*;
+use polars_core::series::IsSorted;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_plan::plans::hive::HivePartitions;
+use polars_plan::prelude::expr_ir::ExprIR;
+use polars_plan::prelude::*;
+
+use super::aggregates::AggregateFn;
+use crate::executors::sinks::group_by::aggregates::AggregateFunction;
+use crate::executors::sinks::group_by::utils::{finalize_ordered_groups, finish_groups};
+use crate::expressions::PhysicalPipedExpr;
+use crate::operators::{
+    DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult, Source, SourceResult,
+};
+use crate::pipeline::morsels_per_sink;
+
+/// Checks if the rows of every group of `keys` are contiguous in the input at `node`, so that
+/// the group_by can be executed by the [`SortedGroupbySink`].
+///
+/// This holds for a single key that is a sorted column, per the sorted flag of the column of an
+/// in-memory scan or of `set_sorted`, and for the keys of a hive partitioned scan that don't
+/// repeat once the scan moved on to files with other keys, e.g. the first partition columns.
+pub(crate) fn contiguous_group_keys(
+    keys: &[ExprIR],
+    mut node: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    let Some(mut names) = keys
+        .iter()
+        .map(|e| match expr_arena.get(e.node()) {
+            AExpr::Column(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+    if names.is_empty() {
+        return false;
+    }
+
+    // Follow the keys down through the operators of the pipeline, which keep the order of
+    // the rows.
+    loop {
+        match lp_arena.get(node) {
+            IR::Filter { input, predicate } => {
+                if !is_streamable(predicate.node(), expr_arena, Context::Default) {
+                    return false;
+                }
+                node = *input;
+            },
+            IR::SimpleProjection { input, .. } => node = *input,
+            IR::Select { input, expr, .. }
+            | IR::HStack {
+                input, exprs: expr, ..
+            } => {
+                if !all_streamable(expr, expr_arena, Context::Default) {
+                    return false;
+                }
+                for e in expr {
+                    let Some(key) = names.iter_mut().find(|name| **name == *e.output_name()) else {
+                        continue;
+                    };
+                    match expr_arena.get(e.node()) {
+                        AExpr::Column(name) => *key = name.clone(),
+                        AExpr::Function {
+                            function: FunctionExpr::SetSortedFlag(sorted),
+                            ..
+                        } => return names.len() == 1 && *sorted != IsSorted::Not,
+                        _ => return false,
+                    }
+                }
+                node = *input;
+            },
+            IR::MapFunction {
+                input,
+                function: FunctionIR::Rechunk,
+            } => node = *input,
+            // The flag is checked instead of `is_sorted_flag`, which holds for any empty
+            // frame, such as the one that stands in for the input of a fan-out.
+            IR::DataFrameScan { df, .. } => {
+                return names.len() == 1
+                    && df.column(&names[0]).is_ok_and(|s| {
+                        s.get_flags()
+                            .intersects(MetadataFlags::SORTED_ASC | MetadataFlags::SORTED_DSC)
+                    })
+            },
+            IR::Scan {
+                hive_parts: Some(hive_parts),
+                ..
+            } => return contiguous_hive_keys(&names, hive_parts),
+            _ => return false,
+        }
+    }
+}
+
+/// Check that the values of the partition columns `names` of the files of a scan form runs
+/// that don't repeat.
+fn contiguous_hive_keys(names: &[PlSmallStr], hive_parts: &[HivePartitions]) -> bool {
+    let mut seen = PlHashSet::new();
+    let mut last = None;
+    for part in hive_parts {
+        let stats = part.get_statistics().column_stats();
+        let Some(values) = names
+            .iter()
+            .map(|name| {
+                let stats = stats.iter().find(|cs| cs.field_name() == name)?;
+                stats.get_min_state()?.get(0).ok()?.into_static().ok()
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+        if last.as_ref() != Some(&values) {
+            if !seen.insert(values.clone()) {
+                return false;
+            }
+            last = Some(values);
+        }
+    }
+    true
+}
+
+/// The `[first, len]` groups of the runs of equal keys.
+fn runs(keys: &[Series], height: usize) -> PolarsResult<GroupsSlice> {
+    if height == 0 {
+        return Ok(vec![]);
+    }
+    let mut new_run = BooleanChunked::full(PlSmallStr::EMPTY, false, height - 1);
+    for key in keys {
+        let changed = key
+            .slice(1, height - 1)
+            .not_equal_missing(&key.slice(0, height - 1))?;
+        new_run = &new_run | &changed;
+    }
+    let mut groups = vec![];
+    let mut first = 0 as IdxSize;
+    for (i, new_run) in new_run.into_no_null_iter().enumerate() {
+        if new_run {
+            let end = i as IdxSize + 1;
+            groups.push([first, end - first]);
+            first = end;
+        }
+    }
+    groups.push([first, height as IdxSize - first]);
+    Ok(groups)
+}
+
+/// The partial aggregates of a group that may continue in other chunks.
+struct OpenGroup {
+    // the keys of the group, as a single row
+    keys: Vec<Series>,
+    aggs: Vec<AggregateFunction>,
+}
+
+impl OpenGroup {
+    fn has_keys_of(&self, other: &Self) -> bool {
+        self.keys
+            .iter()
+            .zip(&other.keys)
+            .all(|(a, b)| a.equals_missing(b))
+    }
+
+    fn combine(&mut self, other: &Self) {
+        for (agg, other) in self.aggs.iter_mut().zip(&other.aggs) {
+            agg.combine(other.as_any())
+        }
+    }
+}
+
+// The groups of a chunk. Only the first and the last group may continue in other chunks.
+struct ChunkGroups {
+    chunk_index: IdxSize,
+    head: OpenGroup,
+    // the aggregated groups between the first and the last group
+    complete: Option<DataFrame>,
+    // `None` if the chunk is a single group
+    tail: Option<OpenGroup>,
+}
+
+/// Executes a group_by of which the rows of every group are contiguous in the input, see
+/// [`contiguous_group_keys`].
+///
+/// Instead of a hash table, the groups are found where the keys change. A group can only
+/// continue in another chunk if it contains the first or the last row of a chunk. All other
+/// groups are aggregated as soon as their chunk arrives, the first and the last group of every
+/// chunk are partially aggregated, so no rows of the input are kept. The partial aggregates are
+/// merged in the order of the chunks by the [`SortedGroupbySource`], which emits the groups in
+/// the order of the input as they close.
+pub(crate) struct SortedGroupbySink {
+    key_columns: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+    aggregation_columns: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+    agg_fns: Arc<[AggregateFunction]>,
+    output_schema: SchemaRef,
+    // the output fields of the aggregations
+    agg_schema: SchemaRef,
+    slice: Option<(i64, usize)>,
+    chunks: Vec<ChunkGroups>,
+}
+
+impl SortedGroupbySink {
+    pub(crate) fn new(
+        key_columns: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        aggregation_columns: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        agg_fns: Arc<[AggregateFunction]>,
+        output_schema: SchemaRef,
+        slice: Option<(i64, usize)>,
+    ) -> Self {
+        let agg_schema = output_schema
+            .iter_fields()
+            .skip(key_columns.len())
+            .collect::<Schema>();
+        Self {
+            key_columns,
+            aggregation_columns,
+            agg_fns,
+            output_schema,
+            agg_schema: Arc::new(agg_schema),
+            slice,
+            chunks: vec![],
+        }
+    }
+
+    fn evaluate(
+        exprs: &[Arc<dyn PhysicalPipedExpr>],
+        chunk: &DataChunk,
+        context: &PExecutionContext,
+    ) -> PolarsResult<Vec<Series>> {
+        exprs
+            .iter()
+            .map(|e| e.evaluate(chunk, &context.execution_state))
+            .collect()
+    }
+
+    /// Start the aggregation of the rows of the group `[first, len]`.
+    fn pre_aggregate(
+        &self,
+        chunk_index: IdxSize,
+        values: &[Series],
+        [first, len]: [IdxSize; 2],
+    ) -> Vec<AggregateFunction> {
+        self.agg_fns
+            .iter()
+            .zip(values)
+            .map(|(agg_fn, values)| {
+                let mut agg_fn = agg_fn.split();
+                agg_fn.pre_agg_ordered(chunk_index, first, len, values);
+                agg_fn
+            })
+            .collect()
+    }
+}
+
+impl Sink for SortedGroupbySink {
+    fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        if chunk.is_empty() {
+            return Ok(SinkResult::CanHaveMoreInput);
+        }
+        let keys = Self::evaluate(&self.key_columns, &chunk, context)?;
+        let values = Self::evaluate(&self.aggregation_columns, &chunk, context)?
+            .into_iter()
+            .map(|s| s.to_physical_repr().rechunk())
+            .collect::<Vec<_>>();
+        let groups = runs(&keys, chunk.data.height())?;
+
+        let open = |group: [IdxSize; 2]| OpenGroup {
+            keys: keys.iter().map(|s| s.slice(group[0] as i64, 1)).collect(),
+            aggs: self.pre_aggregate(chunk.chunk_index, &values, group),
+        };
+        let head = open(groups[0]);
+        let tail = (groups.len() > 1).then(|| open(groups[groups.len() - 1]));
+        let complete = if groups.len() > 2 {
+            let groups = &groups[1..groups.len() - 1];
+            let firsts = groups.iter().map(|[first, _]| *first).collect::<Vec<_>>();
+            let keys = keys
+                .iter()
+                .map(|s| s.take_slice(&firsts))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            let aggs = groups
+                .iter()
+                .map(|group| self.pre_aggregate(chunk.chunk_index, &values, *group))
+                .collect();
+            Some(finish_groups(keys, aggs, &self.agg_fns, &self.agg_schema))
+        } else {
+            None
+        };
+        self.chunks.push(ChunkGroups {
+            chunk_index: chunk.chunk_index,
+            head,
+            complete,
+            tail,
+        });
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, other: &mut dyn Sink) {
+        let other = other.as_any().downcast_mut::<Self>().unwrap();
+        self.chunks.append(&mut other.chunks);
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+        Box::new(Self {
+            key_columns: self.key_columns.clone(),
+            aggregation_columns: self.aggregation_columns.clone(),
+            agg_fns: self.agg_fns.clone(),
+            output_schema: self.output_schema.clone(),
+            agg_schema: self.agg_schema.clone(),
+            slice: self.slice,
+            chunks: vec![],
+        })
+    }
+
+    fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        if self.chunks.is_empty() {
+            return Ok(FinalizedSink::Finished(DataFrame::empty_with_schema(
+                &self.output_schema,
+            )));
+        }
+        let mut chunks = std::mem::take(&mut self.chunks);
+        chunks.sort_unstable_by_key(|groups| groups.chunk_index);
+        let source = SortedGroupbySource {
+            chunks: chunks.into_iter().peekable(),
+            open: None,
+            agg_fns: self.agg_fns.clone(),
+            agg_schema: self.agg_schema.clone(),
+        };
+        finalize_ordered_groups(Box::new(source), &self.output_schema, self.slice, context)
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        "sorted_group_by"
+    }
+}
+
+/// Merges the partial aggregates of the groups that continue in other chunks in the order of
+/// the chunks, and emits the groups of every chunk that are closed once it is merged.
+struct SortedGroupbySource {
+    chunks: std::iter::Peekable<std::vec::IntoIter<ChunkGroups>>,
+    // the last group of the chunks that are merged so far
+    open: Option<OpenGroup>,
+    agg_fns: Arc<[AggregateFunction]>,
+    agg_schema: SchemaRef,
+}
+
+impl SortedGroupbySource {
+    fn finish_open(&mut self) -> DataFrame {
+        let open = self.open.take().unwrap();
+        finish_groups(open.keys, vec![open.aggs], &self.agg_fns, &self.agg_schema)
+    }
+
+    /// Merge the groups of a chunk into the open group, and return the groups that are closed.
+    fn merge(&mut self, groups: ChunkGroups) -> Vec<DataFrame> {
+        let mut dfs = vec![];
+        match &mut self.open {
+            Some(open) if open.has_keys_of(&groups.head) => open.combine(&groups.head),
+            Some(_) => {
+                dfs.push(self.finish_open());
+                self.open = Some(groups.head);
+            },
+            None => self.open = Some(groups.head),
+        }
+        if let Some(tail) = groups.tail {
+            dfs.push(self.finish_open());
+            dfs.extend(groups.complete);
+            self.open = Some(tail);
+        }
+        if self.chunks.peek().is_none() {
+            dfs.push(self.finish_open());
+        }
+        dfs
+    }
+}
+
+impl Source for SortedGroupbySource {
+    fn get_batches(&mut self, _context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        let mut chunks = vec![];
+        while chunks.len() < morsels_per_sink() {
+            let Some(groups) = self.chunks.next() else {
+                break;
+            };
+            let chunk_index = groups.chunk_index;
+            let dfs = self.merge(groups);
+            if dfs.is_empty() {
+                continue;
+            }
+            let mut df = accumulate_dataframes_vertical_unchecked(dfs);
+            df.as_single_chunk_par();
+            chunks.push(DataChunk::new(chunk_index, df));
+        }
+        if chunks.is_empty() {
+            Ok(SourceResult::Finished)
+        } else {
+            Ok(SourceResult::GotMoreData(chunks))
+        }
+    }
+
+    fn fmt(&self) -> &str {
+        "sorted_group_by_source"
+    }
+}
@@ -1,10 +1,22 @@
+use std::path::PathBuf;
+
 use hashbrown::HashMap;
+use polars_core::frame::row::AnyValueBuffer;
 use polars_core::prelude::*;
 use polars_core::utils::{accumulate_dataframes_vertical_unchecked, slice_offsets};
 
+use crate::executors::sinks::group_by::aggregates::{AggregateFn, AggregateFunction};
 use crate::executors::sinks::group_by::ooc::GroupBySource;
+use crate::executors::sinks::group_by::ooc_state::OocState;
+use crate::executors::sinks::group_by::physical_agg_to_logical;
 use crate::executors::sinks::io::{block_thread_until_io_thread_done, IOThread};
-use crate::operators::{DataChunk, FinalizedSink, Sink};
+use crate::operators::{
+    DataChunk, FinalizedSink, PExecutionContext, Sink, Source, SourceResult,
+};
+
+/// The directories of the spilled partitions and the sinks with the in-memory groups of those
+/// partitions.
+pub(super) type SpilledPartitions = Vec<(PathBuf, Box<dyn Sink>)>;
 
 pub(super) fn default_slices<K, V, HB>(
     pre_agg_partitions: &[HashMap<K, V, HB>],
@@ -50,11 +62,54 @@ pub(super) fn compute_slices<K, V, HB>(
     }
 }
 
+/// Takes the IO thread if any of the thread local sinks went out-of-core.
+///
+/// The shared state is reset, so that the sinks that aggregate the spilled partitions
+/// don't continue spilling to the same directory. In that case the slice is returned
+/// and taken from the sink, as it must be applied over the output of all partitions.
+pub(super) fn ooc_payload(
+    ooc_state: &mut OocState,
+    slice: &mut Option<(i64, usize)>,
+) -> (Option<(i64, usize)>, Option<IOThread>) {
+    let iot = ooc_state.io_thread.lock().unwrap().take();
+    ooc_state.ooc = false;
+    match iot {
+        Some(iot) => (slice.take(), Some(iot)),
+        None => (*slice, None),
+    }
+}
+
+/// Waits until all chunks are spilled and collects the spilled partitions.
+///
+/// Every thread local sink spills the rows of the keys that are not in its own table, while
+/// the table of another thread may hold those keys. So `take_partition` must take the in-memory
+/// groups of a spilled partition out of the sink, these are combined with the aggregated
+/// spilled rows of the partition.
+pub(super) fn spilled_partitions(
+    iot: &IOThread,
+    mut take_partition: impl FnMut(usize) -> Box<dyn Sink>,
+) -> PolarsResult<SpilledPartitions> {
+    block_thread_until_io_thread_done(iot);
+
+    let mut partitions = vec![];
+    for entry in std::fs::read_dir(&iot.dir)? {
+        let path = entry?.path();
+        // skips the lockfile
+        let partition = path
+            .file_name()
+            .and_then(|name| name.to_str()?.parse::<usize>().ok());
+        if let Some(partition) = partition {
+            partitions.push((path, take_partition(partition)));
+        }
+    }
+    Ok(partitions)
+}
+
 pub(super) fn finalize_group_by(
     dfs: Vec<DataFrame>,
     output_schema: &Schema,
     slice: Option<(i64, usize)>,
-    ooc_payload: Option<(IOThread, Box<dyn Sink>)>,
+    ooc_payload: Option<(IOThread, SpilledPartitions)>,
 ) -> PolarsResult<FinalizedSink> {
     let df = if dfs.is_empty() {
         DataFrame::empty_with_schema(output_schema)
@@ -66,14 +121,100 @@ pub(super) fn finalize_group_by(
 
     match ooc_payload {
         None => Ok(FinalizedSink::Finished(df)),
-        Some((iot, sink)) => {
-            // we wait until all chunks are spilled
-            block_thread_until_io_thread_done(&iot);
+        Some((iot, partitions)) => Ok(FinalizedSink::Source(Box::new(GroupBySource::new(
+            iot, df, partitions, slice,
+        )?))),
+    }
+}
 
-            Ok(FinalizedSink::Source(Box::new(GroupBySource::new(
-                iot, df, sink, slice,
-            )?)))
+/// The output rows of groups, from the columns of their keys and their aggregates.
+pub(super) fn finish_groups(
+    mut columns: Vec<Series>,
+    mut groups: Vec<Vec<AggregateFunction>>,
+    agg_fns: &[AggregateFunction],
+    agg_schema: &Schema,
+) -> DataFrame {
+    let mut aggs = agg_fns
+        .iter()
+        .enumerate()
+        .map(|(i, agg_fn)| {
+            let mut buffer = AnyValueBuffer::new(&agg_fn.dtype(), groups.len());
+            for aggs in &mut groups {
+                buffer.add(aggs[i].finalize());
+            }
+            buffer.into_series()
+        })
+        .collect::<Vec<_>>();
+    physical_agg_to_logical(&mut aggs, agg_schema);
+    columns.extend(aggs);
+    // SAFETY: all columns have the length of `groups` and the names of the output schema.
+    unsafe { DataFrame::new_no_checks(columns) }
+}
+
+/// Finish a group_by of which `source` emits the groups in order.
+///
+/// A slice with a negative offset needs the number of groups, so the output of the source is
+/// collected first. Other slices are applied to the chunks of the source as they are emitted.
+pub(super) fn finalize_ordered_groups(
+    mut source: Box<dyn Source>,
+    output_schema: &Schema,
+    slice: Option<(i64, usize)>,
+    context: &PExecutionContext,
+) -> PolarsResult<FinalizedSink> {
+    match slice {
+        Some((offset, len)) if offset < 0 => {
+            let mut dfs = vec![];
+            while let SourceResult::GotMoreData(chunks) = source.get_batches(context)? {
+                dfs.extend(chunks.into_iter().map(|chunk| chunk.data));
+            }
+            let df = if dfs.is_empty() {
+                DataFrame::empty_with_schema(output_schema)
+            } else {
+                accumulate_dataframes_vertical_unchecked(dfs)
+            };
+            Ok(FinalizedSink::Finished(df.slice(offset, len)))
         },
+        Some((offset, len)) => Ok(FinalizedSink::Source(Box::new(SlicedSource {
+            source,
+            offset: offset as usize,
+            len,
+        }))),
+        None => Ok(FinalizedSink::Source(source)),
+    }
+}
+
+/// Applies a slice to the chunks of a source that are in order.
+struct SlicedSource {
+    source: Box<dyn Source>,
+    offset: usize,
+    len: usize,
+}
+
+impl Source for SlicedSource {
+    fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        while self.len > 0 {
+            let SourceResult::GotMoreData(chunks) = self.source.get_batches(context)? else {
+                break;
+            };
+            let mut sliced = Vec::with_capacity(chunks.len());
+            for chunk in chunks {
+                let height = chunk.data.height();
+                let df = chunk.data.slice(self.offset as i64, self.len);
+                self.offset = self.offset.saturating_sub(height);
+                self.len -= df.height();
+                if !df.is_empty() {
+                    sliced.push(chunk.with_data(df));
+                }
+            }
+            if !sliced.is_empty() {
+                return Ok(SourceResult::GotMoreData(sliced));
+            }
+        }
+        Ok(SourceResult::Finished)
+    }
+
+    fn fmt(&self) -> &str {
+        self.source.fmt()
     }
 }
 
@@ -11,6 +11,7 @@ use polars_core::utils::arrow::temporal_conversions::SECONDS_IN_DAY;
 use polars_io::prelude::*;
 
 use crate::executors::sinks::get_base_temp_dir;
+use crate::executors::sinks::memory::MemoryAccountant;
 use crate::pipeline::morsels_per_sink;
 
 pub(in crate::executors::sinks) type DfIter =
@@ -27,7 +28,8 @@ pub(crate) struct IOThread {
     pub(in crate::executors::sinks) sent: Arc<AtomicUsize>,
     pub(in crate::executors::sinks) total: Arc<AtomicUsize>,
     pub(in crate::executors::sinks) thread_local_count: Arc<AtomicUsize>,
-    schema: SchemaRef,
+    compression: Option<IpcCompression>,
+    accountant: Arc<MemoryAccountant>,
 }
 
 fn get_lockfile_path(dir: &Path) -> PathBuf {
@@ -58,6 +60,25 @@ fn get_spill_dir(operation_name: &'static str) -> PolarsResult<PathBuf> {
     Ok(dir)
 }
 
+/// The compression of the spilled files, set with the `POLARS_SPILL_COMPRESSION` env var to
+/// `lz4`, `zstd` or `uncompressed` (default).
+fn get_spill_compression() -> PolarsResult<Option<IpcCompression>> {
+    match std::env::var("POLARS_SPILL_COMPRESSION").as_deref() {
+        Err(_) | Ok("uncompressed") => Ok(None),
+        Ok("lz4") => Ok(Some(IpcCompression::LZ4)),
+        Ok("zstd") => Ok(Some(IpcCompression::ZSTD)),
+        Ok(v) => polars_bail!(
+            ComputeError: "invalid 'POLARS_SPILL_COMPRESSION' env var: '{}', expected one of 'lz4', 'zstd' or 'uncompressed'", v
+        ),
+    }
+}
+
+fn spill_writer(file: File, compression: Option<IpcCompression>) -> IpcWriter<File> {
+    IpcWriter::new(file)
+        .with_compat_level(CompatLevel::newest())
+        .with_compression(compression)
+}
+
 fn clean_after_delay(time: Option<SystemTime>, secs: u64, path: &Path) {
     if let Some(time) = time {
         let modified_since = SystemTime::now().duration_since(time).unwrap().as_secs();
@@ -130,8 +151,11 @@ impl IOThread {
         schema: SchemaRef,
         // Will be used as subdirectory name in `~/.base_dir/polars/`
         operation_name: &'static str,
+        // The accountant of the query, which counts the spilled bytes
+        accountant: Arc<MemoryAccountant>,
     ) -> PolarsResult<Self> {
         let dir = get_spill_dir(operation_name)?;
+        let compression = get_spill_compression()?;
 
         // make sure we create lockfile before we GC
         let lockfile_path = get_lockfile_path(&dir);
@@ -152,9 +176,8 @@ impl IOThread {
         let dir2 = dir.clone();
         let total2 = total.clone();
         let lockfile2 = lockfile.clone();
-        let schema2 = schema.clone();
+        let accountant2 = accountant.clone();
         std::thread::spawn(move || {
-            let schema = schema2;
             // this moves the lockfile in the thread
             // we keep one in the thread and one in the `IoThread` struct
             let _keep_hold_on_lockfile = lockfile2;
@@ -171,6 +194,7 @@ impl IOThread {
                     for (part, mut df) in partitions.into_no_null_iter().zip(iter) {
                         df.shrink_to_fit();
                         df.align_chunks();
+                        accountant2.record_spill(df.estimated_size());
                         let mut path = dir2.clone();
                         path.push(format!("{part}"));
 
@@ -178,7 +202,7 @@ impl IOThread {
                         path.push(format!("{count}.ipc"));
 
                         let file = File::create(path).unwrap();
-                        let writer = IpcWriter::new(file).with_compat_level(CompatLevel::newest());
+                        let writer = spill_writer(file, compression);
                         let mut writer = writer.batched(&schema).unwrap();
                         writer.write_batch(&df).unwrap();
                         writer.finish().unwrap();
@@ -189,12 +213,13 @@ impl IOThread {
                     path.push(format!("{count}_0_pass.ipc"));
 
                     let file = File::create(path).unwrap();
-                    let writer = IpcWriter::new(file).with_compat_level(CompatLevel::newest());
+                    let writer = spill_writer(file, compression);
                     let mut writer = writer.batched(&schema).unwrap();
 
                     for mut df in iter {
                         df.shrink_to_fit();
                         df.align_chunks();
+                        accountant2.record_spill(df.estimated_size());
                         writer.write_batch(&df).unwrap();
                     }
                     writer.finish().unwrap();
@@ -202,6 +227,7 @@ impl IOThread {
                     count += 1;
                 }
                 total2.store(count, Ordering::Relaxed);
+                accountant2.finish_spill();
             }
         });
 
@@ -213,7 +239,8 @@ impl IOThread {
             total,
             _lockfile: lockfile,
             thread_local_count,
-            schema,
+            compression,
+            accountant,
         })
     }
 
@@ -222,6 +249,7 @@ impl IOThread {
         // we write locally on this thread
         if self.payload_tx.is_full() {
             df.shrink_to_fit();
+            self.accountant.record_spill(df.estimated_size());
             let mut path = self.dir.clone();
             let count = self.thread_local_count.fetch_add(1, Ordering::Relaxed);
             // thread local name we start with an underscore to ensure we don't get
@@ -229,7 +257,7 @@ impl IOThread {
             path.push(format!("_{count}_full.ipc"));
 
             let file = File::create(path).unwrap();
-            let mut writer = IpcWriter::new(file).with_compat_level(CompatLevel::newest());
+            let mut writer = spill_writer(file, self.compression);
             writer.finish(&mut df).unwrap();
         } else {
             let iter = Box::new(std::iter::once(df));
@@ -247,29 +275,9 @@ impl IOThread {
         self.dump_iter(partition, iter)
     }
 
-    pub(in crate::executors::sinks) fn dump_partition_local(
-        &self,
-        partition_no: IdxSize,
-        mut df: DataFrame,
-    ) {
-        df.shrink_to_fit();
-        let count = self.thread_local_count.fetch_add(1, Ordering::Relaxed);
-        let mut path = self.dir.clone();
-        path.push(format!("{partition_no}"));
-
-        let _ = std::fs::create_dir(&path);
-        // thread local name we start with an underscore to ensure we don't get
-        // duplicates
-        path.push(format!("_{count}.ipc"));
-        let file = File::create(path).unwrap();
-        let writer = IpcWriter::new(file).with_compat_level(CompatLevel::newest());
-        let mut writer = writer.batched(&self.schema).unwrap();
-        writer.write_batch(&df).unwrap();
-        writer.finish().unwrap();
-    }
-
     pub(in crate::executors::sinks) fn dump_iter(&self, partition: Option<IdxCa>, iter: DfIter) {
         let add = iter.size_hint().1.unwrap();
+        self.accountant.start_spill();
         self.payload_tx.send((partition, iter)).unwrap();
         self.sent.fetch_add(add, Ordering::Relaxed);
     }
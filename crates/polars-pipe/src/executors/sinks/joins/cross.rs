@@ -1,28 +1,49 @@
 use std::any::Any;
 use std::iter::StepBy;
 use std::ops::Range;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 use std::vec;
 
+use polars_core::config::verbose;
 use polars_core::error::PolarsResult;
 use polars_core::frame::DataFrame;
+use polars_core::prelude::SchemaRef;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_io::ipc::IpcReader;
+use polars_io::SerReader;
 use polars_ops::prelude::CrossJoin as CrossJoinTrait;
 use polars_utils::arena::Node;
 use polars_utils::pl_str::PlSmallStr;
 
 use crate::executors::operators::PlaceHolder;
+use crate::executors::sinks::io::{block_thread_until_io_thread_done, IOThread};
+use crate::executors::sinks::memory::{MemTracker, MemoryAccountant};
 use crate::operators::{
-    chunks_to_df_unchecked, DataChunk, FinalizedSink, Operator, OperatorResult, PExecutionContext,
-    Sink, SinkResult,
+    DataChunk, FinalizedSink, Operator, OperatorResult, PExecutionContext, Sink, SinkResult,
 };
+use crate::pipeline::{morsels_per_sink, FORCE_OOC};
 
-#[derive(Default)]
+/// Buffers the build side of a cross join. If it doesn't fit in memory, it is spilled to disk
+/// and the probe side is joined against the spilled files one at a time.
 pub struct CrossJoin {
-    chunks: Vec<DataChunk>,
+    chunks: Vec<DataFrame>,
+    schema: SchemaRef,
     suffix: PlSmallStr,
     swapped: bool,
     node: Node,
     placeholder: PlaceHolder,
+    // Stores available memory in the system at the start of this sink.
+    // and stores the memory used by this this sink.
+    mem_track: MemTracker,
+    // buffer in-memory or out-of-core
+    ooc: bool,
+    // when ooc, we write to disk using an IO thread
+    io_thread: Arc<RwLock<Option<IOThread>>>,
+    // total bytes of tables in current chunks
+    current_chunks_size: usize,
+    // total rows sunk into this sink
+    n_rows: usize,
 }
 
 impl CrossJoin {
@@ -31,13 +52,58 @@ impl CrossJoin {
         swapped: bool,
         node: Node,
         placeholder: PlaceHolder,
-    ) -> Self {
-        CrossJoin {
+        schema: SchemaRef,
+        accountant: Arc<MemoryAccountant>,
+    ) -> PolarsResult<Self> {
+        let mut out = CrossJoin {
             chunks: vec![],
+            schema,
             suffix,
             swapped,
             node,
             placeholder,
+            mem_track: MemTracker::new(morsels_per_sink(), accountant),
+            ooc: false,
+            io_thread: Default::default(),
+            current_chunks_size: 0,
+            n_rows: 0,
+        };
+        // for testing purposes
+        if std::env::var(FORCE_OOC).is_ok() {
+            if verbose() {
+                eprintln!("OOC cross join forced");
+            }
+            out.init_ooc()?;
+        }
+        Ok(out)
+    }
+
+    fn init_ooc(&mut self) -> PolarsResult<()> {
+        if verbose() {
+            eprintln!("OOC cross join started");
+        }
+        self.ooc = true;
+
+        // start IO thread
+        let mut iot = self.io_thread.write().unwrap();
+        if iot.is_none() {
+            *iot = Some(IOThread::try_new(
+                self.schema.clone(),
+                "cross_join",
+                self.mem_track.accountant().clone(),
+            )?)
+        }
+        Ok(())
+    }
+
+    fn dump(&mut self, force: bool) {
+        let larger_than_32_mb = self.current_chunks_size > (1 << 25);
+        if (force || larger_than_32_mb) && !self.chunks.is_empty() {
+            // into a single chunk, as every file is read for every chunk of the probe side
+            let df = accumulate_dataframes_vertical_unchecked(self.chunks.drain(..));
+            let iot = self.io_thread.read().unwrap();
+            iot.as_ref().unwrap().dump_chunk(df);
+            self.current_chunks_size = 0;
         }
     }
 }
@@ -51,7 +117,25 @@ impl Sink for CrossJoin {
     }
 
     fn sink(&mut self, _context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
-        self.chunks.push(chunk);
+        let chunk_bytes = chunk.data.estimated_size();
+        if !self.ooc {
+            let used = self.mem_track.fetch_add(chunk_bytes);
+            let free = self.mem_track.get_available();
+
+            // The output is a multiple of the buffered side, so we keep
+            // 3x its size free before we go out of core.
+            if used * 3 > free {
+                self.init_ooc()?;
+                self.dump(true);
+            }
+        }
+        self.current_chunks_size += chunk_bytes;
+        self.n_rows += chunk.data.height();
+        self.chunks.push(chunk.data);
+
+        if self.ooc {
+            self.dump(false);
+        }
         Ok(SinkResult::CanHaveMoreInput)
     }
 
@@ -59,21 +143,71 @@ impl Sink for CrossJoin {
         let other = other.as_any().downcast_mut::<Self>().unwrap();
         let other_chunks = std::mem::take(&mut other.chunks);
         self.chunks.extend(other_chunks);
+        self.current_chunks_size += other.current_chunks_size;
+        self.n_rows += other.n_rows;
+        self.ooc |= other.ooc;
+
+        if self.ooc {
+            self.dump(false)
+        }
     }
 
     fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
         Box::new(Self {
+            chunks: vec![],
+            schema: self.schema.clone(),
             suffix: self.suffix.clone(),
             swapped: self.swapped,
+            node: self.node,
             placeholder: self.placeholder.clone(),
-            ..Default::default()
+            mem_track: self.mem_track.clone(),
+            ooc: self.ooc,
+            io_thread: self.io_thread.clone(),
+            current_chunks_size: 0,
+            n_rows: 0,
         })
     }
 
-    fn finalize(&mut self, _context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+    fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        let build = if self.ooc {
+            // spill everything
+            self.dump(true);
+            let io_thread = self.io_thread.write().unwrap().take().unwrap();
+            block_thread_until_io_thread_done(&io_thread);
+
+            let mut files = std::fs::read_dir(&io_thread.dir)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            // don't read the lock file
+            files.retain(|path| !path.ends_with(".lock"));
+            files.sort_unstable();
+            if context.verbose {
+                eprintln!(
+                    "spilled {} rows of the cross join build side to {} files",
+                    self.n_rows,
+                    files.len()
+                );
+            }
+            BuildSide::Spilled {
+                io_thread,
+                files,
+                schema: self.schema.clone(),
+                height: self.n_rows,
+            }
+        } else {
+            let chunks = std::mem::take(&mut self.chunks);
+            if chunks.is_empty() {
+                BuildSide::InMemory(DataFrame::empty_with_schema(&self.schema))
+            } else {
+                BuildSide::InMemory(accumulate_dataframes_vertical_unchecked(chunks))
+            }
+        };
+
         let op = Box::new(CrossJoinProbe {
-            df: Arc::new(chunks_to_df_unchecked(std::mem::take(&mut self.chunks))),
+            build: Arc::new(build),
             suffix: self.suffix.clone(),
+            part_idx: 0,
+            part_df: Default::default(),
             in_process_left: None,
             in_process_right: None,
             in_process_left_df: Default::default(),
@@ -94,10 +228,69 @@ impl Sink for CrossJoin {
     }
 }
 
+/// The buffered side of a cross join.
+enum BuildSide {
+    InMemory(DataFrame),
+    // Spilled to IPC files that are read one at a time.
+    Spilled {
+        io_thread: IOThread,
+        files: Vec<PathBuf>,
+        schema: SchemaRef,
+        height: usize,
+    },
+}
+
+impl BuildSide {
+    fn height(&self) -> usize {
+        match self {
+            Self::InMemory(df) => df.height(),
+            Self::Spilled { height, .. } => *height,
+        }
+    }
+
+    fn n_parts(&self) -> usize {
+        match self {
+            Self::InMemory(_) => 1,
+            Self::Spilled { files, .. } => files.len().max(1),
+        }
+    }
+
+    fn part(&self, idx: usize) -> PolarsResult<DataFrame> {
+        match self {
+            Self::InMemory(df) => Ok(df.clone()),
+            Self::Spilled { files, schema, .. } => match files.get(idx) {
+                Some(path) => read_df(path),
+                None => Ok(DataFrame::empty_with_schema(schema)),
+            },
+        }
+    }
+}
+
+impl Drop for BuildSide {
+    fn drop(&mut self) {
+        if let Self::Spilled {
+            io_thread, files, ..
+        } = self
+        {
+            for path in files.drain(..) {
+                io_thread.clean(path)
+            }
+        }
+    }
+}
+
+fn read_df(path: &Path) -> PolarsResult<DataFrame> {
+    let file = polars_utils::open_file(path)?;
+    IpcReader::new(file).set_rechunk(false).finish()
+}
+
 #[derive(Clone)]
 pub struct CrossJoinProbe {
-    df: Arc<DataFrame>,
+    build: Arc<BuildSide>,
     suffix: PlSmallStr,
+    // the part of the build side that is in process
+    part_idx: usize,
+    part_df: DataFrame,
     in_process_left: Option<StepBy<Range<usize>>>,
     in_process_right: Option<StepBy<Range<usize>>>,
     in_process_left_df: DataFrame,
@@ -105,6 +298,41 @@ pub struct CrossJoinProbe {
     swapped: bool,
 }
 
+impl CrossJoinProbe {
+    fn load_part(&mut self, idx: usize, size: usize) -> PolarsResult<()> {
+        self.part_df = self.build.part(idx)?;
+        self.part_idx = idx;
+        let mut iter_left = (0..self.part_df.height()).step_by(size);
+        let offset = iter_left.next().unwrap_or(0);
+        self.in_process_left_df = self.part_df.slice(offset as i64, size);
+        self.in_process_left = Some(iter_left);
+        Ok(())
+    }
+
+    fn cross_join(&mut self, right_df: &DataFrame) -> PolarsResult<DataFrame> {
+        let (a, b) = if self.swapped {
+            (right_df, &self.in_process_left_df)
+        } else {
+            (&self.in_process_left_df, right_df)
+        };
+
+        // we use the first join to determine the output names
+        // this we can amortize the name allocations.
+        let mut df = match &self.output_names {
+            None => {
+                let df = a.cross_join(b, Some(self.suffix.clone()), None)?;
+                self.output_names = Some(df.get_column_names_owned());
+                df
+            },
+            Some(names) => a._cross_join_with_names(b, names)?,
+        };
+        // Cross joins can produce multiple chunks.
+        // No parallelize in operators
+        df.as_single_chunk();
+        Ok(df)
+    }
+}
+
 impl Operator for CrossJoinProbe {
     fn execute(
         &mut self,
@@ -119,81 +347,39 @@ impl Operator for CrossJoinProbe {
         if chunk.data.height() > 0 {
             size *= (250 / chunk.data.height()).max(1);
         }
-        if self.df.height() > 0 {
-            size *= (250 / self.df.height()).max(1);
+        if self.build.height() > 0 {
+            size *= (250 / self.build.height()).max(1);
         }
 
+        // this will be the branch of the first call
         if self.in_process_left.is_none() {
-            let mut iter_left = (0..self.df.height()).step_by(size);
-            let offset = iter_left.next().unwrap_or(0);
-            self.in_process_left_df = self.df.slice(offset as i64, size);
-            self.in_process_left = Some(iter_left);
-        }
-        if self.in_process_right.is_none() {
+            self.load_part(0, size)?;
             self.in_process_right = Some((0..chunk.data.height()).step_by(size));
         }
-        // output size is large we process in chunks
-        let iter_left = self.in_process_left.as_mut().unwrap();
-        let iter_right = self.in_process_right.as_mut().unwrap();
 
-        match iter_right.next() {
-            None => {
-                self.in_process_right = None;
-
-                // if right is depleted take the next left chunk
-                match iter_left.next() {
-                    None => {
-                        self.in_process_left = None;
-                        Ok(OperatorResult::NeedsNewData)
-                    },
-                    Some(offset) => {
-                        self.in_process_left_df = self.df.slice(offset as i64, size);
-                        self.in_process_right = Some((0..chunk.data.height()).step_by(size));
-                        let iter_right = self.in_process_right.as_mut().unwrap();
-                        let offset = iter_right.next().unwrap_or(0);
-                        let right_df = chunk.data.slice(offset as i64, size);
-
-                        let (a, b) = if self.swapped {
-                            (&right_df, &self.in_process_left_df)
-                        } else {
-                            (&self.in_process_left_df, &right_df)
-                        };
-
-                        let mut df = a.cross_join(b, Some(self.suffix.clone()), None)?;
-                        // Cross joins can produce multiple chunks.
-                        // No parallelize in operators
-                        df.as_single_chunk();
-                        Ok(OperatorResult::HaveMoreOutPut(chunk.with_data(df)))
-                    },
-                }
-            },
+        loop {
             // deplete the right chunks over the current left chunk
-            Some(offset) => {
-                // this will be the branch of the first call
-
+            let iter_right = self.in_process_right.as_mut().unwrap();
+            if let Some(offset) = iter_right.next() {
                 let right_df = chunk.data.slice(offset as i64, size);
+                let df = self.cross_join(&right_df)?;
+                return Ok(OperatorResult::HaveMoreOutPut(chunk.with_data(df)));
+            }
 
-                let (a, b) = if self.swapped {
-                    (&right_df, &self.in_process_left_df)
-                } else {
-                    (&self.in_process_left_df, &right_df)
-                };
-
-                // we use the first join to determine the output names
-                // this we can amortize the name allocations.
-                let mut df = match &self.output_names {
-                    None => {
-                        let df = a.cross_join(b, Some(self.suffix.clone()), None)?;
-                        self.output_names = Some(df.get_column_names_owned());
-                        df
-                    },
-                    Some(names) => a._cross_join_with_names(b, names)?,
-                };
-                // Cross joins can produce multiple chunks.
-                df.as_single_chunk();
-
-                Ok(OperatorResult::HaveMoreOutPut(chunk.with_data(df)))
-            },
+            // if right is depleted take the next left chunk, from the next part
+            // of the build side if this one is depleted
+            match self.in_process_left.as_mut().unwrap().next() {
+                Some(offset) => self.in_process_left_df = self.part_df.slice(offset as i64, size),
+                None if self.part_idx + 1 < self.build.n_parts() => {
+                    self.load_part(self.part_idx + 1, size)?
+                },
+                None => {
+                    self.in_process_left = None;
+                    self.in_process_right = None;
+                    return Ok(OperatorResult::NeedsNewData);
+                },
+            }
+            self.in_process_right = Some((0..chunk.data.height()).step_by(size));
         }
     }
     fn split(&self, _thread_no: usize) -> Box<dyn Operator> {
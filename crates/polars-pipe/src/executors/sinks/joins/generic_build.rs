@@ -49,6 +49,10 @@ pub struct GenericBuild<K: ExtraPayload> {
     // the join order is swapped to ensure we hash the smaller table
     swapped: bool,
     join_nulls: bool,
+    // the hash table is built in a single partition, as the input is small
+    broadcast: bool,
+    // the join is hinted to never broadcast the hash table
+    partitioned: bool,
     node: Node,
     key_names_left: Arc<[PlSmallStr]>,
     key_names_right: Arc<[PlSmallStr]>,
@@ -64,13 +68,15 @@ impl<K: ExtraPayload> GenericBuild<K> {
         join_columns_left: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
         join_columns_right: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
         join_nulls: bool,
+        broadcast: bool,
+        partitioned: bool,
         node: Node,
         key_names_left: Arc<[PlSmallStr]>,
         key_names_right: Arc<[PlSmallStr]>,
         placeholder: PlaceHolder,
     ) -> Self {
         let hb: PlRandomState = Default::default();
-        let partitions = _set_partition_size();
+        let partitions = if broadcast { 1 } else { _set_partition_size() };
         let hash_tables = PartitionedHashMap::new(load_vec(partitions, || {
             PlIdHashMap::with_capacity(HASHMAP_INIT_SIZE)
         }));
@@ -87,6 +93,8 @@ impl<K: ExtraPayload> GenericBuild<K> {
             hash_tables,
             hashes: vec![],
             join_nulls,
+            broadcast,
+            partitioned,
             node,
             key_names_left,
             key_names_right,
@@ -146,6 +154,31 @@ impl<K: ExtraPayload> GenericBuild<K> {
         self.materialized_join_cols.push(rows_encoded);
         Ok(self.materialized_join_cols.last().unwrap())
     }
+
+    /// Merge the partitions of the hash table into a single one, so that the probes of the
+    /// streamed input don't need to find their partition. This is done at runtime for build
+    /// sides that turn out to be small, which the plan couldn't know.
+    fn broadcast_hash_tables(&mut self) {
+        let tables = std::mem::take(self.hash_tables.inner_mut());
+        let mut merged = PlIdHashMap::with_capacity_and_hasher(
+            tables.iter().map(|ht| ht.len()).sum(),
+            Default::default(),
+        );
+        for ht in tables {
+            for (k, v) in ht {
+                // The partitions hold disjoint keys, so there are no entries to compare with.
+                match merged.raw_entry_mut().from_hash(k.hash, |_| false) {
+                    RawEntryMut::Vacant(entry) => {
+                        entry.insert_hashed_nocheck(k.hash, k, v);
+                    },
+                    RawEntryMut::Occupied(_) => unreachable!(),
+                }
+            }
+        }
+        self.hash_tables = PartitionedHashMap::new(vec![merged]);
+        self.broadcast = true;
+    }
+
     unsafe fn get_row(&self, chunk_idx: ChunkIdx, df_idx: DfIdx) -> &[u8] {
         self.materialized_join_cols
             .get_unchecked_release(chunk_idx as usize)
@@ -283,6 +316,8 @@ impl<K: ExtraPayload> Sink for GenericBuild<K> {
             self.join_columns_left.clone(),
             self.join_columns_right.clone(),
             self.join_nulls,
+            self.broadcast,
+            self.partitioned,
             self.node,
             self.key_names_left.clone(),
             self.key_names_right.clone(),
@@ -302,6 +337,15 @@ impl<K: ExtraPayload> Sink for GenericBuild<K> {
         if left_df.height() > 0 {
             assert_eq!(left_df.n_chunks(), chunks_len);
         }
+        if !self.broadcast && !self.partitioned && left_df.height() <= BROADCAST_JOIN_ROWS {
+            if context.verbose {
+                eprintln!(
+                    "join build side has {} rows, broadcasting its hash table",
+                    left_df.height()
+                );
+            }
+            self.broadcast_hash_tables();
+        }
         // Reallocate to Arc<[]> to get rid of double indirection as this is accessed on every
         // hashtable cmp.
         let materialized_join_cols = Arc::from(std::mem::take(&mut self.materialized_join_cols));
@@ -344,7 +388,7 @@ impl<K: ExtraPayload> Sink for GenericBuild<K> {
                     suffix,
                     hb,
                     hash_tables,
-                    join_columns_left,
+                    join_columns_right,
                     self.swapped,
                     hashes,
                     self.join_nulls,
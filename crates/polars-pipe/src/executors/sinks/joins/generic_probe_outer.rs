@@ -134,8 +134,16 @@ impl<K: ExtraPayload> GenericFullOuterJoinProbe<K> {
         }
 
         if self.coalesce {
+            // `_coalesce_full_join` needs the frame that makes up the left columns of the output,
+            // to know which right key columns got a suffix. If the join order is swapped, `inner`
+            // puts `right_df` on the left, so that is the frame to pass.
+            let df_left = if self.swapped {
+                right_df.clone()
+            } else {
+                left_df.clone()
+            };
             let out = inner(
-                left_df.clone(),
+                left_df,
                 right_df,
                 self.suffix.clone(),
                 self.swapped,
@@ -148,7 +156,7 @@ impl<K: ExtraPayload> GenericFullOuterJoinProbe<K> {
                 l.as_slice(),
                 r.as_slice(),
                 Some(self.suffix.clone()),
-                &left_df,
+                &df_left,
             ))
         } else {
             inner(
@@ -4,6 +4,8 @@ mod generic_build;
 mod generic_probe_inner_left;
 mod generic_probe_outer;
 mod row_values;
+#[cfg(feature = "semi_anti_join")]
+mod semi_anti;
 
 use std::hash::{BuildHasherDefault, Hash, Hasher};
 use std::sync::atomic::AtomicBool;
@@ -17,6 +19,12 @@ use polars_ops::prelude::JoinType;
 use polars_utils::idx_vec::UnitVec;
 use polars_utils::index::ChunkId;
 use polars_utils::partitioned::PartitionedHashMap;
+#[cfg(feature = "semi_anti_join")]
+pub(crate) use semi_anti::*;
+
+/// Build sides of hash joins with at most this many rows are broadcast: their hash table is a
+/// single partition that all threads that stream the other input share.
+pub(crate) const BROADCAST_JOIN_ROWS: usize = 1 << 16;
 
 trait ToRow {
     fn get_row(&self) -> &[u8];
@@ -0,0 +1,158 @@
+use std::any::Any;
+
+use polars_core::prelude::*;
+use polars_utils::arena::Node;
+
+use crate::executors::operators::PlaceHolder;
+use crate::executors::sinks::joins::row_values::RowValues;
+use crate::expressions::PhysicalPipedExpr;
+use crate::operators::{
+    DataChunk, FinalizedSink, Operator, OperatorResult, PExecutionContext, Sink, SinkResult,
+};
+
+type KeySet = PlHashSet<Box<[u8]>>;
+
+/// Collects the distinct keys of the right table of a semi or anti join. Only the keys are
+/// kept, the rows of the left table are filtered by the [`SemiAntiJoinProbe`] operator.
+pub struct SemiAntiJoinBuild {
+    keys: KeySet,
+    anti: bool,
+    join_nulls: bool,
+    join_columns_left: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+    row_values: RowValues,
+    node: Node,
+    placeholder: PlaceHolder,
+}
+
+impl SemiAntiJoinBuild {
+    pub(crate) fn new(
+        anti: bool,
+        join_columns_left: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        join_columns_right: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        join_nulls: bool,
+        node: Node,
+        placeholder: PlaceHolder,
+    ) -> Self {
+        SemiAntiJoinBuild {
+            keys: Default::default(),
+            anti,
+            join_nulls,
+            join_columns_left,
+            row_values: RowValues::new(join_columns_right, false),
+            node,
+            placeholder,
+        }
+    }
+}
+
+impl Sink for SemiAntiJoinBuild {
+    fn node(&self) -> Node {
+        self.node
+    }
+    fn is_join_build(&self) -> bool {
+        true
+    }
+
+    fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        let rows = self
+            .row_values
+            .get_values(context, &chunk, self.join_nulls)?;
+        // Keys with nulls are invalid if nulls don't match.
+        for row in rows.iter().flatten() {
+            if !self.keys.contains(row) {
+                self.keys.insert(row.into());
+            }
+        }
+        self.row_values.clear();
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, other: &mut dyn Sink) {
+        let other = other.as_any().downcast_mut::<Self>().unwrap();
+        if other.keys.len() > self.keys.len() {
+            std::mem::swap(&mut self.keys, &mut other.keys);
+        }
+        self.keys.extend(other.keys.drain());
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+        Box::new(Self {
+            keys: Default::default(),
+            anti: self.anti,
+            join_nulls: self.join_nulls,
+            join_columns_left: self.join_columns_left.clone(),
+            row_values: self.row_values.clone(),
+            node: self.node,
+            placeholder: self.placeholder.clone(),
+        })
+    }
+
+    fn finalize(&mut self, _context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        let op = Box::new(SemiAntiJoinProbe {
+            keys: Arc::new(std::mem::take(&mut self.keys)),
+            anti: self.anti,
+            join_nulls: self.join_nulls,
+            row_values: RowValues::new(self.join_columns_left.clone(), false),
+        });
+        self.placeholder.replace(op);
+
+        Ok(FinalizedSink::Operator)
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        if self.anti {
+            "anti_join_sink"
+        } else {
+            "semi_join_sink"
+        }
+    }
+}
+
+/// Keeps the rows of the left table of which the key is (semi) or isn't (anti) in the right table.
+#[derive(Clone)]
+pub struct SemiAntiJoinProbe {
+    keys: Arc<KeySet>,
+    anti: bool,
+    join_nulls: bool,
+    row_values: RowValues,
+}
+
+impl Operator for SemiAntiJoinProbe {
+    fn execute(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<OperatorResult> {
+        let rows = self
+            .row_values
+            .get_values(context, chunk, self.join_nulls)?;
+        let mask = BooleanChunked::from_iter_values(
+            PlSmallStr::EMPTY,
+            rows.iter().map(|row| match row {
+                Some(row) => self.keys.contains(row) != self.anti,
+                // A key with nulls never matches.
+                None => self.anti,
+            }),
+        );
+        self.row_values.clear();
+
+        let df = chunk.data.filter(&mask)?;
+        Ok(OperatorResult::Finished(chunk.with_data(df)))
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Operator> {
+        Box::new(self.clone())
+    }
+
+    fn fmt(&self) -> &str {
+        if self.anti {
+            "anti_join_probe"
+        } else {
+            "semi_join_probe"
+        }
+    }
+}
@@ -1,14 +1,108 @@
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
+use polars_core::config::get_streaming_memory_limit;
 use polars_utils::sys::MEMINFO;
 
 use crate::pipeline::FORCE_OOC;
 
 const TO_MB: usize = 2 << 19;
 
+/// Accounts for the memory of a single streaming query. It is shared by all nodes of the query.
+///
+/// The memory of the query is the bytes that went into its sinks, without the bytes that the
+/// sinks spilled to disk and the input of the sinks that finished. If the query has a memory
+/// limit, the operators spill and the sources wait once this memory reaches the limit.
+pub struct MemoryAccountant {
+    limit: Option<usize>,
+    // the bytes that the sinks of the query hold in memory
+    buffered: AtomicUsize,
+    // the bytes that the sinks of the query have spilled to disk
+    spilled_bytes: AtomicU64,
+    // the spilled payloads that the IO threads have not written yet
+    pending_spills: AtomicUsize,
+}
+
+impl Default for MemoryAccountant {
+    /// An accountant with the limit of [`get_streaming_memory_limit`], if it is set.
+    fn default() -> Self {
+        Self::new(get_streaming_memory_limit())
+    }
+}
+
+impl MemoryAccountant {
+    /// Create the accountant of a query that may buffer `limit` bytes, if there is a limit.
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            buffered: Default::default(),
+            spilled_bytes: Default::default(),
+            pending_spills: Default::default(),
+        }
+    }
+
+    pub(crate) fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// The bytes that the sinks of the query hold in memory.
+    pub(crate) fn buffered(&self) -> usize {
+        self.buffered.load(Ordering::Relaxed)
+    }
+
+    /// Count `bytes` that went into a sink.
+    pub(crate) fn buffer(&self, bytes: usize) {
+        self.buffered.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Stop counting `bytes` that a sink no longer holds in memory.
+    pub(crate) fn release(&self, bytes: usize) {
+        let _ = self
+            .buffered
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |buffered| {
+                Some(buffered.saturating_sub(bytes))
+            });
+    }
+
+    /// The bytes that the query may still buffer, if it has a limit.
+    fn remaining(&self) -> Option<usize> {
+        self.limit
+            .map(|limit| limit.saturating_sub(self.buffered()))
+    }
+
+    /// Record that a sink spilled `bytes` to disk, which frees them.
+    pub(crate) fn record_spill(&self, bytes: usize) {
+        self.spilled_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.release(bytes);
+    }
+
+    /// The bytes that the sinks of the query have spilled to disk.
+    pub(crate) fn spilled_bytes(&self) -> u64 {
+        self.spilled_bytes.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn start_spill(&self) {
+        self.pending_spills.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn finish_spill(&self) {
+        self.pending_spills.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn has_pending_spills(&self) -> bool {
+        self.pending_spills.load(Ordering::Relaxed) > 0
+    }
+}
+
+/// Tracks the memory that is available to an operator.
+///
+/// If the query has a memory limit, see [`MemoryAccountant`], the available memory is the
+/// smaller of the part of the limit that the query doesn't use and the free memory of the
+/// system.
 #[derive(Clone)]
-pub(super) struct MemTracker {
+pub(crate) struct MemTracker {
     // available memory at the start of this node
     available_mem: Arc<AtomicUsize>,
     used_by_node: Arc<AtomicUsize>,
@@ -16,10 +110,11 @@ pub(super) struct MemTracker {
     thread_count: usize,
     available_at_start: usize,
     refresh_interval: usize,
+    accountant: Arc<MemoryAccountant>,
 }
 
 impl MemTracker {
-    pub(super) fn new(thread_count: usize) -> Self {
+    pub(crate) fn new(thread_count: usize, accountant: Arc<MemoryAccountant>) -> Self {
         let refresh_interval = if std::env::var(FORCE_OOC).is_ok() {
             1
         } else {
@@ -33,17 +128,36 @@ impl MemTracker {
             thread_count,
             available_at_start: 0,
             refresh_interval,
+            accountant,
+        };
+        let free = MEMINFO.free() as usize;
+        out.available_mem
+            .store(out.within_budget(free), Ordering::Relaxed);
+        // the fraction of the memory that is free is relative to the whole limit, also if the
+        // query already uses part of it
+        out.available_at_start = match out.accountant.limit() {
+            Some(limit) => free.min(limit),
+            None => free,
         };
-        let available = MEMINFO.free() as usize;
-        out.available_mem.store(available, Ordering::Relaxed);
-        out.available_at_start = available;
         out
     }
 
+    /// The accountant of the query of this node.
+    pub(crate) fn accountant(&self) -> &Arc<MemoryAccountant> {
+        &self.accountant
+    }
+
+    fn within_budget(&self, free: usize) -> usize {
+        match self.accountant.remaining() {
+            Some(remaining) => free.min(remaining),
+            None => free,
+        }
+    }
+
     /// This shouldn't be called often as this is expensive.
     pub fn refresh_memory(&self) {
-        self.available_mem
-            .store(MEMINFO.free() as usize, Ordering::Relaxed);
+        let available = self.within_budget(MEMINFO.free() as usize);
+        self.available_mem.store(available, Ordering::Relaxed);
     }
 
     /// Get available memory of the system measured on latest refresh.
@@ -63,7 +177,7 @@ impl MemTracker {
         self.available_mem.load(Ordering::Relaxed)
     }
 
-    pub(super) fn free_memory_fraction_since_start(&self) -> f64 {
+    pub(crate) fn free_memory_fraction_since_start(&self) -> f64 {
         // We divide first to reduce the precision loss in floats.
         // We also add 1.0 to available_at_start to prevent division by zero.
         let available_at_start = (self.available_at_start / TO_MB) as f64 + 1.0;
@@ -75,4 +189,15 @@ impl MemTracker {
     pub(super) fn fetch_add(&self, add: usize) -> usize {
         self.used_by_node.fetch_add(add, Ordering::Relaxed)
     }
+
+    /// Applies backpressure on the sources: blocks while the memory budget is exhausted and
+    /// spilled chunks are still waiting to be written, as writing them frees memory.
+    pub(crate) fn wait_for_budget(&self) {
+        if self.accountant.limit().is_none() {
+            return;
+        }
+        while self.accountant.has_pending_spills() && self.get_available_latest() == 0 {
+            std::thread::park_timeout(Duration::from_millis(6))
+        }
+    }
 }
@@ -1,28 +1,34 @@
+mod fan_out;
 pub(crate) mod group_by;
 mod io;
 mod joins;
-mod memory;
+pub(crate) mod memory;
 mod ordered;
 mod output;
-mod reproject;
 mod slice;
 mod sort;
+mod tee;
+mod unique;
 mod utils;
+pub(crate) mod window;
 
 use std::sync::OnceLock;
 
+pub(crate) use fan_out::*;
 pub(crate) use joins::*;
 pub(crate) use ordered::*;
 #[cfg(any(
     feature = "parquet",
     feature = "ipc",
     feature = "csv",
-    feature = "json"
+    feature = "json",
+    feature = "flight"
 ))]
 pub(crate) use output::*;
-pub(crate) use reproject::*;
 pub(crate) use slice::*;
 pub(crate) use sort::*;
+pub(crate) use tee::*;
+pub(crate) use unique::*;
 
 // We must strike a balance between cache coherence and resizing costs.
 // Overallocation seems a lot more expensive than resizing so we start reasonable small.
@@ -30,9 +36,12 @@ const HASHMAP_INIT_SIZE: usize = 64;
 
 pub(crate) static POLARS_TEMP_DIR: OnceLock<String> = OnceLock::new();
 
+/// The directory operations spill to. `POLARS_SPILL_DIR` takes precedence over
+/// `POLARS_TEMP_DIR`, so that spilling can use another disk than the file cache.
 pub(crate) fn get_base_temp_dir() -> &'static str {
     POLARS_TEMP_DIR.get_or_init(|| {
-        let tmp = std::env::var("POLARS_TEMP_DIR")
+        let tmp = std::env::var("POLARS_SPILL_DIR")
+            .or_else(|_| std::env::var("POLARS_TEMP_DIR"))
             .unwrap_or_else(|_| std::env::temp_dir().to_string_lossy().into_owned());
 
         if polars_core::config::verbose() {
@@ -24,7 +24,9 @@ impl OrderedSink {
     }
 
     fn sort(&mut self) {
-        self.chunks.sort_unstable_by_key(|chunk| chunk.chunk_index);
+        // The sort is stable, as the parts of a chunk that an operator splits have the same
+        // index and are sunk in order.
+        self.chunks.sort_by_key(|chunk| chunk.chunk_index);
     }
 }
 
@@ -1,11 +1,14 @@
+use std::fs::File;
+use std::io::Write;
 use std::path::Path;
 
 use crossbeam_channel::bounded;
 use polars_core::prelude::*;
-use polars_io::csv::write::{CsvWriter, CsvWriterOptions};
-use polars_io::SerWriter;
+use polars_io::csv::write::{BatchedWriter, CsvCompression, CsvWriter, CsvWriterOptions};
+use polars_io::{PartitionedWriteOptions, SerWriter};
 
 use crate::executors::sinks::output::file_sink::{init_writer_thread, FilesSink, SinkWriter};
+use crate::executors::sinks::output::partitioned::{PartitionFiles, PartitionedWriter};
 use crate::pipeline::morsels_per_sink;
 
 pub struct CsvSink {}
@@ -13,24 +16,20 @@ impl CsvSink {
     #[allow(clippy::new_ret_no_self)]
     pub fn new(path: &Path, options: CsvWriterOptions, schema: &Schema) -> PolarsResult<FilesSink> {
         let file = std::fs::File::create(path)?;
-        let writer = CsvWriter::new(file)
-            .include_bom(options.include_bom)
-            .include_header(options.include_header)
-            .with_separator(options.serialize_options.separator)
-            .with_line_terminator(options.serialize_options.line_terminator)
-            .with_quote_char(options.serialize_options.quote_char)
-            .with_batch_size(options.batch_size)
-            .with_datetime_format(options.serialize_options.datetime_format)
-            .with_date_format(options.serialize_options.date_format)
-            .with_time_format(options.serialize_options.time_format)
-            .with_float_scientific(options.serialize_options.float_scientific)
-            .with_float_precision(options.serialize_options.float_precision)
-            .with_null_value(options.serialize_options.null)
-            .with_quote_style(options.serialize_options.quote_style)
-            .n_threads(1)
-            .batched(schema)?;
+        Self::new_with_writer(file, options, schema)
+    }
 
-        let writer = Box::new(writer) as Box<dyn SinkWriter + Send + Sync>;
+    /// Write the chunks to `writer`, e.g. a socket, instead of a file.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new_with_writer<W: Write + Send + 'static>(
+        writer: W,
+        options: CsvWriterOptions,
+        schema: &Schema,
+    ) -> PolarsResult<FilesSink> {
+        let maintain_order = options.maintain_order;
+        let writer = batched_writer(writer, options, schema)?;
+
+        let writer = Box::new(writer) as Box<dyn SinkWriter + Send>;
 
         let morsels_per_sink = morsels_per_sink();
         let backpressure = morsels_per_sink * 2;
@@ -39,7 +38,7 @@ impl CsvSink {
         let io_thread_handle = Arc::new(Some(init_writer_thread(
             receiver,
             writer,
-            options.maintain_order,
+            maintain_order,
             morsels_per_sink,
         )));
 
@@ -50,7 +49,31 @@ impl CsvSink {
     }
 }
 
-impl SinkWriter for polars_io::csv::write::BatchedWriter<std::fs::File> {
+fn batched_writer<W: Write>(
+    writer: W,
+    options: CsvWriterOptions,
+    schema: &Schema,
+) -> PolarsResult<BatchedWriter<W>> {
+    CsvWriter::new(writer)
+        .include_bom(options.include_bom)
+        .include_header(options.include_header)
+        .with_separator(options.serialize_options.separator)
+        .with_line_terminator(options.serialize_options.line_terminator)
+        .with_quote_char(options.serialize_options.quote_char)
+        .with_batch_size(options.batch_size)
+        .with_datetime_format(options.serialize_options.datetime_format)
+        .with_date_format(options.serialize_options.date_format)
+        .with_time_format(options.serialize_options.time_format)
+        .with_float_scientific(options.serialize_options.float_scientific)
+        .with_float_precision(options.serialize_options.float_precision)
+        .with_null_value(options.serialize_options.null)
+        .with_quote_style(options.serialize_options.quote_style)
+        .with_compression(options.compression)
+        .n_threads(1)
+        .batched(schema)
+}
+
+impl<W: Write> SinkWriter for BatchedWriter<W> {
     fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
         self.write_batch(df)
     }
@@ -59,3 +82,79 @@ impl SinkWriter for polars_io::csv::write::BatchedWriter<std::fs::File> {
         self.finish()
     }
 }
+
+pub struct PartitionedCsvSink {}
+impl PartitionedCsvSink {
+    /// Write the chunks to a file per partition, in a hive-partitioned directory layout under
+    /// `path`, e.g. `path/year=2024/month=1/00000000.csv`. The partition columns are not written
+    /// to the files.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        path: &Path,
+        partition_by: &[PlSmallStr],
+        options: CsvWriterOptions,
+        partition_options: PartitionedWriteOptions,
+        schema: &Schema,
+    ) -> PolarsResult<FilesSink> {
+        polars_ensure!(
+            !partition_options.write_metadata,
+            InvalidOperation: "writing a '_metadata' file is only supported for Parquet"
+        );
+        let mut file_schema = schema.clone();
+        for name in partition_by {
+            file_schema.shift_remove(name);
+        }
+
+        let maintain_order = options.maintain_order;
+        let writer = PartitionedWriter::new(
+            path,
+            partition_by,
+            schema,
+            true,
+            partition_options.max_rows_per_file,
+            CsvFiles {
+                options,
+                file_schema,
+            },
+        )?;
+        let writer = Box::new(writer) as Box<dyn SinkWriter + Send + Sync>;
+
+        let morsels_per_sink = morsels_per_sink();
+        let backpressure = morsels_per_sink * 2;
+        let (sender, receiver) = bounded(backpressure);
+
+        let io_thread_handle = Arc::new(Some(init_writer_thread(
+            receiver,
+            writer,
+            maintain_order,
+            morsels_per_sink,
+        )));
+
+        Ok(FilesSink {
+            sender,
+            io_thread_handle,
+        })
+    }
+}
+
+struct CsvFiles {
+    options: CsvWriterOptions,
+    // Schema of the written files, without the partition columns.
+    file_schema: Schema,
+}
+
+impl PartitionFiles for CsvFiles {
+    type Writer = BatchedWriter<File>;
+
+    fn extension(&self) -> &'static str {
+        match self.options.compression {
+            CsvCompression::Uncompressed => "csv",
+            CsvCompression::Gzip(_) => "csv.gz",
+            CsvCompression::Zstd(_) => "csv.zst",
+        }
+    }
+
+    fn create(&mut self, file: File) -> PolarsResult<Self::Writer> {
+        batched_writer(file, self.options.clone(), &self.file_schema)
+    }
+}
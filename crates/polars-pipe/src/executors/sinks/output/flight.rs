@@ -0,0 +1,45 @@
+use crossbeam_channel::bounded;
+use polars_core::prelude::*;
+use polars_io::flight::{FlightDescriptor, FlightWriter};
+
+use crate::executors::sinks::output::file_sink::{init_writer_thread, FilesSink, SinkWriter};
+use crate::pipeline::morsels_per_sink;
+
+pub struct FlightSink {}
+impl FlightSink {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        endpoint: &str,
+        descriptor: &FlightDescriptor,
+        schema: &Schema,
+    ) -> PolarsResult<FilesSink> {
+        let writer = FlightWriter::try_new(endpoint, descriptor, schema)?;
+        let writer = Box::new(writer) as Box<dyn SinkWriter + Send>;
+
+        let morsels_per_sink = morsels_per_sink();
+        let backpressure = morsels_per_sink * 2;
+        let (sender, receiver) = bounded(backpressure);
+
+        let io_thread_handle = Arc::new(Some(init_writer_thread(
+            receiver,
+            writer,
+            true,
+            morsels_per_sink,
+        )));
+
+        Ok(FilesSink {
+            sender,
+            io_thread_handle,
+        })
+    }
+}
+
+impl SinkWriter for FlightWriter {
+    fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        self.write_batch(df)
+    }
+
+    fn _finish(&mut self) -> PolarsResult<()> {
+        self.finish()
+    }
+}
@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::path::Path;
 
 use crossbeam_channel::bounded;
@@ -13,7 +14,17 @@ impl IpcSink {
     #[allow(clippy::new_ret_no_self)]
     pub fn new(path: &Path, options: IpcWriterOptions, schema: &Schema) -> PolarsResult<FilesSink> {
         let file = std::fs::File::create(path)?;
-        let writer = IpcWriter::new(file)
+        Self::new_with_writer(file, options, schema)
+    }
+
+    /// Write the chunks to `writer`, e.g. a socket, instead of a file.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new_with_writer<W: Write + Send + 'static>(
+        writer: W,
+        options: IpcWriterOptions,
+        schema: &Schema,
+    ) -> PolarsResult<FilesSink> {
+        let writer = IpcWriter::new(writer)
             .with_compression(options.compression)
             .batched(schema)?;
 
@@ -37,6 +48,43 @@ impl IpcSink {
     }
 }
 
+/// Writes the chunks to a writer in the Arrow IPC stream format, so that a reader can process
+/// the batches as they are written.
+#[cfg(feature = "ipc_streaming")]
+pub struct IpcStreamSink {}
+#[cfg(feature = "ipc_streaming")]
+impl IpcStreamSink {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new_with_writer<W: Write + Send + 'static>(
+        writer: W,
+        options: IpcWriterOptions,
+        schema: &Schema,
+    ) -> PolarsResult<FilesSink> {
+        let writer = IpcStreamWriter::new(writer)
+            .with_compression(options.compression)
+            .with_compat_level(CompatLevel::newest())
+            .batched(schema)?;
+
+        let writer = Box::new(writer) as Box<dyn SinkWriter + Send>;
+
+        let morsels_per_sink = morsels_per_sink();
+        let backpressure = morsels_per_sink * 2;
+        let (sender, receiver) = bounded(backpressure);
+
+        let io_thread_handle = Arc::new(Some(init_writer_thread(
+            receiver,
+            writer,
+            options.maintain_order,
+            morsels_per_sink,
+        )));
+
+        Ok(FilesSink {
+            sender,
+            io_thread_handle,
+        })
+    }
+}
+
 #[cfg(feature = "cloud")]
 pub struct IpcCloudSink {}
 #[cfg(feature = "cloud")]
@@ -85,3 +133,14 @@ impl<W: std::io::Write> SinkWriter for polars_io::ipc::BatchedWriter<W> {
         Ok(())
     }
 }
+
+#[cfg(feature = "ipc_streaming")]
+impl<W: std::io::Write> SinkWriter for polars_io::ipc::BatchedIpcStreamWriter<W> {
+    fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        self.write_batch(df)
+    }
+
+    fn _finish(&mut self) -> PolarsResult<()> {
+        self.finish()
+    }
+}
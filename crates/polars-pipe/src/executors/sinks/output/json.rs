@@ -26,7 +26,11 @@ impl JsonSink {
         _schema: &Schema,
     ) -> PolarsResult<FilesSink> {
         let file = std::fs::File::create(path)?;
-        let writer = BatchedWriter::new(file);
+        let writer = BatchedWriter::new(file)
+            .with_date_format(options.date_format)
+            .with_time_format(options.time_format)
+            .with_datetime_format(options.datetime_format)
+            .with_null_value(options.null_value);
 
         let writer = Box::new(writer) as Box<dyn SinkWriter + Send + Sync>;
 
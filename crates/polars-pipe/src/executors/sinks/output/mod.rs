@@ -4,18 +4,25 @@ mod csv;
     feature = "parquet",
     feature = "ipc",
     feature = "csv",
-    feature = "json"
+    feature = "json",
+    feature = "flight"
 ))]
 mod file_sink;
+#[cfg(feature = "flight")]
+mod flight;
 #[cfg(feature = "ipc")]
 mod ipc;
 #[cfg(feature = "json")]
 mod json;
 #[cfg(feature = "parquet")]
 mod parquet;
+#[cfg(any(feature = "parquet", feature = "csv"))]
+mod partitioned;
 
 #[cfg(feature = "csv")]
 pub use csv::*;
+#[cfg(feature = "flight")]
+pub use flight::*;
 #[cfg(feature = "ipc")]
 pub use ipc::*;
 #[cfg(feature = "json")]
@@ -1,18 +1,21 @@
 use std::any::Any;
+use std::io::Write;
 use std::path::Path;
 use std::thread::JoinHandle;
 
 use crossbeam_channel::{bounded, Receiver, Sender};
 use polars_core::prelude::*;
-use polars_io::parquet::write::{
-    BatchedWriter, ParquetWriteOptions, ParquetWriter, RowGroupIterColumns,
-};
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_io::parquet::write::{BatchedWriter, EncodedRowGroup, ParquetWriteOptions};
+use polars_io::partition::DatasetMetadata;
+use polars_io::PartitionedWriteOptions;
 
 use crate::executors::sinks::output::file_sink::{init_writer_thread, FilesSink, SinkWriter};
+use crate::executors::sinks::output::partitioned::{PartitionFiles, PartitionedWriter};
 use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult};
 use crate::pipeline::morsels_per_sink;
 
-type RowGroups = Vec<RowGroupIterColumns<'static, PolarsError>>;
+type RowGroups = Vec<EncodedRowGroup>;
 
 pub(super) fn init_row_group_writer_thread(
     receiver: Receiver<Option<(IdxSize, RowGroups)>>,
@@ -61,11 +64,8 @@ impl ParquetSink {
     #[allow(clippy::new_ret_no_self)]
     pub fn new(path: &Path, options: ParquetWriteOptions, schema: &Schema) -> PolarsResult<Self> {
         let file = std::fs::File::create(path)?;
-        let writer = ParquetWriter::new(file)
-            .with_compression(options.compression)
-            .with_data_page_size(options.data_page_size)
-            .with_statistics(options.statistics)
-            .with_row_group_size(options.row_group_size)
+        let writer = options
+            .to_writer(file)
             // This is important! Otherwise we will deadlock
             // See: #7074
             .set_parallel(false)
@@ -91,6 +91,121 @@ impl ParquetSink {
     }
 }
 
+/// Writes row groups of `row_group_size` rows, independent of the size of the morsels.
+///
+/// The morsels are collected in order on the io thread, so unlike [`ParquetSink`] the row groups
+/// are also encoded and compressed there.
+pub struct ParquetRowGroupSink {}
+impl ParquetRowGroupSink {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        path: &Path,
+        options: ParquetWriteOptions,
+        schema: &Schema,
+    ) -> PolarsResult<FilesSink> {
+        let file = std::fs::File::create(path)?;
+        let writer = sink_writer(file, &options, schema)?;
+
+        let morsels_per_sink = morsels_per_sink();
+        let backpressure = morsels_per_sink * 2;
+        let (sender, receiver) = bounded(backpressure);
+
+        let io_thread_handle = Arc::new(Some(init_writer_thread(
+            receiver,
+            writer,
+            true,
+            morsels_per_sink,
+        )));
+
+        Ok(FilesSink {
+            sender,
+            io_thread_handle,
+        })
+    }
+}
+
+fn options_to_batched_writer<W: Write>(
+    writer: W,
+    options: &ParquetWriteOptions,
+    schema: &Schema,
+) -> PolarsResult<BatchedWriter<W>> {
+    options
+        .to_writer(writer)
+        // This is important! Otherwise we will deadlock
+        // See: #7074
+        .set_parallel(false)
+        .batched(schema)
+}
+
+fn sink_writer<W: Write + Send + 'static>(
+    writer: W,
+    options: &ParquetWriteOptions,
+    schema: &Schema,
+) -> PolarsResult<Box<dyn SinkWriter + Send>> {
+    let writer = options_to_batched_writer(writer, options, schema)?;
+
+    Ok(Box::new(RowGroupWriter::new(
+        writer,
+        options.row_group_size,
+    )))
+}
+
+/// Buffers the batches until they fill a row group. If no `row_group_size` is set, every batch
+/// is written as a row group.
+struct RowGroupWriter<W: Write> {
+    writer: BatchedWriter<W>,
+    row_group_size: Option<usize>,
+    buffer: Vec<DataFrame>,
+    n_buffered: usize,
+}
+
+impl<W: Write> RowGroupWriter<W> {
+    fn new(writer: BatchedWriter<W>, row_group_size: Option<usize>) -> Self {
+        Self {
+            writer,
+            row_group_size: row_group_size.map(|size| size.max(1)),
+            buffer: vec![],
+            n_buffered: 0,
+        }
+    }
+
+    fn write_row_group(&mut self) -> PolarsResult<()> {
+        let mut df = accumulate_dataframes_vertical_unchecked(self.buffer.drain(..));
+        self.n_buffered = 0;
+        // Every chunk is written as a row group.
+        df.as_single_chunk();
+        self.writer.write_batch(&df)
+    }
+}
+
+impl<W: Write> SinkWriter for RowGroupWriter<W> {
+    fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        let Some(row_group_size) = self.row_group_size else {
+            return self.writer.write_batch(df);
+        };
+        let mut df = df.clone();
+        while self.n_buffered + df.height() >= row_group_size {
+            let (head, tail) = df.split_at((row_group_size - self.n_buffered) as i64);
+            self.buffer.push(head);
+            self.write_row_group()?;
+            df = tail;
+        }
+        if df.height() > 0 {
+            self.n_buffered += df.height();
+            self.buffer.push(df);
+        }
+        Ok(())
+    }
+
+    fn _finish(&mut self) -> PolarsResult<()> {
+        if self.n_buffered > 0 {
+            self.write_row_group()?;
+        }
+        self.writer.finish()?;
+        Ok(())
+    }
+}
+
 impl Sink for ParquetSink {
     fn sink(&mut self, _context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
         // Encode and compress row-groups on every thread.
@@ -139,6 +254,23 @@ impl Sink for ParquetSink {
     }
 }
 
+/// Completes the multipart upload after the footer is written, so that the errors of the upload
+/// are returned by the sink instead of being lost when the writer is dropped.
+#[cfg(feature = "cloud")]
+struct CloudRowGroupWriter(RowGroupWriter<polars_io::cloud::CloudWriter>);
+
+#[cfg(feature = "cloud")]
+impl SinkWriter for CloudRowGroupWriter {
+    fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        self.0._write_batch(df)
+    }
+
+    fn _finish(&mut self) -> PolarsResult<()> {
+        self.0._finish()?;
+        self.0.writer.get_writer().lock().unwrap().get_mut().close()
+    }
+}
+
 #[cfg(feature = "cloud")]
 pub struct ParquetCloudSink {}
 #[cfg(feature = "cloud")]
@@ -152,17 +284,11 @@ impl ParquetCloudSink {
     ) -> PolarsResult<FilesSink> {
         polars_io::pl_async::get_runtime().block_on_potential_spawn(async {
             let cloud_writer = polars_io::cloud::CloudWriter::new(uri, cloud_options).await?;
-            let writer = ParquetWriter::new(cloud_writer)
-                .with_compression(parquet_options.compression)
-                .with_data_page_size(parquet_options.data_page_size)
-                .with_statistics(parquet_options.statistics)
-                .with_row_group_size(parquet_options.row_group_size)
-                // This is important! Otherwise we will deadlock
-                // See: #7074
-                .set_parallel(false)
-                .batched(schema)?;
-
-            let writer = Box::new(writer) as Box<dyn SinkWriter + Send>;
+            let writer = options_to_batched_writer(cloud_writer, &parquet_options, schema)?;
+            let writer = Box::new(CloudRowGroupWriter(RowGroupWriter::new(
+                writer,
+                parquet_options.row_group_size,
+            )));
 
             let morsels_per_sink = morsels_per_sink();
             let backpressure = morsels_per_sink * 2;
@@ -183,13 +309,88 @@ impl ParquetCloudSink {
     }
 }
 
-impl<W: std::io::Write> SinkWriter for polars_io::parquet::write::BatchedWriter<W> {
-    fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
-        self.write_batch(df)
+pub struct PartitionedParquetSink {}
+impl PartitionedParquetSink {
+    /// Write the chunks to a file per partition, in a hive-partitioned directory layout under
+    /// `path`, e.g. `path/year=2024/month=1/00000000.parquet`. The partition columns are also
+    /// written to the files.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        path: &Path,
+        partition_by: &[PlSmallStr],
+        options: ParquetWriteOptions,
+        partition_options: PartitionedWriteOptions,
+        schema: &Schema,
+    ) -> PolarsResult<FilesSink> {
+        let maintain_order = options.maintain_order;
+        let writer = PartitionedWriter::new(
+            path,
+            partition_by,
+            schema,
+            false,
+            partition_options.max_rows_per_file,
+            ParquetFiles {
+                options,
+                schema: schema.clone(),
+                metadata: partition_options
+                    .write_metadata
+                    .then(DatasetMetadata::default),
+            },
+        )?;
+        let writer = Box::new(writer) as Box<dyn SinkWriter + Send>;
+
+        let morsels_per_sink = morsels_per_sink();
+        let backpressure = morsels_per_sink * 2;
+        let (sender, receiver) = bounded(backpressure);
+
+        let io_thread_handle = Arc::new(Some(init_writer_thread(
+            receiver,
+            writer,
+            maintain_order,
+            morsels_per_sink,
+        )));
+
+        Ok(FilesSink {
+            sender,
+            io_thread_handle,
+        })
     }
+}
 
-    fn _finish(&mut self) -> PolarsResult<()> {
-        self.finish()?;
-        Ok(())
+struct ParquetFiles {
+    options: ParquetWriteOptions,
+    schema: Schema,
+    // The footers of the finished files, if a `_metadata` file is written.
+    metadata: Option<DatasetMetadata>,
+}
+
+impl PartitionFiles for ParquetFiles {
+    type Writer = RowGroupWriter<std::fs::File>;
+
+    fn extension(&self) -> &'static str {
+        "parquet"
+    }
+
+    fn create(&mut self, file: std::fs::File) -> PolarsResult<Self::Writer> {
+        let writer = self
+            .options
+            .to_writer(file)
+            // Don't deadlock the io thread, see: #7074
+            .set_parallel(false)
+            .batched(&self.schema)?;
+        Ok(RowGroupWriter::new(writer, self.options.row_group_size))
+    }
+
+    fn file_finished(&mut self, writer: &Self::Writer, relative_path: String) {
+        if let Some(metadata) = &self.metadata {
+            metadata.push(relative_path, writer.writer.metadata().unwrap());
+        }
+    }
+
+    fn finish(&mut self, base_path: &Path) -> PolarsResult<()> {
+        match self.metadata.take() {
+            Some(metadata) => metadata.write(base_path),
+            None => Ok(()),
+        }
     }
 }
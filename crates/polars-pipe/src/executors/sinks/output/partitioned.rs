@@ -0,0 +1,159 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_io::hive::get_hive_path_part;
+
+use crate::executors::sinks::output::file_sink::SinkWriter;
+
+/// The maximum number of partitions that have an open file. If a chunk of a new partition
+/// arrives when this limit is reached, the file of the least recently written partition is
+/// finished. Later chunks of that partition are written to a new file in the same directory.
+const MAX_OPEN_PARTITIONS: usize = 64;
+
+/// Creates and finishes the files of a [`PartitionedWriter`].
+pub(super) trait PartitionFiles {
+    type Writer: SinkWriter;
+
+    /// The extension of the file names, without the leading dot.
+    fn extension(&self) -> &'static str;
+
+    /// Create a writer for a new file.
+    fn create(&mut self, file: File) -> PolarsResult<Self::Writer>;
+
+    /// Called after `writer` is finished. `relative_path` is the path of the file relative to
+    /// the root of the dataset.
+    fn file_finished(&mut self, _writer: &Self::Writer, _relative_path: String) {}
+
+    /// Called after all files are finished.
+    fn finish(&mut self, _base_path: &Path) -> PolarsResult<()> {
+        Ok(())
+    }
+}
+
+struct OpenFile<W> {
+    writer: W,
+    n_rows: usize,
+    relative_path: String,
+}
+
+/// Writes the chunks to a file per partition, in a hive-partitioned directory layout, e.g.
+/// `path/year=2024/month=1/00000000.csv`.
+pub(super) struct PartitionedWriter<F: PartitionFiles> {
+    base_path: PathBuf,
+    partition_by: Vec<PlSmallStr>,
+    partition_by_col_idx: Vec<usize>,
+    drop_partition_columns: bool,
+    max_rows_per_file: Option<usize>,
+    files: F,
+    // The open files per partition path, ordered from least to most recently written.
+    open_files: PlIndexMap<String, OpenFile<F::Writer>>,
+    // The number of files that are created per partition path.
+    n_files: PlHashMap<String, usize>,
+}
+
+impl<F: PartitionFiles> PartitionedWriter<F> {
+    pub(super) fn new(
+        base_path: &Path,
+        partition_by: &[PlSmallStr],
+        schema: &Schema,
+        drop_partition_columns: bool,
+        max_rows_per_file: Option<usize>,
+        files: F,
+    ) -> PolarsResult<Self> {
+        polars_ensure!(
+            !partition_by.is_empty(),
+            InvalidOperation: "partitioned sink requires at least one partition column"
+        );
+        polars_ensure!(
+            max_rows_per_file != Some(0),
+            InvalidOperation: "'max_rows_per_file' must be greater than 0"
+        );
+        let partition_by_col_idx = partition_by
+            .iter()
+            .map(|name| schema.try_index_of(name))
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        Ok(Self {
+            base_path: base_path.to_path_buf(),
+            partition_by: partition_by.to_vec(),
+            partition_by_col_idx,
+            drop_partition_columns,
+            max_rows_per_file,
+            files,
+            open_files: Default::default(),
+            n_files: Default::default(),
+        })
+    }
+
+    fn finish_file(&mut self, mut file: OpenFile<F::Writer>) -> PolarsResult<()> {
+        file.writer._finish()?;
+        self.files.file_finished(&file.writer, file.relative_path);
+        Ok(())
+    }
+
+    fn open_file(&mut self, path_part: &str) -> PolarsResult<OpenFile<F::Writer>> {
+        if self.open_files.len() >= MAX_OPEN_PARTITIONS {
+            if let Some((_, file)) = self.open_files.shift_remove_index(0) {
+                self.finish_file(file)?;
+            }
+        }
+
+        let dir = self.base_path.join(path_part);
+        std::fs::create_dir_all(&dir)?;
+        let n_files = self.n_files.entry(path_part.to_string()).or_default();
+        // Use a fixed-width file name so that it sorts properly.
+        let file_name = format!("{:08x}.{}", *n_files, self.files.extension());
+        *n_files += 1;
+
+        let file = File::create(dir.join(&file_name))?;
+        Ok(OpenFile {
+            writer: self.files.create(file)?,
+            n_rows: 0,
+            relative_path: format!("{path_part}/{file_name}"),
+        })
+    }
+}
+
+impl<F: PartitionFiles> SinkWriter for PartitionedWriter<F> {
+    fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        for part_df in df.partition_by_stable(self.partition_by.iter().cloned(), true)? {
+            let path_part = get_hive_path_part(&part_df, &self.partition_by_col_idx);
+            let mut part_df = if self.drop_partition_columns {
+                part_df.drop_many(self.partition_by.iter().cloned())
+            } else {
+                part_df
+            };
+
+            let mut file = match self.open_files.shift_remove(&path_part) {
+                Some(file) => file,
+                None => self.open_file(&path_part)?,
+            };
+            if let Some(max_rows_per_file) = self.max_rows_per_file {
+                while file.n_rows + part_df.height() > max_rows_per_file {
+                    let (head, tail) = part_df.split_at((max_rows_per_file - file.n_rows) as i64);
+                    if head.height() > 0 {
+                        file.writer._write_batch(&head)?;
+                    }
+                    self.finish_file(file)?;
+                    file = self.open_file(&path_part)?;
+                    part_df = tail;
+                }
+            }
+            if part_df.height() > 0 {
+                file.writer._write_batch(&part_df)?;
+                file.n_rows += part_df.height();
+            }
+            // Mark the partition as most recently written.
+            self.open_files.insert(path_part, file);
+        }
+        Ok(())
+    }
+
+    fn _finish(&mut self) -> PolarsResult<()> {
+        for (_, file) in std::mem::take(&mut self.open_files) {
+            self.finish_file(file)?;
+        }
+        self.files.finish(&self.base_path)
+    }
+}
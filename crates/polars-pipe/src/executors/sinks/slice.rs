@@ -46,7 +46,8 @@ impl SliceSink {
 
     fn sort(&mut self) {
         let mut chunks = self.chunks.lock().unwrap();
-        chunks.sort_unstable_by_key(|chunk| chunk.chunk_index);
+        // stable, see `OrderedSink`
+        chunks.sort_by_key(|chunk| chunk.chunk_index);
     }
 }
 
@@ -70,7 +71,7 @@ impl Sink for SliceSink {
 
             chunks.push(chunk);
 
-            if current_len > (self.len + current_offset) {
+            if current_len + height >= self.len + current_offset {
                 Ok(SinkResult::Finished)
             } else {
                 Ok(SinkResult::CanHaveMoreInput)
@@ -113,6 +114,10 @@ impl Sink for SliceSink {
     fn as_any(&mut self) -> &mut dyn Any {
         self
     }
+    fn remaining_rows(&self) -> Option<usize> {
+        let needed = self.offset.load(Ordering::Acquire) + self.len;
+        Some(needed.saturating_sub(self.current_len.load(Ordering::Acquire)))
+    }
     fn fmt(&self) -> &str {
         "slice_sink"
     }
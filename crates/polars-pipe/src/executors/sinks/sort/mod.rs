@@ -2,6 +2,8 @@ mod ooc;
 mod sink;
 mod sink_multiple;
 mod source;
+mod top_k;
 
 pub(crate) use sink::SortSink;
 pub(crate) use sink_multiple::SortSinkMultiple;
+pub(crate) use top_k::{TopKSink, TOP_K_MAX_ROWS};
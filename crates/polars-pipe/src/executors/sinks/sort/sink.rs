@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
@@ -6,16 +7,23 @@ use polars_core::chunked_array::ops::SortMultipleOptions;
 use polars_core::config::verbose;
 use polars_core::error::PolarsResult;
 use polars_core::frame::DataFrame;
-use polars_core::prelude::{AnyValue, SchemaRef, Series, SortOptions};
+use polars_core::prelude::{polars_err, IdxCa, IdxSize, SchemaRef, SortOptions};
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
 use polars_utils::pl_str::PlSmallStr;
 
 use crate::executors::sinks::io::{block_thread_until_io_thread_done, IOThread};
-use crate::executors::sinks::memory::MemTracker;
+use crate::executors::sinks::memory::{MemTracker, MemoryAccountant};
 use crate::executors::sinks::sort::ooc::sort_ooc;
 use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult};
 use crate::pipeline::{morsels_per_sink, FORCE_OOC};
 
+// Runs are at least 32MB.
+const MIN_RUN_SIZE: usize = 1 << 25;
+// Runs are split in files of ~4MB, one file of every run is in memory during the merge.
+const RUN_FILE_SIZE: usize = 1 << 22;
+
+/// Sorts in memory, or out-of-core if the data doesn't fit. Out-of-core, the data is spilled in
+/// sorted runs that are merged by the [`SortSource`](super::source::SortSource).
 pub struct SortSink {
     schema: SchemaRef,
     chunks: Vec<DataFrame>,
@@ -31,9 +39,10 @@ pub struct SortSink {
     sort_idx: usize,
     slice: Option<(i64, usize)>,
     sort_options: SortMultipleOptions,
-    // Statistics
-    // sampled values so we can find the distribution.
-    dist_sample: Vec<AnyValue<'static>>,
+    // number of runs spilled by all threads, used to number the runs
+    n_runs: Arc<AtomicUsize>,
+    // size in bytes of the chunks from which a run is sorted and spilled
+    run_size: usize,
     // total rows accumulated in current chunk
     current_chunk_rows: usize,
     // total bytes of tables in current chunks
@@ -48,6 +57,7 @@ impl SortSink {
         slice: Option<(i64, usize)>,
         sort_options: SortMultipleOptions,
         schema: SchemaRef,
+        accountant: Arc<MemoryAccountant>,
     ) -> Self {
         // for testing purposes
         let ooc = std::env::var(FORCE_OOC).is_ok();
@@ -56,13 +66,14 @@ impl SortSink {
         let mut out = Self {
             schema,
             chunks: Default::default(),
-            mem_track: MemTracker::new(n_morsels_per_sink),
+            mem_track: MemTracker::new(n_morsels_per_sink, accountant),
             ooc,
             io_thread: Default::default(),
             sort_idx,
             slice,
             sort_options,
-            dist_sample: vec![],
+            n_runs: Default::default(),
+            run_size: MIN_RUN_SIZE,
             current_chunk_rows: 0,
             current_chunks_size: 0,
             ooc_start: None,
@@ -83,10 +94,27 @@ impl SortSink {
         self.ooc_start = Some(Instant::now());
         self.ooc = true;
 
+        // The fewer runs, the less memory the merge needs, so we make the runs as large as the
+        // memory allows.
+        self.run_size = match std::env::var("POLARS_OOC_SORT_RUN_SIZE") {
+            Ok(size) => size.parse().map_err(
+                |_| polars_err!(ComputeError: "could not parse 'POLARS_OOC_SORT_RUN_SIZE' env var"),
+            )?,
+            Err(_) if std::env::var(FORCE_OOC).is_ok() => MIN_RUN_SIZE,
+            Err(_) => std::cmp::max(
+                MIN_RUN_SIZE,
+                self.mem_track.get_available() / (3 * morsels_per_sink()),
+            ),
+        };
+
         // start IO thread
         let mut iot = self.io_thread.write().unwrap();
         if iot.is_none() {
-            *iot = Some(IOThread::try_new(self.schema.clone(), "sort")?)
+            *iot = Some(IOThread::try_new(
+                self.schema.clone(),
+                "sort",
+                self.mem_track.accountant().clone(),
+            )?)
         }
         Ok(())
     }
@@ -114,24 +142,31 @@ impl SortSink {
     }
 
     fn dump(&mut self, force: bool) -> PolarsResult<()> {
-        let larger_than_32_mb = self.current_chunks_size > (1 << 25);
-        if (force || larger_than_32_mb) && !self.chunks.is_empty() {
-            // into a single chunk because multiple file IO's is expensive
-            // and may lead to many smaller files in ooc-sort later, which is exponentially
-            // expensive
+        if (force || self.current_chunks_size > self.run_size) && !self.chunks.is_empty() {
             let df = accumulate_dataframes_vertical_unchecked(self.chunks.drain(..));
             if df.height() > 0 {
-                // SAFETY: we just asserted height > 0
-                let sample = unsafe {
-                    let s = &df.get_columns()[self.sort_idx];
-                    s.to_physical_repr().get_unchecked(0).into_static().unwrap()
-                };
-                self.dist_sample.push(sample);
+                // rows after the slice can't be in the output
+                let run_slice = self.slice.map(|(offset, len)| (0, offset as usize + len));
+                let run = sort_accumulated(
+                    df,
+                    self.sort_idx,
+                    run_slice,
+                    SortOptions::from(&self.sort_options),
+                )?;
+
+                let n_files = (run.estimated_size() / RUN_FILE_SIZE).max(1);
+                let rows_per_file = run.height().div_ceil(n_files).max(1);
+                let files = (0..run.height())
+                    .step_by(rows_per_file)
+                    .map(|offset| run.slice(offset as i64, rows_per_file))
+                    .collect::<Vec<_>>();
+                let run_no = self.n_runs.fetch_add(1, Ordering::Relaxed) as IdxSize;
+                let partitions = IdxCa::from_vec(PlSmallStr::EMPTY, vec![run_no; files.len()]);
 
                 let iot = self.io_thread.read().unwrap();
                 let iot = iot.as_ref().unwrap();
-
-                iot.dump_chunk(df);
+                // the files of a run are written in order to `dir/run_no/`
+                iot.dump_iter(Some(partitions), Box::new(files.into_iter()));
 
                 // reset sizes
                 self.current_chunk_rows = 0;
@@ -158,9 +193,12 @@ impl Sink for SortSink {
             self.ooc_start = Some(ooc_start);
         }
         self.chunks.extend(std::mem::take(&mut other.chunks));
+        self.current_chunks_size += other.current_chunks_size;
+        self.current_chunk_rows += other.current_chunk_rows;
         self.ooc |= other.ooc;
-        self.dist_sample
-            .extend(std::mem::take(&mut other.dist_sample));
+        if other.ooc {
+            self.run_size = std::cmp::max(self.run_size, other.run_size);
+        }
 
         if self.ooc {
             self.dump(false).unwrap()
@@ -177,7 +215,8 @@ impl Sink for SortSink {
             sort_idx: self.sort_idx,
             slice: self.slice,
             sort_options: self.sort_options.clone(),
-            dist_sample: vec![],
+            n_runs: self.n_runs.clone(),
+            run_size: self.run_size,
             current_chunk_rows: 0,
             current_chunks_size: 0,
             ooc_start: self.ooc_start,
@@ -187,13 +226,10 @@ impl Sink for SortSink {
     fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
         if self.ooc {
             // spill everything
-            self.dump(true).unwrap();
+            self.dump(true)?;
             let mut lock = self.io_thread.write().unwrap();
             let io_thread = lock.take().unwrap();
 
-            let dist = Series::from_any_values(PlSmallStr::EMPTY, &self.dist_sample, true).unwrap();
-            let dist = dist.sort_with(SortOptions::from(&self.sort_options))?;
-
             let instant = self.ooc_start.unwrap();
             if context.verbose {
                 eprintln!("finished sinking into OOC sort in {:?}", instant.elapsed());
@@ -205,13 +241,10 @@ impl Sink for SortSink {
 
             sort_ooc(
                 io_thread,
-                dist,
                 self.sort_idx,
-                self.sort_options.descending[0],
-                self.sort_options.nulls_last[0],
+                SortOptions::from(&self.sort_options),
                 self.slice,
                 context.verbose,
-                self.mem_track.clone(),
                 instant,
             )
         } else {
@@ -9,6 +9,7 @@ use polars_row::decode::decode_rows_from_binary;
 use polars_row::EncodingField;
 
 use super::*;
+use crate::executors::sinks::memory::MemoryAccountant;
 use crate::operators::{
     DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult, Source, SourceResult,
 };
@@ -146,6 +147,7 @@ impl SortSinkMultiple {
         sort_options: SortMultipleOptions,
         output_schema: SchemaRef,
         sort_idx: Vec<usize>,
+        accountant: Arc<MemoryAccountant>,
     ) -> PolarsResult<Self> {
         let can_decode = sort_column_can_be_decoded(&output_schema, &sort_idx);
         let mut schema = (*output_schema).clone();
@@ -182,6 +184,7 @@ impl SortSinkMultiple {
                 .with_nulls_last(false)
                 .with_maintain_order(false),
             Arc::new(schema),
+            accountant,
         ));
 
         Ok(SortSinkMultiple {
@@ -1,78 +1,146 @@
-use std::iter::Peekable;
 use std::path::PathBuf;
 use std::time::Instant;
 
+use arrow::array::BinaryArray;
+use polars_core::prelude::sort::arg_sort_multiple::_get_rows_encoded_arr;
 use polars_core::prelude::*;
+use polars_core::series::IsSorted;
 use polars_core::utils::{accumulate_dataframes_vertical_unchecked, split_df};
 use polars_core::POOL;
 use rayon::prelude::*;
 
 use crate::executors::sinks::io::IOThread;
-use crate::executors::sinks::memory::MemTracker;
-use crate::executors::sinks::sort::ooc::{read_df, PartitionSpiller};
+use crate::executors::sinks::sort::ooc::read_df;
 use crate::executors::sinks::sort::sink::sort_accumulated;
 use crate::executors::sources::get_source_index;
 use crate::operators::{DataChunk, PExecutionContext, Source, SourceResult};
 
+/// A sorted run on disk, of which a single file is in memory at a time.
+struct Run {
+    files: std::vec::IntoIter<PathBuf>,
+    // the rows of the loaded file that are not yet merged
+    df: DataFrame,
+    // the row-encoded sort keys of `df`, these compare in sort order
+    keys: BinaryArray<i64>,
+}
+
+impl Run {
+    fn new(files: Vec<PathBuf>) -> Self {
+        Self {
+            files: files.into_iter(),
+            df: Default::default(),
+            keys: BinaryArray::new_empty(ArrowDataType::LargeBinary),
+        }
+    }
+
+    /// Load the next file if all loaded rows are merged. Returns `false` if the run is depleted.
+    fn refill(
+        &mut self,
+        io_thread: &IOThread,
+        sort_idx: usize,
+        sort_options: &SortOptions,
+    ) -> PolarsResult<bool> {
+        while self.df.height() == 0 {
+            let Some(path) = self.files.next() else {
+                return Ok(false);
+            };
+            self.df = read_df(&path)?;
+            io_thread.clean(path);
+            self.keys = _get_rows_encoded_arr(
+                &[self.df.get_columns()[sort_idx].clone()],
+                &[sort_options.descending],
+                &[sort_options.nulls_last],
+            )?;
+        }
+        Ok(true)
+    }
+
+    fn last_key(&self) -> &[u8] {
+        self.keys.value(self.keys.len() - 1)
+    }
+
+    /// Take the loaded rows up to and including `bound`.
+    fn take_until(&mut self, bound: &[u8]) -> DataFrame {
+        // the keys are sorted, so this is a binary search
+        let len = self.keys.len();
+        let (mut lo, mut hi) = (0, len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.keys.value(mid) <= bound {
+                lo = mid + 1
+            } else {
+                hi = mid
+            }
+        }
+        let out = self.df.slice(0, lo);
+        self.df = self.df.slice(lo as i64, len - lo);
+        self.keys.slice(lo, len - lo);
+        out
+    }
+}
+
+/// Merges the sorted runs that the [`SortSink`](super::sink::SortSink) spilled to disk.
+///
+/// Every batch consists of the loaded rows of all runs up to the smallest last loaded key of
+/// all runs. Those are all the rows that sort before that key, so they can be emitted once they
+/// are sorted. The run with that smallest key then loads its next file, so the memory used is
+/// about the size of a single file of every run.
 pub struct SortSource {
-    files: Peekable<std::vec::IntoIter<(u32, PathBuf)>>,
+    runs: Vec<Run>,
     n_threads: usize,
     sort_idx: usize,
-    descending: bool,
-    nulls_last: bool,
+    sort_options: SortOptions,
     chunk_offset: IdxSize,
     slice: Option<(i64, usize)>,
     finished: bool,
     io_thread: IOThread,
-    memtrack: MemTracker,
     // Start of the Source phase
     source_start: Instant,
     // Start of the OOC sort operation.
     ooc_start: Instant,
-    partition_spiller: PartitionSpiller,
-    current_part: usize,
 }
 
 impl SortSource {
-    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
-        mut files: Vec<(u32, PathBuf)>,
+        runs: Vec<Vec<PathBuf>>,
         sort_idx: usize,
-        descending: bool,
-        nulls_last: bool,
+        sort_options: SortOptions,
         slice: Option<(i64, usize)>,
         verbose: bool,
         io_thread: IOThread,
-        memtrack: MemTracker,
         ooc_start: Instant,
-        partition_spiller: PartitionSpiller,
-    ) -> Self {
+    ) -> PolarsResult<Self> {
         if verbose {
-            eprintln!("started sort source phase");
+            eprintln!(
+                "started sort source phase, merging {} sorted runs",
+                runs.len()
+            );
         }
 
-        files.sort_unstable_by_key(|entry| entry.0);
-
-        let n_threads = POOL.current_num_threads();
-        let files = files.into_iter().peekable();
-
-        Self {
-            files,
-            n_threads,
+        let mut runs = runs.into_iter().map(Run::new).collect::<Vec<_>>();
+        // load the first file of every run in parallel
+        let not_depleted = POOL.install(|| {
+            runs.par_iter_mut()
+                .map(|run| run.refill(&io_thread, sort_idx, &sort_options))
+                .collect::<PolarsResult<Vec<_>>>()
+        })?;
+        let mut not_depleted = not_depleted.into_iter();
+        runs.retain(|_| not_depleted.next().unwrap());
+
+        Ok(Self {
+            runs,
+            n_threads: POOL.current_num_threads(),
             sort_idx,
-            descending,
-            nulls_last,
+            sort_options,
             chunk_offset: get_source_index(1) as IdxSize,
             slice,
             finished: false,
             io_thread,
-            memtrack,
             source_start: Instant::now(),
             ooc_start,
-            partition_spiller,
-            current_part: 0,
-        }
+        })
     }
+
     fn finish_batch(&mut self, dfs: Vec<DataFrame>) -> Vec<DataChunk> {
         // TODO: make utility functions to save these allocations
         let chunk_offset = self.chunk_offset;
@@ -86,42 +154,19 @@ impl SortSource {
             .collect()
     }
 
-    fn finish_from_df(&mut self, df: DataFrame) -> PolarsResult<SourceResult> {
-        // Sort a single partition
-        // We always need to sort again!
-        let current_slice = self.slice;
-
-        let mut df = match &mut self.slice {
-            None => sort_accumulated(
-                df,
-                self.sort_idx,
-                None,
-                SortOptions {
-                    descending: self.descending,
-                    nulls_last: self.nulls_last,
-                    multithreaded: true,
-                    maintain_order: false,
-                },
-            ),
+    /// Apply the slice to the sorted rows of a batch.
+    fn slice_batch(&mut self, df: DataFrame) -> DataFrame {
+        match &mut self.slice {
+            None => df,
             Some((offset, len)) => {
                 let df_len = df.height();
                 debug_assert!(*offset >= 0);
                 let out = if *offset as usize >= df_len {
                     *offset -= df_len as i64;
-                    Ok(df.slice(0, 0))
+                    df.slice(0, 0)
                 } else {
-                    let out = sort_accumulated(
-                        df,
-                        self.sort_idx,
-                        current_slice,
-                        SortOptions {
-                            descending: self.descending,
-                            nulls_last: self.nulls_last,
-                            multithreaded: true,
-                            maintain_order: false,
-                        },
-                    );
-                    *len = len.saturating_sub(df_len);
+                    let out = df.slice(*offset, *len);
+                    *len -= out.height();
                     *offset = 0;
                     out
                 };
@@ -130,108 +175,75 @@ impl SortSource {
                 }
                 out
             },
-        }?;
-
-        // convert to chunks
-        let dfs = split_df(&mut df, self.n_threads, true);
-        Ok(SourceResult::GotMoreData(self.finish_batch(dfs)))
+        }
     }
+
     fn print_verbose(&self, verbose: bool) {
         if verbose {
             eprintln!("sort source phase took: {:?}", self.source_start.elapsed());
             eprintln!("full ooc sort took: {:?}", self.ooc_start.elapsed());
         }
     }
-
-    fn get_from_memory(
-        &mut self,
-        read: &mut Vec<DataFrame>,
-        read_size: &mut usize,
-        part: usize,
-        keep_track: bool,
-    ) {
-        while self.current_part <= part {
-            if let Some(df) = self.partition_spiller.get(self.current_part - 1) {
-                if keep_track {
-                    *read_size += df.estimated_size();
-                }
-                read.push(df);
-            }
-            self.current_part += 1;
-        }
-    }
 }
 
 impl Source for SortSource {
     fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult> {
-        // early return
-        if self.finished || self.current_part >= self.partition_spiller.len() {
-            self.print_verbose(context.verbose);
-            return Ok(SourceResult::Finished);
-        }
-        self.current_part += 1;
-        let mut read_size = 0;
-        let mut read = vec![];
-
-        match self.files.next() {
-            None => {
-                // Ensure we fetch all from memory.
-                self.get_from_memory(
-                    &mut read,
-                    &mut read_size,
-                    self.partition_spiller.len(),
-                    false,
-                );
-                if read.is_empty() {
-                    self.print_verbose(context.verbose);
-                    Ok(SourceResult::Finished)
+        loop {
+            if self.finished || self.runs.is_empty() {
+                self.print_verbose(context.verbose);
+                return Ok(SourceResult::Finished);
+            }
+
+            let bound = self
+                .runs
+                .iter()
+                .map(|run| run.last_key())
+                .min()
+                .unwrap()
+                .to_vec();
+            let dfs = self
+                .runs
+                .iter_mut()
+                .map(|run| run.take_until(&bound))
+                .filter(|df| df.height() > 0)
+                .collect::<Vec<_>>();
+
+            let mut df = if dfs.len() == 1 {
+                // a slice of a single run is already sorted
+                let mut df = dfs.into_iter().next().unwrap();
+                let flag = if self.sort_options.descending {
+                    IsSorted::Descending
                 } else {
-                    self.finished = true;
-                    let df = accumulate_dataframes_vertical_unchecked(read);
-                    self.finish_from_df(df)
-                }
-            },
-            Some((mut partition, mut path)) => {
-                self.get_from_memory(&mut read, &mut read_size, partition as usize, true);
-                let limit = self.memtrack.get_available() / 3;
-
-                loop {
-                    if let Some(in_mem) = self.partition_spiller.get(partition as usize) {
-                        read_size += in_mem.estimated_size();
-                        read.push(in_mem)
-                    }
-
-                    let files = std::fs::read_dir(&path)?.collect::<std::io::Result<Vec<_>>>()?;
-
-                    // read the files in a single partition in parallel
-                    let dfs = POOL.install(|| {
-                        files
-                            .par_iter()
-                            .map(|entry| {
-                                let df = read_df(&entry.path())?;
-                                Ok(df)
-                            })
-                            .collect::<PolarsResult<Vec<DataFrame>>>()
-                    })?;
-
-                    let df = accumulate_dataframes_vertical_unchecked(dfs);
-                    read_size += df.estimated_size();
-                    read.push(df);
-                    if read_size > limit {
-                        break;
-                    }
-
-                    let Some((next_part, next_path)) = self.files.next() else {
-                        break;
-                    };
-                    path = next_path;
-                    partition = next_part;
+                    IsSorted::Ascending
+                };
+                // SAFETY: setting the sorted flag doesn't change the length or the name.
+                unsafe { df.get_columns_mut()[self.sort_idx].set_sorted_flag(flag) };
+                df
+            } else {
+                sort_accumulated(
+                    accumulate_dataframes_vertical_unchecked(dfs),
+                    self.sort_idx,
+                    None,
+                    self.sort_options,
+                )?
+            };
+
+            // load the next files of the runs that were merged up to their last key
+            let mut i = 0;
+            while i < self.runs.len() {
+                if self.runs[i].refill(&self.io_thread, self.sort_idx, &self.sort_options)? {
+                    i += 1
+                } else {
+                    self.runs.swap_remove(i);
                 }
-                let df = accumulate_dataframes_vertical_unchecked(read);
-                let out = self.finish_from_df(df);
-                self.io_thread.clean(path);
-                out
-            },
+            }
+
+            df = self.slice_batch(df);
+            if df.height() > 0 {
+                // convert to chunks
+                let dfs = split_df(&mut df, self.n_threads, true);
+                return Ok(SourceResult::GotMoreData(self.finish_batch(dfs)));
+            }
         }
     }
 
@@ -0,0 +1,258 @@
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use polars_core::prelude::sort::_broadcast_bools;
+use polars_core::prelude::sort::arg_sort_multiple::_get_rows_encoded;
+use polars_core::prelude::*;
+use polars_core::series::IsSorted;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+
+use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult};
+
+/// Sorts with a slice of at most this many rows (offset + length) are executed by the
+/// [`TopKSink`] instead of a full sort.
+pub(crate) const TOP_K_MAX_ROWS: usize = 1 << 16;
+// Rejected rows stay in the buffers until there are more of them than rows in the heap.
+const MIN_COMPACT_SIZE: usize = 1 << 16;
+
+// A row in the top k: the row encoded sort key, the position of the row in the input and
+// the location of the row in the buffers.
+struct HeapItem {
+    key: Box<[u8]>,
+    order: (IdxSize, IdxSize),
+    location: (IdxSize, IdxSize),
+}
+
+impl PartialEq for HeapItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapItem {}
+
+impl PartialOrd for HeapItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapItem {
+    // Ties are broken by the position in the input, so that the sort is stable.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.key.as_ref(), self.order).cmp(&(other.key.as_ref(), other.order))
+    }
+}
+
+/// Keeps the first `offset + len` rows of a sort in a bounded max-heap of the row encoded sort
+/// keys, so that `sort().slice()` doesn't need to sort, or spill, all of its input.
+///
+/// Every thread keeps at most `k` rows, the heaps of the threads are merged in `combine`.
+pub struct TopKSink {
+    heap: BinaryHeap<HeapItem>,
+    // the rows in the heap, rejected rows are removed by `compact`
+    buffers: Vec<DataFrame>,
+    n_buffered_rows: usize,
+    k: usize,
+    slice: (usize, usize),
+    schema: SchemaRef,
+    sort_idx: Arc<[usize]>,
+    descending: Vec<bool>,
+    nulls_last: Vec<bool>,
+    // amortize allocations
+    take_idx: Vec<IdxSize>,
+}
+
+impl TopKSink {
+    pub(crate) fn new(
+        slice: (usize, usize),
+        sort_options: &SortMultipleOptions,
+        schema: SchemaRef,
+        sort_idx: Vec<usize>,
+    ) -> Self {
+        let mut descending = sort_options.descending.clone();
+        let mut nulls_last = sort_options.nulls_last.clone();
+        _broadcast_bools(sort_idx.len(), &mut descending);
+        _broadcast_bools(sort_idx.len(), &mut nulls_last);
+
+        Self {
+            heap: Default::default(),
+            buffers: vec![],
+            n_buffered_rows: 0,
+            k: slice.0.saturating_add(slice.1),
+            slice,
+            schema,
+            sort_idx: Arc::from(sort_idx),
+            descending,
+            nulls_last,
+            take_idx: vec![],
+        }
+    }
+
+    /// Whether a row with this key and position belongs in the top k.
+    #[inline]
+    fn accepts(&self, key: &[u8], order: (IdxSize, IdxSize)) -> bool {
+        match self.heap.peek() {
+            Some(top) if self.heap.len() == self.k => (key, order) < (top.key.as_ref(), top.order),
+            _ => self.k > 0,
+        }
+    }
+
+    fn push(&mut self, item: HeapItem) {
+        if self.heap.len() == self.k {
+            self.heap.pop();
+        }
+        self.heap.push(item)
+    }
+
+    /// Gather the rows of the heap in a single buffer.
+    fn compact(&mut self) {
+        if self.buffers.is_empty() {
+            return;
+        }
+        let mut offsets = Vec::with_capacity(self.buffers.len());
+        let mut offset = 0 as IdxSize;
+        for df in &self.buffers {
+            offsets.push(offset);
+            offset += df.height() as IdxSize;
+        }
+
+        let mut items = std::mem::take(&mut self.heap).into_vec();
+        let idx = items
+            .iter_mut()
+            .enumerate()
+            .map(|(i, item)| {
+                let (buffer_idx, row_idx) = item.location;
+                item.location = (0, i as IdxSize);
+                offsets[buffer_idx as usize] + row_idx
+            })
+            .collect::<Vec<_>>();
+        self.heap = BinaryHeap::from(items);
+
+        let df = accumulate_dataframes_vertical_unchecked(std::mem::take(&mut self.buffers));
+        // SAFETY: the locations point into the buffers.
+        let df = unsafe { df._take_unchecked_slice(&idx, true) };
+        self.n_buffered_rows = df.height();
+        self.buffers.push(df);
+    }
+
+    fn maybe_compact(&mut self) {
+        if self.n_buffered_rows > 2 * self.heap.len() + MIN_COMPACT_SIZE {
+            self.compact()
+        }
+    }
+}
+
+impl Sink for TopKSink {
+    fn sink(&mut self, _context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        if self.k == 0 {
+            return Ok(SinkResult::Finished);
+        }
+        if chunk.is_empty() {
+            return Ok(SinkResult::CanHaveMoreInput);
+        }
+        let columns = chunk.data.get_columns();
+        let by = self
+            .sort_idx
+            .iter()
+            .map(|i| columns[*i].clone())
+            .collect::<Vec<_>>();
+        let rows = _get_rows_encoded(&by, &self.descending, &self.nulls_last)?;
+
+        let buffer_idx = self.buffers.len() as IdxSize;
+        for (i, key) in rows.iter().enumerate() {
+            let order = (chunk.chunk_index, i as IdxSize);
+            if self.accepts(key, order) {
+                let location = (buffer_idx, self.take_idx.len() as IdxSize);
+                self.push(HeapItem {
+                    key: key.into(),
+                    order,
+                    location,
+                });
+                self.take_idx.push(i as IdxSize);
+            }
+        }
+
+        if !self.take_idx.is_empty() {
+            // SAFETY: the indexes are rows of this chunk.
+            let df = unsafe { chunk.data._take_unchecked_slice(&self.take_idx, false) };
+            self.n_buffered_rows += df.height();
+            self.buffers.push(df);
+            self.take_idx.clear();
+            self.maybe_compact();
+        }
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, other: &mut dyn Sink) {
+        let other = other.as_any().downcast_mut::<Self>().unwrap();
+        other.compact();
+        let Some(df) = other.buffers.pop() else {
+            return;
+        };
+
+        let buffer_idx = self.buffers.len() as IdxSize;
+        for mut item in std::mem::take(&mut other.heap) {
+            if self.accepts(&item.key, item.order) {
+                item.location.0 = buffer_idx;
+                self.push(item);
+            }
+        }
+        self.n_buffered_rows += df.height();
+        self.buffers.push(df);
+        self.maybe_compact();
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+        Box::new(Self {
+            heap: Default::default(),
+            buffers: vec![],
+            n_buffered_rows: 0,
+            k: self.k,
+            slice: self.slice,
+            schema: self.schema.clone(),
+            sort_idx: self.sort_idx.clone(),
+            descending: self.descending.clone(),
+            nulls_last: self.nulls_last.clone(),
+            take_idx: vec![],
+        })
+    }
+
+    fn finalize(&mut self, _context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        self.compact();
+        let Some(df) = self.buffers.pop() else {
+            return Ok(FinalizedSink::Finished(DataFrame::empty_with_schema(
+                &self.schema,
+            )));
+        };
+
+        let (offset, len) = self.slice;
+        let idx = std::mem::take(&mut self.heap)
+            .into_sorted_vec()
+            .into_iter()
+            .skip(offset)
+            .take(len)
+            .map(|item| item.location.1)
+            .collect::<Vec<_>>();
+        // SAFETY: after compacting, the locations point into `df`.
+        let mut df = unsafe { df._take_unchecked_slice(&idx, true) };
+
+        let flag = if self.descending[0] {
+            IsSorted::Descending
+        } else {
+            IsSorted::Ascending
+        };
+        // SAFETY: we don't change the length or the names of the columns.
+        unsafe { df.get_columns_mut()[self.sort_idx[0]].set_sorted_flag(flag) };
+        Ok(FinalizedSink::Finished(df))
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        "top_k"
+    }
+}
@@ -0,0 +1,596 @@
+use std::any::Any;
+use std::sync::Mutex;
+
+use arrow::array::{ArrayRef, BinaryArray};
+use hashbrown::hash_map::RawEntryMut;
+use polars_core::config::verbose;
+use polars_core::prelude::*;
+use polars_core::utils::{accumulate_dataframes_vertical_unchecked, split_df};
+use polars_row::RowsEncoded;
+use polars_utils::hashing::hash_to_partition;
+
+use crate::executors::sinks::io::{block_thread_until_io_thread_done, IOThread};
+use crate::executors::sinks::memory::{MemTracker, MemoryAccountant};
+use crate::executors::sinks::utils::{hash_rows, RowKey};
+use crate::executors::sources::IpcSourceOneShot;
+use crate::operators::{
+    DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult, Source, SourceResult,
+};
+use crate::pipeline::{morsels_per_sink, PipeLine, FORCE_OOC, PARTITION_SIZE};
+
+// If this is reached we spill all rows to disk and
+// deduplicate the partitions in a second run
+const TO_DISK_THRESHOLD: f64 = 0.3;
+// Replaced rows stay in the buffers until there are more of them than kept rows.
+const MIN_COMPACT_SIZE: usize = 1 << 16;
+
+// The spilled rows store their position in the input and the number of rows with the same
+// key that were already merged, so that the second run keeps the same row.
+const CHUNK_INDEX_COL: PlSmallStr = PlSmallStr::from_static("__POLARS_UNIQUE_CHUNK_INDEX");
+const ROW_INDEX_COL: PlSmallStr = PlSmallStr::from_static("__POLARS_UNIQUE_ROW_INDEX");
+const COUNT_COL: PlSmallStr = PlSmallStr::from_static("__POLARS_UNIQUE_COUNT");
+
+#[derive(Copy, Clone)]
+struct Entry {
+    // position of the kept row in the input: (chunk index, row index)
+    order: (IdxSize, IdxSize),
+    // number of rows with this key
+    count: IdxSize,
+    // location of the kept row in the buffers: (buffer index, row index)
+    location: (IdxSize, IdxSize),
+}
+
+#[inline]
+fn replaces(keep: UniqueKeepStrategy, new: (IdxSize, IdxSize), old: (IdxSize, IdxSize)) -> bool {
+    match keep {
+        UniqueKeepStrategy::First => new < old,
+        UniqueKeepStrategy::Last => new > old,
+        UniqueKeepStrategy::Any | UniqueKeepStrategy::None => false,
+    }
+}
+
+fn with_meta_columns(
+    df: &DataFrame,
+    chunk_indexes: Vec<IdxSize>,
+    row_indexes: Vec<IdxSize>,
+    counts: Vec<IdxSize>,
+) -> DataFrame {
+    df.hstack(&[
+        IdxCa::from_vec(CHUNK_INDEX_COL, chunk_indexes).into_series(),
+        IdxCa::from_vec(ROW_INDEX_COL, row_indexes).into_series(),
+        IdxCa::from_vec(COUNT_COL, counts).into_series(),
+    ])
+    .unwrap()
+}
+
+/// Keeps a single row of every key, or only the rows of which the key is unique with
+/// [`UniqueKeepStrategy::None`].
+///
+/// The kept rows are found with a hash table of the row encoded keys. If the table doesn't fit
+/// in memory, all rows are partitioned by hash and spilled to disk, together with their position
+/// in the input. The partitions are deduplicated one by one in the [`UniqueSource`].
+pub struct UniqueSink {
+    table: PlIdHashMap<RowKey, Entry>,
+    // the kept rows, replaced rows are removed by `compact`
+    buffers: Vec<DataFrame>,
+    n_buffered_rows: usize,
+    keep: UniqueKeepStrategy,
+    // the columns that determine uniqueness
+    key_names: Arc<[PlSmallStr]>,
+    input_schema: SchemaRef,
+    slice: Option<(i64, usize)>,
+    hb: PlRandomState,
+    // amortize allocations
+    key_columns: Vec<ArrayRef>,
+    rows_encoded: RowsEncoded,
+    hashes: Vec<u64>,
+    take_idx: Vec<IdxSize>,
+    // the chunks are read from the spilled partitions and have the meta columns
+    from_spill: bool,
+    // OOC
+    // Stores available memory in the system at the start of this sink.
+    mem_track: MemTracker,
+    // deduplicate in-memory or out-of-core
+    ooc: bool,
+    // when ooc, we write to disk using an IO thread
+    io_thread: Arc<Mutex<Option<IOThread>>>,
+    to_disk_threshold: f64,
+}
+
+impl UniqueSink {
+    pub(crate) fn new(
+        subset: Option<Arc<[PlSmallStr]>>,
+        keep: UniqueKeepStrategy,
+        input_schema: SchemaRef,
+        slice: Option<(i64, usize)>,
+        accountant: Arc<MemoryAccountant>,
+    ) -> Self {
+        let key_names = subset.unwrap_or_else(|| input_schema.iter_names().cloned().collect());
+        let to_disk_threshold = if std::env::var(FORCE_OOC).is_ok() {
+            1.0
+        } else {
+            TO_DISK_THRESHOLD
+        };
+
+        Self {
+            table: Default::default(),
+            buffers: vec![],
+            n_buffered_rows: 0,
+            keep,
+            key_names,
+            input_schema,
+            slice,
+            hb: Default::default(),
+            key_columns: vec![],
+            rows_encoded: Default::default(),
+            hashes: vec![],
+            take_idx: vec![],
+            from_spill: false,
+            mem_track: MemTracker::new(morsels_per_sink(), accountant),
+            ooc: false,
+            io_thread: Default::default(),
+            to_disk_threshold,
+        }
+    }
+
+    fn new_like(&self) -> Self {
+        Self {
+            table: Default::default(),
+            buffers: vec![],
+            n_buffered_rows: 0,
+            keep: self.keep,
+            key_names: self.key_names.clone(),
+            input_schema: self.input_schema.clone(),
+            slice: self.slice,
+            hb: self.hb.clone(),
+            key_columns: vec![],
+            rows_encoded: Default::default(),
+            hashes: vec![],
+            take_idx: vec![],
+            from_spill: self.from_spill,
+            mem_track: self.mem_track.clone(),
+            ooc: self.ooc,
+            io_thread: self.io_thread.clone(),
+            to_disk_threshold: self.to_disk_threshold,
+        }
+    }
+
+    /// The sink that deduplicates a spilled partition.
+    /// A partition is not spilled again, otherwise the second run would not progress.
+    fn new_for_spilled(&self) -> Self {
+        let mut out = self.new_like();
+        out.slice = None;
+        out.from_spill = true;
+        out.ooc = false;
+        out.io_thread = Default::default();
+        out.to_disk_threshold = 0.0;
+        out
+    }
+
+    fn encode_keys(&mut self, df: &DataFrame) -> PolarsResult<BinaryArray<i64>> {
+        for name in self.key_names.iter() {
+            let s = df.column(name)?.to_physical_repr().rechunk();
+            self.key_columns.push(s.array_ref(0).clone());
+        }
+        polars_row::convert_columns_amortized_no_order(&self.key_columns, &mut self.rows_encoded);
+        self.key_columns.clear();
+
+        // SAFETY: we keep rows-encode alive until the next chunk
+        let rows = unsafe { self.rows_encoded.borrow_array() };
+        hash_rows(&rows, &mut self.hashes, &self.hb);
+        Ok(rows)
+    }
+
+    fn insert_chunk(&mut self, chunk: &DataChunk, rows: &BinaryArray<i64>) -> PolarsResult<()> {
+        let meta = if self.from_spill {
+            let get = |name: &PlSmallStr| chunk.data.column(name)?.idx().map(|ca| ca.rechunk());
+            Some((
+                get(&CHUNK_INDEX_COL)?,
+                get(&ROW_INDEX_COL)?,
+                get(&COUNT_COL)?,
+            ))
+        } else {
+            None
+        };
+        let meta = match &meta {
+            Some((chunk_indexes, row_indexes, counts)) => Some((
+                chunk_indexes.cont_slice()?,
+                row_indexes.cont_slice()?,
+                counts.cont_slice()?,
+            )),
+            None => None,
+        };
+
+        let buffer_idx = self.buffers.len() as IdxSize;
+        for (i, (row, &hash)) in rows.values_iter().zip(&self.hashes).enumerate() {
+            let (order, count) = match meta {
+                Some((chunk_indexes, row_indexes, counts)) => {
+                    ((chunk_indexes[i], row_indexes[i]), counts[i])
+                },
+                None => ((chunk.chunk_index, i as IdxSize), 1),
+            };
+            let location = (buffer_idx, self.take_idx.len() as IdxSize);
+
+            let keep_row = match self
+                .table
+                .raw_entry_mut()
+                .from_hash(hash, |k| k.hash == hash && k.row.as_ref() == row)
+            {
+                RawEntryMut::Vacant(entry) => {
+                    let key = RowKey {
+                        hash,
+                        row: row.into(),
+                    };
+                    entry.insert(
+                        key,
+                        Entry {
+                            order,
+                            count,
+                            location,
+                        },
+                    );
+                    true
+                },
+                RawEntryMut::Occupied(mut entry) => {
+                    let entry = entry.get_mut();
+                    entry.count += count;
+                    if replaces(self.keep, order, entry.order) {
+                        entry.order = order;
+                        entry.location = location;
+                        true
+                    } else {
+                        false
+                    }
+                },
+            };
+            if keep_row {
+                self.take_idx.push(i as IdxSize);
+            }
+        }
+
+        if !self.take_idx.is_empty() {
+            // the meta columns are the last columns of a spilled chunk
+            let data = if self.from_spill {
+                let columns = &chunk.data.get_columns()[..self.input_schema.len()];
+                unsafe { DataFrame::new_no_checks(columns.to_vec()) }
+            } else {
+                chunk.data.clone()
+            };
+            // SAFETY: the indexes are rows of this chunk.
+            let df = unsafe { data._take_unchecked_slice(&self.take_idx, false) };
+            self.n_buffered_rows += df.height();
+            self.buffers.push(df);
+            self.take_idx.clear();
+
+            if self.n_buffered_rows > 2 * self.table.len() + MIN_COMPACT_SIZE {
+                self.compact()
+            }
+        }
+        Ok(())
+    }
+
+    /// Gather the kept rows in a single buffer, in the iteration order of the table.
+    fn compact(&mut self) {
+        if self.buffers.is_empty() {
+            return;
+        }
+        let mut offsets = Vec::with_capacity(self.buffers.len());
+        let mut offset = 0 as IdxSize;
+        for df in &self.buffers {
+            offsets.push(offset);
+            offset += df.height() as IdxSize;
+        }
+
+        let idx = self
+            .table
+            .values_mut()
+            .enumerate()
+            .map(|(i, entry)| {
+                let (buffer_idx, row_idx) = entry.location;
+                entry.location = (0, i as IdxSize);
+                offsets[buffer_idx as usize] + row_idx
+            })
+            .collect::<Vec<_>>();
+
+        let df = accumulate_dataframes_vertical_unchecked(std::mem::take(&mut self.buffers));
+        // SAFETY: the locations point into the buffers.
+        let df = unsafe { df._take_unchecked_slice(&idx, true) };
+        self.n_buffered_rows = df.height();
+        self.buffers.push(df);
+    }
+
+    fn check_memory_usage(&mut self) -> PolarsResult<()> {
+        if self.mem_track.free_memory_fraction_since_start() < self.to_disk_threshold {
+            if verbose() {
+                eprintln!("OOC unique started");
+            }
+            self.ooc = true;
+
+            let io_thread = self.io_thread.clone();
+            let mut iot = io_thread.lock().unwrap();
+            if iot.is_none() {
+                let mut schema = self.input_schema.as_ref().clone();
+                for name in [CHUNK_INDEX_COL, ROW_INDEX_COL, COUNT_COL] {
+                    schema.with_column(name, IDX_DTYPE);
+                }
+                *iot = Some(IOThread::try_new(
+                    Arc::new(schema),
+                    "unique",
+                    self.mem_track.accountant().clone(),
+                )?);
+            }
+            let iot = iot.as_ref().unwrap();
+            self.spill_table(iot);
+        }
+        Ok(())
+    }
+
+    /// Spill the kept rows with their order and count and clear the table.
+    fn spill_table(&mut self, iot: &IOThread) {
+        self.compact();
+        let Some(df) = self.buffers.pop() else {
+            return;
+        };
+        self.n_buffered_rows = 0;
+
+        // after compacting, the rows are in a single buffer
+        let n = df.height();
+        let mut chunk_indexes = vec![0; n];
+        let mut row_indexes = vec![0; n];
+        let mut counts = vec![0; n];
+        let mut hashes = vec![0; n];
+        for (key, entry) in self.table.drain() {
+            let i = entry.location.1 as usize;
+            (chunk_indexes[i], row_indexes[i]) = entry.order;
+            counts[i] = entry.count;
+            hashes[i] = key.hash;
+        }
+        let df = with_meta_columns(&df, chunk_indexes, row_indexes, counts);
+        dump_partitioned(iot, &df, &hashes)
+    }
+
+    fn spill_chunk(&mut self, chunk: &DataChunk) {
+        let n = chunk.data.height();
+        let df = with_meta_columns(
+            &chunk.data,
+            vec![chunk.chunk_index; n],
+            (0..n as IdxSize).collect(),
+            vec![1; n],
+        );
+        let iot = self.io_thread.lock().unwrap();
+        dump_partitioned(iot.as_ref().unwrap(), &df, &self.hashes)
+    }
+
+    fn finish(&mut self) -> DataFrame {
+        self.compact();
+        let Some(mut df) = self.buffers.pop() else {
+            return DataFrame::empty_with_schema(&self.input_schema);
+        };
+        if matches!(self.keep, UniqueKeepStrategy::None) {
+            let idx = self
+                .table
+                .values()
+                .filter(|entry| entry.count == 1)
+                .map(|entry| entry.location.1)
+                .collect::<Vec<_>>();
+            // SAFETY: after compacting, the locations point into `df`.
+            df = unsafe { df._take_unchecked_slice(&idx, true) };
+        }
+        self.table.clear();
+        match self.slice {
+            Some((offset, len)) => df.slice(offset, len),
+            None => df,
+        }
+    }
+}
+
+fn dump_partitioned(iot: &IOThread, df: &DataFrame, hashes: &[u64]) {
+    let mut partition_idx = vec![vec![]; PARTITION_SIZE];
+    for (i, &h) in hashes.iter().enumerate() {
+        partition_idx[hash_to_partition(h, PARTITION_SIZE)].push(i as IdxSize);
+    }
+
+    let (partitions, dfs): (Vec<IdxSize>, Vec<DataFrame>) = partition_idx
+        .iter()
+        .enumerate()
+        .filter(|(_, idx)| !idx.is_empty())
+        .map(|(partition, idx)| {
+            // SAFETY: the indexes are rows of `df`.
+            let df = unsafe { df._take_unchecked_slice(idx, false) };
+            (partition as IdxSize, df)
+        })
+        .unzip();
+
+    if !dfs.is_empty() {
+        iot.dump_iter(
+            Some(IdxCa::from_vec(PlSmallStr::EMPTY, partitions)),
+            Box::new(dfs.into_iter()),
+        )
+    }
+}
+
+impl Sink for UniqueSink {
+    fn sink(&mut self, _context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        if chunk.is_empty() {
+            return Ok(SinkResult::CanHaveMoreInput);
+        }
+        let rows = self.encode_keys(&chunk.data)?;
+        if self.ooc {
+            self.spill_chunk(&chunk);
+        } else {
+            self.insert_chunk(&chunk, &rows)?;
+            self.check_memory_usage()?;
+        }
+        self.hashes.clear();
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, other: &mut dyn Sink) {
+        let other = other.as_any().downcast_mut::<Self>().unwrap();
+        other.compact();
+        let Some(df) = other.buffers.pop() else {
+            return;
+        };
+
+        let buffer_idx = self.buffers.len() as IdxSize;
+        for (key, other_entry) in other.table.drain() {
+            let location = (buffer_idx, other_entry.location.1);
+            match self
+                .table
+                .raw_entry_mut()
+                .from_hash(key.hash, |k| k.hash == key.hash && k.row == key.row)
+            {
+                RawEntryMut::Vacant(entry) => {
+                    entry.insert(
+                        key,
+                        Entry {
+                            location,
+                            ..other_entry
+                        },
+                    );
+                },
+                RawEntryMut::Occupied(mut entry) => {
+                    let entry = entry.get_mut();
+                    entry.count += other_entry.count;
+                    if replaces(self.keep, other_entry.order, entry.order) {
+                        entry.order = other_entry.order;
+                        entry.location = location;
+                    }
+                },
+            }
+        }
+        self.n_buffered_rows += df.height();
+        self.buffers.push(df);
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+        Box::new(self.new_like())
+    }
+
+    fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        // any of the threads may have gone out-of-core
+        let iot = self.io_thread.lock().unwrap().take();
+        match iot {
+            None => Ok(FinalizedSink::Finished(self.finish())),
+            Some(iot) => {
+                // the rows that are still in memory are merged in the partitions
+                self.spill_table(&iot);
+                block_thread_until_io_thread_done(&iot);
+                if context.verbose {
+                    eprintln!("finish streaming unique with out-of-core partitions")
+                }
+
+                Ok(FinalizedSink::Source(Box::new(UniqueSource::new(
+                    iot,
+                    Box::new(self.new_for_spilled()),
+                    self.slice,
+                )?)))
+            },
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        "unique"
+    }
+}
+
+/// Deduplicates the spilled partitions of the [`UniqueSink`] one by one.
+struct UniqueSource {
+    // Holding this keeps the lockfile in place
+    io_thread: IOThread,
+    partitions: std::fs::ReadDir,
+    unique_sink: Box<dyn Sink>,
+    chunk_idx: IdxSize,
+    slice: Option<(usize, usize)>,
+}
+
+impl UniqueSource {
+    fn new(
+        io_thread: IOThread,
+        unique_sink: Box<dyn Sink>,
+        slice: Option<(i64, usize)>,
+    ) -> PolarsResult<Self> {
+        if let Some(slice) = slice {
+            polars_ensure!(slice.0 >= 0, ComputeError: "negative slice not supported with out-of-core unique")
+        }
+        let partitions = std::fs::read_dir(&io_thread.dir)?;
+
+        Ok(Self {
+            io_thread,
+            partitions,
+            unique_sink,
+            chunk_idx: 0,
+            slice: slice.map(|slice| (slice.0 as usize, slice.1)),
+        })
+    }
+}
+
+impl Source for UniqueSource {
+    fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        loop {
+            if matches!(self.slice, Some((_, 0))) {
+                return Ok(SourceResult::Finished);
+            }
+            let Some(partition_dir) = self.partitions.next() else {
+                return Ok(SourceResult::Finished);
+            };
+            let partition_dir = partition_dir?.path();
+            if !partition_dir.is_dir() {
+                continue;
+            }
+
+            let files = std::fs::read_dir(&partition_dir)?
+                .map(|e| e.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let sources = files
+                .iter()
+                .map(|path| Ok(Box::new(IpcSourceOneShot::new(path.as_path())?) as Box<dyn Source>))
+                .collect::<PolarsResult<Vec<_>>>()?;
+
+            let mut pipe = PipeLine::new_simple(
+                sources,
+                vec![],
+                self.unique_sink.split(0),
+                verbose(),
+                context.mem_track.accountant().clone(),
+            );
+            let mut df = match pipe.run_pipeline(context, &mut vec![])?.unwrap() {
+                FinalizedSink::Finished(df) => df,
+                _ => unreachable!(),
+            };
+            self.io_thread.clean(partition_dir);
+
+            if let Some(slice) = &mut self.slice {
+                let height = df.height();
+                if slice.0 >= height {
+                    slice.0 -= height;
+                    continue;
+                }
+                df = df.slice(slice.0 as i64, slice.1);
+                slice.0 = 0;
+                slice.1 -= df.height();
+            }
+            if df.is_empty() {
+                continue;
+            }
+
+            let chunks = split_df(&mut df, morsels_per_sink(), false)
+                .into_iter()
+                .map(|data| {
+                    let chunk = DataChunk::new(self.chunk_idx, data);
+                    self.chunk_idx += 1;
+                    chunk
+                })
+                .collect();
+            return Ok(SourceResult::GotMoreData(chunks));
+        }
+    }
+
+    fn fmt(&self) -> &str {
+        "ooc-unique-source"
+    }
+}
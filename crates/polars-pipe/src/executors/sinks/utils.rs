@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use arrow::array::BinaryArray;
 use polars_core::hashing::_hash_binary_array;
 use polars_utils::aliases::PlRandomState;
@@ -14,3 +16,16 @@ pub(super) fn load_vec<T, F: Fn() -> T>(partitions: usize, item: F) -> Vec<T> {
     }
     buf
 }
+
+/// A row encoded key and its hash, to be stored in a `PlIdHashMap`.
+pub(super) struct RowKey {
+    pub(super) hash: u64,
+    pub(super) row: Box<[u8]>,
+}
+
+impl Hash for RowKey {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash)
+    }
+}
@@ -0,0 +1,168 @@
+use polars_core::prelude::*;
+use polars_plan::plans::expr_ir::{ExprIR, OutputName};
+use polars_plan::plans::visitor::{AexprNode, RewriteRecursion, RewritingVisitor, TreeWalker};
+use polars_plan::plans::{all_streamable, Context};
+use polars_plan::prelude::{AExpr, WindowMapping, WindowType};
+use polars_utils::arena::{Arena, Node};
+use polars_utils::format_pl_smallstr;
+
+use crate::executors::sinks::group_by::aggregates::can_convert_to_hash_agg;
+
+pub(super) fn window_column_name(i: usize) -> PlSmallStr {
+    format_pl_smallstr!("__POLARS_WINDOW_{i}")
+}
+
+/// The window expressions of a projection that are computed with a streaming group_by.
+pub struct WindowAggregations {
+    // the projection, of which the windows are replaced by their temporary column
+    pub(crate) exprs: Vec<ExprIR>,
+    // the aggregation of every window, the temporary columns are named by their index
+    pub(crate) aggs: Vec<Node>,
+    // the partition columns, followed by the temporary columns
+    pub(crate) schema: SchemaRef,
+}
+
+impl WindowAggregations {
+    /// The schema of the aggregated groups.
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+}
+
+struct WindowRewriter<'a> {
+    input_schema: &'a Schema,
+    partition_by: Option<Vec<PlSmallStr>>,
+    aggs: Vec<(Node, DataType)>,
+    supported: bool,
+}
+
+impl WindowRewriter<'_> {
+    /// Returns the aggregation and the output dtype of a window that can be computed with a
+    /// hash aggregation, partitioned by the same columns as the other windows.
+    fn supported_window(
+        &mut self,
+        node: Node,
+        expr_arena: &Arena<AExpr>,
+    ) -> Option<(Node, DataType)> {
+        let AExpr::Window {
+            function,
+            partition_by,
+            order_by: None,
+            options: WindowType::Over(WindowMapping::GroupsToRows),
+        } = expr_arena.get(node)
+        else {
+            return None;
+        };
+        let names = partition_by
+            .iter()
+            .map(|node| match expr_arena.get(*node) {
+                AExpr::Column(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if !can_convert_to_hash_agg(*function, expr_arena, self.input_schema) {
+            return None;
+        }
+        match &self.partition_by {
+            Some(partition_by) if partition_by != &names => return None,
+            Some(_) => {},
+            None => self.partition_by = Some(names),
+        }
+        let field = expr_arena
+            .get(node)
+            .to_field(self.input_schema, Context::Default, expr_arena)
+            .ok()?;
+        Some((*function, field.dtype))
+    }
+}
+
+impl RewritingVisitor for WindowRewriter<'_> {
+    type Node = AexprNode;
+    type Arena = Arena<AExpr>;
+
+    fn pre_visit(
+        &mut self,
+        node: &Self::Node,
+        arena: &mut Self::Arena,
+    ) -> PolarsResult<RewriteRecursion> {
+        if matches!(node.to_aexpr(arena), AExpr::Window { .. }) {
+            Ok(RewriteRecursion::MutateAndStop)
+        } else {
+            Ok(RewriteRecursion::NoMutateAndContinue)
+        }
+    }
+
+    fn mutate(
+        &mut self,
+        mut node: Self::Node,
+        arena: &mut Self::Arena,
+    ) -> PolarsResult<Self::Node> {
+        match self.supported_window(node.node(), arena) {
+            Some(agg) => {
+                let name = window_column_name(self.aggs.len());
+                self.aggs.push(agg);
+                node.assign(AExpr::Column(name), arena);
+            },
+            None => self.supported = false,
+        }
+        Ok(node)
+    }
+}
+
+/// Checks if the windows in a projection can be computed by the streaming engine.
+///
+/// That is the case if all windows are aggregations that can be computed with a hash
+/// aggregation, partitioned by the same columns, and if the projection can be streamed once
+/// the windows are replaced by a column. The rewritten expressions are added to the arena,
+/// the original expressions are not modified.
+pub fn window_aggregations(
+    exprs: &[ExprIR],
+    expr_arena: &mut Arena<AExpr>,
+    input_schema: &Schema,
+) -> Option<WindowAggregations> {
+    let mut rewriter = WindowRewriter {
+        input_schema,
+        partition_by: None,
+        aggs: vec![],
+        supported: true,
+    };
+    let exprs = exprs
+        .iter()
+        .map(|e| {
+            let node = AexprNode::new(e.node()).rewrite(&mut rewriter, expr_arena)?;
+            Ok(ExprIR::new(node.node(), OutputName::Alias(e.output_name().clone())))
+        })
+        .collect::<PolarsResult<Vec<_>>>()
+        .ok()?;
+
+    let partition_by = rewriter.partition_by?;
+    if !rewriter.supported || !all_streamable(&exprs, expr_arena, Context::Default) {
+        return None;
+    }
+
+    let n_keys = partition_by.len();
+    let mut schema = Schema::with_capacity(n_keys + rewriter.aggs.len());
+    for name in partition_by {
+        let dtype = input_schema.get(&name)?.clone();
+        schema.with_column(name, dtype);
+    }
+    // no partition columns, or the same column more than once
+    if n_keys == 0 || schema.len() != n_keys {
+        return None;
+    }
+    let aggs = rewriter
+        .aggs
+        .into_iter()
+        .enumerate()
+        .map(|(i, (node, dtype))| {
+            schema.with_column(window_column_name(i), dtype);
+            node
+        })
+        .collect();
+
+    Some(WindowAggregations {
+        exprs,
+        aggs,
+        schema: Arc::new(schema),
+    })
+}
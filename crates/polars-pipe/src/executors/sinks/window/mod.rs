@@ -0,0 +1,415 @@
+mod convert;
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use arrow::array::{ArrayRef, BinaryArray};
+pub use convert::{window_aggregations, WindowAggregations};
+use hashbrown::hash_map::RawEntryMut;
+use polars_core::config::verbose;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_io::ipc::IpcReader;
+use polars_io::SerReader;
+use polars_row::RowsEncoded;
+
+use crate::executors::sinks::io::{block_thread_until_io_thread_done, IOThread};
+use crate::executors::sinks::memory::{MemTracker, MemoryAccountant};
+use crate::executors::sinks::utils::{hash_rows, RowKey};
+use crate::operators::{
+    DataChunk, FinalizedSink, Operator, OperatorResult, PExecutionContext, Sink, SinkResult,
+    Source, SourceResult,
+};
+use crate::pipeline::{morsels_per_sink, FORCE_OOC};
+
+// If this is reached we spill the buffered input to disk,
+// the groups are spilled by the group_by itself
+const TO_DISK_THRESHOLD: f64 = 0.3;
+
+/// Computes the windows of a projection, e.g. `col("x").sum().over("k")`.
+///
+/// The input is buffered while the windows are aggregated by a streaming group_by on the
+/// partition columns. Once all input is seen, the buffered chunks are replayed in order by the
+/// [`WindowSource`], the aggregate of every row is gathered from the groups and the projection
+/// is evaluated. If the input doesn't fit in memory, it is spilled to disk per chunk.
+pub(crate) struct WindowSink {
+    // aggregates the windows per partition
+    group_by: Box<dyn Sink>,
+    // evaluates the projection, once the aggregates are added to the input
+    projection: Box<dyn Operator>,
+    // the buffered input chunks
+    chunks: Vec<DataChunk>,
+    input_schema: SchemaRef,
+    // the partition columns, followed by the aggregates
+    group_schema: SchemaRef,
+    n_keys: usize,
+    output_schema: SchemaRef,
+    // OOC
+    // Stores available memory in the system at the start of this sink.
+    mem_track: MemTracker,
+    // buffer in-memory or out-of-core
+    ooc: bool,
+    // when ooc, we write to disk using an IO thread
+    io_thread: Arc<Mutex<Option<IOThread>>>,
+    to_disk_threshold: f64,
+}
+
+impl WindowSink {
+    pub(crate) fn new(
+        group_by: Box<dyn Sink>,
+        projection: Box<dyn Operator>,
+        input_schema: SchemaRef,
+        group_schema: SchemaRef,
+        n_keys: usize,
+        output_schema: SchemaRef,
+        accountant: Arc<MemoryAccountant>,
+    ) -> Self {
+        let to_disk_threshold = if std::env::var(FORCE_OOC).is_ok() {
+            1.0
+        } else {
+            TO_DISK_THRESHOLD
+        };
+
+        Self {
+            group_by,
+            projection,
+            chunks: vec![],
+            input_schema,
+            group_schema,
+            n_keys,
+            output_schema,
+            mem_track: MemTracker::new(morsels_per_sink(), accountant),
+            ooc: false,
+            io_thread: Default::default(),
+            to_disk_threshold,
+        }
+    }
+
+    fn check_memory_usage(&mut self) -> PolarsResult<()> {
+        if self.mem_track.free_memory_fraction_since_start() < self.to_disk_threshold {
+            if verbose() {
+                eprintln!("OOC window started");
+            }
+            self.ooc = true;
+
+            let mut iot = self.io_thread.lock().unwrap();
+            if iot.is_none() {
+                *iot = Some(IOThread::try_new(
+                    self.input_schema.clone(),
+                    "window",
+                    self.mem_track.accountant().clone(),
+                )?);
+            }
+            let iot = iot.as_ref().unwrap();
+            for chunk in self.chunks.drain(..) {
+                spill_chunk(iot, chunk)
+            }
+        }
+        Ok(())
+    }
+
+    /// Collect the groups of the group_by, which may have gone out-of-core itself.
+    fn finalize_groups(&mut self, context: &PExecutionContext) -> PolarsResult<DataFrame> {
+        match self.group_by.finalize(context)? {
+            FinalizedSink::Finished(df) => Ok(df),
+            FinalizedSink::Source(mut source) => {
+                let mut dfs = vec![];
+                while let SourceResult::GotMoreData(chunks) = source.get_batches(context)? {
+                    dfs.extend(chunks.into_iter().map(|chunk| chunk.data));
+                }
+                if dfs.is_empty() {
+                    Ok(DataFrame::empty_with_schema(&self.group_schema))
+                } else {
+                    Ok(accumulate_dataframes_vertical_unchecked(dfs))
+                }
+            },
+            FinalizedSink::Operator => unreachable!(),
+        }
+    }
+}
+
+// Every chunk is spilled to the partition of its chunk index, so that the chunks can be
+// read back in order.
+fn spill_chunk(iot: &IOThread, chunk: DataChunk) {
+    iot.dump_partition(chunk.chunk_index, chunk.data)
+}
+
+impl Sink for WindowSink {
+    fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        if chunk.is_empty() {
+            return Ok(SinkResult::CanHaveMoreInput);
+        }
+        self.group_by.sink(context, chunk.clone())?;
+        if self.ooc {
+            let iot = self.io_thread.lock().unwrap();
+            spill_chunk(iot.as_ref().unwrap(), chunk);
+        } else {
+            self.chunks.push(chunk);
+            self.check_memory_usage()?;
+        }
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, other: &mut dyn Sink) {
+        let other = other.as_any().downcast_mut::<Self>().unwrap();
+        self.group_by.combine(other.group_by.as_mut());
+        self.chunks.append(&mut other.chunks);
+    }
+
+    fn split(&self, thread_no: usize) -> Box<dyn Sink> {
+        Box::new(Self {
+            group_by: self.group_by.split(thread_no),
+            projection: self.projection.split(thread_no),
+            chunks: vec![],
+            input_schema: self.input_schema.clone(),
+            group_schema: self.group_schema.clone(),
+            n_keys: self.n_keys,
+            output_schema: self.output_schema.clone(),
+            mem_track: self.mem_track.clone(),
+            ooc: self.ooc,
+            io_thread: self.io_thread.clone(),
+            to_disk_threshold: self.to_disk_threshold,
+        })
+    }
+
+    fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        let groups = self.finalize_groups(context)?;
+        let lookup = GroupLookup::new(groups, self.n_keys)?;
+
+        // any of the threads may have gone out-of-core
+        let iot = self.io_thread.lock().unwrap().take();
+        let input = match iot {
+            None => {
+                self.chunks.sort_by_key(|chunk| chunk.chunk_index);
+                WindowInput::InMemory(std::mem::take(&mut self.chunks).into())
+            },
+            Some(iot) => {
+                for chunk in self.chunks.drain(..) {
+                    spill_chunk(&iot, chunk)
+                }
+                block_thread_until_io_thread_done(&iot);
+                if context.verbose {
+                    eprintln!("finish streaming window with out-of-core input")
+                }
+                WindowInput::spilled(iot)?
+            },
+        };
+
+        Ok(FinalizedSink::Source(Box::new(WindowSource {
+            input,
+            lookup,
+            projection: self.projection.split(0),
+            output_names: self.output_schema.iter_names().cloned().collect(),
+        })))
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        "window"
+    }
+}
+
+/// Finds the aggregates of the group of every input row.
+struct GroupLookup {
+    // the row encoded partition columns of every group
+    table: PlIdHashMap<RowKey, IdxSize>,
+    // the aggregates of every group
+    aggs: DataFrame,
+    key_names: Vec<PlSmallStr>,
+    hb: PlRandomState,
+    // amortize allocations
+    key_columns: Vec<ArrayRef>,
+    rows_encoded: RowsEncoded,
+    hashes: Vec<u64>,
+}
+
+impl GroupLookup {
+    fn new(mut groups: DataFrame, n_keys: usize) -> PolarsResult<Self> {
+        groups.as_single_chunk_par();
+        let names = groups.get_column_names_owned();
+        let mut out = Self {
+            table: Default::default(),
+            aggs: groups._select_impl_unchecked(&names[n_keys..])?,
+            key_names: names[..n_keys].to_vec(),
+            hb: Default::default(),
+            key_columns: vec![],
+            rows_encoded: Default::default(),
+            hashes: vec![],
+        };
+
+        let rows = out.encode_keys(&groups)?;
+        for (i, (row, &hash)) in rows.values_iter().zip(&out.hashes).enumerate() {
+            match out
+                .table
+                .raw_entry_mut()
+                .from_hash(hash, |k| k.hash == hash && k.row.as_ref() == row)
+            {
+                RawEntryMut::Vacant(entry) => {
+                    let key = RowKey {
+                        hash,
+                        row: row.into(),
+                    };
+                    entry.insert(key, i as IdxSize);
+                },
+                // The aggregates of one of the groups would be wrong.
+                RawEntryMut::Occupied(_) => polars_bail!(
+                    ComputeError: "the group_by of a streaming window returned a group more than once"
+                ),
+            }
+        }
+        out.hashes.clear();
+        Ok(out)
+    }
+
+    fn encode_keys(&mut self, df: &DataFrame) -> PolarsResult<BinaryArray<i64>> {
+        for name in self.key_names.iter() {
+            let s = df.column(name)?.to_physical_repr().rechunk();
+            self.key_columns.push(s.array_ref(0).clone());
+        }
+        polars_row::convert_columns_amortized_no_order(&self.key_columns, &mut self.rows_encoded);
+        self.key_columns.clear();
+
+        // SAFETY: we keep rows-encode alive until the next chunk
+        let rows = unsafe { self.rows_encoded.borrow_array() };
+        hash_rows(&rows, &mut self.hashes, &self.hb);
+        Ok(rows)
+    }
+
+    /// Add the aggregates of the groups of the rows to `df`.
+    fn add_aggregates(&mut self, df: &DataFrame) -> PolarsResult<DataFrame> {
+        let rows = self.encode_keys(df)?;
+        let idx = rows
+            .values_iter()
+            .zip(&self.hashes)
+            .map(|(row, &hash)| {
+                self.table
+                    .raw_entry()
+                    .from_hash(hash, |k| k.hash == hash && k.row.as_ref() == row)
+                    .map(|(_, idx)| *idx)
+                    // every key of the input should be in the groups
+                    .ok_or_else(|| {
+                        polars_err!(
+                            ComputeError: "the group_by of a streaming window didn't return the group of a row"
+                        )
+                    })
+            })
+            .collect::<PolarsResult<Vec<_>>>();
+        self.hashes.clear();
+        let idx = idx?;
+
+        // SAFETY: the indexes are rows of the groups.
+        let aggs = unsafe { self.aggs._take_unchecked_slice(&idx, false) };
+        df.hstack(aggs.get_columns())
+    }
+}
+
+enum WindowInput {
+    InMemory(VecDeque<DataChunk>),
+    Spilled {
+        // Holding this keeps the lockfile in place
+        io_thread: IOThread,
+        // the directories of the spilled chunks, ordered by chunk index
+        dirs: VecDeque<(IdxSize, PathBuf)>,
+    },
+}
+
+impl WindowInput {
+    fn spilled(io_thread: IOThread) -> PolarsResult<Self> {
+        let mut dirs = std::fs::read_dir(&io_thread.dir)?
+            .map(|e| e.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|path| {
+                let chunk_index = path.file_name()?.to_str()?.parse::<IdxSize>().ok()?;
+                Some((chunk_index, path))
+            })
+            .collect::<Vec<_>>();
+        dirs.sort_unstable_by_key(|(chunk_index, _)| *chunk_index);
+
+        Ok(Self::Spilled {
+            io_thread,
+            dirs: dirs.into(),
+        })
+    }
+
+    fn next_chunks(&mut self, n: usize) -> PolarsResult<Vec<DataChunk>> {
+        match self {
+            Self::InMemory(chunks) => Ok(chunks.drain(..n.min(chunks.len())).collect()),
+            Self::Spilled { io_thread, dirs } => {
+                let mut chunks = Vec::with_capacity(n);
+                while chunks.len() < n {
+                    let Some((chunk_index, dir)) = dirs.pop_front() else {
+                        break;
+                    };
+                    // files are named by the order in which they were written
+                    let mut files = std::fs::read_dir(&dir)?
+                        .map(|e| e.map(|e| e.path()))
+                        .collect::<Result<Vec<_>, _>>()?
+                        .into_iter()
+                        .filter_map(|path| {
+                            let count = path.file_stem()?.to_str()?.parse::<usize>().ok()?;
+                            Some((count, path))
+                        })
+                        .collect::<Vec<_>>();
+                    files.sort_unstable_by_key(|(count, _)| *count);
+
+                    for (_, path) in files {
+                        let file = polars_utils::open_file(&path)?;
+                        let df = IpcReader::new(file).finish()?;
+                        chunks.push(DataChunk::new(chunk_index, df));
+                    }
+                    io_thread.clean(dir);
+                }
+                Ok(chunks)
+            },
+        }
+    }
+}
+
+/// Replays the input of the [`WindowSink`] in order and evaluates the projection.
+struct WindowSource {
+    input: WindowInput,
+    lookup: GroupLookup,
+    projection: Box<dyn Operator>,
+    output_names: Vec<PlSmallStr>,
+}
+
+impl WindowSource {
+    fn evaluate(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: DataChunk,
+    ) -> PolarsResult<DataChunk> {
+        let data = self.lookup.add_aggregates(&chunk.data)?;
+        let chunk = match self.projection.execute(context, &chunk.with_data(data))? {
+            OperatorResult::Finished(chunk) => chunk,
+            _ => unreachable!(),
+        };
+        // remove the aggregates that were not projected
+        let data = chunk.data._select_impl_unchecked(&self.output_names)?;
+        Ok(chunk.with_data(data))
+    }
+}
+
+impl Source for WindowSource {
+    fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        let chunks = self.input.next_chunks(morsels_per_sink())?;
+        if chunks.is_empty() {
+            return Ok(SourceResult::Finished);
+        }
+        let chunks = chunks
+            .into_iter()
+            .map(|chunk| self.evaluate(context, chunk))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        Ok(SourceResult::GotMoreData(chunks))
+    }
+
+    fn fmt(&self) -> &str {
+        "window-source"
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Mutex;
+
+use polars_core::prelude::*;
+use polars_plan::plans::{AnonymousScan, AnonymousScanArgs};
+
+use crate::executors::sources::get_source_index;
+use crate::operators::{DataChunk, PExecutionContext, Source, SourceResult};
+
+type Batches = Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>;
+
+/// Pulls the batches of an [`AnonymousScan`] that allows streaming.
+pub struct AnonymousSource {
+    function: Arc<dyn AnonymousScan>,
+    args: Option<AnonymousScanArgs>,
+    // The batches are only accessed through `&mut self`, the mutex makes the source `Sync`.
+    batches: Option<Mutex<Batches>>,
+    /// The rows of the slice that are left to skip and to take.
+    offset: usize,
+    len: Option<usize>,
+    with_columns: Option<Arc<[PlSmallStr]>>,
+    finished: bool,
+}
+
+impl AnonymousSource {
+    pub(crate) fn new(
+        function: Arc<dyn AnonymousScan>,
+        mut args: AnonymousScanArgs,
+        slice: Option<(usize, usize)>,
+    ) -> Self {
+        args.n_rows = slice.map(|(offset, len)| offset + len);
+        AnonymousSource {
+            function,
+            args: Some(args),
+            batches: None,
+            offset: slice.map(|slice| slice.0).unwrap_or(0),
+            len: slice.map(|slice| slice.1),
+            with_columns: None,
+            finished: false,
+        }
+    }
+}
+
+impl Source for AnonymousSource {
+    fn get_batches(&mut self, _context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        if self.finished {
+            return Ok(SourceResult::Finished);
+        }
+        if let Some(args) = self.args.take() {
+            self.with_columns = args
+                .with_columns
+                .clone()
+                .filter(|columns| !columns.is_empty());
+            self.batches = Some(Mutex::new(self.function.scan_batches(args)?));
+        }
+        let batches = self.batches.as_mut().unwrap().get_mut().unwrap();
+        let Some(mut df) = batches.next().transpose()? else {
+            self.finished = true;
+            self.batches = None;
+            return Ok(SourceResult::Finished);
+        };
+
+        // The scan may not apply the projection and the slice, so they are applied to the batches
+        // here.
+        if let Some(columns) = &self.with_columns {
+            if df.width() != columns.len() {
+                df = df.select(columns.iter().cloned())?;
+            }
+        }
+        if self.offset > 0 {
+            let skip = self.offset.min(df.height());
+            df = df.slice(skip as i64, df.height() - skip);
+            self.offset -= skip;
+        }
+        if let Some(len) = &mut self.len {
+            df = df.head(Some(*len));
+            *len -= df.height();
+            if *len == 0 {
+                self.finished = true;
+                self.batches = None;
+            }
+        }
+        df.as_single_chunk_par();
+        let chunk_index = get_source_index(1) as IdxSize;
+        Ok(SourceResult::GotMoreData(vec![DataChunk::new(
+            chunk_index,
+            df,
+        )]))
+    }
+
+    fn fmt(&self) -> &str {
+        "anonymous"
+    }
+}
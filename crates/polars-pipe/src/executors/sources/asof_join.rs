@@ -0,0 +1,440 @@
+use arrow::array::BinaryArray;
+use polars_core::prelude::sort::arg_sort_multiple::_get_rows_encoded_arr;
+use polars_core::prelude::*;
+use polars_core::utils::{accumulate_dataframes_vertical_unchecked, split_df};
+use polars_core::POOL;
+use polars_ops::prelude::*;
+use polars_plan::prelude::*;
+
+use crate::executors::sources::get_source_index;
+use crate::expressions::PhysicalPipedExpr;
+use crate::operators::{
+    DataChunk, Operator, OperatorResult, PExecutionContext, Source, SourceResult,
+};
+
+/// The left and right input of the asof join at `node`, if both are scans, or merges of sorted
+/// scans, that are merged by an [`AsofJoinSource`].
+pub fn asof_join_inputs(node: Node, lp_arena: &Arena<IR>) -> Option<[Node; 2]> {
+    let IR::Join {
+        input_left,
+        input_right,
+        left_on,
+        right_on,
+        options,
+        ..
+    } = lp_arena.get(node)
+    else {
+        return None;
+    };
+    let args = &options.args;
+    let JoinType::AsOf(asof_options) = &args.how else {
+        return None;
+    };
+    // Forward and nearest matches of a `by` group may lie arbitrarily far ahead in the right
+    // input, so without a tolerance that bounds them the right input isn't streamed.
+    if asof_options.strategy != AsofStrategy::Backward
+        && asof_options.left_by.is_some()
+        && asof_options.tolerance.is_none()
+    {
+        return None;
+    }
+    if left_on.len() != 1
+        || right_on.len() != 1
+        || args.slice.is_some()
+        || args.validation.needs_checks()
+    {
+        return None;
+    }
+    let inputs = [*input_left, *input_right];
+    inputs
+        .iter()
+        .all(|&input| match lp_arena.get(input) {
+            IR::Scan { .. } | IR::DataFrameScan { .. } => true,
+            #[cfg(feature = "merge_sorted")]
+            IR::MapFunction {
+                function: FunctionIR::MergeSorted { .. },
+                ..
+            } => super::merge_sorted_scans(input, lp_arena).is_some(),
+            _ => false,
+        })
+        .then_some(inputs)
+}
+
+/// The source of an input of the asof join, with the operators that filter its chunks.
+pub(crate) type AsofInput = (Box<dyn Source>, Vec<Box<dyn Operator>>);
+
+/// Pulled rows of an input, with their keys.
+struct Batch {
+    df: DataFrame,
+    key: Series,
+    // the row-encoded keys, these compare in sort order
+    encoded: BinaryArray<i64>,
+}
+
+impl Batch {
+    fn new(df: DataFrame, key: Series) -> PolarsResult<Self> {
+        let encoded = _get_rows_encoded_arr(&[key.clone()], &[false], &[false])?;
+        Ok(Self { df, key, encoded })
+    }
+
+    fn last_key(&self) -> &[u8] {
+        self.encoded.value(self.encoded.len() - 1)
+    }
+
+    /// The number of rows with a key smaller than `bound`, or smaller than or equal to it if
+    /// `inclusive`. The keys are sorted, so this is a binary search.
+    fn partition_point(&self, bound: &[u8], inclusive: bool) -> usize {
+        let (mut lo, mut hi) = (0, self.encoded.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let key = self.encoded.value(mid);
+            if key < bound || (inclusive && key == bound) {
+                lo = mid + 1
+            } else {
+                hi = mid
+            }
+        }
+        lo
+    }
+
+    fn slice(&self, offset: usize, len: usize) -> Self {
+        Self {
+            df: self.df.slice(offset as i64, len),
+            key: self.key.slice(offset as i64, len),
+            encoded: self.encoded.clone().sliced(offset, len),
+        }
+    }
+
+    /// Remove the groups that have a row after `last` from `missing`. Returns whether there is
+    /// a row after `last`.
+    fn remove_found(
+        &self,
+        last: &[u8],
+        by: Option<&[PlSmallStr]>,
+        missing: &mut PlHashSet<Vec<u8>>,
+    ) -> PolarsResult<bool> {
+        let after = self.partition_point(last, true);
+        if let Some(by) = by {
+            if !missing.is_empty() && after < self.encoded.len() {
+                for group in self.groups(by, after)?.values_iter() {
+                    missing.remove(group);
+                }
+            }
+        }
+        Ok(after < self.encoded.len())
+    }
+
+    /// The row-encoded largest key that the last key of this batch can match forward within
+    /// `tolerance`, which is in the unit of the physical keys.
+    fn tolerance_bound(&self, tolerance: &AnyValue<'static>) -> PolarsResult<Vec<u8>> {
+        let last = self.key.slice(-1, 1).to_physical_repr().into_owned();
+        let tolerance = Series::from_any_values(PlSmallStr::EMPTY, &[tolerance.clone()], false)?
+            .cast(last.dtype())?;
+        let bound = (&last + &tolerance)?.cast(self.key.dtype())?;
+        let encoded = _get_rows_encoded_arr(&[bound], &[false], &[false])?;
+        Ok(encoded.value(0).to_vec())
+    }
+
+    /// The row-encoded `by` groups of the rows from `offset`.
+    fn groups(&self, by: &[PlSmallStr], offset: usize) -> PolarsResult<BinaryArray<i64>> {
+        let columns = by
+            .iter()
+            .map(|name| {
+                let s = self.df.column(name)?;
+                Ok(s.slice(offset as i64, s.len() - offset))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let flags = vec![false; by.len()];
+        _get_rows_encoded_arr(&columns, &flags, &flags)
+    }
+}
+
+/// An input of the asof join, of which the batches are pulled in order.
+struct Input {
+    source: Box<dyn Source>,
+    // the filters and projections that are pushed down into the scan
+    operators: Vec<Box<dyn Operator>>,
+    key: Arc<dyn PhysicalPipedExpr>,
+    // the largest key that was pulled, to check that the input is sorted
+    last_key: Option<Vec<u8>>,
+    finished: bool,
+}
+
+impl Input {
+    /// Pull the next rows. Returns `None` if the input is depleted.
+    fn next(&mut self, context: &PExecutionContext) -> PolarsResult<Option<Batch>> {
+        loop {
+            if self.finished {
+                return Ok(None);
+            }
+            let mut chunks = match self.source.get_batches(context)? {
+                SourceResult::Finished => {
+                    self.finished = true;
+                    continue;
+                },
+                SourceResult::GotMoreData(chunks) => chunks,
+            };
+            chunks.sort_unstable_by_key(|chunk| chunk.chunk_index);
+            let mut dfs = Vec::with_capacity(chunks.len());
+            for mut chunk in chunks {
+                for op in &mut self.operators {
+                    chunk = match op.execute(context, &chunk)? {
+                        OperatorResult::Finished(chunk) => chunk,
+                        _ => polars_bail!(
+                            ComputeError: "operator '{}' is not supported in an asof join input",
+                            op.fmt()
+                        ),
+                    };
+                }
+                dfs.push(chunk.data);
+            }
+            let mut df = accumulate_dataframes_vertical_unchecked(dfs);
+            if df.height() == 0 {
+                continue;
+            }
+            df.as_single_chunk_par();
+            let key = self
+                .key
+                .evaluate(&DataChunk::new(0, df.clone()), &context.execution_state)?
+                .rechunk();
+            let batch = Batch::new(df, key)?;
+
+            let keys = &batch.encoded;
+            let sorted = self
+                .last_key
+                .as_deref()
+                .map_or(true, |last| last <= keys.value(0))
+                && (1..keys.len()).all(|i| keys.value(i - 1) <= keys.value(i));
+            polars_ensure!(
+                sorted,
+                InvalidOperation: "argument in operation 'asof_join' is not sorted, please sort \
+                the 'expr/series/column' first"
+            );
+            self.last_key = Some(batch.last_key().to_vec());
+            return Ok(Some(batch));
+        }
+    }
+}
+
+/// An asof join of two scans that are sorted on their keys, which merges the inputs instead of
+/// collecting the right table.
+///
+/// Every batch of the left input is joined with a window of the right rows: the rows from the
+/// last row before its first key, to the first row after its last key. Those are all the rows
+/// that its keys can match, because the inputs are sorted. After the join the rows before the
+/// last left key are dropped, except the last one, which the next left keys can still match
+/// backward. With `by` groups that is the last row of every group, and for the forward and
+/// nearest strategy the window is extended until it has a row after the last left key of every
+/// group of the batch, or until it is past the tolerance of the last left key. A group that is
+/// sparse in the right input can thus keep the rows up to its next row or the tolerance in the
+/// window.
+///
+/// Both keys must be sorted over the whole input, also with `by` groups. The inputs are checked
+/// as they are pulled, and an unsorted input raises an error.
+pub struct AsofJoinSource {
+    left: Input,
+    right: Input,
+    args: JoinArgs,
+    // the right rows that the next left rows can match, in the order of their keys
+    window: Vec<Batch>,
+    empty_right: DataFrame,
+    n_threads: usize,
+}
+
+impl AsofJoinSource {
+    pub(crate) fn new(
+        sources: [AsofInput; 2],
+        keys: [Arc<dyn PhysicalPipedExpr>; 2],
+        right_schema: &Schema,
+        args: JoinArgs,
+    ) -> Self {
+        let [(left, left_operators), (right, right_operators)] = sources;
+        let [left_key, right_key] = keys;
+        let input = |source, operators, key| Input {
+            source,
+            operators,
+            key,
+            last_key: None,
+            finished: false,
+        };
+        Self {
+            left: input(left, left_operators, left_key),
+            right: input(right, right_operators, right_key),
+            args,
+            window: vec![],
+            empty_right: DataFrame::empty_with_schema(right_schema),
+            n_threads: POOL.current_num_threads(),
+        }
+    }
+
+    fn options(&self) -> &AsOfOptions {
+        let JoinType::AsOf(options) = &self.args.how else {
+            unreachable!()
+        };
+        options
+    }
+
+    /// Pull the right rows that the keys of `left` up to `last` can match into the window.
+    fn fill_window(
+        &mut self,
+        context: &PExecutionContext,
+        left: &Batch,
+        last: &[u8],
+    ) -> PolarsResult<()> {
+        let options = self.options();
+        // the groups that don't have a right row after `last` yet
+        let mut missing = PlHashSet::new();
+        let mut right_by = None;
+        if let (Some(left_by), Some(by)) = (&options.left_by, &options.right_by) {
+            if options.strategy != AsofStrategy::Backward {
+                let groups = left.groups(left_by, 0)?;
+                missing.extend(groups.values_iter().map(<[u8]>::to_vec));
+                right_by = Some(by.clone());
+            }
+        }
+        // The right rows after the tolerance of the last left key can't match, so the missing
+        // groups stop pulling there. Without a tolerance the join doesn't stream, see
+        // `asof_join_inputs`.
+        let bound = match (&right_by, &options.tolerance) {
+            (Some(_), Some(tolerance)) => Some(left.tolerance_bound(tolerance)?),
+            _ => None,
+        };
+        let past_bound = |batch: &Batch| {
+            bound
+                .as_deref()
+                .is_some_and(|bound| batch.last_key() > bound)
+        };
+
+        let mut beyond = false;
+        let mut past = false;
+        for batch in &self.window {
+            beyond |= batch.remove_found(last, right_by.as_deref(), &mut missing)?;
+            past |= past_bound(batch);
+        }
+        while !beyond || (!missing.is_empty() && !past) {
+            let Some(batch) = self.right.next(context)? else {
+                break;
+            };
+            beyond |= batch.remove_found(last, right_by.as_deref(), &mut missing)?;
+            past |= past_bound(&batch);
+            self.window.push(batch);
+        }
+        Ok(())
+    }
+
+    /// Drop the rows of the window that the left keys from `last` can't match: the rows before
+    /// `last`, except the last one of every group for the backward and nearest strategy.
+    fn prune_window(&mut self, last: &[u8]) -> PolarsResult<()> {
+        let n_before = self
+            .window
+            .iter()
+            .take_while(|batch| batch.last_key() < last)
+            .count();
+        let mut window = std::mem::take(&mut self.window);
+        let mut after = window.split_off(n_before);
+        if let Some(first) = after.first_mut() {
+            let offset = first.partition_point(last, false);
+            if offset > 0 {
+                window.push(first.slice(0, offset));
+                *first = first.slice(offset, first.encoded.len() - offset);
+            }
+        }
+
+        let options = self.options();
+        if options.strategy != AsofStrategy::Forward && !window.is_empty() {
+            let before = concat_batches(window)?;
+            let n = before.encoded.len();
+            let idx = match &options.right_by {
+                Some(by) => {
+                    let groups = before.groups(by, 0)?;
+                    let mut seen = PlHashSet::new();
+                    let mut idx = (0..n)
+                        .rev()
+                        .filter(|&i| seen.insert(groups.value(i)))
+                        .map(|i| i as IdxSize)
+                        .collect::<Vec<_>>();
+                    idx.reverse();
+                    idx
+                },
+                None => vec![(n - 1) as IdxSize],
+            };
+            let idx = IdxCa::from_vec(PlSmallStr::EMPTY, idx);
+            // SAFETY: the indices are rows of `before`.
+            let kept = unsafe {
+                Batch::new(
+                    before.df.take_unchecked(&idx),
+                    before.key.take_unchecked(&idx),
+                )?
+            };
+            after.insert(0, kept);
+        }
+        self.window = after;
+        Ok(())
+    }
+}
+
+fn concat_batches(batches: Vec<Batch>) -> PolarsResult<Batch> {
+    if batches.len() == 1 {
+        return Ok(batches.into_iter().next().unwrap());
+    }
+    let mut key = batches[0].key.clear();
+    let mut dfs = Vec::with_capacity(batches.len());
+    for batch in batches {
+        key.append(&batch.key)?;
+        dfs.push(batch.df);
+    }
+    Batch::new(accumulate_dataframes_vertical_unchecked(dfs), key.rechunk())
+}
+
+impl Source for AsofJoinSource {
+    fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        let Some(left) = self.left.next(context)? else {
+            return Ok(SourceResult::Finished);
+        };
+        let last = left.last_key().to_vec();
+        self.fill_window(context, &left, &last)?;
+
+        let (right_df, right_key) = match self.window.as_slice() {
+            [] => {
+                let key = self.right.key.evaluate(
+                    &DataChunk::new(0, self.empty_right.clone()),
+                    &context.execution_state,
+                )?;
+                (self.empty_right.clone(), key)
+            },
+            [batch] => (batch.df.clone(), batch.key.clone()),
+            batches => {
+                let mut key = batches[0].key.clear();
+                for batch in batches {
+                    key.append(&batch.key)?;
+                }
+                let df = accumulate_dataframes_vertical_unchecked(
+                    batches.iter().map(|batch| batch.df.clone()),
+                );
+                (df, key)
+            },
+        };
+        let mut df = left.df._join_impl(
+            &right_df,
+            vec![left.key],
+            vec![right_key],
+            self.args.clone(),
+            false,
+            false,
+        )?;
+        self.prune_window(&last)?;
+
+        let dfs = split_df(&mut df, self.n_threads, true);
+        let chunk_offset = get_source_index(dfs.len() as u32) as IdxSize;
+        let chunks = dfs
+            .into_iter()
+            .enumerate()
+            .map(|(i, df)| DataChunk::new(chunk_offset + i as IdxSize, df))
+            .collect();
+        Ok(SourceResult::GotMoreData(chunks))
+    }
+
+    fn fmt(&self) -> &str {
+        "asof_join"
+    }
+}
@@ -1,16 +1,26 @@
-use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Mutex;
 
 use polars_core::error::feature_gated;
 use polars_core::{config, POOL};
-use polars_io::csv::read::{BatchedCsvReader, CsvReadOptions, CsvReader};
-use polars_io::path_utils::is_cloud_url;
+#[cfg(feature = "cloud")]
+use polars_io::cloud::CloudOptions;
+#[cfg(feature = "cloud")]
+use polars_io::csv::read::CsvBlockFetcher;
+use polars_io::csv::read::{BatchedCsvReader, CsvBlockReader, CsvReadOptions, CsvReader};
+use polars_io::mmap::MmapBytesReader;
+use polars_io::path_utils::{is_cloud_url, resolve_homedir};
+#[cfg(feature = "cloud")]
+use polars_io::pl_async::get_runtime;
+use polars_io::utils::{decompressing_reader, SupportedCompression};
 use polars_plan::global::_set_n_rows_for_scan;
+use polars_plan::plans::hive::HivePartitions;
 use polars_plan::plans::ScanSources;
 use polars_plan::prelude::FileScanOptions;
 use polars_utils::itertools::Itertools;
 
 use super::*;
-use crate::pipeline::determine_chunk_size;
+use crate::pipeline::chunk_size::AdaptiveChunkSize;
 
 pub(crate) struct CsvSource {
     #[allow(dead_code)]
@@ -19,17 +29,29 @@ pub(crate) struct CsvSource {
     // Safety: `reader` outlives `batched_reader`
     // (so we have to order the `batched_reader` first in the struct fields)
     batched_reader: Option<BatchedCsvReader<'static>>,
-    reader: Option<CsvReader<File>>,
+    reader: Option<CsvReader<Box<dyn MmapBytesReader>>>,
     n_threads: usize,
+    chunk_size: AdaptiveChunkSize,
     sources: ScanSources,
     options: Option<CsvReadOptions>,
     file_options: FileScanOptions,
+    #[cfg(feature = "cloud")]
+    cloud_options: Option<CloudOptions>,
+    // Fetches line-aligned blocks of the current file if it is read with range requests.
+    #[cfg(feature = "cloud")]
+    block_fetcher: Option<CsvBlockFetcher>,
+    // Yields line-aligned blocks of the current file if it is decompressed while reading.
+    // The reader is only accessed through `&mut self`, the mutex makes the source `Sync`.
+    block_reader: Option<Mutex<CsvBlockReader>>,
     verbose: bool,
     // state for multi-file reads
     current_path_idx: usize,
     n_rows_read: usize,
     first_schema: Schema,
     include_file_path: Option<StringChunked>,
+    hive_parts: Option<Arc<Vec<HivePartitions>>>,
+    // The hive partition columns of the current file.
+    hive_columns: Option<Vec<Series>>,
 }
 
 impl CsvSource {
@@ -39,7 +61,7 @@ impl CsvSource {
     fn init_next_reader(&mut self) -> PolarsResult<()> {
         let paths = self
             .sources
-            .as_paths()
+            .into_paths()
             .ok_or_else(|| polars_err!(nyi = "Streaming scanning of in-memory buffers"))?;
         let file_options = self.file_options.clone();
 
@@ -48,9 +70,44 @@ impl CsvSource {
             x.1
         });
 
-        if self.current_path_idx == paths.len()
-            || (n_rows.is_some() && n_rows.unwrap() <= self.n_rows_read)
-        {
+        if n_rows.is_some() && n_rows.unwrap() <= self.n_rows_read {
+            return Ok(());
+        }
+
+        // Continue with the next block of the file that is read with range requests.
+        #[cfg(feature = "cloud")]
+        if let Some(fetcher) = self.block_fetcher.as_mut() {
+            match get_runtime().block_on_potential_spawn(fetcher.next_block())? {
+                Some(block) => {
+                    let path = &paths[self.current_path_idx - 1];
+                    let reader =
+                        self.prepare_options(false)?
+                            .into_reader_with_file_handle(
+                                Box::new(std::io::Cursor::new(block)) as Box<dyn MmapBytesReader>
+                            );
+                    return self.finish_init_reader(reader, path);
+                },
+                None => self.block_fetcher = None,
+            }
+        }
+
+        // Continue with the next block of the file that is decompressed while reading.
+        if let Some(block_reader) = self.block_reader.as_mut() {
+            match block_reader.get_mut().unwrap().next_block()? {
+                Some(block) => {
+                    let path = &paths[self.current_path_idx - 1];
+                    let reader =
+                        self.prepare_options(false)?
+                            .into_reader_with_file_handle(
+                                Box::new(std::io::Cursor::new(block)) as Box<dyn MmapBytesReader>
+                            );
+                    return self.finish_init_reader(reader, path);
+                },
+                None => self.block_reader = None,
+            }
+        }
+
+        if self.current_path_idx == paths.len() {
             return Ok(());
         }
         let path = &paths[self.current_path_idx];
@@ -64,22 +121,128 @@ impl CsvSource {
 
         self.current_path_idx += 1;
 
-        let options = self.options.clone().unwrap();
-        let mut with_columns = file_options.with_columns;
-        let mut projected_len = 0;
-        with_columns
-            .as_ref()
-            .inspect(|columns| projected_len = columns.len());
+        let options = self.prepare_options(true)?;
 
-        if projected_len == 0 {
-            with_columns = None;
-        }
+        let reader: CsvReader<Box<dyn MmapBytesReader>> = if run_async {
+            feature_gated!("cloud", {
+                let parse_options = options.get_parse_options();
+                let mut fetcher =
+                    get_runtime().block_on_potential_spawn(CsvBlockFetcher::try_new_from_path(
+                        path.to_str().unwrap(),
+                        self.cloud_options.as_ref(),
+                        parse_options.quote_char,
+                        parse_options.eol_char,
+                    ))?;
 
-        let n_cols = if projected_len > 0 {
-            projected_len
+                match get_runtime().block_on_potential_spawn(fetcher.next_block())? {
+                    // Compressed files, and files whose line endings cannot be found in the
+                    // raw bytes, cannot be split in blocks, so we fetch the whole file. The
+                    // line numbers of malformed rows are only known if the whole file is read.
+                    Some(block)
+                        if polars_io::utils::is_compressed(&block)
+                            || !parse_options.encoding.is_ascii_compatible()
+                            || options.malformed_rows.is_some() =>
+                    {
+                        if self.verbose {
+                            eprintln!("cannot split CSV file in blocks; fetching the whole file")
+                        }
+                        let file = polars_io::file_cache::FILE_CACHE
+                            .get_entry(path.to_str().unwrap())
+                            // Safety: This was initialized by schema inference.
+                            .unwrap()
+                            .try_open_assume_latest()?;
+                        match self.open_file_reader(file, options)? {
+                            Some(reader) => reader,
+                            None => return self.init_next_reader(),
+                        }
+                    },
+                    Some(block) => {
+                        if self.verbose {
+                            eprintln!(
+                                "reading CSV file of {} bytes with range requests",
+                                fetcher.file_size()
+                            )
+                        }
+                        self.block_fetcher = Some(fetcher);
+                        options.into_reader_with_file_handle(
+                            Box::new(std::io::Cursor::new(block)) as Box<dyn MmapBytesReader>
+                        )
+                    },
+                    // Empty file, continue with the next one.
+                    None => return self.init_next_reader(),
+                }
+            })
         } else {
-            self.schema.len()
+            let file = polars_utils::open_file(&resolve_homedir(path))?;
+            match self.open_file_reader(file, options)? {
+                Some(reader) => reader,
+                // Empty compressed file, continue with the next one.
+                None => return self.init_next_reader(),
+            }
         };
+
+        self.finish_init_reader(reader, path)
+    }
+
+    /// Create the reader of a local file. A compressed file is decompressed while it is read,
+    /// in blocks of complete lines, so that the decompressed file is never fully held in memory.
+    /// Returns `None` if the file is compressed and empty.
+    fn open_file_reader(
+        &mut self,
+        mut file: std::fs::File,
+        options: CsvReadOptions,
+    ) -> PolarsResult<Option<CsvReader<Box<dyn MmapBytesReader>>>> {
+        let mut magic = Vec::with_capacity(10);
+        (&mut file).take(10).read_to_end(&mut magic)?;
+        file.seek(SeekFrom::Start(0))?;
+
+        let parse_options = options.get_parse_options();
+        match SupportedCompression::check(&magic) {
+            // The line endings of other encodings cannot be found in the decompressed bytes, and
+            // the line numbers of malformed rows are only known if the whole file is read.
+            Some(compression)
+                if parse_options.encoding.is_ascii_compatible()
+                    && options.malformed_rows.is_none() =>
+            {
+                if self.verbose {
+                    eprintln!("decompressing {compression:?} compressed CSV file while reading")
+                }
+                let mut block_reader = CsvBlockReader::new(
+                    decompressing_reader(file, compression)?,
+                    parse_options.quote_char,
+                    parse_options.eol_char,
+                );
+                let Some(block) = block_reader.next_block()? else {
+                    return Ok(None);
+                };
+                self.block_reader = Some(Mutex::new(block_reader));
+                Ok(Some(options.into_reader_with_file_handle(
+                    Box::new(std::io::Cursor::new(block)) as Box<dyn MmapBytesReader>,
+                )))
+            },
+            _ => Ok(Some(options.into_reader_with_file_handle(
+                Box::new(file) as Box<dyn MmapBytesReader>
+            ))),
+        }
+    }
+
+    /// Prepare the reader options for the next file or block. Only the start of a file can
+    /// contain a header and rows to skip.
+    fn prepare_options(&self, start_of_file: bool) -> PolarsResult<CsvReadOptions> {
+        let file_options = &self.file_options;
+        let mut options = self.options.clone().unwrap();
+        if !start_of_file {
+            options = options
+                .with_has_header(false)
+                .with_skip_rows(0)
+                .with_skip_rows_after_header(0)
+                .with_raise_if_empty(false);
+        }
+
+        let with_columns = file_options
+            .with_columns
+            .clone()
+            .filter(|columns| !columns.is_empty());
         let n_rows = _set_n_rows_for_scan(
             file_options
                 .slice
@@ -89,51 +252,43 @@ impl CsvSource {
                 })
                 .map(|n| n.saturating_sub(self.n_rows_read)),
         );
-        let row_index = file_options.row_index.map(|mut ri| {
+        let row_index = file_options.row_index.clone().map(|mut ri| {
             ri.offset += self.n_rows_read as IdxSize;
             ri
         });
-        // inversely scale the chunk size by the number of threads so that we reduce memory pressure
-        // in streaming
-        let chunk_size = determine_chunk_size(n_cols, POOL.current_num_threads())?;
-
+        let chunk_size = self.chunk_size.get();
         if self.verbose {
             eprintln!("STREAMING CHUNK SIZE: {chunk_size} rows")
         }
 
-        let options = options
+        Ok(options
             .with_schema(Some(self.schema.clone()))
             .with_n_rows(n_rows)
             .with_columns(with_columns)
             .with_rechunk(false)
-            .with_row_index(row_index);
-
-        let reader: CsvReader<File> = if run_async {
-            feature_gated!("cloud", {
-                options.into_reader_with_file_handle(
-                    polars_io::file_cache::FILE_CACHE
-                        .get_entry(path.to_str().unwrap())
-                        // Safety: This was initialized by schema inference.
-                        .unwrap()
-                        .try_open_assume_latest()?,
-                )
-            })
-        } else {
-            options
-                .with_path(Some(path))
-                .try_into_reader_with_file_path(None)?
-        };
+            .with_chunk_size(chunk_size)
+            .with_row_index(row_index))
+    }
 
-        if let Some(col) = &file_options.include_file_paths {
+    fn finish_init_reader(
+        &mut self,
+        reader: CsvReader<Box<dyn MmapBytesReader>>,
+        path: &std::path::Path,
+    ) -> PolarsResult<()> {
+        if let Some(col) = &self.file_options.include_file_paths {
             self.include_file_path =
                 Some(StringChunked::full(col.clone(), path.to_str().unwrap(), 1));
         };
+        self.hive_columns = self.hive_parts.as_ref().map(|hive_parts| {
+            hive_parts[self.current_path_idx - 1].materialize_partition_columns()
+        });
 
         self.reader = Some(reader);
         let reader = self.reader.as_mut().unwrap();
 
         // Safety: `reader` outlives `batched_reader`
-        let reader: &'static mut CsvReader<File> = unsafe { std::mem::transmute(reader) };
+        let reader: &'static mut CsvReader<Box<dyn MmapBytesReader>> =
+            unsafe { std::mem::transmute(reader) };
         let batched_reader = reader.batched_borrowed()?;
         self.batched_reader = Some(batched_reader);
         Ok(())
@@ -144,27 +299,45 @@ impl CsvSource {
         schema: SchemaRef,
         options: CsvReadOptions,
         file_options: FileScanOptions,
+        #[allow(unused_variables)] cloud_options: Option<polars_io::cloud::CloudOptions>,
+        hive_parts: Option<Arc<Vec<HivePartitions>>>,
         verbose: bool,
     ) -> PolarsResult<Self> {
+        let n_cols = match &file_options.with_columns {
+            Some(columns) if !columns.is_empty() => columns.len(),
+            _ => schema.len(),
+        };
+        // inversely scale the chunk size by the number of threads so that we reduce memory pressure
+        // in streaming
+        let n_threads = POOL.current_num_threads();
+        let chunk_size = AdaptiveChunkSize::new(n_cols, n_threads)?;
         Ok(CsvSource {
             schema,
             reader: None,
             batched_reader: None,
-            n_threads: POOL.current_num_threads(),
+            n_threads,
+            chunk_size,
             sources,
             options: Some(options),
             file_options,
+            #[cfg(feature = "cloud")]
+            cloud_options,
+            #[cfg(feature = "cloud")]
+            block_fetcher: None,
+            block_reader: None,
             verbose,
             current_path_idx: 0,
             n_rows_read: 0,
             first_schema: Default::default(),
             include_file_path: None,
+            hive_parts,
+            hive_columns: None,
         })
     }
 }
 
 impl Source for CsvSource {
-    fn get_batches(&mut self, _context: &PExecutionContext) -> PolarsResult<SourceResult> {
+    fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult> {
         loop {
             let first_read_from_file = self.reader.is_none();
 
@@ -210,6 +383,37 @@ impl Source for CsvSource {
                 })
                 .collect::<Vec<_>>();
 
+            if let Some(hive_columns) = &self.hive_columns {
+                // Only hive partition columns are projected, but the batched reader reads all
+                // columns of the file.
+                let only_hive_columns = self
+                    .file_options
+                    .with_columns
+                    .as_ref()
+                    .is_some_and(|columns| columns.is_empty());
+                for data_chunk in &mut out {
+                    let height = data_chunk.data.height();
+                    if only_hive_columns {
+                        let keep = data_chunk
+                            .data
+                            .get_column_names_owned()
+                            .into_iter()
+                            .filter(|name| {
+                                self.file_options
+                                    .row_index
+                                    .as_ref()
+                                    .is_some_and(|ri| ri.name == *name)
+                                    || self.file_options.include_file_paths.as_ref() == Some(name)
+                            })
+                            .collect::<Vec<_>>();
+                        data_chunk.data = data_chunk.data._select_impl_unchecked(&keep)?;
+                    }
+                    for s in hive_columns {
+                        data_chunk.data.with_column(s.new_from_index(0, height))?;
+                    }
+                }
+            }
+
             if let Some(ca) = &mut self.include_file_path {
                 if ca.len() < max_height {
                     *ca = ca.new_from_index(0, max_height);
@@ -230,6 +434,18 @@ impl Source for CsvSource {
             self.n_rows_read = self.n_rows_read.saturating_add(n_rows_read);
             get_source_index(out.len() as u32);
 
+            let chunk_size = self.chunk_size.get();
+            let new_chunk_size = self.chunk_size.update(context, &out);
+            if new_chunk_size != chunk_size {
+                if self.verbose {
+                    eprintln!("STREAMING CHUNK SIZE: {new_chunk_size} rows")
+                }
+                self.batched_reader
+                    .as_mut()
+                    .unwrap()
+                    .set_chunk_size(new_chunk_size);
+            }
+
             return Ok(SourceResult::GotMoreData(out));
         }
     }
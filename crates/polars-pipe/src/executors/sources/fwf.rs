@@ -0,0 +1,127 @@
+use std::io::Cursor;
+
+use polars_core::prelude::*;
+use polars_core::POOL;
+use polars_io::fwf::{BatchedFwfReader, FwfReadOptions, FwfReader};
+use polars_io::{RowIndex, SerReader};
+use polars_plan::plans::ScanSources;
+use polars_plan::prelude::FileScanOptions;
+
+use crate::executors::sources::get_source_index;
+use crate::operators::{DataChunk, PExecutionContext, Source, SourceResult};
+use crate::pipeline::determine_chunk_size;
+
+/// Reads fixed-width files in batches of lines, so that a file is never fully held in memory as
+/// a [`DataFrame`].
+pub(crate) struct FwfSource {
+    sources: ScanSources,
+    options: FwfReadOptions,
+    file_options: FileScanOptions,
+    batched_reader: Option<BatchedFwfReader>,
+    n_threads: usize,
+    chunk_size: usize,
+    // state for multi-file reads
+    current_source_idx: usize,
+    n_rows_read: usize,
+    include_file_path: Option<StringChunked>,
+    verbose: bool,
+}
+
+impl FwfSource {
+    pub(crate) fn new(
+        sources: ScanSources,
+        schema: SchemaRef,
+        options: FwfReadOptions,
+        file_options: FileScanOptions,
+        verbose: bool,
+    ) -> PolarsResult<Self> {
+        let n_cols = file_options
+            .with_columns
+            .as_ref()
+            .map_or(schema.len(), |columns| columns.len());
+        let n_threads = POOL.current_num_threads();
+        let chunk_size = determine_chunk_size(n_cols, n_threads)?;
+        if verbose {
+            eprintln!("STREAMING CHUNK SIZE: {chunk_size} rows")
+        }
+
+        Ok(Self {
+            sources,
+            options,
+            file_options,
+            batched_reader: None,
+            n_threads,
+            chunk_size,
+            current_source_idx: 0,
+            n_rows_read: 0,
+            include_file_path: None,
+            verbose,
+        })
+    }
+
+    /// Open the next file. Returns `false` if all files are read or the row limit is reached.
+    fn init_next_reader(&mut self) -> PolarsResult<bool> {
+        let n_rows = self.file_options.slice.map(|x| {
+            assert_eq!(x.0, 0);
+            x.1.saturating_sub(self.n_rows_read)
+        });
+        if n_rows == Some(0) || self.current_source_idx == self.sources.len() {
+            return Ok(false);
+        }
+
+        let source = self.sources.at(self.current_source_idx);
+        self.current_source_idx += 1;
+        if self.verbose {
+            eprintln!("reading fixed-width source {}", self.current_source_idx);
+        }
+
+        let row_index = self.file_options.row_index.as_ref().map(|ri| RowIndex {
+            name: ri.name.clone(),
+            offset: ri.offset + self.n_rows_read as IdxSize,
+        });
+        self.include_file_path = self
+            .file_options
+            .include_file_paths
+            .as_ref()
+            .map(|col| StringChunked::full(col.clone(), source.to_include_path_name(), 1));
+        let reader = FwfReader::new(Cursor::new(source.to_memslice()?))
+            .with_options(self.options.clone())
+            .with_columns(self.file_options.with_columns.clone())
+            .with_n_rows(n_rows)
+            .with_row_index(row_index);
+        self.batched_reader = Some(reader.batched(self.chunk_size)?);
+        Ok(true)
+    }
+}
+
+impl Source for FwfSource {
+    fn get_batches(&mut self, _context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        let mut chunks = Vec::with_capacity(self.n_threads);
+        while chunks.len() < self.n_threads {
+            if self.batched_reader.is_none() && !self.init_next_reader()? {
+                break;
+            }
+            match self.batched_reader.as_mut().unwrap().next_batch()? {
+                Some(mut df) => {
+                    self.n_rows_read += df.height();
+                    if let Some(ca) = &self.include_file_path {
+                        df.with_column(ca.new_from_index(0, df.height()))?;
+                    }
+                    df.as_single_chunk_par();
+                    chunks.push(DataChunk::new(get_source_index(1) as IdxSize, df));
+                },
+                None => self.batched_reader = None,
+            }
+        }
+
+        if chunks.is_empty() {
+            Ok(SourceResult::Finished)
+        } else {
+            Ok(SourceResult::GotMoreData(chunks))
+        }
+    }
+
+    fn fmt(&self) -> &str {
+        "fwf"
+    }
+}
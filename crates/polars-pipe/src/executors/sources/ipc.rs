@@ -0,0 +1,292 @@
+use std::io::Cursor;
+
+use arrow::datatypes::ArrowSchema;
+use arrow::io::ipc::read::{
+    read_batch, read_file_dictionaries, read_file_metadata, Dictionaries, FileMetadata,
+};
+use arrow::mmap::{mmap_dictionaries_unchecked, mmap_unchecked};
+use arrow::record_batch::RecordBatch;
+use polars_core::prelude::*;
+use polars_core::POOL;
+use polars_io::RowIndex;
+use polars_plan::plans::hive::HivePartitions;
+use polars_plan::plans::ScanSources;
+use polars_plan::prelude::FileScanOptions;
+use polars_utils::mmap::MemSlice;
+
+use crate::executors::sources::get_source_index;
+use crate::operators::{DataChunk, PExecutionContext, Source, SourceResult};
+use crate::pipeline::determine_chunk_size;
+
+/// Memory maps IPC files and yields their record batches without copying them. The record
+/// batches of files that can't be memory mapped, e.g. as they are compressed, are decoded one at
+/// a time.
+pub(crate) struct IpcSource {
+    sources: ScanSources,
+    file_options: FileScanOptions,
+    hive_parts: Option<Arc<Vec<HivePartitions>>>,
+    reader: Option<IpcFileReader>,
+    n_threads: usize,
+    chunk_size: usize,
+    // state for multi-file reads
+    current_source_idx: usize,
+    n_rows_read: usize,
+    verbose: bool,
+}
+
+/// Reads the record batches of a single file.
+struct IpcFileReader {
+    data: Arc<MemSlice>,
+    metadata: FileMetadata,
+    dictionaries: Dictionaries,
+    // The indices of the projected columns in the file, in the order of the projection.
+    projection: Vec<usize>,
+    schema: ArrowSchema,
+    // If only columns that aren't in the file are projected, the first column of the file is read
+    // to get the height of the record batches.
+    placeholder: bool,
+    next_block: usize,
+    // Set once a record batch can't be memory mapped.
+    decode: bool,
+    message_scratch: Vec<u8>,
+    data_scratch: Vec<u8>,
+    hive_columns: Option<Vec<Series>>,
+    include_file_path: Option<StringChunked>,
+}
+
+impl IpcFileReader {
+    fn new(data: MemSlice, with_columns: Option<&[PlSmallStr]>) -> PolarsResult<Self> {
+        let metadata = read_file_metadata(&mut Cursor::new(data.as_ref()))?;
+        let data = Arc::new(data);
+
+        // Columns that aren't in the file, such as hive partition columns, are added afterwards.
+        let mut projection = match with_columns {
+            Some(columns) => columns
+                .iter()
+                .filter_map(|name| metadata.schema.index_of(name))
+                .collect::<Vec<_>>(),
+            None => (0..metadata.schema.len()).collect(),
+        };
+        let placeholder = projection.is_empty() && !metadata.schema.is_empty();
+        if placeholder {
+            projection.push(0);
+        }
+        let schema = projection
+            .iter()
+            .map(|i| metadata.schema.get_at_index(*i).unwrap())
+            .map(|(name, field)| (name.clone(), field.clone()))
+            .collect();
+
+        let (dictionaries, decode) =
+            match unsafe { mmap_dictionaries_unchecked(&metadata, data.clone()) } {
+                Ok(dictionaries) => (dictionaries, false),
+                Err(_) => {
+                    let bytes: &[u8] = &data;
+                    let dictionaries =
+                        read_file_dictionaries(&mut Cursor::new(bytes), &metadata, &mut vec![])?;
+                    (dictionaries, true)
+                },
+            };
+
+        Ok(Self {
+            data,
+            metadata,
+            dictionaries,
+            projection,
+            schema,
+            placeholder,
+            next_block: 0,
+            decode,
+            message_scratch: vec![],
+            data_scratch: vec![],
+            hive_columns: None,
+            include_file_path: None,
+        })
+    }
+
+    fn next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        if self.next_block == self.metadata.blocks.len() {
+            return Ok(None);
+        }
+        let block = self.next_block;
+        self.next_block += 1;
+
+        if !self.decode {
+            match unsafe {
+                mmap_unchecked(&self.metadata, &self.dictionaries, self.data.clone(), block)
+            } {
+                Ok(batch) => {
+                    let arrays = batch.into_arrays();
+                    let batch = RecordBatch::new(
+                        self.projection.iter().map(|i| arrays[*i].clone()).collect(),
+                    );
+                    return DataFrame::try_from((batch, &self.schema)).map(Some);
+                },
+                // Compressed or unaligned record batches can't be memory mapped.
+                Err(_) => self.decode = true,
+            }
+        }
+
+        // The projection of the decoder must be in the order of the file.
+        let mut sorted_projection = self.projection.clone();
+        sorted_projection.sort_unstable();
+        let bytes: &[u8] = &self.data;
+        let arrays = read_batch(
+            &mut Cursor::new(bytes),
+            &self.dictionaries,
+            &self.metadata,
+            Some(&sorted_projection),
+            None,
+            block,
+            &mut self.message_scratch,
+            &mut self.data_scratch,
+        )?
+        .into_arrays();
+        let batch = RecordBatch::new(
+            self.projection
+                .iter()
+                .map(|i| arrays[sorted_projection.binary_search(i).unwrap()].clone())
+                .collect(),
+        );
+        DataFrame::try_from((batch, &self.schema)).map(Some)
+    }
+
+    /// Add the columns that aren't read from the file to a chunk of a record batch.
+    fn finish_chunk(
+        &self,
+        mut df: DataFrame,
+        row_index: Option<&RowIndex>,
+        n_rows_read: usize,
+    ) -> PolarsResult<DataFrame> {
+        let height = df.height();
+        if let Some(ri) = row_index {
+            df.with_row_index_mut(ri.name.clone(), Some(ri.offset + n_rows_read as IdxSize));
+        }
+        if let Some(hive_columns) = &self.hive_columns {
+            for s in hive_columns {
+                df.with_column(s.new_from_index(0, height))?;
+            }
+        }
+        if let Some(ca) = &self.include_file_path {
+            df.with_column(ca.new_from_index(0, height))?;
+        }
+        if self.placeholder {
+            df = df.drop(self.schema.get_at_index(0).unwrap().0.as_str())?;
+        }
+        Ok(df)
+    }
+}
+
+impl IpcSource {
+    pub(crate) fn new(
+        sources: ScanSources,
+        schema: SchemaRef,
+        file_options: FileScanOptions,
+        hive_parts: Option<Arc<Vec<HivePartitions>>>,
+        verbose: bool,
+    ) -> PolarsResult<Self> {
+        let n_cols = file_options
+            .with_columns
+            .as_ref()
+            .map_or(schema.len(), |columns| columns.len());
+        let n_threads = POOL.current_num_threads();
+        let chunk_size = determine_chunk_size(n_cols, n_threads)?;
+        if verbose {
+            eprintln!("STREAMING CHUNK SIZE: {chunk_size} rows")
+        }
+
+        Ok(Self {
+            sources,
+            file_options,
+            hive_parts,
+            reader: None,
+            n_threads,
+            chunk_size,
+            current_source_idx: 0,
+            n_rows_read: 0,
+            verbose,
+        })
+    }
+
+    fn remaining_rows(&self) -> Option<usize> {
+        self.file_options.slice.map(|x| {
+            assert_eq!(x.0, 0);
+            x.1.saturating_sub(self.n_rows_read)
+        })
+    }
+
+    /// Memory map the next file. Returns `false` if all files are read or the row limit is
+    /// reached.
+    fn init_next_reader(&mut self) -> PolarsResult<bool> {
+        if self.remaining_rows() == Some(0) || self.current_source_idx == self.sources.len() {
+            return Ok(false);
+        }
+
+        let source = self.sources.at(self.current_source_idx);
+        self.current_source_idx += 1;
+        if self.verbose {
+            eprintln!("memory mapping IPC source {}", self.current_source_idx);
+        }
+
+        let mut reader = IpcFileReader::new(
+            source.to_memslice()?,
+            self.file_options.with_columns.as_deref(),
+        )?;
+        reader.hive_columns = self.hive_parts.as_ref().map(|hive_parts| {
+            hive_parts[self.current_source_idx - 1].materialize_partition_columns()
+        });
+        reader.include_file_path = self
+            .file_options
+            .include_file_paths
+            .as_ref()
+            .map(|col| StringChunked::full(col.clone(), source.to_include_path_name(), 1));
+        self.reader = Some(reader);
+        Ok(true)
+    }
+}
+
+impl Source for IpcSource {
+    fn get_batches(&mut self, _context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        let mut chunks = Vec::with_capacity(self.n_threads);
+        while chunks.len() < self.n_threads && self.remaining_rows() != Some(0) {
+            if self.reader.is_none() && !self.init_next_reader()? {
+                break;
+            }
+            let Some(batch) = self.reader.as_mut().unwrap().next_batch()? else {
+                self.reader = None;
+                continue;
+            };
+
+            // Split large record batches, the slices are zero-copy.
+            let mut offset = 0;
+            while offset < batch.height() {
+                let len = self
+                    .remaining_rows()
+                    .unwrap_or(usize::MAX)
+                    .min(self.chunk_size)
+                    .min(batch.height() - offset);
+                if len == 0 {
+                    break;
+                }
+                let df = self.reader.as_ref().unwrap().finish_chunk(
+                    batch.slice(offset as i64, len),
+                    self.file_options.row_index.as_ref(),
+                    self.n_rows_read,
+                )?;
+                offset += len;
+                self.n_rows_read += len;
+                chunks.push(DataChunk::new(get_source_index(1) as IdxSize, df));
+            }
+        }
+
+        if chunks.is_empty() {
+            Ok(SourceResult::Finished)
+        } else {
+            Ok(SourceResult::GotMoreData(chunks))
+        }
+    }
+
+    fn fmt(&self) -> &str {
+        "ipc"
+    }
+}
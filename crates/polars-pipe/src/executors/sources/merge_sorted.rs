@@ -0,0 +1,260 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use arrow::array::BinaryArray;
+use polars_core::prelude::sort::arg_sort_multiple::_get_rows_encoded_arr;
+use polars_core::prelude::*;
+use polars_core::series::IsSorted;
+use polars_core::utils::{accumulate_dataframes_vertical_unchecked, split_df};
+use polars_core::POOL;
+use polars_plan::prelude::*;
+
+use crate::executors::sources::get_source_index;
+use crate::operators::{DataChunk, PExecutionContext, Source, SourceResult};
+
+/// The scans that are merged by the `merge_sorted` at `node`, if it only merges scans. Nested
+/// merges on the same key are flattened, so that all scans are merged by a single
+/// [`MergeSortedSource`].
+pub fn merge_sorted_scans(node: Node, lp_arena: &Arena<IR>) -> Option<Vec<Node>> {
+    let IR::MapFunction {
+        input,
+        function: FunctionIR::MergeSorted { column },
+    } = lp_arena.get(node)
+    else {
+        return None;
+    };
+    let IR::Union { inputs, options } = lp_arena.get(*input) else {
+        return None;
+    };
+    if options.slice.is_some() {
+        return None;
+    }
+
+    let mut scans = Vec::with_capacity(inputs.len());
+    for &input in inputs {
+        // Rechunks are ignored
+        let input = match lp_arena.get(input) {
+            IR::MapFunction {
+                input,
+                function: FunctionIR::Rechunk,
+            } => *input,
+            _ => input,
+        };
+        match lp_arena.get(input) {
+            IR::Scan { .. } => scans.push(input),
+            IR::MapFunction {
+                function: FunctionIR::MergeSorted { column: inner },
+                ..
+            } if inner == column => scans.extend(merge_sorted_scans(input, lp_arena)?),
+            _ => return None,
+        }
+    }
+    Some(scans)
+}
+
+/// An input of the merge, of which the rows that are pulled but not yet merged are buffered.
+struct Input {
+    source: Box<dyn Source>,
+    df: DataFrame,
+    // the row-encoded keys of `df`, these compare in sort order
+    keys: BinaryArray<i64>,
+    // the largest key that was pulled, to check that the input is sorted
+    last_key: Option<Vec<u8>>,
+    finished: bool,
+}
+
+impl Input {
+    fn new(source: Box<dyn Source>) -> Self {
+        Self {
+            source,
+            df: Default::default(),
+            keys: BinaryArray::new_empty(ArrowDataType::LargeBinary),
+            last_key: None,
+            finished: false,
+        }
+    }
+
+    /// Pull the next batches if all buffered rows are merged. Returns `false` if the input is
+    /// depleted.
+    fn refill(&mut self, context: &PExecutionContext, key: &str) -> PolarsResult<bool> {
+        while self.df.height() == 0 {
+            if self.finished {
+                return Ok(false);
+            }
+            let mut chunks = match self.source.get_batches(context)? {
+                SourceResult::Finished => {
+                    self.finished = true;
+                    continue;
+                },
+                SourceResult::GotMoreData(chunks) => chunks,
+            };
+            chunks.sort_unstable_by_key(|chunk| chunk.chunk_index);
+            let df = accumulate_dataframes_vertical_unchecked(
+                chunks.into_iter().map(|chunk| chunk.data),
+            );
+            if df.height() == 0 {
+                continue;
+            }
+            let keys = _get_rows_encoded_arr(&[df.column(key)?.clone()], &[false], &[false])?;
+
+            let first_key = keys.value(0);
+            let sorted = self
+                .last_key
+                .as_deref()
+                .map_or(true, |last| last <= first_key)
+                && (1..keys.len()).all(|i| keys.value(i - 1) <= keys.value(i));
+            polars_ensure!(
+                sorted,
+                InvalidOperation: "the inputs of `merge_sorted` must be sorted on '{}' in \
+                ascending order, with the nulls first", key
+            );
+            self.last_key = Some(keys.value(keys.len() - 1).to_vec());
+            self.df = df;
+            self.keys = keys;
+        }
+        Ok(true)
+    }
+
+    fn last_key(&self) -> &[u8] {
+        self.keys.value(self.keys.len() - 1)
+    }
+
+    /// Take the buffered rows up to and including `bound`, with their keys.
+    fn take_until(&mut self, bound: &[u8]) -> (DataFrame, BinaryArray<i64>) {
+        // the keys are sorted, so this is a binary search
+        let len = self.keys.len();
+        let (mut lo, mut hi) = (0, len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.keys.value(mid) <= bound {
+                lo = mid + 1
+            } else {
+                hi = mid
+            }
+        }
+        let df = self.df.slice(0, lo);
+        let keys = self.keys.clone().sliced(0, lo);
+        self.df = self.df.slice(lo as i64, len - lo);
+        self.keys.slice(lo, len - lo);
+        (df, keys)
+    }
+}
+
+/// Merge the sorted rows of the inputs, in the order of their keys. Rows with equal keys are
+/// taken in the order of the inputs.
+fn merge(parts: Vec<(DataFrame, BinaryArray<i64>)>) -> DataFrame {
+    let mut offsets = Vec::with_capacity(parts.len());
+    let mut height = 0;
+    for (df, _) in &parts {
+        offsets.push(height);
+        height += df.height();
+    }
+
+    let mut heap = BinaryHeap::with_capacity(parts.len());
+    for (i, (_, keys)) in parts.iter().enumerate() {
+        heap.push(Reverse((keys.value(0), i, 0)))
+    }
+    let mut idx = Vec::with_capacity(height);
+    while let Some(Reverse((_, i, row))) = heap.pop() {
+        idx.push((offsets[i] + row) as IdxSize);
+        let keys = &parts[i].1;
+        if row + 1 < keys.len() {
+            heap.push(Reverse((keys.value(row + 1), i, row + 1)))
+        }
+    }
+
+    let df = accumulate_dataframes_vertical_unchecked(parts.into_iter().map(|(df, _)| df));
+    // SAFETY: the indices are the rows of the parts, which are all in `df`.
+    unsafe { df._take_unchecked_slice(&idx, true) }
+}
+
+/// A k-way merge of the sources of files that are sorted on a key, e.g. exports that are
+/// partitioned by time.
+///
+/// Every batch consists of the pulled rows of all inputs up to the smallest last pulled key of
+/// all inputs. Those are all the rows that sort before that key, so they can be emitted once they
+/// are merged. The input with that smallest key then pulls its next batches, so the memory used
+/// is about the size of a batch of every input. The key column of the chunks is flagged as
+/// sorted, so that the operators and sinks that need a sorted input can stream them.
+pub struct MergeSortedSource {
+    inputs: Vec<Input>,
+    key: PlSmallStr,
+    n_threads: usize,
+    initialized: bool,
+}
+
+impl MergeSortedSource {
+    pub(crate) fn new(sources: Vec<Box<dyn Source>>, key: PlSmallStr) -> Self {
+        Self {
+            inputs: sources.into_iter().map(Input::new).collect(),
+            key,
+            n_threads: POOL.current_num_threads(),
+            initialized: false,
+        }
+    }
+
+    /// Pull the next batches of the inputs of which all buffered rows are merged, and drop the
+    /// depleted inputs.
+    fn refill(&mut self, context: &PExecutionContext) -> PolarsResult<()> {
+        let mut i = 0;
+        while i < self.inputs.len() {
+            if self.inputs[i].refill(context, &self.key)? {
+                i += 1
+            } else {
+                // keep the order of the inputs, as ties are broken by that order
+                self.inputs.remove(i);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Source for MergeSortedSource {
+    fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        if !self.initialized {
+            self.refill(context)?;
+            self.initialized = true;
+        }
+        if self.inputs.is_empty() {
+            return Ok(SourceResult::Finished);
+        }
+
+        let bound = self
+            .inputs
+            .iter()
+            .map(|input| input.last_key())
+            .min()
+            .unwrap()
+            .to_vec();
+        let parts = self
+            .inputs
+            .iter_mut()
+            .map(|input| input.take_until(&bound))
+            .filter(|(df, _)| df.height() > 0)
+            .collect::<Vec<_>>();
+        let mut df = if parts.len() == 1 {
+            parts.into_iter().next().unwrap().0
+        } else {
+            merge(parts)
+        };
+        let key_idx = df.try_get_column_index(&self.key)?;
+        // SAFETY: setting the sorted flag doesn't change the length or the name.
+        unsafe { df.get_columns_mut()[key_idx].set_sorted_flag(IsSorted::Ascending) };
+
+        // pull the next batches of the inputs that were merged up to their last key
+        self.refill(context)?;
+
+        let dfs = split_df(&mut df, self.n_threads, true);
+        let chunk_offset = get_source_index(dfs.len() as u32) as IdxSize;
+        let chunks = dfs
+            .into_iter()
+            .enumerate()
+            .map(|(i, df)| DataChunk::new(chunk_offset + i as IdxSize, df))
+            .collect();
+        Ok(SourceResult::GotMoreData(chunks))
+    }
+
+    fn fmt(&self) -> &str {
+        "merge_sorted"
+    }
+}
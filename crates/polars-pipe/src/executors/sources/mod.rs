@@ -1,21 +1,45 @@
+mod anonymous;
+#[cfg(feature = "asof_join")]
+pub(crate) mod asof_join;
+#[cfg(feature = "avro")]
+mod avro;
 #[cfg(feature = "csv")]
 mod csv;
 mod frame;
+#[cfg(feature = "fwf")]
+mod fwf;
+#[cfg(feature = "ipc")]
+mod ipc;
 mod ipc_one_shot;
+#[cfg(feature = "merge_sorted")]
+pub(crate) mod merge_sorted;
 #[cfg(feature = "parquet")]
 mod parquet;
-mod reproject;
+#[cfg(feature = "python")]
+mod python;
 mod union;
 
 use std::sync::atomic::{AtomicU32, Ordering};
 
+pub(crate) use anonymous::AnonymousSource;
+#[cfg(feature = "asof_join")]
+pub(crate) use asof_join::*;
+#[cfg(feature = "avro")]
+pub(crate) use avro::AvroSource;
 #[cfg(feature = "csv")]
 pub(crate) use csv::CsvSource;
 pub(crate) use frame::*;
+#[cfg(feature = "fwf")]
+pub(crate) use fwf::FwfSource;
+#[cfg(feature = "ipc")]
+pub(crate) use ipc::IpcSource;
 pub(crate) use ipc_one_shot::*;
+#[cfg(feature = "merge_sorted")]
+pub(crate) use merge_sorted::*;
 #[cfg(feature = "parquet")]
 pub(crate) use parquet::*;
-pub(crate) use reproject::*;
+#[cfg(feature = "python")]
+pub(crate) use python::PythonSource;
 pub(crate) use union::*;
 
 #[cfg(feature = "csv")]
@@ -23,6 +47,6 @@ use super::*;
 
 static CHUNK_INDEX: AtomicU32 = AtomicU32::new(0);
 
-pub(super) fn get_source_index(add: u32) -> u32 {
+pub(crate) fn get_source_index(add: u32) -> u32 {
     CHUNK_INDEX.fetch_add(add, Ordering::Relaxed)
 }
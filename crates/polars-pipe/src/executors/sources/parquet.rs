@@ -82,7 +82,7 @@ impl ParquetSource {
             .as_paths()
             .ok_or_else(|| polars_err!(nyi = "Streaming scanning of in-memory buffers"))?;
         let path = &paths[index];
-        let options = self.options;
+        let options = self.options.clone();
         let file_options = self.file_options.clone();
         let schema = self.file_info.schema.clone();
 
@@ -134,6 +134,8 @@ impl ParquetSource {
         let batched_reader = {
             let file = std::fs::File::open(path).unwrap();
             let mut reader = ParquetReader::new(file)
+                .with_decryption(options.decryption.clone())
+                .with_metadata_cache(Some(path.as_path()))
                 .with_projection(projection)
                 .check_schema(
                     self.file_info
@@ -203,6 +205,7 @@ impl ParquetSource {
             let mut async_reader =
                 ParquetAsyncReader::from_uri(&uri, cloud_options.as_ref(), metadata)
                     .await?
+                    .with_decryption(options.decryption.clone())
                     .with_row_index(file_options.row_index)
                     .with_projection(projection)
                     .check_schema(
@@ -266,7 +269,9 @@ impl ParquetSource {
 
         let iter = 0..paths.len();
 
-        let prefetch_size = get_file_prefetch_size();
+        let prefetch_size = file_options
+            .max_concurrent_files
+            .map_or(get_file_prefetch_size(), |n| n.get());
         if verbose {
             eprintln!("POLARS PREFETCH_SIZE: {}", prefetch_size)
         }
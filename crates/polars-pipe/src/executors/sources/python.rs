@@ -0,0 +1,111 @@
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+use polars_plan::plans::python::python_df_to_rust;
+use polars_plan::prelude::PythonOptions;
+use pyo3::exceptions::PyStopIteration;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use pyo3::{intern, PyTypeInfo};
+
+use crate::executors::sources::get_source_index;
+use crate::operators::{DataChunk, PExecutionContext, Source, SourceResult};
+
+/// Pulls the batches of the generator of an IO plugin.
+///
+/// The predicate is passed to the IO plugin, but is also applied by an operator after this
+/// source, as the plugin may not be able to apply it.
+pub struct PythonSource {
+    options: PythonOptions,
+    predicate_serialized: Option<Vec<u8>>,
+    batch_size: Option<usize>,
+    generator: Option<PyObject>,
+    finished: bool,
+}
+
+impl PythonSource {
+    pub(crate) fn new(options: PythonOptions, predicate_serialized: Option<Vec<u8>>) -> Self {
+        // If there are filters, take smaller chunks to ensure we can keep memory
+        // pressure low.
+        let batch_size = predicate_serialized.as_ref().map(|_| 100_000);
+        PythonSource {
+            options,
+            predicate_serialized,
+            batch_size,
+            generator: None,
+            finished: false,
+        }
+    }
+
+    fn init_generator(&mut self, py: Python) -> PolarsResult<PyObject> {
+        let pl = PyModule::import_bound(py, intern!(py, "polars")).map_err(to_compute_err)?;
+        let utils = pl.getattr(intern!(py, "_utils")).map_err(to_compute_err)?;
+        let callable = utils
+            .getattr(intern!(py, "_execute_from_rust"))
+            .map_err(to_compute_err)?;
+
+        let python_scan_function = self.options.scan_fn.take().unwrap().0;
+        let with_columns = self
+            .options
+            .with_columns
+            .take()
+            .map(|cols| cols.iter().map(|x| x.to_string()).collect::<Vec<_>>());
+        let predicate = match &self.predicate_serialized {
+            None => None::<()>.into_py(py),
+            Some(buf) => PyBytes::new_bound(py, buf).to_object(py),
+        };
+        let args = (
+            python_scan_function,
+            with_columns,
+            predicate,
+            self.options.n_rows.take(),
+            self.batch_size,
+        );
+        let generator_init = callable.call1(args).map_err(to_compute_err)?;
+        let generator = generator_init
+            .get_item(0)
+            .map_err(|_| polars_err!(ComputeError: "expected tuple got {}", generator_init))?;
+        Ok(generator.unbind())
+    }
+}
+
+impl Source for PythonSource {
+    fn get_batches(&mut self, _context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        if self.finished {
+            return Ok(SourceResult::Finished);
+        }
+        Python::with_gil(|py| {
+            let generator = match &self.generator {
+                Some(generator) => generator.clone_ref(py),
+                None => {
+                    let generator = self.init_generator(py)?;
+                    self.generator = Some(generator.clone_ref(py));
+                    generator
+                },
+            };
+
+            match generator.call_method0(py, intern!(py, "__next__")) {
+                Ok(out) => {
+                    let mut df = python_df_to_rust(py, out.into_bound(py))?;
+                    df.as_single_chunk_par();
+                    let chunk_index = get_source_index(1) as IdxSize;
+                    Ok(SourceResult::GotMoreData(vec![DataChunk::new(
+                        chunk_index,
+                        df,
+                    )]))
+                },
+                Err(err) if err.matches(py, PyStopIteration::type_object_bound(py)) => {
+                    self.finished = true;
+                    self.generator = None;
+                    Ok(SourceResult::Finished)
+                },
+                Err(err) => {
+                    polars_bail!(ComputeError: "caught exception during execution of a Python source, exception: {}", err)
+                },
+            }
+        })
+    }
+
+    fn fmt(&self) -> &str {
+        "python"
+    }
+}
@@ -9,7 +9,8 @@ pub struct DataChunk {
 }
 
 impl DataChunk {
-    pub(crate) fn new(chunk_index: IdxSize, data: DataFrame) -> Self {
+    /// Create a chunk of `data`, of which every column must consist of a single chunk of memory.
+    pub fn new(chunk_index: IdxSize, data: DataFrame) -> Self {
         // Check the invariant that all columns have a single chunk.
         #[cfg(debug_assertions)]
         {
@@ -1,16 +1,42 @@
+use std::sync::Arc;
+
 use polars_expr::state::ExecutionState;
 
+use crate::executors::sinks::memory::{MemTracker, MemoryAccountant};
+use crate::pipeline::chunk_size::ChunkLatency;
+use crate::pipeline::metrics::PipelineMetrics;
+use crate::pipeline::morsels_per_sink;
+
+/// The context in which the operators of a pipeline are executed.
 pub struct PExecutionContext {
     // injected upstream in polars-lazy
     pub(crate) execution_state: ExecutionState,
     pub(crate) verbose: bool,
+    // the memory of the whole query, used for backpressure on the sources
+    pub(crate) mem_track: MemTracker,
+    // the metrics of the nodes that have finished
+    pub(crate) metrics: PipelineMetrics,
+    // the latency of the chunks, used by the sources to adapt their chunk size
+    pub(crate) chunk_latency: ChunkLatency,
 }
 
 impl PExecutionContext {
-    pub(crate) fn new(state: ExecutionState, verbose: bool) -> Self {
+    pub fn new(state: ExecutionState, verbose: bool) -> Self {
+        Self::with_accountant(state, verbose, Default::default())
+    }
+
+    /// Create the context of a query of which the sinks account their memory to `accountant`.
+    pub(crate) fn with_accountant(
+        state: ExecutionState,
+        verbose: bool,
+        accountant: Arc<MemoryAccountant>,
+    ) -> Self {
         PExecutionContext {
             execution_state: state,
             verbose,
+            mem_track: MemTracker::new(morsels_per_sink(), accountant.clone()),
+            metrics: PipelineMetrics::new(accountant),
+            chunk_latency: Default::default(),
         }
     }
 }
@@ -4,9 +4,10 @@ mod operator;
 mod sink;
 mod source;
 
+pub use chunks::DataChunk;
 pub(crate) use chunks::*;
 pub use context::*;
 pub(crate) use operator::*;
 pub(crate) use polars_core::prelude::*;
 pub use sink::*;
-pub(crate) use source::*;
+pub use source::*;
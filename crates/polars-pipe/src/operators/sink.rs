@@ -5,15 +5,22 @@ use polars_utils::arena::Node;
 
 use super::*;
 
+/// The result of a call to [`Sink::sink`].
 #[derive(Debug)]
 pub enum SinkResult {
+    /// The sink doesn't need more input, the pipeline stops pulling data from its source.
     Finished,
     CanHaveMoreInput,
 }
 
+/// The result of [`Sink::finalize`].
 pub enum FinalizedSink {
+    /// The final result of the pipeline. Sinks that write the data elsewhere return an empty
+    /// `DataFrame`.
     Finished(DataFrame),
+    /// Only used by the sinks of the engine that are followed by an operator.
     Operator,
+    /// Only used by the sinks of the engine that are the source of the next pipeline.
     Source(Box<dyn Source>),
 }
 
@@ -28,19 +35,44 @@ impl Debug for FinalizedSink {
     }
 }
 
+/// A sink of the streaming engine, which consumes the chunks of a pipeline.
+///
+/// Every thread of the pipeline gets its own sink, created with [`Sink::split`], and calls
+/// [`Sink::sink`] for the chunks it processed, so a sink receives the chunks out of order. The
+/// [`DataChunk::chunk_index`] gives the position of a chunk in the output. When the pipeline
+/// is done, the sinks of the threads are merged into one with [`Sink::combine`], on which
+/// [`Sink::finalize`] is called.
+///
+/// A sink applies backpressure by blocking in [`Sink::sink`] until the destination accepts more
+/// data, which stalls the thread that produced the chunk, and can stop the pipeline early by
+/// returning [`SinkResult::Finished`].
 pub trait Sink: Send + Sync {
+    /// Consume a chunk of the pipeline.
     fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult>;
 
+    /// Merge the state of the sink of another thread, which was created by [`Sink::split`], into
+    /// this sink.
     fn combine(&mut self, other: &mut dyn Sink);
 
+    /// Create the sink of the thread `thread_no`.
     fn split(&self, thread_no: usize) -> Box<dyn Sink>;
 
+    /// Finish the sink after all chunks are consumed and combined into this sink.
     fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink>;
 
+    /// Used by [`Sink::combine`] to downcast `other` to the type of the sink.
     fn as_any(&mut self) -> &mut dyn Any;
 
+    /// The name of the sink in the description of the pipeline.
     fn fmt(&self) -> &str;
 
+    /// The number of rows the sink still needs before it returns [`SinkResult::Finished`], if it
+    /// has a row limit. The sources then don't read ahead while chunks that cover these rows are
+    /// pushed, so that no data is read that the sink doesn't use.
+    fn remaining_rows(&self) -> Option<usize> {
+        None
+    }
+
     fn is_join_build(&self) -> bool {
         false
     }
@@ -1,12 +1,31 @@
 use super::*;
 
+/// The result of a call to [`Source::get_batches`].
 pub enum SourceResult {
+    /// The source is exhausted, [`Source::get_batches`] isn't called again.
     Finished,
+    /// The next chunks of the source, which are processed in parallel by the pipeline.
     GotMoreData(Vec<DataChunk>),
 }
 
+/// A source of the streaming engine, which produces the data of a pipeline in chunks.
+///
+/// [`Source::get_batches`] is called until it returns [`SourceResult::Finished`]. Every call may
+/// return multiple chunks, which are processed in parallel. The chunk indices determine the order
+/// of the chunks in the output, so they must increase in the order of the data across calls, and
+/// must not be used by the other sources of the query, which [`next_chunk_index`] ensures. Every
+/// chunk must have the schema of the source and every column of a chunk must consist of a single
+/// chunk of memory, see [`DataFrame::as_single_chunk_par`].
 pub trait Source: Send + Sync {
+    /// Produce the next chunks of the source.
     fn get_batches(&mut self, context: &PExecutionContext) -> PolarsResult<SourceResult>;
 
+    /// The name of the source in the description of the pipeline.
     fn fmt(&self) -> &str;
 }
+
+/// Reserve `n` consecutive chunk indices for the chunks of a call to [`Source::get_batches`],
+/// and return the first of them.
+pub fn next_chunk_index(n: u32) -> IdxSize {
+    crate::executors::sources::get_source_index(n) as IdxSize
+}
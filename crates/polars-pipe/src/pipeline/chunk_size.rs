@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use polars_core::prelude::*;
+
+use crate::operators::{DataChunk, PExecutionContext};
+use crate::pipeline::determine_chunk_size;
+
+/// If a chunk takes longer than this in the operators and sink, the chunks shrink.
+const SLOW_CHUNK: Duration = Duration::from_millis(200);
+/// If a chunk takes less than this in the operators and sink, the chunks may grow.
+const FAST_CHUNK: Duration = Duration::from_millis(20);
+/// The chunks shrink if less than this fraction of the memory at the start of the query is free.
+const LOW_MEMORY: f64 = 0.25;
+/// The chunks may only grow if more than this fraction of the memory is free.
+const IDLE_MEMORY: f64 = 0.5;
+const MIN_CHUNK_SIZE: usize = 1000;
+const MAX_CHUNK_BYTES: usize = 1 << 24;
+
+/// The time the chunks of the last batch took in the operators and sink of a pipeline. It is
+/// written by the dispatcher and read by the sources, to adapt their chunk size.
+#[derive(Default)]
+pub(crate) struct ChunkLatency {
+    // the slowest chunk of the running batch, in nanoseconds
+    running: AtomicU64,
+    // the slowest chunk of the last finished batch, in nanoseconds
+    last: AtomicU64,
+}
+
+impl ChunkLatency {
+    /// Record that a chunk of the running batch was pushed through the pipeline since `start`.
+    pub(crate) fn record_chunk(&self, start: Instant) {
+        self.running
+            .fetch_max(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn finish_batch(&self) {
+        let running = self.running.swap(0, Ordering::Relaxed);
+        self.last.store(running, Ordering::Relaxed)
+    }
+
+    /// The latency of the slowest chunk of the last finished batch, if any.
+    fn last(&self) -> Option<Duration> {
+        let last = self.last.load(Ordering::Relaxed);
+        (last > 0).then(|| Duration::from_nanos(last))
+    }
+}
+
+/// The chunk size of a source that adapts while the pipeline runs.
+///
+/// It starts at [`determine_chunk_size`]. The chunks shrink while they are slow in the
+/// operators and sink, or while memory runs low, and they grow while they are fast and memory
+/// is to spare, up to 16MB per chunk given the width of the rows that were read. Setting
+/// `POLARS_STREAMING_CHUNK_SIZE` fixes the chunk size.
+pub(crate) struct AdaptiveChunkSize {
+    chunk_size: usize,
+    n_threads: usize,
+    // estimated bytes per row of the last chunks
+    row_width: usize,
+    fixed: bool,
+}
+
+impl AdaptiveChunkSize {
+    pub(crate) fn new(n_cols: usize, n_threads: usize) -> PolarsResult<Self> {
+        let chunk_size = determine_chunk_size(n_cols, n_threads)?;
+        Ok(Self {
+            chunk_size,
+            n_threads: n_threads.max(1),
+            row_width: 0,
+            fixed: std::env::var("POLARS_STREAMING_CHUNK_SIZE").is_ok(),
+        })
+    }
+
+    pub(crate) fn get(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Adapt the chunk size to the `chunks` that the source produced last and the latency and
+    /// memory of the pipeline. Returns the new chunk size.
+    pub(crate) fn update(&mut self, context: &PExecutionContext, chunks: &[DataChunk]) -> usize {
+        if self.fixed {
+            return self.chunk_size;
+        }
+        let (rows, bytes) = chunks.iter().fold((0, 0), |(rows, bytes), chunk| {
+            (
+                rows + chunk.data.height(),
+                bytes + chunk.data.estimated_size(),
+            )
+        });
+        if rows > 0 {
+            self.row_width = std::cmp::max(bytes / rows, 1);
+        }
+
+        let free = context.mem_track.free_memory_fraction_since_start();
+        let latency = context.chunk_latency.last();
+        let chunk_size = if free < LOW_MEMORY || latency.is_some_and(|l| l > SLOW_CHUNK) {
+            self.chunk_size / 2
+        } else if free > IDLE_MEMORY && latency.is_some_and(|l| l < FAST_CHUNK) {
+            self.chunk_size * 2
+        } else {
+            self.chunk_size
+        };
+        let mut max_chunk_bytes = MAX_CHUNK_BYTES;
+        // With a memory limit the chunks in flight may take 1/16th of it.
+        if let Some(limit) = context.mem_track.accountant().limit() {
+            max_chunk_bytes = std::cmp::min(max_chunk_bytes, limit / (16 * self.n_threads));
+        }
+        let max_chunk_size = max_chunk_bytes / std::cmp::max(self.row_width, 1);
+        self.chunk_size = chunk_size.clamp(MIN_CHUNK_SIZE, max_chunk_size.max(MIN_CHUNK_SIZE));
+        self.chunk_size
+    }
+}
@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::Mutex;
 
 use hashbrown::hash_map::Entry;
 use polars_core::prelude::*;
@@ -10,9 +11,12 @@ use polars_ops::prelude::JoinType;
 use polars_plan::prelude::expr_ir::{ExprIR, OutputName};
 use polars_plan::prelude::*;
 
+use crate::executors::operators::explode::explode_projection;
 use crate::executors::operators::{HstackOperator, PlaceHolder};
 use crate::executors::sinks::group_by::aggregates::convert_to_hash_agg;
 use crate::executors::sinks::group_by::GenericGroupby2;
+use crate::executors::sinks::memory::MemoryAccountant;
+use crate::executors::sinks::window::{window_aggregations, WindowSink};
 use crate::executors::sinks::*;
 use crate::executors::{operators, sources};
 use crate::expressions::PhysicalPipedExpr;
@@ -102,12 +106,17 @@ where
             }
             match scan_type {
                 #[cfg(feature = "csv")]
-                FileScan::Csv { options, .. } => {
+                FileScan::Csv {
+                    options,
+                    cloud_options,
+                } => {
                     let src = sources::CsvSource::new(
                         sources,
                         file_info.schema,
                         options,
                         file_options,
+                        cloud_options,
+                        hive_parts,
                         verbose,
                     )?;
                     Ok(Box::new(src) as Box<dyn Source>)
@@ -158,19 +167,322 @@ where
                     )?;
                     Ok(Box::new(src) as Box<dyn Source>)
                 },
+                #[cfg(feature = "ipc")]
+                FileScan::Ipc { .. } => {
+                    let src = sources::IpcSource::new(
+                        sources,
+                        file_info.schema,
+                        file_options,
+                        hive_parts,
+                        verbose,
+                    )?;
+                    Ok(Box::new(src) as Box<dyn Source>)
+                },
+                #[cfg(feature = "avro")]
+                FileScan::Avro { .. } => {
+                    let src =
+                        sources::AvroSource::new(sources, file_info.schema, file_options, verbose)?;
+                    Ok(Box::new(src) as Box<dyn Source>)
+                },
+                #[cfg(feature = "fwf")]
+                FileScan::Fwf { options } => {
+                    let src = sources::FwfSource::new(
+                        sources,
+                        file_info.schema,
+                        options,
+                        file_options,
+                        verbose,
+                    )?;
+                    Ok(Box::new(src) as Box<dyn Source>)
+                },
+                FileScan::Anonymous { function, .. } => {
+                    // The predicate is also applied by the filter operator, as the scan may not
+                    // apply it.
+                    let predicate = predicate
+                        .filter(|_| function.allows_predicate_pushdown())
+                        .map(|predicate| predicate.to_expr(expr_arena));
+                    let args = AnonymousScanArgs {
+                        n_rows: None,
+                        with_columns: file_options.with_columns,
+                        schema: file_info.schema,
+                        output_schema,
+                        predicate,
+                    };
+                    let slice = file_options
+                        .slice
+                        .map(|(offset, len)| (offset as usize, len));
+                    let src = sources::AnonymousSource::new(function, args, slice);
+                    Ok(Box::new(src) as Box<dyn Source>)
+                },
+                #[allow(unreachable_patterns)]
                 _ => todo!(),
             }
         },
+        #[cfg(feature = "python")]
+        PythonScan { options } => {
+            let mut predicate_serialized = None;
+            if let PythonPredicate::Polars(predicate) = &options.predicate {
+                let schema = options.output_schema.as_ref().unwrap_or(&options.schema);
+                let dsl_expr = predicate.to_expr(expr_arena);
+                predicate_serialized = polars_plan::plans::python::predicate::serialize(&dsl_expr)?;
+
+                let predicate = to_physical(predicate, expr_arena, Some(schema))?;
+                let op = operators::FilterOperator { predicate };
+                operator_objects.push(Box::new(op) as Box<dyn Operator>)
+            }
+            let src = sources::PythonSource::new(options, predicate_serialized);
+            Ok(Box::new(src) as Box<dyn Source>)
+        },
         _ => unreachable!(),
     }
 }
 
+/// The sink of a projection of which the windows are computed by a streaming group_by.
+#[allow(clippy::too_many_arguments)]
+fn get_window_sink<F>(
+    exprs: &[ExprIR],
+    input: Node,
+    output_schema: SchemaRef,
+    options: ProjectionOptions,
+    hstack: bool,
+    lp_arena: &Arena<IR>,
+    expr_arena: &mut Arena<AExpr>,
+    to_physical: &F,
+    accountant: &Arc<MemoryAccountant>,
+) -> PolarsResult<Box<dyn SinkTrait>>
+where
+    F: Fn(&ExprIR, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
+{
+    let input_schema = lp_arena.get(input).schema(lp_arena).into_owned();
+    // this is checked when the streaming plan is built
+    let windows = window_aggregations(exprs, expr_arena, &input_schema).unwrap();
+    let group_schema = windows.schema().clone();
+    let n_keys = group_schema.len() - windows.aggs.len();
+
+    let keys = group_schema
+        .iter_names()
+        .take(n_keys)
+        .map(|name| {
+            let node = expr_arena.add(AExpr::Column(name.clone()));
+            ExprIR::new(node, OutputName::ColumnLhs(name.clone()))
+        })
+        .collect::<Vec<_>>();
+    let key_columns = Arc::new(exprs_to_physical(
+        &keys,
+        expr_arena,
+        to_physical,
+        Some(&input_schema),
+    )?);
+
+    let mut aggregation_columns = Vec::with_capacity(windows.aggs.len());
+    let mut agg_fns = Vec::with_capacity(windows.aggs.len());
+    let mut input_agg_dtypes = Vec::with_capacity(windows.aggs.len());
+    for node in &windows.aggs {
+        let (input_dtype, index, agg_fn) =
+            convert_to_hash_agg(*node, expr_arena, &input_schema, &to_physical);
+        aggregation_columns.push(index);
+        agg_fns.push(agg_fn);
+        input_agg_dtypes.push(input_dtype);
+    }
+    let group_by = Box::new(GenericGroupby2::new(
+        key_columns,
+        Arc::new(aggregation_columns),
+        Arc::from(agg_fns),
+        group_schema.clone(),
+        input_agg_dtypes,
+        None,
+        accountant.clone(),
+    ));
+
+    // the projection is evaluated on the input with the aggregates as extra columns
+    let mut schema = input_schema.as_ref().clone();
+    for (name, dtype) in group_schema.iter().skip(n_keys) {
+        schema.with_column(name.clone(), dtype.clone());
+    }
+    let schema = Arc::new(schema);
+    let projection = if hstack {
+        Box::new(get_hstack(
+            &windows.exprs,
+            expr_arena,
+            to_physical,
+            schema,
+            options,
+        )?) as Box<dyn Operator>
+    } else {
+        Box::new(operators::ProjectionOperator {
+            exprs: exprs_to_physical(&windows.exprs, expr_arena, &to_physical, Some(&schema))?,
+            options,
+        }) as Box<dyn Operator>
+    };
+
+    Ok(Box::new(WindowSink::new(
+        group_by,
+        projection,
+        input_schema,
+        group_schema,
+        n_keys,
+        output_schema,
+        accountant.clone(),
+    )))
+}
+
+/// Create the sink that writes the output of a query to the `payload`.
+fn get_payload_sink(
+    payload: &SinkType,
+    input_schema: SchemaRef,
+) -> PolarsResult<Box<dyn SinkTrait>> {
+    let out = match payload {
+        SinkType::Memory => Box::new(OrderedSink::new(input_schema)) as Box<dyn SinkTrait>,
+        #[allow(unused_variables)]
+        SinkType::File {
+            path, file_type, ..
+        } => {
+            let path = path.as_ref().as_path();
+            match &file_type {
+                #[cfg(feature = "parquet")]
+                FileType::Parquet(options) if options.row_group_size.is_some() => Box::new(
+                    ParquetRowGroupSink::new(path, options.clone(), input_schema.as_ref())?,
+                )
+                    as Box<dyn SinkTrait>,
+                #[cfg(feature = "parquet")]
+                FileType::Parquet(options) => Box::new(ParquetSink::new(
+                    path,
+                    options.clone(),
+                    input_schema.as_ref(),
+                )?) as Box<dyn SinkTrait>,
+                #[cfg(feature = "ipc")]
+                FileType::Ipc(options) => {
+                    Box::new(IpcSink::new(path, *options, input_schema.as_ref())?)
+                        as Box<dyn SinkTrait>
+                },
+                #[cfg(feature = "csv")]
+                FileType::Csv(options) => {
+                    Box::new(CsvSink::new(path, options.clone(), input_schema.as_ref())?)
+                        as Box<dyn SinkTrait>
+                },
+                #[cfg(feature = "json")]
+                FileType::Json(options) => {
+                    Box::new(JsonSink::new(path, options.clone(), input_schema.as_ref())?)
+                        as Box<dyn SinkTrait>
+                },
+                #[allow(unreachable_patterns)]
+                _ => unreachable!(),
+            }
+        },
+        #[allow(unused_variables)]
+        SinkType::Partitioned {
+            path,
+            file_type,
+            partition_by,
+            options: partition_options,
+        } => {
+            let path = path.as_ref().as_path();
+            match &file_type {
+                #[cfg(feature = "parquet")]
+                FileType::Parquet(options) => Box::new(PartitionedParquetSink::new(
+                    path,
+                    partition_by,
+                    options.clone(),
+                    *partition_options,
+                    input_schema.as_ref(),
+                )?) as Box<dyn SinkTrait>,
+                #[cfg(feature = "csv")]
+                FileType::Csv(options) => Box::new(PartitionedCsvSink::new(
+                    path,
+                    partition_by,
+                    options.clone(),
+                    *partition_options,
+                    input_schema.as_ref(),
+                )?) as Box<dyn SinkTrait>,
+                #[allow(unreachable_patterns)]
+                other_file_type => polars_bail!(
+                    InvalidOperation: "partitioned sinking of the file type {other_file_type:?} is not (yet) supported"
+                ),
+            }
+        },
+        #[cfg(feature = "cloud")]
+        SinkType::Cloud {
+            #[cfg(any(feature = "parquet", feature = "ipc"))]
+            uri,
+            file_type,
+            #[cfg(any(feature = "parquet", feature = "ipc"))]
+            cloud_options,
+            ..
+        } => match &file_type {
+            #[cfg(feature = "parquet")]
+            FileType::Parquet(parquet_options) => Box::new(ParquetCloudSink::new(
+                uri.as_ref().as_str(),
+                cloud_options.as_ref(),
+                parquet_options.clone(),
+                input_schema.as_ref(),
+            )?) as Box<dyn SinkTrait>,
+            #[cfg(feature = "ipc")]
+            FileType::Ipc(ipc_options) => Box::new(IpcCloudSink::new(
+                uri.as_ref().as_str(),
+                cloud_options.as_ref(),
+                *ipc_options,
+                input_schema.as_ref(),
+            )?) as Box<dyn SinkTrait>,
+            #[allow(unreachable_patterns)]
+            other_file_type => {
+                todo!("Cloud-sinking of the file type {other_file_type:?} is not (yet) supported.")
+            },
+        },
+        #[cfg(feature = "flight")]
+        SinkType::Flight {
+            endpoint,
+            descriptor,
+        } => Box::new(FlightSink::new(
+            endpoint.as_str(),
+            descriptor,
+            input_schema.as_ref(),
+        )?) as Box<dyn SinkTrait>,
+        #[allow(unused_variables)]
+        SinkType::Writer { writer, file_type } => match &file_type {
+            #[cfg(feature = "ipc")]
+            FileType::Ipc(options) => Box::new(IpcSink::new_with_writer(
+                writer.take()?,
+                *options,
+                input_schema.as_ref(),
+            )?) as Box<dyn SinkTrait>,
+            #[cfg(feature = "ipc_streaming")]
+            FileType::IpcStream(options) => Box::new(IpcStreamSink::new_with_writer(
+                writer.take()?,
+                *options,
+                input_schema.as_ref(),
+            )?) as Box<dyn SinkTrait>,
+            #[cfg(feature = "csv")]
+            FileType::Csv(options) => Box::new(CsvSink::new_with_writer(
+                writer.take()?,
+                options.clone(),
+                input_schema.as_ref(),
+            )?) as Box<dyn SinkTrait>,
+            #[allow(unreachable_patterns)]
+            other_file_type => polars_bail!(
+                InvalidOperation: "sinking the file type {other_file_type:?} to a writer is not (yet) supported"
+            ),
+        },
+        SinkType::Custom { sink } => *sink.take()?.downcast::<Box<dyn SinkTrait>>().map_err(
+            |_| polars_err!(ComputeError: "custom sink does not implement the `Sink` trait"),
+        )?,
+        SinkType::Tee { sinks } => {
+            let sinks = sinks
+                .iter()
+                .map(|payload| get_payload_sink(payload, input_schema.clone()))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            Box::new(TeeSink::new(sinks)) as Box<dyn SinkTrait>
+        },
+    };
+    Ok(out)
+}
+
 pub fn get_sink<F>(
     node: Node,
     lp_arena: &Arena<IR>,
     expr_arena: &mut Arena<AExpr>,
     to_physical: &F,
     callbacks: &mut CallBacks,
+    accountant: &Arc<MemoryAccountant>,
 ) -> PolarsResult<Box<dyn SinkTrait>>
 where
     F: Fn(&ExprIR, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
@@ -179,71 +491,7 @@ where
     let out = match lp_arena.get(node) {
         Sink { input, payload } => {
             let input_schema = lp_arena.get(*input).schema(lp_arena);
-            match payload {
-                SinkType::Memory => {
-                    Box::new(OrderedSink::new(input_schema.into_owned())) as Box<dyn SinkTrait>
-                },
-                #[allow(unused_variables)]
-                SinkType::File {
-                    path, file_type, ..
-                } => {
-                    let path = path.as_ref().as_path();
-                    match &file_type {
-                        #[cfg(feature = "parquet")]
-                        FileType::Parquet(options) => {
-                            Box::new(ParquetSink::new(path, *options, input_schema.as_ref())?)
-                                as Box<dyn SinkTrait>
-                        },
-                        #[cfg(feature = "ipc")]
-                        FileType::Ipc(options) => {
-                            Box::new(IpcSink::new(path, *options, input_schema.as_ref())?)
-                                as Box<dyn SinkTrait>
-                        },
-                        #[cfg(feature = "csv")]
-                        FileType::Csv(options) => {
-                            Box::new(CsvSink::new(path, options.clone(), input_schema.as_ref())?)
-                                as Box<dyn SinkTrait>
-                        },
-                        #[cfg(feature = "json")]
-                        FileType::Json(options) => {
-                            Box::new(JsonSink::new(path, *options, input_schema.as_ref())?)
-                                as Box<dyn SinkTrait>
-                        },
-                        #[allow(unreachable_patterns)]
-                        _ => unreachable!(),
-                    }
-                },
-                #[cfg(feature = "cloud")]
-                SinkType::Cloud {
-                    #[cfg(any(feature = "parquet", feature = "ipc"))]
-                    uri,
-                    file_type,
-                    #[cfg(any(feature = "parquet", feature = "ipc"))]
-                    cloud_options,
-                    ..
-                } => {
-                    match &file_type {
-                        #[cfg(feature = "parquet")]
-                        FileType::Parquet(parquet_options) => Box::new(ParquetCloudSink::new(
-                            uri.as_ref().as_str(),
-                            cloud_options.as_ref(),
-                            *parquet_options,
-                            lp_arena.get(*input).schema(lp_arena).as_ref(),
-                        )?)
-                            as Box<dyn SinkTrait>,
-                        #[cfg(feature = "ipc")]
-                        FileType::Ipc(ipc_options) => Box::new(IpcCloudSink::new(
-                            uri.as_ref().as_str(),
-                            cloud_options.as_ref(),
-                            *ipc_options,
-                            lp_arena.get(*input).schema(lp_arena).as_ref(),
-                        )?)
-                            as Box<dyn SinkTrait>,
-                        #[allow(unreachable_patterns)]
-                        other_file_type => todo!("Cloud-sinking of the file type {other_file_type:?} is not (yet) supported."),
-                    }
-                },
-            }
+            get_payload_sink(payload, input_schema.into_owned())?
         },
         Join {
             input_left,
@@ -256,16 +504,23 @@ where
             // slice pushdown optimization should not set this one in a streaming query.
             assert!(options.args.slice.is_none());
             let swapped = swap_join_order(options);
+            let broadcast = broadcast_join_side(options).is_some();
             let placeholder = callbacks.get(&node).unwrap().clone();
 
             match &options.args.how {
                 #[cfg(feature = "cross_join")]
-                JoinType::Cross => Box::new(CrossJoin::new(
-                    options.args.suffix().clone(),
-                    swapped,
-                    node,
-                    placeholder,
-                )) as Box<dyn SinkTrait>,
+                JoinType::Cross => {
+                    let build_input = if swapped { *input_right } else { *input_left };
+                    let build_schema = lp_arena.get(build_input).schema(lp_arena).into_owned();
+                    Box::new(CrossJoin::new(
+                        options.args.suffix().clone(),
+                        swapped,
+                        node,
+                        placeholder,
+                        build_schema,
+                        accountant.clone(),
+                    )?) as Box<dyn SinkTrait>
+                },
                 jt => {
                     let input_schema_left = lp_arena.get(*input_left).schema(lp_arena);
                     let join_columns_left = Arc::new(exprs_to_physical(
@@ -301,6 +556,8 @@ where
                                 join_columns_left,
                                 join_columns_right,
                                 options.args.join_nulls,
+                                broadcast,
+                                options.partitioned,
                                 node,
                                 // We don't need the key names for these joins.
                                 vec![].into(),
@@ -308,15 +565,26 @@ where
                                 placeholder,
                             )) as Box<dyn SinkTrait>
                         },
+                        // Only the keys of the right table are kept.
+                        #[cfg(feature = "semi_anti_join")]
+                        JoinType::Semi | JoinType::Anti => Box::new(SemiAntiJoinBuild::new(
+                            matches!(jt, JoinType::Anti),
+                            join_columns_left,
+                            join_columns_right,
+                            options.args.join_nulls,
+                            node,
+                            placeholder,
+                        ))
+                            as Box<dyn SinkTrait>,
                         JoinType::Full { .. } => {
                             // First get the names before we (potentially) swap.
                             let key_names_left = join_columns_left
                                 .iter()
                                 .map(|e| e.field(&input_schema_left).unwrap().name)
                                 .collect();
-                            let key_names_right = join_columns_left
+                            let key_names_right = join_columns_right
                                 .iter()
-                                .map(|e| e.field(&input_schema_left).unwrap().name)
+                                .map(|e| e.field(&input_schema_right).unwrap().name)
                                 .collect();
                             // Swap.
                             let (join_columns_left, join_columns_right) = swap_eval();
@@ -328,6 +596,8 @@ where
                                 join_columns_left,
                                 join_columns_right,
                                 options.args.join_nulls,
+                                broadcast,
+                                options.partitioned,
                                 node,
                                 key_names_left,
                                 key_names_right,
@@ -359,13 +629,46 @@ where
         } => {
             let input_schema = lp_arena.get(*input).schema(lp_arena).into_owned();
 
-            if by_column.len() == 1 {
+            // A small slice of the sorted output is selected with a heap of the top k rows.
+            let top_k_slice = match *slice {
+                Some((offset, len))
+                    if offset >= 0 && (offset as usize).saturating_add(len) <= TOP_K_MAX_ROWS =>
+                {
+                    Some((offset as usize, len))
+                },
+                _ => None,
+            };
+
+            if let Some(top_k_slice) = top_k_slice {
+                let sort_idx = by_column
+                    .iter()
+                    .map(|e| {
+                        let name = aexpr_to_leaf_names_iter(e.node(), expr_arena)
+                            .next()
+                            .unwrap();
+                        input_schema.try_index_of(name.as_ref())
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+
+                Box::new(TopKSink::new(
+                    top_k_slice,
+                    sort_options,
+                    input_schema,
+                    sort_idx,
+                )) as Box<dyn SinkTrait>
+            } else if by_column.len() == 1 {
                 let by_column = aexpr_to_leaf_names_iter(by_column[0].node(), expr_arena)
                     .next()
                     .unwrap();
                 let index = input_schema.try_index_of(by_column.as_ref())?;
 
-                let sort_sink = SortSink::new(index, *slice, sort_options.clone(), input_schema);
+                let sort_sink = SortSink::new(
+                    index,
+                    *slice,
+                    sort_options.clone(),
+                    input_schema,
+                    accountant.clone(),
+                );
                 Box::new(sort_sink) as Box<dyn SinkTrait>
             } else {
                 let sort_idx = by_column
@@ -378,102 +681,25 @@ where
                     })
                     .collect::<PolarsResult<Vec<_>>>()?;
 
-                let sort_sink =
-                    SortSinkMultiple::new(*slice, sort_options.clone(), input_schema, sort_idx)?;
+                let sort_sink = SortSinkMultiple::new(
+                    *slice,
+                    sort_options.clone(),
+                    input_schema,
+                    sort_idx,
+                    accountant.clone(),
+                )?;
                 Box::new(sort_sink) as Box<dyn SinkTrait>
             }
         },
         Distinct { input, options } => {
-            // We create a Groupby.agg_first()/agg_last (depending on the keep strategy
             let input_schema = lp_arena.get(*input).schema(lp_arena).into_owned();
-
-            let (keys, aggs, output_schema) = match &options.subset {
-                None => {
-                    let keys = input_schema
-                        .iter_names()
-                        .map(|name| {
-                            let name: PlSmallStr = name.clone();
-                            let node = expr_arena.add(AExpr::Column(name.clone()));
-                            ExprIR::new(node, OutputName::Alias(name))
-                        })
-                        .collect::<Vec<_>>();
-                    let aggs = vec![];
-                    (keys, aggs, input_schema.clone())
-                },
-                Some(keys) => {
-                    let mut group_by_out_schema = Schema::with_capacity(input_schema.len());
-                    let key_names = PlHashSet::from_iter(keys.iter().map(|s| s.as_ref()));
-                    let keys = keys
-                        .iter()
-                        .map(|key| {
-                            let (_, name, dtype) = input_schema.get_full(key.as_ref()).unwrap();
-                            group_by_out_schema.with_column(name.clone(), dtype.clone());
-                            let node = expr_arena.add(AExpr::Column(key.clone()));
-                            ExprIR::new(node, OutputName::Alias(key.clone()))
-                        })
-                        .collect::<Vec<_>>();
-
-                    let aggs = input_schema
-                        .iter_names()
-                        .flat_map(|name| {
-                            if key_names.contains(name.as_str()) {
-                                None
-                            } else {
-                                let (_, name, dtype) =
-                                    input_schema.get_full(name.as_str()).unwrap();
-                                group_by_out_schema.with_column(name.clone(), dtype.clone());
-
-                                let name: PlSmallStr = name.clone();
-                                let col = expr_arena.add(AExpr::Column(name.clone()));
-                                let node = match options.keep_strategy {
-                                    UniqueKeepStrategy::First | UniqueKeepStrategy::Any => {
-                                        expr_arena.add(AExpr::Agg(IRAggExpr::First(col)))
-                                    },
-                                    UniqueKeepStrategy::Last => {
-                                        expr_arena.add(AExpr::Agg(IRAggExpr::Last(col)))
-                                    },
-                                    UniqueKeepStrategy::None => {
-                                        unreachable!()
-                                    },
-                                };
-                                Some(ExprIR::new(node, OutputName::Alias(name)))
-                            }
-                        })
-                        .collect();
-                    (keys, aggs, group_by_out_schema.into())
-                },
-            };
-
-            let key_columns = Arc::new(exprs_to_physical(
-                &keys,
-                expr_arena,
-                to_physical,
-                Some(&input_schema),
-            )?);
-
-            let mut aggregation_columns = Vec::with_capacity(aggs.len());
-            let mut agg_fns = Vec::with_capacity(aggs.len());
-            let mut input_agg_dtypes = Vec::with_capacity(aggs.len());
-
-            for e in &aggs {
-                let (input_dtype, index, agg_fn) =
-                    convert_to_hash_agg(e.node(), expr_arena, &input_schema, &to_physical);
-                aggregation_columns.push(index);
-                agg_fns.push(agg_fn);
-                input_agg_dtypes.push(input_dtype);
-            }
-            let aggregation_columns = Arc::new(aggregation_columns);
-
-            let group_by_sink = Box::new(GenericGroupby2::new(
-                key_columns,
-                aggregation_columns,
-                Arc::from(agg_fns),
-                output_schema,
-                input_agg_dtypes,
+            Box::new(UniqueSink::new(
+                options.subset.clone(),
+                options.keep_strategy,
+                input_schema,
                 options.slice,
-            ));
-
-            Box::new(ReProjectSink::new(input_schema, group_by_sink))
+                accountant.clone(),
+            )) as Box<dyn SinkTrait>
         },
         GroupBy {
             input,
@@ -504,6 +730,28 @@ where
             }
             let aggregation_columns = Arc::new(aggregation_columns);
 
+            #[cfg(feature = "dynamic_group_by")]
+            if let Some(windows) = group_by::TemporalWindows::new(options) {
+                return Ok(Box::new(group_by::DynamicGroupbySink::new(
+                    windows,
+                    aggregation_columns,
+                    Arc::from(agg_fns),
+                    output_schema.clone(),
+                    options.slice,
+                )));
+            }
+
+            // The groups are found where the keys change, instead of in a hash table.
+            if group_by::contiguous_group_keys(keys, *input, lp_arena, expr_arena) {
+                return Ok(Box::new(group_by::SortedGroupbySink::new(
+                    key_columns,
+                    aggregation_columns,
+                    Arc::from(agg_fns),
+                    output_schema.clone(),
+                    options.slice,
+                )));
+            }
+
             if std::env::var("POLARS_STREAMING_GB2").as_deref() == Ok("1") {
                 Box::new(GenericGroupby2::new(
                     key_columns,
@@ -512,6 +760,7 @@ where
                     output_schema.clone(),
                     input_agg_dtypes,
                     options.slice,
+                    accountant.clone(),
                 ))
             } else {
                 match (
@@ -527,6 +776,7 @@ where
                                 input_schema,
                                 output_schema.clone(),
                                 options.slice,
+                                accountant.clone(),
                             )) as Box<dyn SinkTrait>
                         })
                     },
@@ -537,6 +787,7 @@ where
                         input_schema,
                         output_schema.clone(),
                         options.slice,
+                        accountant.clone(),
                     )) as Box<dyn SinkTrait>,
                     _ => Box::new(GenericGroupby2::new(
                         key_columns,
@@ -545,10 +796,43 @@ where
                         output_schema.clone(),
                         input_agg_dtypes,
                         options.slice,
+                        accountant.clone(),
                     )),
                 }
             }
         },
+        HStack {
+            input,
+            exprs,
+            schema: output_schema,
+            options,
+        } => get_window_sink(
+            exprs,
+            *input,
+            output_schema.clone(),
+            *options,
+            true,
+            lp_arena,
+            expr_arena,
+            to_physical,
+            accountant,
+        )?,
+        Select {
+            input,
+            expr,
+            schema: output_schema,
+            options,
+        } => get_window_sink(
+            expr,
+            *input,
+            output_schema.clone(),
+            *options,
+            false,
+            lp_arena,
+            expr_arena,
+            to_physical,
+            accountant,
+        )?,
         lp => {
             panic!("{lp:?} not implemented")
         },
@@ -556,6 +840,43 @@ where
     Ok(out)
 }
 
+/// Create the sink that pushes the output of a query through the `branches` of the queries that
+/// are computed from it, so that the output is computed only once.
+///
+/// A branch is given by the nodes of its operators, in the order in which they run, and the
+/// node of its sink, of which the result is stored in `outputs` when the sink is finalized.
+pub fn get_fan_out_sink<F>(
+    branches: &[(Vec<Node>, Node)],
+    lp_arena: &Arena<IR>,
+    expr_arena: &mut Arena<AExpr>,
+    to_physical: &F,
+    outputs: Arc<Mutex<Vec<DataFrame>>>,
+    accountant: &Arc<MemoryAccountant>,
+) -> PolarsResult<Box<dyn SinkTrait>>
+where
+    F: Fn(&ExprIR, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
+{
+    let branches = branches
+        .iter()
+        .map(|(operators, sink)| {
+            let operators = operators
+                .iter()
+                .map(|node| get_operator(*node, lp_arena, expr_arena, to_physical))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            let sink = get_sink(
+                *sink,
+                lp_arena,
+                expr_arena,
+                to_physical,
+                &mut CallBacks::new(),
+                accountant,
+            )?;
+            Ok(FanOutBranch { operators, sink })
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    Ok(Box::new(FanOutSink::new(branches, outputs)))
+}
+
 pub fn get_dummy_operator() -> PlaceHolder {
     operators::PlaceHolder::new()
 }
@@ -601,11 +922,26 @@ where
             ..
         } => {
             let input_schema = lp_arena.get(*input).schema(lp_arena);
-            let op = operators::ProjectionOperator {
-                exprs: exprs_to_physical(expr, expr_arena, &to_physical, Some(&input_schema))?,
-                options: *options,
-            };
-            Box::new(op) as Box<dyn Operator>
+            if let Some(exploded) = explode_projection(expr, expr_arena) {
+                let projection = operators::ProjectionOperator {
+                    exprs: exprs_to_physical(
+                        &exploded,
+                        expr_arena,
+                        &to_physical,
+                        Some(&input_schema),
+                    )?,
+                    options: *options,
+                };
+                let columns = expr.iter().map(|e| e.output_name().clone()).collect();
+                let op = operators::ExplodeOperator::new(columns, Some(projection));
+                Box::new(op) as Box<dyn Operator>
+            } else {
+                let op = operators::ProjectionOperator {
+                    exprs: exprs_to_physical(expr, expr_arena, &to_physical, Some(&input_schema))?,
+                    options: *options,
+                };
+                Box::new(op) as Box<dyn Operator>
+            }
         },
         HStack {
             exprs,
@@ -631,6 +967,13 @@ where
             let op = operators::FilterOperator { predicate };
             Box::new(op) as Box<dyn Operator>
         },
+        MapFunction {
+            function: FunctionIR::Explode { columns, .. },
+            ..
+        } => {
+            let op = operators::ExplodeOperator::new(columns.clone(), None);
+            Box::new(op) as Box<dyn Operator>
+        },
         MapFunction { function, .. } => {
             let op = operators::FunctionOperator::new(function.clone());
             Box::new(op) as Box<dyn Operator>
@@ -647,10 +990,118 @@ where
     Ok(op)
 }
 
+/// Fuse the `filter` with the `projection` of its output into a single operator, so that only
+/// the columns that are projected are filtered. Returns `None` if they can't be fused.
+pub fn get_filter_projection<F>(
+    filter: Node,
+    projection: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+    to_physical: &F,
+) -> PolarsResult<Option<Box<dyn Operator>>>
+where
+    F: Fn(&ExprIR, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
+{
+    use IR::*;
+    let Filter { input, predicate } = lp_arena.get(filter) else {
+        return Ok(None);
+    };
+    let input_schema = lp_arena.get(*input).schema(lp_arena).into_owned();
+
+    let (columns, projection) = match lp_arena.get(projection) {
+        SimpleProjection { input, columns, .. } if *input == filter => {
+            (columns.iter_names_cloned().collect::<Arc<[_]>>(), None)
+        },
+        Select {
+            input,
+            expr,
+            options,
+            ..
+        } if *input == filter && explode_projection(expr, expr_arena).is_none() => {
+            let leaves = expr
+                .iter()
+                .flat_map(|e| aexpr_to_leaf_names_iter(e.node(), expr_arena))
+                .collect::<PlHashSet<_>>();
+            // keep the order of the input, so that the projection can use the schema
+            let columns = input_schema
+                .iter_names()
+                .filter(|name| leaves.contains(*name))
+                .cloned()
+                .collect::<Arc<[_]>>();
+            // without columns the projection needs all rows to know the height
+            if columns.is_empty() {
+                return Ok(None);
+            }
+            let schema = Arc::new(input_schema.try_project(columns.iter())?);
+            let projection = operators::ProjectionOperator {
+                exprs: exprs_to_physical(expr, expr_arena, to_physical, Some(&schema))?,
+                options: *options,
+            };
+            (columns, Some(projection))
+        },
+        _ => return Ok(None),
+    };
+
+    let predicate = to_physical(predicate, expr_arena, Some(&input_schema))?;
+    let op = operators::FilterProjectionOperator::new(
+        operators::FilterOperator { predicate },
+        columns,
+        input_schema,
+        projection,
+    );
+    Ok(Some(Box::new(op)))
+}
+
+/// The source that merges the sorted scans of the `merge_sorted` at `node`.
+#[cfg(feature = "merge_sorted")]
+fn merge_sorted_source<F>(
+    node: Node,
+    lp_arena: &Arena<IR>,
+    operator_objects: &mut Vec<Box<dyn Operator>>,
+    expr_arena: &Arena<AExpr>,
+    to_physical: &F,
+    verbose: bool,
+) -> PolarsResult<Box<dyn Source>>
+where
+    F: Fn(&ExprIR, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
+{
+    let IR::MapFunction {
+        function: FunctionIR::MergeSorted { column },
+        ..
+    } = lp_arena.get(node)
+    else {
+        unreachable!()
+    };
+    let inputs = super::merge_sorted_scans(node, lp_arena).unwrap();
+    let sources = inputs
+        .iter()
+        .enumerate()
+        .map(|(i, node)| {
+            let lp = lp_arena.get(*node);
+            // the predicates are equal, so only push the predicate of the first
+            // source, which filters the merged rows
+            get_source(
+                lp.clone(),
+                operator_objects,
+                expr_arena,
+                to_physical,
+                i == 0,
+                verbose && i == 0,
+            )
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    Ok(Box::new(sources::MergeSortedSource::new(
+        sources,
+        column.clone(),
+    )))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn create_pipeline<F>(
     sources: &[Node],
     operators: Vec<Box<dyn Operator>>,
+    // the nodes of the plan that the operators execute
+    operator_nodes: Vec<Node>,
     sink_nodes: Vec<(usize, Node, Rc<RefCell<u32>>)>,
     lp_arena: &Arena<IR>,
     expr_arena: &mut Arena<AExpr>,
@@ -660,6 +1111,8 @@ pub fn create_pipeline<F>(
     // If the shared sink is already in cache, that one is used.
     sink_cache: &mut PlHashMap<usize, Box<dyn SinkTrait>>,
     callbacks: &mut CallBacks,
+    // The accountant of the memory of the query, shared by the pipelines of the query.
+    accountant: &Arc<MemoryAccountant>,
 ) -> PolarsResult<PipeLine>
 where
     F: Fn(&ExprIR, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
@@ -687,6 +1140,15 @@ where
                 true,
                 verbose,
             )?,
+            #[cfg(feature = "python")]
+            lp @ PythonScan { .. } => get_source(
+                lp.clone(),
+                &mut operator_objects,
+                expr_arena,
+                &to_physical,
+                true,
+                verbose,
+            )?,
             Union { inputs, .. } => {
                 let sources = inputs
                     .iter()
@@ -706,6 +1168,73 @@ where
                     .collect::<PolarsResult<Vec<_>>>()?;
                 Box::new(sources::UnionSource::new(sources)) as Box<dyn Source>
             },
+            #[cfg(feature = "merge_sorted")]
+            MapFunction {
+                function: FunctionIR::MergeSorted { .. },
+                ..
+            } => merge_sorted_source(
+                *node,
+                lp_arena,
+                &mut operator_objects,
+                expr_arena,
+                &to_physical,
+                verbose,
+            )?,
+            #[cfg(feature = "asof_join")]
+            Join {
+                left_on,
+                right_on,
+                options,
+                ..
+            } => {
+                let inputs = super::asof_join_inputs(*node, lp_arena).unwrap();
+                let [left_schema, right_schema] =
+                    inputs.map(|input| lp_arena.get(input).schema(lp_arena).into_owned());
+                // the filters of the scans are applied by the source, before the rows are merged
+                let get_input = |input: Node| {
+                    let mut operators = vec![];
+                    let source = match lp_arena.get(input) {
+                        #[cfg(feature = "merge_sorted")]
+                        MapFunction {
+                            function: FunctionIR::MergeSorted { .. },
+                            ..
+                        } => merge_sorted_source(
+                            input,
+                            lp_arena,
+                            &mut operators,
+                            expr_arena,
+                            &to_physical,
+                            verbose,
+                        )?,
+                        lp => get_source(
+                            lp.clone(),
+                            &mut operators,
+                            expr_arena,
+                            &to_physical,
+                            true,
+                            verbose,
+                        )?,
+                    };
+                    PolarsResult::Ok((source, operators))
+                };
+                let sources = [get_input(inputs[0])?, get_input(inputs[1])?];
+                let keys = [
+                    to_physical(&left_on[0], expr_arena, Some(&left_schema))?,
+                    to_physical(&right_on[0], expr_arena, Some(&right_schema))?,
+                ];
+
+                let mut args = options.args.clone();
+                if let JoinType::AsOf(asof_options) = &mut args.how {
+                    let dtype = keys[0].field(&left_schema)?.dtype;
+                    resolve_asof_tolerance(asof_options, &dtype)?;
+                }
+                Box::new(sources::AsofJoinSource::new(
+                    sources,
+                    keys,
+                    &right_schema,
+                    args,
+                )) as Box<dyn Source>
+            },
             lp => {
                 panic!("source {lp:?} not (yet) supported")
             },
@@ -716,6 +1245,9 @@ where
     // this offset is because the source might have inserted operators
     let operator_offset = operator_objects.len();
     operator_objects.extend(operators);
+    let source_nodes = sources.iter().map(|node| Some(*node)).collect();
+    let mut plan_operator_nodes = vec![None; operator_offset];
+    plan_operator_nodes.extend(operator_nodes.into_iter().map(Some));
 
     let sinks = sink_nodes
         .into_iter()
@@ -723,22 +1255,33 @@ where
             // ensure that shared sinks are really shared
             // to achieve this we store/fetch them in a cache
             let sink = if *shared_count.borrow() == 1 {
-                get_sink(node, lp_arena, expr_arena, &to_physical, callbacks)?
+                get_sink(
+                    node,
+                    lp_arena,
+                    expr_arena,
+                    &to_physical,
+                    callbacks,
+                    accountant,
+                )?
             } else {
                 match sink_cache.entry(node.0) {
                     Entry::Vacant(entry) => {
-                        let sink = get_sink(node, lp_arena, expr_arena, &to_physical, callbacks)?;
+                        let sink = get_sink(
+                            node,
+                            lp_arena,
+                            expr_arena,
+                            &to_physical,
+                            callbacks,
+                            accountant,
+                        )?;
                         entry.insert(sink.split(0));
                         sink
                     },
                     Entry::Occupied(entry) => entry.get().split(0),
                 }
             };
-            Ok(ThreadedSink::new(
-                sink,
-                shared_count,
-                offset + operator_offset,
-            ))
+            let sink = ThreadedSink::new(sink, shared_count, offset + operator_offset);
+            Ok(sink.with_plan_node(node))
         })
         .collect::<PolarsResult<Vec<_>>>()?;
 
@@ -749,13 +1292,57 @@ where
         },
         sinks,
         verbose,
-    ))
+        accountant.clone(),
+    )
+    .with_plan_nodes(source_nodes, plan_operator_nodes))
+}
+
+/// The input of a hash join that is broadcast.
+///
+/// Its hash table is built in a single partition and shared with all threads that stream the
+/// other input. This is the input that the join is hinted to broadcast, or else the smallest input
+/// that is known to be small.
+///
+/// Inputs of which the scans know that they have at most `BROADCAST_JOIN_ROWS` rows are
+/// broadcast, if no input is hinted to be. Other inputs are still broadcast if they turn out to
+/// be that small once their hash table is built. Joins that are hinted to be partitioned never
+/// broadcast an input.
+pub fn broadcast_join_side(options: &JoinOptions) -> Option<JoinSide> {
+    if options.partitioned {
+        return None;
+    }
+    let side = options.broadcast.or_else(|| {
+        let small =
+            |(known, _): (Option<usize>, usize)| known.filter(|rows| *rows <= BROADCAST_JOIN_ROWS);
+        match (small(options.rows_left), small(options.rows_right)) {
+            (Some(left), Some(right)) if left < right => Some(JoinSide::Left),
+            (Some(_), None) => Some(JoinSide::Left),
+            (_, Some(_)) => Some(JoinSide::Right),
+            (None, None) => None,
+        }
+    })?;
+    match (&options.args.how, side) {
+        (JoinType::Inner | JoinType::Full, _) => Some(side),
+        // Left joins stream the left table to maintain its order.
+        (JoinType::Left, JoinSide::Right) => Some(side),
+        _ => None,
+    }
 }
 
 pub fn swap_join_order(options: &JoinOptions) -> bool {
-    matches!(options.args.how, JoinType::Left)
-        || match (options.rows_left, options.rows_right) {
-            ((Some(left), _), (Some(right), _)) => left > right,
-            ((_, left), (_, right)) => left > right,
+    // Left, semi and anti joins stream the left table to maintain its order.
+    let streams_left = match options.args.how {
+        JoinType::Left => true,
+        #[cfg(feature = "semi_anti_join")]
+        JoinType::Semi | JoinType::Anti => true,
+        _ => false,
+    };
+    streams_left
+        || match broadcast_join_side(options) {
+            Some(side) => side == JoinSide::Right,
+            None => match (options.rows_left, options.rows_right) {
+                ((Some(left), _), (Some(right), _)) => left > right,
+                ((_, left), (_, right)) => left > right,
+            },
         }
 }
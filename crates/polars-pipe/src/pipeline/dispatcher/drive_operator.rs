@@ -1,6 +1,85 @@
+use std::time::Instant;
+
 use super::*;
+use crate::pipeline::metrics::NodeMetrics;
 use crate::pipeline::*;
 
+/// Pull the next batches of a source and record them in the metrics of the source.
+pub(super) fn get_batches(
+    src: &mut dyn Source,
+    ec: &PExecutionContext,
+    metrics: &NodeMetrics,
+) -> PolarsResult<SourceResult> {
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!(
+        "get_batches",
+        source = src.fmt(),
+        chunks = tracing::field::Empty
+    )
+    .entered();
+    let start = Instant::now();
+    let out = src.get_batches(ec)?;
+    metrics.record_time(start);
+    if let SourceResult::GotMoreData(chunks) = &out {
+        #[cfg(feature = "tracing")]
+        span.record("chunks", chunks.len());
+        for chunk in chunks {
+            metrics.record_output(&chunk.data)
+        }
+    }
+    Ok(out)
+}
+
+/// Push a chunk in the sink and record it in the metrics of the sink and the memory of the query.
+fn sink_chunk(
+    ec: &PExecutionContext,
+    sink: &mut Box<dyn Sink>,
+    chunk: DataChunk,
+    metrics: &NodeMetrics,
+) -> PolarsResult<SinkResult> {
+    metrics.record_input(&chunk.data);
+    ec.mem_track
+        .accountant()
+        .buffer(chunk.data.estimated_size());
+    let start = Instant::now();
+    let out = sink.sink(ec, chunk);
+    metrics.record_time(start);
+    out
+}
+
+/// Execute an operator and record the time and the output in the metrics of the operator.
+/// The output is recorded as input of the next operator, if any.
+fn execute_operator(
+    ec: &PExecutionContext,
+    op: &mut dyn Operator,
+    chunk: &DataChunk,
+    op_i: usize,
+    metrics: &[NodeMetrics],
+) -> PolarsResult<OperatorResult> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!(
+        "execute",
+        operator = op.fmt(),
+        chunk = chunk.chunk_index,
+        rows = chunk.data.height()
+    )
+    .entered();
+    let start = Instant::now();
+    let out = op.execute(ec, chunk)?;
+    metrics[op_i].record_time(start);
+    record_operator_output(&out, op_i, metrics);
+    Ok(out)
+}
+
+fn record_operator_output(out: &OperatorResult, op_i: usize, metrics: &[NodeMetrics]) {
+    if let OperatorResult::Finished(chunk) | OperatorResult::HaveMoreOutPut(chunk) = out {
+        metrics[op_i].record_output(&chunk.data);
+        if let Some(next) = metrics.get(op_i + 1) {
+            next.record_input(&chunk.data)
+        }
+    }
+}
+
 /// Take data chunks from the sources and pushes them into the operators + sink. Every operator
 /// works thread local.
 /// The caller passes an `operator_start`/`operator_end` to indicate which part of the pipeline
@@ -15,6 +94,10 @@ pub(super) fn par_process_chunks(
     operator_end: usize,
     src: &mut Box<dyn Source>,
     must_flush: &AtomicBool,
+    max_threads: Option<NonZeroUsize>,
+    src_metrics: &NodeMetrics,
+    op_metrics: &[NodeMetrics],
+    sink_metrics: &NodeMetrics,
 ) -> PolarsResult<(Option<SinkResult>, SourceResult)> {
     debug_assert!(chunks.len() <= sink.len());
     let sink_results = Arc::new(Mutex::new(None));
@@ -31,50 +114,88 @@ pub(super) fn par_process_chunks(
     // Within a rayon scope
     // we spawn the jobs. They don't have to finish in any specific order,
     // this makes it more lightweight than `par_iter`
+    // If the number of threads is limited, every job pushes some of the chunks, one after the
+    // other.
+
+    // A sink that stops the pipeline after enough rows doesn't need the next batches if the
+    // rows of these chunks are enough.
+    let batch_rows = chunks
+        .iter()
+        .map(|chunk| chunk.data.height())
+        .sum::<usize>();
+    let read_ahead = sink
+        .iter()
+        .filter_map(|sink| sink.remaining_rows())
+        .min()
+        .map_or(true, |remaining| remaining > batch_rows);
+    let n_jobs = max_threads.map_or(chunks.len(), |n| n.get().min(chunks.len()));
+    let mut jobs = (0..n_jobs).map(|_| vec![]).collect::<Vec<_>>();
+    for (i, job) in chunks
+        .into_iter()
+        .zip(sink.iter_mut())
+        .zip(operators.iter_mut())
+        .enumerate()
+    {
+        jobs[i % n_jobs].push(job);
+    }
 
     // borrow as ref and move into the closure
     POOL.scope(|s| {
-        for ((chunk, sink), operator_pipe) in chunks
-            .into_iter()
-            .zip(sink.iter_mut())
-            .zip(operators.iter_mut())
-        {
+        for job in jobs {
             let sink_results = sink_results.clone();
-            // Truncate the operators that should run into the current sink.
-            let operator_pipe = &mut operator_pipe[operator_start..operator_end];
 
             s.spawn(move |_| {
-                let out = if operator_pipe.is_empty() {
-                    sink.sink(ec, chunk)
-                } else {
-                    push_operators_single_thread(chunk, ec, operator_pipe, sink, must_flush)
-                };
-
-                match out {
-                    Ok(SinkResult::Finished) | Err(_) => {
-                        let mut lock = sink_results.lock().unwrap();
-                        *lock = Some(out)
-                    },
-                    _ => {},
+                for ((chunk, sink), operator_pipe) in job {
+                    // Truncate the operators that should run into the current sink.
+                    let operator_pipe = &mut operator_pipe[operator_start..operator_end];
+                    let start = Instant::now();
+                    let out = if operator_pipe.is_empty() {
+                        sink_chunk(ec, sink, chunk, sink_metrics)
+                    } else {
+                        push_operators_single_thread(
+                            chunk,
+                            ec,
+                            operator_pipe,
+                            sink,
+                            must_flush,
+                            op_metrics,
+                            sink_metrics,
+                        )
+                    };
+                    ec.chunk_latency.record_chunk(start);
+
+                    match out {
+                        Ok(SinkResult::Finished) | Err(_) => {
+                            let mut lock = sink_results.lock().unwrap();
+                            *lock = Some(out)
+                        },
+                        _ => {},
+                    }
                 }
             })
         }
         // already get batches on the thread pool
         // if one job is finished earlier we can already start that work
-        s.spawn(|_| {
-            let out = src.get_batches(ec);
-            unsafe {
-                let ptr = next_batches_ptr.get();
-                *ptr = Some(out);
-            }
-        })
+        if read_ahead {
+            s.spawn(|_| {
+                let out = get_batches(&mut **src, ec, src_metrics);
+                unsafe {
+                    let ptr = next_batches_ptr.get();
+                    *ptr = Some(out);
+                }
+            })
+        }
     });
 
-    let next_batches = next_batches.unwrap()?;
-    let mut lock = sink_results.lock().unwrap();
-    lock.take()
-        .transpose()
-        .map(|sink_result| (sink_result, next_batches))
+    ec.chunk_latency.finish_batch();
+    let sink_result = sink_results.lock().unwrap().take().transpose()?;
+    let next_batches = match next_batches {
+        Some(next_batches) => next_batches?,
+        // The sink has all the rows it needs, so the rest of the source isn't read.
+        None if matches!(sink_result, Some(SinkResult::Finished)) => SourceResult::Finished,
+        None => get_batches(&mut **src, ec, src_metrics)?,
+    };
+    Ok((sink_result, next_batches))
 }
 
 /// This thread local logic that pushed a data chunk into the operators + sink
@@ -87,8 +208,11 @@ pub(super) fn push_operators_single_thread(
     operators: ThreadedOperatorMut,
     sink: &mut Box<dyn Sink>,
     must_flush: &AtomicBool,
+    op_metrics: &[NodeMetrics],
+    sink_metrics: &NodeMetrics,
 ) -> PolarsResult<SinkResult> {
     debug_assert!(!operators.is_empty());
+    op_metrics[0].record_input(&chunk.data);
 
     // Stack based operator execution.
     let mut in_process = vec![];
@@ -98,13 +222,13 @@ pub(super) fn push_operators_single_thread(
     while let Some((op_i, chunk)) = in_process.pop() {
         match operators.get_mut(op_i) {
             None => {
-                if let SinkResult::Finished = sink.sink(ec, chunk)? {
+                if let SinkResult::Finished = sink_chunk(ec, sink, chunk, sink_metrics)? {
                     return Ok(SinkResult::Finished);
                 }
             },
             Some(op) => {
                 let op = op.get_mut();
-                match op.execute(ec, &chunk)? {
+                match execute_operator(ec, op, &chunk, op_i, op_metrics)? {
                     OperatorResult::Finished(chunk) => {
                         must_flush.store(op.must_flush(), Ordering::Relaxed);
                         in_process.push((op_i + 1, chunk))
@@ -139,6 +263,8 @@ pub(super) fn par_flush(
     operators: &mut [ThreadedOperator],
     operator_start: usize,
     operator_end: usize,
+    op_metrics: &[NodeMetrics],
+    sink_metrics: &NodeMetrics,
 ) {
     // 1. We will iterate the chunks/sinks/operators
     // where every iteration belongs to a single thread
@@ -157,7 +283,7 @@ pub(super) fn par_flush(
             let operator_pipe = &mut operator_pipe[operator_start..operator_end];
 
             s.spawn(move |_| {
-                flush_operators(ec, operator_pipe, sink).unwrap();
+                flush_operators(ec, operator_pipe, sink, op_metrics, sink_metrics).unwrap();
             })
         }
     });
@@ -167,6 +293,8 @@ pub(super) fn flush_operators(
     ec: &PExecutionContext,
     operators: &mut [PhysOperator],
     sink: &mut Box<dyn Sink>,
+    op_metrics: &[NodeMetrics],
+    sink_metrics: &NodeMetrics,
 ) -> PolarsResult<SinkResult> {
     let needs_flush = operators
         .iter_mut()
@@ -199,7 +327,11 @@ pub(super) fn flush_operators(
                     // The branch for flushing.
                     None => {
                         let op = operators.get_mut(op_i).unwrap().get_mut();
-                        match op.flush()? {
+                        let start = Instant::now();
+                        let out = op.flush()?;
+                        op_metrics[op_i].record_time(start);
+                        record_operator_output(&out, op_i, op_metrics);
+                        match out {
                             OperatorResult::Finished(chunk) => {
                                 // Push the chunk in the next operator.
                                 in_process.push((op_i + 1, Some(chunk)))
@@ -219,13 +351,15 @@ pub(super) fn flush_operators(
                     Some(chunk) => {
                         match operators.get_mut(op_i) {
                             None => {
-                                if let SinkResult::Finished = sink.sink(ec, chunk)? {
+                                if let SinkResult::Finished =
+                                    sink_chunk(ec, sink, chunk, sink_metrics)?
+                                {
                                     return Ok(SinkResult::Finished);
                                 }
                             },
                             Some(op) => {
                                 let op = op.get_mut();
-                                match op.execute(ec, &chunk)? {
+                                match execute_operator(ec, op, &chunk, op_i, op_metrics)? {
                                     OperatorResult::Finished(chunk) => {
                                         in_process.push((op_i + 1, Some(chunk)))
                                     },
@@ -1,22 +1,27 @@
 use std::cell::RefCell;
 use std::fmt::{Debug, Formatter};
+use std::num::NonZeroUsize;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use polars_core::error::PolarsResult;
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
 use polars_core::POOL;
 use polars_expr::state::ExecutionState;
+use polars_utils::arena::Node;
 use polars_utils::sync::SyncPtr;
 use rayon::prelude::*;
 
+use crate::executors::sinks::memory::MemoryAccountant;
 use crate::executors::sources::DataFrameSource;
 use crate::operators::{
     DataChunk, FinalizedSink, OperatorResult, PExecutionContext, Sink, SinkResult, Source,
     SourceResult,
 };
-use crate::pipeline::dispatcher::drive_operator::{par_flush, par_process_chunks};
+use crate::pipeline::dispatcher::drive_operator::{get_batches, par_flush, par_process_chunks};
+use crate::pipeline::metrics::NodeMetrics;
 mod drive_operator;
 use super::*;
 
@@ -31,6 +36,8 @@ pub(super) struct ThreadedSink {
     ///   the pipeline will first call the operators on that point and then
     ///   push the result in the sink.
     pub operator_end: usize,
+    /// The node of the plan that the sink executes.
+    node: Option<Node>,
 }
 
 impl ThreadedSink {
@@ -43,9 +50,15 @@ impl ThreadedSink {
             initial_shared_count,
             shared_count,
             operator_end,
+            node: None,
         }
     }
 
+    pub(super) fn with_plan_node(mut self, node: Node) -> Self {
+        self.node = Some(node);
+        self
+    }
+
     // Only the first node of a shared sink should recurse. The others should return.
     fn allow_recursion(&self) -> bool {
         self.initial_shared_count == *self.shared_count.borrow()
@@ -90,9 +103,13 @@ impl ThreadedSink {
 pub struct PipeLine {
     /// All the sources of this pipeline
     sources: Vec<Box<dyn Source>>,
+    /// The nodes of the plan that the sources execute.
+    source_nodes: Vec<Option<Node>>,
     /// All the operators of this pipeline. Some may be placeholders that will be replaced during
     /// execution
     operators: Vec<ThreadedOperator>,
+    /// The nodes of the plan that the operators execute, if they execute one.
+    operator_nodes: Vec<Option<Node>>,
     /// - offset in the operators vec
     ///   at that point the sink should be called.
     ///   the pipeline will first call the operators on that point and then
@@ -101,8 +118,13 @@ pub struct PipeLine {
     ///     when that hits 0, the sink will finalize
     /// - node of the sink
     sinks: Vec<ThreadedSink>,
+    /// The maximum number of threads that push chunks through the pipeline at the same time,
+    /// or `None` for a thread per chunk.
+    max_threads: Option<NonZeroUsize>,
     /// Log runtime info to stderr
     verbose: bool,
+    /// The accountant of the memory of the query that this pipeline is part of.
+    accountant: Arc<MemoryAccountant>,
 }
 
 impl PipeLine {
@@ -112,11 +134,15 @@ impl PipeLine {
         operators: Vec<PhysOperator>,
         sinks: Vec<ThreadedSink>,
         verbose: bool,
+        accountant: Arc<MemoryAccountant>,
     ) -> PipeLine {
         // we don't use the power of two partition size here
         // we only do that in the sinks itself.
         let n_threads = morsels_per_sink();
 
+        let source_nodes = vec![None; sources.len()];
+        let operator_nodes = vec![None; operators.len()];
+
         // We split so that every thread gets an operator
         // every index maps to a chain of operators than can be pushed as a pipeline for one thread
         let operators = (0..n_threads)
@@ -130,18 +156,43 @@ impl PipeLine {
 
         PipeLine {
             sources,
+            source_nodes,
             operators,
+            operator_nodes,
             sinks,
+            max_threads: None,
             verbose,
+            accountant,
         }
     }
 
+    /// Set the nodes of the plan that the sources and the operators execute, for the metrics.
+    pub(super) fn with_plan_nodes(
+        mut self,
+        source_nodes: Vec<Option<Node>>,
+        operator_nodes: Vec<Option<Node>>,
+    ) -> Self {
+        debug_assert_eq!(source_nodes.len(), self.sources.len());
+        debug_assert_eq!(operator_nodes.len(), self.operator_nodes.len());
+        self.source_nodes = source_nodes;
+        self.operator_nodes = operator_nodes;
+        self
+    }
+
+    /// Push the chunks of the sources through the pipeline on at most `max_threads` threads at
+    /// the same time.
+    pub fn with_max_threads(mut self, max_threads: Option<NonZeroUsize>) -> Self {
+        self.max_threads = max_threads;
+        self
+    }
+
     /// Create a pipeline only consisting of a single branch that always finishes with a sink
     pub(crate) fn new_simple(
         sources: Vec<Box<dyn Source>>,
         operators: Vec<PhysOperator>,
         sink: Box<dyn Sink>,
         verbose: bool,
+        accountant: Arc<MemoryAccountant>,
     ) -> Self {
         let operators_len = operators.len();
         Self::new(
@@ -153,6 +204,7 @@ impl PipeLine {
                 operators_len,
             )],
             verbose,
+            accountant,
         )
     }
 
@@ -166,31 +218,44 @@ impl PipeLine {
     fn set_sources(&mut self, src: Box<dyn Source>) {
         self.sources.clear();
         self.sources.push(src);
+        self.source_nodes = vec![None];
     }
 
     fn run_pipeline_no_finalize(
         &mut self,
         ec: &PExecutionContext,
         pipelines: &mut Vec<PipeLine>,
-    ) -> PolarsResult<(u32, Box<dyn Sink>)> {
+    ) -> PolarsResult<(u32, Box<dyn Sink>, NodeMetrics)> {
         let mut out = None;
         let mut operator_start = 0;
         let last_i = self.sinks.len() - 1;
 
-        // For unions we typically first want to push all pipelines
-        // into the union sink before we call `finalize`
-        // however if the sink is finished early, (for instance a `head`)
-        // we don't want to run the rest of the pipelines and we finalize early
-        let mut sink_finished = false;
-
         for (i, mut sink) in std::mem::take(&mut self.sinks).into_iter().enumerate() {
-            for src in &mut std::mem::take(&mut self.sources) {
-                let mut next_batches = src.get_batches(ec)?;
+            // For unions we typically first want to push all pipelines
+            // into the union sink before we call `finalize`
+            // however if the sink is finished early, (for instance a `head`)
+            // we don't want to run the rest of the pipelines and we finalize early.
+            // A sink before the union, e.g. the `head` of a union input, finishing early
+            // doesn't finish the union.
+            let mut sink_finished = false;
+            let sink_metrics = ec.metrics.start_node(sink.node);
+            let op_metrics = (operator_start..sink.operator_end)
+                .map(|op_i| ec.metrics.start_node(self.operator_nodes[op_i]))
+                .collect::<Vec<_>>();
+
+            let source_nodes = std::mem::take(&mut self.source_nodes);
+            for (src, plan_node) in std::mem::take(&mut self.sources)
+                .iter_mut()
+                .zip(source_nodes)
+            {
+                let src_metrics = ec.metrics.start_node(plan_node);
+                let mut next_batches = get_batches(&mut **src, ec, &src_metrics)?;
 
                 let must_flush: AtomicBool = AtomicBool::new(false);
                 while let SourceResult::GotMoreData(chunks) = next_batches {
                     // Every batches iteration we check if we must continue.
                     ec.execution_state.should_stop()?;
+                    ec.mem_track.wait_for_budget();
 
                     let (sink_result, next_batches2) = par_process_chunks(
                         chunks,
@@ -201,8 +266,13 @@ impl PipeLine {
                         sink.operator_end,
                         src,
                         &must_flush,
+                        self.max_threads,
+                        &src_metrics,
+                        &op_metrics,
+                        &sink_metrics,
                     )?;
                     next_batches = next_batches2;
+                    sink_metrics.record_memory(ec.mem_track.accountant().buffered());
 
                     if let Some(SinkResult::Finished) = sink_result {
                         sink_finished = true;
@@ -216,8 +286,15 @@ impl PipeLine {
                         &mut self.operators,
                         operator_start,
                         sink.operator_end,
+                        &op_metrics,
+                        &sink_metrics,
                     );
                 }
+                ec.metrics.push_source(src.fmt(), &src_metrics);
+            }
+            for (op_i, metrics) in (operator_start..sink.operator_end).zip(&op_metrics) {
+                let name = self.operators[0][op_i].get_ref().fmt();
+                ec.metrics.push_operator(name, metrics);
             }
 
             // Before we reduce we also check if we should continue.
@@ -226,6 +303,7 @@ impl PipeLine {
 
             // The sinks have taken all chunks thread locally, now we reduce them into a single
             // result sink.
+            let start = Instant::now();
             let mut reduced_sink = POOL
                 .install(|| {
                     sink.sinks.into_par_iter().reduce_with(|mut a, mut b| {
@@ -234,6 +312,7 @@ impl PipeLine {
                     })
                 })
                 .unwrap();
+            sink_metrics.record_time(start);
             operator_start = sink.operator_end;
 
             let mut shared_sink_count = {
@@ -246,7 +325,8 @@ impl PipeLine {
             if allow_recursion {
                 while shared_sink_count > 0 && !sink_finished {
                     let mut pipeline = pipelines.pop().unwrap();
-                    let (count, mut sink) = pipeline.run_pipeline_no_finalize(ec, pipelines)?;
+                    let (count, mut sink, metrics) =
+                        pipeline.run_pipeline_no_finalize(ec, pipelines)?;
                     // This branch is hit when we have a Union of joins.
                     // The build side must be converted into an operator and replaced in the next pipeline.
 
@@ -264,18 +344,22 @@ impl PipeLine {
                     if sink.is_join_build()
                         && (!reduced_sink.is_join_build() || (sink.node() != reduced_sink.node()))
                     {
-                        let FinalizedSink::Operator = sink.finalize(ec)? else {
+                        let FinalizedSink::Operator = finalize_sink(ec, sink.as_mut(), &metrics)?
+                        else {
                             unreachable!()
                         };
                     } else {
+                        let start = Instant::now();
                         reduced_sink.combine(sink.as_mut());
+                        sink_metrics.record_time(start);
+                        sink_metrics.merge(&metrics);
                         shared_sink_count = count;
                     }
                 }
             }
 
             if i != last_i {
-                let sink_result = reduced_sink.finalize(ec)?;
+                let sink_result = finalize_sink(ec, reduced_sink.as_mut(), &sink_metrics)?;
                 match sink_result {
                     // turn this sink an a new source
                     FinalizedSink::Finished(df) => self.set_df_as_sources(df),
@@ -286,7 +370,7 @@ impl PipeLine {
                     },
                 }
             } else {
-                out = Some((shared_sink_count, reduced_sink))
+                out = Some((shared_sink_count, reduced_sink, sink_metrics))
             }
         }
         Ok(out.unwrap())
@@ -302,32 +386,90 @@ impl PipeLine {
         ec: &PExecutionContext,
         pipelines: &mut Vec<PipeLine>,
     ) -> PolarsResult<Option<FinalizedSink>> {
-        let (sink_shared_count, mut reduced_sink) = self.run_pipeline_no_finalize(ec, pipelines)?;
+        let (sink_shared_count, mut reduced_sink, metrics) =
+            self.run_pipeline_no_finalize(ec, pipelines)?;
         assert_eq!(sink_shared_count, 0);
 
-        let finalized_reduced_sink = reduced_sink.finalize(ec)?;
+        let finalized_reduced_sink = finalize_sink(ec, reduced_sink.as_mut(), &metrics)?;
         Ok(Some(finalized_reduced_sink))
     }
+
+    /// Run the pipeline and combine `sink`, which must be of the same type as the sink of the
+    /// pipeline, into the reduced sink before it is finalized.
+    pub(crate) fn run_pipeline_combined(
+        &mut self,
+        ec: &PExecutionContext,
+        sink: &mut dyn Sink,
+    ) -> PolarsResult<FinalizedSink> {
+        let (sink_shared_count, mut reduced_sink, metrics) =
+            self.run_pipeline_no_finalize(ec, &mut vec![])?;
+        assert_eq!(sink_shared_count, 0);
+
+        reduced_sink.combine(sink);
+        finalize_sink(ec, reduced_sink.as_mut(), &metrics)
+    }
+}
+
+/// Finalize a sink and push its metrics.
+fn finalize_sink(
+    ec: &PExecutionContext,
+    sink: &mut dyn Sink,
+    metrics: &NodeMetrics,
+) -> PolarsResult<FinalizedSink> {
+    #[cfg(feature = "tracing")]
+    let _span =
+        tracing::debug_span!("finalize", sink = sink.fmt(), chunks = metrics.chunks_in()).entered();
+    let start = Instant::now();
+    let out = sink.finalize(ec)?;
+    metrics.record_time(start);
+    metrics.record_memory(ec.mem_track.accountant().buffered());
+    if let FinalizedSink::Finished(df) = &out {
+        metrics.record_output(df)
+    }
+    // The finalized sink no longer holds its input, the spilled part of it is already released.
+    let spilled = ec.metrics.spilled_since_start(metrics);
+    ec.mem_track
+        .accountant()
+        .release(metrics.bytes_in().saturating_sub(spilled) as usize);
+    ec.metrics.push_sink(sink.fmt(), metrics);
+    Ok(out)
 }
 
 /// Executes all branches and replaces operators and sinks during execution to ensure
 /// we materialize.
 pub fn execute_pipeline(
     state: ExecutionState,
-    mut pipelines: Vec<PipeLine>,
+    pipelines: Vec<PipeLine>,
 ) -> PolarsResult<DataFrame> {
+    execute_pipeline_with_metrics(state, pipelines).map(|(out, _)| out)
+}
+
+/// Like [`execute_pipeline`], but also return the metrics of the nodes of these pipelines.
+///
+/// Every row is a source, operator or sink of a pipeline, in the order in which they finished.
+/// The columns are the name of the node, its kind, the index of the node of the plan that it
+/// executes if there is one, the rows and estimated bytes that went in and came out, the wall
+/// time in microseconds summed over all threads, the bytes that a sink spilled to disk and the
+/// peak of the memory that the query buffered while a sink ran, and the first start and the
+/// last end of the work of the node in microseconds since its pipelines started, which are null
+/// if it did no work.
+pub fn execute_pipeline_with_metrics(
+    state: ExecutionState,
+    mut pipelines: Vec<PipeLine>,
+) -> PolarsResult<(DataFrame, DataFrame)> {
     let mut pipeline = pipelines.pop().unwrap();
-    let ec = PExecutionContext::new(state, pipeline.verbose);
+    let ec =
+        PExecutionContext::with_accountant(state, pipeline.verbose, pipeline.accountant.clone());
 
     let mut sink_out = pipeline.run_pipeline(&ec, &mut pipelines)?;
-    loop {
+    let out = loop {
         match &mut sink_out {
             None => {
                 let mut pipeline = pipelines.pop().unwrap();
                 sink_out = pipeline.run_pipeline(&ec, &mut pipelines)?;
             },
-            Some(FinalizedSink::Finished(df)) => return Ok(std::mem::take(df)),
-            Some(FinalizedSink::Source(src)) => return consume_source(&mut **src, &ec),
+            Some(FinalizedSink::Finished(df)) => break std::mem::take(df),
+            Some(FinalizedSink::Source(src)) => break consume_source(&mut **src, &ec)?,
 
             //
             //  1/\
@@ -344,7 +486,9 @@ pub fn execute_pipeline(
                 sink_out = pipeline.run_pipeline(&ec, &mut pipelines)?;
             },
         }
-    }
+    };
+    let metrics = ec.metrics.finish();
+    Ok((out, metrics))
 }
 
 impl Debug for PipeLine {
@@ -370,7 +514,10 @@ impl Debug for PipeLine {
 }
 
 /// Take a source and materialize it into a [`DataFrame`].
-fn consume_source(src: &mut dyn Source, context: &PExecutionContext) -> PolarsResult<DataFrame> {
+pub(crate) fn consume_source(
+    src: &mut dyn Source,
+    context: &PExecutionContext,
+) -> PolarsResult<DataFrame> {
     let mut frames = Vec::with_capacity(32);
 
     while let SourceResult::GotMoreData(batch) = src.get_batches(context)? {
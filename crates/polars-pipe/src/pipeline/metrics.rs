@@ -0,0 +1,247 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use polars_core::prelude::*;
+use polars_utils::arena::Node;
+
+use crate::executors::sinks::memory::MemoryAccountant;
+
+/// Counters of a single node of a pipeline. They are shared by the threads that run the node.
+pub(crate) struct NodeMetrics {
+    chunks_in: AtomicU64,
+    rows_in: AtomicU64,
+    rows_out: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    // in nanoseconds
+    time: AtomicU64,
+    peak_memory: AtomicU64,
+    // the first start and the last end of the work of the node, in nanoseconds since `created`
+    first_start: AtomicU64,
+    last_end: AtomicU64,
+    created: Instant,
+    // the bytes that the query had spilled before the node started
+    spilled_at_start: u64,
+    // the node of the plan that this node of the pipeline executes
+    plan_node: Option<Node>,
+}
+
+impl NodeMetrics {
+    fn new(plan_node: Option<Node>, spilled_at_start: u64) -> Self {
+        Self {
+            chunks_in: Default::default(),
+            rows_in: Default::default(),
+            rows_out: Default::default(),
+            bytes_in: Default::default(),
+            bytes_out: Default::default(),
+            time: Default::default(),
+            peak_memory: Default::default(),
+            first_start: AtomicU64::new(u64::MAX),
+            last_end: Default::default(),
+            created: Instant::now(),
+            spilled_at_start,
+            plan_node,
+        }
+    }
+
+    pub(crate) fn record_input(&self, df: &DataFrame) {
+        self.chunks_in.fetch_add(1, Ordering::Relaxed);
+        self.rows_in
+            .fetch_add(df.height() as u64, Ordering::Relaxed);
+        self.bytes_in
+            .fetch_add(df.estimated_size() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_output(&self, df: &DataFrame) {
+        self.rows_out
+            .fetch_add(df.height() as u64, Ordering::Relaxed);
+        self.bytes_out
+            .fetch_add(df.estimated_size() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn bytes_in(&self) -> u64 {
+        self.bytes_in.load(Ordering::Relaxed)
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn chunks_in(&self) -> u64 {
+        self.chunks_in.load(Ordering::Relaxed)
+    }
+
+    /// Add the time since `start` to the wall time of this node.
+    pub(crate) fn record_time(&self, start: Instant) {
+        let end = Instant::now();
+        self.time
+            .fetch_add((end - start).as_nanos() as u64, Ordering::Relaxed);
+        self.record_interval(start, end);
+    }
+
+    fn record_interval(&self, start: Instant, end: Instant) {
+        let since_created =
+            |t: Instant| t.saturating_duration_since(self.created).as_nanos() as u64;
+        self.first_start
+            .fetch_min(since_created(start), Ordering::Relaxed);
+        self.last_end
+            .fetch_max(since_created(end), Ordering::Relaxed);
+    }
+
+    /// The first start and the last end of the work of this node, if it did any.
+    fn interval(&self) -> Option<(Instant, Instant)> {
+        let first_start = self.first_start.load(Ordering::Relaxed);
+        (first_start != u64::MAX).then(|| {
+            let at = |nanos| self.created + Duration::from_nanos(nanos);
+            (at(first_start), at(self.last_end.load(Ordering::Relaxed)))
+        })
+    }
+
+    pub(crate) fn record_memory(&self, used: usize) {
+        self.peak_memory.fetch_max(used as u64, Ordering::Relaxed);
+    }
+
+    /// Add the counters of the same node that ran in another pipeline.
+    pub(crate) fn merge(&self, other: &NodeMetrics) {
+        for (a, b) in [
+            (&self.chunks_in, &other.chunks_in),
+            (&self.rows_in, &other.rows_in),
+            (&self.rows_out, &other.rows_out),
+            (&self.bytes_in, &other.bytes_in),
+            (&self.bytes_out, &other.bytes_out),
+            (&self.time, &other.time),
+        ] {
+            a.fetch_add(b.load(Ordering::Relaxed), Ordering::Relaxed);
+        }
+        if let Some((start, end)) = other.interval() {
+            self.record_interval(start, end);
+        }
+        self.record_memory(other.peak_memory.load(Ordering::Relaxed) as usize)
+    }
+}
+
+struct NodeSummary {
+    node: PlSmallStr,
+    kind: &'static str,
+    plan_node: Option<Node>,
+    rows_in: u64,
+    rows_out: u64,
+    bytes_in: u64,
+    bytes_out: u64,
+    // in microseconds
+    time: u64,
+    spill_bytes: u64,
+    peak_memory: u64,
+    // the first start and the last end of the work of the node, in microseconds since the
+    // pipelines started
+    interval: Option<(u64, u64)>,
+}
+
+/// Collects the metrics of the nodes of a query in the order in which the nodes finished.
+pub(crate) struct PipelineMetrics {
+    nodes: Mutex<Vec<NodeSummary>>,
+    started: Instant,
+    accountant: Arc<MemoryAccountant>,
+}
+
+impl PipelineMetrics {
+    pub(crate) fn new(accountant: Arc<MemoryAccountant>) -> Self {
+        Self {
+            nodes: Default::default(),
+            started: Instant::now(),
+            accountant,
+        }
+    }
+
+    /// Start counting a node of the pipeline that executes `plan_node`.
+    pub(crate) fn start_node(&self, plan_node: Option<Node>) -> NodeMetrics {
+        NodeMetrics::new(plan_node, self.accountant.spilled_bytes())
+    }
+
+    pub(crate) fn push_source(&self, node: &str, metrics: &NodeMetrics) {
+        self.push(node, "source", metrics, 0)
+    }
+
+    pub(crate) fn push_operator(&self, node: &str, metrics: &NodeMetrics) {
+        self.push(node, "operator", metrics, 0)
+    }
+
+    /// The bytes that the query spilled since the node of `metrics` started.
+    pub(crate) fn spilled_since_start(&self, metrics: &NodeMetrics) -> u64 {
+        self.accountant
+            .spilled_bytes()
+            .saturating_sub(metrics.spilled_at_start)
+    }
+
+    /// Push a sink, which has spilled all bytes that the query spilled since it started.
+    pub(crate) fn push_sink(&self, node: &str, metrics: &NodeMetrics) {
+        self.push(node, "sink", metrics, self.spilled_since_start(metrics))
+    }
+
+    fn push(&self, node: &str, kind: &'static str, metrics: &NodeMetrics, spill_bytes: u64) {
+        let summary = NodeSummary {
+            node: node.into(),
+            kind,
+            plan_node: metrics.plan_node,
+            rows_in: metrics.rows_in.load(Ordering::Relaxed),
+            rows_out: metrics.rows_out.load(Ordering::Relaxed),
+            bytes_in: metrics.bytes_in.load(Ordering::Relaxed),
+            bytes_out: metrics.bytes_out.load(Ordering::Relaxed),
+            time: metrics.time.load(Ordering::Relaxed) / 1000,
+            spill_bytes,
+            peak_memory: metrics.peak_memory.load(Ordering::Relaxed),
+            interval: metrics.interval().map(|(start, end)| {
+                let since_started =
+                    |t: Instant| t.saturating_duration_since(self.started).as_micros() as u64;
+                (since_started(start), since_started(end))
+            }),
+        };
+        self.nodes.lock().unwrap().push(summary)
+    }
+
+    /// The metrics of the nodes that finished, see [`execute_pipeline_with_metrics`].
+    ///
+    /// [`execute_pipeline_with_metrics`]: crate::pipeline::execute_pipeline_with_metrics
+    pub(crate) fn finish(&self) -> DataFrame {
+        summaries_to_df(&std::mem::take(&mut *self.nodes.lock().unwrap()))
+    }
+}
+
+fn summaries_to_df(nodes: &[NodeSummary]) -> DataFrame {
+    let node = StringChunked::from_iter_values(
+        PlSmallStr::from_static("node"),
+        nodes.iter().map(|n| n.node.as_str()),
+    );
+    let kind = StringChunked::from_iter_values(
+        PlSmallStr::from_static("kind"),
+        nodes.iter().map(|n| n.kind),
+    );
+    let plan_node = UInt64Chunked::from_iter_options(
+        PlSmallStr::from_static("plan_node"),
+        nodes.iter().map(|n| n.plan_node.map(|node| node.0 as u64)),
+    );
+    let mut columns = vec![
+        node.into_series(),
+        kind.into_series(),
+        plan_node.into_series(),
+    ];
+    type Counter = (&'static str, fn(&NodeSummary) -> u64);
+    let counters: [Counter; 7] = [
+        ("rows_in", |n| n.rows_in),
+        ("rows_out", |n| n.rows_out),
+        ("bytes_in", |n| n.bytes_in),
+        ("bytes_out", |n| n.bytes_out),
+        ("time", |n| n.time),
+        ("spill_bytes", |n| n.spill_bytes),
+        ("peak_memory", |n| n.peak_memory),
+    ];
+    for (name, get) in counters {
+        let values = nodes.iter().map(get).collect::<Vec<_>>();
+        columns.push(UInt64Chunked::from_vec(name.into(), values).into_series())
+    }
+    type Bound = (&'static str, fn((u64, u64)) -> u64);
+    let bounds: [Bound; 2] = [("start", |(start, _)| start), ("end", |(_, end)| end)];
+    for (name, get) in bounds {
+        let values = nodes.iter().map(|n| n.interval.map(get));
+        columns.push(UInt64Chunked::from_iter_options(name.into(), values).into_series())
+    }
+    unsafe { DataFrame::new_no_checks(columns) }
+}
@@ -1,16 +1,29 @@
+pub(crate) mod chunk_size;
 mod config;
 mod convert;
 mod dispatcher;
+pub(crate) mod metrics;
 
 pub use convert::{
-    create_pipeline, get_dummy_operator, get_operator, get_sink, swap_join_order, CallBacks,
+    broadcast_join_side, create_pipeline, get_dummy_operator, get_fan_out_sink,
+    get_filter_projection, get_operator, get_sink, swap_join_order, CallBacks,
 };
-pub use dispatcher::{execute_pipeline, PipeLine};
+pub(crate) use dispatcher::consume_source;
+pub use dispatcher::{execute_pipeline, execute_pipeline_with_metrics, PipeLine};
 use polars_core::prelude::*;
 use polars_core::POOL;
 use polars_utils::cell::SyncUnsafeCell;
 
+pub use crate::executors::operators::explode::explode_projection;
 pub use crate::executors::sinks::group_by::aggregates::can_convert_to_hash_agg;
+#[cfg(feature = "dynamic_group_by")]
+pub use crate::executors::sinks::group_by::can_stream_temporal_group_by;
+pub use crate::executors::sinks::memory::MemoryAccountant;
+pub use crate::executors::sinks::window::{window_aggregations, WindowAggregations};
+#[cfg(feature = "asof_join")]
+pub use crate::executors::sources::asof_join::asof_join_inputs;
+#[cfg(feature = "merge_sorted")]
+pub use crate::executors::sources::merge_sorted::merge_sorted_scans;
 use crate::operators::{Operator, Sink};
 
 pub(crate) fn morsels_per_sink() -> usize {
@@ -34,7 +47,13 @@ pub(crate) fn determine_chunk_size(n_cols: usize, n_threads: usize) -> PolarsRes
         )
     } else {
         let thread_factor = std::cmp::max(12 / n_threads, 1);
-        Ok(std::cmp::max(50_000 / n_cols.max(1) * thread_factor, 1000))
+        let mut chunk_size = 50_000 / n_cols.max(1) * thread_factor;
+        // With a memory budget the chunks in flight may take 1/16th of it,
+        // assuming 8 bytes per value.
+        if let Some(limit) = polars_core::config::get_streaming_memory_limit() {
+            chunk_size = std::cmp::min(chunk_size, limit / (16 * 8 * n_threads * n_cols.max(1)));
+        }
+        Ok(std::cmp::max(chunk_size, 1000))
     }
 }
 
@@ -102,6 +102,8 @@ pub(super) use self::rolling::RollingFunction;
 pub(super) use self::rolling_by::RollingFunctionBy;
 #[cfg(feature = "strings")]
 pub use self::strings::StringFunction;
+#[cfg(all(feature = "strings", feature = "concat_str"))]
+pub use self::strings::{FormatAlign, FormatSpec};
 #[cfg(feature = "dtype-struct")]
 pub use self::struct_::StructFunction;
 #[cfg(feature = "trigonometry")]
@@ -352,6 +354,11 @@ pub enum FunctionExpr {
     #[cfg(feature = "reinterpret")]
     Reinterpret(bool),
     ExtendConstant,
+    /// A placeholder that is replaced by a literal before a prepared query runs.
+    Param {
+        name: PlSmallStr,
+        dtype: DataType,
+    },
 }
 
 impl Hash for FunctionExpr {
@@ -577,6 +584,10 @@ impl Hash for FunctionExpr {
             #[cfg(feature = "reinterpret")]
             Reinterpret(signed) => signed.hash(state),
             ExtendConstant => {},
+            Param { name, dtype } => {
+                name.hash(state);
+                dtype.hash(state);
+            },
             #[cfg(feature = "top_k")]
             TopKBy { descending } => descending.hash(state),
         }
@@ -764,6 +775,7 @@ impl Display for FunctionExpr {
             #[cfg(feature = "reinterpret")]
             Reinterpret(_) => "reinterpret",
             ExtendConstant => "extend_constant",
+            Param { name, .. } => return write!(f, "param({name})"),
         };
         write!(f, "{s}")
     }
@@ -1160,6 +1172,14 @@ impl From<FunctionExpr> for SpecialEq<Arc<dyn SeriesUdf>> {
             #[cfg(feature = "reinterpret")]
             Reinterpret(signed) => map!(dispatch::reinterpret, signed),
             ExtendConstant => map_as_slice!(dispatch::extend_constant),
+            Param { name, .. } => {
+                let f = move |_: &mut [Series]| -> PolarsResult<Option<Series>> {
+                    polars_bail!(
+                        ComputeError: "parameter '{}' is not bound, bind it with `LazyFrame::prepare`", name
+                    )
+                };
+                wrap!(f)
+            },
         }
     }
 }
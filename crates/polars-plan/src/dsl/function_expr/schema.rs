@@ -338,6 +338,7 @@ impl FunctionExpr {
                 mapper.with_dtype(dt)
             },
             ExtendConstant => mapper.with_same_dtype(),
+            Param { name, dtype } => Ok(Field::new(name.clone(), dtype.clone())),
         }
     }
 
@@ -347,6 +348,7 @@ impl FunctionExpr {
             FunctionExpr::StructExpr(StructFunction::FieldByName(name)) => {
                 Some(OutputName::Field(name.clone()))
             },
+            FunctionExpr::Param { name, .. } => Some(OutputName::LiteralLhs(name.clone())),
             _ => None,
         }
     }
@@ -33,6 +33,8 @@ pub enum StringFunction {
         delimiter: PlSmallStr,
         ignore_nulls: bool,
     },
+    #[cfg(feature = "concat_str")]
+    FormatValue(FormatSpec),
     #[cfg(feature = "regex")]
     Contains {
         literal: bool,
@@ -47,6 +49,11 @@ pub enum StringFunction {
         dtype: DataType,
         pat: PlSmallStr,
     },
+    #[cfg(feature = "extract_groups")]
+    ExtractAllGroups {
+        dtype: DataType,
+        pat: PlSmallStr,
+    },
     #[cfg(feature = "regex")]
     Find {
         literal: bool,
@@ -73,6 +80,33 @@ pub enum StringFunction {
     },
     #[cfg(feature = "string_reverse")]
     Reverse,
+    #[cfg(feature = "string_similarity")]
+    Levenshtein {
+        normalized: bool,
+    },
+    #[cfg(feature = "string_similarity")]
+    Hamming {
+        normalized: bool,
+    },
+    #[cfg(feature = "string_similarity")]
+    JaroWinkler,
+    #[cfg(feature = "string_normalize")]
+    Normalize {
+        form: UnicodeForm,
+    },
+    #[cfg(feature = "string_collation")]
+    CollationKey(CollationOptions),
+    #[cfg(feature = "string_tokenize")]
+    Tokenize(TokenizeOptions),
+    #[cfg(feature = "string_tokenize")]
+    NGrams {
+        n: usize,
+        options: TokenizeOptions,
+    },
+    #[cfg(feature = "string_phonetic")]
+    Soundex,
+    #[cfg(feature = "string_phonetic")]
+    Metaphone,
     #[cfg(feature = "string_pad")]
     PadStart {
         length: usize,
@@ -107,6 +141,11 @@ pub enum StringFunction {
     },
     #[cfg(feature = "dtype-struct")]
     SplitN(usize),
+    #[cfg(feature = "dtype-struct")]
+    SplitToStruct {
+        names: Arc<[PlSmallStr]>,
+        max_splits: Option<usize>,
+    },
     #[cfg(feature = "temporal")]
     Strptime(DataType, StrptimeOptions),
     Split(bool),
@@ -132,12 +171,49 @@ pub enum StringFunction {
     },
 }
 
+/// The alignment of a value that is padded to the width of its placeholder in [`format_str`].
+#[cfg(feature = "concat_str")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub enum FormatAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// The `[[fill]align][0][width][.precision]` specifier of a placeholder in [`format_str`].
+#[cfg(feature = "concat_str")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub struct FormatSpec {
+    pub fill: char,
+    /// Numbers are aligned to the right and other values to the left by default.
+    pub align: Option<FormatAlign>,
+    pub width: usize,
+    /// The number of decimals of numbers, or the maximum number of characters of other values.
+    pub precision: Option<usize>,
+}
+
+#[cfg(feature = "concat_str")]
+impl Default for FormatSpec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: None,
+            width: 0,
+            precision: None,
+        }
+    }
+}
+
 impl StringFunction {
     pub(super) fn get_field(&self, mapper: FieldsMapper) -> PolarsResult<Field> {
         use StringFunction::*;
         match self {
             #[cfg(feature = "concat_str")]
             ConcatVertical { .. } | ConcatHorizontal { .. } => mapper.with_dtype(DataType::String),
+            #[cfg(feature = "concat_str")]
+            FormatValue(_) => mapper.with_dtype(DataType::String),
             #[cfg(feature = "regex")]
             Contains { .. } => mapper.with_dtype(DataType::Boolean),
             CountMatches(_) => mapper.with_dtype(DataType::UInt32),
@@ -146,6 +222,8 @@ impl StringFunction {
             ExtractAll => mapper.with_dtype(DataType::List(Box::new(DataType::String))),
             #[cfg(feature = "extract_groups")]
             ExtractGroups { dtype, .. } => mapper.with_dtype(dtype.clone()),
+            #[cfg(feature = "extract_groups")]
+            ExtractAllGroups { dtype, .. } => mapper.with_dtype(dtype.clone()),
             #[cfg(feature = "string_to_integer")]
             ToInteger { .. } => mapper.with_dtype(DataType::Int64),
             #[cfg(feature = "regex")]
@@ -160,6 +238,26 @@ impl StringFunction {
             Replace { .. } => mapper.with_same_dtype(),
             #[cfg(feature = "string_reverse")]
             Reverse => mapper.with_same_dtype(),
+            #[cfg(feature = "string_similarity")]
+            Levenshtein { normalized } | Hamming { normalized } => {
+                if *normalized {
+                    mapper.with_dtype(DataType::Float64)
+                } else {
+                    mapper.with_dtype(DataType::UInt32)
+                }
+            },
+            #[cfg(feature = "string_similarity")]
+            JaroWinkler => mapper.with_dtype(DataType::Float64),
+            #[cfg(feature = "string_normalize")]
+            Normalize { .. } => mapper.with_same_dtype(),
+            #[cfg(feature = "string_collation")]
+            CollationKey(_) => mapper.with_dtype(DataType::Binary),
+            #[cfg(feature = "string_tokenize")]
+            Tokenize(_) | NGrams { .. } => {
+                mapper.with_dtype(DataType::List(Box::new(DataType::String)))
+            },
+            #[cfg(feature = "string_phonetic")]
+            Soundex | Metaphone => mapper.with_same_dtype(),
             #[cfg(feature = "temporal")]
             Strptime(dtype, _) => mapper.with_dtype(dtype.clone()),
             Split(_) => mapper.with_dtype(DataType::List(Box::new(DataType::String))),
@@ -191,6 +289,13 @@ impl StringFunction {
                     .map(|i| Field::new(format_pl_smallstr!("field_{i}"), DataType::String))
                     .collect(),
             )),
+            #[cfg(feature = "dtype-struct")]
+            SplitToStruct { names, .. } => mapper.with_dtype(DataType::Struct(
+                names
+                    .iter()
+                    .map(|name| Field::new(name.clone(), DataType::String))
+                    .collect(),
+            )),
             #[cfg(feature = "find_many")]
             ContainsMany { .. } => mapper.with_dtype(DataType::Boolean),
             #[cfg(feature = "find_many")]
@@ -213,10 +318,14 @@ impl Display for StringFunction {
             #[cfg(feature = "concat_str")]
             ConcatHorizontal { .. } => "concat_horizontal",
             #[cfg(feature = "concat_str")]
+            FormatValue(_) => "format_value",
+            #[cfg(feature = "concat_str")]
             ConcatVertical { .. } => "concat_vertical",
             ExtractAll => "extract_all",
             #[cfg(feature = "extract_groups")]
             ExtractGroups { .. } => "extract_groups",
+            #[cfg(feature = "extract_groups")]
+            ExtractAllGroups { .. } => "extract_all_groups",
             #[cfg(feature = "string_to_integer")]
             ToInteger { .. } => "to_integer",
             #[cfg(feature = "regex")]
@@ -238,6 +347,24 @@ impl Display for StringFunction {
             Replace { .. } => "replace",
             #[cfg(feature = "string_reverse")]
             Reverse => "reverse",
+            #[cfg(feature = "string_similarity")]
+            Levenshtein { .. } => "levenshtein",
+            #[cfg(feature = "string_similarity")]
+            Hamming { .. } => "hamming",
+            #[cfg(feature = "string_similarity")]
+            JaroWinkler => "jaro_winkler",
+            #[cfg(feature = "string_normalize")]
+            Normalize { .. } => "normalize",
+            #[cfg(feature = "string_collation")]
+            CollationKey(_) => "collation_key",
+            #[cfg(feature = "string_tokenize")]
+            Tokenize(_) => "tokenize",
+            #[cfg(feature = "string_tokenize")]
+            NGrams { .. } => "ngrams",
+            #[cfg(feature = "string_phonetic")]
+            Soundex => "soundex",
+            #[cfg(feature = "string_phonetic")]
+            Metaphone => "metaphone",
             #[cfg(feature = "string_encoding")]
             HexEncode => "hex_encode",
             #[cfg(feature = "binary_encoding")]
@@ -263,6 +390,8 @@ impl Display for StringFunction {
             },
             #[cfg(feature = "dtype-struct")]
             SplitN(_) => "splitn",
+            #[cfg(feature = "dtype-struct")]
+            SplitToStruct { .. } => "split_to_struct",
             #[cfg(feature = "temporal")]
             Strptime(_, _) => "strptime",
             Split(inclusive) => {
@@ -309,6 +438,10 @@ impl From<StringFunction> for SpecialEq<Arc<dyn SeriesUdf>> {
             ExtractGroups { pat, dtype } => {
                 map!(strings::extract_groups, &pat, &dtype)
             },
+            #[cfg(feature = "extract_groups")]
+            ExtractAllGroups { pat, dtype } => {
+                map!(strings::extract_all_groups, &pat, &dtype)
+            },
             #[cfg(feature = "regex")]
             Find { literal, strict } => map_as_slice!(strings::find, literal, strict),
             LenBytes => map!(strings::len_bytes),
@@ -336,6 +469,10 @@ impl From<StringFunction> for SpecialEq<Arc<dyn SeriesUdf>> {
             SplitExact { n, inclusive } => map_as_slice!(strings::split_exact, n, inclusive),
             #[cfg(feature = "dtype-struct")]
             SplitN(n) => map_as_slice!(strings::splitn, n),
+            #[cfg(feature = "dtype-struct")]
+            SplitToStruct { names, max_splits } => {
+                map_as_slice!(strings::split_to_struct, &names, max_splits)
+            },
             #[cfg(feature = "concat_str")]
             ConcatVertical {
                 delimiter,
@@ -346,10 +483,30 @@ impl From<StringFunction> for SpecialEq<Arc<dyn SeriesUdf>> {
                 delimiter,
                 ignore_nulls,
             } => map_as_slice!(strings::concat_hor, &delimiter, ignore_nulls),
+            #[cfg(feature = "concat_str")]
+            FormatValue(spec) => map!(strings::format_value, &spec),
             #[cfg(feature = "regex")]
             Replace { n, literal } => map_as_slice!(strings::replace, literal, n),
             #[cfg(feature = "string_reverse")]
             Reverse => map!(strings::reverse),
+            #[cfg(feature = "string_similarity")]
+            Levenshtein { normalized } => map_as_slice!(strings::levenshtein, normalized),
+            #[cfg(feature = "string_similarity")]
+            Hamming { normalized } => map_as_slice!(strings::hamming, normalized),
+            #[cfg(feature = "string_similarity")]
+            JaroWinkler => map_as_slice!(strings::jaro_winkler),
+            #[cfg(feature = "string_normalize")]
+            Normalize { form } => map!(strings::normalize, form),
+            #[cfg(feature = "string_collation")]
+            CollationKey(options) => map!(strings::collation_key, &options),
+            #[cfg(feature = "string_tokenize")]
+            Tokenize(options) => map!(strings::tokenize, options),
+            #[cfg(feature = "string_tokenize")]
+            NGrams { n, options } => map!(strings::ngrams, n, options),
+            #[cfg(feature = "string_phonetic")]
+            Soundex => map!(strings::soundex),
+            #[cfg(feature = "string_phonetic")]
+            Metaphone => map!(strings::metaphone),
             Uppercase => map!(uppercase),
             Lowercase => map!(lowercase),
             #[cfg(feature = "nightly")]
@@ -514,6 +671,13 @@ pub(super) fn extract_groups(s: &Series, pat: &str, dtype: &DataType) -> PolarsR
     ca.extract_groups(pat, dtype)
 }
 
+#[cfg(feature = "extract_groups")]
+/// Extract the capture groups of every match of a regex pattern as a list of structs
+pub(super) fn extract_all_groups(s: &Series, pat: &str, dtype: &DataType) -> PolarsResult<Series> {
+    let ca = s.str()?;
+    ca.extract_all_groups(pat, dtype)
+}
+
 #[cfg(feature = "string_pad")]
 pub(super) fn pad_start(s: &Series, length: usize, fill_char: char) -> PolarsResult<Series> {
     let ca = s.str()?;
@@ -647,6 +811,19 @@ pub(super) fn splitn(s: &[Series], n: usize) -> PolarsResult<Series> {
     ca.splitn(by, n).map(|ca| ca.into_series())
 }
 
+#[cfg(feature = "dtype-struct")]
+pub(super) fn split_to_struct(
+    s: &[Series],
+    names: &[PlSmallStr],
+    max_splits: Option<usize>,
+) -> PolarsResult<Series> {
+    let ca = s[0].str()?;
+    let by = s[1].str()?;
+
+    ca.split_to_named_struct(by, names, max_splits)
+        .map(|ca| ca.into_series())
+}
+
 pub(super) fn split(s: &[Series], inclusive: bool) -> PolarsResult<Series> {
     let ca = s[0].str()?;
     let by = s[1].str()?;
@@ -762,6 +939,50 @@ pub(super) fn concat_hor(
     Ok(polars_ops::chunked_array::hor_str_concat(&cas, delimiter, ignore_nulls)?.into_series())
 }
 
+#[cfg(feature = "concat_str")]
+pub(super) fn format_value(s: &Series, spec: &FormatSpec) -> PolarsResult<Series> {
+    use polars_core::prelude::arity::unary_elementwise;
+
+    let numeric = s.dtype().is_numeric();
+    let ca: StringChunked = match spec.precision {
+        Some(precision) if numeric => {
+            let s = s.cast(&DataType::Float64)?;
+            unary_elementwise(s.f64()?, |opt_v| opt_v.map(|v| format!("{v:.precision$}")))
+        },
+        Some(precision) => {
+            let s = s.cast(&DataType::String)?;
+            unary_elementwise(s.str()?, |opt_v: Option<&str>| {
+                opt_v.map(|v| v.chars().take(precision).collect::<String>())
+            })
+        },
+        None => s.cast(&DataType::String)?.str()?.clone(),
+    };
+    if spec.width == 0 {
+        return Ok(ca.into_series());
+    }
+
+    let align = spec.align.unwrap_or(if numeric {
+        FormatAlign::Right
+    } else {
+        FormatAlign::Left
+    });
+    let out: StringChunked = unary_elementwise(&ca, |opt_v: Option<&str>| {
+        let v = opt_v?;
+        let pad = spec.width.saturating_sub(v.chars().count());
+        let (left, right) = match align {
+            FormatAlign::Left => (0, pad),
+            FormatAlign::Center => (pad / 2, pad - pad / 2),
+            FormatAlign::Right => (pad, 0),
+        };
+        let mut padded = String::with_capacity(v.len() + pad * spec.fill.len_utf8());
+        padded.extend(std::iter::repeat(spec.fill).take(left));
+        padded.push_str(v);
+        padded.extend(std::iter::repeat(spec.fill).take(right));
+        Some(padded)
+    });
+    Ok(out.into_series())
+}
+
 impl From<StringFunction> for FunctionExpr {
     fn from(str: StringFunction) -> Self {
         FunctionExpr::StringExpr(str)
@@ -930,6 +1151,83 @@ pub(super) fn reverse(s: &Series) -> PolarsResult<Series> {
     Ok(ca.str_reverse().into_series())
 }
 
+#[cfg(feature = "string_similarity")]
+pub(super) fn levenshtein(s: &[Series], normalized: bool) -> PolarsResult<Series> {
+    polars_ensure!(
+        _ensure_lengths(s),
+        ComputeError: "all series in `str.levenshtein` should have equal or unit length",
+    );
+    let ca = s[0].str()?;
+    let other = s[1].str()?;
+    Ok(if normalized {
+        polars_ops::chunked_array::strings::levenshtein_similarity(ca, other).into_series()
+    } else {
+        polars_ops::chunked_array::strings::levenshtein(ca, other).into_series()
+    })
+}
+
+#[cfg(feature = "string_similarity")]
+pub(super) fn hamming(s: &[Series], normalized: bool) -> PolarsResult<Series> {
+    polars_ensure!(
+        _ensure_lengths(s),
+        ComputeError: "all series in `str.hamming` should have equal or unit length",
+    );
+    let ca = s[0].str()?;
+    let other = s[1].str()?;
+    Ok(if normalized {
+        polars_ops::chunked_array::strings::hamming_similarity(ca, other).into_series()
+    } else {
+        polars_ops::chunked_array::strings::hamming(ca, other).into_series()
+    })
+}
+
+#[cfg(feature = "string_similarity")]
+pub(super) fn jaro_winkler(s: &[Series]) -> PolarsResult<Series> {
+    polars_ensure!(
+        _ensure_lengths(s),
+        ComputeError: "all series in `str.jaro_winkler` should have equal or unit length",
+    );
+    let ca = s[0].str()?;
+    let other = s[1].str()?;
+    Ok(polars_ops::chunked_array::strings::jaro_winkler(ca, other).into_series())
+}
+
+#[cfg(feature = "string_normalize")]
+pub(super) fn normalize(s: &Series, form: UnicodeForm) -> PolarsResult<Series> {
+    let ca = s.str()?;
+    Ok(polars_ops::chunked_array::strings::normalize(ca, form).into_series())
+}
+
+#[cfg(feature = "string_collation")]
+pub(super) fn collation_key(s: &Series, options: &CollationOptions) -> PolarsResult<Series> {
+    let ca = s.str()?;
+    Ok(polars_ops::chunked_array::strings::collation_key(ca, options)?.into_series())
+}
+
+#[cfg(feature = "string_tokenize")]
+pub(super) fn tokenize(s: &Series, options: TokenizeOptions) -> PolarsResult<Series> {
+    let ca = s.str()?;
+    Ok(polars_ops::chunked_array::strings::tokenize(ca, options).into_series())
+}
+
+#[cfg(feature = "string_tokenize")]
+pub(super) fn ngrams(s: &Series, n: usize, options: TokenizeOptions) -> PolarsResult<Series> {
+    let ca = s.str()?;
+    polars_ops::chunked_array::strings::ngrams(ca, n, options).map(|ca| ca.into_series())
+}
+
+#[cfg(feature = "string_phonetic")]
+pub(super) fn soundex(s: &Series) -> PolarsResult<Series> {
+    let ca = s.str()?;
+    Ok(polars_ops::chunked_array::strings::soundex(ca).into_series())
+}
+
+#[cfg(feature = "string_phonetic")]
+pub(super) fn metaphone(s: &Series) -> PolarsResult<Series> {
+    let ca = s.str()?;
+    Ok(polars_ops::chunked_array::strings::metaphone(ca).into_series())
+}
+
 #[cfg(feature = "string_to_integer")]
 pub(super) fn to_integer(s: &[Series], strict: bool) -> PolarsResult<Series> {
     let ca = s[0].str()?;
@@ -22,30 +22,113 @@ pub fn concat_str<E: AsRef<[Expr]>>(s: E, separator: &str, ignore_nulls: bool) -
     }
 }
 
+#[cfg(all(feature = "concat_str", feature = "strings"))]
+/// Parse the `[[fill]align][0][width][.precision]` specifier of a placeholder.
+fn parse_format_spec(spec: &str) -> PolarsResult<FormatSpec> {
+    let invalid = || polars_err!(InvalidOperation: "invalid format specifier '{}'", spec);
+    let align = |c| match c {
+        '<' => Some(FormatAlign::Left),
+        '^' => Some(FormatAlign::Center),
+        '>' => Some(FormatAlign::Right),
+        _ => None,
+    };
+
+    let mut out = FormatSpec::default();
+    let mut chars = spec.chars();
+    let mut rest = match (chars.next(), chars.next()) {
+        (Some(fill), Some(c)) if align(c).is_some() => {
+            out.fill = fill;
+            out.align = align(c);
+            chars.as_str()
+        },
+        (Some(c), _) if align(c).is_some() => {
+            out.align = align(c);
+            &spec[1..]
+        },
+        _ => spec,
+    };
+    // Without an alignment, a leading zero pads numbers with zeros.
+    if out.align.is_none() {
+        if let Some(r) = rest.strip_prefix('0') {
+            out.fill = '0';
+            out.align = Some(FormatAlign::Right);
+            rest = r;
+        }
+    }
+
+    let (width, precision) = match rest.split_once('.') {
+        Some((width, precision)) => (width, Some(precision)),
+        None => (rest, None),
+    };
+    if !width.is_empty() {
+        out.width = width.parse().map_err(|_| invalid())?;
+    }
+    if let Some(precision) = precision {
+        out.precision = Some(precision.parse().map_err(|_| invalid())?);
+    }
+    Ok(out)
+}
+
 #[cfg(all(feature = "concat_str", feature = "strings"))]
 /// Format the results of an array of expressions using a format string
+///
+/// The placeholders are `{}`, or `{:spec}` with a `[[fill]align][0][width][.precision]`
+/// specifier as in Rust's `format!`, e.g. `{:>8.2}` for a number with two decimals that is right
+/// aligned in eight characters. `{{` and `}}` are literal braces.
 pub fn format_str<E: AsRef<[Expr]>>(format: &str, args: E) -> PolarsResult<Expr> {
     let mut args: std::collections::VecDeque<Expr> = args.as_ref().to_vec().into();
 
     // Parse the format string, and separate substrings between placeholders
-    let segments: Vec<&str> = format.split("{}").collect();
+    let mut segments = vec![String::new()];
+    let mut specs: Vec<Option<FormatSpec>> = vec![];
+    let mut rest = format;
+    while let Some(i) = rest.find(['{', '}']) {
+        let (text, brace, after) = (&rest[..i], &rest[i..i + 1], &rest[i + 1..]);
+        let segment = segments.last_mut().unwrap();
+        segment.push_str(text);
+        if let Some(after) = after.strip_prefix(brace) {
+            segment.push_str(brace);
+            rest = after;
+            continue;
+        }
+        polars_ensure!(brace == "{", InvalidOperation: "unmatched '}}' in format string");
+        let end = after
+            .find('}')
+            .ok_or_else(|| polars_err!(InvalidOperation: "unmatched '{{' in format string"))?;
+        let spec = match &after[..end] {
+            "" => None,
+            placeholder => {
+                let spec = placeholder.strip_prefix(':').ok_or_else(
+                    || polars_err!(InvalidOperation: "invalid placeholder '{{{}}}'", placeholder),
+                )?;
+                Some(parse_format_spec(spec)?)
+            },
+        };
+        specs.push(spec);
+        segments.push(String::new());
+        rest = &after[end + 1..];
+    }
+    segments.last_mut().unwrap().push_str(rest);
 
     polars_ensure!(
-        segments.len() - 1 == args.len(),
+        specs.len() == args.len(),
         ShapeMismatch: "number of placeholders should equal the number of arguments"
     );
 
     let mut exprs: Vec<Expr> = Vec::new();
 
-    for (i, s) in segments.iter().enumerate() {
+    for (i, s) in segments.into_iter().enumerate() {
         if i > 0 {
             if let Some(arg) = args.pop_front() {
-                exprs.push(arg);
+                exprs.push(match specs[i - 1] {
+                    Some(spec) => arg.map_private(StringFunction::FormatValue(spec).into()),
+                    None => arg,
+                });
             }
         }
 
         if !s.is_empty() {
-            exprs.push(lit(s.to_string()))
+            exprs.push(lit(s))
         }
     }
 
@@ -12,6 +12,7 @@ mod correlation;
 pub(crate) mod horizontal;
 #[cfg(any(feature = "range", feature = "arg_where"))]
 mod index;
+mod param;
 #[cfg(feature = "range")]
 mod range;
 mod repeat;
@@ -31,6 +32,7 @@ pub use correlation::*;
 pub use horizontal::*;
 #[cfg(any(feature = "range", feature = "arg_where"))]
 pub use index::*;
+pub use param::*;
 #[cfg(feature = "dtype-struct")]
 use polars_core::utils::get_supertype;
 #[cfg(all(feature = "range", feature = "temporal"))]
@@ -0,0 +1,24 @@
+use super::*;
+
+/// A placeholder for a value that is bound when a prepared query is executed, see
+/// `LazyFrame::prepare`.
+///
+/// The `dtype` of the value is needed to resolve the schema and to optimize the query before the
+/// value is known.
+pub fn param(name: impl Into<PlSmallStr>, dtype: DataType) -> Expr {
+    Expr::Function {
+        input: vec![],
+        function: FunctionExpr::Param {
+            name: name.into(),
+            dtype,
+        },
+        options: FunctionOptions {
+            collect_groups: ApplyOptions::ElementWise,
+            // There is no input of which the name can be kept.
+            flags: FunctionFlags::default()
+                | FunctionFlags::ALLOW_EMPTY_INPUTS
+                | FunctionFlags::ALLOW_RENAME,
+            ..Default::default()
+        },
+    }
+}
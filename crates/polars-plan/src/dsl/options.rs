@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use polars_ops::prelude::{JoinArgs, JoinType};
 #[cfg(feature = "dynamic_group_by")]
 use polars_time::RollingGroupOptions;
@@ -51,6 +53,20 @@ pub struct JoinOptions {
     /// Holds `(Option<known_size>, estimated_size)`
     pub rows_left: (Option<usize>, usize),
     pub rows_right: (Option<usize>, usize),
+    /// Hint that this input is small, so that the streaming engine builds the hash table of
+    /// the join from it and shares that with all threads that stream the other input.
+    pub broadcast: Option<JoinSide>,
+    /// Hint to partition the hash table of the join in the streaming engine, even if an input
+    /// is small.
+    pub partitioned: bool,
+}
+
+/// An input of a join.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum JoinSide {
+    Left,
+    Right,
 }
 
 impl Default for JoinOptions {
@@ -61,10 +77,45 @@ impl Default for JoinOptions {
             args: JoinArgs::new(JoinType::Left),
             rows_left: (None, usize::MAX),
             rows_right: (None, usize::MAX),
+            broadcast: None,
+            partitioned: false,
         }
     }
 }
 
+/// How the streaming engine builds the hash tables of joins, see [`QueryHints::join`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum JoinHint {
+    /// Build the hash table from this input and share it with all threads that stream the
+    /// other input.
+    Broadcast(JoinSide),
+    /// Partition the hash table, even if an input is small.
+    Partitioned,
+}
+
+/// Hints that force or forbid behaviors of the engines for a single query, where the
+/// environment variables and the global settings apply to all queries of the process.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QueryHints {
+    /// Build the hash tables of the joins of the query in this way, unless a join has a hint
+    /// of its own.
+    pub join: Option<JoinHint>,
+    /// Turn common subplan and subexpression elimination on or off.
+    pub cse: Option<bool>,
+    /// Turn the streaming engine on or off. Off keeps the nodes of the query in memory, also
+    /// when the query is part of a larger query that streams.
+    pub streaming: Option<bool>,
+    /// The maximum number of threads that run the pipelines of the streaming engine for this
+    /// query at the same time. The smallest maximum of the parts of a query holds.
+    pub max_threads: Option<NonZeroUsize>,
+    /// The bytes that the streaming engine may buffer for this query before its operators
+    /// spill. The smallest limit of the parts of a query holds. The default is the
+    /// `POLARS_STREAMING_MEMORY_LIMIT` env var.
+    pub memory_limit: Option<usize>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowType {
@@ -1,6 +1,6 @@
 use super::*;
 /// Specialized expressions for [`Series`] of [`DataType::String`].
-pub struct StringNameSpace(pub(crate) Expr);
+pub struct StringNameSpace(pub Expr);
 
 impl StringNameSpace {
     /// Check if a string value contains a literal substring.
@@ -159,29 +159,7 @@ impl StringNameSpace {
     #[cfg(feature = "extract_groups")]
     // Extract all captures groups from a regex pattern as a struct
     pub fn extract_groups(self, pat: &str) -> PolarsResult<Expr> {
-        // regex will be compiled twice, because it doesn't support serde
-        // and we need to compile it here to determine the output datatype
-
-        use polars_utils::format_pl_smallstr;
-        let reg = regex::Regex::new(pat)?;
-        let names = reg
-            .capture_names()
-            .enumerate()
-            .skip(1)
-            .map(|(idx, opt_name)| {
-                opt_name
-                    .map(PlSmallStr::from_str)
-                    .unwrap_or_else(|| format_pl_smallstr!("{idx}"))
-            })
-            .collect::<Vec<_>>();
-
-        let dtype = DataType::Struct(
-            names
-                .iter()
-                .map(|name| Field::new(name.clone(), DataType::String))
-                .collect(),
-        );
-
+        let dtype = capture_groups_dtype(pat)?;
         Ok(self.0.map_private(
             StringFunction::ExtractGroups {
                 dtype,
@@ -191,6 +169,24 @@ impl StringNameSpace {
         ))
     }
 
+    #[cfg(feature = "extract_groups")]
+    /// Extract the capture groups of every match of a regex pattern as a list of structs, with a
+    /// struct per match. The list is empty if the pattern doesn't match.
+    pub fn extract_all_groups(self, pat: &str) -> PolarsResult<Expr> {
+        let dtype = capture_groups_dtype(pat)?;
+        polars_ensure!(
+            !matches!(&dtype, DataType::Struct(fields) if fields.is_empty()),
+            InvalidOperation: "the pattern of `extract_all_groups` has no capture groups"
+        );
+        Ok(self.0.map_private(
+            StringFunction::ExtractAllGroups {
+                dtype: DataType::List(Box::new(dtype)),
+                pat: pat.into(),
+            }
+            .into(),
+        ))
+    }
+
     /// Pad the start of the string until it reaches the given length.
     ///
     /// Padding is done using the specified `fill_char`.
@@ -394,6 +390,27 @@ impl StringNameSpace {
             .map_many_private(StringFunction::SplitN(n).into(), &[by], false, None)
     }
 
+    #[cfg(feature = "dtype-struct")]
+    /// Split by a given substring into a [`DataType::Struct`] with the fields `names`. With
+    /// `max_splits`, the string is split at most that many times and the remainder is kept intact
+    /// in the field after the last split. The fields without a part are null.
+    pub fn split_to_struct<I, S>(self, by: Expr, names: I, max_splits: Option<usize>) -> Expr
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<PlSmallStr>,
+    {
+        self.0.map_many_private(
+            StringFunction::SplitToStruct {
+                names: names.into_iter().map(|x| x.into()).collect(),
+                max_splits,
+            }
+            .into(),
+            &[by],
+            false,
+            None,
+        )
+    }
+
     #[cfg(feature = "regex")]
     /// Replace values that match a regex `pat` with a `value`.
     pub fn replace(self, pat: Expr, value: Expr, literal: bool) -> Expr {
@@ -438,6 +455,114 @@ impl StringNameSpace {
         )
     }
 
+    #[cfg(feature = "string_similarity")]
+    /// The Levenshtein distance, in characters, to the strings of `other`, or with `normalized`
+    /// the similarity between 0 and 1 that is the distance relative to the longer string.
+    pub fn levenshtein(self, other: Expr, normalized: bool) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::Levenshtein { normalized }),
+            &[other],
+            false,
+            None,
+        )
+    }
+
+    #[cfg(feature = "string_similarity")]
+    /// The Hamming distance to the strings of `other`, or with `normalized` the similarity
+    /// between 0 and 1 that is the distance relative to the length of the strings. It is null
+    /// for strings of different lengths.
+    pub fn hamming(self, other: Expr, normalized: bool) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::Hamming { normalized }),
+            &[other],
+            false,
+            None,
+        )
+    }
+
+    #[cfg(feature = "string_similarity")]
+    /// The Jaro-Winkler similarity, between 0 and 1, to the strings of `other`.
+    pub fn jaro_winkler(self, other: Expr) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::JaroWinkler),
+            &[other],
+            false,
+            None,
+        )
+    }
+
+    #[cfg(feature = "string_normalize")]
+    /// Normalize each string to the Unicode normalization `form`, so that strings that look the
+    /// same but are composed of different code points compare equal.
+    pub fn normalize(self, form: UnicodeForm) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::Normalize { form }),
+            &[],
+            false,
+            None,
+        )
+    }
+
+    #[cfg(feature = "string_collation")]
+    /// A binary key of each string that orders the strings in the collation of `options`, e.g.
+    /// the alphabet of a locale, instead of by their bytes. Sort by the key to sort the strings
+    /// in that order, or group by it to treat strings that differ only in case or accents as
+    /// equal. The collation is only available as this expression, not as an option of `sort`,
+    /// and a locale of which the collation isn't supported is an error.
+    pub fn collation_key(self, options: CollationOptions) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::CollationKey(options)),
+            &[],
+            false,
+            None,
+        )
+    }
+
+    #[cfg(feature = "string_tokenize")]
+    /// Split each string into a list of tokens, e.g. words or characters.
+    pub fn tokenize(self, options: TokenizeOptions) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::Tokenize(options)),
+            &[],
+            false,
+            None,
+        )
+    }
+
+    #[cfg(feature = "string_tokenize")]
+    /// The list of the n-grams of each string: the sequences of `n` consecutive tokens, joined by
+    /// a space, or without a separator for n-grams of characters.
+    pub fn ngrams(self, n: usize, options: TokenizeOptions) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::NGrams { n, options }),
+            &[],
+            false,
+            None,
+        )
+    }
+
+    #[cfg(feature = "string_phonetic")]
+    /// The Soundex code of each string, e.g. `R163` for both "Robert" and "Rupert".
+    pub fn soundex(self) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::Soundex),
+            &[],
+            false,
+            None,
+        )
+    }
+
+    #[cfg(feature = "string_phonetic")]
+    /// The Metaphone code of each string, which is equal for words that sound alike in English.
+    pub fn metaphone(self) -> Expr {
+        self.0.map_many_private(
+            FunctionExpr::StringExpr(StringFunction::Metaphone),
+            &[],
+            false,
+            None,
+        )
+    }
+
     /// Remove leading and trailing characters, or whitespace if matches is None.
     pub fn strip_chars(self, matches: Expr) -> Expr {
         self.0.map_many_private(
@@ -593,3 +718,31 @@ impl StringNameSpace {
         )
     }
 }
+
+#[cfg(feature = "extract_groups")]
+/// The struct with a string field for every capture group of `pat`. Unnamed groups are named by
+/// their position.
+fn capture_groups_dtype(pat: &str) -> PolarsResult<DataType> {
+    // regex will be compiled twice, because it doesn't support serde
+    // and we need to compile it here to determine the output datatype
+
+    use polars_utils::format_pl_smallstr;
+    let reg = regex::Regex::new(pat)?;
+    let names = reg
+        .capture_names()
+        .enumerate()
+        .skip(1)
+        .map(|(idx, opt_name)| {
+            opt_name
+                .map(PlSmallStr::from_str)
+                .unwrap_or_else(|| format_pl_smallstr!("{idx}"))
+        })
+        .collect::<Vec<_>>();
+
+    Ok(DataType::Struct(
+        names
+            .iter()
+            .map(|name| Field::new(name.clone(), DataType::String))
+            .collect(),
+    ))
+}
@@ -33,6 +33,8 @@ bitflags! {
         const ROW_ESTIMATE = 1 << 13;
         /// Replace simple projections with a faster inlined projection that skips the expression engine.
         const FAST_PROJECTION = 1 << 14;
+        /// Reorder chains of inner joins so that the smallest relations are joined first.
+        const JOIN_REORDER = 1 << 15;
     }
 }
 
@@ -273,7 +273,10 @@ impl AExpr {
                 *nested = nested
                     .saturating_sub(options.flags.contains(FunctionFlags::RETURNS_SCALAR) as _);
                 let fields = func_args_to_fields(input, schema, arena, nested)?;
-                polars_ensure!(!fields.is_empty(), ComputeError: "expression: '{}' didn't get any inputs", function);
+                polars_ensure!(
+                    !fields.is_empty() || options.flags.contains(FunctionFlags::ALLOW_EMPTY_INPUTS),
+                    ComputeError: "expression: '{}' didn't get any inputs", function
+                );
                 function.get_field(schema, Context::Default, &fields)
             },
             Slice { input, .. } => arena.get(*input).to_field_impl(schema, arena, nested),
@@ -6,6 +6,7 @@ use polars_core::prelude::*;
 pub use super::options::AnonymousScanOptions;
 use crate::dsl::Expr;
 
+#[derive(Clone)]
 pub struct AnonymousScanArgs {
     pub n_rows: Option<usize>,
     pub with_columns: Option<Arc<[PlSmallStr]>>,
@@ -14,11 +15,40 @@ pub struct AnonymousScanArgs {
     pub predicate: Option<Expr>,
 }
 
+/// The pushdowns that an [`AnonymousScan`] applied to the [`DataFrame`] it returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AppliedPushdowns {
+    /// The rows were filtered with [`AnonymousScanArgs::predicate`].
+    pub predicate: bool,
+    /// Only the columns of [`AnonymousScanArgs::with_columns`] were read.
+    pub projection: bool,
+    /// At most [`AnonymousScanArgs::n_rows`] rows were read.
+    pub slice: bool,
+}
+
 pub trait AnonymousScan: Send + Sync {
     fn as_any(&self) -> &dyn Any;
     /// Creates a DataFrame from the supplied function & scan options.
     fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame>;
 
+    /// Creates a DataFrame from the supplied function & scan options, and reports which of the
+    /// pushed-down predicate, projection and slice were applied. Polars applies the others to the
+    /// returned DataFrame, so that a scan can e.g. only apply the predicates its source supports.
+    ///
+    /// Defaults to [`AnonymousScan::scan`], of which the allowed pushdowns are assumed to be
+    /// applied.
+    fn scan_with_pushdowns(
+        &self,
+        scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<(DataFrame, AppliedPushdowns)> {
+        let applied = AppliedPushdowns {
+            predicate: self.allows_predicate_pushdown(),
+            projection: self.allows_projection_pushdown(),
+            slice: self.allows_slice_pushdown(),
+        };
+        Ok((self.scan(scan_opts)?, applied))
+    }
+
     /// Produce the next batch Polars can consume. Implement this method to get proper
     /// streaming support.
     fn next_batch(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<Option<DataFrame>> {
@@ -48,6 +78,21 @@ pub trait AnonymousScan: Send + Sync {
     fn allows_slice_pushdown(&self) -> bool {
         false
     }
+    /// Specify if the scan provider can be a source of the streaming engine, which pulls the
+    /// batches of [`AnonymousScan::scan_batches`].
+    ///
+    /// Defaults to `false`
+    fn allows_streaming(&self) -> bool {
+        false
+    }
+    /// Produce the batches of a scan one at a time, for the streaming engine.
+    fn scan_batches(
+        &self,
+        scan_opts: AnonymousScanArgs,
+    ) -> PolarsResult<Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>> {
+        let df = self.scan(scan_opts)?;
+        Ok(Box::new(std::iter::once(Ok(df))))
+    }
 }
 
 impl Debug for dyn AnonymousScan {
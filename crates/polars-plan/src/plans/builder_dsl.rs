@@ -1,16 +1,28 @@
+#[cfg(any(feature = "parquet", feature = "ipc", feature = "csv"))]
+use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex, RwLock};
 
 use polars_core::prelude::*;
+#[cfg(feature = "avro")]
+use polars_io::avro::AvroScanOptions;
 #[cfg(any(feature = "parquet", feature = "ipc", feature = "csv"))]
 use polars_io::cloud::CloudOptions;
 #[cfg(feature = "csv")]
 use polars_io::csv::read::CsvReadOptions;
+#[cfg(feature = "fwf")]
+use polars_io::fwf::FwfReadOptions;
 #[cfg(feature = "ipc")]
 use polars_io::ipc::IpcScanOptions;
 #[cfg(feature = "parquet")]
 use polars_io::parquet::read::ParquetOptions;
 use polars_io::HiveOptions;
-#[cfg(any(feature = "parquet", feature = "csv", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "csv",
+    feature = "ipc",
+    feature = "avro",
+    feature = "fwf"
+))]
 use polars_io::RowIndex;
 
 use crate::constants::UNLIMITED_CACHE;
@@ -55,6 +67,8 @@ impl DslBuilder {
             },
             glob: false,
             include_file_paths: None,
+            n_rows_per_file: None,
+            max_concurrent_files: None,
         };
 
         Ok(DslPlan::Scan {
@@ -92,6 +106,9 @@ impl DslBuilder {
         hive_options: HiveOptions,
         glob: bool,
         include_file_paths: Option<PlSmallStr>,
+        decryption: Option<polars_io::parquet::encryption::ParquetDecryption>,
+        n_rows_per_file: Option<usize>,
+        max_concurrent_files: Option<NonZeroUsize>,
     ) -> PolarsResult<Self> {
         let options = FileScanOptions {
             with_columns: None,
@@ -103,6 +120,8 @@ impl DslBuilder {
             hive_options,
             glob,
             include_file_paths,
+            n_rows_per_file,
+            max_concurrent_files,
         };
         Ok(DslPlan::Scan {
             sources: Arc::new(Mutex::new(sources)),
@@ -115,6 +134,7 @@ impl DslBuilder {
                     parallel,
                     low_memory,
                     use_statistics,
+                    decryption,
                 },
                 cloud_options,
                 metadata: None,
@@ -135,6 +155,8 @@ impl DslBuilder {
         cloud_options: Option<CloudOptions>,
         hive_options: HiveOptions,
         include_file_paths: Option<PlSmallStr>,
+        n_rows_per_file: Option<usize>,
+        max_concurrent_files: Option<NonZeroUsize>,
     ) -> PolarsResult<Self> {
         Ok(DslPlan::Scan {
             sources: Arc::new(Mutex::new(sources)),
@@ -150,6 +172,8 @@ impl DslBuilder {
                 hive_options,
                 glob: true,
                 include_file_paths,
+                n_rows_per_file,
+                max_concurrent_files,
             },
             predicate: None,
             scan_type: FileScan::Ipc {
@@ -161,6 +185,78 @@ impl DslBuilder {
         .into())
     }
 
+    #[cfg(feature = "avro")]
+    pub fn scan_avro(
+        sources: DslScanSources,
+        options: AvroScanOptions,
+        n_rows: Option<usize>,
+        cache: bool,
+        row_index: Option<RowIndex>,
+        rechunk: bool,
+        include_file_paths: Option<PlSmallStr>,
+    ) -> PolarsResult<Self> {
+        Ok(DslPlan::Scan {
+            sources: Arc::new(Mutex::new(sources)),
+            file_info: Arc::new(RwLock::new(None)),
+            hive_parts: None,
+            file_options: FileScanOptions {
+                with_columns: None,
+                cache,
+                slice: n_rows.map(|x| (0, x)),
+                rechunk,
+                row_index,
+                file_counter: Default::default(),
+                hive_options: HiveOptions {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+                glob: true,
+                include_file_paths,
+                n_rows_per_file: None,
+                max_concurrent_files: None,
+            },
+            predicate: None,
+            scan_type: FileScan::Avro { options },
+        }
+        .into())
+    }
+
+    #[cfg(feature = "fwf")]
+    pub fn scan_fwf(
+        sources: DslScanSources,
+        options: FwfReadOptions,
+        n_rows: Option<usize>,
+        cache: bool,
+        row_index: Option<RowIndex>,
+        rechunk: bool,
+        include_file_paths: Option<PlSmallStr>,
+    ) -> PolarsResult<Self> {
+        Ok(DslPlan::Scan {
+            sources: Arc::new(Mutex::new(sources)),
+            file_info: Arc::new(RwLock::new(None)),
+            hive_parts: None,
+            file_options: FileScanOptions {
+                with_columns: None,
+                cache,
+                slice: n_rows.map(|x| (0, x)),
+                rechunk,
+                row_index,
+                file_counter: Default::default(),
+                hive_options: HiveOptions {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+                glob: true,
+                include_file_paths,
+                n_rows_per_file: None,
+                max_concurrent_files: None,
+            },
+            predicate: None,
+            scan_type: FileScan::Fwf { options },
+        }
+        .into())
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[cfg(feature = "csv")]
     pub fn scan_csv(
@@ -169,7 +265,10 @@ impl DslBuilder {
         cache: bool,
         cloud_options: Option<CloudOptions>,
         glob: bool,
+        hive_options: HiveOptions,
         include_file_paths: Option<PlSmallStr>,
+        n_rows_per_file: Option<usize>,
+        max_concurrent_files: Option<NonZeroUsize>,
     ) -> PolarsResult<Self> {
         // This gets partially moved by FileScanOptions
         let read_options_clone = read_options.clone();
@@ -181,13 +280,11 @@ impl DslBuilder {
             rechunk: read_options_clone.rechunk,
             row_index: read_options_clone.row_index,
             file_counter: Default::default(),
-            // TODO: Support Hive partitioning.
-            hive_options: HiveOptions {
-                enabled: Some(false),
-                ..Default::default()
-            },
+            hive_options,
             glob,
             include_file_paths,
+            n_rows_per_file,
+            max_concurrent_files,
         };
         Ok(DslPlan::Scan {
             sources: Arc::new(Mutex::new(sources)),
@@ -437,6 +534,7 @@ impl DslBuilder {
         self,
         function: F,
         optimizations: AllowedOptimizations,
+        properties: UdfProperties,
         schema: Option<Arc<dyn UdfSchema>>,
         name: PlSmallStr,
     ) -> Self
@@ -453,6 +551,7 @@ impl DslBuilder {
                 predicate_pd: optimizations.contains(OptFlags::PREDICATE_PUSHDOWN),
                 projection_pd: optimizations.contains(OptFlags::PROJECTION_PUSHDOWN),
                 streamable: optimizations.contains(OptFlags::STREAMING),
+                properties,
                 fmt_str: name,
             }),
         }
@@ -6,7 +6,7 @@ use expr_expansion::{is_regex_projection, rewrite_projections};
 use hive::{hive_partitions_from_paths, HivePartitions};
 #[cfg(any(feature = "ipc", feature = "parquet"))]
 use polars_io::cloud::CloudOptions;
-#[cfg(any(feature = "csv", feature = "json"))]
+#[cfg(any(feature = "csv", feature = "json", feature = "avro", feature = "fwf"))]
 use polars_io::path_utils::expand_paths;
 #[cfg(any(feature = "ipc", feature = "parquet"))]
 use polars_io::path_utils::{expand_paths_hive, expanded_from_single_directory};
@@ -136,13 +136,14 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                 match &mut scan_type {
                     #[cfg(feature = "parquet")]
                     FileScan::Parquet {
+                        options,
                         cloud_options,
                         metadata,
-                        ..
                     } => {
                         let (file_info, md) = scans::parquet_file_info(
                             &sources,
                             &file_options,
+                            options.decryption.as_ref(),
                             cloud_options.as_ref(),
                         )
                         .map_err(|e| e.context(failed_here!(parquet scan)))?;
@@ -183,6 +184,14 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                         cloud_options.as_ref(),
                     )
                     .map_err(|e| e.context(failed_here!(ndjson scan)))?,
+                    #[cfg(feature = "avro")]
+                    FileScan::Avro { .. } => scans::avro_file_info(&sources, &file_options)
+                        .map_err(|e| e.context(failed_here!(avro scan)))?,
+                    #[cfg(feature = "fwf")]
+                    FileScan::Fwf { options } => {
+                        scans::fwf_file_info(&sources, &file_options, options)
+                            .map_err(|e| e.context(failed_here!(fwf scan)))?
+                    },
                     // FileInfo should be set.
                     FileScan::Anonymous { .. } => unreachable!(),
                 }
@@ -227,6 +236,10 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                     FileScan::Csv { .. } => true,
                     #[cfg(feature = "json")]
                     FileScan::NDJson { .. } => true,
+                    #[cfg(feature = "avro")]
+                    FileScan::Avro { .. } => true,
+                    #[cfg(feature = "fwf")]
+                    FileScan::Fwf { .. } => true,
                     FileScan::Anonymous { .. } => false,
                 });
 
@@ -267,6 +280,25 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                 None
             };
 
+            if let Some(n_rows_per_file) =
+                file_options.n_rows_per_file.filter(|_| !sources.is_empty())
+            {
+                let predicate = predicate
+                    .map(|expr| to_expr_ir(expr, ctxt.expr_arena))
+                    .transpose()?;
+                let node = scan_per_file(
+                    sources,
+                    resolved_file_info,
+                    hive_parts,
+                    predicate,
+                    scan_type,
+                    file_options,
+                    n_rows_per_file,
+                    ctxt.lp_arena,
+                )?;
+                return Ok(node);
+            }
+
             if let Some(row_index) = &file_options.row_index {
                 let schema = Arc::make_mut(&mut resolved_file_info.schema);
                 *schema = schema
@@ -666,6 +698,15 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                     };
                     return run_conversion(lp, ctxt, "fill_nan");
                 },
+                DslFunction::FunctionIR(FunctionIR::Hints(hints)) => {
+                    if let Some(join) = hints.join {
+                        apply_join_hint(input, join, ctxt.lp_arena);
+                    }
+                    IR::MapFunction {
+                        input,
+                        function: FunctionIR::Hints(hints),
+                    }
+                },
                 DslFunction::Drop(DropFunction { to_drop, strict }) => {
                     let to_drop = expand_selectors(to_drop, &input_schema, &[])?;
                     let to_drop = to_drop.iter().map(|s| s.as_ref()).collect::<PlHashSet<_>>();
@@ -848,6 +889,10 @@ impl DslScanSources {
             FileScan::NDJson { cloud_options, .. } => {
                 expand_paths(paths, file_options.glob, cloud_options.as_ref())?
             },
+            #[cfg(feature = "avro")]
+            FileScan::Avro { .. } => expand_paths(paths, file_options.glob, None)?,
+            #[cfg(feature = "fwf")]
+            FileScan::Fwf { .. } => expand_paths(paths, file_options.glob, None)?,
             FileScan::Anonymous { .. } => unreachable!(), // Invariant: Anonymous scans are already expanded.
         };
 
@@ -884,6 +929,98 @@ fn expand_scan_paths_with_hive_update(
     Ok(expanded_paths)
 }
 
+/// Split a scan into a union of scans of the individual files, of which at most
+/// `n_rows_per_file` rows are read. The row limit and the row index of the scan are applied to
+/// the union.
+#[allow(clippy::too_many_arguments)]
+fn scan_per_file(
+    sources: ScanSources,
+    mut file_info: FileInfo,
+    hive_parts: Option<Arc<Vec<HivePartitions>>>,
+    predicate: Option<ExprIR>,
+    scan_type: FileScan,
+    mut file_options: FileScanOptions,
+    n_rows_per_file: usize,
+    lp_arena: &mut Arena<IR>,
+) -> PolarsResult<Node> {
+    let slice = file_options.slice.take();
+    let row_index = file_options.row_index.take();
+    // The schema of the file info can already hold the row index, which the scans of the
+    // files don't add.
+    if let Some(row_index) = &row_index {
+        if file_info.schema.contains(&row_index.name) {
+            Arc::make_mut(&mut file_info.schema).shift_remove(&row_index.name);
+        }
+    }
+    let max_parallel_inputs = file_options.max_concurrent_files.take();
+    let rechunk = std::mem::take(&mut file_options.rechunk);
+    file_options.n_rows_per_file = None;
+
+    let inputs = sources
+        .iter()
+        .enumerate()
+        .map(|(i, source)| {
+            #[allow(unused_mut)]
+            let mut scan_type = scan_type.clone();
+            // The metadata belongs to the first file.
+            #[cfg(feature = "parquet")]
+            if let FileScan::Parquet { metadata, .. } = &mut scan_type {
+                *metadata = metadata.take().filter(|_| i == 0);
+            }
+            #[cfg(feature = "ipc")]
+            if let FileScan::Ipc { metadata, .. } = &mut scan_type {
+                *metadata = metadata.take().filter(|_| i == 0);
+            }
+
+            let input = lp_arena.add(IR::Scan {
+                sources: source.into_sources()?,
+                file_info: file_info.clone(),
+                hive_parts: hive_parts
+                    .as_ref()
+                    .map(|hive_parts| Arc::new(vec![hive_parts[i].clone()])),
+                output_schema: None,
+                predicate: predicate.clone(),
+                scan_type,
+                file_options: file_options.clone(),
+            });
+            Ok(lp_arena.add(IR::Slice {
+                input,
+                offset: 0,
+                len: n_rows_per_file as IdxSize,
+            }))
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    let mut node = lp_arena.add(IR::Union {
+        inputs,
+        options: UnionOptions {
+            parallel: true,
+            from_partitioned_ds: true,
+            rechunk,
+            max_parallel_inputs,
+            ..Default::default()
+        },
+    });
+    if let Some((offset, len)) = slice {
+        node = lp_arena.add(IR::Slice {
+            input: node,
+            offset,
+            len: len as IdxSize,
+        });
+    }
+    if let Some(row_index) = row_index {
+        node = lp_arena.add(IR::MapFunction {
+            input: node,
+            function: FunctionIR::RowIndex {
+                name: row_index.name,
+                offset: Some(row_index.offset),
+                schema: Default::default(),
+            },
+        });
+    }
+    Ok(node)
+}
+
 fn expand_filter(
     predicate: Expr,
     input: Node,
@@ -1061,6 +1198,28 @@ where
         .collect()
 }
 
+/// Apply the join hint of [`QueryHints`] to the joins of the plan at `root` that have no hint of
+/// their own.
+fn apply_join_hint(root: Node, hint: JoinHint, lp_arena: &mut Arena<IR>) {
+    let joins = (&*lp_arena)
+        .iter(root)
+        .filter_map(|(node, lp)| matches!(lp, IR::Join { .. }).then_some(node))
+        .collect::<Vec<_>>();
+    for node in joins {
+        let IR::Join { options, .. } = lp_arena.get_mut(node) else {
+            unreachable!()
+        };
+        if options.broadcast.is_some() || options.partitioned {
+            continue;
+        }
+        let options = Arc::make_mut(options);
+        match hint {
+            JoinHint::Broadcast(side) => options.broadcast = Some(side),
+            JoinHint::Partitioned => options.partitioned = true,
+        }
+    }
+}
+
 pub(crate) fn maybe_init_projection_excluding_hive(
     reader_schema: &Either<ArrowSchemaRef, SchemaRef>,
     hive_parts: Option<&HivePartitions>,
@@ -7,7 +7,9 @@ mod ir_to_dsl;
     feature = "ipc",
     feature = "parquet",
     feature = "csv",
-    feature = "json"
+    feature = "json",
+    feature = "avro",
+    feature = "fwf"
 ))]
 mod scans;
 mod stack_opt;
@@ -7,7 +7,12 @@ use polars_io::RowIndex;
 
 use super::*;
 
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "avro",
+    feature = "fwf"
+))]
 fn prepare_output_schema(mut schema: Schema, row_index: Option<&RowIndex>) -> SchemaRef {
     if let Some(rc) = row_index {
         let _ = schema.insert_at_index(0, rc.name.clone(), IDX_DTYPE);
@@ -31,18 +36,34 @@ fn prepare_schemas(mut schema: Schema, row_index: Option<&RowIndex>) -> (SchemaR
 pub(super) fn parquet_file_info(
     sources: &ScanSources,
     file_options: &FileScanOptions,
+    decryption: Option<&polars_io::parquet::encryption::ParquetDecryption>,
     #[allow(unused)] cloud_options: Option<&polars_io::cloud::CloudOptions>,
 ) -> PolarsResult<(FileInfo, Option<FileMetadataRef>)> {
     use polars_core::error::feature_gated;
 
     let (reader_schema, num_rows, metadata) = {
-        if sources.is_cloud_url() {
+        if sources.is_http_range_url() {
+            feature_gated!("parquet_http", {
+                polars_ensure!(
+                    decryption.is_none(),
+                    InvalidOperation: "decryption is not supported when reading Parquet files over HTTP"
+                );
+                let uri = sources.first_path().unwrap().to_string_lossy();
+                let mut reader = ParquetHttpReader::new(&uri);
+                (
+                    reader.schema()?,
+                    Some(reader.num_rows()?),
+                    Some(reader.get_metadata()?.clone()),
+                )
+            })
+        } else if sources.is_cloud_url() {
             let first_path = &sources.as_paths().unwrap()[0];
             feature_gated!("cloud", {
                 let uri = first_path.to_string_lossy();
                 get_runtime().block_on(async {
-                    let mut reader =
-                        ParquetAsyncReader::from_uri(&uri, cloud_options, None).await?;
+                    let mut reader = ParquetAsyncReader::from_uri(&uri, cloud_options, None)
+                        .await?
+                        .with_decryption(decryption.cloned());
 
                     PolarsResult::Ok((
                         reader.schema().await?,
@@ -56,7 +77,9 @@ pub(super) fn parquet_file_info(
                 .first()
                 .ok_or_else(|| polars_err!(ComputeError: "expected at least 1 source"))?;
             let memslice = first_source.to_memslice()?;
-            let mut reader = ParquetReader::new(std::io::Cursor::new(memslice));
+            let mut reader = ParquetReader::new(std::io::Cursor::new(memslice))
+                .with_decryption(decryption.cloned())
+                .with_metadata_cache(first_source.as_path());
             (
                 reader.schema()?,
                 Some(reader.num_rows()?),
@@ -130,6 +153,63 @@ pub(super) fn ipc_file_info(
     Ok((file_info, metadata))
 }
 
+#[cfg(feature = "avro")]
+pub(super) fn avro_file_info(
+    sources: &ScanSources,
+    file_options: &FileScanOptions,
+) -> PolarsResult<FileInfo> {
+    use polars_io::avro::AvroReader;
+
+    if sources.is_cloud_url() {
+        polars_bail!(nyi = "scanning Avro files from cloud storage");
+    }
+    let Some(first) = sources.first() else {
+        polars_bail!(ComputeError: "expected at least 1 source");
+    };
+
+    let memslice = first.to_memslice()?;
+    let reader_schema = AvroReader::new(std::io::Cursor::new(memslice)).arrow_schema()?;
+
+    Ok(FileInfo::new(
+        prepare_output_schema(
+            Schema::from_arrow_schema(&reader_schema),
+            file_options.row_index.as_ref(),
+        ),
+        Some(Either::Left(Arc::new(reader_schema))),
+        (None, usize::MAX),
+    ))
+}
+
+#[cfg(feature = "fwf")]
+pub(super) fn fwf_file_info(
+    sources: &ScanSources,
+    file_options: &FileScanOptions,
+    fwf_options: &mut polars_io::fwf::FwfReadOptions,
+) -> PolarsResult<FileInfo> {
+    use polars_io::fwf::FwfReader;
+
+    if sources.is_cloud_url() {
+        polars_bail!(nyi = "scanning fixed-width files from cloud storage");
+    }
+    let Some(first) = sources.first() else {
+        polars_bail!(ComputeError: "expected at least 1 source");
+    };
+
+    // The layout and the schema are resolved from the first file once, so that the readers of
+    // all files read the same columns.
+    let memslice = first.to_memslice()?;
+    *fwf_options = FwfReader::new(std::io::Cursor::new(memslice))
+        .with_options(fwf_options.clone())
+        .resolved_options()?;
+    let schema = fwf_options.schema.clone().unwrap();
+
+    Ok(FileInfo::new(
+        prepare_output_schema(schema.as_ref().clone(), file_options.row_index.as_ref()),
+        Some(Either::Right(schema)),
+        (None, usize::MAX),
+    ))
+}
+
 #[cfg(feature = "csv")]
 pub(super) fn csv_file_info(
     sources: &ScanSources,
@@ -175,6 +255,22 @@ pub(super) fn csv_file_info(
 
     let infer_schema_func = |i| {
         let source = sources.at(i);
+
+        // Only fetch the head of remote files if we don't need to read all rows for inference.
+        #[cfg(feature = "cloud")]
+        if let (true, Some(infer_schema_length)) =
+            (sources.is_cloud_url(), csv_options.infer_schema_length)
+        {
+            if let Some(si_result) = infer_cloud_csv_schema_from_prefix(
+                source,
+                infer_schema_length,
+                csv_options,
+                cloud_options,
+            )? {
+                return Ok(si_result);
+            }
+        }
+
         let memslice = source.to_memslice_possibly_async(run_async, cache_entries.as_ref(), i)?;
         let owned = &mut vec![];
         let mut reader = std::io::Cursor::new(maybe_decompress_bytes(&memslice, owned)?);
@@ -254,6 +350,66 @@ pub(super) fn csv_file_info(
     ))
 }
 
+/// Infer the schema of a remote CSV file from the first rows, fetched with range requests.
+///
+/// Returns `None` if the file is compressed, in which case the whole file must be fetched.
+#[cfg(all(feature = "csv", feature = "cloud"))]
+fn infer_cloud_csv_schema_from_prefix(
+    source: ScanSourceRef,
+    infer_schema_length: usize,
+    csv_options: &CsvReadOptions,
+    cloud_options: Option<&polars_io::cloud::CloudOptions>,
+) -> PolarsResult<Option<polars_io::csv::read::schema_inference::SchemaInferenceResult>> {
+    use polars_io::csv::read::schema_inference::SchemaInferenceResult;
+    use polars_io::csv::read::{CsvBlockFetcher, InferSchemaSampling};
+    use polars_io::mmap::ReaderBytes;
+    use polars_io::utils::is_compressed;
+
+    let ScanSourceRef::Path(path) = source else {
+        return Ok(None);
+    };
+    let parse_options = csv_options.get_parse_options();
+    // The line endings of other encodings cannot be found in the raw bytes.
+    if !parse_options.encoding.is_ascii_compatible() {
+        return Ok(None);
+    }
+    // Uniform sampling needs all lines of the file.
+    if csv_options.infer_schema_policy.sampling == InferSchemaSampling::Uniform {
+        return Ok(None);
+    }
+    let n_lines = csv_options.skip_rows
+        + csv_options.has_header as usize
+        + csv_options.skip_rows_after_header
+        + infer_schema_length
+        + 1;
+
+    let (prefix, file_size) = get_runtime().block_on_potential_spawn(async {
+        let mut fetcher = CsvBlockFetcher::try_new_from_path(
+            path.to_str().unwrap(),
+            cloud_options,
+            parse_options.quote_char,
+            parse_options.eol_char,
+        )
+        .await?
+        .with_prefetch_size(1);
+        let prefix = fetcher.fetch_prefix(n_lines).await?;
+        PolarsResult::Ok((prefix, fetcher.file_size()))
+    })?;
+
+    if is_compressed(&prefix) {
+        return Ok(None);
+    }
+    if prefix.len() < 2 && csv_options.raise_if_empty {
+        polars_bail!(NoData: "empty CSV")
+    }
+
+    let reader_bytes = ReaderBytes::Borrowed(&prefix);
+    let si_result =
+        SchemaInferenceResult::try_from_reader_bytes_and_options(&reader_bytes, csv_options)?
+            .with_bytes_total(file_size);
+    Ok(Some(si_result))
+}
+
 #[cfg(feature = "json")]
 pub(super) fn ndjson_file_info(
     sources: &ScanSources,
@@ -1,7 +1,11 @@
 use std::hash::{Hash, Hasher};
 
+#[cfg(feature = "avro")]
+use polars_io::avro::AvroScanOptions;
 #[cfg(feature = "csv")]
 use polars_io::csv::read::CsvReadOptions;
+#[cfg(feature = "fwf")]
+use polars_io::fwf::FwfReadOptions;
 #[cfg(feature = "ipc")]
 use polars_io::ipc::IpcScanOptions;
 #[cfg(feature = "parquet")]
@@ -38,6 +42,10 @@ pub enum FileScan {
         options: NDJsonReadOptions,
         cloud_options: Option<polars_io::cloud::CloudOptions>,
     },
+    #[cfg(feature = "avro")]
+    Avro { options: AvroScanOptions },
+    #[cfg(feature = "fwf")]
+    Fwf { options: FwfReadOptions },
     #[cfg_attr(feature = "serde", serde(skip))]
     Anonymous {
         options: Arc<AnonymousScanOptions>,
@@ -96,6 +104,10 @@ impl PartialEq for FileScan {
                     cloud_options: c_r,
                 },
             ) => l == r && c_l == c_r,
+            #[cfg(feature = "avro")]
+            (FileScan::Avro { options: l }, FileScan::Avro { options: r }) => l == r,
+            #[cfg(feature = "fwf")]
+            (FileScan::Fwf { options: l }, FileScan::Fwf { options: r }) => l == r,
             _ => false,
         }
     }
@@ -141,6 +153,10 @@ impl Hash for FileScan {
                 options.hash(state);
                 cloud_options.hash(state)
             },
+            #[cfg(feature = "avro")]
+            FileScan::Avro { options } => options.hash(state),
+            #[cfg(feature = "fwf")]
+            FileScan::Fwf { options } => options.hash(state),
             FileScan::Anonymous { options, .. } => options.hash(state),
         }
     }
@@ -169,6 +185,11 @@ impl FileScan {
             Self::Ipc { .. } => _file_options.row_index.is_some(),
             #[cfg(feature = "parquet")]
             Self::Parquet { .. } => _file_options.row_index.is_some(),
+            #[cfg(feature = "avro")]
+            Self::Avro { .. } => true,
+            #[cfg(feature = "fwf")]
+            Self::Fwf { .. } => true,
+            Self::Anonymous { function, .. } => function.allows_streaming(),
             #[allow(unreachable_patterns)]
             _ => false,
         }
@@ -179,11 +200,15 @@ impl FileScan {
             #[cfg(feature = "csv")]
             Self::Csv { .. } => true,
             #[cfg(feature = "ipc")]
-            Self::Ipc { .. } => false,
+            Self::Ipc { .. } => true,
             #[cfg(feature = "parquet")]
             Self::Parquet { .. } => true,
             #[cfg(feature = "json")]
             Self::NDJson { .. } => false,
+            #[cfg(feature = "avro")]
+            Self::Avro { .. } => true,
+            #[cfg(feature = "fwf")]
+            Self::Fwf { .. } => true,
             #[allow(unreachable_patterns)]
             _ => false,
         }
@@ -0,0 +1,151 @@
+//! Fingerprints of optimized plans, which tell whether a query reads the same data as before.
+use std::hash::{Hash, Hasher};
+use std::time::UNIX_EPOCH;
+
+use polars_core::prelude::*;
+use polars_io::is_cloud_url;
+
+use crate::prelude::visitor::IRNode;
+use crate::prelude::*;
+
+/// The fingerprint of a plan: a digest of everything that is hashed of the plan.
+pub type PlanFingerprint = [u8; 32];
+
+/// Feeds everything that is hashed into a cryptographic digest, so that different plans don't
+/// get the same fingerprint.
+struct DigestHasher(blake3::Hasher);
+
+impl Hasher for DigestHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.finalize();
+        u64::from_le_bytes(digest.as_bytes()[..8].try_into().unwrap())
+    }
+}
+
+// The seeds are fixed, so that the fingerprints of a polars build are stable across processes.
+fn random_state() -> PlRandomState {
+    PlRandomState::with_seeds(
+        0x243f_6a88_85a3_08d3,
+        0x1319_8a2e_0370_7344,
+        0xa409_3822_299f_31d0,
+        0x082e_fa98_ec4e_6c89,
+    )
+}
+
+/// Hash the sizes and the modification times of the files of `sources`, the local equivalent of
+/// their etags. Returns `false` if they have none.
+fn hash_sources<H: Hasher>(sources: &ScanSources, state: &mut H) -> bool {
+    let ScanSources::Paths(paths) = sources else {
+        return false;
+    };
+    for path in paths.iter() {
+        if is_cloud_url(path) {
+            return false;
+        }
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return false;
+        };
+        let Ok(modified) = metadata.modified() else {
+            return false;
+        };
+        metadata.len().hash(state);
+        modified
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos())
+            .hash(state);
+    }
+    true
+}
+
+/// Hash what the hashes of the expressions leave out. Returns `false` if the expressions call user
+/// defined functions.
+fn hash_exprs<H: Hasher>(exprs: &[ExprIR], expr_arena: &Arena<AExpr>, state: &mut H) -> bool {
+    for e in exprs {
+        for (_, ae) in expr_arena.iter(e.node()) {
+            match ae {
+                AExpr::AnonymousFunction { .. } => return false,
+                // the hash of a literal only samples the values of a series
+                AExpr::Literal(LiteralValue::Series(s)) => {
+                    let mut hashes = vec![];
+                    if s.vec_hash(random_state(), &mut hashes).is_err() {
+                        return false;
+                    }
+                    hashes.hash(state);
+                },
+                _ => {},
+            }
+        }
+    }
+    true
+}
+
+/// The fingerprint of the plan at `root` and of the files that it scans.
+///
+/// Returns `None` if the result of the plan may change while its fingerprint doesn't: if it
+/// reads in-memory data, files in the cloud or anonymous scans, or if it calls user defined
+/// functions.
+pub fn plan_fingerprint(
+    root: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<PlanFingerprint> {
+    let mut state = DigestHasher(blake3::Hasher::new());
+    env!("CARGO_PKG_VERSION").hash(&mut state);
+
+    let mut inputs = vec![];
+    let mut exprs = vec![];
+    for (node, lp) in lp_arena.iter(root) {
+        match lp {
+            IR::DataFrameScan { .. } => return None,
+            #[cfg(feature = "python")]
+            IR::PythonScan { .. } => return None,
+            IR::Scan {
+                sources, scan_type, ..
+            } => {
+                if matches!(scan_type, FileScan::Anonymous { .. })
+                    || !hash_sources(sources, &mut state)
+                {
+                    return None;
+                }
+            },
+            IR::MapFunction { function, .. } => match function {
+                FunctionIR::Opaque { .. } | FunctionIR::Pipeline { .. } => return None,
+                #[cfg(feature = "python")]
+                FunctionIR::OpaquePython(_) => return None,
+                FunctionIR::FastCount { sources, .. } => {
+                    if !hash_sources(sources, &mut state) {
+                        return None;
+                    }
+                },
+                _ => {},
+            },
+            IR::GroupBy { apply: Some(_), .. } => return None,
+            IR::Sink { payload, .. } if !matches!(payload, SinkType::Memory) => return None,
+            _ => {},
+        }
+
+        // The ids of caches are arbitrary, only the plans that they cache matter.
+        if matches!(lp, IR::Cache { .. }) {
+            std::mem::discriminant(lp).hash(&mut state);
+        } else {
+            IRNode::new(node)
+                .hashable_and_cmp(lp_arena, expr_arena)
+                .hash(&mut state);
+        }
+
+        exprs.clear();
+        lp.copy_exprs(&mut exprs);
+        if !hash_exprs(&exprs, expr_arena, &mut state) {
+            return None;
+        }
+        // the number of inputs tells apart plans of which the nodes are visited in the same order
+        inputs.clear();
+        lp.copy_inputs(&mut inputs);
+        inputs.len().hash(&mut state);
+    }
+    Some(*state.0.finalize().as_bytes())
+}
@@ -144,7 +144,9 @@ impl fmt::Debug for Expr {
             Function {
                 input, function, ..
             } => {
-                if input.len() >= 2 {
+                if input.is_empty() {
+                    write!(f, "{function}")
+                } else if input.len() >= 2 {
                     write!(f, "{:?}.{function}({:?})", input[0], &input[1..])
                 } else {
                     write!(f, "{:?}.{function}()", input[0])
@@ -13,6 +13,8 @@ use polars_io::cloud::CloudOptions;
 use polars_io::csv::read::{
     count_rows as count_rows_csv, count_rows_from_slice as count_rows_csv_from_slice,
 };
+#[cfg(feature = "parquet")]
+use polars_io::parquet::encryption::ParquetDecryption;
 #[cfg(all(feature = "parquet", feature = "cloud"))]
 use polars_io::parquet::read::ParquetAsyncReader;
 #[cfg(feature = "parquet")]
@@ -34,7 +36,9 @@ pub fn count_rows(
         feature = "parquet",
         feature = "ipc",
         feature = "json",
-        feature = "csv"
+        feature = "csv",
+        feature = "avro",
+        feature = "fwf"
     )))]
     {
         unreachable!()
@@ -44,7 +48,9 @@ pub fn count_rows(
         feature = "parquet",
         feature = "ipc",
         feature = "json",
-        feature = "csv"
+        feature = "csv",
+        feature = "avro",
+        feature = "fwf"
     ))]
     {
         let count: PolarsResult<usize> = match scan_type {
@@ -54,9 +60,11 @@ pub fn count_rows(
                 cloud_options,
             } => count_all_rows_csv(sources, options),
             #[cfg(feature = "parquet")]
-            FileScan::Parquet { cloud_options, .. } => {
-                count_rows_parquet(sources, cloud_options.as_ref())
-            },
+            FileScan::Parquet {
+                options,
+                cloud_options,
+                ..
+            } => count_rows_parquet(sources, options.decryption.as_ref(), cloud_options.as_ref()),
             #[cfg(feature = "ipc")]
             FileScan::Ipc {
                 options,
@@ -73,6 +81,10 @@ pub fn count_rows(
                 options,
                 cloud_options,
             } => count_rows_ndjson(sources, cloud_options.as_ref()),
+            #[cfg(feature = "avro")]
+            FileScan::Avro { .. } => count_rows_avro(sources),
+            #[cfg(feature = "fwf")]
+            FileScan::Fwf { options } => count_rows_fwf(sources, options),
             FileScan::Anonymous { .. } => {
                 unreachable!()
             },
@@ -102,6 +114,7 @@ fn count_all_rows_csv(
                 parse_options.quote_char,
                 parse_options.comment_prefix.as_ref(),
                 parse_options.eol_char,
+                parse_options.encoding,
                 options.has_header,
             ),
             _ => {
@@ -113,6 +126,7 @@ fn count_all_rows_csv(
                     parse_options.quote_char,
                     parse_options.comment_prefix.as_ref(),
                     parse_options.eol_char,
+                    parse_options.encoding,
                     options.has_header,
                 )
             },
@@ -123,6 +137,7 @@ fn count_all_rows_csv(
 #[cfg(feature = "parquet")]
 pub(super) fn count_rows_parquet(
     sources: &ScanSources,
+    decryption: Option<&ParquetDecryption>,
     #[allow(unused)] cloud_options: Option<&CloudOptions>,
 ) -> PolarsResult<usize> {
     if sources.is_empty() {
@@ -134,6 +149,7 @@ pub(super) fn count_rows_parquet(
         feature_gated!("cloud", {
             get_runtime().block_on(count_rows_cloud_parquet(
                 sources.as_paths().unwrap(),
+                decryption,
                 cloud_options,
             ))
         })
@@ -141,7 +157,10 @@ pub(super) fn count_rows_parquet(
         sources
             .iter()
             .map(|source| {
-                ParquetReader::new(std::io::Cursor::new(source.to_memslice()?)).num_rows()
+                ParquetReader::new(std::io::Cursor::new(source.to_memslice()?))
+                    .with_decryption(decryption.cloned())
+                    .with_metadata_cache(source.as_path())
+                    .num_rows()
             })
             .sum::<PolarsResult<usize>>()
     }
@@ -150,12 +169,15 @@ pub(super) fn count_rows_parquet(
 #[cfg(all(feature = "parquet", feature = "async"))]
 async fn count_rows_cloud_parquet(
     paths: &[std::path::PathBuf],
+    decryption: Option<&ParquetDecryption>,
     cloud_options: Option<&CloudOptions>,
 ) -> PolarsResult<usize> {
     let collection = paths.iter().map(|path| {
         with_concurrency_budget(1, || async {
             let mut reader =
-                ParquetAsyncReader::from_uri(&path.to_string_lossy(), cloud_options, None).await?;
+                ParquetAsyncReader::from_uri(&path.to_string_lossy(), cloud_options, None)
+                    .await?
+                    .with_decryption(decryption.cloned());
             reader.num_rows().await
         })
     });
@@ -213,6 +235,31 @@ async fn count_rows_cloud_ipc(
         .map(|rows| rows.iter().map(|v| *v as usize).sum())
 }
 
+#[cfg(feature = "avro")]
+pub(super) fn count_rows_avro(sources: &ScanSources) -> PolarsResult<usize> {
+    sources
+        .iter()
+        .map(|source| {
+            let memslice = source.to_memslice()?;
+            polars_io::avro::count_rows(std::io::Cursor::new(memslice))
+        })
+        .sum::<PolarsResult<usize>>()
+}
+
+#[cfg(feature = "fwf")]
+pub(super) fn count_rows_fwf(
+    sources: &ScanSources,
+    options: &polars_io::fwf::FwfReadOptions,
+) -> PolarsResult<usize> {
+    sources
+        .iter()
+        .map(|source| {
+            let memslice = source.to_memslice()?;
+            polars_io::fwf::count_rows(std::io::Cursor::new(memslice), options)
+        })
+        .sum::<PolarsResult<usize>>()
+}
+
 #[cfg(feature = "json")]
 pub(super) fn count_rows_ndjson(
     sources: &ScanSources,
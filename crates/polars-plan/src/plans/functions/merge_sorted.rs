@@ -1,4 +1,5 @@
 use polars_core::prelude::*;
+use polars_core::series::IsSorted;
 use polars_ops::prelude::*;
 
 pub(super) fn merge_sorted(df: &DataFrame, column: &str) -> PolarsResult<DataFrame> {
@@ -34,5 +35,10 @@ pub(super) fn merge_sorted(df: &DataFrame, column: &str) -> PolarsResult<DataFra
 
     let lhs = left.column(column)?;
     let rhs = right.column(column)?;
-    _merge_sorted_dfs(&left, &right, lhs, rhs, true)
+    let mut out = _merge_sorted_dfs(&left, &right, lhs, rhs, true)?;
+    // the merge of sorted keys is sorted, which allows sorted fast paths downstream
+    let idx = out.try_get_column_index(column)?;
+    // SAFETY: setting the sorted flag doesn't change the length or the name.
+    unsafe { out.get_columns_mut()[idx].set_sorted_flag(IsSorted::Ascending) };
+    Ok(out)
 }
@@ -12,6 +12,7 @@ use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::{Arc, Mutex};
 
+use bitflags::bitflags;
 pub use dsl::*;
 use polars_core::error::feature_gated;
 use polars_core::prelude::*;
@@ -27,6 +28,34 @@ use crate::plans::functions::merge_sorted::merge_sorted;
 use crate::plans::ir::ScanSourcesDisplay;
 use crate::prelude::*;
 
+bitflags! {
+    /// What a user defined function on a `DataFrame` declares about itself, so that the
+    /// optimizer can move filters and slices below it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct UdfProperties: u8 {
+        /// Every output row is computed from a single input row only, the function neither
+        /// adds nor removes rows and it doesn't change the values of the input columns that it
+        /// outputs, e.g. it only adds columns.
+        const ELEMENTWISE = 1;
+        /// The function keeps the order of the rows.
+        const MAINTAINS_ORDER = 1 << 1;
+        /// The function is deterministic and has no side effects, so it may run on fewer rows.
+        const PURE = 1 << 2;
+    }
+}
+
+impl UdfProperties {
+    /// Predicates on the columns of the input can be applied before the function.
+    pub fn allows_predicate_pushdown(self) -> bool {
+        self.contains(Self::ELEMENTWISE | Self::PURE)
+    }
+
+    /// Slices can be applied before the function.
+    pub fn allows_slice_pushdown(self) -> bool {
+        self.contains(Self::ELEMENTWISE | Self::MAINTAINS_ORDER | Self::PURE)
+    }
+}
+
 #[cfg_attr(feature = "ir_serde", derive(Serialize, Deserialize))]
 #[derive(Clone, IntoStaticStr)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
@@ -42,6 +71,7 @@ pub enum FunctionIR {
         ///  allow projection pushdown optimizations
         projection_pd: bool,
         streamable: bool,
+        properties: UdfProperties,
         // used for formatting
         fmt_str: PlSmallStr,
     },
@@ -56,11 +86,17 @@ pub enum FunctionIR {
         function: Arc<Mutex<dyn DataFrameUdfMut>>,
         schema: SchemaRef,
         original: Option<Arc<IRPlan>>,
+        // the nodes of every pipeline in `original`, used for formatting
+        layout: Arc<[PipelineLayout]>,
+        // the metrics of the operators of the last run, used for explain analyze
+        metrics: Arc<Mutex<Option<DataFrame>>>,
     },
     Unnest {
         columns: Arc<[PlSmallStr]>,
     },
     Rechunk,
+    /// Hints for the nodes of the input, see [`QueryHints`]. The output is the input.
+    Hints(QueryHints),
     // The two DataFrames are temporary concatenated
     // this indicates until which chunk the data is from the left df
     // this trick allows us to reuse the `Union` architecture to get map over
@@ -105,6 +141,7 @@ impl PartialEq for FunctionIR {
         use FunctionIR::*;
         match (self, other) {
             (Rechunk, Rechunk) => true,
+            (Hints(l), Hints(r)) => l == r,
             (
                 FastCount {
                     sources: srcs_l, ..
@@ -155,6 +192,7 @@ impl Hash for FunctionIR {
             FunctionIR::Pipeline { .. } => {},
             FunctionIR::Unnest { columns } => columns.hash(state),
             FunctionIR::Rechunk => {},
+            FunctionIR::Hints(hints) => hints.hash(state),
             #[cfg(feature = "merge_sorted")]
             FunctionIR::MergeSorted { column } => column.hash(state),
             FunctionIR::Rename {
@@ -187,6 +225,7 @@ impl FunctionIR {
         use FunctionIR::*;
         match self {
             Rechunk | Pipeline { .. } => false,
+            Hints(hints) => hints.streaming != Some(false),
             #[cfg(feature = "merge_sorted")]
             MergeSorted { .. } => false,
             FastCount { .. } | Unnest { .. } | Rename { .. } | Explode { .. } => true,
@@ -215,12 +254,16 @@ impl FunctionIR {
     pub(crate) fn allow_predicate_pd(&self) -> bool {
         use FunctionIR::*;
         match self {
-            Opaque { predicate_pd, .. } => *predicate_pd,
+            Opaque {
+                predicate_pd,
+                properties,
+                ..
+            } => *predicate_pd || properties.allows_predicate_pushdown(),
             #[cfg(feature = "python")]
             OpaquePython(OpaquePythonUdf { predicate_pd, .. }) => *predicate_pd,
             #[cfg(feature = "pivot")]
             Unpivot { .. } => true,
-            Rechunk | Unnest { .. } | Rename { .. } | Explode { .. } => true,
+            Rechunk | Hints(_) | Unnest { .. } | Rename { .. } | Explode { .. } => true,
             #[cfg(feature = "merge_sorted")]
             MergeSorted { .. } => true,
             RowIndex { .. } | FastCount { .. } => false,
@@ -228,13 +271,29 @@ impl FunctionIR {
         }
     }
 
+    pub(crate) fn allow_slice_pd(&self) -> bool {
+        match self {
+            Self::Opaque {
+                predicate_pd,
+                properties,
+                ..
+            } => *predicate_pd || properties.allows_slice_pushdown(),
+            _ => self.allow_predicate_pd(),
+        }
+    }
+
     pub(crate) fn allow_projection_pd(&self) -> bool {
         use FunctionIR::*;
         match self {
             Opaque { projection_pd, .. } => *projection_pd,
             #[cfg(feature = "python")]
             OpaquePython(OpaquePythonUdf { projection_pd, .. }) => *projection_pd,
-            Rechunk | FastCount { .. } | Unnest { .. } | Rename { .. } | Explode { .. } => true,
+            Rechunk
+            | Hints(_)
+            | FastCount { .. }
+            | Unnest { .. }
+            | Rename { .. }
+            | Explode { .. } => true,
             #[cfg(feature = "pivot")]
             Unpivot { .. } => true,
             #[cfg(feature = "merge_sorted")]
@@ -275,6 +334,7 @@ impl FunctionIR {
                 df.as_single_chunk_par();
                 Ok(df)
             },
+            Hints(_) => Ok(df),
             #[cfg(feature = "merge_sorted")]
             MergeSorted { column } => merge_sorted(&df, column.as_ref()),
             Unnest { columns: _columns } => {
@@ -310,6 +370,8 @@ impl FunctionIR {
             function: _,
             schema: _,
             original,
+            layout: _,
+            metrics: _,
         } = self
         else {
             return None;
@@ -317,6 +379,47 @@ impl FunctionIR {
 
         Some(original.as_ref()?.as_ref().as_ref())
     }
+
+    /// The layout of the pipelines of the plan of [`to_streaming_lp`](Self::to_streaming_lp).
+    pub fn streaming_layout(&self) -> &[PipelineLayout] {
+        match self {
+            Self::Pipeline { layout, .. } => layout,
+            _ => &[],
+        }
+    }
+
+    /// The metrics of the operators of the last run of this streaming pipeline, in the format
+    /// of `LazyFrame::collect_with_streaming_metrics`.
+    pub fn streaming_metrics(&self) -> Option<DataFrame> {
+        match self {
+            Self::Pipeline { metrics, .. } => metrics.lock().unwrap().clone(),
+            _ => None,
+        }
+    }
+}
+
+/// The nodes of the original plan that make up a pipeline of the streaming engine.
+#[derive(Clone, Debug, Default)]
+pub struct PipelineLayout {
+    pub sources: Vec<Node>,
+    /// The operators, from the sources to the sinks.
+    pub operators: Vec<Node>,
+    pub sinks: Vec<Node>,
+}
+
+impl PipelineLayout {
+    /// The role of `node` in this pipeline.
+    pub fn role(&self, node: Node) -> Option<&'static str> {
+        if self.sources.contains(&node) {
+            Some("source")
+        } else if self.operators.contains(&node) {
+            Some("operator")
+        } else if self.sinks.contains(&node) {
+            Some("sink")
+        } else {
+            None
+        }
+    }
 }
 
 impl Debug for FunctionIR {
@@ -362,6 +465,25 @@ impl Display for FunctionIR {
                     ScanSourcesDisplay(sources)
                 )
             },
+            Hints(hints) => {
+                let mut set = vec![];
+                if let Some(join) = hints.join {
+                    set.push(format!("join: {join:?}"));
+                }
+                if let Some(cse) = hints.cse {
+                    set.push(format!("cse: {cse}"));
+                }
+                if let Some(streaming) = hints.streaming {
+                    set.push(format!("streaming: {streaming}"));
+                }
+                if let Some(max_threads) = hints.max_threads {
+                    set.push(format!("max threads: {max_threads}"));
+                }
+                if let Some(memory_limit) = hints.memory_limit {
+                    set.push(format!("memory limit: {memory_limit}"));
+                }
+                write!(f, "HINTS: {}", set.join(", "))
+            },
             v => {
                 let s: &str = v.into();
                 write!(f, "{s}")
@@ -11,15 +11,29 @@ use crate::prelude::*;
 
 pub struct IRDotDisplay<'a> {
     is_streaming: bool,
+    format: DiagramFormat,
     lp: IRPlanRef<'a>,
+    // the pipelines of the streaming plan `lp`
+    layout: &'a [PipelineLayout],
+}
+
+/// The language of a diagram of a plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagramFormat {
+    /// The DOT language of Graphviz.
+    Dot,
+    Mermaid,
 }
 
 const INDENT: &str = "  ";
 
+/// The id of a node of the diagram. Plain nodes are identified by their node in the arena, the
+/// same id as in [`IRDisplay::with_node_ids`].
 #[derive(Clone, Copy)]
 enum DotNode {
     Plain(usize),
     Cache(usize),
+    Streaming(usize),
 }
 
 impl fmt::Display for DotNode {
@@ -27,50 +41,48 @@ impl fmt::Display for DotNode {
         match self {
             DotNode::Plain(n) => write!(f, "p{n}"),
             DotNode::Cache(n) => write!(f, "c{n}"),
+            DotNode::Streaming(n) => write!(f, "s{n}"),
         }
     }
 }
 
-#[inline(always)]
-fn write_label<'a, 'b>(
-    f: &'a mut fmt::Formatter<'b>,
-    id: DotNode,
-    mut w: impl FnMut(&mut EscapeLabel<'a>) -> fmt::Result,
-) -> fmt::Result {
-    write!(f, "{INDENT}{id}[label=\"")?;
-
-    let mut escaped = EscapeLabel(f);
-    w(&mut escaped)?;
-    let EscapeLabel(f) = escaped;
-
-    writeln!(f, "\"]")?;
-
-    Ok(())
-}
-
 impl<'a> IRDotDisplay<'a> {
     pub fn new(lp: IRPlanRef<'a>) -> Self {
-        if let Some(streaming_lp) = lp.extract_streaming_plan() {
-            return Self::new_streaming(streaming_lp);
+        if let IR::MapFunction { function, .. } = lp.root() {
+            if let Some(streaming_lp) = function.to_streaming_lp() {
+                return Self::new_streaming(streaming_lp, function.streaming_layout());
+            }
         }
 
         Self {
             is_streaming: false,
+            format: DiagramFormat::Dot,
             lp,
+            layout: &[],
         }
     }
 
-    fn new_streaming(lp: IRPlanRef<'a>) -> Self {
+    fn new_streaming(lp: IRPlanRef<'a>, layout: &'a [PipelineLayout]) -> Self {
         Self {
             is_streaming: true,
+            format: DiagramFormat::Dot,
             lp,
+            layout,
         }
     }
 
+    /// Write the diagram in the language `format`.
+    pub fn with_format(mut self, format: DiagramFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     fn with_root(&self, root: Node) -> Self {
         Self {
             is_streaming: false,
+            format: self.format,
             lp: self.lp.with_root(root),
+            layout: self.layout,
         }
     }
 
@@ -85,25 +97,65 @@ impl<'a> IRDotDisplay<'a> {
         }
     }
 
-    fn _format(
+    fn write_edge(&self, f: &mut fmt::Formatter<'_>, from: DotNode, to: DotNode) -> fmt::Result {
+        match self.format {
+            DiagramFormat::Dot => writeln!(f, "{INDENT}{from} -- {to}"),
+            DiagramFormat::Mermaid => writeln!(f, "{INDENT}{from} --- {to}"),
+        }
+    }
+
+    #[inline(always)]
+    fn write_label(
         &self,
         f: &mut fmt::Formatter<'_>,
-        parent: Option<DotNode>,
-        last: &mut usize,
-    ) -> std::fmt::Result {
-        use fmt::Write;
+        id: DotNode,
+        mut w: impl FnMut(&mut dyn fmt::Write) -> fmt::Result,
+    ) -> fmt::Result {
+        let (open, close) = match self.format {
+            DiagramFormat::Dot => ("[label=\"", "\"]"),
+            DiagramFormat::Mermaid => ("[\"", "\"]"),
+        };
+        write!(f, "{INDENT}{id}{open}")?;
 
+        let mut escaped: Box<dyn fmt::Write + '_> = match self.format {
+            DiagramFormat::Dot => Box::new(EscapeLabel(&mut *f)),
+            DiagramFormat::Mermaid => Box::new(EscapeMermaidLabel(&mut *f)),
+        };
+        w(escaped.as_mut())?;
+
+        // the role of the node in the pipelines of the streaming engine
+        if let DotNode::Plain(n) = id {
+            for (i, pipeline) in self.layout.iter().enumerate() {
+                if let Some(role) = pipeline.role(Node(n)) {
+                    write!(escaped, "\npipeline {i}: {role}")?;
+                }
+            }
+        }
+        drop(escaped);
+
+        writeln!(f, "{close}")
+    }
+
+    fn _format(&self, f: &mut fmt::Formatter<'_>, parent: Option<DotNode>) -> std::fmt::Result {
         let root = self.lp.root();
 
+        // the pipelines of the streaming engine are drawn in place of the node that runs them
+        if let IR::MapFunction { function, .. } = root {
+            if let Some(streaming_lp) = function.to_streaming_lp() {
+                return Self::new_streaming(streaming_lp, function.streaming_layout())
+                    .with_format(self.format)
+                    ._format(f, parent);
+            }
+        }
+
         let mut parent = parent;
         if self.is_streaming {
-            *last += 1;
-            let streaming_node = DotNode::Plain(*last);
+            let streaming_node = DotNode::Streaming(self.lp.lp_top.0);
 
             if let Some(parent) = parent {
-                writeln!(f, "{INDENT}{parent} -- {streaming_node}")?;
-                write_label(f, streaming_node, |f| f.write_str("STREAMING"))?;
+                self.write_edge(f, parent, streaming_node)?;
             }
+            self.write_label(f, streaming_node, |f| f.write_str("STREAMING"))?;
 
             parent = Some(streaming_node);
         }
@@ -112,46 +164,45 @@ impl<'a> IRDotDisplay<'a> {
         let id = if let IR::Cache { id, .. } = root {
             DotNode::Cache(*id)
         } else {
-            *last += 1;
-            DotNode::Plain(*last)
+            DotNode::Plain(self.lp.lp_top.0)
         };
 
         if let Some(parent) = parent {
-            writeln!(f, "{INDENT}{parent} -- {id}")?;
+            self.write_edge(f, parent, id)?;
         }
 
         use IR::*;
         match root {
             Union { inputs, .. } => {
                 for input in inputs {
-                    self.with_root(*input)._format(f, Some(id), last)?;
+                    self.with_root(*input)._format(f, Some(id))?;
                 }
 
-                write_label(f, id, |f| f.write_str("UNION"))?;
+                self.write_label(f, id, |f| f.write_str("UNION"))?;
             },
             HConcat { inputs, .. } => {
                 for input in inputs {
-                    self.with_root(*input)._format(f, Some(id), last)?;
+                    self.with_root(*input)._format(f, Some(id))?;
                 }
 
-                write_label(f, id, |f| f.write_str("HCONCAT"))?;
+                self.write_label(f, id, |f| f.write_str("HCONCAT"))?;
             },
             Cache {
                 input, cache_hits, ..
             } => {
-                self.with_root(*input)._format(f, Some(id), last)?;
+                self.with_root(*input)._format(f, Some(id))?;
 
                 if *cache_hits == UNLIMITED_CACHE {
-                    write_label(f, id, |f| f.write_str("CACHE"))?;
+                    self.write_label(f, id, |f| f.write_str("CACHE"))?;
                 } else {
-                    write_label(f, id, |f| write!(f, "CACHE: {cache_hits} times"))?;
+                    self.write_label(f, id, |f| write!(f, "CACHE: {cache_hits} times"))?;
                 };
             },
             Filter { predicate, input } => {
-                self.with_root(*input)._format(f, Some(id), last)?;
+                self.with_root(*input)._format(f, Some(id))?;
 
                 let pred = self.display_expr(predicate);
-                write_label(f, id, |f| write!(f, "FILTER BY {pred}"))?;
+                self.write_label(f, id, |f| write!(f, "FILTER BY {pred}"))?;
             },
             #[cfg(feature = "python")]
             PythonScan { options } => {
@@ -163,7 +214,7 @@ impl<'a> IRDotDisplay<'a> {
                 let with_columns = NumColumns(options.with_columns.as_ref().map(|s| s.as_ref()));
                 let total_columns = options.schema.len();
 
-                write_label(f, id, |f| {
+                self.write_label(f, id, |f| {
                     write!(
                         f,
                         "PYTHON SCAN\nπ {with_columns}/{total_columns};\nσ {predicate}"
@@ -176,41 +227,41 @@ impl<'a> IRDotDisplay<'a> {
                 schema,
                 ..
             } => {
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| write!(f, "π {}/{}", expr.len(), schema.len()))?;
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| write!(f, "π {}/{}", expr.len(), schema.len()))?;
             },
             Sort {
                 input, by_column, ..
             } => {
                 let by_column = self.display_exprs(by_column);
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| write!(f, "SORT BY {by_column}"))?;
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| write!(f, "SORT BY {by_column}"))?;
             },
             GroupBy {
                 input, keys, aggs, ..
             } => {
                 let keys = self.display_exprs(keys);
                 let aggs = self.display_exprs(aggs);
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| write!(f, "AGG {aggs}\nBY\n{keys}"))?;
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| write!(f, "AGG {aggs}\nBY\n{keys}"))?;
             },
             HStack { input, exprs, .. } => {
                 let exprs = self.display_exprs(exprs);
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| write!(f, "WITH COLUMNS {exprs}"))?;
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| write!(f, "WITH COLUMNS {exprs}"))?;
             },
             Reduce { input, exprs, .. } => {
                 let exprs = self.display_exprs(exprs);
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| write!(f, "REDUCE {exprs}"))?;
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| write!(f, "REDUCE {exprs}"))?;
             },
             Slice { input, offset, len } => {
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| write!(f, "SLICE offset: {offset}; len: {len}"))?;
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| write!(f, "SLICE offset: {offset}; len: {len}"))?;
             },
             Distinct { input, options, .. } => {
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| {
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| {
                     f.write_str("DISTINCT")?;
 
                     if let Some(subset) = &options.subset {
@@ -242,7 +293,7 @@ impl<'a> IRDotDisplay<'a> {
                 let selection = OptionExprIRDisplay(selection);
                 let total_columns = schema.len();
 
-                write_label(f, id, |f| {
+                self.write_label(f, id, |f| {
                     write!(f, "TABLE\nπ {num_columns}/{total_columns};\nσ {selection}")
                 })?;
             },
@@ -262,7 +313,7 @@ impl<'a> IRDotDisplay<'a> {
                 let total_columns =
                     file_info.schema.len() - usize::from(options.row_index.is_some());
 
-                write_label(f, id, |f| {
+                self.write_label(f, id, |f| {
                     write!(f, "{name} SCAN {path}\nπ {with_columns}/{total_columns};",)?;
 
                     if let Some(predicate) = predicate.as_ref() {
@@ -284,13 +335,13 @@ impl<'a> IRDotDisplay<'a> {
                 options,
                 ..
             } => {
-                self.with_root(*input_left)._format(f, Some(id), last)?;
-                self.with_root(*input_right)._format(f, Some(id), last)?;
+                self.with_root(*input_left)._format(f, Some(id))?;
+                self.with_root(*input_right)._format(f, Some(id))?;
 
                 let left_on = self.display_exprs(left_on);
                 let right_on = self.display_exprs(right_on);
 
-                write_label(f, id, |f| {
+                self.write_label(f, id, |f| {
                     write!(
                         f,
                         "JOIN {}\nleft: {left_on};\nright: {right_on}",
@@ -301,26 +352,28 @@ impl<'a> IRDotDisplay<'a> {
             MapFunction {
                 input, function, ..
             } => {
-                if let Some(streaming_lp) = function.to_streaming_lp() {
-                    Self::new_streaming(streaming_lp)._format(f, Some(id), last)?;
-                } else {
-                    self.with_root(*input)._format(f, Some(id), last)?;
-                    write_label(f, id, |f| write!(f, "{function}"))?;
-                }
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| write!(f, "{function}"))?;
             },
             ExtContext { input, .. } => {
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| f.write_str("EXTERNAL_CONTEXT"))?;
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| f.write_str("EXTERNAL_CONTEXT"))?;
             },
             Sink { input, payload, .. } => {
-                self.with_root(*input)._format(f, Some(id), last)?;
+                self.with_root(*input)._format(f, Some(id))?;
 
-                write_label(f, id, |f| {
+                self.write_label(f, id, |f| {
                     f.write_str(match payload {
                         SinkType::Memory => "SINK (MEMORY)",
                         SinkType::File { .. } => "SINK (FILE)",
+                        SinkType::Partitioned { .. } => "SINK (PARTITIONED)",
                         #[cfg(feature = "cloud")]
                         SinkType::Cloud { .. } => "SINK (CLOUD)",
+                        #[cfg(feature = "flight")]
+                        SinkType::Flight { .. } => "SINK (FLIGHT)",
+                        SinkType::Writer { .. } => "SINK (WRITER)",
+                        SinkType::Custom { .. } => "SINK (CUSTOM)",
+                        SinkType::Tee { .. } => "SINK (TEE)",
                     })
                 })?;
             },
@@ -329,12 +382,12 @@ impl<'a> IRDotDisplay<'a> {
                 let total_columns = self.lp.lp_arena.get(*input).schema(self.lp.lp_arena).len();
 
                 let columns = ColumnsDisplay(columns.as_ref());
-                self.with_root(*input)._format(f, Some(id), last)?;
-                write_label(f, id, |f| {
+                self.with_root(*input)._format(f, Some(id))?;
+                self.write_label(f, id, |f| {
                     write!(f, "simple π {num_columns}/{total_columns}\n[{columns}]")
                 })?;
             },
-            Invalid => write_label(f, id, |f| f.write_str("INVALID"))?,
+            Invalid => self.write_label(f, id, |f| f.write_str("INVALID"))?,
         }
 
         Ok(())
@@ -449,14 +502,42 @@ impl<'a> fmt::Write for EscapeLabel<'a> {
     }
 }
 
+/// Utility structure to write to a [`fmt::Formatter`] whilst escaping the output as the text of
+/// a Mermaid node
+struct EscapeMermaidLabel<'a>(&'a mut dyn fmt::Write);
+
+impl fmt::Write for EscapeMermaidLabel<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '"' => "#quot;",
+                '<' => "#lt;",
+                '>' => "#gt;",
+                '\n' => "<br>",
+                _ => continue,
+            };
+            self.0.write_str(&s[start..i])?;
+            self.0.write_str(escaped)?;
+            start = i + c.len_utf8();
+        }
+        self.0.write_str(&s[start..])
+    }
+}
+
 impl fmt::Display for IRDotDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "graph  polars_query {{")?;
-
-        let mut last = 0;
-        self._format(f, None, &mut last)?;
-
-        writeln!(f, "}}")?;
+        match self.format {
+            DiagramFormat::Dot => {
+                writeln!(f, "graph  polars_query {{")?;
+                self._format(f, None)?;
+                writeln!(f, "}}")?;
+            },
+            DiagramFormat::Mermaid => {
+                writeln!(f, "graph TD")?;
+                self._format(f, None)?;
+            },
+        }
 
         Ok(())
     }
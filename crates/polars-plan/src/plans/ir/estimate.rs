@@ -0,0 +1,361 @@
+//! Estimate the number of rows and the size of the result of a plan before it runs.
+use polars_core::prelude::*;
+use polars_utils::arena::{Arena, Node};
+use recursive::recursive;
+
+use crate::prelude::*;
+
+/// The selectivity of a predicate of which nothing is known.
+const DEFAULT_SELECTIVITY: f64 = 0.5;
+/// The selectivity of an equality, e.g. `col("a") == 1`.
+const EQ_SELECTIVITY: f64 = 0.1;
+/// The selectivity of a range, e.g. `col("a") > 1`.
+const RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+/// The fraction of the rows of a group-by or a unique that every group keeps.
+const GROUP_SELECTIVITY: f64 = 0.1;
+/// The number of bytes of a string or a binary value of which the size is unknown.
+const VARIABLE_WIDTH: usize = 32;
+/// The number of values of a list of which the size is unknown.
+const LIST_LEN: usize = 4;
+
+/// The estimated size of the result of a plan, see [`IRPlanRef::estimate`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlanEstimate {
+    /// The estimated number of rows, or `None` if it is unknown, e.g. for a Python scan.
+    pub rows: Option<usize>,
+    /// The estimated number of bytes of the columns of the result, or `None` if the number of
+    /// rows is unknown.
+    pub bytes: Option<usize>,
+    /// Whether the number of rows is exact, i.e. it only depends on the metadata of the files
+    /// and the sizes of the DataFrames of the plan, and no selectivity had to be guessed.
+    pub exact: bool,
+}
+
+#[derive(Clone, Copy)]
+struct Rows {
+    rows: f64,
+    exact: bool,
+}
+
+impl Rows {
+    fn exact(rows: usize) -> Self {
+        Self {
+            rows: rows as f64,
+            exact: true,
+        }
+    }
+
+    /// An estimate of a number of rows that is not known.
+    fn estimated(rows: usize) -> Self {
+        Self {
+            rows: rows as f64,
+            exact: false,
+        }
+    }
+
+    fn inexact(self) -> Self {
+        Self {
+            exact: false,
+            ..self
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        Self {
+            rows: self.rows.max(other.rows),
+            exact: self.exact && other.exact,
+        }
+    }
+
+    fn scale(self, selectivity: f64) -> Self {
+        Self {
+            rows: self.rows * selectivity,
+            exact: false,
+        }
+    }
+
+    fn slice(self, slice: Option<(i64, usize)>) -> Self {
+        let Some((offset, len)) = slice else {
+            return self;
+        };
+        let available = if offset >= 0 {
+            (self.rows - offset as f64).max(0.0)
+        } else {
+            self.rows.min(-offset as f64)
+        };
+        Self {
+            rows: available.min(len as f64),
+            exact: self.exact,
+        }
+    }
+
+    fn groups(self) -> Self {
+        let groups = if self.rows > 0.0 {
+            (self.rows * GROUP_SELECTIVITY).max(1.0)
+        } else {
+            0.0
+        };
+        Self {
+            rows: groups,
+            exact: self.exact && self.rows <= 1.0,
+        }
+    }
+}
+
+/// The fraction of the rows for which `predicate` is true, with the heuristics of System R.
+#[recursive]
+fn selectivity(predicate: Node, expr_arena: &Arena<AExpr>) -> f64 {
+    match expr_arena.get(predicate) {
+        AExpr::BinaryExpr { left, op, right } => match op {
+            Operator::And | Operator::LogicalAnd => {
+                selectivity(*left, expr_arena) * selectivity(*right, expr_arena)
+            },
+            Operator::Or | Operator::LogicalOr => {
+                let (l, r) = (
+                    selectivity(*left, expr_arena),
+                    selectivity(*right, expr_arena),
+                );
+                l + r - l * r
+            },
+            Operator::Eq | Operator::EqValidity => EQ_SELECTIVITY,
+            Operator::NotEq | Operator::NotEqValidity => 1.0 - EQ_SELECTIVITY,
+            Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq => RANGE_SELECTIVITY,
+            _ => DEFAULT_SELECTIVITY,
+        },
+        AExpr::Function {
+            function: FunctionExpr::Boolean(function),
+            input,
+            ..
+        } => match function {
+            BooleanFunction::Not => 1.0 - selectivity(input[0].node(), expr_arena),
+            BooleanFunction::IsNull => EQ_SELECTIVITY,
+            BooleanFunction::IsNotNull => 1.0 - EQ_SELECTIVITY,
+            #[cfg(feature = "is_between")]
+            BooleanFunction::IsBetween { .. } => RANGE_SELECTIVITY * RANGE_SELECTIVITY,
+            _ => DEFAULT_SELECTIVITY,
+        },
+        AExpr::Literal(LiteralValue::Boolean(true)) => 1.0,
+        AExpr::Literal(LiteralValue::Boolean(false)) => 0.0,
+        AExpr::Alias(e, _) => selectivity(*e, expr_arena),
+        _ => DEFAULT_SELECTIVITY,
+    }
+}
+
+fn filter(rows: Rows, predicate: Option<&ExprIR>, expr_arena: &Arena<AExpr>) -> Rows {
+    match predicate {
+        Some(predicate) => rows.scale(selectivity(predicate.node(), expr_arena)),
+        None => rows,
+    }
+}
+
+#[recursive]
+fn estimate_rows(node: Node, lp_arena: &Arena<IR>, expr_arena: &Arena<AExpr>) -> Option<Rows> {
+    use IR::*;
+    let rows = |node: Node| estimate_rows(node, lp_arena, expr_arena);
+    let out = match lp_arena.get(node) {
+        #[cfg(feature = "python")]
+        PythonScan { .. } => return None,
+        DataFrameScan { df, filter: f, .. } => {
+            filter(Rows::exact(df.height()), f.as_ref(), expr_arena)
+        },
+        Scan {
+            file_info,
+            predicate,
+            file_options,
+            sources,
+            ..
+        } => {
+            let mut rows = match file_info.row_estimation {
+                (Some(known), _) => Rows::exact(known),
+                (None, usize::MAX) => return None,
+                (None, estimated) => Rows::estimated(estimated),
+            };
+            if let Some(n_rows) = file_options.n_rows_per_file {
+                let max = (n_rows * sources.len().max(1)) as f64;
+                if max < rows.rows {
+                    rows = Rows::estimated(max as usize);
+                }
+            }
+            filter(rows, predicate.as_ref(), expr_arena).slice(file_options.slice)
+        },
+        Slice { input, offset, len } => rows(*input)?.slice(Some((*offset, *len as usize))),
+        Filter { input, predicate } => filter(rows(*input)?, Some(predicate), expr_arena),
+        Reduce { .. } => Rows::exact(1),
+        Select { input, expr, .. } => {
+            if !expr.is_empty() && expr.iter().all(|e| e.is_scalar(expr_arena)) {
+                Rows::exact(1)
+            } else {
+                rows(*input)?
+            }
+        },
+        HStack { input, .. }
+        | SimpleProjection { input, .. }
+        | Cache { input, .. }
+        | ExtContext { input, .. }
+        | Sink { input, .. } => rows(*input)?,
+        Sort { input, slice, .. } => rows(*input)?.slice(*slice),
+        GroupBy {
+            input,
+            keys,
+            options,
+            ..
+        } => {
+            let input = rows(*input)?;
+            #[cfg(feature = "dynamic_group_by")]
+            if options.rolling.is_some() {
+                // a group per row
+                return Some(input.slice(options.slice));
+            }
+            #[cfg(feature = "dynamic_group_by")]
+            if options.dynamic.is_some() {
+                return Some(input.groups().slice(options.slice));
+            }
+            if keys.is_empty() {
+                Rows::exact(1)
+            } else {
+                input.groups().slice(options.slice)
+            }
+        },
+        Distinct { input, options } => rows(*input)?.groups().slice(options.slice),
+        Join {
+            input_left,
+            input_right,
+            options,
+            ..
+        } => {
+            let (left, right) = (rows(*input_left)?, rows(*input_right)?);
+            let rows = match &options.args.how {
+                JoinType::Cross => Rows {
+                    rows: left.rows * right.rows,
+                    exact: left.exact && right.exact,
+                },
+                JoinType::Inner => left.max(right).inexact(),
+                JoinType::Left => left.inexact(),
+                JoinType::Right => right.inexact(),
+                JoinType::Full => left.max(right).inexact(),
+                #[cfg(feature = "asof_join")]
+                JoinType::AsOf(_) => left,
+                #[cfg(feature = "semi_anti_join")]
+                JoinType::Semi | JoinType::Anti => left.scale(DEFAULT_SELECTIVITY),
+                #[cfg(feature = "iejoin")]
+                JoinType::IEJoin(_) => left.scale(RANGE_SELECTIVITY).max(right),
+            };
+            rows.slice(options.args.slice)
+        },
+        MapFunction { input, function } => {
+            let rows = rows(*input)?;
+            match function {
+                // every value of a list is a row of its own
+                FunctionIR::Explode { .. } => rows.scale(LIST_LEN as f64),
+                #[cfg(feature = "pivot")]
+                FunctionIR::Unpivot { args, .. } => rows.scale(args.on.len().max(1) as f64),
+                #[cfg(feature = "merge_sorted")]
+                FunctionIR::MergeSorted { .. } => return None,
+                FunctionIR::Opaque { .. } => rows.inexact(),
+                _ => rows,
+            }
+        },
+        Union { inputs, options } => {
+            let mut out = Rows::exact(0);
+            for input in inputs {
+                let input = rows(*input)?;
+                out.rows += input.rows;
+                out.exact &= input.exact;
+            }
+            out.slice(options.slice)
+        },
+        HConcat { inputs, .. } => {
+            let mut out = Rows::exact(0);
+            for input in inputs {
+                out = out.max(rows(*input)?);
+            }
+            out
+        },
+        Invalid => unreachable!(),
+    };
+    Some(out)
+}
+
+/// The estimated number of bytes of a value of `dtype`.
+fn value_width(dtype: &DataType) -> usize {
+    match dtype {
+        DataType::String | DataType::Binary => VARIABLE_WIDTH,
+        DataType::List(inner) => LIST_LEN * value_width(inner) + 8,
+        #[cfg(feature = "dtype-array")]
+        DataType::Array(inner, width) => width * value_width(inner),
+        #[cfg(feature = "dtype-struct")]
+        DataType::Struct(fields) => fields.iter().map(|f| value_width(f.dtype())).sum(),
+        DataType::Null => 0,
+        DataType::Boolean | DataType::Int8 | DataType::UInt8 => 1,
+        DataType::Int16 | DataType::UInt16 => 2,
+        DataType::Int32 | DataType::UInt32 | DataType::Float32 => 4,
+        #[cfg(feature = "dtype-date")]
+        DataType::Date => 4,
+        #[cfg(feature = "dtype-categorical")]
+        DataType::Categorical(_, _) | DataType::Enum(_, _) => 4,
+        #[cfg(feature = "dtype-decimal")]
+        DataType::Decimal(_, _) => 16,
+        _ => 8,
+    }
+}
+
+/// The number of bytes of a row of the columns of `df`, per name.
+fn dataframe_widths(df: &DataFrame) -> PlHashMap<PlSmallStr, f64> {
+    let height = df.height();
+    df.get_columns()
+        .iter()
+        .filter(|_| height > 0)
+        .map(|c| {
+            let width = c.estimated_size() as f64 / height as f64;
+            (c.name().clone(), width)
+        })
+        .collect()
+}
+
+impl IRPlanRef<'_> {
+    /// Estimate the number of rows and the number of bytes of the result of the plan, so that
+    /// callers can e.g. choose between the streaming and the in-memory engine or size their
+    /// resources before the query runs.
+    ///
+    /// The number of rows of scans is read from the metadata of the files, where it is known,
+    /// and every filter, group-by and join scales it by a selectivity that is guessed from the
+    /// kind of the operation, e.g. an equality keeps a tenth of the rows. The size of a row is
+    /// computed from its data types, or from the columns of the DataFrames of the plan where
+    /// these have the same name.
+    pub fn estimate(self) -> PlanEstimate {
+        let Some(rows) = estimate_rows(self.lp_top, self.lp_arena, self.expr_arena) else {
+            return PlanEstimate::default();
+        };
+        let mut widths = PlHashMap::new();
+        for (_, lp) in self.lp_arena.iter(self.lp_top) {
+            if let IR::DataFrameScan { df, .. } = lp {
+                for (name, width) in dataframe_widths(df) {
+                    widths.entry(name).or_insert(width);
+                }
+            }
+        }
+        let schema = self.lp_arena.get(self.lp_top).schema(self.lp_arena);
+        let row_width = schema
+            .iter()
+            .map(|(name, dtype)| {
+                widths
+                    .get(name)
+                    .copied()
+                    .unwrap_or_else(|| value_width(dtype) as f64)
+            })
+            .sum::<f64>();
+        let n_rows = rows.rows.round() as usize;
+        PlanEstimate {
+            rows: Some(n_rows),
+            bytes: Some((n_rows as f64 * row_width).round() as usize),
+            exact: rows.exact,
+        }
+    }
+}
+
+impl IRPlan {
+    /// See [`IRPlanRef::estimate`].
+    pub fn estimate(&self) -> PlanEstimate {
+        self.as_ref().estimate()
+    }
+}
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-use polars_core::datatypes::AnyValue;
+use polars_core::datatypes::{AnyValue, PlHashMap};
 use polars_core::schema::Schema;
 use polars_io::RowIndex;
 use recursive::recursive;
@@ -12,6 +12,8 @@ use crate::prelude::*;
 
 pub struct IRDisplay<'a> {
     is_streaming: bool,
+    node_ids: bool,
+    annotations: Option<&'a PlHashMap<Node, String>>,
     lp: IRPlanRef<'a>,
 }
 
@@ -103,6 +105,8 @@ impl<'a> IRDisplay<'a> {
 
         Self {
             is_streaming: false,
+            node_ids: false,
+            annotations: None,
             lp,
         }
     }
@@ -110,10 +114,27 @@ impl<'a> IRDisplay<'a> {
     fn new_streaming(lp: IRPlanRef<'a>) -> Self {
         Self {
             is_streaming: true,
+            node_ids: false,
+            annotations: None,
             lp,
         }
     }
 
+    /// Write the id of every node above it, the same id as in the diagrams of
+    /// [`IRDotDisplay`].
+    pub fn with_node_ids(mut self) -> Self {
+        self.node_ids = true;
+        self
+    }
+
+    /// Write the ids of the nodes, followed by the annotations of the nodes that have one, e.g.
+    /// their runtime statistics.
+    pub fn with_annotations(mut self, annotations: &'a PlHashMap<Node, String>) -> Self {
+        self.node_ids = true;
+        self.annotations = Some(annotations);
+        self
+    }
+
     fn root(&self) -> &IR {
         self.lp.root()
     }
@@ -121,6 +142,8 @@ impl<'a> IRDisplay<'a> {
     fn with_root(&self, root: Node) -> Self {
         Self {
             is_streaming: false,
+            node_ids: self.node_ids,
+            annotations: self.annotations,
             lp: self.lp.with_root(root),
         }
     }
@@ -153,6 +176,21 @@ impl<'a> IRDisplay<'a> {
             indent
         };
 
+        let is_pipeline = matches!(
+            self.root(),
+            IR::MapFunction { function, .. } if function.to_streaming_lp().is_some()
+        );
+        if self.node_ids && !is_pipeline {
+            match self.root() {
+                IR::Cache { id, .. } => write!(f, "{:indent$}[c{id}]", "")?,
+                _ => write!(f, "{:indent$}[p{}]", "", self.lp.lp_top.0)?,
+            }
+            if let Some(annotation) = self.annotations.and_then(|a| a.get(&self.lp.lp_top)) {
+                write!(f, " {annotation}")?;
+            }
+            writeln!(f)?;
+        }
+
         let sub_indent = indent + 2;
         use IR::*;
 
@@ -358,7 +396,10 @@ impl<'a> IRDisplay<'a> {
                 input, function, ..
             } => {
                 if let Some(streaming_lp) = function.to_streaming_lp() {
-                    IRDisplay::new_streaming(streaming_lp)._format(f, indent)
+                    let mut display = IRDisplay::new_streaming(streaming_lp);
+                    display.node_ids = self.node_ids;
+                    display.annotations = self.annotations;
+                    display._format(f, indent)
                 } else {
                     write!(f, "{:indent$}{function}", "")?;
                     self.with_root(*input)._format(f, sub_indent)
@@ -372,8 +413,14 @@ impl<'a> IRDisplay<'a> {
                 let name = match payload {
                     SinkType::Memory => "SINK (memory)",
                     SinkType::File { .. } => "SINK (file)",
+                    SinkType::Partitioned { .. } => "SINK (partitioned)",
                     #[cfg(feature = "cloud")]
                     SinkType::Cloud { .. } => "SINK (cloud)",
+                    #[cfg(feature = "flight")]
+                    SinkType::Flight { .. } => "SINK (flight)",
+                    SinkType::Writer { .. } => "SINK (writer)",
+                    SinkType::Custom { .. } => "SINK (custom)",
+                    SinkType::Tee { .. } => "SINK (tee)",
                 };
                 write!(f, "{:indent$}{name}", "")?;
                 self.with_root(*input)._format(f, sub_indent)
@@ -611,6 +658,9 @@ impl<'a> Display for ExprIRDisplay<'a> {
                 let falsy = self.with_root(falsy);
                 write!(f, "when({predicate}).then({truthy}).otherwise({falsy})",)
             },
+            Function {
+                input, function, ..
+            } if input.is_empty() => write!(f, "{function}"),
             Function {
                 input, function, ..
             } => {
@@ -0,0 +1,183 @@
+//! A machine-readable explain of plans, for tools that check the shapes of plans.
+use polars_utils::pl_str::PlSmallStr;
+use serde_json::{json, Map, Value};
+
+use crate::prelude::*;
+
+fn exprs(exprs: &[ExprIR], expr_arena: &Arena<AExpr>) -> Value {
+    exprs
+        .iter()
+        .map(|e| Value::String(e.display(expr_arena).to_string()))
+        .collect()
+}
+
+fn opt_expr(e: Option<&ExprIR>, expr_arena: &Arena<AExpr>) -> Value {
+    e.map_or(Value::Null, |e| {
+        Value::String(e.display(expr_arena).to_string())
+    })
+}
+
+fn names<'a>(names: impl IntoIterator<Item = &'a PlSmallStr>) -> Value {
+    names
+        .into_iter()
+        .map(|name| Value::String(name.to_string()))
+        .collect()
+}
+
+fn slice(slice: Option<(i64, usize)>) -> Value {
+    slice.map_or(Value::Null, |(offset, len)| json!([offset, len]))
+}
+
+fn sources(sources: &ScanSources) -> Value {
+    match sources {
+        ScanSources::Paths(paths) => paths
+            .iter()
+            .map(|p| Value::String(p.to_string_lossy().into_owned()))
+            .collect(),
+        ScanSources::Files(files) => json!(format!("{} in-memory files", files.len())),
+        ScanSources::Buffers(buffers) => json!(format!("{} in-memory buffers", buffers.len())),
+    }
+}
+
+impl<'a> IRPlanRef<'a> {
+    /// The node at the root and its details, with the same id as in [`IRDisplay::with_node_ids`].
+    fn node_json(self, streaming: bool) -> Value {
+        let lp = self.root();
+        let expr_arena = self.expr_arena;
+
+        if let IR::MapFunction { function, .. } = lp {
+            if let Some(streaming_lp) = function.to_streaming_lp() {
+                return streaming_lp.node_json(true);
+            }
+        }
+
+        let mut node = Map::new();
+        let id = match lp {
+            IR::Cache { id, .. } => format!("c{id}"),
+            _ => format!("p{}", self.lp_top.0),
+        };
+        node.insert("id".into(), id.into());
+        node.insert("kind".into(), lp.name().into());
+        node.insert("streaming".into(), streaming.into());
+        node.insert(
+            "schema".into(),
+            names(lp.schema(self.lp_arena).iter_names()),
+        );
+
+        let mut insert = |key: &str, value: Value| {
+            node.insert(key.into(), value);
+        };
+        use IR::*;
+        match lp {
+            Scan {
+                sources: scan_sources,
+                predicate,
+                file_options,
+                ..
+            } => {
+                insert("sources", sources(scan_sources));
+                insert(
+                    "projection",
+                    file_options
+                        .with_columns
+                        .as_deref()
+                        .map_or(Value::Null, names),
+                );
+                insert("predicate", opt_expr(predicate.as_ref(), expr_arena));
+                insert("slice", slice(file_options.slice));
+                insert(
+                    "row_index",
+                    file_options
+                        .row_index
+                        .as_ref()
+                        .map_or(Value::Null, |ri| ri.name.as_str().into()),
+                );
+            },
+            DataFrameScan {
+                output_schema,
+                filter,
+                ..
+            } => {
+                insert(
+                    "projection",
+                    output_schema
+                        .as_ref()
+                        .map_or(Value::Null, |s| names(s.iter_names())),
+                );
+                insert("predicate", opt_expr(filter.as_ref(), expr_arena));
+            },
+            Filter { predicate, .. } => insert("predicate", opt_expr(Some(predicate), expr_arena)),
+            Slice { offset, len, .. } => insert("slice", json!([offset, len])),
+            SimpleProjection { columns, .. } => insert("columns", names(columns.iter_names())),
+            Select { expr, .. } => insert("expressions", exprs(expr, expr_arena)),
+            Reduce { exprs: e, .. } | HStack { exprs: e, .. } => {
+                insert("expressions", exprs(e, expr_arena))
+            },
+            Sort {
+                by_column,
+                slice: sort_slice,
+                sort_options,
+                ..
+            } => {
+                insert("by", exprs(by_column, expr_arena));
+                insert("descending", json!(sort_options.descending));
+                insert("nulls_last", json!(sort_options.nulls_last));
+                insert("slice", slice(*sort_slice));
+            },
+            Cache { cache_hits, .. } => insert("cache_hits", json!(cache_hits)),
+            GroupBy {
+                keys,
+                aggs,
+                maintain_order,
+                ..
+            } => {
+                insert("keys", exprs(keys, expr_arena));
+                insert("aggregations", exprs(aggs, expr_arena));
+                insert("maintain_order", json!(maintain_order));
+            },
+            Join {
+                left_on,
+                right_on,
+                options,
+                ..
+            } => {
+                insert("how", options.args.how.to_string().into());
+                insert("left_on", exprs(left_on, expr_arena));
+                insert("right_on", exprs(right_on, expr_arena));
+                insert("slice", slice(options.args.slice));
+            },
+            Distinct { options, .. } => {
+                insert(
+                    "subset",
+                    options.subset.as_deref().map_or(Value::Null, names),
+                );
+                insert("keep", format!("{:?}", options.keep_strategy).into());
+                insert("slice", slice(options.slice));
+            },
+            MapFunction { function, .. } => insert("function", function.to_string().into()),
+            _ => {},
+        }
+
+        let inputs = lp
+            .get_inputs_vec()
+            .into_iter()
+            .map(|input| self.with_root(input).node_json(streaming))
+            .collect();
+        node.insert("inputs".into(), inputs);
+        Value::Object(node)
+    }
+
+    /// The plan as JSON: every node is an object with its `id`, its `kind`, the columns of its
+    /// `schema`, the details of its kind, e.g. its expressions or what was pushed down into
+    /// scans, and its `inputs`.
+    pub fn to_json(self) -> String {
+        serde_json::to_string_pretty(&self.node_json(false)).unwrap()
+    }
+}
+
+impl IRPlan {
+    /// See [`IRPlanRef::to_json`].
+    pub fn to_json(&self) -> String {
+        self.as_ref().to_json()
+    }
+}
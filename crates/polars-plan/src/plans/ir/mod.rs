@@ -1,6 +1,9 @@
 mod dot;
+mod estimate;
 mod format;
 mod inputs;
+#[cfg(feature = "explain_json")]
+mod json;
 mod scan_sources;
 mod schema;
 pub(crate) mod tree_format;
@@ -8,7 +11,8 @@ pub(crate) mod tree_format;
 use std::borrow::Cow;
 use std::fmt;
 
-pub use dot::{EscapeLabel, IRDotDisplay, PathsDisplay, ScanSourcesDisplay};
+pub use dot::{DiagramFormat, EscapeLabel, IRDotDisplay, PathsDisplay, ScanSourcesDisplay};
+pub use estimate::PlanEstimate;
 pub use format::{ExprIRDisplay, IRDisplay};
 use hive::HivePartitions;
 use polars_core::prelude::*;
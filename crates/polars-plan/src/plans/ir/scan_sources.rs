@@ -127,6 +127,12 @@ impl ScanSources {
         self.first_path().is_some_and(polars_io::is_cloud_url)
     }
 
+    /// Is the first path a plain HTTP(S) URL that is read with range requests?
+    pub fn is_http_range_url(&self) -> bool {
+        self.first_path()
+            .is_some_and(polars_io::path_utils::is_http_range_url)
+    }
+
     pub fn len(&self) -> usize {
         match self {
             Self::Paths(s) => s.len(),
@@ -188,6 +194,22 @@ impl<'a> ScanSourceRef<'a> {
         }
     }
 
+    pub fn as_path(&self) -> Option<&Path> {
+        match self {
+            Self::Path(path) => Some(*path),
+            _ => None,
+        }
+    }
+
+    /// Create [`ScanSources`] that only contain this source.
+    pub fn into_sources(self) -> PolarsResult<ScanSources> {
+        Ok(match self {
+            Self::Path(path) => ScanSources::Paths([path.to_path_buf()].into()),
+            Self::File(file) => ScanSources::Files([file.try_clone()?].into()),
+            Self::Buffer(buff) => ScanSources::Buffers([buff.clone()].into()),
+        })
+    }
+
     /// Turn the scan source into a memory slice
     pub fn to_memslice(&self) -> PolarsResult<MemSlice> {
         self.to_memslice_possibly_async(false, None, 0)
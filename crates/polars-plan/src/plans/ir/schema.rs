@@ -39,8 +39,14 @@ impl IR {
             Sink { payload, .. } => match payload {
                 SinkType::Memory => "sink (memory)",
                 SinkType::File { .. } => "sink (file)",
+                SinkType::Partitioned { .. } => "sink (partitioned)",
                 #[cfg(feature = "cloud")]
                 SinkType::Cloud { .. } => "sink (cloud)",
+                #[cfg(feature = "flight")]
+                SinkType::Flight { .. } => "sink (flight)",
+                SinkType::Writer { .. } => "sink (writer)",
+                SinkType::Custom { .. } => "sink (custom)",
+                SinkType::Tee { .. } => "sink (tee)",
             },
             SimpleProjection { .. } => "simple_projection",
             Invalid => "invalid",
@@ -356,8 +356,14 @@ impl<'a> TreeFmtNode<'a> {
                             match payload {
                                 SinkType::Memory => "SINK (memory)",
                                 SinkType::File { .. } => "SINK (file)",
+                                SinkType::Partitioned { .. } => "SINK (partitioned)",
                                 #[cfg(feature = "cloud")]
                                 SinkType::Cloud { .. } => "SINK (cloud)",
+                                #[cfg(feature = "flight")]
+                                SinkType::Flight { .. } => "SINK (flight)",
+                                SinkType::Writer { .. } => "SINK (writer)",
+                                SinkType::Custom { .. } => "SINK (custom)",
+                                SinkType::Tee { .. } => "SINK (tee)",
                             },
                         ),
                         vec![self.lp_node(None, *input)],
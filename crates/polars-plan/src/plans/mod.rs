@@ -20,6 +20,8 @@ pub(crate) mod conversion;
 pub(crate) mod debug;
 pub mod expr_ir;
 mod file_scan;
+#[cfg(feature = "result_cache")]
+mod fingerprint;
 mod format;
 mod functions;
 pub mod hive;
@@ -30,6 +32,10 @@ pub(crate) mod options;
 #[cfg(feature = "python")]
 pub mod python;
 mod schema;
+#[cfg(feature = "cse")]
+mod shared_subplan;
+#[cfg(feature = "substrait")]
+mod substrait;
 pub mod visitor;
 
 pub use aexpr::*;
@@ -40,6 +46,8 @@ pub use builder_ir::*;
 pub use conversion::*;
 pub(crate) use expr_ir::*;
 pub use file_scan::*;
+#[cfg(feature = "result_cache")]
+pub use fingerprint::*;
 pub use functions::*;
 pub use ir::*;
 pub use iterator::*;
@@ -48,6 +56,8 @@ pub use optimizer::*;
 pub use schema::*;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "cse")]
+pub use shared_subplan::*;
 use strum_macros::IntoStaticStr;
 
 #[derive(Clone, Copy, Debug)]
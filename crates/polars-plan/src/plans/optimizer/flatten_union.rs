@@ -8,7 +8,8 @@ pub struct FlattenUnionRule {}
 
 fn get_union_inputs(node: Node, lp_arena: &Arena<IR>) -> Option<&[Node]> {
     match lp_arena.get(node) {
-        IR::Union { inputs, .. } => Some(inputs),
+        // The inputs of a union with a bound on its parallelism are kept together.
+        IR::Union { inputs, options } if options.max_parallel_inputs.is_none() => Some(inputs),
         _ => None,
     }
 }
@@ -27,7 +28,9 @@ impl OptimizationRule for FlattenUnionRule {
                 inputs,
                 mut options,
             } if inputs.iter().any(|node| match lp_arena.get(*node) {
-                Union { options, .. } => !options.flattened_by_opt,
+                Union { options, .. } => {
+                    !options.flattened_by_opt && options.max_parallel_inputs.is_none()
+                },
                 _ => false,
             }) =>
             {
@@ -0,0 +1,208 @@
+//! Reorder chains of inner joins, e.g. star joins of a fact table with many dimension tables,
+//! so that the smallest relations are joined first.
+
+use polars_core::prelude::*;
+use polars_utils::arena::{Arena, Node};
+
+use super::*;
+
+/// An inner join of the chain with the relation `right`.
+struct Step {
+    right: Node,
+    left_on: Vec<ExprIR>,
+    right_on: Vec<ExprIR>,
+    options: Arc<JoinOptions>,
+    rows: usize,
+}
+
+/// The estimated number of rows of the output of `node`, from the statistics of the scans.
+/// `usize::MAX` if it is unknown.
+fn estimated_rows(node: Node, lp_arena: &Arena<IR>) -> usize {
+    use IR::*;
+    match lp_arena.get(node) {
+        DataFrameScan { df, .. } => df.height(),
+        Scan { file_info, .. } => {
+            let (known, estimated) = file_info.row_estimation;
+            known.unwrap_or(estimated)
+        },
+        // the selectivity of predicates is unknown, assume that they halve the rows
+        Filter { input, .. } => match estimated_rows(*input, lp_arena) {
+            usize::MAX => usize::MAX,
+            rows => rows / 2,
+        },
+        Slice { input, len, .. } => std::cmp::min(estimated_rows(*input, lp_arena), *len as usize),
+        Select { input, .. }
+        | HStack { input, .. }
+        | SimpleProjection { input, .. }
+        | Sort { input, .. }
+        | Cache { input, .. } => estimated_rows(*input, lp_arena),
+        MapFunction { input, function } if !function.expands_rows() => {
+            estimated_rows(*input, lp_arena)
+        },
+        _ => usize::MAX,
+    }
+}
+
+fn column_names<'a>(
+    exprs: &'a [ExprIR],
+    expr_arena: &'a Arena<AExpr>,
+) -> Option<Vec<&'a PlSmallStr>> {
+    exprs
+        .iter()
+        .map(|e| match expr_arena.get(e.node()) {
+            AExpr::Column(name) => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether the inputs of `join` may be joined in any order: an inner join on columns, of which
+/// the right table keeps its role.
+fn is_reorderable(join: &IR, expr_arena: &Arena<AExpr>) -> bool {
+    let IR::Join {
+        left_on,
+        right_on,
+        options,
+        ..
+    } = join
+    else {
+        return false;
+    };
+    matches!(options.args.how, JoinType::Inner)
+        && options.args.slice.is_none()
+        && options.args.should_coalesce()
+        // the left table changes, so only the right table may be validated
+        && matches!(
+            options.args.validation,
+            JoinValidation::ManyToMany | JoinValidation::ManyToOne
+        )
+        && column_names(left_on, expr_arena).is_some()
+        && column_names(right_on, expr_arena).is_some()
+}
+
+/// The base relation and the joins of the chain of inner joins at `root`, in the order they are
+/// written.
+fn collect_chain(root: Node, lp_arena: &Arena<IR>, expr_arena: &Arena<AExpr>) -> (Node, Vec<Step>) {
+    let mut steps = vec![];
+    let mut node = root;
+    while let join @ IR::Join {
+        input_left,
+        input_right,
+        left_on,
+        right_on,
+        options,
+        ..
+    } = lp_arena.get(node)
+    {
+        if !is_reorderable(join, expr_arena) {
+            break;
+        }
+        steps.push(Step {
+            right: *input_right,
+            left_on: left_on.clone(),
+            right_on: right_on.clone(),
+            options: options.clone(),
+            rows: estimated_rows(*input_right, lp_arena),
+        });
+        node = *input_left;
+    }
+    steps.reverse();
+    (node, steps)
+}
+
+/// The order in which the `steps` are joined: the smallest relation of which the keys are
+/// available is joined first. Returns `None` if the relations can't be reordered because their
+/// columns would clash.
+fn join_order(
+    base: Node,
+    steps: &[Step],
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<Vec<usize>> {
+    // The columns that every relation adds to the output. These must be distinct, as otherwise
+    // the suffixes of the clashing columns would depend on the order.
+    let mut available = PlHashSet::new();
+    for name in lp_arena.get(base).schema(lp_arena).iter_names() {
+        available.insert(name.clone());
+    }
+    let mut added = Vec::with_capacity(steps.len());
+    let mut all = available.clone();
+    for step in steps {
+        let keys = column_names(&step.right_on, expr_arena)?;
+        let names = lp_arena
+            .get(step.right)
+            .schema(lp_arena)
+            .iter_names()
+            .filter(|name| !keys.contains(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        for name in &names {
+            if !all.insert(name.clone()) {
+                return None;
+            }
+        }
+        added.push(names);
+    }
+
+    let mut order = Vec::with_capacity(steps.len());
+    let mut remaining = (0..steps.len()).collect::<Vec<_>>();
+    while !remaining.is_empty() {
+        // ties are joined in the written order
+        let (pos, &next) = remaining
+            .iter()
+            .enumerate()
+            .filter(|&(_, &i)| {
+                column_names(&steps[i].left_on, expr_arena)
+                    .is_some_and(|keys| keys.iter().all(|key| available.contains(*key)))
+            })
+            .min_by_key(|&(_, &i)| steps[i].rows)?;
+        remaining.remove(pos);
+        available.extend(added[next].iter().cloned());
+        order.push(next);
+    }
+    Some(order)
+}
+
+/// Reorder the chains of inner joins in the plan at `root` by the estimated number of rows of
+/// their relations. The columns of a reordered chain are projected in their original order.
+pub(super) fn reorder_joins(
+    root: Node,
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &mut Arena<AExpr>,
+    verbose: bool,
+) -> PolarsResult<()> {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let (base, steps) = collect_chain(node, lp_arena, expr_arena);
+        if steps.len() < 2 {
+            lp_arena.get(node).copy_inputs(&mut stack);
+            continue;
+        }
+
+        stack.push(base);
+        stack.extend(steps.iter().map(|step| step.right));
+
+        let Some(order) = join_order(base, &steps, lp_arena, expr_arena) else {
+            continue;
+        };
+        if order.iter().enumerate().all(|(i, next)| i == *next) {
+            continue;
+        }
+        if verbose {
+            eprintln!("reorder the inputs of {} inner joins", steps.len());
+        }
+
+        let schema = lp_arena.get(node).schema(lp_arena).into_owned();
+        let mut steps = steps.into_iter().map(Some).collect::<Vec<_>>();
+        let mut builder = IRBuilder::new(base, expr_arena, lp_arena);
+        for i in order {
+            let step = steps[i].take().unwrap();
+            builder = builder.join(step.right, step.left_on, step.right_on, step.options);
+        }
+        let lp = builder
+            .project_simple(schema.iter_names().cloned())?
+            .build();
+        lp_arena.replace(node, lp);
+    }
+    Ok(())
+}
@@ -14,13 +14,16 @@ mod cse;
 mod flatten_union;
 #[cfg(feature = "fused")]
 mod fused;
+mod join_reorder;
 mod join_utils;
+pub mod pattern;
 mod predicate_pushdown;
 mod projection_pushdown;
 mod simplify_expr;
 mod slice_pushdown_expr;
 mod slice_pushdown_lp;
 mod stack_opt;
+mod user_rules;
 
 use collapse_and_project::SimpleProjectionAndCollapse;
 use delay_rechunk::DelayRechunk;
@@ -31,6 +34,9 @@ pub use projection_pushdown::ProjectionPushDown;
 pub use simplify_expr::{SimplifyBooleanRule, SimplifyExprRule};
 use slice_pushdown_lp::SlicePushDown;
 pub use stack_opt::{OptimizationRule, StackOptimizer};
+pub use user_rules::{
+    register_optimization_rule, registered_optimization_rules, unregister_optimization_rule,
+};
 
 use self::flatten_union::FlattenUnionRule;
 pub use crate::frame::{AllowedOptimizations, OptFlags};
@@ -88,6 +94,7 @@ pub fn optimize(
     let slice_pushdown = opt_state.contains(OptFlags::SLICE_PUSHDOWN);
     let streaming = opt_state.contains(OptFlags::STREAMING);
     let fast_projection = opt_state.contains(OptFlags::FAST_PROJECTION);
+    let join_reorder = opt_state.contains(OptFlags::JOIN_REORDER);
 
     // Don't run optimizations that don't make sense on a single node.
     // This keeps eager execution more snappy.
@@ -140,6 +147,11 @@ pub fn optimize(
     #[cfg(not(feature = "cse"))]
     let _cse_plan_changed = false;
 
+    // Should be run before the pushdowns, so that they push into the reordered joins.
+    if join_reorder && !eager {
+        join_reorder::reorder_joins(lp_top, lp_arena, expr_arena, verbose)?;
+    }
+
     // Should be run before predicate pushdown.
     if projection_pushdown {
         let mut projection_pushdown_opt = ProjectionPushDown::new();
@@ -193,6 +205,9 @@ pub fn optimize(
         rules.push(Box::new(FlattenUnionRule {}));
     }
 
+    // The rules of other crates run after the built-in ones.
+    rules.extend(user_rules::user_rules());
+
     lp_top = opt.optimize_loop(&mut rules, expr_arena, lp_arena, lp_top)?;
 
     if members.has_joins_or_unions && members.has_cache && _cse_plan_changed {
@@ -0,0 +1,77 @@
+//! Helpers that match the nodes that optimization rules rewrite.
+//!
+//! They return the parts of the node if it has the expected kind, so that rules can chain them
+//! with `?` or `let else`:
+//!
+//! ```ignore
+//! let (left, right) = pattern::binary_op(node, Operator::Multiply, expr_arena)?;
+//! let name = pattern::column(left, expr_arena)?;
+//! ```
+use super::*;
+
+/// The name of the column that the expression selects.
+pub fn column(node: Node, expr_arena: &Arena<AExpr>) -> Option<&PlSmallStr> {
+    match expr_arena.get(node) {
+        AExpr::Column(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// The value of the literal expression.
+pub fn literal(node: Node, expr_arena: &Arena<AExpr>) -> Option<&LiteralValue> {
+    match expr_arena.get(node) {
+        AExpr::Literal(lv) => Some(lv),
+        _ => None,
+    }
+}
+
+/// The left operand, the operator and the right operand of the binary expression.
+pub fn binary(node: Node, expr_arena: &Arena<AExpr>) -> Option<(Node, Operator, Node)> {
+    match expr_arena.get(node) {
+        AExpr::BinaryExpr { left, op, right } => Some((*left, *op, *right)),
+        _ => None,
+    }
+}
+
+/// The operands of the binary expression, if it applies `op`.
+pub fn binary_op(node: Node, op: Operator, expr_arena: &Arena<AExpr>) -> Option<(Node, Node)> {
+    match binary(node, expr_arena)? {
+        (left, current, right) if current == op => Some((left, right)),
+        _ => None,
+    }
+}
+
+/// The function and the inputs of the function expression.
+pub fn function(node: Node, expr_arena: &Arena<AExpr>) -> Option<(&FunctionExpr, &[ExprIR])> {
+    match expr_arena.get(node) {
+        AExpr::Function {
+            function, input, ..
+        } => Some((function, input)),
+        _ => None,
+    }
+}
+
+/// The input of the plan, if it has exactly one.
+pub fn single_input(node: Node, lp_arena: &Arena<IR>) -> Option<Node> {
+    let inputs = lp_arena.get(node).get_inputs();
+    match inputs.as_slice() {
+        [input] => Some(*input),
+        _ => None,
+    }
+}
+
+/// The input and the predicate of the filter.
+pub fn filter(node: Node, lp_arena: &Arena<IR>) -> Option<(Node, &ExprIR)> {
+    match lp_arena.get(node) {
+        IR::Filter { input, predicate } => Some((*input, predicate)),
+        _ => None,
+    }
+}
+
+/// The input and the expressions of the selection.
+pub fn select(node: Node, lp_arena: &Arena<IR>) -> Option<(Node, &[ExprIR])> {
+    match lp_arena.get(node) {
+        IR::Select { input, expr, .. } => Some((*input, expr)),
+        _ => None,
+    }
+}
@@ -416,6 +416,10 @@ impl<'a> PredicatePushDown<'a> {
                     FileScan::Anonymous { function, .. } => function.allows_predicate_pushdown(),
                     #[cfg(feature = "json")]
                     FileScan::NDJson { .. } => true,
+                    #[cfg(feature = "avro")]
+                    FileScan::Avro { .. } => options.slice.is_none(),
+                    #[cfg(feature = "fwf")]
+                    FileScan::Fwf { .. } => options.slice.is_none(),
                     #[allow(unreachable_patterns)]
                     _ => true,
                 };
@@ -493,7 +497,10 @@ impl<'a> PredicatePushDown<'a> {
                 options,
                 acc_predicates,
             ),
-            MapFunction { ref function, .. } => {
+            MapFunction {
+                ref function,
+                input,
+            } => {
                 if function.allow_predicate_pd() {
                     match function {
                         FunctionIR::Rename { existing, new, .. } => {
@@ -574,6 +581,29 @@ impl<'a> PredicatePushDown<'a> {
                                 expr_arena,
                             ))
                         },
+                        FunctionIR::Opaque { .. } => {
+                            // predicates on the columns that the function adds are done here
+                            let input_schema = lp_arena.get(input).schema(lp_arena).into_owned();
+                            let local_predicates = transfer_to_local_by_name(
+                                expr_arena,
+                                &mut acc_predicates,
+                                |name| !input_schema.contains(name),
+                            );
+
+                            let lp = self.pushdown_and_continue(
+                                lp,
+                                acc_predicates,
+                                lp_arena,
+                                expr_arena,
+                                false,
+                            )?;
+                            Ok(self.optional_apply_predicate(
+                                lp,
+                                local_predicates,
+                                lp_arena,
+                                expr_arena,
+                            ))
+                        },
                         _ => self.pushdown_and_continue(
                             lp,
                             acc_predicates,
@@ -418,6 +418,10 @@ impl ProjectionPushDown {
                     FileScan::Csv { .. } => true,
                     #[cfg(feature = "parquet")]
                     FileScan::Parquet { .. } => true,
+                    #[cfg(feature = "avro")]
+                    FileScan::Avro { .. } => true,
+                    #[cfg(feature = "fwf")]
+                    FileScan::Fwf { .. } => true,
                 };
 
                 if do_optimization {
@@ -486,10 +490,14 @@ impl ProjectionPushDown {
                         }
                         Some(Arc::new(schema))
                     } else {
-                        file_options.with_columns = maybe_init_projection_excluding_hive(
-                            file_info.reader_schema.as_ref().unwrap(),
-                            hive_parts.as_ref().map(|x| &x[0]),
-                        );
+                        // Anonymous scans don't have a reader schema, they read all columns.
+                        file_options.with_columns =
+                            file_info.reader_schema.as_ref().and_then(|reader_schema| {
+                                maybe_init_projection_excluding_hive(
+                                    reader_schema,
+                                    hive_parts.as_ref().map(|x| &x[0]),
+                                )
+                            });
                         None
                     };
                 }
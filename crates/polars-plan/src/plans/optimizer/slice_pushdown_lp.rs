@@ -401,7 +401,7 @@ impl SlicePushDown {
                 self.no_pushdown_restart_opt(lp, state, lp_arena, expr_arena)
             },
             // [Pushdown]
-            (MapFunction {input, function}, _) if function.allow_predicate_pd() => {
+            (MapFunction {input, function}, _) if function.allow_slice_pd() => {
                 let lp = MapFunction {input, function};
                 self.pushdown_and_continue(lp, state, lp_arena, expr_arena)
             },
@@ -0,0 +1,54 @@
+//! Optimization rules that crates outside of polars register.
+use std::sync::{Arc, RwLock};
+
+use super::*;
+
+type RuleFactory = Arc<dyn Fn() -> Box<dyn OptimizationRule> + Send + Sync>;
+
+static USER_RULES: RwLock<Vec<(PlSmallStr, RuleFactory)>> = RwLock::new(Vec::new());
+
+/// Register an optimization rule that rewrites the nodes of all the plans that are optimized
+/// afterwards. `factory` creates the rule for every optimization, so rules may keep state.
+///
+/// The rules run after the built-in rules of each node, until no rule changes the plan anymore.
+/// They must not change the schema of the nodes that they rewrite. The helpers of [`pattern`]
+/// match the nodes that rules commonly rewrite.
+pub fn register_optimization_rule<F>(name: &str, factory: F) -> PolarsResult<()>
+where
+    F: Fn() -> Box<dyn OptimizationRule> + Send + Sync + 'static,
+{
+    let mut rules = USER_RULES.write().unwrap();
+    polars_ensure!(
+        !rules.iter().any(|(registered, _)| registered == name),
+        Duplicate: "an optimization rule named '{}' is already registered", name
+    );
+    rules.push((name.into(), Arc::new(factory)));
+    Ok(())
+}
+
+/// Remove the optimization rule registered under `name`. Returns `false` if there is none.
+pub fn unregister_optimization_rule(name: &str) -> bool {
+    let mut rules = USER_RULES.write().unwrap();
+    let len = rules.len();
+    rules.retain(|(registered, _)| registered != name);
+    rules.len() != len
+}
+
+/// The names of the registered optimization rules, in the order in which they run.
+pub fn registered_optimization_rules() -> Vec<PlSmallStr> {
+    USER_RULES
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+pub(super) fn user_rules() -> Vec<Box<dyn OptimizationRule>> {
+    USER_RULES
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(_, factory)| factory())
+        .collect()
+}
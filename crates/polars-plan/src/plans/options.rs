@@ -1,6 +1,10 @@
-#[cfg(feature = "json")]
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use bitflags::bitflags;
 use polars_core::prelude::*;
@@ -13,7 +17,7 @@ use polars_io::ipc::IpcWriterOptions;
 use polars_io::json::JsonWriterOptions;
 #[cfg(feature = "parquet")]
 use polars_io::parquet::write::ParquetWriteOptions;
-use polars_io::{HiveOptions, RowIndex};
+use polars_io::{HiveOptions, PartitionedWriteOptions, RowIndex};
 #[cfg(feature = "dynamic_group_by")]
 use polars_time::{DynamicGroupOptions, RollingGroupOptions};
 #[cfg(feature = "serde")]
@@ -39,6 +43,10 @@ pub struct FileScanOptions {
     pub hive_options: HiveOptions,
     pub glob: bool,
     pub include_file_paths: Option<PlSmallStr>,
+    /// Read at most this number of rows of every file.
+    pub n_rows_per_file: Option<usize>,
+    /// Open and parse at most this number of files at the same time.
+    pub max_concurrent_files: Option<NonZeroUsize>,
 }
 
 #[derive(Clone, Debug, Copy, Default, Eq, PartialEq, Hash)]
@@ -51,6 +59,8 @@ pub struct UnionOptions {
     pub from_partitioned_ds: bool,
     pub flattened_by_opt: bool,
     pub rechunk: bool,
+    /// Execute at most this number of inputs at the same time.
+    pub max_parallel_inputs: Option<NonZeroUsize>,
 }
 
 #[derive(Clone, Debug, Copy, Default, Eq, PartialEq, Hash)]
@@ -301,12 +311,117 @@ pub enum SinkType {
         path: Arc<PathBuf>,
         file_type: FileType,
     },
+    /// Write a file per unique combination of the values of the `partition_by` columns, in a
+    /// hive-partitioned directory layout under `path`.
+    Partitioned {
+        path: Arc<PathBuf>,
+        file_type: FileType,
+        partition_by: Arc<[PlSmallStr]>,
+        options: PartitionedWriteOptions,
+    },
     #[cfg(feature = "cloud")]
     Cloud {
         uri: Arc<String>,
         file_type: FileType,
         cloud_options: Option<polars_io::cloud::CloudOptions>,
     },
+    /// Upload the batches to a Flight service with a `DoPut` call.
+    #[cfg(feature = "flight")]
+    Flight {
+        endpoint: Arc<String>,
+        descriptor: polars_io::flight::FlightDescriptor,
+    },
+    /// Write the file to a writer, such as a socket. A writer can't be serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Writer {
+        writer: SharedWriter,
+        file_type: FileType,
+    },
+    /// Stream the batches into a custom sink of the streaming engine. A sink can't be serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom {
+        sink: SharedSink,
+    },
+    /// Stream the batches into all of the `sinks`, in a single pass over the input.
+    Tee {
+        sinks: Arc<[SinkType]>,
+    },
+}
+
+/// A writer that is shared by the clones of a plan. The sink takes the writer when the query
+/// runs, so that the query can run only once.
+#[derive(Clone)]
+pub struct SharedWriter(Arc<Mutex<Option<Box<dyn Write + Send>>>>);
+
+impl SharedWriter {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Some(Box::new(writer)))))
+    }
+
+    pub fn take(&self) -> PolarsResult<Box<dyn Write + Send>> {
+        self.0.lock().unwrap().take().ok_or_else(
+            || polars_err!(ComputeError: "the writer of this sink can only be written once"),
+        )
+    }
+}
+
+impl Debug for SharedWriter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SharedWriter")
+    }
+}
+
+impl PartialEq for SharedWriter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedWriter {}
+
+impl Hash for SharedWriter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).hash(state)
+    }
+}
+
+/// A custom sink that is shared by the clones of a plan. The sink is type-erased, as the sink
+/// trait is defined by the streaming engine, which takes the sink when the query runs.
+#[derive(Clone)]
+pub struct SharedSink(Arc<Mutex<Option<Box<dyn Any + Send>>>>);
+
+impl SharedSink {
+    pub fn new(sink: impl Any + Send) -> Self {
+        Self(Arc::new(Mutex::new(Some(Box::new(sink)))))
+    }
+
+    pub fn take(&self) -> PolarsResult<Box<dyn Any + Send>> {
+        self.0
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| polars_err!(ComputeError: "a custom sink can only be written once"))
+    }
+}
+
+impl Debug for SharedSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SharedSink")
+    }
+}
+
+impl PartialEq for SharedSink {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedSink {}
+
+impl Hash for SharedSink {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).hash(state)
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -323,6 +438,9 @@ pub enum FileType {
     Parquet(ParquetWriteOptions),
     #[cfg(feature = "ipc")]
     Ipc(IpcWriterOptions),
+    /// The Arrow IPC stream format, which is written without a footer.
+    #[cfg(feature = "ipc_streaming")]
+    IpcStream(IpcWriterOptions),
     #[cfg(feature = "csv")]
     Csv(CsvWriterOptions),
     #[cfg(feature = "json")]
@@ -393,6 +511,7 @@ impl From<UnionArgs> for UnionOptions {
             from_partitioned_ds: args.from_partitioned_ds,
             flattened_by_opt: false,
             rechunk: args.rechunk,
+            max_parallel_inputs: None,
         }
     }
 }
@@ -1,2 +1,25 @@
 pub mod predicate;
 pub mod pyarrow;
+
+use polars_core::prelude::*;
+use pyo3::intern;
+use pyo3::prelude::*;
+
+/// Take the columns of a `polars.DataFrame` that is returned by a Python source.
+pub fn python_df_to_rust(py: Python, df: Bound<PyAny>) -> PolarsResult<DataFrame> {
+    let err = |_| polars_err!(ComputeError: "expected a polars.DataFrame; got {}", df);
+    let pydf = df.getattr(intern!(py, "_df")).map_err(err)?;
+    let raw_parts = pydf
+        .call_method0(intern!(py, "into_raw_parts"))
+        .map_err(err)?;
+    let raw_parts = raw_parts.extract::<(usize, usize, usize)>().unwrap();
+
+    let (ptr, len, cap) = raw_parts;
+    unsafe {
+        Ok(DataFrame::new_no_checks(Vec::from_raw_parts(
+            ptr as *mut Series,
+            len,
+            cap,
+        )))
+    }
+}
@@ -0,0 +1,39 @@
+//! Subplans that several plans have in common, so that they can be computed once.
+use crate::prelude::visitor::IRNode;
+use crate::prelude::*;
+
+/// Find a subplan that all plans at `roots` compute, e.g. the same scans and joins followed by
+/// different filters. The plans must be in the same arena.
+///
+/// Returns the node of the subplan in every plan, in the order of `roots`, or `None` if the
+/// plans have no subplan in common that each of them computes exactly once. The nodes are
+/// visited from the root down, so the subplan that is found is not part of a larger shared one.
+pub fn shared_subplan(
+    roots: &[Node],
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<Vec<Node>> {
+    if roots.len() < 2 {
+        return None;
+    }
+    let is_equal = |a: Node, b: Node| {
+        IRNode::new(a).hashable_and_cmp(lp_arena, expr_arena)
+            == IRNode::new(b).hashable_and_cmp(lp_arena, expr_arena)
+    };
+
+    'candidates: for (candidate, _) in lp_arena.iter(roots[0]) {
+        let mut nodes = Vec::with_capacity(roots.len());
+        for root in roots {
+            let mut found = lp_arena
+                .iter(*root)
+                .map(|(node, _)| node)
+                .filter(|node| is_equal(candidate, *node));
+            match (found.next(), found.next()) {
+                (Some(node), None) => nodes.push(node),
+                _ => continue 'candidates,
+            }
+        }
+        return Some(nodes);
+    }
+    None
+}
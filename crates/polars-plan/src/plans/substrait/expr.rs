@@ -0,0 +1,545 @@
+use polars_core::chunked_array::cast::CastOptions;
+use polars_core::prelude::*;
+use recursive::recursive;
+use serde_json::{json, Map, Value};
+
+use super::{unsupported, Writer};
+use crate::prelude::*;
+
+pub(super) const COMPARISON: &str =
+    "https://github.com/substrait-io/substrait/blob/main/extensions/functions_comparison.yaml";
+pub(super) const BOOLEAN: &str =
+    "https://github.com/substrait-io/substrait/blob/main/extensions/functions_boolean.yaml";
+const ARITHMETIC: &str =
+    "https://github.com/substrait-io/substrait/blob/main/extensions/functions_arithmetic.yaml";
+const AGGREGATE_GENERIC: &str =
+    "https://github.com/substrait-io/substrait/blob/main/extensions/functions_aggregate_generic.yaml";
+
+const NULLABLE: &str = "NULLABILITY_NULLABLE";
+
+/// The fields that an expression refers to: the schema of an input of the relation the
+/// expression is evaluated on, and the position of the first field of that input.
+#[derive(Clone, Copy)]
+pub(super) struct Input<'a> {
+    pub(super) schema: &'a Schema,
+    pub(super) offset: usize,
+}
+
+impl<'a> Input<'a> {
+    pub(super) fn new(schema: &'a Schema) -> Self {
+        Self { schema, offset: 0 }
+    }
+}
+
+fn object(key: &str, value: Value) -> Value {
+    let mut object = Map::new();
+    object.insert(key.to_string(), value);
+    Value::Object(object)
+}
+
+pub(super) fn field_index(schema: &Schema, name: &str) -> PolarsResult<usize> {
+    schema
+        .index_of(name)
+        .ok_or_else(|| polars_err!(ColumnNotFound: "{}", name))
+}
+
+/// The reference to the field at `index` of the input of the relation.
+pub(super) fn field(index: usize) -> Value {
+    json!({
+        "selection": {
+            "directReference": {"structField": {"field": index}},
+            "rootReference": {},
+        }
+    })
+}
+
+/// Push the names of the field and of its nested fields, in depth-first order.
+pub(super) fn field_names(name: &str, dtype: &DataType, names: &mut Vec<String>) {
+    names.push(name.to_string());
+    #[cfg(feature = "dtype-struct")]
+    if let DataType::Struct(fields) = dtype {
+        for field in fields {
+            field_names(field.name(), field.dtype(), names);
+        }
+    }
+    #[cfg(not(feature = "dtype-struct"))]
+    let _ = dtype;
+}
+
+pub(super) fn named_struct(schema: &Schema) -> PolarsResult<Value> {
+    let mut names = vec![];
+    let mut types = vec![];
+    for (name, dtype) in schema.iter() {
+        field_names(name, dtype, &mut names);
+        types.push(data_type(dtype)?);
+    }
+    Ok(json!({
+        "names": names,
+        "struct": {"types": types, "nullability": "NULLABILITY_REQUIRED"},
+    }))
+}
+
+/// The Substrait type of `dtype`. Unsigned integers are represented by the signed integers that
+/// hold all their values.
+pub(super) fn data_type(dtype: &DataType) -> PolarsResult<Value> {
+    use DataType::*;
+    let (kind, mut ty) = match dtype {
+        Boolean => ("bool", json!({})),
+        Int8 => ("i8", json!({})),
+        Int16 | UInt8 => ("i16", json!({})),
+        Int32 | UInt16 => ("i32", json!({})),
+        Int64 | UInt32 => ("i64", json!({})),
+        Float32 => ("fp32", json!({})),
+        Float64 => ("fp64", json!({})),
+        String => ("string", json!({})),
+        Binary => ("binary", json!({})),
+        #[cfg(feature = "dtype-date")]
+        Date => ("date", json!({})),
+        #[cfg(feature = "dtype-datetime")]
+        Datetime(tu, tz) => {
+            let kind = if tz.is_some() {
+                "precisionTimestampTz"
+            } else {
+                "precisionTimestamp"
+            };
+            (kind, json!({ "precision": precision(*tu) }))
+        },
+        #[cfg(feature = "dtype-decimal")]
+        Decimal(precision, scale) => (
+            "decimal",
+            json!({"precision": precision.unwrap_or(38), "scale": scale.unwrap_or(0)}),
+        ),
+        List(inner) => ("list", json!({ "type": data_type(inner)? })),
+        #[cfg(feature = "dtype-struct")]
+        Struct(fields) => {
+            let types = fields
+                .iter()
+                .map(|f| data_type(f.dtype()))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            ("struct", json!({ "types": types }))
+        },
+        dt => return unsupported(&format!("the data type {dt}")),
+    };
+    ty["nullability"] = json!(NULLABLE);
+    Ok(object(kind, ty))
+}
+
+#[cfg(feature = "dtype-datetime")]
+fn precision(tu: TimeUnit) -> u32 {
+    match tu {
+        TimeUnit::Milliseconds => 3,
+        TimeUnit::Microseconds => 6,
+        TimeUnit::Nanoseconds => 9,
+    }
+}
+
+/// The name of `dtype` in the compound names of functions, e.g. `add:i64_i64`.
+fn signature_name(dtype: &DataType) -> PolarsResult<&'static str> {
+    use DataType::*;
+    let name = match dtype {
+        Boolean => "bool",
+        Int8 => "i8",
+        Int16 | UInt8 => "i16",
+        Int32 | UInt16 => "i32",
+        Int64 | UInt32 => "i64",
+        Float32 => "fp32",
+        Float64 => "fp64",
+        String => "str",
+        Binary => "vbin",
+        #[cfg(feature = "dtype-date")]
+        Date => "date",
+        #[cfg(feature = "dtype-datetime")]
+        Datetime(_, None) => "pts",
+        #[cfg(feature = "dtype-datetime")]
+        Datetime(_, Some(_)) => "ptstz",
+        #[cfg(feature = "dtype-decimal")]
+        Decimal(_, _) => "dec",
+        List(_) => "list",
+        #[cfg(feature = "dtype-struct")]
+        Struct(_) => "struct",
+        dt => return unsupported(&format!("the data type {dt}")),
+    };
+    Ok(name)
+}
+
+fn float(v: f64) -> Value {
+    // the JSON encoding of protobuf writes the non-finite floats as strings
+    if v.is_nan() {
+        json!("NaN")
+    } else if v.is_infinite() {
+        json!(if v > 0.0 { "Infinity" } else { "-Infinity" })
+    } else {
+        json!(v)
+    }
+}
+
+/// The Substrait literal of the value `av` of type `dtype`.
+pub(super) fn literal(av: AnyValue, dtype: &DataType) -> PolarsResult<Value> {
+    use AnyValue::*;
+    let literal = match av {
+        Null => object("null", data_type(dtype)?),
+        Boolean(v) => json!({ "boolean": v }),
+        Int8(v) => json!({ "i8": v }),
+        Int16(v) => json!({ "i16": v }),
+        UInt8(v) => json!({ "i16": v }),
+        Int32(v) => json!({ "i32": v }),
+        UInt16(v) => json!({ "i32": v }),
+        // the JSON encoding of protobuf writes 64-bit integers as strings
+        Int64(v) => json!({ "i64": v.to_string() }),
+        UInt32(v) => json!({ "i64": v.to_string() }),
+        Float32(v) => json!({ "fp32": float(v as f64) }),
+        Float64(v) => json!({ "fp64": float(v) }),
+        String(v) => json!({ "string": v }),
+        StringOwned(v) => json!({ "string": v.as_str() }),
+        #[cfg(feature = "dtype-date")]
+        Date(v) => json!({ "date": v }),
+        #[cfg(feature = "dtype-datetime")]
+        Datetime(v, tu, tz) => {
+            let kind = if tz.is_some() {
+                "precisionTimestampTz"
+            } else {
+                "precisionTimestamp"
+            };
+            object(
+                kind,
+                json!({"precision": precision(tu), "value": v.to_string()}),
+            )
+        },
+        List(s) => {
+            let values = s
+                .iter()
+                .map(|av| literal(av, s.dtype()))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            json!({ "list": {"values": values} })
+        },
+        av => return unsupported(&format!("the value {av}")),
+    };
+    Ok(literal)
+}
+
+impl Writer<'_> {
+    /// The function of the extension at `uri` with the compound name `name` applied to the
+    /// `args`.
+    pub(super) fn scalar_function(
+        &mut self,
+        uri: &'static str,
+        name: String,
+        args: Vec<Value>,
+        dtype: &DataType,
+    ) -> PolarsResult<Value> {
+        let anchor = self.extensions.function(uri, name);
+        let arguments = args
+            .into_iter()
+            .map(|arg| json!({ "value": arg }))
+            .collect::<Vec<_>>();
+        Ok(json!({
+            "scalarFunction": {
+                "functionReference": anchor,
+                "arguments": arguments,
+                "outputType": data_type(dtype)?,
+            }
+        }))
+    }
+
+    fn binary(
+        &mut self,
+        (left, op, right): (Node, Operator, Node),
+        input: Input,
+        dtype: &DataType,
+    ) -> PolarsResult<Value> {
+        use Operator::*;
+        let expr_arena = self.expr_arena;
+        let left_dtype =
+            expr_arena
+                .get(left)
+                .to_dtype(input.schema, Context::Default, expr_arena)?;
+        let right_dtype =
+            expr_arena
+                .get(right)
+                .to_dtype(input.schema, Context::Default, expr_arena)?;
+        let mut args = vec![self.expr(left, input)?, self.expr(right, input)?];
+        let is_bool = left_dtype.is_bool() && right_dtype.is_bool();
+
+        let (uri, name) = match op {
+            Eq => (COMPARISON, "equal:any_any".to_string()),
+            NotEq => (COMPARISON, "not_equal:any_any".to_string()),
+            Lt => (COMPARISON, "lt:any_any".to_string()),
+            LtEq => (COMPARISON, "lte:any_any".to_string()),
+            Gt => (COMPARISON, "gt:any_any".to_string()),
+            GtEq => (COMPARISON, "gte:any_any".to_string()),
+            EqValidity => (COMPARISON, "is_not_distinct_from:any_any".to_string()),
+            NotEqValidity => (COMPARISON, "is_distinct_from:any_any".to_string()),
+            And | LogicalAnd if is_bool => (BOOLEAN, "and:bool".to_string()),
+            Or | LogicalOr if is_bool => (BOOLEAN, "or:bool".to_string()),
+            Xor if is_bool => (BOOLEAN, "xor:bool_bool".to_string()),
+            Plus | Minus | Multiply => {
+                let name = match op {
+                    Plus => "add",
+                    Minus => "subtract",
+                    _ => "multiply",
+                };
+                let signature = format!(
+                    "{}_{}",
+                    signature_name(&left_dtype)?,
+                    signature_name(&right_dtype)?
+                );
+                (ARITHMETIC, format!("{name}:{signature}"))
+            },
+            // the division of integers in Substrait truncates, polars floors it
+            Divide if left_dtype.is_float() && right_dtype.is_float() => {
+                let signature = format!(
+                    "{}_{}",
+                    signature_name(&left_dtype)?,
+                    signature_name(&right_dtype)?
+                );
+                (ARITHMETIC, format!("divide:{signature}"))
+            },
+            TrueDivide => {
+                let mut signature = vec![];
+                for (arg, arg_dtype) in args.iter_mut().zip([&left_dtype, &right_dtype]) {
+                    if arg_dtype.is_float() {
+                        signature.push(signature_name(arg_dtype)?);
+                    } else {
+                        *arg = json!({
+                            "cast": {
+                                "type": data_type(&DataType::Float64)?,
+                                "input": arg.take(),
+                                "failureBehavior": "FAILURE_BEHAVIOR_THROW_EXCEPTION",
+                            }
+                        });
+                        signature.push("fp64");
+                    }
+                }
+                (ARITHMETIC, format!("divide:{}", signature.join("_")))
+            },
+            op => return unsupported(&format!("the operator {op}")),
+        };
+        self.scalar_function(uri, name, args, dtype)
+    }
+
+    fn function(
+        &mut self,
+        args: &[ExprIR],
+        function: &FunctionExpr,
+        input: Input,
+        dtype: &DataType,
+    ) -> PolarsResult<Value> {
+        let [arg] = args else {
+            return unsupported(&format!("the function {function}"));
+        };
+        let expr_arena = self.expr_arena;
+        let arg_dtype =
+            expr_arena
+                .get(arg.node())
+                .to_dtype(input.schema, Context::Default, expr_arena)?;
+        let arg_name = signature_name(&arg_dtype)?;
+
+        let (uri, name) = match function {
+            FunctionExpr::Boolean(function) => match function {
+                BooleanFunction::Not if arg_dtype.is_bool() => (BOOLEAN, "not:bool".to_string()),
+                BooleanFunction::IsNull => (COMPARISON, "is_null:any".to_string()),
+                BooleanFunction::IsNotNull => (COMPARISON, "is_not_null:any".to_string()),
+                BooleanFunction::IsNan => (COMPARISON, format!("is_nan:{arg_name}")),
+                BooleanFunction::IsFinite => (COMPARISON, format!("is_finite:{arg_name}")),
+                BooleanFunction::IsInfinite => (COMPARISON, format!("is_infinite:{arg_name}")),
+                _ => return unsupported(&format!("the function {function}")),
+            },
+            FunctionExpr::Negate => (ARITHMETIC, format!("negate:{arg_name}")),
+            #[cfg(feature = "abs")]
+            FunctionExpr::Abs => (ARITHMETIC, format!("abs:{arg_name}")),
+            _ => return unsupported(&format!("the function {function}")),
+        };
+        let arg = self.expr(arg.node(), input)?;
+        self.scalar_function(uri, name, vec![arg], dtype)
+    }
+
+    /// The Substrait expression of the elementwise expression at `node`.
+    #[recursive]
+    pub(super) fn expr(&mut self, node: Node, input: Input) -> PolarsResult<Value> {
+        let expr_arena = self.expr_arena;
+        let ae = expr_arena.get(node);
+        let dtype = || ae.to_dtype(input.schema, Context::Default, expr_arena);
+
+        let expr = match ae {
+            AExpr::Alias(e, _) => self.expr(*e, input)?,
+            AExpr::Column(name) => field(input.offset + field_index(input.schema, name)?),
+            AExpr::Literal(lv) => match lv {
+                // these are columns rather than scalars
+                LiteralValue::Series(_) | LiteralValue::Range { .. } => {
+                    return unsupported("a literal series");
+                },
+                lv => {
+                    let Some(av) = lv.to_any_value() else {
+                        return unsupported(&format!("the literal {lv:?}"));
+                    };
+                    object("literal", literal(av, &lv.get_datatype())?)
+                },
+            },
+            AExpr::BinaryExpr { left, op, right } => {
+                self.binary((*left, *op, *right), input, &dtype()?)?
+            },
+            AExpr::Cast {
+                expr,
+                dtype,
+                options,
+            } => {
+                let failure_behavior = match options {
+                    CastOptions::Strict => "FAILURE_BEHAVIOR_THROW_EXCEPTION",
+                    CastOptions::NonStrict => "FAILURE_BEHAVIOR_RETURN_NULL",
+                    CastOptions::Overflowing => "FAILURE_BEHAVIOR_UNSPECIFIED",
+                };
+                json!({
+                    "cast": {
+                        "type": data_type(dtype)?,
+                        "input": self.expr(*expr, input)?,
+                        "failureBehavior": failure_behavior,
+                    }
+                })
+            },
+            AExpr::Ternary {
+                predicate,
+                truthy,
+                falsy,
+            } => json!({
+                "ifThen": {
+                    "ifs": [{
+                        "if": self.expr(*predicate, input)?,
+                        "then": self.expr(*truthy, input)?,
+                    }],
+                    "else": self.expr(*falsy, input)?,
+                }
+            }),
+            AExpr::Function {
+                input: args,
+                function,
+                ..
+            } => self.function(args, function, input, &dtype()?)?,
+            _ => {
+                return unsupported(&format!(
+                    "the expression {}",
+                    node_to_expr(node, expr_arena)
+                ))
+            },
+        };
+        Ok(expr)
+    }
+
+    fn measure_function(
+        &mut self,
+        uri: &'static str,
+        name: String,
+        args: Vec<Value>,
+        options: Vec<Value>,
+        dtype: &DataType,
+    ) -> PolarsResult<Value> {
+        let anchor = self.extensions.function(uri, name);
+        let arguments = args
+            .into_iter()
+            .map(|arg| json!({ "value": arg }))
+            .collect::<Vec<_>>();
+        Ok(json!({
+            "measure": {
+                "functionReference": anchor,
+                "arguments": arguments,
+                "options": options,
+                "outputType": data_type(dtype)?,
+                "phase": "AGGREGATION_PHASE_INITIAL_TO_RESULT",
+                "invocation": "AGGREGATION_INVOCATION_ALL",
+            }
+        }))
+    }
+
+    /// The Substrait measure of the aggregation at `node` of an elementwise expression.
+    pub(super) fn measure(&mut self, node: Node, input: Input) -> PolarsResult<Value> {
+        use IRAggExpr::*;
+        let expr_arena = self.expr_arena;
+        let ae = expr_arena.get(node);
+        let agg = match ae {
+            AExpr::Alias(e, _) => return self.measure(*e, input),
+            AExpr::Agg(agg) => agg,
+            AExpr::Len => {
+                let dtype = IDX_DTYPE;
+                return self.measure_function(
+                    AGGREGATE_GENERIC,
+                    "count".into(),
+                    vec![],
+                    vec![],
+                    &dtype,
+                );
+            },
+            _ => {
+                return unsupported(&format!(
+                    "the expression {} as an aggregation",
+                    node_to_expr(node, expr_arena)
+                ))
+            },
+        };
+        let dtype = ae.to_dtype(input.schema, Context::Default, expr_arena)?;
+
+        let (uri, name, arg, options) = match agg {
+            Min { input: e, .. } => (ARITHMETIC, "min", *e, vec![]),
+            Max { input: e, .. } => (ARITHMETIC, "max", *e, vec![]),
+            Sum(e) => (ARITHMETIC, "sum", *e, vec![]),
+            Mean(e) => (ARITHMETIC, "avg", *e, vec![]),
+            Count(_, true) => {
+                return self.measure_function(
+                    AGGREGATE_GENERIC,
+                    "count".into(),
+                    vec![],
+                    vec![],
+                    &dtype,
+                );
+            },
+            Count(e, false) => (AGGREGATE_GENERIC, "count", *e, vec![]),
+            Std(e, ddof) | Var(e, ddof) => {
+                let distribution = match ddof {
+                    0 => "POPULATION",
+                    1 => "SAMPLE",
+                    _ => return unsupported(&format!("a ddof of {ddof}")),
+                };
+                let name = if matches!(agg, Std(..)) {
+                    "std_dev"
+                } else {
+                    "variance"
+                };
+                let options = vec![json!({
+                    "name": "distribution",
+                    "preference": [distribution],
+                })];
+                (ARITHMETIC, name, *e, options)
+            },
+            agg => {
+                let agg: &str = agg.into();
+                return unsupported(&format!("the aggregation {agg}"));
+            },
+        };
+        let arg_name = if uri == AGGREGATE_GENERIC {
+            "any"
+        } else {
+            let arg_dtype =
+                expr_arena
+                    .get(arg)
+                    .to_dtype(input.schema, Context::Default, expr_arena)?;
+            signature_name(&arg_dtype)?
+        };
+        let arg = self.expr(arg, input)?;
+        self.measure_function(
+            uri,
+            format!("{name}:{arg_name}"),
+            vec![arg],
+            options,
+            &dtype,
+        )
+    }
+
+    /// The measure of the value of any row of the group of the field at `index`.
+    pub(super) fn any_value(&mut self, index: usize, dtype: &DataType) -> PolarsResult<Value> {
+        self.measure_function(
+            AGGREGATE_GENERIC,
+            "any_value:any".into(),
+            vec![field(index)],
+            vec![],
+            dtype,
+        )
+    }
+}
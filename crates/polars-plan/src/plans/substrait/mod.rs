@@ -0,0 +1,598 @@
+//! Export of plans to [Substrait](https://substrait.io), a vendor-neutral format of relational
+//! plans. The plan is written in the JSON encoding of the Substrait protobuf messages.
+mod expr;
+
+use arrow::legacy::error::to_compute_err;
+use polars_core::prelude::*;
+use recursive::recursive;
+use serde_json::{json, Value};
+
+use self::expr::*;
+use crate::prelude::*;
+
+/// The version of the Substrait specification that the plans conform to.
+const SUBSTRAIT_VERSION: (u32, u32, u32) = (0, 52, 0);
+
+/// The extension functions that a plan refers to, by the anchors of their declarations.
+#[derive(Default)]
+struct Extensions {
+    uris: Vec<&'static str>,
+    // the anchor of the uri and the compound name of every function
+    functions: Vec<(usize, String)>,
+}
+
+impl Extensions {
+    /// The anchor of the function `name` of the extension at `uri`, which is declared on first
+    /// use.
+    fn function(&mut self, uri: &'static str, name: String) -> usize {
+        let uri = match self.uris.iter().position(|u| *u == uri) {
+            Some(i) => i + 1,
+            None => {
+                self.uris.push(uri);
+                self.uris.len()
+            },
+        };
+        match self
+            .functions
+            .iter()
+            .position(|(u, f)| *u == uri && *f == name)
+        {
+            Some(i) => i + 1,
+            None => {
+                self.functions.push((uri, name));
+                self.functions.len()
+            },
+        }
+    }
+}
+
+struct Writer<'a> {
+    lp_arena: &'a Arena<IR>,
+    expr_arena: &'a Arena<AExpr>,
+    extensions: Extensions,
+}
+
+fn unsupported<T>(what: &str) -> PolarsResult<T> {
+    polars_bail!(InvalidOperation: "{} cannot be exported to Substrait", what)
+}
+
+/// The relation of `len` rows of `input` from `offset`.
+fn fetch(input: Value, offset: i64, len: u64) -> PolarsResult<Value> {
+    if offset < 0 {
+        return unsupported("a slice with a negative offset");
+    }
+    // a slice to the end has the maximum length
+    let count = if len >= IdxSize::MAX as u64 {
+        -1
+    } else {
+        len as i64
+    };
+    Ok(json!({
+        "fetch": {
+            "input": input,
+            "offset": offset.to_string(),
+            "count": count.to_string(),
+        }
+    }))
+}
+
+/// The projection of `exprs` of the `n_input` fields of `input`. Only the projected fields are
+/// emitted.
+fn project(input: Value, n_input: usize, exprs: Vec<Value>) -> Value {
+    let emit = (n_input..n_input + exprs.len()).collect::<Vec<_>>();
+    json!({
+        "project": {
+            "common": {"emit": {"outputMapping": emit}},
+            "input": input,
+            "expressions": exprs,
+        }
+    })
+}
+
+/// The read relation of a table of `schema`, of which only the fields of `output_schema` are
+/// emitted and the rows that match the `predicate`.
+fn read(
+    writer: &mut Writer,
+    schema: &Schema,
+    output_schema: Option<&SchemaRef>,
+    predicate: Option<&ExprIR>,
+    table: (&str, Value),
+) -> PolarsResult<Value> {
+    let mut read = json!({"baseSchema": named_struct(schema)?});
+    read[table.0] = table.1;
+    if let Some(predicate) = predicate {
+        read["filter"] = writer.expr(predicate.node(), Input::new(schema))?;
+    }
+    if let Some(output_schema) = output_schema {
+        let items = output_schema
+            .iter_names()
+            .map(|name| Ok(json!({"field": field_index(schema, name)?})))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        read["projection"] = json!({
+            "select": {"structItems": items},
+            "maintainSingularStruct": true,
+        });
+    }
+    Ok(json!({ "read": read }))
+}
+
+fn uri(path: &std::path::Path) -> String {
+    let path = path.to_string_lossy();
+    if path.contains("://") || !path.starts_with('/') {
+        path.into_owned()
+    } else {
+        format!("file://{path}")
+    }
+}
+
+impl Writer<'_> {
+    #[recursive]
+    fn rel(&mut self, node: Node) -> PolarsResult<Value> {
+        use IR::*;
+        let lp_arena = self.lp_arena;
+        let schema = |node: Node| lp_arena.get(node).schema(lp_arena).into_owned();
+
+        let rel = match lp_arena.get(node) {
+            Slice { input, offset, len } => fetch(self.rel(*input)?, *offset, *len as u64)?,
+            Filter { input, predicate } => {
+                let condition = self.expr(predicate.node(), Input::new(&schema(*input)))?;
+                json!({"filter": {"input": self.rel(*input)?, "condition": condition}})
+            },
+            Scan {
+                sources,
+                file_info,
+                hive_parts,
+                predicate,
+                output_schema,
+                scan_type,
+                file_options,
+            } => {
+                if hive_parts.is_some()
+                    || file_options.row_index.is_some()
+                    || file_options.include_file_paths.is_some()
+                    || file_options.n_rows_per_file.is_some()
+                {
+                    return unsupported("a scan with columns that are not in the files");
+                }
+                let Some(paths) = sources.as_paths() else {
+                    return unsupported("a scan of in-memory sources");
+                };
+                let format: (&str, Value) = match scan_type {
+                    #[cfg(feature = "parquet")]
+                    FileScan::Parquet { .. } => ("parquet", json!({})),
+                    #[cfg(feature = "ipc")]
+                    FileScan::Ipc { .. } => ("arrow", json!({})),
+                    #[cfg(feature = "csv")]
+                    FileScan::Csv { options, .. } => {
+                        let parse_options = &options.parse_options;
+                        let quote = parse_options
+                            .quote_char
+                            .map(|c| (c as char).to_string())
+                            .unwrap_or_default();
+                        let header_lines = options.has_header as usize + options.skip_rows;
+                        let text = json!({
+                            "fieldDelimiter":
+                                String::from_utf8_lossy(parse_options.separator.as_bytes()),
+                            "maxLineSize": "0",
+                            "quote": quote,
+                            "headerLinesToSkip": header_lines.to_string(),
+                            "escape": "",
+                        });
+                        ("text", text)
+                    },
+                    _ => {
+                        let format: &str = scan_type.into();
+                        return unsupported(&format!("a {format} scan"));
+                    },
+                };
+                let items = paths
+                    .iter()
+                    .map(|path| {
+                        let mut item = json!({ "uriFile": uri(path) });
+                        item[format.0] = format.1.clone();
+                        item
+                    })
+                    .collect::<Vec<_>>();
+                let table = ("localFiles", json!({ "items": items }));
+                let rel = read(
+                    self,
+                    &file_info.schema,
+                    output_schema.as_ref(),
+                    predicate.as_ref(),
+                    table,
+                )?;
+                match file_options.slice {
+                    Some((offset, len)) => fetch(rel, offset, len as u64)?,
+                    None => rel,
+                }
+            },
+            DataFrameScan {
+                df,
+                schema,
+                output_schema,
+                filter,
+            } => {
+                let values = (0..df.height())
+                    .map(|i| {
+                        let fields = df
+                            .get_columns()
+                            .iter()
+                            .map(|s| literal(s.get(i)?, s.dtype()))
+                            .collect::<PolarsResult<Vec<_>>>()?;
+                        Ok(json!({ "fields": fields }))
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let table = ("virtualTable", json!({ "values": values }));
+                read(self, schema, output_schema.as_ref(), filter.as_ref(), table)?
+            },
+            SimpleProjection { input, columns } => {
+                let input_schema = schema(*input);
+                let exprs = columns
+                    .iter_names()
+                    .map(|name| Ok(field(field_index(&input_schema, name)?)))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                project(self.rel(*input)?, input_schema.len(), exprs)
+            },
+            Select { input, expr, .. } => {
+                let input_schema = schema(*input);
+                let exprs = expr
+                    .iter()
+                    .map(|e| self.expr(e.node(), Input::new(&input_schema)))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                project(self.rel(*input)?, input_schema.len(), exprs)
+            },
+            HStack {
+                input,
+                exprs,
+                schema: output_schema,
+                ..
+            } => {
+                let input_schema = schema(*input);
+                let columns = output_schema
+                    .iter_names()
+                    .map(
+                        |name| match exprs.iter().find(|e| e.output_name() == name) {
+                            Some(e) => self.expr(e.node(), Input::new(&input_schema)),
+                            None => Ok(field(field_index(&input_schema, name)?)),
+                        },
+                    )
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                project(self.rel(*input)?, input_schema.len(), columns)
+            },
+            Reduce { input, exprs, .. } => {
+                let input_schema = schema(*input);
+                let measures = exprs
+                    .iter()
+                    .map(|e| self.measure(e.node(), Input::new(&input_schema)))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                json!({"aggregate": {"input": self.rel(*input)?, "measures": measures}})
+            },
+            Sort {
+                input,
+                by_column,
+                slice,
+                sort_options,
+            } => {
+                let input_schema = schema(*input);
+                let sorts = by_column
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| {
+                        let descending = sort_options
+                            .descending
+                            .get(i)
+                            .or(sort_options.descending.first());
+                        let nulls_last = sort_options
+                            .nulls_last
+                            .get(i)
+                            .or(sort_options.nulls_last.first());
+                        let direction = match (
+                            descending.copied().unwrap_or(false),
+                            nulls_last.copied().unwrap_or(false),
+                        ) {
+                            (false, false) => "SORT_DIRECTION_ASC_NULLS_FIRST",
+                            (false, true) => "SORT_DIRECTION_ASC_NULLS_LAST",
+                            (true, false) => "SORT_DIRECTION_DESC_NULLS_FIRST",
+                            (true, true) => "SORT_DIRECTION_DESC_NULLS_LAST",
+                        };
+                        let expr = self.expr(e.node(), Input::new(&input_schema))?;
+                        Ok(json!({"expr": expr, "direction": direction}))
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let rel = json!({"sort": {"input": self.rel(*input)?, "sorts": sorts}});
+                match slice {
+                    Some((offset, len)) => fetch(rel, *offset, *len as u64)?,
+                    None => rel,
+                }
+            },
+            // the cached relation is written in full at every use
+            Cache { input, .. } => self.rel(*input)?,
+            GroupBy {
+                input,
+                keys,
+                aggs,
+                apply,
+                options,
+                ..
+            } => {
+                if apply.is_some() {
+                    return unsupported("a group_by with a function");
+                }
+                #[cfg(feature = "dynamic_group_by")]
+                if options.dynamic.is_some() || options.rolling.is_some() {
+                    return unsupported("a group_by over windows");
+                }
+                let input_schema = schema(*input);
+                let keys = keys
+                    .iter()
+                    .map(|e| self.expr(e.node(), Input::new(&input_schema)))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let measures = aggs
+                    .iter()
+                    .map(|e| self.measure(e.node(), Input::new(&input_schema)))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let rel = json!({
+                    "aggregate": {
+                        "input": self.rel(*input)?,
+                        "groupings": [{"groupingExpressions": keys}],
+                        "measures": measures,
+                    }
+                });
+                match options.slice {
+                    Some((offset, len)) => fetch(rel, offset, len as u64)?,
+                    None => rel,
+                }
+            },
+            Join {
+                input_left,
+                input_right,
+                schema: output_schema,
+                left_on,
+                right_on,
+                options,
+            } => self.join(
+                (*input_left, *input_right),
+                output_schema,
+                (left_on, right_on),
+                options,
+            )?,
+            Distinct { input, options } => {
+                if !matches!(options.keep_strategy, UniqueKeepStrategy::Any) {
+                    return unsupported("a unique that keeps a particular row");
+                }
+                let input_schema = schema(*input);
+                let subset = match &options.subset {
+                    Some(subset) => subset
+                        .iter()
+                        .map(|name| field_index(&input_schema, name))
+                        .collect::<PolarsResult<Vec<_>>>()?,
+                    None => (0..input_schema.len()).collect(),
+                };
+                let keys = subset.iter().map(|i| field(*i)).collect::<Vec<_>>();
+                // the other columns take the value of any row of the group
+                let mut others = vec![];
+                let mut measures = vec![];
+                for (i, (_, dtype)) in input_schema.iter().enumerate() {
+                    if !subset.contains(&i) {
+                        others.push(i);
+                        measures.push(self.any_value(i, dtype)?);
+                    }
+                }
+                let mut rel = json!({
+                    "aggregate": {
+                        "input": self.rel(*input)?,
+                        "groupings": [{"groupingExpressions": keys}],
+                        "measures": measures,
+                    }
+                });
+                if !others.is_empty() {
+                    // the columns in the order of the input
+                    let columns = (0..input_schema.len())
+                        .map(|i| match subset.iter().position(|key| *key == i) {
+                            Some(pos) => field(pos),
+                            None => {
+                                field(subset.len() + others.iter().position(|o| *o == i).unwrap())
+                            },
+                        })
+                        .collect::<Vec<_>>();
+                    rel = project(rel, input_schema.len(), columns);
+                }
+                match options.slice {
+                    Some((offset, len)) => fetch(rel, offset, len as u64)?,
+                    None => rel,
+                }
+            },
+            MapFunction { input, function } => match function {
+                // the fields are positional, their names only matter at the root
+                FunctionIR::Rename { .. } | FunctionIR::Rechunk | FunctionIR::Hints(_) => {
+                    self.rel(*input)?
+                },
+                _ => return unsupported(&format!("the function {function}")),
+            },
+            Union { inputs, options } => {
+                let inputs = inputs
+                    .iter()
+                    .map(|input| self.rel(*input))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let rel = json!({"set": {"inputs": inputs, "op": "SET_OP_UNION_ALL"}});
+                match options.slice {
+                    Some((offset, len)) => fetch(rel, offset, len as u64)?,
+                    None => rel,
+                }
+            },
+            lp => return unsupported(&format!("a {} node", lp.name())),
+        };
+        Ok(rel)
+    }
+
+    fn join(
+        &mut self,
+        (left, right): (Node, Node),
+        output_schema: &Schema,
+        (left_on, right_on): (&[ExprIR], &[ExprIR]),
+        options: &JoinOptions,
+    ) -> PolarsResult<Value> {
+        let lp_arena = self.lp_arena;
+        let left_schema = lp_arena.get(left).schema(lp_arena).into_owned();
+        let right_schema = lp_arena.get(right).schema(lp_arena).into_owned();
+        let args = &options.args;
+
+        let join_type = match args.how {
+            JoinType::Inner => "JOIN_TYPE_INNER",
+            JoinType::Left => "JOIN_TYPE_LEFT",
+            JoinType::Full => "JOIN_TYPE_OUTER",
+            #[cfg(feature = "semi_anti_join")]
+            JoinType::Semi => "JOIN_TYPE_LEFT_SEMI",
+            #[cfg(feature = "semi_anti_join")]
+            JoinType::Anti => "JOIN_TYPE_LEFT_ANTI",
+            JoinType::Cross => "JOIN_TYPE_INNER",
+            _ => return unsupported(&format!("a {} join", args.how)),
+        };
+
+        // the fields of the right input follow the fields of the left input
+        let left_input = Input::new(&left_schema);
+        let right_input = Input {
+            schema: &right_schema,
+            offset: left_schema.len(),
+        };
+        let mut keys = Vec::with_capacity(left_on.len());
+        let mut condition = vec![];
+        for (l, r) in left_on.iter().zip(right_on) {
+            let l = self.expr(l.node(), left_input)?;
+            let r = self.expr(r.node(), right_input)?;
+            let function = if args.join_nulls {
+                "is_not_distinct_from"
+            } else {
+                "equal"
+            };
+            condition.push(self.scalar_function(
+                COMPARISON,
+                format!("{function}:any_any"),
+                vec![l.clone(), r.clone()],
+                &DataType::Boolean,
+            )?);
+            keys.push((l, r));
+        }
+        let condition = match condition.len() {
+            0 => json!({"literal": {"boolean": true}}),
+            1 => condition.pop().unwrap(),
+            _ => self.scalar_function(
+                BOOLEAN,
+                "and:bool".to_string(),
+                condition,
+                &DataType::Boolean,
+            )?,
+        };
+
+        let mut rel = json!({
+            "join": {
+                "left": self.rel(left)?,
+                "right": self.rel(right)?,
+                "expression": condition,
+                "type": join_type,
+            }
+        });
+
+        if !matches!(join_type, "JOIN_TYPE_LEFT_SEMI" | "JOIN_TYPE_LEFT_ANTI") {
+            // The output of the join has all the fields of both inputs, project them to the
+            // columns of the polars join.
+            let coalesce_full = matches!(args.how, JoinType::Full) && args.should_coalesce();
+            let suffix = args.suffix().as_str();
+            let columns = output_schema
+                .iter()
+                .map(|(name, dtype)| {
+                    if let Some(i) = left_schema.index_of(name) {
+                        let key = left_on.iter().position(|e| {
+                            matches!(self.expr_arena.get(e.node()), AExpr::Column(c) if c == name)
+                        });
+                        return match key {
+                            Some(key) if coalesce_full => {
+                                let (l, r) = keys[key].clone();
+                                self.scalar_function(
+                                    COMPARISON,
+                                    "coalesce:any_any".to_string(),
+                                    vec![l, r],
+                                    dtype,
+                                )
+                            },
+                            _ => Ok(field(i)),
+                        };
+                    }
+                    let right_name = right_schema
+                        .index_of(name)
+                        .or_else(|| right_schema.index_of(name.strip_suffix(suffix)?));
+                    match right_name {
+                        Some(i) => Ok(field(left_schema.len() + i)),
+                        None => polars_bail!(ColumnNotFound: "{}", name),
+                    }
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            rel = project(rel, left_schema.len() + right_schema.len(), columns);
+        }
+        match args.slice {
+            Some((offset, len)) => fetch(rel, offset, len as u64),
+            None => Ok(rel),
+        }
+    }
+}
+
+impl IRPlanRef<'_> {
+    /// Write the plan as a [Substrait](https://substrait.io) plan in the JSON encoding of its
+    /// protobuf messages, so that it can be executed by other engines.
+    ///
+    /// Errors if the plan has nodes or expressions that have no equivalent in Substrait.
+    pub fn to_substrait(self) -> PolarsResult<String> {
+        let plan = self.extract_streaming_plan().unwrap_or(self);
+        let mut writer = Writer {
+            lp_arena: plan.lp_arena,
+            expr_arena: plan.expr_arena,
+            extensions: Extensions::default(),
+        };
+        let input = writer.rel(plan.lp_top)?;
+        let schema = plan.root().schema(plan.lp_arena);
+        let mut names = vec![];
+        for (name, dtype) in schema.iter() {
+            field_names(name, dtype, &mut names);
+        }
+
+        let Extensions { uris, functions } = writer.extensions;
+        let uris = uris
+            .iter()
+            .enumerate()
+            .map(|(i, uri)| json!({"extensionUriAnchor": i + 1, "uri": uri}))
+            .collect::<Vec<_>>();
+        let functions = functions
+            .iter()
+            .enumerate()
+            .map(|(i, (uri, name))| {
+                json!({
+                    "extensionFunction": {
+                        "extensionUriReference": uri,
+                        "functionAnchor": i + 1,
+                        "name": name,
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        let (major, minor, patch) = SUBSTRAIT_VERSION;
+        let plan = json!({
+            "version": {
+                "majorNumber": major,
+                "minorNumber": minor,
+                "patchNumber": patch,
+                "producer": "polars",
+            },
+            "extensionUris": uris,
+            "extensions": functions,
+            "relations": [{"root": {"input": input, "names": names}}],
+        });
+        serde_json::to_string(&plan).map_err(to_compute_err)
+    }
+}
+
+impl IRPlan {
+    /// See [`IRPlanRef::to_substrait`].
+    pub fn to_substrait(&self) -> PolarsResult<String> {
+        self.as_ref().to_substrait()
+    }
+}
@@ -384,3 +384,43 @@ pub fn merge_schemas(schemas: &[SchemaRef]) -> PolarsResult<Schema> {
 
     Ok(merged_schema)
 }
+
+/// Convert the `tolerance_str` of an asof join, e.g. "5m", to a tolerance in the unit of the asof
+/// key, of which `dtype` is the data type.
+#[cfg(feature = "asof_join")]
+pub fn resolve_asof_tolerance(options: &mut AsOfOptions, dtype: &DataType) -> PolarsResult<()> {
+    use polars_core::utils::arrow::temporal_conversions::MILLISECONDS_IN_DAY;
+    use DataType::*;
+
+    let Some(tol) = &options.tolerance_str else {
+        return Ok(());
+    };
+    let duration = polars_time::Duration::parse(tol);
+    polars_ensure!(
+        duration.months() == 0,
+        ComputeError: "cannot use month offset in timedelta of an asof join; \
+        consider using 4 weeks"
+    );
+    match dtype {
+        Datetime(tu, _) | Duration(tu) => {
+            let tolerance = match tu {
+                TimeUnit::Nanoseconds => duration.duration_ns(),
+                TimeUnit::Microseconds => duration.duration_us(),
+                TimeUnit::Milliseconds => duration.duration_ms(),
+            };
+            options.tolerance = Some(AnyValue::from(tolerance))
+        },
+        Date => {
+            let days = (duration.duration_ms() / MILLISECONDS_IN_DAY) as i32;
+            options.tolerance = Some(AnyValue::from(days))
+        },
+        Time => {
+            let tolerance = duration.duration_ns();
+            options.tolerance = Some(AnyValue::from(tolerance))
+        },
+        _ => {
+            polars_bail!(InvalidOperation: "can only use timedelta string language with Date/Datetime/Duration/Time dtypes")
+        },
+    }
+    Ok(())
+}
@@ -59,6 +59,7 @@ impl PyBatchedCsv {
         decimal_comma: bool,
     ) -> PyResult<PyBatchedCsv> {
         let null_values = null_values.map(|w| w.0);
+        let separator = Separator::try_from(separator).map_err(PyPolarsErr::from)?;
         let eol_char = eol_char.as_bytes()[0];
         let row_index = row_index.map(|(name, offset)| RowIndex {
             name: name.into(),
@@ -112,7 +113,7 @@ impl PyBatchedCsv {
             .with_raise_if_empty(raise_if_empty)
             .with_parse_options(
                 CsvParseOptions::default()
-                    .with_separator(separator.as_bytes()[0])
+                    .with_separator(separator)
                     .with_encoding(encoding.0)
                     .with_missing_is_null(!missing_utf8_is_empty_string)
                     .with_comment_prefix(comment_prefix)
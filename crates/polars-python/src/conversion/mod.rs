@@ -135,6 +135,12 @@ impl<'a> FromPyObject<'a> for Wrap<NullValues> {
                     .map(|(a, b)| ((&*a).into(), (&*b).into()))
                     .collect(),
             )))
+        } else if let Ok(s) = ob.extract::<Vec<(PyBackedStr, Vec<PyBackedStr>)>>() {
+            Ok(Wrap(NullValues::NamedMultiple(
+                s.into_iter()
+                    .map(|(a, b)| ((&*a).into(), b.into_iter().map(|x| (&*x).into()).collect()))
+                    .collect(),
+            )))
         } else {
             Err(
                 PyPolarsErr::Other("could not extract value from null_values argument".into())
@@ -820,9 +826,13 @@ impl<'py> FromPyObject<'py> for Wrap<CsvEncoding> {
         let parsed = match &*ob.extract::<PyBackedStr>()? {
             "utf8" => CsvEncoding::Utf8,
             "utf8-lossy" => CsvEncoding::LossyUtf8,
+            "latin1" => CsvEncoding::Latin1,
+            "windows-1252" => CsvEncoding::Windows1252,
+            "utf16-le" => CsvEncoding::Utf16Le,
+            "utf16-be" => CsvEncoding::Utf16Be,
             v => {
                 return Err(PyValueError::new_err(format!(
-                    "csv `encoding` must be one of {{'utf8', 'utf8-lossy'}}, got {v}",
+                    "csv `encoding` must be one of {{'utf8', 'utf8-lossy', 'latin1', 'windows-1252', 'utf16-le', 'utf16-be'}}, got {v}",
                 )))
             },
         };
@@ -1210,6 +1220,49 @@ pub(crate) fn parse_fill_null_strategy(
     Ok(parsed)
 }
 
+#[cfg(feature = "csv")]
+pub(crate) fn parse_csv_compression(
+    compression: &str,
+    compression_level: Option<i32>,
+) -> PyResult<CsvCompression> {
+    let parsed = match compression {
+        "uncompressed" => CsvCompression::Uncompressed,
+        "gzip" => CsvCompression::Gzip(
+            compression_level
+                .map(|lvl| {
+                    u32::try_from(lvl)
+                        .ok()
+                        .filter(|lvl| *lvl <= 9)
+                        .ok_or_else(|| {
+                            PyValueError::new_err(format!(
+                                "gzip `compression_level` must be between 0 and 9, got {lvl}"
+                            ))
+                        })
+                })
+                .transpose()?,
+        ),
+        "zstd" => CsvCompression::Zstd(
+            compression_level
+                .map(|lvl| {
+                    if (1..=22).contains(&lvl) {
+                        Ok(lvl)
+                    } else {
+                        Err(PyValueError::new_err(format!(
+                            "zstd `compression_level` must be between 1 and 22, got {lvl}"
+                        )))
+                    }
+                })
+                .transpose()?,
+        ),
+        e => {
+            return Err(PyValueError::new_err(format!(
+                "csv `compression` must be one of {{'uncompressed', 'gzip', 'zstd'}}, got {e}",
+            )))
+        },
+    };
+    Ok(parsed)
+}
+
 #[cfg(feature = "parquet")]
 pub(crate) fn parse_parquet_compression(
     compression: &str,
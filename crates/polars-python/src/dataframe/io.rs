@@ -68,6 +68,7 @@ impl PyDataFrame {
         schema: Option<Wrap<Schema>>,
     ) -> PyResult<Self> {
         let null_values = null_values.map(|w| w.0);
+        let separator = Separator::try_from(separator).map_err(PyPolarsErr::from)?;
         let eol_char = eol_char.as_bytes()[0];
         let row_index = row_index.map(|(name, offset)| RowIndex {
             name: name.into(),
@@ -117,7 +118,7 @@ impl PyDataFrame {
                 .with_raise_if_empty(raise_if_empty)
                 .with_parse_options(
                     CsvParseOptions::default()
-                        .with_separator(separator.as_bytes()[0])
+                        .with_separator(separator)
                         .with_encoding(encoding.0)
                         .with_missing_is_null(!missing_utf8_is_empty_string)
                         .with_comment_prefix(comment_prefix)
@@ -404,10 +405,15 @@ impl PyDataFrame {
             py.allow_threads(|| {
                 let write_options = ParquetWriteOptions {
                     compression,
+                    column_compression: vec![],
+                    column_encoding: vec![],
+                    bloom_filter_columns: vec![],
                     statistics: statistics.0,
                     row_group_size,
                     data_page_size,
+                    dictionary_page_size: None,
                     maintain_order: true,
+                    encryption: None,
                 };
                 write_partitioned_dataset(
                     &mut self.df,
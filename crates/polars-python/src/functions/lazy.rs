@@ -113,18 +113,11 @@ pub fn col(name: &str) -> PyExpr {
 
 #[pyfunction]
 pub fn collect_all(lfs: Vec<PyLazyFrame>, py: Python) -> PyResult<Vec<PyDataFrame>> {
-    use polars_core::utils::rayon::prelude::*;
-
     let out = py.allow_threads(|| {
-        polars_core::POOL.install(|| {
-            lfs.par_iter()
-                .map(|lf| {
-                    let df = lf.ldf.clone().collect()?;
-                    Ok(PyDataFrame::new(df))
-                })
-                .collect::<polars_core::error::PolarsResult<Vec<_>>>()
-                .map_err(PyPolarsErr::from)
-        })
+        let lfs = lfs.into_iter().map(|lf| lf.ldf).collect::<Vec<_>>();
+        dsl::collect_all(lfs)
+            .map(|dfs| dfs.into_iter().map(PyDataFrame::new).collect::<Vec<_>>())
+            .map_err(PyPolarsErr::from)
     });
 
     Ok(out?)
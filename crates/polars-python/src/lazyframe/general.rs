@@ -149,7 +149,7 @@ impl PyLazyFrame {
     ) -> PyResult<Self> {
         let null_values = null_values.map(|w| w.0);
         let quote_char = quote_char.map(|s| s.as_bytes()[0]);
-        let separator = separator.as_bytes()[0];
+        let separator = Separator::try_from(separator).map_err(PyPolarsErr::from)?;
         let eol_char = eol_char.as_bytes()[0];
         let row_index = row_index.map(|(name, offset)| RowIndex {
             name: name.into(),
@@ -287,6 +287,10 @@ impl PyLazyFrame {
             hive_options,
             glob,
             include_file_paths: include_file_paths.map(|x| x.into()),
+            decryption: None,
+            schema_evolution: Default::default(),
+            n_rows_per_file: None,
+            max_concurrent_files: None,
         };
 
         let sources = sources.0;
@@ -347,6 +351,8 @@ impl PyLazyFrame {
             cloud_options: None,
             hive_options,
             include_file_paths: include_file_paths.map(|x| x.into()),
+            n_rows_per_file: None,
+            max_concurrent_files: None,
         };
 
         let sources = sources.0;
@@ -616,7 +622,7 @@ impl PyLazyFrame {
     }
 
     #[cfg(all(feature = "streaming", feature = "parquet"))]
-    #[pyo3(signature = (path, compression, compression_level, statistics, row_group_size, data_page_size, maintain_order))]
+    #[pyo3(signature = (path, compression, compression_level, statistics, row_group_size, data_page_size, maintain_order, cloud_options, retries))]
     fn sink_parquet(
         &self,
         py: Python,
@@ -627,17 +633,39 @@ impl PyLazyFrame {
         row_group_size: Option<usize>,
         data_page_size: Option<usize>,
         maintain_order: bool,
+        cloud_options: Option<Vec<(String, String)>>,
+        retries: usize,
     ) -> PyResult<()> {
         let compression = parse_parquet_compression(compression, compression_level)?;
 
         let options = ParquetWriteOptions {
             compression,
+            column_compression: vec![],
+            column_encoding: vec![],
+            bloom_filter_columns: vec![],
             statistics: statistics.0,
             row_group_size,
             data_page_size,
+            dictionary_page_size: None,
             maintain_order,
+            encryption: None,
         };
 
+        #[cfg(feature = "cloud")]
+        if polars_io::is_cloud_url(&path) {
+            let uri = path.to_string_lossy().into_owned();
+            let cloud_options = parse_cloud_options(&uri, cloud_options.unwrap_or_default())?
+                .with_max_retries(retries);
+            py.allow_threads(|| {
+                let ldf = self.ldf.clone();
+                ldf.sink_parquet_cloud(uri, Some(cloud_options), options)
+                    .map_err(PyPolarsErr::from)
+            })?;
+            return Ok(());
+        }
+        #[cfg(not(feature = "cloud"))]
+        let _ = (cloud_options, retries);
+
         // if we don't allow threads and we have udfs trying to acquire the gil from different
         // threads we deadlock.
         py.allow_threads(|| {
@@ -671,7 +699,7 @@ impl PyLazyFrame {
     }
 
     #[cfg(all(feature = "streaming", feature = "csv"))]
-    #[pyo3(signature = (path, include_bom, include_header, separator, line_terminator, quote_char, batch_size, datetime_format, date_format, time_format, float_scientific, float_precision, null_value, quote_style, maintain_order))]
+    #[pyo3(signature = (path, include_bom, include_header, separator, line_terminator, quote_char, batch_size, datetime_format, date_format, time_format, float_scientific, float_precision, null_value, quote_style, maintain_order, compression, compression_level))]
     fn sink_csv(
         &self,
         py: Python,
@@ -690,7 +718,10 @@ impl PyLazyFrame {
         null_value: Option<String>,
         quote_style: Option<Wrap<QuoteStyle>>,
         maintain_order: bool,
+        compression: &str,
+        compression_level: Option<i32>,
     ) -> PyResult<()> {
+        let compression = parse_csv_compression(compression, compression_level)?;
         let quote_style = quote_style.map_or(QuoteStyle::default(), |wrap| wrap.0);
         let null_value = null_value.unwrap_or(SerializeOptions::default().null);
 
@@ -713,6 +744,7 @@ impl PyLazyFrame {
             maintain_order,
             batch_size,
             serialize_options,
+            compression,
         };
 
         // if we don't allow threads and we have udfs trying to acquire the gil from different
@@ -726,15 +758,30 @@ impl PyLazyFrame {
 
     #[allow(clippy::too_many_arguments)]
     #[cfg(all(feature = "streaming", feature = "json"))]
-    #[pyo3(signature = (path, maintain_order))]
-    fn sink_json(&self, py: Python, path: PathBuf, maintain_order: bool) -> PyResult<()> {
-        let options = JsonWriterOptions { maintain_order };
+    #[pyo3(signature = (path, maintain_order, date_format, time_format, datetime_format, null_value))]
+    fn sink_json(
+        &self,
+        py: Python,
+        path: PathBuf,
+        maintain_order: bool,
+        date_format: Option<String>,
+        time_format: Option<String>,
+        datetime_format: Option<String>,
+        null_value: Option<String>,
+    ) -> PyResult<()> {
+        let options = JsonWriterOptions {
+            maintain_order,
+            date_format,
+            time_format,
+            datetime_format,
+            null_value,
+        };
 
         // if we don't allow threads and we have udfs trying to acquire the gil from different
         // threads we deadlock.
         py.allow_threads(|| {
             let ldf = self.ldf.clone();
-            ldf.sink_json(path, options).map_err(PyPolarsErr::from)
+            ldf.sink_ndjson(path, options).map_err(PyPolarsErr::from)
         })?;
         Ok(())
     }
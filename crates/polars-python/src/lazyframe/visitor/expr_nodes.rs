@@ -799,6 +799,10 @@ pub(crate) fn into_py(py: Python<'_>, expr: &AExpr) -> PyResult<PyObject> {
                         pat.as_str(),
                     )
                         .to_object(py),
+                    #[cfg(feature = "extract_groups")]
+                    StringFunction::ExtractAllGroups { .. } => {
+                        return Err(PyNotImplementedError::new_err("extract all groups"))
+                    },
                     #[cfg(feature = "regex")]
                     StringFunction::Find { literal, strict } => {
                         (PyStringFunction::Find.into_py(py), literal, strict).to_object(py)
@@ -831,6 +835,26 @@ pub(crate) fn into_py(py: Python<'_>, expr: &AExpr) -> PyResult<PyObject> {
                     StringFunction::Reverse => {
                         (PyStringFunction::Reverse.into_py(py),).to_object(py)
                     },
+                    StringFunction::Levenshtein { .. }
+                    | StringFunction::Hamming { .. }
+                    | StringFunction::JaroWinkler => {
+                        return Err(PyNotImplementedError::new_err("string similarity"))
+                    },
+                    StringFunction::CollationKey(_) => {
+                        return Err(PyNotImplementedError::new_err("collation key"))
+                    },
+                    StringFunction::Normalize { .. } => {
+                        return Err(PyNotImplementedError::new_err("unicode normalization"))
+                    },
+                    StringFunction::FormatValue(_) => {
+                        return Err(PyNotImplementedError::new_err("format value"))
+                    },
+                    StringFunction::Tokenize(_) | StringFunction::NGrams { .. } => {
+                        return Err(PyNotImplementedError::new_err("tokenize"))
+                    },
+                    StringFunction::Soundex | StringFunction::Metaphone => {
+                        return Err(PyNotImplementedError::new_err("phonetic encoding"))
+                    },
                     StringFunction::PadStart { length, fill_char } => {
                         (PyStringFunction::PadStart.into_py(py), length, fill_char).to_object(py)
                     },
@@ -878,6 +902,9 @@ pub(crate) fn into_py(py: Python<'_>, expr: &AExpr) -> PyResult<PyObject> {
                     StringFunction::SplitN(n) => {
                         (PyStringFunction::SplitN.into_py(py), n).to_object(py)
                     },
+                    StringFunction::SplitToStruct { .. } => {
+                        return Err(PyNotImplementedError::new_err("split to struct"))
+                    },
                     StringFunction::Strptime(_, options) => (
                         PyStringFunction::Strptime.into_py(py),
                         options
@@ -1296,6 +1323,7 @@ pub(crate) fn into_py(py: Python<'_>, expr: &AExpr) -> PyResult<PyObject> {
                 },
                 FunctionExpr::Reinterpret(signed) => ("reinterpret", signed).to_object(py),
                 FunctionExpr::ExtendConstant => ("extend_constant",).to_object(py),
+                FunctionExpr::Param { .. } => return Err(PyNotImplementedError::new_err("param")),
                 FunctionExpr::Business(_) => {
                     return Err(PyNotImplementedError::new_err("business"))
                 },
@@ -370,6 +370,12 @@ pub(crate) fn into_py(py: Python<'_>, plan: &IR) -> PyResult<PyObject> {
                         .map_err(|err| PyValueError::new_err(format!("{err:?}")))?;
                     ("ndjson", options).into_py(py)
                 },
+                #[cfg(feature = "avro")]
+                FileScan::Avro { .. } => return Err(PyNotImplementedError::new_err("avro scan")),
+                #[cfg(feature = "fwf")]
+                FileScan::Fwf { .. } => {
+                    return Err(PyNotImplementedError::new_err("fixed-width scan"))
+                },
                 FileScan::Anonymous { .. } => {
                     return Err(PyNotImplementedError::new_err("anonymous scan"))
                 },
@@ -548,12 +554,15 @@ pub(crate) fn into_py(py: Python<'_>, plan: &IR) -> PyResult<PyObject> {
                     predicate_pd: _,
                     projection_pd: _,
                     streamable: _,
+                    properties: _,
                     fmt_str: _,
                 } => return Err(PyNotImplementedError::new_err("opaque rust mapfunction")),
                 FunctionIR::Pipeline {
                     function: _,
                     schema: _,
                     original: _,
+                    layout: _,
+                    metrics: _,
                 } => return Err(PyNotImplementedError::new_err("pipeline mapfunction")),
                 FunctionIR::Unnest { columns } => (
                     "unnest",
@@ -561,6 +570,7 @@ pub(crate) fn into_py(py: Python<'_>, plan: &IR) -> PyResult<PyObject> {
                 )
                     .to_object(py),
                 FunctionIR::Rechunk => ("rechunk",).to_object(py),
+                FunctionIR::Hints(_) => ("hints",).to_object(py),
                 #[cfg(feature = "merge_sorted")]
                 FunctionIR::MergeSorted { column } => {
                     ("merge_sorted", column.to_string()).to_object(py)
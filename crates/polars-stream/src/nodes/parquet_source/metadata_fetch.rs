@@ -2,9 +2,11 @@ use std::sync::Arc;
 
 use futures::StreamExt;
 use polars_error::PolarsResult;
+use polars_io::parquet::encryption::ParquetDecryption;
 use polars_io::prelude::FileMetadata;
 use polars_io::utils::byte_source::{DynByteSource, MemSliceByteSource};
 use polars_io::utils::slice::SplitSlicePosition;
+use polars_parquet::parquet::read::{deserialize_metadata_with_decryption, footer_is_encrypted};
 use polars_utils::mmap::MemSlice;
 use polars_utils::pl_str::PlSmallStr;
 
@@ -110,6 +112,7 @@ impl ParquetSourceNode {
         };
 
         let first_metadata = self.first_metadata.clone();
+        let decryption = self.options.decryption.clone();
 
         let process_metadata_bytes = {
             move |handle: task_handles_ext::AbortOnDropHandle<
@@ -117,6 +120,7 @@ impl ParquetSourceNode {
             >| {
                 let projected_arrow_fields = projected_arrow_fields.clone();
                 let first_metadata = first_metadata.clone();
+                let decryption = decryption.clone();
                 // Run on CPU runtime - metadata deserialization is expensive, especially
                 // for very wide tables.
                 let handle = async_executor::spawn(TaskPriority::Low, async move {
@@ -125,9 +129,13 @@ impl ParquetSourceNode {
                     let metadata = if path_index == 0 {
                         Arc::unwrap_or_clone(first_metadata)
                     } else {
-                        polars_parquet::parquet::read::deserialize_metadata(
-                            metadata_bytes.as_ref(),
-                            metadata_bytes.len() * 2 + 1024,
+                        let (metadata_bytes, footer) = metadata_bytes.split_at(
+                            metadata_bytes.len() - polars_parquet::parquet::FOOTER_SIZE as usize,
+                        );
+                        deserialize_metadata_with_decryption(
+                            metadata_bytes,
+                            footer_is_encrypted(&footer[4..])?,
+                            decryption.as_ref().map(ParquetDecryption::properties),
                         )?
                     };
 
@@ -12,7 +12,7 @@ pub(super) async fn read_parquet_metadata_bytes(
     verbose: bool,
 ) -> PolarsResult<(MemSlice, Option<MemSlice>)> {
     use polars_parquet::parquet::error::ParquetError;
-    use polars_parquet::parquet::PARQUET_MAGIC;
+    use polars_parquet::parquet::{PARQUET_MAGIC, PARQUET_MAGIC_ENCRYPTED_FOOTER};
 
     const FOOTER_HEADER_SIZE: usize = polars_parquet::parquet::FOOTER_SIZE as usize;
 
@@ -42,10 +42,11 @@ pub(super) async fn read_parquet_metadata_bytes(
     let (v, remaining) = footer_header_bytes.split_at(4);
     let footer_size = i32::from_le_bytes(v.try_into().unwrap());
 
-    if remaining != PARQUET_MAGIC {
+    if remaining != PARQUET_MAGIC && remaining != PARQUET_MAGIC_ENCRYPTED_FOOTER {
         return Err(ParquetError::OutOfSpec(format!(
-            r#"expected parquet magic bytes "{}" in footer, got "{}" instead"#,
+            r#"expected parquet magic bytes "{}" or "{}" in footer, got "{}" instead"#,
             std::str::from_utf8(&PARQUET_MAGIC).unwrap(),
+            std::str::from_utf8(&PARQUET_MAGIC_ENCRYPTED_FOOTER).unwrap(),
             String::from_utf8_lossy(remaining)
         ))
         .into());
@@ -121,6 +121,8 @@ fn visualize_plan_rec(
                 FileScan::Ipc { .. } => "ipc-source",
                 FileScan::NDJson { .. } => "ndjson-source",
                 FileScan::Anonymous { .. } => "anonymous-source",
+                #[allow(unreachable_patterns)]
+                _ => "unknown-source",
             };
 
             let mut out = name.to_string();
@@ -182,6 +182,7 @@
 //!     - `dot_diagram` - Create dot diagrams from lazy logical plans.
 //! * `sql` - Pass SQL queries to polars.
 //! * `streaming` - Be able to process datasets that are larger than RAM.
+//!     - `tracing` - Emit [tracing](https://docs.rs/tracing/) spans around the nodes of the streaming engine.
 //! * `random` - Generate arrays with randomly sampled values
 //! * `ndarray`- Convert from [`DataFrame`] to [ndarray](https://docs.rs/ndarray/)
 //! * `temporal` - Conversions between [Chrono](https://docs.rs/chrono/) and Polars for temporal data types
@@ -204,12 +205,27 @@
 //!     - `serde-lazy` - Support for [serde](https://crates.io/crates/serde) serialization and deserialization.
 //!                 Can be used for JSON and more serde supported serialization formats.
 //!     - `parquet` - Read Apache Parquet format
+//!     - `parquet_http` - Read Parquet files from plain HTTP(S) servers with range requests
 //!     - `json` - JSON serialization
 //!     - `ipc` - Arrow's IPC format serialization
+//!     - `orc` - Read Apache ORC format
+//!     - `excel` - Read and write Excel workbooks in the XLSX format
+//!     - `postgres` - Read the results of Postgres queries in parallel partitions
+//!     - `sqlite` - Read and write SQLite databases
+//!     - `flight` - Read from and write to Arrow Flight services
+//!     - `kafka` - Read bounded ranges of Kafka topics
+//!     - `fwf` - Read fixed-width files
+//!     - `dataset` - Read directories of mixed Parquet, CSV and IPC files as a single partitioned dataset
+//!     - `delta` - Read and write Delta Lake tables
+//!     - `iceberg` - Read Apache Iceberg tables
+//!     - `avro` - Read and write Apache Avro files and read Avro messages
+//!     - `avro_schema_registry` - Resolve the schemas of Avro messages from a Confluent schema registry
 //!     - `decompress` - Automatically infer compression of csvs and decompress them.
 //!                      Supported compressions:
 //!                         * zip
 //!                         * gzip
+//!                         * zstd
+//!     - `decompress-bzip2` - Also decompress bzip2 compressed csvs. This builds the bzip2 C library.
 //!
 //! [`StringChunked`]: crate::datatypes::StringChunked
 //! [column selection]: polars_lazy::dsl::col
@@ -0,0 +1,105 @@
+use apache_avro::types::{Record, Value};
+use apache_avro::{to_avro_datum, GenericSingleObjectWriter, Schema as AvroSchema};
+use polars::io::avro::{AvroMessageFormat, AvroMessageReader, AvroSchemaStore};
+use polars::prelude::*;
+
+const SCHEMA: &str = r#"
+{
+    "type": "record",
+    "name": "event",
+    "namespace": "org.pola",
+    "fields": [
+        {"name": "id", "type": "long"},
+        {"name": "name", "type": "string"},
+        {"name": "score", "type": ["null", "double"], "default": null}
+    ]
+}
+"#;
+
+fn values() -> Vec<Value> {
+    let schema = AvroSchema::parse_str(SCHEMA).unwrap();
+    [
+        (1i64, "a", Some(0.5f64)),
+        (2, "b", None),
+        (3, "c", Some(1.5)),
+    ]
+    .into_iter()
+    .map(|(id, name, score)| {
+        let mut record = Record::new(&schema).unwrap();
+        record.put("id", id);
+        record.put("name", name);
+        record.put("score", score);
+        record.into()
+    })
+    .collect()
+}
+
+fn expected() -> DataFrame {
+    df![
+        "id" => [1i64, 2, 3],
+        "name" => ["a", "b", "c"],
+        "score" => [Some(0.5f64), None, Some(1.5)],
+    ]
+    .unwrap()
+}
+
+#[test]
+fn read_single_object_messages() -> PolarsResult<()> {
+    let schema = AvroSchema::parse_str(SCHEMA).unwrap();
+    let mut writer = GenericSingleObjectWriter::new_with_capacity(&schema, 64).unwrap();
+    let messages = values()
+        .into_iter()
+        .map(|value| {
+            let mut message = vec![];
+            writer.write_value(value, &mut message).unwrap();
+            message
+        })
+        .collect::<Vec<_>>();
+
+    let mut store = AvroSchemaStore::new();
+    store.register(SCHEMA)?;
+    let df = AvroMessageReader::new(&mut store, AvroMessageFormat::SingleObject)
+        .read(messages.iter().map(|m| m.as_slice()))?;
+    assert!(df.equals_missing(&expected()));
+
+    let df = AvroMessageReader::new(&mut store, AvroMessageFormat::SingleObject)
+        .with_columns(Some(vec!["score".to_string(), "id".to_string()]))
+        .read(messages.iter().map(|m| m.as_slice()))?;
+    assert_eq!(df.get_column_names(), ["id", "score"]);
+
+    // Messages of a schema that isn't registered can't be read.
+    let mut store = AvroSchemaStore::new();
+    assert!(
+        AvroMessageReader::new(&mut store, AvroMessageFormat::SingleObject)
+            .read(messages.iter().map(|m| m.as_slice()))
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn read_confluent_messages() -> PolarsResult<()> {
+    let schema = AvroSchema::parse_str(SCHEMA).unwrap();
+    let messages = values()
+        .into_iter()
+        .map(|value| {
+            let mut message = vec![0, 0, 0, 0, 42];
+            message.extend(to_avro_datum(&schema, value).unwrap());
+            message
+        })
+        .collect::<Vec<_>>();
+
+    let mut store = AvroSchemaStore::new();
+    store.register_with_id(42, SCHEMA)?;
+    let df = AvroMessageReader::new(&mut store, AvroMessageFormat::Confluent)
+        .read(messages.iter().map(|m| m.as_slice()))?;
+    assert!(df.equals_missing(&expected()));
+
+    // A message without the magic byte is rejected.
+    assert!(
+        AvroMessageReader::new(&mut store, AvroMessageFormat::Confluent)
+            .read([&messages[0][1..]])
+            .is_err()
+    );
+    Ok(())
+}
@@ -1,6 +1,9 @@
 //! Read and write from and to Apache Avro
 
+mod message;
 mod read;
 mod read_async;
+#[cfg(feature = "lazy")]
+mod scan;
 mod write;
 mod write_async;
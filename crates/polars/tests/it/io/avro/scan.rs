@@ -0,0 +1,68 @@
+use std::fs::File;
+
+use polars::io::avro::AvroWriter;
+use polars::io::RowIndex;
+use polars::prelude::*;
+
+#[test]
+fn scan_avro() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_avro");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+
+    let mut df = df![
+        "a" => [1i64, 2, 3],
+        "b" => ["x", "y", "z"],
+        "c" => [1.0f64, 2.0, 3.0],
+    ]?;
+    for name in ["0.avro", "1.avro"] {
+        AvroWriter::new(File::create(dir.join(name))?).finish(&mut df)?;
+    }
+
+    let args = ScanArgsAvro {
+        row_index: Some(RowIndex {
+            name: "idx".into(),
+            offset: 0,
+        }),
+        ..Default::default()
+    };
+    let lf = LazyFrame::scan_avro(dir.join("*.avro"), args)?;
+
+    let q = lf
+        .clone()
+        .filter(col("a").gt(lit(1)))
+        .select([col("idx"), col("b")]);
+    let expected = df![
+        "idx" => [1 as IdxSize, 2, 4, 5],
+        "b" => ["y", "z", "y", "z"],
+    ]?;
+    assert!(q.clone().collect()?.equals(&expected));
+    #[cfg(feature = "streaming")]
+    assert!(q.with_streaming(true).collect()?.equals(&expected));
+
+    let out = lf.clone().limit(4).collect()?;
+    assert_eq!(out.column("idx")?.idx()?.into_no_null_iter().collect::<Vec<_>>(), [0, 1, 2, 3]);
+
+    let out = lf.select([len()]).collect()?;
+    assert_eq!(out.column("len")?.idx()?.get(0), Some(6));
+
+    let args = ScanArgsAvro {
+        include_file_paths: Some("path".into()),
+        ..Default::default()
+    };
+    let q = LazyFrame::scan_avro(dir.join("*.avro"), args)?
+        .filter(col("a").eq(lit(1)))
+        .select([col("path")]);
+    let expected = df![
+        "path" => [
+            dir.join("0.avro").to_str().unwrap(),
+            dir.join("1.avro").to_str().unwrap(),
+        ],
+    ]?;
+    assert!(q.clone().collect()?.equals(&expected));
+    #[cfg(feature = "streaming")]
+    assert!(q.with_streaming(true).collect()?.equals(&expected));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
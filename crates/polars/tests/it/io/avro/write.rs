@@ -9,7 +9,7 @@ use arrow::record_batch::RecordBatchT;
 use avro_schema::schema::{Field as AvroField, Record, Schema as AvroSchema};
 use polars::io::avro::{AvroReader, AvroWriter};
 use polars::io::{SerReader, SerWriter};
-use polars::prelude::df;
+use polars::prelude::{df, DataFrame, DataType, IntoSeries, Series, TimeUnit};
 use polars_error::PolarsResult;
 
 use super::read::read_avro;
@@ -472,3 +472,75 @@ fn test_with_columns() -> PolarsResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_write_multiple_chunks() -> PolarsResult<()> {
+    // The header is written once, before the blocks of all chunks.
+    let mut df = df!("i64" => &[1i64, 2], "string" => &["a", "b"])?;
+    df.vstack_mut(&df!("i64" => &[3i64], "string" => &["c"])?)?;
+    assert_eq!(df.n_chunks(), 2);
+
+    let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    AvroWriter::new(&mut buf).finish(&mut df)?;
+    buf.set_position(0);
+
+    let read_df = AvroReader::new(buf).finish()?;
+    assert!(df.equals(&read_df));
+
+    // A frame without rows still has a header with its schema.
+    let mut empty = df.clear();
+    let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    AvroWriter::new(&mut buf).finish(&mut empty)?;
+    buf.set_position(0);
+
+    let read_df = AvroReader::new(buf).finish()?;
+    assert_eq!(read_df.schema(), empty.schema());
+    assert_eq!(read_df.height(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_write_and_read_timezone() -> PolarsResult<()> {
+    // Avro timestamps are instants in UTC, so they are read in the UTC time zone.
+    let mut df = df!("ms" => &[0i64, 1_000], "us" => &[0i64, 1_000_000])?;
+    let to_utc = |s: &Series, tu: TimeUnit| -> PolarsResult<Series> {
+        s.cast(&DataType::Datetime(tu, Some("UTC".into())))
+    };
+    df = DataFrame::new(vec![
+        to_utc(df.column("ms")?, TimeUnit::Milliseconds)?,
+        to_utc(df.column("us")?, TimeUnit::Microseconds)?,
+    ])?;
+
+    let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    AvroWriter::new(&mut buf).finish(&mut df)?;
+    buf.set_position(0);
+
+    let read_df = AvroReader::new(buf).finish()?;
+    assert_eq!(read_df.schema(), df.schema());
+    assert!(df.equals(&read_df));
+
+    // Other time zones are written as UTC instants.
+    let mut df = DataFrame::new(vec![df.column("ms")?.cast(&DataType::Datetime(
+        TimeUnit::Milliseconds,
+        Some("Europe/Amsterdam".into()),
+    ))?])?;
+    let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    AvroWriter::new(&mut buf).finish(&mut df)?;
+    buf.set_position(0);
+
+    let read_df = AvroReader::new(buf).finish()?;
+    assert_eq!(
+        read_df.column("ms")?.dtype(),
+        &DataType::Datetime(TimeUnit::Milliseconds, Some("UTC".into()))
+    );
+    let physical = |df: &DataFrame| -> PolarsResult<Series> {
+        Ok(df
+            .column("ms")?
+            .datetime()?
+            .physical()
+            .clone()
+            .into_series())
+    };
+    assert!(physical(&read_df)?.equals(&physical(&df)?));
+    Ok(())
+}
@@ -39,6 +39,104 @@ fn write_csv() {
     assert_eq!("0,22.1\r\n1,19.9\r\n2,7.0\r\n3,2.0\r\n4,3.0\r\n", csv);
 }
 
+#[test]
+#[cfg(feature = "decompress")]
+fn write_compressed_csv() -> PolarsResult<()> {
+    let mut df = create_df();
+    let read_options = CsvReadOptions::default().with_schema(Some(Arc::new(df.schema())));
+
+    for compression in [
+        CsvCompression::Gzip(None),
+        CsvCompression::Gzip(Some(9)),
+        CsvCompression::Zstd(None),
+    ] {
+        let mut buf: Vec<u8> = Vec::new();
+        CsvWriter::new(&mut buf)
+            .with_compression(compression)
+            .finish(&mut df)?;
+        assert!(polars::io::utils::is_compressed(&buf));
+
+        let out = read_options
+            .clone()
+            .into_reader_with_file_handle(Cursor::new(buf))
+            .finish()?;
+        assert!(out.equals(&df));
+
+        // The batches are written to a single compressed stream.
+        let mut buf: Vec<u8> = Vec::new();
+        let mut writer = CsvWriter::new(&mut buf)
+            .with_compression(compression)
+            .batched(&df.schema())?;
+        writer.write_batch(&df.slice(0, 2))?;
+        writer.write_batch(&df.slice(2, 3))?;
+        writer.finish()?;
+        drop(writer);
+
+        let out = read_options
+            .clone()
+            .into_reader_with_file_handle(Cursor::new(buf))
+            .finish()?;
+        assert!(out.equals(&df));
+    }
+    Ok(())
+}
+
+/// `a,b\n1,2\n3,4\n` compressed with bzip2.
+#[cfg(all(feature = "decompress", feature = "decompress-bzip2"))]
+const BZIP2_CSV: [u8; 50] = [
+    66, 90, 104, 57, 49, 65, 89, 38, 83, 89, 3, 12, 31, 27, 0, 0, 5, 89, 0, 0, 16, 0, 4, 60, 0, 48,
+    0, 32, 0, 34, 30, 161, 136, 67, 2, 39, 52, 227, 128, 30, 46, 228, 138, 112, 161, 32, 6, 24, 62,
+    54,
+];
+
+#[test]
+#[cfg(all(feature = "decompress", feature = "decompress-bzip2"))]
+fn read_bzip2_compressed_csv() -> PolarsResult<()> {
+    assert!(polars::io::utils::is_compressed(&BZIP2_CSV));
+    // Text starting with the bzip2 magic bytes is not compressed.
+    assert!(!polars::io::utils::is_compressed(b"BZh91,a\n"));
+
+    let df = CsvReadOptions::default()
+        .into_reader_with_file_handle(Cursor::new(BZIP2_CSV.to_vec()))
+        .finish()?;
+    let expected = df!["a" => [1i64, 3], "b" => [2i64, 4]]?;
+    assert!(df.equals(&expected));
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "decompress", feature = "lazy", feature = "streaming"))]
+fn scan_mixed_compressed_csv_streaming() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_mixed_compressed_csv");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+
+    let mut df = df!["a" => [5i64, 6], "b" => [7i64, 8]]?;
+    for (name, compression) in [
+        ("0.csv", CsvCompression::Uncompressed),
+        ("1.csv.gz", CsvCompression::Gzip(None)),
+        ("2.csv.zst", CsvCompression::Zstd(None)),
+    ] {
+        CsvWriter::new(std::fs::File::create(dir.join(name))?)
+            .with_compression(compression)
+            .finish(&mut df)?;
+    }
+    std::fs::write(dir.join("3.csv.bz2"), BZIP2_CSV)?;
+
+    let out = LazyCsvReader::new(dir.join("*").to_str().unwrap())
+        .finish()?
+        .with_streaming(true)
+        .collect()?;
+    let expected = df![
+        "a" => [5i64, 6, 5, 6, 5, 6, 1, 3],
+        "b" => [7i64, 8, 7, 8, 7, 8, 2, 4],
+    ]?;
+    assert!(out.equals(&expected));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "timezones")]
 fn write_dates() {
@@ -1047,6 +1145,62 @@ fn test_null_values_infer_schema() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_null_values_per_column() -> PolarsResult<()> {
+    let csv = r#"country,score
+NA,1
+BE,NA
+NL,-
+NA,4"#;
+    let options = CsvReadOptions::default().map_parse_options(|parse_options| {
+        parse_options.with_null_values(Some(NullValues::NamedMultiple(vec![(
+            "score".into(),
+            vec!["NA".into(), "-".into()],
+        )])))
+    });
+
+    let df = options
+        .clone()
+        .into_reader_with_file_handle(Cursor::new(csv))
+        .finish()?;
+    assert_eq!(df.dtypes(), &[DataType::String, DataType::Int64]);
+    assert_eq!(df.column("country")?.null_count(), 0);
+    assert_eq!(df.column("score")?.null_count(), 2);
+
+    // The batched reader must honor the same null values.
+    let mut reader = options
+        .with_schema(Some(Arc::new(df.schema())))
+        .into_reader_with_file_handle(Cursor::new(csv));
+    let mut batched = reader.batched_borrowed()?;
+    let batches = batched.next_batches(4)?.unwrap();
+    let out = concat_df(&batches)?;
+    assert!(out.equals_missing(&df));
+    Ok(())
+}
+
+#[test]
+fn test_batched_csv_reader_n_rows() -> PolarsResult<()> {
+    let csv = (0..1000).fold(String::from("a,b\n"), |csv, i| {
+        csv + &format!("{i},{}\n", i * 2)
+    });
+    let mut reader = CsvReadOptions::default()
+        .with_n_rows(Some(10))
+        .with_chunk_size(100)
+        .into_reader_with_file_handle(Cursor::new(csv));
+    let mut batched = reader.batched_borrowed()?;
+
+    // Only the requested rows are parsed, not `n` batches of the chunk size.
+    let mut dfs = vec![];
+    while let Some(batches) = batched.next_batches(4)? {
+        assert_eq!(batches.len(), 1);
+        dfs.extend(batches);
+    }
+    let out = concat_df(&dfs)?;
+    assert_eq!(out.height(), 10);
+    assert_eq!(out.column("a")?.i64()?.get(9), Some(9));
+    Ok(())
+}
+
 #[test]
 fn test_comma_separated_field_in_tsv() -> PolarsResult<()> {
     let csv = "first\tsecond\n1\t2.3,2.4\n3\t4.5,4.6\n";
@@ -1059,6 +1213,331 @@ fn test_comma_separated_field_in_tsv() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_multi_byte_separator() -> PolarsResult<()> {
+    for separator in ["||", "~|~"] {
+        let csv = ["a", "b", "c"].join(separator)
+            + "\n"
+            + &["1", "\"x, ~ |\"", "2.5"].join(separator)
+            + "\n"
+            + &["2", "", "3.5"].join(separator)
+            + "\n";
+        let options = CsvReadOptions::default().map_parse_options(|parse_options| {
+            parse_options.with_separator(Separator::try_from(separator).unwrap())
+        });
+
+        let df = options
+            .clone()
+            .into_reader_with_file_handle(Cursor::new(csv.as_str()))
+            .finish()?;
+        let expected = df![
+            "a" => [1i64, 2],
+            "b" => [Some("x, ~ |"), None],
+            "c" => [2.5f64, 3.5],
+        ]?;
+        assert!(df.equals_missing(&expected));
+
+        // The batched reader must split the fields in the same way.
+        let mut reader = options
+            .with_schema(Some(Arc::new(df.schema())))
+            .into_reader_with_file_handle(Cursor::new(csv.as_str()));
+        let mut batched = reader.batched_borrowed()?;
+        let batches = batched.next_batches(4)?.unwrap();
+        let out = concat_df(&batches)?;
+        assert!(out.equals_missing(&expected));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_transcode_encodings() -> PolarsResult<()> {
+    let csv = "name,city\n\"Zoë\",Málaga\nJosé,\"Besançon\"\n";
+    let expected = df![
+        "name" => ["Zoë", "José"],
+        "city" => ["Málaga", "Besançon"],
+    ]?;
+
+    let latin1: Vec<u8> = csv.chars().map(|c| c as u8).collect();
+    let utf16_le: Vec<u8> = csv.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+    let utf16_be: Vec<u8> = csv.encode_utf16().flat_map(|c| c.to_be_bytes()).collect();
+
+    for (encoding, bytes) in [
+        (CsvEncoding::Latin1, latin1.clone()),
+        (CsvEncoding::Windows1252, latin1),
+        (CsvEncoding::Utf16Le, utf16_le),
+        (CsvEncoding::Utf16Be, utf16_be),
+    ] {
+        let options = CsvReadOptions::default()
+            .map_parse_options(|parse_options| parse_options.with_encoding(encoding));
+
+        let df = options
+            .clone()
+            .into_reader_with_file_handle(Cursor::new(bytes.clone()))
+            .finish()?;
+        assert!(df.equals(&expected));
+
+        let mut reader = options
+            .with_schema(Some(Arc::new(df.schema())))
+            .into_reader_with_file_handle(Cursor::new(bytes));
+        let mut batched = reader.batched_borrowed()?;
+        let batches = batched.next_batches(4)?.unwrap();
+        assert!(concat_df(&batches)?.equals(&expected));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_transcode_chunks() -> PolarsResult<()> {
+    // The file is split into lines before the chunks are transcoded.
+    let mut csv = String::from("größe,ort\n");
+    for i in 0..10_000 {
+        csv.push_str(&format!("{i},\"Málaga, {i}\"\n"));
+    }
+    let latin1: Vec<u8> = csv.chars().map(|c| c as u8).collect();
+    let expected = CsvReadOptions::default()
+        .into_reader_with_file_handle(Cursor::new(csv.as_str()))
+        .finish()?;
+    assert_eq!(expected.get_column_names(), &["größe", "ort"]);
+
+    let options = CsvReadOptions::default()
+        .with_n_threads(Some(4))
+        .with_chunk_size(100)
+        .map_parse_options(|parse_options| parse_options.with_encoding(CsvEncoding::Latin1));
+    let df = options
+        .clone()
+        .into_reader_with_file_handle(Cursor::new(latin1.clone()))
+        .finish()?;
+    assert!(df.equals(&expected));
+
+    let df = options
+        .clone()
+        .with_n_rows(Some(9_000))
+        .into_reader_with_file_handle(Cursor::new(latin1.clone()))
+        .finish()?;
+    assert!(df.equals(&expected.head(Some(9_000))));
+
+    let mut reader = options
+        .with_schema(Some(Arc::new(expected.schema())))
+        .into_reader_with_file_handle(Cursor::new(latin1));
+    let mut batched = reader.batched_borrowed()?;
+    let mut batches = vec![];
+    while let Some(b) = batched.next_batches(4)? {
+        batches.extend(b);
+    }
+    assert!(concat_df(&batches)?.equals(&expected));
+    Ok(())
+}
+
+#[test]
+fn test_infer_schema_policy() -> PolarsResult<()> {
+    let csv = "a,b\n1,x\n2,y\n3.5,z\n4,w\n";
+
+    // The float is not in the first two rows, but it is in the uniform sample.
+    let report = SchemaInferenceReport::new();
+    let df = CsvReadOptions::default()
+        .with_infer_schema_length(Some(2))
+        .with_infer_schema_policy(
+            InferSchemaPolicy::default()
+                .with_sampling(InferSchemaSampling::Uniform)
+                .with_report(Some(report.clone())),
+        )
+        .into_reader_with_file_handle(Cursor::new(csv))
+        .finish()?;
+    assert_eq!(df.column("a")?.dtype(), &DataType::Float64);
+
+    let report = report.to_dataframe()?;
+    assert_eq!(report.height(), 1);
+    assert_eq!(report.column("column")?.str()?.get(0), Some("a"));
+    assert_eq!(report.column("dtype")?.str()?.get(0), Some("f64"));
+    assert!(report
+        .column("observed")?
+        .explode()?
+        .equals(&Series::new("observed".into(), ["f64", "i64"])));
+
+    let df = CsvReadOptions::default()
+        .with_infer_schema_length(None)
+        .with_infer_schema_policy(
+            InferSchemaPolicy::default().with_int_float_conflict(IntFloatConflict::String),
+        )
+        .into_reader_with_file_handle(Cursor::new(csv))
+        .finish()?;
+    assert_eq!(df.column("a")?.dtype(), &DataType::String);
+    Ok(())
+}
+
+#[test]
+fn test_malformed_rows() -> PolarsResult<()> {
+    let csv = "a,b\n1,x\n\"2\nx\",y\n3,z,extra\n4,w\n";
+    let schema = Schema::from_iter([
+        Field::new("a".into(), DataType::Int64),
+        Field::new("b".into(), DataType::String),
+    ]);
+    let sink = MalformedRowsSink::new();
+    let options = CsvReadOptions::default()
+        .with_schema(Some(Arc::new(schema)))
+        .with_malformed_rows(Some(sink.clone()));
+
+    let df = options
+        .clone()
+        .into_reader_with_file_handle(Cursor::new(csv))
+        .finish()?;
+    let expected = df![
+        "a" => [Some(1i64), None, Some(3), Some(4)],
+        "b" => ["x", "y", "z", "w"],
+    ]?;
+    assert!(df.equals_missing(&expected));
+
+    let malformed = sink.to_dataframe()?;
+    assert_eq!(
+        malformed.column("line")?.idx()?.to_vec(),
+        [Some(3), Some(5)]
+    );
+    assert_eq!(
+        malformed.column("raw")?.str()?.into_iter().collect::<Vec<_>>(),
+        [Some("\"2\nx\",y"), Some("3,z,extra")]
+    );
+
+    // The batched reader collects the same lines.
+    sink.clear();
+    let mut reader = options.into_reader_with_file_handle(Cursor::new(csv));
+    let mut batched = reader.batched_borrowed()?;
+    while batched.next_batches(1)?.is_some() {}
+    assert!(sink.to_dataframe()?.equals(&malformed));
+    Ok(())
+}
+
+#[test]
+fn test_ragged_lines() -> PolarsResult<()> {
+    let csv = "a,b,c\n1,x,p\n2,y\n3,z,q,extra\n";
+    let schema = Arc::new(Schema::from_iter([
+        Field::new("a".into(), DataType::Int64),
+        Field::new("b".into(), DataType::String),
+        Field::new("c".into(), DataType::String),
+    ]));
+    let read = |ragged_lines: RaggedLines,
+                sink: Option<MalformedRowsSink>,
+                columns: Option<&[&str]>|
+     -> PolarsResult<(DataFrame, DataFrame)> {
+        let options = CsvReadOptions::default()
+            .with_schema(Some(schema.clone()))
+            .with_columns(columns.map(|c| c.iter().map(|s| PlSmallStr::from_str(s)).collect()))
+            .with_malformed_rows(sink)
+            .map_parse_options(|opts| opts.with_ragged_lines(ragged_lines));
+        let eager = options
+            .clone()
+            .into_reader_with_file_handle(Cursor::new(csv))
+            .finish()?;
+        let mut reader = options.into_reader_with_file_handle(Cursor::new(csv));
+        let mut batched = reader.batched_borrowed()?;
+        let mut batches = vec![];
+        while let Some(dfs) = batched.next_batches(1)? {
+            batches.extend(dfs);
+        }
+        Ok((eager, concat_df(&batches)?))
+    };
+
+    let err = read(RaggedLines::Pad, None, None).unwrap_err();
+    assert!(err.to_string().contains("found more fields"));
+
+    let (eager, batched) = read(RaggedLines::Truncate, None, None)?;
+    let expected = df![
+        "a" => [1i64, 2, 3],
+        "b" => ["x", "y", "z"],
+        "c" => [Some("p"), None, Some("q")],
+    ]?;
+    assert!(eager.equals_missing(&expected));
+    assert!(batched.equals_missing(&expected));
+
+    // Extra fields after the last projected column are not parsed.
+    for ragged_lines in [RaggedLines::Pad, RaggedLines::Truncate] {
+        let (eager, batched) = read(ragged_lines, None, Some(&["a"]))?;
+        assert_eq!(
+            eager.column("a")?.i64()?.to_vec(),
+            [Some(1), Some(2), Some(3)]
+        );
+        assert!(batched.equals(&eager));
+    }
+
+    // Missing fields raise as well, also if the missing column is not projected.
+    let truncated = &csv[..csv.rfind("3,").unwrap()];
+    let err = CsvReadOptions::default()
+        .with_schema(Some(schema.clone()))
+        .with_columns(Some(Arc::from([PlSmallStr::from_static("a")])))
+        .map_parse_options(|opts| opts.with_ragged_lines(RaggedLines::Raise))
+        .into_reader_with_file_handle(Cursor::new(truncated))
+        .finish()
+        .unwrap_err();
+    assert!(err.to_string().contains("found fewer fields"));
+
+    let sink = MalformedRowsSink::new();
+    let (eager, batched) = read(RaggedLines::MalformedRows, Some(sink.clone()), None)?;
+    assert!(eager.equals_missing(&expected));
+    assert!(batched.equals_missing(&expected));
+    let malformed = sink.to_dataframe()?;
+    assert_eq!(
+        malformed.column("line")?.idx()?.to_vec(),
+        [Some(3), Some(4), Some(3), Some(4)]
+    );
+    assert_eq!(
+        malformed.column("raw")?.str()?.into_iter().collect::<Vec<_>>(),
+        [
+            Some("2,y"),
+            Some("3,z,q,extra"),
+            Some("2,y"),
+            Some("3,z,q,extra")
+        ]
+    );
+
+    // Collecting the lines requires a sink.
+    assert!(read(RaggedLines::MalformedRows, None, None).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_ragged_lines_projected() -> PolarsResult<()> {
+    let csv = "a,b,c\n1,x,p\n2,y\n3,z,q,extra,more\n4,w,r\n";
+    let schema = Arc::new(Schema::from_iter([
+        Field::new("a".into(), DataType::Int64),
+        Field::new("b".into(), DataType::String),
+        Field::new("c".into(), DataType::String),
+    ]));
+    let read = |ragged_lines: RaggedLines, sink: Option<MalformedRowsSink>| {
+        CsvReadOptions::default()
+            .with_schema(Some(schema.clone()))
+            .with_columns(Some(Arc::from([PlSmallStr::from_static("b")])))
+            .with_malformed_rows(sink)
+            .map_parse_options(|opts| opts.with_ragged_lines(ragged_lines))
+            .into_reader_with_file_handle(Cursor::new(csv))
+            .finish()
+    };
+    let expected = df!["b" => ["x", "y", "z", "w"]]?;
+
+    // The fields after the last projected column are not parsed, so extra fields are ignored.
+    for ragged_lines in [RaggedLines::Pad, RaggedLines::Truncate] {
+        assert!(read(ragged_lines, None)?.equals(&expected));
+    }
+
+    let err = read(RaggedLines::Raise, None).unwrap_err();
+    assert!(err.to_string().contains("found fewer fields"));
+    let err = CsvReadOptions::default()
+        .with_schema(Some(schema.clone()))
+        .with_columns(Some(Arc::from([PlSmallStr::from_static("b")])))
+        .map_parse_options(|opts| opts.with_ragged_lines(RaggedLines::Raise))
+        .into_reader_with_file_handle(Cursor::new(csv.replace("2,y\n", "")))
+        .finish()
+        .unwrap_err();
+    assert!(err.to_string().contains("found more fields"));
+
+    let sink = MalformedRowsSink::new();
+    assert!(read(RaggedLines::MalformedRows, Some(sink.clone()))?.equals(&expected));
+    let malformed = sink.to_dataframe()?;
+    assert_eq!(
+        malformed.column("raw")?.str()?.into_iter().collect::<Vec<_>>(),
+        [Some("2,y"), Some("3,z,q,extra,more")]
+    );
+    Ok(())
+}
+
 #[test]
 fn test_quoted_projection() -> PolarsResult<()> {
     let csv = r#"c1,c2,c3,c4,c5
@@ -1396,3 +1875,33 @@ fn test_read_io_reader() {
     let expected = CsvReader::new(file).finish().unwrap();
     assert!(df.equals(&expected))
 }
+
+#[test]
+fn test_batched_csv_reader_set_chunk_size() -> PolarsResult<()> {
+    let csv = std::iter::once("a,b".to_string())
+        .chain((0..10_000).map(|i| format!("{i},{}", i * 2)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let expected = CsvReadOptions::default()
+        .into_reader_with_file_handle(Cursor::new(csv.clone()))
+        .finish()?;
+
+    let mut reader = CsvReadOptions::default()
+        .with_chunk_size(100)
+        .into_reader_with_file_handle(Cursor::new(csv));
+    let mut batched = reader.batched_borrowed()?;
+    let mut batches = batched.next_batches(2)?.unwrap();
+    let small = batches[0].height();
+
+    // The chunk size changes between batches without losing or repeating rows.
+    batched.set_chunk_size(2000);
+    let larger = batched.next_batches(1)?.unwrap();
+    assert!(larger[0].height() > small);
+    batches.extend(larger);
+    while let Some(next) = batched.next_batches(4)? {
+        batches.extend(next);
+    }
+    let out = concat_df(&batches)?;
+    assert!(out.equals(&expected));
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use polars::io::delta::{write_delta, DeltaWriteMode, DeltaWriteOptions};
+use polars::prelude::*;
+
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+fn z85_encode(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for chunk in bytes.chunks(4) {
+        let mut value = u32::from_be_bytes(chunk.try_into().unwrap()) as u64;
+        let mut digits = [0; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = Z85_ALPHABET[(value % 85) as usize];
+            value /= 85;
+        }
+        out.extend(digits.map(char::from));
+    }
+    out
+}
+
+/// An inline deletion vector of these rows, which must be less than 2^16.
+fn inline_deletion_vector(rows: &[u16]) -> String {
+    let mut bytes = 1681511377u32.to_le_bytes().to_vec();
+    bytes.extend(1u64.to_le_bytes());
+    bytes.extend(0u32.to_le_bytes());
+    bytes.extend(12346u32.to_le_bytes());
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(0u16.to_le_bytes());
+    bytes.extend((rows.len() as u16 - 1).to_le_bytes());
+    bytes.extend([0; 4]);
+    for row in rows {
+        bytes.extend(row.to_le_bytes());
+    }
+    let size = bytes.len();
+    bytes.resize(size.next_multiple_of(4), 0);
+    format!(
+        r#"{{"storageType":"i","pathOrInlineDv":"{}","sizeInBytes":{},"cardinality":{}}}"#,
+        z85_encode(&bytes),
+        size,
+        rows.len()
+    )
+}
+
+#[test]
+fn scan_delta() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_delta");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("_delta_log"))?;
+
+    for (name, values) in [("0.parquet", [1i64, 2, 3]), ("1.parquet", [4, 5, 6])] {
+        let mut df = df!("a" => values)?;
+        let file = std::fs::File::create(dir.join(name))?;
+        ParquetWriter::new(file).finish(&mut df)?;
+    }
+
+    let schema = r#"{\"type\":\"struct\",\"fields\":[{\"name\":\"a\",\"type\":\"long\",\"nullable\":true,\"metadata\":{}},{\"name\":\"p\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}"#;
+    let commits = [
+        format!(
+            r#"{{"protocol":{{"minReaderVersion":1,"minWriterVersion":2}}}}
+{{"metaData":{{"id":"test","format":{{"provider":"parquet","options":{{}}}},"schemaString":"{schema}","partitionColumns":["p"],"configuration":{{}}}}}}
+{{"add":{{"path":"0.parquet","partitionValues":{{"p":"1"}},"size":0,"modificationTime":0,"dataChange":true}}}}
+{{"add":{{"path":"1.parquet","partitionValues":{{"p":"2"}},"size":0,"modificationTime":0,"dataChange":true}}}}
+"#
+        ),
+        // Delete the second row of the first file.
+        format!(
+            r#"{{"remove":{{"path":"0.parquet","dataChange":true}}}}
+{{"add":{{"path":"0.parquet","partitionValues":{{"p":"1"}},"size":0,"modificationTime":0,"dataChange":true,"deletionVector":{}}}}}
+"#,
+            inline_deletion_vector(&[1])
+        ),
+    ];
+    for (version, commit) in commits.iter().enumerate() {
+        let name = format!("{version:020}.json");
+        std::fs::write(dir.join("_delta_log").join(name), commit)?;
+    }
+
+    let df = LazyFrame::scan_delta(&dir, ScanArgsDelta::default())?
+        .sort(["a"], Default::default())
+        .collect()?;
+    let expected = df!(
+        "a" => [1i64, 3, 4, 5, 6],
+        "p" => [1i32, 1, 2, 2, 2],
+    )?;
+    assert!(df.equals(&expected));
+
+    // The first version has no deleted rows.
+    let args = ScanArgsDelta {
+        version: Some(0),
+        ..Default::default()
+    };
+    let df = LazyFrame::scan_delta(&dir, args)?
+        .sort(["a"], Default::default())
+        .collect()?;
+    assert_eq!(df.column("a")?.len(), 6);
+
+    let args = ScanArgsDelta {
+        partition_filter: Some(col("p").eq(lit(2))),
+        ..Default::default()
+    };
+    let df = LazyFrame::scan_delta(&dir, args)?.collect()?;
+    assert_eq!(
+        df.column("p")?
+            .i32()?
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [2, 2, 2]
+    );
+
+    let args = ScanArgsDelta {
+        partition_filter: Some(col("p").gt(lit(2))),
+        ..Default::default()
+    };
+    let df = LazyFrame::scan_delta(&dir, args)?.collect()?;
+    assert_eq!(df.shape(), (0, 2));
+    Ok(())
+}
+
+#[test]
+fn write_delta_modes() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_write_delta");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let df = df!(
+        "a" => [1i64, 2, 3],
+        "p" => ["x", "y", "x"],
+    )?;
+    let options = DeltaWriteOptions::default().with_partition_by(Some(vec!["p".into()]));
+    assert_eq!(write_delta(&df, &dir, &options)?, 0);
+    assert!(dir.join("p=x").is_dir());
+
+    // The columns are matched by name.
+    let df = df!(
+        "p" => [None, Some("y")],
+        "a" => [4i64, 5],
+    )?;
+    assert_eq!(write_delta(&df, &dir, &DeltaWriteOptions::default())?, 1);
+    let df = LazyFrame::scan_delta(&dir, ScanArgsDelta::default())?
+        .sort(["a"], Default::default())
+        .collect()?;
+    let expected = df!(
+        "a" => [1i64, 2, 3, 4, 5],
+        "p" => [Some("x"), Some("y"), Some("x"), None, Some("y")],
+    )?;
+    assert!(df.equals_missing(&expected));
+
+    let df = df!("a" => [1i32], "p" => ["x"])?;
+    assert!(write_delta(&df, &dir, &DeltaWriteOptions::default()).is_err());
+    let df = df!("b" => [1i64])?;
+    let options = DeltaWriteOptions::default().with_mode(DeltaWriteMode::Overwrite);
+    assert!(write_delta(&df, &dir, &options).is_err());
+
+    let options = options.with_overwrite_schema(true);
+    assert_eq!(write_delta(&df, &dir, &options)?, 2);
+    let df = LazyFrame::scan_delta(&dir, ScanArgsDelta::default())?.collect()?;
+    assert!(df.equals(&df!("b" => [1i64])?));
+
+    // Earlier versions can still be read.
+    let args = ScanArgsDelta {
+        version: Some(1),
+        ..Default::default()
+    };
+    let df = LazyFrame::scan_delta(&dir, args)?.collect()?;
+    assert_eq!(df.shape(), (5, 2));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "streaming")]
+fn sink_delta() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_sink_delta");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let df = df!("a" => [1i64, 2, 3])?;
+    for version in 0..2 {
+        let committed = df
+            .clone()
+            .lazy()
+            .with_streaming(true)
+            .sink_delta(&dir, DeltaWriteOptions::default())?;
+        assert_eq!(committed, version);
+    }
+    let df = LazyFrame::scan_delta(&dir, ScanArgsDelta::default())?.collect()?;
+    assert_eq!(df.shape(), (6, 1));
+
+    let options = DeltaWriteOptions::default().with_mode(DeltaWriteMode::Overwrite);
+    df!("a" => [4i64])?
+        .lazy()
+        .with_streaming(true)
+        .sink_delta(&dir, options)?;
+    let df = LazyFrame::scan_delta(&dir, ScanArgsDelta::default())?.collect()?;
+    assert!(df.equals(&df!("a" => [4i64])?));
+    Ok(())
+}
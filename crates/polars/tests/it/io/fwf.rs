@@ -0,0 +1,60 @@
+use polars::io::fwf::FwfReadOptions;
+use polars::io::RowIndex;
+use polars::prelude::*;
+
+#[test]
+fn scan_fwf() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_fwf");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+
+    let file = "\
+a  b    c
+1  x    1.0
+2  y    2.0
+3  z    3.0
+";
+    for name in ["0.txt", "1.txt"] {
+        std::fs::write(dir.join(name), file)?;
+    }
+
+    let args = ScanArgsFwf {
+        options: FwfReadOptions::default(),
+        row_index: Some(RowIndex {
+            name: "idx".into(),
+            offset: 0,
+        }),
+        ..Default::default()
+    };
+    let lf = LazyFrame::scan_fwf(dir.join("*.txt"), args)?;
+    assert_eq!(
+        lf.clone().collect_schema()?.as_ref(),
+        &Schema::from_iter([
+            Field::new("idx".into(), IDX_DTYPE),
+            Field::new("a".into(), DataType::Int64),
+            Field::new("b".into(), DataType::String),
+            Field::new("c".into(), DataType::Float64),
+        ])
+    );
+
+    let q = lf
+        .clone()
+        .filter(col("a").gt(lit(1)))
+        .select([col("idx"), col("b")]);
+    let expected = df![
+        "idx" => [1 as IdxSize, 2, 4, 5],
+        "b" => ["y", "z", "y", "z"],
+    ]?;
+    assert!(q.clone().collect()?.equals(&expected));
+    #[cfg(feature = "streaming")]
+    assert!(q.with_streaming(true).collect()?.equals(&expected));
+
+    let out = lf.clone().limit(4).collect()?;
+    assert_eq!(out.column("idx")?.idx()?.into_no_null_iter().collect::<Vec<_>>(), [0, 1, 2, 3]);
+
+    let out = lf.select([len()]).collect()?;
+    assert_eq!(out.column("len")?.idx()?.get(0), Some(6));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
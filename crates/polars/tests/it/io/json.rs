@@ -185,3 +185,30 @@ fn test_read_ndjson_iss_6148() {
     let df = JsonLineReader::new(cursor).finish();
     assert!(df.is_ok());
 }
+
+#[test]
+#[cfg(all(feature = "dtype-date", feature = "dtype-time"))]
+fn write_ndjson_batched_with_formats() -> PolarsResult<()> {
+    use polars::export::chrono::{NaiveDate, NaiveTime};
+    use polars::io::json::BatchedWriter;
+
+    let mut df = df![
+        "a" => [Some(1), None],
+        "date" => [Some(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()), None],
+        "time" => [NaiveTime::from_hms_opt(12, 30, 0).unwrap(); 2],
+    ]?;
+    df.align_chunks();
+
+    let mut buf = vec![];
+    let mut writer = BatchedWriter::new(&mut buf)
+        .with_date_format(Some("%d/%m/%Y".into()))
+        .with_null_value(Some("NA".into()));
+    writer.write_batch(&df)?;
+
+    assert_eq!(
+        std::str::from_utf8(&buf).unwrap(),
+        "{\"a\":1,\"date\":\"01/03/2024\",\"time\":\"12:30:00\"}\n\
+         {\"a\":\"NA\",\"date\":\"NA\",\"time\":\"12:30:00\"}\n"
+    );
+    Ok(())
+}
@@ -9,11 +9,20 @@ mod parquet;
 #[cfg(feature = "avro")]
 mod avro;
 
+#[cfg(all(feature = "delta", feature = "lazy"))]
+mod delta;
+
+#[cfg(all(feature = "fwf", feature = "lazy"))]
+mod fwf;
+
 #[cfg(feature = "ipc")]
 mod ipc;
 #[cfg(feature = "ipc_streaming")]
 mod ipc_stream;
 
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
 use polars::prelude::*;
 
 pub(crate) fn create_df() -> DataFrame {
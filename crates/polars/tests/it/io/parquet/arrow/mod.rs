@@ -1254,6 +1254,7 @@ fn integration_write(
         compression: CompressionOptions::Uncompressed,
         version: Version::V1,
         data_page_size: None,
+        dictionary_page_size: None,
     };
 
     let encodings = schema
@@ -48,6 +48,7 @@ fn round_trip_opt_stats(
         compression,
         version,
         data_page_size: None,
+        dictionary_page_size: None,
     };
 
     let iter = vec![RecordBatchT::try_new(vec![array.clone()])];
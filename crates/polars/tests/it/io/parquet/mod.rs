@@ -206,3 +206,466 @@ fn test_vstack_empty_3220() -> PolarsResult<()> {
     assert!(stacked.equals(&read_df));
     Ok(())
 }
+
+#[test]
+fn test_column_compression() -> PolarsResult<()> {
+    use polars_parquet::parquet::compression::Compression;
+    use polars_parquet::read::read_metadata;
+
+    let mut df = df! {
+        "a" => [1, 2, 3],
+        "b" => ["x", "y", "z"]
+    }?;
+    let mut buf = Cursor::new(Vec::new());
+    ParquetWriter::new(&mut buf)
+        .with_compression(ParquetCompression::Zstd(None))
+        .with_column_compression(vec![("b".into(), ParquetCompression::Snappy)])
+        .finish(&mut df)?;
+
+    buf.set_position(0);
+    let metadata = read_metadata(&mut buf)?;
+    let compression = |name: &str| {
+        metadata.row_groups[0]
+            .columns_under_root_iter(name)
+            .next()
+            .unwrap()
+            .compression()
+    };
+    assert_eq!(compression("a"), Compression::Zstd);
+    assert_eq!(compression("b"), Compression::Snappy);
+
+    let read_df = ParquetReader::new(buf).finish()?;
+    assert!(df.equals(&read_df));
+    Ok(())
+}
+
+#[test]
+fn test_column_encoding() -> PolarsResult<()> {
+    use polars_parquet::parquet::encoding::Encoding;
+    use polars_parquet::read::read_metadata;
+
+    let mut df = df! {
+        "int" => [1i64, 2, 1, 2],
+        "float" => [1.5f64, 2.5, 1.5, 2.5],
+        "plain" => ["x", "y", "x", "y"],
+        "dict" => ["x", "y", "x", "y"],
+    }?;
+    let write = |df: &mut DataFrame, dictionary_page_size| -> PolarsResult<_> {
+        let mut buf = Cursor::new(Vec::new());
+        ParquetWriter::new(&mut buf)
+            .with_column_encoding(vec![
+                ("int".into(), ParquetEncoding::DeltaBinaryPacked),
+                ("float".into(), ParquetEncoding::ByteStreamSplit),
+                ("plain".into(), ParquetEncoding::Plain),
+            ])
+            .with_dictionary_page_size(dictionary_page_size)
+            .finish(df)?;
+        buf.set_position(0);
+        Ok(buf)
+    };
+    let has_encoding = |buf: &mut Cursor<Vec<u8>>, name: &str, encoding: Encoding| {
+        let metadata = read_metadata(buf).unwrap();
+        let column = metadata.row_groups[0]
+            .columns_under_root_iter(name)
+            .next()
+            .unwrap();
+        column.column_encoding().contains(&encoding.into())
+    };
+
+    let mut buf = write(&mut df, None)?;
+    assert!(has_encoding(&mut buf, "int", Encoding::DeltaBinaryPacked));
+    assert!(has_encoding(&mut buf, "float", Encoding::ByteStreamSplit));
+    assert!(!has_encoding(&mut buf, "plain", Encoding::RleDictionary));
+    assert!(has_encoding(&mut buf, "dict", Encoding::RleDictionary));
+    buf.set_position(0);
+    assert!(df.equals(&ParquetReader::new(buf).finish()?));
+
+    // The dictionary is larger than a single byte.
+    let mut buf = write(&mut df, Some(1))?;
+    assert!(!has_encoding(&mut buf, "dict", Encoding::RleDictionary));
+    buf.set_position(0);
+    assert!(df.equals(&ParquetReader::new(buf).finish()?));
+
+    let result = ParquetWriter::new(Cursor::new(Vec::new()))
+        .with_column_encoding(vec![("plain".into(), ParquetEncoding::ByteStreamSplit)])
+        .finish(&mut df);
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "lazy", feature = "streaming"))]
+fn test_sink_parquet_row_group_size() -> PolarsResult<()> {
+    use polars_parquet::read::read_metadata;
+
+    let dir = std::env::temp_dir().join("polars_test_sink_parquet_row_group_size");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("out.parquet");
+
+    let df = df! {
+        "a" => (0..10).collect::<Vec<i32>>(),
+    }?;
+    // Morsels of 4 rows.
+    let frames = [df.slice(0, 4), df.slice(4, 4), df.slice(8, 2)];
+    let lf = concat(
+        frames.map(|df| df.lazy()),
+        UnionArgs {
+            rechunk: false,
+            ..Default::default()
+        },
+    )?;
+    lf.with_streaming(true).sink_parquet(
+        &path,
+        ParquetWriteOptions {
+            row_group_size: Some(3),
+            ..Default::default()
+        },
+    )?;
+
+    let mut file = std::fs::File::open(&path)?;
+    let metadata = read_metadata(&mut file)?;
+    let row_group_sizes = metadata
+        .row_groups
+        .iter()
+        .map(|rg| rg.num_rows())
+        .collect::<Vec<_>>();
+    assert_eq!(row_group_sizes, [3, 3, 3, 1]);
+
+    let read_df = ParquetReader::new(std::fs::File::open(&path)?).finish()?;
+    assert!(df.equals(&read_df));
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "lazy", feature = "streaming", feature = "cloud_write"))]
+fn test_sink_parquet_cloud_url() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_sink_parquet_cloud_url");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("out.parquet");
+    let uri = format!("file://{}", path.to_str().unwrap());
+
+    let df = df! {
+        "a" => (0..10).collect::<Vec<i32>>(),
+    }?;
+    df.clone()
+        .lazy()
+        .with_streaming(true)
+        .sink_parquet(&uri, Default::default())?;
+
+    let read_df = ParquetReader::new(std::fs::File::open(&path)?).finish()?;
+    assert!(df.equals(&read_df));
+    Ok(())
+}
+
+#[test]
+fn test_write_parquet_partitioned() -> PolarsResult<()> {
+    use polars_parquet::read::read_metadata;
+
+    let dir = std::env::temp_dir().join("polars_test_write_parquet_partitioned");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let mut df = df! {
+        "part" => ["a", "b", "a", "a"],
+        "value" => [1, 2, 3, 4],
+    }?;
+    write_parquet_partitioned(
+        &mut df,
+        &dir,
+        ["part"],
+        &ParquetWriteOptions::default(),
+        &PartitionedWriteOptions {
+            max_rows_per_file: Some(2),
+            write_metadata: true,
+        },
+        1 << 30,
+    )?;
+
+    let read =
+        |path: &str| ParquetReader::new(std::fs::File::open(dir.join(path))?).finish();
+    assert!(read("part=a/00000000.parquet")?.equals(&df! {
+        "part" => ["a", "a"],
+        "value" => [1, 3],
+    }?));
+    assert!(read("part=a/00000001.parquet")?.equals(&df! {
+        "part" => ["a"],
+        "value" => [4],
+    }?));
+    assert!(read("part=b/00000000.parquet")?.equals(&df! {
+        "part" => ["b"],
+        "value" => [2],
+    }?));
+
+    let mut file = std::fs::File::open(dir.join("_metadata"))?;
+    let metadata = read_metadata(&mut file)?;
+    assert_eq!(metadata.num_rows, 4);
+    let row_groups = metadata
+        .row_groups
+        .iter()
+        .map(|rg| {
+            let column = rg.columns_under_root_iter("value").next().unwrap();
+            (column.file_path().clone().unwrap(), rg.num_rows())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        row_groups,
+        [
+            ("part=a/00000000.parquet".to_string(), 2),
+            ("part=a/00000001.parquet".to_string(), 1),
+            ("part=b/00000000.parquet".to_string(), 1),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_write_bloom_filters() -> PolarsResult<()> {
+    use polars_parquet::parquet::bloom_filter;
+    use polars_parquet::read::read_metadata;
+
+    // The ids of every row group span the same range, so the statistics can't skip row groups.
+    let mut df = df! {
+        "id" => [1i64, 7, 3, 8, 2, 6],
+        "name" => ["a", "g", "c", "h", "b", "f"],
+    }?;
+    let mut buf = Cursor::new(Vec::new());
+    ParquetWriter::new(&mut buf)
+        .with_bloom_filter_columns(vec!["id".into(), "name".into()])
+        .with_row_group_size(Some(2))
+        .finish(&mut df)?;
+
+    buf.set_position(0);
+    let metadata = read_metadata(&mut buf)?;
+    assert_eq!(metadata.row_groups.len(), 3);
+    let mut bitset = vec![];
+    for (rg, ids) in metadata.row_groups.iter().zip([[1i64, 7], [3, 8], [2, 6]]) {
+        let column = rg.columns_under_root_iter("id").next().unwrap();
+        bloom_filter::read(column, &mut buf, &mut bitset)?;
+        for id in 1..=8 {
+            let hash = bloom_filter::hash_native(id);
+            // Bloom filters have no false negatives.
+            if ids.contains(&id) {
+                assert!(bloom_filter::is_in_set(&bitset, hash));
+            }
+        }
+    }
+
+    let read_df = ParquetReader::new(buf).finish()?;
+    assert!(df.equals(&read_df));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parquet_encryption")]
+fn test_parquet_encryption() -> PolarsResult<()> {
+    use polars::io::parquet::encryption::*;
+    use polars_parquet::parquet::error::ParquetError;
+
+    type Keys = Vec<(&'static [u8], [u8; 16])>;
+
+    fn key_retriever(keys: Keys) -> Arc<dyn KeyRetriever> {
+        Arc::new(move |key_metadata: &[u8]| {
+            keys.iter()
+                .find(|(metadata, _)| *metadata == key_metadata)
+                .map(|(_, key)| key.to_vec())
+                .ok_or_else(|| ParquetError::InvalidParameter("unknown key".to_string()))
+        })
+    }
+
+    let keys: Keys = vec![(&b"footer"[..], [1; 16]), (&b"name"[..], [2; 16])];
+    let mut df = df! {
+        "id" => [1i64, 2, 3],
+        "name" => ["a", "b", "c"],
+    }?;
+    let encryption = FileEncryptionProperties::new(key_retriever(keys.clone()), b"footer".to_vec())
+        .with_column_key_metadata(vec![("name".to_string(), b"name".to_vec())]);
+    let mut buf = Cursor::new(Vec::new());
+    ParquetWriter::new(&mut buf)
+        .with_encryption(Some(ParquetEncryption::new(encryption)))
+        .finish(&mut df)?;
+    let bytes = buf.into_inner();
+    assert_eq!(&bytes[..4], b"PARE");
+
+    let read = |keys: Keys| {
+        let decryption = FileDecryptionProperties::new(key_retriever(keys));
+        ParquetReader::new(Cursor::new(bytes.clone()))
+            .with_decryption(Some(ParquetDecryption::new(decryption)))
+            .finish()
+    };
+    assert!(df.equals(&read(keys)?));
+    assert!(ParquetReader::new(Cursor::new(bytes.clone()))
+        .finish()
+        .is_err());
+    // The footer can't be authenticated with the wrong key.
+    assert!(read(vec![(&b"footer"[..], [3; 16]), (&b"name"[..], [2; 16])]).is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn test_scan_parquet_bloom_filter_predicate() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_parquet_bloom_filter_predicate");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("ids.parquet");
+
+    let mut df = df! {
+        "id" => [1i64, 7, 3, 8, 2, 6],
+        "name" => ["a", "g", "c", "h", "b", "f"],
+    }?;
+    ParquetWriter::new(std::fs::File::create(&path)?)
+        .with_bloom_filter_columns(vec!["id".into(), "name".into()])
+        .with_row_group_size(Some(2))
+        .finish(&mut df)?;
+
+    let scan = |predicate: Expr| {
+        LazyFrame::scan_parquet(&path, Default::default())?
+            .filter(predicate)
+            .collect()
+    };
+    let out = scan(col("id").eq(lit(3i64)))?;
+    assert!(out.equals(&df! { "id" => [3i64], "name" => ["c"] }?));
+    let out = scan(lit("h").eq(col("name")))?;
+    assert!(out.equals(&df! { "id" => [8i64], "name" => ["h"] }?));
+    let out = scan(col("id").eq(lit(5i64)))?;
+    assert_eq!(out.height(), 0);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn test_scan_parquet_metadata_cache() -> PolarsResult<()> {
+    use polars::io::parquet::metadata_cache::{self, MetadataCacheKey};
+
+    std::env::set_var("POLARS_PARQUET_METADATA_CACHE", "1");
+    let dir = std::env::temp_dir().join("polars_test_scan_parquet_metadata_cache");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("cached.parquet");
+    let write = |mut df: DataFrame| -> PolarsResult<DataFrame> {
+        ParquetWriter::new(std::fs::File::create(&path)?).finish(&mut df)?;
+        Ok(df)
+    };
+    let scan = || LazyFrame::scan_parquet(&path, Default::default())?.collect();
+
+    let df = write(df! { "a" => [1i64, 2, 3] }?)?;
+    assert!(scan()?.equals(&df));
+    let key = MetadataCacheKey::from_local_file(&path).unwrap();
+    let metadata = metadata_cache::get(&key).unwrap();
+    assert!(scan()?.equals(&df));
+    assert!(Arc::ptr_eq(&metadata, &metadata_cache::get(&key).unwrap()));
+
+    // The metadata of an overwritten file is read again.
+    let df = write(df! { "a" => [4i64, 5, 6, 7] }?)?;
+    assert!(scan()?.equals(&df));
+    std::env::remove_var("POLARS_PARQUET_METADATA_CACHE");
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn test_scan_parquet_page_index_predicate() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_parquet_page_index_predicate");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("sorted.parquet");
+
+    let ts = (0..10_000i64).collect::<Vec<_>>();
+    let value = (0..10_000i64).map(|i| i % 7).collect::<Vec<_>>();
+    let mut df = df! { "ts" => ts, "value" => value }?;
+    // Small pages, so that a row group contains many pages.
+    ParquetWriter::new(std::fs::File::create(&path)?)
+        .with_data_page_size(Some(1024))
+        .with_row_group_size(Some(5_000))
+        .finish(&mut df)?;
+
+    for parallel in [
+        ParallelStrategy::None,
+        ParallelStrategy::RowGroups,
+        ParallelStrategy::Prefiltered,
+    ] {
+        let args = ScanArgsParquet {
+            parallel,
+            row_index: Some(polars::io::RowIndex {
+                name: "index".into(),
+                offset: 0,
+            }),
+            ..Default::default()
+        };
+        let predicate = col("ts")
+            .gt_eq(lit(4_990i64))
+            .and(col("ts").lt(lit(5_010i64)));
+        let out = LazyFrame::scan_parquet(&path, args)?
+            .filter(predicate.clone())
+            .collect()?;
+        let expected = df
+            .clone()
+            .lazy()
+            .with_row_index("index", None)
+            .filter(predicate)
+            .collect()?;
+        assert!(out.equals(&expected));
+        assert_eq!(out.height(), 20);
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn test_scan_parquet_sorted_row_groups() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_parquet_sorted_row_groups");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("sorted.parquet");
+
+    for descending in [false, true] {
+        let mut ts = (0..100i64).collect::<Vec<_>>();
+        if descending {
+            ts.reverse();
+        }
+        let mut df = df! { "ts" => ts, "value" => (0..100i64).collect::<Vec<_>>() }?;
+        ParquetWriter::new(std::fs::File::create(&path)?)
+            .with_row_group_size(Some(10))
+            .finish(&mut df)?;
+
+        for predicate in [
+            col("ts").gt_eq(lit(35i64)).and(col("ts").lt(lit(52i64))),
+            col("ts").lt(lit(3i64)).or(col("ts").gt(lit(95i64))),
+            col("ts").gt(lit(100i64)),
+            col("ts").eq(lit(99i64)).and(col("value").gt(lit(-1i64))),
+        ] {
+            for parallel in [ParallelStrategy::None, ParallelStrategy::RowGroups] {
+                let args = ScanArgsParquet {
+                    parallel,
+                    row_index: Some(polars::io::RowIndex {
+                        name: "index".into(),
+                        offset: 0,
+                    }),
+                    ..Default::default()
+                };
+                let out = LazyFrame::scan_parquet(&path, args)?
+                    .filter(predicate.clone())
+                    .collect()?;
+                let expected = df
+                    .clone()
+                    .lazy()
+                    .with_row_index("index", None)
+                    .filter(predicate.clone())
+                    .collect()?;
+                assert!(out.equals(&expected));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bloom_filter_unsupported_dtype() -> PolarsResult<()> {
+    let mut df = df! { "flag" => [true, false] }?;
+    let result = ParquetWriter::new(Cursor::new(Vec::new()))
+        .with_bloom_filter_columns(vec!["flag".into()])
+        .finish(&mut df);
+    assert!(matches!(result, Err(PolarsError::InvalidOperation(_))));
+    Ok(())
+}
@@ -26,6 +26,7 @@ fn round_trip(
         compression,
         version,
         data_page_size: None,
+        dictionary_page_size: None,
     };
 
     let iter = vec![RecordBatchT::try_new(vec![array.clone()])];
@@ -0,0 +1,53 @@
+use polars::io::sqlite::{read_sqlite, write_sqlite, SqliteIfTableExists, SqliteWriteOptions};
+use polars::prelude::*;
+
+fn sqlite_df() -> DataFrame {
+    let dates = Series::new("date".into(), [Some(0i32), None, Some(19_000)])
+        .cast(&DataType::Date)
+        .unwrap();
+    let datetimes = Series::new(
+        "datetime".into(),
+        [Some(1_500_000i64), Some(-86_400_000_000), None],
+    )
+    .cast(&DataType::Datetime(TimeUnit::Microseconds, None))
+    .unwrap();
+    DataFrame::new(vec![
+        Series::new("id".into(), [1i64, 2, 3]),
+        Series::new("value".into(), [Some(1.5f64), None, Some(-2.0)]),
+        Series::new("name".into(), [Some("a"), Some("it's"), None]),
+        Series::new("flag".into(), [true, false, true]),
+        dates,
+        datetimes,
+    ])
+    .unwrap()
+}
+
+#[test]
+fn write_read_sqlite() -> PolarsResult<()> {
+    let path = std::env::temp_dir().join("polars_test_write_read_sqlite.db");
+    let _ = std::fs::remove_file(&path);
+    let df = sqlite_df();
+
+    // Insert multiple batches with a tail batch.
+    let options = SqliteWriteOptions::default().with_batch_size(2);
+    write_sqlite(&df, &path, "test", &options)?;
+    let out = read_sqlite(&path, "SELECT * FROM test")?;
+    assert!(out.equals_missing(&df));
+
+    assert!(write_sqlite(&df, &path, "test", &options).is_err());
+    let options = options.with_if_table_exists(SqliteIfTableExists::Append);
+    write_sqlite(&df, &path, "test", &options)?;
+    let out = read_sqlite(&path, "SELECT id, count(*) AS n FROM test GROUP BY id")?;
+    assert_eq!(
+        out.column("n")?.i64()?.to_vec(),
+        [Some(2), Some(2), Some(2)]
+    );
+
+    let options = options.with_if_table_exists(SqliteIfTableExists::Replace);
+    write_sqlite(&df.head(Some(1)), &path, "test", &options)?;
+    let out = read_sqlite(&path, "SELECT name FROM test")?;
+    assert_eq!(out.shape(), (1, 1));
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
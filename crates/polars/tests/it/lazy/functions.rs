@@ -24,3 +24,34 @@ fn test_format_str() {
 
     assert!(out.equals_missing(&expected));
 }
+
+#[test]
+#[cfg(all(feature = "concat_str", feature = "strings"))]
+fn test_format_str_spec() {
+    let a = df![
+        "a" => ["x", "long"],
+        "b" => [1.5, 12.3456],
+        "c" => [7, 42]
+    ]
+    .unwrap();
+
+    let out = a
+        .lazy()
+        .select([format_str(
+            "{{{:*^6}}} / {:>8.2}|{:03}|{:.2}",
+            [col("a"), col("b"), col("c"), col("a")],
+        )
+        .unwrap()
+        .alias("formatted")])
+        .collect()
+        .unwrap();
+
+    let expected = df![
+        "formatted" => ["{**x***} /     1.50|007|x", "{*long*} /    12.35|042|lo"]
+    ]
+    .unwrap();
+
+    assert!(out.equals_missing(&expected));
+    assert!(format_str("{:x}", [col("a")]).is_err());
+    assert!(format_str("{} }", [col("a")]).is_err());
+}
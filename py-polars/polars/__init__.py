@@ -167,9 +167,11 @@ from polars.io import (
     read_parquet,
     read_parquet_schema,
     scan_csv,
+    scan_database,
     scan_delta,
     scan_iceberg,
     scan_ipc,
+    scan_lance,
     scan_ndjson,
     scan_parquet,
     scan_pyarrow_dataset,
@@ -258,9 +260,11 @@ __all__ = [
     "read_parquet",
     "read_parquet_schema",
     "scan_csv",
+    "scan_database",
     "scan_delta",
     "scan_iceberg",
     "scan_ipc",
+    "scan_lance",
     "scan_ndjson",
     "scan_parquet",
     "scan_pyarrow_dataset",
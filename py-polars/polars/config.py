@@ -2,6 +2,7 @@ from __future__ import annotations
 
 import contextlib
 import os
+import re
 from pathlib import Path
 from typing import TYPE_CHECKING, Any, Literal, get_args
 
@@ -60,7 +61,10 @@ _POLARS_CFG_ENV_VARS = {
     "POLARS_FMT_TABLE_HIDE_DATAFRAME_SHAPE_INFORMATION",
     "POLARS_FMT_TABLE_INLINE_COLUMN_DATA_TYPE",
     "POLARS_FMT_TABLE_ROUNDED_CORNERS",
+    "POLARS_SPILL_COMPRESSION",
+    "POLARS_SPILL_DIR",
     "POLARS_STREAMING_CHUNK_SIZE",
+    "POLARS_STREAMING_MEMORY_LIMIT",
     "POLARS_TABLE_WIDTH",
     "POLARS_VERBOSE",
     "POLARS_MAX_EXPR_DEPTH",
@@ -761,6 +765,90 @@ class Config(contextlib.ContextDecorator):
             os.environ["POLARS_STREAMING_CHUNK_SIZE"] = str(size)
         return cls
 
+    @classmethod
+    def set_streaming_memory_limit(cls, limit: int | str | None) -> type[Config]:
+        """
+        Set the memory budget of a query in the `streaming` engine.
+
+        When the memory taken by a streaming query exceeds the budget, the sources
+        produce smaller chunks and wait for spilled data to be written, and the
+        operators that can go out-of-core spill to disk.
+
+        Parameters
+        ----------
+        limit
+            Number of bytes, or a string with a unit (powers of 1024), e.g. "8GB".
+            Set to `None` to only be limited by the free memory of the system.
+
+        Examples
+        --------
+        >>> with pl.Config(streaming_memory_limit="512MB"):
+        ...     df = pl.LazyFrame({"a": [1, 2, 3]}).collect(streaming=True)
+        """
+        if limit is None:
+            os.environ.pop("POLARS_STREAMING_MEMORY_LIMIT", None)
+        elif isinstance(limit, int):
+            if limit < 1:
+                msg = "streaming memory limit must be >= 1"
+                raise ValueError(msg)
+            os.environ["POLARS_STREAMING_MEMORY_LIMIT"] = str(limit)
+        elif re.fullmatch(
+            r"\s*\d+(\.\d+)?\s*([KMGT](I?B)?|B)?\s*", limit, flags=re.IGNORECASE
+        ):
+            os.environ["POLARS_STREAMING_MEMORY_LIMIT"] = limit
+        else:
+            msg = f"invalid streaming memory limit: {limit!r}"
+            raise ValueError(msg)
+        return cls
+
+    @classmethod
+    def set_streaming_spill_compression(
+        cls, compression: Literal["lz4", "zstd", "uncompressed"] | None
+    ) -> type[Config]:
+        """
+        Set the compression of the files the `streaming` engine spills to disk.
+
+        Compressed files take less disk space and IO, at the cost of some CPU time.
+
+        Parameters
+        ----------
+        compression : str
+            * "lz4": fast compression
+            * "zstd": good compression
+            * "uncompressed": no compression (default)
+
+        Raises
+        ------
+        ValueError: if compression string not recognised.
+        """
+        if compression is None:
+            os.environ.pop("POLARS_SPILL_COMPRESSION", None)
+        elif compression not in {"lz4", "zstd", "uncompressed"}:
+            msg = f"invalid spill compression: {compression!r}"
+            raise ValueError(msg)
+        else:
+            os.environ["POLARS_SPILL_COMPRESSION"] = compression
+        return cls
+
+    @classmethod
+    def set_streaming_spill_dir(cls, path: str | Path | None) -> type[Config]:
+        """
+        Set the directory the `streaming` engine spills to disk in.
+
+        Defaults to the `POLARS_TEMP_DIR` environment variable or else the temporary
+        directory of the system. The directory is fixed by the first query that spills.
+
+        Parameters
+        ----------
+        path
+            Path to a directory on a disk with enough free space for the spilled data.
+        """
+        if path is None:
+            os.environ.pop("POLARS_SPILL_DIR", None)
+        else:
+            os.environ["POLARS_SPILL_DIR"] = str(path)
+        return cls
+
     @classmethod
     def set_tbl_cell_alignment(
         cls, format: Literal["LEFT", "CENTER", "RIGHT"] | None
@@ -156,6 +156,7 @@ if TYPE_CHECKING:
     import gevent
     import great_tables
     import hypothesis
+    import lance
     import numpy
     import pandas
     import pyarrow
@@ -180,6 +181,7 @@ else:
     fsspec, _FSSPEC_AVAILABLE = _lazy_import("fsspec")
     great_tables, _GREAT_TABLES_AVAILABLE = _lazy_import("great_tables")
     hypothesis, _HYPOTHESIS_AVAILABLE = _lazy_import("hypothesis")
+    lance, _LANCE_AVAILABLE = _lazy_import("lance")
     numpy, _NUMPY_AVAILABLE = _lazy_import("numpy")
     pandas, _PANDAS_AVAILABLE = _lazy_import("pandas")
     pyarrow, _PYARROW_AVAILABLE = _lazy_import("pyarrow")
@@ -306,6 +308,7 @@ __all__ = [
     "fsspec",
     "gevent",
     "great_tables",
+    "lance",
     "numpy",
     "pandas",
     "pydantic",
@@ -324,6 +327,7 @@ __all__ = [
     "_FSSPEC_AVAILABLE",
     "_GEVENT_AVAILABLE",
     "_HYPOTHESIS_AVAILABLE",
+    "_LANCE_AVAILABLE",
     "_NUMPY_AVAILABLE",
     "_PANDAS_AVAILABLE",
     "_PYARROW_AVAILABLE",
@@ -0,0 +1,225 @@
+from __future__ import annotations
+
+import contextlib
+import json
+import math
+from typing import TYPE_CHECKING, Any, Iterator
+
+import polars._reexport as pl
+from polars.convert import from_arrow
+from polars.io.database._utils import _open_adbc_connection
+
+if TYPE_CHECKING:
+    from polars import DataFrame, Expr
+    from polars._typing import SchemaDict
+
+_COMPARISON_OPERATORS = {
+    "Eq": "=",
+    "NotEq": "<>",
+    "Lt": "<",
+    "LtEq": "<=",
+    "Gt": ">",
+    "GtEq": ">=",
+}
+
+_INTEGER_LITERALS = {
+    "Int8",
+    "Int16",
+    "Int32",
+    "Int64",
+    "UInt8",
+    "UInt16",
+    "UInt32",
+    "UInt64",
+    "Int",
+}
+
+
+def _quote_identifier(name: str) -> str:
+    return '"' + name.replace('"', '""') + '"'
+
+
+def _literal_to_sql(value: Any) -> str | None:
+    if not isinstance(value, dict) or len(value) != 1:
+        return None
+    ((kind, v),) = value.items()
+    if kind == "Boolean":
+        return "TRUE" if v else "FALSE"
+    elif kind == "String":
+        return "'" + v.replace("'", "''") + "'"
+    elif kind in _INTEGER_LITERALS and isinstance(v, int):
+        return str(v)
+    elif kind in ("Float32", "Float64", "Float") and isinstance(v, (int, float)):
+        return None if math.isnan(v) or math.isinf(v) else repr(float(v))
+    return None
+
+
+def _operand_to_sql(node: Any) -> str | None:
+    if not isinstance(node, dict) or len(node) != 1:
+        return None
+    ((kind, value),) = node.items()
+    if kind == "Column" and isinstance(value, str):
+        return _quote_identifier(value)
+    elif kind == "Literal":
+        return _literal_to_sql(value)
+    return None
+
+
+def _node_to_sql(node: Any) -> str | None:
+    """Translate a (JSON-serialized) predicate to SQL, or return None if unsupported."""
+    if not isinstance(node, dict) or len(node) != 1:
+        return None
+    ((kind, value),) = node.items()
+
+    if kind == "BinaryExpr":
+        op = value["op"]
+        if op in ("And", "LogicalAnd", "Or", "LogicalOr"):
+            lhs, rhs = _node_to_sql(value["left"]), _node_to_sql(value["right"])
+            if lhs is None or rhs is None:
+                return None
+            return f"({lhs} {'AND' if op.endswith('And') else 'OR'} {rhs})"
+        elif op in _COMPARISON_OPERATORS:
+            lhs, rhs = _operand_to_sql(value["left"]), _operand_to_sql(value["right"])
+            if lhs is None or rhs is None:
+                return None
+            return f"({lhs} {_COMPARISON_OPERATORS[op]} {rhs})"
+
+    elif kind == "Function":
+        function, inputs = value.get("function"), value.get("input", [])
+        if not isinstance(function, dict) or len(inputs) != 1:
+            return None
+        boolean_function = function.get("Boolean")
+        if boolean_function == "Not":
+            operand = _node_to_sql(inputs[0])
+            return None if operand is None else f"(NOT {operand})"
+        elif boolean_function in ("IsNull", "IsNotNull"):
+            operand = _operand_to_sql(inputs[0])
+            if operand is None:
+                return None
+            null_check = "IS NULL" if boolean_function == "IsNull" else "IS NOT NULL"
+            return f"({operand} {null_check})"
+
+    return None
+
+
+def _split_conjunction(node: Any) -> list[Any]:
+    if isinstance(node, dict) and "BinaryExpr" in node:
+        value = node["BinaryExpr"]
+        if value["op"] in ("And", "LogicalAnd"):
+            return _split_conjunction(value["left"]) + _split_conjunction(value["right"])
+    return [node]
+
+
+def _predicate_to_sql(predicate: Expr) -> tuple[str | None, bool]:
+    """
+    Translate the conjunctions of a predicate that can be expressed in SQL.
+
+    Returns the SQL condition (if any), and whether the whole predicate is translated.
+    """
+    try:
+        tree = json.loads(predicate.meta.serialize(format="json"))
+    except Exception:
+        return None, False
+
+    conditions: list[str] = []
+    complete = True
+    for node in _split_conjunction(tree):
+        if (sql := _node_to_sql(node)) is not None:
+            conditions.append(sql)
+        else:
+            complete = False
+    return (" AND ".join(conditions) or None), complete
+
+
+def _scan_query(
+    query: str,
+    with_columns: list[str] | None,
+    predicate: Expr | None,
+    n_rows: int | None,
+) -> tuple[str, bool]:
+    """Wrap the query with the projection, predicate and limit that can be pushed down."""
+    condition, complete = (
+        _predicate_to_sql(predicate) if predicate is not None else (None, True)
+    )
+    if with_columns is None and condition is None and n_rows is None:
+        return query, complete
+
+    columns = (
+        ", ".join(_quote_identifier(c) for c in with_columns)
+        if with_columns is not None
+        else "*"
+    )
+    sql = f"SELECT {columns} FROM ({query}) AS _polars_scan"
+    if condition is not None:
+        sql += f" WHERE {condition}"
+    if n_rows is not None and complete:
+        sql += f" LIMIT {n_rows}"
+    return sql, complete
+
+
+@contextlib.contextmanager
+def _adbc_cursor(connection: str | Any) -> Iterator[Any]:
+    if isinstance(connection, str):
+        with _open_adbc_connection(connection) as conn, conn.cursor() as cursor:
+            yield cursor
+    else:
+        with connection.cursor() as cursor:
+            yield cursor
+
+
+def _adbc_schema(
+    query: str,
+    connection: str | Any,
+    execute_options: dict[str, Any] | None,
+) -> Any:
+    """Get the Arrow schema of the result of a query, without fetching its rows."""
+    with _adbc_cursor(connection) as cursor:
+        try:
+            return cursor.adbc_execute_schema(query)
+        except Exception:
+            # not all drivers can determine the schema without executing the query
+            # (and some infer it from the first rows of the result)
+            cursor.execute(query, **(execute_options or {}))
+            return cursor.fetch_record_batch().schema
+
+
+def _scan_adbc_batches(
+    query: str,
+    connection: str | Any,
+    schema_overrides: SchemaDict | None,
+    execute_options: dict[str, Any] | None,
+    with_columns: list[str] | None,
+    predicate: Expr | None,
+    n_rows: int | None,
+    batch_size: int | None,
+) -> Iterator[DataFrame]:
+    # note: the size of the batches is determined by the driver, so `batch_size`
+    # (which is only a hint) is not used
+    sql, complete = _scan_query(query, with_columns, predicate, n_rows)
+    if schema_overrides and with_columns is not None:
+        schema_overrides = {
+            name: dtype
+            for name, dtype in schema_overrides.items()
+            if name in with_columns
+        }
+    with _adbc_cursor(connection) as cursor:
+        cursor.execute(sql, **(execute_options or {}))
+        reader = cursor.fetch_record_batch()
+        n_batches = 0
+        for batch in reader:
+            df = from_arrow(batch, schema_overrides=schema_overrides)
+            assert isinstance(df, pl.DataFrame)
+            if predicate is not None and not complete:
+                df = df.filter(predicate)
+            if n_rows is not None:
+                df = df.head(n_rows)
+                n_rows -= df.height
+            n_batches += 1
+            yield df
+            if n_rows == 0:
+                break
+
+        if n_batches == 0:
+            df = from_arrow(reader.schema.empty_table(), schema_overrides=schema_overrides)
+            assert isinstance(df, pl.DataFrame)
+            yield df
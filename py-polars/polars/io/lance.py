@@ -0,0 +1,99 @@
+from __future__ import annotations
+
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from polars.dependencies import _LANCE_AVAILABLE, lance
+from polars.io.pyarrow_dataset.anonymous_scan import _scan_pyarrow_dataset
+
+if TYPE_CHECKING:
+    from lance import LanceDataset
+
+    from polars import LazyFrame
+
+
+def scan_lance(
+    source: str | Path | LanceDataset,
+    *,
+    version: int | str | None = None,
+    storage_options: dict[str, Any] | None = None,
+    allow_pyarrow_filter: bool = True,
+    batch_size: int | None = None,
+) -> LazyFrame:
+    """
+    Lazily read from a Lance dataset.
+
+    The columns that are selected and the predicates of the query are pushed down
+    to the Lance scanner, so that only the columns and the fragments of the
+    dataset that are needed are read.
+
+    Parameters
+    ----------
+    source
+        Path or URI to the root of the Lance dataset, or a `LanceDataset`.
+
+        Note: For Local filesystem, absolute and relative paths are supported but
+        for the supported object storages - GCS, Azure and S3 full URI must be provided.
+    version
+        Numerical version or tag of the Lance dataset.
+
+        Note: If `version` is not provided, the latest version of the dataset
+        is read. This parameter is ignored if `source` is a `LanceDataset`.
+    storage_options
+        Extra options for the storage backends supported by `lance`.
+        For cloud storages, this may include configurations for authentication etc.
+        This parameter is ignored if `source` is a `LanceDataset`.
+    allow_pyarrow_filter
+        Allow predicates to be pushed down to Lance. This can lead to different
+        results if comparisons are done with null values as Lance handles this
+        different than polars does.
+    batch_size
+        The maximum row count for scanned record batches.
+
+    Returns
+    -------
+    LazyFrame
+
+    Examples
+    --------
+    Creates a scan for a Lance dataset from local filesystem.
+
+    >>> dataset_path = "/path/to/dataset.lance"
+    >>> pl.scan_lance(dataset_path).collect()  # doctest: +SKIP
+
+    Creates a scan for a specific version of the Lance dataset, only reading the
+    rows and the columns that are needed by the query.
+
+    >>> (
+    ...     pl.scan_lance(dataset_path, version=2)
+    ...     .filter(pl.col("label") == "cat")
+    ...     .select("id", "label")
+    ...     .collect()
+    ... )  # doctest: +SKIP
+
+    Post-process the results of a vector search.
+
+    >>> import lance
+    >>> ds = lance.dataset(dataset_path)  # doctest: +SKIP
+    >>> nearest = ds.to_table(
+    ...     nearest={"column": "vector", "q": query_vector, "k": 10}
+    ... )  # doctest: +SKIP
+    >>> pl.from_arrow(nearest).sort("_distance")  # doctest: +SKIP
+    """
+    if isinstance(source, (str, Path)):
+        _check_if_lance_available()
+        source = lance.dataset(
+            str(source), version=version, storage_options=storage_options
+        )
+
+    return _scan_pyarrow_dataset(
+        source,
+        allow_pyarrow_filter=allow_pyarrow_filter,
+        batch_size=batch_size,
+    )
+
+
+def _check_if_lance_available() -> None:
+    if not _LANCE_AVAILABLE:
+        msg = "lance is not installed" "\n\nPlease run: pip install pylance"
+        raise ModuleNotFoundError(msg)
@@ -101,6 +101,7 @@ if TYPE_CHECKING:
         AsofJoinStrategy,
         ClosedInterval,
         ColumnNameOrSelector,
+        CsvCompression,
         CsvQuoteStyle,
         EngineType,
         ExplainFormat,
@@ -2248,6 +2249,8 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         row_group_size: int | None = None,
         data_page_size: int | None = None,
         maintain_order: bool = True,
+        storage_options: dict[str, Any] | None = None,
+        retries: int = 2,
         type_coercion: bool = True,
         predicate_pushdown: bool = True,
         projection_pushdown: bool = True,
@@ -2267,7 +2270,9 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         Parameters
         ----------
         path
-            File path to which the file should be written.
+            File path to which the file should be written. If the path is a cloud URL,
+            e.g. `s3://bucket/file.parquet`, the file is uploaded in parts while it is
+            written.
         compression : {'lz4', 'uncompressed', 'snappy', 'gzip', 'lzo', 'brotli', 'zstd'}
             Choose "zstd" for good compression performance.
             Choose "lz4" for fast compression/decompression.
@@ -2307,6 +2312,20 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         maintain_order
             Maintain the order in which data is processed.
             Setting this to `False` will be slightly faster.
+        storage_options
+            Options that indicate how to connect to a cloud provider.
+
+            The cloud providers currently supported are AWS, GCP, and Azure.
+            See supported keys here:
+
+            * `aws <https://docs.rs/object_store/latest/object_store/aws/enum.AmazonS3ConfigKey.html>`_
+            * `gcp <https://docs.rs/object_store/latest/object_store/gcp/enum.GoogleConfigKey.html>`_
+            * `azure <https://docs.rs/object_store/latest/object_store/azure/enum.AzureConfigKey.html>`_
+
+            If `storage_options` is not provided, Polars will try to infer the
+            information from environment variables.
+        retries
+            Number of retries if accessing a cloud instance fails.
         type_coercion
             Do type coercion optimization.
         predicate_pushdown
@@ -2328,6 +2347,10 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         --------
         >>> lf = pl.scan_csv("/path/to/my_larger_than_ram_file.csv")  # doctest: +SKIP
         >>> lf.sink_parquet("out.parquet")  # doctest: +SKIP
+
+        Stream the result to an object store.
+
+        >>> lf.sink_parquet("s3://bucket/out.parquet")  # doctest: +SKIP
         """
         lf = self._set_sink_optimizations(
             type_coercion=type_coercion,
@@ -2355,6 +2378,12 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
                 "null_count": True,
             }
 
+        if storage_options:
+            storage_options = list(storage_options.items())  # type: ignore[assignment]
+        else:
+            # Handle empty dict input
+            storage_options = None
+
         return lf.sink_parquet(
             path=normalize_filepath(path),
             compression=compression,
@@ -2363,6 +2392,8 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
             row_group_size=row_group_size,
             data_page_size=data_page_size,
             maintain_order=maintain_order,
+            cloud_options=storage_options,
+            retries=retries,
         )
 
     @unstable()
@@ -2454,6 +2485,8 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         null_value: str | None = None,
         quote_style: CsvQuoteStyle | None = None,
         maintain_order: bool = True,
+        compression: CsvCompression = "uncompressed",
+        compression_level: int | None = None,
         type_coercion: bool = True,
         predicate_pushdown: bool = True,
         projection_pushdown: bool = True,
@@ -2528,6 +2561,14 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         maintain_order
             Maintain the order in which data is processed.
             Setting this to `False` will be slightly faster.
+        compression : {'uncompressed', 'gzip', 'zstd'}
+            Compress the written file with this format.
+        compression_level
+            The level of compression to use. Higher compression means smaller files on
+            disk.
+
+            - "gzip" : min-level: 0, max-level: 9.
+            - "zstd" : min-level: 1, max-level: 22.
         type_coercion
             Do type coercion optimization.
         predicate_pushdown
@@ -2549,6 +2590,7 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         --------
         >>> lf = pl.scan_csv("/path/to/my_larger_than_ram_file.csv")  # doctest: +SKIP
         >>> lf.sink_csv("out.csv")  # doctest: +SKIP
+        >>> lf.sink_csv("out.csv.gz", compression="gzip")  # doctest: +SKIP
         """
         from polars.io.csv._utils import _check_arg_is_1byte
 
@@ -2582,6 +2624,8 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
             null_value=null_value,
             quote_style=quote_style,
             maintain_order=maintain_order,
+            compression=compression,
+            compression_level=compression_level,
         )
 
     @unstable()
@@ -2590,6 +2634,10 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         path: str | Path,
         *,
         maintain_order: bool = True,
+        date_format: str | None = None,
+        time_format: str | None = None,
+        datetime_format: str | None = None,
+        null_value: str | None = None,
         type_coercion: bool = True,
         predicate_pushdown: bool = True,
         projection_pushdown: bool = True,
@@ -2613,6 +2661,22 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         maintain_order
             Maintain the order in which data is processed.
             Setting this to `False` will be slightly faster.
+        date_format
+            A format string, with the specifiers defined by the
+            `chrono <https://docs.rs/chrono/latest/chrono/format/strftime/index.html>`_
+            Rust crate. If no format specified, the default is `%Y-%m-%d`.
+        time_format
+            A format string, with the specifiers defined by the
+            `chrono <https://docs.rs/chrono/latest/chrono/format/strftime/index.html>`_
+            Rust crate. If no format specified, the default is `%H:%M:%S%.f`.
+        datetime_format
+            A format string, with the specifiers defined by the
+            `chrono <https://docs.rs/chrono/latest/chrono/format/strftime/index.html>`_
+            Rust crate. If no format specified, the default is
+            `%Y-%m-%d %H:%M:%S%.f`.
+        null_value
+            A string representing null values, which is written instead of `null`.
+            Null values in the fields of nested columns are written as `null`.
         type_coercion
             Do type coercion optimization.
         predicate_pushdown
@@ -2634,6 +2698,12 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         --------
         >>> lf = pl.scan_csv("/path/to/my_larger_than_ram_file.csv")  # doctest: +SKIP
         >>> lf.sink_ndjson("out.ndjson")  # doctest: +SKIP
+
+        Write the dates as `dd/mm/yyyy` and the null values as empty strings.
+
+        >>> lf.sink_ndjson(
+        ...     "out.ndjson", date_format="%d/%m/%Y", null_value=""
+        ... )  # doctest: +SKIP
         """
         lf = self._set_sink_optimizations(
             type_coercion=type_coercion,
@@ -2644,7 +2714,14 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
             no_optimization=no_optimization,
         )
 
-        return lf.sink_json(path=path, maintain_order=maintain_order)
+        return lf.sink_json(
+            path=path,
+            maintain_order=maintain_order,
+            date_format=date_format,
+            time_format=time_format,
+            datetime_format=datetime_format,
+            null_value=null_value,
+        )
 
     def _set_sink_optimizations(
         self,
@@ -72,6 +72,7 @@ def _get_dependency_list() -> list[str]:
         "fsspec",
         "gevent",
         "great_tables",
+        "lance",
         "matplotlib",
         "nest_asyncio",
         "numpy",
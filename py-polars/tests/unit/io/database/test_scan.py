@@ -0,0 +1,66 @@
+from __future__ import annotations
+
+import sys
+from typing import TYPE_CHECKING
+
+import pytest
+
+import polars as pl
+from polars.io.database._scan import _predicate_to_sql, _scan_query
+from polars.testing import assert_frame_equal
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+
+def test_scan_query_pushdown() -> None:
+    query = "SELECT * FROM test_data"
+    assert _scan_query(query, None, None, None) == (query, True)
+
+    sql, complete = _scan_query(
+        query, ["id", "name"], (pl.col("id") > 1) & pl.col("name").is_not_null(), 10
+    )
+    assert sql == (
+        'SELECT "id", "name" FROM (SELECT * FROM test_data) AS _polars_scan'
+        ' WHERE ("id" > 1) AND ("name" IS NOT NULL) LIMIT 10'
+    )
+    assert complete
+
+    # the rows limit cannot be pushed down with a partially translated predicate
+    sql, complete = _scan_query(
+        query, None, (pl.col("name") == "it's") & pl.col("name").str.contains("x"), 1
+    )
+    assert sql == (
+        "SELECT * FROM (SELECT * FROM test_data) AS _polars_scan"
+        " WHERE (\"name\" = 'it''s')"
+    )
+    assert not complete
+
+
+def test_predicate_to_sql() -> None:
+    assert _predicate_to_sql((pl.col("a") <= 1.5) | ~(pl.col("b") != "x")) == (
+        "((\"a\" <= 1.5) OR (NOT (\"b\" <> 'x')))",
+        True,
+    )
+    assert _predicate_to_sql(pl.col("a") + 1 > 2) == (None, False)
+
+
+@pytest.mark.skipif(
+    sys.version_info < (3, 9) or sys.platform == "win32",
+    reason="adbc_driver_sqlite not available on py3.8/windows",
+)
+@pytest.mark.write_disk
+def test_scan_database_adbc(tmp_sqlite_db: Path) -> None:
+    pytest.importorskip("adbc_driver_sqlite")
+    uri = f"sqlite:///{tmp_sqlite_db}"
+    lf = pl.scan_database("SELECT * FROM test_data", uri)
+    assert lf.collect_schema().names() == ["id", "name", "value", "date"]
+
+    expected = pl.DataFrame({"id": [2], "name": ["other"]})
+    q = lf.filter(pl.col("value") < 0).select("id", "name")
+    assert_frame_equal(q.collect(), expected)
+    assert_frame_equal(q.collect(streaming=True), expected)
+
+    q = lf.filter(pl.col("name").str.starts_with("mi")).select("id")
+    assert_frame_equal(q.collect(), pl.DataFrame({"id": [1]}))
+    assert lf.head(1).collect().height == 1
@@ -0,0 +1,56 @@
+from __future__ import annotations
+
+from typing import TYPE_CHECKING
+
+import pytest
+
+import polars as pl
+from polars.testing import assert_frame_equal
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+lance = pytest.importorskip("lance")
+
+
+@pytest.fixture
+def lance_path(tmp_path: Path) -> Path:
+    path = tmp_path / "dataset.lance"
+    df = pl.DataFrame(
+        {
+            "id": [1, 2, 3, 4],
+            "label": ["cat", "dog", "cat", None],
+            "vector": [[0.0, 1.0], [1.0, 0.0], [0.5, 0.5], [1.0, 1.0]],
+        },
+        schema_overrides={"vector": pl.Array(pl.Float32, 2)},
+    )
+    lance.write_dataset(df.head(2).to_arrow(), path)
+    lance.write_dataset(df.tail(2).to_arrow(), path, mode="append")
+    return path
+
+
+@pytest.mark.write_disk
+def test_scan_lance(lance_path: Path) -> None:
+    out = pl.scan_lance(lance_path).collect()
+    assert out.columns == ["id", "label", "vector"]
+    assert out["id"].sort().to_list() == [1, 2, 3, 4]
+
+    out = (
+        pl.scan_lance(lance_path)
+        .filter(pl.col("label") == "cat")
+        .select("id")
+        .sort("id")
+        .collect()
+    )
+    assert_frame_equal(out, pl.DataFrame({"id": [1, 3]}))
+
+    assert pl.scan_lance(lance_path).head(3).collect().height == 3
+
+
+@pytest.mark.write_disk
+def test_scan_lance_version(lance_path: Path) -> None:
+    out = pl.scan_lance(lance_path, version=1).collect()
+    assert out["id"].sort().to_list() == [1, 2]
+
+    ds = lance.dataset(lance_path, version=2)
+    assert pl.scan_lance(ds).collect().height == 4
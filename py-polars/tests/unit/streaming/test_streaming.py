@@ -367,3 +367,82 @@ def test_streaming_with_hconcat(tmp_path: Path) -> None:
     )
 
     assert_frame_equal(result, expected)
+
+
+@pytest.mark.write_disk
+@pytest.mark.parametrize("force_ooc", [False, True])
+def test_streaming_window_aggregations(
+    force_ooc: bool, tmp_path: Path, monkeypatch: Any, capfd: Any
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_VERBOSE", "1")
+    if force_ooc:
+        monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+
+    n = 10_000
+    df = pl.DataFrame(
+        {
+            "key": pl.int_range(n, eager=True).shuffle(seed=0) % 100,
+            "value": pl.int_range(n, eager=True) % 7,
+        }
+    )
+    # null keys form a partition as well
+    lf = df.with_columns(pl.when(pl.col("key") % 11 != 0).then(pl.col("key"))).lazy()
+
+    q = lf.with_columns(
+        pl.col("value").sum().over("key").alias("sum"),
+        (pl.col("value") - pl.col("value").mean().over("key")).alias("diff"),
+        pl.len().over("key").alias("len"),
+    )
+    assert_frame_equal(q.collect(streaming=True), q.collect(streaming=False))
+
+    q = lf.select(pl.col("key"), pl.col("value").max().over("key"))
+    assert_frame_equal(q.collect(streaming=True), q.collect(streaming=False))
+
+    (_, err) = capfd.readouterr()
+    assert "df -> window" in err
+
+
+@pytest.mark.parametrize("period", ["3i", "7i", "20i"])
+@pytest.mark.parametrize("closed", ["left", "right", "both", "none"])
+def test_streaming_temporal_group_by(
+    period: str, closed: Any, monkeypatch: Any, capfd: Any
+) -> None:
+    monkeypatch.setenv("POLARS_VERBOSE", "1")
+    monkeypatch.setenv("POLARS_STREAMING_CHUNK_SIZE", "100")
+
+    n = 10_000
+    lf = pl.LazyFrame(
+        {
+            "t": pl.int_range(n, eager=True) // 3,
+            "value": pl.int_range(n, eager=True) % 7,
+        }
+    )
+
+    for label in ["left", "right", "datapoint"]:
+        q = lf.group_by_dynamic(
+            "t",
+            every="7i",
+            period=period,
+            closed=closed,
+            label=label,  # type: ignore[arg-type]
+            include_boundaries=label == "left",
+        ).agg(pl.col("value").sum(), pl.col("value").first().alias("first"))
+        assert_frame_equal(q.collect(streaming=True), q.collect(streaming=False))
+
+    if closed in ("right", "both"):
+        q = lf.rolling("t", period=period, closed=closed).agg(
+            pl.col("value").sum(), pl.len()
+        )
+        assert_frame_equal(q.collect(streaming=True), q.collect(streaming=False))
+
+    (_, err) = capfd.readouterr()
+    assert "df -> dynamic_group_by" in err
+
+
+def test_streaming_temporal_group_by_unsorted() -> None:
+    lf = pl.LazyFrame({"t": [3, 2, 1] * 1000, "value": range(3000)})
+    q = lf.group_by_dynamic("t", every="2i").agg(pl.col("value").sum())
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="not sorted"):
+        q.collect(streaming=True)
@@ -292,6 +292,47 @@ def test_streaming_group_by_ooc_q3(
     assert_frame_equal(result, expected)
 
 
+@pytest.mark.write_disk
+@pytest.mark.parametrize("dtype", [pl.Int64, pl.String])
+def test_streaming_group_by_ooc_high_cardinality(
+    dtype: pl.DataType,
+    tmp_path: Path,
+    monkeypatch: Any,
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+    # Many small chunks, so that the threads go out-of-core while the keys of the rows
+    # they spill are in the tables of the other threads.
+    monkeypatch.setenv("POLARS_STREAMING_CHUNK_SIZE", "100")
+
+    n = 10_000
+    df = pl.DataFrame(
+        {
+            "key": pl.int_range(n, eager=True).shuffle(seed=0) % 5_000,
+            "value": pl.int_range(n, eager=True) % 7,
+        }
+    ).with_columns(pl.col("key").cast(dtype))
+    file_path = tmp_path / "data.parquet"
+    df.write_parquet(file_path)
+    q = (
+        pl.scan_parquet(file_path)
+        .group_by("key")
+        .agg(pl.sum("value"), pl.len())
+        .sort("key")
+    )
+
+    expected = q.collect(streaming=False)
+    result = q.collect(streaming=True)
+    assert result["key"].is_unique().all()
+    assert_frame_equal(result, expected, check_dtype=False)
+    assert_frame_equal(
+        q.slice(10, 20).collect(streaming=True),
+        expected.slice(10, 20),
+        check_dtype=False,
+    )
+
+
 def test_streaming_group_by_struct_key() -> None:
     df = pl.DataFrame(
         {"A": [1, 2, 3, 2], "B": ["google", "ms", "apple", "ms"], "C": [2, 3, 4, 3]}
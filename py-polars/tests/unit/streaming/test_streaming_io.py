@@ -1,6 +1,7 @@
 from __future__ import annotations
 
 import io
+from datetime import date, datetime, time
 from typing import TYPE_CHECKING, Any
 from unittest.mock import patch
 
@@ -12,6 +13,8 @@ from polars.testing import assert_frame_equal
 if TYPE_CHECKING:
     from pathlib import Path
 
+    from polars._typing import CsvCompression
+
 pytestmark = pytest.mark.xdist_group("streaming")
 
 
@@ -154,6 +157,8 @@ def test_sink_csv_with_options() -> None:
             null_value="BOOM",
             quote_style="always",
             maintain_order=False,
+            compression="zstd",
+            compression_level=3,
         )
 
         ldf.optimization_toggle().sink_csv.assert_called_with(
@@ -172,9 +177,22 @@ def test_sink_csv_with_options() -> None:
             null_value="BOOM",
             quote_style="always",
             maintain_order=False,
+            compression="zstd",
+            compression_level=3,
         )
 
 
+@pytest.mark.write_disk
+@pytest.mark.parametrize("compression", ["gzip", "zstd"])
+def test_sink_csv_compression(compression: CsvCompression, tmp_path: Path) -> None:
+    lf = pl.LazyFrame({"a": [1, 2, 3], "b": ["x", "y", "z"]})
+    target_file = tmp_path / "sink.csv"
+    lf.sink_csv(target_file, compression=compression)
+
+    assert target_file.read_bytes()[:4] != b"a,b\n"
+    assert_frame_equal(pl.read_csv(target_file), lf.collect())
+
+
 @pytest.mark.parametrize(("value"), ["abc", ""])
 def test_sink_csv_exception_for_separator(value: str) -> None:
     df = pl.LazyFrame({"dummy": ["abc"]})
@@ -247,6 +265,33 @@ def test_sink_ndjson_should_write_same_data(
     assert_frame_equal(df, expected)
 
 
+@pytest.mark.write_disk
+def test_sink_ndjson_formats(tmp_path: Path) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    target_path = tmp_path / "formats.ndjson"
+
+    lf = pl.LazyFrame(
+        {
+            "a": [1, None],
+            "date": [date(2024, 3, 1), None],
+            "dt": [datetime(2024, 3, 1, 12, 30), datetime(2024, 3, 2)],
+            "time": [time(12, 30), time(8)],
+        }
+    )
+    lf.sink_ndjson(
+        target_path,
+        date_format="%d/%m/%Y",
+        datetime_format="%Y-%m-%dT%H:%M",
+        time_format="%H:%M",
+        null_value="NA",
+    )
+
+    assert target_path.read_text().splitlines() == [
+        '{"a":1,"date":"01/03/2024","dt":"2024-03-01T12:30","time":"12:30"}',
+        '{"a":"NA","date":"NA","dt":"2024-03-02T00:00","time":"08:00"}',
+    ]
+
+
 @pytest.mark.write_disk
 def test_parquet_eq_statistics(monkeypatch: Any, capfd: Any, tmp_path: Path) -> None:
     tmp_path.mkdir(exist_ok=True)
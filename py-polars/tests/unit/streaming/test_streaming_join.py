@@ -127,6 +127,22 @@ def test_streaming_cross_join_empty() -> None:
     assert out.columns == ["col1", "col1_right"]
 
 
+@pytest.mark.write_disk
+def test_streaming_cross_join_ooc(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+
+    a = pl.LazyFrame({"a": range(300)})
+    b = pl.LazyFrame({"b": range(700)})
+    q = a.join(b, how="cross")
+
+    expected = q.collect().sort("a", "b")
+    assert_frame_equal(q.collect(streaming=True).sort("a", "b"), expected)
+
+
 def test_streaming_join_rechunk_12498() -> None:
     rows = pl.int_range(0, 2)
 
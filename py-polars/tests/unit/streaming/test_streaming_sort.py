@@ -94,16 +94,16 @@ def test_ooc_sort(tmp_path: Path, monkeypatch: Any) -> None:
 
 @pytest.mark.debug
 @pytest.mark.write_disk
-@pytest.mark.parametrize("spill_source", [True, False])
+@pytest.mark.parametrize("many_runs", [True, False])
 def test_streaming_sort(
-    tmp_path: Path, monkeypatch: Any, capfd: Any, spill_source: bool
+    tmp_path: Path, monkeypatch: Any, capfd: Any, many_runs: bool
 ) -> None:
     tmp_path.mkdir(exist_ok=True)
     monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
     monkeypatch.setenv("POLARS_FORCE_OOC", "1")
     monkeypatch.setenv("POLARS_VERBOSE", "1")
-    if spill_source:
-        monkeypatch.setenv("POLARS_SPILL_SORT_PARTITIONS", "1")
+    if many_runs:
+        monkeypatch.setenv("POLARS_OOC_SORT_RUN_SIZE", "1")
     # this creates a lot of duplicate partitions and triggers: #7568
     assert (
         pl.Series(np.random.randint(0, 100, 100))
@@ -115,20 +115,38 @@ def test_streaming_sort(
     )
     (_, err) = capfd.readouterr()
     assert "df -> sort" in err
-    if spill_source:
-        assert "PARTITIONED FORCE SPILLED" in err
+    assert "merging" in err
 
 
 @pytest.mark.write_disk
-@pytest.mark.parametrize("spill_source", [True, False])
+def test_streaming_sort_memory_limit(
+    tmp_path: Path, monkeypatch: Any, capfd: Any
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_VERBOSE", "1")
+
+    df = pl.int_range(1_000_000, eager=True).shuffle(seed=0).to_frame("a")
+    q = df.lazy().sort("a")
+    # the budget is exceeded by the first chunks, so the sort goes out-of-core
+    with pl.Config(streaming_memory_limit="1MB"):
+        result = q.collect(streaming=True)
+    assert_frame_equal(result, q.collect())
+
+    (_, err) = capfd.readouterr()
+    assert "OOC sort started" in err
+
+
+@pytest.mark.write_disk
+@pytest.mark.parametrize("many_runs", [True, False])
 def test_out_of_core_sort_9503(
-    tmp_path: Path, monkeypatch: Any, spill_source: bool
+    tmp_path: Path, monkeypatch: Any, many_runs: bool
 ) -> None:
     tmp_path.mkdir(exist_ok=True)
     monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
     monkeypatch.setenv("POLARS_FORCE_OOC", "1")
-    if spill_source:
-        monkeypatch.setenv("POLARS_SPILL_SORT_PARTITIONS", "1")
+    if many_runs:
+        monkeypatch.setenv("POLARS_OOC_SORT_RUN_SIZE", "1")
     np.random.seed(0)
 
     num_rows = 100_000
@@ -183,6 +201,32 @@ def test_out_of_core_sort_9503(
     }
 
 
+@pytest.mark.write_disk
+@pytest.mark.parametrize("compression", ["lz4", "zstd", "uncompressed"])
+def test_ooc_sort_merge_runs(
+    tmp_path: Path, monkeypatch: Any, compression: str
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+    monkeypatch.setenv("POLARS_OOC_SORT_RUN_SIZE", "1")
+    monkeypatch.setenv("POLARS_SPILL_COMPRESSION", compression)
+    monkeypatch.setenv("POLARS_STREAMING_CHUNK_SIZE", "1000")
+
+    s = pl.Series("a", np.random.randint(0, 1000, 20_000)).extend_constant(None, 100)
+    df = s.shuffle(seed=1).to_frame().with_row_index()
+
+    for descending, nulls_last in [(False, False), (True, True), (True, False)]:
+        sort = {"descending": descending, "nulls_last": nulls_last}
+        expected = df.sort("a", **sort)["a"]
+
+        out = df.lazy().sort("a", **sort).collect(streaming=True)["a"]
+        assert_series_equal(out, expected)
+
+        out = df.lazy().sort("a", **sort).slice(5_000, 100).collect(streaming=True)
+        assert_series_equal(out["a"], expected.slice(5_000, 100))
+
+
 @pytest.mark.write_disk
 @pytest.mark.slow
 def test_streaming_sort_multiple_columns(
@@ -293,3 +337,32 @@ def test_sort_descending_nulls_last(descending: bool, nulls_last: bool) -> None:
         .collect(streaming=True),
         pl.DataFrame({"x": ref_x, "y": ref_y}),
     )
+
+
+@pytest.mark.parametrize("descending", [True, False])
+@pytest.mark.parametrize("nulls_last", [True, False])
+def test_streaming_sort_top_k(
+    descending: bool, nulls_last: bool, monkeypatch: Any, capfd: Any
+) -> None:
+    monkeypatch.setenv("POLARS_VERBOSE", "1")
+    np.random.seed(0)
+    df = pl.DataFrame(
+        {
+            "a": np.random.randint(0, 100, 10_000),
+            "b": np.random.randint(0, 3, 10_000),
+            "c": range(10_000),
+        }
+    ).with_columns(pl.when(pl.col("c") % 7 != 0).then(pl.col("a")))
+
+    sort = {"descending": descending, "nulls_last": nulls_last}
+    for offset, length in [(0, 10), (25, 100), (9_990, 100)]:
+        # ties are not ordered, only compare the sort column
+        q = df.lazy().sort("a", **sort).slice(offset, length)
+        expected = df.sort("a", **sort).slice(offset, length)
+        assert_series_equal(q.collect(streaming=True)["a"], expected["a"])
+
+        q = df.lazy().sort("b", "a", "c", **sort).slice(offset, length)
+        assert_frame_equal(q.collect(streaming=True), q.collect(streaming=False))
+
+    (_, err) = capfd.readouterr()
+    assert "df -> top_k" in err
@@ -51,4 +51,36 @@ def test_streaming_unique(monkeypatch: Any, capfd: Any) -> None:
     q = df.lazy().unique(subset=None, maintain_order=False).sort(["a", "b", "c"])
     assert_frame_equal(q.collect(streaming=True), q.collect(streaming=False))
     (_, err) = capfd.readouterr()
-    assert "df -> re-project-sink -> sort_multiple" in err
+    assert "df -> unique -> sort_multiple" in err
+
+
+@pytest.mark.parametrize("keep", ["first", "last", "any", "none"])
+def test_streaming_unique_keep(keep: Any) -> None:
+    df = pl.DataFrame(
+        {
+            "a": [1, 2, 2, 3, 3, 3, None, None],
+            "b": [1, 2, 3, 4, 5, 6, 7, 8],
+        }
+    )
+    q = df.lazy().unique(subset="a", keep=keep, maintain_order=False)
+    result = q.collect(streaming=True)
+    expected = q.collect(streaming=False)
+    if keep == "any":
+        assert result["a"].sort().to_list() == expected["a"].sort().to_list()
+    else:
+        assert_frame_equal(result.sort("a"), expected.sort("a"))
+
+
+@pytest.mark.write_disk
+@pytest.mark.parametrize("keep", ["first", "last", "none"])
+def test_streaming_unique_ooc(keep: Any, tmp_path: Path, monkeypatch: Any) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+
+    n = 10_000
+    df = pl.DataFrame({"a": pl.int_range(n, eager=True) % 3_000, "b": range(n)})
+    q = df.lazy().unique(subset="a", keep=keep, maintain_order=False)
+    assert_frame_equal(
+        q.collect(streaming=True).sort("a"), q.collect(streaming=False).sort("a")
+    )
@@ -760,6 +760,19 @@ def test_set_streaming_chunk_size() -> None:
         cfg.set_streaming_chunk_size(0)
 
 
+def test_set_streaming_memory_limit() -> None:
+    with pl.Config() as cfg:
+        cfg.set_streaming_memory_limit("8GB")
+        assert os.environ.get("POLARS_STREAMING_MEMORY_LIMIT") == "8GB"
+        cfg.set_streaming_memory_limit(1 << 20)
+        assert os.environ.get("POLARS_STREAMING_MEMORY_LIMIT") == "1048576"
+    assert "POLARS_STREAMING_MEMORY_LIMIT" not in os.environ
+
+    for limit in (0, "8 apples"):
+        with pytest.raises(ValueError), pl.Config() as cfg:
+            cfg.set_streaming_memory_limit(limit)
+
+
 def test_set_fmt_str_lengths_invalid_length() -> None:
     with pl.Config() as cfg:
         with pytest.raises(ValueError):
@@ -841,6 +854,9 @@ def test_warn_unstable(recwarn: pytest.WarningsRecorder) -> None:
             "1",
         ),
         ("POLARS_STREAMING_CHUNK_SIZE", "set_streaming_chunk_size", 100, "100"),
+        ("POLARS_STREAMING_MEMORY_LIMIT", "set_streaming_memory_limit", "1GB", "1GB"),
+        ("POLARS_SPILL_COMPRESSION", "set_streaming_spill_compression", "lz4", "lz4"),
+        ("POLARS_SPILL_DIR", "set_streaming_spill_dir", "/tmp/spill", "/tmp/spill"),
         ("POLARS_TABLE_WIDTH", "set_tbl_width_chars", 80, "80"),
         ("POLARS_VERBOSE", "set_verbose", True, "1"),
         ("POLARS_WARN_UNSTABLE", "warn_unstable", True, "1"),